@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+
+use crate::ids::IDENTITY_REGISTRY_PROGRAM_ID;
+
+/// identity_registry's AgentIdentity account
+/// PDA seeds: ["agent", agent_address]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AgentIdentity {
+    pub agent_address: Pubkey,
+    /// Metaplex Core NFT asset address
+    pub asset_address: Pubkey,
+    pub metadata_uri: String,
+    pub registration_timestamp: i64,
+    pub last_active_timestamp: i64,
+    pub activity_count: u64,
+    pub is_active: bool,
+    /// SOL collateral (lamports), including pending_unstake_amount
+    pub staked_amount: u64,
+    pub stake_unlock_timestamp: i64,
+    pub slash_count: u32,
+    pub total_slashed: u64,
+    pub bump: u8,
+    pub deactivated_at: i64,
+    pub pending_unstake_amount: u64,
+    pub first_staked_at: i64,
+    /// Operator key for the scoped instructions (default if none)
+    pub delegate: Pubkey,
+    pub delegate_scope: u8,
+    pub capabilities: u32,
+    pub service_category: Option<ServiceCategory>,
+    pub metadata_hash: [u8; 32],
+    pub metadata_version: u32,
+    pub name_record: Pubkey,
+    /// 0 = never expires
+    pub expires_at: i64,
+    pub referrer: Pubkey,
+    pub referral_count: u32,
+    pub deactivated_by: Pubkey,
+    pub deactivation_reason_hash: [u8; 32],
+    pub delegated_stake: u64,
+    pub delegated_shares: u64,
+    pub pending_delegated_shares: u64,
+    pub last_heartbeat_at: i64,
+    pub health_status: HealthStatus,
+    pub transferable: bool,
+    /// Layout version; 0 until migrate_account upgrades the account
+    pub version: u8,
+}
+
+/// Self-reported health carried by identity_registry's record_heartbeat
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HealthStatus {
+    #[default]
+    Healthy,
+    Degraded,
+}
+
+/// Primary kind of service an agent offers
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServiceCategory {
+    Inference,
+    DataFeed,
+    CodeGeneration,
+    Trading,
+    Other,
+}
+
+/// AgentIdentity's fields through expires_at, the standing other programs check
+#[derive(AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AgentIdentityPrefix {
+    pub agent_address: Pubkey,
+    pub asset_address: Pubkey,
+    pub metadata_uri: String,
+    pub registration_timestamp: i64,
+    pub last_active_timestamp: i64,
+    pub activity_count: u64,
+    pub is_active: bool,
+    pub staked_amount: u64,
+    pub stake_unlock_timestamp: i64,
+    pub slash_count: u32,
+    pub total_slashed: u64,
+    pub bump: u8,
+    pub deactivated_at: i64,
+    pub pending_unstake_amount: u64,
+    pub first_staked_at: i64,
+    pub delegate: Pubkey,
+    pub delegate_scope: u8,
+    pub capabilities: u32,
+    pub service_category: Option<ServiceCategory>,
+    pub metadata_hash: [u8; 32],
+    pub metadata_version: u32,
+    pub name_record: Pubkey,
+    pub expires_at: i64,
+}
+
+const AGENT_IDENTITY_DISCRIMINATOR: [u8; 8] = [11, 149, 31, 27, 186, 76, 241, 72];
+
+external_account!(AgentIdentity, IDENTITY_REGISTRY_PROGRAM_ID, AGENT_IDENTITY_DISCRIMINATOR);
+external_account!(read_only AgentIdentityPrefix, IDENTITY_REGISTRY_PROGRAM_ID, AGENT_IDENTITY_DISCRIMINATOR);
+
+impl AgentIdentity {
+    /// Same rule as identity_registry's AgentIdentity::is_expired
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    /// Same rule as identity_registry's AgentIdentity::is_current: active and not expired
+    pub fn is_current(&self, now: i64) -> bool {
+        self.is_active && !self.is_expired(now)
+    }
+}
+
+impl AgentIdentityPrefix {
+    /// Same rule as AgentIdentity::is_expired
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    /// Same rule as AgentIdentity::is_current
+    pub fn is_current(&self, now: i64) -> bool {
+        self.is_active && !self.is_expired(now)
+    }
+}
+
+impl From<&AgentIdentity> for AgentIdentityPrefix {
+    fn from(identity: &AgentIdentity) -> Self {
+        AgentIdentityPrefix {
+            agent_address: identity.agent_address,
+            asset_address: identity.asset_address,
+            metadata_uri: identity.metadata_uri.clone(),
+            registration_timestamp: identity.registration_timestamp,
+            last_active_timestamp: identity.last_active_timestamp,
+            activity_count: identity.activity_count,
+            is_active: identity.is_active,
+            staked_amount: identity.staked_amount,
+            stake_unlock_timestamp: identity.stake_unlock_timestamp,
+            slash_count: identity.slash_count,
+            total_slashed: identity.total_slashed,
+            bump: identity.bump,
+            deactivated_at: identity.deactivated_at,
+            pending_unstake_amount: identity.pending_unstake_amount,
+            first_staked_at: identity.first_staked_at,
+            delegate: identity.delegate,
+            delegate_scope: identity.delegate_scope,
+            capabilities: identity.capabilities,
+            service_category: identity.service_category,
+            metadata_hash: identity.metadata_hash,
+            metadata_version: identity.metadata_version,
+            name_record: identity.name_record,
+            expires_at: identity.expires_at,
+        }
+    }
+}
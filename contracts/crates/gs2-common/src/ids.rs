@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+/// identity_registry program; owns AgentIdentity
+pub const IDENTITY_REGISTRY_PROGRAM_ID: Pubkey = pubkey!("2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e");
+
+/// reputation_registry program; owns AgentReputation
+pub const REPUTATION_REGISTRY_PROGRAM_ID: Pubkey = pubkey!("A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp");
+
+/// validation_registry program
+pub const VALIDATION_REGISTRY_PROGRAM_ID: Pubkey = pubkey!("9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc");
+
+/// vote_registry program; its stats PDA signs reputation_registry's stat CPIs
+pub const VOTE_REGISTRY_PROGRAM_ID: Pubkey = pubkey!("EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6");
+
+/// token_staking program
+pub const TOKEN_STAKING_PROGRAM_ID: Pubkey = pubkey!("4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL");
+
+/// agent_profile program; read-only aggregate of an agent's accounts
+pub const AGENT_PROFILE_PROGRAM_ID: Pubkey = pubkey!("Bz1DuYWipM1Y1buubMm5TdSXFn9PQnNPGAyPVMnuf89a");
@@ -0,0 +1,108 @@
+//! Program IDs, PDA seeds and account layouts shared by the GhostSpeak programs
+//! and their off-chain Rust clients.
+//!
+//! The account structs mirror the owning program's own definition field for
+//! field. identity_registry, reputation_registry and token_staking each convert
+//! their account into the mirror with an exhaustive destructure, so a field
+//! added on one side only is a compile error, and round-trip tests in those
+//! programs pin the field order.
+//!
+//! The `*Prefix` structs decode only an account's leading fields. Readers that
+//! need a few fields should prefer them: they skip the rest of the account and
+//! keep working on accounts created before later fields were appended.
+//!
+//! Account versioning: every account type added from now on carries a
+//! `version: u8` as its first field after the discriminator, set to the
+//! owning type's `CURRENT_VERSION` on creation and bumped by that program's
+//! `migrate_account` instruction. AgentIdentity, AgentReputation and
+//! token_staking's StakingVault predate the convention, so their `version`
+//! is appended after their original fields instead: putting it first would
+//! shift the leading fields the `*Prefix` layouts and other programs read.
+
+/// Implement anchor's account traits for a struct owned by another program:
+/// the discriminator is checked on deserialize and `owner()` is that program.
+/// `read_only` skips AccountSerialize, for prefix layouts that must never be
+/// written back over a full account.
+macro_rules! external_account {
+    (read_only $name:ident, $owner:path, $discriminator:expr) => {
+        impl anchor_lang::Discriminator for $name {
+            const DISCRIMINATOR: &'static [u8] = &$discriminator;
+        }
+
+        impl anchor_lang::Owner for $name {
+            fn owner() -> anchor_lang::prelude::Pubkey {
+                $owner
+            }
+        }
+
+        impl anchor_lang::AccountDeserialize for $name {
+            fn try_deserialize(buf: &mut &[u8]) -> anchor_lang::Result<Self> {
+                use anchor_lang::Discriminator;
+                if buf.len() < Self::DISCRIMINATOR.len() {
+                    return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound.into());
+                }
+                if &buf[..Self::DISCRIMINATOR.len()] != Self::DISCRIMINATOR {
+                    return Err(anchor_lang::error!(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch)
+                        .with_account_name(stringify!($name)));
+                }
+                Self::try_deserialize_unchecked(buf)
+            }
+
+            fn try_deserialize_unchecked(buf: &mut &[u8]) -> anchor_lang::Result<Self> {
+                use anchor_lang::Discriminator;
+                let mut data = &buf[Self::DISCRIMINATOR.len()..];
+                anchor_lang::AnchorDeserialize::deserialize(&mut data).map_err(|_| {
+                    anchor_lang::error!(anchor_lang::error::ErrorCode::AccountDidNotDeserialize)
+                        .with_account_name(stringify!($name))
+                })
+            }
+        }
+    };
+    ($name:ident, $owner:path, $discriminator:expr) => {
+        external_account!(read_only $name, $owner, $discriminator);
+
+        impl anchor_lang::AccountSerialize for $name {
+            fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> anchor_lang::Result<()> {
+                use anchor_lang::Discriminator;
+                writer
+                    .write_all(Self::DISCRIMINATOR)
+                    .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotSerialize)?;
+                anchor_lang::AnchorSerialize::serialize(self, writer)
+                    .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotSerialize)?;
+                Ok(())
+            }
+        }
+    };
+}
+
+// Programs on another anchor version name this crate's traits through here
+pub use anchor_lang;
+
+pub mod identity;
+pub mod ids;
+pub mod reputation;
+pub mod seeds;
+pub mod staking;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+
+pub use identity::*;
+pub use ids::*;
+pub use reputation::*;
+pub use seeds::*;
+pub use staking::*;
+
+/// Byte-wise equality that can run in a const assertion
+pub const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::ids::REPUTATION_REGISTRY_PROGRAM_ID;
+
+/// reputation_registry's AgentReputation account
+/// PDA seeds: ["reputation", agent_address]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AgentReputation {
+    pub agent_address: Pubkey,
+    /// Overall reputation score (0-1000)
+    pub overall_score: u16,
+    pub component_scores: ComponentScores,
+    pub stats: ReputationStats,
+    pub payment_proofs_merkle_root: [u8; 32],
+    pub last_updated: i64,
+    pub bump: u8,
+    /// Score before decay
+    pub base_score: u16,
+    pub last_activity: i64,
+    pub decay_enabled: bool,
+    pub decay_rate_bps: u16,
+    pub update_nonce: u64,
+    pub tier: u8,
+    pub is_frozen: bool,
+    pub frozen_at: i64,
+    pub initialized_by: InitializedBy,
+    pub payment_proof_count: u32,
+    /// Layout version; 0 until migrate_account upgrades the account
+    pub version: u8,
+    /// When update_reputation last applied a score
+    pub last_authority_update: i64,
+}
+
+/// Component scores (0-100 each)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ComponentScores {
+    pub trust: u8,
+    pub quality: u8,
+    pub reliability: u8,
+    pub economic: u8,
+    pub social: u8,
+}
+
+/// Vote and review counters
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReputationStats {
+    pub total_votes: u32,
+    pub positive_votes: u32,
+    pub negative_votes: u32,
+    pub total_reviews: u32,
+    /// 0-50 (review rating multiplied by 10)
+    pub avg_review_rating: u8,
+}
+
+/// Who created an AgentReputation account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InitializedBy {
+    #[default]
+    Unknown,
+    Agent,
+    Authority,
+    IdentityRegistry,
+}
+
+/// AgentReputation's fields through overall_score, all vote weighting needs
+#[derive(AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AgentReputationPrefix {
+    pub agent_address: Pubkey,
+    pub overall_score: u16,
+}
+
+const AGENT_REPUTATION_DISCRIMINATOR: [u8; 8] = [245, 56, 239, 246, 36, 231, 227, 67];
+
+external_account!(AgentReputation, REPUTATION_REGISTRY_PROGRAM_ID, AGENT_REPUTATION_DISCRIMINATOR);
+external_account!(read_only AgentReputationPrefix, REPUTATION_REGISTRY_PROGRAM_ID, AGENT_REPUTATION_DISCRIMINATOR);
+
+impl From<&AgentReputation> for AgentReputationPrefix {
+    fn from(reputation: &AgentReputation) -> Self {
+        AgentReputationPrefix {
+            agent_address: reputation.agent_address,
+            overall_score: reputation.overall_score,
+        }
+    }
+}
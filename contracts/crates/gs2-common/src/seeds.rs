@@ -0,0 +1,14 @@
+/// Seed prefix of identity_registry's AgentIdentity PDA: ["agent", agent_address]
+pub const IDENTITY_AGENT_SEED: &[u8] = b"agent";
+
+/// Seed prefix of reputation_registry's AgentReputation PDA: ["reputation", agent_address]
+pub const REPUTATION_SEED: &[u8] = b"reputation";
+
+/// Seed of reputation_registry's MultisigAuthority PDA, which may slash via CPI
+pub const REPUTATION_MULTISIG_SEED: &[u8] = b"multisig_authority";
+
+/// Seed of the vote_registry PDA that signs stat-increment CPIs into reputation_registry
+pub const STATS_CPI_AUTHORITY_SEED: &[u8] = b"reputation_cpi";
+
+/// Seed prefix of token_staking's AgentEndorsementIndex PDA: ["endorsement_index", target_agent]
+pub const ENDORSEMENT_INDEX_SEED: &[u8] = b"endorsement_index";
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::ids::TOKEN_STAKING_PROGRAM_ID;
+
+/// token_staking's AgentEndorsementIndex account, readable without that
+/// program's anchor 0.30 types
+/// PDA seeds: ["endorsement_index", target_agent]
+#[derive(AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AgentEndorsementIndex {
+    pub target_agent: Pubkey,
+    pub vault_count: u32,
+    pub total_trust_weight: u64,
+    pub total_stakers: u32,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+const AGENT_ENDORSEMENT_INDEX_DISCRIMINATOR: [u8; 8] = [45, 65, 120, 187, 142, 198, 203, 211];
+
+external_account!(read_only AgentEndorsementIndex, TOKEN_STAKING_PROGRAM_ID, AGENT_ENDORSEMENT_INDEX_DISCRIMINATOR);
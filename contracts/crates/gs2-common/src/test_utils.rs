@@ -0,0 +1,33 @@
+//! Reproducible value sweeps for the programs' property tests. Only built with
+//! the `test-utils` feature, which the programs enable as a dev-dependency.
+
+/// splitmix64, so the sweeps are random-looking but reproducible
+pub fn next(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// `count` pseudo-random values spread across every magnitude
+pub fn u64_samples(count: usize) -> Vec<u64> {
+    let mut state = 1;
+    (0..count)
+        .map(|_| {
+            let value = next(&mut state);
+            value >> (next(&mut state) % 64)
+        })
+        .collect()
+}
+
+/// `count` pseudo-random values of either sign spread across every magnitude
+pub fn i64_samples(count: usize) -> Vec<i64> {
+    let mut state = 1;
+    (0..count)
+        .map(|_| {
+            let value = next(&mut state) as i64;
+            value >> (next(&mut state) % 64)
+        })
+        .collect()
+}
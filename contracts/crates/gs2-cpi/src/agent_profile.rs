@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+pub use ::agent_profile::cpi::{self, accounts};
+pub use ::agent_profile::program::AgentProfile as AgentProfileProgram;
+pub use ::agent_profile::{
+    id, AgentProfile, EndorsementSummary, EndpointSummary, IdentitySummary, ReputationSummary, StakingSummary,
+    VoteSummary, ID,
+};
+
+/// get_agent_profile; pass the agent's endpoint validations with `CpiContext::with_remaining_accounts`
+pub fn get_agent_profile<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::GetAgentProfile<'info>>,
+) -> Result<AgentProfile> {
+    Ok(cpi::get_agent_profile(ctx)?.get())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+pub use ::identity_registry::cpi::{self, accounts};
+pub use ::identity_registry::instructions::SlashHistoryView;
+pub use ::identity_registry::program::IdentityRegistry;
+pub use ::identity_registry::state::{IdentityStatus, MetadataCheck, ReferralSummary, TrustScoreBreakdown};
+pub use ::identity_registry::{id, ID};
+
+/// verify_identity; `strict` also fails the CPI unless the agent is active, unexpired and staked
+pub fn verify_identity<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::VerifyIdentity<'info>>,
+    strict: bool,
+) -> Result<IdentityStatus> {
+    Ok(cpi::verify_identity(ctx, strict)?.get())
+}
+
+/// verify_metadata: compare `metadata_hash` with the stored one
+pub fn verify_metadata<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::VerifyMetadata<'info>>,
+    metadata_hash: [u8; 32],
+) -> Result<MetadataCheck> {
+    Ok(cpi::verify_metadata(ctx, metadata_hash)?.get())
+}
+
+/// get_referrals
+pub fn get_referrals<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::GetReferrals<'info>>,
+) -> Result<ReferralSummary> {
+    Ok(cpi::get_referrals(ctx)?.get())
+}
+
+/// get_trust_score
+pub fn get_trust_score<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::GetTrustScore<'info>>,
+) -> Result<TrustScoreBreakdown> {
+    Ok(cpi::get_trust_score(ctx)?.get())
+}
+
+/// get_slash_history; pass the slash records with `CpiContext::with_remaining_accounts`
+pub fn get_slash_history<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::GetSlashHistory<'info>>,
+) -> Result<SlashHistoryView> {
+    Ok(cpi::get_slash_history(ctx)?.get())
+}
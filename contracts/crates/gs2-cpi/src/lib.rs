@@ -0,0 +1,34 @@
+//! CPI interface to the GhostSpeak programs.
+//!
+//! Each module re-exports one program's Anchor `cpi` surface: the instruction
+//! builders in `cpi`, their account structs in `cpi::accounts`, the `program`
+//! type for `Program<'info, _>` constraints, its ID and the types its view
+//! instructions return. The helper functions wrap those view instructions and
+//! decode their return data, so callers get the typed result directly:
+//!
+//! ```ignore
+//! let score = gs2_cpi::reputation_registry::get_effective_score(CpiContext::new(
+//!     ctx.accounts.reputation_registry_program.to_account_info(),
+//!     gs2_cpi::reputation_registry::accounts::GetEffectiveScore {
+//!         agent_reputation: ctx.accounts.agent_reputation.to_account_info(),
+//!         decay_config: ctx.accounts.decay_config.to_account_info(),
+//!     },
+//! ))?;
+//! ```
+//!
+//! token_staking is built against anchor 0.30, so its module sits behind the
+//! `token-staking` feature: its contexts and results use that version's types,
+//! and callers on 0.32 invoke it through `token_staking::cpi` by building the
+//! 0.30 context from `anchor_lang_030`, which the feature also re-exports.
+
+pub mod agent_profile;
+pub mod identity_registry;
+pub mod reputation_registry;
+#[cfg(feature = "token-staking")]
+pub mod token_staking;
+pub mod validation_registry;
+pub mod vote_registry;
+
+#[cfg(feature = "token-staking")]
+pub use anchor_lang_030;
+pub use gs2_common;
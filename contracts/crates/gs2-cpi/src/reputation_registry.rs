@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+pub use ::reputation_registry::cpi::{self, accounts};
+pub use ::reputation_registry::program::ReputationRegistry;
+pub use ::reputation_registry::{id, ReputationView, ID};
+
+/// get_reputation
+pub fn get_reputation<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::GetReputation<'info>>,
+) -> Result<ReputationView> {
+    Ok(cpi::get_reputation(ctx)?.get())
+}
+
+/// get_effective_score: overall score with decay applied as of now
+pub fn get_effective_score<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::GetEffectiveScore<'info>>,
+) -> Result<u16> {
+    Ok(cpi::get_effective_score(ctx)?.get())
+}
+
+/// get_tier
+pub fn get_tier<'info>(ctx: CpiContext<'_, '_, '_, 'info, accounts::GetTier<'info>>) -> Result<u8> {
+    Ok(cpi::get_tier(ctx)?.get())
+}
@@ -0,0 +1,21 @@
+//! Built against anchor 0.30: contexts and results here are `anchor_lang_030` types
+
+use anchor_lang_030::prelude::*;
+
+pub use ::token_staking::cpi::{self, accounts};
+pub use ::token_staking::program::TokenStaking;
+pub use ::token_staking::{id, AgentEndorsementIndex, CategoryBreakdown, ID};
+
+/// get_category_breakdown
+pub fn get_category_breakdown<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::GetCategoryBreakdown<'info>>,
+) -> Result<CategoryBreakdown> {
+    Ok(cpi::get_category_breakdown(ctx)?.get())
+}
+
+/// get_agent_endorsement
+pub fn get_agent_endorsement<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::GetAgentEndorsement<'info>>,
+) -> Result<AgentEndorsementIndex> {
+    Ok(cpi::get_agent_endorsement(ctx)?.get())
+}
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+pub use ::validation_registry::cpi::{self, accounts};
+pub use ::validation_registry::program::ValidationRegistry;
+pub use ::validation_registry::{id, ValidationView, ID};
+
+/// query_validations; fails the CPI if the endpoint's consensus is below `min_score`
+pub fn query_validations<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::QueryValidations<'info>>,
+    min_score: u16,
+) -> Result<ValidationView> {
+    Ok(cpi::query_validations(ctx, min_score)?.get())
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+pub use ::vote_registry::cpi::{self, accounts};
+pub use ::vote_registry::program::VoteRegistry;
+pub use ::vote_registry::{id, ContentRatingStats, VoteTally, ID};
+
+/// get_vote_tally
+pub fn get_vote_tally<'info>(ctx: CpiContext<'_, '_, '_, 'info, accounts::GetVoteTally<'info>>) -> Result<VoteTally> {
+    Ok(cpi::get_vote_tally(ctx)?.get())
+}
+
+/// get_content_stats
+pub fn get_content_stats<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::GetContentStats<'info>>,
+) -> Result<ContentRatingStats> {
+    Ok(cpi::get_content_stats(ctx)?.get())
+}
+
+/// get_endorsement_weight; pass the agent's endorsements with `CpiContext::with_remaining_accounts`
+pub fn get_endorsement_weight<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::GetEndorsementWeight<'info>>,
+) -> Result<u64> {
+    Ok(cpi::get_endorsement_weight(ctx)?.get())
+}
@@ -115,7 +115,8 @@ function x402Middleware(config: X402MiddlewareConfig) {
           signature,
           signatureHash,
           amount,
-          config.contentType
+          config.contentType,
+          PublicKey.default
         )
         .accounts({
           receipt: receiptPda,
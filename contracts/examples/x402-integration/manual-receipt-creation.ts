@@ -177,7 +177,8 @@ async function main() {
         mockSignature,
         mockSignatureHash,
         mockAmount,
-        ContentType.Chat
+        ContentType.Chat,
+        PublicKey.default
       )
       .accounts({
         receipt: receiptPda,
@@ -201,7 +202,8 @@ async function main() {
       amount: (receipt.amount as BN).toString(),
       timestamp: new Date(Number(receipt.timestamp) * 1000).toISOString(),
       contentType: Object.keys(receipt.contentType as object)[0],
-      voteCast: receipt.voteCast,
+      payerVoted: receipt.payerVoted,
+      recipientVoted: receipt.recipientVoted,
     })
   } catch (error) {
     console.error('Error:', (error as Error).message)
@@ -112,7 +112,8 @@ async function verifyAndCreateReceipt(
       signature,
       signatureHash,
       amount,
-      ContentType.Chat
+      ContentType.Chat,
+      PublicKey.default
     )
     .accounts({
       receipt: receiptPda,
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ProfileError {
+    #[msg("Account layout version is no longer supported; call the owning program's migrate_account first")]
+    UnsupportedAccountVersion,
+
+    #[msg("Endorsement index is not token_staking's index for this agent")]
+    InvalidEndorsementIndex,
+
+    #[msg("Endpoint account is not a validation_registry endpoint of this agent")]
+    InvalidEndpointAccount,
+}
@@ -0,0 +1,200 @@
+use anchor_lang::prelude::*;
+use gs2_common::{AgentEndorsementIndex, ENDORSEMENT_INDEX_SEED, TOKEN_STAKING_PROGRAM_ID};
+use identity_registry::state::AgentIdentity;
+use reputation_registry::{AgentReputation, DecayConfig};
+use std::collections::BTreeSet;
+use validation_registry::{EndpointValidation, ProviderEndpointIndex};
+use vote_registry::{EndorsementStats, VoteTally};
+
+use crate::error::ProfileError;
+use crate::state::*;
+
+/// Every account but the agent and decay config is optional; pass the
+/// agent's EndpointValidation PDAs as remaining accounts to count its stamps
+#[derive(Accounts)]
+pub struct GetAgentProfile<'info> {
+    /// CHECK: The agent's wallet address
+    pub agent: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [AgentIdentity::SEED_PREFIX, agent.key().as_ref()],
+        bump = agent_identity.bump,
+        seeds::program = identity_registry::ID,
+        constraint = agent_identity.is_supported_version() @ ProfileError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Option<Account<'info, AgentIdentity>>,
+
+    #[account(
+        seeds = [AgentReputation::SEED_PREFIX, agent.key().as_ref()],
+        bump = agent_reputation.bump,
+        seeds::program = reputation_registry::ID,
+        constraint = agent_reputation.is_supported_version() @ ProfileError::UnsupportedAccountVersion
+    )]
+    pub agent_reputation: Option<Account<'info, AgentReputation>>,
+
+    /// CHECK: reputation_registry's decay config PDA; defaults apply while it is uninitialized
+    #[account(seeds = [DecayConfig::SEED_PREFIX], bump, seeds::program = reputation_registry::ID)]
+    pub decay_config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [VoteTally::SEED_PREFIX, agent.key().as_ref()],
+        bump = vote_tally.bump,
+        seeds::program = vote_registry::ID
+    )]
+    pub vote_tally: Option<Account<'info, VoteTally>>,
+
+    #[account(
+        seeds = [EndorsementStats::SEED_PREFIX, agent.key().as_ref()],
+        bump = endorsement_stats.bump,
+        seeds::program = vote_registry::ID
+    )]
+    pub endorsement_stats: Option<Account<'info, EndorsementStats>>,
+
+    /// CHECK: token_staking's AgentEndorsementIndex, read through gs2-common's
+    /// layout; owner and discriminator are checked in the handler
+    #[account(
+        seeds = [ENDORSEMENT_INDEX_SEED, agent.key().as_ref()],
+        bump,
+        seeds::program = TOKEN_STAKING_PROGRAM_ID
+    )]
+    pub endorsement_index: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [ProviderEndpointIndex::SEED_PREFIX, agent.key().as_ref()],
+        bump = endpoint_index.bump,
+        seeds::program = validation_registry::ID
+    )]
+    pub endpoint_index: Option<Account<'info, ProviderEndpointIndex>>,
+}
+
+/// Read every account passed in into one AgentProfile. Accounts are checked
+/// for owner, discriminator and seeds before they are read, so a spoofed
+/// account fails the instruction rather than being reported.
+pub fn handler(ctx: Context<GetAgentProfile>) -> Result<AgentProfile> {
+    let now = Clock::get()?.unix_timestamp;
+    let agent = ctx.accounts.agent.key();
+
+    let identity = ctx.accounts.agent_identity.as_ref().map(|identity| IdentitySummary {
+        is_active: identity.is_active,
+        is_expired: identity.is_expired(now),
+        staked_amount: identity.staked_amount,
+        delegated_stake: identity.delegated_stake,
+        slash_count: identity.slash_count,
+        capabilities: identity.capabilities,
+        registration_timestamp: identity.registration_timestamp,
+        last_active_timestamp: identity.last_active_timestamp,
+    });
+
+    let reputation = match &ctx.accounts.agent_reputation {
+        Some(reputation) => {
+            let params = DecayConfig::resolve(&ctx.accounts.decay_config)?;
+            Some(ReputationSummary {
+                overall_score: reputation.overall_score,
+                effective_score: reputation.get_effective_score(now, &params),
+                tier: reputation.tier,
+                is_frozen: reputation.is_frozen,
+            })
+        }
+        None => None,
+    };
+
+    let votes = ctx.accounts.vote_tally.as_ref().map(|tally| VoteSummary {
+        upvotes: tally.upvotes,
+        downvotes: tally.downvotes,
+        neutrals: tally.neutrals,
+        weight_sum: tally.weight_sum,
+    });
+
+    let endorsements = ctx.accounts.endorsement_stats.as_ref().map(|stats| EndorsementSummary {
+        received: stats.endorsements_received,
+        given: stats.endorsements_given,
+    });
+
+    let staking = ctx
+        .accounts
+        .endorsement_index
+        .as_ref()
+        .map(|index| load_staking_summary(index, &agent))
+        .transpose()?;
+
+    let endpoints = ctx.accounts.endpoint_index.as_ref().map(|index| EndpointSummary {
+        endpoint_count: index.endpoint_hashes.len() as u32,
+        stamps_issued: index.stamps_issued,
+    });
+
+    let stamped_endpoint_count = count_stamped_endpoints(ctx.remaining_accounts, &agent, now)?;
+
+    msg!(
+        "Profile for agent {}: {} stamped endpoints",
+        agent,
+        stamped_endpoint_count
+    );
+
+    Ok(AgentProfile {
+        agent,
+        identity,
+        reputation,
+        votes,
+        endorsements,
+        staking,
+        endpoints,
+        stamped_endpoint_count,
+    })
+}
+
+/// Decode token_staking's endorsement index through gs2-common's mirror of it
+fn load_staking_summary(info: &AccountInfo, agent: &Pubkey) -> Result<StakingSummary> {
+    require_keys_eq!(
+        *info.owner,
+        TOKEN_STAKING_PROGRAM_ID,
+        ProfileError::InvalidEndorsementIndex
+    );
+
+    let data = info.try_borrow_data()?;
+    let index = AgentEndorsementIndex::try_deserialize(&mut &data[..])
+        .map_err(|_| error!(ProfileError::InvalidEndorsementIndex))?;
+    require!(
+        index.target_agent == *agent,
+        ProfileError::InvalidEndorsementIndex
+    );
+
+    Ok(StakingSummary {
+        vault_count: index.vault_count,
+        total_stakers: index.total_stakers,
+        total_trust_weight: index.total_trust_weight,
+    })
+}
+
+/// Count the endpoints in `accounts` holding a valid stamp at `now`; each must
+/// be a distinct EndpointValidation PDA whose provider is the agent
+fn count_stamped_endpoints(accounts: &[AccountInfo], agent: &Pubkey, now: i64) -> Result<u32> {
+    let mut seen = BTreeSet::new();
+    let mut stamped: u32 = 0;
+    for info in accounts {
+        require!(
+            info.owner == &validation_registry::ID && seen.insert(info.key()),
+            ProfileError::InvalidEndpointAccount
+        );
+
+        let data = info.try_borrow_data()?;
+        let validation = EndpointValidation::try_deserialize(&mut &data[..])?;
+        let expected = Pubkey::create_program_address(
+            &[
+                EndpointValidation::SEED_PREFIX,
+                validation.endpoint_hash.as_ref(),
+                &[validation.bump],
+            ],
+            &validation_registry::ID,
+        )
+        .map_err(|_| error!(ProfileError::InvalidEndpointAccount))?;
+        require!(
+            validation.provider_agent == *agent && info.key() == expected,
+            ProfileError::InvalidEndpointAccount
+        );
+
+        if validation.is_stamp_valid(now) {
+            stamped = stamped.saturating_add(1);
+        }
+    }
+    Ok(stamped)
+}
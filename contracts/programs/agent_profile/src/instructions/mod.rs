@@ -0,0 +1,3 @@
+pub mod get_agent_profile;
+
+pub use get_agent_profile::*;
@@ -0,0 +1,22 @@
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+use anchor_lang::prelude::*;
+
+pub use error::*;
+pub use instructions::*;
+pub use state::*;
+
+declare_id!("Bz1DuYWipM1Y1buubMm5TdSXFn9PQnNPGAyPVMnuf89a");
+
+#[program]
+pub mod agent_profile {
+    use super::*;
+
+    /// Assemble an agent's identity, reputation, votes, endorsements, stake and
+    /// stamps into one AgentProfile (view function, returned via return data)
+    pub fn get_agent_profile(ctx: Context<GetAgentProfile>) -> Result<AgentProfile> {
+        instructions::get_agent_profile::handler(ctx)
+    }
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+/// Result of get_agent_profile. Each section is None when its account was
+/// not passed in.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AgentProfile {
+    pub agent: Pubkey,
+    /// From identity_registry's AgentIdentity
+    pub identity: Option<IdentitySummary>,
+    /// From reputation_registry's AgentReputation
+    pub reputation: Option<ReputationSummary>,
+    /// From vote_registry's VoteTally
+    pub votes: Option<VoteSummary>,
+    /// From vote_registry's EndorsementStats
+    pub endorsements: Option<EndorsementSummary>,
+    /// From token_staking's AgentEndorsementIndex
+    pub staking: Option<StakingSummary>,
+    /// From validation_registry's ProviderEndpointIndex
+    pub endpoints: Option<EndpointSummary>,
+    /// Endpoint validations passed as remaining accounts that hold a valid stamp
+    pub stamped_endpoint_count: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IdentitySummary {
+    pub is_active: bool,
+    /// Past a non-zero expires_at without renewal
+    pub is_expired: bool,
+    /// Collateral staked by the agent itself (lamports)
+    pub staked_amount: u64,
+    /// Collateral backers have staked for the agent (lamports)
+    pub delegated_stake: u64,
+    pub slash_count: u32,
+    /// CAPABILITY_* bitmask
+    pub capabilities: u32,
+    pub registration_timestamp: i64,
+    pub last_active_timestamp: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReputationSummary {
+    pub overall_score: u16,
+    /// Overall score with decay applied as of now
+    pub effective_score: u16,
+    pub tier: u8,
+    pub is_frozen: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VoteSummary {
+    pub upvotes: u32,
+    pub downvotes: u32,
+    pub neutrals: u32,
+    /// Sum of vote weights (100 = 1.0x)
+    pub weight_sum: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EndorsementSummary {
+    /// Active endorsements the agent has received
+    pub received: u32,
+    /// Active endorsements the agent has given
+    pub given: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StakingSummary {
+    /// token_staking vaults endorsing the agent
+    pub vault_count: u32,
+    pub total_stakers: u32,
+    pub total_trust_weight: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EndpointSummary {
+    /// Endpoints submitted for validation
+    pub endpoint_count: u32,
+    /// Stamps ever issued across those endpoints
+    pub stamps_issued: u32,
+}
@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use crate::state::ServiceCategory;
+
+/// Emitted for every identity created by register_agent or register_agents_batch
+#[event]
+pub struct AgentRegistered {
+    pub agent: Pubkey,
+    pub asset_address: Pubkey,
+    /// Default pubkey when the agent registered without a referrer
+    pub referrer: Pubkey,
+    pub metadata_uri: String,
+    pub metadata_hash: [u8; 32],
+    pub capabilities: u32,
+    pub service_category: Option<ServiceCategory>,
+    pub transferable: bool,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when update_identity replaces the metadata URI and hash
+#[event]
+pub struct IdentityUpdated {
+    pub agent: Pubkey,
+    /// The owner, or a delegate with the metadata scope
+    pub updated_by: Pubkey,
+    pub metadata_uri: String,
+    pub metadata_hash: [u8; 32],
+    pub metadata_version: u32,
+    pub timestamp: i64,
+}
+
+/// Emitted when an identity is deactivated
+#[event]
+pub struct AgentDeactivated {
+    pub agent: Pubkey,
+    pub deactivated_by: Pubkey,
+    /// sha256 of the deactivation reason
+    pub reason_hash: [u8; 32],
+    /// Stake left in the pool; it can still be requested and withdrawn
+    pub staked_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when transfer_identity moves an identity to a new wallet. Records
+/// keyed by the old address (reputation, votes, slash records) are not moved.
+#[event]
+pub struct IdentityTransferred {
+    pub old_agent: Pubkey,
+    pub new_agent: Pubkey,
+    pub asset_address: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a deactivated identity is restored
+#[event]
+pub struct AgentReactivated {
+    pub agent: Pubkey,
+    pub reactivated_by: Pubkey,
+    /// True when the ProgramConfig admin bypassed the owner cooldown
+    pub admin_override: bool,
+    pub deactivated_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when stake_collateral adds SOL to an agent's own stake
+#[event]
+pub struct CollateralStaked {
+    pub agent: Pubkey,
+    /// The owner, or a delegate with the stake scope
+    pub staked_by: Pubkey,
+    pub amount: u64,
+    /// Agent's own stake after the deposit
+    pub staked_amount: u64,
+    /// Start of the stake age used by the trust score
+    pub first_staked_at: i64,
+    /// Staking pool total after the deposit
+    pub pool_total_staked: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when request_unstake starts (or restarts) the unlock cooldown
+#[event]
+pub struct UnstakeRequested {
+    pub agent: Pubkey,
+    pub amount: u64,
+    /// Total pending after this request
+    pub pending_unstake_amount: u64,
+    /// When the pending amount becomes withdrawable
+    pub unlock_timestamp: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when withdraw_unstaked pays out the pending unstake
+#[event]
+pub struct CollateralUnstaked {
+    pub agent: Pubkey,
+    pub amount: u64,
+    /// Agent's own stake left after the withdrawal
+    pub staked_amount: u64,
+    /// Staking pool total after the withdrawal
+    pub pool_total_staked: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by slash_agent and slash_agent_multisig when lamports move into a slash record's escrow
+#[event]
+pub struct AgentSlashed {
+    pub agent: Pubkey,
+    pub slash_record: Pubkey,
+    pub slash_index: u32,
+    /// slash_agent's authority, or the payer of a multisig slash
+    pub authority: Pubkey,
+    pub severity_bps: u16,
+    /// Total slashed, including the delegated share
+    pub amount: u64,
+    pub delegated_amount: u64,
+    /// Agent's own and delegated stake after the slash
+    pub staked_amount: u64,
+    pub delegated_stake: u64,
+    pub pending_unstake_amount: u64,
+    pub slash_count: u32,
+    pub reason_hash: [u8; 32],
+    pub appeal_deadline: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the staking pool authority pauses or unpauses staking
+#[event]
+pub struct StakingPaused {
+    pub authority: Pubkey,
+    /// False when staking was unpaused
+    pub is_paused: bool,
+    pub timestamp: i64,
+}
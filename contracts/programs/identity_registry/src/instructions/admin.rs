@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{ProgramConfig, UserRateLimit};
+use crate::state::{ExemptAccount, ProgramConfig, ProgramGuards, UserRateLimit};
 
 // ==================== ADMIN ERRORS ====================
 
@@ -15,6 +15,28 @@ pub enum AdminError {
     InvalidRateLimit,
     #[msg("Pause reason too long (max 128 chars)")]
     PauseReasonTooLong,
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("No admin transfer is pending")]
+    NoPendingAdmin,
+    #[msg("Invalid admin address")]
+    InvalidAdmin,
+    #[msg("Max pause duration must be positive")]
+    InvalidPauseDuration,
+    #[msg("Extending a pause requires a new reason")]
+    PauseReasonReused,
+    #[msg("Program is not paused")]
+    ProgramNotPaused,
+    #[msg("Pause has not expired yet")]
+    PauseNotExpired,
+    #[msg("Exemption cap must be above zero")]
+    InvalidExemptionCap,
+    #[msg("Rate limit account is still active; only its user can close it")]
+    RateLimitNotIdle,
+    #[msg("Identity validity must not be negative")]
+    InvalidValidityPeriod,
+    #[msg("Heartbeat interval must be positive")]
+    InvalidHeartbeatInterval,
 }
 
 // ==================== INITIALIZE PROGRAM CONFIG ====================
@@ -40,7 +62,10 @@ pub struct InitializeProgramConfig<'info> {
 pub fn initialize_program_config(
     ctx: Context<InitializeProgramConfig>,
     rate_limit_per_minute: u32,
+    max_pause_duration_seconds: i64,
 ) -> Result<()> {
+    require!(max_pause_duration_seconds > 0, AdminError::InvalidPauseDuration);
+
     let config = &mut ctx.accounts.config;
 
     config.admin = ctx.accounts.admin.key();
@@ -49,6 +74,10 @@ pub fn initialize_program_config(
     config.pause_reason = String::new();
     config.rate_limit_per_minute = rate_limit_per_minute;
     config.bump = ctx.bumps.config;
+    config.pending_admin = Pubkey::default();
+    config.max_pause_duration_seconds = max_pause_duration_seconds;
+    config.default_validity_seconds = 0;
+    config.heartbeat_interval_seconds = ProgramConfig::DEFAULT_HEARTBEAT_INTERVAL;
 
     msg!("Program config initialized by {}", config.admin);
 
@@ -71,12 +100,19 @@ pub struct PauseProgram<'info> {
 }
 
 /// Pause all program operations (emergency only)
+///
+/// Re-pausing restarts the max pause duration, so an emergency can be
+/// extended, but only under a new reason.
 pub fn pause_program(ctx: Context<PauseProgram>, reason: String) -> Result<()> {
     require!(reason.len() <= 128, AdminError::PauseReasonTooLong);
 
     let config = &mut ctx.accounts.config;
     let clock = Clock::get()?;
 
+    if config.is_paused {
+        require!(reason != config.pause_reason, AdminError::PauseReasonReused);
+    }
+
     config.is_paused = true;
     config.paused_at = clock.unix_timestamp;
     config.pause_reason = reason.clone();
@@ -99,6 +135,35 @@ pub fn unpause_program(ctx: Context<PauseProgram>) -> Result<()> {
     Ok(())
 }
 
+// ==================== UNPAUSE EXPIRED ====================
+
+#[derive(Accounts)]
+pub struct UnpauseExpired<'info> {
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Clear a pause that has outlived its max duration (permissionless)
+pub fn unpause_expired(ctx: Context<UnpauseExpired>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(config.is_paused, AdminError::ProgramNotPaused);
+    require!(!config.pause_active(now), AdminError::PauseNotExpired);
+
+    config.is_paused = false;
+    config.paused_at = 0;
+    config.pause_reason = String::new();
+
+    msg!("Expired pause lifted at {}", now);
+
+    Ok(())
+}
+
 // ==================== UPDATE RATE LIMIT ====================
 
 #[derive(Accounts)]
@@ -130,6 +195,52 @@ pub fn update_rate_limit(
     Ok(())
 }
 
+// ==================== UPDATE DEFAULT VALIDITY ====================
+
+/// Set how long identities stay current after registration or renewal.
+/// 0 turns expiry off for new registrations and renewals; identities that
+/// already carry an expires_at keep it until renewed.
+pub fn update_default_validity(
+    ctx: Context<UpdateRateLimit>,
+    default_validity_seconds: i64,
+) -> Result<()> {
+    require!(default_validity_seconds >= 0, AdminError::InvalidValidityPeriod);
+
+    let config = &mut ctx.accounts.config;
+    let old_validity = config.default_validity_seconds;
+    config.default_validity_seconds = default_validity_seconds;
+
+    msg!(
+        "Default identity validity updated: {}s -> {}s",
+        old_validity,
+        default_validity_seconds
+    );
+
+    Ok(())
+}
+
+// ==================== UPDATE HEARTBEAT INTERVAL ====================
+
+/// Set the minimum spacing between heartbeats from one identity
+pub fn update_heartbeat_interval(
+    ctx: Context<UpdateRateLimit>,
+    heartbeat_interval_seconds: i64,
+) -> Result<()> {
+    require!(heartbeat_interval_seconds > 0, AdminError::InvalidHeartbeatInterval);
+
+    let config = &mut ctx.accounts.config;
+    let old_interval = config.heartbeat_interval_seconds;
+    config.heartbeat_interval_seconds = heartbeat_interval_seconds;
+
+    msg!(
+        "Heartbeat interval updated: {}s -> {}s",
+        old_interval,
+        heartbeat_interval_seconds
+    );
+
+    Ok(())
+}
+
 // ==================== INITIALIZE USER RATE LIMIT ====================
 
 #[derive(Accounts)]
@@ -159,12 +270,53 @@ pub fn initialize_user_rate_limit(ctx: Context<InitializeUserRateLimit>) -> Resu
     rate_limit.instruction_count = 0;
     rate_limit.last_instruction = clock.unix_timestamp;
     rate_limit.bump = ctx.bumps.rate_limit;
+    rate_limit.payer = ctx.accounts.user.key();
 
     msg!("Rate limit tracking initialized for {}", rate_limit.user);
 
     Ok(())
 }
 
+// ==================== CLOSE USER RATE LIMIT ====================
+
+#[derive(Accounts)]
+pub struct CloseUserRateLimit<'info> {
+    #[account(
+        mut,
+        close = payer,
+        seeds = [UserRateLimit::SEED_PREFIX, rate_limit.user.as_ref()],
+        bump = rate_limit.bump,
+        has_one = payer
+    )]
+    pub rate_limit: Account<'info, UserRateLimit>,
+
+    /// CHECK: Original rent payer recorded on the account; receives the refund
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    pub closer: Signer<'info>,
+}
+
+/// Close a rate limit account and refund its payer. The user can close it
+/// anytime; anyone else only once it has been idle for IDLE_CLOSE_PERIOD.
+pub fn close_user_rate_limit(ctx: Context<CloseUserRateLimit>) -> Result<()> {
+    let rate_limit = &ctx.accounts.rate_limit;
+
+    if ctx.accounts.closer.key() != rate_limit.user {
+        let idle = Clock::get()?
+            .unix_timestamp
+            .saturating_sub(rate_limit.last_instruction);
+        require!(
+            idle > UserRateLimit::IDLE_CLOSE_PERIOD,
+            AdminError::RateLimitNotIdle
+        );
+    }
+
+    msg!("Rate limit account for {} closed", rate_limit.user);
+
+    Ok(())
+}
+
 // ==================== CHECK RATE LIMIT ====================
 
 #[derive(Accounts)]
@@ -183,6 +335,10 @@ pub struct CheckRateLimit<'info> {
     pub rate_limit: Account<'info, UserRateLimit>,
 
     pub user: Signer<'info>,
+
+    /// CHECK: User's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, user.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
 }
 
 /// Rate limit window duration (60 seconds)
@@ -192,37 +348,131 @@ const RATE_LIMIT_WINDOW_SECONDS: i64 = 60;
 /// Returns Ok(()) if within limit, Err if exceeded
 pub fn check_rate_limit(ctx: Context<CheckRateLimit>) -> Result<()> {
     let config = &ctx.accounts.config;
-    let rate_limit = &mut ctx.accounts.rate_limit;
     let clock = Clock::get()?;
 
     // Check if program is paused
-    require!(!config.is_paused, AdminError::ProgramPaused);
+    require_not_paused(config)?;
+
+    let max_per_minute =
+        ExemptAccount::rate_limit(&ctx.accounts.rate_exemption, config.rate_limit_per_minute)?;
+    match max_per_minute {
+        Some(max_per_minute) => consume_rate_limit(
+            &mut ctx.accounts.rate_limit,
+            max_per_minute,
+            clock.unix_timestamp,
+        ),
+        None => Ok(()),
+    }
+}
 
+/// Count one instruction against the user's window, failing once the
+/// per-minute budget is spent
+fn consume_rate_limit(
+    rate_limit: &mut UserRateLimit,
+    max_per_minute: u32,
+    current_timestamp: i64,
+) -> Result<()> {
     // Check if we're in a new window
-    let window_elapsed = clock.unix_timestamp.saturating_sub(rate_limit.window_start);
+    let window_elapsed = current_timestamp.saturating_sub(rate_limit.window_start);
 
     if window_elapsed >= RATE_LIMIT_WINDOW_SECONDS {
         // Reset window
-        rate_limit.window_start = clock.unix_timestamp;
+        rate_limit.window_start = current_timestamp;
         rate_limit.instruction_count = 1;
     } else {
         // Check if within limit
         require!(
-            rate_limit.instruction_count < config.rate_limit_per_minute,
+            rate_limit.instruction_count < max_per_minute,
             AdminError::RateLimitExceeded
         );
         rate_limit.instruction_count = rate_limit.instruction_count.saturating_add(1);
     }
 
-    rate_limit.last_instruction = clock.unix_timestamp;
+    rate_limit.last_instruction = current_timestamp;
 
     Ok(())
 }
 
-// ==================== TRANSFER ADMIN ====================
+// ==================== RATE LIMIT EXEMPTIONS ====================
 
 #[derive(Accounts)]
-pub struct TransferAdmin<'info> {
+pub struct GrantRateExemption<'info> {
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ AdminError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ExemptAccount::LEN,
+        seeds = [ExemptAccount::SEED_PREFIX, account.key().as_ref()],
+        bump
+    )]
+    pub exemption: Account<'info, ExemptAccount>,
+
+    /// CHECK: The wallet being exempted
+    pub account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Exempt a trusted relayer or service from the program rate limit, either
+/// fully (`max_per_minute` None) or up to its own per-minute cap
+pub fn grant_rate_exemption(
+    ctx: Context<GrantRateExemption>,
+    max_per_minute: Option<u32>,
+) -> Result<()> {
+    require!(max_per_minute != Some(0), AdminError::InvalidExemptionCap);
+
+    let exemption = &mut ctx.accounts.exemption;
+    exemption.account = ctx.accounts.account.key();
+    exemption.max_per_minute = max_per_minute;
+    exemption.granted_at = Clock::get()?.unix_timestamp;
+    exemption.bump = ctx.bumps.exemption;
+
+    msg!("Rate limit exemption granted to {}", exemption.account);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevokeRateExemption<'info> {
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ AdminError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [ExemptAccount::SEED_PREFIX, exemption.account.as_ref()],
+        bump = exemption.bump
+    )]
+    pub exemption: Account<'info, ExemptAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+/// Revoke an exemption; the program limit applies again from the next instruction
+pub fn revoke_rate_exemption(ctx: Context<RevokeRateExemption>) -> Result<()> {
+    msg!("Rate limit exemption revoked for {}", ctx.accounts.exemption.account);
+
+    Ok(())
+}
+
+// ==================== TWO-STEP ADMIN TRANSFER ====================
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
     #[account(
         mut,
         seeds = [ProgramConfig::SEED_PREFIX],
@@ -232,26 +482,110 @@ pub struct TransferAdmin<'info> {
     pub config: Account<'info, ProgramConfig>,
 
     pub current_admin: Signer<'info>,
+}
+
+/// Propose a new admin; rights only move once they call accept_admin
+pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    require!(new_admin != Pubkey::default(), AdminError::InvalidAdmin);
+
+    let config = &mut ctx.accounts.config;
+    config.pending_admin = new_admin;
+
+    msg!("Admin transfer proposed: {} -> {}", config.admin, new_admin);
 
-    /// CHECK: The new admin address
-    pub new_admin: UncheckedAccount<'info>,
+    Ok(())
 }
 
-/// Transfer admin rights to a new address
-pub fn transfer_admin(ctx: Context<TransferAdmin>) -> Result<()> {
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.pending_admin != Pubkey::default() @ AdminError::NoPendingAdmin,
+        constraint = config.pending_admin == new_admin.key() @ AdminError::NotPendingAdmin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub new_admin: Signer<'info>,
+}
+
+/// Accept a pending admin transfer (pending admin only)
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
     let config = &mut ctx.accounts.config;
     let old_admin = config.admin;
-    config.admin = ctx.accounts.new_admin.key();
+    config.admin = config.pending_admin;
+    config.pending_admin = Pubkey::default();
 
     msg!("Admin transferred: {} -> {}", old_admin, config.admin);
 
     Ok(())
 }
 
+#[derive(Accounts)]
+pub struct CancelAdminTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.admin == current_admin.key() @ AdminError::UnauthorizedAdmin,
+        constraint = config.pending_admin != Pubkey::default() @ AdminError::NoPendingAdmin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub current_admin: Signer<'info>,
+}
+
+/// Withdraw a pending admin transfer
+pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    msg!("Admin transfer to {} cancelled", config.pending_admin);
+    config.pending_admin = Pubkey::default();
+
+    Ok(())
+}
+
 // ==================== HELPER: CHECK PAUSE STATE ====================
 
-/// Helper to check if program is paused (can be called from other instructions)
+/// Helper to check if program is paused (can be called from other instructions).
+/// A pause past its max duration counts as lifted even before unpause_expired runs.
 pub fn require_not_paused(config: &Account<ProgramConfig>) -> Result<()> {
-    require!(!config.is_paused, AdminError::ProgramPaused);
+    require!(
+        !config.pause_active(Clock::get()?.unix_timestamp),
+        AdminError::ProgramPaused
+    );
     Ok(())
 }
+
+// ==================== HELPER: ENFORCE PROGRAM GUARDS ====================
+
+/// Enforce the program-wide pause and, when the caller's UserRateLimit is
+/// supplied, its per-minute budget. A rate limit exemption replaces the
+/// program limit with its own cap, or skips counting entirely. Returns the
+/// resolved guards so callers can read other config values.
+///
+/// Gated: register_agent, register_agents_batch, update_identity, update_asset,
+/// update_capabilities, claim_name, renew_identity, record_heartbeat,
+/// transfer_identity, stake_collateral, request_unstake, withdraw_unstaked, stake_for_agent,
+/// request_delegated_unstake and withdraw_delegated_stake.
+/// Exempt: admin and config instructions (so a paused program can still be
+/// unpaused), slashing and appeals, migrations, deactivate/reactivate/close
+/// and views.
+pub fn enforce_program_guards(
+    config: &AccountInfo,
+    rate_exemption: &AccountInfo,
+    rate_limit: Option<&mut Account<UserRateLimit>>,
+) -> Result<ProgramGuards> {
+    let guards = ProgramConfig::resolve(config)?;
+    require!(!guards.is_paused, AdminError::ProgramPaused);
+
+    if let Some(rate_limit) = rate_limit {
+        if let Some(max_per_minute) =
+            ExemptAccount::rate_limit(rate_exemption, guards.rate_limit_per_minute)?
+        {
+            consume_rate_limit(rate_limit, max_per_minute, Clock::get()?.unix_timestamp)?;
+        }
+    }
+
+    Ok(guards)
+}
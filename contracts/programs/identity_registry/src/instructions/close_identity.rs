@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use super::migrate::MigrationError;
+use crate::state::{AgentIdentity, REPUTATION_REGISTRY_PROGRAM_ID, REPUTATION_SEED};
+
+/// Close a wound-down identity and return its rent to the agent.
+///
+/// Expected order: unstake everything, release or transfer any claimed
+/// name, deactivate, close the agent's AgentReputation with
+/// reputation_registry's close_reputation, then close the identity. The
+/// reputation PDA must be passed and must not exist, so an identity is never
+/// removed from under a live reputation record.
+#[derive(Accounts)]
+pub struct CloseIdentity<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent.key().as_ref()],
+        bump = agent_identity.bump,
+        constraint = agent_identity.agent_address == agent.key() @ IdentityError::UnauthorizedClose,
+        close = agent,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// CHECK: The agent's AgentReputation PDA in reputation_registry; must not exist
+    #[account(
+        seeds = [REPUTATION_SEED, agent.key().as_ref()],
+        bump,
+        seeds::program = REPUTATION_REGISTRY_PROGRAM_ID
+    )]
+    pub agent_reputation: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub agent: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseIdentity>) -> Result<()> {
+    let agent_identity = &ctx.accounts.agent_identity;
+
+    // Fail loudly rather than strand collateral in the staking pool
+    require!(
+        agent_identity.staked_amount == 0,
+        IdentityError::StakeRemaining
+    );
+    require!(
+        agent_identity.stake_unlock_timestamp == 0,
+        IdentityError::StakeUnlockPending
+    );
+    require!(
+        agent_identity.delegated_shares == 0,
+        IdentityError::DelegatedStakeRemaining
+    );
+    require!(!agent_identity.is_active, IdentityError::IdentityStillActive);
+    require!(
+        agent_identity.name_record == Pubkey::default(),
+        IdentityError::NameStillClaimed
+    );
+
+    // Only data marks a live record: anyone can send lamports to the PDA
+    require!(
+        ctx.accounts.agent_reputation.data_is_empty(),
+        IdentityError::ReputationNotClosed
+    );
+
+    msg!("Agent identity closed: {}", agent_identity.agent_address);
+
+    Ok(())
+}
+
+#[error_code]
+pub enum IdentityError {
+    #[msg("Unauthorized: signer is not the agent owner")]
+    UnauthorizedClose,
+    #[msg("Identity still holds staked collateral; unstake before closing")]
+    StakeRemaining,
+    #[msg("Identity has a pending stake unlock")]
+    StakeUnlockPending,
+    #[msg("Identity must be deactivated before closing")]
+    IdentityStillActive,
+    #[msg("Close the agent's reputation account before its identity")]
+    ReputationNotClosed,
+    #[msg("Release or transfer the agent's name before closing")]
+    NameStillClaimed,
+    #[msg("Backers still hold delegated stake in this identity")]
+    DelegatedStakeRemaining,
+}
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use super::migrate::MigrationError;
+use crate::events::AgentDeactivated;
 use crate::state::AgentIdentity;
 
 #[derive(Accounts)]
@@ -7,7 +9,8 @@ pub struct DeactivateAgent<'info> {
         mut,
         seeds = [AgentIdentity::SEED_PREFIX, agent.key().as_ref()],
         bump = agent_identity.bump,
-        has_one = agent_address @ IdentityError::UnauthorizedDeactivation
+        has_one = agent_address @ IdentityError::UnauthorizedDeactivation,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
     )]
     pub agent_identity: Account<'info, AgentIdentity>,
 
@@ -18,7 +21,9 @@ pub struct DeactivateAgent<'info> {
     pub agent_address: UncheckedAccount<'info>,
 }
 
-pub fn handler(ctx: Context<DeactivateAgent>) -> Result<()> {
+/// Deactivate the identity, recording who did it and the sha256 of why.
+/// Existing stake stays withdrawable; new stake is refused until reactivation.
+pub fn handler(ctx: Context<DeactivateAgent>, reason_hash: [u8; 32]) -> Result<()> {
     require!(
         ctx.accounts.agent_identity.is_active,
         IdentityError::AlreadyDeactivated
@@ -28,9 +33,20 @@ pub fn handler(ctx: Context<DeactivateAgent>) -> Result<()> {
     let clock = Clock::get()?;
 
     agent_identity.is_active = false;
+    agent_identity.deactivated_at = clock.unix_timestamp;
+    agent_identity.deactivated_by = ctx.accounts.agent.key();
+    agent_identity.deactivation_reason_hash = reason_hash;
     agent_identity.last_active_timestamp = clock.unix_timestamp;
     agent_identity.activity_count = agent_identity.activity_count.saturating_add(1);
 
+    emit!(AgentDeactivated {
+        agent: agent_identity.agent_address,
+        deactivated_by: agent_identity.deactivated_by,
+        reason_hash,
+        staked_amount: agent_identity.staked_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
     msg!("Agent identity deactivated: {}", ctx.accounts.agent.key());
 
     Ok(())
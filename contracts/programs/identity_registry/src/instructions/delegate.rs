@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use super::migrate::MigrationError;
+use crate::state::{AgentIdentity, DELEGATE_SCOPE_ALL};
+
+// ==================== SET DELEGATE ====================
+
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent.key().as_ref()],
+        bump = agent_identity.bump,
+        has_one = agent_address @ DelegateError::UnauthorizedDelegation,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// Identity owner; delegates cannot manage delegation
+    pub agent: Signer<'info>,
+
+    /// CHECK: This is the agent_address stored in agent_identity
+    pub agent_address: UncheckedAccount<'info>,
+}
+
+/// Grant an operator key the DELEGATE_SCOPE_* flags in `scope_flags`,
+/// replacing any previous delegate
+pub fn set_delegate(ctx: Context<SetDelegate>, delegate: Pubkey, scope_flags: u8) -> Result<()> {
+    require!(
+        delegate != Pubkey::default() && delegate != ctx.accounts.agent.key(),
+        DelegateError::InvalidDelegate
+    );
+    require!(
+        scope_flags != 0 && scope_flags & !DELEGATE_SCOPE_ALL == 0,
+        DelegateError::InvalidDelegateScope
+    );
+
+    let agent_identity = &mut ctx.accounts.agent_identity;
+    agent_identity.delegate = delegate;
+    agent_identity.delegate_scope = scope_flags;
+
+    msg!(
+        "Delegate {} set for agent {} (scope {:#04b})",
+        delegate,
+        agent_identity.agent_address,
+        scope_flags
+    );
+
+    Ok(())
+}
+
+// ==================== REVOKE DELEGATE ====================
+
+/// Remove the identity's delegate (owner only)
+pub fn revoke_delegate(ctx: Context<SetDelegate>) -> Result<()> {
+    let agent_identity = &mut ctx.accounts.agent_identity;
+    require!(
+        agent_identity.delegate != Pubkey::default(),
+        DelegateError::NoDelegate
+    );
+
+    msg!(
+        "Delegate {} revoked for agent {}",
+        agent_identity.delegate,
+        agent_identity.agent_address
+    );
+
+    agent_identity.delegate = Pubkey::default();
+    agent_identity.delegate_scope = 0;
+
+    Ok(())
+}
+
+#[error_code]
+pub enum DelegateError {
+    #[msg("Unauthorized: signer is not the agent owner")]
+    UnauthorizedDelegation,
+    #[msg("Delegate must be a key other than the owner")]
+    InvalidDelegate,
+    #[msg("Delegate scope must be a non-empty set of known flags")]
+    InvalidDelegateScope,
+    #[msg("Identity has no delegate")]
+    NoDelegate,
+}
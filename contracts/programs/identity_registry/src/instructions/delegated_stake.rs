@@ -0,0 +1,382 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use super::admin::enforce_program_guards;
+use super::stake::{check_pool_debit, StakingError};
+use super::migrate::MigrationError;
+use crate::state::{
+    AgentIdentity, DelegatedStake, ExemptAccount, ProgramConfig, StakingPool, UserRateLimit,
+    STAKE_UNLOCK_PERIOD,
+};
+
+// ============================================================================
+// STAKE FOR AGENT
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct StakeForAgent<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
+        bump = agent_identity.bump,
+        has_one = agent_address @ StakingError::UnauthorizedAgent,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [StakingPool::SEED_PREFIX],
+        bump = staking_pool.bump,
+        constraint = !staking_pool.is_paused @ StakingError::StakingPaused,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    /// The backer's position; created on the first deposit
+    #[account(
+        init_if_needed,
+        payer = backer,
+        space = DelegatedStake::LEN,
+        seeds = [DelegatedStake::SEED_PREFIX, agent_address.key().as_ref(), backer.key().as_ref()],
+        bump,
+    )]
+    pub delegated_stake: Account<'info, DelegatedStake>,
+
+    /// CHECK: Validated by seed constraint
+    pub agent_address: UncheckedAccount<'info>,
+
+    /// Any wallet other than the agent; pays the stake
+    #[account(mut)]
+    pub backer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Program config PDA; unpaused while it is uninitialized
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// Caller's rate limit; the per-minute budget is enforced when supplied
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, backer.key().as_ref()],
+        bump = rate_limit.bump
+    )]
+    pub rate_limit: Option<Account<'info, UserRateLimit>>,
+
+    /// CHECK: Caller's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, backer.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
+}
+
+/// Deposit SOL into the pool as collateral for someone else's agent.
+/// The backer receives shares of the agent's delegated stake, so later
+/// slashes reduce every backer's position in proportion.
+pub fn stake_for_agent(ctx: Context<StakeForAgent>, amount: u64) -> Result<()> {
+    enforce_program_guards(
+        &ctx.accounts.program_config,
+        &ctx.accounts.rate_exemption,
+        ctx.accounts.rate_limit.as_mut(),
+    )?;
+
+    let agent_identity = &mut ctx.accounts.agent_identity;
+    let staking_pool = &mut ctx.accounts.staking_pool;
+    let delegated_stake = &mut ctx.accounts.delegated_stake;
+    let backer = ctx.accounts.backer.key();
+
+    require!(agent_identity.is_active, StakingError::AgentDeactivated);
+    require!(
+        backer != agent_identity.agent_address,
+        StakingError::SelfDelegation
+    );
+
+    let effective_min = staking_pool.min_stake_for(agent_identity.service_category);
+    require!(amount >= effective_min, StakingError::BelowMinimumStake);
+
+    let shares = agent_identity
+        .shares_for_deposit(amount)
+        .filter(|shares| *shares > 0)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.backer.to_account_info(),
+                to: staking_pool.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    delegated_stake.agent = agent_identity.agent_address;
+    delegated_stake.backer = backer;
+    delegated_stake.bump = ctx.bumps.delegated_stake;
+    delegated_stake.shares = delegated_stake
+        .shares
+        .checked_add(shares)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    agent_identity.delegated_stake = agent_identity
+        .delegated_stake
+        .checked_add(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    agent_identity.delegated_shares = agent_identity
+        .delegated_shares
+        .checked_add(shares)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    staking_pool.total_staked = staking_pool
+        .total_staked
+        .checked_add(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    msg!(
+        "Backer {} staked {} lamports for agent {}. Delegated stake: {}",
+        backer,
+        amount,
+        agent_identity.agent_address,
+        agent_identity.delegated_stake
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// REQUEST DELEGATED UNSTAKE
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct RequestDelegatedUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
+        bump = agent_identity.bump,
+        has_one = agent_address @ StakingError::UnauthorizedAgent,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [StakingPool::SEED_PREFIX],
+        bump = staking_pool.bump,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    #[account(
+        mut,
+        seeds = [DelegatedStake::SEED_PREFIX, agent_address.key().as_ref(), backer.key().as_ref()],
+        bump = delegated_stake.bump,
+        has_one = backer @ StakingError::UnauthorizedBacker,
+    )]
+    pub delegated_stake: Account<'info, DelegatedStake>,
+
+    /// CHECK: Validated by seed constraint
+    pub agent_address: UncheckedAccount<'info>,
+
+    pub backer: Signer<'info>,
+
+    /// CHECK: Program config PDA; unpaused while it is uninitialized
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// Caller's rate limit; the per-minute budget is enforced when supplied
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, backer.key().as_ref()],
+        bump = rate_limit.bump
+    )]
+    pub rate_limit: Option<Account<'info, UserRateLimit>>,
+
+    /// CHECK: Caller's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, backer.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
+}
+
+/// Start the unlock cooldown for `amount` lamports of the backer's position.
+/// Like request_unstake, requests stack and restart the cooldown.
+pub fn request_delegated_unstake(ctx: Context<RequestDelegatedUnstake>, amount: u64) -> Result<()> {
+    enforce_program_guards(
+        &ctx.accounts.program_config,
+        &ctx.accounts.rate_exemption,
+        ctx.accounts.rate_limit.as_mut(),
+    )?;
+
+    let agent_identity = &mut ctx.accounts.agent_identity;
+    let delegated_stake = &mut ctx.accounts.delegated_stake;
+    let staking_pool = &ctx.accounts.staking_pool;
+    let clock = Clock::get()?;
+
+    require!(amount > 0, StakingError::InvalidUnstakeAmount);
+
+    // Round up so a request for the full position's value covers every share
+    let shares = u64::try_from(
+        (amount as u128 * agent_identity.delegated_shares as u128)
+            .div_ceil(agent_identity.delegated_stake.max(1) as u128),
+    )
+    .map_err(|_| error!(StakingError::ArithmeticOverflow))?;
+    let available = delegated_stake.shares.saturating_sub(delegated_stake.pending_shares);
+    require!(shares <= available, StakingError::InsufficientStake);
+
+    delegated_stake.pending_shares = delegated_stake
+        .pending_shares
+        .checked_add(shares)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    agent_identity.pending_delegated_shares = agent_identity
+        .pending_delegated_shares
+        .checked_add(shares)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    let unlock_period = if staking_pool.unlock_period > 0 {
+        staking_pool.unlock_period
+    } else {
+        STAKE_UNLOCK_PERIOD
+    };
+    delegated_stake.unlock_timestamp = clock
+        .unix_timestamp
+        .checked_add(unlock_period)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    msg!(
+        "Backer {} requested {} lamports ({} shares) from agent {}, withdrawable at {}",
+        delegated_stake.backer,
+        amount,
+        shares,
+        agent_identity.agent_address,
+        delegated_stake.unlock_timestamp
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// WITHDRAW DELEGATED STAKE
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct WithdrawDelegatedStake<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
+        bump = agent_identity.bump,
+        has_one = agent_address @ StakingError::UnauthorizedAgent,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [StakingPool::SEED_PREFIX],
+        bump = staking_pool.bump,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    #[account(
+        mut,
+        seeds = [DelegatedStake::SEED_PREFIX, agent_address.key().as_ref(), backer.key().as_ref()],
+        bump = delegated_stake.bump,
+        has_one = backer @ StakingError::UnauthorizedBacker,
+    )]
+    pub delegated_stake: Account<'info, DelegatedStake>,
+
+    /// CHECK: Validated by seed constraint
+    pub agent_address: UncheckedAccount<'info>,
+
+    /// Receives the withdrawn stake, and the position's rent once it is empty
+    #[account(mut)]
+    pub backer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Program config PDA; unpaused while it is uninitialized
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// Caller's rate limit; the per-minute budget is enforced when supplied
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, backer.key().as_ref()],
+        bump = rate_limit.bump
+    )]
+    pub rate_limit: Option<Account<'info, UserRateLimit>>,
+
+    /// CHECK: Caller's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, backer.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
+}
+
+/// Withdraw the current value of the backer's pending shares once their
+/// cooldown has elapsed. The position is closed when no shares remain.
+pub fn withdraw_delegated_stake(ctx: Context<WithdrawDelegatedStake>) -> Result<()> {
+    enforce_program_guards(
+        &ctx.accounts.program_config,
+        &ctx.accounts.rate_exemption,
+        ctx.accounts.rate_limit.as_mut(),
+    )?;
+
+    let agent_identity = &mut ctx.accounts.agent_identity;
+    let staking_pool = &mut ctx.accounts.staking_pool;
+    let delegated_stake = &mut ctx.accounts.delegated_stake;
+    let clock = Clock::get()?;
+
+    let shares = delegated_stake.pending_shares;
+    require!(shares > 0, StakingError::NoPendingUnstake);
+    require!(
+        clock.unix_timestamp >= delegated_stake.unlock_timestamp,
+        StakingError::StakeLocked
+    );
+
+    let amount = agent_identity.delegated_value(shares);
+    check_pool_debit(&staking_pool.to_account_info(), amount)?;
+
+    **staking_pool.to_account_info().try_borrow_mut_lamports()? = staking_pool
+        .to_account_info()
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    **ctx.accounts.backer.to_account_info().try_borrow_mut_lamports()? = ctx
+        .accounts
+        .backer
+        .to_account_info()
+        .lamports()
+        .checked_add(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    agent_identity.delegated_stake = agent_identity
+        .delegated_stake
+        .checked_sub(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    agent_identity.delegated_shares = agent_identity
+        .delegated_shares
+        .checked_sub(shares)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    agent_identity.pending_delegated_shares = agent_identity
+        .pending_delegated_shares
+        .checked_sub(shares)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    staking_pool.total_staked = staking_pool
+        .total_staked
+        .checked_sub(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    delegated_stake.shares = delegated_stake
+        .shares
+        .checked_sub(shares)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    delegated_stake.pending_shares = 0;
+    delegated_stake.unlock_timestamp = 0;
+
+    msg!(
+        "Backer {} withdrew {} lamports from agent {}. Delegated stake: {}",
+        delegated_stake.backer,
+        amount,
+        agent_identity.agent_address,
+        agent_identity.delegated_stake
+    );
+
+    if delegated_stake.shares == 0 {
+        delegated_stake.close(ctx.accounts.backer.to_account_info())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,286 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use crate::state::{
+    AgentIdentity, ProgramConfig, StakingPool, UserRateLimit, DEFAULT_SLASH_APPEAL_WINDOW,
+    DEFAULT_TRUST_AGE_WEIGHT, DEFAULT_TRUST_SLASH_PENALTY, DEFAULT_TRUST_STAKE_WEIGHT,
+};
+
+/// Grow a program-owned account to `new_len`, topping up rent from `payer`.
+/// New bytes are zeroed, so appended fields start at their zero value.
+pub(crate) fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let required = Rent::get()?.minimum_balance(new_len);
+    let shortfall = required.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    account.resize(new_len)?;
+    Ok(())
+}
+
+/// Check that an account is owned by this program and carries the expected discriminator
+pub(crate) fn check_legacy_account(account: &AccountInfo, discriminator: &[u8]) -> Result<()> {
+    require_keys_eq!(*account.owner, crate::ID, MigrationError::InvalidMigrationTarget);
+    let data = account.try_borrow_data()?;
+    require!(
+        data.len() >= discriminator.len() && &data[..discriminator.len()] == discriminator,
+        MigrationError::InvalidMigrationTarget
+    );
+    Ok(())
+}
+
+// ==================== MIGRATE ACCOUNT ====================
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Possibly legacy-sized account; owner and discriminator are checked in the handler
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
+        bump
+    )]
+    pub agent_identity: UncheckedAccount<'info>,
+
+    /// CHECK: The agent's wallet address (seed only)
+    pub agent_address: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade an AgentIdentity of any earlier layout to CURRENT_VERSION (permissionless).
+/// The account is resized to the current layout; appended fields start zeroed.
+pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+    let account = ctx.accounts.agent_identity.to_account_info();
+    check_legacy_account(&account, AgentIdentity::DISCRIMINATOR)?;
+
+    let old_len = account.data_len();
+    require!(old_len >= AgentIdentity::LEGACY_LEN, MigrationError::InvalidMigrationTarget);
+    if old_len < AgentIdentity::LEN {
+        grow_account(
+            &account,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            AgentIdentity::LEN,
+        )?;
+    }
+
+    let mut identity = AgentIdentity::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    if identity.version >= AgentIdentity::CURRENT_VERSION {
+        msg!("Identity account already at version {}", identity.version);
+        return Ok(());
+    }
+
+    if old_len < AgentIdentity::PRE_VERSION_LEN {
+        // Accounts predating two-step unstaking set stake_unlock_timestamp at
+        // stake time. Nothing is pending after the resize, so clear it; the
+        // agent must now request_unstake and wait out the cooldown.
+        identity.stake_unlock_timestamp = 0;
+        // Stake age is unknown for existing stakes; count it from the migration
+        if identity.staked_amount > 0 {
+            identity.first_staked_at = Clock::get()?.unix_timestamp;
+        }
+    }
+    identity.version = AgentIdentity::CURRENT_VERSION;
+    identity.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    msg!(
+        "Identity account for {} migrated to version {}",
+        ctx.accounts.agent_address.key(),
+        identity.version
+    );
+
+    Ok(())
+}
+
+// ==================== MIGRATE STAKING POOL ====================
+
+#[derive(Accounts)]
+pub struct MigrateStakingPool<'info> {
+    /// CHECK: Legacy-sized account; owner and discriminator are checked in the handler
+    #[account(
+        mut,
+        seeds = [StakingPool::SEED_PREFIX],
+        bump
+    )]
+    pub staking_pool: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Resize a legacy StakingPool and set the slash treasury, appeal window and trust weights (permissionless)
+/// Category minimums start unset, so every category uses the global minimum.
+pub fn migrate_staking_pool(ctx: Context<MigrateStakingPool>) -> Result<()> {
+    let account = ctx.accounts.staking_pool.to_account_info();
+    check_legacy_account(&account, StakingPool::DISCRIMINATOR)?;
+
+    require!(
+        account.data_len() >= StakingPool::LEGACY_LEN,
+        MigrationError::InvalidMigrationTarget
+    );
+    if account.data_len() >= StakingPool::LEN {
+        msg!("Staking pool already migrated");
+        return Ok(());
+    }
+    // Pools already carrying escrow and trust settings only gain the zeroed
+    // category minimums; their configured values must survive the resize
+    let has_trust_config = account.data_len() >= StakingPool::PRE_CATEGORY_LEN;
+
+    grow_account(
+        &account,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        StakingPool::LEN,
+    )?;
+
+    // A zeroed treasury would strand released slashes; default to the authority
+    let mut pool = StakingPool::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    if !has_trust_config {
+        pool.treasury = pool.authority;
+        pool.slash_appeal_window = DEFAULT_SLASH_APPEAL_WINDOW;
+        pool.trust_stake_weight = DEFAULT_TRUST_STAKE_WEIGHT;
+        pool.trust_age_weight = DEFAULT_TRUST_AGE_WEIGHT;
+        pool.trust_slash_penalty = DEFAULT_TRUST_SLASH_PENALTY;
+    }
+    pool.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    msg!("Staking pool migrated (treasury {})", pool.treasury);
+
+    Ok(())
+}
+
+// ==================== MIGRATE PROGRAM CONFIG ====================
+
+#[derive(Accounts)]
+pub struct MigrateProgramConfig<'info> {
+    /// CHECK: Legacy-sized account; owner and discriminator are checked in the handler
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Resize a legacy ProgramConfig and set the default max pause duration and
+/// heartbeat interval (permissionless)
+pub fn migrate_program_config(ctx: Context<MigrateProgramConfig>) -> Result<()> {
+    let account = ctx.accounts.config.to_account_info();
+    check_legacy_account(&account, ProgramConfig::DISCRIMINATOR)?;
+
+    require!(
+        account.data_len() >= ProgramConfig::LEGACY_LEN,
+        MigrationError::InvalidMigrationTarget
+    );
+    if account.data_len() >= ProgramConfig::LEN {
+        msg!("Program config already migrated");
+        return Ok(());
+    }
+
+    grow_account(
+        &account,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        ProgramConfig::LEN,
+    )?;
+
+    // The zeroed pending_admin already means no transfer is pending, and the
+    // zeroed default_validity_seconds leaves identity expiry off. Both
+    // durations must be positive once set, so zero marks a field this
+    // resize just appended.
+    let mut config = ProgramConfig::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    if config.max_pause_duration_seconds == 0 {
+        config.max_pause_duration_seconds = ProgramConfig::DEFAULT_MAX_PAUSE_DURATION;
+    }
+    if config.heartbeat_interval_seconds == 0 {
+        config.heartbeat_interval_seconds = ProgramConfig::DEFAULT_HEARTBEAT_INTERVAL;
+    }
+    config.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    msg!("Program config migrated to {} bytes", ProgramConfig::LEN);
+
+    Ok(())
+}
+
+// ==================== MIGRATE USER RATE LIMIT ====================
+
+#[derive(Accounts)]
+pub struct MigrateUserRateLimit<'info> {
+    /// CHECK: Legacy-sized account; owner and discriminator are checked in the handler
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, user.key().as_ref()],
+        bump
+    )]
+    pub rate_limit: UncheckedAccount<'info>,
+
+    /// CHECK: The rate limit's user (seed only)
+    pub user: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Resize a legacy UserRateLimit; its user paid the original rent, so it becomes the payer (permissionless)
+pub fn migrate_user_rate_limit(ctx: Context<MigrateUserRateLimit>) -> Result<()> {
+    let account = ctx.accounts.rate_limit.to_account_info();
+    check_legacy_account(&account, UserRateLimit::DISCRIMINATOR)?;
+
+    require!(
+        account.data_len() >= UserRateLimit::LEGACY_LEN,
+        MigrationError::InvalidMigrationTarget
+    );
+    if account.data_len() >= UserRateLimit::LEN {
+        msg!("Rate limit account already migrated");
+        return Ok(());
+    }
+
+    grow_account(
+        &account,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        UserRateLimit::LEN,
+    )?;
+
+    let mut rate_limit = UserRateLimit::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    rate_limit.payer = rate_limit.user;
+    rate_limit.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    msg!("Rate limit account for {} migrated", rate_limit.user);
+
+    Ok(())
+}
+
+#[error_code]
+pub enum MigrationError {
+    #[msg("Account is not a valid legacy account for this migration")]
+    InvalidMigrationTarget,
+    #[msg("Account layout version is no longer supported; call migrate_account first")]
+    UnsupportedAccountVersion,
+}
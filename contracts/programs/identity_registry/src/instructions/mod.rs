@@ -1,13 +1,48 @@
+// `handler` and a few local IdentityError types repeat across modules; use their paths
+#![allow(ambiguous_glob_reexports)]
+
 pub mod register_agent;
+pub mod register_agents_batch;
 pub mod update_identity;
+pub mod update_asset;
+pub mod update_capabilities;
 pub mod verify_identity;
+pub mod verify_metadata;
+pub mod referrals;
 pub mod deactivate_agent;
+pub mod reactivate_agent;
+pub mod close_identity;
+pub mod transfer_identity;
+pub mod renew_identity;
+pub mod record_heartbeat;
+pub mod delegate;
+pub mod name;
 pub mod stake;
+pub mod delegated_stake;
+pub mod slash_appeal;
+pub mod trust_score;
 pub mod admin;
+pub mod migrate;
 
 pub use register_agent::*;
+pub use register_agents_batch::*;
 pub use update_identity::*;
+pub use update_asset::*;
+pub use update_capabilities::*;
 pub use verify_identity::*;
+pub use verify_metadata::*;
+pub use referrals::*;
 pub use deactivate_agent::*;
+pub use reactivate_agent::*;
+pub use close_identity::*;
+pub use transfer_identity::*;
+pub use renew_identity::*;
+pub use record_heartbeat::*;
+pub use delegate::*;
+pub use name::*;
 pub use stake::*;
+pub use delegated_stake::*;
+pub use slash_appeal::*;
+pub use trust_score::*;
 pub use admin::*;
+pub use migrate::*;
@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use super::admin::enforce_program_guards;
+use super::migrate::MigrationError;
+use crate::state::{AgentIdentity, ExemptAccount, NameRecord, ProgramConfig, UserRateLimit};
+
+// ==================== CLAIM NAME ====================
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct ClaimName<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent.key().as_ref()],
+        bump = agent_identity.bump,
+        constraint = agent_identity.agent_address == agent.key() @ NameError::UnauthorizedNameOwner,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// Fails to initialize when the name is already claimed
+    #[account(
+        init,
+        payer = agent,
+        space = NameRecord::LEN,
+        seeds = [NameRecord::SEED_PREFIX, name.as_bytes()],
+        bump
+    )]
+    pub name_record: Account<'info, NameRecord>,
+
+    /// Identity owner; delegates cannot claim names
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Program config PDA; unpaused while it is uninitialized
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// Caller's rate limit; the per-minute budget is enforced when supplied
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, agent.key().as_ref()],
+        bump = rate_limit.bump
+    )]
+    pub rate_limit: Option<Account<'info, UserRateLimit>>,
+
+    /// CHECK: Caller's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, agent.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
+}
+
+/// Claim a unique name for the agent. `name` must already be normalized
+/// (see NameRecord::is_valid_name); an agent holds at most one name.
+pub fn claim_name(ctx: Context<ClaimName>, name: String) -> Result<()> {
+    enforce_program_guards(
+        &ctx.accounts.program_config,
+        &ctx.accounts.rate_exemption,
+        ctx.accounts.rate_limit.as_mut(),
+    )?;
+
+    require!(NameRecord::is_valid_name(&name), NameError::InvalidName);
+
+    let agent_identity = &mut ctx.accounts.agent_identity;
+    require!(agent_identity.is_active, NameError::IdentityDeactivated);
+    require!(
+        agent_identity.name_record == Pubkey::default(),
+        NameError::NameAlreadyAssigned
+    );
+
+    let name_record = &mut ctx.accounts.name_record;
+    name_record.name = name;
+    name_record.agent = agent_identity.agent_address;
+    name_record.claimed_at = Clock::get()?.unix_timestamp;
+    name_record.bump = ctx.bumps.name_record;
+
+    agent_identity.name_record = name_record.key();
+
+    msg!(
+        "Name \"{}\" claimed by agent {}",
+        name_record.name,
+        name_record.agent
+    );
+
+    Ok(())
+}
+
+// ==================== RELEASE NAME ====================
+
+#[derive(Accounts)]
+pub struct ReleaseName<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent.key().as_ref()],
+        bump = agent_identity.bump,
+        constraint = agent_identity.name_record == name_record.key() @ NameError::NotNameOwner,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [NameRecord::SEED_PREFIX, name_record.name.as_bytes()],
+        bump = name_record.bump,
+        constraint = name_record.agent == agent.key() @ NameError::NotNameOwner,
+        close = agent
+    )]
+    pub name_record: Account<'info, NameRecord>,
+
+    /// Identity owner; receives the name record's rent
+    #[account(mut)]
+    pub agent: Signer<'info>,
+}
+
+/// Give up the agent's name so anyone can claim it again
+pub fn release_name(ctx: Context<ReleaseName>) -> Result<()> {
+    ctx.accounts.agent_identity.name_record = Pubkey::default();
+
+    msg!(
+        "Name \"{}\" released by agent {}",
+        ctx.accounts.name_record.name,
+        ctx.accounts.agent.key()
+    );
+
+    Ok(())
+}
+
+// ==================== TRANSFER NAME ====================
+
+#[derive(Accounts)]
+pub struct TransferName<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent.key().as_ref()],
+        bump = agent_identity.bump,
+        constraint = agent_identity.name_record == name_record.key() @ NameError::NotNameOwner,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [NameRecord::SEED_PREFIX, name_record.name.as_bytes()],
+        bump = name_record.bump,
+        constraint = name_record.agent == agent.key() @ NameError::NotNameOwner
+    )]
+    pub name_record: Account<'info, NameRecord>,
+
+    /// Receiving agent's identity; must not hold a name yet
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, new_agent_identity.agent_address.as_ref()],
+        bump = new_agent_identity.bump,
+        constraint = new_agent_identity.name_record == Pubkey::default() @ NameError::NameAlreadyAssigned,
+        constraint = new_agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub new_agent_identity: Account<'info, AgentIdentity>,
+
+    /// Current holder of the name
+    pub agent: Signer<'info>,
+}
+
+/// Hand the agent's name to another registered agent (current holder only)
+pub fn transfer_name(ctx: Context<TransferName>) -> Result<()> {
+    let name_record = &mut ctx.accounts.name_record;
+    let new_agent_identity = &mut ctx.accounts.new_agent_identity;
+
+    name_record.agent = new_agent_identity.agent_address;
+    name_record.claimed_at = Clock::get()?.unix_timestamp;
+    new_agent_identity.name_record = name_record.key();
+    ctx.accounts.agent_identity.name_record = Pubkey::default();
+
+    msg!(
+        "Name \"{}\" transferred from {} to {}",
+        name_record.name,
+        ctx.accounts.agent.key(),
+        name_record.agent
+    );
+
+    Ok(())
+}
+
+#[error_code]
+pub enum NameError {
+    #[msg("Unauthorized: signer is not the agent owner")]
+    UnauthorizedNameOwner,
+    #[msg("Names are 3-32 characters of a-z, 0-9 and inner dashes")]
+    InvalidName,
+    #[msg("Identity is deactivated and cannot claim a name")]
+    IdentityDeactivated,
+    #[msg("Agent already holds a name")]
+    NameAlreadyAssigned,
+    #[msg("Signer does not hold this name")]
+    NotNameOwner,
+}
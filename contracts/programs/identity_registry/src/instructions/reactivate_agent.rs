@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use super::migrate::MigrationError;
+use crate::events::AgentReactivated;
+use crate::state::{AgentIdentity, ProgramConfig};
+
+#[derive(Accounts)]
+pub struct ReactivateAgent<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
+        bump = agent_identity.bump,
+        has_one = agent_address @ IdentityError::UnauthorizedReactivation,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// CHECK: This is the agent_address stored in agent_identity
+    pub agent_address: UncheckedAccount<'info>,
+
+    /// Program config; required only when the admin reactivates
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Option<Account<'info, ProgramConfig>>,
+
+    /// The agent owner (after the cooldown) or the ProgramConfig admin (anytime)
+    pub signer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ReactivateAgent>) -> Result<()> {
+    require!(
+        !ctx.accounts.agent_identity.is_active,
+        IdentityError::AlreadyActive
+    );
+
+    let signer = ctx.accounts.signer.key();
+    let clock = Clock::get()?;
+
+    let is_admin = ctx
+        .accounts
+        .config
+        .as_ref()
+        .is_some_and(|config| config.admin == signer);
+    let is_owner = signer == ctx.accounts.agent_identity.agent_address;
+    require!(is_admin || is_owner, IdentityError::UnauthorizedReactivation);
+
+    if !is_admin {
+        require!(
+            clock.unix_timestamp >= ctx.accounts.agent_identity.reactivation_available_at(),
+            IdentityError::ReactivationCooldown
+        );
+    }
+
+    let agent_identity = &mut ctx.accounts.agent_identity;
+    let deactivated_at = agent_identity.deactivated_at;
+
+    agent_identity.is_active = true;
+    agent_identity.deactivated_at = 0;
+    agent_identity.deactivated_by = Pubkey::default();
+    agent_identity.deactivation_reason_hash = [0; 32];
+    agent_identity.last_active_timestamp = clock.unix_timestamp;
+    agent_identity.activity_count = agent_identity.activity_count.saturating_add(1);
+
+    emit!(AgentReactivated {
+        agent: agent_identity.agent_address,
+        reactivated_by: signer,
+        admin_override: is_admin,
+        deactivated_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Agent identity reactivated: {}", agent_identity.agent_address);
+
+    Ok(())
+}
+
+#[error_code]
+pub enum IdentityError {
+    #[msg("Identity is already active")]
+    AlreadyActive,
+    #[msg("Unauthorized: signer is neither the agent owner nor the admin")]
+    UnauthorizedReactivation,
+    #[msg("Owner reactivation is only allowed 72 hours after deactivation")]
+    ReactivationCooldown,
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use super::admin::enforce_program_guards;
+use super::migrate::MigrationError;
+use crate::state::{
+    AgentIdentity, ExemptAccount, HealthStatus, ProgramConfig, UserRateLimit,
+    DELEGATE_SCOPE_ACTIVITY,
+};
+
+#[derive(Accounts)]
+pub struct RecordHeartbeat<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
+        bump = agent_identity.bump,
+        has_one = agent_address @ HeartbeatError::UnauthorizedHeartbeat,
+        constraint = agent_identity.can_sign(&agent.key(), DELEGATE_SCOPE_ACTIVITY)
+            @ HeartbeatError::UnauthorizedHeartbeat,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// Owner, or a delegate with the activity scope
+    pub agent: Signer<'info>,
+
+    /// CHECK: This is the agent_address stored in agent_identity
+    pub agent_address: UncheckedAccount<'info>,
+
+    /// CHECK: Program config PDA; unpaused while it is uninitialized
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// Caller's rate limit; the per-minute budget is enforced when supplied
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, agent.key().as_ref()],
+        bump = rate_limit.bump
+    )]
+    pub rate_limit: Option<Account<'info, UserRateLimit>>,
+
+    /// CHECK: Caller's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, agent.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
+}
+
+/// Mark the agent as alive. Heartbeats closer together than the configured
+/// interval are rejected so activity_count cannot be inflated; `status`
+/// replaces the stored health when supplied.
+pub fn handler(ctx: Context<RecordHeartbeat>, status: Option<HealthStatus>) -> Result<()> {
+    let guards = enforce_program_guards(
+        &ctx.accounts.program_config,
+        &ctx.accounts.rate_exemption,
+        ctx.accounts.rate_limit.as_mut(),
+    )?;
+
+    let agent_identity = &mut ctx.accounts.agent_identity;
+    require!(agent_identity.is_active, HeartbeatError::IdentityDeactivated);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        agent_identity.last_heartbeat_at == 0
+            || now >= agent_identity.last_heartbeat_at.saturating_add(guards.heartbeat_interval_seconds),
+        HeartbeatError::HeartbeatTooSoon
+    );
+
+    agent_identity.last_heartbeat_at = now;
+    agent_identity.last_active_timestamp = now;
+    agent_identity.activity_count = agent_identity.activity_count.saturating_add(1);
+    if let Some(status) = status {
+        agent_identity.health_status = status;
+    }
+
+    msg!(
+        "Heartbeat from {} ({:?})",
+        agent_identity.agent_address,
+        agent_identity.health_status
+    );
+
+    Ok(())
+}
+
+#[error_code]
+pub enum HeartbeatError {
+    #[msg("Unauthorized: signer is not the agent owner or an activity delegate")]
+    UnauthorizedHeartbeat,
+    #[msg("Identity is deactivated")]
+    IdentityDeactivated,
+    #[msg("Heartbeat sent before the minimum interval elapsed")]
+    HeartbeatTooSoon,
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use super::migrate::MigrationError;
+use crate::state::{AgentIdentity, ReferralSummary};
+
+#[derive(Accounts)]
+pub struct GetReferrals<'info> {
+    #[account(
+        seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
+        bump = agent_identity.bump,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// CHECK: The agent address whose referrals are returned
+    pub agent_address: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<GetReferrals>) -> Result<ReferralSummary> {
+    let agent_identity = &ctx.accounts.agent_identity;
+
+    Ok(ReferralSummary {
+        referrer: agent_identity.referrer,
+        referral_count: agent_identity.referral_count,
+    })
+}
@@ -1,7 +1,94 @@
 use anchor_lang::prelude::*;
-use crate::state::AgentIdentity;
+use super::admin::enforce_program_guards;
+use super::migrate::MigrationError;
+use crate::events::AgentRegistered;
+use crate::state::{
+    AgentIdentity, ExemptAccount, ProgramConfig, ServiceCategory, UserRateLimit,
+    KNOWN_CAPABILITIES_MASK, MPL_CORE_ASSET_V1_KEY, MPL_CORE_PROGRAM_ID,
+};
+
+/// Leading fields of a Metaplex Core AssetV1 account.
+/// Only the owner is needed, so the rest of the asset is not deserialized.
+#[derive(AnchorDeserialize)]
+struct CoreAssetPrefix {
+    key: u8,
+    owner: Pubkey,
+}
+
+/// Check that `asset` is a Metaplex Core asset owned by `agent`
+pub(crate) fn verify_core_asset(asset: &AccountInfo, agent: &Pubkey) -> Result<()> {
+    require_keys_eq!(*asset.owner, MPL_CORE_PROGRAM_ID, IdentityError::InvalidCoreAsset);
+
+    let data = asset.try_borrow_data()?;
+    let prefix = CoreAssetPrefix::deserialize(&mut &data[..])
+        .map_err(|_| error!(IdentityError::InvalidCoreAsset))?;
+    require!(
+        prefix.key == MPL_CORE_ASSET_V1_KEY,
+        IdentityError::InvalidCoreAsset
+    );
+    require_keys_eq!(prefix.owner, *agent, IdentityError::InvalidAssetOwnership);
+
+    Ok(())
+}
+
+/// Check the inputs shared by register_agent and register_agents_batch
+pub(crate) fn validate_registration(metadata_uri: &str, capabilities: u32) -> Result<()> {
+    require!(
+        metadata_uri.len() <= 200,
+        IdentityError::MetadataUriTooLong
+    );
+
+    require!(
+        capabilities & !KNOWN_CAPABILITIES_MASK == 0,
+        IdentityError::UnknownCapabilities
+    );
+
+    Ok(())
+}
+
+/// Fill in a freshly created identity and emit AgentRegistered.
+/// agent_address, bump, capabilities, service_category, referrer and
+/// transferable must already be set by the caller.
+pub(crate) fn record_registration(
+    agent_identity: &mut AgentIdentity,
+    asset_address: Pubkey,
+    metadata_uri: String,
+    metadata_hash: [u8; 32],
+    now: i64,
+    default_validity_seconds: i64,
+) {
+    agent_identity.asset_address = asset_address;
+    agent_identity.metadata_uri = metadata_uri;
+    agent_identity.metadata_hash = metadata_hash;
+    agent_identity.metadata_version = 1;
+    agent_identity.registration_timestamp = now;
+    agent_identity.last_active_timestamp = now;
+    agent_identity.activity_count = 1;
+    agent_identity.is_active = true;
+    agent_identity.deactivated_at = 0;
+    agent_identity.pending_unstake_amount = 0;
+    agent_identity.expires_at = AgentIdentity::expiry_for(now, default_validity_seconds);
+    agent_identity.version = AgentIdentity::CURRENT_VERSION;
+
+    emit!(AgentRegistered {
+        agent: agent_identity.agent_address,
+        asset_address,
+        referrer: agent_identity.referrer,
+        metadata_uri: agent_identity.metadata_uri.clone(),
+        metadata_hash,
+        capabilities: agent_identity.capabilities,
+        service_category: agent_identity.service_category,
+        transferable: agent_identity.transferable,
+        expires_at: agent_identity.expires_at,
+        timestamp: now,
+    });
+
+    msg!("Agent identity registered: {}", agent_identity.agent_address);
+    msg!("NFT asset address: {}", asset_address);
+}
 
 #[derive(Accounts)]
+#[instruction(asset_address: Pubkey)]
 pub struct RegisterAgent<'info> {
     #[account(
         init,
@@ -15,33 +102,93 @@ pub struct RegisterAgent<'info> {
     #[account(mut)]
     pub agent: Signer<'info>,
 
+    /// CHECK: Metaplex Core asset; program owner and asset owner are checked in the handler
+    #[account(address = asset_address @ IdentityError::AssetAddressMismatch)]
+    pub asset: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
+
+    /// Referring agent's identity; required when `referrer` is set
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, referrer_identity.agent_address.as_ref()],
+        bump = referrer_identity.bump,
+        constraint = referrer_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub referrer_identity: Option<Account<'info, AgentIdentity>>,
+
+    /// CHECK: Program config PDA; unpaused while it is uninitialized
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// Caller's rate limit; the per-minute budget is enforced when supplied
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, agent.key().as_ref()],
+        bump = rate_limit.bump
+    )]
+    pub rate_limit: Option<Account<'info, UserRateLimit>>,
+
+    /// CHECK: Caller's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, agent.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
 }
 
 pub fn handler(
     ctx: Context<RegisterAgent>,
     asset_address: Pubkey,
     metadata_uri: String,
+    metadata_hash: [u8; 32],
+    capabilities: u32,
+    service_category: Option<ServiceCategory>,
+    referrer: Option<Pubkey>,
+    transferable: bool,
 ) -> Result<()> {
-    require!(
-        metadata_uri.len() <= 200,
-        IdentityError::MetadataUriTooLong
-    );
+    let guards = enforce_program_guards(
+        &ctx.accounts.program_config,
+        &ctx.accounts.rate_exemption,
+        ctx.accounts.rate_limit.as_mut(),
+    )?;
+
+    validate_registration(&metadata_uri, capabilities)?;
+
+    verify_core_asset(&ctx.accounts.asset, &ctx.accounts.agent.key())?;
 
-    let agent_identity = &mut ctx.accounts.agent_identity;
     let clock = Clock::get()?;
 
+    match (referrer, ctx.accounts.referrer_identity.as_mut()) {
+        (Some(referrer), Some(referrer_identity)) => {
+            require!(
+                referrer != ctx.accounts.agent.key()
+                    && referrer_identity.agent_address == referrer,
+                IdentityError::InvalidReferrer
+            );
+            require!(
+                referrer_identity.is_current(clock.unix_timestamp),
+                IdentityError::ReferrerNotActive
+            );
+            referrer_identity.referral_count = referrer_identity.referral_count.saturating_add(1);
+        }
+        (None, None) => {}
+        _ => return err!(IdentityError::InvalidReferrer),
+    }
+
+    let agent_identity = &mut ctx.accounts.agent_identity;
+
     agent_identity.agent_address = ctx.accounts.agent.key();
-    agent_identity.asset_address = asset_address;
-    agent_identity.metadata_uri = metadata_uri;
-    agent_identity.registration_timestamp = clock.unix_timestamp;
-    agent_identity.last_active_timestamp = clock.unix_timestamp;
-    agent_identity.activity_count = 1;
-    agent_identity.is_active = true;
     agent_identity.bump = ctx.bumps.agent_identity;
-
-    msg!("Agent identity registered: {}", ctx.accounts.agent.key());
-    msg!("NFT asset address: {}", asset_address);
+    agent_identity.capabilities = capabilities;
+    agent_identity.service_category = service_category;
+    agent_identity.referrer = referrer.unwrap_or_default();
+    agent_identity.transferable = transferable;
+    record_registration(
+        agent_identity,
+        asset_address,
+        metadata_uri,
+        metadata_hash,
+        clock.unix_timestamp,
+        guards.default_validity_seconds,
+    );
 
     Ok(())
 }
@@ -50,4 +197,16 @@ pub fn handler(
 pub enum IdentityError {
     #[msg("Metadata URI exceeds maximum length of 200 characters")]
     MetadataUriTooLong,
+    #[msg("Asset account does not match asset_address")]
+    AssetAddressMismatch,
+    #[msg("Account is not a Metaplex Core asset")]
+    InvalidCoreAsset,
+    #[msg("Metaplex Core asset is not owned by the agent")]
+    InvalidAssetOwnership,
+    #[msg("Capabilities contain bits unknown to this program version")]
+    UnknownCapabilities,
+    #[msg("Referrer must be another agent whose identity is passed as referrer_identity")]
+    InvalidReferrer,
+    #[msg("Referrer identity is not active")]
+    ReferrerNotActive,
 }
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+use super::admin::enforce_program_guards;
+use super::register_agent::{record_registration, validate_registration, verify_core_asset};
+use crate::state::{
+    AgentIdentity, BatchRegistration, ExemptAccount, ProgramConfig, UserRateLimit,
+    MAX_BATCH_REGISTRATIONS,
+};
+
+/// Pass three remaining accounts per entry, in entry order:
+/// the agent wallet (signer), its AgentIdentity PDA (writable) and its Core asset.
+#[derive(Accounts)]
+pub struct RegisterAgentsBatch<'info> {
+    /// Fleet operator; pays rent for every identity in the batch
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Program config PDA; unpaused while it is uninitialized
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// Payer's rate limit; the per-minute budget is enforced when supplied
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, payer.key().as_ref()],
+        bump = rate_limit.bump
+    )]
+    pub rate_limit: Option<Account<'info, UserRateLimit>>,
+
+    /// CHECK: Payer's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, payer.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
+}
+
+/// Register up to MAX_BATCH_REGISTRATIONS agents in one transaction. Each
+/// identity is created exactly as register_agent would, without capabilities,
+/// category or referrer; any failing entry aborts the whole batch.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RegisterAgentsBatch<'info>>,
+    registrations: Vec<BatchRegistration>,
+) -> Result<()> {
+    let guards = enforce_program_guards(
+        &ctx.accounts.program_config,
+        &ctx.accounts.rate_exemption,
+        ctx.accounts.rate_limit.as_mut(),
+    )?;
+
+    require!(
+        !registrations.is_empty() && registrations.len() <= MAX_BATCH_REGISTRATIONS,
+        BatchError::InvalidBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() == registrations.len() * 3,
+        BatchError::BatchAccountsMismatch
+    );
+
+    let clock = Clock::get()?;
+    let space = AgentIdentity::LEN as u64;
+    let lamports = Rent::get()?.minimum_balance(AgentIdentity::LEN);
+
+    let entries = registrations.into_iter().zip(ctx.remaining_accounts.chunks(3));
+    for (registration, accounts) in entries {
+        let (agent, identity_info, asset) = (&accounts[0], &accounts[1], &accounts[2]);
+
+        require!(agent.is_signer, BatchError::AgentMustSign);
+        require_keys_eq!(
+            *asset.key,
+            registration.asset_address,
+            BatchError::AssetAddressMismatch
+        );
+        validate_registration(&registration.metadata_uri, 0)?;
+        verify_core_asset(asset, agent.key)?;
+
+        let (expected, bump) = Pubkey::find_program_address(
+            &[AgentIdentity::SEED_PREFIX, agent.key.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(*identity_info.key, expected, BatchError::IdentityAddressMismatch);
+        require!(identity_info.data_is_empty(), BatchError::AgentAlreadyRegistered);
+
+        create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: identity_info.clone(),
+                },
+                &[&[AgentIdentity::SEED_PREFIX, agent.key.as_ref(), &[bump]]],
+            ),
+            lamports,
+            space,
+            ctx.program_id,
+        )?;
+
+        let mut agent_identity = AgentIdentity {
+            agent_address: *agent.key,
+            bump,
+            transferable: registration.transferable,
+            ..Default::default()
+        };
+        record_registration(
+            &mut agent_identity,
+            registration.asset_address,
+            registration.metadata_uri,
+            registration.metadata_hash,
+            clock.unix_timestamp,
+            guards.default_validity_seconds,
+        );
+        agent_identity.try_serialize(&mut &mut identity_info.try_borrow_mut_data()?[..])?;
+    }
+
+    Ok(())
+}
+
+#[error_code]
+pub enum BatchError {
+    #[msg("Batch must contain between 1 and MAX_BATCH_REGISTRATIONS entries")]
+    InvalidBatchSize,
+    #[msg("Expected three remaining accounts (agent, identity, asset) per entry")]
+    BatchAccountsMismatch,
+    #[msg("Every agent wallet in the batch must sign the transaction")]
+    AgentMustSign,
+    #[msg("Asset account does not match the entry's asset_address")]
+    AssetAddressMismatch,
+    #[msg("Identity account is not the agent's AgentIdentity PDA")]
+    IdentityAddressMismatch,
+    #[msg("Agent already has a registered identity")]
+    AgentAlreadyRegistered,
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use super::admin::enforce_program_guards;
+use super::migrate::MigrationError;
+use crate::state::{AgentIdentity, ExemptAccount, ProgramConfig, UserRateLimit};
+
+#[derive(Accounts)]
+pub struct RenewIdentity<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent.key().as_ref()],
+        bump = agent_identity.bump,
+        constraint = agent_identity.agent_address == agent.key() @ RenewError::UnauthorizedRenewal,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// Identity owner
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    /// CHECK: Program config PDA; unpaused while it is uninitialized
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// Caller's rate limit; the per-minute budget is enforced when supplied
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, agent.key().as_ref()],
+        bump = rate_limit.bump
+    )]
+    pub rate_limit: Option<Account<'info, UserRateLimit>>,
+
+    /// CHECK: Caller's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, agent.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
+}
+
+/// Restart the identity's validity period from now. Works on expired
+/// identities too, keeping their history; with expiry disabled in the
+/// config this clears expires_at instead.
+pub fn handler(ctx: Context<RenewIdentity>) -> Result<()> {
+    let guards = enforce_program_guards(
+        &ctx.accounts.program_config,
+        &ctx.accounts.rate_exemption,
+        ctx.accounts.rate_limit.as_mut(),
+    )?;
+
+    let agent_identity = &mut ctx.accounts.agent_identity;
+    let clock = Clock::get()?;
+
+    agent_identity.expires_at =
+        AgentIdentity::expiry_for(clock.unix_timestamp, guards.default_validity_seconds);
+    agent_identity.last_active_timestamp = clock.unix_timestamp;
+    agent_identity.activity_count = agent_identity.activity_count.saturating_add(1);
+
+    msg!(
+        "Identity for {} renewed until {}",
+        agent_identity.agent_address,
+        agent_identity.expires_at
+    );
+
+    Ok(())
+}
+
+#[error_code]
+pub enum RenewError {
+    #[msg("Unauthorized: signer is not the agent owner")]
+    UnauthorizedRenewal,
+}
@@ -0,0 +1,283 @@
+use anchor_lang::prelude::*;
+
+use super::stake::StakingError;
+use super::migrate::MigrationError;
+use crate::state::{AgentIdentity, SlashRecord, SlashStatus, StakingPool, MAX_SLASH_APPEAL_WINDOW};
+
+/// Move `amount` escrowed lamports out of a slash record.
+/// Only the escrow is moved; the record keeps its rent.
+fn release_escrow(record: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    **record.try_borrow_mut_lamports()? = record
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+// ============================================================================
+// APPEAL SLASH (Agent)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct AppealSlash<'info> {
+    #[account(
+        mut,
+        seeds = [
+            SlashRecord::SEED_PREFIX,
+            agent.key().as_ref(),
+            slash_record.slash_index.to_le_bytes().as_ref()
+        ],
+        bump = slash_record.bump,
+        constraint = slash_record.agent == agent.key() @ StakingError::UnauthorizedAppeal,
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+
+    pub agent: Signer<'info>,
+}
+
+/// Flag an escrowed slash for review before its appeal window closes
+pub fn appeal_slash(ctx: Context<AppealSlash>) -> Result<()> {
+    let record = &mut ctx.accounts.slash_record;
+    let clock = Clock::get()?;
+
+    require!(
+        record.status == SlashStatus::Escrowed,
+        StakingError::SlashNotAppealable
+    );
+    require!(
+        clock.unix_timestamp <= record.appeal_deadline,
+        StakingError::AppealWindowClosed
+    );
+
+    record.status = SlashStatus::Appealed;
+
+    msg!(
+        "Agent {} appealed slash {} ({} lamports)",
+        record.agent,
+        record.slash_index,
+        record.amount
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// RESOLVE APPEAL (Authority Only)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ResolveAppeal<'info> {
+    #[account(
+        mut,
+        seeds = [
+            SlashRecord::SEED_PREFIX,
+            slash_record.agent.as_ref(),
+            slash_record.slash_index.to_le_bytes().as_ref()
+        ],
+        bump = slash_record.bump,
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+
+    /// Slashed agent's identity; only needed to uphold, so a denial still
+    /// settles the escrow after the identity was closed or transferred
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, slash_record.agent.as_ref()],
+        bump = agent_identity.bump,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Option<Account<'info, AgentIdentity>>,
+
+    #[account(
+        mut,
+        seeds = [StakingPool::SEED_PREFIX],
+        bump = staking_pool.bump,
+        has_one = authority @ StakingError::UnauthorizedSlash,
+        has_one = treasury @ StakingError::InvalidTreasury,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Must match staking_pool.treasury (enforced by has_one)
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+/// Settle an appealed slash. Upholding the appeal returns the escrow to the
+/// agent's stake; denying it releases the escrow to the treasury.
+pub fn resolve_appeal(ctx: Context<ResolveAppeal>, uphold: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.slash_record.status == SlashStatus::Appealed,
+        StakingError::SlashNotAppealed
+    );
+
+    let amount = ctx.accounts.slash_record.amount;
+    let record_info = ctx.accounts.slash_record.to_account_info();
+
+    if uphold {
+        let agent_identity = ctx
+            .accounts
+            .agent_identity
+            .as_mut()
+            .ok_or(StakingError::AppealIdentityRequired)?;
+        release_escrow(&record_info, &ctx.accounts.staking_pool.to_account_info(), amount)?;
+
+        let staking_pool = &mut ctx.accounts.staking_pool;
+
+        // Backers get back the part that was taken from delegated stake. Once
+        // every backer has left no share can claim it, and crediting it to the
+        // delegated pool would hand it to the next backer, so it goes to the agent
+        let delegated_amount = if agent_identity.delegated_shares == 0 {
+            0
+        } else {
+            ctx.accounts.slash_record.delegated_amount.min(amount)
+        };
+        let own_amount = amount - delegated_amount;
+
+        if own_amount > 0 && agent_identity.staked_amount == 0 {
+            staking_pool.total_stakers = staking_pool.total_stakers.saturating_add(1);
+        }
+        agent_identity.staked_amount = agent_identity
+            .staked_amount
+            .checked_add(own_amount)
+            .ok_or(StakingError::ArithmeticOverflow)?;
+        agent_identity.delegated_stake = agent_identity
+            .delegated_stake
+            .checked_add(delegated_amount)
+            .ok_or(StakingError::ArithmeticOverflow)?;
+        agent_identity.total_slashed = agent_identity.total_slashed.saturating_sub(amount);
+
+        staking_pool.total_staked = staking_pool
+            .total_staked
+            .checked_add(amount)
+            .ok_or(StakingError::ArithmeticOverflow)?;
+        staking_pool.total_slashed = staking_pool.total_slashed.saturating_sub(amount);
+
+        ctx.accounts.slash_record.status = SlashStatus::Returned;
+    } else {
+        release_escrow(&record_info, &ctx.accounts.treasury.to_account_info(), amount)?;
+        ctx.accounts.slash_record.status = SlashStatus::Released;
+    }
+    ctx.accounts.slash_record.resolved_at = clock.unix_timestamp;
+
+    msg!(
+        "Appeal on slash {} for agent {} {}: {} lamports {}",
+        ctx.accounts.slash_record.slash_index,
+        ctx.accounts.slash_record.agent,
+        if uphold { "upheld" } else { "denied" },
+        amount,
+        if uphold { "returned to stake" } else { "released to treasury" }
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// FINALIZE SLASH (Permissionless)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct FinalizeSlash<'info> {
+    #[account(
+        mut,
+        seeds = [
+            SlashRecord::SEED_PREFIX,
+            slash_record.agent.as_ref(),
+            slash_record.slash_index.to_le_bytes().as_ref()
+        ],
+        bump = slash_record.bump,
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+
+    #[account(
+        seeds = [StakingPool::SEED_PREFIX],
+        bump = staking_pool.bump,
+        has_one = treasury @ StakingError::InvalidTreasury,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    /// CHECK: Must match staking_pool.treasury (enforced by has_one)
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+/// Release an unappealed slash to the treasury once its appeal window has closed
+pub fn finalize_slash(ctx: Context<FinalizeSlash>) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.slash_record.status == SlashStatus::Escrowed,
+        StakingError::SlashNotAppealable
+    );
+    require!(
+        clock.unix_timestamp > ctx.accounts.slash_record.appeal_deadline,
+        StakingError::AppealWindowActive
+    );
+
+    let amount = ctx.accounts.slash_record.amount;
+    release_escrow(
+        &ctx.accounts.slash_record.to_account_info(),
+        &ctx.accounts.treasury.to_account_info(),
+        amount,
+    )?;
+
+    let record = &mut ctx.accounts.slash_record;
+    record.status = SlashStatus::Released;
+    record.resolved_at = clock.unix_timestamp;
+
+    msg!(
+        "Slash {} for agent {} finalized: {} lamports released to treasury",
+        record.slash_index,
+        record.agent,
+        amount
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// UPDATE SLASH CONFIG (Authority Only)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct UpdateSlashConfig<'info> {
+    #[account(
+        mut,
+        seeds = [StakingPool::SEED_PREFIX],
+        bump = staking_pool.bump,
+        has_one = authority @ StakingError::UnauthorizedSlash,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set the treasury that receives released slashes and the appeal window
+/// applied to future slashes
+pub fn update_slash_config(
+    ctx: Context<UpdateSlashConfig>,
+    treasury: Pubkey,
+    appeal_window_seconds: i64,
+) -> Result<()> {
+    require!(
+        (0..=MAX_SLASH_APPEAL_WINDOW).contains(&appeal_window_seconds),
+        StakingError::InvalidAppealWindow
+    );
+
+    let staking_pool = &mut ctx.accounts.staking_pool;
+    staking_pool.treasury = treasury;
+    staking_pool.slash_appeal_window = appeal_window_seconds;
+
+    msg!(
+        "Slash config updated: treasury {}, appeal window {}s",
+        treasury,
+        appeal_window_seconds
+    );
+
+    Ok(())
+}
@@ -1,7 +1,33 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
+use solana_sha256_hasher::hash;
+
+use super::admin::enforce_program_guards;
+use super::migrate::MigrationError;
+use crate::events::{AgentSlashed, CollateralStaked, CollateralUnstaked, StakingPaused, UnstakeRequested};
+use crate::state::{
+    AgentIdentity, ExemptAccount, ProgramConfig, SlashRecord, SlashStatus, StakingPool, UserRateLimit,
+    DEFAULT_SLASH_APPEAL_WINDOW, DELEGATE_SCOPE_STAKE, DEFAULT_TRUST_AGE_WEIGHT, DEFAULT_TRUST_SLASH_PENALTY,
+    DEFAULT_TRUST_STAKE_WEIGHT, MAX_SLASH_HISTORY_PAGE, MAX_SLASH_REASON_LEN, MIN_STAKE_AMOUNT,
+    REPUTATION_MULTISIG_SEED, REPUTATION_REGISTRY_PROGRAM_ID, SLASH_RECORD_RETENTION_PERIOD,
+    STAKE_UNLOCK_PERIOD, ServiceCategory,
+};
+
+/// Check that the pool PDA can pay out `amount` lamports and stay rent-exempt.
+/// Bookkeeping (total_staked) can drift from the real balance, so the
+/// account's lamports are checked directly before any debit.
+pub(crate) fn check_pool_debit(staking_pool: &AccountInfo, amount: u64) -> Result<()> {
+    let balance = staking_pool.lamports();
+    require!(amount <= balance, StakingError::InsufficientPoolLamports);
+
+    let rent_exempt = Rent::get()?.minimum_balance(StakingPool::LEN);
+    require!(
+        balance - amount >= rent_exempt,
+        StakingError::PoolBelowRentExempt
+    );
 
-use crate::state::{AgentIdentity, StakingPool, ProgramConfig, MIN_STAKE_AMOUNT, STAKE_UNLOCK_PERIOD};
+    Ok(())
+}
 
 // ============================================================================
 // STAKE COLLATERAL
@@ -11,9 +37,12 @@ use crate::state::{AgentIdentity, StakingPool, ProgramConfig, MIN_STAKE_AMOUNT,
 pub struct StakeCollateral<'info> {
     #[account(
         mut,
-        seeds = [AgentIdentity::SEED_PREFIX, agent.key().as_ref()],
+        seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
         bump = agent_identity.bump,
         has_one = agent_address @ StakingError::UnauthorizedAgent,
+        constraint = agent_identity.can_sign(&agent.key(), DELEGATE_SCOPE_STAKE)
+            @ StakingError::UnauthorizedAgent,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
     )]
     pub agent_identity: Account<'info, AgentIdentity>,
 
@@ -25,7 +54,7 @@ pub struct StakeCollateral<'info> {
     )]
     pub staking_pool: Account<'info, StakingPool>,
 
-    /// CHECK: This is the agent's wallet that must sign
+    /// Owner, or a delegate with the stake scope; pays the stake
     #[account(mut)]
     pub agent: Signer<'info>,
 
@@ -33,16 +62,42 @@ pub struct StakeCollateral<'info> {
     pub agent_address: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Program config PDA; unpaused while it is uninitialized
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// Caller's rate limit; the per-minute budget is enforced when supplied
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, agent.key().as_ref()],
+        bump = rate_limit.bump
+    )]
+    pub rate_limit: Option<Account<'info, UserRateLimit>>,
+
+    /// CHECK: Caller's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, agent.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
 }
 
 /// Stake SOL as collateral for an agent identity
 pub fn stake_collateral(ctx: Context<StakeCollateral>, amount: u64) -> Result<()> {
+    enforce_program_guards(
+        &ctx.accounts.program_config,
+        &ctx.accounts.rate_exemption,
+        ctx.accounts.rate_limit.as_mut(),
+    )?;
+
     let agent_identity = &mut ctx.accounts.agent_identity;
     let staking_pool = &mut ctx.accounts.staking_pool;
     let clock = Clock::get()?;
 
-    // Validate minimum stake
-    let effective_min = staking_pool.min_stake_amount.max(MIN_STAKE_AMOUNT);
+    // Deactivated agents may only unwind existing stake
+    require!(agent_identity.is_active, StakingError::AgentDeactivated);
+
+    // Validate minimum stake against the agent's category floor. Only new
+    // deposits are checked, so raising a floor never touches existing stake.
+    let effective_min = staking_pool.min_stake_for(agent_identity.service_category);
     require!(amount >= effective_min, StakingError::BelowMinimumStake);
 
     // Check if this is first stake
@@ -64,17 +119,6 @@ pub fn stake_collateral(ctx: Context<StakeCollateral>, amount: u64) -> Result<()
         .checked_add(amount)
         .ok_or(StakingError::ArithmeticOverflow)?;
 
-    // Set unlock timestamp (must wait unlock_period to unstake)
-    let unlock_period = if staking_pool.unlock_period > 0 {
-        staking_pool.unlock_period
-    } else {
-        STAKE_UNLOCK_PERIOD
-    };
-    agent_identity.stake_unlock_timestamp = clock
-        .unix_timestamp
-        .checked_add(unlock_period)
-        .ok_or(StakingError::ArithmeticOverflow)?;
-
     // Update activity timestamp
     agent_identity.last_active_timestamp = clock.unix_timestamp;
     agent_identity.activity_count = agent_identity.activity_count.saturating_add(1);
@@ -88,6 +132,19 @@ pub fn stake_collateral(ctx: Context<StakeCollateral>, amount: u64) -> Result<()
     if !was_staker {
         staking_pool.total_stakers = staking_pool.total_stakers.saturating_add(1);
     }
+    if agent_identity.first_staked_at == 0 {
+        agent_identity.first_staked_at = clock.unix_timestamp;
+    }
+
+    emit!(CollateralStaked {
+        agent: agent_identity.agent_address,
+        staked_by: ctx.accounts.agent.key(),
+        amount,
+        staked_amount: agent_identity.staked_amount,
+        first_staked_at: agent_identity.first_staked_at,
+        pool_total_staked: staking_pool.total_staked,
+        timestamp: clock.unix_timestamp,
+    });
 
     msg!(
         "Staked {} lamports for agent {}. Total staked: {}",
@@ -100,16 +157,118 @@ pub fn stake_collateral(ctx: Context<StakeCollateral>, amount: u64) -> Result<()
 }
 
 // ============================================================================
-// UNSTAKE COLLATERAL
+// REQUEST UNSTAKE
 // ============================================================================
 
 #[derive(Accounts)]
-pub struct UnstakeCollateral<'info> {
+pub struct RequestUnstake<'info> {
     #[account(
         mut,
         seeds = [AgentIdentity::SEED_PREFIX, agent.key().as_ref()],
         bump = agent_identity.bump,
         has_one = agent_address @ StakingError::UnauthorizedAgent,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [StakingPool::SEED_PREFIX],
+        bump = staking_pool.bump,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    /// CHECK: This is the agent's wallet that must sign
+    pub agent: Signer<'info>,
+
+    /// CHECK: Validated by seed constraint
+    pub agent_address: UncheckedAccount<'info>,
+
+    /// CHECK: Program config PDA; unpaused while it is uninitialized
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// Caller's rate limit; the per-minute budget is enforced when supplied
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, agent.key().as_ref()],
+        bump = rate_limit.bump
+    )]
+    pub rate_limit: Option<Account<'info, UserRateLimit>>,
+
+    /// CHECK: Caller's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, agent.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
+}
+
+/// Start the unlock cooldown for part of the stake.
+/// Requests stack; each one restarts the cooldown for the whole pending amount.
+pub fn request_unstake(ctx: Context<RequestUnstake>, amount: u64) -> Result<()> {
+    enforce_program_guards(
+        &ctx.accounts.program_config,
+        &ctx.accounts.rate_exemption,
+        ctx.accounts.rate_limit.as_mut(),
+    )?;
+
+    let agent_identity = &mut ctx.accounts.agent_identity;
+    let staking_pool = &ctx.accounts.staking_pool;
+    let clock = Clock::get()?;
+
+    require!(amount > 0, StakingError::InvalidUnstakeAmount);
+    require!(
+        agent_identity.active_stake() >= amount,
+        StakingError::InsufficientStake
+    );
+
+    agent_identity.pending_unstake_amount = agent_identity
+        .pending_unstake_amount
+        .checked_add(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    let unlock_period = if staking_pool.unlock_period > 0 {
+        staking_pool.unlock_period
+    } else {
+        STAKE_UNLOCK_PERIOD
+    };
+    agent_identity.stake_unlock_timestamp = clock
+        .unix_timestamp
+        .checked_add(unlock_period)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    // Update activity timestamp
+    agent_identity.last_active_timestamp = clock.unix_timestamp;
+    agent_identity.activity_count = agent_identity.activity_count.saturating_add(1);
+
+    emit!(UnstakeRequested {
+        agent: agent_identity.agent_address,
+        amount,
+        pending_unstake_amount: agent_identity.pending_unstake_amount,
+        unlock_timestamp: agent_identity.stake_unlock_timestamp,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Unstake of {} lamports requested for agent {}. Pending: {}, withdrawable at {}",
+        amount,
+        agent_identity.agent_address,
+        agent_identity.pending_unstake_amount,
+        agent_identity.stake_unlock_timestamp
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// WITHDRAW UNSTAKED
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct WithdrawUnstaked<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent.key().as_ref()],
+        bump = agent_identity.bump,
+        has_one = agent_address @ StakingError::UnauthorizedAgent,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
     )]
     pub agent_identity: Account<'info, AgentIdentity>,
 
@@ -128,25 +287,49 @@ pub struct UnstakeCollateral<'info> {
     pub agent_address: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Program config PDA; unpaused while it is uninitialized
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// Caller's rate limit; the per-minute budget is enforced when supplied
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, agent.key().as_ref()],
+        bump = rate_limit.bump
+    )]
+    pub rate_limit: Option<Account<'info, UserRateLimit>>,
+
+    /// CHECK: Caller's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, agent.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
 }
 
-/// Unstake SOL collateral after unlock period
-pub fn unstake_collateral(ctx: Context<UnstakeCollateral>, amount: u64) -> Result<()> {
+/// Withdraw the pending unstake once its cooldown has elapsed
+pub fn withdraw_unstaked(ctx: Context<WithdrawUnstaked>) -> Result<()> {
+    enforce_program_guards(
+        &ctx.accounts.program_config,
+        &ctx.accounts.rate_exemption,
+        ctx.accounts.rate_limit.as_mut(),
+    )?;
+
     let agent_identity = &mut ctx.accounts.agent_identity;
     let staking_pool = &mut ctx.accounts.staking_pool;
     let clock = Clock::get()?;
 
-    // Validate unlock period has passed
+    require!(
+        agent_identity.pending_unstake_amount > 0,
+        StakingError::NoPendingUnstake
+    );
     require!(
         agent_identity.can_unlock_stake(clock.unix_timestamp),
         StakingError::StakeLocked
     );
 
-    // Validate sufficient stake
-    require!(
-        agent_identity.staked_amount >= amount,
-        StakingError::InsufficientStake
-    );
+    // Slashing may have reduced the stake below the requested amount
+    let amount = agent_identity
+        .pending_unstake_amount
+        .min(agent_identity.staked_amount);
 
     // Calculate remaining stake after unstake
     let remaining_stake = agent_identity
@@ -154,14 +337,10 @@ pub fn unstake_collateral(ctx: Context<UnstakeCollateral>, amount: u64) -> Resul
         .checked_sub(amount)
         .ok_or(StakingError::ArithmeticOverflow)?;
 
-    // Transfer SOL from staking pool PDA to agent
-    // Use invoke_signed with PDA seeds
-    let pool_seeds = &[
-        StakingPool::SEED_PREFIX,
-        &[staking_pool.bump],
-    ];
-    let signer_seeds = &[&pool_seeds[..]];
+    check_pool_debit(&staking_pool.to_account_info(), amount)?;
 
+    // Transfer SOL from staking pool PDA to agent
+    // The pool is program-owned, so lamports can be moved directly
     **staking_pool.to_account_info().try_borrow_mut_lamports()? = staking_pool
         .to_account_info()
         .lamports()
@@ -178,11 +357,13 @@ pub fn unstake_collateral(ctx: Context<UnstakeCollateral>, amount: u64) -> Resul
 
     // Update agent identity
     agent_identity.staked_amount = remaining_stake;
+    agent_identity.pending_unstake_amount = 0;
+    agent_identity.stake_unlock_timestamp = 0;
 
-    // Reset unlock timestamp if fully unstaked
     if remaining_stake == 0 {
-        agent_identity.stake_unlock_timestamp = 0;
         staking_pool.total_stakers = staking_pool.total_stakers.saturating_sub(1);
+        // A later stake starts a new stake age
+        agent_identity.first_staked_at = 0;
     }
 
     // Update activity timestamp
@@ -195,8 +376,16 @@ pub fn unstake_collateral(ctx: Context<UnstakeCollateral>, amount: u64) -> Resul
         .checked_sub(amount)
         .ok_or(StakingError::ArithmeticOverflow)?;
 
+    emit!(CollateralUnstaked {
+        agent: agent_identity.agent_address,
+        amount,
+        staked_amount: agent_identity.staked_amount,
+        pool_total_staked: staking_pool.total_staked,
+        timestamp: clock.unix_timestamp,
+    });
+
     msg!(
-        "Unstaked {} lamports for agent {}. Remaining: {}",
+        "Withdrew {} lamports for agent {}. Remaining: {}",
         amount,
         agent_identity.agent_address,
         agent_identity.staked_amount
@@ -215,6 +404,7 @@ pub struct SlashAgent<'info> {
         mut,
         seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
         bump = agent_identity.bump,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
     )]
     pub agent_identity: Account<'info, AgentIdentity>,
 
@@ -226,30 +416,75 @@ pub struct SlashAgent<'info> {
     )]
     pub staking_pool: Account<'info, StakingPool>,
 
+    /// Record of this slash and escrow for the slashed lamports;
+    /// indexed by the agent's slash_count before the slash
+    #[account(
+        init,
+        payer = authority,
+        space = SlashRecord::LEN,
+        seeds = [
+            SlashRecord::SEED_PREFIX,
+            agent_address.key().as_ref(),
+            agent_identity.slash_count.to_le_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+
     /// CHECK: Agent being slashed (not the signer)
     pub agent_address: UncheckedAccount<'info>,
 
-    /// Authority that can perform slashing
-    pub authority: Signer<'info>,
-
-    /// CHECK: Treasury to receive slashed funds
+    /// Authority that can perform slashing (pays for the slash record)
     #[account(mut)]
-    pub treasury: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 /// Slash an agent's stake for violations (authority only)
 /// violation_severity_bps: 0-10000 (0% to 100% severity)
+/// The slashed lamports are escrowed in the SlashRecord for the pool's
+/// appeal window; see finalize_slash and resolve_appeal.
 pub fn slash_agent(
     ctx: Context<SlashAgent>,
     violation_severity_bps: u16,
     reason: String,
 ) -> Result<()> {
-    let agent_identity = &mut ctx.accounts.agent_identity;
-    let staking_pool = &mut ctx.accounts.staking_pool;
-    let clock = Clock::get()?;
+    require!(
+        !ctx.accounts.staking_pool.multisig_slashing_only,
+        StakingError::LegacySlashingDisabled
+    );
+    require!(
+        reason.len() <= MAX_SLASH_REASON_LEN,
+        StakingError::SlashReasonTooLong
+    );
+
+    apply_slash(
+        &mut ctx.accounts.agent_identity,
+        &mut ctx.accounts.staking_pool,
+        &mut ctx.accounts.slash_record,
+        ctx.bumps.slash_record,
+        ctx.accounts.authority.key(),
+        violation_severity_bps,
+        hash(reason.as_bytes()).to_bytes(),
+    )?;
+
+    msg!("Reason: {}", reason);
 
+    Ok(())
+}
+
+/// Move the quadratic slash amount from the pool into the slash record's
+/// escrow and fill in the record. Shared by both slashing paths.
+fn apply_slash(
+    agent_identity: &mut Account<AgentIdentity>,
+    staking_pool: &mut Account<StakingPool>,
+    slash_record: &mut Account<SlashRecord>,
+    slash_record_bump: u8,
+    authority: Pubkey,
+    violation_severity_bps: u16,
+    reason_hash: [u8; 32],
+) -> Result<()> {
     // Validate severity
     require!(
         violation_severity_bps <= 10000,
@@ -261,21 +496,26 @@ pub fn slash_agent(
 
     // Ensure there's something to slash
     require!(slash_amount > 0, StakingError::NothingToSlash);
+
+    // Backers lose the same fraction of their stake as the agent
+    let delegated_amount = agent_identity.delegated_slash_share(slash_amount);
+    let own_amount = slash_amount - delegated_amount;
     require!(
-        agent_identity.staked_amount >= slash_amount,
+        agent_identity.staked_amount >= own_amount
+            && agent_identity.delegated_stake >= delegated_amount,
         StakingError::InsufficientStake
     );
 
-    // Transfer slashed funds to treasury
+    check_pool_debit(&staking_pool.to_account_info(), slash_amount)?;
+
+    // Move slashed funds into escrow in the slash record
     **staking_pool.to_account_info().try_borrow_mut_lamports()? = staking_pool
         .to_account_info()
         .lamports()
         .checked_sub(slash_amount)
         .ok_or(StakingError::ArithmeticOverflow)?;
 
-    **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? = ctx
-        .accounts
-        .treasury
+    **slash_record.to_account_info().try_borrow_mut_lamports()? = slash_record
         .to_account_info()
         .lamports()
         .checked_add(slash_amount)
@@ -284,9 +524,21 @@ pub fn slash_agent(
     // Update agent identity
     agent_identity.staked_amount = agent_identity
         .staked_amount
-        .checked_sub(slash_amount)
+        .checked_sub(own_amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    agent_identity.delegated_stake = agent_identity
+        .delegated_stake
+        .checked_sub(delegated_amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    // Pending unstakes are still collateral; the slash reaches them too
+    agent_identity.pending_unstake_amount = agent_identity
+        .pending_unstake_amount
+        .min(agent_identity.staked_amount);
+    let slash_index = agent_identity.slash_count;
+    agent_identity.slash_count = agent_identity
+        .slash_count
+        .checked_add(1)
         .ok_or(StakingError::ArithmeticOverflow)?;
-    agent_identity.slash_count = agent_identity.slash_count.saturating_add(1);
     agent_identity.total_slashed = agent_identity
         .total_slashed
         .checked_add(slash_amount)
@@ -303,16 +555,281 @@ pub fn slash_agent(
         .ok_or(StakingError::ArithmeticOverflow)?;
 
     // Check if agent is now fully unstaked
-    if agent_identity.staked_amount == 0 {
+    if own_amount > 0 && agent_identity.staked_amount == 0 {
+        agent_identity.stake_unlock_timestamp = 0;
+        agent_identity.pending_unstake_amount = 0;
         staking_pool.total_stakers = staking_pool.total_stakers.saturating_sub(1);
     }
 
+    let clock = Clock::get()?;
+    slash_record.agent = agent_identity.agent_address;
+    slash_record.slash_index = slash_index;
+    slash_record.severity_bps = violation_severity_bps;
+    slash_record.amount = slash_amount;
+    slash_record.reason_hash = reason_hash;
+    slash_record.authority = authority;
+    slash_record.timestamp = clock.unix_timestamp;
+    slash_record.bump = slash_record_bump;
+    slash_record.status = SlashStatus::Escrowed;
+    slash_record.appeal_deadline = clock
+        .unix_timestamp
+        .checked_add(staking_pool.slash_appeal_window)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    slash_record.resolved_at = 0;
+    slash_record.delegated_amount = delegated_amount;
+
+    emit!(AgentSlashed {
+        agent: agent_identity.agent_address,
+        slash_record: slash_record.key(),
+        slash_index,
+        authority,
+        severity_bps: violation_severity_bps,
+        amount: slash_amount,
+        delegated_amount,
+        staked_amount: agent_identity.staked_amount,
+        delegated_stake: agent_identity.delegated_stake,
+        pending_unstake_amount: agent_identity.pending_unstake_amount,
+        slash_count: agent_identity.slash_count,
+        reason_hash,
+        appeal_deadline: slash_record.appeal_deadline,
+        timestamp: clock.unix_timestamp,
+    });
+
     msg!(
-        "Slashed {} lamports from agent {} (severity: {}bps). Reason: {}",
+        "Slashed {} lamports from agent {} (severity: {}bps), escrowed until {}",
         slash_amount,
         agent_identity.agent_address,
         violation_severity_bps,
-        reason
+        slash_record.appeal_deadline
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// SLASH AGENT (Reputation Multisig CPI)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SlashAgentMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
+        bump = agent_identity.bump,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [StakingPool::SEED_PREFIX],
+        bump = staking_pool.bump,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    /// Record of this slash and escrow for the slashed lamports;
+    /// indexed by the agent's slash_count before the slash
+    #[account(
+        init,
+        payer = payer,
+        space = SlashRecord::LEN,
+        seeds = [
+            SlashRecord::SEED_PREFIX,
+            agent_address.key().as_ref(),
+            agent_identity.slash_count.to_le_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+
+    /// CHECK: Agent being slashed (not the signer)
+    pub agent_address: UncheckedAccount<'info>,
+
+    /// reputation_registry's multisig PDA; can only sign through invoke_signed from that program
+    #[account(
+        seeds = [REPUTATION_MULTISIG_SEED],
+        bump,
+        seeds::program = REPUTATION_REGISTRY_PROGRAM_ID
+    )]
+    pub multisig_authority: Signer<'info>,
+
+    /// Multisig executor; pays for the slash record
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Slash an agent's stake on behalf of an approved reputation multisig proposal
+/// (CPI from reputation_registry only). Same severity curve and escrow as slash_agent.
+pub fn slash_agent_multisig(
+    ctx: Context<SlashAgentMultisig>,
+    violation_severity_bps: u16,
+    reason_hash: [u8; 32],
+) -> Result<()> {
+    apply_slash(
+        &mut ctx.accounts.agent_identity,
+        &mut ctx.accounts.staking_pool,
+        &mut ctx.accounts.slash_record,
+        ctx.bumps.slash_record,
+        ctx.accounts.payer.key(),
+        violation_severity_bps,
+        reason_hash,
+    )?;
+
+    msg!("Slash authorized by multisig {}", ctx.accounts.multisig_authority.key());
+
+    Ok(())
+}
+
+// ============================================================================
+// DISABLE LEGACY SLASHING (Authority Only)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct DisableLegacySlashing<'info> {
+    #[account(
+        mut,
+        seeds = [StakingPool::SEED_PREFIX],
+        bump = staking_pool.bump,
+        has_one = authority @ StakingError::UnauthorizedSlash,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Reject slash_agent from now on so slashes need multisig quorum.
+/// One-way: a compromised authority key must not be able to switch it back.
+pub fn disable_legacy_slashing(ctx: Context<DisableLegacySlashing>) -> Result<()> {
+    ctx.accounts.staking_pool.multisig_slashing_only = true;
+    msg!("Single-authority slashing disabled; slashes now require the reputation multisig");
+    Ok(())
+}
+
+// ============================================================================
+// GET SLASH HISTORY (View)
+// ============================================================================
+
+/// One slash record returned by get_slash_history
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SlashRecordView {
+    pub slash_index: u32,
+    pub severity_bps: u16,
+    pub amount: u64,
+    pub reason_hash: [u8; 32],
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Slash history page returned to callers via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SlashHistoryView {
+    pub agent: Pubkey,
+    pub slash_count: u32,
+    pub total_slashed: u64,
+    pub records: Vec<SlashRecordView>,
+}
+
+/// Pass up to MAX_SLASH_HISTORY_PAGE SlashRecord PDAs as remaining accounts.
+/// Closed records (empty accounts) are skipped.
+#[derive(Accounts)]
+pub struct GetSlashHistory<'info> {
+    #[account(
+        seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
+        bump = agent_identity.bump,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// CHECK: The agent's wallet address
+    pub agent_address: UncheckedAccount<'info>,
+}
+
+/// Return the agent's slash totals and the passed-in slash records
+pub fn get_slash_history<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetSlashHistory<'info>>,
+) -> Result<SlashHistoryView> {
+    let agent_identity = &ctx.accounts.agent_identity;
+    require!(
+        ctx.remaining_accounts.len() <= MAX_SLASH_HISTORY_PAGE,
+        StakingError::SlashHistoryPageTooLarge
+    );
+
+    let mut records = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts {
+        if info.data_is_empty() {
+            continue;
+        }
+        let record = Account::<SlashRecord>::try_from(info)?;
+        require_keys_eq!(
+            record.agent,
+            agent_identity.agent_address,
+            StakingError::SlashRecordMismatch
+        );
+        records.push(SlashRecordView {
+            slash_index: record.slash_index,
+            severity_bps: record.severity_bps,
+            amount: record.amount,
+            reason_hash: record.reason_hash,
+            authority: record.authority,
+            timestamp: record.timestamp,
+        });
+    }
+
+    Ok(SlashHistoryView {
+        agent: agent_identity.agent_address,
+        slash_count: agent_identity.slash_count,
+        total_slashed: agent_identity.total_slashed,
+        records,
+    })
+}
+
+// ============================================================================
+// CLOSE SLASH RECORD
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CloseSlashRecord<'info> {
+    #[account(
+        mut,
+        seeds = [
+            SlashRecord::SEED_PREFIX,
+            slash_record.agent.as_ref(),
+            slash_record.slash_index.to_le_bytes().as_ref()
+        ],
+        bump = slash_record.bump,
+        has_one = authority @ StakingError::UnauthorizedSlash,
+        close = authority,
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+
+    /// CHECK: The authority that paid for the record; receives the rent (permissionless close)
+    #[account(mut)]
+    pub authority: UncheckedAccount<'info>,
+}
+
+/// Close a finalized slash record once the retention period has passed (permissionless)
+pub fn close_slash_record(ctx: Context<CloseSlashRecord>) -> Result<()> {
+    let record = &ctx.accounts.slash_record;
+    let clock = Clock::get()?;
+
+    // Escrowed funds must leave the record before its lamports go to the authority
+    require!(record.is_finalized(), StakingError::SlashNotFinalized);
+
+    let closable_at = record
+        .timestamp
+        .checked_add(SLASH_RECORD_RETENTION_PERIOD)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    require!(
+        clock.unix_timestamp >= closable_at,
+        StakingError::SlashRecordRetentionActive
+    );
+
+    msg!(
+        "Slash record {} for agent {} closed",
+        record.slash_index,
+        record.agent
     );
 
     Ok(())
@@ -351,6 +868,12 @@ pub fn initialize_staking_pool(ctx: Context<InitializeStakingPool>) -> Result<()
     staking_pool.unlock_period = STAKE_UNLOCK_PERIOD;
     staking_pool.is_paused = false;
     staking_pool.bump = ctx.bumps.staking_pool;
+    staking_pool.treasury = ctx.accounts.authority.key();
+    staking_pool.slash_appeal_window = DEFAULT_SLASH_APPEAL_WINDOW;
+    staking_pool.multisig_slashing_only = false;
+    staking_pool.trust_stake_weight = DEFAULT_TRUST_STAKE_WEIGHT;
+    staking_pool.trust_age_weight = DEFAULT_TRUST_AGE_WEIGHT;
+    staking_pool.trust_slash_penalty = DEFAULT_TRUST_SLASH_PENALTY;
 
     msg!("Staking pool initialized with authority: {}", staking_pool.authority);
 
@@ -376,18 +899,65 @@ pub struct PauseStaking<'info> {
 
 /// Pause staking (emergency)
 pub fn pause_staking(ctx: Context<PauseStaking>) -> Result<()> {
-    ctx.accounts.staking_pool.is_paused = true;
+    set_staking_paused(ctx, true)?;
     msg!("Staking paused by authority");
     Ok(())
 }
 
 /// Unpause staking
 pub fn unpause_staking(ctx: Context<PauseStaking>) -> Result<()> {
-    ctx.accounts.staking_pool.is_paused = false;
+    set_staking_paused(ctx, false)?;
     msg!("Staking unpaused by authority");
     Ok(())
 }
 
+fn set_staking_paused(ctx: Context<PauseStaking>, is_paused: bool) -> Result<()> {
+    ctx.accounts.staking_pool.is_paused = is_paused;
+    emit!(StakingPaused {
+        authority: ctx.accounts.authority.key(),
+        is_paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+// ============================================================================
+// CATEGORY MINIMUM STAKE
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetCategoryMinStake<'info> {
+    #[account(
+        mut,
+        seeds = [StakingPool::SEED_PREFIX],
+        bump = staking_pool.bump,
+        has_one = authority @ StakingError::UnauthorizedSlash,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set the minimum stake for one service category (0 reverts to the global
+/// minimum). Existing stakes are not touched; agents below a raised floor
+/// keep their stake but stop reporting has_minimum_stake.
+pub fn set_category_min_stake(
+    ctx: Context<SetCategoryMinStake>,
+    category: ServiceCategory,
+    amount: u64,
+) -> Result<()> {
+    require!(
+        amount == 0 || amount >= MIN_STAKE_AMOUNT,
+        StakingError::InvalidCategoryMinimum
+    );
+
+    ctx.accounts.staking_pool.category_min_stakes[category as usize] = amount;
+
+    msg!("Minimum stake for {:?} set to {} lamports", category, amount);
+
+    Ok(())
+}
+
 // ============================================================================
 // ERROR CODES
 // ============================================================================
@@ -420,4 +990,73 @@ pub enum StakingError {
 
     #[msg("Arithmetic overflow")]
     ArithmeticOverflow,
+
+    #[msg("Unstake amount must be greater than zero")]
+    InvalidUnstakeAmount,
+
+    #[msg("No unstake has been requested")]
+    NoPendingUnstake,
+
+    #[msg("Staking pool holds fewer lamports than the requested debit")]
+    InsufficientPoolLamports,
+
+    #[msg("Debit would leave the staking pool below its rent-exempt minimum")]
+    PoolBelowRentExempt,
+
+    #[msg("Slash reason exceeds maximum length of 200 bytes")]
+    SlashReasonTooLong,
+
+    #[msg("Too many slash records requested in one call")]
+    SlashHistoryPageTooLarge,
+
+    #[msg("Slash record does not belong to this agent")]
+    SlashRecordMismatch,
+
+    #[msg("Slash record is still within its retention period")]
+    SlashRecordRetentionActive,
+
+    #[msg("Slashed funds are still in escrow")]
+    SlashNotFinalized,
+
+    #[msg("Slash is not awaiting appeal")]
+    SlashNotAppealable,
+
+    #[msg("Appeal window has closed")]
+    AppealWindowClosed,
+
+    #[msg("Appeal window is still open")]
+    AppealWindowActive,
+
+    #[msg("Slash has not been appealed")]
+    SlashNotAppealed,
+
+    #[msg("Unauthorized: only the slashed agent can appeal")]
+    UnauthorizedAppeal,
+
+    #[msg("Treasury does not match the staking pool's configured treasury")]
+    InvalidTreasury,
+
+    #[msg("Appeal window must be between 0 and 30 days")]
+    InvalidAppealWindow,
+
+    #[msg("Single-authority slashing is disabled; slashes must go through the reputation multisig")]
+    LegacySlashingDisabled,
+
+    #[msg("Trust weights exceed the maximum trust score")]
+    InvalidTrustWeights,
+
+    #[msg("Deactivated agents cannot add stake")]
+    AgentDeactivated,
+
+    #[msg("Agents stake for themselves with stake_collateral")]
+    SelfDelegation,
+
+    #[msg("Unauthorized: not the backer of this delegated stake")]
+    UnauthorizedBacker,
+
+    #[msg("Category minimum must be zero or at least the global minimum stake")]
+    InvalidCategoryMinimum,
+
+    #[msg("Upholding an appeal requires the agent's identity")]
+    AppealIdentityRequired,
 }
@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use super::admin::enforce_program_guards;
+use super::migrate::MigrationError;
+use crate::events::IdentityTransferred;
+use crate::state::{AgentIdentity, ExemptAccount, NameRecord, ProgramConfig, UserRateLimit};
+
+/// Move a transferable identity to `new_owner`, who must co-sign so an
+/// identity can't be pushed onto a wallet that never asked for it.
+///
+/// AgentIdentity PDAs are keyed by wallet, so the identity is copied into a
+/// new PDA under `new_owner` and the old one is closed to the current owner.
+/// Stake is keyed the same way and must be fully withdrawn first. Records
+/// keyed by the old address elsewhere (reputation, votes, slash records)
+/// stay where they are; indexers follow IdentityTransferred to link them.
+#[derive(Accounts)]
+pub struct TransferIdentity<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent.key().as_ref()],
+        bump = agent_identity.bump,
+        constraint = agent_identity.agent_address == agent.key() @ TransferError::UnauthorizedTransfer,
+        close = agent,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// The identity's new PDA; fails if `new_owner` already has an identity
+    #[account(
+        init,
+        payer = agent,
+        space = AgentIdentity::LEN,
+        seeds = [AgentIdentity::SEED_PREFIX, new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_identity: Account<'info, AgentIdentity>,
+
+    /// Claimed name, required when the identity holds one; it moves with the identity
+    #[account(
+        mut,
+        seeds = [NameRecord::SEED_PREFIX, name_record.name.as_bytes()],
+        bump = name_record.bump,
+        constraint = agent_identity.name_record == name_record.key() @ TransferError::NameRecordMismatch
+    )]
+    pub name_record: Option<Account<'info, NameRecord>>,
+
+    /// Current owner; pays for the new PDA and receives the old one's rent
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    /// Wallet receiving the identity
+    pub new_owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Program config PDA; unpaused while it is uninitialized
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// Caller's rate limit; the per-minute budget is enforced when supplied
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, agent.key().as_ref()],
+        bump = rate_limit.bump
+    )]
+    pub rate_limit: Option<Account<'info, UserRateLimit>>,
+
+    /// CHECK: Caller's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, agent.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<TransferIdentity>) -> Result<()> {
+    enforce_program_guards(
+        &ctx.accounts.program_config,
+        &ctx.accounts.rate_exemption,
+        ctx.accounts.rate_limit.as_mut(),
+    )?;
+
+    let new_owner = ctx.accounts.new_owner.key();
+    let old_identity = &ctx.accounts.agent_identity;
+    let old_owner = old_identity.agent_address;
+
+    require!(old_identity.transferable, TransferError::IdentityNotTransferable);
+    require!(
+        new_owner != old_owner && new_owner != Pubkey::default(),
+        TransferError::InvalidNewOwner
+    );
+    require!(
+        old_identity.staked_amount == 0 && old_identity.stake_unlock_timestamp == 0,
+        TransferError::StakeRemaining
+    );
+    require!(
+        old_identity.delegated_shares == 0,
+        TransferError::DelegatedStakeRemaining
+    );
+    require!(
+        old_identity.name_record == Pubkey::default() || ctx.accounts.name_record.is_some(),
+        TransferError::NameRecordMismatch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Operator keys were chosen by the old owner, so the delegate does not carry over
+    let new_identity = &mut ctx.accounts.new_identity;
+    new_identity.set_inner(AgentIdentity {
+        agent_address: new_owner,
+        bump: ctx.bumps.new_identity,
+        delegate: Pubkey::default(),
+        delegate_scope: 0,
+        ..(**old_identity).clone()
+    });
+
+    if let Some(name_record) = ctx.accounts.name_record.as_mut() {
+        name_record.agent = new_owner;
+        name_record.claimed_at = now;
+    }
+
+    emit!(IdentityTransferred {
+        old_agent: old_owner,
+        new_agent: new_owner,
+        asset_address: new_identity.asset_address,
+        timestamp: now,
+    });
+
+    msg!("Agent identity transferred from {} to {}", old_owner, new_owner);
+
+    Ok(())
+}
+
+#[error_code]
+pub enum TransferError {
+    #[msg("Unauthorized: signer is not the agent owner")]
+    UnauthorizedTransfer,
+    #[msg("Identity is soulbound and cannot be transferred")]
+    IdentityNotTransferable,
+    #[msg("New owner must be a different, non-default address")]
+    InvalidNewOwner,
+    #[msg("Identity still holds staked collateral; withdraw it before transferring")]
+    StakeRemaining,
+    #[msg("Backers still hold delegated stake in this identity")]
+    DelegatedStakeRemaining,
+    #[msg("The identity's claimed name record must be passed")]
+    NameRecordMismatch,
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use super::stake::StakingError;
+use super::migrate::MigrationError;
+use crate::state::{AgentIdentity, StakingPool, TrustScoreBreakdown, MAX_TRUST_SCORE};
+
+// ============================================================================
+// GET TRUST SCORE (View)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct GetTrustScore<'info> {
+    #[account(
+        seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
+        bump = agent_identity.bump,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [StakingPool::SEED_PREFIX],
+        bump = staking_pool.bump,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    /// CHECK: The agent's wallet address
+    pub agent_address: UncheckedAccount<'info>,
+}
+
+/// Compute the agent's stake-weighted trust score from stake size, stake age
+/// and slash count, weighted by the pool's trust config
+pub fn get_trust_score(ctx: Context<GetTrustScore>) -> Result<TrustScoreBreakdown> {
+    let agent_identity = &ctx.accounts.agent_identity;
+    let clock = Clock::get()?;
+
+    let breakdown = agent_identity.trust_score(&ctx.accounts.staking_pool, clock.unix_timestamp);
+
+    msg!(
+        "Trust score for agent {}: {} (stake +{}, age +{}, slashes -{})",
+        agent_identity.agent_address,
+        breakdown.trust_score,
+        breakdown.stake_points,
+        breakdown.age_points,
+        breakdown.slash_penalty
+    );
+
+    Ok(breakdown)
+}
+
+// ============================================================================
+// UPDATE TRUST WEIGHTS (Authority Only)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct UpdateTrustWeights<'info> {
+    #[account(
+        mut,
+        seeds = [StakingPool::SEED_PREFIX],
+        bump = staking_pool.bump,
+        has_one = authority @ StakingError::UnauthorizedSlash,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set the weights used by get_trust_score. Stake and age weights together
+/// may not exceed MAX_TRUST_SCORE.
+pub fn update_trust_weights(
+    ctx: Context<UpdateTrustWeights>,
+    stake_weight: u16,
+    age_weight: u16,
+    slash_penalty: u16,
+) -> Result<()> {
+    require!(
+        (stake_weight as u32 + age_weight as u32) <= MAX_TRUST_SCORE as u32
+            && slash_penalty <= MAX_TRUST_SCORE,
+        StakingError::InvalidTrustWeights
+    );
+
+    let staking_pool = &mut ctx.accounts.staking_pool;
+    staking_pool.trust_stake_weight = stake_weight;
+    staking_pool.trust_age_weight = age_weight;
+    staking_pool.trust_slash_penalty = slash_penalty;
+
+    msg!(
+        "Trust weights updated: stake {}, age {}, slash penalty {}",
+        stake_weight,
+        age_weight,
+        slash_penalty
+    );
+
+    Ok(())
+}
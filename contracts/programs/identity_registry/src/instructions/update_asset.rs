@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use super::admin::enforce_program_guards;
+use super::migrate::MigrationError;
+use crate::instructions::register_agent::verify_core_asset;
+use crate::state::{AgentIdentity, ExemptAccount, ProgramConfig, UserRateLimit};
+
+#[derive(Accounts)]
+pub struct UpdateAsset<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent.key().as_ref()],
+        bump = agent_identity.bump,
+        has_one = agent_address @ IdentityError::UnauthorizedUpdate,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    /// CHECK: This is the agent_address stored in agent_identity
+    pub agent_address: UncheckedAccount<'info>,
+
+    /// CHECK: New Metaplex Core asset; program owner and asset owner are checked in the handler
+    pub asset: UncheckedAccount<'info>,
+
+    /// CHECK: Program config PDA; unpaused while it is uninitialized
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// Caller's rate limit; the per-minute budget is enforced when supplied
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, agent.key().as_ref()],
+        bump = rate_limit.bump
+    )]
+    pub rate_limit: Option<Account<'info, UserRateLimit>>,
+
+    /// CHECK: Caller's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, agent.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
+}
+
+/// Re-link the identity to a different Metaplex Core asset owned by the agent
+pub fn handler(ctx: Context<UpdateAsset>) -> Result<()> {
+    enforce_program_guards(
+        &ctx.accounts.program_config,
+        &ctx.accounts.rate_exemption,
+        ctx.accounts.rate_limit.as_mut(),
+    )?;
+
+    require!(
+        ctx.accounts.agent_identity.is_active,
+        IdentityError::IdentityDeactivated
+    );
+
+    let asset_address = ctx.accounts.asset.key();
+    require_keys_neq!(
+        asset_address,
+        ctx.accounts.agent_identity.asset_address,
+        IdentityError::AssetUnchanged
+    );
+
+    verify_core_asset(&ctx.accounts.asset, &ctx.accounts.agent.key())?;
+
+    let agent_identity = &mut ctx.accounts.agent_identity;
+    let clock = Clock::get()?;
+    let previous = agent_identity.asset_address;
+
+    agent_identity.asset_address = asset_address;
+    agent_identity.last_active_timestamp = clock.unix_timestamp;
+    agent_identity.activity_count = agent_identity.activity_count.saturating_add(1);
+
+    msg!(
+        "Agent {} re-linked asset {} -> {}",
+        agent_identity.agent_address,
+        previous,
+        asset_address
+    );
+
+    Ok(())
+}
+
+#[error_code]
+pub enum IdentityError {
+    #[msg("Identity is deactivated and cannot be updated")]
+    IdentityDeactivated,
+    #[msg("Unauthorized: signer is not the agent owner")]
+    UnauthorizedUpdate,
+    #[msg("Identity is already linked to this asset")]
+    AssetUnchanged,
+}
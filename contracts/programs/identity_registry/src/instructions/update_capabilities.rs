@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use super::admin::enforce_program_guards;
+use super::migrate::MigrationError;
+use crate::state::{
+    AgentIdentity, ExemptAccount, ProgramConfig, ServiceCategory, UserRateLimit,
+    DELEGATE_SCOPE_METADATA, KNOWN_CAPABILITIES_MASK,
+};
+
+#[derive(Accounts)]
+pub struct UpdateCapabilities<'info> {
+    #[account(
+        mut,
+        seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
+        bump = agent_identity.bump,
+        has_one = agent_address @ CapabilityError::UnauthorizedCapabilityUpdate,
+        constraint = agent_identity.can_sign(&agent.key(), DELEGATE_SCOPE_METADATA)
+            @ CapabilityError::UnauthorizedCapabilityUpdate,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// Owner, or a delegate with the metadata scope
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    /// CHECK: This is the agent_address stored in agent_identity
+    pub agent_address: UncheckedAccount<'info>,
+
+    /// CHECK: Program config PDA; unpaused while it is uninitialized
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// Caller's rate limit; the per-minute budget is enforced when supplied
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, agent.key().as_ref()],
+        bump = rate_limit.bump
+    )]
+    pub rate_limit: Option<Account<'info, UserRateLimit>>,
+
+    /// CHECK: Caller's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, agent.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateCapabilities>,
+    capabilities: u32,
+    service_category: Option<ServiceCategory>,
+) -> Result<()> {
+    enforce_program_guards(
+        &ctx.accounts.program_config,
+        &ctx.accounts.rate_exemption,
+        ctx.accounts.rate_limit.as_mut(),
+    )?;
+
+    require!(
+        capabilities & !KNOWN_CAPABILITIES_MASK == 0,
+        CapabilityError::UnknownCapabilities
+    );
+    require!(
+        ctx.accounts.agent_identity.is_active,
+        CapabilityError::IdentityDeactivated
+    );
+
+    let agent_identity = &mut ctx.accounts.agent_identity;
+    let clock = Clock::get()?;
+
+    agent_identity.capabilities = capabilities;
+    agent_identity.service_category = service_category;
+    agent_identity.last_active_timestamp = clock.unix_timestamp;
+    agent_identity.activity_count = agent_identity.activity_count.saturating_add(1);
+
+    msg!(
+        "Capabilities for {} set to {:#x}",
+        agent_identity.agent_address,
+        capabilities
+    );
+
+    Ok(())
+}
+
+#[error_code]
+pub enum CapabilityError {
+    #[msg("Capabilities contain bits unknown to this program version")]
+    UnknownCapabilities,
+    #[msg("Identity is deactivated and cannot be updated")]
+    IdentityDeactivated,
+    #[msg("Unauthorized: signer is not the agent owner or a scoped delegate")]
+    UnauthorizedCapabilityUpdate,
+}
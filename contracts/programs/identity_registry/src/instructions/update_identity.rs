@@ -1,27 +1,59 @@
 use anchor_lang::prelude::*;
-use crate::state::AgentIdentity;
+use super::admin::enforce_program_guards;
+use super::migrate::MigrationError;
+use crate::events::IdentityUpdated;
+use crate::state::{
+    AgentIdentity, ExemptAccount, ProgramConfig, UserRateLimit, DELEGATE_SCOPE_METADATA,
+};
 
 #[derive(Accounts)]
 pub struct UpdateIdentity<'info> {
     #[account(
         mut,
-        seeds = [AgentIdentity::SEED_PREFIX, agent.key().as_ref()],
+        seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
         bump = agent_identity.bump,
-        has_one = agent_address @ IdentityError::UnauthorizedUpdate
+        has_one = agent_address @ IdentityError::UnauthorizedUpdate,
+        constraint = agent_identity.can_sign(&agent.key(), DELEGATE_SCOPE_METADATA)
+            @ IdentityError::UnauthorizedUpdate,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
     )]
     pub agent_identity: Account<'info, AgentIdentity>,
 
+    /// Owner, or a delegate with the metadata scope
     #[account(mut)]
     pub agent: Signer<'info>,
 
     /// CHECK: This is the agent_address stored in agent_identity
     pub agent_address: UncheckedAccount<'info>,
+
+    /// CHECK: Program config PDA; unpaused while it is uninitialized
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// Caller's rate limit; the per-minute budget is enforced when supplied
+    #[account(
+        mut,
+        seeds = [UserRateLimit::SEED_PREFIX, agent.key().as_ref()],
+        bump = rate_limit.bump
+    )]
+    pub rate_limit: Option<Account<'info, UserRateLimit>>,
+
+    /// CHECK: Caller's rate limit exemption PDA; the program limit applies while it is uninitialized
+    #[account(seeds = [ExemptAccount::SEED_PREFIX, agent.key().as_ref()], bump)]
+    pub rate_exemption: UncheckedAccount<'info>,
 }
 
 pub fn handler(
     ctx: Context<UpdateIdentity>,
     metadata_uri: String,
+    metadata_hash: [u8; 32],
 ) -> Result<()> {
+    enforce_program_guards(
+        &ctx.accounts.program_config,
+        &ctx.accounts.rate_exemption,
+        ctx.accounts.rate_limit.as_mut(),
+    )?;
+
     require!(
         metadata_uri.len() <= 200,
         IdentityError::MetadataUriTooLong
@@ -36,10 +68,21 @@ pub fn handler(
     let clock = Clock::get()?;
 
     agent_identity.metadata_uri = metadata_uri;
+    agent_identity.metadata_hash = metadata_hash;
+    agent_identity.metadata_version = agent_identity.metadata_version.saturating_add(1);
     agent_identity.last_active_timestamp = clock.unix_timestamp;
     agent_identity.activity_count = agent_identity.activity_count.saturating_add(1);
 
-    msg!("Agent identity updated: {}", ctx.accounts.agent.key());
+    emit!(IdentityUpdated {
+        agent: agent_identity.agent_address,
+        updated_by: ctx.accounts.agent.key(),
+        metadata_uri: agent_identity.metadata_uri.clone(),
+        metadata_hash,
+        metadata_version: agent_identity.metadata_version,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Agent identity updated: {}", ctx.accounts.agent_address.key());
 
     Ok(())
 }
@@ -50,6 +93,6 @@ pub enum IdentityError {
     MetadataUriTooLong,
     #[msg("Identity is deactivated and cannot be updated")]
     IdentityDeactivated,
-    #[msg("Unauthorized: signer is not the agent owner")]
+    #[msg("Unauthorized: signer is not the agent owner or a scoped delegate")]
     UnauthorizedUpdate,
 }
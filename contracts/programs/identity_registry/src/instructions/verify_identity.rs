@@ -1,25 +1,56 @@
 use anchor_lang::prelude::*;
-use crate::state::AgentIdentity;
+use super::migrate::MigrationError;
+use crate::state::{AgentIdentity, IdentityStatus, StakingPool, MIN_STAKE_AMOUNT};
 
 #[derive(Accounts)]
 pub struct VerifyIdentity<'info> {
     #[account(
         seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
-        bump = agent_identity.bump
+        bump = agent_identity.bump,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
     )]
     pub agent_identity: Account<'info, AgentIdentity>,
 
     /// CHECK: The agent address being verified
     pub agent_address: UncheckedAccount<'info>,
+
+    /// Supplies the agent's category minimum; without it the global minimum applies
+    #[account(
+        seeds = [StakingPool::SEED_PREFIX],
+        bump = staking_pool.bump
+    )]
+    pub staking_pool: Option<Account<'info, StakingPool>>,
 }
 
-pub fn handler(ctx: Context<VerifyIdentity>) -> Result<()> {
+/// Report the agent's standing as an IdentityStatus. In strict mode the
+/// instruction also fails unless the identity is active, unexpired and
+/// holds the minimum stake, so other programs can CPI into it as a guard.
+pub fn handler(ctx: Context<VerifyIdentity>, strict: bool) -> Result<IdentityStatus> {
     let agent_identity = &ctx.accounts.agent_identity;
+    let now = Clock::get()?.unix_timestamp;
+
+    let minimum_stake = ctx
+        .accounts
+        .staking_pool
+        .as_ref()
+        .map_or(MIN_STAKE_AMOUNT, |pool| pool.min_stake_for(agent_identity.service_category));
+
+    let age_seconds = now.saturating_sub(agent_identity.registration_timestamp).max(0);
+    let status = IdentityStatus {
+        is_active: agent_identity.is_active,
+        is_expired: agent_identity.is_expired(now),
+        has_minimum_stake: agent_identity.has_minimum_stake(minimum_stake),
+        staked_amount: agent_identity.staked_amount,
+        last_active_timestamp: agent_identity.last_active_timestamp,
+        slash_count: agent_identity.slash_count,
+        registration_age_days: u32::try_from(age_seconds / (24 * 60 * 60)).unwrap_or(u32::MAX),
+    };
 
-    require!(
-        agent_identity.is_active,
-        IdentityError::IdentityNotActive
-    );
+    if strict {
+        require!(status.is_active, IdentityError::IdentityNotActive);
+        require!(!status.is_expired, IdentityError::IdentityExpired);
+        require!(status.has_minimum_stake, IdentityError::InsufficientStake);
+    }
 
     msg!("Identity verified for agent: {}", ctx.accounts.agent_address.key());
     msg!("NFT asset: {}", agent_identity.asset_address);
@@ -27,11 +58,15 @@ pub fn handler(ctx: Context<VerifyIdentity>) -> Result<()> {
     msg!("Last active: {}", agent_identity.last_active_timestamp);
     msg!("Activity count: {}", agent_identity.activity_count);
 
-    Ok(())
+    Ok(status)
 }
 
 #[error_code]
 pub enum IdentityError {
     #[msg("Identity is not active")]
     IdentityNotActive,
+    #[msg("Identity has expired; the owner must renew it")]
+    IdentityExpired,
+    #[msg("Identity does not hold the minimum stake")]
+    InsufficientStake,
 }
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use super::migrate::MigrationError;
+use crate::state::{AgentIdentity, MetadataCheck};
+
+#[derive(Accounts)]
+pub struct VerifyMetadata<'info> {
+    #[account(
+        seeds = [AgentIdentity::SEED_PREFIX, agent_address.key().as_ref()],
+        bump = agent_identity.bump,
+        constraint = agent_identity.is_supported_version() @ MigrationError::UnsupportedAccountVersion
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// CHECK: The agent address whose metadata is checked
+    pub agent_address: UncheckedAccount<'info>,
+}
+
+/// Compare `metadata_hash` (sha256 of the fetched metadata JSON) with the
+/// hash recorded on-chain. A mismatch is reported, not raised as an error.
+pub fn handler(ctx: Context<VerifyMetadata>, metadata_hash: [u8; 32]) -> Result<MetadataCheck> {
+    let agent_identity = &ctx.accounts.agent_identity;
+    let matches = agent_identity.metadata_hash == metadata_hash;
+
+    if matches {
+        msg!(
+            "Metadata verified for agent {} (version {})",
+            agent_identity.agent_address,
+            agent_identity.metadata_version
+        );
+    } else {
+        msg!(
+            "Metadata hash mismatch for agent {} (version {})",
+            agent_identity.agent_address,
+            agent_identity.metadata_version
+        );
+    }
+
+    Ok(MetadataCheck {
+        matches,
+        metadata_hash: agent_identity.metadata_hash,
+        metadata_version: agent_identity.metadata_version,
+    })
+}
@@ -1,7 +1,10 @@
+#![allow(clippy::too_many_arguments)]
+
 use anchor_lang::prelude::*;
 
 declare_id!("2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e");
 
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -11,31 +14,141 @@ use instructions::*;
 pub mod identity_registry {
     use super::*;
 
-    /// Register a new agent identity linked to a Metaplex Core NFT
+    /// Register a new agent identity linked to a Metaplex Core NFT; `transferable` is fixed for life
     pub fn register_agent(
         ctx: Context<RegisterAgent>,
         asset_address: Pubkey,
         metadata_uri: String,
+        metadata_hash: [u8; 32],
+        capabilities: u32,
+        service_category: Option<state::ServiceCategory>,
+        referrer: Option<Pubkey>,
+        transferable: bool,
+    ) -> Result<()> {
+        instructions::register_agent::handler(
+            ctx,
+            asset_address,
+            metadata_uri,
+            metadata_hash,
+            capabilities,
+            service_category,
+            referrer,
+            transferable,
+        )
+    }
+
+    /// Register several agents in one transaction; agent wallets are passed as remaining accounts
+    pub fn register_agents_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RegisterAgentsBatch<'info>>,
+        registrations: Vec<state::BatchRegistration>,
     ) -> Result<()> {
-        instructions::register_agent::handler(ctx, asset_address, metadata_uri)
+        instructions::register_agents_batch::handler(ctx, registrations)
     }
 
-    /// Update agent identity metadata URI
+    /// Update agent identity metadata URI and its hash, bumping metadata_version
     pub fn update_identity(
         ctx: Context<UpdateIdentity>,
         metadata_uri: String,
+        metadata_hash: [u8; 32],
     ) -> Result<()> {
-        instructions::update_identity::handler(ctx, metadata_uri)
+        instructions::update_identity::handler(ctx, metadata_uri, metadata_hash)
+    }
+
+    /// Re-link the identity to a different Metaplex Core asset owned by the agent
+    pub fn update_asset(ctx: Context<UpdateAsset>) -> Result<()> {
+        instructions::update_asset::handler(ctx)
+    }
+
+    /// Set the capability bitmask and service category (owner or metadata delegate)
+    pub fn update_capabilities(
+        ctx: Context<UpdateCapabilities>,
+        capabilities: u32,
+        service_category: Option<state::ServiceCategory>,
+    ) -> Result<()> {
+        instructions::update_capabilities::handler(ctx, capabilities, service_category)
+    }
+
+    /// Return the agent's IdentityStatus; `strict` also fails unless it is active, unexpired and staked
+    pub fn verify_identity(
+        ctx: Context<VerifyIdentity>,
+        strict: bool,
+    ) -> Result<state::IdentityStatus> {
+        instructions::verify_identity::handler(ctx, strict)
+    }
+
+    /// Check a metadata hash against the stored one and return the result
+    pub fn verify_metadata(
+        ctx: Context<VerifyMetadata>,
+        metadata_hash: [u8; 32],
+    ) -> Result<state::MetadataCheck> {
+        instructions::verify_metadata::handler(ctx, metadata_hash)
     }
 
-    /// Verify agent identity exists and is active
-    pub fn verify_identity(ctx: Context<VerifyIdentity>) -> Result<()> {
-        instructions::verify_identity::handler(ctx)
+    /// Return who referred the agent and how many agents it has referred
+    pub fn get_referrals(ctx: Context<GetReferrals>) -> Result<state::ReferralSummary> {
+        instructions::referrals::handler(ctx)
     }
 
-    /// Deactivate an agent identity (emergency use)
-    pub fn deactivate_agent(ctx: Context<DeactivateAgent>) -> Result<()> {
-        instructions::deactivate_agent::handler(ctx)
+    /// Deactivate an agent identity (emergency use), recording the sha256 of the reason
+    pub fn deactivate_agent(ctx: Context<DeactivateAgent>, reason_hash: [u8; 32]) -> Result<()> {
+        instructions::deactivate_agent::handler(ctx, reason_hash)
+    }
+
+    /// Reactivate a deactivated identity (owner after cooldown, or admin anytime)
+    pub fn reactivate_agent(ctx: Context<ReactivateAgent>) -> Result<()> {
+        instructions::reactivate_agent::handler(ctx)
+    }
+
+    /// Close a deactivated, fully unstaked identity and reclaim its rent
+    pub fn close_identity(ctx: Context<CloseIdentity>) -> Result<()> {
+        instructions::close_identity::handler(ctx)
+    }
+
+    /// Move a transferable identity to a new owner's PDA (both owners sign); stake must be withdrawn first
+    pub fn transfer_identity(ctx: Context<TransferIdentity>) -> Result<()> {
+        instructions::transfer_identity::handler(ctx)
+    }
+
+    /// Restart the identity's validity period, reviving it if expired (owner only)
+    pub fn renew_identity(ctx: Context<RenewIdentity>) -> Result<()> {
+        instructions::renew_identity::handler(ctx)
+    }
+
+    /// Record a liveness heartbeat, optionally reporting health (owner or activity delegate)
+    pub fn record_heartbeat(
+        ctx: Context<RecordHeartbeat>,
+        status: Option<state::HealthStatus>,
+    ) -> Result<()> {
+        instructions::record_heartbeat::handler(ctx, status)
+    }
+
+    /// Let an operator key sign scoped instructions for this identity (owner only)
+    pub fn set_delegate(
+        ctx: Context<SetDelegate>,
+        delegate: Pubkey,
+        scope_flags: u8,
+    ) -> Result<()> {
+        instructions::delegate::set_delegate(ctx, delegate, scope_flags)
+    }
+
+    /// Remove the identity's delegate (owner only)
+    pub fn revoke_delegate(ctx: Context<SetDelegate>) -> Result<()> {
+        instructions::delegate::revoke_delegate(ctx)
+    }
+
+    /// Claim a unique, normalized agent name (owner only, one name per agent)
+    pub fn claim_name(ctx: Context<ClaimName>, name: String) -> Result<()> {
+        instructions::name::claim_name(ctx, name)
+    }
+
+    /// Release the agent's name and reclaim the record's rent
+    pub fn release_name(ctx: Context<ReleaseName>) -> Result<()> {
+        instructions::name::release_name(ctx)
+    }
+
+    /// Transfer the agent's name to another agent that has none
+    pub fn transfer_name(ctx: Context<TransferName>) -> Result<()> {
+        instructions::name::transfer_name(ctx)
     }
 
     // ==================== STAKING INSTRUCTIONS ====================
@@ -50,9 +163,32 @@ pub mod identity_registry {
         instructions::stake::stake_collateral(ctx, amount)
     }
 
-    /// Unstake SOL collateral after unlock period
-    pub fn unstake_collateral(ctx: Context<UnstakeCollateral>, amount: u64) -> Result<()> {
-        instructions::stake::unstake_collateral(ctx, amount)
+    /// Request withdrawal of staked SOL, starting the unlock cooldown
+    pub fn request_unstake(ctx: Context<RequestUnstake>, amount: u64) -> Result<()> {
+        instructions::stake::request_unstake(ctx, amount)
+    }
+
+    /// Withdraw the pending unstake after the cooldown has elapsed
+    pub fn withdraw_unstaked(ctx: Context<WithdrawUnstaked>) -> Result<()> {
+        instructions::stake::withdraw_unstaked(ctx)
+    }
+
+    /// Stake SOL as collateral for another wallet's agent, tracked in a DelegatedStake PDA
+    pub fn stake_for_agent(ctx: Context<StakeForAgent>, amount: u64) -> Result<()> {
+        instructions::delegated_stake::stake_for_agent(ctx, amount)
+    }
+
+    /// Start the unlock cooldown for part of a backer's delegated stake
+    pub fn request_delegated_unstake(
+        ctx: Context<RequestDelegatedUnstake>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::delegated_stake::request_delegated_unstake(ctx, amount)
+    }
+
+    /// Withdraw a backer's pending delegated stake after the cooldown
+    pub fn withdraw_delegated_stake(ctx: Context<WithdrawDelegatedStake>) -> Result<()> {
+        instructions::delegated_stake::withdraw_delegated_stake(ctx)
     }
 
     /// Slash agent stake for protocol violations (authority only)
@@ -64,6 +200,71 @@ pub mod identity_registry {
         instructions::stake::slash_agent(ctx, violation_severity_bps, reason)
     }
 
+    /// Slash agent stake for an approved reputation multisig proposal (CPI only)
+    pub fn slash_agent_multisig(
+        ctx: Context<SlashAgentMultisig>,
+        violation_severity_bps: u16,
+        reason_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::stake::slash_agent_multisig(ctx, violation_severity_bps, reason_hash)
+    }
+
+    /// Permanently disable single-authority slashing (authority only)
+    pub fn disable_legacy_slashing(ctx: Context<DisableLegacySlashing>) -> Result<()> {
+        instructions::stake::disable_legacy_slashing(ctx)
+    }
+
+    /// Appeal an escrowed slash before its appeal window closes (slashed agent only)
+    pub fn appeal_slash(ctx: Context<AppealSlash>) -> Result<()> {
+        instructions::slash_appeal::appeal_slash(ctx)
+    }
+
+    /// Resolve an appeal: return the escrow to the agent's stake or release it to the treasury
+    pub fn resolve_appeal(ctx: Context<ResolveAppeal>, uphold: bool) -> Result<()> {
+        instructions::slash_appeal::resolve_appeal(ctx, uphold)
+    }
+
+    /// Release an unappealed slash to the treasury after the appeal window (permissionless)
+    pub fn finalize_slash(ctx: Context<FinalizeSlash>) -> Result<()> {
+        instructions::slash_appeal::finalize_slash(ctx)
+    }
+
+    /// Set the slash treasury and appeal window (authority only)
+    pub fn update_slash_config(
+        ctx: Context<UpdateSlashConfig>,
+        treasury: Pubkey,
+        appeal_window_seconds: i64,
+    ) -> Result<()> {
+        instructions::slash_appeal::update_slash_config(ctx, treasury, appeal_window_seconds)
+    }
+
+    /// Return the agent's 0-1000 stake-weighted trust score and its breakdown
+    pub fn get_trust_score(ctx: Context<GetTrustScore>) -> Result<state::TrustScoreBreakdown> {
+        instructions::trust_score::get_trust_score(ctx)
+    }
+
+    /// Set the stake, stake-age and slash-penalty weights of the trust score (authority only)
+    pub fn update_trust_weights(
+        ctx: Context<UpdateTrustWeights>,
+        stake_weight: u16,
+        age_weight: u16,
+        slash_penalty: u16,
+    ) -> Result<()> {
+        instructions::trust_score::update_trust_weights(ctx, stake_weight, age_weight, slash_penalty)
+    }
+
+    /// Return an agent's slash totals and the slash records passed as remaining accounts
+    pub fn get_slash_history<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetSlashHistory<'info>>,
+    ) -> Result<SlashHistoryView> {
+        instructions::stake::get_slash_history(ctx)
+    }
+
+    /// Close a slash record after its retention period (permissionless, refunds the authority)
+    pub fn close_slash_record(ctx: Context<CloseSlashRecord>) -> Result<()> {
+        instructions::stake::close_slash_record(ctx)
+    }
+
     /// Pause staking operations (emergency only)
     pub fn pause_staking(ctx: Context<PauseStaking>) -> Result<()> {
         instructions::stake::pause_staking(ctx)
@@ -74,14 +275,28 @@ pub mod identity_registry {
         instructions::stake::unpause_staking(ctx)
     }
 
+    /// Set or clear the minimum stake for a service category (authority only)
+    pub fn set_category_min_stake(
+        ctx: Context<SetCategoryMinStake>,
+        category: state::ServiceCategory,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::stake::set_category_min_stake(ctx, category, amount)
+    }
+
     // ==================== ADMIN INSTRUCTIONS (Pause & Rate Limiting) ====================
 
     /// Initialize program configuration (admin only, one-time)
     pub fn initialize_program_config(
         ctx: Context<InitializeProgramConfig>,
         rate_limit_per_minute: u32,
+        max_pause_duration_seconds: i64,
     ) -> Result<()> {
-        instructions::admin::initialize_program_config(ctx, rate_limit_per_minute)
+        instructions::admin::initialize_program_config(
+            ctx,
+            rate_limit_per_minute,
+            max_pause_duration_seconds,
+        )
     }
 
     /// Pause all program operations (emergency)
@@ -94,6 +309,11 @@ pub mod identity_registry {
         instructions::admin::unpause_program(ctx)
     }
 
+    /// Lift a pause that has outlived its max duration (permissionless)
+    pub fn unpause_expired(ctx: Context<UnpauseExpired>) -> Result<()> {
+        instructions::admin::unpause_expired(ctx)
+    }
+
     /// Update the global rate limit
     pub fn update_rate_limit(
         ctx: Context<UpdateRateLimit>,
@@ -102,18 +322,84 @@ pub mod identity_registry {
         instructions::admin::update_rate_limit(ctx, rate_limit_per_minute)
     }
 
+    /// Set how long identities stay current after registration or renewal (0 = never expire)
+    pub fn update_default_validity(
+        ctx: Context<UpdateRateLimit>,
+        default_validity_seconds: i64,
+    ) -> Result<()> {
+        instructions::admin::update_default_validity(ctx, default_validity_seconds)
+    }
+
+    /// Set the minimum spacing between heartbeats from one identity (admin only)
+    pub fn update_heartbeat_interval(
+        ctx: Context<UpdateRateLimit>,
+        heartbeat_interval_seconds: i64,
+    ) -> Result<()> {
+        instructions::admin::update_heartbeat_interval(ctx, heartbeat_interval_seconds)
+    }
+
     /// Initialize user rate limit tracking
     pub fn initialize_user_rate_limit(ctx: Context<InitializeUserRateLimit>) -> Result<()> {
         instructions::admin::initialize_user_rate_limit(ctx)
     }
 
+    /// Close a rate limit account (user anytime, anyone once idle for 30 days)
+    pub fn close_user_rate_limit(ctx: Context<CloseUserRateLimit>) -> Result<()> {
+        instructions::admin::close_user_rate_limit(ctx)
+    }
+
     /// Check and update rate limit (call before operations)
     pub fn check_rate_limit(ctx: Context<CheckRateLimit>) -> Result<()> {
         instructions::admin::check_rate_limit(ctx)
     }
 
-    /// Transfer admin rights
-    pub fn transfer_admin(ctx: Context<TransferAdmin>) -> Result<()> {
-        instructions::admin::transfer_admin(ctx)
+    /// Exempt a relayer or service from the rate limit, fully or up to its own cap (admin only)
+    pub fn grant_rate_exemption(
+        ctx: Context<GrantRateExemption>,
+        max_per_minute: Option<u32>,
+    ) -> Result<()> {
+        instructions::admin::grant_rate_exemption(ctx, max_per_minute)
+    }
+
+    /// Revoke a rate limit exemption (admin only)
+    pub fn revoke_rate_exemption(ctx: Context<RevokeRateExemption>) -> Result<()> {
+        instructions::admin::revoke_rate_exemption(ctx)
+    }
+
+    /// Propose a new admin (takes effect once they accept)
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::admin::propose_admin(ctx, new_admin)
+    }
+
+    /// Accept a pending admin transfer (pending admin only)
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::admin::accept_admin(ctx)
+    }
+
+    /// Cancel a pending admin transfer (current admin only)
+    pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+        instructions::admin::cancel_admin_transfer(ctx)
+    }
+
+    // ==================== MIGRATIONS ====================
+
+    /// Upgrade an identity account to the current layout version (permissionless)
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::migrate::migrate_account(ctx)
+    }
+
+    /// Resize a legacy staking pool; the treasury defaults to the pool authority (permissionless)
+    pub fn migrate_staking_pool(ctx: Context<MigrateStakingPool>) -> Result<()> {
+        instructions::migrate::migrate_staking_pool(ctx)
+    }
+
+    /// Resize a legacy user rate limit, recording its user as the payer (permissionless)
+    pub fn migrate_user_rate_limit(ctx: Context<MigrateUserRateLimit>) -> Result<()> {
+        instructions::migrate::migrate_user_rate_limit(ctx)
+    }
+
+    /// Resize a legacy program config with no pending admin and the default max pause (permissionless)
+    pub fn migrate_program_config(ctx: Context<MigrateProgramConfig>) -> Result<()> {
+        instructions::migrate::migrate_program_config(ctx)
     }
 }
@@ -7,12 +7,99 @@ use anchor_lang::prelude::*;
 /// Minimum stake amount: 0.1 SOL (100_000_000 lamports)
 pub const MIN_STAKE_AMOUNT: u64 = 100_000_000;
 
-/// Stake unlock period: 7 days in seconds
+/// Unstake cooldown between request_unstake and withdraw_unstaked: 7 days in seconds
 pub const STAKE_UNLOCK_PERIOD: i64 = 7 * 24 * 60 * 60;
 
 /// Maximum slash percentage: 50% (5000 basis points)
 pub const MAX_SLASH_BPS: u16 = 5000;
 
+/// Maximum slash reason length (bytes); only its hash is stored
+pub const MAX_SLASH_REASON_LEN: usize = 200;
+
+/// Slash records are kept for 1 year before they can be closed
+pub const SLASH_RECORD_RETENTION_PERIOD: i64 = 365 * 24 * 60 * 60;
+
+/// Default window for an agent to appeal a slash: 7 days in seconds
+pub const DEFAULT_SLASH_APPEAL_WINDOW: i64 = 7 * 24 * 60 * 60;
+
+/// Longest appeal window the authority may configure: 30 days in seconds
+pub const MAX_SLASH_APPEAL_WINDOW: i64 = 30 * 24 * 60 * 60;
+
+/// Maximum records returned by one get_slash_history call (return data is capped at 1024 bytes)
+pub const MAX_SLASH_HISTORY_PAGE: usize = 10;
+
+/// Maximum identities created by one register_agents_batch call (compute bound)
+pub const MAX_BATCH_REGISTRATIONS: usize = 5;
+
+/// Owner-initiated reactivation cooldown: 72 hours in seconds
+pub const REACTIVATION_COOLDOWN: i64 = 72 * 60 * 60;
+
+/// Doublings of MIN_STAKE_AMOUNT that earn the full trust stake weight;
+/// the last step is reached at 2^9 x MIN_STAKE_AMOUNT (51.2 SOL)
+pub const TRUST_STAKE_LOG_STEPS: u64 = 10;
+
+/// Stake age that earns the full trust age weight: 365 days in seconds
+pub const TRUST_STAKE_AGE_CAP: i64 = 365 * 24 * 60 * 60;
+
+/// Upper bound of get_trust_score
+pub const MAX_TRUST_SCORE: u16 = 1000;
+
+/// Default trust points for a maximal stake
+pub const DEFAULT_TRUST_STAKE_WEIGHT: u16 = 600;
+
+/// Default trust points for a stake at least TRUST_STAKE_AGE_CAP old
+pub const DEFAULT_TRUST_AGE_WEIGHT: u16 = 400;
+
+/// Default trust points removed per slash
+pub const DEFAULT_TRUST_SLASH_PENALTY: u16 = 150;
+
+pub use gs2_common::{REPUTATION_MULTISIG_SEED, REPUTATION_REGISTRY_PROGRAM_ID, REPUTATION_SEED};
+
+/// Delegate scope: update_identity (metadata URI)
+pub const DELEGATE_SCOPE_METADATA: u8 = 1 << 0;
+
+/// Delegate scope: activity recording (heartbeats and similar liveness calls)
+pub const DELEGATE_SCOPE_ACTIVITY: u8 = 1 << 1;
+
+/// Delegate scope: stake_collateral top-ups (never unstake or withdraw)
+pub const DELEGATE_SCOPE_STAKE: u8 = 1 << 2;
+
+/// All assignable delegate scopes
+pub const DELEGATE_SCOPE_ALL: u8 =
+    DELEGATE_SCOPE_METADATA | DELEGATE_SCOPE_ACTIVITY | DELEGATE_SCOPE_STAKE;
+
+/// Capability: model inference
+pub const CAPABILITY_INFERENCE: u32 = 1 << 0;
+
+/// Capability: data feeds and oracles
+pub const CAPABILITY_DATA_FEEDS: u32 = 1 << 1;
+
+/// Capability: code generation
+pub const CAPABILITY_CODE_GENERATION: u32 = 1 << 2;
+
+/// Capability: trading and execution
+pub const CAPABILITY_TRADING: u32 = 1 << 3;
+
+/// Capability bits defined by the first capabilities release
+pub const CAPABILITY_MASK_V1: u32 =
+    CAPABILITY_INFERENCE | CAPABILITY_DATA_FEEDS | CAPABILITY_CODE_GENERATION | CAPABILITY_TRADING;
+
+/// Bits accepted by this program version. New flags extend this mask in a
+/// later release, so old clients that only set V1 bits keep validating.
+pub const KNOWN_CAPABILITIES_MASK: u32 = CAPABILITY_MASK_V1;
+
+/// Shortest claimable agent name (bytes)
+pub const MIN_NAME_LEN: usize = 3;
+
+/// Longest claimable agent name (bytes); also the PDA seed limit
+pub const MAX_NAME_LEN: usize = 32;
+
+/// Metaplex Core program; owner of every identity NFT asset
+pub const MPL_CORE_PROGRAM_ID: Pubkey = pubkey!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
+
+/// Metaplex Core `Key::AssetV1` discriminant (first byte of an asset account)
+pub const MPL_CORE_ASSET_V1_KEY: u8 = 1;
+
 // ============================================================================
 // AGENT IDENTITY (Enhanced with Staking)
 // ============================================================================
@@ -20,7 +107,7 @@ pub const MAX_SLASH_BPS: u16 = 5000;
 /// Agent Identity Account
 /// PDA seeds: ["agent", agent_address]
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct AgentIdentity {
     /// The agent's wallet address (owner)
     pub agent_address: Pubkey,
@@ -49,7 +136,7 @@ pub struct AgentIdentity {
     /// Amount of SOL staked as collateral (lamports)
     pub staked_amount: u64,
 
-    /// Timestamp when stake can be unlocked (0 if not staking)
+    /// Timestamp when the pending unstake can be withdrawn (0 if none pending)
     pub stake_unlock_timestamp: i64,
 
     /// Number of times this agent has been slashed
@@ -60,11 +147,101 @@ pub struct AgentIdentity {
 
     /// PDA bump seed
     pub bump: u8,
+
+    /// Timestamp of the most recent deactivation (0 if never deactivated)
+    pub deactivated_at: i64,
+
+    /// Portion of staked_amount requested for withdrawal (still slashable)
+    pub pending_unstake_amount: u64,
+
+    /// Start of the current stake; reset when the agent withdraws everything (0 if never staked)
+    pub first_staked_at: i64,
+
+    /// Operator key allowed to sign the scoped instructions (default if none)
+    pub delegate: Pubkey,
+
+    /// DELEGATE_SCOPE_* flags granted to the delegate
+    pub delegate_scope: u8,
+
+    /// CAPABILITY_* bitmask advertised for marketplace filtering
+    pub capabilities: u32,
+
+    /// Primary service category, if the agent declared one
+    pub service_category: Option<ServiceCategory>,
+
+    /// sha256 of the canonical metadata JSON behind metadata_uri
+    pub metadata_hash: [u8; 32],
+
+    /// Incremented on every metadata update (1 at registration, 0 if migrated without a hash)
+    pub metadata_version: u32,
+
+    /// NameRecord PDA of the agent's claimed name (default if none)
+    pub name_record: Pubkey,
+
+    /// Timestamp after which the identity counts as lapsed until renewed (0 = never expires)
+    pub expires_at: i64,
+
+    /// Agent that referred this one at registration (default if none)
+    pub referrer: Pubkey,
+
+    /// Number of agents registered with this agent as their referrer
+    pub referral_count: u32,
+
+    /// Signer of the most recent deactivation (default while active)
+    pub deactivated_by: Pubkey,
+
+    /// sha256 of the reason given for the most recent deactivation (zeroed while active)
+    pub deactivation_reason_hash: [u8; 32],
+
+    /// Lamports backers have staked for this agent, net of slashes (includes pending withdrawals)
+    pub delegated_stake: u64,
+
+    /// Outstanding DelegatedStake shares; each is worth delegated_stake / delegated_shares
+    pub delegated_shares: u64,
+
+    /// Portion of delegated_shares that backers have requested to withdraw (still slashable)
+    pub pending_delegated_shares: u64,
+
+    /// Timestamp of the most recent record_heartbeat (0 if none)
+    pub last_heartbeat_at: i64,
+
+    /// Health reported by the most recent heartbeat that carried one
+    pub health_status: HealthStatus,
+
+    /// Whether transfer_identity may move this identity to another wallet
+    /// (false = soulbound; chosen at registration)
+    pub transferable: bool,
+
+    /// Layout version (see CURRENT_VERSION); 0 on accounts migrate_account has not upgraded
+    pub version: u8,
+}
+
+/// Self-reported health carried by record_heartbeat
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum HealthStatus {
+    #[default]
+    Healthy,
+    Degraded,
+}
+
+/// Primary kind of service an agent offers
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum ServiceCategory {
+    Inference,
+    DataFeed,
+    CodeGeneration,
+    Trading,
+    Other,
+}
+
+impl ServiceCategory {
+    /// Number of categories; sizes per-category tables such as StakingPool::category_min_stakes
+    pub const COUNT: usize = 5;
 }
 
 impl AgentIdentity {
     /// Seed prefix for PDA derivation
-    pub const SEED_PREFIX: &'static [u8] = b"agent";
+    pub const SEED_PREFIX: &'static [u8] = gs2_common::IDENTITY_AGENT_SEED;
 
     /// Calculate space for rent (updated with staking fields)
     pub const LEN: usize = 8 + // discriminator
@@ -79,16 +256,144 @@ impl AgentIdentity {
         8 + // stake_unlock_timestamp
         4 + // slash_count
         8 + // total_slashed
-        1; // bump
+        1 + // bump
+        8 + // deactivated_at
+        8 + // pending_unstake_amount
+        8 + // first_staked_at
+        32 + // delegate
+        1 + // delegate_scope
+        4 + // capabilities
+        1 + 1 + // service_category
+        32 + // metadata_hash
+        4 + // metadata_version
+        32 + // name_record
+        8 + // expires_at
+        32 + // referrer
+        4 + // referral_count
+        32 + // deactivated_by
+        32 + // deactivation_reason_hash
+        8 + // delegated_stake
+        8 + // delegated_shares
+        8 + // pending_delegated_shares
+        8 + // last_heartbeat_at
+        1 + // health_status
+        1 + // transferable
+        1; // version
+
+    /// Size of accounts created before the version field was added
+    pub const PRE_VERSION_LEN: usize = Self::LEN - 1;
+
+    /// Size of accounts created before deactivated_at, pending_unstake_amount,
+    /// first_staked_at, the delegate, capability, metadata hash, name, expiry,
+    /// referral, deactivation, delegated stake, heartbeat and transferable
+    /// fields were added
+    pub const LEGACY_LEN: usize = Self::PRE_VERSION_LEN
+        - 8 - 8 - 8 - 32 - 1 - 4 - 2 - 32 - 4 - 32 - 8 - 32 - 4 - 32 - 32 - 8 - 8 - 8 - 8 - 1 - 1;
+
+    /// Layout version written by register_agent and migrate_account
+    pub const CURRENT_VERSION: u8 = 1;
+
+    /// Oldest layout version the handlers accept; older accounts must call migrate_account
+    pub const MIN_SUPPORTED_VERSION: u8 = 1;
+
+    /// Whether the handlers accept this account's layout version
+    pub fn is_supported_version(&self) -> bool {
+        self.version >= Self::MIN_SUPPORTED_VERSION
+    }
+
+    /// Whether `signer` may sign an instruction gated by `scope`: always the
+    /// owner, or the delegate when it holds that scope
+    pub fn can_sign(&self, signer: &Pubkey, scope: u8) -> bool {
+        *signer == self.agent_address
+            || (self.delegate != Pubkey::default()
+                && *signer == self.delegate
+                && self.delegate_scope & scope == scope)
+    }
 
-    /// Check if agent has minimum stake
-    pub fn has_minimum_stake(&self) -> bool {
-        self.staked_amount >= MIN_STAKE_AMOUNT
+    /// expires_at for an identity registered or renewed at `now`
+    /// (0 when `validity_seconds` is 0, i.e. expiry is disabled)
+    pub fn expiry_for(now: i64, validity_seconds: i64) -> i64 {
+        if validity_seconds == 0 {
+            0
+        } else {
+            now.saturating_add(validity_seconds)
+        }
+    }
+
+    /// Whether the identity has passed its expires_at without being renewed
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    /// Active and not expired; expired identities are treated like inactive
+    /// ones until renew_identity runs
+    pub fn is_current(&self, now: i64) -> bool {
+        self.is_active && !self.is_expired(now)
     }
 
-    /// Check if stake can be unlocked
+    /// Stake not already requested for withdrawal
+    pub fn active_stake(&self) -> u64 {
+        self.staked_amount.saturating_sub(self.pending_unstake_amount)
+    }
+
+    /// Lamports currently backing `shares` of this agent's delegated stake
+    pub fn delegated_value(&self, shares: u64) -> u64 {
+        if self.delegated_shares == 0 {
+            return 0;
+        }
+        (shares as u128 * self.delegated_stake as u128 / self.delegated_shares as u128) as u64
+    }
+
+    /// Shares minted for a backer depositing `amount`; 1:1 while no shares exist
+    pub fn shares_for_deposit(&self, amount: u64) -> Option<u64> {
+        if self.delegated_shares == 0 {
+            return Some(amount);
+        }
+        if self.delegated_stake == 0 {
+            return None;
+        }
+        let shares = amount as u128 * self.delegated_shares as u128 / self.delegated_stake as u128;
+        u64::try_from(shares).ok()
+    }
+
+    /// Delegated stake not already requested for withdrawal
+    pub fn active_delegated_stake(&self) -> u64 {
+        self.delegated_value(self.delegated_shares.saturating_sub(self.pending_delegated_shares))
+    }
+
+    /// Check if own plus delegated stake meets `minimum` (excluding any pending
+    /// unstake); pass StakingPool::min_stake_for the agent's category
+    pub fn has_minimum_stake(&self, minimum: u64) -> bool {
+        self.active_stake().saturating_add(self.active_delegated_stake()) >= minimum
+    }
+
+    /// Part of a slash of `amount` borne by backers, in proportion to their
+    /// share of the agent's total collateral
+    pub fn delegated_slash_share(&self, amount: u64) -> u64 {
+        let total = self.staked_amount as u128 + self.delegated_stake as u128;
+        if total == 0 {
+            return 0;
+        }
+        (amount as u128 * self.delegated_stake as u128 / total) as u64
+    }
+
+    /// Earliest time the owner may reactivate without the admin.
+    /// Identities deactivated before deactivated_at existed fall back to
+    /// last_active_timestamp, which deactivate_agent also stamped.
+    pub fn reactivation_available_at(&self) -> i64 {
+        let deactivated_at = if self.deactivated_at > 0 {
+            self.deactivated_at
+        } else {
+            self.last_active_timestamp
+        };
+        deactivated_at.saturating_add(REACTIVATION_COOLDOWN)
+    }
+
+    /// Check if the pending unstake has cleared its cooldown
     pub fn can_unlock_stake(&self, current_timestamp: i64) -> bool {
-        self.stake_unlock_timestamp > 0 && current_timestamp >= self.stake_unlock_timestamp
+        self.pending_unstake_amount > 0
+            && self.stake_unlock_timestamp > 0
+            && current_timestamp >= self.stake_unlock_timestamp
     }
 
     /// Calculate slash amount using quadratic curve (2026 best practice)
@@ -97,19 +402,388 @@ impl AgentIdentity {
     /// - 70% violation → 49% slashed (capped at MAX_SLASH_BPS)
     pub fn calculate_slash_amount(&self, violation_severity_bps: u16) -> u64 {
         // Quadratic slashing: slash_pct = (severity_bps / 10000)^2 * 100%
-        // Capped at MAX_SLASH_BPS
-        // All arithmetic uses checked/saturating operations for safety
+        // Capped at MAX_SLASH_BPS; severities above 10000 count as 10000, so
+        // the amount never decreases as severity rises
         let severity = violation_severity_bps.min(10000) as u64;
         let slash_bps = severity
             .saturating_mul(severity)
             .saturating_div(10000)
             .min(MAX_SLASH_BPS as u64);
 
-        // Apply to staked amount (checked arithmetic)
-        self.staked_amount
-            .checked_mul(slash_bps)
-            .and_then(|v| v.checked_div(10000))
-            .unwrap_or(0)
+        // Apply to own plus delegated stake in u128, so large stakes can't
+        // overflow; at most half of two u64s, the result fits back into u64
+        let total = self.staked_amount as u128 + self.delegated_stake as u128;
+        u64::try_from(total * slash_bps as u128 / 10000).unwrap_or(u64::MAX)
+    }
+    /// Stake-weighted trust figure (0 to MAX_TRUST_SCORE) using the pool's weights.
+    /// Agents below the minimum active stake score 0.
+    pub fn trust_score(&self, pool: &StakingPool, current_timestamp: i64) -> TrustScoreBreakdown {
+        let active_stake = self.active_stake();
+        if active_stake < MIN_STAKE_AMOUNT {
+            return TrustScoreBreakdown::default();
+        }
+
+        // Log-scaled: one step per doubling of the minimum stake
+        let steps = ((active_stake / MIN_STAKE_AMOUNT).ilog2() as u64 + 1).min(TRUST_STAKE_LOG_STEPS);
+        let stake_points = (pool.trust_stake_weight as u64 * steps / TRUST_STAKE_LOG_STEPS) as u16;
+
+        let age = current_timestamp
+            .saturating_sub(self.first_staked_at)
+            .clamp(0, TRUST_STAKE_AGE_CAP);
+        let age_points = (pool.trust_age_weight as i64 * age / TRUST_STAKE_AGE_CAP) as u16;
+
+        let slash_penalty = (pool.trust_slash_penalty as u32)
+            .saturating_mul(self.slash_count)
+            .min(u16::MAX as u32) as u16;
+
+        let trust_score = stake_points
+            .saturating_add(age_points)
+            .saturating_sub(slash_penalty)
+            .min(MAX_TRUST_SCORE);
+
+        TrustScoreBreakdown {
+            trust_score,
+            stake_points,
+            age_points,
+            slash_penalty,
+        }
+    }
+}
+
+// gs2-common mirrors AgentIdentity for the other programs and off-chain clients.
+// These fail to compile when the program ID, discriminator, a field or an enum
+// variant changes on one side only; the round-trip test below pins field order.
+const _: () = assert!(gs2_common::bytes_eq(
+    &crate::ID.to_bytes(),
+    &gs2_common::IDENTITY_REGISTRY_PROGRAM_ID.to_bytes()
+));
+const _: () = assert!(gs2_common::bytes_eq(
+    AgentIdentity::DISCRIMINATOR,
+    <gs2_common::AgentIdentity as Discriminator>::DISCRIMINATOR
+));
+
+impl From<&AgentIdentity> for gs2_common::AgentIdentity {
+    fn from(identity: &AgentIdentity) -> Self {
+        let AgentIdentity {
+            agent_address,
+            asset_address,
+            metadata_uri,
+            registration_timestamp,
+            last_active_timestamp,
+            activity_count,
+            is_active,
+            staked_amount,
+            stake_unlock_timestamp,
+            slash_count,
+            total_slashed,
+            bump,
+            deactivated_at,
+            pending_unstake_amount,
+            first_staked_at,
+            delegate,
+            delegate_scope,
+            capabilities,
+            service_category,
+            metadata_hash,
+            metadata_version,
+            name_record,
+            expires_at,
+            referrer,
+            referral_count,
+            deactivated_by,
+            deactivation_reason_hash,
+            delegated_stake,
+            delegated_shares,
+            pending_delegated_shares,
+            last_heartbeat_at,
+            health_status,
+            transferable,
+            version,
+        } = identity.clone();
+        gs2_common::AgentIdentity {
+            agent_address,
+            asset_address,
+            metadata_uri,
+            registration_timestamp,
+            last_active_timestamp,
+            activity_count,
+            is_active,
+            staked_amount,
+            stake_unlock_timestamp,
+            slash_count,
+            total_slashed,
+            bump,
+            deactivated_at,
+            pending_unstake_amount,
+            first_staked_at,
+            delegate,
+            delegate_scope,
+            capabilities,
+            service_category: service_category.map(Into::into),
+            metadata_hash,
+            metadata_version,
+            name_record,
+            expires_at,
+            referrer,
+            referral_count,
+            deactivated_by,
+            deactivation_reason_hash,
+            delegated_stake,
+            delegated_shares,
+            pending_delegated_shares,
+            last_heartbeat_at,
+            health_status: health_status.into(),
+            transferable,
+            version,
+        }
+    }
+}
+
+impl From<ServiceCategory> for gs2_common::ServiceCategory {
+    fn from(category: ServiceCategory) -> Self {
+        match category {
+            ServiceCategory::Inference => Self::Inference,
+            ServiceCategory::DataFeed => Self::DataFeed,
+            ServiceCategory::CodeGeneration => Self::CodeGeneration,
+            ServiceCategory::Trading => Self::Trading,
+            ServiceCategory::Other => Self::Other,
+        }
+    }
+}
+
+impl From<HealthStatus> for gs2_common::HealthStatus {
+    fn from(status: HealthStatus) -> Self {
+        match status {
+            HealthStatus::Healthy => Self::Healthy,
+            HealthStatus::Degraded => Self::Degraded,
+        }
+    }
+}
+
+/// Components of AgentIdentity::trust_score
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct TrustScoreBreakdown {
+    pub trust_score: u16,
+    pub stake_points: u16,
+    pub age_points: u16,
+    pub slash_penalty: u16,
+}
+
+/// One entry of a register_agents_batch call
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchRegistration {
+    pub asset_address: Pubkey,
+    pub metadata_uri: String,
+    pub metadata_hash: [u8; 32],
+    pub transferable: bool,
+}
+
+/// Result of verify_identity, for CPI callers that branch on an agent's standing
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct IdentityStatus {
+    pub is_active: bool,
+    /// Past a non-zero expires_at without renewal
+    pub is_expired: bool,
+    pub has_minimum_stake: bool,
+    pub staked_amount: u64,
+    pub last_active_timestamp: i64,
+    pub slash_count: u32,
+    /// Whole days since registration
+    pub registration_age_days: u32,
+}
+
+/// Result of verify_metadata; `matches` compares the supplied hash with metadata_hash
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct MetadataCheck {
+    pub matches: bool,
+    pub metadata_hash: [u8; 32],
+    pub metadata_version: u32,
+}
+
+/// Result of get_referrals
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ReferralSummary {
+    /// Agent that referred this one (default if none)
+    pub referrer: Pubkey,
+    /// Agents registered with this one as their referrer
+    pub referral_count: u32,
+}
+
+// ============================================================================
+// NAME RECORD (Unique Agent Handles)
+// ============================================================================
+
+/// Claim on a unique, human-readable agent name
+/// PDA seeds: ["name", name]
+#[account]
+#[derive(InitSpace)]
+pub struct NameRecord {
+    /// Normalized name: lowercase ASCII letters, digits and inner dashes
+    #[max_len(32)]
+    pub name: String,
+
+    /// Wallet address of the agent holding the name
+    pub agent: Pubkey,
+
+    /// Timestamp of the claim or last transfer
+    pub claimed_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl NameRecord {
+    /// Seed prefix for PDA derivation
+    pub const SEED_PREFIX: &'static [u8] = b"name";
+
+    /// Calculate space for rent
+    pub const LEN: usize = 8 + // discriminator
+        4 + MAX_NAME_LEN + // name
+        32 + // agent
+        8 + // claimed_at
+        1; // bump
+
+    /// Whether `name` is already in normalized form: MIN_NAME_LEN to
+    /// MAX_NAME_LEN bytes of `a-z`, `0-9` and `-`, not starting or ending
+    /// with a dash. Names are never rewritten on-chain, so "Alice" and
+    /// "alice" cannot both map to a PDA.
+    pub fn is_valid_name(name: &str) -> bool {
+        (MIN_NAME_LEN..=MAX_NAME_LEN).contains(&name.len())
+            && name
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+            && !name.starts_with('-')
+            && !name.ends_with('-')
+    }
+}
+
+// ============================================================================
+// DELEGATED STAKE (Third-party Collateral)
+// ============================================================================
+
+/// A backer's position in an agent's delegated stake
+/// PDA seeds: ["delegated_stake", agent, backer]
+#[account]
+#[derive(InitSpace)]
+pub struct DelegatedStake {
+    /// Wallet address of the backed agent
+    pub agent: Pubkey,
+
+    /// Wallet that deposited the stake and alone may withdraw it
+    pub backer: Pubkey,
+
+    /// Shares of the agent's delegated stake held by the backer
+    pub shares: u64,
+
+    /// Portion of shares requested for withdrawal (still slashable)
+    pub pending_shares: u64,
+
+    /// When the pending shares become withdrawable (0 if none pending)
+    pub unlock_timestamp: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl DelegatedStake {
+    /// Seed prefix for PDA derivation
+    pub const SEED_PREFIX: &'static [u8] = b"delegated_stake";
+
+    /// Calculate space for rent
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        32 + // backer
+        8 + // shares
+        8 + // pending_shares
+        8 + // unlock_timestamp
+        1; // bump
+}
+
+// ============================================================================
+// SLASH RECORD (On-chain Slash History)
+// ============================================================================
+
+/// Lifecycle of a slash and its escrowed lamports
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
+pub enum SlashStatus {
+    /// Funds held in the record; the agent may still appeal
+    #[default]
+    Escrowed,
+    /// Agent appealed; awaiting resolve_appeal
+    Appealed,
+    /// Appeal upheld; funds returned to the agent's stake
+    Returned,
+    /// Funds released to the pool treasury
+    Released,
+}
+
+/// Record of a single slash, so a disputed slash has something on-chain to
+/// point at. The record also escrows the slashed lamports (on top of its
+/// rent) until the appeal window closes or an appeal is resolved.
+/// PDA seeds: ["slash", agent_address, slash_index (u32 LE)]
+#[account]
+#[derive(InitSpace)]
+pub struct SlashRecord {
+    /// Slashed agent's wallet address
+    pub agent: Pubkey,
+
+    /// Index of this slash for the agent (agent_identity.slash_count at slash time)
+    pub slash_index: u32,
+
+    /// Violation severity passed to slash_agent (basis points)
+    pub severity_bps: u16,
+
+    /// Lamports slashed
+    pub amount: u64,
+
+    /// SHA-256 of the reason string
+    pub reason_hash: [u8; 32],
+
+    /// Staking authority that performed the slash, or the multisig
+    /// executor for multisig slashes (paid the rent)
+    pub authority: Pubkey,
+
+    /// Unix timestamp of the slash
+    pub timestamp: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+
+    /// Where the escrowed lamports are in their lifecycle
+    pub status: SlashStatus,
+
+    /// Last moment the agent can appeal
+    pub appeal_deadline: i64,
+
+    /// Timestamp the funds were returned or released (0 while escrowed)
+    pub resolved_at: i64,
+
+    /// Part of `amount` taken from delegated stake; returned there if the appeal is upheld
+    pub delegated_amount: u64,
+}
+
+impl SlashRecord {
+    /// Seed prefix for PDA derivation
+    pub const SEED_PREFIX: &'static [u8] = b"slash";
+
+    /// Calculate space for rent
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        4 + // slash_index
+        2 + // severity_bps
+        8 + // amount
+        32 + // reason_hash
+        32 + // authority
+        8 + // timestamp
+        1 + // bump
+        1 + // status
+        8 + // appeal_deadline
+        8 + // resolved_at
+        8; // delegated_amount
+
+    /// Funds have left escrow (returned or released)
+    pub fn is_finalized(&self) -> bool {
+        matches!(self.status, SlashStatus::Returned | SlashStatus::Released)
     }
 }
 
@@ -145,6 +819,27 @@ pub struct StakingPool {
 
     /// PDA bump seed
     pub bump: u8,
+
+    /// Receives slashed funds once they leave escrow
+    pub treasury: Pubkey,
+
+    /// Seconds an agent has to appeal a slash
+    pub slash_appeal_window: i64,
+
+    /// When set, only the reputation multisig can slash; slash_agent is rejected
+    pub multisig_slashing_only: bool,
+
+    /// Trust points for a maximal (log-scaled) stake
+    pub trust_stake_weight: u16,
+
+    /// Trust points for a stake at least TRUST_STAKE_AGE_CAP old
+    pub trust_age_weight: u16,
+
+    /// Trust points removed per slash
+    pub trust_slash_penalty: u16,
+
+    /// Minimum stake per ServiceCategory, indexed by variant (0 = use the global minimum)
+    pub category_min_stakes: [u64; ServiceCategory::COUNT],
 }
 
 impl StakingPool {
@@ -160,7 +855,32 @@ impl StakingPool {
         8 + // min_stake_amount
         8 + // unlock_period
         1 + // is_paused
-        1; // bump
+        1 + // bump
+        32 + // treasury
+        8 + // slash_appeal_window
+        1 + // multisig_slashing_only
+        2 + // trust_stake_weight
+        2 + // trust_age_weight
+        2 + // trust_slash_penalty
+        8 * ServiceCategory::COUNT; // category_min_stakes
+
+    /// Size of pools created before slash escrow, trust weights and
+    /// per-category minimums were added
+    pub const LEGACY_LEN: usize = Self::LEN - 32 - 8 - 1 - 2 - 2 - 2 - 8 * ServiceCategory::COUNT;
+
+    /// Size of pools migrated for slash escrow and trust weights, before
+    /// per-category minimums were added
+    pub const PRE_CATEGORY_LEN: usize = Self::LEN - 8 * ServiceCategory::COUNT;
+
+    /// Minimum stake for an agent in `category`: the category's own floor
+    /// when one is set, otherwise the global minimum
+    pub fn min_stake_for(&self, category: Option<ServiceCategory>) -> u64 {
+        let global = self.min_stake_amount.max(MIN_STAKE_AMOUNT);
+        match category.map(|category| self.category_min_stakes[category as usize]) {
+            Some(category_min) if category_min > 0 => category_min,
+            _ => global,
+        }
+    }
 }
 
 // ============================================================================
@@ -190,6 +910,18 @@ pub struct ProgramConfig {
 
     /// PDA bump seed
     pub bump: u8,
+
+    /// Admin proposed by propose_admin, awaiting accept_admin (default if none)
+    pub pending_admin: Pubkey,
+
+    /// Seconds after paused_at when a pause lapses on its own
+    pub max_pause_duration_seconds: i64,
+
+    /// Lifetime given to identities at registration and renewal (0 = identities never expire)
+    pub default_validity_seconds: i64,
+
+    /// Minimum seconds between two heartbeats from the same identity
+    pub heartbeat_interval_seconds: i64,
 }
 
 impl ProgramConfig {
@@ -203,10 +935,72 @@ impl ProgramConfig {
         8 + // paused_at
         4 + 100 + // pause_reason
         4 + // rate_limit_per_minute
-        1; // bump
+        1 + // bump
+        32 + // pending_admin
+        8 + // max_pause_duration_seconds
+        8 + // default_validity_seconds
+        8; // heartbeat_interval_seconds
+
+    /// Size before pending_admin, max_pause_duration_seconds,
+    /// default_validity_seconds and heartbeat_interval_seconds were appended
+    pub const LEGACY_LEN: usize = Self::LEN - 32 - 8 - 8 - 8;
 
     /// Default rate limit: 60 instructions per minute
     pub const DEFAULT_RATE_LIMIT: u32 = 60;
+
+    /// Default pause lifetime for migrated configs: 7 days
+    pub const DEFAULT_MAX_PAUSE_DURATION: i64 = 7 * 24 * 60 * 60;
+
+    /// Default minimum spacing between heartbeats: 1 hour
+    pub const DEFAULT_HEARTBEAT_INTERVAL: i64 = 60 * 60;
+
+    /// Whether a pause is in force at `now`; a pause past its max duration no longer counts
+    pub fn pause_active(&self, now: i64) -> bool {
+        self.is_paused && now < self.pause_expires_at()
+    }
+
+    /// Timestamp when the current pause lapses
+    pub fn pause_expires_at(&self) -> i64 {
+        self.paused_at.saturating_add(self.max_pause_duration_seconds)
+    }
+
+    /// Read the guards from the (possibly uninitialized) PDA; the program is
+    /// unpaused with the default rate limit, heartbeat interval and no
+    /// identity expiry until initialize_program_config runs
+    pub fn resolve(info: &AccountInfo) -> Result<ProgramGuards> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(ProgramGuards::default());
+        }
+        let data = info.try_borrow_data()?;
+        let config = ProgramConfig::try_deserialize(&mut &data[..])?;
+        Ok(ProgramGuards {
+            is_paused: config.pause_active(Clock::get()?.unix_timestamp),
+            rate_limit_per_minute: config.rate_limit_per_minute,
+            default_validity_seconds: config.default_validity_seconds,
+            heartbeat_interval_seconds: config.heartbeat_interval_seconds,
+        })
+    }
+}
+
+/// Pause flag, rate limit, identity validity and heartbeat interval resolved
+/// for a single gated instruction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProgramGuards {
+    pub is_paused: bool,
+    pub rate_limit_per_minute: u32,
+    pub default_validity_seconds: i64,
+    pub heartbeat_interval_seconds: i64,
+}
+
+impl Default for ProgramGuards {
+    fn default() -> Self {
+        Self {
+            is_paused: false,
+            rate_limit_per_minute: ProgramConfig::DEFAULT_RATE_LIMIT,
+            default_validity_seconds: 0,
+            heartbeat_interval_seconds: ProgramConfig::DEFAULT_HEARTBEAT_INTERVAL,
+        }
+    }
 }
 
 // ============================================================================
@@ -232,6 +1026,9 @@ pub struct UserRateLimit {
 
     /// PDA bump seed
     pub bump: u8,
+
+    /// Wallet that paid the rent; refunded on close
+    pub payer: Pubkey,
 }
 
 impl UserRateLimit {
@@ -244,11 +1041,18 @@ impl UserRateLimit {
         8 + // window_start
         4 + // instruction_count
         8 + // last_instruction
-        1; // bump
+        1 + // bump
+        32; // payer
+
+    /// Size before payer was appended
+    pub const LEGACY_LEN: usize = Self::LEN - 32;
 
     /// Window size: 60 seconds
     pub const WINDOW_SIZE: i64 = 60;
 
+    /// Idle time after which anyone may close the account: 30 days
+    pub const IDLE_CLOSE_PERIOD: i64 = 30 * 24 * 60 * 60;
+
     /// Check if rate limit is exceeded
     pub fn is_rate_limited(&self, current_timestamp: i64, max_per_minute: u32) -> bool {
         // If window has passed, reset
@@ -269,3 +1073,253 @@ impl UserRateLimit {
         self.last_instruction = current_timestamp;
     }
 }
+
+// ============================================================================
+// RATE LIMIT EXEMPTION (Trusted Relayers & Services)
+// ============================================================================
+
+/// Admin-granted exemption from the program rate limit
+/// PDA seeds: ["rate_exempt", account]
+#[account]
+#[derive(InitSpace)]
+pub struct ExemptAccount {
+    /// Exempted wallet
+    pub account: Pubkey,
+
+    /// Per-minute cap replacing the program limit (None = not counted at all)
+    pub max_per_minute: Option<u32>,
+
+    /// Timestamp when the exemption was granted
+    pub granted_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ExemptAccount {
+    /// Seed prefix for PDA derivation
+    pub const SEED_PREFIX: &'static [u8] = b"rate_exempt";
+
+    /// Calculate space for rent
+    pub const LEN: usize = 8 + // discriminator
+        32 + // account
+        1 + 4 + // max_per_minute
+        8 + // granted_at
+        1; // bump
+
+    /// Per-minute cap for the wallet behind the (possibly absent) exemption PDA:
+    /// the exemption's cap, None when fully exempt, otherwise `program_limit`
+    pub fn rate_limit(info: &AccountInfo, program_limit: u32) -> Result<Option<u32>> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(Some(program_limit));
+        }
+        let data = info.try_borrow_data()?;
+        let exemption = ExemptAccount::try_deserialize(&mut &data[..])?;
+        Ok(exemption.max_per_minute)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gs2_common::test_utils::u64_samples;
+
+    fn key(byte: u8) -> Pubkey {
+        Pubkey::new_from_array([byte; 32])
+    }
+
+    /// An identity with a distinct non-default value in every field, so a
+    /// swapped or missing field shows up as a mismatch
+    fn identity() -> AgentIdentity {
+        AgentIdentity {
+            agent_address: key(1),
+            asset_address: key(2),
+            metadata_uri: "https://example.com/agent.json".to_string(),
+            registration_timestamp: 3,
+            last_active_timestamp: 4,
+            activity_count: 5,
+            is_active: true,
+            staked_amount: 6,
+            stake_unlock_timestamp: 7,
+            slash_count: 8,
+            total_slashed: 9,
+            bump: 10,
+            deactivated_at: 11,
+            pending_unstake_amount: 12,
+            first_staked_at: 13,
+            delegate: key(14),
+            delegate_scope: DELEGATE_SCOPE_ALL,
+            capabilities: CAPABILITY_MASK_V1,
+            service_category: Some(ServiceCategory::CodeGeneration),
+            metadata_hash: [15; 32],
+            metadata_version: 16,
+            name_record: key(17),
+            expires_at: 18,
+            referrer: key(19),
+            referral_count: 20,
+            deactivated_by: key(21),
+            deactivation_reason_hash: [22; 32],
+            delegated_stake: 23,
+            delegated_shares: 24,
+            pending_delegated_shares: 25,
+            last_heartbeat_at: 26,
+            health_status: HealthStatus::Degraded,
+            transferable: true,
+            version: 27,
+        }
+    }
+
+    #[test]
+    fn gs2_common_reads_agent_identity() {
+        let identity = identity();
+        let mut data = Vec::new();
+        identity.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= 8 + AgentIdentity::INIT_SPACE);
+        // Real accounts are allocated at full size, so trailing bytes are zeroed
+        data.resize(8 + AgentIdentity::INIT_SPACE, 0);
+
+        let mirror = gs2_common::AgentIdentity::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(mirror, gs2_common::AgentIdentity::from(&identity));
+        assert_eq!(mirror.is_current(17), identity.is_current(17));
+        assert_eq!(mirror.is_current(18), identity.is_current(18));
+    }
+
+    #[test]
+    fn gs2_common_writes_agent_identity() {
+        let mirror = gs2_common::AgentIdentity::from(&identity());
+        let mut data = Vec::new();
+        mirror.try_serialize(&mut data).unwrap();
+
+        let decoded = AgentIdentity::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(gs2_common::AgentIdentity::from(&decoded), mirror);
+    }
+
+    #[test]
+    fn gs2_common_prefix_reads_accounts_without_later_fields() {
+        let identity = identity();
+        let mut data = Vec::new();
+        identity.try_serialize(&mut data).unwrap();
+
+        // Cut everything after expires_at, as on an identity created before referrals
+        let later_fields = 32 + 4 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1 + 1;
+        let legacy = &data[..data.len() - later_fields];
+        assert!(gs2_common::AgentIdentity::try_deserialize(&mut &legacy[..]).is_err());
+
+        let prefix = gs2_common::AgentIdentityPrefix::try_deserialize(&mut &legacy[..]).unwrap();
+        assert_eq!(prefix, gs2_common::AgentIdentityPrefix::from(&gs2_common::AgentIdentity::from(&identity)));
+        assert_eq!(prefix.expires_at, identity.expires_at);
+    }
+
+    /// Boundary values plus pseudo-random ones spread across every magnitude
+    fn u64_domain() -> Vec<u64> {
+        let mut values = vec![
+            0,
+            1,
+            MIN_STAKE_AMOUNT - 1,
+            MIN_STAKE_AMOUNT,
+            MIN_STAKE_AMOUNT << 9,
+            u64::MAX / 10_000,
+            u64::MAX / 2,
+            u64::MAX - 1,
+            u64::MAX,
+        ];
+        values.extend(u64_samples(64));
+        values
+    }
+
+    fn i64_domain() -> Vec<i64> {
+        let mut values = vec![i64::MIN, i64::MIN + 1, -1, 0, 1, TRUST_STAKE_AGE_CAP, i64::MAX - 1, i64::MAX];
+        values.extend(u64_domain().into_iter().map(|v| v as i64));
+        values
+    }
+
+    fn pool(stake_weight: u16, age_weight: u16, slash_penalty: u16) -> StakingPool {
+        StakingPool {
+            authority: key(1),
+            total_staked: 0,
+            total_stakers: 0,
+            total_slashed: 0,
+            min_stake_amount: MIN_STAKE_AMOUNT,
+            unlock_period: 0,
+            is_paused: false,
+            bump: 0,
+            treasury: key(1),
+            slash_appeal_window: 0,
+            multisig_slashing_only: false,
+            trust_stake_weight: stake_weight,
+            trust_age_weight: age_weight,
+            trust_slash_penalty: slash_penalty,
+            category_min_stakes: [0; ServiceCategory::COUNT],
+        }
+    }
+
+    #[test]
+    fn slash_amount_is_capped_and_monotonic_in_severity() {
+        let mut identity = identity();
+        for staked in u64_domain() {
+            for delegated in [0, 1, staked, u64::MAX] {
+                identity.staked_amount = staked;
+                identity.delegated_stake = delegated;
+                let cap = (staked as u128 + delegated as u128) * MAX_SLASH_BPS as u128 / 10_000;
+
+                let mut previous = 0;
+                for severity in (0..=u16::MAX).step_by(7).chain([10_000, u16::MAX]) {
+                    let amount = identity.calculate_slash_amount(severity);
+                    assert!(amount as u128 <= cap, "{staked} + {delegated} at {severity}: {amount}");
+                    assert!(amount >= previous, "{staked} + {delegated} at {severity}: {amount} < {previous}");
+                    previous = amount;
+                }
+                assert_eq!(previous as u128, cap, "{staked} + {delegated}");
+            }
+        }
+    }
+
+    #[test]
+    fn slash_amount_does_not_wrap_when_delegated_stake_pushes_past_u64() {
+        let mut identity = identity();
+        identity.staked_amount = u64::MAX - 1;
+        identity.delegated_stake = 2;
+        assert_eq!(identity.calculate_slash_amount(10_000), 1 << 63);
+
+        identity.staked_amount = u64::MAX;
+        identity.delegated_stake = u64::MAX;
+        assert_eq!(identity.calculate_slash_amount(10_000), u64::MAX);
+        assert_eq!(identity.delegated_slash_share(u64::MAX), u64::MAX / 2);
+    }
+
+    #[test]
+    fn trust_score_is_bounded_and_monotonic_in_stake_and_age() {
+        let mut identity = identity();
+        identity.pending_unstake_amount = 0;
+        for (stake_weight, age_weight, slash_penalty) in
+            [(DEFAULT_TRUST_STAKE_WEIGHT, DEFAULT_TRUST_AGE_WEIGHT, DEFAULT_TRUST_SLASH_PENALTY), (u16::MAX, u16::MAX, 0)]
+        {
+            let pool = pool(stake_weight, age_weight, slash_penalty);
+            for first_staked_at in i64_domain() {
+                identity.first_staked_at = first_staked_at;
+
+                let mut stakes = u64_domain();
+                stakes.sort_unstable();
+                let mut previous = 0;
+                for &staked in &stakes {
+                    identity.staked_amount = staked;
+                    let score = identity.trust_score(&pool, first_staked_at.saturating_add(86_400));
+                    assert!(score.trust_score <= MAX_TRUST_SCORE);
+                    assert!(score.stake_points <= stake_weight && score.age_points <= age_weight);
+                    assert!(score.trust_score >= previous, "stake {staked}");
+                    previous = score.trust_score;
+                }
+
+                identity.staked_amount = MIN_STAKE_AMOUNT;
+                let mut times = i64_domain();
+                times.sort_unstable();
+                let mut previous = 0;
+                for now in times {
+                    let score = identity.trust_score(&pool, now).trust_score;
+                    assert!(score >= previous, "staked at {first_staked_at}, now {now}");
+                    previous = score;
+                }
+            }
+        }
+    }
+}
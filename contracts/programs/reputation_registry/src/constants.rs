@@ -2,3 +2,9 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+// identity_registry's AgentIdentity PDA may sign initialize_reputation via CPI;
+// only vote_registry's stats PDA may call record_vote_result/record_review
+pub use gs2_common::{
+    IDENTITY_AGENT_SEED, IDENTITY_REGISTRY_PROGRAM_ID, STATS_CPI_AUTHORITY_SEED, VOTE_REGISTRY_PROGRAM_ID,
+};
@@ -28,4 +28,49 @@ pub enum ReputationError {
 
     #[msg("Invalid authority for this operation")]
     InvalidAuthority,
+
+    #[msg("Stale update: nonce does not match or computed_at is not newer than last update")]
+    StaleUpdate,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+
+    #[msg("Account is not a valid legacy account for this migration")]
+    InvalidMigrationTarget,
+
+    #[msg("Account layout version is no longer supported; call migrate_account first")]
+    UnsupportedAccountVersion,
+
+    #[msg("Tier thresholds must be strictly increasing, at most 1000, with margin at most 100")]
+    InvalidTierThresholds,
+
+    #[msg("Reputation is frozen pending investigation")]
+    ReputationFrozen,
+
+    #[msg("Reputation is not frozen")]
+    ReputationNotFrozen,
+
+    #[msg("Only the agent, the reputation authority or identity_registry may initialize reputation")]
+    UnauthorizedInitializer,
+
+    #[msg("Review rating must be between 0 and 50")]
+    InvalidReviewRatingInput,
+
+    #[msg("Signature hash does not match the payment signature")]
+    PaymentSignatureHashMismatch,
+
+    #[msg("Payment proof is still within its retention period")]
+    PaymentProofRetentionActive,
+
+    #[msg("Registry is paused by emergency multisig proposal")]
+    RegistryPaused,
+
+    #[msg("Deactivate the agent's identity before closing its reputation")]
+    IdentityStillActive,
+
+    #[msg("Payment time must not be in the future or older than the payment proof retention period")]
+    PaymentOutsideRetention,
 }
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+/// Emitted whenever an agent's scores are overwritten by the authority or a multisig proposal
+#[event]
+pub struct ReputationUpdated {
+    pub agent: Pubkey,
+    pub overall_score: u16,
+    pub update_nonce: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the reputation authority nominates a successor
+#[event]
+pub struct AuthorityTransferProposed {
+    pub current_authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+/// Emitted when the reputation authority changes hands
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+/// Emitted only when an agent's tier crosses a boundary
+#[event]
+pub struct TierChanged {
+    pub agent: Pubkey,
+    pub previous_tier: u8,
+    pub new_tier: u8,
+    pub overall_score: u16,
+}
+
+/// Emitted when an agent's reputation is frozen; the reason text is kept off-chain
+#[event]
+pub struct AgentFrozen {
+    pub agent: Pubkey,
+    pub reason_hash: [u8; 32],
+    pub frozen_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a freeze is lifted
+#[event]
+pub struct AgentUnfrozen {
+    pub agent: Pubkey,
+    pub unfrozen_by: Pubkey,
+    pub frozen_seconds: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an emergency pause proposal is executed
+#[event]
+pub struct RegistryPauseChanged {
+    pub paused: bool,
+    pub proposal_id: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use crate::state::{MultisigAuthority, ReputationAuthority};
+use crate::error::ReputationError;
+use crate::events::{AuthorityTransferProposed, AuthorityTransferred};
+
+// ==================== PROPOSE AUTHORITY TRANSFER ====================
+
+#[derive(Accounts)]
+pub struct ProposeAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [ReputationAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ ReputationError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, ReputationAuthority>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Nominate a new authority; the nominee must accept before it takes effect
+pub fn propose_authority_transfer(
+    ctx: Context<ProposeAuthorityTransfer>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    require!(
+        new_authority != Pubkey::default() && new_authority != ctx.accounts.authority.key(),
+        ReputationError::InvalidAuthority
+    );
+
+    let authority_account = &mut ctx.accounts.authority_account;
+    authority_account.pending_authority = new_authority;
+
+    emit!(AuthorityTransferProposed {
+        current_authority: authority_account.authority,
+        pending_authority: new_authority,
+    });
+
+    msg!("Authority transfer proposed: {} -> {}", authority_account.authority, new_authority);
+
+    Ok(())
+}
+
+// ==================== CANCEL AUTHORITY TRANSFER ====================
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [ReputationAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ ReputationError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, ReputationAuthority>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Withdraw a pending nomination
+pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+    let authority_account = &mut ctx.accounts.authority_account;
+
+    require!(
+        authority_account.pending_authority != Pubkey::default(),
+        ReputationError::NoPendingAuthority
+    );
+
+    msg!("Authority transfer to {} cancelled", authority_account.pending_authority);
+    authority_account.pending_authority = Pubkey::default();
+
+    Ok(())
+}
+
+// ==================== ACCEPT AUTHORITY TRANSFER ====================
+
+#[derive(Accounts)]
+pub struct AcceptAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [ReputationAuthority::SEED_PREFIX],
+        bump = authority_account.bump
+    )]
+    pub authority_account: Account<'info, ReputationAuthority>,
+
+    /// The nominated authority
+    pub new_authority: Signer<'info>,
+}
+
+/// Complete a transfer (must be signed by the nominated authority)
+pub fn accept_authority_transfer(ctx: Context<AcceptAuthorityTransfer>) -> Result<()> {
+    let authority_account = &mut ctx.accounts.authority_account;
+
+    require!(
+        authority_account.pending_authority != Pubkey::default(),
+        ReputationError::NoPendingAuthority
+    );
+    require!(
+        authority_account.pending_authority == ctx.accounts.new_authority.key(),
+        ReputationError::NotPendingAuthority
+    );
+
+    let previous_authority = authority_account.authority;
+    authority_account.authority = authority_account.pending_authority;
+    authority_account.pending_authority = Pubkey::default();
+
+    emit!(AuthorityTransferred {
+        previous_authority,
+        new_authority: authority_account.authority,
+    });
+
+    msg!("Authority transferred: {} -> {}", previous_authority, authority_account.authority);
+
+    Ok(())
+}
+
+// ==================== RENOUNCE TO MULTISIG ====================
+
+#[derive(Accounts)]
+pub struct RenounceAuthorityToMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [ReputationAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ ReputationError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, ReputationAuthority>,
+
+    /// The multisig must already exist so updates can still flow through proposals
+    #[account(
+        seeds = [MultisigAuthority::SEED_PREFIX],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, MultisigAuthority>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Hand authority to the multisig PDA; direct update_reputation calls become impossible
+pub fn renounce_authority_to_multisig(ctx: Context<RenounceAuthorityToMultisig>) -> Result<()> {
+    let authority_account = &mut ctx.accounts.authority_account;
+    let previous_authority = authority_account.authority;
+
+    authority_account.authority = ctx.accounts.multisig.key();
+    authority_account.pending_authority = Pubkey::default();
+
+    emit!(AuthorityTransferred {
+        previous_authority,
+        new_authority: authority_account.authority,
+    });
+
+    msg!("Authority renounced to multisig {}", authority_account.authority);
+
+    Ok(())
+}
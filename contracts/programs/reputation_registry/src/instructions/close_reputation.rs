@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use gs2_common::AgentIdentityPrefix;
+use crate::constants::{IDENTITY_AGENT_SEED, IDENTITY_REGISTRY_PROGRAM_ID};
+use crate::state::AgentReputation;
+use crate::error::ReputationError;
+
+/// Close an agent's reputation and return its rent to the agent.
+///
+/// This is the step before identity_registry's close_identity, which refuses
+/// while the reputation account still exists. The identity must already be
+/// deactivated (or gone, after a transfer), and a frozen reputation stays
+/// until the investigation behind the freeze is over.
+#[derive(Accounts)]
+pub struct CloseReputation<'info> {
+    #[account(
+        mut,
+        seeds = [AgentReputation::SEED_PREFIX, agent.key().as_ref()],
+        bump = agent_reputation.bump,
+        close = agent,
+        constraint = !agent_reputation.is_frozen @ ReputationError::ReputationFrozen
+    )]
+    pub agent_reputation: Account<'info, AgentReputation>,
+
+    /// CHECK: The agent's AgentIdentity PDA in identity_registry; read with AgentIdentityPrefix
+    #[account(
+        seeds = [IDENTITY_AGENT_SEED, agent.key().as_ref()],
+        bump,
+        seeds::program = IDENTITY_REGISTRY_PROGRAM_ID
+    )]
+    pub agent_identity: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub agent: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseReputation>) -> Result<()> {
+    let identity = &ctx.accounts.agent_identity;
+    if !identity.data_is_empty() {
+        let data = identity.try_borrow_data()?;
+        let identity = AgentIdentityPrefix::try_deserialize(&mut &data[..])?;
+        require!(!identity.is_active, ReputationError::IdentityStillActive);
+    }
+
+    msg!("Reputation closed for agent: {}", ctx.accounts.agent_reputation.agent_address);
+
+    Ok(())
+}
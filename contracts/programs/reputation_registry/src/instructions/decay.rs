@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::state::AgentReputation;
+use crate::state::{
+    AgentReputation, DecayConfig, MultisigAuthority, PauseState, ReputationAuthority, TierConfig,
+};
+use crate::events::TierChanged;
 use crate::error::ReputationError;
 
 // ==================== DECAY ERRORS ====================
@@ -10,6 +13,155 @@ pub enum DecayError {
     DecayNotEnabled,
     #[msg("Invalid decay rate: must be between 100 and 10000 bps")]
     InvalidDecayRate,
+    #[msg("Invalid half-life: must be between 7 and 365 days")]
+    InvalidHalfLife,
+    #[msg("Invalid decay floor: must be at most 1000")]
+    InvalidMinScore,
+    #[msg("Invalid grace period: must be at most 365 days")]
+    InvalidGracePeriod,
+    #[msg("Decay is globally paused")]
+    DecayPaused,
+}
+
+fn validate_decay_config(half_life_days: u16, min_score: u16, grace_period_days: u16) -> Result<()> {
+    require!(
+        (DecayConfig::MIN_HALF_LIFE_DAYS..=DecayConfig::MAX_HALF_LIFE_DAYS).contains(&half_life_days),
+        DecayError::InvalidHalfLife
+    );
+    require!(min_score <= 1000, DecayError::InvalidMinScore);
+    require!(
+        grace_period_days <= DecayConfig::MAX_GRACE_PERIOD_DAYS,
+        DecayError::InvalidGracePeriod
+    );
+    Ok(())
+}
+
+// ==================== INITIALIZE DECAY CONFIG ====================
+
+#[derive(Accounts)]
+pub struct InitializeDecayConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = DecayConfig::LEN,
+        seeds = [DecayConfig::SEED_PREFIX],
+        bump
+    )]
+    pub decay_config: Account<'info, DecayConfig>,
+
+    #[account(
+        seeds = [ReputationAuthority::SEED_PREFIX],
+        bump = authority_account.bump
+    )]
+    pub authority_account: Account<'info, ReputationAuthority>,
+
+    /// Multisig whose admin may act in place of the authority
+    #[account(
+        seeds = [MultisigAuthority::SEED_PREFIX],
+        bump = multisig.bump
+    )]
+    pub multisig: Option<Account<'info, MultisigAuthority>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the registry-wide decay config (authority or multisig admin)
+pub fn initialize_decay_config(
+    ctx: Context<InitializeDecayConfig>,
+    half_life_days: u16,
+    min_score: u16,
+    grace_period_days: u16,
+) -> Result<()> {
+    require!(
+        ctx.accounts.authority_account.is_admin(
+            ctx.accounts.authority.key,
+            ctx.accounts.multisig.as_deref()
+        ),
+        ReputationError::UnauthorizedAuthority
+    );
+    validate_decay_config(half_life_days, min_score, grace_period_days)?;
+
+    let config = &mut ctx.accounts.decay_config;
+    config.half_life_days = half_life_days;
+    config.min_score = min_score;
+    config.grace_period_days = grace_period_days;
+    config.decay_paused = false;
+    config.updated_at = Clock::get()?.unix_timestamp;
+    config.bump = ctx.bumps.decay_config;
+
+    msg!(
+        "Decay config initialized: half-life {}d, floor {}, grace {}d",
+        half_life_days,
+        min_score,
+        grace_period_days
+    );
+
+    Ok(())
+}
+
+// ==================== UPDATE DECAY CONFIG ====================
+
+#[derive(Accounts)]
+pub struct UpdateDecayConfig<'info> {
+    #[account(
+        mut,
+        seeds = [DecayConfig::SEED_PREFIX],
+        bump = decay_config.bump
+    )]
+    pub decay_config: Account<'info, DecayConfig>,
+
+    #[account(
+        seeds = [ReputationAuthority::SEED_PREFIX],
+        bump = authority_account.bump
+    )]
+    pub authority_account: Account<'info, ReputationAuthority>,
+
+    /// Multisig whose admin may act in place of the authority
+    #[account(
+        seeds = [MultisigAuthority::SEED_PREFIX],
+        bump = multisig.bump
+    )]
+    pub multisig: Option<Account<'info, MultisigAuthority>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Update decay parameters and the global kill-switch (authority or multisig admin)
+pub fn update_decay_config(
+    ctx: Context<UpdateDecayConfig>,
+    half_life_days: u16,
+    min_score: u16,
+    grace_period_days: u16,
+    decay_paused: bool,
+) -> Result<()> {
+    require!(
+        ctx.accounts.authority_account.is_admin(
+            ctx.accounts.authority.key,
+            ctx.accounts.multisig.as_deref()
+        ),
+        ReputationError::UnauthorizedAuthority
+    );
+    validate_decay_config(half_life_days, min_score, grace_period_days)?;
+
+    let config = &mut ctx.accounts.decay_config;
+    config.half_life_days = half_life_days;
+    config.min_score = min_score;
+    config.grace_period_days = grace_period_days;
+    config.decay_paused = decay_paused;
+    config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Decay config updated: half-life {}d, floor {}, grace {}d, paused {}",
+        half_life_days,
+        min_score,
+        grace_period_days,
+        decay_paused
+    );
+
+    Ok(())
 }
 
 // ==================== APPLY DECAY ====================
@@ -19,10 +171,24 @@ pub struct ApplyDecay<'info> {
     #[account(
         mut,
         seeds = [AgentReputation::SEED_PREFIX, agent_reputation.agent_address.as_ref()],
-        bump = agent_reputation.bump
+        bump = agent_reputation.bump,
+        constraint = !agent_reputation.is_frozen @ ReputationError::ReputationFrozen,
+        constraint = agent_reputation.is_supported_version() @ ReputationError::UnsupportedAccountVersion
     )]
     pub agent_reputation: Account<'info, AgentReputation>,
 
+    /// CHECK: Decay config PDA; defaults apply while it is uninitialized
+    #[account(seeds = [DecayConfig::SEED_PREFIX], bump)]
+    pub decay_config: UncheckedAccount<'info>,
+
+    /// CHECK: Tier config PDA; default thresholds apply while it is uninitialized
+    #[account(seeds = [TierConfig::SEED_PREFIX], bump)]
+    pub tier_config: UncheckedAccount<'info>,
+
+    /// CHECK: Pause state PDA; unpaused while it is uninitialized
+    #[account(seeds = [PauseState::SEED_PREFIX], bump)]
+    pub pause_state: UncheckedAccount<'info>,
+
     /// Anyone can trigger decay calculation (permissionless)
     pub caller: Signer<'info>,
 }
@@ -34,14 +200,32 @@ pub fn apply_decay(ctx: Context<ApplyDecay>) -> Result<()> {
     let clock = Clock::get()?;
 
     require!(reputation.decay_enabled, DecayError::DecayNotEnabled);
+    require!(
+        !PauseState::is_paused(&ctx.accounts.pause_state)?,
+        ReputationError::RegistryPaused
+    );
+
+    let params = DecayConfig::resolve(&ctx.accounts.decay_config)?;
+    require!(!params.paused, DecayError::DecayPaused);
 
     // Calculate and apply decayed score
-    let decayed_score = reputation.calculate_decayed_score(clock.unix_timestamp);
+    let decayed_score = reputation.calculate_decayed_score(clock.unix_timestamp, &params);
     let previous_score = reputation.overall_score;
 
     reputation.overall_score = decayed_score;
     reputation.last_updated = clock.unix_timestamp;
 
+    // Decay can only move the score down, so this may demote but never promote
+    let tier_params = TierConfig::resolve(&ctx.accounts.tier_config)?;
+    if let Some(previous_tier) = reputation.refresh_tier(&tier_params) {
+        emit!(TierChanged {
+            agent: reputation.agent_address,
+            previous_tier,
+            new_tier: reputation.tier,
+            overall_score: decayed_score,
+        });
+    }
+
     msg!(
         "Decay applied to agent {}: {} -> {}",
         reputation.agent_address,
@@ -59,7 +243,8 @@ pub struct EnableDecay<'info> {
     #[account(
         mut,
         seeds = [AgentReputation::SEED_PREFIX, agent_reputation.agent_address.as_ref()],
-        bump = agent_reputation.bump
+        bump = agent_reputation.bump,
+        constraint = agent_reputation.is_supported_version() @ ReputationError::UnsupportedAccountVersion
     )]
     pub agent_reputation: Account<'info, AgentReputation>,
 
@@ -74,7 +259,7 @@ pub fn enable_decay(ctx: Context<EnableDecay>, decay_rate_bps: u16) -> Result<()
     let clock = Clock::get()?;
 
     require!(
-        decay_rate_bps >= 100 && decay_rate_bps <= 10000,
+        (100..=10000).contains(&decay_rate_bps),
         DecayError::InvalidDecayRate
     );
 
@@ -101,7 +286,8 @@ pub struct DisableDecay<'info> {
     #[account(
         mut,
         seeds = [AgentReputation::SEED_PREFIX, agent_reputation.agent_address.as_ref()],
-        bump = agent_reputation.bump
+        bump = agent_reputation.bump,
+        constraint = agent_reputation.is_supported_version() @ ReputationError::UnsupportedAccountVersion
     )]
     pub agent_reputation: Account<'info, AgentReputation>,
 
@@ -130,7 +316,9 @@ pub struct RecordActivity<'info> {
     #[account(
         mut,
         seeds = [AgentReputation::SEED_PREFIX, agent_reputation.agent_address.as_ref()],
-        bump = agent_reputation.bump
+        bump = agent_reputation.bump,
+        constraint = !agent_reputation.is_frozen @ ReputationError::ReputationFrozen,
+        constraint = agent_reputation.is_supported_version() @ ReputationError::UnsupportedAccountVersion
     )]
     pub agent_reputation: Account<'info, AgentReputation>,
 
@@ -169,9 +357,14 @@ pub fn record_activity(ctx: Context<RecordActivity>) -> Result<()> {
 pub struct GetEffectiveScore<'info> {
     #[account(
         seeds = [AgentReputation::SEED_PREFIX, agent_reputation.agent_address.as_ref()],
-        bump = agent_reputation.bump
+        bump = agent_reputation.bump,
+        constraint = agent_reputation.is_supported_version() @ ReputationError::UnsupportedAccountVersion
     )]
     pub agent_reputation: Account<'info, AgentReputation>,
+
+    /// CHECK: Decay config PDA; defaults apply while it is uninitialized
+    #[account(seeds = [DecayConfig::SEED_PREFIX], bump)]
+    pub decay_config: UncheckedAccount<'info>,
 }
 
 /// Get the effective score with decay applied (view function)
@@ -179,7 +372,8 @@ pub fn get_effective_score(ctx: Context<GetEffectiveScore>) -> Result<u16> {
     let reputation = &ctx.accounts.agent_reputation;
     let clock = Clock::get()?;
 
-    let effective_score = reputation.get_effective_score(clock.unix_timestamp);
+    let params = DecayConfig::resolve(&ctx.accounts.decay_config)?;
+    let effective_score = reputation.get_effective_score(clock.unix_timestamp, &params);
 
     msg!(
         "Effective score for agent {}: {} (base: {}, decay_enabled: {})",
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use crate::state::{AgentReputation, MultisigAuthority, ReputationAuthority};
+use crate::error::ReputationError;
+use crate::events::{AgentFrozen, AgentUnfrozen};
+
+// ==================== FREEZE REPUTATION ====================
+
+#[derive(Accounts)]
+pub struct FreezeReputation<'info> {
+    #[account(
+        mut,
+        seeds = [AgentReputation::SEED_PREFIX, agent_reputation.agent_address.as_ref()],
+        bump = agent_reputation.bump,
+        constraint = agent_reputation.is_supported_version() @ ReputationError::UnsupportedAccountVersion
+    )]
+    pub agent_reputation: Account<'info, AgentReputation>,
+
+    #[account(
+        seeds = [ReputationAuthority::SEED_PREFIX],
+        bump = authority_account.bump
+    )]
+    pub authority_account: Account<'info, ReputationAuthority>,
+
+    /// Multisig whose admin may act in place of the authority
+    #[account(
+        seeds = [MultisigAuthority::SEED_PREFIX],
+        bump = multisig.bump
+    )]
+    pub multisig: Option<Account<'info, MultisigAuthority>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Freeze an agent's reputation pending investigation (authority or multisig admin).
+/// Only a hash of the reason is recorded on-chain.
+pub fn freeze_reputation(ctx: Context<FreezeReputation>, reason_hash: [u8; 32]) -> Result<()> {
+    require!(
+        ctx.accounts.authority_account.is_admin(
+            ctx.accounts.authority.key,
+            ctx.accounts.multisig.as_deref()
+        ),
+        ReputationError::UnauthorizedAuthority
+    );
+
+    let reputation = &mut ctx.accounts.agent_reputation;
+    require!(!reputation.is_frozen, ReputationError::ReputationFrozen);
+
+    let clock = Clock::get()?;
+    reputation.is_frozen = true;
+    reputation.frozen_at = clock.unix_timestamp;
+
+    emit!(AgentFrozen {
+        agent: reputation.agent_address,
+        reason_hash,
+        frozen_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Reputation frozen for agent {}", reputation.agent_address);
+
+    Ok(())
+}
+
+// ==================== UNFREEZE REPUTATION ====================
+
+#[derive(Accounts)]
+pub struct UnfreezeReputation<'info> {
+    #[account(
+        mut,
+        seeds = [AgentReputation::SEED_PREFIX, agent_reputation.agent_address.as_ref()],
+        bump = agent_reputation.bump,
+        constraint = agent_reputation.is_supported_version() @ ReputationError::UnsupportedAccountVersion
+    )]
+    pub agent_reputation: Account<'info, AgentReputation>,
+
+    #[account(
+        seeds = [ReputationAuthority::SEED_PREFIX],
+        bump = authority_account.bump
+    )]
+    pub authority_account: Account<'info, ReputationAuthority>,
+
+    /// Multisig whose admin may act in place of the authority
+    #[account(
+        seeds = [MultisigAuthority::SEED_PREFIX],
+        bump = multisig.bump
+    )]
+    pub multisig: Option<Account<'info, MultisigAuthority>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Lift a freeze (authority or multisig admin).
+/// The decay clock is shifted by the frozen duration so no decay is applied for that period.
+pub fn unfreeze_reputation(ctx: Context<UnfreezeReputation>) -> Result<()> {
+    require!(
+        ctx.accounts.authority_account.is_admin(
+            ctx.accounts.authority.key,
+            ctx.accounts.multisig.as_deref()
+        ),
+        ReputationError::UnauthorizedAuthority
+    );
+
+    let reputation = &mut ctx.accounts.agent_reputation;
+    require!(reputation.is_frozen, ReputationError::ReputationNotFrozen);
+
+    let clock = Clock::get()?;
+    let frozen_seconds = clock.unix_timestamp.saturating_sub(reputation.frozen_at);
+
+    reputation.last_activity = reputation.last_activity.saturating_add(frozen_seconds);
+    reputation.is_frozen = false;
+    reputation.frozen_at = 0;
+
+    emit!(AgentUnfrozen {
+        agent: reputation.agent_address,
+        unfrozen_by: ctx.accounts.authority.key(),
+        frozen_seconds,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Reputation unfrozen for agent {} after {}s",
+        reputation.agent_address,
+        frozen_seconds
+    );
+
+    Ok(())
+}
@@ -1,37 +1,72 @@
 use anchor_lang::prelude::*;
-use crate::state::AgentReputation;
+use crate::state::{AgentReputation, ComponentScores, DecayConfig, ReputationStats};
+use crate::error::ReputationError;
+
+/// Snapshot of an agent's reputation returned to callers via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReputationView {
+    pub agent: Pubkey,
+    pub overall_score: u16,
+    /// overall_score with decay applied as of the current slot
+    pub effective_score: u16,
+    pub component_scores: ComponentScores,
+    pub stats: ReputationStats,
+    pub last_updated: i64,
+    pub base_score: u16,
+    pub last_activity: i64,
+    pub decay_enabled: bool,
+    pub decay_rate_bps: u16,
+    pub tier: u8,
+    pub update_nonce: u64,
+    pub is_frozen: bool,
+    pub payment_proof_count: u32,
+}
 
 #[derive(Accounts)]
 pub struct GetReputation<'info> {
     #[account(
         seeds = [AgentReputation::SEED_PREFIX, agent_address.key().as_ref()],
-        bump = agent_reputation.bump
+        bump = agent_reputation.bump,
+        constraint = agent_reputation.is_supported_version() @ ReputationError::UnsupportedAccountVersion
     )]
     pub agent_reputation: Account<'info, AgentReputation>,
 
     /// CHECK: The agent's wallet address
     pub agent_address: UncheckedAccount<'info>,
+
+    /// CHECK: Decay config PDA; defaults apply while it is uninitialized
+    #[account(seeds = [DecayConfig::SEED_PREFIX], bump)]
+    pub decay_config: UncheckedAccount<'info>,
 }
 
-pub fn handler(ctx: Context<GetReputation>) -> Result<()> {
+pub fn handler(ctx: Context<GetReputation>) -> Result<ReputationView> {
     let rep = &ctx.accounts.agent_reputation;
+    let clock = Clock::get()?;
+    let params = DecayConfig::resolve(&ctx.accounts.decay_config)?;
+
+    let view = ReputationView {
+        agent: rep.agent_address,
+        overall_score: rep.overall_score,
+        effective_score: rep.get_effective_score(clock.unix_timestamp, &params),
+        component_scores: rep.component_scores,
+        stats: rep.stats,
+        last_updated: rep.last_updated,
+        base_score: rep.base_score,
+        last_activity: rep.last_activity,
+        decay_enabled: rep.decay_enabled,
+        decay_rate_bps: rep.decay_rate_bps,
+        tier: rep.tier,
+        update_nonce: rep.update_nonce,
+        is_frozen: rep.is_frozen,
+        payment_proof_count: rep.payment_proof_count,
+    };
+
+    msg!(
+        "Reputation for agent {}: {}/1000 (effective {})",
+        view.agent,
+        view.overall_score,
+        view.effective_score
+    );
 
-    msg!("=== Agent Reputation ===");
-    msg!("Agent: {}", rep.agent_address);
-    msg!("Overall Score: {}/1000", rep.overall_score);
-    msg!("Component Scores:");
-    msg!("  Trust: {}/100", rep.component_scores.trust);
-    msg!("  Quality: {}/100", rep.component_scores.quality);
-    msg!("  Reliability: {}/100", rep.component_scores.reliability);
-    msg!("  Economic: {}/100", rep.component_scores.economic);
-    msg!("  Social: {}/100", rep.component_scores.social);
-    msg!("Statistics:");
-    msg!("  Total Votes: {}", rep.stats.total_votes);
-    msg!("  Positive Votes: {}", rep.stats.positive_votes);
-    msg!("  Negative Votes: {}", rep.stats.negative_votes);
-    msg!("  Total Reviews: {}", rep.stats.total_reviews);
-    msg!("  Avg Review Rating: {}/50", rep.stats.avg_review_rating);
-    msg!("Last Updated: {}", rep.last_updated);
-
-    Ok(())
+    Ok(view)
 }
@@ -27,6 +27,7 @@ pub fn handler(ctx: Context<InitializeAuthority>) -> Result<()> {
 
     authority_account.authority = ctx.accounts.authority.key();
     authority_account.bump = ctx.bumps.authority_account;
+    authority_account.pending_authority = Pubkey::default();
 
     msg!("Reputation authority initialized: {}", authority_account.authority);
 
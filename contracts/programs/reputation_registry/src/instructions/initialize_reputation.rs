@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
-use crate::state::{AgentReputation, ComponentScores, ReputationStats};
+use crate::constants::{IDENTITY_AGENT_SEED, IDENTITY_REGISTRY_PROGRAM_ID};
+use crate::state::{
+    AgentReputation, ComponentScores, InitializedBy, ReputationAuthority, ReputationStats,
+    TIER_BRONZE,
+};
 use crate::error::ReputationError;
 
 #[derive(Accounts)]
@@ -16,13 +20,46 @@ pub struct InitializeReputation<'info> {
     /// CHECK: The agent's wallet address
     pub agent_address: UncheckedAccount<'info>,
 
+    /// Needed only when the reputation authority is the initializer
+    #[account(
+        seeds = [ReputationAuthority::SEED_PREFIX],
+        bump = authority_account.bump
+    )]
+    pub authority_account: Option<Account<'info, ReputationAuthority>>,
+
+    /// The agent, the reputation authority, or identity_registry's AgentIdentity PDA (via CPI)
+    pub initializer: Signer<'info>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
+fn resolve_initializer(ctx: &Context<InitializeReputation>) -> Result<InitializedBy> {
+    let initializer = ctx.accounts.initializer.key();
+    let agent = ctx.accounts.agent_address.key();
+
+    if initializer == agent {
+        return Ok(InitializedBy::Agent);
+    }
+    if ctx.accounts.authority_account.as_ref().is_some_and(|a| a.authority == initializer) {
+        return Ok(InitializedBy::Authority);
+    }
+    let (identity_pda, _) = Pubkey::find_program_address(
+        &[IDENTITY_AGENT_SEED, agent.as_ref()],
+        &IDENTITY_REGISTRY_PROGRAM_ID,
+    );
+    if initializer == identity_pda {
+        return Ok(InitializedBy::IdentityRegistry);
+    }
+
+    err!(ReputationError::UnauthorizedInitializer)
+}
+
 pub fn handler(ctx: Context<InitializeReputation>) -> Result<()> {
+    let initialized_by = resolve_initializer(&ctx)?;
+
     let agent_reputation = &mut ctx.accounts.agent_reputation;
     let clock = Clock::get()?;
 
@@ -33,8 +70,24 @@ pub fn handler(ctx: Context<InitializeReputation>) -> Result<()> {
     agent_reputation.payment_proofs_merkle_root = [0; 32];
     agent_reputation.last_updated = clock.unix_timestamp;
     agent_reputation.bump = ctx.bumps.agent_reputation;
+    agent_reputation.base_score = 0;
+    agent_reputation.last_activity = clock.unix_timestamp;
+    agent_reputation.decay_enabled = false;
+    agent_reputation.decay_rate_bps = 10000;
+    agent_reputation.update_nonce = 0;
+    agent_reputation.tier = TIER_BRONZE;
+    agent_reputation.is_frozen = false;
+    agent_reputation.frozen_at = 0;
+    agent_reputation.initialized_by = initialized_by;
+    agent_reputation.payment_proof_count = 0;
+    agent_reputation.version = AgentReputation::CURRENT_VERSION;
+    agent_reputation.last_authority_update = 0;
 
-    msg!("Reputation initialized for agent: {}", ctx.accounts.agent_address.key());
+    msg!(
+        "Reputation initialized for agent: {} (by {:?})",
+        ctx.accounts.agent_address.key(),
+        initialized_by
+    );
 
     Ok(())
 }
@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use crate::state::{
+    AgentReputation, MultisigAuthority, ReputationAuthority, TierConfig,
+    DEFAULT_EXECUTION_DELAY_SECONDS,
+};
+use crate::error::ReputationError;
+
+/// Grow a program-owned account to `new_len`, topping up rent from `payer`.
+/// New bytes are zeroed, so appended fields start at their zero value.
+pub(crate) fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let required = Rent::get()?.minimum_balance(new_len);
+    let shortfall = required.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    account.resize(new_len)?;
+    Ok(())
+}
+
+/// Check that an account is owned by this program and carries the expected discriminator
+pub(crate) fn check_legacy_account(account: &AccountInfo, discriminator: &[u8]) -> Result<()> {
+    require_keys_eq!(*account.owner, crate::ID, ReputationError::InvalidMigrationTarget);
+    let data = account.try_borrow_data()?;
+    require!(
+        data.len() >= discriminator.len() && &data[..discriminator.len()] == discriminator,
+        ReputationError::InvalidMigrationTarget
+    );
+    Ok(())
+}
+
+// ==================== MIGRATE AUTHORITY ====================
+
+#[derive(Accounts)]
+pub struct MigrateAuthority<'info> {
+    /// CHECK: Legacy-sized account; owner and discriminator are checked in the handler
+    #[account(
+        mut,
+        seeds = [ReputationAuthority::SEED_PREFIX],
+        bump
+    )]
+    pub authority_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Resize a pre-rotation ReputationAuthority to the current layout (permissionless)
+pub fn migrate_authority(ctx: Context<MigrateAuthority>) -> Result<()> {
+    let account = ctx.accounts.authority_account.to_account_info();
+    check_legacy_account(&account, ReputationAuthority::DISCRIMINATOR)?;
+
+    require!(
+        account.data_len() >= ReputationAuthority::LEGACY_LEN,
+        ReputationError::InvalidMigrationTarget
+    );
+    if account.data_len() >= ReputationAuthority::LEN {
+        msg!("Authority account already migrated");
+        return Ok(());
+    }
+
+    grow_account(
+        &account,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        ReputationAuthority::LEN,
+    )?;
+
+    msg!("Authority account migrated to {} bytes", ReputationAuthority::LEN);
+
+    Ok(())
+}
+
+// ==================== MIGRATE ACCOUNT ====================
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Possibly legacy-sized account; owner and discriminator are checked in the handler
+    #[account(
+        mut,
+        seeds = [AgentReputation::SEED_PREFIX, agent_address.key().as_ref()],
+        bump
+    )]
+    pub agent_reputation: UncheckedAccount<'info>,
+
+    /// CHECK: The agent's wallet address (seed only)
+    pub agent_address: UncheckedAccount<'info>,
+
+    /// CHECK: Tier config PDA; default thresholds apply while it is uninitialized
+    #[account(seeds = [TierConfig::SEED_PREFIX], bump)]
+    pub tier_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade an AgentReputation of any earlier layout to CURRENT_VERSION, deriving
+/// the tier of accounts that predate it (permissionless)
+pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+    let account = ctx.accounts.agent_reputation.to_account_info();
+    check_legacy_account(&account, AgentReputation::DISCRIMINATOR)?;
+
+    let old_len = account.data_len();
+    require!(old_len >= AgentReputation::LEGACY_LEN, ReputationError::InvalidMigrationTarget);
+    if old_len < AgentReputation::LEN {
+        grow_account(
+            &account,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            AgentReputation::LEN,
+        )?;
+    }
+
+    let mut reputation = AgentReputation::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    if reputation.version >= AgentReputation::CURRENT_VERSION {
+        msg!("Reputation account already at version {}", reputation.version);
+        return Ok(());
+    }
+
+    // Appended fields are zeroed; derive the tier from the existing score
+    if old_len == AgentReputation::LEGACY_LEN {
+        let tier_params = TierConfig::resolve(&ctx.accounts.tier_config)?;
+        reputation.tier = tier_params.raw_tier(reputation.overall_score);
+    }
+    reputation.version = AgentReputation::CURRENT_VERSION;
+    reputation.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    msg!(
+        "Reputation account for {} migrated to version {} (tier {})",
+        reputation.agent_address,
+        reputation.version,
+        reputation.tier
+    );
+
+    Ok(())
+}
+
+// ==================== MIGRATE MULTISIG ====================
+
+#[derive(Accounts)]
+pub struct MigrateMultisig<'info> {
+    /// CHECK: Legacy-sized account; owner and discriminator are checked in the handler
+    #[account(
+        mut,
+        seeds = [MultisigAuthority::SEED_PREFIX],
+        bump
+    )]
+    pub multisig: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Resize a pre-timelock MultisigAuthority and apply the default execution delay (permissionless)
+pub fn migrate_multisig(ctx: Context<MigrateMultisig>) -> Result<()> {
+    let account = ctx.accounts.multisig.to_account_info();
+    check_legacy_account(&account, MultisigAuthority::DISCRIMINATOR)?;
+
+    require!(
+        account.data_len() >= MultisigAuthority::LEGACY_LEN,
+        ReputationError::InvalidMigrationTarget
+    );
+    if account.data_len() >= MultisigAuthority::LEN {
+        msg!("Multisig account already migrated");
+        return Ok(());
+    }
+
+    grow_account(
+        &account,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        MultisigAuthority::LEN,
+    )?;
+
+    // A zeroed delay would disable the timelock; start from the default instead
+    let mut multisig = MultisigAuthority::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    multisig.execution_delay_seconds = DEFAULT_EXECUTION_DELAY_SECONDS;
+    multisig.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    msg!("Multisig account migrated (execution delay {}s)", multisig.execution_delay_seconds);
+
+    Ok(())
+}
@@ -1,3 +1,6 @@
+// Each module's `handler` shares one name, so it is only called by module path
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize_authority;
 pub mod initialize_reputation;
 pub mod update_reputation;
@@ -5,6 +8,12 @@ pub mod record_payment_proof;
 pub mod get_reputation;
 pub mod multisig;
 pub mod decay;
+pub mod authority;
+pub mod migrate;
+pub mod tier;
+pub mod freeze;
+pub mod record_stats;
+pub mod close_reputation;
 
 pub use initialize_authority::*;
 pub use initialize_reputation::*;
@@ -13,3 +22,9 @@ pub use record_payment_proof::*;
 pub use get_reputation::*;
 pub use multisig::*;
 pub use decay::*;
+pub use authority::*;
+pub use migrate::*;
+pub use tier::*;
+pub use freeze::*;
+pub use record_stats::*;
+pub use close_reputation::*;
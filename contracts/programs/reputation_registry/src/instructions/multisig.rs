@@ -1,10 +1,14 @@
 use anchor_lang::prelude::*;
 use crate::state::{
     MultisigAuthority, MultisigProposal, AgentReputation,
-    ProposalType, ProposalStatus, ComponentScores, ReputationStats,
-    MAX_MULTISIG_SIGNERS,
+    ProposalType, ProposalStatus, ComponentScores, ReputationStats, TierConfig, PauseState,
+    MAX_MULTISIG_SIGNERS, DEFAULT_EXECUTION_DELAY_SECONDS, MAX_EXECUTION_DELAY_SECONDS,
+    PROPOSAL_CLOSE_GRACE_SECONDS,
 };
 use crate::error::ReputationError;
+use crate::events::{RegistryPauseChanged, ReputationUpdated, TierChanged};
+use identity_registry::cpi::accounts::SlashAgentMultisig;
+use identity_registry::program::IdentityRegistry;
 
 // ==================== MULTI-SIG ERRORS ====================
 
@@ -34,6 +38,24 @@ pub enum MultisigError {
     MultisigPaused,
     #[msg("Cannot remove signer: would go below threshold")]
     WouldGobelowThreshold,
+    #[msg("Execution delay has not elapsed since the proposal was approved")]
+    TimelockActive,
+    #[msg("Execution delay must be between 0 and 7 days")]
+    InvalidExecutionDelay,
+    #[msg("Only pending or approved proposals can be cancelled")]
+    ProposalNotCancellable,
+    #[msg("Unauthorized: only a multisig signer or the target agent can cancel")]
+    UnauthorizedCanceller,
+    #[msg("Only executed, rejected or expired proposals can be closed")]
+    ProposalNotClosable,
+    #[msg("Proposal is still within its post-finalization audit period")]
+    ProposalGracePeriodActive,
+    #[msg("Signer has not approved this proposal")]
+    NotApproved,
+    #[msg("Approvals cannot be revoked once quorum is reached")]
+    QuorumAlreadyReached,
+    #[msg("Invalid slash severity (must be 0-10000)")]
+    InvalidSlashSeverity,
 }
 
 // ==================== INITIALIZE MULTISIG ====================
@@ -80,6 +102,7 @@ pub fn initialize_multisig(
     multisig.is_active = true;
     multisig.created_at = clock.unix_timestamp;
     multisig.bump = ctx.bumps.multisig;
+    multisig.execution_delay_seconds = DEFAULT_EXECUTION_DELAY_SECONDS;
 
     msg!("Multi-sig authority initialized with {} signers, threshold {}",
          multisig.signers.len(), threshold);
@@ -113,7 +136,8 @@ pub struct ProposeReputationUpdate<'info> {
     /// The agent reputation account to update
     #[account(
         seeds = [AgentReputation::SEED_PREFIX, target_agent.key().as_ref()],
-        bump = agent_reputation.bump
+        bump = agent_reputation.bump,
+        constraint = agent_reputation.is_supported_version() @ ReputationError::UnsupportedAccountVersion
     )]
     pub agent_reputation: Account<'info, AgentReputation>,
 
@@ -163,6 +187,8 @@ pub fn propose_reputation_update(
     proposal.created_at = clock.unix_timestamp;
     proposal.executed_at = 0;
     proposal.bump = ctx.bumps.proposal;
+    proposal.approval_reached_at = 0;
+    proposal.expected_nonce = ctx.accounts.agent_reputation.update_nonce;
 
     // Auto-approve by proposer
     proposal.record_approval(signer_index as u8);
@@ -232,6 +258,7 @@ pub fn approve_proposal(
     // Check if we reached quorum
     if proposal.has_quorum(multisig.threshold) {
         proposal.status = ProposalStatus::Approved;
+        proposal.approval_reached_at = clock.unix_timestamp;
         msg!("Proposal {} approved with {} signatures", proposal.proposal_id, proposal.approval_count);
     } else {
         msg!("Proposal {} has {}/{} approvals",
@@ -241,6 +268,57 @@ pub fn approve_proposal(
     Ok(())
 }
 
+// ==================== REVOKE APPROVAL ====================
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct RevokeApproval<'info> {
+    #[account(
+        seeds = [MultisigAuthority::SEED_PREFIX],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, MultisigAuthority>,
+
+    #[account(
+        mut,
+        seeds = [
+            MultisigProposal::SEED_PREFIX,
+            &proposal_id.to_le_bytes()
+        ],
+        bump = proposal.bump,
+        constraint = proposal.status == ProposalStatus::Pending @ MultisigError::QuorumAlreadyReached
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Withdraw an approval while the proposal is still below quorum
+pub fn revoke_approval(
+    ctx: Context<RevokeApproval>,
+    _proposal_id: u64,
+) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+
+    let signer_index = multisig.signers
+        .iter()
+        .position(|s| s == ctx.accounts.signer.key)
+        .ok_or(MultisigError::UnauthorizedSigner)?;
+
+    require!(
+        proposal.has_approved(signer_index as u8),
+        MultisigError::NotApproved
+    );
+
+    proposal.revoke_approval(signer_index as u8);
+
+    msg!("Signer {} revoked approval of proposal {} ({}/{} approvals)",
+         signer_index, proposal.proposal_id, proposal.approval_count, multisig.threshold);
+
+    Ok(())
+}
+
 // ==================== EXECUTE REPUTATION PROPOSAL ====================
 
 #[derive(Accounts)]
@@ -267,10 +345,20 @@ pub struct ExecuteReputationProposal<'info> {
     #[account(
         mut,
         seeds = [AgentReputation::SEED_PREFIX, proposal.target_agent.as_ref()],
-        bump = agent_reputation.bump
+        bump = agent_reputation.bump,
+        constraint = !agent_reputation.is_frozen @ ReputationError::ReputationFrozen,
+        constraint = agent_reputation.is_supported_version() @ ReputationError::UnsupportedAccountVersion
     )]
     pub agent_reputation: Account<'info, AgentReputation>,
 
+    /// CHECK: Tier config PDA; default thresholds apply while it is uninitialized
+    #[account(seeds = [TierConfig::SEED_PREFIX], bump)]
+    pub tier_config: UncheckedAccount<'info>,
+
+    /// CHECK: Pause state PDA; unpaused while it is uninitialized
+    #[account(seeds = [PauseState::SEED_PREFIX], bump)]
+    pub pause_state: UncheckedAccount<'info>,
+
     pub executor: Signer<'info>,
 }
 
@@ -290,6 +378,26 @@ pub fn execute_reputation_proposal(
         multisig.signers.contains(ctx.accounts.executor.key),
         MultisigError::UnauthorizedSigner
     );
+    require!(
+        !PauseState::is_paused(&ctx.accounts.pause_state)?,
+        ReputationError::RegistryPaused
+    );
+
+    // Give observers time to react before an approved update lands
+    require!(
+        proposal.is_executable(clock.unix_timestamp, multisig.execution_delay_seconds),
+        MultisigError::TimelockActive
+    );
+    require!(
+        !proposal.is_execution_window_closed(clock.unix_timestamp, multisig.execution_delay_seconds),
+        MultisigError::ProposalExpired
+    );
+
+    // Reject proposals built against a reputation that has since changed
+    require!(
+        reputation.update_nonce == proposal.expected_nonce,
+        ReputationError::StaleUpdate
+    );
 
     // Apply the reputation update
     reputation.overall_score = proposal.proposed_score;
@@ -297,6 +405,26 @@ pub fn execute_reputation_proposal(
     reputation.stats = proposal.proposed_stats;
     reputation.payment_proofs_merkle_root = proposal.proposed_merkle_root;
     reputation.last_updated = clock.unix_timestamp;
+    reputation.update_nonce = reputation.update_nonce
+        .checked_add(1)
+        .ok_or(ReputationError::ArithmeticOverflow)?;
+
+    emit!(ReputationUpdated {
+        agent: reputation.agent_address,
+        overall_score: reputation.overall_score,
+        update_nonce: reputation.update_nonce,
+        timestamp: clock.unix_timestamp,
+    });
+
+    let tier_params = TierConfig::resolve(&ctx.accounts.tier_config)?;
+    if let Some(previous_tier) = reputation.refresh_tier(&tier_params) {
+        emit!(TierChanged {
+            agent: reputation.agent_address,
+            previous_tier,
+            new_tier: reputation.tier,
+            overall_score: reputation.overall_score,
+        });
+    }
 
     // Mark proposal as executed
     proposal.status = ProposalStatus::Executed;
@@ -308,6 +436,454 @@ pub fn execute_reputation_proposal(
     Ok(())
 }
 
+// ==================== CANCEL PROPOSAL ====================
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CancelProposal<'info> {
+    #[account(
+        seeds = [MultisigAuthority::SEED_PREFIX],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, MultisigAuthority>,
+
+    #[account(
+        mut,
+        seeds = [
+            MultisigProposal::SEED_PREFIX,
+            &proposal_id.to_le_bytes()
+        ],
+        bump = proposal.bump,
+        constraint = matches!(
+            proposal.status,
+            ProposalStatus::Pending | ProposalStatus::Approved
+        ) @ MultisigError::ProposalNotCancellable
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    pub canceller: Signer<'info>,
+}
+
+/// Cancel a proposal before execution (any signer, or the agent a reputation update targets)
+pub fn cancel_proposal(
+    ctx: Context<CancelProposal>,
+    _proposal_id: u64,
+) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+    let canceller = ctx.accounts.canceller.key();
+
+    let is_target_agent = proposal.proposal_type == ProposalType::UpdateReputation
+        && proposal.target_agent == canceller;
+    require!(
+        multisig.signers.contains(&canceller) || is_target_agent,
+        MultisigError::UnauthorizedCanceller
+    );
+
+    proposal.status = ProposalStatus::Rejected;
+    proposal.executed_at = Clock::get()?.unix_timestamp;
+
+    msg!("Proposal {} cancelled by {}", proposal.proposal_id, canceller);
+
+    Ok(())
+}
+
+// ==================== CLOSE PROPOSAL ====================
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CloseProposal<'info> {
+    #[account(
+        seeds = [MultisigAuthority::SEED_PREFIX],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, MultisigAuthority>,
+
+    #[account(
+        mut,
+        seeds = [
+            MultisigProposal::SEED_PREFIX,
+            &proposal_id.to_le_bytes()
+        ],
+        bump = proposal.bump,
+        has_one = proposer @ MultisigError::UnauthorizedSigner,
+        close = proposer
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    /// CHECK: Original proposer; receives the reclaimed rent
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+/// Close a finalized proposal after the audit period (permissionless)
+pub fn close_proposal(
+    ctx: Context<CloseProposal>,
+    _proposal_id: u64,
+) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &ctx.accounts.proposal;
+    let clock = Clock::get()?;
+
+    // New proposals are always seeded with proposal_count, so a closed id is never reused
+    require!(
+        proposal.proposal_id < multisig.proposal_count,
+        MultisigError::ProposalNotClosable
+    );
+
+    let finalized_at = proposal
+        .finalized_at(clock.unix_timestamp, multisig.execution_delay_seconds)
+        .ok_or(MultisigError::ProposalNotClosable)?;
+    require!(
+        clock.unix_timestamp >= finalized_at.saturating_add(PROPOSAL_CLOSE_GRACE_SECONDS),
+        MultisigError::ProposalGracePeriodActive
+    );
+
+    msg!("Proposal {} closed; rent returned to {}", proposal.proposal_id, proposal.proposer);
+
+    Ok(())
+}
+
+// ==================== PROPOSE EMERGENCY PAUSE ====================
+
+#[derive(Accounts)]
+pub struct ProposeEmergencyPause<'info> {
+    #[account(
+        mut,
+        seeds = [MultisigAuthority::SEED_PREFIX],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, MultisigAuthority>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = MultisigProposal::LEN,
+        seeds = [
+            MultisigProposal::SEED_PREFIX,
+            &multisig.proposal_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Propose pausing (or, with pause = false, unpausing) the registry
+pub fn propose_emergency_pause(
+    ctx: Context<ProposeEmergencyPause>,
+    pause: bool,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(multisig.is_active, MultisigError::MultisigPaused);
+    let signer_index = multisig.signers
+        .iter()
+        .position(|s| s == ctx.accounts.proposer.key)
+        .ok_or(MultisigError::UnauthorizedSigner)?;
+
+    let clock = Clock::get()?;
+
+    proposal.proposal_id = multisig.proposal_count;
+    proposal.proposal_type = if pause { ProposalType::EmergencyPause } else { ProposalType::Unpause };
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.target_agent = Pubkey::default();
+    proposal.proposed_score = 0;
+    proposal.proposed_components = ComponentScores::default();
+    proposal.proposed_stats = ReputationStats::default();
+    proposal.proposed_merkle_root = [0; 32];
+    proposal.target_signer = Pubkey::default();
+    proposal.new_threshold = 0;
+    proposal.approval_bitmap = 0;
+    proposal.approval_count = 0;
+    proposal.status = ProposalStatus::Pending;
+    proposal.created_at = clock.unix_timestamp;
+    proposal.executed_at = 0;
+    proposal.bump = ctx.bumps.proposal;
+    proposal.approval_reached_at = 0;
+    proposal.expected_nonce = 0;
+
+    // Auto-approve by proposer
+    proposal.record_approval(signer_index as u8);
+
+    multisig.proposal_count = multisig.proposal_count.checked_add(1)
+        .ok_or(ReputationError::ArithmeticOverflow)?;
+
+    msg!("{} proposal {} created by signer {}",
+         if pause { "Pause" } else { "Unpause" }, proposal.proposal_id, signer_index);
+
+    Ok(())
+}
+
+// ==================== EXECUTE EMERGENCY PAUSE ====================
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ExecuteEmergencyPause<'info> {
+    #[account(
+        seeds = [MultisigAuthority::SEED_PREFIX],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, MultisigAuthority>,
+
+    #[account(
+        mut,
+        seeds = [
+            MultisigProposal::SEED_PREFIX,
+            &proposal_id.to_le_bytes()
+        ],
+        bump = proposal.bump,
+        constraint = proposal.status == ProposalStatus::Approved @ MultisigError::InsufficientApprovals,
+        constraint = matches!(
+            proposal.proposal_type,
+            ProposalType::EmergencyPause | ProposalType::Unpause
+        ) @ ReputationError::InvalidAuthority
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = PauseState::LEN,
+        seeds = [PauseState::SEED_PREFIX],
+        bump
+    )]
+    pub pause_state: Account<'info, PauseState>,
+
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Execute an approved pause/unpause proposal
+pub fn execute_emergency_pause(
+    ctx: Context<ExecuteEmergencyPause>,
+    _proposal_id: u64,
+) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+    let pause_state = &mut ctx.accounts.pause_state;
+    let clock = Clock::get()?;
+
+    require!(multisig.is_active, MultisigError::MultisigPaused);
+    require!(
+        multisig.signers.contains(ctx.accounts.executor.key),
+        MultisigError::UnauthorizedSigner
+    );
+    require!(
+        !proposal.is_execution_window_closed(clock.unix_timestamp, multisig.execution_delay_seconds),
+        MultisigError::ProposalExpired
+    );
+
+    pause_state.paused = proposal.proposal_type == ProposalType::EmergencyPause;
+    pause_state.last_proposal_id = proposal.proposal_id;
+    pause_state.updated_at = clock.unix_timestamp;
+    pause_state.bump = ctx.bumps.pause_state;
+
+    proposal.status = ProposalStatus::Executed;
+    proposal.executed_at = clock.unix_timestamp;
+
+    emit!(RegistryPauseChanged {
+        paused: pause_state.paused,
+        proposal_id: proposal.proposal_id,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Proposal {} executed: registry {}",
+         proposal.proposal_id, if pause_state.paused { "paused" } else { "unpaused" });
+
+    Ok(())
+}
+
+// ==================== PROPOSE SLASH ====================
+
+#[derive(Accounts)]
+pub struct ProposeSlash<'info> {
+    #[account(
+        mut,
+        seeds = [MultisigAuthority::SEED_PREFIX],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, MultisigAuthority>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = MultisigProposal::LEN,
+        seeds = [
+            MultisigProposal::SEED_PREFIX,
+            &multisig.proposal_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    /// CHECK: The agent whose identity_registry stake would be slashed
+    pub target_agent: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Propose slashing an agent's stake (must be a multisig signer)
+pub fn propose_slash(
+    ctx: Context<ProposeSlash>,
+    severity_bps: u16,
+    reason_hash: [u8; 32],
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(severity_bps <= 10000, MultisigError::InvalidSlashSeverity);
+    require!(multisig.is_active, MultisigError::MultisigPaused);
+    let signer_index = multisig.signers
+        .iter()
+        .position(|s| s == ctx.accounts.proposer.key)
+        .ok_or(MultisigError::UnauthorizedSigner)?;
+
+    let clock = Clock::get()?;
+
+    proposal.proposal_id = multisig.proposal_count;
+    proposal.proposal_type = ProposalType::SlashAgent;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.target_agent = ctx.accounts.target_agent.key();
+    proposal.proposed_score = severity_bps;
+    proposal.proposed_components = ComponentScores::default();
+    proposal.proposed_stats = ReputationStats::default();
+    proposal.proposed_merkle_root = reason_hash;
+    proposal.target_signer = Pubkey::default();
+    proposal.new_threshold = 0;
+    proposal.approval_bitmap = 0;
+    proposal.approval_count = 0;
+    proposal.status = ProposalStatus::Pending;
+    proposal.created_at = clock.unix_timestamp;
+    proposal.executed_at = 0;
+    proposal.bump = ctx.bumps.proposal;
+    proposal.approval_reached_at = 0;
+    proposal.expected_nonce = 0;
+
+    // Auto-approve by proposer
+    proposal.record_approval(signer_index as u8);
+
+    multisig.proposal_count = multisig.proposal_count.checked_add(1)
+        .ok_or(ReputationError::ArithmeticOverflow)?;
+
+    msg!("Slash proposal {} created by signer {}: agent {} at {}bps",
+         proposal.proposal_id, signer_index, proposal.target_agent, severity_bps);
+
+    Ok(())
+}
+
+// ==================== EXECUTE SLASH PROPOSAL ====================
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ExecuteSlashProposal<'info> {
+    /// Signs the identity_registry CPI as the authorized slasher
+    #[account(
+        seeds = [MultisigAuthority::SEED_PREFIX],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, MultisigAuthority>,
+
+    #[account(
+        mut,
+        seeds = [
+            MultisigProposal::SEED_PREFIX,
+            &proposal_id.to_le_bytes()
+        ],
+        bump = proposal.bump,
+        constraint = proposal.status == ProposalStatus::Approved @ MultisigError::InsufficientApprovals,
+        constraint = proposal.proposal_type == ProposalType::SlashAgent @ ReputationError::InvalidAuthority
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    /// CHECK: identity_registry AgentIdentity PDA; validated by identity_registry
+    #[account(mut)]
+    pub agent_identity: UncheckedAccount<'info>,
+
+    /// CHECK: identity_registry StakingPool PDA; validated by identity_registry
+    #[account(mut)]
+    pub staking_pool: UncheckedAccount<'info>,
+
+    /// CHECK: identity_registry SlashRecord PDA to create; validated by identity_registry
+    #[account(mut)]
+    pub slash_record: UncheckedAccount<'info>,
+
+    /// CHECK: The agent named in the proposal
+    #[account(address = proposal.target_agent @ ReputationError::InvalidAuthority)]
+    pub target_agent: UncheckedAccount<'info>,
+
+    /// Multisig signer executing the proposal; pays for the slash record
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    pub identity_registry_program: Program<'info, IdentityRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Execute an approved slash proposal by CPI into identity_registry
+pub fn execute_slash_proposal(
+    ctx: Context<ExecuteSlashProposal>,
+    _proposal_id: u64,
+) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let clock = Clock::get()?;
+
+    require!(multisig.is_active, MultisigError::MultisigPaused);
+    require!(
+        multisig.signers.contains(ctx.accounts.executor.key),
+        MultisigError::UnauthorizedSigner
+    );
+
+    // Give the agent and observers time to react before a slash lands
+    require!(
+        ctx.accounts.proposal.is_executable(clock.unix_timestamp, multisig.execution_delay_seconds),
+        MultisigError::TimelockActive
+    );
+    require!(
+        !ctx.accounts.proposal.is_execution_window_closed(clock.unix_timestamp, multisig.execution_delay_seconds),
+        MultisigError::ProposalExpired
+    );
+
+    let signer_seeds: &[&[&[u8]]] = &[&[MultisigAuthority::SEED_PREFIX, &[multisig.bump]]];
+    identity_registry::cpi::slash_agent_multisig(
+        CpiContext::new_with_signer(
+            ctx.accounts.identity_registry_program.to_account_info(),
+            SlashAgentMultisig {
+                agent_identity: ctx.accounts.agent_identity.to_account_info(),
+                staking_pool: ctx.accounts.staking_pool.to_account_info(),
+                slash_record: ctx.accounts.slash_record.to_account_info(),
+                agent_address: ctx.accounts.target_agent.to_account_info(),
+                multisig_authority: ctx.accounts.multisig.to_account_info(),
+                payer: ctx.accounts.executor.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        ctx.accounts.proposal.proposed_score,
+        ctx.accounts.proposal.proposed_merkle_root,
+    )?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.status = ProposalStatus::Executed;
+    proposal.executed_at = clock.unix_timestamp;
+
+    msg!("Proposal {} executed: agent {} slashed at {}bps",
+         proposal.proposal_id, proposal.target_agent, proposal.proposed_score);
+
+    Ok(())
+}
+
 // ==================== ADD SIGNER ====================
 
 #[derive(Accounts)]
@@ -423,6 +999,27 @@ pub fn update_threshold(
     Ok(())
 }
 
+// ==================== UPDATE EXECUTION DELAY ====================
+
+/// Set the delay between quorum and execution of reputation proposals (admin only)
+pub fn update_execution_delay(
+    ctx: Context<UpdateThreshold>,
+    delay_seconds: i64,
+) -> Result<()> {
+    require!(
+        (0..=MAX_EXECUTION_DELAY_SECONDS).contains(&delay_seconds),
+        MultisigError::InvalidExecutionDelay
+    );
+
+    let multisig = &mut ctx.accounts.multisig;
+    let old_delay = multisig.execution_delay_seconds;
+    multisig.execution_delay_seconds = delay_seconds;
+
+    msg!("Execution delay updated from {}s to {}s", old_delay, delay_seconds);
+
+    Ok(())
+}
+
 // ==================== PAUSE/UNPAUSE MULTISIG ====================
 
 #[derive(Accounts)]
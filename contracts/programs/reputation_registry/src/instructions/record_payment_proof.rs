@@ -1,44 +1,143 @@
 use anchor_lang::prelude::*;
-use crate::state::AgentReputation;
+use solana_sha256_hasher::hash;
+use crate::state::{
+    AgentReputation, PauseState, PaymentProof, ReputationAuthority, PAYMENT_PROOF_RETENTION_SECONDS,
+};
 use crate::error::ReputationError;
 
 #[derive(Accounts)]
+#[instruction(payment_signature: String, signature_hash: [u8; 32])]
 pub struct RecordPaymentProof<'info> {
     #[account(
         mut,
         seeds = [AgentReputation::SEED_PREFIX, agent_address.key().as_ref()],
-        bump = agent_reputation.bump
+        bump = agent_reputation.bump,
+        constraint = agent_reputation.is_supported_version() @ ReputationError::UnsupportedAccountVersion
     )]
     pub agent_reputation: Account<'info, AgentReputation>,
 
+    /// One account per (agent, signature); init fails if the proof was already recorded
+    #[account(
+        init,
+        payer = authority,
+        space = PaymentProof::LEN,
+        seeds = [
+            PaymentProof::SEED_PREFIX,
+            agent_address.key().as_ref(),
+            &signature_hash
+        ],
+        bump
+    )]
+    pub payment_proof: Account<'info, PaymentProof>,
+
+    #[account(
+        seeds = [ReputationAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ ReputationError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, ReputationAuthority>,
+
     /// CHECK: The agent's wallet address
     pub agent_address: UncheckedAccount<'info>,
 
-    /// Authority that can record proofs
+    /// CHECK: Pause state PDA; unpaused while it is uninitialized
+    #[account(seeds = [PauseState::SEED_PREFIX], bump)]
+    pub pause_state: UncheckedAccount<'info>,
+
+    /// Authority that can record proofs (pays rent for the proof account)
+    #[account(mut)]
     pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handler(
     ctx: Context<RecordPaymentProof>,
     payment_signature: String,
+    signature_hash: [u8; 32],
+    amount: Option<u64>,
+    paid_at: i64,
 ) -> Result<()> {
+    require!(
+        !PauseState::is_paused(&ctx.accounts.pause_state)?,
+        ReputationError::RegistryPaused
+    );
     require!(
         payment_signature.len() <= 88, // Solana signature length
         ReputationError::PaymentSignatureTooLong
     );
+    require!(
+        hash(payment_signature.as_bytes()).to_bytes() == signature_hash,
+        ReputationError::PaymentSignatureHashMismatch
+    );
+
+    let clock = Clock::get()?;
+
+    // A proof is only closed once it is past retention, so rejecting payments
+    // that old keeps a closed proof from being recorded and counted again
+    require!(
+        paid_at <= clock.unix_timestamp
+            && clock.unix_timestamp.saturating_sub(paid_at) < PAYMENT_PROOF_RETENTION_SECONDS,
+        ReputationError::PaymentOutsideRetention
+    );
 
-    // In a real implementation, this would:
-    // 1. Verify the payment proof signature
-    // 2. Add it to a Merkle tree
-    // 3. Update the merkle_root in agent_reputation
-    //
-    // For now, we'll just log the payment proof
+    let proof = &mut ctx.accounts.payment_proof;
+    proof.agent = ctx.accounts.agent_address.key();
+    proof.signature_hash = signature_hash;
+    proof.amount = amount;
+    proof.recorded_at = clock.unix_timestamp;
+    proof.recorder = ctx.accounts.authority.key();
+    proof.bump = ctx.bumps.payment_proof;
 
-    msg!("Payment proof recorded for agent: {}", ctx.accounts.agent_address.key());
+    let reputation = &mut ctx.accounts.agent_reputation;
+    reputation.payment_proof_count = reputation.payment_proof_count
+        .checked_add(1)
+        .ok_or(ReputationError::ArithmeticOverflow)?;
+
+    msg!("Payment proof recorded for agent: {}", proof.agent);
     msg!("Payment signature: {}", payment_signature);
+    msg!("Total payment proofs: {}", reputation.payment_proof_count);
+
+    Ok(())
+}
+
+// ==================== CLOSE PAYMENT PROOF ====================
+
+#[derive(Accounts)]
+pub struct ClosePaymentProof<'info> {
+    #[account(
+        mut,
+        has_one = recorder @ ReputationError::UnauthorizedAuthority,
+        close = recorder,
+        seeds = [
+            PaymentProof::SEED_PREFIX,
+            payment_proof.agent.as_ref(),
+            &payment_proof.signature_hash
+        ],
+        bump = payment_proof.bump
+    )]
+    pub payment_proof: Account<'info, PaymentProof>,
+
+    /// Original recorder; receives the reclaimed rent
+    #[account(mut)]
+    pub recorder: Signer<'info>,
+}
+
+/// Close a payment proof after the retention period
+/// payment_proof_count is a lifetime total and is not decremented
+pub fn close_payment_proof(ctx: Context<ClosePaymentProof>) -> Result<()> {
+    let proof = &ctx.accounts.payment_proof;
+    let clock = Clock::get()?;
+
+    let retained_until = proof.recorded_at
+        .checked_add(PAYMENT_PROOF_RETENTION_SECONDS)
+        .ok_or(ReputationError::ArithmeticOverflow)?;
+    require!(
+        clock.unix_timestamp >= retained_until,
+        ReputationError::PaymentProofRetentionActive
+    );
 
-    // NOTE: Actual Merkle tree implementation would go here
-    // This would involve updating payment_proofs_merkle_root
+    msg!("Payment proof closed for agent: {}", proof.agent);
 
     Ok(())
 }
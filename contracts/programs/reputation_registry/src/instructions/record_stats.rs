@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use crate::constants::{STATS_CPI_AUTHORITY_SEED, VOTE_REGISTRY_PROGRAM_ID};
+use crate::state::AgentReputation;
+use crate::error::ReputationError;
+
+/// Outcome of a peer vote as seen by the reputation registry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VoteOutcome {
+    Positive,
+    Negative,
+    Neutral,
+}
+
+// ==================== RECORD VOTE RESULT ====================
+
+#[derive(Accounts)]
+pub struct RecordVoteResult<'info> {
+    #[account(
+        mut,
+        seeds = [AgentReputation::SEED_PREFIX, agent_reputation.agent_address.as_ref()],
+        bump = agent_reputation.bump,
+        constraint = agent_reputation.is_supported_version() @ ReputationError::UnsupportedAccountVersion
+    )]
+    pub agent_reputation: Account<'info, AgentReputation>,
+
+    /// vote_registry's stats PDA; can only sign through invoke_signed from that program
+    #[account(
+        seeds = [STATS_CPI_AUTHORITY_SEED],
+        bump,
+        seeds::program = VOTE_REGISTRY_PROGRAM_ID
+    )]
+    pub cpi_authority: Signer<'info>,
+}
+
+/// Increment vote counters for an agent (CPI from vote_registry only)
+pub fn record_vote_result(ctx: Context<RecordVoteResult>, outcome: VoteOutcome) -> Result<()> {
+    let reputation = &mut ctx.accounts.agent_reputation;
+    let agent = reputation.agent_address;
+    let stats = &mut reputation.stats;
+
+    stats.total_votes = stats.total_votes
+        .checked_add(1)
+        .ok_or(ReputationError::ArithmeticOverflow)?;
+    match outcome {
+        VoteOutcome::Positive => {
+            stats.positive_votes = stats.positive_votes
+                .checked_add(1)
+                .ok_or(ReputationError::ArithmeticOverflow)?;
+        }
+        VoteOutcome::Negative => {
+            stats.negative_votes = stats.negative_votes
+                .checked_add(1)
+                .ok_or(ReputationError::ArithmeticOverflow)?;
+        }
+        VoteOutcome::Neutral => {}
+    }
+
+    msg!(
+        "Vote recorded for agent {}: {:?} ({} total)",
+        agent,
+        outcome,
+        stats.total_votes
+    );
+
+    Ok(())
+}
+
+/// Move one vote from `previous` to `outcome` when a voter amends it (CPI from vote_registry only)
+/// total_votes is unchanged
+pub fn amend_vote_result(
+    ctx: Context<RecordVoteResult>,
+    previous: VoteOutcome,
+    outcome: VoteOutcome,
+) -> Result<()> {
+    let reputation = &mut ctx.accounts.agent_reputation;
+    let agent = reputation.agent_address;
+    let stats = &mut reputation.stats;
+
+    match previous {
+        VoteOutcome::Positive => {
+            stats.positive_votes = stats.positive_votes
+                .checked_sub(1)
+                .ok_or(ReputationError::ArithmeticOverflow)?;
+        }
+        VoteOutcome::Negative => {
+            stats.negative_votes = stats.negative_votes
+                .checked_sub(1)
+                .ok_or(ReputationError::ArithmeticOverflow)?;
+        }
+        VoteOutcome::Neutral => {}
+    }
+    match outcome {
+        VoteOutcome::Positive => {
+            stats.positive_votes = stats.positive_votes
+                .checked_add(1)
+                .ok_or(ReputationError::ArithmeticOverflow)?;
+        }
+        VoteOutcome::Negative => {
+            stats.negative_votes = stats.negative_votes
+                .checked_add(1)
+                .ok_or(ReputationError::ArithmeticOverflow)?;
+        }
+        VoteOutcome::Neutral => {}
+    }
+
+    msg!("Vote amended for agent {}: {:?} -> {:?}", agent, previous, outcome);
+
+    Ok(())
+}
+
+// ==================== RECORD REVIEW ====================
+
+#[derive(Accounts)]
+pub struct RecordReview<'info> {
+    #[account(
+        mut,
+        seeds = [AgentReputation::SEED_PREFIX, agent_reputation.agent_address.as_ref()],
+        bump = agent_reputation.bump,
+        constraint = agent_reputation.is_supported_version() @ ReputationError::UnsupportedAccountVersion
+    )]
+    pub agent_reputation: Account<'info, AgentReputation>,
+
+    /// vote_registry's stats PDA; can only sign through invoke_signed from that program
+    #[account(
+        seeds = [STATS_CPI_AUTHORITY_SEED],
+        bump,
+        seeds::program = VOTE_REGISTRY_PROGRAM_ID
+    )]
+    pub cpi_authority: Signer<'info>,
+}
+
+/// Add a review and recompute the running average (CPI from vote_registry only)
+/// rating uses the same 0-50 scale as avg_review_rating
+pub fn record_review(ctx: Context<RecordReview>, rating: u8) -> Result<()> {
+    require!(rating <= 50, ReputationError::InvalidReviewRatingInput);
+
+    let reputation = &mut ctx.accounts.agent_reputation;
+    let agent = reputation.agent_address;
+    let stats = &mut reputation.stats;
+    let previous_count = stats.total_reviews as u64;
+    let new_count = previous_count
+        .checked_add(1)
+        .ok_or(ReputationError::ArithmeticOverflow)?;
+
+    // Running average rounded to nearest: (avg * n + rating) / (n + 1)
+    let total = (stats.avg_review_rating as u64)
+        .checked_mul(previous_count)
+        .and_then(|sum| sum.checked_add(rating as u64))
+        .and_then(|sum| sum.checked_add(new_count / 2))
+        .ok_or(ReputationError::ArithmeticOverflow)?;
+
+    stats.avg_review_rating = (total / new_count) as u8;
+    stats.total_reviews = u32::try_from(new_count).map_err(|_| ReputationError::ArithmeticOverflow)?;
+
+    msg!(
+        "Review recorded for agent {}: rating {}, average {} over {} reviews",
+        agent,
+        rating,
+        stats.avg_review_rating,
+        stats.total_reviews
+    );
+
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use crate::state::{AgentReputation, MultisigAuthority, ReputationAuthority, TierConfig};
+use crate::error::ReputationError;
+
+fn validate_tier_config(
+    silver_threshold: u16,
+    gold_threshold: u16,
+    platinum_threshold: u16,
+    promotion_margin: u16,
+) -> Result<()> {
+    require!(
+        0 < silver_threshold
+            && silver_threshold < gold_threshold
+            && gold_threshold < platinum_threshold
+            && platinum_threshold <= 1000
+            && promotion_margin <= TierConfig::MAX_PROMOTION_MARGIN,
+        ReputationError::InvalidTierThresholds
+    );
+    Ok(())
+}
+
+// ==================== INITIALIZE TIER CONFIG ====================
+
+#[derive(Accounts)]
+pub struct InitializeTierConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = TierConfig::LEN,
+        seeds = [TierConfig::SEED_PREFIX],
+        bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+
+    #[account(
+        seeds = [ReputationAuthority::SEED_PREFIX],
+        bump = authority_account.bump
+    )]
+    pub authority_account: Account<'info, ReputationAuthority>,
+
+    /// Multisig whose admin may act in place of the authority
+    #[account(
+        seeds = [MultisigAuthority::SEED_PREFIX],
+        bump = multisig.bump
+    )]
+    pub multisig: Option<Account<'info, MultisigAuthority>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the registry-wide tier thresholds (authority or multisig admin)
+pub fn initialize_tier_config(
+    ctx: Context<InitializeTierConfig>,
+    silver_threshold: u16,
+    gold_threshold: u16,
+    platinum_threshold: u16,
+    promotion_margin: u16,
+) -> Result<()> {
+    require!(
+        ctx.accounts.authority_account.is_admin(
+            ctx.accounts.authority.key,
+            ctx.accounts.multisig.as_deref()
+        ),
+        ReputationError::UnauthorizedAuthority
+    );
+    validate_tier_config(silver_threshold, gold_threshold, platinum_threshold, promotion_margin)?;
+
+    let config = &mut ctx.accounts.tier_config;
+    config.silver_threshold = silver_threshold;
+    config.gold_threshold = gold_threshold;
+    config.platinum_threshold = platinum_threshold;
+    config.promotion_margin = promotion_margin;
+    config.updated_at = Clock::get()?.unix_timestamp;
+    config.bump = ctx.bumps.tier_config;
+
+    msg!(
+        "Tier config initialized: silver {}, gold {}, platinum {}, margin {}",
+        silver_threshold,
+        gold_threshold,
+        platinum_threshold,
+        promotion_margin
+    );
+
+    Ok(())
+}
+
+// ==================== UPDATE TIER CONFIG ====================
+
+#[derive(Accounts)]
+pub struct UpdateTierConfig<'info> {
+    #[account(
+        mut,
+        seeds = [TierConfig::SEED_PREFIX],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+
+    #[account(
+        seeds = [ReputationAuthority::SEED_PREFIX],
+        bump = authority_account.bump
+    )]
+    pub authority_account: Account<'info, ReputationAuthority>,
+
+    /// Multisig whose admin may act in place of the authority
+    #[account(
+        seeds = [MultisigAuthority::SEED_PREFIX],
+        bump = multisig.bump
+    )]
+    pub multisig: Option<Account<'info, MultisigAuthority>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Update tier thresholds (authority or multisig admin).
+/// Stored tiers are not rewritten; each agent picks up the new thresholds on its next score change.
+pub fn update_tier_config(
+    ctx: Context<UpdateTierConfig>,
+    silver_threshold: u16,
+    gold_threshold: u16,
+    platinum_threshold: u16,
+    promotion_margin: u16,
+) -> Result<()> {
+    require!(
+        ctx.accounts.authority_account.is_admin(
+            ctx.accounts.authority.key,
+            ctx.accounts.multisig.as_deref()
+        ),
+        ReputationError::UnauthorizedAuthority
+    );
+    validate_tier_config(silver_threshold, gold_threshold, platinum_threshold, promotion_margin)?;
+
+    let config = &mut ctx.accounts.tier_config;
+    config.silver_threshold = silver_threshold;
+    config.gold_threshold = gold_threshold;
+    config.platinum_threshold = platinum_threshold;
+    config.promotion_margin = promotion_margin;
+    config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Tier config updated: silver {}, gold {}, platinum {}, margin {}",
+        silver_threshold,
+        gold_threshold,
+        platinum_threshold,
+        promotion_margin
+    );
+
+    Ok(())
+}
+
+// ==================== GET TIER (VIEW) ====================
+
+#[derive(Accounts)]
+pub struct GetTier<'info> {
+    #[account(
+        seeds = [AgentReputation::SEED_PREFIX, agent_reputation.agent_address.as_ref()],
+        bump = agent_reputation.bump,
+        constraint = agent_reputation.is_supported_version() @ ReputationError::UnsupportedAccountVersion
+    )]
+    pub agent_reputation: Account<'info, AgentReputation>,
+}
+
+/// Get the agent's stored tier (view function)
+pub fn get_tier(ctx: Context<GetTier>) -> Result<u8> {
+    let reputation = &ctx.accounts.agent_reputation;
+
+    msg!(
+        "Tier for agent {}: {} (score: {})",
+        reputation.agent_address,
+        reputation.tier,
+        reputation.overall_score
+    );
+
+    Ok(reputation.tier)
+}
@@ -1,13 +1,18 @@
 use anchor_lang::prelude::*;
-use crate::state::{AgentReputation, ComponentScores, ReputationStats, ReputationAuthority};
+use crate::state::{
+    AgentReputation, ComponentScores, PauseState, ReputationStats, ReputationAuthority, TierConfig,
+};
 use crate::error::ReputationError;
+use crate::events::{ReputationUpdated, TierChanged};
 
 #[derive(Accounts)]
 pub struct UpdateReputation<'info> {
     #[account(
         mut,
         seeds = [AgentReputation::SEED_PREFIX, agent_address.key().as_ref()],
-        bump = agent_reputation.bump
+        bump = agent_reputation.bump,
+        constraint = !agent_reputation.is_frozen @ ReputationError::ReputationFrozen,
+        constraint = agent_reputation.is_supported_version() @ ReputationError::UnsupportedAccountVersion
     )]
     pub agent_reputation: Account<'info, AgentReputation>,
 
@@ -21,6 +26,14 @@ pub struct UpdateReputation<'info> {
     /// CHECK: The agent's wallet address
     pub agent_address: UncheckedAccount<'info>,
 
+    /// CHECK: Tier config PDA; default thresholds apply while it is uninitialized
+    #[account(seeds = [TierConfig::SEED_PREFIX], bump)]
+    pub tier_config: UncheckedAccount<'info>,
+
+    /// CHECK: Pause state PDA; unpaused while it is uninitialized
+    #[account(seeds = [PauseState::SEED_PREFIX], bump)]
+    pub pause_state: UncheckedAccount<'info>,
+
     /// Authority that can update reputation
     pub authority: Signer<'info>,
 }
@@ -31,7 +44,14 @@ pub fn handler(
     component_scores: ComponentScores,
     stats: ReputationStats,
     payment_proofs_merkle_root: [u8; 32],
+    expected_nonce: u64,
+    computed_at: i64,
 ) -> Result<()> {
+    require!(
+        !PauseState::is_paused(&ctx.accounts.pause_state)?,
+        ReputationError::RegistryPaused
+    );
+
     // Validate overall score
     require!(
         overall_score <= 1000,
@@ -57,14 +77,47 @@ pub fn handler(
     let agent_reputation = &mut ctx.accounts.agent_reputation;
     let clock = Clock::get()?;
 
+    // Staleness guard: the caller must have seen the latest nonce, and the
+    // score must have been computed after the last authority update. Decay is
+    // permissionless, so it must not be able to invalidate a pending update
+    require!(
+        expected_nonce == agent_reputation.update_nonce,
+        ReputationError::StaleUpdate
+    );
+    require!(
+        computed_at > agent_reputation.last_authority_update && computed_at <= clock.unix_timestamp,
+        ReputationError::StaleUpdate
+    );
+
     agent_reputation.overall_score = overall_score;
     agent_reputation.component_scores = component_scores;
     agent_reputation.stats = stats;
     agent_reputation.payment_proofs_merkle_root = payment_proofs_merkle_root;
     agent_reputation.last_updated = clock.unix_timestamp;
+    agent_reputation.last_authority_update = clock.unix_timestamp;
+    agent_reputation.update_nonce = agent_reputation.update_nonce
+        .checked_add(1)
+        .ok_or(ReputationError::ArithmeticOverflow)?;
+
+    emit!(ReputationUpdated {
+        agent: agent_reputation.agent_address,
+        overall_score,
+        update_nonce: agent_reputation.update_nonce,
+        timestamp: clock.unix_timestamp,
+    });
+
+    let tier_params = TierConfig::resolve(&ctx.accounts.tier_config)?;
+    if let Some(previous_tier) = agent_reputation.refresh_tier(&tier_params) {
+        emit!(TierChanged {
+            agent: agent_reputation.agent_address,
+            previous_tier,
+            new_tier: agent_reputation.tier,
+            overall_score,
+        });
+    }
 
     msg!("Reputation updated for agent: {}", ctx.accounts.agent_address.key());
-    msg!("New overall score: {}", overall_score);
+    msg!("New overall score: {} (nonce {})", overall_score, agent_reputation.update_nonce);
 
     Ok(())
 }
@@ -1,5 +1,6 @@
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -7,6 +8,8 @@ use anchor_lang::prelude::*;
 
 pub use constants::*;
 pub use error::*;
+pub use events::*;
+#[allow(ambiguous_glob_reexports)]
 pub use instructions::*;
 pub use state::*;
 
@@ -21,18 +24,54 @@ pub mod reputation_registry {
         instructions::initialize_authority::handler(ctx)
     }
 
+    /// Nominate a new reputation authority (current authority only)
+    pub fn propose_authority_transfer(
+        ctx: Context<ProposeAuthorityTransfer>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::authority::propose_authority_transfer(ctx, new_authority)
+    }
+
+    /// Cancel a pending authority nomination (current authority only)
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        instructions::authority::cancel_authority_transfer(ctx)
+    }
+
+    /// Accept a pending authority nomination (nominee only)
+    pub fn accept_authority_transfer(ctx: Context<AcceptAuthorityTransfer>) -> Result<()> {
+        instructions::authority::accept_authority_transfer(ctx)
+    }
+
+    /// Hand authority to the multisig PDA so updates must go through proposals
+    pub fn renounce_authority_to_multisig(ctx: Context<RenounceAuthorityToMultisig>) -> Result<()> {
+        instructions::authority::renounce_authority_to_multisig(ctx)
+    }
+
+    /// Resize a legacy authority account to the current layout (permissionless)
+    pub fn migrate_authority(ctx: Context<MigrateAuthority>) -> Result<()> {
+        instructions::migrate::migrate_authority(ctx)
+    }
+
     /// Initialize reputation account for a registered agent
     pub fn initialize_reputation(ctx: Context<InitializeReputation>) -> Result<()> {
         instructions::initialize_reputation::handler(ctx)
     }
 
+    /// Close a deactivated agent's reputation and reclaim its rent (agent only)
+    pub fn close_reputation(ctx: Context<CloseReputation>) -> Result<()> {
+        instructions::close_reputation::handler(ctx)
+    }
+
     /// Update reputation scores with Merkle proof verification
+    /// Rejects out-of-order submissions via expected_nonce and computed_at
     pub fn update_reputation(
         ctx: Context<UpdateReputation>,
         overall_score: u16,
         component_scores: ComponentScores,
         stats: ReputationStats,
         payment_proofs_merkle_root: [u8; 32],
+        expected_nonce: u64,
+        computed_at: i64,
     ) -> Result<()> {
         instructions::update_reputation::handler(
             ctx,
@@ -40,22 +79,52 @@ pub mod reputation_registry {
             component_scores,
             stats,
             payment_proofs_merkle_root,
+            expected_nonce,
+            computed_at,
         )
     }
 
-    /// Record a verified payment proof
+    /// Record a verified payment proof (fails if the signature was already
+    /// recorded, or the payment is older than the retention period)
     pub fn record_payment_proof(
         ctx: Context<RecordPaymentProof>,
         payment_signature: String,
+        signature_hash: [u8; 32],
+        amount: Option<u64>,
+        paid_at: i64,
     ) -> Result<()> {
-        instructions::record_payment_proof::handler(ctx, payment_signature)
+        instructions::record_payment_proof::handler(ctx, payment_signature, signature_hash, amount, paid_at)
     }
 
-    /// Get reputation data (view function)
-    pub fn get_reputation(ctx: Context<GetReputation>) -> Result<()> {
+    /// Close a payment proof past its retention period and return rent to the recorder
+    pub fn close_payment_proof(ctx: Context<ClosePaymentProof>) -> Result<()> {
+        instructions::record_payment_proof::close_payment_proof(ctx)
+    }
+
+    /// Get reputation data (view function, returned via return data)
+    pub fn get_reputation(ctx: Context<GetReputation>) -> Result<ReputationView> {
         instructions::get_reputation::handler(ctx)
     }
 
+    /// Increment vote counters (CPI from vote_registry only)
+    pub fn record_vote_result(ctx: Context<RecordVoteResult>, outcome: VoteOutcome) -> Result<()> {
+        instructions::record_stats::record_vote_result(ctx, outcome)
+    }
+
+    /// Move an amended vote between the positive/negative counters (CPI from vote_registry only)
+    pub fn amend_vote_result(
+        ctx: Context<RecordVoteResult>,
+        previous: VoteOutcome,
+        outcome: VoteOutcome,
+    ) -> Result<()> {
+        instructions::record_stats::amend_vote_result(ctx, previous, outcome)
+    }
+
+    /// Record a review and recompute the average rating (CPI from vote_registry only)
+    pub fn record_review(ctx: Context<RecordReview>, rating: u8) -> Result<()> {
+        instructions::record_stats::record_review(ctx, rating)
+    }
+
     // ==================== MULTI-SIG INSTRUCTIONS ====================
 
     /// Initialize multi-sig authority with signers and threshold
@@ -88,7 +157,12 @@ pub mod reputation_registry {
         instructions::multisig::approve_proposal(ctx, proposal_id)
     }
 
-    /// Execute an approved reputation proposal
+    /// Withdraw an approval from a proposal that has not reached quorum
+    pub fn revoke_approval(ctx: Context<RevokeApproval>, proposal_id: u64) -> Result<()> {
+        instructions::multisig::revoke_approval(ctx, proposal_id)
+    }
+
+    /// Execute an approved reputation proposal once the execution delay has elapsed
     pub fn execute_reputation_proposal(
         ctx: Context<ExecuteReputationProposal>,
         proposal_id: u64,
@@ -96,6 +170,40 @@ pub mod reputation_registry {
         instructions::multisig::execute_reputation_proposal(ctx, proposal_id)
     }
 
+    /// Cancel a pending or approved proposal (any signer, or the targeted agent)
+    pub fn cancel_proposal(ctx: Context<CancelProposal>, proposal_id: u64) -> Result<()> {
+        instructions::multisig::cancel_proposal(ctx, proposal_id)
+    }
+
+    /// Close an executed, rejected or expired proposal after the audit period (permissionless)
+    pub fn close_proposal(ctx: Context<CloseProposal>, proposal_id: u64) -> Result<()> {
+        instructions::multisig::close_proposal(ctx, proposal_id)
+    }
+
+    /// Propose an emergency pause (pause = true) or unpause (pause = false) of the registry
+    pub fn propose_emergency_pause(ctx: Context<ProposeEmergencyPause>, pause: bool) -> Result<()> {
+        instructions::multisig::propose_emergency_pause(ctx, pause)
+    }
+
+    /// Execute an approved pause/unpause proposal
+    pub fn execute_emergency_pause(ctx: Context<ExecuteEmergencyPause>, proposal_id: u64) -> Result<()> {
+        instructions::multisig::execute_emergency_pause(ctx, proposal_id)
+    }
+
+    /// Propose slashing an agent's identity_registry stake (requires multisig approval)
+    pub fn propose_slash(
+        ctx: Context<ProposeSlash>,
+        severity_bps: u16,
+        reason_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::multisig::propose_slash(ctx, severity_bps, reason_hash)
+    }
+
+    /// Execute an approved slash proposal once the execution delay has elapsed
+    pub fn execute_slash_proposal(ctx: Context<ExecuteSlashProposal>, proposal_id: u64) -> Result<()> {
+        instructions::multisig::execute_slash_proposal(ctx, proposal_id)
+    }
+
     /// Add a signer to multisig (admin only)
     pub fn add_signer(ctx: Context<AddSigner>, new_signer: Pubkey) -> Result<()> {
         instructions::multisig::add_signer(ctx, new_signer)
@@ -111,6 +219,11 @@ pub mod reputation_registry {
         instructions::multisig::update_threshold(ctx, new_threshold)
     }
 
+    /// Update the delay between quorum and execution (admin only)
+    pub fn update_execution_delay(ctx: Context<UpdateThreshold>, delay_seconds: i64) -> Result<()> {
+        instructions::multisig::update_execution_delay(ctx, delay_seconds)
+    }
+
     /// Pause multisig (emergency only)
     pub fn pause_multisig(ctx: Context<PauseMultisig>) -> Result<()> {
         instructions::multisig::pause_multisig(ctx)
@@ -143,8 +256,98 @@ pub mod reputation_registry {
         instructions::decay::record_activity(ctx)
     }
 
-    /// Get effective score with decay applied (view function)
+    /// Initialize registry-wide decay parameters (authority or multisig admin)
+    pub fn initialize_decay_config(
+        ctx: Context<InitializeDecayConfig>,
+        half_life_days: u16,
+        min_score: u16,
+        grace_period_days: u16,
+    ) -> Result<()> {
+        instructions::decay::initialize_decay_config(ctx, half_life_days, min_score, grace_period_days)
+    }
+
+    /// Update decay parameters and global kill-switch (authority or multisig admin)
+    pub fn update_decay_config(
+        ctx: Context<UpdateDecayConfig>,
+        half_life_days: u16,
+        min_score: u16,
+        grace_period_days: u16,
+        decay_paused: bool,
+    ) -> Result<()> {
+        instructions::decay::update_decay_config(
+            ctx,
+            half_life_days,
+            min_score,
+            grace_period_days,
+            decay_paused,
+        )
+    }
+
+    /// Get effective score with decay applied (view function, returned via return data)
     pub fn get_effective_score(ctx: Context<GetEffectiveScore>) -> Result<u16> {
         instructions::decay::get_effective_score(ctx)
     }
+
+    // ==================== FREEZE INSTRUCTIONS ====================
+
+    /// Freeze an agent's reputation during an investigation (authority or multisig admin)
+    pub fn freeze_reputation(ctx: Context<FreezeReputation>, reason_hash: [u8; 32]) -> Result<()> {
+        instructions::freeze::freeze_reputation(ctx, reason_hash)
+    }
+
+    /// Lift a freeze without applying decay for the frozen period (authority or multisig admin)
+    pub fn unfreeze_reputation(ctx: Context<UnfreezeReputation>) -> Result<()> {
+        instructions::freeze::unfreeze_reputation(ctx)
+    }
+
+    // ==================== TIER INSTRUCTIONS ====================
+
+    /// Initialize registry-wide tier thresholds (authority or multisig admin)
+    pub fn initialize_tier_config(
+        ctx: Context<InitializeTierConfig>,
+        silver_threshold: u16,
+        gold_threshold: u16,
+        platinum_threshold: u16,
+        promotion_margin: u16,
+    ) -> Result<()> {
+        instructions::tier::initialize_tier_config(
+            ctx,
+            silver_threshold,
+            gold_threshold,
+            platinum_threshold,
+            promotion_margin,
+        )
+    }
+
+    /// Update tier thresholds and promotion margin (authority or multisig admin)
+    pub fn update_tier_config(
+        ctx: Context<UpdateTierConfig>,
+        silver_threshold: u16,
+        gold_threshold: u16,
+        platinum_threshold: u16,
+        promotion_margin: u16,
+    ) -> Result<()> {
+        instructions::tier::update_tier_config(
+            ctx,
+            silver_threshold,
+            gold_threshold,
+            platinum_threshold,
+            promotion_margin,
+        )
+    }
+
+    /// Get the agent's stored tier (view function)
+    pub fn get_tier(ctx: Context<GetTier>) -> Result<u8> {
+        instructions::tier::get_tier(ctx)
+    }
+
+    /// Upgrade a reputation account to the current layout version (permissionless)
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::migrate::migrate_account(ctx)
+    }
+
+    /// Resize a legacy multisig account and apply the default execution delay (permissionless)
+    pub fn migrate_multisig(ctx: Context<MigrateMultisig>) -> Result<()> {
+        instructions::migrate::migrate_multisig(ctx)
+    }
 }
@@ -20,7 +20,21 @@ pub struct ReputationStats {
     pub avg_review_rating: u8, // 0-50 (multiplied by 10 for precision)
 }
 
-/// Decay configuration constants
+/// Who created an AgentReputation account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default, Debug)]
+pub enum InitializedBy {
+    /// Created before this was recorded
+    #[default]
+    Unknown,
+    /// The agent's own wallet
+    Agent,
+    /// The reputation authority
+    Authority,
+    /// identity_registry during agent registration (CPI)
+    IdentityRegistry,
+}
+
+/// Default decay configuration (used when no DecayConfig account exists)
 pub const DECAY_HALF_LIFE_DAYS: i64 = 90; // Score halves every 90 days of inactivity
 pub const DECAY_MIN_SCORE: u16 = 100; // Minimum score after decay
 pub const DECAY_GRACE_PERIOD_DAYS: i64 = 30; // No decay for first 30 days
@@ -65,11 +79,35 @@ pub struct AgentReputation {
 
     /// Custom decay rate multiplier (100 = normal, 50 = half decay)
     pub decay_rate_bps: u16,
+
+    /// Monotonic counter bumped on every authority/multisig score update
+    pub update_nonce: u64,
+
+    /// Derived tier (see TIER_* constants), recomputed whenever overall_score changes
+    pub tier: u8,
+
+    /// Updates and decay are blocked while frozen for investigation
+    pub is_frozen: bool,
+
+    /// When the current freeze started (0 if never frozen)
+    pub frozen_at: i64,
+
+    /// Who created this account (audit trail)
+    pub initialized_by: InitializedBy,
+
+    /// Number of distinct payment proofs recorded (see PaymentProof)
+    pub payment_proof_count: u32,
+
+    /// Layout version (see CURRENT_VERSION); 0 on accounts migrate_account has not upgraded
+    pub version: u8,
+
+    /// When update_reputation last applied a score; decay does not touch it
+    pub last_authority_update: i64,
 }
 
 impl AgentReputation {
     /// Seed prefix for PDA derivation
-    pub const SEED_PREFIX: &'static [u8] = b"reputation";
+    pub const SEED_PREFIX: &'static [u8] = gs2_common::REPUTATION_SEED;
 
     /// Calculate space for rent
     pub const LEN: usize = 8 + // discriminator
@@ -83,11 +121,43 @@ impl AgentReputation {
         2 + // base_score
         8 + // last_activity
         1 + // decay_enabled
-        2; // decay_rate_bps
+        2 + // decay_rate_bps
+        8 + // update_nonce
+        1 + // tier
+        1 + // is_frozen
+        8 + // frozen_at
+        1 + // initialized_by
+        4 + // payment_proof_count
+        1 + // version
+        8; // last_authority_update
+
+    /// Size of version 1 accounts, created before last_authority_update was added
+    pub const V1_LEN: usize = Self::LEN - 8;
+
+    /// Size of accounts created before the version field was added
+    pub const PRE_VERSION_LEN: usize = Self::V1_LEN - 1;
+
+    /// Size of accounts created before update_nonce and later fields were added
+    pub const LEGACY_LEN: usize = Self::PRE_VERSION_LEN - 8 - 1 - 1 - 8 - 1 - 4;
+
+    /// Layout version written by initialize_reputation and migrate_account
+    pub const CURRENT_VERSION: u8 = 2;
+
+    /// Oldest layout version the handlers accept; older accounts must call migrate_account
+    pub const MIN_SUPPORTED_VERSION: u8 = 2;
+
+    /// Whether the handlers accept this account's layout version
+    pub fn is_supported_version(&self) -> bool {
+        self.version >= Self::MIN_SUPPORTED_VERSION
+    }
 
     /// Calculate the decayed score based on time since last activity
     /// Uses exponential decay with configurable half-life
-    pub fn calculate_decayed_score(&self, current_time: i64) -> u16 {
+    ///
+    /// The result is never above base_score, never below
+    /// min(params.min_score, base_score) and never rises as current_time
+    /// advances. A half-life under one day counts as one day.
+    pub fn calculate_decayed_score(&self, current_time: i64, params: &DecayParams) -> u16 {
         // If decay is disabled, return base score
         if !self.decay_enabled {
             return self.base_score;
@@ -99,21 +169,21 @@ impl AgentReputation {
             .saturating_div(SECONDS_PER_DAY);
 
         // Grace period: no decay
-        if days_inactive <= DECAY_GRACE_PERIOD_DAYS {
+        if days_inactive <= params.grace_period_days {
             return self.base_score;
         }
 
         // Effective days for decay calculation
-        let effective_days = days_inactive.saturating_sub(DECAY_GRACE_PERIOD_DAYS);
+        let effective_days = days_inactive.saturating_sub(params.grace_period_days);
 
         // Apply custom decay rate (default 10000 = 100%)
-        let decay_multiplier = self.decay_rate_bps.max(100).min(10000) as i64;
+        let decay_multiplier = self.decay_rate_bps.clamp(100, 10000) as i64;
 
         // Exponential decay: score * 0.5^(days/half_life)
         // Using approximation: score * (1 - decay_factor)^periods
         // Where periods = effective_days / half_life
         let periods = effective_days.saturating_mul(decay_multiplier)
-            .saturating_div(DECAY_HALF_LIFE_DAYS.saturating_mul(10000));
+            .saturating_div(params.half_life_days.max(1).saturating_mul(10000));
 
         // For each period, multiply by 0.5 (shift right by 1)
         // Clamped to prevent underflow
@@ -122,8 +192,15 @@ impl AgentReputation {
             decayed = decayed.saturating_div(2);
         }
 
-        // Apply minimum score floor
-        (decayed as u16).max(DECAY_MIN_SCORE)
+        // Apply minimum score floor; decay never raises a score already below it
+        (decayed as u16).max(params.min_score.min(self.base_score))
+    }
+
+    /// Recompute the tier from overall_score; returns the previous tier if it changed
+    pub fn refresh_tier(&mut self, params: &TierParams) -> Option<u8> {
+        let previous = self.tier;
+        self.tier = params.tier_for(self.overall_score, previous);
+        (self.tier != previous).then_some(previous)
     }
 
     /// Record activity to reset decay clock
@@ -132,15 +209,94 @@ impl AgentReputation {
     }
 
     /// Get effective score with decay applied
-    pub fn get_effective_score(&self, current_time: i64) -> u16 {
-        if self.decay_enabled {
-            self.calculate_decayed_score(current_time)
+    /// The decay clock stops while frozen
+    pub fn get_effective_score(&self, current_time: i64, params: &DecayParams) -> u16 {
+        let as_of = if self.is_frozen { self.frozen_at } else { current_time };
+        if self.decay_enabled && !params.paused {
+            self.calculate_decayed_score(as_of, params)
         } else {
             self.overall_score
         }
     }
 }
 
+// gs2-common mirrors AgentReputation for the other programs and off-chain clients.
+// These fail to compile when the program ID, discriminator, a field or an enum
+// variant changes on one side only; the round-trip test below pins field order.
+const _: () = assert!(gs2_common::bytes_eq(
+    &crate::ID.to_bytes(),
+    &gs2_common::REPUTATION_REGISTRY_PROGRAM_ID.to_bytes()
+));
+const _: () = assert!(gs2_common::bytes_eq(
+    AgentReputation::DISCRIMINATOR,
+    <gs2_common::AgentReputation as Discriminator>::DISCRIMINATOR
+));
+
+impl From<&AgentReputation> for gs2_common::AgentReputation {
+    fn from(reputation: &AgentReputation) -> Self {
+        let AgentReputation {
+            agent_address,
+            overall_score,
+            component_scores: ComponentScores { trust, quality, reliability, economic, social },
+            stats:
+                ReputationStats {
+                    total_votes,
+                    positive_votes,
+                    negative_votes,
+                    total_reviews,
+                    avg_review_rating,
+                },
+            payment_proofs_merkle_root,
+            last_updated,
+            bump,
+            base_score,
+            last_activity,
+            decay_enabled,
+            decay_rate_bps,
+            update_nonce,
+            tier,
+            is_frozen,
+            frozen_at,
+            initialized_by,
+            payment_proof_count,
+            version,
+            last_authority_update,
+        } = *reputation;
+        gs2_common::AgentReputation {
+            agent_address,
+            overall_score,
+            component_scores: gs2_common::ComponentScores { trust, quality, reliability, economic, social },
+            stats: gs2_common::ReputationStats {
+                total_votes,
+                positive_votes,
+                negative_votes,
+                total_reviews,
+                avg_review_rating,
+            },
+            payment_proofs_merkle_root,
+            last_updated,
+            bump,
+            base_score,
+            last_activity,
+            decay_enabled,
+            decay_rate_bps,
+            update_nonce,
+            tier,
+            is_frozen,
+            frozen_at,
+            initialized_by: match initialized_by {
+                InitializedBy::Unknown => gs2_common::InitializedBy::Unknown,
+                InitializedBy::Agent => gs2_common::InitializedBy::Agent,
+                InitializedBy::Authority => gs2_common::InitializedBy::Authority,
+                InitializedBy::IdentityRegistry => gs2_common::InitializedBy::IdentityRegistry,
+            },
+            payment_proof_count,
+            version,
+            last_authority_update,
+        }
+    }
+}
+
 /// Authority configuration for reputation registry
 /// PDA seeds: ["authority"]
 #[account]
@@ -151,6 +307,9 @@ pub struct ReputationAuthority {
 
     /// PDA bump seed
     pub bump: u8,
+
+    /// Nominated successor awaiting acceptance (default pubkey = none)
+    pub pending_authority: Pubkey,
 }
 
 impl ReputationAuthority {
@@ -160,9 +319,267 @@ impl ReputationAuthority {
     /// Calculate space for rent
     pub const LEN: usize = 8 + // discriminator
         32 + // authority
+        1 + // bump
+        32; // pending_authority
+
+    /// Size of accounts created before pending_authority was added
+    pub const LEGACY_LEN: usize = 8 + 32 + 1;
+
+    /// Registry admin operations accept either the authority or the multisig admin
+    pub fn is_admin(&self, key: &Pubkey, multisig: Option<&MultisigAuthority>) -> bool {
+        self.authority == *key || multisig.is_some_and(|m| m.is_active && m.admin == *key)
+    }
+}
+
+// ==================== PAYMENT PROOFS ====================
+
+/// Payment proofs may be closed to reclaim rent once this old (180 days).
+/// Payments older than this cannot be recorded, so a closed proof cannot be
+/// recorded again.
+pub const PAYMENT_PROOF_RETENTION_SECONDS: i64 = 180 * SECONDS_PER_DAY;
+
+/// A recorded x402 payment, one per (agent, signature)
+/// PDA seeds: ["payment_proof", agent, signature_hash]
+#[account]
+#[derive(InitSpace)]
+pub struct PaymentProof {
+    /// Agent that received the payment
+    pub agent: Pubkey,
+
+    /// SHA-256 of the payment transaction signature
+    pub signature_hash: [u8; 32],
+
+    /// Payment amount in lamports, if known
+    pub amount: Option<u64>,
+
+    /// When the proof was recorded
+    pub recorded_at: i64,
+
+    /// Signer that recorded the proof (receives rent on close)
+    pub recorder: Pubkey,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl PaymentProof {
+    pub const SEED_PREFIX: &'static [u8] = b"payment_proof";
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        32 + // signature_hash
+        9 + // amount (Option<u64>)
+        8 + // recorded_at
+        32 + // recorder
         1; // bump
 }
 
+// ==================== DECAY CONFIG ====================
+
+/// Decay parameters resolved for a single calculation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecayParams {
+    pub half_life_days: i64,
+    pub min_score: u16,
+    pub grace_period_days: i64,
+    pub paused: bool,
+}
+
+impl Default for DecayParams {
+    fn default() -> Self {
+        Self {
+            half_life_days: DECAY_HALF_LIFE_DAYS,
+            min_score: DECAY_MIN_SCORE,
+            grace_period_days: DECAY_GRACE_PERIOD_DAYS,
+            paused: false,
+        }
+    }
+}
+
+/// Registry-wide decay configuration
+/// PDA seeds: ["decay_config"]
+#[account]
+#[derive(InitSpace)]
+pub struct DecayConfig {
+    /// Days of inactivity (after grace) for the score to halve
+    pub half_life_days: u16,
+
+    /// Floor below which decay never pushes a score
+    pub min_score: u16,
+
+    /// Days of inactivity before decay starts
+    pub grace_period_days: u16,
+
+    /// Global kill-switch: when set, no decay is applied anywhere
+    pub decay_paused: bool,
+
+    /// Last time the config was changed
+    pub updated_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl DecayConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"decay_config";
+
+    pub const LEN: usize = 8 + // discriminator
+        2 + // half_life_days
+        2 + // min_score
+        2 + // grace_period_days
+        1 + // decay_paused
+        8 + // updated_at
+        1; // bump
+
+    pub const MIN_HALF_LIFE_DAYS: u16 = 7;
+    pub const MAX_HALF_LIFE_DAYS: u16 = 365;
+    pub const MAX_GRACE_PERIOD_DAYS: u16 = 365;
+
+    pub fn params(&self) -> DecayParams {
+        DecayParams {
+            half_life_days: self.half_life_days as i64,
+            min_score: self.min_score,
+            grace_period_days: self.grace_period_days as i64,
+            paused: self.decay_paused,
+        }
+    }
+
+    /// Resolve decay parameters from the (possibly uninitialized) config PDA,
+    /// falling back to the compile-time defaults when it doesn't exist yet
+    pub fn resolve(info: &AccountInfo) -> Result<DecayParams> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(DecayParams::default());
+        }
+        let data = info.try_borrow_data()?;
+        let config = DecayConfig::try_deserialize(&mut &data[..])?;
+        Ok(config.params())
+    }
+}
+
+// ==================== TIER CONFIG ====================
+
+pub const TIER_BRONZE: u8 = 0;
+pub const TIER_SILVER: u8 = 1;
+pub const TIER_GOLD: u8 = 2;
+pub const TIER_PLATINUM: u8 = 3;
+
+/// Default tier thresholds (used when no TierConfig account exists)
+pub const DEFAULT_SILVER_THRESHOLD: u16 = 250;
+pub const DEFAULT_GOLD_THRESHOLD: u16 = 500;
+pub const DEFAULT_PLATINUM_THRESHOLD: u16 = 750;
+pub const DEFAULT_PROMOTION_MARGIN: u16 = 10;
+
+/// Tier thresholds resolved for a single calculation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TierParams {
+    pub silver_threshold: u16,
+    pub gold_threshold: u16,
+    pub platinum_threshold: u16,
+    pub promotion_margin: u16,
+}
+
+impl Default for TierParams {
+    fn default() -> Self {
+        Self {
+            silver_threshold: DEFAULT_SILVER_THRESHOLD,
+            gold_threshold: DEFAULT_GOLD_THRESHOLD,
+            platinum_threshold: DEFAULT_PLATINUM_THRESHOLD,
+            promotion_margin: DEFAULT_PROMOTION_MARGIN,
+        }
+    }
+}
+
+impl TierParams {
+    /// Minimum score for a tier (Bronze has no floor)
+    pub fn threshold(&self, tier: u8) -> u16 {
+        match tier {
+            TIER_SILVER => self.silver_threshold,
+            TIER_GOLD => self.gold_threshold,
+            TIER_PLATINUM => self.platinum_threshold,
+            _ => 0,
+        }
+    }
+
+    /// Highest tier whose threshold the score meets, ignoring hysteresis
+    pub fn raw_tier(&self, score: u16) -> u8 {
+        (TIER_SILVER..=TIER_PLATINUM)
+            .rev()
+            .find(|&t| score >= self.threshold(t))
+            .unwrap_or(TIER_BRONZE)
+    }
+
+    /// Tier for a score given the current tier.
+    /// Demotion is immediate; promotion requires clearing the threshold by promotion_margin.
+    pub fn tier_for(&self, score: u16, current: u8) -> u8 {
+        let raw = self.raw_tier(score);
+        if raw <= current {
+            return raw;
+        }
+        (current + 1..=raw)
+            .rev()
+            .find(|&t| score >= self.threshold(t).saturating_add(self.promotion_margin))
+            .unwrap_or(current)
+    }
+}
+
+/// Registry-wide tier thresholds
+/// PDA seeds: ["tier_config"]
+#[account]
+#[derive(InitSpace)]
+pub struct TierConfig {
+    /// Minimum score for Silver
+    pub silver_threshold: u16,
+
+    /// Minimum score for Gold
+    pub gold_threshold: u16,
+
+    /// Minimum score for Platinum
+    pub platinum_threshold: u16,
+
+    /// Points above a threshold required before promoting into that tier
+    pub promotion_margin: u16,
+
+    /// Last time the config was changed
+    pub updated_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl TierConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"tier_config";
+
+    pub const LEN: usize = 8 + // discriminator
+        2 + // silver_threshold
+        2 + // gold_threshold
+        2 + // platinum_threshold
+        2 + // promotion_margin
+        8 + // updated_at
+        1; // bump
+
+    pub const MAX_PROMOTION_MARGIN: u16 = 100;
+
+    pub fn params(&self) -> TierParams {
+        TierParams {
+            silver_threshold: self.silver_threshold,
+            gold_threshold: self.gold_threshold,
+            platinum_threshold: self.platinum_threshold,
+            promotion_margin: self.promotion_margin,
+        }
+    }
+
+    /// Resolve tier thresholds from the (possibly uninitialized) config PDA,
+    /// falling back to the defaults when it doesn't exist yet
+    pub fn resolve(info: &AccountInfo) -> Result<TierParams> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(TierParams::default());
+        }
+        let data = info.try_borrow_data()?;
+        let config = TierConfig::try_deserialize(&mut &data[..])?;
+        Ok(config.params())
+    }
+}
+
 // ==================== MULTI-SIG AUTHORITY (2026 Best Practice) ====================
 
 /// Maximum number of signers in multi-sig (3-of-5 or 5-of-7 typical)
@@ -174,6 +591,15 @@ pub const MAX_PENDING_PROPOSALS: usize = 10;
 /// Proposal expiry time (48 hours)
 pub const PROPOSAL_EXPIRY_SECONDS: i64 = 48 * 60 * 60;
 
+/// Terminal proposals stay on-chain this long for auditability before they can be closed (7 days)
+pub const PROPOSAL_CLOSE_GRACE_SECONDS: i64 = 7 * SECONDS_PER_DAY;
+
+/// Default delay between reaching quorum and execution (6 hours)
+pub const DEFAULT_EXECUTION_DELAY_SECONDS: i64 = 6 * 60 * 60;
+
+/// Upper bound on the configurable execution delay (7 days)
+pub const MAX_EXECUTION_DELAY_SECONDS: i64 = 7 * SECONDS_PER_DAY;
+
 /// Multi-sig Authority Configuration
 /// PDA seeds: ["multisig_authority"]
 #[account]
@@ -200,10 +626,13 @@ pub struct MultisigAuthority {
 
     /// PDA bump seed
     pub bump: u8,
+
+    /// Seconds an approved reputation proposal must wait before execution
+    pub execution_delay_seconds: i64,
 }
 
 impl MultisigAuthority {
-    pub const SEED_PREFIX: &'static [u8] = b"multisig_authority";
+    pub const SEED_PREFIX: &'static [u8] = gs2_common::REPUTATION_MULTISIG_SEED;
 
     pub const LEN: usize = 8 + // discriminator
         4 + (32 * MAX_MULTISIG_SIGNERS) + // signers vec
@@ -212,7 +641,11 @@ impl MultisigAuthority {
         32 + // admin
         1 + // is_active
         8 + // created_at
-        1; // bump
+        1 + // bump
+        8; // execution_delay_seconds
+
+    /// Size of accounts created before execution_delay_seconds was added
+    pub const LEGACY_LEN: usize = Self::LEN - 8;
 }
 
 /// Proposal types for multi-sig approval
@@ -228,6 +661,10 @@ pub enum ProposalType {
     UpdateThreshold,
     /// Emergency pause
     EmergencyPause,
+    /// Lift an emergency pause
+    Unpause,
+    /// Slash an agent's stake in identity_registry
+    SlashAgent,
 }
 
 /// Proposal status
@@ -255,10 +692,10 @@ pub struct MultisigProposal {
     /// Proposer address
     pub proposer: Pubkey,
 
-    /// Agent address (for reputation updates)
+    /// Agent address (for reputation updates and slashes)
     pub target_agent: Pubkey,
 
-    /// Proposed overall score (for reputation updates)
+    /// Proposed overall score (for reputation updates), or severity in bps (for slashes)
     pub proposed_score: u16,
 
     /// Proposed component scores (for reputation updates)
@@ -267,7 +704,7 @@ pub struct MultisigProposal {
     /// Proposed stats (for reputation updates)
     pub proposed_stats: ReputationStats,
 
-    /// Proposed merkle root (for reputation updates)
+    /// Proposed merkle root (for reputation updates), or reason hash (for slashes)
     pub proposed_merkle_root: [u8; 32],
 
     /// For AddSigner/RemoveSigner: the signer address
@@ -288,11 +725,17 @@ pub struct MultisigProposal {
     /// Creation timestamp
     pub created_at: i64,
 
-    /// Execution timestamp (if executed)
+    /// Execution timestamp (if executed) or cancellation timestamp (if rejected)
     pub executed_at: i64,
 
     /// PDA bump seed
     pub bump: u8,
+
+    /// When the proposal reached quorum (0 until approved); starts the execution delay
+    pub approval_reached_at: i64,
+
+    /// For reputation updates: target's update_nonce when the proposal was created
+    pub expected_nonce: u64,
 }
 
 impl MultisigProposal {
@@ -314,7 +757,9 @@ impl MultisigProposal {
         1 + // status
         8 + // created_at
         8 + // executed_at
-        1; // bump
+        1 + // bump
+        8 + // approval_reached_at
+        8; // expected_nonce
 
     /// Check if a signer has already approved (using bitmap)
     pub fn has_approved(&self, signer_index: u8) -> bool {
@@ -327,6 +772,12 @@ impl MultisigProposal {
         self.approval_count = self.approval_count.saturating_add(1);
     }
 
+    /// Withdraw a previously recorded approval
+    pub fn revoke_approval(&mut self, signer_index: u8) {
+        self.approval_bitmap &= !(1 << signer_index);
+        self.approval_count = self.approval_count.saturating_sub(1);
+    }
+
     /// Check if proposal has expired
     pub fn is_expired(&self, current_time: i64) -> bool {
         current_time > self.created_at.saturating_add(PROPOSAL_EXPIRY_SECONDS)
@@ -336,4 +787,281 @@ impl MultisigProposal {
     pub fn has_quorum(&self, threshold: u8) -> bool {
         self.approval_count >= threshold
     }
+
+    /// When the proposal reached a terminal state, or None if it can still progress.
+    /// `delay_seconds` is the multisig's configured execution delay.
+    pub fn finalized_at(&self, current_time: i64, delay_seconds: i64) -> Option<i64> {
+        match self.status {
+            ProposalStatus::Executed | ProposalStatus::Rejected => Some(self.executed_at),
+            ProposalStatus::Expired => Some(self.created_at.saturating_add(PROPOSAL_EXPIRY_SECONDS)),
+            ProposalStatus::Pending if self.is_expired(current_time) => {
+                Some(self.created_at.saturating_add(PROPOSAL_EXPIRY_SECONDS))
+            }
+            ProposalStatus::Approved if self.is_execution_window_closed(current_time, delay_seconds) => {
+                Some(self.execution_deadline(delay_seconds))
+            }
+            _ => None,
+        }
+    }
+
+    /// Execution delay that applies to this proposal: pause proposals run as soon
+    /// as they reach quorum, everything else waits `configured_delay`
+    pub fn execution_delay(&self, configured_delay: i64) -> i64 {
+        match self.proposal_type {
+            ProposalType::EmergencyPause | ProposalType::Unpause => 0,
+            _ => configured_delay,
+        }
+    }
+
+    /// Last moment an approved proposal may be executed: PROPOSAL_EXPIRY_SECONDS
+    /// after its execution delay ends
+    pub fn execution_deadline(&self, configured_delay: i64) -> i64 {
+        self.approval_reached_at
+            .saturating_add(self.execution_delay(configured_delay))
+            .saturating_add(PROPOSAL_EXPIRY_SECONDS)
+    }
+
+    /// Whether an approved proposal was left unexecuted past its deadline
+    pub fn is_execution_window_closed(&self, current_time: i64, configured_delay: i64) -> bool {
+        current_time > self.execution_deadline(configured_delay)
+    }
+
+    /// Check if the post-approval delay has elapsed
+    pub fn is_executable(&self, current_time: i64, delay_seconds: i64) -> bool {
+        self.approval_reached_at > 0
+            && current_time >= self.approval_reached_at.saturating_add(delay_seconds)
+    }
+}
+
+// ==================== EMERGENCY PAUSE ====================
+
+/// Registry-wide emergency pause, toggled only through multisig proposals
+/// PDA seeds: ["pause_state"]
+#[account]
+#[derive(InitSpace)]
+pub struct PauseState {
+    /// Whether score-changing instructions are blocked
+    pub paused: bool,
+
+    /// Proposal that last changed the pause state
+    pub last_proposal_id: u64,
+
+    /// Last time the pause state changed
+    pub updated_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl PauseState {
+    pub const SEED_PREFIX: &'static [u8] = b"pause_state";
+
+    pub const LEN: usize = 8 + // discriminator
+        1 + // paused
+        8 + // last_proposal_id
+        8 + // updated_at
+        1; // bump
+
+    /// Read the pause flag from the (possibly uninitialized) PDA;
+    /// the registry is unpaused until the first pause proposal executes
+    pub fn is_paused(info: &AccountInfo) -> Result<bool> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(false);
+        }
+        let data = info.try_borrow_data()?;
+        let state = PauseState::try_deserialize(&mut &data[..])?;
+        Ok(state.paused)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gs2_common::test_utils::i64_samples;
+
+    /// A reputation with a distinct non-default value in every field, so a
+    /// swapped or missing field shows up as a mismatch
+    fn reputation() -> AgentReputation {
+        AgentReputation {
+            agent_address: Pubkey::new_from_array([1; 32]),
+            overall_score: 2,
+            component_scores: ComponentScores { trust: 3, quality: 4, reliability: 5, economic: 6, social: 7 },
+            stats: ReputationStats {
+                total_votes: 8,
+                positive_votes: 9,
+                negative_votes: 10,
+                total_reviews: 11,
+                avg_review_rating: 12,
+            },
+            payment_proofs_merkle_root: [13; 32],
+            last_updated: 14,
+            bump: 15,
+            base_score: 16,
+            last_activity: 17,
+            decay_enabled: true,
+            decay_rate_bps: 18,
+            update_nonce: 19,
+            tier: 20,
+            is_frozen: true,
+            frozen_at: 21,
+            initialized_by: InitializedBy::IdentityRegistry,
+            payment_proof_count: 22,
+            version: 23,
+            last_authority_update: 24,
+        }
+    }
+
+    #[test]
+    fn gs2_common_reads_agent_reputation() {
+        let reputation = reputation();
+        let mut data = Vec::new();
+        reputation.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), AgentReputation::LEN);
+
+        let mirror = gs2_common::AgentReputation::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(mirror, gs2_common::AgentReputation::from(&reputation));
+    }
+
+    #[test]
+    fn gs2_common_writes_agent_reputation() {
+        let mirror = gs2_common::AgentReputation::from(&reputation());
+        let mut data = Vec::new();
+        mirror.try_serialize(&mut data).unwrap();
+
+        let decoded = AgentReputation::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(gs2_common::AgentReputation::from(&decoded), mirror);
+    }
+
+    #[test]
+    fn gs2_common_prefix_reads_legacy_accounts() {
+        let reputation = reputation();
+        let mut data = Vec::new();
+        reputation.try_serialize(&mut data).unwrap();
+
+        let legacy = &data[..AgentReputation::LEGACY_LEN];
+        assert!(gs2_common::AgentReputation::try_deserialize(&mut &legacy[..]).is_err());
+
+        let prefix = gs2_common::AgentReputationPrefix::try_deserialize(&mut &legacy[..]).unwrap();
+        assert_eq!(prefix.agent_address, reputation.agent_address);
+        assert_eq!(prefix.overall_score, reputation.overall_score);
+    }
+
+    #[test]
+    fn proposal_fields_added_later_are_appended() {
+        let proposal = MultisigProposal {
+            proposal_id: 7,
+            proposal_type: ProposalType::UpdateReputation,
+            proposer: Pubkey::new_unique(),
+            target_agent: Pubkey::new_unique(),
+            proposed_score: 640,
+            proposed_components: ComponentScores::default(),
+            proposed_stats: ReputationStats::default(),
+            proposed_merkle_root: [3; 32],
+            target_signer: Pubkey::default(),
+            new_threshold: 0,
+            approval_bitmap: 1,
+            approval_count: 1,
+            status: ProposalStatus::Approved,
+            created_at: 1_700_000_000,
+            executed_at: 0,
+            bump: 254,
+            approval_reached_at: 1_700_000_100,
+            expected_nonce: 0x0102_0304_0506_0708,
+        };
+        let mut data = Vec::new();
+        proposal.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), MultisigProposal::LEN);
+
+        // Accounts created before approval_reached_at and expected_nonce keep
+        // every earlier field at the same offset
+        assert_eq!(data[MultisigProposal::LEN - 16..MultisigProposal::LEN - 8], 1_700_000_100i64.to_le_bytes());
+        assert_eq!(data[MultisigProposal::LEN - 8..], 0x0102_0304_0506_0708u64.to_le_bytes());
+        assert_eq!(data[MultisigProposal::LEN - 17], 254);
+    }
+
+    #[test]
+    fn approved_proposals_finalize_once_their_execution_window_closes() {
+        let mut proposal = MultisigProposal {
+            proposal_id: 1,
+            proposal_type: ProposalType::UpdateReputation,
+            proposer: Pubkey::default(),
+            target_agent: Pubkey::default(),
+            proposed_score: 0,
+            proposed_components: ComponentScores::default(),
+            proposed_stats: ReputationStats::default(),
+            proposed_merkle_root: [0; 32],
+            target_signer: Pubkey::default(),
+            new_threshold: 0,
+            approval_bitmap: 3,
+            approval_count: 2,
+            status: ProposalStatus::Approved,
+            created_at: 1_000,
+            executed_at: 0,
+            bump: 0,
+            approval_reached_at: 2_000,
+            expected_nonce: 0,
+        };
+        let delay = DEFAULT_EXECUTION_DELAY_SECONDS;
+        let deadline = 2_000 + delay + PROPOSAL_EXPIRY_SECONDS;
+
+        assert_eq!(proposal.finalized_at(deadline, delay), None);
+        assert_eq!(proposal.finalized_at(deadline + 1, delay), Some(deadline));
+
+        // Pause proposals skip the delay, so their window closes sooner
+        proposal.proposal_type = ProposalType::EmergencyPause;
+        assert_eq!(proposal.finalized_at(deadline - delay + 1, delay), Some(deadline - delay));
+    }
+
+    /// Boundary timestamps plus pseudo-random ones of every magnitude and sign, sorted
+    fn timestamps() -> Vec<i64> {
+        let mut values = vec![i64::MIN, i64::MIN + 1, -SECONDS_PER_DAY, -1, 0, 1, SECONDS_PER_DAY, i64::MAX - 1, i64::MAX];
+        values.extend(i64_samples(64));
+        values.sort_unstable();
+        values
+    }
+
+    fn decay_params() -> Vec<DecayParams> {
+        let mut params = vec![DecayParams::default()];
+        for half_life_days in [0, 1, DecayConfig::MIN_HALF_LIFE_DAYS, DecayConfig::MAX_HALF_LIFE_DAYS, u16::MAX] {
+            for min_score in [0, DECAY_MIN_SCORE, 1000] {
+                for grace_period_days in [0, DecayConfig::MAX_GRACE_PERIOD_DAYS] {
+                    params.push(DecayParams {
+                        half_life_days: half_life_days as i64,
+                        min_score,
+                        grace_period_days: grace_period_days as i64,
+                        paused: false,
+                    });
+                }
+            }
+        }
+        params
+    }
+
+    #[test]
+    fn decayed_score_stays_between_floor_and_base_and_never_rises_over_time() {
+        let mut reputation = reputation();
+        reputation.decay_enabled = true;
+        for params in decay_params() {
+            for base_score in [0, 1, DECAY_MIN_SCORE - 1, DECAY_MIN_SCORE, 500, 1000] {
+                for decay_rate_bps in [0, 100, 5000, 10_000, u16::MAX] {
+                    for last_activity in [i64::MIN, -1, 0, 1_700_000_000, i64::MAX] {
+                        reputation.base_score = base_score;
+                        reputation.decay_rate_bps = decay_rate_bps;
+                        reputation.last_activity = last_activity;
+
+                        let floor = params.min_score.min(base_score);
+                        let mut previous = base_score;
+                        for now in timestamps() {
+                            let score = reputation.calculate_decayed_score(now, &params);
+                            assert!(
+                                (floor..=previous).contains(&score),
+                                "{params:?} base {base_score} rate {decay_rate_bps} at {last_activity}..{now}: {score}"
+                            );
+                            previous = score;
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
@@ -40,4 +40,148 @@ pub enum TokenStakingError {
 
     #[msg("Arithmetic overflow")]
     ArithmeticOverflow,
+
+    #[msg("Slash severity must be 1-10000 bps")]
+    InvalidSlashSeverity,
+
+    #[msg("Slashing is not configured for this vault")]
+    SlashingNotConfigured,
+
+    #[msg("Unauthorized: not the vault slash authority")]
+    UnauthorizedSlashAuthority,
+
+    #[msg("Penalty destination must be a token account of the vault mint")]
+    InvalidPenaltyDestination,
+
+    #[msg("Stake position has been slashed")]
+    PositionSlashed,
+
+    #[msg("Invalid reward period (must be 1 second to 365 days)")]
+    InvalidRewardPeriod,
+
+    #[msg("Reward amount must be greater than zero")]
+    InvalidRewardAmount,
+
+    #[msg("Unauthorized: not the vault registry admin")]
+    UnauthorizedRegistryAdmin,
+
+    #[msg("Unauthorized: not the vault verifier")]
+    UnauthorizedVerifier,
+
+    #[msg("Vault is not verified")]
+    VaultNotVerified,
+
+    #[msg("Invalid unstake cooldown (must be 0 to 365 days)")]
+    InvalidUnstakeCooldown,
+
+    #[msg("Vault uses an unstake cooldown: use request_unstake and withdraw_unstaked")]
+    CooldownModeVault,
+
+    #[msg("Vault uses a stake lock: use unstake_tokens")]
+    LockModeVault,
+
+    #[msg("No pending unstake to withdraw")]
+    NoPendingUnstake,
+
+    #[msg("Stake position still holds tokens")]
+    PositionStillActive,
+
+    #[msg("Stake position has unclaimed rewards")]
+    UnclaimedRewards,
+
+    #[msg("Vault still holds stake or tokens")]
+    VaultNotEmpty,
+
+    #[msg("Vault reward token account must be provided")]
+    MissingRewardTokenAccount,
+
+    #[msg("Account is not a legacy staking account of this program")]
+    InvalidMigrationTarget,
+
+    #[msg("Vault layout version is no longer supported; call migrate_account first")]
+    UnsupportedAccountVersion,
+
+    #[msg("Stake positions do not add up to the vault totals")]
+    CategoryBackfillMismatch,
+
+    #[msg("Stake would exceed the vault's maximum total stake")]
+    VaultAtCapacity,
+
+    #[msg("Stake would exceed the vault's per-staker cap")]
+    StakerCapExceeded,
+
+    #[msg("Invalid emergency grace period (must be 0 to 365 days)")]
+    InvalidEmergencyGracePeriod,
+
+    #[msg("Early-exit penalty exceeds the maximum")]
+    InvalidEarlyExitPenalty,
+
+    #[msg("Emergency withdrawal is not available for this vault")]
+    EmergencyExitUnavailable,
+
+    #[msg("Invalid normalization (must be at most 10000 bps)")]
+    InvalidNormalization,
+
+    #[msg("Invalid lock duration (lock-mode vaults only; from the vault's lock period up to 365 days)")]
+    InvalidLockDuration,
+
+    #[msg("Requested lock ends before the position's current lock")]
+    LockShorterThanRemaining,
+
+    #[msg("Invalid lock tiers (at most 4, ascending locks within 365 days, non-decreasing boosts of 1x to 3x)")]
+    InvalidLockTiers,
+
+    #[msg("Price feed is not a fully verified Pyth price update")]
+    InvalidPriceFeed,
+
+    #[msg("USD-normalized vault requires its price feed")]
+    MissingPriceFeed,
+
+    #[msg("Price feed is older than the vault's maximum price age")]
+    StalePrice,
+
+    #[msg("Price feed reports a zero or negative price")]
+    InvalidPrice,
+
+    #[msg("Invalid maximum price age (must be 0 to 1 day)")]
+    InvalidMaxPriceAge,
+
+    #[msg("Position was staked on the staker's behalf: top up with stake_tokens_for")]
+    DelegatedPosition,
+
+    #[msg("Only the funder or operator of a delegated position can top it up")]
+    UnauthorizedOperator,
+
+    #[msg("Token account is not the position's withdrawal address")]
+    WithdrawalAddressMismatch,
+
+    #[msg("Invalid protocol fee (must be at most 100 bps)")]
+    InvalidProtocolFee,
+
+    #[msg("Only the protocol admin can perform this action")]
+    UnauthorizedProtocolAdmin,
+
+    #[msg("Token account is not owned by the protocol treasury")]
+    InvalidTreasuryAccount,
+
+    #[msg("Invalid fee withdrawal amount (must be positive and at most the collected fees)")]
+    InvalidFeeWithdrawal,
+
+    #[msg("Target agent has no identity in identity_registry")]
+    AgentNotRegistered,
+
+    #[msg("Target agent's identity is deactivated")]
+    AgentInactive,
+
+    #[msg("Target agent has been slashed too many times")]
+    AgentSlashLimitExceeded,
+
+    #[msg("Invalid agent slash threshold (must be at least 1)")]
+    InvalidSlashThreshold,
+
+    #[msg("Stakers can still claim rewards; the reward account can be swept once the claim grace period ends")]
+    RewardClaimWindowOpen,
+
+    #[msg("Authority token account must be provided to receive the remaining rewards")]
+    MissingAuthorityTokenAccount,
 }
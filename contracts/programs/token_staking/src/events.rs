@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::state::StakeCategory;
+
+/// Emitted when the platform verifier marks a vault as verified
+#[event]
+pub struct VaultVerified {
+    pub vault: Pubkey,
+    pub target_agent: Pubkey,
+    pub token_mint: Pubkey,
+    pub verifier: Pubkey,
+    pub reason_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when the platform verifier withdraws a vault's verification
+#[event]
+pub struct VaultUnverified {
+    pub vault: Pubkey,
+    pub target_agent: Pubkey,
+    pub token_mint: Pubkey,
+    pub verifier: Pubkey,
+    pub reason_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when initialize_vault opens a vault for a target agent
+#[event]
+pub struct VaultInitialized {
+    pub vault: Pubkey,
+    pub vault_token_account: Pubkey,
+    pub endorsement_index: Pubkey,
+    pub target_agent: Pubkey,
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub min_stake_amount: u64,
+    pub lock_period_seconds: i64,
+    pub weight_multiplier: u16,
+    /// 0 in lock mode
+    pub unstake_cooldown_seconds: i64,
+    /// 0 = uncapped
+    pub max_total_staked: u64,
+    pub max_per_staker: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by stake_tokens and stake_tokens_for after the position is credited
+#[event]
+pub struct TokensStaked {
+    pub vault: Pubkey,
+    pub stake_position: Pubkey,
+    pub endorsement_index: Pubkey,
+    pub staker: Pubkey,
+    /// Wallet the tokens came from; the staker unless staked on their behalf
+    pub funder: Pubkey,
+    pub target_agent: Pubkey,
+    pub token_mint: Pubkey,
+    pub category: StakeCategory,
+    /// Credited to the position, net of the protocol fee
+    pub amount: u64,
+    pub protocol_fee: u64,
+    /// Whether this stake opened (or re-opened) the position
+    pub is_new_position: bool,
+    /// Position after the stake
+    pub position_amount: u64,
+    pub trust_weight: u64,
+    pub lock_boost_bps: u16,
+    pub locked_until: i64,
+    /// Vault and agent totals after the stake
+    pub vault_total_staked: u64,
+    pub vault_total_stakers: u32,
+    pub vault_trust_weight: u64,
+    pub agent_trust_weight: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when unstake_tokens pays out part or all of a position
+#[event]
+pub struct TokensUnstaked {
+    pub vault: Pubkey,
+    pub stake_position: Pubkey,
+    pub endorsement_index: Pubkey,
+    pub staker: Pubkey,
+    /// Token account the payout went to
+    pub destination: Pubkey,
+    pub target_agent: Pubkey,
+    pub token_mint: Pubkey,
+    pub category: StakeCategory,
+    /// Debited from the position; the payout is this less the protocol fee
+    pub amount: u64,
+    pub protocol_fee: u64,
+    /// Whether the position was closed
+    pub is_full_unstake: bool,
+    /// Position after the unstake
+    pub position_amount: u64,
+    pub trust_weight: u64,
+    /// Vault and agent totals after the unstake
+    pub vault_total_staked: u64,
+    pub vault_total_stakers: u32,
+    pub vault_trust_weight: u64,
+    pub agent_trust_weight: u64,
+    pub timestamp: i64,
+}
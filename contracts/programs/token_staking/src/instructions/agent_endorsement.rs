@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::state::AgentEndorsementIndex;
+
+#[derive(Accounts)]
+pub struct GetAgentEndorsement<'info> {
+    #[account(
+        seeds = [
+            AgentEndorsementIndex::SEED_PREFIX,
+            target_agent.key().as_ref()
+        ],
+        bump = endorsement_index.bump,
+    )]
+    pub endorsement_index: Account<'info, AgentEndorsementIndex>,
+
+    /// CHECK: The endorsed agent's pubkey (seed only)
+    pub target_agent: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<GetAgentEndorsement>) -> Result<AgentEndorsementIndex> {
+    Ok((*ctx.accounts.endorsement_index).clone())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{StakeCategory, StakingVault};
+use crate::error::TokenStakingError;
+
+/// Per-category stake totals of a vault returned to callers via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CategoryBreakdown {
+    pub vault: Pubkey,
+    pub target_agent: Pubkey,
+    pub total_staked: u64,
+    pub total_stakers: u32,
+    /// Indexed by StakeCategory::index (General, Quality, Reliability, Capability, Security)
+    pub category_staked: [u64; StakeCategory::COUNT],
+    pub category_stakers: [u32; StakeCategory::COUNT],
+}
+
+#[derive(Accounts)]
+pub struct GetCategoryBreakdown<'info> {
+    #[account(
+        seeds = [
+            StakingVault::SEED_PREFIX,
+            vault.target_agent.as_ref(),
+            vault.token_mint.as_ref()
+        ],
+        bump = vault.bump,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
+    )]
+    pub vault: Account<'info, StakingVault>,
+}
+
+pub fn handler(ctx: Context<GetCategoryBreakdown>) -> Result<CategoryBreakdown> {
+    let vault = &ctx.accounts.vault;
+
+    Ok(CategoryBreakdown {
+        vault: vault.key(),
+        target_agent: vault.target_agent,
+        total_staked: vault.total_staked,
+        total_stakers: vault.total_stakers,
+        category_staked: vault.category_staked,
+        category_stakers: vault.category_stakers,
+    })
+}
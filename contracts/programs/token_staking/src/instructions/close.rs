@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+
+use crate::state::{AgentEndorsementIndex, StakingVault, StakePosition};
+use crate::error::TokenStakingError;
+
+#[derive(Accounts)]
+pub struct CloseStakePosition<'info> {
+    /// The fully unstaked position; its rent returns to the staker
+    #[account(
+        mut,
+        close = staker,
+        seeds = [
+            StakePosition::SEED_PREFIX,
+            stake_position.vault.as_ref(),
+            staker.key().as_ref()
+        ],
+        bump = stake_position.bump,
+        constraint = !stake_position.is_active && stake_position.amount == 0
+            @ TokenStakingError::PositionStillActive,
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    /// CHECK: The position's vault; only checked for whether it still exists
+    #[account(address = stake_position.vault)]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub staker: Signer<'info>,
+}
+
+/// Close an inactive, empty stake position. Seeds come from the position
+/// itself so it can still be closed after its vault is gone. Accrued rewards
+/// must be claimed first, since closing would forfeit them, unless the vault
+/// has been closed and its unclaimed rewards swept.
+pub fn close_stake_position(ctx: Context<CloseStakePosition>) -> Result<()> {
+    let stake_position = &ctx.accounts.stake_position;
+
+    require!(
+        stake_position.rewards_accrued == 0 || ctx.accounts.vault.data_is_empty(),
+        TokenStakingError::UnclaimedRewards
+    );
+
+    msg!(
+        "Stake position of {} on vault {} closed",
+        stake_position.staker,
+        stake_position.vault
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseVault<'info> {
+    /// The staking vault; its rent returns to the authority
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            StakingVault::SEED_PREFIX,
+            vault.target_agent.as_ref(),
+            vault.token_mint.as_ref()
+        ],
+        bump = vault.bump,
+        has_one = authority @ TokenStakingError::UnauthorizedAuthority,
+        constraint = vault.total_staked == 0 && vault.total_stakers == 0
+            @ TokenStakingError::VaultNotEmpty,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
+    )]
+    pub vault: Account<'info, StakingVault>,
+
+    /// The vault's token account
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::VAULT_TOKEN_SEED,
+            vault.key().as_ref()
+        ],
+        bump = vault.vault_bump,
+        constraint = vault_token_account.amount == 0 @ TokenStakingError::VaultNotEmpty,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// The vault's reward token account; required once rewards have been funded
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::REWARD_TOKEN_SEED,
+            vault.key().as_ref()
+        ],
+        bump = vault.reward_bump,
+    )]
+    pub reward_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Authority's token account; receives rewards left in the reward account
+    #[account(
+        mut,
+        constraint = authority_token_account.mint == vault.token_mint @ TokenStakingError::InvalidTokenMint,
+        constraint = authority_token_account.owner == authority.key() @ TokenStakingError::InvalidTokenOwner,
+    )]
+    pub authority_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// The agent's cross-vault endorsement index
+    #[account(
+        mut,
+        seeds = [
+            AgentEndorsementIndex::SEED_PREFIX,
+            vault.target_agent.as_ref()
+        ],
+        bump = endorsement_index.bump,
+    )]
+    pub endorsement_index: Account<'info, AgentEndorsementIndex>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Close an empty vault along with its token accounts (authority only)
+///
+/// Rounding dust and rewards streamed while nothing was staked can never be
+/// claimed, so whatever is left in the reward account goes to the authority.
+/// That sweep waits REWARD_CLAIM_GRACE_PERIOD past the reward period so former
+/// stakers can still claim what they accrued.
+pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
+    let clock = Clock::get()?;
+    ctx.accounts.endorsement_index.remove_vault(&mut ctx.accounts.vault, clock.unix_timestamp);
+
+    let vault = &ctx.accounts.vault;
+
+    let has_rewards_account = vault.reward_token_account != Pubkey::default();
+    require!(
+        !has_rewards_account || ctx.accounts.reward_token_account.is_some(),
+        TokenStakingError::MissingRewardTokenAccount
+    );
+
+    let target_agent = vault.target_agent;
+    let token_mint = vault.token_mint;
+
+    let vault_seeds = &[
+        StakingVault::SEED_PREFIX,
+        target_agent.as_ref(),
+        token_mint.as_ref(),
+        &[vault.bump],
+    ];
+    let signer_seeds = &[&vault_seeds[..]];
+
+    let remaining_rewards = ctx
+        .accounts
+        .reward_token_account
+        .as_ref()
+        .map_or(0, |account| account.amount);
+    if remaining_rewards > 0 {
+        let claimable_until = vault
+            .reward_period_end
+            .checked_add(StakingVault::REWARD_CLAIM_GRACE_PERIOD)
+            .ok_or(TokenStakingError::ArithmeticOverflow)?;
+        require!(
+            clock.unix_timestamp >= claimable_until,
+            TokenStakingError::RewardClaimWindowOpen
+        );
+        let authority_token_account = ctx
+            .accounts
+            .authority_token_account
+            .as_ref()
+            .ok_or(TokenStakingError::MissingAuthorityTokenAccount)?;
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.reward_token_account.as_ref().unwrap().to_account_info(),
+                to: authority_token_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, remaining_rewards)?;
+
+        msg!("Swept {} unclaimed reward tokens to the authority", remaining_rewards);
+    }
+
+    let mut token_accounts = vec![ctx.accounts.vault_token_account.to_account_info()];
+    if let Some(reward_token_account) = &ctx.accounts.reward_token_account {
+        token_accounts.push(reward_token_account.to_account_info());
+    }
+
+    for account in token_accounts {
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account,
+                destination: ctx.accounts.authority.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::close_account(close_ctx)?;
+    }
+
+    msg!("Vault for agent {} closed by {}", target_agent, ctx.accounts.authority.key());
+
+    Ok(())
+}
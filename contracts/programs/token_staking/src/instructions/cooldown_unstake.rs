@@ -0,0 +1,232 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::{AgentEndorsementIndex, StakingVault, StakePosition};
+use crate::error::TokenStakingError;
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    /// The staking vault (must be in cooldown mode)
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::SEED_PREFIX,
+            vault.target_agent.as_ref(),
+            vault.token_mint.as_ref()
+        ],
+        bump = vault.bump,
+        constraint = vault.cooldown_mode @ TokenStakingError::LockModeVault,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
+    )]
+    pub vault: Account<'info, StakingVault>,
+
+    /// The stake position
+    #[account(
+        mut,
+        seeds = [
+            StakePosition::SEED_PREFIX,
+            vault.key().as_ref(),
+            staker.key().as_ref()
+        ],
+        bump = stake_position.bump,
+        constraint = stake_position.is_active @ TokenStakingError::StakeNotActive,
+        constraint = stake_position.staker == staker.key() @ TokenStakingError::UnauthorizedStaker,
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    /// The agent's cross-vault endorsement index
+    #[account(
+        mut,
+        seeds = [
+            AgentEndorsementIndex::SEED_PREFIX,
+            vault.target_agent.as_ref()
+        ],
+        bump = endorsement_index.bump,
+    )]
+    pub endorsement_index: Account<'info, AgentEndorsementIndex>,
+
+    /// The staker (must be original staker)
+    pub staker: Signer<'info>,
+}
+
+/// Start the unstake cooldown for part of a position.
+/// Requests stack; each one restarts the cooldown for the whole pending amount.
+pub fn request_unstake(ctx: Context<RequestUnstake>, amount: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let stake_position = &mut ctx.accounts.stake_position;
+    let clock = Clock::get()?;
+
+    // Validate amount
+    require!(
+        amount > 0 && amount <= stake_position.active_amount(),
+        TokenStakingError::InvalidUnstakeAmount
+    );
+
+    stake_position.pending_unstake_amount = stake_position.pending_unstake_amount
+        .checked_add(amount)
+        .ok_or(TokenStakingError::ArithmeticOverflow)?;
+    stake_position.unstake_available_at = clock.unix_timestamp
+        .checked_add(vault.unstake_cooldown_seconds)
+        .ok_or(TokenStakingError::ArithmeticOverflow)?;
+
+    // The pending amount stops counting toward trust weight straight away
+    let trust_weight = vault.position_trust_weight(stake_position, stake_position.active_amount());
+    vault.set_position_weight(stake_position, trust_weight);
+    ctx.accounts.endorsement_index.sync_vault(vault, clock.unix_timestamp);
+
+    msg!(
+        "Unstake of {} tokens requested from agent {}. Pending: {}, withdrawable at {}",
+        amount,
+        vault.target_agent,
+        stake_position.pending_unstake_amount,
+        stake_position.unstake_available_at
+    );
+    msg!("New trust weight: {}", stake_position.trust_weight);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawUnstaked<'info> {
+    /// The staking vault (must be in cooldown mode)
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::SEED_PREFIX,
+            vault.target_agent.as_ref(),
+            vault.token_mint.as_ref()
+        ],
+        bump = vault.bump,
+        constraint = vault.cooldown_mode @ TokenStakingError::LockModeVault,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
+    )]
+    pub vault: Account<'info, StakingVault>,
+
+    /// The vault's token account
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::VAULT_TOKEN_SEED,
+            vault.key().as_ref()
+        ],
+        bump = vault.vault_bump,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// The stake position
+    #[account(
+        mut,
+        seeds = [
+            StakePosition::SEED_PREFIX,
+            vault.key().as_ref(),
+            staker.key().as_ref()
+        ],
+        bump = stake_position.bump,
+        constraint = stake_position.is_active @ TokenStakingError::StakeNotActive,
+        constraint = stake_position.staker == staker.key() @ TokenStakingError::UnauthorizedStaker,
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    /// Token account to receive tokens (the recorded withdrawal address of a delegated position)
+    #[account(
+        mut,
+        constraint = staker_token_account.mint == vault.token_mint @ TokenStakingError::InvalidTokenMint,
+        constraint = stake_position.is_withdrawal_account(&staker_token_account.key(), &staker_token_account.owner)
+            @ TokenStakingError::InvalidTokenOwner,
+    )]
+    pub staker_token_account: Account<'info, TokenAccount>,
+
+    /// The agent's cross-vault endorsement index
+    #[account(
+        mut,
+        seeds = [
+            AgentEndorsementIndex::SEED_PREFIX,
+            vault.target_agent.as_ref()
+        ],
+        bump = endorsement_index.bump,
+    )]
+    pub endorsement_index: Account<'info, AgentEndorsementIndex>,
+
+    /// The staker (must be original staker)
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Withdraw the pending unstake once its cooldown has elapsed
+pub fn withdraw_unstaked(ctx: Context<WithdrawUnstaked>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Settle rewards earned on the current amount before it changes
+    ctx.accounts.vault.settle_rewards(&mut ctx.accounts.stake_position, clock.unix_timestamp)?;
+
+    let vault = &ctx.accounts.vault;
+    let stake_position = &mut ctx.accounts.stake_position;
+
+    require!(
+        stake_position.pending_unstake_amount > 0,
+        TokenStakingError::NoPendingUnstake
+    );
+    require!(
+        stake_position.can_withdraw_unstaked(clock.unix_timestamp),
+        TokenStakingError::StakeLocked
+    );
+
+    // Slashing may have reduced the position below the requested amount
+    let amount = stake_position.pending_unstake_amount.min(stake_position.amount);
+
+    // Transfer tokens from vault to staker using PDA signing
+    let target_agent = vault.target_agent;
+    let token_mint = vault.token_mint;
+
+    let vault_seeds = &[
+        StakingVault::SEED_PREFIX,
+        target_agent.as_ref(),
+        token_mint.as_ref(),
+        &[vault.bump],
+    ];
+    let signer_seeds = &[&vault_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            to: ctx.accounts.staker_token_account.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    // Update stake position
+    stake_position.amount = stake_position.amount
+        .checked_sub(amount)
+        .ok_or(TokenStakingError::ArithmeticOverflow)?;
+    stake_position.pending_unstake_amount = 0;
+    stake_position.unstake_available_at = 0;
+
+    let vault = &mut ctx.accounts.vault;
+    if stake_position.amount == 0 {
+        stake_position.is_active = false;
+        stake_position.unstaked_at = clock.unix_timestamp;
+        vault.set_position_weight(stake_position, 0);
+        vault.total_stakers = vault.total_stakers.saturating_sub(1);
+    }
+
+    // Update vault totals
+    vault.debit_category(stake_position.category, amount, stake_position.amount == 0)?;
+    vault.total_staked = vault.total_staked
+        .checked_sub(amount)
+        .ok_or(TokenStakingError::ArithmeticOverflow)?;
+    vault.updated_at = clock.unix_timestamp;
+    ctx.accounts.endorsement_index.sync_vault(vault, clock.unix_timestamp);
+
+    msg!(
+        "Withdrew {} unstaked tokens from agent {}",
+        amount,
+        vault.target_agent
+    );
+
+    Ok(())
+}
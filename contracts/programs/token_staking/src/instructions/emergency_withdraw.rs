@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::{AgentEndorsementIndex, StakingVault, StakePosition};
+use crate::error::TokenStakingError;
+
+#[derive(Accounts)]
+pub struct EmergencyWithdraw<'info> {
+    /// The staking vault
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::SEED_PREFIX,
+            vault.target_agent.as_ref(),
+            vault.token_mint.as_ref()
+        ],
+        bump = vault.bump,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
+    )]
+    pub vault: Account<'info, StakingVault>,
+
+    /// The vault's token account
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::VAULT_TOKEN_SEED,
+            vault.key().as_ref()
+        ],
+        bump = vault.vault_bump,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// The stake position
+    #[account(
+        mut,
+        seeds = [
+            StakePosition::SEED_PREFIX,
+            vault.key().as_ref(),
+            staker.key().as_ref()
+        ],
+        bump = stake_position.bump,
+        constraint = stake_position.is_active @ TokenStakingError::StakeNotActive,
+        constraint = stake_position.staker == staker.key() @ TokenStakingError::UnauthorizedStaker,
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    /// Token account to receive tokens (the recorded withdrawal address of a delegated position)
+    #[account(
+        mut,
+        constraint = staker_token_account.mint == vault.token_mint @ TokenStakingError::InvalidTokenMint,
+        constraint = stake_position.is_withdrawal_account(&staker_token_account.key(), &staker_token_account.owner)
+            @ TokenStakingError::InvalidTokenOwner,
+    )]
+    pub staker_token_account: Account<'info, TokenAccount>,
+
+    /// Penalty destination; required when the vault charges an early-exit penalty
+    #[account(
+        mut,
+        address = vault.penalty_destination @ TokenStakingError::InvalidPenaltyDestination,
+    )]
+    pub penalty_destination: Option<Account<'info, TokenAccount>>,
+
+    /// The agent's cross-vault endorsement index
+    #[account(
+        mut,
+        seeds = [
+            AgentEndorsementIndex::SEED_PREFIX,
+            vault.target_agent.as_ref()
+        ],
+        bump = endorsement_index.bump,
+    )]
+    pub endorsement_index: Account<'info, AgentEndorsementIndex>,
+
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Withdraw a whole position ignoring its lock or cooldown, once the vault has
+/// been paused past its grace period or the authority has enabled early exit.
+/// The vault's early-exit penalty goes to the penalty destination; accrued
+/// rewards stay claimable.
+pub fn handler(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Settle rewards earned on the current amount before it changes
+    ctx.accounts.vault.settle_rewards(&mut ctx.accounts.stake_position, clock.unix_timestamp)?;
+
+    let vault = &ctx.accounts.vault;
+    let stake_position = &mut ctx.accounts.stake_position;
+
+    require!(
+        vault.emergency_exit_open(clock.unix_timestamp),
+        TokenStakingError::EmergencyExitUnavailable
+    );
+
+    let amount = stake_position.amount;
+    let penalty = StakingVault::calculate_slash_amount(amount, vault.early_exit_penalty_bps);
+    let payout = amount - penalty;
+
+    // Transfer tokens out of the vault using PDA signing
+    let target_agent = vault.target_agent;
+    let token_mint = vault.token_mint;
+
+    let vault_seeds = &[
+        StakingVault::SEED_PREFIX,
+        target_agent.as_ref(),
+        token_mint.as_ref(),
+        &[vault.bump],
+    ];
+    let signer_seeds = &[&vault_seeds[..]];
+
+    if penalty > 0 {
+        let penalty_destination = ctx.accounts.penalty_destination
+            .as_ref()
+            .ok_or(TokenStakingError::InvalidPenaltyDestination)?;
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_token_account.to_account_info(),
+                to: penalty_destination.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, penalty)?;
+    }
+
+    if payout > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_token_account.to_account_info(),
+                to: ctx.accounts.staker_token_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, payout)?;
+    }
+
+    // Close out the position
+    stake_position.amount = 0;
+    stake_position.is_active = false;
+    stake_position.unstaked_at = clock.unix_timestamp;
+    stake_position.pending_unstake_amount = 0;
+    stake_position.unstake_available_at = 0;
+
+    // Update vault totals
+    let vault = &mut ctx.accounts.vault;
+    vault.set_position_weight(stake_position, 0);
+    vault.debit_category(stake_position.category, amount, true)?;
+    vault.total_stakers = vault.total_stakers.saturating_sub(1);
+    vault.total_staked = vault.total_staked
+        .checked_sub(amount)
+        .ok_or(TokenStakingError::ArithmeticOverflow)?;
+    vault.updated_at = clock.unix_timestamp;
+    ctx.accounts.endorsement_index.sync_vault(vault, clock.unix_timestamp);
+
+    msg!(
+        "Emergency withdrawal of {} tokens from agent {} ({} penalty)",
+        payout,
+        vault.target_agent,
+        penalty
+    );
+
+    Ok(())
+}
@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
-};
+use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use crate::state::StakingVault;
+use crate::state::{
+    AgentEndorsementIndex, AgentStanding, LockTier, ProtocolConfig, StakeCategory, StakingVault,
+    IDENTITY_AGENT_SEED, IDENTITY_REGISTRY_PROGRAM_ID,
+};
 use crate::error::TokenStakingError;
+use crate::events::VaultInitialized;
 
 #[derive(Accounts)]
 pub struct InitializeVault<'info> {
@@ -41,9 +42,39 @@ pub struct InitializeVault<'info> {
     pub token_mint: Account<'info, Mint>,
 
     /// The target agent who will receive endorsements
-    /// CHECK: This is just the agent's pubkey, no validation needed
+    /// CHECK: Only its pubkey is used; agent_identity proves it is a registered agent
     pub target_agent: UncheckedAccount<'info>,
 
+    /// The target agent's identity; it must be active and under the slash threshold
+    /// CHECK: PDA derivation under identity_registry is enforced; contents are read via AgentStanding::load
+    #[account(
+        seeds = [IDENTITY_AGENT_SEED, target_agent.key().as_ref()],
+        bump,
+        seeds::program = IDENTITY_REGISTRY_PROGRAM_ID,
+    )]
+    pub agent_identity: UncheckedAccount<'info>,
+
+    /// Protocol settings holding the agent slash threshold; may be uninitialized
+    /// CHECK: PDA derivation is enforced; contents are read via ProtocolConfig::load
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump,
+    )]
+    pub protocol_config: UncheckedAccount<'info>,
+
+    /// The agent's cross-vault endorsement index (created with its first vault)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AgentEndorsementIndex::LEN,
+        seeds = [
+            AgentEndorsementIndex::SEED_PREFIX,
+            target_agent.key().as_ref()
+        ],
+        bump
+    )]
+    pub endorsement_index: Account<'info, AgentEndorsementIndex>,
+
     /// Authority creating the vault (must be target agent or authorized)
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -59,6 +90,9 @@ pub fn handler(
     min_stake_amount: u64,
     lock_period_seconds: i64,
     weight_multiplier: u16,
+    unstake_cooldown_seconds: i64,
+    max_total_staked: u64,
+    max_per_staker: u64,
 ) -> Result<()> {
     // Validate lock period
     require!(
@@ -68,10 +102,21 @@ pub fn handler(
 
     // Validate weight multiplier (10 = 0.1x, 100 = 1x, 1000 = 10x)
     require!(
-        weight_multiplier >= 10 && weight_multiplier <= 1000,
+        (10..=1000).contains(&weight_multiplier),
         TokenStakingError::InvalidWeightMultiplier
     );
 
+    // Validate unstake cooldown (0 = lock mode)
+    require!(
+        (0..=StakingVault::MAX_LOCK_PERIOD).contains(&unstake_cooldown_seconds),
+        TokenStakingError::InvalidUnstakeCooldown
+    );
+
+    // Vaults can only be created for registered agents in good standing
+    let max_slash_count = ProtocolConfig::max_agent_slash_count(&ctx.accounts.protocol_config)?;
+    AgentStanding::load(&ctx.accounts.agent_identity, &ctx.accounts.target_agent.key())?
+        .require_good_standing(max_slash_count)?;
+
     let vault = &mut ctx.accounts.vault;
     let clock = Clock::get()?;
 
@@ -90,6 +135,64 @@ pub fn handler(
     vault.updated_at = clock.unix_timestamp;
     vault.bump = ctx.bumps.vault;
     vault.vault_bump = ctx.bumps.vault_token_account;
+    vault.slash_authority = Pubkey::default();
+    vault.penalty_destination = Pubkey::default();
+    vault.total_slashed = 0;
+    vault.reward_token_account = Pubkey::default();
+    vault.reward_rate = 0;
+    vault.reward_period_end = 0;
+    vault.reward_updated_at = clock.unix_timestamp;
+    vault.reward_per_token_stored = 0;
+    vault.total_rewards_funded = 0;
+    vault.reward_bump = 0;
+    vault.verification_reason_hash = [0; 32];
+    vault.verification_updated_at = 0;
+    vault.cooldown_mode = unstake_cooldown_seconds > 0;
+    vault.unstake_cooldown_seconds = unstake_cooldown_seconds;
+    vault.category_staked = [0; StakeCategory::COUNT];
+    vault.category_stakers = [0; StakeCategory::COUNT];
+    vault.max_total_staked = max_total_staked;
+    vault.max_per_staker = max_per_staker;
+    vault.paused_at = 0;
+    vault.emergency_grace_period = 0;
+    vault.early_exit_enabled = false;
+    vault.early_exit_penalty_bps = 0;
+    vault.total_trust_weight = 0;
+    vault.normalization_bps = StakingVault::NORMALIZATION_ONE;
+    vault.is_indexed = false;
+    vault.indexed_trust_weight = 0;
+    vault.indexed_stakers = 0;
+    vault.lock_tiers = [LockTier::default(); StakingVault::MAX_LOCK_TIERS];
+    vault.lock_tier_count = 0;
+    vault.price_feed = Pubkey::default();
+    vault.usd_normalized = false;
+    vault.max_price_age = 0;
+    vault.price_fallback_to_raw = false;
+    vault.token_decimals = ctx.accounts.token_mint.decimals;
+    vault.last_price = 0;
+    vault.last_price_exponent = 0;
+    vault.last_price_published_at = 0;
+    vault.version = StakingVault::CURRENT_VERSION;
+
+    let endorsement_index = &mut ctx.accounts.endorsement_index;
+    endorsement_index.ensure_initialized(vault.target_agent, ctx.bumps.endorsement_index);
+    endorsement_index.sync_vault(vault, clock.unix_timestamp);
+
+    emit!(VaultInitialized {
+        vault: vault.key(),
+        vault_token_account: vault.vault_token_account,
+        endorsement_index: endorsement_index.key(),
+        target_agent: vault.target_agent,
+        token_mint: vault.token_mint,
+        authority: vault.authority,
+        min_stake_amount: vault.min_stake_amount,
+        lock_period_seconds,
+        weight_multiplier,
+        unstake_cooldown_seconds,
+        max_total_staked,
+        max_per_staker,
+        timestamp: clock.unix_timestamp,
+    });
 
     msg!(
         "Initialized staking vault for agent {} with token {}",
@@ -97,10 +200,11 @@ pub fn handler(
         vault.token_mint
     );
     msg!(
-        "Min stake: {}, Lock period: {}s, Weight multiplier: {}",
+        "Min stake: {}, Lock period: {}s, Weight multiplier: {}, Unstake cooldown: {}s",
         min_stake_amount,
         lock_period_seconds,
-        weight_multiplier
+        weight_multiplier,
+        unstake_cooldown_seconds
     );
 
     Ok(())
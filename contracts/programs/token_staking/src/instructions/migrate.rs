@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+use crate::state::{StakeCategory, StakePosition, StakingVault};
+use crate::error::TokenStakingError;
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Possibly legacy-sized vault; owner, discriminator and PDA are checked in the handler
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade a vault of any earlier layout to CURRENT_VERSION (permissionless).
+///
+/// A vault created before the per-category buckets is backfilled: the
+/// appended stake caps start at 0 (unlimited), the lock tier table starts
+/// empty (no boosts), USD normalization starts disabled, and a vault that is
+/// already paused starts its emergency grace period now. The vault joins its
+/// agent's endorsement index on the next stake. Every active position of such
+/// a vault must be passed as a remaining account, sorted by address; the
+/// backfilled buckets must add up to the vault totals. Later layouts only
+/// need the version bump and take no remaining accounts.
+pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+    let account = ctx.accounts.vault.to_account_info();
+    require_keys_eq!(*account.owner, crate::ID, TokenStakingError::InvalidMigrationTarget);
+    let old_len = account.data_len();
+    {
+        let data = account.try_borrow_data()?;
+        require!(
+            old_len >= StakingVault::LEGACY_LEN && data[..8] == StakingVault::DISCRIMINATOR,
+            TokenStakingError::InvalidMigrationTarget
+        );
+    }
+
+    if old_len < StakingVault::LEN {
+        // Grow the account, topping up rent from the payer; new bytes are zeroed
+        let required = Rent::get()?.minimum_balance(StakingVault::LEN);
+        let shortfall = required.saturating_sub(account.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: account.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        account.realloc(StakingVault::LEN, false)?;
+    }
+
+    let mut vault = StakingVault::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    let expected = Pubkey::create_program_address(
+        &[
+            StakingVault::SEED_PREFIX,
+            vault.target_agent.as_ref(),
+            vault.token_mint.as_ref(),
+            &[vault.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| TokenStakingError::InvalidMigrationTarget)?;
+    require_keys_eq!(account.key(), expected, TokenStakingError::InvalidMigrationTarget);
+
+    if vault.version >= StakingVault::CURRENT_VERSION {
+        msg!("Vault already at version {}", vault.version);
+        return Ok(());
+    }
+
+    if old_len < StakingVault::PRE_VERSION_LEN {
+        backfill_categories(&mut vault, &account, ctx.remaining_accounts)?;
+        vault.normalization_bps = StakingVault::NORMALIZATION_ONE;
+        if !vault.is_active {
+            vault.paused_at = Clock::get()?.unix_timestamp;
+        }
+    }
+    vault.version = StakingVault::CURRENT_VERSION;
+    vault.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    msg!(
+        "Vault for agent {} migrated to version {} with {} stakers across {} categories",
+        vault.target_agent,
+        vault.version,
+        vault.total_stakers,
+        StakeCategory::COUNT
+    );
+
+    Ok(())
+}
+
+/// Rebuild the per-category buckets and total trust weight from the vault's active positions
+fn backfill_categories(
+    vault: &mut StakingVault,
+    account: &AccountInfo,
+    positions: &[AccountInfo],
+) -> Result<()> {
+    let mut previous: Option<Pubkey> = None;
+    for info in positions {
+        require!(
+            previous.is_none_or(|key| key < info.key()),
+            TokenStakingError::CategoryBackfillMismatch
+        );
+        previous = Some(info.key());
+
+        require_keys_eq!(*info.owner, crate::ID, TokenStakingError::InvalidMigrationTarget);
+        let position = StakePosition::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require_keys_eq!(position.vault, account.key(), TokenStakingError::InvalidMigrationTarget);
+        if position.is_active {
+            vault.credit_category(position.category, position.amount, true)?;
+            vault.total_trust_weight = vault.total_trust_weight.saturating_add(position.trust_weight);
+        }
+    }
+
+    let staked: u64 = vault.category_staked.iter().sum();
+    let stakers: u32 = vault.category_stakers.iter().sum();
+    require!(
+        staked == vault.total_staked && stakers == vault.total_stakers,
+        TokenStakingError::CategoryBackfillMismatch
+    );
+    Ok(())
+}
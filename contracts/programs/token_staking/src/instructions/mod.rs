@@ -1,9 +1,34 @@
+// The glob exports clash on `handler`, which lib.rs always calls qualified
+#![allow(ambiguous_glob_reexports)]
+
+pub mod agent_endorsement;
+pub mod category_breakdown;
+pub mod close;
+pub mod cooldown_unstake;
+pub mod emergency_withdraw;
 pub mod initialize_vault;
+pub mod migrate;
+pub mod protocol_fees;
+pub mod rewards;
+pub mod slash_stake;
 pub mod stake_tokens;
+pub mod stake_tokens_for;
 pub mod unstake_tokens;
 pub mod update_vault;
+pub mod verification;
 
+pub use agent_endorsement::*;
+pub use category_breakdown::*;
+pub use close::*;
+pub use cooldown_unstake::*;
+pub use emergency_withdraw::*;
 pub use initialize_vault::*;
+pub use migrate::*;
+pub use protocol_fees::*;
+pub use rewards::*;
+pub use slash_stake::*;
 pub use stake_tokens::*;
+pub use stake_tokens_for::*;
 pub use unstake_tokens::*;
 pub use update_vault::*;
+pub use verification::*;
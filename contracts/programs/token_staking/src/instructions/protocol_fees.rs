@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::state::ProtocolConfig;
+use crate::error::TokenStakingError;
+
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ProtocolConfig::LEN,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// The protocol admin (typically deployer)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the protocol fee config
+pub fn initialize_protocol_config(
+    ctx: Context<InitializeProtocolConfig>,
+    treasury: Pubkey,
+    protocol_fee_bps: u16,
+    charge_unstake_fee: bool,
+) -> Result<()> {
+    require!(
+        protocol_fee_bps <= ProtocolConfig::MAX_PROTOCOL_FEE_BPS,
+        TokenStakingError::InvalidProtocolFee
+    );
+
+    let config = &mut ctx.accounts.protocol_config;
+    config.admin = ctx.accounts.admin.key();
+    config.treasury = treasury;
+    config.protocol_fee_bps = protocol_fee_bps;
+    config.charge_unstake_fee = charge_unstake_fee;
+    config.max_agent_slash_count = ProtocolConfig::DEFAULT_MAX_AGENT_SLASH_COUNT;
+    config.bump = ctx.bumps.protocol_config;
+
+    msg!(
+        "Protocol config initialized: fee {} bps (unstake fee {}), treasury {}",
+        protocol_fee_bps,
+        charge_unstake_fee,
+        treasury
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+        has_one = admin @ TokenStakingError::UnauthorizedProtocolAdmin,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Change the treasury, fee rate, unstake fee flag or agent slash threshold
+pub fn update_protocol_config(
+    ctx: Context<UpdateProtocolConfig>,
+    treasury: Option<Pubkey>,
+    protocol_fee_bps: Option<u16>,
+    charge_unstake_fee: Option<bool>,
+    max_agent_slash_count: Option<u32>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.protocol_config;
+
+    if let Some(treasury) = treasury {
+        config.treasury = treasury;
+        msg!("Updated protocol treasury to {}", treasury);
+    }
+
+    if let Some(fee_bps) = protocol_fee_bps {
+        require!(
+            fee_bps <= ProtocolConfig::MAX_PROTOCOL_FEE_BPS,
+            TokenStakingError::InvalidProtocolFee
+        );
+        config.protocol_fee_bps = fee_bps;
+        msg!("Updated protocol fee to {} bps", fee_bps);
+    }
+
+    if let Some(charge) = charge_unstake_fee {
+        config.charge_unstake_fee = charge;
+        msg!("Updated unstake fee to {}", charge);
+    }
+
+    if let Some(max_slash_count) = max_agent_slash_count {
+        require!(max_slash_count > 0, TokenStakingError::InvalidSlashThreshold);
+        config.max_agent_slash_count = max_slash_count;
+        msg!("Updated agent slash threshold to {}", max_slash_count);
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+        has_one = admin @ TokenStakingError::UnauthorizedProtocolAdmin,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// The protocol's fee account for this mint
+    #[account(
+        mut,
+        seeds = [
+            ProtocolConfig::FEE_ACCOUNT_SEED,
+            token_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub fee_token_account: Account<'info, TokenAccount>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    /// Treasury token account receiving the fees
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == token_mint.key() @ TokenStakingError::InvalidTokenMint,
+        constraint = treasury_token_account.owner == protocol_config.treasury @ TokenStakingError::InvalidTreasuryAccount,
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Sweep collected fees of one mint to the treasury (the whole balance when `amount` is None)
+pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>, amount: Option<u64>) -> Result<()> {
+    let balance = ctx.accounts.fee_token_account.amount;
+    let amount = amount.unwrap_or(balance);
+    require!(
+        amount > 0 && amount <= balance,
+        TokenStakingError::InvalidFeeWithdrawal
+    );
+
+    let config_seeds = &[ProtocolConfig::SEED_PREFIX, &[ctx.accounts.protocol_config.bump]];
+    let signer_seeds = &[&config_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.fee_token_account.to_account_info(),
+            to: ctx.accounts.treasury_token_account.to_account_info(),
+            authority: ctx.accounts.protocol_config.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    msg!(
+        "Swept {} protocol fee tokens of mint {} to the treasury",
+        amount,
+        ctx.accounts.token_mint.key()
+    );
+    Ok(())
+}
+
+/// Move a protocol fee from `from` to the protocol's fee account (no-op for a zero fee)
+pub(crate) fn collect_fee<'info>(
+    token_program: &Program<'info, Token>,
+    from: &Account<'info, TokenAccount>,
+    fee_token_account: &Account<'info, TokenAccount>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    fee: u64,
+) -> Result<()> {
+    if fee == 0 {
+        return Ok(());
+    }
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: from.to_account_info(),
+            to: fee_token_account.to_account_info(),
+            authority,
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, fee)
+}
@@ -0,0 +1,224 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::state::{StakingVault, StakePosition};
+use crate::error::TokenStakingError;
+
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    /// The staking vault
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::SEED_PREFIX,
+            vault.target_agent.as_ref(),
+            vault.token_mint.as_ref()
+        ],
+        bump = vault.bump,
+        has_one = authority @ TokenStakingError::UnauthorizedAuthority,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
+    )]
+    pub vault: Account<'info, StakingVault>,
+
+    /// The vault's reward token account (PDA-controlled, created on first funding)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [
+            StakingVault::REWARD_TOKEN_SEED,
+            vault.key().as_ref()
+        ],
+        bump,
+        token::mint = token_mint,
+        token::authority = vault,
+    )]
+    pub reward_token_account: Account<'info, TokenAccount>,
+
+    /// The vault's token mint, which rewards are paid in
+    #[account(address = vault.token_mint @ TokenStakingError::InvalidTokenMint)]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Authority's token account funding the rewards
+    #[account(
+        mut,
+        constraint = funder_token_account.mint == vault.token_mint @ TokenStakingError::InvalidTokenMint,
+        constraint = funder_token_account.owner == authority.key() @ TokenStakingError::InvalidTokenOwner,
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    /// The vault authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Deposit reward tokens to be streamed to stakers over `period_seconds`
+///
+/// Rewards still undistributed from the current period are rolled into the new one.
+pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64, period_seconds: i64) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    require!(amount > 0, TokenStakingError::InvalidRewardAmount);
+    require!(
+        period_seconds > 0 && period_seconds <= StakingVault::MAX_REWARD_PERIOD,
+        TokenStakingError::InvalidRewardPeriod
+    );
+
+    // Transfer reward tokens from the authority to the reward account
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.funder_token_account.to_account_info(),
+            to: ctx.accounts.reward_token_account.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.update_reward_per_token(now)?;
+
+    // Carry over whatever the current period has not distributed yet
+    let leftover = if now < vault.reward_period_end {
+        ((vault.reward_period_end - now) as u128)
+            .checked_mul(vault.reward_rate)
+            .ok_or(TokenStakingError::ArithmeticOverflow)?
+    } else {
+        0
+    };
+    let funded = (amount as u128)
+        .checked_mul(StakingVault::REWARD_PRECISION)
+        .ok_or(TokenStakingError::ArithmeticOverflow)?;
+
+    vault.reward_rate = funded
+        .checked_add(leftover)
+        .ok_or(TokenStakingError::ArithmeticOverflow)?
+        / period_seconds as u128;
+    vault.reward_period_end = now
+        .checked_add(period_seconds)
+        .ok_or(TokenStakingError::ArithmeticOverflow)?;
+    vault.reward_token_account = ctx.accounts.reward_token_account.key();
+    vault.reward_bump = ctx.bumps.reward_token_account;
+    vault.total_rewards_funded = vault.total_rewards_funded
+        .checked_add(amount)
+        .ok_or(TokenStakingError::ArithmeticOverflow)?;
+    vault.updated_at = now;
+
+    msg!(
+        "Funded {} reward tokens for agent {} over {}s (ends {})",
+        amount,
+        vault.target_agent,
+        period_seconds,
+        vault.reward_period_end
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    /// The staking vault
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::SEED_PREFIX,
+            vault.target_agent.as_ref(),
+            vault.token_mint.as_ref()
+        ],
+        bump = vault.bump,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
+    )]
+    pub vault: Account<'info, StakingVault>,
+
+    /// The vault's reward token account
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::REWARD_TOKEN_SEED,
+            vault.key().as_ref()
+        ],
+        bump = vault.reward_bump,
+    )]
+    pub reward_token_account: Account<'info, TokenAccount>,
+
+    /// The stake position whose rewards are claimed
+    #[account(
+        mut,
+        seeds = [
+            StakePosition::SEED_PREFIX,
+            vault.key().as_ref(),
+            staker.key().as_ref()
+        ],
+        bump = stake_position.bump,
+        constraint = stake_position.staker == staker.key() @ TokenStakingError::UnauthorizedStaker,
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    /// Staker's token account to receive rewards
+    #[account(
+        mut,
+        constraint = staker_token_account.mint == vault.token_mint @ TokenStakingError::InvalidTokenMint,
+        constraint = staker_token_account.owner == staker.key() @ TokenStakingError::InvalidTokenOwner,
+    )]
+    pub staker_token_account: Account<'info, TokenAccount>,
+
+    /// The staker
+    pub staker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pay out all rewards a stake position has accrued
+pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    ctx.accounts.vault.settle_rewards(&mut ctx.accounts.stake_position, clock.unix_timestamp)?;
+
+    let vault = &ctx.accounts.vault;
+    let stake_position = &mut ctx.accounts.stake_position;
+    let reward = stake_position.rewards_accrued;
+
+    if reward == 0 {
+        msg!("No rewards to claim");
+        return Ok(());
+    }
+
+    // Transfer rewards using PDA signing
+    let target_agent = vault.target_agent;
+    let token_mint = vault.token_mint;
+
+    let vault_seeds = &[
+        StakingVault::SEED_PREFIX,
+        target_agent.as_ref(),
+        token_mint.as_ref(),
+        &[vault.bump],
+    ];
+    let signer_seeds = &[&vault_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.reward_token_account.to_account_info(),
+            to: ctx.accounts.staker_token_account.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, reward)?;
+
+    stake_position.rewards_accrued = 0;
+    stake_position.rewards_claimed = stake_position.rewards_claimed
+        .checked_add(reward)
+        .ok_or(TokenStakingError::ArithmeticOverflow)?;
+
+    msg!(
+        "Claimed {} reward tokens from agent {}",
+        reward,
+        vault.target_agent
+    );
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::{AgentEndorsementIndex, StakingVault, StakePosition};
+use crate::error::TokenStakingError;
+
+#[derive(Accounts)]
+pub struct SlashStakePosition<'info> {
+    /// The staking vault
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::SEED_PREFIX,
+            vault.target_agent.as_ref(),
+            vault.token_mint.as_ref()
+        ],
+        bump = vault.bump,
+        constraint = vault.slashing_enabled() @ TokenStakingError::SlashingNotConfigured,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
+    )]
+    pub vault: Account<'info, StakingVault>,
+
+    /// The vault's token account
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::VAULT_TOKEN_SEED,
+            vault.key().as_ref()
+        ],
+        bump = vault.vault_bump,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// The stake position being slashed
+    #[account(
+        mut,
+        seeds = [
+            StakePosition::SEED_PREFIX,
+            vault.key().as_ref(),
+            stake_position.staker.as_ref()
+        ],
+        bump = stake_position.bump,
+        constraint = stake_position.is_active @ TokenStakingError::StakeNotActive,
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    /// Token account configured to receive slashed tokens
+    #[account(
+        mut,
+        address = vault.penalty_destination @ TokenStakingError::InvalidPenaltyDestination,
+    )]
+    pub penalty_destination: Account<'info, TokenAccount>,
+
+    /// The agent's cross-vault endorsement index
+    #[account(
+        mut,
+        seeds = [
+            AgentEndorsementIndex::SEED_PREFIX,
+            vault.target_agent.as_ref()
+        ],
+        bump = endorsement_index.bump,
+    )]
+    pub endorsement_index: Account<'info, AgentEndorsementIndex>,
+
+    /// The vault's slash authority
+    #[account(
+        constraint = slash_authority.key() == vault.slash_authority @ TokenStakingError::UnauthorizedSlashAuthority,
+    )]
+    pub slash_authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Slash `severity_bps` of a stake position to the vault's penalty destination
+pub fn handler(ctx: Context<SlashStakePosition>, severity_bps: u16) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Settle rewards earned on the current amount before it changes
+    ctx.accounts.vault.settle_rewards(&mut ctx.accounts.stake_position, clock.unix_timestamp)?;
+
+    let vault = &ctx.accounts.vault;
+    let stake_position = &mut ctx.accounts.stake_position;
+
+    require!(
+        severity_bps > 0 && severity_bps <= StakingVault::MAX_SLASH_BPS,
+        TokenStakingError::InvalidSlashSeverity
+    );
+
+    let slash_amount = StakingVault::calculate_slash_amount(stake_position.amount, severity_bps);
+
+    // Move the slashed tokens out of the vault using PDA signing
+    if slash_amount > 0 {
+        let target_agent = vault.target_agent;
+        let token_mint = vault.token_mint;
+
+        let vault_seeds = &[
+            StakingVault::SEED_PREFIX,
+            target_agent.as_ref(),
+            token_mint.as_ref(),
+            &[vault.bump],
+        ];
+        let signer_seeds = &[&vault_seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_token_account.to_account_info(),
+                to: ctx.accounts.penalty_destination.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, slash_amount)?;
+    }
+
+    // Update stake position
+    stake_position.amount = stake_position.amount
+        .checked_sub(slash_amount)
+        .ok_or(TokenStakingError::ArithmeticOverflow)?;
+    stake_position.is_slashed = true;
+    // Pending unstakes are still collateral; the slash reaches them too
+    stake_position.pending_unstake_amount = stake_position.pending_unstake_amount
+        .min(stake_position.amount);
+
+    let vault = &mut ctx.accounts.vault;
+    if stake_position.amount == 0 {
+        // Nothing left to unstake - close out the position
+        stake_position.is_active = false;
+        stake_position.unstaked_at = clock.unix_timestamp;
+        stake_position.unstake_available_at = 0;
+        vault.set_position_weight(stake_position, 0);
+        vault.total_stakers = vault.total_stakers.saturating_sub(1);
+    } else {
+        let trust_weight = vault.position_trust_weight(stake_position, stake_position.active_amount());
+        vault.set_position_weight(stake_position, trust_weight);
+    }
+
+    // Update vault totals
+    vault.debit_category(stake_position.category, slash_amount, stake_position.amount == 0)?;
+    vault.total_staked = vault.total_staked
+        .checked_sub(slash_amount)
+        .ok_or(TokenStakingError::ArithmeticOverflow)?;
+    vault.total_slashed = vault.total_slashed
+        .checked_add(slash_amount)
+        .ok_or(TokenStakingError::ArithmeticOverflow)?;
+    vault.updated_at = clock.unix_timestamp;
+    ctx.accounts.endorsement_index.sync_vault(vault, clock.unix_timestamp);
+
+    msg!(
+        "Slashed {} tokens ({} bps) from staker {} on agent {}",
+        slash_amount,
+        severity_bps,
+        stake_position.staker,
+        vault.target_agent
+    );
+    msg!(
+        "Remaining stake: {}, New trust weight: {}",
+        stake_position.amount,
+        stake_position.trust_weight
+    );
+
+    Ok(())
+}
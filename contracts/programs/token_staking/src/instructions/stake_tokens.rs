@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
-use crate::state::{StakingVault, StakePosition, StakeCategory};
+use crate::state::{
+    AgentEndorsementIndex, AgentStanding, ProtocolConfig, StakingVault, StakePosition, StakeCategory,
+    VaultRegistryConfig, IDENTITY_AGENT_SEED, IDENTITY_REGISTRY_PROGRAM_ID,
+};
 use crate::error::TokenStakingError;
+use crate::events::TokensStaked;
+use crate::instructions::protocol_fees::collect_fee;
 
 #[derive(Accounts)]
 pub struct StakeTokens<'info> {
@@ -16,6 +21,7 @@ pub struct StakeTokens<'info> {
         ],
         bump = vault.bump,
         constraint = vault.is_active @ TokenStakingError::VaultNotActive,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
     )]
     pub vault: Account<'info, StakingVault>,
 
@@ -56,45 +62,241 @@ pub struct StakeTokens<'info> {
     #[account(mut)]
     pub staker: Signer<'info>,
 
+    /// Platform config; may be uninitialized, in which case unverified vaults are accepted
+    /// CHECK: PDA derivation is enforced; contents are read via VaultRegistryConfig::load
+    #[account(
+        seeds = [VaultRegistryConfig::SEED_PREFIX],
+        bump,
+    )]
+    pub registry_config: UncheckedAccount<'info>,
+
+    /// The agent's cross-vault endorsement index
+    #[account(
+        init_if_needed,
+        payer = staker,
+        space = AgentEndorsementIndex::LEN,
+        seeds = [
+            AgentEndorsementIndex::SEED_PREFIX,
+            vault.target_agent.as_ref()
+        ],
+        bump
+    )]
+    pub endorsement_index: Account<'info, AgentEndorsementIndex>,
+
+    /// The vault's price feed; required when the vault is USD-normalized
+    /// CHECK: Pinned to vault.price_feed; owner and layout are checked by FeedPrice::load
+    #[account(address = vault.price_feed @ TokenStakingError::InvalidPriceFeed)]
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// The target agent's identity; it must be active and under the slash threshold
+    /// CHECK: PDA derivation under identity_registry is enforced; contents are read via AgentStanding::load
+    #[account(
+        seeds = [IDENTITY_AGENT_SEED, vault.target_agent.as_ref()],
+        bump,
+        seeds::program = IDENTITY_REGISTRY_PROGRAM_ID,
+    )]
+    pub agent_identity: UncheckedAccount<'info>,
+
+    /// Protocol fee settings; may be uninitialized, in which case no fee is charged
+    /// CHECK: PDA derivation is enforced; contents are read via ProtocolConfig::load
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump,
+    )]
+    pub protocol_config: UncheckedAccount<'info>,
+
+    /// The protocol's fee account for the vault's mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = staker,
+        seeds = [
+            ProtocolConfig::FEE_ACCOUNT_SEED,
+            vault.token_mint.as_ref()
+        ],
+        bump,
+        token::mint = token_mint,
+        token::authority = protocol_config,
+    )]
+    pub fee_token_account: Account<'info, TokenAccount>,
+
+    /// The vault's token mint
+    #[account(address = vault.token_mint @ TokenStakingError::InvalidTokenMint)]
+    pub token_mint: Account<'info, Mint>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
 
 /// Stake tokens to endorse an agent
+///
+/// `lock_duration` commits the position for longer than the vault's lock period,
+/// boosting its trust weight by the vault's lock tiers. When omitted, a new position
+/// takes the vault's lock period and a top-up keeps the position's current duration.
+/// The protocol fee is deducted from `amount` before the position is credited.
 pub fn handler(
     ctx: Context<StakeTokens>,
     amount: u64,
     category: StakeCategory,
+    lock_duration: Option<i64>,
 ) -> Result<()> {
-    let vault = &mut ctx.accounts.vault;
-    let stake_position = &mut ctx.accounts.stake_position;
+    // A position staked on someone's behalf only takes top-ups through stake_tokens_for
+    let stake_position = &ctx.accounts.stake_position;
+    require!(
+        !stake_position.is_active || !stake_position.is_delegated(),
+        TokenStakingError::DelegatedPosition
+    );
+
+    // New stakes require the target agent to be registered and in good standing
+    let max_slash_count = ProtocolConfig::max_agent_slash_count(&ctx.accounts.protocol_config)?;
+    AgentStanding::load(&ctx.accounts.agent_identity, &ctx.accounts.vault.target_agent)?
+        .require_good_standing(max_slash_count)?;
+
+    let fee = ProtocolConfig::stake_fee(&ctx.accounts.protocol_config, amount)?;
+    let net_amount = amount.checked_sub(fee).ok_or(TokenStakingError::ArithmeticOverflow)?;
+
+    let is_new_stake = record_stake(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.stake_position,
+        &mut ctx.accounts.endorsement_index,
+        &ctx.accounts.registry_config,
+        ctx.accounts.price_feed.as_deref(),
+        StakeRequest {
+            staker: ctx.accounts.staker.key(),
+            funder: ctx.accounts.staker.key(),
+            position_bump: ctx.bumps.stake_position,
+            amount: net_amount,
+            fee,
+            category,
+            lock_duration,
+        },
+    )?;
+    if is_new_stake {
+        ctx.accounts.stake_position.clear_delegation();
+    }
+
+    // Transfer tokens from staker to vault, and the fee to the protocol
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.staker_token_account.to_account_info(),
+            to: ctx.accounts.vault_token_account.to_account_info(),
+            authority: ctx.accounts.staker.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, net_amount)?;
+    collect_fee(
+        &ctx.accounts.token_program,
+        &ctx.accounts.staker_token_account,
+        &ctx.accounts.fee_token_account,
+        ctx.accounts.staker.to_account_info(),
+        &[],
+        fee,
+    )?;
+
+    if fee > 0 {
+        msg!("Protocol fee: {} tokens", fee);
+    }
+    Ok(())
+}
+
+/// A stake into a position, shared by stake_tokens and stake_tokens_for
+pub(crate) struct StakeRequest {
+    /// Staker of record (the position PDA's wallet)
+    pub staker: Pubkey,
+    /// Wallet the tokens come from
+    pub funder: Pubkey,
+    pub position_bump: u8,
+    /// Credited to the position, net of `fee`
+    pub amount: u64,
+    /// Protocol fee charged on top of `amount`
+    pub fee: u64,
+    pub category: StakeCategory,
+    pub lock_duration: Option<i64>,
+}
+
+/// Validate a stake and record it on the position, vault and endorsement index;
+/// the caller transfers the tokens. Returns whether the position was (re)opened.
+pub(crate) fn record_stake<'info>(
+    vault: &mut Account<'info, StakingVault>,
+    stake_position: &mut Account<'info, StakePosition>,
+    endorsement_index: &mut Account<'info, AgentEndorsementIndex>,
+    registry_config: &AccountInfo<'info>,
+    price_feed: Option<&AccountInfo<'info>>,
+    request: StakeRequest,
+) -> Result<bool> {
+    let StakeRequest { staker, funder, position_bump, amount, fee, category, lock_duration } = request;
     let clock = Clock::get()?;
 
     // Prevent self-staking
     require!(
-        ctx.accounts.staker.key() != vault.target_agent,
+        staker != vault.target_agent,
         TokenStakingError::SelfStakingNotAllowed
     );
 
+    // Verified-only deployments reject stakes into unverified vaults
+    let registry_config = VaultRegistryConfig::load(registry_config)?;
+    require!(
+        vault.is_verified || registry_config.is_none_or(|config| !config.require_verified_vaults),
+        TokenStakingError::VaultNotVerified
+    );
+
+    // A slashed position cannot be topped up or re-opened
+    require!(
+        !stake_position.is_slashed,
+        TokenStakingError::PositionSlashed
+    );
+
     // Validate minimum stake
     require!(
         amount >= vault.min_stake_amount,
         TokenStakingError::BelowMinimumStake
     );
 
+    // Settle rewards earned on the current amount before it changes
+    vault.settle_rewards(stake_position, clock.unix_timestamp)?;
+
     // Check if this is a new stake or adding to existing
     let is_new_stake = !stake_position.is_active || stake_position.staker == Pubkey::default();
 
-    // Transfer tokens from staker to vault
-    let transfer_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.staker_token_account.to_account_info(),
-            to: ctx.accounts.vault_token_account.to_account_info(),
-            authority: ctx.accounts.staker.to_account_info(),
-        },
+    // Enforce the vault's capacity and per-staker caps
+    let position_total = if is_new_stake { amount } else { stake_position.amount.saturating_add(amount) };
+    vault.check_stake_caps(amount, position_total)?;
+
+    // Resolve the lock duration (cooldown-mode vaults lock nothing at stake time)
+    let lock_duration = match lock_duration {
+        Some(duration) => {
+            require!(
+                !vault.cooldown_mode
+                    && duration >= vault.lock_period_seconds
+                    && duration <= StakingVault::MAX_LOCK_PERIOD,
+                TokenStakingError::InvalidLockDuration
+            );
+            duration
+        }
+        None if vault.cooldown_mode => 0,
+        None if is_new_stake => vault.lock_period_seconds,
+        None => vault.lock_period_seconds.max(stake_position.lock_duration),
+    };
+
+    // Calculate lock until timestamp
+    let locked_until = if vault.cooldown_mode {
+        clock.unix_timestamp
+    } else {
+        clock.unix_timestamp
+            .checked_add(lock_duration)
+            .ok_or(TokenStakingError::ArithmeticOverflow)?
+    };
+
+    // A top-up may extend the lock but never shorten it
+    require!(
+        is_new_stake || locked_until >= stake_position.locked_until,
+        TokenStakingError::LockShorterThanRemaining
     );
-    token::transfer(transfer_ctx, amount)?;
+    stake_position.lock_duration = lock_duration;
+    stake_position.lock_boost_bps = vault.lock_boost_bps(lock_duration);
+
+    // USD-normalized vaults weigh the stake at the feed's current price
+    vault.observe_price(price_feed, clock.unix_timestamp)?;
 
     // Calculate trust weight
     let total_stake = if is_new_stake {
@@ -103,36 +305,47 @@ pub fn handler(
         stake_position.amount.checked_add(amount)
             .ok_or(TokenStakingError::ArithmeticOverflow)?
     };
-    let trust_weight = vault.calculate_trust_weight(total_stake);
-
-    // Calculate lock until timestamp
-    let locked_until = clock.unix_timestamp
-        .checked_add(vault.lock_period_seconds)
-        .ok_or(TokenStakingError::ArithmeticOverflow)?;
+    let pending_unstake = if is_new_stake { 0 } else { stake_position.pending_unstake_amount };
+    let trust_weight = vault.position_trust_weight(
+        stake_position,
+        total_stake.saturating_sub(pending_unstake),
+    );
 
     // Update or initialize stake position
     if is_new_stake {
         stake_position.vault = vault.key();
-        stake_position.staker = ctx.accounts.staker.key();
+        stake_position.staker = staker;
         stake_position.target_agent = vault.target_agent;
         stake_position.token_mint = vault.token_mint;
         stake_position.amount = amount;
         stake_position.category = category;
-        stake_position.trust_weight = trust_weight;
+        vault.set_position_weight(stake_position, trust_weight);
         stake_position.staked_at = clock.unix_timestamp;
         stake_position.locked_until = locked_until;
         stake_position.unstaked_at = 0;
         stake_position.is_active = true;
         stake_position.is_slashed = false;
-        stake_position.bump = ctx.bumps.stake_position;
+        stake_position.pending_unstake_amount = 0;
+        stake_position.unstake_available_at = 0;
+        stake_position.bump = position_bump;
 
         // Update vault staker count
         vault.total_stakers = vault.total_stakers.saturating_add(1);
+        vault.credit_category(category, amount, true)?;
     } else {
+        // Adding to existing stake - a category change moves the whole position to the new bucket
+        let previous_category = stake_position.category;
+        if previous_category == category {
+            vault.credit_category(category, amount, false)?;
+        } else {
+            vault.debit_category(previous_category, stake_position.amount, true)?;
+            vault.credit_category(category, total_stake, true)?;
+        }
+
         // Adding to existing stake - extend lock period
         stake_position.amount = total_stake;
-        stake_position.trust_weight = trust_weight;
-        stake_position.locked_until = locked_until; // Reset lock period
+        vault.set_position_weight(stake_position, trust_weight);
+        stake_position.locked_until = locked_until;
         stake_position.category = category; // Update category if changed
     }
 
@@ -141,6 +354,30 @@ pub fn handler(
         .checked_add(amount)
         .ok_or(TokenStakingError::ArithmeticOverflow)?;
     vault.updated_at = clock.unix_timestamp;
+    endorsement_index.sync_vault(vault, clock.unix_timestamp);
+
+    emit!(TokensStaked {
+        vault: vault.key(),
+        stake_position: stake_position.key(),
+        endorsement_index: endorsement_index.key(),
+        staker,
+        funder,
+        target_agent: vault.target_agent,
+        token_mint: vault.token_mint,
+        category,
+        amount,
+        protocol_fee: fee,
+        is_new_position: is_new_stake,
+        position_amount: stake_position.amount,
+        trust_weight: stake_position.trust_weight,
+        lock_boost_bps: stake_position.lock_boost_bps,
+        locked_until,
+        vault_total_staked: vault.total_staked,
+        vault_total_stakers: vault.total_stakers,
+        vault_trust_weight: vault.total_trust_weight,
+        agent_trust_weight: endorsement_index.total_trust_weight,
+        timestamp: clock.unix_timestamp,
+    });
 
     msg!(
         "Staked {} tokens on agent {} (category: {:?})",
@@ -149,11 +386,12 @@ pub fn handler(
         category
     );
     msg!(
-        "Total stake: {}, Trust weight: {}, Locked until: {}",
+        "Total stake: {}, Trust weight: {} (lock boost {} bps), Locked until: {}",
         stake_position.amount,
         trust_weight,
+        stake_position.lock_boost_bps,
         locked_until
     );
 
-    Ok(())
+    Ok(is_new_stake)
 }
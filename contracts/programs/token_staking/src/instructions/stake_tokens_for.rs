@@ -0,0 +1,266 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::instructions::protocol_fees::collect_fee;
+use crate::instructions::stake_tokens::{record_stake, StakeRequest};
+use crate::state::{
+    AgentEndorsementIndex, AgentStanding, ProtocolConfig, StakingVault, StakePosition, StakeCategory,
+    VaultRegistryConfig, IDENTITY_AGENT_SEED, IDENTITY_REGISTRY_PROGRAM_ID,
+};
+use crate::error::TokenStakingError;
+
+#[derive(Accounts)]
+pub struct StakeTokensFor<'info> {
+    /// The staking vault
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::SEED_PREFIX,
+            vault.target_agent.as_ref(),
+            vault.token_mint.as_ref()
+        ],
+        bump = vault.bump,
+        constraint = vault.is_active @ TokenStakingError::VaultNotActive,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
+    )]
+    pub vault: Account<'info, StakingVault>,
+
+    /// The vault's token account
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::VAULT_TOKEN_SEED,
+            vault.key().as_ref()
+        ],
+        bump = vault.vault_bump,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// Create or top up the beneficiary's stake position
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = StakePosition::LEN,
+        seeds = [
+            StakePosition::SEED_PREFIX,
+            vault.key().as_ref(),
+            beneficiary.key().as_ref()
+        ],
+        bump
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    /// Funder's token account
+    #[account(
+        mut,
+        constraint = funder_token_account.mint == vault.token_mint @ TokenStakingError::InvalidTokenMint,
+        constraint = funder_token_account.owner == funder.key() @ TokenStakingError::InvalidTokenOwner,
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    /// Where unstaked tokens are returned (recorded when the position is opened)
+    #[account(
+        constraint = withdrawal_token_account.mint == vault.token_mint @ TokenStakingError::InvalidTokenMint,
+    )]
+    pub withdrawal_token_account: Account<'info, TokenAccount>,
+
+    /// The staker of record, who alone can unstake
+    /// CHECK: Any wallet may be staked for; it only seeds the position
+    pub beneficiary: UncheckedAccount<'info>,
+
+    /// The funder (or, for top-ups, the position's operator)
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// Platform config; may be uninitialized, in which case unverified vaults are accepted
+    /// CHECK: PDA derivation is enforced; contents are read via VaultRegistryConfig::load
+    #[account(
+        seeds = [VaultRegistryConfig::SEED_PREFIX],
+        bump,
+    )]
+    pub registry_config: UncheckedAccount<'info>,
+
+    /// The agent's cross-vault endorsement index
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = AgentEndorsementIndex::LEN,
+        seeds = [
+            AgentEndorsementIndex::SEED_PREFIX,
+            vault.target_agent.as_ref()
+        ],
+        bump
+    )]
+    pub endorsement_index: Account<'info, AgentEndorsementIndex>,
+
+    /// The vault's price feed; required when the vault is USD-normalized
+    /// CHECK: Pinned to vault.price_feed; owner and layout are checked by FeedPrice::load
+    #[account(address = vault.price_feed @ TokenStakingError::InvalidPriceFeed)]
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// The target agent's identity; it must be active and under the slash threshold
+    /// CHECK: PDA derivation under identity_registry is enforced; contents are read via AgentStanding::load
+    #[account(
+        seeds = [IDENTITY_AGENT_SEED, vault.target_agent.as_ref()],
+        bump,
+        seeds::program = IDENTITY_REGISTRY_PROGRAM_ID,
+    )]
+    pub agent_identity: UncheckedAccount<'info>,
+
+    /// Protocol fee settings; may be uninitialized, in which case no fee is charged
+    /// CHECK: PDA derivation is enforced; contents are read via ProtocolConfig::load
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump,
+    )]
+    pub protocol_config: UncheckedAccount<'info>,
+
+    /// The protocol's fee account for the vault's mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = funder,
+        seeds = [
+            ProtocolConfig::FEE_ACCOUNT_SEED,
+            vault.token_mint.as_ref()
+        ],
+        bump,
+        token::mint = token_mint,
+        token::authority = protocol_config,
+    )]
+    pub fee_token_account: Account<'info, TokenAccount>,
+
+    /// The vault's token mint
+    #[account(address = vault.token_mint @ TokenStakingError::InvalidTokenMint)]
+    pub token_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Stake tokens on a beneficiary's behalf
+///
+/// Opening a position records the signer as its funder and the withdrawal token
+/// account that unstaked tokens return to. Only the funder or the operator it sets
+/// can top the position up, and only the beneficiary can unstake. The protocol fee
+/// is deducted from `amount` before the position is credited.
+pub fn handler(
+    ctx: Context<StakeTokensFor>,
+    amount: u64,
+    category: StakeCategory,
+    lock_duration: Option<i64>,
+) -> Result<()> {
+    let funder = ctx.accounts.funder.key();
+    let withdrawal_address = ctx.accounts.withdrawal_token_account.key();
+
+    // The agent cannot fund its own endorsement through a beneficiary
+    require!(
+        funder != ctx.accounts.vault.target_agent,
+        TokenStakingError::SelfStakingNotAllowed
+    );
+
+    let stake_position = &ctx.accounts.stake_position;
+    if stake_position.is_active {
+        require!(
+            stake_position.can_top_up(&funder),
+            TokenStakingError::UnauthorizedOperator
+        );
+        require_keys_eq!(
+            withdrawal_address,
+            stake_position.withdrawal_address,
+            TokenStakingError::WithdrawalAddressMismatch
+        );
+    }
+
+    // New stakes require the target agent to be registered and in good standing
+    let max_slash_count = ProtocolConfig::max_agent_slash_count(&ctx.accounts.protocol_config)?;
+    AgentStanding::load(&ctx.accounts.agent_identity, &ctx.accounts.vault.target_agent)?
+        .require_good_standing(max_slash_count)?;
+
+    let fee = ProtocolConfig::stake_fee(&ctx.accounts.protocol_config, amount)?;
+    let net_amount = amount.checked_sub(fee).ok_or(TokenStakingError::ArithmeticOverflow)?;
+
+    let is_new_stake = record_stake(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.stake_position,
+        &mut ctx.accounts.endorsement_index,
+        &ctx.accounts.registry_config,
+        ctx.accounts.price_feed.as_deref(),
+        StakeRequest {
+            staker: ctx.accounts.beneficiary.key(),
+            funder,
+            position_bump: ctx.bumps.stake_position,
+            amount: net_amount,
+            fee,
+            category,
+            lock_duration,
+        },
+    )?;
+    if is_new_stake {
+        let stake_position = &mut ctx.accounts.stake_position;
+        stake_position.funder = funder;
+        stake_position.operator = Pubkey::default();
+        stake_position.withdrawal_address = withdrawal_address;
+    }
+
+    // Transfer tokens from funder to vault, and the fee to the protocol
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.funder_token_account.to_account_info(),
+            to: ctx.accounts.vault_token_account.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, net_amount)?;
+    collect_fee(
+        &ctx.accounts.token_program,
+        &ctx.accounts.funder_token_account,
+        &ctx.accounts.fee_token_account,
+        ctx.accounts.funder.to_account_info(),
+        &[],
+        fee,
+    )?;
+
+    msg!(
+        "Funded by {} for {}, withdrawals to {}",
+        ctx.accounts.stake_position.funder,
+        ctx.accounts.stake_position.staker,
+        ctx.accounts.stake_position.withdrawal_address
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetStakeOperator<'info> {
+    /// The delegated position
+    #[account(
+        mut,
+        seeds = [
+            StakePosition::SEED_PREFIX,
+            stake_position.vault.as_ref(),
+            stake_position.staker.as_ref()
+        ],
+        bump = stake_position.bump,
+        constraint = stake_position.is_active @ TokenStakingError::StakeNotActive,
+        constraint = stake_position.is_delegated() && stake_position.funder == funder.key()
+            @ TokenStakingError::UnauthorizedOperator,
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    pub funder: Signer<'info>,
+}
+
+/// Set the wallet allowed to top up a delegated position (funder only; default pubkey clears it)
+pub fn set_stake_operator(ctx: Context<SetStakeOperator>, operator: Pubkey) -> Result<()> {
+    let stake_position = &mut ctx.accounts.stake_position;
+    stake_position.operator = operator;
+
+    msg!(
+        "Operator of {}'s position on vault {} set to {}",
+        stake_position.staker,
+        stake_position.vault,
+        operator
+    );
+    Ok(())
+}
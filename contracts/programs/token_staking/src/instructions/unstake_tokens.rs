@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
-use crate::state::{StakingVault, StakePosition};
+use crate::state::{AgentEndorsementIndex, ProtocolConfig, StakingVault, StakePosition};
 use crate::error::TokenStakingError;
+use crate::events::TokensUnstaked;
+use crate::instructions::protocol_fees::collect_fee;
 
 #[derive(Accounts)]
 pub struct UnstakeTokens<'info> {
@@ -15,6 +17,8 @@ pub struct UnstakeTokens<'info> {
             vault.token_mint.as_ref()
         ],
         bump = vault.bump,
+        constraint = !vault.cooldown_mode @ TokenStakingError::CooldownModeVault,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
     )]
     pub vault: Account<'info, StakingVault>,
 
@@ -43,26 +47,72 @@ pub struct UnstakeTokens<'info> {
     )]
     pub stake_position: Account<'info, StakePosition>,
 
-    /// Staker's token account to receive tokens
+    /// Token account to receive tokens (the recorded withdrawal address of a delegated position)
     #[account(
         mut,
         constraint = staker_token_account.mint == vault.token_mint @ TokenStakingError::InvalidTokenMint,
-        constraint = staker_token_account.owner == staker.key() @ TokenStakingError::InvalidTokenOwner,
+        constraint = stake_position.is_withdrawal_account(&staker_token_account.key(), &staker_token_account.owner)
+            @ TokenStakingError::InvalidTokenOwner,
     )]
     pub staker_token_account: Account<'info, TokenAccount>,
 
+    /// The agent's cross-vault endorsement index
+    #[account(
+        mut,
+        seeds = [
+            AgentEndorsementIndex::SEED_PREFIX,
+            vault.target_agent.as_ref()
+        ],
+        bump = endorsement_index.bump,
+    )]
+    pub endorsement_index: Account<'info, AgentEndorsementIndex>,
+
     /// The staker (must be original staker)
     #[account(mut)]
     pub staker: Signer<'info>,
 
+    /// Protocol fee settings; may be uninitialized, in which case no fee is charged
+    /// CHECK: PDA derivation is enforced; contents are read via ProtocolConfig::load
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump,
+    )]
+    pub protocol_config: UncheckedAccount<'info>,
+
+    /// The protocol's fee account for the vault's mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = staker,
+        seeds = [
+            ProtocolConfig::FEE_ACCOUNT_SEED,
+            vault.token_mint.as_ref()
+        ],
+        bump,
+        token::mint = token_mint,
+        token::authority = protocol_config,
+    )]
+    pub fee_token_account: Account<'info, TokenAccount>,
+
+    /// The vault's token mint
+    #[account(address = vault.token_mint @ TokenStakingError::InvalidTokenMint)]
+    pub token_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
 
 /// Unstake tokens after lock period
+///
+/// When the protocol charges unstake fees, the fee is withheld from the tokens paid out;
+/// the position and vault are debited the full `amount`.
 pub fn handler(ctx: Context<UnstakeTokens>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Settle rewards earned on the current amount before it changes
+    ctx.accounts.vault.settle_rewards(&mut ctx.accounts.stake_position, clock.unix_timestamp)?;
+
     let vault = &ctx.accounts.vault;
     let stake_position = &mut ctx.accounts.stake_position;
-    let clock = Clock::get()?;
 
     // Check lock period has passed
     require!(
@@ -79,6 +129,9 @@ pub fn handler(ctx: Context<UnstakeTokens>, amount: u64) -> Result<()> {
     // Check if fully unstaking or partial
     let is_full_unstake = amount == stake_position.amount;
 
+    let fee = ProtocolConfig::unstake_fee(&ctx.accounts.protocol_config, amount)?;
+    let payout = amount.checked_sub(fee).ok_or(TokenStakingError::ArithmeticOverflow)?;
+
     // Transfer tokens from vault to staker using PDA signing
     let target_agent = vault.target_agent;
     let token_mint = vault.token_mint;
@@ -100,7 +153,15 @@ pub fn handler(ctx: Context<UnstakeTokens>, amount: u64) -> Result<()> {
         },
         signer_seeds,
     );
-    token::transfer(transfer_ctx, amount)?;
+    token::transfer(transfer_ctx, payout)?;
+    collect_fee(
+        &ctx.accounts.token_program,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.fee_token_account,
+        ctx.accounts.vault.to_account_info(),
+        signer_seeds,
+        fee,
+    )?;
 
     // Update stake position
     stake_position.amount = stake_position.amount
@@ -110,23 +171,47 @@ pub fn handler(ctx: Context<UnstakeTokens>, amount: u64) -> Result<()> {
     if is_full_unstake {
         stake_position.is_active = false;
         stake_position.unstaked_at = clock.unix_timestamp;
-        stake_position.trust_weight = 0;
 
         // Update vault staker count
         let vault = &mut ctx.accounts.vault;
+        vault.set_position_weight(stake_position, 0);
         vault.total_stakers = vault.total_stakers.saturating_sub(1);
     } else {
         // Partial unstake - recalculate trust weight
-        let vault = &ctx.accounts.vault;
-        stake_position.trust_weight = vault.calculate_trust_weight(stake_position.amount);
+        let vault = &mut ctx.accounts.vault;
+        let trust_weight = vault.position_trust_weight(stake_position, stake_position.amount);
+        vault.set_position_weight(stake_position, trust_weight);
     }
 
     // Update vault totals
     let vault = &mut ctx.accounts.vault;
+    vault.debit_category(stake_position.category, amount, is_full_unstake)?;
     vault.total_staked = vault.total_staked
         .checked_sub(amount)
         .ok_or(TokenStakingError::ArithmeticOverflow)?;
     vault.updated_at = clock.unix_timestamp;
+    ctx.accounts.endorsement_index.sync_vault(vault, clock.unix_timestamp);
+
+    emit!(TokensUnstaked {
+        vault: vault.key(),
+        stake_position: stake_position.key(),
+        endorsement_index: ctx.accounts.endorsement_index.key(),
+        staker: stake_position.staker,
+        destination: ctx.accounts.staker_token_account.key(),
+        target_agent: vault.target_agent,
+        token_mint: vault.token_mint,
+        category: stake_position.category,
+        amount,
+        protocol_fee: fee,
+        is_full_unstake,
+        position_amount: stake_position.amount,
+        trust_weight: stake_position.trust_weight,
+        vault_total_staked: vault.total_staked,
+        vault_total_stakers: vault.total_stakers,
+        vault_trust_weight: vault.total_trust_weight,
+        agent_trust_weight: ctx.accounts.endorsement_index.total_trust_weight,
+        timestamp: clock.unix_timestamp,
+    });
 
     msg!(
         "Unstaked {} tokens from agent {}",
@@ -134,6 +219,10 @@ pub fn handler(ctx: Context<UnstakeTokens>, amount: u64) -> Result<()> {
         vault.target_agent
     );
 
+    if fee > 0 {
+        msg!("Protocol fee: {} tokens", fee);
+    }
+
     if is_full_unstake {
         msg!("Stake position fully closed");
     } else {
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
 
-use crate::state::StakingVault;
+use crate::state::{FeedPrice, LockTier, StakingVault};
 use crate::error::TokenStakingError;
 
 #[derive(Accounts)]
@@ -14,6 +15,7 @@ pub struct UpdateVault<'info> {
         ],
         bump = vault.bump,
         has_one = authority @ TokenStakingError::UnauthorizedAuthority,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
     )]
     pub vault: Account<'info, StakingVault>,
 
@@ -26,6 +28,9 @@ pub fn update_vault_config(
     min_stake_amount: Option<u64>,
     lock_period_seconds: Option<i64>,
     weight_multiplier: Option<u16>,
+    max_total_staked: Option<u64>,
+    max_per_staker: Option<u64>,
+    lock_tiers: Option<Vec<LockTier>>,
 ) -> Result<()> {
     let vault = &mut ctx.accounts.vault;
     let clock = Clock::get()?;
@@ -46,13 +51,33 @@ pub fn update_vault_config(
 
     if let Some(multiplier) = weight_multiplier {
         require!(
-            multiplier >= 10 && multiplier <= 1000,
+            (10..=1000).contains(&multiplier),
             TokenStakingError::InvalidWeightMultiplier
         );
         vault.weight_multiplier = multiplier;
         msg!("Updated weight multiplier to {}", multiplier);
     }
 
+    // Lowering a cap below current stake only blocks new stakes; nothing is forced out
+    if let Some(max_total) = max_total_staked {
+        vault.max_total_staked = max_total;
+        msg!("Updated max total staked to {} (0 = unlimited)", max_total);
+    }
+
+    if let Some(max_staker) = max_per_staker {
+        vault.max_per_staker = max_staker;
+        msg!("Updated max per staker to {} (0 = unlimited)", max_staker);
+    }
+
+    // Existing positions keep the boost they staked with until their next stake
+    if let Some(tiers) = lock_tiers {
+        StakingVault::validate_lock_tiers(&tiers)?;
+        vault.lock_tiers = [LockTier::default(); StakingVault::MAX_LOCK_TIERS];
+        vault.lock_tiers[..tiers.len()].copy_from_slice(&tiers);
+        vault.lock_tier_count = tiers.len() as u8;
+        msg!("Updated lock tiers ({} in use)", tiers.len());
+    }
+
     vault.updated_at = clock.unix_timestamp;
 
     Ok(())
@@ -63,6 +88,10 @@ pub fn pause_vault(ctx: Context<UpdateVault>) -> Result<()> {
     let vault = &mut ctx.accounts.vault;
     let clock = Clock::get()?;
 
+    // Re-pausing keeps the original pause time, so the emergency grace period cannot be reset
+    if vault.is_active {
+        vault.paused_at = clock.unix_timestamp;
+    }
     vault.is_active = false;
     vault.updated_at = clock.unix_timestamp;
 
@@ -76,6 +105,7 @@ pub fn unpause_vault(ctx: Context<UpdateVault>) -> Result<()> {
     let clock = Clock::get()?;
 
     vault.is_active = true;
+    vault.paused_at = 0;
     vault.updated_at = clock.unix_timestamp;
 
     msg!("Vault unpaused for agent {}", vault.target_agent);
@@ -93,6 +123,7 @@ pub struct TransferVaultAuthority<'info> {
         ],
         bump = vault.bump,
         has_one = authority @ TokenStakingError::UnauthorizedAuthority,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
     )]
     pub vault: Account<'info, StakingVault>,
 
@@ -118,3 +149,191 @@ pub fn transfer_authority(ctx: Context<TransferVaultAuthority>) -> Result<()> {
     );
     Ok(())
 }
+
+#[derive(Accounts)]
+pub struct ConfigureSlashing<'info> {
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::SEED_PREFIX,
+            vault.target_agent.as_ref(),
+            vault.token_mint.as_ref()
+        ],
+        bump = vault.bump,
+        has_one = authority @ TokenStakingError::UnauthorizedAuthority,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
+    )]
+    pub vault: Account<'info, StakingVault>,
+
+    /// Token account slashed tokens are sent to
+    #[account(
+        constraint = penalty_destination.mint == vault.token_mint @ TokenStakingError::InvalidPenaltyDestination,
+        constraint = penalty_destination.key() != vault.vault_token_account @ TokenStakingError::InvalidPenaltyDestination,
+    )]
+    pub penalty_destination: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set who may slash stake positions and where slashed tokens go
+/// (the default pubkey as slash authority disables slashing)
+pub fn configure_slashing(ctx: Context<ConfigureSlashing>, slash_authority: Pubkey) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    vault.slash_authority = slash_authority;
+    vault.penalty_destination = ctx.accounts.penalty_destination.key();
+    vault.updated_at = clock.unix_timestamp;
+
+    msg!(
+        "Slash authority set to {}, penalty destination {}",
+        vault.slash_authority,
+        vault.penalty_destination
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConfigureEmergencyExit<'info> {
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::SEED_PREFIX,
+            vault.target_agent.as_ref(),
+            vault.token_mint.as_ref()
+        ],
+        bump = vault.bump,
+        has_one = authority @ TokenStakingError::UnauthorizedAuthority,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
+    )]
+    pub vault: Account<'info, StakingVault>,
+
+    /// Token account early-exit penalties are sent to (shared with slashing)
+    #[account(
+        constraint = penalty_destination.mint == vault.token_mint @ TokenStakingError::InvalidPenaltyDestination,
+        constraint = penalty_destination.key() != vault.vault_token_account @ TokenStakingError::InvalidPenaltyDestination,
+    )]
+    pub penalty_destination: Option<Account<'info, TokenAccount>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Configure when stakers may emergency_withdraw and what it costs them
+/// (a grace period of 0 uses DEFAULT_EMERGENCY_GRACE_PERIOD)
+pub fn configure_emergency_exit(
+    ctx: Context<ConfigureEmergencyExit>,
+    grace_period_seconds: i64,
+    early_exit_enabled: bool,
+    early_exit_penalty_bps: u16,
+) -> Result<()> {
+    require!(
+        (0..=StakingVault::MAX_LOCK_PERIOD).contains(&grace_period_seconds),
+        TokenStakingError::InvalidEmergencyGracePeriod
+    );
+    require!(
+        early_exit_penalty_bps <= StakingVault::MAX_EARLY_EXIT_PENALTY_BPS,
+        TokenStakingError::InvalidEarlyExitPenalty
+    );
+
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    if let Some(penalty_destination) = &ctx.accounts.penalty_destination {
+        vault.penalty_destination = penalty_destination.key();
+    }
+    require!(
+        early_exit_penalty_bps == 0 || vault.penalty_destination != Pubkey::default(),
+        TokenStakingError::InvalidPenaltyDestination
+    );
+
+    vault.emergency_grace_period = grace_period_seconds;
+    vault.early_exit_enabled = early_exit_enabled;
+    vault.early_exit_penalty_bps = early_exit_penalty_bps;
+    vault.updated_at = clock.unix_timestamp;
+
+    msg!(
+        "Emergency exit: grace period {}s, early exit {}, penalty {} bps",
+        grace_period_seconds,
+        early_exit_enabled,
+        early_exit_penalty_bps
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConfigurePriceFeed<'info> {
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::SEED_PREFIX,
+            vault.target_agent.as_ref(),
+            vault.token_mint.as_ref()
+        ],
+        bump = vault.bump,
+        has_one = authority @ TokenStakingError::UnauthorizedAuthority,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
+    )]
+    pub vault: Account<'info, StakingVault>,
+
+    /// The vault token mint (its decimals scale USD values)
+    #[account(address = vault.token_mint @ TokenStakingError::InvalidTokenMint)]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Pyth PriceUpdateV2 account pricing the vault token; omit to keep the current feed
+    /// CHECK: Owner and layout are checked by FeedPrice::load
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Configure USD-normalized trust weights (a max price age of 0 uses DEFAULT_MAX_PRICE_AGE)
+///
+/// A newly set feed must report a usable price, which seeds last_price.
+/// Existing positions keep their weights until their next change.
+pub fn configure_price_feed(
+    ctx: Context<ConfigurePriceFeed>,
+    usd_normalized: bool,
+    max_price_age: i64,
+    price_fallback_to_raw: bool,
+) -> Result<()> {
+    require!(
+        (0..=StakingVault::MAX_PRICE_AGE).contains(&max_price_age),
+        TokenStakingError::InvalidMaxPriceAge
+    );
+
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    vault.max_price_age = max_price_age;
+    vault.price_fallback_to_raw = price_fallback_to_raw;
+    vault.token_decimals = ctx.accounts.token_mint.decimals;
+
+    if let Some(price_feed) = &ctx.accounts.price_feed {
+        let price = FeedPrice::load(price_feed)?;
+        require!(price.price > 0, TokenStakingError::InvalidPrice);
+        require!(
+            price.is_usable(clock.unix_timestamp, vault.effective_max_price_age()),
+            TokenStakingError::StalePrice
+        );
+        vault.price_feed = price_feed.key();
+        vault.last_price = price.price;
+        vault.last_price_exponent = price.exponent;
+        vault.last_price_published_at = price.publish_time;
+    }
+    require!(
+        !usd_normalized || vault.price_feed != Pubkey::default(),
+        TokenStakingError::MissingPriceFeed
+    );
+
+    vault.usd_normalized = usd_normalized;
+    vault.updated_at = clock.unix_timestamp;
+
+    msg!(
+        "Price feed {}: USD-normalized {}, max age {}s, raw fallback {}",
+        vault.price_feed,
+        usd_normalized,
+        vault.effective_max_price_age(),
+        price_fallback_to_raw
+    );
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AgentEndorsementIndex, StakingVault, VaultRegistryConfig};
+use crate::error::TokenStakingError;
+use crate::events::{VaultUnverified, VaultVerified};
+
+#[derive(Accounts)]
+pub struct InitializeRegistryConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = VaultRegistryConfig::LEN,
+        seeds = [VaultRegistryConfig::SEED_PREFIX],
+        bump
+    )]
+    pub registry_config: Account<'info, VaultRegistryConfig>,
+
+    /// The platform admin (typically deployer)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the platform config with its vault verifier
+pub fn initialize_registry_config(
+    ctx: Context<InitializeRegistryConfig>,
+    verifier: Pubkey,
+    require_verified_vaults: bool,
+) -> Result<()> {
+    let config = &mut ctx.accounts.registry_config;
+
+    config.admin = ctx.accounts.admin.key();
+    config.verifier = verifier;
+    config.require_verified_vaults = require_verified_vaults;
+    config.bump = ctx.bumps.registry_config;
+
+    msg!(
+        "Vault registry initialized: verifier {}, verified-only staking {}",
+        verifier,
+        require_verified_vaults
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateRegistryConfig<'info> {
+    #[account(
+        mut,
+        seeds = [VaultRegistryConfig::SEED_PREFIX],
+        bump = registry_config.bump,
+        has_one = admin @ TokenStakingError::UnauthorizedRegistryAdmin,
+    )]
+    pub registry_config: Account<'info, VaultRegistryConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Change the vault verifier or the verified-only staking mode
+pub fn update_registry_config(
+    ctx: Context<UpdateRegistryConfig>,
+    verifier: Option<Pubkey>,
+    require_verified_vaults: Option<bool>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.registry_config;
+
+    if let Some(verifier) = verifier {
+        config.verifier = verifier;
+        msg!("Updated vault verifier to {}", verifier);
+    }
+
+    if let Some(required) = require_verified_vaults {
+        config.require_verified_vaults = required;
+        msg!("Updated verified-only staking to {}", required);
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetVaultVerification<'info> {
+    #[account(
+        seeds = [VaultRegistryConfig::SEED_PREFIX],
+        bump = registry_config.bump,
+        has_one = verifier @ TokenStakingError::UnauthorizedVerifier,
+    )]
+    pub registry_config: Account<'info, VaultRegistryConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::SEED_PREFIX,
+            vault.target_agent.as_ref(),
+            vault.token_mint.as_ref()
+        ],
+        bump = vault.bump,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
+    )]
+    pub vault: Account<'info, StakingVault>,
+
+    pub verifier: Signer<'info>,
+}
+
+/// Mark a vault as verified; the reason text is kept off-chain
+pub fn verify_vault(ctx: Context<SetVaultVerification>, reason_hash: [u8; 32]) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    vault.is_verified = true;
+    vault.verification_reason_hash = reason_hash;
+    vault.verification_updated_at = clock.unix_timestamp;
+    vault.updated_at = clock.unix_timestamp;
+
+    emit!(VaultVerified {
+        vault: vault.key(),
+        target_agent: vault.target_agent,
+        token_mint: vault.token_mint,
+        verifier: ctx.accounts.verifier.key(),
+        reason_hash,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Vault verified for agent {}", vault.target_agent);
+    Ok(())
+}
+
+/// Withdraw a vault's verification; the reason text is kept off-chain
+pub fn unverify_vault(ctx: Context<SetVaultVerification>, reason_hash: [u8; 32]) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    vault.is_verified = false;
+    vault.verification_reason_hash = reason_hash;
+    vault.verification_updated_at = clock.unix_timestamp;
+    vault.updated_at = clock.unix_timestamp;
+
+    emit!(VaultUnverified {
+        vault: vault.key(),
+        target_agent: vault.target_agent,
+        token_mint: vault.token_mint,
+        verifier: ctx.accounts.verifier.key(),
+        reason_hash,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Vault unverified for agent {}", vault.target_agent);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetVaultNormalization<'info> {
+    #[account(
+        seeds = [VaultRegistryConfig::SEED_PREFIX],
+        bump = registry_config.bump,
+        has_one = verifier @ TokenStakingError::UnauthorizedVerifier,
+    )]
+    pub registry_config: Account<'info, VaultRegistryConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            StakingVault::SEED_PREFIX,
+            vault.target_agent.as_ref(),
+            vault.token_mint.as_ref()
+        ],
+        bump = vault.bump,
+        constraint = vault.is_supported_version() @ TokenStakingError::UnsupportedAccountVersion
+    )]
+    pub vault: Account<'info, StakingVault>,
+
+    /// The agent's cross-vault endorsement index
+    #[account(
+        mut,
+        seeds = [
+            AgentEndorsementIndex::SEED_PREFIX,
+            vault.target_agent.as_ref()
+        ],
+        bump = endorsement_index.bump,
+    )]
+    pub endorsement_index: Account<'info, AgentEndorsementIndex>,
+
+    pub verifier: Signer<'info>,
+}
+
+/// Scale a vault's contribution to its agent's endorsement index
+/// (10_000 = 1x; lower values keep low-value mints from dominating)
+pub fn set_vault_normalization(ctx: Context<SetVaultNormalization>, normalization_bps: u16) -> Result<()> {
+    require!(
+        normalization_bps <= StakingVault::NORMALIZATION_ONE,
+        TokenStakingError::InvalidNormalization
+    );
+
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    vault.normalization_bps = normalization_bps;
+    vault.updated_at = clock.unix_timestamp;
+    ctx.accounts.endorsement_index.sync_vault(vault, clock.unix_timestamp);
+
+    msg!(
+        "Vault normalization for agent {} set to {} bps",
+        vault.target_agent,
+        normalization_bps
+    );
+    Ok(())
+}
@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
 
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
 pub use error::*;
+pub use events::*;
+#[allow(ambiguous_glob_reexports)]
 pub use instructions::*;
 pub use state::*;
 
@@ -16,48 +19,144 @@ pub mod token_staking {
 
     /// Initialize a staking vault for an agent's token
     /// Allows agents to register their SPL tokens for staking-based endorsements
+    /// The target agent must be registered in identity_registry, active and under the slash threshold
+    /// A non-zero unstake_cooldown_seconds puts the vault in cooldown mode
+    /// max_total_staked and max_per_staker cap stakes (0 = unlimited)
     pub fn initialize_vault(
         ctx: Context<InitializeVault>,
         min_stake_amount: u64,
         lock_period_seconds: i64,
         weight_multiplier: u16,
+        unstake_cooldown_seconds: i64,
+        max_total_staked: u64,
+        max_per_staker: u64,
     ) -> Result<()> {
         instructions::initialize_vault::handler(
             ctx,
             min_stake_amount,
             lock_period_seconds,
             weight_multiplier,
+            unstake_cooldown_seconds,
+            max_total_staked,
+            max_per_staker,
         )
     }
 
     /// Stake tokens to endorse an agent
-    /// Creates or adds to an existing stake position
+    /// Creates or adds to an existing stake position; an optional lock_duration
+    /// beyond the vault's lock period earns the matching lock-tier boost
+    /// The protocol fee is deducted before the position is credited
+    /// Rejected once the target agent is deactivated or over the slash threshold; exits stay open
     pub fn stake_tokens(
         ctx: Context<StakeTokens>,
         amount: u64,
         category: StakeCategory,
+        lock_duration: Option<i64>,
     ) -> Result<()> {
-        instructions::stake_tokens::handler(ctx, amount, category)
+        instructions::stake_tokens::handler(ctx, amount, category, lock_duration)
     }
 
-    /// Unstake tokens after lock period
+    /// Stake tokens on a beneficiary's behalf (e.g. from a DAO treasury)
+    /// The beneficiary is the staker of record and unstakes to the recorded withdrawal account
+    pub fn stake_tokens_for(
+        ctx: Context<StakeTokensFor>,
+        amount: u64,
+        category: StakeCategory,
+        lock_duration: Option<i64>,
+    ) -> Result<()> {
+        instructions::stake_tokens_for::handler(ctx, amount, category, lock_duration)
+    }
+
+    /// Set the wallet allowed to top up a delegated position (funder only)
+    pub fn set_stake_operator(ctx: Context<SetStakeOperator>, operator: Pubkey) -> Result<()> {
+        instructions::stake_tokens_for::set_stake_operator(ctx, operator)
+    }
+
+    /// Unstake tokens after lock period (lock-mode vaults)
     /// Can be partial or full withdrawal
     pub fn unstake_tokens(ctx: Context<UnstakeTokens>, amount: u64) -> Result<()> {
         instructions::unstake_tokens::handler(ctx, amount)
     }
 
+    /// Start the unstake cooldown for part of a position (cooldown-mode vaults)
+    /// The amount stops counting toward trust weight but stays slashable
+    pub fn request_unstake(ctx: Context<RequestUnstake>, amount: u64) -> Result<()> {
+        instructions::cooldown_unstake::request_unstake(ctx, amount)
+    }
+
+    /// Withdraw the pending unstake once its cooldown has elapsed
+    pub fn withdraw_unstaked(ctx: Context<WithdrawUnstaked>) -> Result<()> {
+        instructions::cooldown_unstake::withdraw_unstaked(ctx)
+    }
+
+    /// Withdraw a whole position ignoring its lock, once the vault has been paused
+    /// past its grace period or early exit is enabled (early-exit penalty applies)
+    pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+        instructions::emergency_withdraw::handler(ctx)
+    }
+
+    /// Fund staker rewards (authority only)
+    /// Streams amount reward tokens to stakers pro rata over period_seconds
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64, period_seconds: i64) -> Result<()> {
+        instructions::rewards::fund_rewards(ctx, amount, period_seconds)
+    }
+
+    /// Claim the rewards a stake position has accrued
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        instructions::rewards::claim_rewards(ctx)
+    }
+
+    /// Slash a stake position (vault slash authority only)
+    /// Moves severity_bps of the staked tokens to the penalty destination
+    pub fn slash_stake_position(ctx: Context<SlashStakePosition>, severity_bps: u16) -> Result<()> {
+        instructions::slash_stake::handler(ctx, severity_bps)
+    }
+
+    /// Close a fully unstaked position, refunding its rent to the staker
+    pub fn close_stake_position(ctx: Context<CloseStakePosition>) -> Result<()> {
+        instructions::close::close_stake_position(ctx)
+    }
+
+    /// Close an empty vault and its token accounts (authority only)
+    pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
+        instructions::close::close_vault(ctx)
+    }
+
+    /// Per-category stake totals of a vault, returned via return data
+    pub fn get_category_breakdown(ctx: Context<GetCategoryBreakdown>) -> Result<CategoryBreakdown> {
+        instructions::category_breakdown::handler(ctx)
+    }
+
+    /// Token-staked endorsement of an agent across all its vaults, returned via return data
+    pub fn get_agent_endorsement(ctx: Context<GetAgentEndorsement>) -> Result<AgentEndorsementIndex> {
+        instructions::agent_endorsement::handler(ctx)
+    }
+
+    /// Upgrade a vault to the current layout version (permissionless)
+    /// Vaults created before per-category totals are backfilled from their positions:
+    /// pass every active position of the vault as a remaining account, sorted by address
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::migrate::migrate_account(ctx)
+    }
+
     /// Update vault configuration (authority only)
     pub fn update_vault_config(
         ctx: Context<UpdateVault>,
         min_stake_amount: Option<u64>,
         lock_period_seconds: Option<i64>,
         weight_multiplier: Option<u16>,
+        max_total_staked: Option<u64>,
+        max_per_staker: Option<u64>,
+        lock_tiers: Option<Vec<LockTier>>,
     ) -> Result<()> {
         instructions::update_vault::update_vault_config(
             ctx,
             min_stake_amount,
             lock_period_seconds,
             weight_multiplier,
+            max_total_staked,
+            max_per_staker,
+            lock_tiers,
         )
     }
 
@@ -75,4 +174,109 @@ pub mod token_staking {
     pub fn transfer_authority(ctx: Context<TransferVaultAuthority>) -> Result<()> {
         instructions::update_vault::transfer_authority(ctx)
     }
+
+    /// Set the vault's slash authority and penalty destination (authority only)
+    pub fn configure_slashing(ctx: Context<ConfigureSlashing>, slash_authority: Pubkey) -> Result<()> {
+        instructions::update_vault::configure_slashing(ctx, slash_authority)
+    }
+
+    /// Configure the emergency exit grace period, early-exit flag and penalty (authority only)
+    pub fn configure_emergency_exit(
+        ctx: Context<ConfigureEmergencyExit>,
+        grace_period_seconds: i64,
+        early_exit_enabled: bool,
+        early_exit_penalty_bps: u16,
+    ) -> Result<()> {
+        instructions::update_vault::configure_emergency_exit(
+            ctx,
+            grace_period_seconds,
+            early_exit_enabled,
+            early_exit_penalty_bps,
+        )
+    }
+
+    /// Configure the vault's Pyth price feed and USD-normalized trust weights (authority only)
+    pub fn configure_price_feed(
+        ctx: Context<ConfigurePriceFeed>,
+        usd_normalized: bool,
+        max_price_age: i64,
+        price_fallback_to_raw: bool,
+    ) -> Result<()> {
+        instructions::update_vault::configure_price_feed(
+            ctx,
+            usd_normalized,
+            max_price_age,
+            price_fallback_to_raw,
+        )
+    }
+
+    /// Create the platform config holding the vault verifier
+    pub fn initialize_registry_config(
+        ctx: Context<InitializeRegistryConfig>,
+        verifier: Pubkey,
+        require_verified_vaults: bool,
+    ) -> Result<()> {
+        instructions::verification::initialize_registry_config(ctx, verifier, require_verified_vaults)
+    }
+
+    /// Update the vault verifier or verified-only staking mode (admin only)
+    pub fn update_registry_config(
+        ctx: Context<UpdateRegistryConfig>,
+        verifier: Option<Pubkey>,
+        require_verified_vaults: Option<bool>,
+    ) -> Result<()> {
+        instructions::verification::update_registry_config(ctx, verifier, require_verified_vaults)
+    }
+
+    /// Mark a vault as verified (verifier only)
+    pub fn verify_vault(ctx: Context<SetVaultVerification>, reason_hash: [u8; 32]) -> Result<()> {
+        instructions::verification::verify_vault(ctx, reason_hash)
+    }
+
+    /// Withdraw a vault's verification (verifier only)
+    pub fn unverify_vault(ctx: Context<SetVaultVerification>, reason_hash: [u8; 32]) -> Result<()> {
+        instructions::verification::unverify_vault(ctx, reason_hash)
+    }
+
+    /// Scale a vault's contribution to its agent's endorsement index (verifier only)
+    pub fn set_vault_normalization(ctx: Context<SetVaultNormalization>, normalization_bps: u16) -> Result<()> {
+        instructions::verification::set_vault_normalization(ctx, normalization_bps)
+    }
+
+    /// Create the protocol fee config (fee of at most 100 bps on stakes, optionally on unstakes)
+    pub fn initialize_protocol_config(
+        ctx: Context<InitializeProtocolConfig>,
+        treasury: Pubkey,
+        protocol_fee_bps: u16,
+        charge_unstake_fee: bool,
+    ) -> Result<()> {
+        instructions::protocol_fees::initialize_protocol_config(
+            ctx,
+            treasury,
+            protocol_fee_bps,
+            charge_unstake_fee,
+        )
+    }
+
+    /// Update the protocol treasury, fee rate, unstake fee flag or agent slash threshold (admin only)
+    pub fn update_protocol_config(
+        ctx: Context<UpdateProtocolConfig>,
+        treasury: Option<Pubkey>,
+        protocol_fee_bps: Option<u16>,
+        charge_unstake_fee: Option<bool>,
+        max_agent_slash_count: Option<u32>,
+    ) -> Result<()> {
+        instructions::protocol_fees::update_protocol_config(
+            ctx,
+            treasury,
+            protocol_fee_bps,
+            charge_unstake_fee,
+            max_agent_slash_count,
+        )
+    }
+
+    /// Sweep collected protocol fees of a mint to the treasury (admin only)
+    pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>, amount: Option<u64>) -> Result<()> {
+        instructions::protocol_fees::withdraw_protocol_fees(ctx, amount)
+    }
 }
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use gs2_common::AgentIdentityPrefix;
+
+use crate::error::TokenStakingError;
+
+/// identity_registry program; vault target agents must hold an AgentIdentity there.
+/// Rebuilt from gs2-common's bytes, since this program's anchor has its own Pubkey type.
+pub const IDENTITY_REGISTRY_PROGRAM_ID: Pubkey =
+    Pubkey::new_from_array(gs2_common::IDENTITY_REGISTRY_PROGRAM_ID.to_bytes());
+
+pub use gs2_common::IDENTITY_AGENT_SEED;
+
+/// Whether a vault's target agent is registered, active and not slashed too often
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AgentStanding {
+    pub is_active: bool,
+    pub slash_count: u32,
+}
+
+impl AgentStanding {
+    /// Read the standing of `agent` from its AgentIdentity PDA (seeds checked by the caller)
+    pub fn load(identity: &AccountInfo, agent: &Pubkey) -> Result<AgentStanding> {
+        require!(
+            identity.owner == &IDENTITY_REGISTRY_PROGRAM_ID && !identity.data_is_empty(),
+            TokenStakingError::AgentNotRegistered
+        );
+
+        let data = identity.try_borrow_data()?;
+        let agent_identity = <AgentIdentityPrefix as gs2_common::anchor_lang::AccountDeserialize>::try_deserialize(
+            &mut &data[..],
+        )
+        .map_err(|_| error!(TokenStakingError::AgentNotRegistered))?;
+        require!(
+            agent_identity.agent_address.to_bytes() == agent.to_bytes(),
+            TokenStakingError::AgentNotRegistered
+        );
+
+        Ok(AgentStanding {
+            is_active: agent_identity.is_active,
+            slash_count: agent_identity.slash_count,
+        })
+    }
+
+    /// Reject agents that are deactivated or have been slashed `max_slash_count` times or more
+    pub fn require_good_standing(&self, max_slash_count: u32) -> Result<()> {
+        require!(self.is_active, TokenStakingError::AgentInactive);
+        require!(
+            self.slash_count < max_slash_count,
+            TokenStakingError::AgentSlashLimitExceeded
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+
+use crate::state::StakingVault;
+
+/// Token-staked endorsement of an agent summed across all of its vaults
+/// PDA seeds: ["endorsement_index", target_agent]
+#[account]
+#[derive(InitSpace)]
+pub struct AgentEndorsementIndex {
+    /// The endorsed agent
+    pub target_agent: Pubkey,
+
+    /// Number of the agent's vaults counted in the index
+    pub vault_count: u32,
+
+    /// Sum of each vault's normalized aggregate trust weight
+    pub total_trust_weight: u64,
+
+    /// Sum of each vault's active stakers
+    pub total_stakers: u32,
+
+    /// Last update timestamp
+    pub updated_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl AgentEndorsementIndex {
+    pub const SEED_PREFIX: &'static [u8] = gs2_common::ENDORSEMENT_INDEX_SEED;
+
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // target_agent
+        4 +   // vault_count
+        8 +   // total_trust_weight
+        4 +   // total_stakers
+        8 +   // updated_at
+        1;    // bump
+
+    /// Fill in a freshly created index (no-op once initialized)
+    pub fn ensure_initialized(&mut self, target_agent: Pubkey, bump: u8) {
+        if self.target_agent == Pubkey::default() {
+            self.target_agent = target_agent;
+            self.bump = bump;
+        }
+    }
+
+    /// Replace what `vault` last contributed to the index with its current totals
+    ///
+    /// Must run after every change to the vault's trust weight, stakers or normalization.
+    pub fn sync_vault(&mut self, vault: &mut StakingVault, now: i64) {
+        if !vault.is_indexed {
+            self.vault_count = self.vault_count.saturating_add(1);
+            vault.is_indexed = true;
+        }
+
+        let trust_weight = vault.normalized_trust_weight();
+        self.total_trust_weight = self.total_trust_weight
+            .saturating_sub(vault.indexed_trust_weight)
+            .saturating_add(trust_weight);
+        self.total_stakers = self.total_stakers
+            .saturating_sub(vault.indexed_stakers)
+            .saturating_add(vault.total_stakers);
+        vault.indexed_trust_weight = trust_weight;
+        vault.indexed_stakers = vault.total_stakers;
+        self.updated_at = now;
+    }
+
+    /// Take a closing vault out of the index
+    pub fn remove_vault(&mut self, vault: &mut StakingVault, now: i64) {
+        if vault.is_indexed {
+            self.vault_count = self.vault_count.saturating_sub(1);
+            vault.is_indexed = false;
+        }
+        self.total_trust_weight = self.total_trust_weight.saturating_sub(vault.indexed_trust_weight);
+        self.total_stakers = self.total_stakers.saturating_sub(vault.indexed_stakers);
+        vault.indexed_trust_weight = 0;
+        vault.indexed_stakers = 0;
+        self.updated_at = now;
+    }
+}
+
+// gs2-common mirrors AgentEndorsementIndex for agent_profile and off-chain
+// clients. These fail to compile when the program ID, discriminator or a field
+// changes on one side only; the round-trip test below pins field order.
+const _: () = assert!(gs2_common::bytes_eq(
+    &crate::ID.to_bytes(),
+    &gs2_common::TOKEN_STAKING_PROGRAM_ID.to_bytes()
+));
+const _: () = assert!(gs2_common::bytes_eq(
+    &<AgentEndorsementIndex as anchor_lang::Discriminator>::DISCRIMINATOR,
+    <gs2_common::AgentEndorsementIndex as gs2_common::anchor_lang::Discriminator>::DISCRIMINATOR
+));
+
+impl From<&AgentEndorsementIndex> for gs2_common::AgentEndorsementIndex {
+    fn from(index: &AgentEndorsementIndex) -> Self {
+        let AgentEndorsementIndex {
+            target_agent,
+            vault_count,
+            total_trust_weight,
+            total_stakers,
+            updated_at,
+            bump,
+        } = index.clone();
+        gs2_common::AgentEndorsementIndex {
+            target_agent: gs2_common::anchor_lang::prelude::Pubkey::new_from_array(target_agent.to_bytes()),
+            vault_count,
+            total_trust_weight,
+            total_stakers,
+            updated_at,
+            bump,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gs2_common::anchor_lang::AccountDeserialize as _;
+
+    #[test]
+    fn gs2_common_reads_agent_endorsement_index() {
+        let index = AgentEndorsementIndex {
+            target_agent: Pubkey::new_unique(),
+            vault_count: 3,
+            total_trust_weight: 123_456_789,
+            total_stakers: 42,
+            updated_at: 1_700_000_000,
+            bump: 254,
+        };
+        let mut data = Vec::new();
+        index.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), AgentEndorsementIndex::LEN);
+
+        let mirror = gs2_common::AgentEndorsementIndex::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(mirror, gs2_common::AgentEndorsementIndex::from(&index));
+    }
+}
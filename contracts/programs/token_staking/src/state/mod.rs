@@ -1,5 +1,15 @@
 pub mod staking_vault;
 pub mod stake_position;
+pub mod registry_config;
+pub mod endorsement_index;
+pub mod price_feed;
+pub mod protocol_config;
+pub mod agent_identity;
 
 pub use staking_vault::*;
 pub use stake_position::*;
+pub use registry_config::*;
+pub use endorsement_index::*;
+pub use price_feed::*;
+pub use protocol_config::*;
+pub use agent_identity::*;
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::error::TokenStakingError;
+
+/// Pyth Solana Receiver program; owner of every PriceUpdateV2 account
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Anchor discriminator of the receiver's PriceUpdateV2 account
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Wormhole verification level of a posted price update
+#[derive(AnchorDeserialize)]
+enum VerificationLevel {
+    Partial { _num_signatures: u8 },
+    Full,
+}
+
+/// Leading fields of a Pyth PriceUpdateV2 account, up to the publish time.
+/// The EMA fields and posted slot are not needed, so they are not deserialized.
+#[derive(AnchorDeserialize)]
+struct PriceUpdatePrefix {
+    discriminator: [u8; 8],
+    _write_authority: Pubkey,
+    verification_level: VerificationLevel,
+    _feed_id: [u8; 32],
+    price: i64,
+    _conf: u64,
+    exponent: i32,
+    publish_time: i64,
+}
+
+/// A USD price read from a feed: one whole token is worth price * 10^exponent USD
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeedPrice {
+    pub price: i64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+impl FeedPrice {
+    /// Read the price from a fully verified Pyth PriceUpdateV2 account
+    pub fn load(feed: &AccountInfo) -> Result<FeedPrice> {
+        require_keys_eq!(*feed.owner, PYTH_RECEIVER_PROGRAM_ID, TokenStakingError::InvalidPriceFeed);
+
+        let data = feed.try_borrow_data()?;
+        let prefix = PriceUpdatePrefix::deserialize(&mut &data[..])
+            .map_err(|_| error!(TokenStakingError::InvalidPriceFeed))?;
+        require!(
+            prefix.discriminator == PRICE_UPDATE_V2_DISCRIMINATOR
+                && matches!(prefix.verification_level, VerificationLevel::Full),
+            TokenStakingError::InvalidPriceFeed
+        );
+
+        Ok(FeedPrice {
+            price: prefix.price,
+            exponent: prefix.exponent,
+            publish_time: prefix.publish_time,
+        })
+    }
+
+    /// Whether the price is positive and no older than `max_age` seconds at `now`
+    pub fn is_usable(&self, now: i64, max_age: i64) -> bool {
+        self.price > 0 && now.saturating_sub(self.publish_time) <= max_age
+    }
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::error::TokenStakingError;
+
+/// Protocol-wide token staking settings: fees and target agent eligibility
+/// PDA seeds: ["protocol_config"]
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolConfig {
+    /// Can change the settings and sweep collected fees
+    pub admin: Pubkey,
+
+    /// Wallet whose token accounts collected fees are swept to
+    pub treasury: Pubkey,
+
+    /// Fee on stakes, in bps of the staked amount (at most MAX_PROTOCOL_FEE_BPS)
+    pub protocol_fee_bps: u16,
+
+    /// Whether unstake_tokens is charged the same fee on the withdrawn amount
+    pub charge_unstake_fee: bool,
+
+    /// Agents slashed this many times or more cannot get new vaults or stakes
+    pub max_agent_slash_count: u32,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ProtocolConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"protocol_config";
+    /// Per-mint fee token account, owned by the config PDA
+    pub const FEE_ACCOUNT_SEED: &'static [u8] = b"protocol_fees";
+
+    /// 1% fee ceiling
+    pub const MAX_PROTOCOL_FEE_BPS: u16 = 100;
+
+    /// Slash threshold used until the config is initialized
+    pub const DEFAULT_MAX_AGENT_SLASH_COUNT: u32 = 3;
+
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // admin
+        32 +  // treasury
+        2 +   // protocol_fee_bps
+        1 +   // charge_unstake_fee
+        4 +   // max_agent_slash_count
+        1;    // bump
+
+    /// Read the (possibly uninitialized) config PDA; None until it is initialized
+    pub fn load(info: &AccountInfo) -> Result<Option<ProtocolConfig>> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(None);
+        }
+        let data = info.try_borrow_data()?;
+        Ok(Some(ProtocolConfig::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Fee on `amount`, rounded down (so stakes too small to owe a whole base unit pay none)
+    pub fn fee_on(&self, amount: u64) -> Result<u64> {
+        let fee = (amount as u128)
+            .checked_mul(self.protocol_fee_bps as u128)
+            .ok_or(TokenStakingError::ArithmeticOverflow)?
+            / 10_000;
+        u64::try_from(fee).map_err(|_| TokenStakingError::ArithmeticOverflow.into())
+    }
+
+    /// Slash count at which target agents are rejected; the default until the config is initialized
+    pub fn max_agent_slash_count(info: &AccountInfo) -> Result<u32> {
+        Ok(ProtocolConfig::load(info)?
+            .map_or(ProtocolConfig::DEFAULT_MAX_AGENT_SLASH_COUNT, |config| config.max_agent_slash_count))
+    }
+
+    /// Fee owed on a stake of `amount`; zero until the config is initialized
+    pub fn stake_fee(info: &AccountInfo, amount: u64) -> Result<u64> {
+        match ProtocolConfig::load(info)? {
+            Some(config) => config.fee_on(amount),
+            None => Ok(0),
+        }
+    }
+
+    /// Fee owed on an unstake of `amount`; zero unless unstake fees are enabled
+    pub fn unstake_fee(info: &AccountInfo, amount: u64) -> Result<u64> {
+        match ProtocolConfig::load(info)? {
+            Some(config) if config.charge_unstake_fee => config.fee_on(amount),
+            _ => Ok(0),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+/// Platform-wide token staking settings
+/// PDA seeds: ["registry_config"]
+#[account]
+#[derive(InitSpace)]
+pub struct VaultRegistryConfig {
+    /// Can change the verifier and staking mode
+    pub admin: Pubkey,
+
+    /// Can verify and unverify vaults
+    pub verifier: Pubkey,
+
+    /// Whether stake_tokens only accepts stakes into verified vaults
+    pub require_verified_vaults: bool,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl VaultRegistryConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"registry_config";
+
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // admin
+        32 +  // verifier
+        1 +   // require_verified_vaults
+        1;    // bump
+
+    /// Read the (possibly uninitialized) config PDA; None until it is initialized
+    pub fn load(info: &AccountInfo) -> Result<Option<VaultRegistryConfig>> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(None);
+        }
+        let data = info.try_borrow_data()?;
+        Ok(Some(VaultRegistryConfig::try_deserialize(&mut &data[..])?))
+    }
+}
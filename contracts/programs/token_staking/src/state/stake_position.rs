@@ -10,6 +10,16 @@ pub enum StakeCategory {
     Security,       // Security best practices
 }
 
+impl StakeCategory {
+    /// Number of categories (size of the vault's per-category buckets)
+    pub const COUNT: usize = 5;
+
+    /// Bucket index of this category
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
 impl Default for StakeCategory {
     fn default() -> Self {
         Self::General
@@ -59,6 +69,36 @@ pub struct StakePosition {
 
     /// PDA bump
     pub bump: u8,
+
+    /// Vault reward_per_token_stored when this position's rewards were last settled
+    pub reward_per_token_paid: u128,
+
+    /// Rewards settled but not yet claimed
+    pub rewards_accrued: u64,
+
+    /// Rewards claimed over the position's lifetime
+    pub rewards_claimed: u64,
+
+    /// Amount requested for withdrawal in a cooldown-mode vault (still slashable)
+    pub pending_unstake_amount: u64,
+
+    /// When the pending unstake can be withdrawn (0 if none)
+    pub unstake_available_at: i64,
+
+    /// Lock duration chosen at the latest stake (at least the vault's lock period)
+    pub lock_duration: i64,
+
+    /// Trust-weight boost of the chosen lock's tier (10_000 = 1x)
+    pub lock_boost_bps: u16,
+
+    /// Wallet that funded a position staked on the staker's behalf (default pubkey: self-staked)
+    pub funder: Pubkey,
+
+    /// Wallet the funder allows to top up a delegated position (default pubkey: none)
+    pub operator: Pubkey,
+
+    /// Token account a delegated position's unstaked tokens are returned to
+    pub withdrawal_address: Pubkey,
 }
 
 impl StakePosition {
@@ -77,7 +117,56 @@ impl StakePosition {
         8 +   // unstaked_at
         1 +   // is_active
         1 +   // is_slashed
-        1;    // bump
+        1 +   // bump
+        16 +  // reward_per_token_paid
+        8 +   // rewards_accrued
+        8 +   // rewards_claimed
+        8 +   // pending_unstake_amount
+        8 +   // unstake_available_at
+        8 +   // lock_duration
+        2 +   // lock_boost_bps
+        32 +  // funder
+        32 +  // operator
+        32;   // withdrawal_address
+
+    /// Whether the position was staked on the staker's behalf by a funder
+    pub fn is_delegated(&self) -> bool {
+        self.funder != Pubkey::default()
+    }
+
+    /// Make the position self-staked again
+    pub fn clear_delegation(&mut self) {
+        self.funder = Pubkey::default();
+        self.operator = Pubkey::default();
+        self.withdrawal_address = Pubkey::default();
+    }
+
+    /// Whether `signer` may top up this delegated position (its funder or operator)
+    pub fn can_top_up(&self, signer: &Pubkey) -> bool {
+        self.is_delegated()
+            && (*signer == self.funder
+                || (self.operator != Pubkey::default() && *signer == self.operator))
+    }
+
+    /// Whether withdrawn tokens may be sent to `token_account` owned by `owner`:
+    /// a delegated position's recorded withdrawal address, otherwise any account of the staker
+    pub fn is_withdrawal_account(&self, token_account: &Pubkey, owner: &Pubkey) -> bool {
+        if self.is_delegated() {
+            *token_account == self.withdrawal_address
+        } else {
+            *owner == self.staker
+        }
+    }
+
+    /// Amount that still counts toward trust weight (excludes a pending unstake)
+    pub fn active_amount(&self) -> u64 {
+        self.amount.saturating_sub(self.pending_unstake_amount)
+    }
+
+    /// Check if the pending unstake has cleared its cooldown
+    pub fn can_withdraw_unstaked(&self, current_timestamp: i64) -> bool {
+        self.pending_unstake_amount > 0 && current_timestamp >= self.unstake_available_at
+    }
 
     /// Check if the stake can be unlocked
     pub fn can_unlock(&self, current_timestamp: i64) -> bool {
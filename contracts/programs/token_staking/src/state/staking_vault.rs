@@ -1,5 +1,22 @@
 use anchor_lang::prelude::*;
 
+use crate::error::TokenStakingError;
+use crate::state::{FeedPrice, StakeCategory, StakePosition};
+
+/// A lock duration and the trust-weight boost it earns
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub struct LockTier {
+    /// Minimum lock duration in seconds to reach this tier
+    pub lock_seconds: i64,
+
+    /// Trust-weight boost in basis points (10_000 = 1x)
+    pub boost_bps: u16,
+}
+
+impl LockTier {
+    pub const LEN: usize = 8 + 2;
+}
+
 /// Configuration for a staking vault (registered SPL token)
 /// One vault per target agent per token
 /// PDA seeds: ["vault", target_agent, token_mint]
@@ -50,11 +67,130 @@ pub struct StakingVault {
 
     /// Vault token account bump
     pub vault_bump: u8,
+
+    /// Signer allowed to slash stake positions (default pubkey: slashing disabled)
+    pub slash_authority: Pubkey,
+
+    /// Token account of the vault mint that slashed tokens are sent to
+    pub penalty_destination: Pubkey,
+
+    /// Total tokens slashed from positions in this vault
+    pub total_slashed: u64,
+
+    /// Reward token account (PDA-controlled, created by the first fund_rewards)
+    pub reward_token_account: Pubkey,
+
+    /// Reward tokens distributed per second, scaled by REWARD_PRECISION
+    pub reward_rate: u128,
+
+    /// When the current reward period ends
+    pub reward_period_end: i64,
+
+    /// When reward_per_token_stored was last brought up to date
+    pub reward_updated_at: i64,
+
+    /// Rewards accrued per staked token unit since the vault opened, scaled by REWARD_PRECISION
+    pub reward_per_token_stored: u128,
+
+    /// Total reward tokens funded
+    pub total_rewards_funded: u64,
+
+    /// Reward token account bump
+    pub reward_bump: u8,
+
+    /// Hash of the off-chain reason for the latest verify/unverify
+    pub verification_reason_hash: [u8; 32],
+
+    /// When the vault was last verified or unverified (0 if never)
+    pub verification_updated_at: i64,
+
+    /// Whether exits go through request_unstake / withdraw_unstaked instead of
+    /// a lock set at stake time (chosen at initialization)
+    pub cooldown_mode: bool,
+
+    /// Seconds between request_unstake and withdraw_unstaked in cooldown mode
+    pub unstake_cooldown_seconds: i64,
+
+    /// Tokens staked per StakeCategory (indexed by StakeCategory::index); sums to total_staked
+    pub category_staked: [u64; StakeCategory::COUNT],
+
+    /// Active stakers per StakeCategory; sums to total_stakers
+    pub category_stakers: [u32; StakeCategory::COUNT],
+
+    /// Cap on total_staked (0: unlimited)
+    pub max_total_staked: u64,
+
+    /// Cap on a single position's amount (0: unlimited)
+    pub max_per_staker: u64,
+
+    /// When the vault was last paused (0 while active)
+    pub paused_at: i64,
+
+    /// Seconds a vault must stay paused before stakers may emergency_withdraw
+    /// (0: DEFAULT_EMERGENCY_GRACE_PERIOD)
+    pub emergency_grace_period: i64,
+
+    /// Whether the authority has opened emergency_withdraw regardless of pause state
+    pub early_exit_enabled: bool,
+
+    /// Share of an emergency withdrawal sent to the penalty destination
+    pub early_exit_penalty_bps: u16,
+
+    /// Sum of the trust weights of the vault's active positions
+    pub total_trust_weight: u64,
+
+    /// Scale applied to total_trust_weight in the agent's endorsement index
+    /// (10_000 = 1x; set by the verifier so no single mint dominates)
+    pub normalization_bps: u16,
+
+    /// Whether the vault is counted in the agent's endorsement index
+    pub is_indexed: bool,
+
+    /// Normalized trust weight last added to the agent's endorsement index
+    pub indexed_trust_weight: u64,
+
+    /// Stakers last added to the agent's endorsement index
+    pub indexed_stakers: u32,
+
+    /// Lock tiers in ascending lock order; only the first lock_tier_count are in use
+    pub lock_tiers: [LockTier; StakingVault::MAX_LOCK_TIERS],
+
+    /// Number of lock tiers in use (0: no lock boosts)
+    pub lock_tier_count: u8,
+
+    /// Pyth PriceUpdateV2 account pricing the vault token in USD (default pubkey: none)
+    pub price_feed: Pubkey,
+
+    /// Whether trust weights are computed from the micro-USD value of a stake
+    pub usd_normalized: bool,
+
+    /// Oldest price stake_tokens accepts, in seconds (0: DEFAULT_MAX_PRICE_AGE)
+    pub max_price_age: i64,
+
+    /// Whether a stale or non-positive price falls back to raw token units
+    /// instead of rejecting the stake
+    pub price_fallback_to_raw: bool,
+
+    /// Decimals of the vault token mint
+    pub token_decimals: u8,
+
+    /// Latest usable price read from the feed (0: none, weights use raw token units)
+    pub last_price: i64,
+
+    /// Exponent of last_price
+    pub last_price_exponent: i32,
+
+    /// Publish time of last_price
+    pub last_price_published_at: i64,
+
+    /// Layout version (see CURRENT_VERSION); 0 on vaults migrate_account has not upgraded
+    pub version: u8,
 }
 
 impl StakingVault {
     pub const SEED_PREFIX: &'static [u8] = b"vault";
     pub const VAULT_TOKEN_SEED: &'static [u8] = b"vault_token";
+    pub const REWARD_TOKEN_SEED: &'static [u8] = b"reward_token";
 
     /// Default minimum stake (1 token, assuming 6 decimals)
     pub const DEFAULT_MIN_STAKE: u64 = 1_000_000;
@@ -81,12 +217,620 @@ impl StakingVault {
         8 +   // created_at
         8 +   // updated_at
         1 +   // bump
-        1;    // vault_bump
+        1 +   // vault_bump
+        32 +  // slash_authority
+        32 +  // penalty_destination
+        8 +   // total_slashed
+        32 +  // reward_token_account
+        16 +  // reward_rate
+        8 +   // reward_period_end
+        8 +   // reward_updated_at
+        16 +  // reward_per_token_stored
+        8 +   // total_rewards_funded
+        1 +   // reward_bump
+        32 +  // verification_reason_hash
+        8 +   // verification_updated_at
+        1 +   // cooldown_mode
+        8 +   // unstake_cooldown_seconds
+        8 * StakeCategory::COUNT +  // category_staked
+        4 * StakeCategory::COUNT +  // category_stakers
+        8 +   // max_total_staked
+        8 +   // max_per_staker
+        8 +   // paused_at
+        8 +   // emergency_grace_period
+        1 +   // early_exit_enabled
+        2 +   // early_exit_penalty_bps
+        8 +   // total_trust_weight
+        2 +   // normalization_bps
+        1 +   // is_indexed
+        8 +   // indexed_trust_weight
+        4 +   // indexed_stakers
+        LockTier::LEN * Self::MAX_LOCK_TIERS +  // lock_tiers
+        1 +   // lock_tier_count
+        32 +  // price_feed
+        1 +   // usd_normalized
+        8 +   // max_price_age
+        1 +   // price_fallback_to_raw
+        1 +   // token_decimals
+        8 +   // last_price
+        4 +   // last_price_exponent
+        8 +   // last_price_published_at
+        1;    // version
+
+    /// Size of vaults created before the version field was added
+    pub const PRE_VERSION_LEN: usize = Self::LEN - 1;
+
+    /// Size of vaults created before the per-category buckets and the fields after them
+    pub const LEGACY_LEN: usize = Self::PRE_VERSION_LEN
+        - 8 * StakeCategory::COUNT - 4 * StakeCategory::COUNT  // category buckets
+        - 8 - 8                  // stake caps
+        - 8 - 8 - 1 - 2          // emergency exit
+        - 8 - 2 - 1 - 8 - 4      // endorsement index
+        - LockTier::LEN * Self::MAX_LOCK_TIERS - 1  // lock tiers
+        - 32 - 1 - 8 - 1 - 1 - 8 - 4 - 8;          // price feed
+
+    /// Layout version written by initialize_vault and migrate_account
+    pub const CURRENT_VERSION: u8 = 1;
+
+    /// Oldest layout version the handlers accept; older vaults must call migrate_account
+    pub const MIN_SUPPORTED_VERSION: u8 = 1;
+
+    /// Pause length after which stakers may emergency_withdraw, unless configured (30 days)
+    pub const DEFAULT_EMERGENCY_GRACE_PERIOD: i64 = 30 * 24 * 60 * 60;
+
+    /// Largest early-exit penalty (20%), so a hostile authority cannot confiscate stakes
+    pub const MAX_EARLY_EXIT_PENALTY_BPS: u16 = 2_000;
+
+    /// Unscaled normalization (1x)
+    pub const NORMALIZATION_ONE: u16 = 10_000;
+
+    /// Maximum number of lock tiers
+    pub const MAX_LOCK_TIERS: usize = 4;
+
+    /// Unboosted trust weight (1x)
+    pub const LOCK_BOOST_ONE: u16 = 10_000;
+
+    /// Largest lock-tier boost (3x)
+    pub const MAX_LOCK_BOOST_BPS: u16 = 30_000;
+
+    /// Price age accepted when max_price_age is 0 (60 seconds)
+    pub const DEFAULT_MAX_PRICE_AGE: i64 = 60;
+
+    /// Largest configurable max_price_age (1 day)
+    pub const MAX_PRICE_AGE: i64 = 24 * 60 * 60;
+
+    /// Maximum reward period (365 days)
+    pub const MAX_REWARD_PERIOD: i64 = 365 * 24 * 60 * 60;
+
+    /// Time after reward_period_end left for stakers to claim before close_vault
+    /// may sweep the reward account to the authority (30 days)
+    pub const REWARD_CLAIM_GRACE_PERIOD: i64 = 30 * 24 * 60 * 60;
+
+    /// Fixed-point scale of reward_rate and reward_per_token_stored
+    pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+    /// Largest slash severity (100% of the position)
+    pub const MAX_SLASH_BPS: u16 = 10_000;
+
+    /// Whether the handlers accept this vault's layout version
+    pub fn is_supported_version(&self) -> bool {
+        self.version >= Self::MIN_SUPPORTED_VERSION
+    }
+
+    /// Whether a slash authority has been configured
+    pub fn slashing_enabled(&self) -> bool {
+        self.slash_authority != Pubkey::default()
+    }
+
+    /// Tokens a slash of `severity_bps` removes from a position of `amount`.
+    /// Severities above MAX_SLASH_BPS take the whole position, never more.
+    pub fn calculate_slash_amount(amount: u64, severity_bps: u16) -> u64 {
+        let severity_bps = severity_bps.min(Self::MAX_SLASH_BPS);
+        ((amount as u128) * (severity_bps as u128) / (Self::MAX_SLASH_BPS as u128)) as u64
+    }
+
+    /// Set a position's trust weight, keeping total_trust_weight in step
+    pub fn set_position_weight(&mut self, position: &mut StakePosition, trust_weight: u64) {
+        self.total_trust_weight = self.total_trust_weight
+            .saturating_sub(position.trust_weight)
+            .saturating_add(trust_weight);
+        position.trust_weight = trust_weight;
+    }
+
+    /// Lock tiers in use
+    pub fn active_lock_tiers(&self) -> &[LockTier] {
+        &self.lock_tiers[..self.lock_tier_count as usize]
+    }
+
+    /// Boost of the highest tier `lock_duration` reaches (1x below the first tier)
+    pub fn lock_boost_bps(&self, lock_duration: i64) -> u16 {
+        self.active_lock_tiers()
+            .iter()
+            .rev()
+            .find(|tier| lock_duration >= tier.lock_seconds)
+            .map_or(Self::LOCK_BOOST_ONE, |tier| tier.boost_bps)
+    }
+
+    /// Check a tier table: ascending locks within MAX_LOCK_PERIOD and
+    /// non-decreasing boosts between 1x and MAX_LOCK_BOOST_BPS
+    pub fn validate_lock_tiers(tiers: &[LockTier]) -> Result<()> {
+        require!(tiers.len() <= Self::MAX_LOCK_TIERS, TokenStakingError::InvalidLockTiers);
+        for tier in tiers {
+            require!(
+                tier.lock_seconds > 0
+                    && tier.lock_seconds <= Self::MAX_LOCK_PERIOD
+                    && (Self::LOCK_BOOST_ONE..=Self::MAX_LOCK_BOOST_BPS).contains(&tier.boost_bps),
+                TokenStakingError::InvalidLockTiers
+            );
+        }
+        for pair in tiers.windows(2) {
+            require!(
+                pair[0].lock_seconds < pair[1].lock_seconds && pair[0].boost_bps <= pair[1].boost_bps,
+                TokenStakingError::InvalidLockTiers
+            );
+        }
+        Ok(())
+    }
+
+    /// Trust weight of `amount` held by `position`, boosted by its lock tier
+    pub fn position_trust_weight(&self, position: &StakePosition, amount: u64) -> u64 {
+        let boost_bps = position.lock_boost_bps.max(Self::LOCK_BOOST_ONE);
+        ((self.calculate_trust_weight(amount) as u128 * boost_bps as u128)
+            / Self::LOCK_BOOST_ONE as u128) as u64
+    }
+
+    /// Oldest price age accepted at stake time
+    pub fn effective_max_price_age(&self) -> i64 {
+        if self.max_price_age > 0 {
+            self.max_price_age
+        } else {
+            Self::DEFAULT_MAX_PRICE_AGE
+        }
+    }
+
+    /// Refresh last_price from the feed before a stake is weighed (no-op unless usd_normalized)
+    ///
+    /// A stale or non-positive price is rejected, or with price_fallback_to_raw clears
+    /// last_price so weights use raw token units until a usable price is read again.
+    pub fn observe_price(&mut self, feed: Option<&AccountInfo>, now: i64) -> Result<()> {
+        if !self.usd_normalized {
+            return Ok(());
+        }
+        let feed = feed.ok_or(TokenStakingError::MissingPriceFeed)?;
+        let price = FeedPrice::load(feed)?;
+
+        if price.is_usable(now, self.effective_max_price_age()) {
+            self.last_price = price.price;
+            self.last_price_exponent = price.exponent;
+            self.last_price_published_at = price.publish_time;
+            return Ok(());
+        }
+
+        require!(price.price > 0 || self.price_fallback_to_raw, TokenStakingError::InvalidPrice);
+        require!(self.price_fallback_to_raw, TokenStakingError::StalePrice);
+        self.last_price = 0;
+        msg!("Price unusable (price {}, published {}); weighing raw token units", price.price, price.publish_time);
+        Ok(())
+    }
+
+    /// Micro-USD value of `amount` base units of a token with `decimals`,
+    /// priced at price * 10^exponent USD per whole token (saturating at u64::MAX)
+    pub fn micro_usd_value(amount: u64, price: i64, exponent: i32, decimals: u8) -> u64 {
+        let value = amount as u128 * price.max(0) as u128;
+        let shift = exponent as i64 + 6 - decimals as i64;
+        let scaled = if shift >= 0 {
+            10u128.checked_pow(shift as u32).and_then(|scale| value.checked_mul(scale))
+        } else {
+            Some(10u128.checked_pow(shift.unsigned_abs() as u32).map_or(0, |scale| value / scale))
+        };
+        scaled.map_or(u64::MAX, |micro_usd| u64::try_from(micro_usd).unwrap_or(u64::MAX))
+    }
+
+    /// Quantity the trust-weight curve is applied to: the micro-USD value of
+    /// `amount` when USD-normalized with a usable last price, otherwise `amount`
+    pub fn weight_basis(&self, amount: u64) -> u64 {
+        if self.usd_normalized && self.last_price > 0 {
+            Self::micro_usd_value(amount, self.last_price, self.last_price_exponent, self.token_decimals)
+        } else {
+            amount
+        }
+    }
+
+    /// Aggregate trust weight scaled by normalization_bps
+    pub fn normalized_trust_weight(&self) -> u64 {
+        ((self.total_trust_weight as u128 * self.normalization_bps as u128)
+            / Self::NORMALIZATION_ONE as u128) as u64
+    }
+
+    /// Whether stakers may currently leave through emergency_withdraw
+    pub fn emergency_exit_open(&self, now: i64) -> bool {
+        if self.early_exit_enabled {
+            return true;
+        }
+        let grace_period = if self.emergency_grace_period > 0 {
+            self.emergency_grace_period
+        } else {
+            Self::DEFAULT_EMERGENCY_GRACE_PERIOD
+        };
+        !self.is_active && now >= self.paused_at.saturating_add(grace_period)
+    }
+
+    /// Check a stake of `amount` bringing a position to `position_total` against the vault caps
+    pub fn check_stake_caps(&self, amount: u64, position_total: u64) -> Result<()> {
+        if self.max_total_staked > 0 {
+            let new_total = self.total_staked
+                .checked_add(amount)
+                .ok_or(TokenStakingError::ArithmeticOverflow)?;
+            require!(new_total <= self.max_total_staked, TokenStakingError::VaultAtCapacity);
+        }
+        if self.max_per_staker > 0 {
+            require!(position_total <= self.max_per_staker, TokenStakingError::StakerCapExceeded);
+        }
+        Ok(())
+    }
+
+    /// Add `amount` to a category bucket, counting a new staker in it when `joins`
+    pub fn credit_category(&mut self, category: StakeCategory, amount: u64, joins: bool) -> Result<()> {
+        let index = category.index();
+        self.category_staked[index] = self.category_staked[index]
+            .checked_add(amount)
+            .ok_or(TokenStakingError::ArithmeticOverflow)?;
+        if joins {
+            self.category_stakers[index] = self.category_stakers[index].saturating_add(1);
+        }
+        Ok(())
+    }
+
+    /// Remove `amount` from a category bucket, dropping a staker from it when `leaves`
+    pub fn debit_category(&mut self, category: StakeCategory, amount: u64, leaves: bool) -> Result<()> {
+        let index = category.index();
+        self.category_staked[index] = self.category_staked[index]
+            .checked_sub(amount)
+            .ok_or(TokenStakingError::ArithmeticOverflow)?;
+        if leaves {
+            self.category_stakers[index] = self.category_stakers[index].saturating_sub(1);
+        }
+        Ok(())
+    }
+
+    /// Rewards accrued per staked token unit up to `now` (capped at the period end)
+    ///
+    /// Time during which nothing is staked distributes nothing.
+    pub fn reward_per_token(&self, now: i64) -> Result<u128> {
+        let applicable_until = now.min(self.reward_period_end);
+        if self.total_staked == 0 || applicable_until <= self.reward_updated_at {
+            return Ok(self.reward_per_token_stored);
+        }
+
+        let elapsed = (applicable_until - self.reward_updated_at) as u128;
+        let accrued = elapsed
+            .checked_mul(self.reward_rate)
+            .ok_or(TokenStakingError::ArithmeticOverflow)?
+            / self.total_staked as u128;
+        Ok(self
+            .reward_per_token_stored
+            .checked_add(accrued)
+            .ok_or(TokenStakingError::ArithmeticOverflow)?)
+    }
+
+    /// Bring reward_per_token_stored up to `now`
+    pub fn update_reward_per_token(&mut self, now: i64) -> Result<()> {
+        self.reward_per_token_stored = self.reward_per_token(now)?;
+        self.reward_updated_at = now;
+        Ok(())
+    }
+
+    /// Bring the vault accumulator up to `now` and move what `position` earned
+    /// since it was last settled into its rewards_accrued
+    ///
+    /// Must run before the position's amount or the vault's total_staked change.
+    pub fn settle_rewards(&mut self, position: &mut StakePosition, now: i64) -> Result<()> {
+        self.update_reward_per_token(now)?;
+
+        let per_token = self.reward_per_token_stored - position.reward_per_token_paid;
+        let earned = (position.amount as u128)
+            .checked_mul(per_token)
+            .ok_or(TokenStakingError::ArithmeticOverflow)?
+            / Self::REWARD_PRECISION;
+        let earned = u64::try_from(earned).map_err(|_| TokenStakingError::ArithmeticOverflow)?;
+
+        position.rewards_accrued = position.rewards_accrued
+            .checked_add(earned)
+            .ok_or(TokenStakingError::ArithmeticOverflow)?;
+        position.reward_per_token_paid = self.reward_per_token_stored;
+        Ok(())
+    }
+
+    /// Fractional bits of the fixed-point log2 behind trust weights
+    pub const LOG2_FRACTION_BITS: u32 = 8;
 
     /// Calculate trust weight from staked amount
-    /// Uses log2(amount + 1) * multiplier for diminishing returns
+    /// Uses log2(amount + 1) * multiplier for diminishing returns:
+    /// weight = floor(log2(amount + 1) * 100) * weight_multiplier / 100,
+    /// with log2 in integer fixed point so every validator computes the same value
+    /// (at most 1 below the exact base weight before the multiplier).
+    /// USD-normalized vaults apply the curve to the stake's micro-USD value instead.
     pub fn calculate_trust_weight(&self, amount: u64) -> u64 {
-        let base_weight = (((amount as f64) + 1.0).log2() * 100.0) as u64;
+        let log2 = Self::log2_fixed(self.weight_basis(amount) as u128 + 1);
+        let base_weight = (log2 * 100) >> Self::LOG2_FRACTION_BITS;
         (base_weight * self.weight_multiplier as u64) / 100
     }
+
+    /// log2(x) rounded down to LOG2_FRACTION_BITS fractional bits (x >= 1)
+    ///
+    /// The integer part is the index of the highest set bit. The mantissa is then
+    /// normalized to [1, 2) with 63 fractional bits and squared once per fractional
+    /// bit: a square of 2 or more sets that bit and is halved back into range.
+    pub fn log2_fixed(x: u128) -> u64 {
+        let integer = 127 - x.leading_zeros();
+        let mut mantissa = if integer >= 63 {
+            x >> (integer - 63)
+        } else {
+            x << (63 - integer)
+        };
+
+        let mut log2 = (integer as u64) << Self::LOG2_FRACTION_BITS;
+        for bit in (0..Self::LOG2_FRACTION_BITS).rev() {
+            mantissa = (mantissa * mantissa) >> 63;
+            if mantissa >= 1 << 64 {
+                log2 |= 1 << bit;
+                mantissa >>= 1;
+            }
+        }
+        log2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gs2_common::test_utils::u64_samples;
+
+    const MULTIPLIERS: [u16; 5] = [10, 99, 100, 250, 1000];
+
+    fn vault(weight_multiplier: u16) -> StakingVault {
+        StakingVault {
+            target_agent: Pubkey::default(),
+            token_mint: Pubkey::default(),
+            vault_token_account: Pubkey::default(),
+            min_stake_amount: StakingVault::DEFAULT_MIN_STAKE,
+            lock_period_seconds: StakingVault::DEFAULT_LOCK_PERIOD,
+            weight_multiplier,
+            total_staked: 0,
+            total_stakers: 0,
+            authority: Pubkey::default(),
+            is_active: true,
+            is_verified: false,
+            created_at: 0,
+            updated_at: 0,
+            bump: 0,
+            vault_bump: 0,
+            slash_authority: Pubkey::default(),
+            penalty_destination: Pubkey::default(),
+            total_slashed: 0,
+            reward_token_account: Pubkey::default(),
+            reward_rate: 0,
+            reward_period_end: 0,
+            reward_updated_at: 0,
+            reward_per_token_stored: 0,
+            total_rewards_funded: 0,
+            reward_bump: 0,
+            verification_reason_hash: [0; 32],
+            verification_updated_at: 0,
+            cooldown_mode: false,
+            unstake_cooldown_seconds: 0,
+            category_staked: [0; StakeCategory::COUNT],
+            category_stakers: [0; StakeCategory::COUNT],
+            max_total_staked: 0,
+            max_per_staker: 0,
+            paused_at: 0,
+            emergency_grace_period: 0,
+            early_exit_enabled: false,
+            early_exit_penalty_bps: 0,
+            total_trust_weight: 0,
+            normalization_bps: StakingVault::NORMALIZATION_ONE,
+            is_indexed: false,
+            indexed_trust_weight: 0,
+            indexed_stakers: 0,
+            lock_tiers: [LockTier::default(); StakingVault::MAX_LOCK_TIERS],
+            lock_tier_count: 0,
+            price_feed: Pubkey::default(),
+            usd_normalized: false,
+            max_price_age: 0,
+            price_fallback_to_raw: false,
+            token_decimals: 6,
+            last_price: 0,
+            last_price_exponent: 0,
+            last_price_published_at: 0,
+            version: StakingVault::CURRENT_VERSION,
+        }
+    }
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    fn tiered_vault() -> StakingVault {
+        let mut vault = vault(100);
+        let tiers = [
+            LockTier { lock_seconds: 30 * DAY, boost_bps: 10_000 },
+            LockTier { lock_seconds: 90 * DAY, boost_bps: 15_000 },
+            LockTier { lock_seconds: 365 * DAY, boost_bps: 25_000 },
+        ];
+        vault.lock_tiers[..tiers.len()].copy_from_slice(&tiers);
+        vault.lock_tier_count = tiers.len() as u8;
+        vault
+    }
+
+    /// The previous f64 implementation
+    fn reference_weight(amount: u64, weight_multiplier: u16) -> u64 {
+        let base_weight = (((amount as f64) + 1.0).log2() * 100.0) as u64;
+        (base_weight * weight_multiplier as u64) / 100
+    }
+
+    fn base_weight(amount: u64) -> u64 {
+        vault(100).calculate_trust_weight(amount)
+    }
+
+    /// Amounts at, just below and just above every power of two
+    fn powers_of_two() -> impl Iterator<Item = u64> {
+        (0..64).flat_map(|k| {
+            let power = 1u64 << k;
+            [power - 1, power, power + 1]
+        })
+    }
+
+    #[test]
+    fn lock_boost_uses_highest_reached_tier() {
+        let tiered = tiered_vault();
+        assert_eq!(tiered.lock_boost_bps(7 * DAY), 10_000);
+        assert_eq!(tiered.lock_boost_bps(30 * DAY), 10_000);
+        assert_eq!(tiered.lock_boost_bps(90 * DAY - 1), 10_000);
+        assert_eq!(tiered.lock_boost_bps(90 * DAY), 15_000);
+        assert_eq!(tiered.lock_boost_bps(365 * DAY), 25_000);
+        assert_eq!(vault(100).lock_boost_bps(365 * DAY), StakingVault::LOCK_BOOST_ONE);
+    }
+
+    #[test]
+    fn lock_tiers_must_ascend_within_bounds() {
+        let tier = |days: i64, boost_bps: u16| LockTier { lock_seconds: days * DAY, boost_bps };
+        assert!(StakingVault::validate_lock_tiers(&[]).is_ok());
+        assert!(StakingVault::validate_lock_tiers(&tiered_vault().lock_tiers[..3]).is_ok());
+        assert!(StakingVault::validate_lock_tiers(&[tier(90, 15_000), tier(30, 10_000)]).is_err());
+        assert!(StakingVault::validate_lock_tiers(&[tier(30, 15_000), tier(90, 10_000)]).is_err());
+        assert!(StakingVault::validate_lock_tiers(&[tier(30, 9_999)]).is_err());
+        assert!(StakingVault::validate_lock_tiers(&[tier(30, 30_001)]).is_err());
+        assert!(StakingVault::validate_lock_tiers(&[tier(366, 20_000)]).is_err());
+        assert!(StakingVault::validate_lock_tiers(&[tier(1, 10_000); 5]).is_err());
+    }
+
+    #[test]
+    fn micro_usd_value_scales_by_price_exponent_and_decimals() {
+        // 10 tokens (6 decimals) at $2.50 (250_000_000 * 10^-8) = $25
+        assert_eq!(StakingVault::micro_usd_value(10_000_000, 250_000_000, -8, 6), 25_000_000);
+        // 1 token (9 decimals) at $150 (15_000 * 10^-2)
+        assert_eq!(StakingVault::micro_usd_value(1_000_000_000, 15_000, -2, 9), 150_000_000);
+        // Positive exponents and sub-micro-dollar values
+        assert_eq!(StakingVault::micro_usd_value(1, 3, 2, 0), 300_000_000);
+        assert_eq!(StakingVault::micro_usd_value(1, 1, -8, 6), 0);
+        assert_eq!(StakingVault::micro_usd_value(u64::MAX, i64::MAX, 10, 0), u64::MAX);
+        assert_eq!(StakingVault::micro_usd_value(5, -1, 0, 0), 0);
+    }
+
+    #[test]
+    fn usd_normalized_weight_uses_last_price() {
+        let mut normalized = vault(100);
+        normalized.usd_normalized = true;
+        // No usable price yet: raw token units
+        assert_eq!(normalized.calculate_trust_weight(10_000_000), base_weight(10_000_000));
+
+        normalized.last_price = 250_000_000;
+        normalized.last_price_exponent = -8;
+        assert_eq!(normalized.calculate_trust_weight(10_000_000), base_weight(25_000_000));
+    }
+
+    #[test]
+    fn log2_fixed_is_exact_at_powers_of_two() {
+        for k in 0..=64u64 {
+            assert_eq!(StakingVault::log2_fixed(1u128 << k), k << StakingVault::LOG2_FRACTION_BITS);
+        }
+    }
+
+    #[test]
+    fn weight_matches_reference_where_log2_is_whole() {
+        for k in 0..64 {
+            let amount = (1u64 << k) - 1;
+            for multiplier in MULTIPLIERS {
+                assert_eq!(
+                    vault(multiplier).calculate_trust_weight(amount),
+                    reference_weight(amount, multiplier)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn base_weight_is_within_one_of_reference_around_powers_of_two() {
+        for amount in powers_of_two().chain([u64::MAX - 1, u64::MAX]) {
+            let (weight, reference) = (base_weight(amount), reference_weight(amount, 100));
+            assert!(weight.abs_diff(reference) <= 1, "amount {amount}: {weight} vs {reference}");
+        }
+    }
+
+    #[test]
+    fn weight_at_u64_max() {
+        assert_eq!(base_weight(u64::MAX), 6400);
+        assert_eq!(vault(1000).calculate_trust_weight(u64::MAX), 64_000);
+    }
+
+    #[test]
+    fn weight_never_decreases_with_amount() {
+        let mut amounts: Vec<u64> = powers_of_two().chain(0..10_000).collect();
+        amounts.sort_unstable();
+        for pair in amounts.windows(2) {
+            assert!(base_weight(pair[0]) <= base_weight(pair[1]));
+        }
+    }
+
+    #[test]
+    fn stored_weights_recompute_within_one_percent() {
+        let amounts = (0..10_000u64)
+            .chain((1..=1_000u64).map(|i| i * 1_000_003))
+            .chain((0..64).map(|k| (1u64 << k).saturating_mul(3) / 2))
+            .chain(powers_of_two());
+        for amount in amounts {
+            for multiplier in MULTIPLIERS {
+                let stored = reference_weight(amount, multiplier);
+                let recomputed = vault(multiplier).calculate_trust_weight(amount);
+                let tolerance = (stored / 100).max(1);
+                assert!(
+                    recomputed.abs_diff(stored) <= tolerance,
+                    "amount {amount} x{multiplier}: {recomputed} vs stored {stored}"
+                );
+            }
+        }
+    }
+
+    /// Powers of two plus pseudo-random amounts of every magnitude, sorted
+    fn amounts() -> Vec<u64> {
+        let mut amounts: Vec<u64> = powers_of_two().chain([0, u64::MAX - 1, u64::MAX]).chain(u64_samples(256)).collect();
+        amounts.sort_unstable();
+        amounts
+    }
+
+    #[test]
+    fn slash_amount_never_exceeds_the_position_and_grows_with_severity() {
+        for amount in amounts() {
+            let mut previous = 0;
+            for severity_bps in (0..=u16::MAX).step_by(13).chain([StakingVault::MAX_SLASH_BPS, u16::MAX]) {
+                let slashed = StakingVault::calculate_slash_amount(amount, severity_bps);
+                assert!(slashed <= amount, "{amount} at {severity_bps}: {slashed}");
+                assert!(slashed >= previous, "{amount} at {severity_bps}: {slashed} < {previous}");
+                previous = slashed;
+            }
+            assert_eq!(previous, amount);
+        }
+    }
+
+    #[test]
+    fn weight_never_decreases_with_amount_for_any_multiplier_or_price() {
+        let mut vaults: Vec<StakingVault> = [0, 1, 100, 1000, u16::MAX].into_iter().map(vault).collect();
+        for (price, exponent, decimals) in [(1, -8, 0), (i64::MAX, 0, 0), (i64::MAX, i32::MAX, 0), (1, i32::MIN, 255)] {
+            let mut normalized = vault(u16::MAX);
+            normalized.usd_normalized = true;
+            normalized.last_price = price;
+            normalized.last_price_exponent = exponent;
+            normalized.token_decimals = decimals;
+            vaults.push(normalized);
+        }
+
+        let cap = (StakingVault::log2_fixed(1 << 64) * 100) >> StakingVault::LOG2_FRACTION_BITS;
+        for vault in vaults {
+            let mut previous = 0;
+            for amount in amounts() {
+                let weight = vault.calculate_trust_weight(amount);
+                assert!(weight >= previous, "x{} at {amount}", vault.weight_multiplier);
+                assert!(weight <= cap * vault.weight_multiplier as u64 / 100);
+                previous = weight;
+            }
+        }
+    }
 }
@@ -2,3 +2,59 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+// Validators must hold an active AgentIdentity in identity_registry
+pub use gs2_common::{IDENTITY_AGENT_SEED, IDENTITY_REGISTRY_PROGRAM_ID};
+
+/// Minimum SOL bond (in lamports) a validator locks in its ValidatorRecord
+pub const MIN_VALIDATOR_BOND: u64 = 100_000_000; // 0.1 SOL
+
+/// Stamp validity used until a ValidationConfig is initialized
+pub const DEFAULT_STAMP_VALIDITY_SECONDS: i64 = 90 * 24 * 60 * 60; // 90 days
+
+/// Retention period used until a ValidationConfig is initialized
+pub const DEFAULT_RETENTION_SECONDS: i64 = 180 * 24 * 60 * 60; // 180 days
+
+/// Stamp tier thresholds used until a ValidationConfig is initialized
+pub const DEFAULT_BRONZE_THRESHOLD: u16 = 700;
+pub const DEFAULT_SILVER_THRESHOLD: u16 = 850;
+pub const DEFAULT_GOLD_THRESHOLD: u16 = 950;
+
+/// Responses at or above this time (ms) count as failed tests
+pub const RESPONSE_TIMEOUT_MS: u64 = 30_000;
+
+/// Responses faster than this (ms) earn a speed bonus on their score
+pub const FAST_RESPONSE_MS: u64 = 1_000;
+
+/// Faster responses are treated as this (ms) so implausible timings gain nothing extra
+pub const RESPONSE_TIME_FLOOR_MS: u64 = 100;
+
+/// Largest speed bonus, in basis points of the score (10%)
+pub const MAX_SPEED_BONUS_BPS: u64 = 1_000;
+
+/// TestResult::cost_micro_usd sentinel for a result that reports no cost
+pub const COST_NOT_REPORTED: u64 = u64::MAX;
+
+/// Longest llm_model name a TestResult may carry
+pub const MAX_LLM_MODEL_LEN: usize = 32;
+
+/// Minimum counted results before the highest and lowest scores are trimmed
+pub const TRIM_MIN_RESULTS: usize = 5;
+
+/// Endpoint hashes kept in a provider's ProviderEndpointIndex
+pub const MAX_INDEXED_ENDPOINTS: usize = 16;
+
+/// Lamports a provider escrows to open a dispute; forfeited if the dispute is rejected
+pub const DISPUTE_BOND: u64 = 10_000_000; // 0.01 SOL
+
+/// Disputes that may be open on one endpoint at a time
+pub const MAX_OPEN_DISPUTES: u32 = 3;
+
+/// Signers a StampMultisig can hold
+pub const MAX_MULTISIG_SIGNERS: usize = 7;
+
+/// How long a stamp proposal can be approved and executed (48 hours)
+pub const PROPOSAL_EXPIRY_SECONDS: i64 = 48 * 60 * 60;
+
+/// Largest submission fee the authority can configure (1 SOL)
+pub const MAX_SUBMISSION_FEE_LAMPORTS: u64 = 1_000_000_000;
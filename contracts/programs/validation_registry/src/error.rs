@@ -8,7 +8,7 @@ pub enum ValidationError {
     #[msg("Too many test results (maximum 10 allowed)")]
     TooManyTestResults,
 
-    #[msg("LLM model name exceeds maximum length of 50 characters")]
+    #[msg("LLM model name exceeds maximum length of 32 characters")]
     LlmModelNameTooLong,
 
     #[msg("Consensus score must be between 0 and 1000")]
@@ -25,4 +25,148 @@ pub enum ValidationError {
 
     #[msg("Unauthorized: signer is not the authorized authority")]
     UnauthorizedAuthority,
+
+    #[msg("Unauthorized: only the provider agent or the authority can re-validate")]
+    UnauthorizedResubmission,
+
+    #[msg("Validator has already submitted a test result for this endpoint")]
+    DuplicateValidator,
+
+    #[msg("Submitter is not a registered validator")]
+    ValidatorNotRegistered,
+
+    #[msg("Validator has been slashed and can no longer submit results")]
+    ValidatorSlashed,
+
+    #[msg("Validator bond is below the minimum")]
+    InsufficientValidatorBond,
+
+    #[msg("Validator does not hold an active agent identity")]
+    ValidatorIdentityInactive,
+
+    #[msg("No validation stamp is currently issued")]
+    StampNotIssued,
+
+    #[msg("Validation stamp has not expired yet")]
+    StampNotExpired,
+
+    #[msg("Stamp validity must be greater than zero")]
+    InvalidStampValidity,
+
+    #[msg("Account is not a legacy EndpointValidation owned by this program")]
+    InvalidMigrationTarget,
+
+    #[msg("Stamp thresholds must satisfy 0 < bronze <= silver <= gold <= 1000")]
+    InvalidStampThresholds,
+
+    #[msg("Provider agent does not hold an active agent identity")]
+    ProviderIdentityInactive,
+
+    #[msg("Provider endpoint index is full")]
+    EndpointIndexFull,
+
+    #[msg("Unauthorized: only the provider agent can dispute its endpoint's results")]
+    UnauthorizedDispute,
+
+    #[msg("Disputed result index is out of range")]
+    InvalidDisputedResult,
+
+    #[msg("Test result is already under dispute")]
+    ResultAlreadyDisputed,
+
+    #[msg("Too many open disputes on this endpoint")]
+    TooManyOpenDisputes,
+
+    #[msg("Stamp issuance is frozen while disputes are open")]
+    DisputesOpen,
+
+    #[msg("Dispute has already been resolved")]
+    DisputeNotOpen,
+
+    #[msg("Disputed result is no longer part of this validation")]
+    DisputedResultNotFound,
+
+    #[msg("Consensus score is below the required minimum")]
+    BelowThreshold,
+
+    #[msg("Unauthorized: only the recorded payer or the provider agent can close a validation")]
+    UnauthorizedClose,
+
+    #[msg("Validation is still within its retention period")]
+    RetentionPeriodActive,
+
+    #[msg("Validation still holds a valid stamp")]
+    StampStillValid,
+
+    #[msg("Retention period must be greater than zero")]
+    InvalidRetentionPeriod,
+
+    #[msg("Endpoint hash does not match the sha256 of the canonical endpoint URL")]
+    HashMismatch,
+
+    #[msg("Endpoint URL must use the https scheme")]
+    InvalidUrlScheme,
+
+    #[msg("Endpoint URL has no host")]
+    InvalidEndpointUrl,
+
+    #[msg("HTTP status class must be 0 (not reported) or 1-5")]
+    InvalidHttpStatusClass,
+
+    #[msg("New authority must differ from the current one and not be the default pubkey")]
+    InvalidAuthority,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+
+    #[msg("Multisig needs 1-7 signers and a threshold between 1 and the signer count")]
+    InvalidMultisigConfig,
+
+    #[msg("Stamp multisig is already enabled")]
+    MultisigAlreadyEnabled,
+
+    #[msg("Unauthorized: not a stamp multisig signer")]
+    UnauthorizedSigner,
+
+    #[msg("Signer has already approved this proposal")]
+    AlreadyApproved,
+
+    #[msg("Proposal is no longer pending")]
+    ProposalNotPending,
+
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+
+    #[msg("Stamp multisig is enabled: an approved proposal is required")]
+    MultisigApprovalRequired,
+
+    #[msg("Proposal does not authorize this stamp action")]
+    ProposalMismatch,
+
+    #[msg("Submission fee exceeds the maximum")]
+    InvalidSubmissionFee,
+
+    #[msg("A submission fee is due: the endpoint's reward pool must be provided")]
+    RewardPoolRequired,
+
+    #[msg("Rewards can be claimed once consensus is calculated and no disputes are open")]
+    ConsensusNotFinal,
+
+    #[msg("No results are eligible for this endpoint's rewards")]
+    NoEligibleValidators,
+
+    #[msg("Validator has no eligible results for this endpoint")]
+    NotEligibleForReward,
+
+    #[msg("Reward already claimed")]
+    RewardAlreadyClaimed,
+
+    #[msg("Identity layout version is no longer supported; call identity_registry's migrate_account first")]
+    UnsupportedIdentityVersion,
+
+    #[msg("Validators still have unclaimed rewards in this endpoint's reward pool")]
+    RewardsUnclaimed,
 }
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::state::StampTier;
+
+/// Emitted when issue_validation_stamp stamps an endpoint
+#[event]
+pub struct ValidationStampIssued {
+    pub endpoint_hash: [u8; 32],
+    pub provider_agent: Pubkey,
+    pub tier: StampTier,
+    pub consensus_score: u16,
+    /// When the stamp stops being valid
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a provider disputes one of its endpoint's test results
+#[event]
+pub struct DisputeOpened {
+    pub endpoint_hash: [u8; 32],
+    pub dispute_index: u32,
+    pub disputer: Pubkey,
+    pub result_index: u8,
+    /// Validator that submitted the disputed result
+    pub validator: Pubkey,
+    pub evidence_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when the authority upholds or rejects a dispute
+#[event]
+pub struct DisputeResolved {
+    pub endpoint_hash: [u8; 32],
+    pub dispute_index: u32,
+    pub upheld: bool,
+    /// Consensus score after resolution
+    pub consensus_score: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when the validation authority nominates a successor
+#[event]
+pub struct AuthorityTransferProposed {
+    pub current_authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+/// Emitted when the validation authority changes hands
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+/// Emitted when stamp issuance and revocation start requiring multisig quorum
+#[event]
+pub struct StampMultisigEnabled {
+    pub authority: Pubkey,
+    pub multisig: Pubkey,
+    pub threshold: u8,
+}
+
+/// Emitted when a validator claims its share of an endpoint's reward pool
+#[event]
+pub struct ValidatorRewardClaimed {
+    pub endpoint_hash: [u8; 32],
+    pub validator: Pubkey,
+    /// Eligible results the share was computed from
+    pub results: u8,
+    pub amount: u64,
+}
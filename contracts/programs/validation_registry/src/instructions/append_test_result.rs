@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use super::submit_validation::check_test_result;
+use crate::state::{EndpointValidation, TestResult, ValidatorRecord};
+use crate::error::ValidationError;
+
+#[derive(Accounts)]
+pub struct AppendTestResult<'info> {
+    #[account(
+        mut,
+        seeds = [
+            EndpointValidation::SEED_PREFIX,
+            &endpoint_validation.endpoint_hash
+        ],
+        bump = endpoint_validation.bump
+    )]
+    pub endpoint_validation: Account<'info, EndpointValidation>,
+
+    /// Independent validator submitting one result
+    pub validator: Signer<'info>,
+
+    #[account(
+        seeds = [ValidatorRecord::SEED_PREFIX, validator.key().as_ref()],
+        bump = validator_record.bump,
+        constraint = validator_record.is_in_good_standing() @ ValidationError::ValidatorSlashed
+    )]
+    pub validator_record: Account<'info, ValidatorRecord>,
+}
+
+pub fn handler(ctx: Context<AppendTestResult>, test_result: TestResult) -> Result<()> {
+    let validator = ctx.accounts.validator.key();
+    let validation = &mut ctx.accounts.endpoint_validation;
+
+    // A stamped round is final; changing its results needs a new round
+    require!(
+        !validation.stamp_issued(),
+        ValidationError::StampAlreadyIssued
+    );
+
+    require!(
+        validation.test_results.len() < 10,
+        ValidationError::TooManyTestResults
+    );
+
+    check_test_result(&test_result)?;
+
+    require!(
+        validation.test_results.iter().all(|r| r.validator != validator),
+        ValidationError::DuplicateValidator
+    );
+
+    // The submitted validator field is ignored; results are attributed to the signer
+    validation.test_results.push(TestResult {
+        validator,
+        ..test_result
+    });
+    // Any previous consensus no longer reflects the result set
+    validation.consensus_score = 0;
+
+    msg!("Test result appended for endpoint: {}", validation.endpoint_url);
+    msg!("Validator: {}", validator);
+    msg!("Test results count: {}", validation.test_results.len());
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use crate::events::{AuthorityTransferProposed, AuthorityTransferred, StampMultisigEnabled};
+use crate::state::{StampMultisig, ValidationAuthority};
+use crate::error::ValidationError;
+
+// ==================== TRANSFER VALIDATION AUTHORITY ====================
+
+#[derive(Accounts)]
+pub struct TransferValidationAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [ValidationAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ ValidationError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, ValidationAuthority>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Nominate a new authority; the nominee must accept before it takes effect
+pub fn transfer_validation_authority(
+    ctx: Context<TransferValidationAuthority>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    require!(
+        new_authority != Pubkey::default() && new_authority != ctx.accounts.authority.key(),
+        ValidationError::InvalidAuthority
+    );
+
+    let authority_account = &mut ctx.accounts.authority_account;
+    authority_account.pending_authority = new_authority;
+
+    emit!(AuthorityTransferProposed {
+        current_authority: authority_account.authority,
+        pending_authority: new_authority,
+    });
+
+    msg!("Authority transfer proposed: {} -> {}", authority_account.authority, new_authority);
+
+    Ok(())
+}
+
+// ==================== CANCEL AUTHORITY TRANSFER ====================
+
+#[derive(Accounts)]
+pub struct CancelValidationAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [ValidationAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ ValidationError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, ValidationAuthority>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Withdraw a pending nomination
+pub fn cancel_validation_authority_transfer(
+    ctx: Context<CancelValidationAuthorityTransfer>,
+) -> Result<()> {
+    let authority_account = &mut ctx.accounts.authority_account;
+
+    require!(
+        authority_account.pending_authority != Pubkey::default(),
+        ValidationError::NoPendingAuthority
+    );
+
+    msg!("Authority transfer to {} cancelled", authority_account.pending_authority);
+    authority_account.pending_authority = Pubkey::default();
+
+    Ok(())
+}
+
+// ==================== ACCEPT VALIDATION AUTHORITY ====================
+
+#[derive(Accounts)]
+pub struct AcceptValidationAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [ValidationAuthority::SEED_PREFIX],
+        bump = authority_account.bump
+    )]
+    pub authority_account: Account<'info, ValidationAuthority>,
+
+    /// The nominated authority
+    pub new_authority: Signer<'info>,
+}
+
+/// Complete a transfer (must be signed by the nominated authority)
+pub fn accept_validation_authority(ctx: Context<AcceptValidationAuthority>) -> Result<()> {
+    let authority_account = &mut ctx.accounts.authority_account;
+
+    require!(
+        authority_account.pending_authority != Pubkey::default(),
+        ValidationError::NoPendingAuthority
+    );
+    require!(
+        authority_account.pending_authority == ctx.accounts.new_authority.key(),
+        ValidationError::NotPendingAuthority
+    );
+
+    let previous_authority = authority_account.authority;
+    authority_account.authority = authority_account.pending_authority;
+    authority_account.pending_authority = Pubkey::default();
+
+    emit!(AuthorityTransferred {
+        previous_authority,
+        new_authority: authority_account.authority,
+    });
+
+    msg!("Authority transferred: {} -> {}", previous_authority, authority_account.authority);
+
+    Ok(())
+}
+
+// ==================== ENABLE STAMP MULTISIG ====================
+
+#[derive(Accounts)]
+pub struct EnableStampMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [ValidationAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ ValidationError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, ValidationAuthority>,
+
+    /// The multisig must already exist so stamps can still be approved
+    #[account(
+        seeds = [StampMultisig::SEED_PREFIX],
+        bump = stamp_multisig.bump
+    )]
+    pub stamp_multisig: Account<'info, StampMultisig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Point stamp authority at the multisig. One-way: from here on issue_validation_stamp
+/// and revoke_stamp also need an approved StampProposal.
+pub fn enable_stamp_multisig(ctx: Context<EnableStampMultisig>) -> Result<()> {
+    let authority_account = &mut ctx.accounts.authority_account;
+
+    require!(
+        !authority_account.multisig_enabled(),
+        ValidationError::MultisigAlreadyEnabled
+    );
+
+    authority_account.stamp_multisig = ctx.accounts.stamp_multisig.key();
+
+    emit!(StampMultisigEnabled {
+        authority: authority_account.authority,
+        multisig: authority_account.stamp_multisig,
+        threshold: ctx.accounts.stamp_multisig.threshold,
+    });
+
+    msg!("Stamp multisig enabled: {}", authority_account.stamp_multisig);
+
+    Ok(())
+}
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::state::{EndpointValidation, ValidationAuthority};
+use crate::constants::{
+    FAST_RESPONSE_MS, MAX_SPEED_BONUS_BPS, RESPONSE_TIME_FLOOR_MS, TRIM_MIN_RESULTS,
+};
+use crate::state::{EndpointValidation, TestResult, ValidationAuthority, ValidationConfig};
 use crate::error::ValidationError;
 
 #[derive(Accounts)]
@@ -23,6 +26,13 @@ pub struct CalculateConsensus<'info> {
 
     /// Authority that can calculate consensus
     pub authority: Signer<'info>,
+
+    /// Validation config; no latency penalty applies when omitted
+    #[account(
+        seeds = [ValidationConfig::SEED_PREFIX],
+        bump = validation_config.bump
+    )]
+    pub validation_config: Option<Account<'info, ValidationConfig>>,
 }
 
 pub fn handler(ctx: Context<CalculateConsensus>) -> Result<()> {
@@ -33,41 +43,167 @@ pub fn handler(ctx: Context<CalculateConsensus>) -> Result<()> {
         ValidationError::InsufficientTestResults
     );
 
-    // Calculate consensus score based on test results
-    let mut total_score: u32 = 0;
-    let mut successful_tests: u32 = 0;
+    let p95_threshold_ms = ctx
+        .accounts
+        .validation_config
+        .as_ref()
+        .map_or(0, |config| config.p95_latency_threshold_ms);
+    let breakdown = compute_consensus(&validation.test_results, p95_threshold_ms);
+    validation.consensus_score = breakdown.consensus;
 
-    for result in &validation.test_results {
-        if result.success {
-            successful_tests = successful_tests.saturating_add(1);
-            total_score = total_score.saturating_add(result.score as u32);
-        }
-    }
+    msg!("Consensus calculated: {}/1000", breakdown.consensus);
+    msg!("Successful tests: {}/{}", breakdown.successful_tests, validation.test_results.len());
+    msg!("Average score: {}/100", breakdown.avg_score);
 
-    // Consensus formula:
-    // - Base score from average of successful tests (0-100)
-    // - Success rate bonus (0-900)
-    // - Total max: 1000
+    Ok(())
+}
 
-    let avg_score = if successful_tests > 0 {
-        total_score / successful_tests
-    } else {
-        0
-    };
+/// Intermediate values of a consensus calculation
+pub struct ConsensusBreakdown {
+    pub consensus: u16,
+    pub successful_tests: u32,
+    pub avg_score: u32,
+}
+
+/// Consensus formula (all integer math):
+/// - A result counts as successful if it passed and responded under
+///   RESPONSE_TIMEOUT_MS; timeouts count as failures.
+/// - Each successful score gets a speed bonus of up to MAX_SPEED_BONUS_BPS,
+///   scaling linearly from 0 at FAST_RESPONSE_MS to the maximum at
+///   RESPONSE_TIME_FLOOR_MS, capped at 100.
+/// - With a non-zero `p95_threshold_ms`, a result reporting a higher p95
+///   latency has its score scaled by threshold / p95 (twice the threshold
+///   halves it). Results that don't report p95 are unaffected.
+/// - With TRIM_MIN_RESULTS or more successful results, the highest and lowest
+///   adjusted scores are dropped before averaging (trimmed mean, 0-100).
+/// - Success rate (0-100%) is scaled by 9 into a 0-900 bonus.
+/// - Total is capped at 1000.
+pub fn compute_consensus(test_results: &[TestResult], p95_threshold_ms: u32) -> ConsensusBreakdown {
+    let mut scores: Vec<u32> = test_results
+        .iter()
+        .filter(|r| r.is_counted())
+        .map(|r| {
+            latency_penalized_score(
+                speed_adjusted_score(r.score, r.response_time),
+                r.p95_ms,
+                p95_threshold_ms,
+            )
+        })
+        .collect();
+    let successful_tests = scores.len() as u32;
+
+    if scores.len() >= TRIM_MIN_RESULTS {
+        scores.sort_unstable();
+        scores.pop();
+        scores.remove(0);
+    }
+
+    let avg_score = (scores.iter().sum::<u32>())
+        .checked_div(scores.len() as u32)
+        .unwrap_or(0);
 
     let success_rate = successful_tests
         .saturating_mul(100)
-        .checked_div(validation.test_results.len() as u32)
+        .checked_div(test_results.len() as u32)
         .unwrap_or(0);
-    let success_bonus = success_rate.saturating_mul(9) / 10; // Scale to 0-900
+    let success_bonus = success_rate.saturating_mul(9); // Scale to 0-900
+
+    ConsensusBreakdown {
+        consensus: avg_score.saturating_add(success_bonus).min(1000) as u16,
+        successful_tests,
+        avg_score,
+    }
+}
+
+/// Score (0-100) with the response-time bonus applied, in basis points
+fn speed_adjusted_score(score: u8, response_time: u64) -> u32 {
+    let response_time = response_time.max(RESPONSE_TIME_FLOOR_MS);
+    let bonus_bps = FAST_RESPONSE_MS.saturating_sub(response_time) * MAX_SPEED_BONUS_BPS
+        / (FAST_RESPONSE_MS - RESPONSE_TIME_FLOOR_MS);
+    let adjusted = u64::from(score) * (10_000 + bonus_bps) / 10_000;
+    adjusted.min(100) as u32
+}
 
-    let consensus = avg_score.saturating_add(success_bonus).min(1000) as u16;
+/// Scale a score down when its reported p95 latency exceeds the threshold
+fn latency_penalized_score(score: u32, p95_ms: u32, p95_threshold_ms: u32) -> u32 {
+    if p95_threshold_ms == 0 || p95_ms <= p95_threshold_ms {
+        return score;
+    }
+    (u64::from(score) * u64::from(p95_threshold_ms) / u64::from(p95_ms)) as u32
+}
 
-    validation.consensus_score = consensus;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{COST_NOT_REPORTED, RESPONSE_TIMEOUT_MS};
+    use gs2_common::test_utils::next;
 
-    msg!("Consensus calculated: {}/1000", consensus);
-    msg!("Successful tests: {}/{}", successful_tests, validation.test_results.len());
-    msg!("Average score: {}/100", avg_score);
+    /// A result with every field drawn from its full domain, edges included
+    fn result(state: &mut u64) -> TestResult {
+        let response_time = match next(state) % 4 {
+            0 => u64::MAX,
+            1 => RESPONSE_TIMEOUT_MS,
+            _ => next(state) % (RESPONSE_TIMEOUT_MS + 1_000),
+        };
+        let p95_ms = match next(state) % 3 {
+            0 => 0,
+            1 => u32::MAX,
+            _ => next(state) as u32 % 10_000,
+        };
+        TestResult {
+            llm_model: "gpt-4".to_string(),
+            success: next(state) % 4 != 0,
+            response_time,
+            score: next(state) as u8,
+            validator: Pubkey::default(),
+            p50_ms: 0,
+            p95_ms,
+            cost_micro_usd: COST_NOT_REPORTED,
+            http_status_class: 0,
+            error_code_hash: [0; 32],
+        }
+    }
 
-    Ok(())
+    fn thresholds() -> [u32; 4] {
+        [0, 1, 2_000, u32::MAX]
+    }
+
+    #[test]
+    fn consensus_stays_in_range_for_any_results() {
+        let mut state = 1;
+        for len in (0..=12).chain([50]) {
+            for _ in 0..200 {
+                let results: Vec<TestResult> = (0..len).map(|_| result(&mut state)).collect();
+                for threshold in thresholds() {
+                    let breakdown = compute_consensus(&results, threshold);
+                    assert!(breakdown.consensus <= 1000);
+                    assert!(breakdown.avg_score <= 100);
+                    assert_eq!(
+                        breakdown.successful_tests as usize,
+                        results.iter().filter(|r| r.is_counted()).count()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn raising_one_score_never_lowers_consensus() {
+        let mut state = 2;
+        for len in 1..=12 {
+            for _ in 0..200 {
+                let mut results: Vec<TestResult> = (0..len).map(|_| result(&mut state)).collect();
+                let index = next(&mut state) as usize % len;
+                for threshold in thresholds() {
+                    let mut previous = 0;
+                    for score in 0..=u8::MAX {
+                        results[index].score = score;
+                        let consensus = compute_consensus(&results, threshold).consensus;
+                        assert!(consensus >= previous, "score {score}: {consensus} < {previous}");
+                        previous = consensus;
+                    }
+                }
+            }
+        }
+    }
 }
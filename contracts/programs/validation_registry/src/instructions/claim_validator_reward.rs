@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::events::ValidatorRewardClaimed;
+use crate::state::{EndpointValidation, RewardPool};
+use crate::error::ValidationError;
+
+#[derive(Accounts)]
+pub struct ClaimValidatorReward<'info> {
+    #[account(
+        seeds = [
+            EndpointValidation::SEED_PREFIX,
+            &endpoint_validation.endpoint_hash
+        ],
+        bump = endpoint_validation.bump
+    )]
+    pub endpoint_validation: Account<'info, EndpointValidation>,
+
+    #[account(
+        mut,
+        seeds = [RewardPool::SEED_PREFIX, &endpoint_validation.endpoint_hash],
+        bump = reward_pool.bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    #[account(mut)]
+    pub validator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ClaimValidatorReward>) -> Result<()> {
+    let validation = &ctx.accounts.endpoint_validation;
+
+    // Shares are fixed from the results the final consensus was computed over
+    require!(
+        validation.consensus_score > 0 && validation.open_disputes() == 0,
+        ValidationError::ConsensusNotFinal
+    );
+
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    if !reward_pool.settled {
+        reward_pool.settle(&validation.test_results);
+    }
+
+    // With no eligible results the fee stays in the pool until the validation is closed
+    require!(
+        reward_pool.eligible_results > 0,
+        ValidationError::NoEligibleValidators
+    );
+
+    let validator = ctx.accounts.validator.key();
+    let index = reward_pool
+        .shares
+        .iter()
+        .position(|share| share.validator == validator)
+        .ok_or(ValidationError::NotEligibleForReward)?;
+    require!(
+        !reward_pool.shares[index].claimed,
+        ValidationError::RewardAlreadyClaimed
+    );
+
+    let amount = reward_pool.payout(index);
+    reward_pool.shares[index].claimed = true;
+    // Payouts never exceed what is left of total_lamports
+    reward_pool.distributed_lamports += amount;
+
+    reward_pool.sub_lamports(amount)?;
+    ctx.accounts.validator.add_lamports(amount)?;
+
+    emit!(ValidatorRewardClaimed {
+        endpoint_hash: reward_pool.endpoint_hash,
+        validator,
+        results: reward_pool.shares[index].results,
+        amount,
+    });
+
+    msg!("Validator reward claimed: {} lamports to {}", amount, validator);
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use crate::constants::DEFAULT_RETENTION_SECONDS;
+use crate::state::{EndpointValidation, ProviderEndpointIndex, RewardPool, ValidationConfig};
+use crate::error::ValidationError;
+
+#[derive(Accounts)]
+pub struct CloseValidation<'info> {
+    #[account(
+        mut,
+        seeds = [
+            EndpointValidation::SEED_PREFIX,
+            &endpoint_validation.endpoint_hash
+        ],
+        bump = endpoint_validation.bump,
+        close = payer
+    )]
+    pub endpoint_validation: Account<'info, EndpointValidation>,
+
+    /// Provider's endpoint index; the closed endpoint is removed from it
+    #[account(
+        mut,
+        seeds = [ProviderEndpointIndex::SEED_PREFIX, endpoint_validation.provider_agent.as_ref()],
+        bump = endpoint_index.bump
+    )]
+    pub endpoint_index: Account<'info, ProviderEndpointIndex>,
+
+    /// CHECK: Must match endpoint_validation.payer; receives the rent
+    #[account(mut, address = endpoint_validation.payer)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Recorded payer or provider agent
+    #[account(
+        constraint = signer.key() == endpoint_validation.payer
+            || signer.key() == endpoint_validation.provider_agent
+            @ ValidationError::UnauthorizedClose
+    )]
+    pub signer: Signer<'info>,
+
+    /// Validation config; the default retention period applies when omitted
+    #[account(
+        seeds = [ValidationConfig::SEED_PREFIX],
+        bump = validation_config.bump
+    )]
+    pub validation_config: Option<Account<'info, ValidationConfig>>,
+
+    /// Endpoint's reward pool, if any; closes to the payer once no validator is owed a share
+    #[account(
+        mut,
+        seeds = [RewardPool::SEED_PREFIX, &endpoint_validation.endpoint_hash],
+        bump = reward_pool.bump,
+        close = payer
+    )]
+    pub reward_pool: Option<Account<'info, RewardPool>>,
+}
+
+pub fn handler(ctx: Context<CloseValidation>) -> Result<()> {
+    let validation = &ctx.accounts.endpoint_validation;
+    let now = Clock::get()?.unix_timestamp;
+
+    let retention = ctx
+        .accounts
+        .validation_config
+        .as_ref()
+        .map_or(DEFAULT_RETENTION_SECONDS, |config| config.retention_seconds);
+    require!(
+        now.saturating_sub(validation.timestamp) >= retention,
+        ValidationError::RetentionPeriodActive
+    );
+
+    // Stamps must have expired or been revoked first
+    require!(
+        !validation.is_stamp_valid(now),
+        ValidationError::StampStillValid
+    );
+
+    require!(
+        validation.open_disputes() == 0,
+        ValidationError::DisputesOpen
+    );
+
+    // Fees return to the payer only when no validator earned a share of them
+    if let Some(reward_pool) = ctx.accounts.reward_pool.as_mut() {
+        if !reward_pool.settled && validation.consensus_score > 0 {
+            reward_pool.settle(&validation.test_results);
+        }
+        require!(
+            reward_pool.eligible_results == 0
+                || reward_pool.distributed_lamports >= reward_pool.total_lamports,
+            ValidationError::RewardsUnclaimed
+        );
+    }
+
+    let endpoint_hash = validation.endpoint_hash;
+    ctx.accounts
+        .endpoint_index
+        .endpoint_hashes
+        .retain(|hash| *hash != endpoint_hash);
+
+    msg!("Validation closed for endpoint: {}", validation.endpoint_url);
+    msg!("Rent returned to: {}", validation.payer);
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use crate::constants::{
+    DEFAULT_BRONZE_THRESHOLD, DEFAULT_GOLD_THRESHOLD, DEFAULT_RETENTION_SECONDS,
+    DEFAULT_SILVER_THRESHOLD, DEFAULT_STAMP_VALIDITY_SECONDS, MAX_SUBMISSION_FEE_LAMPORTS,
+};
+use crate::state::{ValidationAuthority, ValidationConfig};
+use crate::error::ValidationError;
+
+// ==================== INITIALIZE CONFIG ====================
+
+#[derive(Accounts)]
+pub struct InitializeValidationConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = ValidationConfig::LEN,
+        seeds = [ValidationConfig::SEED_PREFIX],
+        bump
+    )]
+    pub validation_config: Account<'info, ValidationConfig>,
+
+    #[account(
+        seeds = [ValidationAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ ValidationError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, ValidationAuthority>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the validation config with default parameters (authority only)
+pub fn initialize_validation_config(ctx: Context<InitializeValidationConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.validation_config;
+
+    config.stamp_validity_seconds = DEFAULT_STAMP_VALIDITY_SECONDS;
+    config.bump = ctx.bumps.validation_config;
+    config.bronze_threshold = DEFAULT_BRONZE_THRESHOLD;
+    config.silver_threshold = DEFAULT_SILVER_THRESHOLD;
+    config.gold_threshold = DEFAULT_GOLD_THRESHOLD;
+    config.retention_seconds = DEFAULT_RETENTION_SECONDS;
+    // No latency penalty until the authority sets a threshold
+    config.p95_latency_threshold_ms = 0;
+    // Validation is free until the authority sets a fee
+    config.submission_fee_lamports = 0;
+    config.reward_counted_results_only = true;
+
+    msg!("Validation config initialized");
+    msg!("Stamp validity: {}s", config.stamp_validity_seconds);
+
+    Ok(())
+}
+
+// ==================== UPDATE STAMP VALIDITY ====================
+
+#[derive(Accounts)]
+pub struct UpdateValidationConfig<'info> {
+    #[account(
+        mut,
+        seeds = [ValidationConfig::SEED_PREFIX],
+        bump = validation_config.bump
+    )]
+    pub validation_config: Account<'info, ValidationConfig>,
+
+    #[account(
+        seeds = [ValidationAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ ValidationError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, ValidationAuthority>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Change how long future stamps stay valid; issued stamps keep their expiry
+pub fn update_stamp_validity(
+    ctx: Context<UpdateValidationConfig>,
+    stamp_validity_seconds: i64,
+) -> Result<()> {
+    require!(
+        stamp_validity_seconds > 0,
+        ValidationError::InvalidStampValidity
+    );
+
+    ctx.accounts.validation_config.stamp_validity_seconds = stamp_validity_seconds;
+
+    msg!("Stamp validity updated: {}s", stamp_validity_seconds);
+
+    Ok(())
+}
+
+// ==================== UPDATE STAMP THRESHOLDS ====================
+
+/// Change the consensus thresholds for each stamp tier; issued stamps keep their tier
+pub fn update_stamp_thresholds(
+    ctx: Context<UpdateValidationConfig>,
+    bronze_threshold: u16,
+    silver_threshold: u16,
+    gold_threshold: u16,
+) -> Result<()> {
+    require!(
+        bronze_threshold > 0
+            && bronze_threshold <= silver_threshold
+            && silver_threshold <= gold_threshold
+            && gold_threshold <= 1000,
+        ValidationError::InvalidStampThresholds
+    );
+
+    let config = &mut ctx.accounts.validation_config;
+    config.bronze_threshold = bronze_threshold;
+    config.silver_threshold = silver_threshold;
+    config.gold_threshold = gold_threshold;
+
+    msg!(
+        "Stamp thresholds updated: bronze {}, silver {}, gold {}",
+        bronze_threshold,
+        silver_threshold,
+        gold_threshold
+    );
+
+    Ok(())
+}
+
+// ==================== UPDATE RETENTION PERIOD ====================
+
+/// Change how long validations must sit after their last round before they can be closed
+pub fn update_retention_period(
+    ctx: Context<UpdateValidationConfig>,
+    retention_seconds: i64,
+) -> Result<()> {
+    require!(
+        retention_seconds > 0,
+        ValidationError::InvalidRetentionPeriod
+    );
+
+    ctx.accounts.validation_config.retention_seconds = retention_seconds;
+
+    msg!("Retention period updated: {}s", retention_seconds);
+
+    Ok(())
+}
+
+// ==================== UPDATE LATENCY THRESHOLD ====================
+
+/// Set the p95 latency (ms) above which results are penalized in consensus; 0 disables the penalty
+pub fn update_latency_threshold(
+    ctx: Context<UpdateValidationConfig>,
+    p95_latency_threshold_ms: u32,
+) -> Result<()> {
+    ctx.accounts.validation_config.p95_latency_threshold_ms = p95_latency_threshold_ms;
+
+    msg!("p95 latency threshold updated: {}ms", p95_latency_threshold_ms);
+
+    Ok(())
+}
+
+// ==================== UPDATE REWARD CONFIG ====================
+
+/// Set the provider submission fee and whether only consensus-counted results earn a share
+pub fn update_reward_config(
+    ctx: Context<UpdateValidationConfig>,
+    submission_fee_lamports: u64,
+    reward_counted_results_only: bool,
+) -> Result<()> {
+    require!(
+        submission_fee_lamports <= MAX_SUBMISSION_FEE_LAMPORTS,
+        ValidationError::InvalidSubmissionFee
+    );
+
+    let config = &mut ctx.accounts.validation_config;
+    config.submission_fee_lamports = submission_fee_lamports;
+    config.reward_counted_results_only = reward_counted_results_only;
+
+    msg!(
+        "Reward config updated: fee {} lamports, counted results only {}",
+        submission_fee_lamports,
+        reward_counted_results_only
+    );
+
+    Ok(())
+}
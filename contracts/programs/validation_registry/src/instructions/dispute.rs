@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use super::calculate_consensus::compute_consensus;
+use crate::constants::{DISPUTE_BOND, MAX_OPEN_DISPUTES};
+use crate::events::{DisputeOpened, DisputeResolved};
+use crate::state::{
+    DisputeStatus, EndpointValidation, ValidationAuthority, ValidationConfig, ValidationDispute,
+};
+use crate::error::ValidationError;
+
+// ==================== DISPUTE VALIDATION ====================
+
+#[derive(Accounts)]
+#[instruction(endpoint_hash: [u8; 32])]
+pub struct DisputeValidation<'info> {
+    #[account(
+        mut,
+        seeds = [EndpointValidation::SEED_PREFIX, &endpoint_hash],
+        bump = endpoint_validation.bump,
+        constraint = endpoint_validation.provider_agent == provider_agent.key()
+            @ ValidationError::UnauthorizedDispute
+    )]
+    pub endpoint_validation: Account<'info, EndpointValidation>,
+
+    #[account(
+        init,
+        payer = provider_agent,
+        space = ValidationDispute::LEN,
+        seeds = [
+            ValidationDispute::SEED_PREFIX,
+            &endpoint_hash,
+            endpoint_validation.dispute_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub dispute: Account<'info, ValidationDispute>,
+
+    /// Provider agent disputing one of its endpoint's results; pays the bond
+    #[account(mut)]
+    pub provider_agent: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Flag a test result as disputed, escrowing DISPUTE_BOND and freezing stamp
+/// issuance for the endpoint until the dispute is resolved
+pub fn dispute_validation(
+    ctx: Context<DisputeValidation>,
+    endpoint_hash: [u8; 32],
+    disputed_result_index: u8,
+    evidence_hash: [u8; 32],
+) -> Result<()> {
+    let index = disputed_result_index as usize;
+    let validation = &ctx.accounts.endpoint_validation;
+
+    require!(
+        index < validation.test_results.len(),
+        ValidationError::InvalidDisputedResult
+    );
+    require!(
+        !validation.is_result_disputed(index),
+        ValidationError::ResultAlreadyDisputed
+    );
+    require!(
+        validation.open_disputes() < MAX_OPEN_DISPUTES,
+        ValidationError::TooManyOpenDisputes
+    );
+
+    // The bond sits in the dispute record itself, above its rent-exempt balance
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.provider_agent.to_account_info(),
+                to: ctx.accounts.dispute.to_account_info(),
+            },
+        ),
+        DISPUTE_BOND,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let validation = &mut ctx.accounts.endpoint_validation;
+    let dispute_index = validation.dispute_count;
+
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.endpoint_hash = endpoint_hash;
+    dispute.dispute_index = dispute_index;
+    dispute.disputer = ctx.accounts.provider_agent.key();
+    dispute.result_index = disputed_result_index;
+    dispute.disputed_result = validation.test_results[index].clone();
+    dispute.evidence_hash = evidence_hash;
+    dispute.bond_lamports = DISPUTE_BOND;
+    dispute.validation_round = validation.validation_round;
+    dispute.status = DisputeStatus::Open;
+    dispute.opened_at = now;
+    dispute.resolved_at = 0;
+    dispute.bump = ctx.bumps.dispute;
+
+    validation.disputed_results |= 1 << index;
+    validation.dispute_count = validation.dispute_count.saturating_add(1);
+
+    emit!(DisputeOpened {
+        endpoint_hash,
+        dispute_index,
+        disputer: dispute.disputer,
+        result_index: disputed_result_index,
+        validator: dispute.disputed_result.validator,
+        evidence_hash,
+        timestamp: now,
+    });
+
+    msg!("Dispute {} opened for endpoint: {}", dispute_index, validation.endpoint_url);
+    msg!("Disputed result index: {}", disputed_result_index);
+    msg!("Open disputes: {}", validation.open_disputes());
+
+    Ok(())
+}
+
+// ==================== RESOLVE DISPUTE ====================
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        mut,
+        seeds = [
+            ValidationDispute::SEED_PREFIX,
+            &dispute.endpoint_hash,
+            dispute.dispute_index.to_le_bytes().as_ref()
+        ],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, ValidationDispute>,
+
+    #[account(
+        mut,
+        seeds = [EndpointValidation::SEED_PREFIX, &dispute.endpoint_hash],
+        bump = endpoint_validation.bump
+    )]
+    pub endpoint_validation: Account<'info, EndpointValidation>,
+
+    #[account(
+        seeds = [ValidationAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ ValidationError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, ValidationAuthority>,
+
+    /// Authority that resolves disputes; receives forfeited bonds
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Must match dispute.disputer; receives the bond back when upheld
+    #[account(mut, address = dispute.disputer)]
+    pub disputer: UncheckedAccount<'info>,
+
+    /// Validation config; no latency penalty applies to the recomputed consensus when omitted
+    #[account(
+        seeds = [ValidationConfig::SEED_PREFIX],
+        bump = validation_config.bump
+    )]
+    pub validation_config: Option<Account<'info, ValidationConfig>>,
+}
+
+/// Settle an open dispute. Upholding it removes the disputed result, recomputes
+/// consensus and returns the bond; rejecting it keeps the result and forfeits
+/// the bond to the authority.
+pub fn resolve_dispute(ctx: Context<ResolveDispute>, uphold: bool) -> Result<()> {
+    require!(
+        ctx.accounts.dispute.status == DisputeStatus::Open,
+        ValidationError::DisputeNotOpen
+    );
+
+    let position = find_disputed_result(&ctx.accounts.endpoint_validation, &ctx.accounts.dispute);
+    let p95_threshold_ms = ctx
+        .accounts
+        .validation_config
+        .as_ref()
+        .map_or(0, |config| config.p95_latency_threshold_ms);
+    let bond = ctx.accounts.dispute.bond_lamports;
+
+    // Program-owned account, so the bond can be moved directly; rent stays behind
+    ctx.accounts.dispute.sub_lamports(bond)?;
+    if uphold {
+        ctx.accounts.disputer.add_lamports(bond)?;
+    } else {
+        ctx.accounts.authority.add_lamports(bond)?;
+    }
+
+    let validation = &mut ctx.accounts.endpoint_validation;
+    if uphold {
+        let position = position.ok_or(ValidationError::DisputedResultNotFound)?;
+        validation.remove_test_result(position);
+        validation.consensus_score = if validation.test_results.len() >= 3 {
+            compute_consensus(&validation.test_results, p95_threshold_ms).consensus
+        } else {
+            0
+        };
+    } else if let Some(position) = position {
+        validation.disputed_results &= !(1 << position);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.bond_lamports = 0;
+    dispute.status = if uphold { DisputeStatus::Upheld } else { DisputeStatus::Rejected };
+    dispute.resolved_at = now;
+
+    emit!(DisputeResolved {
+        endpoint_hash: dispute.endpoint_hash,
+        dispute_index: dispute.dispute_index,
+        upheld: uphold,
+        consensus_score: validation.consensus_score,
+        timestamp: now,
+    });
+
+    msg!(
+        "Dispute {} on endpoint {} {}: {} lamports {}",
+        dispute.dispute_index,
+        validation.endpoint_url,
+        if uphold { "upheld" } else { "rejected" },
+        bond,
+        if uphold { "returned to the provider" } else { "forfeited" }
+    );
+    msg!("Consensus score: {}/1000", validation.consensus_score);
+
+    Ok(())
+}
+
+/// Current position of a dispute's result. Upholding an earlier dispute shifts
+/// later results down, so fall back to the flagged result with the same value.
+fn find_disputed_result(validation: &EndpointValidation, dispute: &ValidationDispute) -> Option<usize> {
+    let matches = |i: usize| {
+        validation.is_result_disputed(i) && validation.test_results[i] == dispute.disputed_result
+    };
+    let original = dispute.result_index as usize;
+    if original < validation.test_results.len() && matches(original) {
+        return Some(original);
+    }
+    (0..validation.test_results.len()).find(|&i| matches(i))
+}
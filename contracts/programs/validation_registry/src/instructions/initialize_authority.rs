@@ -27,6 +27,8 @@ pub fn handler(ctx: Context<InitializeAuthority>) -> Result<()> {
 
     authority_account.authority = ctx.accounts.authority.key();
     authority_account.bump = ctx.bumps.authority_account;
+    authority_account.pending_authority = Pubkey::default();
+    authority_account.stamp_multisig = Pubkey::default();
 
     msg!("Validation authority initialized: {}", authority_account.authority);
 
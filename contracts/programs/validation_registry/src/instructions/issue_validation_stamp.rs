@@ -1,5 +1,14 @@
 use anchor_lang::prelude::*;
-use crate::state::{EndpointValidation, ValidationAuthority};
+use crate::constants::{
+    DEFAULT_BRONZE_THRESHOLD, DEFAULT_GOLD_THRESHOLD, DEFAULT_SILVER_THRESHOLD,
+    DEFAULT_STAMP_VALIDITY_SECONDS,
+};
+use super::multisig::authorize_stamp_action;
+use crate::events::ValidationStampIssued;
+use crate::state::{
+    stamp_tier_for, EndpointValidation, ProviderEndpointIndex, StampAction, StampProposal,
+    StampTier, ValidationAuthority, ValidationConfig,
+};
 use crate::error::ValidationError;
 
 #[derive(Accounts)]
@@ -21,33 +30,109 @@ pub struct IssueValidationStamp<'info> {
     )]
     pub authority_account: Account<'info, ValidationAuthority>,
 
-    /// Authority that can issue stamps
+    /// Authority that can issue stamps (with an approved proposal once the stamp multisig is enabled)
     pub authority: Signer<'info>,
+
+    /// Validation config; default validity and tier thresholds apply when omitted
+    #[account(
+        seeds = [ValidationConfig::SEED_PREFIX],
+        bump = validation_config.bump
+    )]
+    pub validation_config: Option<Account<'info, ValidationConfig>>,
+
+    /// Provider's endpoint index; records the stamp
+    #[account(
+        mut,
+        seeds = [ProviderEndpointIndex::SEED_PREFIX, endpoint_validation.provider_agent.as_ref()],
+        bump = endpoint_index.bump
+    )]
+    pub endpoint_index: Account<'info, ProviderEndpointIndex>,
+
+    /// Approved Issue proposal; required only once the stamp multisig is enabled
+    #[account(
+        mut,
+        seeds = [StampProposal::SEED_PREFIX, &stamp_proposal.proposal_id.to_le_bytes()],
+        bump = stamp_proposal.bump
+    )]
+    pub stamp_proposal: Option<Account<'info, StampProposal>>,
 }
 
 pub fn handler(ctx: Context<IssueValidationStamp>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    authorize_stamp_action(
+        &ctx.accounts.authority_account,
+        ctx.accounts.stamp_proposal.as_mut(),
+        StampAction::Issue,
+        ctx.accounts.endpoint_validation.endpoint_hash,
+        [0; 32],
+        now,
+    )?;
+
     let validation = &mut ctx.accounts.endpoint_validation;
 
     require!(
-        !validation.stamp_issued,
+        !validation.stamp_issued(),
         ValidationError::StampAlreadyIssued
     );
 
+    require!(
+        validation.open_disputes() == 0,
+        ValidationError::DisputesOpen
+    );
+
     require!(
         validation.consensus_score > 0,
         ValidationError::InvalidConsensusScore
     );
 
-    // Minimum consensus score of 700/1000 required for stamp
+    // Gold additionally requires every test to have passed
+    let all_passed = validation.test_results.iter().all(|r| r.success);
+    let (tier, validity) = match ctx.accounts.validation_config.as_ref() {
+        Some(config) => (
+            config.tier_for(validation.consensus_score, all_passed),
+            config.stamp_validity_seconds,
+        ),
+        None => (
+            stamp_tier_for(
+                validation.consensus_score,
+                all_passed,
+                DEFAULT_BRONZE_THRESHOLD,
+                DEFAULT_SILVER_THRESHOLD,
+                DEFAULT_GOLD_THRESHOLD,
+            ),
+            DEFAULT_STAMP_VALIDITY_SECONDS,
+        ),
+    };
+
+    // Consensus must reach at least the Bronze threshold
     require!(
-        validation.consensus_score >= 700,
+        tier != StampTier::None,
         ValidationError::InvalidConsensusScore
     );
 
-    validation.stamp_issued = true;
+    validation.stamp_tier = tier;
+    validation.stamp_issued_at = now;
+    validation.stamp_expires_at = now.saturating_add(validity);
+    validation.stamp_revoked_at = 0;
+    validation.stamp_revocation_reason = [0; 32];
+
+    let index = &mut ctx.accounts.endpoint_index;
+    index.stamps_issued = index.stamps_issued.saturating_add(1);
+    index.latest_stamped_hash = validation.endpoint_hash;
+
+    emit!(ValidationStampIssued {
+        endpoint_hash: validation.endpoint_hash,
+        provider_agent: validation.provider_agent,
+        tier,
+        consensus_score: validation.consensus_score,
+        expires_at: validation.stamp_expires_at,
+        timestamp: now,
+    });
 
     msg!("Validation stamp issued for endpoint: {}", validation.endpoint_url);
+    msg!("Stamp tier: {:?}", tier);
     msg!("Consensus score: {}/1000", validation.consensus_score);
+    msg!("Stamp expires at: {}", validation.stamp_expires_at);
     msg!("Provider agent: {}", validation.provider_agent);
 
     Ok(())
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::{EndpointValidation, StampTier};
+use crate::error::ValidationError;
+
+/// Permissionless: anyone can clear a stamp once it is past its expiry
+#[derive(Accounts)]
+pub struct MarkStampExpired<'info> {
+    #[account(
+        mut,
+        seeds = [
+            EndpointValidation::SEED_PREFIX,
+            &endpoint_validation.endpoint_hash
+        ],
+        bump = endpoint_validation.bump
+    )]
+    pub endpoint_validation: Account<'info, EndpointValidation>,
+}
+
+pub fn handler(ctx: Context<MarkStampExpired>) -> Result<()> {
+    let validation = &mut ctx.accounts.endpoint_validation;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        validation.stamp_issued(),
+        ValidationError::StampNotIssued
+    );
+
+    require!(
+        !validation.is_stamp_valid(now),
+        ValidationError::StampNotExpired
+    );
+
+    validation.stamp_tier = StampTier::None;
+
+    msg!("Validation stamp expired for endpoint: {}", validation.endpoint_url);
+    msg!("Expired at: {}", validation.stamp_expires_at);
+
+    Ok(())
+}
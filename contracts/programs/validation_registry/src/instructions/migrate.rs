@@ -0,0 +1,237 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use crate::constants::DEFAULT_STAMP_VALIDITY_SECONDS;
+use crate::state::{EndpointValidation, LegacyTestResult, StampTier, ValidationAuthority};
+use crate::error::ValidationError;
+
+/// Fields common to every stored EndpointValidation layout. The Vec is
+/// length-prefixed, so older layouts are decoded field by field rather than
+/// relying on zeroed bytes after a shorter, rewritten result list.
+/// `stamp_tier` was a bool before tiers existed; 0/1 decode as None/Bronze.
+#[derive(AnchorDeserialize)]
+struct LegacyEndpointValidation {
+    endpoint_hash: [u8; 32],
+    endpoint_url: String,
+    provider_agent: Pubkey,
+    test_results: Vec<LegacyTestResult>,
+    consensus_score: u16,
+    stamp_tier: StampTier,
+    timestamp: i64,
+    bump: u8,
+    validation_round: u32,
+}
+
+/// Stamp lifetime fields, present from PRE_DISPUTE_LEN
+#[derive(AnchorDeserialize)]
+struct LegacyStampFields {
+    stamp_issued_at: i64,
+    stamp_expires_at: i64,
+    stamp_revoked_at: i64,
+    stamp_revocation_reason: [u8; 32],
+}
+
+/// Dispute fields, present from PRE_PAYER_LEN
+#[derive(AnchorDeserialize)]
+struct LegacyDisputeFields {
+    disputed_results: u16,
+    dispute_count: u32,
+}
+
+// ==================== MIGRATE ENDPOINT VALIDATION ====================
+
+#[derive(Accounts)]
+pub struct MigrateEndpointValidation<'info> {
+    /// CHECK: Legacy-sized account; owner and discriminator are checked in the handler
+    #[account(mut)]
+    pub endpoint_validation: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Resize a legacy EndpointValidation to the current layout (permissionless).
+/// A stamp issued before expiry existed runs for the default validity from migration time;
+/// migrated accounts start with no disputes and record the provider as their payer.
+/// Stored test results gain unreported metrics and have llm_model trimmed to 32 bytes.
+pub fn migrate_endpoint_validation(ctx: Context<MigrateEndpointValidation>) -> Result<()> {
+    let account = ctx.accounts.endpoint_validation.to_account_info();
+
+    require_keys_eq!(*account.owner, crate::ID, ValidationError::InvalidMigrationTarget);
+    let validation = {
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && &data[..8] == EndpointValidation::DISCRIMINATOR,
+            ValidationError::InvalidMigrationTarget
+        );
+        require!(
+            data.len() >= EndpointValidation::LEGACY_LEN,
+            ValidationError::InvalidMigrationTarget
+        );
+        if data.len() >= EndpointValidation::LEN {
+            msg!("Endpoint validation already migrated");
+            return Ok(());
+        }
+        decode_legacy(&data)?
+    };
+
+    // The PDA must match the stored hash so arbitrary accounts can't be rewritten
+    let (expected, _) = Pubkey::find_program_address(
+        &[EndpointValidation::SEED_PREFIX, &validation.endpoint_hash],
+        &crate::ID,
+    );
+    require_keys_eq!(account.key(), expected, ValidationError::InvalidMigrationTarget);
+
+    grow_account(
+        &account,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        EndpointValidation::LEN,
+    )?;
+    validation.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    msg!(
+        "Endpoint validation {} migrated to {} bytes",
+        account.key(),
+        EndpointValidation::LEN
+    );
+
+    Ok(())
+}
+
+/// Grow a program-owned account to `new_len`, topping up rent from `payer`.
+/// New bytes are zeroed.
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let required = Rent::get()?.minimum_balance(new_len);
+    let shortfall = required.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    account.resize(new_len)?;
+    Ok(())
+}
+
+/// Decode any earlier layout, telling them apart by account size, and fill
+/// the fields that layout lacked
+fn decode_legacy(data: &[u8]) -> Result<EndpointValidation> {
+    let len = data.len();
+    let mut data = &data[8..];
+    let legacy = LegacyEndpointValidation::deserialize(&mut data)?;
+
+    let stamp = if len >= EndpointValidation::PRE_DISPUTE_LEN {
+        LegacyStampFields::deserialize(&mut data)?
+    } else {
+        let stamped = legacy.stamp_tier != StampTier::None;
+        let now = Clock::get()?.unix_timestamp;
+        LegacyStampFields {
+            stamp_issued_at: if stamped { now } else { 0 },
+            stamp_expires_at: if stamped {
+                now.saturating_add(DEFAULT_STAMP_VALIDITY_SECONDS)
+            } else {
+                0
+            },
+            stamp_revoked_at: 0,
+            stamp_revocation_reason: [0; 32],
+        }
+    };
+
+    let disputes = if len >= EndpointValidation::PRE_PAYER_LEN {
+        LegacyDisputeFields::deserialize(&mut data)?
+    } else {
+        LegacyDisputeFields {
+            disputed_results: 0,
+            dispute_count: 0,
+        }
+    };
+
+    // The original payer isn't recorded before PRE_METRICS_LEN; the provider receives the rent on close
+    let payer = if len >= EndpointValidation::PRE_METRICS_LEN {
+        Pubkey::deserialize(&mut data)?
+    } else {
+        legacy.provider_agent
+    };
+
+    Ok(EndpointValidation {
+        endpoint_hash: legacy.endpoint_hash,
+        endpoint_url: legacy.endpoint_url,
+        provider_agent: legacy.provider_agent,
+        test_results: legacy.test_results.into_iter().map(Into::into).collect(),
+        consensus_score: legacy.consensus_score,
+        stamp_tier: legacy.stamp_tier,
+        timestamp: legacy.timestamp,
+        bump: legacy.bump,
+        validation_round: legacy.validation_round,
+        stamp_issued_at: stamp.stamp_issued_at,
+        stamp_expires_at: stamp.stamp_expires_at,
+        stamp_revoked_at: stamp.stamp_revoked_at,
+        stamp_revocation_reason: stamp.stamp_revocation_reason,
+        disputed_results: disputes.disputed_results,
+        dispute_count: disputes.dispute_count,
+        payer,
+    })
+}
+
+// ==================== MIGRATE AUTHORITY ====================
+
+#[derive(Accounts)]
+pub struct MigrateAuthority<'info> {
+    /// CHECK: Legacy-sized account; owner and discriminator are checked in the handler
+    #[account(
+        mut,
+        seeds = [ValidationAuthority::SEED_PREFIX],
+        bump
+    )]
+    pub authority_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Resize a pre-rotation ValidationAuthority to the current layout (permissionless).
+/// The appended fields are zeroed: no pending transfer and single-key stamps.
+pub fn migrate_authority(ctx: Context<MigrateAuthority>) -> Result<()> {
+    let account = ctx.accounts.authority_account.to_account_info();
+
+    require_keys_eq!(*account.owner, crate::ID, ValidationError::InvalidMigrationTarget);
+    {
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() >= ValidationAuthority::LEGACY_LEN
+                && &data[..8] == ValidationAuthority::DISCRIMINATOR,
+            ValidationError::InvalidMigrationTarget
+        );
+    }
+    if account.data_len() >= ValidationAuthority::LEN {
+        msg!("Authority account already migrated");
+        return Ok(());
+    }
+
+    grow_account(
+        &account,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        ValidationAuthority::LEN,
+    )?;
+
+    msg!("Authority account migrated to {} bytes", ValidationAuthority::LEN);
+
+    Ok(())
+}
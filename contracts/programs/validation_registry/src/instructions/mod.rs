@@ -1,11 +1,40 @@
+// Every module below exports a `handler`; callers name the module
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize_authority;
 pub mod submit_validation;
+pub mod resubmit_validation;
+pub mod append_test_result;
 pub mod query_validations;
+pub mod register_validator;
+pub mod slash_validator;
 pub mod calculate_consensus;
 pub mod issue_validation_stamp;
+pub mod revoke_stamp;
+pub mod mark_stamp_expired;
+pub mod dispute;
+pub mod close_validation;
+pub mod claim_validator_reward;
+pub mod config;
+pub mod authority;
+pub mod multisig;
+pub mod migrate;
 
 pub use initialize_authority::*;
 pub use submit_validation::*;
+pub use resubmit_validation::*;
+pub use append_test_result::*;
 pub use query_validations::*;
+pub use register_validator::*;
+pub use slash_validator::*;
 pub use calculate_consensus::*;
 pub use issue_validation_stamp::*;
+pub use revoke_stamp::*;
+pub use mark_stamp_expired::*;
+pub use dispute::*;
+pub use close_validation::*;
+pub use claim_validator_reward::*;
+pub use config::*;
+pub use authority::*;
+pub use multisig::*;
+pub use migrate::*;
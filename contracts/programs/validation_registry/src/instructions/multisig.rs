@@ -0,0 +1,222 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_MULTISIG_SIGNERS;
+use crate::state::{
+    ProposalStatus, StampAction, StampMultisig, StampProposal, ValidationAuthority,
+};
+use crate::error::ValidationError;
+
+// ==================== INITIALIZE STAMP MULTISIG ====================
+
+#[derive(Accounts)]
+pub struct InitializeStampMultisig<'info> {
+    #[account(
+        seeds = [ValidationAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ ValidationError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, ValidationAuthority>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = StampMultisig::LEN,
+        seeds = [StampMultisig::SEED_PREFIX],
+        bump
+    )]
+    pub stamp_multisig: Account<'info, StampMultisig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the stamp multisig with its signers and threshold. It has no effect
+/// until the authority opts in with enable_stamp_multisig.
+pub fn initialize_stamp_multisig(
+    ctx: Context<InitializeStampMultisig>,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require!(
+        !signers.is_empty() && signers.len() <= MAX_MULTISIG_SIGNERS,
+        ValidationError::InvalidMultisigConfig
+    );
+    require!(
+        threshold > 0 && threshold as usize <= signers.len(),
+        ValidationError::InvalidMultisigConfig
+    );
+    require!(
+        signers.iter().enumerate().all(|(i, s)| !signers[..i].contains(s)),
+        ValidationError::InvalidMultisigConfig
+    );
+
+    let multisig = &mut ctx.accounts.stamp_multisig;
+    multisig.signers = signers;
+    multisig.threshold = threshold;
+    multisig.proposal_count = 0;
+    multisig.created_at = Clock::get()?.unix_timestamp;
+    multisig.bump = ctx.bumps.stamp_multisig;
+
+    msg!("Stamp multisig initialized with {} signers, threshold {}",
+         multisig.signers.len(), threshold);
+
+    Ok(())
+}
+
+// ==================== PROPOSE STAMP ACTION ====================
+
+#[derive(Accounts)]
+pub struct ProposeStampAction<'info> {
+    #[account(
+        mut,
+        seeds = [StampMultisig::SEED_PREFIX],
+        bump = stamp_multisig.bump
+    )]
+    pub stamp_multisig: Account<'info, StampMultisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = StampProposal::LEN,
+        seeds = [
+            StampProposal::SEED_PREFIX,
+            &stamp_multisig.proposal_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub stamp_proposal: Account<'info, StampProposal>,
+
+    /// Multisig signer creating the proposal; counts as its first approval
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Propose issuing or revoking an endpoint's stamp (multisig signers only)
+pub fn propose_stamp_action(
+    ctx: Context<ProposeStampAction>,
+    action: StampAction,
+    endpoint_hash: [u8; 32],
+    reason_hash: [u8; 32],
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.stamp_multisig;
+    let signer_index = multisig
+        .signer_index(ctx.accounts.proposer.key)
+        .ok_or(ValidationError::UnauthorizedSigner)?;
+
+    let proposal = &mut ctx.accounts.stamp_proposal;
+    proposal.proposal_id = multisig.proposal_count;
+    proposal.action = action;
+    proposal.endpoint_hash = endpoint_hash;
+    // Issuance carries no reason
+    proposal.reason_hash = match action {
+        StampAction::Issue => [0; 32],
+        StampAction::Revoke => reason_hash,
+    };
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.approval_bitmap = 0;
+    proposal.approval_count = 0;
+    proposal.status = ProposalStatus::Pending;
+    proposal.created_at = Clock::get()?.unix_timestamp;
+    proposal.executed_at = 0;
+    proposal.bump = ctx.bumps.stamp_proposal;
+
+    // Auto-approve by proposer
+    proposal.record_approval(signer_index, multisig.threshold);
+
+    multisig.proposal_count = multisig.proposal_count.saturating_add(1);
+
+    msg!("Stamp proposal {} ({:?}) created by signer {}",
+         proposal.proposal_id, action, signer_index);
+
+    Ok(())
+}
+
+// ==================== APPROVE STAMP PROPOSAL ====================
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ApproveStampProposal<'info> {
+    #[account(
+        seeds = [StampMultisig::SEED_PREFIX],
+        bump = stamp_multisig.bump
+    )]
+    pub stamp_multisig: Account<'info, StampMultisig>,
+
+    #[account(
+        mut,
+        seeds = [StampProposal::SEED_PREFIX, &proposal_id.to_le_bytes()],
+        bump = stamp_proposal.bump
+    )]
+    pub stamp_proposal: Account<'info, StampProposal>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Approve a pending stamp proposal; it becomes executable once the threshold is met
+pub fn approve_stamp_proposal(ctx: Context<ApproveStampProposal>, _proposal_id: u64) -> Result<()> {
+    let multisig = &ctx.accounts.stamp_multisig;
+    let proposal = &mut ctx.accounts.stamp_proposal;
+
+    require!(
+        proposal.status == ProposalStatus::Pending,
+        ValidationError::ProposalNotPending
+    );
+    require!(
+        !proposal.is_expired(Clock::get()?.unix_timestamp),
+        ValidationError::ProposalExpired
+    );
+
+    let signer_index = multisig
+        .signer_index(ctx.accounts.signer.key)
+        .ok_or(ValidationError::UnauthorizedSigner)?;
+    require!(
+        !proposal.has_approved(signer_index),
+        ValidationError::AlreadyApproved
+    );
+
+    proposal.record_approval(signer_index, multisig.threshold);
+
+    msg!("Stamp proposal {} has {}/{} approvals",
+         proposal.proposal_id, proposal.approval_count, multisig.threshold);
+
+    Ok(())
+}
+
+/// Gate a stamp action on multisig quorum when the authority has opted in.
+/// In single-key mode the authority's signature suffices and any proposal is ignored;
+/// otherwise `proposal` must be approved for exactly this action, and is consumed.
+pub(crate) fn authorize_stamp_action(
+    authority_account: &ValidationAuthority,
+    proposal: Option<&mut Account<StampProposal>>,
+    action: StampAction,
+    endpoint_hash: [u8; 32],
+    reason_hash: [u8; 32],
+    now: i64,
+) -> Result<()> {
+    if !authority_account.multisig_enabled() {
+        return Ok(());
+    }
+
+    let proposal = proposal.ok_or(ValidationError::MultisigApprovalRequired)?;
+    require!(
+        proposal.status == ProposalStatus::Approved,
+        ValidationError::MultisigApprovalRequired
+    );
+    require!(!proposal.is_expired(now), ValidationError::ProposalExpired);
+    require!(
+        proposal.action == action
+            && proposal.endpoint_hash == endpoint_hash
+            && proposal.reason_hash == reason_hash,
+        ValidationError::ProposalMismatch
+    );
+
+    proposal.status = ProposalStatus::Executed;
+    proposal.executed_at = now;
+
+    msg!("Executing stamp proposal {}", proposal.proposal_id);
+
+    Ok(())
+}
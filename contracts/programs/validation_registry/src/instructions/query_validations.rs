@@ -1,5 +1,18 @@
 use anchor_lang::prelude::*;
-use crate::state::EndpointValidation;
+use crate::state::{EndpointValidation, StampTier};
+use crate::error::ValidationError;
+
+/// Snapshot of an endpoint validation returned to callers via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ValidationView {
+    pub endpoint_url: String,
+    pub provider_agent: Pubkey,
+    pub consensus_score: u16,
+    pub stamp_issued: bool,
+    pub stamp_tier: StampTier,
+    pub test_count: u8,
+    pub timestamp: i64,
+}
 
 #[derive(Accounts)]
 pub struct QueryValidations<'info> {
@@ -13,26 +26,33 @@ pub struct QueryValidations<'info> {
     pub endpoint_validation: Account<'info, EndpointValidation>,
 }
 
-pub fn handler(ctx: Context<QueryValidations>) -> Result<()> {
+/// Return the endpoint's validation snapshot. Fails with BelowThreshold when the
+/// consensus score is under `min_score`, so callers can CPI it as a guard.
+pub fn handler(ctx: Context<QueryValidations>, min_score: u16) -> Result<ValidationView> {
     let validation = &ctx.accounts.endpoint_validation;
 
-    msg!("=== Endpoint Validation ===");
-    msg!("Endpoint URL: {}", validation.endpoint_url);
-    msg!("Provider Agent: {}", validation.provider_agent);
-    msg!("Consensus Score: {}/1000", validation.consensus_score);
-    msg!("Stamp Issued: {}", validation.stamp_issued);
-    msg!("Timestamp: {}", validation.timestamp);
-    msg!("Test Results Count: {}", validation.test_results.len());
+    require!(
+        validation.consensus_score >= min_score,
+        ValidationError::BelowThreshold
+    );
+
+    let view = ValidationView {
+        endpoint_url: validation.endpoint_url.clone(),
+        provider_agent: validation.provider_agent,
+        consensus_score: validation.consensus_score,
+        stamp_issued: validation.stamp_issued(),
+        stamp_tier: validation.stamp_tier,
+        test_count: validation.test_results.len() as u8,
+        timestamp: validation.timestamp,
+    };
 
-    for (i, result) in validation.test_results.iter().enumerate() {
-        msg!("Test #{}: {} - Success: {}, Score: {}/100, Response Time: {}ms",
-            i + 1,
-            result.llm_model,
-            result.success,
-            result.score,
-            result.response_time
-        );
-    }
+    msg!(
+        "Validation for endpoint {}: {}/1000, stamp {:?}, {} tests",
+        view.endpoint_url,
+        view.consensus_score,
+        view.stamp_tier,
+        view.test_count
+    );
 
-    Ok(())
+    Ok(view)
 }
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use identity_registry::state::AgentIdentity;
+use crate::constants::{IDENTITY_AGENT_SEED, IDENTITY_REGISTRY_PROGRAM_ID, MIN_VALIDATOR_BOND};
+use crate::state::ValidatorRecord;
+use crate::error::ValidationError;
+
+#[derive(Accounts)]
+pub struct RegisterValidator<'info> {
+    #[account(
+        init,
+        payer = validator,
+        space = ValidatorRecord::LEN,
+        seeds = [ValidatorRecord::SEED_PREFIX, validator.key().as_ref()],
+        bump
+    )]
+    pub validator_record: Account<'info, ValidatorRecord>,
+
+    /// Validator's identity (from identity_registry)
+    #[account(
+        seeds = [IDENTITY_AGENT_SEED, validator.key().as_ref()],
+        bump = validator_identity.bump,
+        seeds::program = IDENTITY_REGISTRY_PROGRAM_ID,
+        constraint = validator_identity.is_supported_version() @ ValidationError::UnsupportedIdentityVersion
+    )]
+    pub validator_identity: Account<'info, AgentIdentity>,
+
+    #[account(mut)]
+    pub validator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterValidator>, bond_lamports: u64) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        ctx.accounts.validator_identity.is_current(clock.unix_timestamp),
+        ValidationError::ValidatorIdentityInactive
+    );
+
+    require!(
+        bond_lamports >= MIN_VALIDATOR_BOND,
+        ValidationError::InsufficientValidatorBond
+    );
+
+    // The bond sits in the record itself, above its rent-exempt balance
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.validator.to_account_info(),
+                to: ctx.accounts.validator_record.to_account_info(),
+            },
+        ),
+        bond_lamports,
+    )?;
+
+    let record = &mut ctx.accounts.validator_record;
+    record.validator = ctx.accounts.validator.key();
+    record.bond_lamports = bond_lamports;
+    record.registered_at = clock.unix_timestamp;
+    record.is_slashed = false;
+    record.slashed_at = 0;
+    record.slash_reason_hash = [0; 32];
+    record.bump = ctx.bumps.validator_record;
+
+    msg!("Validator registered: {}", record.validator);
+    msg!("Bond: {} lamports", bond_lamports);
+
+    Ok(())
+}
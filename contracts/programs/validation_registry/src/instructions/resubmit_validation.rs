@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use super::submit_validation::{prepare_test_results, require_validator_in_good_standing};
+use crate::state::{EndpointValidation, StampTier, TestResult, ValidationAuthority, ValidatorRecord};
+use crate::error::ValidationError;
+
+#[derive(Accounts)]
+pub struct ResubmitValidation<'info> {
+    #[account(
+        mut,
+        seeds = [
+            EndpointValidation::SEED_PREFIX,
+            &endpoint_validation.endpoint_hash
+        ],
+        bump = endpoint_validation.bump
+    )]
+    pub endpoint_validation: Account<'info, EndpointValidation>,
+
+    #[account(
+        seeds = [ValidationAuthority::SEED_PREFIX],
+        bump = authority_account.bump
+    )]
+    pub authority_account: Account<'info, ValidationAuthority>,
+
+    /// Provider agent or validation authority
+    #[account(
+        constraint = signer.key() == endpoint_validation.provider_agent
+            || signer.key() == authority_account.authority
+            @ ValidationError::UnauthorizedResubmission
+    )]
+    pub signer: Signer<'info>,
+
+    /// Signer's validator record; required when a provider resubmits test results
+    #[account(
+        seeds = [ValidatorRecord::SEED_PREFIX, signer.key().as_ref()],
+        bump = validator_record.bump
+    )]
+    pub validator_record: Option<Account<'info, ValidatorRecord>>,
+}
+
+pub fn handler(ctx: Context<ResubmitValidation>, test_results: Vec<TestResult>) -> Result<()> {
+    // The validation authority is trusted; anyone else must be a validator in good standing
+    let signer = ctx.accounts.signer.key();
+    if !test_results.is_empty() && signer != ctx.accounts.authority_account.authority {
+        require_validator_in_good_standing(ctx.accounts.validator_record.as_deref())?;
+    }
+
+    // Open disputes point into the current results
+    require!(
+        ctx.accounts.endpoint_validation.open_disputes() == 0,
+        ValidationError::DisputesOpen
+    );
+
+    let mut test_results = test_results;
+    prepare_test_results(&mut test_results, signer)?;
+
+    let validation = &mut ctx.accounts.endpoint_validation;
+    let clock = Clock::get()?;
+
+    // A new round invalidates the previous consensus and any stamp it earned
+    validation.test_results = test_results;
+    validation.consensus_score = 0;
+    validation.stamp_tier = StampTier::None;
+    validation.timestamp = clock.unix_timestamp;
+    validation.validation_round = validation.validation_round.saturating_add(1);
+
+    msg!("Validation resubmitted for endpoint: {}", validation.endpoint_url);
+    msg!("Validation round: {}", validation.validation_round);
+    msg!("Test results count: {}", validation.test_results.len());
+
+    Ok(())
+}
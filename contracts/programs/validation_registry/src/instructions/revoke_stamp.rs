@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use super::multisig::authorize_stamp_action;
+use crate::state::{EndpointValidation, StampAction, StampProposal, StampTier, ValidationAuthority};
+use crate::error::ValidationError;
+
+#[derive(Accounts)]
+pub struct RevokeStamp<'info> {
+    #[account(
+        mut,
+        seeds = [
+            EndpointValidation::SEED_PREFIX,
+            &endpoint_validation.endpoint_hash
+        ],
+        bump = endpoint_validation.bump
+    )]
+    pub endpoint_validation: Account<'info, EndpointValidation>,
+
+    #[account(
+        seeds = [ValidationAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ ValidationError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, ValidationAuthority>,
+
+    /// Authority that can revoke stamps (with an approved proposal once the stamp multisig is enabled)
+    pub authority: Signer<'info>,
+
+    /// Approved Revoke proposal; required only once the stamp multisig is enabled
+    #[account(
+        mut,
+        seeds = [StampProposal::SEED_PREFIX, &stamp_proposal.proposal_id.to_le_bytes()],
+        bump = stamp_proposal.bump
+    )]
+    pub stamp_proposal: Option<Account<'info, StampProposal>>,
+}
+
+pub fn handler(ctx: Context<RevokeStamp>, reason_hash: [u8; 32]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    authorize_stamp_action(
+        &ctx.accounts.authority_account,
+        ctx.accounts.stamp_proposal.as_mut(),
+        StampAction::Revoke,
+        ctx.accounts.endpoint_validation.endpoint_hash,
+        reason_hash,
+        now,
+    )?;
+
+    let validation = &mut ctx.accounts.endpoint_validation;
+
+    require!(
+        validation.stamp_issued(),
+        ValidationError::StampNotIssued
+    );
+
+    // stamp_revoked_at distinguishes revocation from a stamp that simply expired
+    validation.stamp_tier = StampTier::None;
+    validation.stamp_revoked_at = now;
+    validation.stamp_revocation_reason = reason_hash;
+
+    msg!("Validation stamp revoked for endpoint: {}", validation.endpoint_url);
+    msg!("Provider agent: {}", validation.provider_agent);
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::{ValidationAuthority, ValidatorRecord};
+use crate::error::ValidationError;
+
+#[derive(Accounts)]
+pub struct SlashValidator<'info> {
+    #[account(
+        mut,
+        seeds = [ValidatorRecord::SEED_PREFIX, validator_record.validator.as_ref()],
+        bump = validator_record.bump,
+        constraint = !validator_record.is_slashed @ ValidationError::ValidatorSlashed
+    )]
+    pub validator_record: Account<'info, ValidatorRecord>,
+
+    #[account(
+        seeds = [ValidationAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ ValidationError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, ValidationAuthority>,
+
+    /// Authority that can slash validators; receives the seized bond
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SlashValidator>, reason_hash: [u8; 32]) -> Result<()> {
+    let seized = ctx.accounts.validator_record.bond_lamports;
+
+    // Program-owned account, so the bond can be moved directly; rent stays behind
+    ctx.accounts.validator_record.sub_lamports(seized)?;
+    ctx.accounts.authority.add_lamports(seized)?;
+
+    let record = &mut ctx.accounts.validator_record;
+    record.bond_lamports = 0;
+    record.is_slashed = true;
+    record.slashed_at = Clock::get()?.unix_timestamp;
+    record.slash_reason_hash = reason_hash;
+
+    msg!("Validator slashed: {}", record.validator);
+    msg!("Bond seized: {} lamports", seized);
+
+    Ok(())
+}
@@ -1,5 +1,14 @@
 use anchor_lang::prelude::*;
-use crate::state::{EndpointValidation, TestResult};
+use anchor_lang::system_program::{transfer, Transfer};
+use identity_registry::state::AgentIdentity;
+use solana_sha256_hasher::hash;
+use crate::constants::{
+    IDENTITY_AGENT_SEED, IDENTITY_REGISTRY_PROGRAM_ID, MAX_INDEXED_ENDPOINTS, MAX_LLM_MODEL_LEN,
+};
+use crate::state::{
+    EndpointValidation, ProviderEndpointIndex, RewardPool, StampTier, TestResult, ValidationConfig,
+    ValidatorRecord,
+};
 use crate::error::ValidationError;
 
 #[derive(Accounts)]
@@ -18,13 +27,54 @@ pub struct SubmitValidation<'info> {
     pub endpoint_validation: Account<'info, EndpointValidation>,
 
     /// The provider agent's public key
-    /// CHECK: Validated as provider in instruction
+    /// CHECK: Bound to provider_identity by its seeds
     pub provider_agent: UncheckedAccount<'info>,
 
+    /// Provider's identity (from identity_registry); must be active
+    #[account(
+        seeds = [IDENTITY_AGENT_SEED, provider_agent.key().as_ref()],
+        bump = provider_identity.bump,
+        seeds::program = IDENTITY_REGISTRY_PROGRAM_ID,
+        constraint = provider_identity.is_supported_version() @ ValidationError::UnsupportedIdentityVersion
+    )]
+    pub provider_identity: Account<'info, AgentIdentity>,
+
+    /// Provider's endpoint index, created on its first submission
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ProviderEndpointIndex::LEN,
+        seeds = [ProviderEndpointIndex::SEED_PREFIX, provider_agent.key().as_ref()],
+        bump
+    )]
+    pub endpoint_index: Account<'info, ProviderEndpointIndex>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// Payer's validator record; required when test results are submitted
+    #[account(
+        seeds = [ValidatorRecord::SEED_PREFIX, payer.key().as_ref()],
+        bump = validator_record.bump
+    )]
+    pub validator_record: Option<Account<'info, ValidatorRecord>>,
+
+    /// Validation config; no fee is charged until it is initialized
+    /// CHECK: PDA checked by seeds; read with ValidationConfig::load
+    #[account(seeds = [ValidationConfig::SEED_PREFIX], bump)]
+    pub validation_config: UncheckedAccount<'info>,
+
+    /// Endpoint's reward pool; required while a submission fee is configured
+    #[account(
+        init,
+        payer = payer,
+        space = RewardPool::LEN,
+        seeds = [RewardPool::SEED_PREFIX, &endpoint_hash],
+        bump
+    )]
+    pub reward_pool: Option<Account<'info, RewardPool>>,
 }
 
 pub fn handler(
@@ -38,30 +88,85 @@ pub fn handler(
         ValidationError::EndpointUrlTooLong
     );
 
+    // The PDA is keyed by the hash, so it must commit to the canonical URL
+    let endpoint_url = canonicalize_endpoint_url(&endpoint_url)?;
     require!(
-        test_results.len() <= 10,
-        ValidationError::TooManyTestResults
+        hash(endpoint_url.as_bytes()).to_bytes() == endpoint_hash,
+        ValidationError::HashMismatch
     );
 
-    // Validate each test result
-    for result in &test_results {
-        require!(
-            result.llm_model.len() <= 50,
-            ValidationError::LlmModelNameTooLong
-        );
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.provider_identity.is_current(clock.unix_timestamp),
+        ValidationError::ProviderIdentityInactive
+    );
+
+    // An empty submission only opens the endpoint for independent validators
+    if !test_results.is_empty() {
+        require_validator_in_good_standing(ctx.accounts.validator_record.as_deref())?;
+    }
+
+    let mut test_results = test_results;
+    prepare_test_results(&mut test_results, ctx.accounts.payer.key())?;
+
+    // Each endpoint PDA is created once, so every submission here is a new endpoint
+    let index = &mut ctx.accounts.endpoint_index;
+    if index.provider == Pubkey::default() {
+        index.provider = ctx.accounts.provider_agent.key();
+        index.bump = ctx.bumps.endpoint_index;
+    }
+    require!(
+        index.endpoint_hashes.len() < MAX_INDEXED_ENDPOINTS,
+        ValidationError::EndpointIndexFull
+    );
+    index.endpoint_hashes.push(endpoint_hash);
+    index.latest_endpoint_hash = endpoint_hash;
+
+    // Whoever opens the endpoint funds its validators, so a provider can't dodge the
+    // fee by submitting from a second wallet
+    let config = ValidationConfig::load(&ctx.accounts.validation_config)?;
+    if let Some(reward_pool) = ctx.accounts.reward_pool.as_mut() {
+        reward_pool.endpoint_hash = endpoint_hash;
+        reward_pool.counted_results_only = config
+            .as_ref()
+            .is_none_or(|config| config.reward_counted_results_only);
+        reward_pool.bump = ctx.bumps.reward_pool.unwrap();
+    }
+    let fee = config.map_or(0, |config| config.submission_fee_lamports);
+    if fee > 0 {
+        let reward_pool = ctx
+            .accounts
+            .reward_pool
+            .as_ref()
+            .ok_or(ValidationError::RewardPoolRequired)?;
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: reward_pool.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+        ctx.accounts.reward_pool.as_mut().unwrap().total_lamports = fee;
+
+        msg!("Submission fee: {} lamports", fee);
     }
 
     let endpoint_validation = &mut ctx.accounts.endpoint_validation;
-    let clock = Clock::get()?;
 
     endpoint_validation.endpoint_hash = endpoint_hash;
     endpoint_validation.endpoint_url = endpoint_url;
     endpoint_validation.provider_agent = ctx.accounts.provider_agent.key();
     endpoint_validation.test_results = test_results;
     endpoint_validation.consensus_score = 0; // Will be calculated separately
-    endpoint_validation.stamp_issued = false;
+    endpoint_validation.stamp_tier = StampTier::None;
     endpoint_validation.timestamp = clock.unix_timestamp;
     endpoint_validation.bump = ctx.bumps.endpoint_validation;
+    endpoint_validation.validation_round = 1;
+    endpoint_validation.payer = ctx.accounts.payer.key();
+
 
     msg!("Validation submitted for endpoint: {}", endpoint_validation.endpoint_url);
     msg!("Provider agent: {}", ctx.accounts.provider_agent.key());
@@ -69,3 +174,60 @@ pub fn handler(
 
     Ok(())
 }
+
+/// Canonical form of an https endpoint URL: lowercase scheme and host, no
+/// default port and no trailing slash. Path and query are kept as given.
+pub fn canonicalize_endpoint_url(url: &str) -> Result<String> {
+    let (scheme, rest) = url
+        .split_once("://")
+        .ok_or(ValidationError::InvalidUrlScheme)?;
+    require!(
+        scheme.eq_ignore_ascii_case("https"),
+        ValidationError::InvalidUrlScheme
+    );
+
+    let host_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (host, path) = rest.split_at(host_end);
+    let host = host.to_ascii_lowercase();
+    let host = host.strip_suffix(":443").unwrap_or(&host);
+    require!(!host.is_empty(), ValidationError::InvalidEndpointUrl);
+
+    let path = path.strip_suffix('/').unwrap_or(path);
+    Ok(format!("https://{}{}", host, path))
+}
+
+/// Bounds-check a submitted batch of test results and attribute them to the submitter
+pub(crate) fn prepare_test_results(test_results: &mut [TestResult], submitter: Pubkey) -> Result<()> {
+    require!(
+        test_results.len() <= 10,
+        ValidationError::TooManyTestResults
+    );
+
+    // Validate each test result
+    for result in test_results.iter_mut() {
+        check_test_result(result)?;
+        result.validator = submitter;
+    }
+
+    Ok(())
+}
+
+/// Bounds-check a single test result's model name and metrics
+pub(crate) fn check_test_result(result: &TestResult) -> Result<()> {
+    require!(
+        result.llm_model.len() <= MAX_LLM_MODEL_LEN,
+        ValidationError::LlmModelNameTooLong
+    );
+    require!(
+        result.http_status_class <= 5,
+        ValidationError::InvalidHttpStatusClass
+    );
+    Ok(())
+}
+
+/// Reject submitters without a registered, unslashed ValidatorRecord
+pub(crate) fn require_validator_in_good_standing(record: Option<&ValidatorRecord>) -> Result<()> {
+    let record = record.ok_or(ValidationError::ValidatorNotRegistered)?;
+    require!(record.is_in_good_standing(), ValidationError::ValidatorSlashed);
+    Ok(())
+}
@@ -1,5 +1,6 @@
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -7,6 +8,7 @@ use anchor_lang::prelude::*;
 
 pub use constants::*;
 pub use error::*;
+#[allow(ambiguous_glob_reexports)]
 pub use instructions::*;
 pub use state::*;
 
@@ -31,9 +33,38 @@ pub mod validation_registry {
         instructions::submit_validation::handler(ctx, endpoint_url, endpoint_hash, test_results)
     }
 
-    /// Query validation results for an endpoint
-    pub fn query_validations(ctx: Context<QueryValidations>) -> Result<()> {
-        instructions::query_validations::handler(ctx)
+    /// Re-validate an endpoint with fresh test results, starting a new round
+    pub fn resubmit_validation(
+        ctx: Context<ResubmitValidation>,
+        test_results: Vec<TestResult>,
+    ) -> Result<()> {
+        instructions::resubmit_validation::handler(ctx, test_results)
+    }
+
+    /// Append a single validator's test result to an endpoint validation
+    pub fn append_test_result(
+        ctx: Context<AppendTestResult>,
+        test_result: TestResult,
+    ) -> Result<()> {
+        instructions::append_test_result::handler(ctx, test_result)
+    }
+
+    /// Register as a validator, bonding `bond_lamports` (requires an active agent identity)
+    pub fn register_validator(ctx: Context<RegisterValidator>, bond_lamports: u64) -> Result<()> {
+        instructions::register_validator::handler(ctx, bond_lamports)
+    }
+
+    /// Slash a validator for provably false results, seizing its bond
+    pub fn slash_validator(ctx: Context<SlashValidator>, reason_hash: [u8; 32]) -> Result<()> {
+        instructions::slash_validator::handler(ctx, reason_hash)
+    }
+
+    /// Return an endpoint's validation view, failing if its consensus is below `min_score`
+    pub fn query_validations(
+        ctx: Context<QueryValidations>,
+        min_score: u16,
+    ) -> Result<ValidationView> {
+        instructions::query_validations::handler(ctx, min_score)
     }
 
     /// Calculate consensus score from validation results
@@ -45,4 +76,160 @@ pub mod validation_registry {
     pub fn issue_validation_stamp(ctx: Context<IssueValidationStamp>) -> Result<()> {
         instructions::issue_validation_stamp::handler(ctx)
     }
+
+    /// Revoke an endpoint's validation stamp, recording the reason hash
+    pub fn revoke_stamp(ctx: Context<RevokeStamp>, reason_hash: [u8; 32]) -> Result<()> {
+        instructions::revoke_stamp::handler(ctx, reason_hash)
+    }
+
+    /// Clear a validation stamp that is past its expiry (permissionless)
+    pub fn mark_stamp_expired(ctx: Context<MarkStampExpired>) -> Result<()> {
+        instructions::mark_stamp_expired::handler(ctx)
+    }
+
+    /// Dispute one of the endpoint's test results, escrowing a bond (provider agent only)
+    pub fn dispute_validation(
+        ctx: Context<DisputeValidation>,
+        endpoint_hash: [u8; 32],
+        disputed_result_index: u8,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::dispute::dispute_validation(
+            ctx,
+            endpoint_hash,
+            disputed_result_index,
+            evidence_hash,
+        )
+    }
+
+    /// Uphold or reject an open dispute (authority only)
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, uphold: bool) -> Result<()> {
+        instructions::dispute::resolve_dispute(ctx, uphold)
+    }
+
+    /// Close a stale, unstamped validation and return its rent to the payer
+    pub fn close_validation(ctx: Context<CloseValidation>) -> Result<()> {
+        instructions::close_validation::handler(ctx)
+    }
+
+    /// Claim a validator's pro-rata share of an endpoint's submission fees
+    pub fn claim_validator_reward(ctx: Context<ClaimValidatorReward>) -> Result<()> {
+        instructions::claim_validator_reward::handler(ctx)
+    }
+
+    /// Create the validation config PDA (authority only)
+    pub fn initialize_validation_config(ctx: Context<InitializeValidationConfig>) -> Result<()> {
+        instructions::config::initialize_validation_config(ctx)
+    }
+
+    /// Set how long newly issued stamps stay valid (authority only)
+    pub fn update_stamp_validity(
+        ctx: Context<UpdateValidationConfig>,
+        stamp_validity_seconds: i64,
+    ) -> Result<()> {
+        instructions::config::update_stamp_validity(ctx, stamp_validity_seconds)
+    }
+
+    /// Set the consensus thresholds for Bronze, Silver and Gold stamps (authority only)
+    pub fn update_stamp_thresholds(
+        ctx: Context<UpdateValidationConfig>,
+        bronze_threshold: u16,
+        silver_threshold: u16,
+        gold_threshold: u16,
+    ) -> Result<()> {
+        instructions::config::update_stamp_thresholds(
+            ctx,
+            bronze_threshold,
+            silver_threshold,
+            gold_threshold,
+        )
+    }
+
+    /// Set how long validations are retained before they can be closed (authority only)
+    pub fn update_retention_period(
+        ctx: Context<UpdateValidationConfig>,
+        retention_seconds: i64,
+    ) -> Result<()> {
+        instructions::config::update_retention_period(ctx, retention_seconds)
+    }
+
+    /// Set the p95 latency above which results are penalized in consensus (authority only)
+    pub fn update_latency_threshold(
+        ctx: Context<UpdateValidationConfig>,
+        p95_latency_threshold_ms: u32,
+    ) -> Result<()> {
+        instructions::config::update_latency_threshold(ctx, p95_latency_threshold_ms)
+    }
+
+    /// Set the provider submission fee and reward split rule (authority only)
+    pub fn update_reward_config(
+        ctx: Context<UpdateValidationConfig>,
+        submission_fee_lamports: u64,
+        reward_counted_results_only: bool,
+    ) -> Result<()> {
+        instructions::config::update_reward_config(
+            ctx,
+            submission_fee_lamports,
+            reward_counted_results_only,
+        )
+    }
+
+    /// Nominate a new validation authority (current authority only)
+    pub fn transfer_validation_authority(
+        ctx: Context<TransferValidationAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::authority::transfer_validation_authority(ctx, new_authority)
+    }
+
+    /// Cancel a pending authority nomination (current authority only)
+    pub fn cancel_validation_authority_transfer(
+        ctx: Context<CancelValidationAuthorityTransfer>,
+    ) -> Result<()> {
+        instructions::authority::cancel_validation_authority_transfer(ctx)
+    }
+
+    /// Accept a pending authority nomination (nominee only)
+    pub fn accept_validation_authority(ctx: Context<AcceptValidationAuthority>) -> Result<()> {
+        instructions::authority::accept_validation_authority(ctx)
+    }
+
+    /// Create the stamp multisig with its signers and threshold (authority only)
+    pub fn initialize_stamp_multisig(
+        ctx: Context<InitializeStampMultisig>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::multisig::initialize_stamp_multisig(ctx, signers, threshold)
+    }
+
+    /// Require multisig quorum for stamp issuance and revocation from now on (authority only)
+    pub fn enable_stamp_multisig(ctx: Context<EnableStampMultisig>) -> Result<()> {
+        instructions::authority::enable_stamp_multisig(ctx)
+    }
+
+    /// Propose issuing or revoking an endpoint's stamp (multisig signers only)
+    pub fn propose_stamp_action(
+        ctx: Context<ProposeStampAction>,
+        action: StampAction,
+        endpoint_hash: [u8; 32],
+        reason_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::multisig::propose_stamp_action(ctx, action, endpoint_hash, reason_hash)
+    }
+
+    /// Approve a pending stamp proposal (multisig signers only)
+    pub fn approve_stamp_proposal(ctx: Context<ApproveStampProposal>, proposal_id: u64) -> Result<()> {
+        instructions::multisig::approve_stamp_proposal(ctx, proposal_id)
+    }
+
+    /// Resize a legacy EndpointValidation to the current layout (permissionless)
+    pub fn migrate_endpoint_validation(ctx: Context<MigrateEndpointValidation>) -> Result<()> {
+        instructions::migrate::migrate_endpoint_validation(ctx)
+    }
+
+    /// Resize a legacy authority account to the current layout (permissionless)
+    pub fn migrate_authority(ctx: Context<MigrateAuthority>) -> Result<()> {
+        instructions::migrate::migrate_authority(ctx)
+    }
 }
@@ -1,13 +1,94 @@
 use anchor_lang::prelude::*;
+use crate::constants::{
+    COST_NOT_REPORTED, MAX_INDEXED_ENDPOINTS, MAX_MULTISIG_SIGNERS, PROPOSAL_EXPIRY_SECONDS,
+    RESPONSE_TIMEOUT_MS,
+};
 
-/// Test result from a single LLM validation
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+/// Test result from a single LLM validation.
+/// The metrics after `validator` are optional; unreported values hold the
+/// sentinels noted on each field.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub struct TestResult {
-    #[max_len(50)]
+    #[max_len(32)]
     pub llm_model: String,      // e.g., "gpt-4", "claude-3", "gemini-pro"
     pub success: bool,           // Whether the test passed
     pub response_time: u64,      // Response time in milliseconds
     pub score: u8,               // Quality score 0-100
+    pub validator: Pubkey,       // Wallet that submitted this result (set by the program)
+    pub p50_ms: u32,             // Median latency in milliseconds (0 = not reported)
+    pub p95_ms: u32,             // 95th percentile latency in milliseconds (0 = not reported)
+    pub cost_micro_usd: u64,     // Token cost in micro-USD (COST_NOT_REPORTED = not reported)
+    pub http_status_class: u8,   // 1-5 for 1xx-5xx (0 = not reported)
+    pub error_code_hash: [u8; 32], // sha256 of the error code string (zeroed = no error)
+}
+
+impl TestResult {
+    /// Serialized size of one result
+    pub const LEN: usize = 4 + 32 + // llm_model (String with max 32 chars)
+        1 + // success
+        8 + // response_time
+        1 + // score
+        32 + // validator
+        4 + // p50_ms
+        4 + // p95_ms
+        8 + // cost_micro_usd
+        1 + // http_status_class
+        32; // error_code_hash
+
+    /// Serialized size of one result before the metrics were added (50-char llm_model)
+    pub const LEGACY_LEN: usize = 4 + 50 + 1 + 8 + 1 + 32;
+
+    /// Whether the result counts toward consensus: a success that didn't time out
+    pub fn is_counted(&self) -> bool {
+        self.success && self.response_time < RESPONSE_TIMEOUT_MS
+    }
+}
+
+/// TestResult as stored before the metrics were added
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyTestResult {
+    pub llm_model: String,
+    pub success: bool,
+    pub response_time: u64,
+    pub score: u8,
+    pub validator: Pubkey,
+}
+
+impl From<LegacyTestResult> for TestResult {
+    /// Upgrade a stored result, trimming llm_model to 32 bytes on a char boundary
+    fn from(legacy: LegacyTestResult) -> Self {
+        let mut llm_model = legacy.llm_model;
+        let mut end = llm_model.len().min(32);
+        while !llm_model.is_char_boundary(end) {
+            end -= 1;
+        }
+        llm_model.truncate(end);
+
+        Self {
+            llm_model,
+            success: legacy.success,
+            response_time: legacy.response_time,
+            score: legacy.score,
+            validator: legacy.validator,
+            p50_ms: 0,
+            p95_ms: 0,
+            cost_micro_usd: COST_NOT_REPORTED,
+            http_status_class: 0,
+            error_code_hash: [0; 32],
+        }
+    }
+}
+
+/// Validation stamp tier, ordered from none to highest.
+/// Serialized as a single byte, so the former `stamp_issued: bool` reads as
+/// None (false) or Bronze (true).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Debug, InitSpace)]
+pub enum StampTier {
+    #[default]
+    None,
+    Bronze,
+    Silver,
+    Gold,
 }
 
 /// Endpoint Validation Account
@@ -25,21 +106,46 @@ pub struct EndpointValidation {
     /// Provider agent's public key
     pub provider_agent: Pubkey,
 
-    /// Validation test results (up to 10 LLM tests)
+    /// Validation test results (up to 10 LLM tests, at most one per validator
+    /// when appended)
     #[max_len(10)]
     pub test_results: Vec<TestResult>,
 
     /// Consensus score (0-1000)
     pub consensus_score: u16,
 
-    /// Whether validation stamp has been issued
-    pub stamp_issued: bool,
+    /// Tier of the issued validation stamp (None if not stamped)
+    pub stamp_tier: StampTier,
 
     /// Timestamp of validation
     pub timestamp: i64,
 
     /// PDA bump seed
     pub bump: u8,
+
+    /// Number of times this endpoint has been validated (1 on first submission)
+    pub validation_round: u32,
+
+    /// When the current (or last) stamp was issued (0 if never)
+    pub stamp_issued_at: i64,
+
+    /// When the current (or last) stamp stops being valid (0 = no expiry)
+    pub stamp_expires_at: i64,
+
+    /// When the last stamp was revoked by the authority (0 if not revoked)
+    pub stamp_revoked_at: i64,
+
+    /// Hash of the off-chain reason for the revocation
+    pub stamp_revocation_reason: [u8; 32],
+
+    /// Bitmask of test_results indices under an open dispute
+    pub disputed_results: u16,
+
+    /// Disputes ever opened on this endpoint (next ValidationDispute index)
+    pub dispute_count: u32,
+
+    /// Wallet that paid the account's rent; receives it back on close_validation
+    pub payer: Pubkey,
 }
 
 impl EndpointValidation {
@@ -51,11 +157,66 @@ impl EndpointValidation {
         32 + // endpoint_hash
         4 + 200 + // endpoint_url (String with max 200 chars)
         32 + // provider_agent
-        4 + (10 * (4 + 50 + 1 + 8 + 1)) + // test_results (Vec with max 10 TestResults)
+        4 + (10 * TestResult::LEN) + // test_results (Vec with max 10 TestResults)
         2 + // consensus_score
-        1 + // stamp_issued
+        1 + // stamp_tier
         8 + // timestamp
-        1; // bump
+        1 + // bump
+        4 + // validation_round
+        8 + // stamp_issued_at
+        8 + // stamp_expires_at
+        8 + // stamp_revoked_at
+        32 + // stamp_revocation_reason
+        2 + // disputed_results
+        4 + // dispute_count
+        32; // payer
+
+    /// Size before TestResult gained its metrics
+    pub const PRE_METRICS_LEN: usize =
+        Self::LEN - 10 * (TestResult::LEN - TestResult::LEGACY_LEN);
+
+    /// Size before payer was appended
+    pub const PRE_PAYER_LEN: usize = Self::PRE_METRICS_LEN - 32;
+
+    /// Size before disputed_results and dispute_count were appended
+    pub const PRE_DISPUTE_LEN: usize = Self::PRE_PAYER_LEN - 2 - 4;
+
+    /// Size before stamp_issued_at, stamp_expires_at, stamp_revoked_at and
+    /// stamp_revocation_reason were appended
+    pub const LEGACY_LEN: usize = Self::PRE_DISPUTE_LEN - 8 - 8 - 8 - 32;
+
+    /// Compatibility view: stamped at any tier from Bronze up
+    pub fn stamp_issued(&self) -> bool {
+        self.stamp_tier >= StampTier::Bronze
+    }
+
+    /// Whether the endpoint holds a stamp that is neither expired nor revoked at `now`
+    pub fn is_stamp_valid(&self, now: i64) -> bool {
+        self.stamp_issued() && (self.stamp_expires_at == 0 || now < self.stamp_expires_at)
+    }
+
+    /// Whether the last stamp ended through revocation rather than expiry
+    pub fn is_stamp_revoked(&self) -> bool {
+        self.stamp_revoked_at != 0
+    }
+
+    /// Number of disputes awaiting resolve_dispute
+    pub fn open_disputes(&self) -> u32 {
+        self.disputed_results.count_ones()
+    }
+
+    /// Whether the result at `index` is under an open dispute
+    pub fn is_result_disputed(&self, index: usize) -> bool {
+        index < 16 && self.disputed_results & (1 << index) != 0
+    }
+
+    /// Remove the result at `index`, shifting the dispute flags of later results down with it
+    pub fn remove_test_result(&mut self, index: usize) {
+        self.test_results.remove(index);
+        let below = self.disputed_results & ((1u16 << index) - 1);
+        let above = (self.disputed_results >> (index + 1)) << index;
+        self.disputed_results = below | above;
+    }
 }
 
 /// Authority configuration for validation registry
@@ -68,6 +229,12 @@ pub struct ValidationAuthority {
 
     /// PDA bump seed
     pub bump: u8,
+
+    /// Nominated successor awaiting acceptance (default pubkey = none)
+    pub pending_authority: Pubkey,
+
+    /// StampMultisig whose quorum gates stamp issuance and revocation (default pubkey = single key)
+    pub stamp_multisig: Pubkey,
 }
 
 impl ValidationAuthority {
@@ -77,5 +244,483 @@ impl ValidationAuthority {
     /// Calculate space for rent
     pub const LEN: usize = 8 + // discriminator
         32 + // authority
+        1 + // bump
+        32 + // pending_authority
+        32; // stamp_multisig
+
+    /// Size of accounts created before pending_authority and stamp_multisig were added
+    pub const LEGACY_LEN: usize = 8 + 32 + 1;
+
+    /// Whether stamps need an approved StampProposal rather than the authority alone
+    pub fn multisig_enabled(&self) -> bool {
+        self.stamp_multisig != Pubkey::default()
+    }
+}
+
+/// Signers whose quorum can gate stamp issuance and revocation
+/// PDA seeds: ["stamp_multisig"]
+#[account]
+#[derive(InitSpace)]
+pub struct StampMultisig {
+    /// Authorized signers (up to MAX_MULTISIG_SIGNERS)
+    #[max_len(7)]
+    pub signers: Vec<Pubkey>,
+
+    /// Approvals required before a proposal can be executed
+    pub threshold: u8,
+
+    /// Total proposals created (for unique proposal IDs)
+    pub proposal_count: u64,
+
+    /// Creation timestamp
+    pub created_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl StampMultisig {
+    pub const SEED_PREFIX: &'static [u8] = b"stamp_multisig";
+
+    pub const LEN: usize = 8 + // discriminator
+        4 + (32 * MAX_MULTISIG_SIGNERS) + // signers vec
+        1 + // threshold
+        8 + // proposal_count
+        8 + // created_at
+        1; // bump
+
+    /// Position of `key` among the signers, used as its approval bit
+    pub fn signer_index(&self, key: &Pubkey) -> Option<u8> {
+        self.signers.iter().position(|s| s == key).map(|i| i as u8)
+    }
+}
+
+/// Stamp action a multisig proposal authorizes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum StampAction {
+    Issue,
+    Revoke,
+}
+
+/// Proposal lifecycle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
+pub enum ProposalStatus {
+    #[default]
+    Pending,
+    Approved,
+    Executed,
+}
+
+/// Multisig approval for one stamp action on one endpoint
+/// PDA seeds: ["stamp_proposal", proposal_id.to_le_bytes()]
+#[account]
+#[derive(InitSpace)]
+pub struct StampProposal {
+    /// Unique proposal ID
+    pub proposal_id: u64,
+
+    /// Action to authorize
+    pub action: StampAction,
+
+    /// Endpoint the action applies to
+    pub endpoint_hash: [u8; 32],
+
+    /// Revocation reason hash (zeroed for issuance)
+    pub reason_hash: [u8; 32],
+
+    /// Signer that created the proposal
+    pub proposer: Pubkey,
+
+    /// Signers who have approved, by signer index
+    pub approval_bitmap: u8,
+
+    /// Number of approvals received
+    pub approval_count: u8,
+
+    /// Current status
+    pub status: ProposalStatus,
+
+    /// Creation timestamp
+    pub created_at: i64,
+
+    /// Execution timestamp (0 until executed)
+    pub executed_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl StampProposal {
+    pub const SEED_PREFIX: &'static [u8] = b"stamp_proposal";
+
+    pub const LEN: usize = 8 + // discriminator
+        8 + // proposal_id
+        1 + // action
+        32 + // endpoint_hash
+        32 + // reason_hash
+        32 + // proposer
+        1 + // approval_bitmap
+        1 + // approval_count
+        1 + // status
+        8 + // created_at
+        8 + // executed_at
+        1; // bump
+
+    /// Check if a signer has already approved
+    pub fn has_approved(&self, signer_index: u8) -> bool {
+        (self.approval_bitmap & (1 << signer_index)) != 0
+    }
+
+    /// Record approval from a signer, moving to Approved once `threshold` is met
+    pub fn record_approval(&mut self, signer_index: u8, threshold: u8) {
+        self.approval_bitmap |= 1 << signer_index;
+        self.approval_count = self.approval_count.saturating_add(1);
+        if self.approval_count >= threshold {
+            self.status = ProposalStatus::Approved;
+        }
+    }
+
+    /// Proposals can no longer be approved or executed after PROPOSAL_EXPIRY_SECONDS
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        current_time > self.created_at.saturating_add(PROPOSAL_EXPIRY_SECONDS)
+    }
+}
+
+/// Registered validator allowed to submit test results
+/// PDA seeds: ["validator", validator]
+#[account]
+#[derive(InitSpace)]
+pub struct ValidatorRecord {
+    /// Validator wallet (must hold an active AgentIdentity)
+    pub validator: Pubkey,
+
+    /// Lamports bonded in this account on top of its rent
+    pub bond_lamports: u64,
+
+    /// Registration timestamp
+    pub registered_at: i64,
+
+    /// Set when the authority slashes the validator for false results
+    pub is_slashed: bool,
+
+    /// Slash timestamp (0 if never slashed)
+    pub slashed_at: i64,
+
+    /// Hash of the off-chain evidence behind the slash
+    pub slash_reason_hash: [u8; 32],
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ValidatorRecord {
+    /// Seed prefix for PDA derivation
+    pub const SEED_PREFIX: &'static [u8] = b"validator";
+
+    /// Calculate space for rent
+    pub const LEN: usize = 8 + // discriminator
+        32 + // validator
+        8 + // bond_lamports
+        8 + // registered_at
+        1 + // is_slashed
+        8 + // slashed_at
+        32 + // slash_reason_hash
         1; // bump
+
+    /// Whether this validator may still submit test results
+    pub fn is_in_good_standing(&self) -> bool {
+        !self.is_slashed
+    }
+}
+
+/// Tunable validation parameters, managed by the validation authority
+/// PDA seeds: ["config"]
+#[account]
+#[derive(InitSpace)]
+pub struct ValidationConfig {
+    /// How long an issued stamp stays valid, in seconds
+    pub stamp_validity_seconds: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+
+    /// Minimum consensus score for a Bronze stamp
+    pub bronze_threshold: u16,
+
+    /// Minimum consensus score for a Silver stamp
+    pub silver_threshold: u16,
+
+    /// Minimum consensus score for a Gold stamp (all tests must also pass)
+    pub gold_threshold: u16,
+
+    /// How long after its last round a validation must sit before it can be closed, in seconds
+    pub retention_seconds: i64,
+
+    /// Reported p95 latency above this (ms) reduces a result's consensus score (0 = no penalty)
+    pub p95_latency_threshold_ms: u32,
+
+    /// Lamports paid into the endpoint's RewardPool by whoever opens it (0 = free)
+    pub submission_fee_lamports: u64,
+
+    /// Whether only results counted in consensus earn reward shares, rather than every result
+    pub reward_counted_results_only: bool,
+}
+
+impl ValidationConfig {
+    /// Seed prefix for PDA derivation
+    pub const SEED_PREFIX: &'static [u8] = b"config";
+
+    /// Calculate space for rent
+    pub const LEN: usize = 8 + // discriminator
+        8 + // stamp_validity_seconds
+        1 + // bump
+        2 + // bronze_threshold
+        2 + // silver_threshold
+        2 + // gold_threshold
+        8 + // retention_seconds
+        4 + // p95_latency_threshold_ms
+        8 + // submission_fee_lamports
+        1; // reward_counted_results_only
+
+    /// Read the (possibly uninitialized) config PDA; None until it is initialized
+    pub fn load(info: &AccountInfo) -> Result<Option<ValidationConfig>> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(None);
+        }
+        let data = info.try_borrow_data()?;
+        Ok(Some(ValidationConfig::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Tier earned by `consensus_score`; Gold also requires every test to have passed
+    pub fn tier_for(&self, consensus_score: u16, all_passed: bool) -> StampTier {
+        stamp_tier_for(
+            consensus_score,
+            all_passed,
+            self.bronze_threshold,
+            self.silver_threshold,
+            self.gold_threshold,
+        )
+    }
+}
+
+/// Map a consensus score onto a stamp tier using the given thresholds
+pub fn stamp_tier_for(
+    consensus_score: u16,
+    all_passed: bool,
+    bronze_threshold: u16,
+    silver_threshold: u16,
+    gold_threshold: u16,
+) -> StampTier {
+    if consensus_score >= gold_threshold && all_passed {
+        StampTier::Gold
+    } else if consensus_score >= silver_threshold {
+        StampTier::Silver
+    } else if consensus_score >= bronze_threshold {
+        StampTier::Bronze
+    } else {
+        StampTier::None
+    }
+}
+
+/// On-chain list of a provider's validated endpoints
+/// PDA seeds: ["endpoints", provider]
+#[account]
+#[derive(InitSpace)]
+pub struct ProviderEndpointIndex {
+    /// Provider agent wallet
+    pub provider: Pubkey,
+
+    /// Endpoint hashes submitted for this provider, oldest first
+    #[max_len(MAX_INDEXED_ENDPOINTS)]
+    pub endpoint_hashes: Vec<[u8; 32]>,
+
+    /// Most recently submitted endpoint hash
+    pub latest_endpoint_hash: [u8; 32],
+
+    /// Stamps issued across all of the provider's endpoints
+    pub stamps_issued: u32,
+
+    /// Most recently stamped endpoint hash
+    pub latest_stamped_hash: [u8; 32],
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ProviderEndpointIndex {
+    /// Seed prefix for PDA derivation
+    pub const SEED_PREFIX: &'static [u8] = b"endpoints";
+
+    /// Calculate space for rent
+    pub const LEN: usize = 8 + // discriminator
+        32 + // provider
+        4 + (MAX_INDEXED_ENDPOINTS * 32) + // endpoint_hashes
+        32 + // latest_endpoint_hash
+        4 + // stamps_issued
+        32 + // latest_stamped_hash
+        1; // bump
+}
+
+/// Lifecycle of a validation dispute and its bond
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default, Debug)]
+pub enum DisputeStatus {
+    /// Bond held in the record; stamping is frozen until resolved
+    #[default]
+    Open,
+    /// Disputed result removed; bond returned to the provider
+    Upheld,
+    /// Result kept; bond forfeited to the validation authority
+    Rejected,
+}
+
+/// A provider's challenge to one of its endpoint's test results. The record
+/// escrows the dispute bond (on top of its rent) until resolve_dispute.
+/// PDA seeds: ["dispute", endpoint_hash, dispute_index (u32 LE)]
+#[account]
+#[derive(InitSpace)]
+pub struct ValidationDispute {
+    /// Endpoint whose result is disputed
+    pub endpoint_hash: [u8; 32],
+
+    /// Index of this dispute for the endpoint (dispute_count at open time)
+    pub dispute_index: u32,
+
+    /// Provider agent that opened the dispute (paid the bond and rent)
+    pub disputer: Pubkey,
+
+    /// Position of the disputed result when the dispute was opened
+    pub result_index: u8,
+
+    /// Copy of the disputed result; located again by value on resolution,
+    /// since upholding an earlier dispute shifts later indices
+    pub disputed_result: TestResult,
+
+    /// Hash of the off-chain evidence
+    pub evidence_hash: [u8; 32],
+
+    /// Lamports escrowed in this account on top of its rent
+    pub bond_lamports: u64,
+
+    /// Validation round the disputed result belongs to
+    pub validation_round: u32,
+
+    /// Where the dispute is in its lifecycle
+    pub status: DisputeStatus,
+
+    /// Unix timestamp the dispute was opened
+    pub opened_at: i64,
+
+    /// Unix timestamp the dispute was resolved (0 while open)
+    pub resolved_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ValidationDispute {
+    /// Seed prefix for PDA derivation
+    pub const SEED_PREFIX: &'static [u8] = b"dispute";
+
+    /// Calculate space for rent
+    pub const LEN: usize = 8 + // discriminator
+        32 + // endpoint_hash
+        4 + // dispute_index
+        32 + // disputer
+        1 + // result_index
+        TestResult::LEN + // disputed_result
+        32 + // evidence_hash
+        8 + // bond_lamports
+        4 + // validation_round
+        1 + // status
+        8 + // opened_at
+        8 + // resolved_at
+        1; // bump
+}
+
+/// One validator's stake in a RewardPool, fixed when the pool is settled
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct RewardShare {
+    /// Validator wallet
+    pub validator: Pubkey,
+    /// Eligible results it contributed
+    pub results: u8,
+    /// Whether its share has been paid out
+    pub claimed: bool,
+}
+
+/// Submission fees for one endpoint, owed to the validators whose results it was judged on
+/// PDA seeds: ["reward_pool", endpoint_hash]
+#[account]
+#[derive(InitSpace)]
+pub struct RewardPool {
+    /// Endpoint the fees were paid for
+    pub endpoint_hash: [u8; 32],
+
+    /// Fees deposited, in lamports (on top of the pool's rent)
+    pub total_lamports: u64,
+
+    /// Fees paid out so far
+    pub distributed_lamports: u64,
+
+    /// Split rule copied from ValidationConfig at submission
+    pub counted_results_only: bool,
+
+    /// Whether shares have been fixed from the endpoint's results (on the first claim)
+    pub settled: bool,
+
+    /// Eligible results across all shares
+    pub eligible_results: u8,
+
+    /// Per-validator shares (one per distinct validator, at most one per result)
+    #[max_len(10)]
+    pub shares: Vec<RewardShare>,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RewardPool {
+    pub const SEED_PREFIX: &'static [u8] = b"reward_pool";
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // endpoint_hash
+        8 + // total_lamports
+        8 + // distributed_lamports
+        1 + // counted_results_only
+        1 + // settled
+        1 + // eligible_results
+        4 + (10 * (32 + 1 + 1)) + // shares (Vec with max 10 RewardShares)
+        1; // bump
+
+    /// Fix each validator's share from the endpoint's current results
+    pub fn settle(&mut self, test_results: &[TestResult]) {
+        let mut shares: Vec<RewardShare> = Vec::new();
+        for result in test_results
+            .iter()
+            .filter(|r| !self.counted_results_only || r.is_counted())
+        {
+            match shares.iter_mut().find(|s| s.validator == result.validator) {
+                Some(share) => share.results = share.results.saturating_add(1),
+                None => shares.push(RewardShare {
+                    validator: result.validator,
+                    results: 1,
+                    claimed: false,
+                }),
+            }
+        }
+        self.eligible_results = shares.iter().map(|s| s.results).sum();
+        self.shares = shares;
+        self.settled = true;
+    }
+
+    /// Lamports owed to share `index`. The last unclaimed share takes whatever
+    /// rounding left behind, so a single validator receives the whole pool.
+    pub fn payout(&self, index: usize) -> u64 {
+        let unclaimed = self.shares.iter().filter(|s| !s.claimed).count();
+        if unclaimed == 1 {
+            return self.total_lamports.saturating_sub(self.distributed_lamports);
+        }
+        (u128::from(self.total_lamports) * u128::from(self.shares[index].results)
+            / u128::from(self.eligible_results)) as u64
+    }
 }
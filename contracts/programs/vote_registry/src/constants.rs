@@ -0,0 +1,58 @@
+// Voters and voted agents must hold a current AgentIdentity in identity_registry;
+// their AgentReputation in reputation_registry weights votes
+pub use gs2_common::{
+    IDENTITY_AGENT_SEED, IDENTITY_REGISTRY_PROGRAM_ID, REPUTATION_REGISTRY_PROGRAM_ID, REPUTATION_SEED,
+};
+
+/// Default fixed part of an endorsement's stake (0.01 SOL, the legacy MIN_STAKE)
+pub const DEFAULT_ENDORSEMENT_BASE_STAKE: u64 = 10_000_000;
+
+/// Default stake added per point of endorsement strength (0.0001 SOL)
+pub const DEFAULT_ENDORSEMENT_STAKE_PER_POINT: u64 = 100_000;
+
+/// Default cap on the active endorsements one agent may give
+pub const DEFAULT_MAX_ACTIVE_ENDORSEMENTS: u32 = 10;
+
+/// Default time an endorsement stays current before it must be re-affirmed (180 days)
+pub const DEFAULT_ENDORSEMENT_VALIDITY_SECONDS: i64 = 180 * 24 * 60 * 60;
+
+/// Default minimum time between two votes by the same voter on the same agent (24 hours)
+pub const DEFAULT_PAIR_VOTE_COOLDOWN_SECONDS: i64 = 24 * 60 * 60;
+
+/// Default window over which votes by one voter on one agent are capped (30 days)
+pub const DEFAULT_PAIR_VOTE_WINDOW_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Default cap on votes by one voter on one agent per window
+pub const DEFAULT_MAX_PAIR_VOTES_PER_WINDOW: u32 = 10;
+
+/// Highest overall_score reputation_registry assigns; caps reputation thresholds
+pub const MAX_REPUTATION_SCORE: u16 = 1000;
+
+/// Default reputation a voter needs to cast a peer vote
+pub const DEFAULT_MIN_VOTER_REPUTATION: u16 = 100;
+
+/// Default reputation an endorser needs to endorse an agent
+pub const DEFAULT_MIN_ENDORSER_REPUTATION: u16 = 500;
+
+/// Default reputation a rater needs to rate content (none)
+pub const DEFAULT_MIN_RATER_REPUTATION: u16 = 0;
+
+/// Default normalized amounts (9 decimals) at which the second and third vote
+/// weight tiers start: 0.01 and 0.1 SOL
+pub const DEFAULT_VOTE_WEIGHT_THRESHOLDS: [u64; 2] = [10_000_000, 100_000_000];
+
+/// Default weight of each vote weight tier in basis points: 1.0x, 1.25x, 1.5x
+pub const DEFAULT_VOTE_WEIGHT_TIER_BPS: [u16; 3] = [10_000, 12_500, 15_000];
+
+/// Cap on any vote weight tier in basis points (3.0x)
+pub const MAX_VOTE_WEIGHT_BPS: u16 = 30_000;
+
+/// Default age below which a voter's identity counts as new (0 disables the rule)
+pub const DEFAULT_NEW_VOTER_AGE_SECONDS: i64 = 0;
+
+/// Default share of the vote weight a new voter contributes, in basis points (0.5x)
+pub const DEFAULT_NEW_VOTER_WEIGHT_BPS: u16 = 5_000;
+
+/// Default age at which an endorsement's weight has decayed linearly to zero
+/// (360 days: half weight at the 180-day default expiry)
+pub const DEFAULT_ENDORSEMENT_DECAY_SECONDS: i64 = 360 * 24 * 60 * 60;
@@ -1,11 +1,13 @@
 use anchor_lang::prelude::*;
 
+/// Error codes are 6000 + discriminant. Removed duplicates keep their codes
+/// reserved (explicit discriminants below) so existing codes never shift.
 #[error_code]
 pub enum VoteError {
     #[msg("Voter does not have an active identity")]
     InactiveVoter,
 
-    #[msg("Voter reputation is too low (minimum 100 required)")]
+    #[msg("Voter reputation is below the configured minimum (100 by default)")]
     InsufficientReputation,
 
     #[msg("Quality score must be between 0 and 100")]
@@ -20,14 +22,13 @@ pub enum VoteError {
     #[msg("Endorsement strength must be between 0 and 100")]
     InvalidEndorsementStrength,
 
-    #[msg("Endorser reputation is too low (minimum 500 required for endorsements)")]
+    #[msg("Endorser reputation is below the configured minimum (500 by default)")]
     InsufficientEndorserReputation,
 
-    #[msg("Endorsement stake is too low (minimum 0.01 SOL)")]
-    InsufficientEndorsementStake,
+    // 6007 reserved: InsufficientEndorsementStake, never raised (stake is derived, not supplied)
 
-    #[msg("Agent has reached maximum endorsement limit (10 max)")]
-    MaxEndorsementsReached,
+    #[msg("Agent has reached its maximum number of active endorsements")]
+    MaxEndorsementsReached = 8,
 
     #[msg("Cannot endorse yourself")]
     SelfEndorsementNotAllowed,
@@ -47,19 +48,13 @@ pub enum VoteError {
     #[msg("Cannot create receipt for transaction with yourself")]
     SelfTransactionNotAllowed,
 
-    #[msg("Vote has already been cast using this transaction receipt")]
-    AlreadyVoted,
+    // 6015-6017 reserved: AlreadyVoted, NotPartyToTransaction and VotedAgentMismatch,
+    // duplicates of VoteAlreadyCast, VoterNotPartyToTransaction and VotedAgentNotCounterparty
 
-    #[msg("Voter is not a party to this transaction")]
-    NotPartyToTransaction,
+    #[msg("Voting window has expired")]
+    VotingWindowExpired = 18,
 
-    #[msg("Voted agent does not match the counterparty in the receipt")]
-    VotedAgentMismatch,
-
-    #[msg("Voting window has expired (30 days from transaction)")]
-    VotingWindowExpired,
-
-    #[msg("Vote has already been cast using this transaction receipt")]
+    #[msg("Voter has already cast its vote using this transaction receipt")]
     VoteAlreadyCast,
 
     #[msg("Voter is not a party to this transaction (must be payer or recipient)")]
@@ -67,4 +62,163 @@ pub enum VoteError {
 
     #[msg("Voted agent must be the counterparty in the transaction receipt")]
     VotedAgentNotCounterparty,
+
+    #[msg("Identity registry program account does not match the expected program ID")]
+    InvalidIdentityProgram,
+
+    #[msg("Reputation registry program account does not match the expected program ID")]
+    InvalidReputationProgram,
+
+    #[msg("External account is not owned by the expected program")]
+    InvalidAccountOwner,
+
+    #[msg("Only the original voter can amend this vote")]
+    NotOriginalVoter,
+
+    #[msg("Vote amendment window has expired (48 hours from the vote)")]
+    AmendmentWindowExpired,
+
+    #[msg("Unauthorized: signer is not the authorized authority")]
+    UnauthorizedAuthority,
+
+    #[msg("Only the endorser can revoke this endorsement")]
+    NotEndorser,
+
+    #[msg("Endorsement is no longer active")]
+    EndorsementNotActive,
+
+    #[msg("Endorsement cannot be revoked before the minimum duration (30 days)")]
+    EndorsementLocked,
+
+    #[msg("Endorsed agent has not been slashed in the identity registry")]
+    EndorsedAgentNotSlashed,
+
+    #[msg("Endorsement base stake must be at least 0.01 SOL")]
+    InvalidEndorsementStakeConfig,
+
+    #[msg("Endorser balance does not cover the required endorsement stake")]
+    InsufficientBalanceForStake,
+
+    #[msg("Maximum active endorsements must be greater than zero")]
+    InvalidMaxEndorsements,
+
+    #[msg("Endorsement validity period must be greater than zero")]
+    InvalidEndorsementValidity,
+
+    #[msg("Endorsement has expired and can no longer be re-affirmed")]
+    EndorsementExpired,
+
+    #[msg("Endorsement has not expired yet")]
+    EndorsementNotExpired,
+
+    #[msg("Only the payer of the transaction receipt can rate its content")]
+    RaterNotPayer,
+
+    #[msg("Transaction receipt recipient is not the rated agent")]
+    ReceiptRecipientMismatch,
+
+    #[msg("Verified receipts need an Ed25519 verify instruction immediately before this one")]
+    MissingPaymentVerification,
+
+    #[msg("Ed25519 verify instruction must check exactly one signature with inline data")]
+    InvalidPaymentVerification,
+
+    #[msg("Payer-signed payment message does not match the receipt")]
+    PaymentSignatureMismatch,
+
+    #[msg("Only verified transaction receipts can be used to vote")]
+    UnverifiedReceipt,
+
+    #[msg("The receipt creator cannot confirm or reject its own receipt")]
+    CreatorCannotConfirm,
+
+    #[msg("Only the receipt counterparty can confirm or reject it")]
+    NotReceiptCounterparty,
+
+    #[msg("Transaction receipt was already confirmed")]
+    ReceiptAlreadyConfirmed,
+
+    #[msg("Transaction receipt was rejected by the counterparty")]
+    ReceiptDisputed,
+
+    #[msg("Transaction receipt has not been confirmed by the counterparty")]
+    ReceiptNotConfirmed,
+
+    #[msg("Native SOL needs no payment mint registration")]
+    NativePaymentMint,
+
+    #[msg("Payment mint decimals must be at most 18")]
+    InvalidMintDecimals,
+
+    #[msg("Maximum number of payment mints registered")]
+    TooManyPaymentMints,
+
+    #[msg("Voted on this agent too recently; wait for the pair cooldown to pass")]
+    VoteCooldownActive,
+
+    #[msg("Maximum votes on this agent reached for the current window")]
+    PairVoteLimitReached,
+
+    #[msg("Invalid pair vote limits: window and cap must be positive and the cooldown non-negative")]
+    InvalidPairVoteLimits,
+
+    #[msg("Only the receipt creator can close it")]
+    NotReceiptCreator,
+
+    #[msg("Transaction receipt is still inside its voting window")]
+    ReceiptStillActive,
+
+    #[msg("Verified receipts cannot be closed; the payer's signature could be replayed")]
+    VerifiedReceiptNotClosable,
+
+    #[msg("Peer vote has not been folded into the vote tally")]
+    VoteNotTallied,
+
+    #[msg("Peer vote is still inside its retention period")]
+    VoteRetentionActive,
+
+    #[msg("Peer vote has no comment to reveal")]
+    NoCommentToReveal,
+
+    #[msg("Comment is longer than 280 bytes")]
+    CommentTooLong,
+
+    #[msg("sha256(comment || salt) does not match the vote's comment hash")]
+    CommentHashMismatch,
+
+    #[msg("Only the voted agent can dispute a vote")]
+    NotVotedAgent,
+
+    #[msg("Peer vote has already been disputed")]
+    VoteAlreadyDisputed,
+
+    #[msg("Peer vote has no pending dispute")]
+    VoteNotDisputed,
+
+    #[msg("Peer vote is under dispute or was removed")]
+    VoteDisputed,
+
+    #[msg("Reputation thresholds must be at most 1000")]
+    InvalidReputationThreshold,
+
+    #[msg("Voting window must be positive and at most 30 days")]
+    InvalidVotingWindow,
+
+    #[msg("Vote weight thresholds must ascend from above zero and tier weights be whole hundredths of at most 3.0x")]
+    InvalidVoteWeightTiers,
+
+    #[msg("Voter and voted agent are linked to the same Core asset")]
+    SharedAssetVote,
+
+    #[msg("Voter and voted agent share a delegate, or one is the other's delegate")]
+    SharedDelegateVote,
+
+    #[msg("New voter age must not be negative and new voter weight must be 1-10000 bps")]
+    InvalidNewVoterRule,
+
+    #[msg("Endorsement decay period must not be negative")]
+    InvalidEndorsementDecay,
+
+    #[msg("Account is not an endorsement PDA of the agent, or was passed twice")]
+    InvalidEndorsementAccount,
 }
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::state::{ContentType, EndorsementCategory, QualityScores, VoteType};
+
+/// Emitted when a transaction receipt is created, verified or not
+#[event]
+pub struct ReceiptCreated {
+    pub receipt: Pubkey,
+    pub creator: Pubkey,
+    pub payer: Pubkey,
+    pub recipient: Pubkey,
+    /// x402 payment signature
+    pub signature: String,
+    /// In the smallest units of payment_mint
+    pub amount: u64,
+    pub payment_mint: Pubkey,
+    pub content_type: ContentType,
+    /// Backed by the payer's Ed25519 signature (and so already confirmed)
+    pub verified: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when cast_peer_vote records a vote and folds it into the voted agent's tally
+#[event]
+pub struct PeerVoteCast {
+    pub peer_vote: Pubkey,
+    pub transaction_receipt: Pubkey,
+    pub vote_tally: Pubkey,
+    pub vote_pair: Pubkey,
+    pub voter: Pubkey,
+    pub voted_agent: Pubkey,
+    pub vote_type: VoteType,
+    /// 100 = 1.0x
+    pub vote_weight: u16,
+    pub quality_scores: QualityScores,
+    /// Mean of the four quality scores, rounded down
+    pub quality_average: u8,
+    pub voter_reputation: u16,
+    pub new_voter: bool,
+    /// Voted agent's tally after this vote
+    pub upvotes: u32,
+    pub downvotes: u32,
+    pub neutrals: u32,
+    pub weight_sum: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when rate_content rates the content behind a receipt
+#[event]
+pub struct ContentRated {
+    pub content_rating: Pubkey,
+    pub content_stats: Pubkey,
+    pub transaction_receipt: Pubkey,
+    pub agent: Pubkey,
+    pub rater: Pubkey,
+    /// 0-100
+    pub quality_rating: u8,
+    pub content_type: ContentType,
+    pub amount_paid: u64,
+    pub payment_mint: Pubkey,
+    pub rater_reputation: u16,
+    /// Rated agent's totals across content types after this rating
+    pub total_ratings: u32,
+    pub total_rating_sum: u64,
+    pub total_weighted_rating_sum: u64,
+    pub total_weight_sum: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when endorse_agent locks an endorsement stake
+#[event]
+pub struct AgentEndorsed {
+    pub endorsement: Pubkey,
+    pub endorser_stats: Pubkey,
+    pub endorsed_stats: Pubkey,
+    pub endorser: Pubkey,
+    pub endorsed: Pubkey,
+    pub strength: u8,
+    pub category: EndorsementCategory,
+    /// Lamports locked in the endorsement
+    pub stake_amount: u64,
+    pub weight: u32,
+    pub endorser_reputation: u16,
+    pub expires_at: i64,
+    /// Counters after this endorsement
+    pub endorsements_given: u32,
+    pub endorsements_received: u32,
+    pub timestamp: i64,
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use reputation_registry::cpi::accounts::RecordVoteResult;
+use reputation_registry::STATS_CPI_AUTHORITY_SEED;
+use super::cast_peer_vote::vote_outcome;
+use crate::constants::{REPUTATION_REGISTRY_PROGRAM_ID, REPUTATION_SEED};
+use crate::state::{PeerVote, QualityScores, VoteTally, VoteType};
+use crate::error::VoteError;
+
+#[derive(Accounts)]
+pub struct AmendPeerVote<'info> {
+    #[account(
+        mut,
+        seeds = [
+            PeerVote::SEED_PREFIX,
+            peer_vote.transaction_receipt.as_ref(),
+            peer_vote.voter.as_ref()
+        ],
+        bump = peer_vote.bump,
+        has_one = voter @ VoteError::NotOriginalVoter
+    )]
+    pub peer_vote: Account<'info, PeerVote>,
+
+    /// Tally of the voted agent, adjusted to reflect the amendment
+    #[account(
+        mut,
+        seeds = [VoteTally::SEED_PREFIX, peer_vote.voted_agent.as_ref()],
+        bump = vote_tally.bump
+    )]
+    pub vote_tally: Account<'info, VoteTally>,
+
+    /// Voted agent's reputation; its vote counters follow a changed vote type
+    /// CHECK: Validated via seeds and owner; written by reputation_registry
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, peer_vote.voted_agent.as_ref()],
+        bump,
+        seeds::program = REPUTATION_REGISTRY_PROGRAM_ID,
+        owner = REPUTATION_REGISTRY_PROGRAM_ID @ VoteError::InvalidAccountOwner
+    )]
+    pub voted_agent_reputation: AccountInfo<'info>,
+
+    /// PDA that signs stat-update CPIs into reputation_registry
+    /// CHECK: Seeds only; never holds data
+    #[account(seeds = [STATS_CPI_AUTHORITY_SEED], bump)]
+    pub reputation_cpi_authority: AccountInfo<'info>,
+
+    /// Original voter
+    pub voter: Signer<'info>,
+
+    /// CHECK: Reputation Registry program; pinned by address
+    #[account(address = REPUTATION_REGISTRY_PROGRAM_ID @ VoteError::InvalidReputationProgram)]
+    pub reputation_registry_program: AccountInfo<'info>,
+}
+
+/// Replace the vote type and quality scores of a vote within
+/// PeerVote::AMENDMENT_WINDOW_SECONDS of casting it. A changed vote type is
+/// moved between the voted agent's reputation vote counters as well, so
+/// reputation_registry agrees with the tally.
+pub fn handler(
+    ctx: Context<AmendPeerVote>,
+    vote_type: VoteType,
+    quality_scores: QualityScores,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let peer_vote = &mut ctx.accounts.peer_vote;
+
+    // Pending and removed votes are out of the tally, so there is nothing to amend
+    require!(
+        peer_vote.is_counted(),
+        VoteError::VoteDisputed
+    );
+
+    require!(
+        peer_vote.is_amendable(clock.unix_timestamp),
+        VoteError::AmendmentWindowExpired
+    );
+
+    require!(
+        quality_scores.response_quality <= 100 &&
+        quality_scores.response_speed <= 100 &&
+        quality_scores.accuracy <= 100 &&
+        quality_scores.professionalism <= 100,
+        VoteError::InvalidQualityScore
+    );
+
+    ctx.accounts.vote_tally.amend(
+        peer_vote.vote_type,
+        &peer_vote.quality_scores,
+        vote_type,
+        &quality_scores,
+    );
+
+    if vote_type != peer_vote.vote_type {
+        let cpi_bump = ctx.bumps.reputation_cpi_authority;
+        let signer_seeds: &[&[&[u8]]] = &[&[STATS_CPI_AUTHORITY_SEED, &[cpi_bump]]];
+        reputation_registry::cpi::amend_vote_result(
+            CpiContext::new_with_signer(
+                ctx.accounts.reputation_registry_program.to_account_info(),
+                RecordVoteResult {
+                    agent_reputation: ctx.accounts.voted_agent_reputation.to_account_info(),
+                    cpi_authority: ctx.accounts.reputation_cpi_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            vote_outcome(peer_vote.vote_type),
+            vote_outcome(vote_type),
+        )?;
+    }
+
+    peer_vote.vote_type = vote_type;
+    peer_vote.quality_scores = quality_scores;
+    peer_vote.amended_count = peer_vote.amended_count.saturating_add(1);
+    peer_vote.amended_at = clock.unix_timestamp;
+
+    msg!(
+        "Vote by {} on {} amended to {:?} (amendment #{})",
+        peer_vote.voter,
+        peer_vote.voted_agent,
+        vote_type,
+        peer_vote.amended_count
+    );
+
+    Ok(())
+}
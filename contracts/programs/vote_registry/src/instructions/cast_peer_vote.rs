@@ -1,27 +1,14 @@
 use anchor_lang::prelude::*;
-use crate::state::{PeerVote, VoteType, QualityScores, TransactionReceipt};
+use gs2_common::{AgentIdentityPrefix, AgentReputationPrefix};
+use reputation_registry::cpi::accounts::RecordVoteResult;
+use reputation_registry::{VoteOutcome, STATS_CPI_AUTHORITY_SEED};
+use crate::state::{PeerVote, VoteType, QualityScores, TransactionReceipt, VoteConfig, VoteDisputeStatus, VotePairState, VoteTally};
+use crate::constants::{
+    DEFAULT_MIN_VOTER_REPUTATION, DEFAULT_PAIR_VOTE_WINDOW_SECONDS, IDENTITY_AGENT_SEED, IDENTITY_REGISTRY_PROGRAM_ID,
+    REPUTATION_REGISTRY_PROGRAM_ID, REPUTATION_SEED,
+};
 use crate::error::VoteError;
-
-/// External AgentIdentity account structure (from identity_registry)
-#[account]
-pub struct AgentIdentity {
-    pub agent_address: Pubkey,
-    pub asset_address: Pubkey,
-    pub metadata_uri: String,
-    pub registration_timestamp: i64,
-    pub last_active_timestamp: i64,
-    pub activity_count: u64,
-    pub is_active: bool,
-    pub bump: u8,
-}
-
-/// External AgentReputation account structure (from reputation_registry)
-#[account]
-pub struct AgentReputation {
-    pub agent_address: Pubkey,
-    pub overall_score: u16,
-    // ... other fields not needed for this check
-}
+use crate::events::PeerVoteCast;
 
 #[derive(Accounts)]
 #[instruction(voted_agent: Pubkey)]
@@ -32,58 +19,117 @@ pub struct CastPeerVote<'info> {
         space = PeerVote::LEN,
         seeds = [
             PeerVote::SEED_PREFIX,
-            transaction_receipt.key().as_ref()
+            transaction_receipt.key().as_ref(),
+            voter.key().as_ref()
         ],
         bump
     )]
     pub peer_vote: Account<'info, PeerVote>,
 
+    /// Voted agent's running vote totals, created on its first vote
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VoteTally::LEN,
+        seeds = [VoteTally::SEED_PREFIX, voted_agent.as_ref()],
+        bump
+    )]
+    pub vote_tally: Account<'info, VoteTally>,
+
+    /// Voter's vote history on the voted agent, created on the pair's first vote
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VotePairState::LEN,
+        seeds = [VotePairState::SEED_PREFIX, voter.key().as_ref(), voted_agent.as_ref()],
+        bump
+    )]
+    pub vote_pair: Account<'info, VotePairState>,
+
     /// Transaction receipt that proves the interaction
     /// Note: x402 supports micropayments as low as $0.001, so no minimum amount required
     #[account(
         mut,
-        constraint = !transaction_receipt.vote_cast @ VoteError::VoteAlreadyCast,
-        constraint = transaction_receipt.payer == voter.key() || transaction_receipt.recipient == voter.key() @ VoteError::VoterNotPartyToTransaction
+        constraint = transaction_receipt.payer == voter.key() || transaction_receipt.recipient == voter.key() @ VoteError::VoterNotPartyToTransaction,
+        constraint = !transaction_receipt.has_voted(voter.key()) @ VoteError::VoteAlreadyCast,
+        constraint = !transaction_receipt.is_disputed @ VoteError::ReceiptDisputed
     )]
     pub transaction_receipt: Account<'info, TransactionReceipt>,
 
     /// Voter's identity (from identity_registry)
-    /// CHECK: Validated via seeds and is_active check
+    /// CHECK: Validated via seeds, owner and is_current check
     #[account(
-        seeds = [b"agent", voter.key().as_ref()],
+        seeds = [IDENTITY_AGENT_SEED, voter.key().as_ref()],
         bump,
-        seeds::program = identity_registry_program.key()
+        seeds::program = IDENTITY_REGISTRY_PROGRAM_ID,
+        owner = IDENTITY_REGISTRY_PROGRAM_ID @ VoteError::InvalidAccountOwner
     )]
     pub voter_identity: AccountInfo<'info>,
 
     /// Voter's reputation (from reputation_registry)
-    /// CHECK: Validated via seeds and reputation check
+    /// CHECK: Validated via seeds, owner and reputation check
     #[account(
-        seeds = [b"reputation", voter.key().as_ref()],
+        seeds = [REPUTATION_SEED, voter.key().as_ref()],
         bump,
-        seeds::program = reputation_registry_program.key()
+        seeds::program = REPUTATION_REGISTRY_PROGRAM_ID,
+        owner = REPUTATION_REGISTRY_PROGRAM_ID @ VoteError::InvalidAccountOwner
     )]
     pub voter_reputation: AccountInfo<'info>,
 
     /// Voted agent's identity (from identity_registry)
-    /// CHECK: Validated via seeds and is_active check
+    /// CHECK: Validated via seeds, owner and is_current check
     #[account(
-        seeds = [b"agent", voted_agent.as_ref()],
+        seeds = [IDENTITY_AGENT_SEED, voted_agent.as_ref()],
         bump,
-        seeds::program = identity_registry_program.key()
+        seeds::program = IDENTITY_REGISTRY_PROGRAM_ID,
+        owner = IDENTITY_REGISTRY_PROGRAM_ID @ VoteError::InvalidAccountOwner
     )]
     pub voted_agent_identity: AccountInfo<'info>,
 
+    /// Voted agent's reputation (stats updated via CPI)
+    /// CHECK: Validated via seeds and owner; written by reputation_registry
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, voted_agent.as_ref()],
+        bump,
+        seeds::program = REPUTATION_REGISTRY_PROGRAM_ID,
+        owner = REPUTATION_REGISTRY_PROGRAM_ID @ VoteError::InvalidAccountOwner
+    )]
+    pub voted_agent_reputation: AccountInfo<'info>,
+
+    /// PDA that signs stat-update CPIs into reputation_registry
+    /// CHECK: Seeds only; never holds data
+    #[account(seeds = [STATS_CPI_AUTHORITY_SEED], bump)]
+    pub reputation_cpi_authority: AccountInfo<'info>,
+
     #[account(mut)]
     pub voter: Signer<'info>,
 
-    /// CHECK: Identity Registry program
+    /// CHECK: Identity Registry program; pinned by address
+    #[account(address = IDENTITY_REGISTRY_PROGRAM_ID @ VoteError::InvalidIdentityProgram)]
     pub identity_registry_program: AccountInfo<'info>,
 
-    /// CHECK: Reputation Registry program
+    /// CHECK: Reputation Registry program; pinned by address
+    #[account(address = REPUTATION_REGISTRY_PROGRAM_ID @ VoteError::InvalidReputationProgram)]
     pub reputation_registry_program: AccountInfo<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// Vote config; until it is initialized unverified and unconfirmed receipts are
+    /// accepted, pair vote limits are not enforced, the shared asset and delegate
+    /// guards are on, there is no new voter rule, and the reputation threshold,
+    /// voting window and vote weight tiers are the defaults
+    /// CHECK: PDA checked by seeds; read with VoteConfig::load
+    #[account(seeds = [VoteConfig::SEED_PREFIX], bump)]
+    pub vote_config: UncheckedAccount<'info>,
+}
+
+/// Whether both identities name the same delegate, or one names the other's wallet
+fn linked_by_delegate(a: &AgentIdentityPrefix, b: &AgentIdentityPrefix) -> bool {
+    let has_delegate = |identity: &AgentIdentityPrefix| identity.delegate != Pubkey::default();
+    (has_delegate(a) && a.delegate == b.delegate)
+        || (has_delegate(a) && a.delegate == b.agent_address)
+        || (has_delegate(b) && b.delegate == a.agent_address)
 }
 
 pub fn handler(
@@ -101,11 +147,31 @@ pub fn handler(
     let transaction_payer = ctx.accounts.transaction_receipt.payer;
     let transaction_recipient = ctx.accounts.transaction_receipt.recipient;
     let transaction_receipt_key = ctx.accounts.transaction_receipt.key();
+    let payment_mint = ctx.accounts.transaction_receipt.payment_mint;
+
+    let config = VoteConfig::load(&ctx.accounts.vote_config)?;
+    let payment_decimals = ctx.accounts.transaction_receipt.payment_decimals(config.as_ref());
+    let normalized_amount = ctx.accounts.transaction_receipt.normalized_amount(config.as_ref());
 
-    // Validate voting window (30 days from transaction)
+    // Strict modes: only payer-signed and/or counterparty-confirmed receipts count
+    if let Some(config) = &config {
+        require!(
+            !config.require_verified_receipts || ctx.accounts.transaction_receipt.verified,
+            VoteError::UnverifiedReceipt
+        );
+        require!(
+            !config.require_confirmed_receipts || ctx.accounts.transaction_receipt.counterparty_confirmed,
+            VoteError::ReceiptNotConfirmed
+        );
+    }
+
+    // Validate voting window (30 days from transaction unless the config shortens it)
+    let voting_window = config
+        .as_ref()
+        .map_or(TransactionReceipt::VOTING_WINDOW_SECONDS, |config| config.voting_window_seconds);
     let time_since_transaction = clock.unix_timestamp - transaction_timestamp;
     require!(
-        time_since_transaction <= TransactionReceipt::VOTING_WINDOW_SECONDS,
+        time_since_transaction <= voting_window,
         VoteError::VotingWindowExpired
     );
 
@@ -122,33 +188,77 @@ pub fn handler(
         VoteError::VotedAgentNotCounterparty
     );
 
+    // Anti wash-trading: limit how often this voter can vote on this agent
+    let vote_pair = &mut ctx.accounts.vote_pair;
+    if vote_pair.voter == Pubkey::default() {
+        vote_pair.voter = voter_key;
+        vote_pair.voted_agent = voted_agent;
+        vote_pair.bump = ctx.bumps.vote_pair;
+    }
+
+    let window_seconds = config
+        .as_ref()
+        .map_or(DEFAULT_PAIR_VOTE_WINDOW_SECONDS, |config| config.pair_vote_window_seconds);
+    if let Some(config) = &config {
+        require!(
+            !vote_pair.in_cooldown(clock.unix_timestamp, config.pair_vote_cooldown_seconds),
+            VoteError::VoteCooldownActive
+        );
+        require!(
+            vote_pair.votes_counted(clock.unix_timestamp, window_seconds) < config.max_pair_votes_per_window,
+            VoteError::PairVoteLimitReached
+        );
+    }
+    vote_pair.record(clock.unix_timestamp, window_seconds);
+
     // Deserialize and validate voter identity
     let voter_identity_data = &ctx.accounts.voter_identity.data.borrow();
-    let voter_identity = AgentIdentity::try_deserialize(&mut &voter_identity_data[..])?;
+    let voter_identity = AgentIdentityPrefix::try_deserialize(&mut &voter_identity_data[..])?;
 
     require!(
-        voter_identity.is_active,
+        voter_identity.is_current(clock.unix_timestamp),
         VoteError::InactiveVoter
     );
 
     // Deserialize and validate voter reputation
     let voter_reputation_data = &ctx.accounts.voter_reputation.data.borrow();
-    let voter_reputation = AgentReputation::try_deserialize(&mut &voter_reputation_data[..])?;
+    let voter_reputation = AgentReputationPrefix::try_deserialize(&mut &voter_reputation_data[..])?;
 
+    let min_voter_reputation = config
+        .as_ref()
+        .map_or(DEFAULT_MIN_VOTER_REPUTATION, |config| config.min_voter_reputation);
     require!(
-        voter_reputation.overall_score >= 100,
+        voter_reputation.overall_score >= min_voter_reputation,
         VoteError::InsufficientReputation
     );
 
     // Deserialize and validate voted agent identity
     let voted_agent_identity_data = &ctx.accounts.voted_agent_identity.data.borrow();
-    let voted_agent_identity = AgentIdentity::try_deserialize(&mut &voted_agent_identity_data[..])?;
+    let voted_agent_identity = AgentIdentityPrefix::try_deserialize(&mut &voted_agent_identity_data[..])?;
 
     require!(
-        voted_agent_identity.is_active,
+        voted_agent_identity.is_current(clock.unix_timestamp),
         VoteError::VotedAgentNotActive
     );
 
+    // Sybil guards: identities linked through one asset or delegate are one operator
+    if config.as_ref().is_none_or(|config| config.reject_shared_asset_votes) {
+        require!(
+            voter_identity.asset_address == Pubkey::default()
+                || voter_identity.asset_address != voted_agent_identity.asset_address,
+            VoteError::SharedAssetVote
+        );
+    }
+    if config.as_ref().is_none_or(|config| config.reject_shared_delegate_votes) {
+        require!(
+            !linked_by_delegate(&voter_identity, &voted_agent_identity),
+            VoteError::SharedDelegateVote
+        );
+    }
+    let new_voter = config
+        .as_ref()
+        .is_some_and(|config| config.is_new_voter(voter_identity.registration_timestamp, clock.unix_timestamp));
+
     // Validate quality scores
     require!(
         quality_scores.response_quality <= 100 &&
@@ -168,16 +278,69 @@ pub fn handler(
     peer_vote.timestamp = clock.unix_timestamp;
     peer_vote.voter_reputation_snapshot = voter_reputation.overall_score;
     peer_vote.transaction_receipt = transaction_receipt_key;
-    peer_vote.vote_weight = PeerVote::calculate_vote_weight(transaction_amount);
+    peer_vote.vote_weight = PeerVote::calculate_vote_weight(normalized_amount, config.as_ref());
+    if let Some(config) = config.as_ref().filter(|_| new_voter) {
+        peer_vote.vote_weight = PeerVote::new_voter_weight(peer_vote.vote_weight, config.new_voter_weight_bps);
+    }
     peer_vote.bump = ctx.bumps.peer_vote;
+    peer_vote.amended_count = 0;
+    peer_vote.amended_at = 0;
+    peer_vote.comment_revealed = false;
+    peer_vote.dispute_status = VoteDisputeStatus::None;
+    peer_vote.new_voter = new_voter;
+
+    // Fold the vote into the agent's tally
+    let tally = &mut ctx.accounts.vote_tally;
+    if tally.agent == Pubkey::default() {
+        tally.agent = voted_agent;
+        tally.bump = ctx.bumps.vote_tally;
+    }
+    tally.record(vote_type, &quality_scores, peer_vote.vote_weight, clock.unix_timestamp);
+    peer_vote.tallied = true;
+
+    // Mark this side of the receipt as voted
+    ctx.accounts.transaction_receipt.mark_voted(voter_key);
 
-    // Mark transaction receipt as voted
-    ctx.accounts.transaction_receipt.vote_cast = true;
+    // Update the voted agent's reputation stats in the same transaction
+    let outcome = vote_outcome(vote_type);
+    let cpi_bump = ctx.bumps.reputation_cpi_authority;
+    let signer_seeds: &[&[&[u8]]] = &[&[STATS_CPI_AUTHORITY_SEED, &[cpi_bump]]];
+    reputation_registry::cpi::record_vote_result(
+        CpiContext::new_with_signer(
+            ctx.accounts.reputation_registry_program.to_account_info(),
+            RecordVoteResult {
+                agent_reputation: ctx.accounts.voted_agent_reputation.to_account_info(),
+                cpi_authority: ctx.accounts.reputation_cpi_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        outcome,
+    )?;
 
     // Calculate weighted vote power for analytics (using saturating math for safety)
     let vote_weight = peer_vote.vote_weight;
     let weighted_vote_power = (vote_weight as u32).saturating_mul(voter_reputation.overall_score as u32);
 
+    emit!(PeerVoteCast {
+        peer_vote: peer_vote.key(),
+        transaction_receipt: transaction_receipt_key,
+        vote_tally: ctx.accounts.vote_tally.key(),
+        vote_pair: ctx.accounts.vote_pair.key(),
+        voter: voter_key,
+        voted_agent,
+        vote_type,
+        vote_weight,
+        quality_scores,
+        quality_average: quality_scores.average(),
+        voter_reputation: voter_reputation.overall_score,
+        new_voter,
+        upvotes: ctx.accounts.vote_tally.upvotes,
+        downvotes: ctx.accounts.vote_tally.downvotes,
+        neutrals: ctx.accounts.vote_tally.neutrals,
+        weight_sum: ctx.accounts.vote_tally.weight_sum,
+        timestamp: clock.unix_timestamp,
+    });
+
     // Comprehensive vote analytics logging
     msg!("======================================");
     msg!("=== VOTE CAST SUCCESSFULLY ===");
@@ -188,11 +351,28 @@ pub fn handler(
     msg!("Transaction Receipt: {}", transaction_receipt_key);
     msg!("--------------------------------------");
     msg!("=== Transaction Details ===");
-    msg!("Transaction Amount: {} SOL", transaction_amount as f64 / 1_000_000_000.0);
+    match payment_decimals {
+        Some(decimals) if payment_mint == TransactionReceipt::NATIVE_MINT => {
+            msg!("Transaction Amount: {} SOL", transaction_amount as f64 / 10f64.powi(decimals as i32));
+        }
+        Some(decimals) => {
+            msg!("Transaction Amount: {} (mint {})", transaction_amount as f64 / 10f64.powi(decimals as i32), payment_mint);
+        }
+        None => {
+            msg!("Transaction Amount: {} base units (mint {}, decimals not registered)", transaction_amount, payment_mint);
+        }
+    }
     msg!("Transaction Timestamp: {}", transaction_timestamp);
     msg!("--------------------------------------");
     msg!("=== Vote Weighting ===");
-    msg!("Vote Weight: {}x (based on tx amount)", vote_weight as f32 / 100.0);
+    if config.as_ref().is_some_and(|config| config.flat_vote_weight) {
+        msg!("Vote Weight: {}x (flat)", vote_weight as f32 / 100.0);
+    } else {
+        msg!("Vote Weight: {}x (based on tx amount)", vote_weight as f32 / 100.0);
+    }
+    if new_voter {
+        msg!("New voter: weight reduced");
+    }
     msg!("Voter Reputation: {}", voter_reputation.overall_score);
     msg!("Weighted Vote Power: {}", weighted_vote_power);
     msg!("--------------------------------------");
@@ -201,12 +381,17 @@ pub fn handler(
     msg!("Response Speed: {}/100", quality_scores.response_speed);
     msg!("Accuracy: {}/100", quality_scores.accuracy);
     msg!("Professionalism: {}/100", quality_scores.professionalism);
-    msg!("Average Quality: {}/100",
-         (quality_scores.response_quality as u16 +
-          quality_scores.response_speed as u16 +
-          quality_scores.accuracy as u16 +
-          quality_scores.professionalism as u16) / 4);
+    msg!("Average Quality: {}/100", quality_scores.average());
     msg!("======================================");
 
     Ok(())
 }
+
+/// How reputation_registry counts a vote of `vote_type`
+pub(crate) fn vote_outcome(vote_type: VoteType) -> VoteOutcome {
+    match vote_type {
+        VoteType::Upvote => VoteOutcome::Positive,
+        VoteType::Downvote => VoteOutcome::Negative,
+        VoteType::Neutral => VoteOutcome::Neutral,
+    }
+}
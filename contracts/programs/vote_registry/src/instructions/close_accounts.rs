@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use crate::state::{PeerVote, TransactionReceipt, VoteDisputeStatus, VoteTally};
+use crate::error::VoteError;
+
+// ==================== CLOSE RECEIPT ====================
+
+#[derive(Accounts)]
+pub struct CloseReceipt<'info> {
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ VoteError::NotReceiptCreator,
+        constraint = !receipt.verified @ VoteError::VerifiedReceiptNotClosable
+    )]
+    pub receipt: Account<'info, TransactionReceipt>,
+
+    /// Party that created (and paid rent for) the receipt; receives the rent
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+/// Reclaim a receipt's rent once its voting window has passed, when any vote
+/// it allowed has been cast or forfeited. Verified receipts stay open: closing
+/// one would let the payer's signed payment message be replayed to re-create
+/// it with a fresh voting window.
+pub fn close_receipt(ctx: Context<CloseReceipt>) -> Result<()> {
+    let clock = Clock::get()?;
+    let receipt = &ctx.accounts.receipt;
+
+    require!(
+        receipt.is_voting_closed(clock.unix_timestamp),
+        VoteError::ReceiptStillActive
+    );
+
+    msg!("Transaction receipt {} closed by {}", receipt.signature, receipt.creator);
+
+    Ok(())
+}
+
+// ==================== CLOSE PEER VOTE ====================
+
+#[derive(Accounts)]
+pub struct ClosePeerVote<'info> {
+    #[account(
+        mut,
+        close = voter,
+        seeds = [
+            PeerVote::SEED_PREFIX,
+            peer_vote.transaction_receipt.as_ref(),
+            peer_vote.voter.as_ref()
+        ],
+        bump = peer_vote.bump,
+        has_one = voter @ VoteError::NotOriginalVoter
+    )]
+    pub peer_vote: Account<'info, PeerVote>,
+
+    /// Tally of the voted agent, which keeps counting the vote after closure
+    #[account(
+        mut,
+        seeds = [VoteTally::SEED_PREFIX, peer_vote.voted_agent.as_ref()],
+        bump = vote_tally.bump
+    )]
+    pub vote_tally: Account<'info, VoteTally>,
+
+    /// Original voter, who paid the vote's rent and receives it back
+    #[account(mut)]
+    pub voter: Signer<'info>,
+}
+
+/// Reclaim a vote's rent after PeerVote::RETENTION_SECONDS. The vote stays in
+/// the tally (unless a dispute removed it); the receipt's voted flag keeps it
+/// from being cast again. Votes with a pending dispute cannot be closed.
+pub fn close_peer_vote(ctx: Context<ClosePeerVote>) -> Result<()> {
+    let clock = Clock::get()?;
+    let peer_vote = &ctx.accounts.peer_vote;
+
+    require!(
+        peer_vote.tallied,
+        VoteError::VoteNotTallied
+    );
+
+    require!(
+        peer_vote.dispute_status != VoteDisputeStatus::Pending,
+        VoteError::VoteDisputed
+    );
+
+    require!(
+        peer_vote.is_retention_over(clock.unix_timestamp),
+        VoteError::VoteRetentionActive
+    );
+
+    // Removed votes were already taken out of the tally
+    if peer_vote.is_counted() {
+        let tally = &mut ctx.accounts.vote_tally;
+        tally.closed_votes = tally.closed_votes.saturating_add(1);
+    }
+
+    msg!("Vote by {} on {} closed", peer_vote.voter, peer_vote.voted_agent);
+
+    Ok(())
+}
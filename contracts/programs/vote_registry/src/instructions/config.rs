@@ -0,0 +1,381 @@
+use anchor_lang::prelude::*;
+use crate::constants::{
+    DEFAULT_ENDORSEMENT_BASE_STAKE, DEFAULT_ENDORSEMENT_DECAY_SECONDS, DEFAULT_ENDORSEMENT_STAKE_PER_POINT,
+    DEFAULT_ENDORSEMENT_VALIDITY_SECONDS,
+    DEFAULT_MAX_ACTIVE_ENDORSEMENTS, DEFAULT_MAX_PAIR_VOTES_PER_WINDOW, DEFAULT_PAIR_VOTE_COOLDOWN_SECONDS,
+    DEFAULT_MIN_ENDORSER_REPUTATION, DEFAULT_MIN_RATER_REPUTATION, DEFAULT_MIN_VOTER_REPUTATION,
+    DEFAULT_NEW_VOTER_AGE_SECONDS, DEFAULT_NEW_VOTER_WEIGHT_BPS, DEFAULT_PAIR_VOTE_WINDOW_SECONDS,
+    MAX_REPUTATION_SCORE,
+};
+use crate::state::{AgentEndorsement, PaymentMintScale, TransactionReceipt, VoteAuthority, VoteConfig, VoteWeightTiers};
+use crate::error::VoteError;
+
+// ==================== INITIALIZE CONFIG ====================
+
+#[derive(Accounts)]
+pub struct InitializeVoteConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = VoteConfig::LEN,
+        seeds = [VoteConfig::SEED_PREFIX],
+        bump
+    )]
+    pub vote_config: Account<'info, VoteConfig>,
+
+    #[account(
+        seeds = [VoteAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ VoteError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, VoteAuthority>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the vote config with default parameters (authority only)
+pub fn initialize_vote_config(ctx: Context<InitializeVoteConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.vote_config;
+
+    config.endorsement_base_stake = DEFAULT_ENDORSEMENT_BASE_STAKE;
+    config.endorsement_stake_per_point = DEFAULT_ENDORSEMENT_STAKE_PER_POINT;
+    config.bump = ctx.bumps.vote_config;
+    config.max_active_endorsements = DEFAULT_MAX_ACTIVE_ENDORSEMENTS;
+    config.endorsement_validity_seconds = DEFAULT_ENDORSEMENT_VALIDITY_SECONDS;
+    config.require_verified_receipts = false;
+    config.require_confirmed_receipts = false;
+    config.payment_mints = Vec::new();
+    config.pair_vote_cooldown_seconds = DEFAULT_PAIR_VOTE_COOLDOWN_SECONDS;
+    config.pair_vote_window_seconds = DEFAULT_PAIR_VOTE_WINDOW_SECONDS;
+    config.max_pair_votes_per_window = DEFAULT_MAX_PAIR_VOTES_PER_WINDOW;
+    config.min_voter_reputation = DEFAULT_MIN_VOTER_REPUTATION;
+    config.min_endorser_reputation = DEFAULT_MIN_ENDORSER_REPUTATION;
+    config.min_rater_reputation = DEFAULT_MIN_RATER_REPUTATION;
+    config.voting_window_seconds = TransactionReceipt::VOTING_WINDOW_SECONDS;
+    config.flat_vote_weight = false;
+    config.vote_weight_tiers = VoteWeightTiers::DEFAULT;
+    config.reject_shared_asset_votes = true;
+    config.reject_shared_delegate_votes = true;
+    config.new_voter_age_seconds = DEFAULT_NEW_VOTER_AGE_SECONDS;
+    config.new_voter_weight_bps = DEFAULT_NEW_VOTER_WEIGHT_BPS;
+    config.endorsement_decay_seconds = DEFAULT_ENDORSEMENT_DECAY_SECONDS;
+
+    msg!("Vote config initialized");
+    msg!(
+        "Endorsement stake: {} + {} per strength point",
+        config.endorsement_base_stake,
+        config.endorsement_stake_per_point
+    );
+
+    Ok(())
+}
+
+// ==================== UPDATE ENDORSEMENT STAKE ====================
+
+#[derive(Accounts)]
+pub struct UpdateVoteConfig<'info> {
+    #[account(
+        mut,
+        seeds = [VoteConfig::SEED_PREFIX],
+        bump = vote_config.bump
+    )]
+    pub vote_config: Account<'info, VoteConfig>,
+
+    #[account(
+        seeds = [VoteAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ VoteError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, VoteAuthority>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Change the stake future endorsements lock; existing endorsements keep theirs
+pub fn update_endorsement_stake(
+    ctx: Context<UpdateVoteConfig>,
+    base_stake: u64,
+    stake_per_point: u64,
+) -> Result<()> {
+    require!(
+        base_stake >= AgentEndorsement::MIN_STAKE,
+        VoteError::InvalidEndorsementStakeConfig
+    );
+
+    let config = &mut ctx.accounts.vote_config;
+    config.endorsement_base_stake = base_stake;
+    config.endorsement_stake_per_point = stake_per_point;
+
+    msg!(
+        "Endorsement stake updated: {} + {} per strength point",
+        base_stake,
+        stake_per_point
+    );
+
+    Ok(())
+}
+
+// ==================== UPDATE MAX ENDORSEMENTS ====================
+
+/// Change how many active endorsements one agent may give; agents already over
+/// a lowered cap keep their endorsements but cannot add more
+pub fn update_max_active_endorsements(
+    ctx: Context<UpdateVoteConfig>,
+    max_active_endorsements: u32,
+) -> Result<()> {
+    require!(
+        max_active_endorsements > 0,
+        VoteError::InvalidMaxEndorsements
+    );
+
+    ctx.accounts.vote_config.max_active_endorsements = max_active_endorsements;
+
+    msg!("Max active endorsements updated: {}", max_active_endorsements);
+
+    Ok(())
+}
+
+// ==================== UPDATE ENDORSEMENT VALIDITY ====================
+
+/// Change how long endorsements stay current; applies from the next endorsement
+/// or re-affirmation, existing expiries are left as they are
+pub fn update_endorsement_validity(
+    ctx: Context<UpdateVoteConfig>,
+    validity_seconds: i64,
+) -> Result<()> {
+    require!(
+        validity_seconds > 0,
+        VoteError::InvalidEndorsementValidity
+    );
+
+    ctx.accounts.vote_config.endorsement_validity_seconds = validity_seconds;
+
+    msg!("Endorsement validity updated: {} seconds", validity_seconds);
+
+    Ok(())
+}
+
+// ==================== UPDATE ENDORSEMENT DECAY ====================
+
+/// Change how fast endorsement weight fades with age (0 disables decay); applies
+/// to every endorsement, since weight is computed when it is read
+pub fn update_endorsement_decay(ctx: Context<UpdateVoteConfig>, decay_seconds: i64) -> Result<()> {
+    require!(
+        decay_seconds >= 0,
+        VoteError::InvalidEndorsementDecay
+    );
+
+    ctx.accounts.vote_config.endorsement_decay_seconds = decay_seconds;
+
+    msg!("Endorsement decay updated: {} seconds", decay_seconds);
+
+    Ok(())
+}
+
+// ==================== UPDATE RECEIPT VERIFICATION ====================
+
+/// Turn strict mode on or off: when on, cast_peer_vote rejects receipts not
+/// created by create_verified_transaction_receipt
+pub fn update_require_verified_receipts(
+    ctx: Context<UpdateVoteConfig>,
+    require_verified_receipts: bool,
+) -> Result<()> {
+    ctx.accounts.vote_config.require_verified_receipts = require_verified_receipts;
+
+    msg!("Require verified receipts: {}", require_verified_receipts);
+
+    Ok(())
+}
+
+// ==================== UPDATE RECEIPT CONFIRMATION ====================
+
+/// Turn counterparty confirmation on or off: when on, cast_peer_vote rejects
+/// receipts the non-creator party has not confirmed (verified receipts count
+/// as confirmed)
+pub fn update_require_confirmed_receipts(
+    ctx: Context<UpdateVoteConfig>,
+    require_confirmed_receipts: bool,
+) -> Result<()> {
+    ctx.accounts.vote_config.require_confirmed_receipts = require_confirmed_receipts;
+
+    msg!("Require confirmed receipts: {}", require_confirmed_receipts);
+
+    Ok(())
+}
+
+// ==================== SET PAYMENT MINT DECIMALS ====================
+
+/// Register an SPL payment mint's decimals, or change those of a registered
+/// mint, so receipt amounts in that mint can be normalized
+pub fn set_payment_mint_decimals(
+    ctx: Context<UpdateVoteConfig>,
+    mint: Pubkey,
+    decimals: u8,
+) -> Result<()> {
+    require!(
+        mint != TransactionReceipt::NATIVE_MINT,
+        VoteError::NativePaymentMint
+    );
+
+    require!(
+        decimals <= VoteConfig::MAX_MINT_DECIMALS,
+        VoteError::InvalidMintDecimals
+    );
+
+    let config = &mut ctx.accounts.vote_config;
+    if let Some(scale) = config.payment_mints.iter_mut().find(|scale| scale.mint == mint) {
+        scale.decimals = decimals;
+    } else {
+        require!(
+            config.payment_mints.len() < VoteConfig::MAX_PAYMENT_MINTS,
+            VoteError::TooManyPaymentMints
+        );
+        config.payment_mints.push(PaymentMintScale { mint, decimals });
+    }
+
+    msg!("Payment mint {} decimals: {}", mint, decimals);
+
+    Ok(())
+}
+
+// ==================== UPDATE PAIR VOTE LIMITS ====================
+
+/// Change how often one voter may vote on the same agent: at most one vote per
+/// cooldown and `max_votes_per_window` votes per window
+pub fn update_pair_vote_limits(
+    ctx: Context<UpdateVoteConfig>,
+    cooldown_seconds: i64,
+    window_seconds: i64,
+    max_votes_per_window: u32,
+) -> Result<()> {
+    require!(
+        cooldown_seconds >= 0 && window_seconds > 0 && max_votes_per_window > 0,
+        VoteError::InvalidPairVoteLimits
+    );
+
+    let config = &mut ctx.accounts.vote_config;
+    config.pair_vote_cooldown_seconds = cooldown_seconds;
+    config.pair_vote_window_seconds = window_seconds;
+    config.max_pair_votes_per_window = max_votes_per_window;
+
+    msg!(
+        "Pair vote limits updated: {}s cooldown, {} votes per {}s",
+        cooldown_seconds,
+        max_votes_per_window,
+        window_seconds
+    );
+
+    Ok(())
+}
+
+// ==================== UPDATE REPUTATION THRESHOLDS ====================
+
+/// Change the reputation needed to vote, endorse and rate content (0-1000)
+pub fn update_reputation_thresholds(
+    ctx: Context<UpdateVoteConfig>,
+    min_voter_reputation: u16,
+    min_endorser_reputation: u16,
+    min_rater_reputation: u16,
+) -> Result<()> {
+    require!(
+        min_voter_reputation <= MAX_REPUTATION_SCORE &&
+        min_endorser_reputation <= MAX_REPUTATION_SCORE &&
+        min_rater_reputation <= MAX_REPUTATION_SCORE,
+        VoteError::InvalidReputationThreshold
+    );
+
+    let config = &mut ctx.accounts.vote_config;
+    config.min_voter_reputation = min_voter_reputation;
+    config.min_endorser_reputation = min_endorser_reputation;
+    config.min_rater_reputation = min_rater_reputation;
+
+    msg!(
+        "Reputation thresholds updated: vote {}, endorse {}, rate {}",
+        min_voter_reputation,
+        min_endorser_reputation,
+        min_rater_reputation
+    );
+
+    Ok(())
+}
+
+// ==================== UPDATE VOTING WINDOW ====================
+
+/// Change how long after a transaction its receipt can be voted on; may only
+/// shorten the 30-day TransactionReceipt::VOTING_WINDOW_SECONDS
+pub fn update_voting_window(ctx: Context<UpdateVoteConfig>, voting_window_seconds: i64) -> Result<()> {
+    require!(
+        voting_window_seconds > 0 && voting_window_seconds <= TransactionReceipt::VOTING_WINDOW_SECONDS,
+        VoteError::InvalidVotingWindow
+    );
+
+    ctx.accounts.vote_config.voting_window_seconds = voting_window_seconds;
+
+    msg!("Voting window updated: {}s", voting_window_seconds);
+
+    Ok(())
+}
+
+// ==================== UPDATE VOTE WEIGHTING ====================
+
+/// Change how peer votes are weighted by transaction amount, or switch to flat
+/// 1.0x weights; applies to votes cast from now on, existing weights are kept
+pub fn update_vote_weighting(
+    ctx: Context<UpdateVoteConfig>,
+    flat_vote_weight: bool,
+    vote_weight_tiers: VoteWeightTiers,
+) -> Result<()> {
+    require!(
+        vote_weight_tiers.is_valid(),
+        VoteError::InvalidVoteWeightTiers
+    );
+
+    let config = &mut ctx.accounts.vote_config;
+    config.flat_vote_weight = flat_vote_weight;
+    config.vote_weight_tiers = vote_weight_tiers;
+
+    msg!(
+        "Vote weighting updated: flat {}, tiers {:?} bps from {:?}",
+        flat_vote_weight,
+        vote_weight_tiers.weights_bps,
+        vote_weight_tiers.thresholds
+    );
+
+    Ok(())
+}
+
+// ==================== UPDATE SYBIL GUARDS ====================
+
+/// Turn the identity relationship checks in cast_peer_vote on or off, and set
+/// how long voters count as new and what share of the vote weight they carry
+pub fn update_sybil_guards(
+    ctx: Context<UpdateVoteConfig>,
+    reject_shared_asset_votes: bool,
+    reject_shared_delegate_votes: bool,
+    new_voter_age_seconds: i64,
+    new_voter_weight_bps: u16,
+) -> Result<()> {
+    require!(
+        new_voter_age_seconds >= 0 && new_voter_weight_bps > 0 && new_voter_weight_bps <= 10_000,
+        VoteError::InvalidNewVoterRule
+    );
+
+    let config = &mut ctx.accounts.vote_config;
+    config.reject_shared_asset_votes = reject_shared_asset_votes;
+    config.reject_shared_delegate_votes = reject_shared_delegate_votes;
+    config.new_voter_age_seconds = new_voter_age_seconds;
+    config.new_voter_weight_bps = new_voter_weight_bps;
+
+    msg!(
+        "Sybil guards updated: shared asset {}, shared delegate {}, new voters {} bps for {}s",
+        reject_shared_asset_votes,
+        reject_shared_delegate_votes,
+        new_voter_weight_bps,
+        new_voter_age_seconds
+    );
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::{TransactionReceipt, ContentType};
 use crate::error::VoteError;
+use crate::events::ReceiptCreated;
 
 #[derive(Accounts)]
 #[instruction(signature: String, signature_hash: [u8; 32])]
@@ -37,14 +38,43 @@ pub struct CreateTransactionReceipt<'info> {
 pub fn handler(
     ctx: Context<CreateTransactionReceipt>,
     signature: String,
-    signature_hash: [u8; 32],
+    _signature_hash: [u8; 32],
     amount: u64,
     content_type: ContentType,
+    payment_mint: Pubkey,
+) -> Result<()> {
+    record_receipt(
+        &mut ctx.accounts.receipt,
+        ctx.accounts.creator.key(),
+        ctx.accounts.payer_pubkey.key(),
+        ctx.accounts.recipient_pubkey.key(),
+        signature,
+        amount,
+        content_type,
+        payment_mint,
+        ctx.bumps.receipt,
+        false,
+    )
+}
+
+/// Validate the parties and fill in a new receipt; shared by the unverified
+/// and verified receipt instructions.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn record_receipt(
+    receipt: &mut Account<TransactionReceipt>,
+    creator: Pubkey,
+    payer: Pubkey,
+    recipient: Pubkey,
+    signature: String,
+    amount: u64,
+    content_type: ContentType,
+    payment_mint: Pubkey,
+    bump: u8,
+    verified: bool,
 ) -> Result<()> {
     // Validate creator is either payer or recipient
     require!(
-        ctx.accounts.creator.key() == ctx.accounts.payer_pubkey.key() ||
-        ctx.accounts.creator.key() == ctx.accounts.recipient_pubkey.key(),
+        creator == payer || creator == recipient,
         VoteError::UnauthorizedReceiptCreation
     );
 
@@ -56,26 +86,50 @@ pub fn handler(
 
     // Validate payer and recipient are different
     require!(
-        ctx.accounts.payer_pubkey.key() != ctx.accounts.recipient_pubkey.key(),
+        payer != recipient,
         VoteError::SelfTransactionNotAllowed
     );
 
-    let receipt = &mut ctx.accounts.receipt;
     let clock = Clock::get()?;
 
     receipt.signature = signature.clone();
-    receipt.payer = ctx.accounts.payer_pubkey.key();
-    receipt.recipient = ctx.accounts.recipient_pubkey.key();
+    receipt.payer = payer;
+    receipt.recipient = recipient;
     receipt.amount = amount;
     receipt.timestamp = clock.unix_timestamp;
     receipt.content_type = content_type;
-    receipt.vote_cast = false;
-    receipt.bump = ctx.bumps.receipt;
+    receipt.payer_voted = false;
+    receipt.recipient_voted = false;
+    receipt.bump = bump;
+    receipt.verified = verified;
+    receipt.creator = creator;
+    // The payer's Ed25519 signature already attests to the payment
+    receipt.counterparty_confirmed = verified;
+    receipt.is_disputed = false;
+    receipt.payment_mint = payment_mint;
+
+    emit!(ReceiptCreated {
+        receipt: receipt.key(),
+        creator,
+        payer,
+        recipient,
+        signature: signature.clone(),
+        amount,
+        payment_mint,
+        content_type,
+        verified,
+        timestamp: clock.unix_timestamp,
+    });
 
     msg!("Transaction receipt created: {}", signature);
-    msg!("Payer: {}, Recipient: {}, Amount: {} lamports",
-         receipt.payer, receipt.recipient, amount);
-    msg!("Content type: {:?}", content_type);
+    if receipt.is_native() {
+        msg!("Payer: {}, Recipient: {}, Amount: {} lamports",
+             receipt.payer, receipt.recipient, amount);
+    } else {
+        msg!("Payer: {}, Recipient: {}, Amount: {} base units of mint {}",
+             receipt.payer, receipt.recipient, amount, payment_mint);
+    }
+    msg!("Content type: {:?}, Verified: {}", content_type, verified);
 
     Ok(())
 }
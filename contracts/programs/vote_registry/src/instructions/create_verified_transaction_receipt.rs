@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+use solana_sdk_ids::{ed25519_program, sysvar};
+use super::create_transaction_receipt::record_receipt;
+use crate::state::{ContentType, TransactionReceipt};
+use crate::error::VoteError;
+
+/// Ed25519 program data starts with a signature count and a padding byte
+const ED25519_OFFSETS_START: usize = 2;
+
+/// instruction_index value meaning "inside the Ed25519 instruction itself"
+const ED25519_INLINE_DATA: u16 = u16::MAX;
+
+#[derive(Accounts)]
+#[instruction(signature: String, signature_hash: [u8; 32])]
+pub struct CreateVerifiedTransactionReceipt<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = TransactionReceipt::LEN,
+        seeds = [
+            TransactionReceipt::SEED_PREFIX,
+            payer_pubkey.key().as_ref(),
+            recipient_pubkey.key().as_ref(),
+            &signature_hash
+        ],
+        bump
+    )]
+    pub receipt: Account<'info, TransactionReceipt>,
+
+    /// Payer in the x402 transaction; must have signed the payment message
+    /// CHECK: Matched against the Ed25519-verified public key
+    pub payer_pubkey: UncheckedAccount<'info>,
+
+    /// Recipient in the x402 transaction
+    /// CHECK: Validated in instruction that creator is payer or recipient
+    pub recipient_pubkey: UncheckedAccount<'info>,
+
+    /// Creator of this receipt (must be payer or recipient)
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// CHECK: Instructions sysvar; pinned by address
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create a receipt backed by the payer's signature over
+/// TransactionReceipt::payment_message, checked by an Ed25519 program
+/// instruction placed immediately before this one.
+pub fn handler(
+    ctx: Context<CreateVerifiedTransactionReceipt>,
+    signature: String,
+    signature_hash: [u8; 32],
+    amount: u64,
+    content_type: ContentType,
+    payment_mint: Pubkey,
+) -> Result<()> {
+    let payer = ctx.accounts.payer_pubkey.key();
+    let recipient = ctx.accounts.recipient_pubkey.key();
+
+    let (signer, message) = preceding_ed25519_signature(&ctx.accounts.instructions_sysvar)?;
+
+    require!(
+        signer == payer &&
+        message == TransactionReceipt::payment_message(&payer, &recipient, &payment_mint, amount, &signature_hash),
+        VoteError::PaymentSignatureMismatch
+    );
+
+    record_receipt(
+        &mut ctx.accounts.receipt,
+        ctx.accounts.creator.key(),
+        payer,
+        recipient,
+        signature,
+        amount,
+        content_type,
+        payment_mint,
+        ctx.bumps.receipt,
+        true,
+    )
+}
+
+/// Public key and message of the single signature checked by the Ed25519
+/// program instruction immediately before the current one. The runtime has
+/// already verified the signature if that instruction is in the transaction.
+fn preceding_ed25519_signature(instructions_sysvar: &AccountInfo) -> Result<(Pubkey, Vec<u8>)> {
+    let current = load_current_index_checked(instructions_sysvar)?;
+    require!(current > 0, VoteError::MissingPaymentVerification);
+
+    let instruction = load_instruction_at_checked(usize::from(current - 1), instructions_sysvar)?;
+    require!(
+        instruction.program_id == ed25519_program::ID,
+        VoteError::MissingPaymentVerification
+    );
+
+    // Ed25519SignatureOffsets: signature_offset, signature_instruction_index,
+    // public_key_offset, public_key_instruction_index, message_data_offset,
+    // message_data_size, message_instruction_index (u16 LE each)
+    let data = &instruction.data;
+    require!(data.first() == Some(&1), VoteError::InvalidPaymentVerification);
+
+    let offset = |field: usize| -> Result<usize> {
+        let at = ED25519_OFFSETS_START + 2 * field;
+        data.get(at..at + 2)
+            .map(|bytes| usize::from(u16::from_le_bytes([bytes[0], bytes[1]])))
+            .ok_or_else(|| error!(VoteError::InvalidPaymentVerification))
+    };
+
+    // Signature, key and message must all live in the Ed25519 instruction's own data
+    for index_field in [1, 3, 6] {
+        require!(
+            offset(index_field)? == usize::from(ED25519_INLINE_DATA),
+            VoteError::InvalidPaymentVerification
+        );
+    }
+
+    let public_key_offset = offset(2)?;
+    let (message_offset, message_size) = (offset(4)?, offset(5)?);
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or_else(|| error!(VoteError::InvalidPaymentVerification))?;
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or_else(|| error!(VoteError::InvalidPaymentVerification))?;
+
+    let public_key = Pubkey::try_from(public_key).map_err(|_| error!(VoteError::InvalidPaymentVerification))?;
+    Ok((public_key, message.to_vec()))
+}
@@ -1,27 +1,13 @@
 use anchor_lang::prelude::*;
+use gs2_common::{AgentIdentityPrefix, AgentReputationPrefix};
 use anchor_lang::system_program;
-use crate::state::{AgentEndorsement, EndorsementCategory};
+use crate::state::{AgentEndorsement, EndorsementCategory, EndorsementStats, VoteConfig};
+use crate::constants::{
+    DEFAULT_ENDORSEMENT_VALIDITY_SECONDS, DEFAULT_MAX_ACTIVE_ENDORSEMENTS, DEFAULT_MIN_ENDORSER_REPUTATION,
+    IDENTITY_AGENT_SEED, IDENTITY_REGISTRY_PROGRAM_ID, REPUTATION_REGISTRY_PROGRAM_ID, REPUTATION_SEED,
+};
 use crate::error::VoteError;
-
-/// External AgentIdentity account structure (from identity_registry)
-#[account]
-pub struct AgentIdentity {
-    pub agent_address: Pubkey,
-    pub asset_address: Pubkey,
-    pub metadata_uri: String,
-    pub registration_timestamp: i64,
-    pub last_active_timestamp: i64,
-    pub activity_count: u64,
-    pub is_active: bool,
-    pub bump: u8,
-}
-
-/// External AgentReputation account structure (from reputation_registry)
-#[account]
-pub struct AgentReputation {
-    pub agent_address: Pubkey,
-    pub overall_score: u16,
-}
+use crate::events::AgentEndorsed;
 
 #[derive(Accounts)]
 #[instruction(endorsed_agent: Pubkey)]
@@ -39,43 +25,78 @@ pub struct EndorseAgent<'info> {
     )]
     pub endorsement: Account<'info, AgentEndorsement>,
 
-    /// Endorser's identity (must be active)
-    /// CHECK: Validated via seeds and is_active check
+    /// Endorser's counters; endorsements_given is capped
     #[account(
-        seeds = [b"agent", endorser.key().as_ref()],
+        init_if_needed,
+        payer = endorser,
+        space = EndorsementStats::LEN,
+        seeds = [EndorsementStats::SEED_PREFIX, endorser.key().as_ref()],
+        bump
+    )]
+    pub endorser_stats: Account<'info, EndorsementStats>,
+
+    /// Endorsed agent's counters
+    #[account(
+        init_if_needed,
+        payer = endorser,
+        space = EndorsementStats::LEN,
+        seeds = [EndorsementStats::SEED_PREFIX, endorsed_agent.as_ref()],
+        bump
+    )]
+    pub endorsed_stats: Account<'info, EndorsementStats>,
+
+    /// Endorser's identity (must be active and unexpired)
+    /// CHECK: Validated via seeds, owner and is_current check
+    #[account(
+        seeds = [IDENTITY_AGENT_SEED, endorser.key().as_ref()],
         bump,
-        seeds::program = identity_registry_program.key()
+        seeds::program = IDENTITY_REGISTRY_PROGRAM_ID,
+        owner = IDENTITY_REGISTRY_PROGRAM_ID @ VoteError::InvalidAccountOwner
     )]
     pub endorser_identity: AccountInfo<'info>,
 
-    /// Endorser's reputation (must be >= 500)
-    /// CHECK: Validated via seeds and reputation check
+    /// Endorser's reputation (must reach the config's min_endorser_reputation, 500 by default)
+    /// CHECK: Validated via seeds, owner and reputation check
     #[account(
-        seeds = [b"reputation", endorser.key().as_ref()],
+        seeds = [REPUTATION_SEED, endorser.key().as_ref()],
         bump,
-        seeds::program = reputation_registry_program.key()
+        seeds::program = REPUTATION_REGISTRY_PROGRAM_ID,
+        owner = REPUTATION_REGISTRY_PROGRAM_ID @ VoteError::InvalidAccountOwner
     )]
     pub endorser_reputation: AccountInfo<'info>,
 
-    /// Endorsed agent's identity (must be active)
-    /// CHECK: Validated via seeds and is_active check
+    /// Endorsed agent's identity (must be active and unexpired)
+    /// CHECK: Validated via seeds, owner and is_current check
     #[account(
-        seeds = [b"agent", endorsed_agent.as_ref()],
+        seeds = [IDENTITY_AGENT_SEED, endorsed_agent.as_ref()],
         bump,
-        seeds::program = identity_registry_program.key()
+        seeds::program = IDENTITY_REGISTRY_PROGRAM_ID,
+        owner = IDENTITY_REGISTRY_PROGRAM_ID @ VoteError::InvalidAccountOwner
     )]
     pub endorsed_agent_identity: AccountInfo<'info>,
 
     #[account(mut)]
     pub endorser: Signer<'info>,
 
-    /// CHECK: Identity Registry program
+    /// CHECK: Identity Registry program; pinned by address
+    #[account(address = IDENTITY_REGISTRY_PROGRAM_ID @ VoteError::InvalidIdentityProgram)]
     pub identity_registry_program: AccountInfo<'info>,
 
-    /// CHECK: Reputation Registry program
+    /// CHECK: Reputation Registry program; pinned by address
+    #[account(address = REPUTATION_REGISTRY_PROGRAM_ID @ VoteError::InvalidReputationProgram)]
     pub reputation_registry_program: AccountInfo<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// Vote config; when omitted the stake is a flat AgentEndorsement::MIN_STAKE,
+    /// the cap is DEFAULT_MAX_ACTIVE_ENDORSEMENTS, the validity period is
+    /// DEFAULT_ENDORSEMENT_VALIDITY_SECONDS and the reputation threshold is
+    /// DEFAULT_MIN_ENDORSER_REPUTATION
+    #[account(
+        seeds = [VoteConfig::SEED_PREFIX],
+        bump = vote_config.bump
+    )]
+    pub vote_config: Option<Account<'info, VoteConfig>>,
 }
 
 pub fn handler(
@@ -96,35 +117,74 @@ pub fn handler(
         VoteError::InvalidEndorsementStrength
     );
 
+    let clock = Clock::get()?;
+
     // Deserialize and validate endorser identity
     let endorser_identity_data = &ctx.accounts.endorser_identity.data.borrow();
-    let endorser_identity = AgentIdentity::try_deserialize(&mut &endorser_identity_data[..])?;
+    let endorser_identity = AgentIdentityPrefix::try_deserialize(&mut &endorser_identity_data[..])?;
 
     require!(
-        endorser_identity.is_active,
+        endorser_identity.is_current(clock.unix_timestamp),
         VoteError::InactiveVoter
     );
 
     // Deserialize and validate endorser reputation
     let endorser_reputation_data = &ctx.accounts.endorser_reputation.data.borrow();
-    let endorser_reputation = AgentReputation::try_deserialize(&mut &endorser_reputation_data[..])?;
+    let endorser_reputation = AgentReputationPrefix::try_deserialize(&mut &endorser_reputation_data[..])?;
 
+    let min_endorser_reputation = ctx
+        .accounts
+        .vote_config
+        .as_ref()
+        .map_or(DEFAULT_MIN_ENDORSER_REPUTATION, |config| config.min_endorser_reputation);
     require!(
-        endorser_reputation.overall_score >= 500,
+        endorser_reputation.overall_score >= min_endorser_reputation,
         VoteError::InsufficientEndorserReputation
     );
 
     // Deserialize and validate endorsed agent identity
     let endorsed_agent_identity_data = &ctx.accounts.endorsed_agent_identity.data.borrow();
-    let endorsed_agent_identity = AgentIdentity::try_deserialize(&mut &endorsed_agent_identity_data[..])?;
+    let endorsed_agent_identity = AgentIdentityPrefix::try_deserialize(&mut &endorsed_agent_identity_data[..])?;
 
     require!(
-        endorsed_agent_identity.is_active,
+        endorsed_agent_identity.is_current(clock.unix_timestamp),
         VoteError::EndorsedAgentNotActive
     );
 
+    // Stake scales with strength under the config; flat MIN_STAKE without one
+    let (stake_base, stake_per_point, max_active, validity) = ctx
+        .accounts
+        .vote_config
+        .as_ref()
+        .map_or(
+            (
+                AgentEndorsement::MIN_STAKE,
+                0,
+                DEFAULT_MAX_ACTIVE_ENDORSEMENTS,
+                DEFAULT_ENDORSEMENT_VALIDITY_SECONDS,
+            ),
+            |config| {
+                (
+                    config.endorsement_base_stake,
+                    config.endorsement_stake_per_point,
+                    config.max_active_endorsements,
+                    config.endorsement_validity_seconds,
+                )
+            },
+        );
+
+    require!(
+        ctx.accounts.endorser_stats.endorsements_given < max_active,
+        VoteError::MaxEndorsementsReached
+    );
+    let stake_amount = AgentEndorsement::required_stake(stake_base, stake_per_point, strength);
+
+    require!(
+        ctx.accounts.endorser.lamports() >= stake_amount,
+        VoteError::InsufficientBalanceForStake
+    );
+
     // Transfer stake to endorsement PDA
-    let stake_amount = AgentEndorsement::MIN_STAKE;
 
     system_program::transfer(
         CpiContext::new(
@@ -138,7 +198,6 @@ pub fn handler(
     )?;
 
     let endorsement = &mut ctx.accounts.endorsement;
-    let clock = Clock::get()?;
 
     endorsement.endorser = ctx.accounts.endorser.key();
     endorsement.endorsed = endorsed_agent;
@@ -149,6 +208,44 @@ pub fn handler(
     endorsement.stake_amount = stake_amount;
     endorsement.is_active = true;
     endorsement.bump = ctx.bumps.endorsement;
+    endorsement.is_slashed = false;
+    endorsement.slashed_at = 0;
+    endorsement.stake_base = stake_base;
+    endorsement.stake_per_point = stake_per_point;
+    endorsement.expires_at = clock.unix_timestamp.saturating_add(validity);
+    endorsement.weight = AgentEndorsement::static_weight(strength, endorser_reputation.overall_score);
+    endorsement.affirmed_at = clock.unix_timestamp;
+
+    let endorser_stats = &mut ctx.accounts.endorser_stats;
+    if endorser_stats.agent == Pubkey::default() {
+        endorser_stats.agent = ctx.accounts.endorser.key();
+        endorser_stats.bump = ctx.bumps.endorser_stats;
+    }
+    endorser_stats.endorsements_given = endorser_stats.endorsements_given.saturating_add(1);
+
+    let endorsed_stats = &mut ctx.accounts.endorsed_stats;
+    if endorsed_stats.agent == Pubkey::default() {
+        endorsed_stats.agent = endorsed_agent;
+        endorsed_stats.bump = ctx.bumps.endorsed_stats;
+    }
+    endorsed_stats.endorsements_received = endorsed_stats.endorsements_received.saturating_add(1);
+
+    emit!(AgentEndorsed {
+        endorsement: endorsement.key(),
+        endorser_stats: endorser_stats.key(),
+        endorsed_stats: endorsed_stats.key(),
+        endorser: endorsement.endorser,
+        endorsed: endorsed_agent,
+        strength,
+        category,
+        stake_amount,
+        weight: endorsement.weight,
+        endorser_reputation: endorsement.endorser_reputation_snapshot,
+        expires_at: endorsement.expires_at,
+        endorsements_given: endorser_stats.endorsements_given,
+        endorsements_received: endorsed_stats.endorsements_received,
+        timestamp: clock.unix_timestamp,
+    });
 
     msg!("Agent {} endorsed {} with strength {} in category {:?}",
          ctx.accounts.endorser.key(), endorsed_agent, strength, category);
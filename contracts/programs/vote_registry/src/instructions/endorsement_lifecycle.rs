@@ -0,0 +1,295 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use gs2_common::AgentIdentityPrefix;
+use crate::state::{AgentEndorsement, EndorsementStats, VoteAuthority, VoteConfig};
+use crate::constants::{
+    DEFAULT_ENDORSEMENT_VALIDITY_SECONDS, IDENTITY_AGENT_SEED, IDENTITY_REGISTRY_PROGRAM_ID,
+};
+use crate::error::VoteError;
+
+// ==================== REVOKE ENDORSEMENT ====================
+
+#[derive(Accounts)]
+pub struct RevokeEndorsement<'info> {
+    /// Closed to the endorser, returning the stake along with the rent
+    #[account(
+        mut,
+        seeds = [
+            AgentEndorsement::SEED_PREFIX,
+            endorsement.endorser.as_ref(),
+            endorsement.endorsed.as_ref()
+        ],
+        bump = endorsement.bump,
+        has_one = endorser @ VoteError::NotEndorser,
+        constraint = endorsement.is_active @ VoteError::EndorsementNotActive,
+        close = endorser
+    )]
+    pub endorsement: Account<'info, AgentEndorsement>,
+
+    /// Endorser's counters
+    #[account(
+        mut,
+        seeds = [EndorsementStats::SEED_PREFIX, endorsement.endorser.as_ref()],
+        bump = endorser_stats.bump
+    )]
+    pub endorser_stats: Account<'info, EndorsementStats>,
+
+    /// Endorsed agent's counters
+    #[account(
+        mut,
+        seeds = [EndorsementStats::SEED_PREFIX, endorsement.endorsed.as_ref()],
+        bump = endorsed_stats.bump
+    )]
+    pub endorsed_stats: Account<'info, EndorsementStats>,
+
+    #[account(mut)]
+    pub endorser: Signer<'info>,
+}
+
+/// Withdraw an endorsement once it has stood for
+/// AgentEndorsement::MIN_ENDORSEMENT_DURATION_SECONDS. Slashed endorsements
+/// are inactive and cannot be revoked.
+pub fn revoke_endorsement(ctx: Context<RevokeEndorsement>) -> Result<()> {
+    let clock = Clock::get()?;
+    let endorsement = &mut ctx.accounts.endorsement;
+
+    require!(
+        endorsement.is_revocable(clock.unix_timestamp),
+        VoteError::EndorsementLocked
+    );
+
+    endorsement.is_active = false;
+    release_slot(&mut ctx.accounts.endorser_stats, &mut ctx.accounts.endorsed_stats);
+
+    msg!("Endorsement of {} revoked by {}", endorsement.endorsed, endorsement.endorser);
+    msg!("Stake refunded: {} lamports", endorsement.stake_amount);
+
+    Ok(())
+}
+
+// ==================== SLASH ENDORSEMENT ====================
+
+#[derive(Accounts)]
+pub struct SlashEndorsement<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AgentEndorsement::SEED_PREFIX,
+            endorsement.endorser.as_ref(),
+            endorsement.endorsed.as_ref()
+        ],
+        bump = endorsement.bump,
+        constraint = endorsement.is_active @ VoteError::EndorsementNotActive
+    )]
+    pub endorsement: Account<'info, AgentEndorsement>,
+
+    /// Endorser's counters
+    #[account(
+        mut,
+        seeds = [EndorsementStats::SEED_PREFIX, endorsement.endorser.as_ref()],
+        bump = endorser_stats.bump
+    )]
+    pub endorser_stats: Account<'info, EndorsementStats>,
+
+    /// Endorsed agent's counters
+    #[account(
+        mut,
+        seeds = [EndorsementStats::SEED_PREFIX, endorsement.endorsed.as_ref()],
+        bump = endorsed_stats.bump
+    )]
+    pub endorsed_stats: Account<'info, EndorsementStats>,
+
+    /// Endorsed agent's identity (must record at least one slash)
+    /// CHECK: Validated via seeds, owner and slash_count check
+    #[account(
+        seeds = [IDENTITY_AGENT_SEED, endorsement.endorsed.as_ref()],
+        bump,
+        seeds::program = IDENTITY_REGISTRY_PROGRAM_ID,
+        owner = IDENTITY_REGISTRY_PROGRAM_ID @ VoteError::InvalidAccountOwner
+    )]
+    pub endorsed_agent_identity: AccountInfo<'info>,
+
+    #[account(
+        seeds = [VoteAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ VoteError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, VoteAuthority>,
+
+    /// Authority that can slash endorsements; receives the seized stake
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Seize an endorsement's stake after the endorsed agent was slashed in
+/// identity_registry. The account stays behind, inactive, as a record.
+pub fn slash_endorsement(ctx: Context<SlashEndorsement>) -> Result<()> {
+    let identity_data = &ctx.accounts.endorsed_agent_identity.data.borrow();
+    let endorsed_identity = AgentIdentityPrefix::try_deserialize(&mut &identity_data[..])?;
+
+    require!(
+        endorsed_identity.slash_count > 0,
+        VoteError::EndorsedAgentNotSlashed
+    );
+
+    let seized = ctx.accounts.endorsement.stake_amount;
+
+    // Program-owned account, so the stake can be moved directly; rent stays behind
+    ctx.accounts.endorsement.sub_lamports(seized)?;
+    ctx.accounts.authority.add_lamports(seized)?;
+
+    let endorsement = &mut ctx.accounts.endorsement;
+    endorsement.stake_amount = 0;
+    endorsement.is_active = false;
+    endorsement.is_slashed = true;
+    endorsement.slashed_at = Clock::get()?.unix_timestamp;
+    release_slot(&mut ctx.accounts.endorser_stats, &mut ctx.accounts.endorsed_stats);
+
+    msg!("Endorsement of {} by {} slashed", endorsement.endorsed, endorsement.endorser);
+    msg!("Stake seized: {} lamports", seized);
+
+    Ok(())
+}
+
+// ==================== REAFFIRM ENDORSEMENT ====================
+
+#[derive(Accounts)]
+pub struct ReaffirmEndorsement<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AgentEndorsement::SEED_PREFIX,
+            endorsement.endorser.as_ref(),
+            endorsement.endorsed.as_ref()
+        ],
+        bump = endorsement.bump,
+        has_one = endorser @ VoteError::NotEndorser,
+        constraint = endorsement.is_active @ VoteError::EndorsementNotActive
+    )]
+    pub endorsement: Account<'info, AgentEndorsement>,
+
+    #[account(mut)]
+    pub endorser: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Vote config; when omitted the validity period is
+    /// DEFAULT_ENDORSEMENT_VALIDITY_SECONDS
+    #[account(
+        seeds = [VoteConfig::SEED_PREFIX],
+        bump = vote_config.bump
+    )]
+    pub vote_config: Option<Account<'info, VoteConfig>>,
+}
+
+/// Push an unexpired endorsement's expires_at a full validity period past now
+/// and restart its age decay, optionally locking `extra_stake` more lamports
+/// alongside the existing stake
+pub fn reaffirm_endorsement(ctx: Context<ReaffirmEndorsement>, extra_stake: u64) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        ctx.accounts.endorsement.is_current(clock.unix_timestamp),
+        VoteError::EndorsementExpired
+    );
+
+    let validity = ctx
+        .accounts
+        .vote_config
+        .as_ref()
+        .map_or(DEFAULT_ENDORSEMENT_VALIDITY_SECONDS, |config| config.endorsement_validity_seconds);
+
+    if extra_stake > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.endorser.to_account_info(),
+                    to: ctx.accounts.endorsement.to_account_info(),
+                },
+            ),
+            extra_stake,
+        )?;
+    }
+
+    let endorsement = &mut ctx.accounts.endorsement;
+    endorsement.stake_amount = endorsement.stake_amount.saturating_add(extra_stake);
+    endorsement.expires_at = clock.unix_timestamp.saturating_add(validity);
+    endorsement.affirmed_at = clock.unix_timestamp;
+
+    msg!("Endorsement of {} re-affirmed by {}", endorsement.endorsed, endorsement.endorser);
+    msg!("Expires at: {} (stake now {} lamports)", endorsement.expires_at, endorsement.stake_amount);
+
+    Ok(())
+}
+
+// ==================== MARK ENDORSEMENT EXPIRED ====================
+
+#[derive(Accounts)]
+pub struct MarkEndorsementExpired<'info> {
+    /// Closed to the endorser, returning the stake along with the rent
+    #[account(
+        mut,
+        seeds = [
+            AgentEndorsement::SEED_PREFIX,
+            endorsement.endorser.as_ref(),
+            endorsement.endorsed.as_ref()
+        ],
+        bump = endorsement.bump,
+        has_one = endorser @ VoteError::NotEndorser,
+        constraint = endorsement.is_active @ VoteError::EndorsementNotActive,
+        close = endorser
+    )]
+    pub endorsement: Account<'info, AgentEndorsement>,
+
+    /// Endorser's counters
+    #[account(
+        mut,
+        seeds = [EndorsementStats::SEED_PREFIX, endorsement.endorser.as_ref()],
+        bump = endorser_stats.bump
+    )]
+    pub endorser_stats: Account<'info, EndorsementStats>,
+
+    /// Endorsed agent's counters
+    #[account(
+        mut,
+        seeds = [EndorsementStats::SEED_PREFIX, endorsement.endorsed.as_ref()],
+        bump = endorsed_stats.bump
+    )]
+    pub endorsed_stats: Account<'info, EndorsementStats>,
+
+    /// Endorser wallet; receives the stake, need not sign
+    /// CHECK: Pinned to endorsement.endorser by has_one
+    #[account(mut)]
+    pub endorser: UncheckedAccount<'info>,
+
+    /// Anyone may expire a lapsed endorsement
+    pub caller: Signer<'info>,
+}
+
+/// Retire an endorsement past its expires_at, refunding the stake to the
+/// endorser. Permissionless so lapsed endorsements stop counting against the
+/// endorser's cap without the endorser having to act.
+pub fn mark_endorsement_expired(ctx: Context<MarkEndorsementExpired>) -> Result<()> {
+    let clock = Clock::get()?;
+    let endorsement = &mut ctx.accounts.endorsement;
+
+    require!(
+        !endorsement.is_current(clock.unix_timestamp),
+        VoteError::EndorsementNotExpired
+    );
+
+    endorsement.is_active = false;
+    release_slot(&mut ctx.accounts.endorser_stats, &mut ctx.accounts.endorsed_stats);
+
+    msg!("Endorsement of {} by {} expired", endorsement.endorsed, endorsement.endorser);
+    msg!("Stake refunded: {} lamports", endorsement.stake_amount);
+
+    Ok(())
+}
+
+/// Drop a no-longer-active endorsement from both agents' counters
+fn release_slot(endorser_stats: &mut EndorsementStats, endorsed_stats: &mut EndorsementStats) {
+    endorser_stats.endorsements_given = endorser_stats.endorsements_given.saturating_sub(1);
+    endorsed_stats.endorsements_received = endorsed_stats.endorsements_received.saturating_sub(1);
+}
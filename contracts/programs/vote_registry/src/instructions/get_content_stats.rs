@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::ContentRatingStats;
+
+#[derive(Accounts)]
+pub struct GetContentStats<'info> {
+    #[account(
+        seeds = [ContentRatingStats::SEED_PREFIX, agent.key().as_ref()],
+        bump = content_stats.bump
+    )]
+    pub content_stats: Account<'info, ContentRatingStats>,
+
+    /// CHECK: The agent's wallet address
+    pub agent: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<GetContentStats>) -> Result<ContentRatingStats> {
+    let stats = &ctx.accounts.content_stats;
+
+    msg!(
+        "Content stats for agent {}: {} ratings, rating sum {}",
+        stats.agent,
+        stats.total_ratings,
+        stats.total_rating_sum
+    );
+
+    Ok((**stats).clone())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+use crate::constants::DEFAULT_ENDORSEMENT_DECAY_SECONDS;
+use crate::state::{AgentEndorsement, VoteConfig};
+use crate::error::VoteError;
+
+/// Pass the agent's AgentEndorsement PDAs as remaining accounts
+#[derive(Accounts)]
+pub struct GetEndorsementWeight<'info> {
+    /// CHECK: The endorsed agent's wallet address
+    pub agent: UncheckedAccount<'info>,
+
+    /// Vote config; DEFAULT_ENDORSEMENT_DECAY_SECONDS applies until it is initialized
+    /// CHECK: PDA checked by seeds; read with VoteConfig::load
+    #[account(seeds = [VoteConfig::SEED_PREFIX], bump)]
+    pub vote_config: UncheckedAccount<'info>,
+}
+
+/// Sum the current, age-decayed weight of the endorsements passed in
+/// remaining_accounts; each must be a distinct endorsement PDA of the agent
+pub fn handler(ctx: Context<GetEndorsementWeight>) -> Result<u64> {
+    let now = Clock::get()?.unix_timestamp;
+    let agent = ctx.accounts.agent.key();
+    let decay_seconds = VoteConfig::load(&ctx.accounts.vote_config)?
+        .map_or(DEFAULT_ENDORSEMENT_DECAY_SECONDS, |config| config.endorsement_decay_seconds);
+
+    let mut seen = BTreeSet::new();
+    let mut total_weight: u64 = 0;
+    let mut counted: u32 = 0;
+    for info in ctx.remaining_accounts {
+        require!(
+            info.owner == &crate::ID && seen.insert(info.key()),
+            VoteError::InvalidEndorsementAccount
+        );
+
+        let data = info.try_borrow_data()?;
+        let endorsement = AgentEndorsement::try_deserialize(&mut &data[..])?;
+        let expected = Pubkey::create_program_address(
+            &[
+                AgentEndorsement::SEED_PREFIX,
+                endorsement.endorser.as_ref(),
+                agent.as_ref(),
+                &[endorsement.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| error!(VoteError::InvalidEndorsementAccount))?;
+        require!(
+            endorsement.endorsed == agent && info.key() == expected,
+            VoteError::InvalidEndorsementAccount
+        );
+
+        let weight = endorsement.effective_weight(now, decay_seconds);
+        if weight > 0 {
+            counted = counted.saturating_add(1);
+        }
+        total_weight = total_weight.saturating_add(weight);
+    }
+
+    msg!(
+        "Endorsement weight for agent {}: {} from {} current endorsements",
+        agent,
+        total_weight,
+        counted
+    );
+
+    Ok(total_weight)
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::VoteTally;
+
+#[derive(Accounts)]
+pub struct GetVoteTally<'info> {
+    #[account(
+        seeds = [VoteTally::SEED_PREFIX, agent.key().as_ref()],
+        bump = vote_tally.bump
+    )]
+    pub vote_tally: Account<'info, VoteTally>,
+
+    /// CHECK: The agent's wallet address
+    pub agent: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<GetVoteTally>) -> Result<VoteTally> {
+    let tally = &ctx.accounts.vote_tally;
+
+    msg!(
+        "Vote tally for agent {}: {} up, {} down, {} neutral",
+        tally.agent,
+        tally.upvotes,
+        tally.downvotes,
+        tally.neutrals
+    );
+
+    Ok((**tally).clone())
+}
@@ -1,9 +1,34 @@
+// `handler` is defined in each module, hence the allow
+#![allow(ambiguous_glob_reexports)]
+
 pub mod create_transaction_receipt;
+pub mod create_verified_transaction_receipt;
 pub mod cast_peer_vote;
 pub mod rate_content;
 pub mod endorse_agent;
+pub mod get_vote_tally;
+pub mod amend_peer_vote;
+pub mod initialize_authority;
+pub mod endorsement_lifecycle;
+pub mod config;
+pub mod receipt_confirmation;
+pub mod close_accounts;
+pub mod get_content_stats;
+pub mod vote_disputes;
+pub mod get_endorsement_weight;
 
 pub use create_transaction_receipt::*;
+pub use create_verified_transaction_receipt::*;
 pub use cast_peer_vote::*;
 pub use rate_content::*;
 pub use endorse_agent::*;
+pub use get_vote_tally::*;
+pub use amend_peer_vote::*;
+pub use initialize_authority::*;
+pub use endorsement_lifecycle::*;
+pub use config::*;
+pub use receipt_confirmation::*;
+pub use close_accounts::*;
+pub use get_content_stats::*;
+pub use vote_disputes::*;
+pub use get_endorsement_weight::*;
@@ -1,29 +1,15 @@
 use anchor_lang::prelude::*;
-use crate::state::{ContentRating, ContentType};
+use gs2_common::{AgentIdentityPrefix, AgentReputationPrefix};
+use reputation_registry::cpi::accounts::RecordReview;
+use reputation_registry::STATS_CPI_AUTHORITY_SEED;
+use crate::state::{ContentRating, ContentRatingStats, TransactionReceipt, VoteConfig};
+use crate::constants::{
+    DEFAULT_MIN_RATER_REPUTATION, IDENTITY_AGENT_SEED, IDENTITY_REGISTRY_PROGRAM_ID, REPUTATION_REGISTRY_PROGRAM_ID, REPUTATION_SEED,
+};
 use crate::error::VoteError;
-
-/// External AgentIdentity account structure (from identity_registry)
-#[account]
-pub struct AgentIdentity {
-    pub agent_address: Pubkey,
-    pub asset_address: Pubkey,
-    pub metadata_uri: String,
-    pub registration_timestamp: i64,
-    pub last_active_timestamp: i64,
-    pub activity_count: u64,
-    pub is_active: bool,
-    pub bump: u8,
-}
-
-/// External AgentReputation account structure (from reputation_registry)
-#[account]
-pub struct AgentReputation {
-    pub agent_address: Pubkey,
-    pub overall_score: u16,
-}
+use crate::events::ContentRated;
 
 #[derive(Accounts)]
-#[instruction(x402_signature: String)]
 pub struct RateContent<'info> {
     #[account(
         init,
@@ -31,36 +17,58 @@ pub struct RateContent<'info> {
         space = ContentRating::LEN,
         seeds = [
             ContentRating::SEED_PREFIX,
-            x402_signature.as_bytes()
+            transaction_receipt.key().as_ref()
         ],
         bump
     )]
     pub content_rating: Account<'info, ContentRating>,
 
-    /// Rater's identity (must be active)
-    /// CHECK: Validated via seeds and is_active check
+    /// Rated agent's aggregated rating stats, created on the agent's first rating
     #[account(
-        seeds = [b"agent", rater.key().as_ref()],
+        init_if_needed,
+        payer = rater,
+        space = ContentRatingStats::LEN,
+        seeds = [ContentRatingStats::SEED_PREFIX, rated_agent.key().as_ref()],
+        bump
+    )]
+    pub content_stats: Account<'info, ContentRatingStats>,
+
+    /// Transaction receipt proving the rater paid the rated agent; supplies
+    /// the signature, amount and content type recorded on the rating
+    #[account(
+        constraint = transaction_receipt.payer == rater.key() @ VoteError::RaterNotPayer,
+        constraint = transaction_receipt.recipient == rated_agent.key() @ VoteError::ReceiptRecipientMismatch,
+        constraint = !transaction_receipt.is_disputed @ VoteError::ReceiptDisputed
+    )]
+    pub transaction_receipt: Account<'info, TransactionReceipt>,
+
+    /// Rater's identity (must be active and unexpired)
+    /// CHECK: Validated via seeds, owner and is_current check
+    #[account(
+        seeds = [IDENTITY_AGENT_SEED, rater.key().as_ref()],
         bump,
-        seeds::program = identity_registry_program.key()
+        seeds::program = IDENTITY_REGISTRY_PROGRAM_ID,
+        owner = IDENTITY_REGISTRY_PROGRAM_ID @ VoteError::InvalidAccountOwner
     )]
     pub rater_identity: AccountInfo<'info>,
 
     /// Rater's reputation (for weighting)
-    /// CHECK: Validated via seeds
+    /// CHECK: Validated via seeds and owner
     #[account(
-        seeds = [b"reputation", rater.key().as_ref()],
+        seeds = [REPUTATION_SEED, rater.key().as_ref()],
         bump,
-        seeds::program = reputation_registry_program.key()
+        seeds::program = REPUTATION_REGISTRY_PROGRAM_ID,
+        owner = REPUTATION_REGISTRY_PROGRAM_ID @ VoteError::InvalidAccountOwner
     )]
     pub rater_reputation: AccountInfo<'info>,
 
-    /// Rated agent's identity (must be active)
-    /// CHECK: Validated via seeds and is_active check
+    /// Rated agent's identity (must be active and unexpired)
+    /// CHECK: Validated via seeds, owner and is_current check
     #[account(
-        seeds = [b"agent", rated_agent.key().as_ref()],
+        seeds = [IDENTITY_AGENT_SEED, rated_agent.key().as_ref()],
         bump,
-        seeds::program = identity_registry_program.key()
+        seeds::program = IDENTITY_REGISTRY_PROGRAM_ID,
+        owner = IDENTITY_REGISTRY_PROGRAM_ID @ VoteError::InvalidAccountOwner
     )]
     pub rated_agent_identity: AccountInfo<'info>,
 
@@ -68,75 +76,152 @@ pub struct RateContent<'info> {
     /// CHECK: Validated above
     pub rated_agent: UncheckedAccount<'info>,
 
+    /// Rated agent's reputation (review stats updated via CPI)
+    /// CHECK: Validated via seeds and owner; written by reputation_registry
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, rated_agent.key().as_ref()],
+        bump,
+        seeds::program = REPUTATION_REGISTRY_PROGRAM_ID,
+        owner = REPUTATION_REGISTRY_PROGRAM_ID @ VoteError::InvalidAccountOwner
+    )]
+    pub rated_agent_reputation: AccountInfo<'info>,
+
+    /// PDA that signs stat-update CPIs into reputation_registry
+    /// CHECK: Seeds only; never holds data
+    #[account(seeds = [STATS_CPI_AUTHORITY_SEED], bump)]
+    pub reputation_cpi_authority: AccountInfo<'info>,
+
     #[account(mut)]
     pub rater: Signer<'info>,
 
-    /// CHECK: Identity Registry program
+    /// CHECK: Identity Registry program; pinned by address
+    #[account(address = IDENTITY_REGISTRY_PROGRAM_ID @ VoteError::InvalidIdentityProgram)]
     pub identity_registry_program: AccountInfo<'info>,
 
-    /// CHECK: Reputation Registry program
+    /// CHECK: Reputation Registry program; pinned by address
+    #[account(address = REPUTATION_REGISTRY_PROGRAM_ID @ VoteError::InvalidReputationProgram)]
     pub reputation_registry_program: AccountInfo<'info>,
 
     pub system_program: Program<'info, System>,
-}
 
-pub fn handler(
-    ctx: Context<RateContent>,
-    x402_signature: String,
-    quality_rating: u8,
-    content_type: ContentType,
-    amount_paid: u64,
-) -> Result<()> {
-    // Validate x402 signature length
-    require!(
-        x402_signature.len() <= 88,
-        VoteError::InvalidX402Signature
-    );
+    /// Vote config; until it is initialized any reputation may rate
+    /// CHECK: PDA checked by seeds; read with VoteConfig::load
+    #[account(seeds = [VoteConfig::SEED_PREFIX], bump)]
+    pub vote_config: UncheckedAccount<'info>,
+}
 
+/// Rate the content delivered in a receipted x402 payment. One rating per
+/// receipt; payment details come from the receipt, not the caller.
+pub fn handler(ctx: Context<RateContent>, quality_rating: u8) -> Result<()> {
     // Validate quality rating
     require!(
         quality_rating <= 100,
         VoteError::InvalidContentRating
     );
 
+    let clock = Clock::get()?;
+
     // Deserialize and validate rater identity
     let rater_identity_data = &ctx.accounts.rater_identity.data.borrow();
-    let rater_identity = AgentIdentity::try_deserialize(&mut &rater_identity_data[..])?;
+    let rater_identity = AgentIdentityPrefix::try_deserialize(&mut &rater_identity_data[..])?;
 
     require!(
-        rater_identity.is_active,
+        rater_identity.is_current(clock.unix_timestamp),
         VoteError::InactiveVoter
     );
 
     // Deserialize and validate rater reputation
     let rater_reputation_data = &ctx.accounts.rater_reputation.data.borrow();
-    let rater_reputation = AgentReputation::try_deserialize(&mut &rater_reputation_data[..])?;
+    let rater_reputation = AgentReputationPrefix::try_deserialize(&mut &rater_reputation_data[..])?;
+
+    let min_rater_reputation = VoteConfig::load(&ctx.accounts.vote_config)?
+        .map_or(DEFAULT_MIN_RATER_REPUTATION, |config| config.min_rater_reputation);
+    require!(
+        rater_reputation.overall_score >= min_rater_reputation,
+        VoteError::InsufficientReputation
+    );
 
     // Deserialize and validate rated agent identity
     let rated_agent_identity_data = &ctx.accounts.rated_agent_identity.data.borrow();
-    let rated_agent_identity = AgentIdentity::try_deserialize(&mut &rated_agent_identity_data[..])?;
+    let rated_agent_identity = AgentIdentityPrefix::try_deserialize(&mut &rated_agent_identity_data[..])?;
 
     require!(
-        rated_agent_identity.is_active,
+        rated_agent_identity.is_current(clock.unix_timestamp),
         VoteError::RatedAgentNotActive
     );
 
+    let receipt = &ctx.accounts.transaction_receipt;
     let content_rating = &mut ctx.accounts.content_rating;
-    let clock = Clock::get()?;
 
     content_rating.agent = ctx.accounts.rated_agent.key();
     content_rating.rater = ctx.accounts.rater.key();
-    content_rating.x402_signature = x402_signature.clone();
+    content_rating.x402_signature = receipt.signature.clone();
     content_rating.quality_rating = quality_rating;
-    content_rating.content_type = content_type;
-    content_rating.amount_paid = amount_paid;
+    content_rating.content_type = receipt.content_type;
+    content_rating.amount_paid = receipt.amount;
     content_rating.timestamp = clock.unix_timestamp;
     content_rating.rater_reputation_snapshot = rater_reputation.overall_score;
     content_rating.bump = ctx.bumps.content_rating;
+    content_rating.transaction_receipt = receipt.key();
+    content_rating.payment_mint = receipt.payment_mint;
+
+    let stats = &mut ctx.accounts.content_stats;
+    if stats.agent == Pubkey::default() {
+        stats.agent = ctx.accounts.rated_agent.key();
+        stats.bump = ctx.bumps.content_stats;
+    }
+    stats.record(
+        content_rating.content_type,
+        quality_rating,
+        content_rating.rater_reputation_snapshot,
+        clock.unix_timestamp,
+    );
+
+    // Record the review on the rated agent's reputation (0-100 -> 0-50, rounded)
+    let review_rating = quality_rating.div_ceil(2);
+    let cpi_bump = ctx.bumps.reputation_cpi_authority;
+    let signer_seeds: &[&[&[u8]]] = &[&[STATS_CPI_AUTHORITY_SEED, &[cpi_bump]]];
+    reputation_registry::cpi::record_review(
+        CpiContext::new_with_signer(
+            ctx.accounts.reputation_registry_program.to_account_info(),
+            RecordReview {
+                agent_reputation: ctx.accounts.rated_agent_reputation.to_account_info(),
+                cpi_authority: ctx.accounts.reputation_cpi_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        review_rating,
+    )?;
+
+    let stats = &ctx.accounts.content_stats;
+    emit!(ContentRated {
+        content_rating: content_rating.key(),
+        content_stats: stats.key(),
+        transaction_receipt: content_rating.transaction_receipt,
+        agent: content_rating.agent,
+        rater: content_rating.rater,
+        quality_rating,
+        content_type: content_rating.content_type,
+        amount_paid: content_rating.amount_paid,
+        payment_mint: content_rating.payment_mint,
+        rater_reputation: content_rating.rater_reputation_snapshot,
+        total_ratings: stats.total_ratings,
+        total_rating_sum: stats.total_rating_sum,
+        total_weighted_rating_sum: stats.total_weighted_rating_sum,
+        total_weight_sum: stats.total_weight_sum,
+        timestamp: clock.unix_timestamp,
+    });
 
     msg!("Content rated: {} by {}", ctx.accounts.rated_agent.key(), ctx.accounts.rater.key());
-    msg!("Quality: {}/100, Type: {:?}, Amount: {} lamports", quality_rating, content_type, amount_paid);
-    msg!("x402 signature: {}", x402_signature);
+    msg!(
+        "Quality: {}/100, Type: {:?}, Amount: {} (mint {})",
+        quality_rating,
+        content_rating.content_type,
+        content_rating.amount_paid,
+        content_rating.payment_mint
+    );
+    msg!("x402 signature: {}", content_rating.x402_signature);
 
     Ok(())
 }
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::TransactionReceipt;
+use crate::error::VoteError;
+
+#[derive(Accounts)]
+pub struct ReceiptConfirmation<'info> {
+    #[account(
+        mut,
+        constraint = !receipt.is_disputed @ VoteError::ReceiptDisputed,
+        constraint = !receipt.counterparty_confirmed @ VoteError::ReceiptAlreadyConfirmed
+    )]
+    pub receipt: Account<'info, TransactionReceipt>,
+
+    /// Party that did not create the receipt
+    pub counterparty: Signer<'info>,
+}
+
+// ==================== CONFIRM RECEIPT ====================
+
+/// Attest, as the non-creator party, that the receipted payment happened
+pub fn confirm_receipt(ctx: Context<ReceiptConfirmation>) -> Result<()> {
+    check_counterparty(&ctx.accounts.receipt, ctx.accounts.counterparty.key())?;
+
+    let receipt = &mut ctx.accounts.receipt;
+    receipt.counterparty_confirmed = true;
+
+    msg!("Transaction receipt {} confirmed by {}", receipt.signature, ctx.accounts.counterparty.key());
+
+    Ok(())
+}
+
+// ==================== REJECT RECEIPT ====================
+
+/// Dispute a receipt the non-creator party never agreed to. Permanent: no
+/// votes can be cast with a disputed receipt.
+pub fn reject_receipt(ctx: Context<ReceiptConfirmation>) -> Result<()> {
+    check_counterparty(&ctx.accounts.receipt, ctx.accounts.counterparty.key())?;
+
+    let receipt = &mut ctx.accounts.receipt;
+    receipt.is_disputed = true;
+
+    msg!("Transaction receipt {} rejected by {}", receipt.signature, ctx.accounts.counterparty.key());
+
+    Ok(())
+}
+
+/// Only the non-creator party may confirm or reject
+fn check_counterparty(receipt: &TransactionReceipt, signer: Pubkey) -> Result<()> {
+    require!(
+        signer != receipt.creator,
+        VoteError::CreatorCannotConfirm
+    );
+
+    require!(
+        signer == receipt.counterparty(),
+        VoteError::NotReceiptCounterparty
+    );
+
+    Ok(())
+}
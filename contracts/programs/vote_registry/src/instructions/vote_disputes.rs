@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+use crate::state::{CommentReveal, PeerVote, VoteAuthority, VoteDisputeStatus, VoteTally};
+use crate::error::VoteError;
+
+// ==================== REVEAL COMMENT ====================
+
+#[derive(Accounts)]
+pub struct RevealComment<'info> {
+    #[account(
+        mut,
+        seeds = [
+            PeerVote::SEED_PREFIX,
+            peer_vote.transaction_receipt.as_ref(),
+            peer_vote.voter.as_ref()
+        ],
+        bump = peer_vote.bump
+    )]
+    pub peer_vote: Account<'info, PeerVote>,
+
+    #[account(
+        init,
+        payer = revealer,
+        space = CommentReveal::LEN,
+        seeds = [CommentReveal::SEED_PREFIX, peer_vote.key().as_ref()],
+        bump
+    )]
+    pub comment_reveal: Account<'info, CommentReveal>,
+
+    /// Anyone holding the comment and salt; usually the voter
+    #[account(mut)]
+    pub revealer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Publish a vote's comment, proven by sha256(comment || salt) == comment_hash.
+/// Each vote's comment can be revealed once.
+pub fn reveal_comment(ctx: Context<RevealComment>, comment: String, salt: [u8; 32]) -> Result<()> {
+    let peer_vote = &mut ctx.accounts.peer_vote;
+
+    require!(
+        peer_vote.comment_hash != [0u8; 32],
+        VoteError::NoCommentToReveal
+    );
+
+    require!(
+        comment.len() <= CommentReveal::MAX_COMMENT_LEN,
+        VoteError::CommentTooLong
+    );
+
+    require!(
+        hashv(&[comment.as_bytes(), &salt]).to_bytes() == peer_vote.comment_hash,
+        VoteError::CommentHashMismatch
+    );
+
+    let clock = Clock::get()?;
+    let reveal = &mut ctx.accounts.comment_reveal;
+    reveal.peer_vote = peer_vote.key();
+    reveal.revealer = ctx.accounts.revealer.key();
+    reveal.comment = comment;
+    reveal.revealed_at = clock.unix_timestamp;
+    reveal.bump = ctx.bumps.comment_reveal;
+
+    peer_vote.comment_revealed = true;
+
+    msg!("Comment on vote by {} revealed by {}", peer_vote.voter, reveal.revealer);
+
+    Ok(())
+}
+
+// ==================== DISPUTE VOTE ====================
+
+#[derive(Accounts)]
+pub struct DisputeVote<'info> {
+    #[account(
+        mut,
+        seeds = [
+            PeerVote::SEED_PREFIX,
+            peer_vote.transaction_receipt.as_ref(),
+            peer_vote.voter.as_ref()
+        ],
+        bump = peer_vote.bump,
+        has_one = voted_agent @ VoteError::NotVotedAgent
+    )]
+    pub peer_vote: Account<'info, PeerVote>,
+
+    /// Tally of the voted agent; the vote is taken out while the dispute is pending
+    #[account(
+        mut,
+        seeds = [VoteTally::SEED_PREFIX, peer_vote.voted_agent.as_ref()],
+        bump = vote_tally.bump
+    )]
+    pub vote_tally: Account<'info, VoteTally>,
+
+    /// Agent the vote was cast on
+    pub voted_agent: Signer<'info>,
+}
+
+/// Flag a vote for authority review. The vote is excluded from the tally until
+/// resolve_vote_dispute settles it. A vote can be disputed once.
+pub fn dispute_vote(ctx: Context<DisputeVote>) -> Result<()> {
+    let peer_vote = &mut ctx.accounts.peer_vote;
+
+    require!(
+        peer_vote.dispute_status == VoteDisputeStatus::None,
+        VoteError::VoteAlreadyDisputed
+    );
+
+    require!(
+        peer_vote.tallied,
+        VoteError::VoteNotTallied
+    );
+
+    let tally = &mut ctx.accounts.vote_tally;
+    tally.exclude(peer_vote.vote_type, &peer_vote.quality_scores, peer_vote.vote_weight);
+    tally.disputed_votes = tally.disputed_votes.saturating_add(1);
+
+    peer_vote.dispute_status = VoteDisputeStatus::Pending;
+
+    msg!("Vote by {} on {} disputed", peer_vote.voter, peer_vote.voted_agent);
+
+    Ok(())
+}
+
+// ==================== RESOLVE VOTE DISPUTE ====================
+
+#[derive(Accounts)]
+pub struct ResolveVoteDispute<'info> {
+    #[account(
+        mut,
+        seeds = [
+            PeerVote::SEED_PREFIX,
+            peer_vote.transaction_receipt.as_ref(),
+            peer_vote.voter.as_ref()
+        ],
+        bump = peer_vote.bump
+    )]
+    pub peer_vote: Account<'info, PeerVote>,
+
+    /// Tally of the voted agent; a reinstated vote is added back
+    #[account(
+        mut,
+        seeds = [VoteTally::SEED_PREFIX, peer_vote.voted_agent.as_ref()],
+        bump = vote_tally.bump
+    )]
+    pub vote_tally: Account<'info, VoteTally>,
+
+    #[account(
+        seeds = [VoteAuthority::SEED_PREFIX],
+        bump = authority_account.bump,
+        has_one = authority @ VoteError::UnauthorizedAuthority
+    )]
+    pub authority_account: Account<'info, VoteAuthority>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Settle a pending dispute: remove the vote for good or reinstate it in the
+/// tally. Reputation stats recorded by cast_peer_vote are unchanged either way.
+pub fn resolve_vote_dispute(ctx: Context<ResolveVoteDispute>, remove_vote: bool) -> Result<()> {
+    let peer_vote = &mut ctx.accounts.peer_vote;
+
+    require!(
+        peer_vote.dispute_status == VoteDisputeStatus::Pending,
+        VoteError::VoteNotDisputed
+    );
+
+    let tally = &mut ctx.accounts.vote_tally;
+    tally.disputed_votes = tally.disputed_votes.saturating_sub(1);
+
+    if remove_vote {
+        tally.removed_votes = tally.removed_votes.saturating_add(1);
+        peer_vote.dispute_status = VoteDisputeStatus::Removed;
+    } else {
+        tally.include(peer_vote.vote_type, &peer_vote.quality_scores, peer_vote.vote_weight);
+        peer_vote.dispute_status = VoteDisputeStatus::Reinstated;
+    }
+
+    msg!(
+        "Dispute of vote by {} on {} resolved: {:?}",
+        peer_vote.voter,
+        peer_vote.voted_agent,
+        peer_vote.dispute_status
+    );
+
+    Ok(())
+}
@@ -1,10 +1,15 @@
+pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
 use anchor_lang::prelude::*;
 
+pub use constants::*;
 pub use error::*;
+pub use events::*;
+#[allow(ambiguous_glob_reexports)]
 pub use instructions::*;
 pub use state::*;
 
@@ -21,6 +26,7 @@ pub mod vote_registry {
         signature_hash: [u8; 32],
         amount: u64,
         content_type: ContentType,
+        payment_mint: Pubkey,
     ) -> Result<()> {
         instructions::create_transaction_receipt::handler(
             ctx,
@@ -28,9 +34,39 @@ pub mod vote_registry {
             signature_hash,
             amount,
             content_type,
+            payment_mint,
         )
     }
 
+    /// Create a transaction receipt backed by the payer's Ed25519 signature over the payment
+    pub fn create_verified_transaction_receipt(
+        ctx: Context<CreateVerifiedTransactionReceipt>,
+        signature: String,
+        signature_hash: [u8; 32],
+        amount: u64,
+        content_type: ContentType,
+        payment_mint: Pubkey,
+    ) -> Result<()> {
+        instructions::create_verified_transaction_receipt::handler(
+            ctx,
+            signature,
+            signature_hash,
+            amount,
+            content_type,
+            payment_mint,
+        )
+    }
+
+    /// Confirm a transaction receipt as the party that did not create it
+    pub fn confirm_receipt(ctx: Context<ReceiptConfirmation>) -> Result<()> {
+        instructions::receipt_confirmation::confirm_receipt(ctx)
+    }
+
+    /// Permanently dispute a transaction receipt as the party that did not create it
+    pub fn reject_receipt(ctx: Context<ReceiptConfirmation>) -> Result<()> {
+        instructions::receipt_confirmation::reject_receipt(ctx)
+    }
+
     /// Cast a peer vote on another agent
     pub fn cast_peer_vote(
         ctx: Context<CastPeerVote>,
@@ -48,21 +84,9 @@ pub mod vote_registry {
         )
     }
 
-    /// Rate content from an x402 transaction
-    pub fn rate_content(
-        ctx: Context<RateContent>,
-        x402_signature: String,
-        quality_rating: u8,
-        content_type: ContentType,
-        amount_paid: u64,
-    ) -> Result<()> {
-        instructions::rate_content::handler(
-            ctx,
-            x402_signature,
-            quality_rating,
-            content_type,
-            amount_paid,
-        )
+    /// Rate content from an x402 transaction, backed by its transaction receipt
+    pub fn rate_content(ctx: Context<RateContent>, quality_rating: u8) -> Result<()> {
+        instructions::rate_content::handler(ctx, quality_rating)
     }
 
     /// Endorse another agent (requires stake)
@@ -74,4 +98,194 @@ pub mod vote_registry {
     ) -> Result<()> {
         instructions::endorse_agent::handler(ctx, endorsed_agent, strength, category)
     }
+
+    /// Amend a peer vote's type and quality scores within the grace period
+    pub fn amend_peer_vote(
+        ctx: Context<AmendPeerVote>,
+        vote_type: VoteType,
+        quality_scores: QualityScores,
+    ) -> Result<()> {
+        instructions::amend_peer_vote::handler(ctx, vote_type, quality_scores)
+    }
+
+    /// Reveal a peer vote's comment by proving sha256(comment || salt) == comment_hash
+    pub fn reveal_comment(ctx: Context<RevealComment>, comment: String, salt: [u8; 32]) -> Result<()> {
+        instructions::vote_disputes::reveal_comment(ctx, comment, salt)
+    }
+
+    /// Dispute a vote as the voted agent, excluding it from the tally pending review
+    pub fn dispute_vote(ctx: Context<DisputeVote>) -> Result<()> {
+        instructions::vote_disputes::dispute_vote(ctx)
+    }
+
+    /// Resolve a vote dispute (authority only): remove the vote or reinstate it
+    pub fn resolve_vote_dispute(ctx: Context<ResolveVoteDispute>, remove_vote: bool) -> Result<()> {
+        instructions::vote_disputes::resolve_vote_dispute(ctx, remove_vote)
+    }
+
+    /// Close an unverified receipt after its voting window and refund the creator's rent
+    pub fn close_receipt(ctx: Context<CloseReceipt>) -> Result<()> {
+        instructions::close_accounts::close_receipt(ctx)
+    }
+
+    /// Close a tallied peer vote after the retention period and refund the voter's rent
+    pub fn close_peer_vote(ctx: Context<ClosePeerVote>) -> Result<()> {
+        instructions::close_accounts::close_peer_vote(ctx)
+    }
+
+    /// Initialize the vote registry authority
+    pub fn initialize_authority(ctx: Context<InitializeAuthority>) -> Result<()> {
+        instructions::initialize_authority::handler(ctx)
+    }
+
+    /// Revoke an endorsement after the minimum duration and reclaim its stake
+    pub fn revoke_endorsement(ctx: Context<RevokeEndorsement>) -> Result<()> {
+        instructions::endorsement_lifecycle::revoke_endorsement(ctx)
+    }
+
+    /// Seize an endorsement's stake after the endorsed agent was slashed (authority only)
+    pub fn slash_endorsement(ctx: Context<SlashEndorsement>) -> Result<()> {
+        instructions::endorsement_lifecycle::slash_endorsement(ctx)
+    }
+
+    /// Extend an endorsement's expiry, optionally adding stake
+    pub fn reaffirm_endorsement(ctx: Context<ReaffirmEndorsement>, extra_stake: u64) -> Result<()> {
+        instructions::endorsement_lifecycle::reaffirm_endorsement(ctx, extra_stake)
+    }
+
+    /// Retire an expired endorsement and refund its stake to the endorser (permissionless)
+    pub fn mark_endorsement_expired(ctx: Context<MarkEndorsementExpired>) -> Result<()> {
+        instructions::endorsement_lifecycle::mark_endorsement_expired(ctx)
+    }
+
+    /// Create the vote config with default parameters (authority only)
+    pub fn initialize_vote_config(ctx: Context<InitializeVoteConfig>) -> Result<()> {
+        instructions::config::initialize_vote_config(ctx)
+    }
+
+    /// Set the base and per-strength-point endorsement stake (authority only)
+    pub fn update_endorsement_stake(
+        ctx: Context<UpdateVoteConfig>,
+        base_stake: u64,
+        stake_per_point: u64,
+    ) -> Result<()> {
+        instructions::config::update_endorsement_stake(ctx, base_stake, stake_per_point)
+    }
+
+    /// Set how many active endorsements one agent may give (authority only)
+    pub fn update_max_active_endorsements(
+        ctx: Context<UpdateVoteConfig>,
+        max_active_endorsements: u32,
+    ) -> Result<()> {
+        instructions::config::update_max_active_endorsements(ctx, max_active_endorsements)
+    }
+
+    /// Set how long endorsements stay current before re-affirmation (authority only)
+    pub fn update_endorsement_validity(
+        ctx: Context<UpdateVoteConfig>,
+        validity_seconds: i64,
+    ) -> Result<()> {
+        instructions::config::update_endorsement_validity(ctx, validity_seconds)
+    }
+
+    /// Set how long endorsement weight takes to decay to zero, or 0 for no decay (authority only)
+    pub fn update_endorsement_decay(ctx: Context<UpdateVoteConfig>, decay_seconds: i64) -> Result<()> {
+        instructions::config::update_endorsement_decay(ctx, decay_seconds)
+    }
+
+    /// Require verified receipts for peer votes (authority only)
+    pub fn update_require_verified_receipts(
+        ctx: Context<UpdateVoteConfig>,
+        require_verified_receipts: bool,
+    ) -> Result<()> {
+        instructions::config::update_require_verified_receipts(ctx, require_verified_receipts)
+    }
+
+    /// Require counterparty-confirmed receipts for peer votes (authority only)
+    pub fn update_require_confirmed_receipts(
+        ctx: Context<UpdateVoteConfig>,
+        require_confirmed_receipts: bool,
+    ) -> Result<()> {
+        instructions::config::update_require_confirmed_receipts(ctx, require_confirmed_receipts)
+    }
+
+    /// Register the decimals of an SPL payment mint (authority only)
+    pub fn set_payment_mint_decimals(
+        ctx: Context<UpdateVoteConfig>,
+        mint: Pubkey,
+        decimals: u8,
+    ) -> Result<()> {
+        instructions::config::set_payment_mint_decimals(ctx, mint, decimals)
+    }
+
+    /// Set the per voter/agent pair vote cooldown and window cap (authority only)
+    pub fn update_pair_vote_limits(
+        ctx: Context<UpdateVoteConfig>,
+        cooldown_seconds: i64,
+        window_seconds: i64,
+        max_votes_per_window: u32,
+    ) -> Result<()> {
+        instructions::config::update_pair_vote_limits(ctx, cooldown_seconds, window_seconds, max_votes_per_window)
+    }
+
+    /// Set the reputation needed to vote, endorse and rate content (authority only)
+    pub fn update_reputation_thresholds(
+        ctx: Context<UpdateVoteConfig>,
+        min_voter_reputation: u16,
+        min_endorser_reputation: u16,
+        min_rater_reputation: u16,
+    ) -> Result<()> {
+        instructions::config::update_reputation_thresholds(
+            ctx,
+            min_voter_reputation,
+            min_endorser_reputation,
+            min_rater_reputation,
+        )
+    }
+
+    /// Shorten the window after a transaction in which its receipt can be voted on (authority only)
+    pub fn update_voting_window(ctx: Context<UpdateVoteConfig>, voting_window_seconds: i64) -> Result<()> {
+        instructions::config::update_voting_window(ctx, voting_window_seconds)
+    }
+
+    /// Set the transaction amount tiers peer votes are weighted by, or flat 1.0x weights (authority only)
+    pub fn update_vote_weighting(
+        ctx: Context<UpdateVoteConfig>,
+        flat_vote_weight: bool,
+        vote_weight_tiers: VoteWeightTiers,
+    ) -> Result<()> {
+        instructions::config::update_vote_weighting(ctx, flat_vote_weight, vote_weight_tiers)
+    }
+
+    /// Toggle the shared asset and shared delegate vote checks and set the new voter rule (authority only)
+    pub fn update_sybil_guards(
+        ctx: Context<UpdateVoteConfig>,
+        reject_shared_asset_votes: bool,
+        reject_shared_delegate_votes: bool,
+        new_voter_age_seconds: i64,
+        new_voter_weight_bps: u16,
+    ) -> Result<()> {
+        instructions::config::update_sybil_guards(
+            ctx,
+            reject_shared_asset_votes,
+            reject_shared_delegate_votes,
+            new_voter_age_seconds,
+            new_voter_weight_bps,
+        )
+    }
+
+    /// Return an agent's aggregated vote totals via return data
+    pub fn get_vote_tally(ctx: Context<GetVoteTally>) -> Result<VoteTally> {
+        instructions::get_vote_tally::handler(ctx)
+    }
+
+    /// Return an agent's aggregated content rating stats via return data
+    pub fn get_content_stats(ctx: Context<GetContentStats>) -> Result<ContentRatingStats> {
+        instructions::get_content_stats::handler(ctx)
+    }
+
+    /// Return an agent's current, age-decayed endorsement weight via return data
+    pub fn get_endorsement_weight(ctx: Context<GetEndorsementWeight>) -> Result<u64> {
+        instructions::get_endorsement_weight::handler(ctx)
+    }
 }
@@ -41,13 +41,34 @@ pub struct AgentEndorsement {
 
     /// PDA bump
     pub bump: u8,
+
+    /// Whether the stake was seized because the endorsed agent was slashed
+    pub is_slashed: bool,
+
+    /// Timestamp of the slash (0 if never slashed)
+    pub slashed_at: i64,
+
+    /// VoteConfig base stake in effect when the endorsement was made
+    pub stake_base: u64,
+
+    /// VoteConfig per-strength-point stake in effect when the endorsement was made
+    pub stake_per_point: u64,
+
+    /// Timestamp after which the endorsement no longer counts unless re-affirmed
+    pub expires_at: i64,
+
+    /// Static weight: strength * endorser_reputation_snapshot, before age decay
+    pub weight: u32,
+
+    /// Timestamp of the endorsement or its latest re-affirmation; age decay starts here
+    pub affirmed_at: i64,
 }
 
 impl AgentEndorsement {
     /// Seed prefix for PDA derivation
     pub const SEED_PREFIX: &'static [u8] = b"endorsement";
 
-    /// Minimum stake required (0.01 SOL)
+    /// Minimum stake required (0.01 SOL); the whole stake when no VoteConfig is supplied
     pub const MIN_STAKE: u64 = 10_000_000; // 0.01 SOL in lamports
 
     /// Calculate space for rent
@@ -60,5 +81,54 @@ impl AgentEndorsement {
         2 + // endorser_reputation_snapshot
         8 + // stake_amount
         1 + // is_active
-        1; // bump
+        1 + // bump
+        1 + // is_slashed
+        8 + // slashed_at
+        8 + // stake_base
+        8 + // stake_per_point
+        8 + // expires_at
+        4 + // weight
+        8; // affirmed_at
+
+    /// Minimum time an endorsement must stand before it can be revoked (30 days)
+    pub const MIN_ENDORSEMENT_DURATION_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+    /// Stake required for an endorsement of the given strength (0-100)
+    pub fn required_stake(stake_base: u64, stake_per_point: u64, strength: u8) -> u64 {
+        stake_per_point
+            .saturating_mul(strength.into())
+            .saturating_add(stake_base)
+    }
+
+    /// Whether the endorser may revoke the endorsement at `now`
+    pub fn is_revocable(&self, now: i64) -> bool {
+        now.saturating_sub(self.timestamp) >= Self::MIN_ENDORSEMENT_DURATION_SECONDS
+    }
+
+    /// Whether the endorsement is active and not yet past expires_at
+    pub fn is_current(&self, now: i64) -> bool {
+        self.is_active && now < self.expires_at
+    }
+
+    /// Static weight of an endorsement of the given strength by an endorser
+    /// with the given reputation (at most 100 * 1000)
+    pub fn static_weight(strength: u8, endorser_reputation: u16) -> u32 {
+        u32::from(strength) * u32::from(endorser_reputation)
+    }
+
+    /// Weight at `now`: the static weight scaled down linearly with the time
+    /// since affirmed_at, reaching zero after `decay_seconds` (no decay if 0);
+    /// zero once the endorsement is no longer current
+    pub fn effective_weight(&self, now: i64, decay_seconds: i64) -> u64 {
+        if !self.is_current(now) {
+            return 0;
+        }
+        if decay_seconds <= 0 {
+            return self.weight.into();
+        }
+
+        let age = now.saturating_sub(self.affirmed_at).max(0);
+        let remaining = decay_seconds.saturating_sub(age).max(0);
+        (u128::from(self.weight) * remaining as u128 / decay_seconds as u128) as u64
+    }
 }
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+/// Comment Reveal Account
+/// Plaintext of a peer vote's comment, proven against its comment_hash
+/// PDA seeds: ["comment_reveal", peer_vote]
+#[account]
+#[derive(InitSpace)]
+pub struct CommentReveal {
+    /// Peer vote the comment belongs to
+    pub peer_vote: Pubkey,
+
+    /// Whoever revealed the comment (paid the rent)
+    pub revealer: Pubkey,
+
+    /// Comment text; sha256(comment || salt) matches the vote's comment_hash
+    #[max_len(280)]
+    pub comment: String,
+
+    /// Timestamp of the reveal
+    pub revealed_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl CommentReveal {
+    /// Seed prefix for PDA derivation
+    pub const SEED_PREFIX: &'static [u8] = b"comment_reveal";
+
+    /// Maximum comment length in bytes
+    pub const MAX_COMMENT_LEN: usize = 280;
+
+    /// Calculate space for rent
+    pub const LEN: usize = 8 + // discriminator
+        32 + // peer_vote
+        32 + // revealer
+        4 + 280 + // comment (String with max 280 bytes)
+        8 + // revealed_at
+        1; // bump
+}
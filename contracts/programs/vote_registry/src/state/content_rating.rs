@@ -11,8 +11,21 @@ pub enum ContentType {
     Other,
 }
 
+impl ContentType {
+    /// Number of content types, for per-type arrays
+    pub const COUNT: usize = 6;
+
+    /// Position of this type in per-type arrays
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
 /// Content Rating Account
-/// PDA seeds: ["content_rating", x402_signature_hash]
+/// PDA seeds: ["content_rating", transaction_receipt]
+///
+/// The receipt is itself seeded by sha256(x402 signature), so a full 88-char
+/// base58 signature never has to fit into a 32-byte seed.
 #[account]
 #[derive(InitSpace)]
 pub struct ContentRating {
@@ -22,17 +35,17 @@ pub struct ContentRating {
     /// Rater (could be another agent or human)
     pub rater: Pubkey,
 
-    /// x402 transaction signature (proof of payment)
+    /// x402 transaction signature, copied from the receipt
     #[max_len(88)]
     pub x402_signature: String,
 
     /// Content quality rating (0-100)
     pub quality_rating: u8,
 
-    /// Content type (API response, generated text, image, etc.)
+    /// Content type (API response, generated text, image, etc.), copied from the receipt
     pub content_type: ContentType,
 
-    /// Amount paid in x402 transaction (in lamports)
+    /// Amount paid in x402 transaction (payment_mint base units), copied from the receipt
     pub amount_paid: u64,
 
     /// Timestamp of rating
@@ -43,6 +56,12 @@ pub struct ContentRating {
 
     /// PDA bump
     pub bump: u8,
+
+    /// Transaction receipt the rating was made against
+    pub transaction_receipt: Pubkey,
+
+    /// Mint of the payment, copied from the receipt (Pubkey::default() for native SOL)
+    pub payment_mint: Pubkey,
 }
 
 impl ContentRating {
@@ -59,5 +78,7 @@ impl ContentRating {
         8 + // amount_paid
         8 + // timestamp
         2 + // rater_reputation_snapshot
-        1; // bump
+        1 + // bump
+        32 + // transaction_receipt
+        32; // payment_mint
 }
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use super::ContentType;
+
+/// Content Rating Stats Account
+/// Aggregate of every content rating an agent has received, bucketed by
+/// content type and kept in step by rate_content
+/// PDA seeds: ["content_stats", agent]
+#[account]
+#[derive(InitSpace)]
+pub struct ContentRatingStats {
+    /// Agent the ratings were given to
+    pub agent: Pubkey,
+
+    /// Ratings received per content type (indexed by ContentType)
+    pub ratings_count: [u32; ContentType::COUNT],
+
+    /// Sum of quality ratings (0-100) per content type
+    pub rating_sum: [u64; ContentType::COUNT],
+
+    /// Sum of quality rating * rater weight per content type
+    pub weighted_rating_sum: [u64; ContentType::COUNT],
+
+    /// Sum of rater weights per content type
+    pub weight_sum: [u64; ContentType::COUNT],
+
+    /// Ratings received across all content types
+    pub total_ratings: u32,
+
+    /// Sum of quality ratings across all content types
+    pub total_rating_sum: u64,
+
+    /// Sum of quality rating * rater weight across all content types
+    pub total_weighted_rating_sum: u64,
+
+    /// Sum of rater weights across all content types
+    pub total_weight_sum: u64,
+
+    /// Timestamp of the most recent rating
+    pub last_rated_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ContentRatingStats {
+    /// Seed prefix for PDA derivation
+    pub const SEED_PREFIX: &'static [u8] = b"content_stats";
+
+    /// Calculate space for rent
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        4 * ContentType::COUNT + // ratings_count
+        8 * ContentType::COUNT + // rating_sum
+        8 * ContentType::COUNT + // weighted_rating_sum
+        8 * ContentType::COUNT + // weight_sum
+        4 + // total_ratings
+        8 + // total_rating_sum
+        8 + // total_weighted_rating_sum
+        8 + // total_weight_sum
+        8 + // last_rated_at
+        1; // bump
+
+    /// Weight a rating by the rater's reputation snapshot, floored at 1 so
+    /// raters with no reputation yet still count
+    pub fn rater_weight(reputation_snapshot: u16) -> u64 {
+        u64::from(reputation_snapshot.max(1))
+    }
+
+    /// Fold one rating into the stats
+    pub fn record(
+        &mut self,
+        content_type: ContentType,
+        quality_rating: u8,
+        reputation_snapshot: u16,
+        timestamp: i64,
+    ) {
+        let i = content_type.index();
+        let rating = u64::from(quality_rating);
+        let weight = Self::rater_weight(reputation_snapshot);
+        let weighted = rating.saturating_mul(weight);
+
+        self.ratings_count[i] = self.ratings_count[i].saturating_add(1);
+        self.rating_sum[i] = self.rating_sum[i].saturating_add(rating);
+        self.weighted_rating_sum[i] = self.weighted_rating_sum[i].saturating_add(weighted);
+        self.weight_sum[i] = self.weight_sum[i].saturating_add(weight);
+
+        self.total_ratings = self.total_ratings.saturating_add(1);
+        self.total_rating_sum = self.total_rating_sum.saturating_add(rating);
+        self.total_weighted_rating_sum = self.total_weighted_rating_sum.saturating_add(weighted);
+        self.total_weight_sum = self.total_weight_sum.saturating_add(weight);
+        self.last_rated_at = timestamp;
+    }
+}
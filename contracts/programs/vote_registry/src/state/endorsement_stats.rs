@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+/// Endorsement Stats Account
+/// Active endorsements an agent has given and received, kept in step by
+/// endorse_agent, revoke_endorsement, slash_endorsement and
+/// mark_endorsement_expired. Endorsements past expires_at keep counting until
+/// someone calls mark_endorsement_expired.
+/// PDA seeds: ["endorsement_stats", agent]
+#[account]
+#[derive(InitSpace)]
+pub struct EndorsementStats {
+    /// Agent the counters belong to
+    pub agent: Pubkey,
+
+    /// Active endorsements this agent has given
+    pub endorsements_given: u32,
+
+    /// Active endorsements this agent has received
+    pub endorsements_received: u32,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl EndorsementStats {
+    /// Seed prefix for PDA derivation
+    pub const SEED_PREFIX: &'static [u8] = b"endorsement_stats";
+
+    /// Calculate space for rent
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        4 + // endorsements_given
+        4 + // endorsements_received
+        1; // bump
+}
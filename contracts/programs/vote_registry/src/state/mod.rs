@@ -2,8 +2,22 @@ pub mod peer_vote;
 pub mod content_rating;
 pub mod agent_endorsement;
 pub mod transaction_receipt;
+pub mod vote_tally;
+pub mod vote_authority;
+pub mod vote_config;
+pub mod endorsement_stats;
+pub mod vote_pair_state;
+pub mod content_rating_stats;
+pub mod comment_reveal;
 
 pub use peer_vote::*;
 pub use content_rating::*;
 pub use agent_endorsement::*;
 pub use transaction_receipt::*;
+pub use vote_tally::*;
+pub use vote_authority::*;
+pub use vote_config::*;
+pub use endorsement_stats::*;
+pub use vote_pair_state::*;
+pub use content_rating_stats::*;
+pub use comment_reveal::*;
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use super::{VoteConfig, VoteWeightTiers};
 
 /// Vote type for peer voting
 #[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
@@ -8,6 +9,15 @@ pub enum VoteType {
     Neutral,     // Mixed/neutral experience
 }
 
+/// Where a vote stands in the dispute process
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum VoteDisputeStatus {
+    None,        // Never disputed
+    Pending,     // Disputed by the voted agent, awaiting authority review
+    Removed,     // Dispute upheld; the vote no longer counts
+    Reinstated,  // Dispute rejected; the vote counts again
+}
+
 /// Quality scores for peer voting (0-100 each)
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
 pub struct QualityScores {
@@ -17,8 +27,20 @@ pub struct QualityScores {
     pub professionalism: u8,      // 0-100: Professional behavior?
 }
 
+impl QualityScores {
+    /// Mean of the four scores, rounded down
+    pub fn average(&self) -> u8 {
+        ((self.response_quality as u16
+            + self.response_speed as u16
+            + self.accuracy as u16
+            + self.professionalism as u16)
+            / 4) as u8
+    }
+}
+
 /// Peer Vote Account
-/// PDA seeds: ["peer_vote", transaction_receipt.key()]
+/// PDA seeds: ["peer_vote", transaction_receipt.key(), voter]
+/// One vote per party per receipt: the payer rates the recipient and vice versa
 #[account]
 #[derive(InitSpace)]
 pub struct PeerVote {
@@ -51,6 +73,26 @@ pub struct PeerVote {
 
     /// PDA bump
     pub bump: u8,
+
+    /// Number of times the voter has amended this vote
+    pub amended_count: u16,
+
+    /// Timestamp of the latest amendment (0 if never amended)
+    pub amended_at: i64,
+
+    /// Whether the vote has been folded into the voted agent's VoteTally;
+    /// only tallied votes may be closed
+    pub tallied: bool,
+
+    /// Whether the comment behind comment_hash has been revealed in a CommentReveal
+    pub comment_revealed: bool,
+
+    /// Dispute state; pending and removed votes are excluded from the VoteTally
+    pub dispute_status: VoteDisputeStatus,
+
+    /// Whether the voter's identity was new when voting, so vote_weight was reduced
+    /// by VoteConfig::new_voter_weight_bps
+    pub new_voter: bool,
 }
 
 impl PeerVote {
@@ -68,22 +110,66 @@ impl PeerVote {
         2 + // voter_reputation_snapshot
         32 + // transaction_receipt
         2 + // vote_weight
-        1; // bump
+        1 + // bump
+        2 + // amended_count
+        8 + // amended_at
+        1 + // tallied
+        1 + // comment_revealed
+        1 + // dispute_status (enum with 4 variants)
+        1; // new_voter
+
+    /// Grace period after `timestamp` during which the voter may amend the vote (48 hours)
+    pub const AMENDMENT_WINDOW_SECONDS: i64 = 48 * 60 * 60;
+
+    /// Time after `timestamp` the vote account is kept before the voter may
+    /// close it; well past the amendment window (30 days)
+    pub const RETENTION_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+    /// Whether the vote can still be amended at `now`
+    pub fn is_amendable(&self, now: i64) -> bool {
+        now.saturating_sub(self.timestamp) <= Self::AMENDMENT_WINDOW_SECONDS
+    }
+
+    /// Whether the vote currently counts in the voted agent's VoteTally
+    pub fn is_counted(&self) -> bool {
+        self.tallied
+            && !matches!(self.dispute_status, VoteDisputeStatus::Pending | VoteDisputeStatus::Removed)
+    }
+
+    /// Whether the retention period has passed at `now`
+    pub fn is_retention_over(&self, now: i64) -> bool {
+        now.saturating_sub(self.timestamp) >= Self::RETENTION_SECONDS
+    }
+
+    /// Vote weight of a 1.0x vote
+    pub const BASE_VOTE_WEIGHT: u16 = 100;
 
     /// Calculate vote weight based on transaction amount
     ///
     /// x402 Reality: Payments range from $0.001 (single API call) to ~$1.00 (extended service)
     /// Average payment: ~$0.078 (7.8 cents)
     ///
-    /// Strategy: Equal weight for all transactions
-    /// - Every transaction = 1.0x weight (100)
-    /// - Reputation reflects service quality, not payment size
-    /// - Micropayments are the norm, not the exception
+    /// Strategy: Small, bounded tiers instead of proportional weight
+    /// - By default 1.0x below 0.01 SOL, 1.25x up to 0.1 SOL and 1.5x above
+    /// - Tiers are set in the VoteConfig in basis points and capped at 3.0x
+    /// - Deployments that prefer one transaction, one vote set flat_vote_weight
     ///
-    /// Returns constant weight of 100 (1.0x) for all transactions
-    pub fn calculate_vote_weight(_transaction_amount: u64) -> u16 {
-        // Constant weight: 1 transaction = 1 vote
-        // Amount is recorded but doesn't affect vote power
-        100
+    /// `normalized_amount` is TransactionReceipt::normalized_amount; amounts in
+    /// an unregistered mint fall in the first tier. Returns 100 = 1.0x.
+    pub fn calculate_vote_weight(normalized_amount: Option<u64>, config: Option<&VoteConfig>) -> u16 {
+        if config.is_some_and(|config| config.flat_vote_weight) {
+            return Self::BASE_VOTE_WEIGHT;
+        }
+
+        let tiers = config.map_or(VoteWeightTiers::DEFAULT, |config| config.vote_weight_tiers);
+        let weight_bps = tiers.weight_bps(normalized_amount.unwrap_or(0));
+        weight_bps / (10_000 / Self::BASE_VOTE_WEIGHT)
+    }
+
+    /// Scale a vote weight down to `weight_bps` (at most 10_000) for a new
+    /// voter; a vote never weighs less than 1
+    pub fn new_voter_weight(vote_weight: u16, weight_bps: u16) -> u16 {
+        let scaled = u32::from(vote_weight) * u32::from(weight_bps.min(10_000)) / 10_000;
+        (scaled as u16).max(1)
     }
 }
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use super::ContentType;
+use super::{ContentType, VoteConfig};
 
 /// Transaction Receipt Account
 /// Created after every x402 payment to enable vote verification
@@ -17,7 +17,7 @@ pub struct TransactionReceipt {
     /// Recipient (service provider)
     pub recipient: Pubkey,
 
-    /// Amount paid (in lamports)
+    /// Amount paid, in the smallest units of payment_mint (lamports for native SOL)
     pub amount: u64,
 
     /// Timestamp of transaction
@@ -26,11 +26,29 @@ pub struct TransactionReceipt {
     /// Content type delivered
     pub content_type: ContentType,
 
-    /// Whether a vote has been cast using this receipt
-    pub vote_cast: bool,
+    /// Whether the payer has voted on the recipient using this receipt
+    pub payer_voted: bool,
+
+    /// Whether the recipient has voted on the payer using this receipt
+    pub recipient_voted: bool,
 
     /// PDA bump
     pub bump: u8,
+
+    /// Whether the payer signed the payment message (create_verified_transaction_receipt)
+    pub verified: bool,
+
+    /// Party that created the receipt (payer or recipient)
+    pub creator: Pubkey,
+
+    /// Whether the non-creator party confirmed the receipt (implied when verified)
+    pub counterparty_confirmed: bool,
+
+    /// Whether the non-creator party rejected the receipt; blocks votes permanently
+    pub is_disputed: bool,
+
+    /// Mint the payment was made in; NATIVE_MINT (Pubkey::default()) for native SOL
+    pub payment_mint: Pubkey,
 }
 
 impl TransactionReceipt {
@@ -42,6 +60,93 @@ impl TransactionReceipt {
     /// Any payment amount enables voting to support the micropayment use case
     pub const VOTING_WINDOW_SECONDS: i64 = 30 * 24 * 60 * 60;
 
+    /// payment_mint value for payments in native SOL
+    pub const NATIVE_MINT: Pubkey = Pubkey::new_from_array([0; 32]);
+
+    /// Decimals of native SOL (lamports per SOL = 10^9)
+    pub const NATIVE_DECIMALS: u8 = 9;
+
+    /// Domain prefix of the payment message a payer signs to verify a receipt
+    pub const PAYMENT_MESSAGE_DOMAIN: &'static [u8] = b"ghostspeak:x402-receipt:v2";
+
+    /// Canonical payment message:
+    /// domain || payer || recipient || payment_mint || amount (u64 LE) || nonce.
+    /// The receipt's signature_hash is the nonce, so one signed message backs one receipt.
+    pub fn payment_message(
+        payer: &Pubkey,
+        recipient: &Pubkey,
+        payment_mint: &Pubkey,
+        amount: u64,
+        nonce: &[u8; 32],
+    ) -> Vec<u8> {
+        let mut message = Vec::with_capacity(Self::PAYMENT_MESSAGE_DOMAIN.len() + 32 + 32 + 32 + 8 + 32);
+        message.extend_from_slice(Self::PAYMENT_MESSAGE_DOMAIN);
+        message.extend_from_slice(payer.as_ref());
+        message.extend_from_slice(recipient.as_ref());
+        message.extend_from_slice(payment_mint.as_ref());
+        message.extend_from_slice(&amount.to_le_bytes());
+        message.extend_from_slice(nonce);
+        message
+    }
+
+    /// Whether the voting window has fully passed, so no further votes can use the receipt
+    pub fn is_voting_closed(&self, now: i64) -> bool {
+        now.saturating_sub(self.timestamp) > Self::VOTING_WINDOW_SECONDS
+    }
+
+    /// Whether the payment was made in native SOL
+    pub fn is_native(&self) -> bool {
+        self.payment_mint == Self::NATIVE_MINT
+    }
+
+    /// Decimals of the payment mint: fixed for native SOL, otherwise the scale
+    /// registered in the VoteConfig (None if the mint is not registered)
+    pub fn payment_decimals(&self, config: Option<&VoteConfig>) -> Option<u8> {
+        if self.is_native() {
+            Some(Self::NATIVE_DECIMALS)
+        } else {
+            config.and_then(|config| config.mint_decimals(&self.payment_mint))
+        }
+    }
+
+    /// Amount rescaled to NATIVE_DECIMALS so amounts in different mints share one
+    /// scale (at face value, without price conversion); None if the decimals are unknown
+    pub fn normalized_amount(&self, config: Option<&VoteConfig>) -> Option<u64> {
+        let decimals = self.payment_decimals(config)?;
+        Some(if decimals <= Self::NATIVE_DECIMALS {
+            self.amount.saturating_mul(10u64.pow((Self::NATIVE_DECIMALS - decimals).into()))
+        } else {
+            self.amount / 10u64.pow((decimals - Self::NATIVE_DECIMALS).into())
+        })
+    }
+
+    /// Whether `voter` (payer or recipient) has already voted using this receipt
+    pub fn has_voted(&self, voter: Pubkey) -> bool {
+        if voter == self.payer {
+            self.payer_voted
+        } else {
+            self.recipient_voted
+        }
+    }
+
+    /// The party that did not create the receipt and so must confirm or reject it
+    pub fn counterparty(&self) -> Pubkey {
+        if self.creator == self.payer {
+            self.recipient
+        } else {
+            self.payer
+        }
+    }
+
+    /// Record that `voter` (payer or recipient) has voted using this receipt
+    pub fn mark_voted(&mut self, voter: Pubkey) {
+        if voter == self.payer {
+            self.payer_voted = true;
+        } else {
+            self.recipient_voted = true;
+        }
+    }
+
     /// Calculate space for rent
     pub const LEN: usize = 8 + // discriminator
         4 + 88 + // signature (String with max 88 chars)
@@ -50,6 +155,12 @@ impl TransactionReceipt {
         8 + // amount
         8 + // timestamp
         1 + // content_type (enum)
-        1 + // vote_cast
-        1; // bump
+        1 + // payer_voted
+        1 + // recipient_voted
+        1 + // bump
+        1 + // verified
+        32 + // creator
+        1 + // counterparty_confirmed
+        1 + // is_disputed
+        32; // payment_mint
 }
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+/// Vote registry authority account
+/// PDA seeds: ["authority"]
+#[account]
+#[derive(InitSpace)]
+pub struct VoteAuthority {
+    /// The authority wallet that can slash endorsements
+    pub authority: Pubkey,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl VoteAuthority {
+    /// Seed prefix for PDA derivation
+    pub const SEED_PREFIX: &'static [u8] = b"authority";
+
+    /// Calculate space for rent
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        1; // bump
+}
@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use crate::constants::{DEFAULT_VOTE_WEIGHT_THRESHOLDS, DEFAULT_VOTE_WEIGHT_TIER_BPS, MAX_VOTE_WEIGHT_BPS};
+
+/// Tunable vote registry parameters, managed by the vote authority
+/// PDA seeds: ["config"]
+#[account]
+#[derive(InitSpace)]
+pub struct VoteConfig {
+    /// Fixed lamports every endorsement locks
+    pub endorsement_base_stake: u64,
+
+    /// Lamports locked per point of endorsement strength (0-100)
+    pub endorsement_stake_per_point: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+
+    /// Cap on the active endorsements one agent may give
+    pub max_active_endorsements: u32,
+
+    /// Seconds an endorsement stays current after it is made or re-affirmed
+    pub endorsement_validity_seconds: i64,
+
+    /// Whether cast_peer_vote only accepts payer-signed (verified) receipts
+    pub require_verified_receipts: bool,
+
+    /// Whether cast_peer_vote only accepts receipts the counterparty confirmed
+    pub require_confirmed_receipts: bool,
+
+    /// Decimals of the SPL mints receipts may be denominated in (native SOL is implicit)
+    #[max_len(8)]
+    pub payment_mints: Vec<PaymentMintScale>,
+
+    /// Minimum seconds between two votes by the same voter on the same agent (0 disables)
+    pub pair_vote_cooldown_seconds: i64,
+
+    /// Window over which votes by one voter on one agent are capped
+    pub pair_vote_window_seconds: i64,
+
+    /// Votes one voter may cast on one agent per window
+    pub max_pair_votes_per_window: u32,
+
+    /// Reputation a voter needs to cast a peer vote
+    pub min_voter_reputation: u16,
+
+    /// Reputation an endorser needs to endorse an agent
+    pub min_endorser_reputation: u16,
+
+    /// Reputation a rater needs to rate content
+    pub min_rater_reputation: u16,
+
+    /// Seconds after a receipt during which it can be voted on; at most
+    /// TransactionReceipt::VOTING_WINDOW_SECONDS so close_receipt stays safe
+    pub voting_window_seconds: i64,
+
+    /// Whether every peer vote weighs 1.0x regardless of the transaction amount
+    pub flat_vote_weight: bool,
+
+    /// Amount tiers peer votes are weighted by unless flat_vote_weight is set
+    pub vote_weight_tiers: VoteWeightTiers,
+
+    /// Whether cast_peer_vote rejects votes between identities linked to the same Core asset
+    pub reject_shared_asset_votes: bool,
+
+    /// Whether cast_peer_vote rejects votes between identities sharing a delegate,
+    /// or where one party is the other's delegate
+    pub reject_shared_delegate_votes: bool,
+
+    /// Identity age below which a voter counts as new (0 disables the rule)
+    pub new_voter_age_seconds: i64,
+
+    /// Share of the vote weight a new voter contributes, in basis points
+    pub new_voter_weight_bps: u16,
+
+    /// Age at which an endorsement's weight has decayed linearly to zero (0 disables decay)
+    pub endorsement_decay_seconds: i64,
+}
+
+/// Scale of an SPL payment mint, used to normalize receipt amounts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct PaymentMintScale {
+    /// SPL token mint
+    pub mint: Pubkey,
+
+    /// Mint decimals: one whole token is 10^decimals base units
+    pub decimals: u8,
+}
+
+/// Transaction amount tiers a peer vote's weight is taken from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct VoteWeightTiers {
+    /// Normalized amounts (9 decimals) at which the second and third tier start
+    pub thresholds: [u64; 2],
+
+    /// Weight of each tier in basis points (10_000 = 1.0x), in whole hundredths
+    pub weights_bps: [u16; 3],
+}
+
+impl VoteWeightTiers {
+    /// 1.0x below 0.01 SOL, 1.25x up to 0.1 SOL, 1.5x from 0.1 SOL
+    pub const DEFAULT: Self = Self {
+        thresholds: DEFAULT_VOTE_WEIGHT_THRESHOLDS,
+        weights_bps: DEFAULT_VOTE_WEIGHT_TIER_BPS,
+    };
+
+    /// Thresholds ascend from above zero, and every weight is a positive whole
+    /// hundredth no greater than MAX_VOTE_WEIGHT_BPS
+    pub fn is_valid(&self) -> bool {
+        self.thresholds[0] > 0
+            && self.thresholds[0] < self.thresholds[1]
+            && self.weights_bps.iter().all(|&bps| bps > 0 && bps <= MAX_VOTE_WEIGHT_BPS && bps % 100 == 0)
+    }
+
+    /// Weight in basis points of a normalized amount; each threshold belongs
+    /// to the tier it starts, and the result never exceeds MAX_VOTE_WEIGHT_BPS
+    pub fn weight_bps(&self, normalized_amount: u64) -> u16 {
+        let tier = self.thresholds.iter().filter(|&&threshold| normalized_amount >= threshold).count();
+        self.weights_bps[tier].min(MAX_VOTE_WEIGHT_BPS)
+    }
+}
+
+impl VoteConfig {
+    /// Seed prefix for PDA derivation
+    pub const SEED_PREFIX: &'static [u8] = b"config";
+
+    /// Maximum number of registered payment mints
+    pub const MAX_PAYMENT_MINTS: usize = 8;
+
+    /// Largest accepted mint decimals (10^18 still fits in a u64)
+    pub const MAX_MINT_DECIMALS: u8 = 18;
+
+    /// Calculate space for rent
+    pub const LEN: usize = 8 + // discriminator
+        8 + // endorsement_base_stake
+        8 + // endorsement_stake_per_point
+        1 + // bump
+        4 + // max_active_endorsements
+        8 + // endorsement_validity_seconds
+        1 + // require_verified_receipts
+        1 + // require_confirmed_receipts
+        4 + Self::MAX_PAYMENT_MINTS * (32 + 1) + // payment_mints (Vec of mint + decimals)
+        8 + // pair_vote_cooldown_seconds
+        8 + // pair_vote_window_seconds
+        4 + // max_pair_votes_per_window
+        2 + // min_voter_reputation
+        2 + // min_endorser_reputation
+        2 + // min_rater_reputation
+        8 + // voting_window_seconds
+        1 + // flat_vote_weight
+        2 * 8 + 3 * 2 + // vote_weight_tiers (2 thresholds + 3 tier weights)
+        1 + // reject_shared_asset_votes
+        1 + // reject_shared_delegate_votes
+        8 + // new_voter_age_seconds
+        2 + // new_voter_weight_bps
+        8; // endorsement_decay_seconds
+
+    /// Decimals registered for an SPL payment mint
+    pub fn mint_decimals(&self, mint: &Pubkey) -> Option<u8> {
+        self.payment_mints
+            .iter()
+            .find(|scale| scale.mint == *mint)
+            .map(|scale| scale.decimals)
+    }
+
+    /// Whether an identity registered at `registration_timestamp` is still new at `now`
+    pub fn is_new_voter(&self, registration_timestamp: i64, now: i64) -> bool {
+        self.new_voter_age_seconds > 0
+            && now.saturating_sub(registration_timestamp) < self.new_voter_age_seconds
+    }
+
+    /// Read the (possibly uninitialized) config PDA; None until it is initialized
+    pub fn load(info: &AccountInfo) -> Result<Option<VoteConfig>> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(None);
+        }
+        let data = info.try_borrow_data()?;
+        Ok(Some(VoteConfig::try_deserialize(&mut &data[..])?))
+    }
+}
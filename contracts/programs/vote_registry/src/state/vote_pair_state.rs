@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+/// Vote Pair State Account
+/// Rate-limits how often one agent can vote on another, so two colluding
+/// agents cannot trade tiny payments to inflate each other's stats
+/// PDA seeds: ["vote_pair", voter, voted_agent]
+#[account]
+#[derive(InitSpace)]
+pub struct VotePairState {
+    /// Agent casting the votes
+    pub voter: Pubkey,
+
+    /// Agent the votes are cast on
+    pub voted_agent: Pubkey,
+
+    /// Timestamp of the voter's most recent vote on voted_agent
+    pub last_vote_at: i64,
+
+    /// Start of the current counting window
+    pub window_start: i64,
+
+    /// Votes cast since window_start
+    pub votes_in_window: u32,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl VotePairState {
+    /// Seed prefix for PDA derivation
+    pub const SEED_PREFIX: &'static [u8] = b"vote_pair";
+
+    /// Calculate space for rent
+    pub const LEN: usize = 8 + // discriminator
+        32 + // voter
+        32 + // voted_agent
+        8 + // last_vote_at
+        8 + // window_start
+        4 + // votes_in_window
+        1; // bump
+
+    /// Whether the previous vote was less than `cooldown_seconds` ago
+    pub fn in_cooldown(&self, now: i64, cooldown_seconds: i64) -> bool {
+        now - self.last_vote_at < cooldown_seconds
+    }
+
+    /// Votes counted towards the cap at `now`; zero once the window has elapsed
+    pub fn votes_counted(&self, now: i64, window_seconds: i64) -> u32 {
+        if now - self.window_start < window_seconds {
+            self.votes_in_window
+        } else {
+            0
+        }
+    }
+
+    /// Record a vote at `now`, starting a new window if the current one has elapsed
+    pub fn record(&mut self, now: i64, window_seconds: i64) {
+        if now - self.window_start >= window_seconds {
+            self.window_start = now;
+            self.votes_in_window = 0;
+        }
+        self.votes_in_window = self.votes_in_window.saturating_add(1);
+        self.last_vote_at = now;
+    }
+}
@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use super::{QualityScores, VoteType};
+
+/// Running sums of each quality dimension across an agent's votes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct QualitySums {
+    pub response_quality: u64,
+    pub response_speed: u64,
+    pub accuracy: u64,
+    pub professionalism: u64,
+}
+
+/// Vote Tally Account
+/// Aggregate of every peer vote cast on an agent, kept in step by cast_peer_vote
+/// PDA seeds: ["vote_tally", agent]
+#[account]
+#[derive(InitSpace)]
+pub struct VoteTally {
+    /// Agent the votes were cast on
+    pub agent: Pubkey,
+
+    /// Upvotes received
+    pub upvotes: u32,
+
+    /// Downvotes received
+    pub downvotes: u32,
+
+    /// Neutral votes received
+    pub neutrals: u32,
+
+    /// Sum of quality scores per dimension (divide by total_votes for averages)
+    pub quality_sums: QualitySums,
+
+    /// Sum of vote weights (100 = 1.0x)
+    pub weight_sum: u64,
+
+    /// Timestamp of the most recent vote
+    pub last_vote_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Votes still counted here whose PeerVote accounts have since been closed
+    pub closed_votes: u32,
+
+    /// Votes excluded while their dispute awaits authority review
+    pub disputed_votes: u32,
+
+    /// Votes excluded for good after a dispute was upheld
+    pub removed_votes: u32,
+}
+
+impl VoteTally {
+    /// Seed prefix for PDA derivation
+    pub const SEED_PREFIX: &'static [u8] = b"vote_tally";
+
+    /// Calculate space for rent
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        4 + // upvotes
+        4 + // downvotes
+        4 + // neutrals
+        32 + // quality_sums (4 u64s)
+        8 + // weight_sum
+        8 + // last_vote_at
+        1 + // bump
+        4 + // closed_votes
+        4 + // disputed_votes
+        4; // removed_votes
+
+    /// Fold one vote into the tally
+    pub fn record(
+        &mut self,
+        vote_type: VoteType,
+        quality_scores: &QualityScores,
+        vote_weight: u16,
+        timestamp: i64,
+    ) {
+        self.include(vote_type, quality_scores, vote_weight);
+        self.last_vote_at = timestamp;
+    }
+
+    /// Add a vote's type, scores and weight without touching last_vote_at
+    pub fn include(&mut self, vote_type: VoteType, quality_scores: &QualityScores, vote_weight: u16) {
+        match vote_type {
+            VoteType::Upvote => self.upvotes = self.upvotes.saturating_add(1),
+            VoteType::Downvote => self.downvotes = self.downvotes.saturating_add(1),
+            VoteType::Neutral => self.neutrals = self.neutrals.saturating_add(1),
+        }
+
+        let sums = &mut self.quality_sums;
+        sums.response_quality = sums.response_quality.saturating_add(quality_scores.response_quality.into());
+        sums.response_speed = sums.response_speed.saturating_add(quality_scores.response_speed.into());
+        sums.accuracy = sums.accuracy.saturating_add(quality_scores.accuracy.into());
+        sums.professionalism = sums.professionalism.saturating_add(quality_scores.professionalism.into());
+
+        self.weight_sum = self.weight_sum.saturating_add(vote_weight.into());
+    }
+
+    /// Take a previously included vote back out of the tally
+    pub fn exclude(&mut self, vote_type: VoteType, quality_scores: &QualityScores, vote_weight: u16) {
+        match vote_type {
+            VoteType::Upvote => self.upvotes = self.upvotes.saturating_sub(1),
+            VoteType::Downvote => self.downvotes = self.downvotes.saturating_sub(1),
+            VoteType::Neutral => self.neutrals = self.neutrals.saturating_sub(1),
+        }
+
+        let sums = &mut self.quality_sums;
+        sums.response_quality = sums.response_quality.saturating_sub(quality_scores.response_quality.into());
+        sums.response_speed = sums.response_speed.saturating_sub(quality_scores.response_speed.into());
+        sums.accuracy = sums.accuracy.saturating_sub(quality_scores.accuracy.into());
+        sums.professionalism = sums.professionalism.saturating_sub(quality_scores.professionalism.into());
+
+        self.weight_sum = self.weight_sum.saturating_sub(vote_weight.into());
+    }
+
+    /// Replace an amended vote's type and quality scores; weight and
+    /// last_vote_at are unchanged since the vote itself is not new
+    pub fn amend(
+        &mut self,
+        old_type: VoteType,
+        old_scores: &QualityScores,
+        new_type: VoteType,
+        new_scores: &QualityScores,
+    ) {
+        match old_type {
+            VoteType::Upvote => self.upvotes = self.upvotes.saturating_sub(1),
+            VoteType::Downvote => self.downvotes = self.downvotes.saturating_sub(1),
+            VoteType::Neutral => self.neutrals = self.neutrals.saturating_sub(1),
+        }
+        match new_type {
+            VoteType::Upvote => self.upvotes = self.upvotes.saturating_add(1),
+            VoteType::Downvote => self.downvotes = self.downvotes.saturating_add(1),
+            VoteType::Neutral => self.neutrals = self.neutrals.saturating_add(1),
+        }
+
+        let sums = &mut self.quality_sums;
+        sums.response_quality = sums.response_quality
+            .saturating_sub(old_scores.response_quality.into())
+            .saturating_add(new_scores.response_quality.into());
+        sums.response_speed = sums.response_speed
+            .saturating_sub(old_scores.response_speed.into())
+            .saturating_add(new_scores.response_speed.into());
+        sums.accuracy = sums.accuracy
+            .saturating_sub(old_scores.accuracy.into())
+            .saturating_add(new_scores.accuracy.into());
+        sums.professionalism = sums.professionalism
+            .saturating_sub(old_scores.professionalism.into())
+            .saturating_add(new_scores.professionalism.into());
+    }
+
+    /// Total votes of every type
+    pub fn total_votes(&self) -> u32 {
+        self.upvotes
+            .saturating_add(self.downvotes)
+            .saturating_add(self.neutrals)
+    }
+}
@@ -125,7 +125,8 @@ async function main() {
       signature,
       signatureHash,
       paymentAmount,
-      { chat: {} } // ContentType
+      { chat: {} } // ContentType,
+      PublicKey.default
     )
     .accounts({
       receipt: receiptPda,
@@ -146,7 +147,8 @@ async function main() {
   console.log('  Payer:', receiptAccount.payer.toBase58());
   console.log('  Recipient:', receiptAccount.recipient.toBase58());
   console.log('  Amount:', receiptAccount.amount.toNumber());
-  console.log('  Vote cast:', receiptAccount.voteCast);
+  console.log('  Payer voted:', receiptAccount.payerVoted);
+  console.log('  Recipient voted:', receiptAccount.recipientVoted);
   console.log();
 
   // === STEP 3: Setup identities ===
@@ -171,7 +173,12 @@ async function main() {
     await identityRegistry.methods
       .registerAgent(
         voter.publicKey,
-        'https://example.com/voter-metadata.json'
+        'https://example.com/voter-metadata.json',
+        Array.from(createHash('sha256').update('https://example.com/voter-metadata.json').digest()),
+        0,
+        null,
+        null,
+        false
       )
       .accounts({
         agentIdentity: voterIdentityPda,
@@ -190,7 +197,12 @@ async function main() {
     await identityRegistry.methods
       .registerAgent(
         votedAgent.publicKey,
-        'https://example.com/agent-metadata.json'
+        'https://example.com/agent-metadata.json',
+        Array.from(createHash('sha256').update('https://example.com/agent-metadata.json').digest()),
+        0,
+        null,
+        null,
+        false
       )
       .accounts({
         agentIdentity: votedAgentIdentityPda,
@@ -321,7 +333,7 @@ async function main() {
 
   // Verify receipt is marked as voted
   const updatedReceipt = await (voteRegistry.account as any)['transactionReceipt'].fetch(receiptPda);
-  console.log('  Receipt vote_cast:', updatedReceipt.voteCast);
+  console.log('  Receipt payer_voted:', updatedReceipt.payerVoted);
   console.log();
 
   // === SUCCESS ===
@@ -74,7 +74,8 @@ export class ReceiptCreator {
         txData.signature,
         Array.from(txData.signatureHash),
         amountU64,
-        { [ContentType[contentType].toLowerCase()]: {} } // Convert enum to Anchor format
+        { [ContentType[contentType].toLowerCase()]: {} } // Convert enum to Anchor format,
+        PublicKey.default
       )
       .accounts({
         receipt: receiptPda,
@@ -0,0 +1,153 @@
+//! Example of an external program calling the GhostSpeak programs through
+//! gs2-cpi. Exercised by tests/integration/cpi-caller.test.ts and
+//! tests/reputation-registry/view-return-data.test.ts.
+
+use anchor_lang::prelude::*;
+use gs2_cpi::identity_registry::{self, IdentityRegistry};
+use gs2_cpi::reputation_registry::{self, ReputationRegistry};
+
+declare_id!("2V7PiKSoLC4G1Ytpea3b4FVeyGsyMLbuC54GppQZco8S");
+
+#[program]
+pub mod cpi_caller {
+    use super::*;
+
+    /// Read an agent's identity status and reputation by CPI and return both
+    pub fn check_agent(ctx: Context<CheckAgent>) -> Result<AgentCheck> {
+        let identity_program = ctx.accounts.identity_registry_program.to_account_info();
+        let reputation_program = ctx.accounts.reputation_registry_program.to_account_info();
+
+        let status = identity_registry::verify_identity(
+            CpiContext::new(
+                identity_program,
+                identity_registry::accounts::VerifyIdentity {
+                    agent_identity: ctx.accounts.agent_identity.to_account_info(),
+                    agent_address: ctx.accounts.agent_address.to_account_info(),
+                    staking_pool: None,
+                },
+            ),
+            false,
+        )?;
+
+        let view = reputation_registry::get_reputation(CpiContext::new(
+            reputation_program.clone(),
+            reputation_registry::accounts::GetReputation {
+                agent_reputation: ctx.accounts.agent_reputation.to_account_info(),
+                agent_address: ctx.accounts.agent_address.to_account_info(),
+                decay_config: ctx.accounts.decay_config.to_account_info(),
+            },
+        ))?;
+
+        let effective_score = reputation_registry::get_effective_score(CpiContext::new(
+            reputation_program.clone(),
+            reputation_registry::accounts::GetEffectiveScore {
+                agent_reputation: ctx.accounts.agent_reputation.to_account_info(),
+                decay_config: ctx.accounts.decay_config.to_account_info(),
+            },
+        ))?;
+
+        let tier = reputation_registry::get_tier(CpiContext::new(
+            reputation_program,
+            reputation_registry::accounts::GetTier {
+                agent_reputation: ctx.accounts.agent_reputation.to_account_info(),
+            },
+        ))?;
+
+        // The same reputation read three ways must agree
+        require_keys_eq!(view.agent, ctx.accounts.agent_address.key(), CallerError::ReturnDataMismatch);
+        require_eq!(view.effective_score, effective_score, CallerError::ReturnDataMismatch);
+        require_eq!(view.tier, tier, CallerError::ReturnDataMismatch);
+
+        Ok(AgentCheck {
+            agent: view.agent,
+            is_active: status.is_active,
+            has_minimum_stake: status.has_minimum_stake,
+            staked_amount: status.staked_amount,
+            overall_score: view.overall_score,
+            effective_score,
+            tier,
+        })
+    }
+
+    /// Read an agent's reputation by CPI and return what the view reported
+    pub fn read_reputation(ctx: Context<ReadReputation>) -> Result<ReputationRead> {
+        let view = reputation_registry::get_reputation(CpiContext::new(
+            ctx.accounts.reputation_registry_program.to_account_info(),
+            reputation_registry::accounts::GetReputation {
+                agent_reputation: ctx.accounts.agent_reputation.to_account_info(),
+                agent_address: ctx.accounts.agent_address.to_account_info(),
+                decay_config: ctx.accounts.decay_config.to_account_info(),
+            },
+        ))?;
+
+        Ok(ReputationRead {
+            agent: view.agent,
+            overall_score: view.overall_score,
+            effective_score: view.effective_score,
+            tier: view.tier,
+            update_nonce: view.update_nonce,
+            is_frozen: view.is_frozen,
+        })
+    }
+}
+
+#[derive(Accounts)]
+pub struct CheckAgent<'info> {
+    /// CHECK: Validated by identity_registry
+    pub agent_identity: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by reputation_registry
+    pub agent_reputation: UncheckedAccount<'info>,
+
+    /// CHECK: The agent's wallet address
+    pub agent_address: UncheckedAccount<'info>,
+
+    /// CHECK: reputation_registry's decay config PDA, validated by it
+    pub decay_config: UncheckedAccount<'info>,
+
+    pub identity_registry_program: Program<'info, IdentityRegistry>,
+    pub reputation_registry_program: Program<'info, ReputationRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct ReadReputation<'info> {
+    /// CHECK: Validated by reputation_registry
+    pub agent_reputation: UncheckedAccount<'info>,
+
+    /// CHECK: The agent's wallet address
+    pub agent_address: UncheckedAccount<'info>,
+
+    /// CHECK: reputation_registry's decay config PDA, validated by it
+    pub decay_config: UncheckedAccount<'info>,
+
+    pub reputation_registry_program: Program<'info, ReputationRegistry>,
+}
+
+/// What check_agent read, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AgentCheck {
+    pub agent: Pubkey,
+    pub is_active: bool,
+    pub has_minimum_stake: bool,
+    pub staked_amount: u64,
+    pub overall_score: u16,
+    pub effective_score: u16,
+    pub tier: u8,
+}
+
+/// What read_reputation read, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReputationRead {
+    pub agent: Pubkey,
+    pub overall_score: u16,
+    pub effective_score: u16,
+    pub tier: u8,
+    pub update_nonce: u64,
+    pub is_frozen: bool,
+}
+
+#[error_code]
+pub enum CallerError {
+    #[msg("Views of the same account returned different data")]
+    ReturnDataMismatch,
+}
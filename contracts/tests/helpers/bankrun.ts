@@ -0,0 +1,639 @@
+/**
+ * Shared bankrun helpers for program test suites
+ *
+ * Wraps the boilerplate every suite repeats: loading a program from its
+ * generated IDL, funding keypairs from the context payer, moving the
+ * on-chain clock forward, and mocking external accounts.
+ */
+import { ProgramTestContext, Clock } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { BN, EventParser, Program, type Event, type Idl } from '@coral-xyz/anchor';
+import {
+  AddressLookupTableAccount,
+  AddressLookupTableProgram,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+} from '@solana/web3.js';
+import { createAccount, mintTo, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { createHash } from 'crypto';
+import * as fs from 'fs';
+import { fetchAccount } from './anchor-accounts';
+
+/**
+ * Load a workspace program from ./target/idl/<name>.json
+ *
+ * @param name - The program's crate name (e.g. 'reputation_registry')
+ * @param provider - Bankrun provider bound to the test context
+ */
+export function loadProgram(name: string, provider: BankrunProvider): Program<Idl> {
+  const idl = JSON.parse(
+    fs.readFileSync(`./target/idl/${name}.json`, 'utf-8')
+  ) as Idl;
+  return new Program(idl, provider);
+}
+
+/**
+ * Transfer lamports from the context payer to an account
+ */
+export async function airdrop(
+  context: ProgramTestContext,
+  to: PublicKey,
+  lamports: number
+): Promise<void> {
+  const tx = new Transaction();
+  tx.recentBlockhash = context.lastBlockhash;
+  tx.feePayer = context.payer.publicKey;
+  tx.add(
+    SystemProgram.transfer({
+      fromPubkey: context.payer.publicKey,
+      toPubkey: to,
+      lamports,
+    })
+  );
+  tx.sign(context.payer);
+  await context.banksClient.processTransaction(tx);
+}
+
+/**
+ * Read the current unix timestamp from the bank clock
+ */
+export async function now(context: ProgramTestContext): Promise<number> {
+  const clock = await context.banksClient.getClock();
+  return Number(clock.unixTimestamp);
+}
+
+/**
+ * Advance the bank clock by the given number of seconds
+ */
+export async function advanceTime(
+  context: ProgramTestContext,
+  seconds: number
+): Promise<void> {
+  const clock = await context.banksClient.getClock();
+  context.setClock(
+    new Clock(
+      clock.slot + 1n,
+      clock.epochStartTimestamp,
+      clock.epoch,
+      clock.leaderScheduleEpoch,
+      clock.unixTimestamp + BigInt(seconds)
+    )
+  );
+  context.lastBlockhash = (await context.banksClient.getLatestBlockhash())![0];
+}
+
+/**
+ * Simulate an instruction and return the raw bytes it set via set_return_data
+ *
+ * @throws if the simulation fails or the program set no return data
+ */
+export async function simulateReturnData(
+  context: ProgramTestContext,
+  ix: TransactionInstruction
+): Promise<Buffer> {
+  const tx = new Transaction().add(ix);
+  tx.recentBlockhash = context.lastBlockhash;
+  tx.feePayer = context.payer.publicKey;
+  tx.sign(context.payer);
+
+  const sim = await context.banksClient.simulateTransaction(tx);
+  if (sim.result) {
+    throw new Error(`simulation failed: ${sim.result}\n${sim.meta?.logMessages.join('\n')}`);
+  }
+  const returnData = sim.meta?.returnData;
+  if (!returnData) {
+    throw new Error('instruction set no return data');
+  }
+  return Buffer.from(returnData.data);
+}
+
+/**
+ * Simulate an instruction and decode the Anchor events `program` emitted
+ *
+ * @param signers - Extra signers besides the context payer
+ * @throws if the simulation fails
+ */
+export async function simulateEvents(
+  context: ProgramTestContext,
+  program: Program<Idl>,
+  ix: TransactionInstruction,
+  signers: Keypair[] = []
+): Promise<Event[]> {
+  const tx = new Transaction().add(ix);
+  tx.recentBlockhash = context.lastBlockhash;
+  tx.feePayer = context.payer.publicKey;
+  tx.sign(context.payer, ...signers);
+
+  const sim = await context.banksClient.simulateTransaction(tx);
+  if (sim.result) {
+    throw new Error(`simulation failed: ${sim.result}\n${sim.meta?.logMessages.join('\n')}`);
+  }
+  const parser = new EventParser(program.programId, program.coder);
+  return Array.from(parser.parseLogs(sim.meta?.logMessages ?? []));
+}
+
+/**
+ * Write an address lookup table holding `addresses` and move past its extension slot
+ *
+ * For instructions whose account list does not fit a legacy transaction.
+ */
+export async function lookupTable(
+  context: ProgramTestContext,
+  addresses: PublicKey[]
+): Promise<AddressLookupTableAccount> {
+  const key = Keypair.generate().publicKey;
+  const { slot } = await context.banksClient.getClock();
+
+  // LookupTableMeta: type tag, deactivation slot (never), last extended slot,
+  // start index and authority (None), padded to 56 bytes
+  const meta = Buffer.alloc(56);
+  meta.writeUInt32LE(1, 0);
+  meta.writeBigUInt64LE(0xffffffffffffffffn, 4);
+  meta.writeBigUInt64LE(slot, 12);
+  const data = Buffer.concat([meta, ...addresses.map((address) => address.toBuffer())]);
+
+  context.setAccount(key, {
+    lamports: 1_000_000_000,
+    data,
+    owner: AddressLookupTableProgram.programId,
+    executable: false,
+  });
+  context.warpToSlot(slot + 1n);
+  context.lastBlockhash = (await context.banksClient.getLatestBlockhash())![0];
+
+  return new AddressLookupTableAccount({ key, state: AddressLookupTableAccount.deserialize(data) });
+}
+
+/**
+ * Send instructions as a v0 transaction paid by the context payer
+ *
+ * @throws with the program logs if the transaction fails
+ */
+export async function processV0(
+  context: ProgramTestContext,
+  instructions: TransactionInstruction[],
+  signers: Keypair[],
+  tables: AddressLookupTableAccount[]
+): Promise<void> {
+  const message = new TransactionMessage({
+    payerKey: context.payer.publicKey,
+    recentBlockhash: context.lastBlockhash,
+    instructions,
+  }).compileToV0Message(tables);
+  const tx = new VersionedTransaction(message);
+  tx.sign([context.payer, ...signers]);
+
+  const result = await context.banksClient.tryProcessTransaction(tx);
+  if (result.result) {
+    throw new Error(`transaction failed: ${result.result}\n${result.meta?.logMessages.join('\n')}`);
+  }
+}
+
+/**
+ * Rewrite an account with only its first `length` bytes, as an older program
+ * version would have allocated it; lamports and owner are kept
+ *
+ * For migration tests: create an account with the current program, then cut
+ * it back to a legacy layout before calling the program's migrate instruction.
+ */
+export async function truncateAccount(
+  context: ProgramTestContext,
+  address: PublicKey,
+  length: number
+): Promise<void> {
+  const account = await context.banksClient.getAccount(address);
+  if (!account) {
+    throw new Error(`account ${address.toBase58()} does not exist`);
+  }
+  context.setAccount(address, { ...account, data: account.data.subarray(0, length) });
+}
+
+/** Metaplex Core program id */
+export const MPL_CORE_PROGRAM_ID = new PublicKey('CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d');
+
+/**
+ * Write a minimal Metaplex Core AssetV1 account owned by `owner`
+ *
+ * Only the serialized prefix identity_registry reads is meaningful:
+ * key (AssetV1 = 1), owner, update authority (None), empty name and uri.
+ *
+ * @returns the new asset address
+ */
+export function mockCoreAsset(
+  context: ProgramTestContext,
+  owner: PublicKey,
+  programOwner: PublicKey = MPL_CORE_PROGRAM_ID
+): PublicKey {
+  const asset = Keypair.generate().publicKey;
+  const data = Buffer.concat([
+    Buffer.from([1]),
+    owner.toBuffer(),
+    Buffer.from([0]),
+    Buffer.alloc(4),
+    Buffer.alloc(4),
+    Buffer.from([0]),
+  ]);
+  context.setAccount(asset, {
+    lamports: 1_000_000_000,
+    data,
+    owner: programOwner,
+    executable: false,
+  });
+  return asset;
+}
+
+/** Pyth Solana Receiver program id */
+export const PYTH_RECEIVER_PROGRAM_ID = new PublicKey('rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ');
+
+/**
+ * Write a fully verified Pyth PriceUpdateV2 account reporting price * 10^exponent USD
+ *
+ * Rewrites `feed` in place when given, so a test can move the price or publish time.
+ *
+ * @returns the feed address
+ */
+export function mockPriceFeed(
+  context: ProgramTestContext,
+  price: { price: number; exponent: number; publishTime: number },
+  feed: PublicKey = Keypair.generate().publicKey,
+  programOwner: PublicKey = PYTH_RECEIVER_PROGRAM_ID
+): PublicKey {
+  const message = Buffer.alloc(32 + 8 + 8 + 4 + 8 + 8 + 8 + 8);
+  message.writeBigInt64LE(BigInt(price.price), 32);
+  message.writeInt32LE(price.exponent, 48);
+  message.writeBigInt64LE(BigInt(price.publishTime), 52);
+  message.writeBigInt64LE(BigInt(price.publishTime), 60);
+  const data = Buffer.concat([
+    createHash('sha256').update('account:PriceUpdateV2').digest().subarray(0, 8),
+    Keypair.generate().publicKey.toBuffer(),
+    Buffer.from([1]),
+    message,
+    Buffer.alloc(8),
+  ]);
+  context.setAccount(feed, {
+    lamports: 1_000_000_000,
+    data,
+    owner: programOwner,
+    executable: false,
+  });
+  return feed;
+}
+
+/** identity_registry program id */
+export const IDENTITY_REGISTRY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+
+/**
+ * Write an identity_registry AgentIdentity for `agent` at its PDA
+ *
+ * Only the serialized prefix token_staking reads is meaningful: agent address,
+ * empty metadata URI, is_active and slash_count. Rewrites the account in place,
+ * so a test can deactivate or slash the agent.
+ *
+ * @returns the identity address
+ */
+export function mockAgentIdentity(
+  context: ProgramTestContext,
+  agent: PublicKey,
+  standing: { isActive?: boolean; slashCount?: number } = {},
+  programOwner: PublicKey = IDENTITY_REGISTRY_PROGRAM_ID
+): PublicKey {
+  const [identity] = PublicKey.findProgramAddressSync(
+    [Buffer.from('agent'), agent.toBuffer()],
+    IDENTITY_REGISTRY_PROGRAM_ID
+  );
+  // registration, last-active and activity count, then is_active, staked amount, unlock time, slash count
+  const fields = Buffer.alloc(8 + 8 + 8 + 1 + 8 + 8 + 4);
+  fields.writeUInt8(standing.isActive === false ? 0 : 1, 24);
+  fields.writeUInt32LE(standing.slashCount ?? 0, 41);
+  const data = Buffer.concat([
+    createHash('sha256').update('account:AgentIdentity').digest().subarray(0, 8),
+    agent.toBuffer(),
+    Keypair.generate().publicKey.toBuffer(),
+    Buffer.alloc(4),
+    fields,
+  ]);
+  context.setAccount(identity, {
+    lamports: 1_000_000_000,
+    data,
+    owner: programOwner,
+    executable: false,
+  });
+  return identity;
+}
+
+/** reputation_registry program id */
+export const REPUTATION_REGISTRY_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+
+/**
+ * Register a fresh wallet, funded with 10 SOL, as an identity_registry agent
+ */
+export async function registeredWallet(context: ProgramTestContext, identityProgram: Program<Idl>): Promise<Keypair> {
+  const wallet = Keypair.generate();
+  await airdrop(context, wallet.publicKey, 10_000_000_000);
+
+  const asset = mockCoreAsset(context, wallet.publicKey);
+  await identityProgram.methods
+    .registerAgent(
+      asset,
+      'https://example.com/agent.json',
+      metadataHash('https://example.com/agent.json'),
+      0,
+      null,
+      null,
+      false
+    )
+    .accounts({
+      agentIdentity: PublicKey.findProgramAddressSync(
+        [Buffer.from('agent'), wallet.publicKey.toBuffer()],
+        IDENTITY_REGISTRY_PROGRAM_ID
+      )[0],
+      agent: wallet.publicKey,
+      asset,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([wallet])
+    .rpc();
+  return wallet;
+}
+
+/**
+ * A registered agent whose reputation the reputation authority has set to `score`
+ *
+ * 500 is enough to vote and to endorse under the default vote_registry thresholds.
+ * `authority` must already hold the reputation_registry authority account.
+ */
+export async function reputableAgent(
+  context: ProgramTestContext,
+  identityProgram: Program<Idl>,
+  reputationProgram: Program<Idl>,
+  authority: Keypair,
+  score = 500
+): Promise<Keypair> {
+  const wallet = await registeredWallet(context, identityProgram);
+  const pda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, REPUTATION_REGISTRY_PROGRAM_ID)[0];
+  const agentReputation = pda(Buffer.from('reputation'), wallet.publicKey.toBuffer());
+
+  await reputationProgram.methods
+    .initializeReputation()
+    .accounts({
+      agentReputation,
+      agentAddress: wallet.publicKey,
+      authorityAccount: null,
+      initializer: wallet.publicKey,
+      payer: wallet.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([wallet])
+    .rpc();
+
+  await advanceTime(context, 10);
+  await reputationProgram.methods
+    .updateReputation(
+      score,
+      { trust: 50, quality: 50, reliability: 50, economic: 50, social: 50 },
+      { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+      new Array(32).fill(0),
+      new BN(0),
+      new BN(await now(context))
+    )
+    .accounts({
+      agentReputation,
+      authorityAccount: pda(Buffer.from('authority')),
+      agentAddress: wallet.publicKey,
+      authority: authority.publicKey,
+    })
+    .signers([authority])
+    .rpc();
+  return wallet;
+}
+
+/** token_staking program id */
+export const TOKEN_STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+
+/** Minimum stake of the vaults vaultOwner creates, in token base units */
+export const VAULT_MIN_STAKE = 1_000_000;
+
+/** Lock period of the vaults vaultOwner creates (7 days) */
+export const VAULT_LOCK_PERIOD = 7 * 24 * 60 * 60;
+
+/** initialize_vault parameters a token_staking suite may override; caps of 0 are uncapped */
+export type VaultOptions = Partial<{
+  cooldownSeconds: number;
+  maxTotalStake: number;
+  maxStakePerStaker: number;
+}>;
+
+/** A funded staker wallet and its token account */
+export type TokenStaker = { wallet: Keypair; tokens: PublicKey };
+
+/**
+ * The identity registry PDA of the agent a token_staking vault belongs to
+ *
+ * @param program - token_staking program
+ */
+export async function vaultIdentityPda(program: Program<Idl>, vault: PublicKey): Promise<PublicKey> {
+  const { targetAgent } = await fetchAccount(program, 'stakingVault', vault);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('agent'), targetAgent.toBuffer()],
+    IDENTITY_REGISTRY_PROGRAM_ID
+  )[0];
+}
+
+/**
+ * A token_staking vault for a fresh agent with a mocked identity; the agent
+ * is also the vault authority. The vault has a VAULT_MIN_STAKE minimum,
+ * a VAULT_LOCK_PERIOD lock and a 1% trust multiplier.
+ *
+ * @param program - token_staking program
+ * @param mint - The vault's stake token
+ */
+export async function vaultOwner(
+  context: ProgramTestContext,
+  program: Program<Idl>,
+  mint: PublicKey,
+  options: VaultOptions = {}
+): Promise<{ agent: Keypair; vault: PublicKey }> {
+  const { cooldownSeconds = 0, maxTotalStake = 0, maxStakePerStaker = 0 } = options;
+  const agent = Keypair.generate();
+  await airdrop(context, agent.publicKey, 10_000_000_000);
+  const pda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, TOKEN_STAKING_PROGRAM_ID)[0];
+  const vault = pda(Buffer.from('vault'), agent.publicKey.toBuffer(), mint.toBuffer());
+
+  await program.methods
+    .initializeVault(
+      new BN(VAULT_MIN_STAKE),
+      new BN(VAULT_LOCK_PERIOD),
+      100,
+      new BN(cooldownSeconds),
+      new BN(maxTotalStake),
+      new BN(maxStakePerStaker)
+    )
+    .accounts({
+      vault,
+      vaultTokenAccount: pda(Buffer.from('vault_token'), vault.toBuffer()),
+      tokenMint: mint,
+      targetAgent: agent.publicKey,
+      agentIdentity: mockAgentIdentity(context, agent.publicKey),
+      protocolConfig: pda(Buffer.from('protocol_config')),
+      endorsementIndex: pda(Buffer.from('endorsement_index'), agent.publicKey.toBuffer()),
+      authority: agent.publicKey,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      rent: SYSVAR_RENT_PUBKEY,
+    })
+    .signers([agent])
+    .rpc();
+  return { agent, vault };
+}
+
+/**
+ * A staker funded with SOL and a token account holding 100 × VAULT_MIN_STAKE of `mint`
+ *
+ * @param mint - Must be mintable by the context payer
+ */
+export async function tokenStaker(context: ProgramTestContext, mint: PublicKey): Promise<TokenStaker> {
+  const wallet = Keypair.generate();
+  await airdrop(context, wallet.publicKey, 10_000_000_000);
+  const tokens = await createAccount(context.banksClient, context.payer, mint, wallet.publicKey);
+  await mintTo(context.banksClient, context.payer, mint, tokens, context.payer, 100 * VAULT_MIN_STAKE);
+  return { wallet, tokens };
+}
+
+/** vote_registry program id */
+export const VOTE_REGISTRY_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+let receiptCount = 0;
+
+/**
+ * Create a fresh vote_registry TransactionReceipt for a 0.001 SOL API payment
+ * from `payer` to `recipient`, signed by the payer
+ *
+ * @param program - vote_registry program
+ * @returns the receipt address
+ */
+export async function transactionReceipt(
+  program: Program<Idl>,
+  payer: Keypair,
+  recipient: Keypair
+): Promise<PublicKey> {
+  const signature = `receipt_sig_${receiptCount++}`;
+  const signatureHash = Array.from(createHash('sha256').update(signature).digest());
+  const [receipt] = PublicKey.findProgramAddressSync(
+    [Buffer.from('tx_receipt'), payer.publicKey.toBuffer(), recipient.publicKey.toBuffer(), Buffer.from(signatureHash)],
+    VOTE_REGISTRY_PROGRAM_ID
+  );
+
+  await program.methods
+    .createTransactionReceipt(signature, signatureHash, new BN(1_000_000), { apiResponse: {} }, PublicKey.default)
+    .accounts({
+      receipt,
+      payerPubkey: payer.publicKey,
+      recipientPubkey: recipient.publicKey,
+      creator: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([payer])
+    .rpc();
+  return receipt;
+}
+
+/**
+ * sha256 of an agent's metadata JSON, as passed to register_agent and update_identity
+ *
+ * @param metadata - Canonical metadata JSON (tests usually pass the URI itself)
+ */
+export function metadataHash(metadata: string): number[] {
+  return Array.from(createHash('sha256').update(metadata).digest());
+}
+
+/**
+ * Structured metrics of a validation TestResult that reports none of them:
+ * zero latencies and status class, the u64::MAX cost sentinel, no error code
+ */
+export function unreportedMetrics() {
+  return {
+    p50Ms: 0,
+    p95Ms: 0,
+    costMicroUsd: new BN('18446744073709551615'),
+    httpStatusClass: 0,
+    errorCodeHash: Array(32).fill(0) as number[],
+  };
+}
+
+/** Fields of a validation TestResult a test may override */
+export type TestResultOverrides = Partial<{
+  llmModel: string;
+  success: boolean;
+  responseTime: number;
+  score: number;
+}> &
+  Partial<ReturnType<typeof unreportedMetrics>>;
+
+/**
+ * A validation TestResult: a successful gpt-4 run scoring 95, slower than the
+ * 1s fast-response cutoff so it earns no speed bonus, with no metrics reported
+ *
+ * @param overrides - Fields to replace; the validator is always assigned on-chain
+ */
+export function testResult(overrides: TestResultOverrides = {}) {
+  const { responseTime = 2_000, ...fields } = overrides;
+  return {
+    llmModel: 'gpt-4',
+    success: true,
+    score: 95,
+    // Assigned on-chain from the submitter
+    validator: PublicKey.default,
+    ...unreportedMetrics(),
+    ...fields,
+    responseTime: new BN(responseTime),
+  };
+}
+
+/**
+ * One passing testResult per model in a typical three-model validation
+ *
+ * @param overrides - Applied to every result
+ */
+export function passingResults(overrides: TestResultOverrides = {}) {
+  return ['gpt-4', 'claude-3', 'gemini-pro'].map((llmModel) => testResult({ llmModel, ...overrides }));
+}
+
+/** validation_registry program id */
+export const VALIDATION_REGISTRY_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+
+/** Bond the validators bondedValidator registers put up (0.1 SOL, the minimum) */
+export const VALIDATOR_BOND = 100_000_000;
+
+/**
+ * A registered agent wallet with a ValidatorRecord bonded for VALIDATOR_BOND
+ */
+export async function bondedValidator(
+  context: ProgramTestContext,
+  identityProgram: Program<Idl>,
+  validationProgram: Program<Idl>
+): Promise<Keypair> {
+  const validator = await registeredWallet(context, identityProgram);
+  await validationProgram.methods
+    .registerValidator(new BN(VALIDATOR_BOND))
+    .accounts({
+      validatorRecord: PublicKey.findProgramAddressSync(
+        [Buffer.from('validator'), validator.publicKey.toBuffer()],
+        VALIDATION_REGISTRY_PROGRAM_ID
+      )[0],
+      validatorIdentity: PublicKey.findProgramAddressSync(
+        [Buffer.from('agent'), validator.publicKey.toBuffer()],
+        IDENTITY_REGISTRY_PROGRAM_ID
+      )[0],
+      validator: validator.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([validator])
+    .rpc();
+  return validator;
+}
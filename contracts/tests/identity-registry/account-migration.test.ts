@@ -0,0 +1,264 @@
+/**
+ * Account Migration Tests
+ * Tests AgentIdentity layout versioning and the migrate_account instruction
+ *
+ * Account migration ensures:
+ * 1. An identity in the pre-version layout is rejected until it is migrated
+ * 2. migrate_account grows it to the current size, sets the current version and keeps every field
+ * 3. Migrating a current identity again changes nothing
+ * 4. An identity below the minimum supported version fails with UnsupportedAccountVersion
+ * 5. The agent instructions work on a migrated identity
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  simulateReturnData,
+  truncateAccount,
+} from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const CURRENT_VERSION = 1;
+
+describe('Account Migration', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let stakingPoolPda: PublicKey;
+
+  function identityPda(agent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('agent'), agent.publicKey.toBuffer()],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function stakeAccounts(agent: Keypair) {
+    return {
+      agentIdentity: identityPda(agent),
+      stakingPool: stakingPoolPda,
+      agent: agent.publicKey,
+      agentAddress: agent.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  async function accountData(address: PublicKey): Promise<Buffer> {
+    return Buffer.from((await context.banksClient.getAccount(address))!.data);
+  }
+
+  /** Register a fresh agent holding 1 SOL of collateral */
+  async function stakedAgent(): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    await program.methods
+      .stakeCollateral(new BN(LAMPORTS_PER_SOL))
+      .accounts(stakeAccounts(agent))
+      .signers([agent])
+      .rpc();
+    return agent;
+  }
+
+  /** Cut the identity back to the layout written before the version field existed */
+  async function preVersionAgent(): Promise<Keypair> {
+    const agent = await stakedAgent();
+    const data = await accountData(identityPda(agent));
+    await truncateAccount(context, identityPda(agent), data.length - 1);
+    return agent;
+  }
+
+  function migrate(agent: Keypair) {
+    return program.methods
+      .migrateAccount()
+      .accounts({
+        agentIdentity: identityPda(agent),
+        agentAddress: agent.publicKey,
+        payer: context.payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  function heartbeat(agent: Keypair) {
+    return program.methods
+      .recordHeartbeat(null)
+      .accounts({ agentIdentity: identityPda(agent), agent: agent.publicKey, agentAddress: agent.publicKey })
+      .signers([agent])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    await program.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('new identities start at the current version', async () => {
+    const agent = await stakedAgent();
+    expect((await fetchAccount(program, 'agentIdentity', identityPda(agent))).version).toBe(CURRENT_VERSION);
+  });
+
+  test('a pre-version identity is rejected until it is migrated', async () => {
+    const agent = await preVersionAgent();
+    await expect(heartbeat(agent)).rejects.toThrow(/AccountDidNotDeserialize/);
+
+    await migrate(agent);
+    await heartbeat(agent);
+  });
+
+  test('migrate_account grows the identity and keeps every field', async () => {
+    const agent = await stakedAgent();
+    const before = await fetchAccount(program, 'agentIdentity', identityPda(agent));
+    const fullLength = (await accountData(identityPda(agent))).length;
+    await truncateAccount(context, identityPda(agent), fullLength - 1);
+
+    await migrate(agent);
+
+    expect((await accountData(identityPda(agent))).length).toBe(fullLength);
+    const after = await fetchAccount(program, 'agentIdentity', identityPda(agent));
+    expect(after.version).toBe(CURRENT_VERSION);
+    // Only pre-two-step-unstake layouts reset the stake clock
+    expect(after.firstStakedAt.toNumber()).toBe(before.firstStakedAt.toNumber());
+    expect(after.stakedAmount.toNumber()).toBe(LAMPORTS_PER_SOL);
+    expect(after.metadataVersion).toBe(before.metadataVersion);
+    expect(after.agentAddress.equals(agent.publicKey)).toBe(true);
+  });
+
+  test('migrating a current identity again changes nothing', async () => {
+    const agent = await preVersionAgent();
+    await migrate(agent);
+    const migrated = await accountData(identityPda(agent));
+
+    await migrate(agent);
+    expect((await accountData(identityPda(agent))).equals(migrated)).toBe(true);
+  });
+
+  test('an identity below the minimum version fails with UnsupportedAccountVersion', async () => {
+    const agent = await stakedAgent();
+    const account = (await context.banksClient.getAccount(identityPda(agent)))!;
+    const data = Buffer.from(account.data);
+    data[data.length - 1] = 0;
+    context.setAccount(identityPda(agent), { ...account, data });
+
+    await expect(heartbeat(agent)).rejects.toThrow(/UnsupportedAccountVersion/);
+
+    await migrate(agent);
+    expect((await fetchAccount(program, 'agentIdentity', identityPda(agent))).version).toBe(CURRENT_VERSION);
+    await heartbeat(agent);
+  });
+
+  test('the agent instructions work on a migrated identity', async () => {
+    const agent = await preVersionAgent();
+    await migrate(agent);
+    const identityAccounts = {
+      agentIdentity: identityPda(agent),
+      agent: agent.publicKey,
+      agentAddress: agent.publicKey,
+    };
+
+    const uri = 'https://example.com/agent-v2.json';
+    await program.methods.updateIdentity(uri, metadataHash(uri)).accounts(identityAccounts).signers([agent]).rpc();
+    await program.methods.updateCapabilities(1, { trading: {} }).accounts(identityAccounts).signers([agent]).rpc();
+    await program.methods.recordHeartbeat({ degraded: {} }).accounts(identityAccounts).signers([agent]).rpc();
+    await program.methods
+      .setDelegate(Keypair.generate().publicKey, 1)
+      .accounts(identityAccounts)
+      .signers([agent])
+      .rpc();
+    await program.methods.revokeDelegate().accounts(identityAccounts).signers([agent]).rpc();
+
+    await program.methods
+      .stakeCollateral(new BN(LAMPORTS_PER_SOL))
+      .accounts(stakeAccounts(agent))
+      .signers([agent])
+      .rpc();
+    await program.methods
+      .requestUnstake(new BN(LAMPORTS_PER_SOL))
+      .accounts(stakeAccounts(agent))
+      .signers([agent])
+      .rpc();
+
+    const statusIx = await program.methods
+      .verifyIdentity(false)
+      .accounts({ agentIdentity: identityPda(agent), agentAddress: agent.publicKey, stakingPool: stakingPoolPda })
+      .instruction();
+    const status = program.coder.types.decode('IdentityStatus', await simulateReturnData(context, statusIx));
+    expect(status.isActive).toBe(true);
+
+    const trustIx = await program.methods
+      .getTrustScore()
+      .accounts({ agentIdentity: identityPda(agent), stakingPool: stakingPoolPda, agentAddress: agent.publicKey })
+      .instruction();
+    const trust = program.coder.types.decode('TrustScoreBreakdown', await simulateReturnData(context, trustIx));
+    expect(trust.stakePoints).toBeGreaterThan(0);
+
+    await program.methods.deactivateAgent(new Array(32).fill(0)).accounts(identityAccounts).signers([agent]).rpc();
+    await program.methods
+      .reactivateAgent()
+      .accounts({
+        agentIdentity: identityPda(agent),
+        agentAddress: agent.publicKey,
+        config: null,
+        signer: agent.publicKey,
+      })
+      .signers([agent])
+      .rpc();
+
+    const identity = await fetchAccount(program, 'agentIdentity', identityPda(agent));
+    expect(identity.metadataUri).toBe(uri);
+    expect(identity.capabilities).toBe(1);
+    expect(identity.stakedAmount.toNumber()).toBe(2 * LAMPORTS_PER_SOL);
+    expect(identity.pendingUnstakeAmount.toNumber()).toBe(LAMPORTS_PER_SOL);
+    expect(identity.isActive).toBe(true);
+    expect(identity.version).toBe(CURRENT_VERSION);
+  });
+});
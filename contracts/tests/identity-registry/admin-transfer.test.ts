@@ -0,0 +1,133 @@
+/**
+ * Admin Transfer Tests
+ * Tests the two-step ProgramConfig admin handover
+ *
+ * Two-step transfer ensures:
+ * 1. Only the proposed admin can accept
+ * 2. The current admin can cancel a pending transfer
+ * 3. The current admin keeps full rights until the transfer is accepted
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+
+describe('Admin Transfer', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let admin: Keypair;
+  let configPda: PublicKey;
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  function propose(current: Keypair, newAdmin: PublicKey) {
+    return program.methods
+      .proposeAdmin(newAdmin)
+      .accounts({ config: configPda, currentAdmin: current.publicKey })
+      .signers([current])
+      .rpc();
+  }
+
+  function accept(signer: Keypair) {
+    return program.methods
+      .acceptAdmin()
+      .accounts({ config: configPda, newAdmin: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function cancel(current: Keypair) {
+    return program.methods
+      .cancelAdminTransfer()
+      .accounts({ config: configPda, currentAdmin: current.publicKey })
+      .signers([current])
+      .rpc();
+  }
+
+  function updateRateLimit(signer: Keypair, limit: number) {
+    return program.methods
+      .updateRateLimit(limit)
+      .accounts({ config: configPda, admin: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function config() {
+    return fetchAccount(program, 'programConfig', configPda);
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    admin = await fundedKeypair();
+    [configPda] = PublicKey.findProgramAddressSync([Buffer.from('program_config')], IDENTITY_PROGRAM_ID);
+
+    await program.methods
+      .initializeProgramConfig(60, new BN(7 * 24 * 60 * 60))
+      .accounts({ config: configPda, admin: admin.publicKey, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+  });
+
+  test('accepting with the wrong key fails', async () => {
+    const candidate = await fundedKeypair();
+    const impostor = await fundedKeypair();
+
+    await expect(accept(candidate)).rejects.toThrow(/NoPendingAdmin/);
+
+    await propose(admin, candidate.publicKey);
+    await expect(accept(impostor)).rejects.toThrow(/NotPendingAdmin/);
+
+    await cancel(admin);
+  });
+
+  test('the current admin can cancel a pending transfer', async () => {
+    const candidate = await fundedKeypair();
+    await propose(admin, candidate.publicKey);
+    expect((await config()).pendingAdmin.toBase58()).toBe(candidate.publicKey.toBase58());
+
+    await expect(cancel(candidate)).rejects.toThrow(/UnauthorizedAdmin/);
+    await cancel(admin);
+
+    expect((await config()).pendingAdmin.toBase58()).toBe(PublicKey.default.toBase58());
+    await expect(accept(candidate)).rejects.toThrow(/NoPendingAdmin/);
+  });
+
+  test('the old admin retains power until acceptance', async () => {
+    const candidate = await fundedKeypair();
+    await propose(admin, candidate.publicKey);
+
+    await updateRateLimit(admin, 30);
+    await expect(updateRateLimit(candidate, 30)).rejects.toThrow(/UnauthorizedAdmin/);
+
+    await accept(candidate);
+
+    const after = await config();
+    expect(after.admin.toBase58()).toBe(candidate.publicKey.toBase58());
+    expect(after.pendingAdmin.toBase58()).toBe(PublicKey.default.toBase58());
+    await expect(updateRateLimit(admin, 30)).rejects.toThrow(/UnauthorizedAdmin/);
+    await updateRateLimit(candidate, 30);
+
+    // Hand control back for any later tests
+    await propose(candidate, admin.publicKey);
+    await accept(admin);
+  });
+});
@@ -0,0 +1,129 @@
+/**
+ * Batch Registration Tests
+ * Tests register_agents_batch for fleet operators
+ *
+ * Batch registration ensures:
+ * 1. Every identity in a batch matches what register_agent would create
+ * 2. One invalid entry (e.g. an already-registered agent) fails the whole batch
+ * 3. Batches are capped at MAX_BATCH_REGISTRATIONS entries
+ *
+ * Five agents with their identities and assets exceed the legacy transaction
+ * size, so batches are sent as v0 transactions with a lookup table.
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  loadProgram,
+  lookupTable,
+  metadataHash,
+  mockCoreAsset,
+  processV0,
+} from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const MAX_BATCH_REGISTRATIONS = 5;
+const URI = 'ipfs://a';
+
+describe('Batch Registration', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function fleet(size: number) {
+    return Array.from({ length: size }, () => {
+      const agent = Keypair.generate();
+      return { agent, asset: mockCoreAsset(context, agent.publicKey) };
+    });
+  }
+
+  async function registerBatch(agents: { agent: Keypair; asset: PublicKey }[]) {
+    const remaining = agents.flatMap(({ agent, asset }) => [
+      { pubkey: agent.publicKey, isSigner: true, isWritable: false },
+      { pubkey: identityPda(agent.publicKey), isSigner: false, isWritable: true },
+      { pubkey: asset, isSigner: false, isWritable: false },
+    ]);
+    const ix = await program.methods
+      .registerAgentsBatch(
+        agents.map(({ asset }) => ({
+          assetAddress: asset,
+          metadataUri: URI,
+          metadataHash: metadataHash(URI),
+          transferable: false,
+        }))
+      )
+      .accounts({ payer: context.payer.publicKey, systemProgram: SystemProgram.programId })
+      .remainingAccounts(remaining)
+      .instruction();
+
+    const table = await lookupTable(
+      context,
+      ix.keys.filter((key) => !key.isSigner).map((key) => key.pubkey)
+    );
+    await processV0(context, [ix], agents.map(({ agent }) => agent), [table]);
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+  });
+
+  test('a 5-agent batch registers every agent', async () => {
+    const agents = fleet(MAX_BATCH_REGISTRATIONS);
+    await registerBatch(agents);
+
+    for (const { agent, asset } of agents) {
+      const identity = await fetchAccount(program, 'agentIdentity', identityPda(agent.publicKey));
+      expect(identity.agentAddress.toBase58()).toBe(agent.publicKey.toBase58());
+      expect(identity.assetAddress.toBase58()).toBe(asset.toBase58());
+      expect(identity.metadataUri).toBe(URI);
+      expect(identity.metadataHash).toEqual(metadataHash(URI));
+      expect(identity.metadataVersion).toBe(1);
+      expect(identity.activityCount.toNumber()).toBe(1);
+      expect(identity.isActive).toBe(true);
+    }
+  });
+
+  test('an already-registered agent fails the whole batch', async () => {
+    const agents = fleet(3);
+    const registered = agents[1];
+    await airdrop(context, registered.agent.publicKey, LAMPORTS_PER_SOL);
+    await program.methods
+      .registerAgent(registered.asset, URI, metadataHash(URI), 0, null, null, false)
+      .accounts({
+        agentIdentity: identityPda(registered.agent.publicKey),
+        agent: registered.agent.publicKey,
+        asset: registered.asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([registered.agent])
+      .rpc();
+
+    await expect(registerBatch(agents)).rejects.toThrow(/AgentAlreadyRegistered/);
+    for (const { agent } of [agents[0], agents[2]]) {
+      expect(await context.banksClient.getAccount(identityPda(agent.publicKey))).toBeNull();
+    }
+  });
+
+  test('batches over the cap are rejected', async () => {
+    const agents = fleet(MAX_BATCH_REGISTRATIONS + 1);
+
+    await expect(registerBatch(agents)).rejects.toThrow(/InvalidBatchSize/);
+    expect(await context.banksClient.getAccount(identityPda(agents[0].agent.publicKey))).toBeNull();
+  });
+});
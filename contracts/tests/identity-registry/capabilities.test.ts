@@ -0,0 +1,145 @@
+/**
+ * Capability Tests
+ * Tests capability flags and service categories on AgentIdentity
+ *
+ * Capabilities ensure:
+ * 1. register_agent stores the capability bitmask and service category
+ * 2. update_capabilities can set and clear them (owner or metadata delegate)
+ * 3. Bits outside the known capability mask are rejected
+ * 4. Both fields are exposed in the IDL for off-chain filtering
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+
+const INFERENCE = 1 << 0;
+const DATA_FEEDS = 1 << 1;
+const TRADING = 1 << 3;
+const UNKNOWN_BIT = 1 << 31;
+
+describe('Capabilities', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  async function register(capabilities: number, category: object | null): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, LAMPORTS_PER_SOL);
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        capabilities,
+        category,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    return agent;
+  }
+
+  function updateCapabilities(agent: Keypair, capabilities: number, category: object | null, signer = agent) {
+    return program.methods
+      .updateCapabilities(capabilities, category)
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: signer.publicKey,
+        agentAddress: agent.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function identity(agent: Keypair) {
+    return fetchAccount(program, 'agentIdentity', identityPda(agent.publicKey));
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+  });
+
+  test('registration sets capabilities and category', async () => {
+    const agent = await register(INFERENCE | DATA_FEEDS, { dataFeed: {} });
+
+    const account = await identity(agent);
+    expect(account.capabilities).toBe(INFERENCE | DATA_FEEDS);
+    expect(account.serviceCategory).toEqual({ dataFeed: {} });
+  });
+
+  test('update_capabilities sets and clears', async () => {
+    const agent = await register(INFERENCE, null);
+    expect((await identity(agent)).serviceCategory).toBeNull();
+
+    await updateCapabilities(agent, INFERENCE | TRADING, { trading: {} });
+    let account = await identity(agent);
+    expect(account.capabilities).toBe(INFERENCE | TRADING);
+    expect(account.serviceCategory).toEqual({ trading: {} });
+
+    await updateCapabilities(agent, 0, null);
+    account = await identity(agent);
+    expect(account.capabilities).toBe(0);
+    expect(account.serviceCategory).toBeNull();
+  });
+
+  test('unknown bits are rejected', async () => {
+    await expect(register(INFERENCE | UNKNOWN_BIT, null)).rejects.toThrow(/UnknownCapabilities/);
+
+    const agent = await register(INFERENCE, null);
+    await expect(updateCapabilities(agent, UNKNOWN_BIT, null)).rejects.toThrow(/UnknownCapabilities/);
+  });
+
+  test('only the owner or a metadata delegate can update', async () => {
+    const agent = await register(INFERENCE, null);
+    const operator = Keypair.generate();
+    await airdrop(context, operator.publicKey, LAMPORTS_PER_SOL);
+
+    await expect(updateCapabilities(agent, TRADING, null, operator)).rejects.toThrow(
+      /UnauthorizedCapabilityUpdate/
+    );
+
+    await program.methods
+      .setDelegate(operator.publicKey, 1)
+      .accounts({ agentIdentity: identityPda(agent.publicKey), agent: agent.publicKey, agentAddress: agent.publicKey })
+      .signers([agent])
+      .rpc();
+    await updateCapabilities(agent, TRADING, null, operator);
+    expect((await identity(agent)).capabilities).toBe(TRADING);
+  });
+
+  test('the IDL exposes the new fields', () => {
+    const agentIdentity = program.idl.types!.find((t) => t.name === 'AgentIdentity')!;
+    const fields = (agentIdentity.type as { fields: { name: string }[] }).fields.map((f) => f.name);
+    expect(fields).toEqual(expect.arrayContaining(['capabilities', 'service_category']));
+
+    const categories = program.idl.types!.find((t) => t.name === 'ServiceCategory')!;
+    const variants = (categories.type as { variants: { name: string }[] }).variants.map((v) => v.name);
+    expect(variants).toEqual(['Inference', 'DataFeed', 'CodeGeneration', 'Trading', 'Other']);
+  });
+});
@@ -0,0 +1,184 @@
+/**
+ * Category Minimum Stake Tests
+ * Tests per-service-category minimum stakes on the staking pool
+ *
+ * Category minimums ensure:
+ * 1. Deposits for an agent must meet its category's minimum when one is set
+ * 2. Categories without a minimum, and agents without a category, use the global minimum
+ * 3. Raising a minimum keeps existing stakes but is reflected in has_minimum_stake
+ * 4. Only the pool authority can set a category minimum
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset, simulateReturnData } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const MIN_STAKE = 100_000_000;
+
+describe('Category Minimum Stake', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let stakingPoolPda: PublicKey;
+
+  function identityPda(agent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('agent'), agent.publicKey.toBuffer()],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function stakeAccounts(agent: Keypair) {
+    return {
+      agentIdentity: identityPda(agent),
+      stakingPool: stakingPoolPda,
+      agent: agent.publicKey,
+      agentAddress: agent.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  /** Register a fresh agent in `category` (null for none) */
+  async function registeredAgent(category: object | null): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        category,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    return agent;
+  }
+
+  function stake(agent: Keypair, lamports: number) {
+    return program.methods
+      .stakeCollateral(new BN(lamports))
+      .accounts(stakeAccounts(agent))
+      .signers([agent])
+      .rpc();
+  }
+
+  function setCategoryMinStake(category: object, lamports: number, signer = authority) {
+    return program.methods
+      .setCategoryMinStake(category, new BN(lamports))
+      .accounts({ stakingPool: stakingPoolPda, authority: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function hasMinimumStake(agent: Keypair): Promise<boolean> {
+    const ix = await program.methods
+      .verifyIdentity(false)
+      .accounts({
+        agentIdentity: identityPda(agent),
+        agentAddress: agent.publicKey,
+        stakingPool: stakingPoolPda,
+      })
+      .instruction();
+    return program.coder.types.decode('IdentityStatus', await simulateReturnData(context, ix)).hasMinimumStake;
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    await program.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await setCategoryMinStake({ trading: {} }, LAMPORTS_PER_SOL);
+  });
+
+  test('deposits must meet the category minimum', async () => {
+    const agent = await registeredAgent({ trading: {} });
+
+    await expect(stake(agent, MIN_STAKE)).rejects.toThrow(/BelowMinimumStake/);
+    await stake(agent, LAMPORTS_PER_SOL);
+
+    expect((await fetchAccount(program, 'agentIdentity', identityPda(agent))).stakedAmount.toNumber()).toBe(
+      LAMPORTS_PER_SOL
+    );
+    expect(await hasMinimumStake(agent)).toBe(true);
+
+    const pool = await fetchAccount(program, 'stakingPool', stakingPoolPda);
+    expect(pool.categoryMinStakes[3].toNumber()).toBe(LAMPORTS_PER_SOL);
+  });
+
+  test('other categories and uncategorized agents use the global minimum', async () => {
+    const inference = await registeredAgent({ inference: {} });
+    await stake(inference, MIN_STAKE);
+    expect(await hasMinimumStake(inference)).toBe(true);
+
+    const uncategorized = await registeredAgent(null);
+    await expect(stake(uncategorized, MIN_STAKE - 1)).rejects.toThrow(/BelowMinimumStake/);
+    await stake(uncategorized, MIN_STAKE);
+    expect(await hasMinimumStake(uncategorized)).toBe(true);
+  });
+
+  test('raising a minimum keeps existing stakes', async () => {
+    const agent = await registeredAgent({ dataFeed: {} });
+    await stake(agent, MIN_STAKE);
+
+    await setCategoryMinStake({ dataFeed: {} }, 2 * LAMPORTS_PER_SOL);
+
+    // The stake stays in place and can still exit, but no longer meets the floor
+    expect(await hasMinimumStake(agent)).toBe(false);
+    await program.methods
+      .requestUnstake(new BN(MIN_STAKE))
+      .accounts(stakeAccounts(agent))
+      .signers([agent])
+      .rpc();
+
+    // Clearing the minimum falls back to the global floor
+    await setCategoryMinStake({ dataFeed: {} }, 0);
+    const identity = await fetchAccount(program, 'agentIdentity', identityPda(agent));
+    expect(identity.pendingUnstakeAmount.toNumber()).toBe(MIN_STAKE);
+  });
+
+  test('only the authority can set a minimum', async () => {
+    const outsider = Keypair.generate();
+    await airdrop(context, outsider.publicKey, LAMPORTS_PER_SOL);
+
+    await expect(setCategoryMinStake({ other: {} }, LAMPORTS_PER_SOL, outsider)).rejects.toThrow(
+      /UnauthorizedSlash/
+    );
+    await expect(setCategoryMinStake({ other: {} }, MIN_STAKE - 1)).rejects.toThrow(/InvalidCategoryMinimum/);
+  });
+});
@@ -0,0 +1,207 @@
+/**
+ * Close Identity Tests
+ * Tests reclaiming rent from a wound-down AgentIdentity
+ *
+ * Closing ensures:
+ * 1. Identities with staked or pending-unstake collateral cannot be closed
+ * 2. Active identities must be deactivated first
+ * 3. The reputation account must be closed first, and only once the identity is deactivated
+ * 4. The rent is returned to the agent wallet
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { airdrop, advanceTime, loadProgram, metadataHash, mockCoreAsset } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const STAKE_AMOUNT = 100_000_000;
+const UNLOCK_PERIOD = 7 * 24 * 60 * 60;
+
+describe('Close Identity', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let authority: Keypair;
+  let agent: Keypair;
+  let identityPda: PublicKey;
+  let reputationPda: PublicKey;
+  let stakingPoolPda: PublicKey;
+
+  function close(signer: Keypair, agentReputation: PublicKey = reputationPda) {
+    return identityProgram.methods
+      .closeIdentity()
+      .accounts({ agentIdentity: identityPda, agentReputation, agent: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function closeReputation() {
+    return reputationProgram.methods
+      .closeReputation()
+      .accounts({ agentReputation: reputationPda, agentIdentity: identityPda, agent: agent.publicKey })
+      .signers([agent])
+      .rpc();
+  }
+
+  function stakeAccounts() {
+    return {
+      agentIdentity: identityPda,
+      stakingPool: stakingPoolPda,
+      agent: agent.publicKey,
+      agentAddress: agent.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+
+    authority = Keypair.generate();
+    agent = Keypair.generate();
+    for (const kp of [authority, agent]) {
+      await airdrop(context, kp.publicKey, 10_000_000_000);
+    }
+
+    [identityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('agent'), agent.publicKey.toBuffer()],
+      IDENTITY_PROGRAM_ID
+    );
+    [reputationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), agent.publicKey.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    );
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+
+    await identityProgram.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda,
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda,
+        agentAddress: agent.publicKey,
+        authorityAccount: null,
+        initializer: agent.publicKey,
+        payer: agent.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    await identityProgram.methods
+      .stakeCollateral(new BN(STAKE_AMOUNT))
+      .accounts(stakeAccounts())
+      .signers([agent])
+      .rpc();
+  });
+
+  test('the reputation cannot be closed while the identity is active', async () => {
+    await expect(closeReputation()).rejects.toThrow(/IdentityStillActive/);
+  });
+
+  test('an active identity with stake cannot be closed', async () => {
+    await expect(close(agent)).rejects.toThrow(/StakeRemaining/);
+  });
+
+  test('a pending unstake blocks the close', async () => {
+    await identityProgram.methods
+      .requestUnstake(new BN(STAKE_AMOUNT))
+      .accounts(stakeAccounts())
+      .signers([agent])
+      .rpc();
+
+    await expect(close(agent)).rejects.toThrow(/StakeRemaining/);
+  });
+
+  test('an active identity cannot be closed after unstaking', async () => {
+    await advanceTime(context, UNLOCK_PERIOD);
+    await identityProgram.methods
+      .withdrawUnstaked()
+      .accounts(stakeAccounts())
+      .signers([agent])
+      .rpc();
+
+    await expect(close(agent)).rejects.toThrow(/IdentityStillActive/);
+  });
+
+  test('only the agent can close its identity', async () => {
+    await identityProgram.methods
+      .deactivateAgent(new Array(32).fill(0))
+      .accounts({ agentIdentity: identityPda, agent: agent.publicKey, agentAddress: agent.publicKey })
+      .signers([agent])
+      .rpc();
+
+    const stranger = Keypair.generate();
+    await airdrop(context, stranger.publicKey, 1_000_000_000);
+    await expect(close(stranger)).rejects.toThrow();
+  });
+
+  test('a live reputation account blocks the close', async () => {
+    await expect(close(agent)).rejects.toThrow(/ReputationNotClosed/);
+    await expect(close(agent, Keypair.generate().publicKey)).rejects.toThrow(/ConstraintSeeds/);
+  });
+
+  test('closing the reputation refunds its rent to the agent', async () => {
+    const reputationLamports = (await context.banksClient.getAccount(reputationPda))!.lamports;
+    const before = await context.banksClient.getBalance(agent.publicKey);
+
+    await closeReputation();
+
+    expect(await context.banksClient.getAccount(reputationPda)).toBeNull();
+    expect((await context.banksClient.getBalance(agent.publicKey)) - before).toBe(BigInt(reputationLamports));
+  });
+
+  test('a deactivated, unstaked identity closes and refunds rent', async () => {
+    const identityLamports = (await context.banksClient.getAccount(identityPda))!.lamports;
+    const before = await context.banksClient.getBalance(agent.publicKey);
+
+    await close(agent);
+
+    expect(await context.banksClient.getAccount(identityPda)).toBeNull();
+    // The provider wallet pays the fee, so the agent receives exactly the rent
+    const after = await context.banksClient.getBalance(agent.publicKey);
+    expect(after - before).toBe(BigInt(identityLamports));
+  });
+});
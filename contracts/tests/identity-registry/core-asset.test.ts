@@ -0,0 +1,122 @@
+/**
+ * Core Asset Verification Tests
+ * Tests that identities can only link Metaplex Core assets the agent owns
+ *
+ * Asset verification ensures:
+ * 1. register_agent rejects assets owned by someone else
+ * 2. register_agent rejects accounts that are not Core assets
+ * 3. update_asset re-links only to another asset the agent owns
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+
+describe('Core Asset Verification', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let agent: Keypair;
+  let other: Keypair;
+  let identityPda: PublicKey;
+
+  function register(asset: PublicKey, assetAddress: PublicKey = asset) {
+    return program.methods
+      .registerAgent(
+        assetAddress,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda,
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  function updateAsset(asset: PublicKey) {
+    return program.methods
+      .updateAsset()
+      .accounts({
+        agentIdentity: identityPda,
+        agent: agent.publicKey,
+        agentAddress: agent.publicKey,
+        asset,
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    agent = Keypair.generate();
+    other = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10_000_000_000);
+
+    [identityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('agent'), agent.publicKey.toBuffer()],
+      IDENTITY_PROGRAM_ID
+    );
+  });
+
+  test("registering someone else's asset fails", async () => {
+    const asset = mockCoreAsset(context, other.publicKey);
+
+    await expect(register(asset)).rejects.toThrow(/InvalidAssetOwnership/);
+  });
+
+  test('registering a non-Core account fails', async () => {
+    const fake = mockCoreAsset(context, agent.publicKey, SystemProgram.programId);
+
+    await expect(register(fake)).rejects.toThrow(/InvalidCoreAsset/);
+  });
+
+  test('the asset account must match asset_address', async () => {
+    const asset = mockCoreAsset(context, agent.publicKey);
+
+    await expect(register(asset, Keypair.generate().publicKey)).rejects.toThrow(/AssetAddressMismatch/);
+  });
+
+  test('registering an owned asset succeeds', async () => {
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await register(asset);
+
+    const identity = await fetchAccount(program, 'agentIdentity', identityPda);
+    expect(identity.assetAddress.toBase58()).toBe(asset.toBase58());
+  });
+
+  test('update_asset rejects an asset owned by someone else', async () => {
+    const asset = mockCoreAsset(context, other.publicKey);
+
+    await expect(updateAsset(asset)).rejects.toThrow(/InvalidAssetOwnership/);
+  });
+
+  test('update_asset re-links to a new owned asset', async () => {
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await updateAsset(asset);
+
+    const identity = await fetchAccount(program, 'agentIdentity', identityPda);
+    expect(identity.assetAddress.toBase58()).toBe(asset.toBase58());
+    await expect(updateAsset(asset)).rejects.toThrow(/AssetUnchanged/);
+  });
+});
@@ -0,0 +1,173 @@
+/**
+ * Deactivation Tests
+ * Tests the deactivation record and its effect on staking
+ *
+ * Deactivation ensures:
+ * 1. deactivate_agent records when, by whom and why (reason hash)
+ * 2. Deactivated agents cannot add stake but can still request and withdraw it
+ * 3. Reactivation clears the deactivation record
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, metadataHash, mockCoreAsset, now } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const UNLOCK_PERIOD = 7 * 24 * 60 * 60;
+const COOLDOWN_SECONDS = 72 * 60 * 60;
+
+const REASON_HASH = Array.from(createHash('sha256').update('Compromised operator key').digest());
+
+describe('Deactivation', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let stakingPoolPda: PublicKey;
+
+  function identityPda(agent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('agent'), agent.publicKey.toBuffer()],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function stakeAccounts(agent: Keypair) {
+    return {
+      agentIdentity: identityPda(agent),
+      stakingPool: stakingPoolPda,
+      agent: agent.publicKey,
+      agentAddress: agent.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  function stake(agent: Keypair, lamports: number) {
+    return program.methods
+      .stakeCollateral(new BN(lamports))
+      .accounts(stakeAccounts(agent))
+      .signers([agent])
+      .rpc();
+  }
+
+  /** Register a fresh agent, stake 1 SOL and deactivate it */
+  async function deactivatedAgent(): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    await stake(agent, LAMPORTS_PER_SOL);
+
+    await program.methods
+      .deactivateAgent(REASON_HASH)
+      .accounts({ agentIdentity: identityPda(agent), agent: agent.publicKey, agentAddress: agent.publicKey })
+      .signers([agent])
+      .rpc();
+    return agent;
+  }
+
+  function identity(agent: Keypair) {
+    return fetchAccount(program, 'agentIdentity', identityPda(agent));
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    const authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    await program.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('deactivation records when, by whom and why', async () => {
+    const agent = await deactivatedAgent();
+
+    const state = await identity(agent);
+    expect(state.isActive).toBe(false);
+    expect(state.deactivatedAt.toNumber()).toBe(await now(context));
+    expect(state.deactivatedBy.toBase58()).toBe(agent.publicKey.toBase58());
+    expect(state.deactivationReasonHash).toEqual(REASON_HASH);
+  });
+
+  test('deactivated agents cannot stake but can still exit', async () => {
+    const agent = await deactivatedAgent();
+
+    await expect(stake(agent, LAMPORTS_PER_SOL)).rejects.toThrow(/AgentDeactivated/);
+
+    await program.methods
+      .requestUnstake(new BN(LAMPORTS_PER_SOL))
+      .accounts(stakeAccounts(agent))
+      .signers([agent])
+      .rpc();
+    await advanceTime(context, UNLOCK_PERIOD);
+    await program.methods
+      .withdrawUnstaked()
+      .accounts(stakeAccounts(agent))
+      .signers([agent])
+      .rpc();
+
+    expect((await identity(agent)).stakedAmount.toNumber()).toBe(0);
+  });
+
+  test('reactivation clears the deactivation record', async () => {
+    const agent = await deactivatedAgent();
+    await advanceTime(context, COOLDOWN_SECONDS);
+
+    await program.methods
+      .reactivateAgent()
+      .accounts({
+        agentIdentity: identityPda(agent),
+        agentAddress: agent.publicKey,
+        config: null,
+        signer: agent.publicKey,
+      })
+      .signers([agent])
+      .rpc();
+
+    const state = await identity(agent);
+    expect(state.isActive).toBe(true);
+    expect(state.deactivatedAt.toNumber()).toBe(0);
+    expect(state.deactivatedBy.toBase58()).toBe(PublicKey.default.toBase58());
+    expect(state.deactivationReasonHash).toEqual(new Array(32).fill(0));
+
+    await stake(agent, LAMPORTS_PER_SOL);
+  });
+});
@@ -0,0 +1,279 @@
+/**
+ * Delegated Stake Tests
+ * Tests third-party backers staking SOL collateral for an agent
+ *
+ * Delegated stake ensures:
+ * 1. A backer's stake counts toward the agent's minimum stake
+ * 2. Slashes split between own and delegated stake in proportion
+ * 3. Backers withdraw through the same request/cooldown/withdraw flow
+ * 4. Only the backer can withdraw their delegated stake
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  simulateReturnData,
+} from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const UNLOCK_PERIOD = 7 * 24 * 60 * 60;
+
+describe('Delegated Stake', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let stakingPoolPda: PublicKey;
+
+  function identityPda(agent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('agent'), agent.publicKey.toBuffer()],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function delegatedStakePda(agent: Keypair, backer: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('delegated_stake'), agent.publicKey.toBuffer(), backer.publicKey.toBuffer()],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function slashRecordPda(agent: Keypair, index: number): PublicKey {
+    const indexBytes = Buffer.alloc(4);
+    indexBytes.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('slash'), agent.publicKey.toBuffer(), indexBytes],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  /** Register a fresh agent, staking `lamports` of its own when non-zero */
+  async function registeredAgent(lamports: number): Promise<Keypair> {
+    const agent = await fundedKeypair();
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    if (lamports > 0) {
+      await program.methods
+        .stakeCollateral(new BN(lamports))
+        .accounts({
+          agentIdentity: identityPda(agent),
+          stakingPool: stakingPoolPda,
+          agent: agent.publicKey,
+          agentAddress: agent.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([agent])
+        .rpc();
+    }
+
+    return agent;
+  }
+
+  function delegatedAccounts(agent: Keypair, backer: Keypair) {
+    return {
+      agentIdentity: identityPda(agent),
+      stakingPool: stakingPoolPda,
+      delegatedStake: delegatedStakePda(agent, backer),
+      agentAddress: agent.publicKey,
+      backer: backer.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  function stakeFor(agent: Keypair, backer: Keypair, lamports: number) {
+    return program.methods
+      .stakeForAgent(new BN(lamports))
+      .accounts(delegatedAccounts(agent, backer))
+      .signers([backer])
+      .rpc();
+  }
+
+  function requestDelegatedUnstake(agent: Keypair, backer: Keypair, lamports: number) {
+    return program.methods
+      .requestDelegatedUnstake(new BN(lamports))
+      .accounts(delegatedAccounts(agent, backer))
+      .signers([backer])
+      .rpc();
+  }
+
+  function withdrawDelegated(agent: Keypair, backer: Keypair) {
+    return program.methods
+      .withdrawDelegatedStake()
+      .accounts(delegatedAccounts(agent, backer))
+      .signers([backer])
+      .rpc();
+  }
+
+  function identity(agent: Keypair) {
+    return fetchAccount(program, 'agentIdentity', identityPda(agent));
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    authority = await fundedKeypair();
+
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    await program.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('third-party stake raises the effective stake', async () => {
+    const agent = await registeredAgent(0);
+    const backer = await fundedKeypair();
+
+    await stakeFor(agent, backer, LAMPORTS_PER_SOL);
+
+    const state = await identity(agent);
+    expect(state.stakedAmount.toNumber()).toBe(0);
+    expect(state.delegatedStake.toNumber()).toBe(LAMPORTS_PER_SOL);
+
+    const position = await fetchAccount(program, 'delegatedStake', delegatedStakePda(agent, backer));
+    expect(position.backer.toBase58()).toBe(backer.publicKey.toBase58());
+    expect(position.shares.toNumber()).toBe(LAMPORTS_PER_SOL);
+
+    const ix = await program.methods
+      .verifyIdentity(true)
+      .accounts({ agentIdentity: identityPda(agent), agentAddress: agent.publicKey })
+      .instruction();
+    const status = program.coder.types.decode('IdentityStatus', await simulateReturnData(context, ix));
+    expect(status.hasMinimumStake).toBe(true);
+
+    await expect(stakeFor(agent, agent, LAMPORTS_PER_SOL)).rejects.toThrow(/SelfDelegation/);
+  });
+
+  test('a slash splits proportionally between own and delegated stake', async () => {
+    const agent = await registeredAgent(LAMPORTS_PER_SOL);
+    const backer = await fundedKeypair();
+    await stakeFor(agent, backer, 3 * LAMPORTS_PER_SOL);
+
+    // 10000 bps severity slashes the 50% cap of the 4 SOL total
+    await program.methods
+      .slashAgent(10000, 'Served fabricated results')
+      .accounts({
+        agentIdentity: identityPda(agent),
+        stakingPool: stakingPoolPda,
+        slashRecord: slashRecordPda(agent, 0),
+        agentAddress: agent.publicKey,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    const state = await identity(agent);
+    expect(state.stakedAmount.toNumber()).toBe(LAMPORTS_PER_SOL / 2);
+    expect(state.delegatedStake.toNumber()).toBe((3 * LAMPORTS_PER_SOL) / 2);
+
+    const record = await fetchAccount(program, 'slashRecord', slashRecordPda(agent, 0));
+    expect(record.amount.toNumber()).toBe(2 * LAMPORTS_PER_SOL);
+    expect(record.delegatedAmount.toNumber()).toBe((3 * LAMPORTS_PER_SOL) / 2);
+
+    // The backer's shares are unchanged but now worth half as much
+    await requestDelegatedUnstake(agent, backer, (3 * LAMPORTS_PER_SOL) / 2);
+    await advanceTime(context, UNLOCK_PERIOD);
+    const before = await context.banksClient.getBalance(backer.publicKey);
+    await withdrawDelegated(agent, backer);
+    const after = await context.banksClient.getBalance(backer.publicKey);
+    expect(Number(after - before)).toBeGreaterThanOrEqual((3 * LAMPORTS_PER_SOL) / 2);
+  });
+
+  test('backer withdrawal respects the cooldown', async () => {
+    const agent = await registeredAgent(0);
+    const backer = await fundedKeypair();
+    await stakeFor(agent, backer, LAMPORTS_PER_SOL);
+
+    await expect(withdrawDelegated(agent, backer)).rejects.toThrow(/NoPendingUnstake/);
+
+    await requestDelegatedUnstake(agent, backer, LAMPORTS_PER_SOL / 2);
+    await advanceTime(context, UNLOCK_PERIOD - 60);
+    await expect(withdrawDelegated(agent, backer)).rejects.toThrow(/StakeLocked/);
+
+    await advanceTime(context, 60);
+    const before = await context.banksClient.getBalance(backer.publicKey);
+    await withdrawDelegated(agent, backer);
+    const after = await context.banksClient.getBalance(backer.publicKey);
+
+    expect(after - before).toBe(BigInt(LAMPORTS_PER_SOL / 2));
+    const state = await identity(agent);
+    expect(state.delegatedStake.toNumber()).toBe(LAMPORTS_PER_SOL / 2);
+    expect(state.pendingDelegatedShares.toNumber()).toBe(0);
+  });
+
+  test("the agent cannot withdraw a backer's funds", async () => {
+    const agent = await registeredAgent(0);
+    const backer = await fundedKeypair();
+    await stakeFor(agent, backer, LAMPORTS_PER_SOL);
+
+    // The agent has no position of its own, and its own stake excludes delegated funds
+    await expect(
+      program.methods
+        .requestDelegatedUnstake(new BN(LAMPORTS_PER_SOL))
+        .accounts({ ...delegatedAccounts(agent, backer), backer: agent.publicKey })
+        .signers([agent])
+        .rpc()
+    ).rejects.toThrow();
+    await expect(
+      program.methods
+        .requestUnstake(new BN(LAMPORTS_PER_SOL))
+        .accounts({
+          agentIdentity: identityPda(agent),
+          stakingPool: stakingPoolPda,
+          agent: agent.publicKey,
+          agentAddress: agent.publicKey,
+        })
+        .signers([agent])
+        .rpc()
+    ).rejects.toThrow(/InsufficientStake/);
+
+    expect((await identity(agent)).delegatedStake.toNumber()).toBe(LAMPORTS_PER_SOL);
+  });
+});
@@ -0,0 +1,208 @@
+/**
+ * Delegate Tests
+ * Tests operator keys signing on behalf of an agent identity
+ *
+ * Delegation ensures:
+ * 1. A delegate can sign only the instructions its scope flags allow
+ * 2. A delegate can never unstake, withdraw or manage delegation
+ * 3. A revoked delegate is rejected
+ * 4. The owner can always sign
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+
+const SCOPE_METADATA = 1 << 0;
+const SCOPE_STAKE = 1 << 2;
+
+describe('Delegates', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let stakingPoolPda: PublicKey;
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  /** Registered agent with 1 SOL staked */
+  async function stakedAgent(): Promise<Keypair> {
+    const agent = await fundedKeypair();
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    await stake(agent, agent);
+    return agent;
+  }
+
+  function setDelegate(owner: Keypair, delegate: PublicKey, scope: number) {
+    return program.methods
+      .setDelegate(delegate, scope)
+      .accounts({ agentIdentity: identityPda(owner.publicKey), agent: owner.publicKey, agentAddress: owner.publicKey })
+      .signers([owner])
+      .rpc();
+  }
+
+  function revokeDelegate(owner: Keypair) {
+    return program.methods
+      .revokeDelegate()
+      .accounts({ agentIdentity: identityPda(owner.publicKey), agent: owner.publicKey, agentAddress: owner.publicKey })
+      .signers([owner])
+      .rpc();
+  }
+
+  function updateMetadata(owner: Keypair, signer: Keypair, uri: string) {
+    return program.methods
+      .updateIdentity(uri, metadataHash(uri))
+      .accounts({
+        agentIdentity: identityPda(owner.publicKey),
+        agent: signer.publicKey,
+        agentAddress: owner.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  function stake(owner: Keypair, signer: Keypair) {
+    return program.methods
+      .stakeCollateral(new BN(LAMPORTS_PER_SOL))
+      .accounts({
+        agentIdentity: identityPda(owner.publicKey),
+        stakingPool: stakingPoolPda,
+        agent: signer.publicKey,
+        agentAddress: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  function requestUnstake(owner: Keypair, signer: Keypair) {
+    return program.methods
+      .requestUnstake(new BN(LAMPORTS_PER_SOL / 2))
+      .accounts({
+        agentIdentity: identityPda(owner.publicKey),
+        stakingPool: stakingPoolPda,
+        agent: signer.publicKey,
+        agentAddress: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    const authority = await fundedKeypair();
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    await program.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('a metadata delegate can update metadata but not unstake', async () => {
+    const owner = await stakedAgent();
+    const operator = await fundedKeypair();
+    await setDelegate(owner, operator.publicKey, SCOPE_METADATA);
+
+    await updateMetadata(owner, operator, 'https://example.com/operator.json');
+    const identity = await fetchAccount(program, 'agentIdentity', identityPda(owner.publicKey));
+    expect(identity.metadataUri).toBe('https://example.com/operator.json');
+    expect(identity.delegate.toBase58()).toBe(operator.publicKey.toBase58());
+
+    // Out of scope, and unstaking is owner-only regardless of scope
+    await expect(stake(owner, operator)).rejects.toThrow(/UnauthorizedAgent/);
+    await expect(requestUnstake(owner, operator)).rejects.toThrow();
+  });
+
+  test('a stake delegate can top up but never unstake', async () => {
+    const owner = await stakedAgent();
+    const operator = await fundedKeypair();
+    await setDelegate(owner, operator.publicKey, SCOPE_STAKE | SCOPE_METADATA);
+
+    await stake(owner, operator);
+    const identity = await fetchAccount(program, 'agentIdentity', identityPda(owner.publicKey));
+    expect(identity.stakedAmount.toNumber()).toBe(2 * LAMPORTS_PER_SOL);
+
+    await expect(requestUnstake(owner, operator)).rejects.toThrow();
+  });
+
+  test('a revoked delegate is rejected', async () => {
+    const owner = await stakedAgent();
+    const operator = await fundedKeypair();
+    await setDelegate(owner, operator.publicKey, SCOPE_METADATA);
+    await revokeDelegate(owner);
+
+    await expect(updateMetadata(owner, operator, 'https://example.com/stale.json')).rejects.toThrow(
+      /UnauthorizedUpdate/
+    );
+    await expect(revokeDelegate(owner)).rejects.toThrow(/NoDelegate/);
+  });
+
+  test('the owner always works and only the owner manages delegation', async () => {
+    const owner = await stakedAgent();
+    const operator = await fundedKeypair();
+    await setDelegate(owner, operator.publicKey, SCOPE_METADATA);
+
+    await updateMetadata(owner, owner, 'https://example.com/owner.json');
+    await stake(owner, owner);
+    await requestUnstake(owner, owner);
+
+    // The delegate cannot re-delegate to itself or anyone else
+    await expect(
+      program.methods
+        .setDelegate(operator.publicKey, SCOPE_STAKE)
+        .accounts({
+          agentIdentity: identityPda(owner.publicKey),
+          agent: operator.publicKey,
+          agentAddress: owner.publicKey,
+        })
+        .signers([operator])
+        .rpc()
+    ).rejects.toThrow();
+    await expect(setDelegate(owner, operator.publicKey, 1 << 7)).rejects.toThrow(/InvalidDelegateScope/);
+  });
+});
@@ -0,0 +1,172 @@
+/**
+ * Heartbeat Tests
+ * Tests record_heartbeat liveness updates and its anti-spam interval
+ *
+ * Heartbeats ensure:
+ * 1. A heartbeat refreshes last_active_timestamp and bumps activity_count
+ * 2. Heartbeats closer together than the configured interval are rejected
+ * 3. Delegates with the activity scope can heartbeat; other delegates cannot
+ * 4. An optional status byte records healthy/degraded on the identity
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, metadataHash, mockCoreAsset, now } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const HOUR = 60 * 60;
+
+const SCOPE_METADATA = 1 << 0;
+const SCOPE_ACTIVITY = 1 << 1;
+
+describe('Heartbeat', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let admin: Keypair;
+  let configPda: PublicKey;
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  async function registeredAgent(): Promise<Keypair> {
+    const agent = await fundedKeypair();
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    return agent;
+  }
+
+  function heartbeat(owner: Keypair, status: object | null = null, signer = owner) {
+    return program.methods
+      .recordHeartbeat(status)
+      .accounts({
+        agentIdentity: identityPda(owner.publicKey),
+        agent: signer.publicKey,
+        agentAddress: owner.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  function setDelegate(owner: Keypair, delegate: PublicKey, scope: number) {
+    return program.methods
+      .setDelegate(delegate, scope)
+      .accounts({ agentIdentity: identityPda(owner.publicKey), agent: owner.publicKey, agentAddress: owner.publicKey })
+      .signers([owner])
+      .rpc();
+  }
+
+  function identity(agent: Keypair) {
+    return fetchAccount(program, 'agentIdentity', identityPda(agent.publicKey));
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    admin = await fundedKeypair();
+    [configPda] = PublicKey.findProgramAddressSync([Buffer.from('program_config')], IDENTITY_PROGRAM_ID);
+    await program.methods
+      .initializeProgramConfig(60, new BN(7 * 24 * HOUR))
+      .accounts({ config: configPda, admin: admin.publicKey, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+  });
+
+  test('a heartbeat updates activity and health', async () => {
+    const agent = await registeredAgent();
+    const before = await identity(agent);
+    await advanceTime(context, 10 * HOUR);
+
+    await heartbeat(agent, { degraded: {} });
+
+    const after = await identity(agent);
+    expect(after.lastHeartbeatAt.toNumber()).toBe(await now(context));
+    expect(after.lastActiveTimestamp.toNumber()).toBe(await now(context));
+    expect(after.activityCount.toNumber()).toBe(before.activityCount.toNumber() + 1);
+    expect(after.healthStatus).toEqual({ degraded: {} });
+
+    // A heartbeat without a status keeps the last reported one
+    await advanceTime(context, HOUR);
+    await heartbeat(agent);
+    expect((await identity(agent)).healthStatus).toEqual({ degraded: {} });
+  });
+
+  test('a second heartbeat within the interval fails', async () => {
+    const agent = await registeredAgent();
+    await heartbeat(agent);
+
+    await advanceTime(context, HOUR - 60);
+    await expect(heartbeat(agent)).rejects.toThrow(/HeartbeatTooSoon/);
+
+    await advanceTime(context, 60);
+    await heartbeat(agent, { healthy: {} });
+  });
+
+  test('the interval is configurable', async () => {
+    await program.methods
+      .updateHeartbeatInterval(new BN(5 * 60))
+      .accounts({ config: configPda, admin: admin.publicKey })
+      .signers([admin])
+      .rpc();
+
+    const agent = await registeredAgent();
+    await heartbeat(agent);
+    await advanceTime(context, 5 * 60);
+    await heartbeat(agent);
+
+    await expect(
+      program.methods
+        .updateHeartbeatInterval(new BN(0))
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc()
+    ).rejects.toThrow(/InvalidHeartbeatInterval/);
+  });
+
+  test('only delegates with the activity scope can heartbeat', async () => {
+    const agent = await registeredAgent();
+    const operator = await fundedKeypair();
+
+    await setDelegate(agent, operator.publicKey, SCOPE_METADATA);
+    await expect(heartbeat(agent, null, operator)).rejects.toThrow(/UnauthorizedHeartbeat/);
+
+    await setDelegate(agent, operator.publicKey, SCOPE_ACTIVITY);
+    await heartbeat(agent, null, operator);
+    expect((await identity(agent)).lastHeartbeatAt.toNumber()).toBe(await now(context));
+  });
+});
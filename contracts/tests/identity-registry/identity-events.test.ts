@@ -0,0 +1,271 @@
+/**
+ * Identity Events Tests
+ * Tests the events identity_registry emits for indexers
+ *
+ * Identity events ensure:
+ * 1. Registration, metadata updates and deactivation each emit one typed event
+ * 2. Stake, unstake and slash events carry the new stake totals, so indexers need not re-fetch the identity
+ * 3. Pausing and unpausing staking emit StakingPaused with the new state
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram, Transaction, TransactionInstruction } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  now,
+  simulateEvents,
+} from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const UNLOCK_PERIOD = 7 * 24 * 60 * 60;
+const METADATA_URI = 'https://example.com/agent.json';
+
+describe('Identity Events', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let stakingPoolPda: PublicKey;
+
+  function identityPda(agent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('agent'), agent.publicKey.toBuffer()],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function slashRecordPda(agent: Keypair, index: number): PublicKey {
+    const indexBytes = Buffer.alloc(4);
+    indexBytes.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('slash'), agent.publicKey.toBuffer(), indexBytes],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function stakeAccounts(agent: Keypair) {
+    return {
+      agentIdentity: identityPda(agent),
+      stakingPool: stakingPoolPda,
+      agent: agent.publicKey,
+      agentAddress: agent.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  /**
+   * Simulate `ix` to decode the events it emits, then send it for real.
+   * Returns the single event, which must be named `name`.
+   */
+  async function emitted(ix: TransactionInstruction, signers: Keypair[], name: string) {
+    const events = await simulateEvents(context, program, ix, signers);
+    expect(events.map((event) => event.name)).toEqual([name]);
+
+    const tx = new Transaction().add(ix);
+    tx.recentBlockhash = context.lastBlockhash;
+    tx.feePayer = context.payer.publicKey;
+    tx.sign(context.payer, ...signers);
+    await context.banksClient.processTransaction(tx);
+    return events[0].data;
+  }
+
+  function registerIx(agent: Keypair, asset: PublicKey) {
+    return program.methods
+      .registerAgent(asset, METADATA_URI, metadataHash(METADATA_URI), 3, null, null, true)
+      .accounts({
+        agentIdentity: identityPda(agent),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  /** A fresh, registered agent */
+  async function registeredAgent(): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10 * LAMPORTS_PER_SOL);
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await emitted(await registerIx(agent, asset), [agent], 'agentRegistered');
+    return agent;
+  }
+
+  function stakeIx(agent: Keypair, lamports: number) {
+    return program.methods.stakeCollateral(new BN(lamports)).accounts(stakeAccounts(agent)).instruction();
+  }
+
+  async function poolTotal(): Promise<number> {
+    return (await fetchAccount(program, 'stakingPool', stakingPoolPda)).totalStaked.toNumber();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    await program.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('register_agent emits AgentRegistered with the new identity fields', async () => {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10 * LAMPORTS_PER_SOL);
+    const asset = mockCoreAsset(context, agent.publicKey);
+
+    const event = await emitted(await registerIx(agent, asset), [agent], 'agentRegistered');
+    const state = await fetchAccount(program, 'agentIdentity', identityPda(agent));
+    expect(event.agent.equals(agent.publicKey)).toBe(true);
+    expect(event.assetAddress.equals(asset)).toBe(true);
+    expect(event.referrer.equals(PublicKey.default)).toBe(true);
+    expect(event.metadataUri).toBe(METADATA_URI);
+    expect(Array.from(event.metadataHash as number[])).toEqual(metadataHash(METADATA_URI));
+    expect(event.capabilities).toBe(3);
+    expect(event.serviceCategory).toBeNull();
+    expect(event.transferable).toBe(true);
+    expect(event.expiresAt.toNumber()).toBe(state.expiresAt.toNumber());
+    expect(event.timestamp.toNumber()).toBe(state.registrationTimestamp.toNumber());
+  });
+
+  test('update_identity emits IdentityUpdated with the new metadata version', async () => {
+    const agent = await registeredAgent();
+    const uri = 'https://example.com/agent-v2.json';
+
+    const ix = await program.methods
+      .updateIdentity(uri, metadataHash(uri))
+      .accounts({ agentIdentity: identityPda(agent), agent: agent.publicKey, agentAddress: agent.publicKey })
+      .instruction();
+    const event = await emitted(ix, [agent], 'identityUpdated');
+
+    expect(event.agent.equals(agent.publicKey)).toBe(true);
+    expect(event.updatedBy.equals(agent.publicKey)).toBe(true);
+    expect(event.metadataUri).toBe(uri);
+    expect(Array.from(event.metadataHash as number[])).toEqual(metadataHash(uri));
+    expect(event.metadataVersion).toBe(2);
+    expect(event.timestamp.toNumber()).toBe(await now(context));
+  });
+
+  test('deactivate_agent emits AgentDeactivated', async () => {
+    const agent = await registeredAgent();
+    const reasonHash = Array.from(createHash('sha256').update('Retired').digest());
+
+    const ix = await program.methods
+      .deactivateAgent(reasonHash)
+      .accounts({ agentIdentity: identityPda(agent), agent: agent.publicKey, agentAddress: agent.publicKey })
+      .instruction();
+    const event = await emitted(ix, [agent], 'agentDeactivated');
+
+    expect(event.agent.equals(agent.publicKey)).toBe(true);
+    expect(event.deactivatedBy.equals(agent.publicKey)).toBe(true);
+    expect(Array.from(event.reasonHash as number[])).toEqual(reasonHash);
+    expect(event.stakedAmount.toNumber()).toBe(0);
+  });
+
+  test('stake and unstake events carry the agent and pool totals', async () => {
+    const agent = await registeredAgent();
+
+    const staked = await emitted(await stakeIx(agent, LAMPORTS_PER_SOL), [agent], 'collateralStaked');
+    expect(staked.agent.equals(agent.publicKey)).toBe(true);
+    expect(staked.stakedBy.equals(agent.publicKey)).toBe(true);
+    expect(staked.amount.toNumber()).toBe(LAMPORTS_PER_SOL);
+    expect(staked.stakedAmount.toNumber()).toBe(LAMPORTS_PER_SOL);
+    expect(staked.firstStakedAt.toNumber()).toBe(await now(context));
+    expect(staked.poolTotalStaked.toNumber()).toBe(await poolTotal());
+
+    const requestIx = await program.methods
+      .requestUnstake(new BN(LAMPORTS_PER_SOL / 4))
+      .accounts(stakeAccounts(agent))
+      .instruction();
+    const requested = await emitted(requestIx, [agent], 'unstakeRequested');
+    expect(requested.amount.toNumber()).toBe(LAMPORTS_PER_SOL / 4);
+    expect(requested.pendingUnstakeAmount.toNumber()).toBe(LAMPORTS_PER_SOL / 4);
+    expect(requested.unlockTimestamp.toNumber()).toBe(requested.timestamp.toNumber() + UNLOCK_PERIOD);
+
+    await advanceTime(context, UNLOCK_PERIOD);
+    const withdrawIx = await program.methods.withdrawUnstaked().accounts(stakeAccounts(agent)).instruction();
+    const unstaked = await emitted(withdrawIx, [agent], 'collateralUnstaked');
+    expect(unstaked.agent.equals(agent.publicKey)).toBe(true);
+    expect(unstaked.amount.toNumber()).toBe(LAMPORTS_PER_SOL / 4);
+    expect(unstaked.stakedAmount.toNumber()).toBe((3 * LAMPORTS_PER_SOL) / 4);
+    expect(unstaked.poolTotalStaked.toNumber()).toBe(await poolTotal());
+  });
+
+  test('slash_agent emits AgentSlashed with the post-slash stake', async () => {
+    const agent = await registeredAgent();
+    await emitted(await stakeIx(agent, LAMPORTS_PER_SOL), [agent], 'collateralStaked');
+    const reason = 'Served forged results';
+
+    // 10000 bps severity slashes the 50% cap
+    const ix = await program.methods
+      .slashAgent(10000, reason)
+      .accounts({
+        agentIdentity: identityPda(agent),
+        stakingPool: stakingPoolPda,
+        slashRecord: slashRecordPda(agent, 0),
+        agentAddress: agent.publicKey,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+    const event = await emitted(ix, [authority], 'agentSlashed');
+
+    const state = await fetchAccount(program, 'agentIdentity', identityPda(agent));
+    const record = await fetchAccount(program, 'slashRecord', slashRecordPda(agent, 0));
+    expect(event.agent.equals(agent.publicKey)).toBe(true);
+    expect(event.slashRecord.equals(slashRecordPda(agent, 0))).toBe(true);
+    expect(event.slashIndex).toBe(0);
+    expect(event.authority.equals(authority.publicKey)).toBe(true);
+    expect(event.severityBps).toBe(10000);
+    expect(event.amount.toNumber()).toBe(LAMPORTS_PER_SOL / 2);
+    expect(event.delegatedAmount.toNumber()).toBe(0);
+    expect(event.stakedAmount.toNumber()).toBe(state.stakedAmount.toNumber());
+    expect(event.delegatedStake.toNumber()).toBe(state.delegatedStake.toNumber());
+    expect(event.pendingUnstakeAmount.toNumber()).toBe(state.pendingUnstakeAmount.toNumber());
+    expect(event.slashCount).toBe(1);
+    const reasonHash = Array.from(createHash('sha256').update(reason).digest());
+    expect(Array.from(event.reasonHash as number[])).toEqual(reasonHash);
+    expect(event.appealDeadline.toNumber()).toBe(record.appealDeadline.toNumber());
+  });
+
+  test('pause_staking and unpause_staking emit StakingPaused', async () => {
+    const pauseIx = await program.methods
+      .pauseStaking()
+      .accounts({ stakingPool: stakingPoolPda, authority: authority.publicKey })
+      .instruction();
+    const paused = await emitted(pauseIx, [authority], 'stakingPaused');
+    expect(paused.authority.equals(authority.publicKey)).toBe(true);
+    expect(paused.isPaused).toBe(true);
+
+    const unpauseIx = await program.methods
+      .unpauseStaking()
+      .accounts({ stakingPool: stakingPoolPda, authority: authority.publicKey })
+      .instruction();
+    const unpaused = await emitted(unpauseIx, [authority], 'stakingPaused');
+    expect(unpaused.isPaused).toBe(false);
+    expect((await fetchAccount(program, 'stakingPool', stakingPoolPda)).isPaused).toBe(false);
+  });
+});
@@ -0,0 +1,297 @@
+/**
+ * Identity Expiry Tests
+ * Tests optional identity expiry and renewal
+ *
+ * Expiry ensures:
+ * 1. Registration stamps expires_at from the config's default validity
+ * 2. Expired identities are reported by verify_identity and cannot be voted on
+ * 3. renew_identity revives an expired identity without losing its history
+ * 4. A default validity of 0 disables expiry entirely
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  now,
+  simulateReturnData,
+} from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const VALIDITY = 30 * 24 * 60 * 60;
+
+const QUALITY = { responseQuality: 80, responseSpeed: 80, accuracy: 80, professionalism: 80 };
+
+describe('Identity Expiry', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let admin: Keypair;
+  let configPda: PublicKey;
+  let authorityPda: PublicKey;
+  let cpiAuthorityPda: PublicKey;
+  let receiptCount = 0;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  async function registerWithReputation(): Promise<Keypair> {
+    const wallet = Keypair.generate();
+    await airdrop(context, wallet.publicKey, 10 * LAMPORTS_PER_SOL);
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        agent: wallet.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda(wallet.publicKey),
+        agentAddress: wallet.publicKey,
+        authorityAccount: null,
+        initializer: wallet.publicKey,
+        payer: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+    return wallet;
+  }
+
+  function setValidity(seconds: number) {
+    return identityProgram.methods
+      .updateDefaultValidity(new BN(seconds))
+      .accounts({ config: configPda, admin: admin.publicKey })
+      .signers([admin])
+      .rpc();
+  }
+
+  function renew(agent: Keypair) {
+    return identityProgram.methods
+      .renewIdentity()
+      .accounts({ agentIdentity: identityPda(agent.publicKey), agent: agent.publicKey })
+      .signers([agent])
+      .rpc();
+  }
+
+  async function isExpired(agent: Keypair): Promise<boolean> {
+    const ix = await identityProgram.methods
+      .verifyIdentity(false)
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agentAddress: agent.publicKey,
+        stakingPool: null,
+      })
+      .instruction();
+    return identityProgram.coder.types.decode('IdentityStatus', await simulateReturnData(context, ix)).isExpired;
+  }
+
+  /** Give the voter the score needed to vote (score >= 100) */
+  async function makeEligibleVoter(voter: Keypair) {
+    await reputationProgram.methods
+      .updateReputation(
+        500,
+        { trust: 50, quality: 50, reliability: 50, economic: 50, social: 50 },
+        { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+        new Array(32).fill(0),
+        new BN(0),
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda(voter.publicKey),
+        authorityAccount: authorityPda,
+        agentAddress: voter.publicKey,
+        authority: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function vote(voter: Keypair, agent: Keypair) {
+    const signature = `expiry_sig_${receiptCount++}`;
+    const signatureHash = Array.from(createHash('sha256').update(signature).digest());
+    const [receiptPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('tx_receipt'),
+        voter.publicKey.toBuffer(),
+        agent.publicKey.toBuffer(),
+        Buffer.from(signatureHash),
+      ],
+      VOTE_PROGRAM_ID
+    );
+
+    await voteProgram.methods
+      .createTransactionReceipt(signature, signatureHash, new BN(1_000_000), { apiResponse: {} }, PublicKey.default)
+      .accounts({
+        receipt: receiptPda,
+        payerPubkey: voter.publicKey,
+        recipientPubkey: agent.publicKey,
+        creator: voter.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+    const [votePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('peer_vote'), receiptPda.toBuffer(), voter.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    );
+
+    await voteProgram.methods
+      .castPeerVote(agent.publicKey, { upvote: {} }, QUALITY, new Array(32).fill(0))
+      .accounts({
+        peerVote: votePda,
+        transactionReceipt: receiptPda,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(agent.publicKey),
+        votedAgentReputation: reputationPda(agent.publicKey),
+        reputationCpiAuthority: cpiAuthorityPda,
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    admin = Keypair.generate();
+    await airdrop(context, admin.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [configPda] = PublicKey.findProgramAddressSync([Buffer.from('program_config')], IDENTITY_PROGRAM_ID);
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    [cpiAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('reputation_cpi')], VOTE_PROGRAM_ID);
+
+    await identityProgram.methods
+      .initializeProgramConfig(60, new BN(7 * 24 * 60 * 60))
+      .accounts({ config: configPda, admin: admin.publicKey, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: admin.publicKey,
+        initializer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  test('a default validity of 0 disables expiry', async () => {
+    const agent = await registerWithReputation();
+
+    const identity = await fetchAccount(identityProgram, 'agentIdentity', identityPda(agent.publicKey));
+    expect(identity.expiresAt.toNumber()).toBe(0);
+
+    await advanceTime(context, 10 * VALIDITY);
+    expect(await isExpired(agent)).toBe(false);
+  });
+
+  test('registration sets expires_at, and expired agents cannot be voted on', async () => {
+    // Registered while expiry is off, so the voter never lapses
+    const voter = await registerWithReputation();
+    await setValidity(VALIDITY);
+    const agent = await registerWithReputation();
+
+    const registeredAt = await now(context);
+    const identity = await fetchAccount(identityProgram, 'agentIdentity', identityPda(agent.publicKey));
+    expect(identity.expiresAt.toNumber()).toBe(registeredAt + VALIDITY);
+
+    await advanceTime(context, VALIDITY + 1);
+    await makeEligibleVoter(voter);
+
+    expect(await isExpired(agent)).toBe(true);
+    await expect(vote(voter, agent)).rejects.toThrow(/VotedAgentNotActive/);
+
+    // Renewal revives the identity with its history intact
+    const activityBefore = identity.activityCount.toNumber();
+    await renew(agent);
+    const renewed = await fetchAccount(identityProgram, 'agentIdentity', identityPda(agent.publicKey));
+    expect(renewed.expiresAt.toNumber()).toBe((await now(context)) + VALIDITY);
+    expect(renewed.registrationTimestamp.toNumber()).toBe(identity.registrationTimestamp.toNumber());
+    expect(renewed.activityCount.toNumber()).toBe(activityBefore + 1);
+
+    expect(await isExpired(agent)).toBe(false);
+    await vote(voter, agent);
+  });
+
+  test('renewing with expiry disabled clears expires_at', async () => {
+    await setValidity(VALIDITY);
+    const agent = await registerWithReputation();
+    await setValidity(0);
+
+    await renew(agent);
+    const identity = await fetchAccount(identityProgram, 'agentIdentity', identityPda(agent.publicKey));
+    expect(identity.expiresAt.toNumber()).toBe(0);
+  });
+
+  test('only the admin sets the default validity', async () => {
+    const outsider = Keypair.generate();
+    await airdrop(context, outsider.publicKey, LAMPORTS_PER_SOL);
+
+    await expect(
+      identityProgram.methods
+        .updateDefaultValidity(new BN(VALIDITY))
+        .accounts({ config: configPda, admin: outsider.publicKey })
+        .signers([outsider])
+        .rpc()
+    ).rejects.toThrow(/UnauthorizedAdmin/);
+    await expect(setValidity(-1)).rejects.toThrow(/InvalidValidityPeriod/);
+  });
+});
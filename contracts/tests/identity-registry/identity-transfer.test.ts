@@ -0,0 +1,201 @@
+/**
+ * Identity Transfer Tests
+ * Tests soulbound and transferable identities and transfer_identity
+ *
+ * Transfer ensures:
+ * 1. A transferable identity moves to a PDA under the new owner with its history
+ * 2. The old PDA is closed, the delegate is cleared and a claimed name follows the identity
+ * 3. Soulbound identities reject transfer_identity
+ * 4. Identities still holding stake cannot be transferred
+ * 5. The new owner must co-sign, so nobody can push an identity onto a wallet
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+
+const SCOPE_METADATA = 1 << 0;
+
+describe('Identity Transfer', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let stakingPoolPda: PublicKey;
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function namePda(name: string): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('name'), Buffer.from(name)], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  async function register(transferable: boolean): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10 * LAMPORTS_PER_SOL);
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        transferable
+      )
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    return agent;
+  }
+
+  function transfer(agent: Keypair, newOwner: Keypair, nameRecord: PublicKey | null = null) {
+    return program.methods
+      .transferIdentity()
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        newIdentity: identityPda(newOwner.publicKey),
+        nameRecord,
+        agent: agent.publicKey,
+        newOwner: newOwner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent, newOwner])
+      .rpc();
+  }
+
+  function stakeAccounts(agent: Keypair) {
+    return {
+      agentIdentity: identityPda(agent.publicKey),
+      stakingPool: stakingPoolPda,
+      agent: agent.publicKey,
+      agentAddress: agent.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    const authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    await program.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('a transferable identity moves to the new owner', async () => {
+    const agent = await register(true);
+    const newOwner = Keypair.generate();
+    const operator = Keypair.generate().publicKey;
+
+    await program.methods
+      .setDelegate(operator, SCOPE_METADATA)
+      .accounts({ agentIdentity: identityPda(agent.publicKey), agent: agent.publicKey, agentAddress: agent.publicKey })
+      .signers([agent])
+      .rpc();
+    await program.methods
+      .claimName('transfer-me')
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        nameRecord: namePda('transfer-me'),
+        agent: agent.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    const before = await fetchAccount(program, 'agentIdentity', identityPda(agent.publicKey));
+
+    await transfer(agent, newOwner, namePda('transfer-me'));
+
+    expect(await context.banksClient.getAccount(identityPda(agent.publicKey))).toBeNull();
+
+    const after = await fetchAccount(program, 'agentIdentity', identityPda(newOwner.publicKey));
+    expect(after.agentAddress.toBase58()).toBe(newOwner.publicKey.toBase58());
+    expect(after.assetAddress.toBase58()).toBe(before.assetAddress.toBase58());
+    expect(after.registrationTimestamp.toNumber()).toBe(before.registrationTimestamp.toNumber());
+    expect(after.activityCount.toNumber()).toBe(before.activityCount.toNumber());
+    expect(after.transferable).toBe(true);
+    expect(after.delegate.toBase58()).toBe(PublicKey.default.toBase58());
+    expect(after.nameRecord.toBase58()).toBe(namePda('transfer-me').toBase58());
+
+    const name = await fetchAccount(program, 'nameRecord', namePda('transfer-me'));
+    expect(name.agent.toBase58()).toBe(newOwner.publicKey.toBase58());
+  });
+
+  test('soulbound identities cannot be transferred', async () => {
+    const agent = await register(false);
+
+    await expect(transfer(agent, Keypair.generate())).rejects.toThrow(/IdentityNotTransferable/);
+    const identity = await fetchAccount(program, 'agentIdentity', identityPda(agent.publicKey));
+    expect(identity.transferable).toBe(false);
+  });
+
+  test('outstanding stake blocks a transfer', async () => {
+    const agent = await register(true);
+    await program.methods
+      .stakeCollateral(new BN(LAMPORTS_PER_SOL))
+      .accounts(stakeAccounts(agent))
+      .signers([agent])
+      .rpc();
+
+    await expect(transfer(agent, Keypair.generate())).rejects.toThrow(/StakeRemaining/);
+
+    // A requested but unwithdrawn unstake still counts
+    await program.methods
+      .requestUnstake(new BN(LAMPORTS_PER_SOL))
+      .accounts(stakeAccounts(agent))
+      .signers([agent])
+      .rpc();
+    await expect(transfer(agent, Keypair.generate())).rejects.toThrow(/StakeRemaining/);
+  });
+
+  test('an identity cannot be transferred without the new owner signing', async () => {
+    const agent = await register(true);
+    const victim = Keypair.generate();
+
+    await expect(
+      program.methods
+        .transferIdentity()
+        .accounts({
+          agentIdentity: identityPda(agent.publicKey),
+          newIdentity: identityPda(victim.publicKey),
+          nameRecord: null,
+          agent: agent.publicKey,
+          newOwner: victim.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([agent])
+        .rpc()
+    ).rejects.toThrow();
+    expect(await context.banksClient.getAccount(identityPda(victim.publicKey))).toBeNull();
+    expect(await context.banksClient.getAccount(identityPda(agent.publicKey))).not.toBeNull();
+  });
+});
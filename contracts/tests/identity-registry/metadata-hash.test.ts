@@ -0,0 +1,107 @@
+/**
+ * Metadata Hash Tests
+ * Tests the metadata integrity hash stored alongside the metadata URI
+ *
+ * Metadata hashes ensure:
+ * 1. register_agent records the hash with metadata_version 1
+ * 2. update_identity replaces the hash and bumps the version
+ * 3. verify_metadata reports whether fetched metadata matches the record
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset, simulateReturnData } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+
+const METADATA_V1 = '{"name":"agent","version":1}';
+const METADATA_V2 = '{"name":"agent","version":2}';
+
+describe('Metadata Hash', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  async function register(): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, LAMPORTS_PER_SOL);
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(asset, 'https://example.com/agent.json', metadataHash(METADATA_V1), 0, null, null, false)
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    return agent;
+  }
+
+  async function verify(agent: Keypair, metadata: string) {
+    const ix = await program.methods
+      .verifyMetadata(metadataHash(metadata))
+      .accounts({ agentIdentity: identityPda(agent.publicKey), agentAddress: agent.publicKey })
+      .instruction();
+    return program.coder.types.decode('MetadataCheck', await simulateReturnData(context, ix));
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+  });
+
+  test('registration stores the hash at version 1', async () => {
+    const agent = await register();
+
+    const identity = await fetchAccount(program, 'agentIdentity', identityPda(agent.publicKey));
+    expect(identity.metadataHash).toEqual(metadataHash(METADATA_V1));
+    expect(identity.metadataVersion).toBe(1);
+  });
+
+  test('an update replaces the hash and bumps the version', async () => {
+    const agent = await register();
+
+    await program.methods
+      .updateIdentity('https://example.com/agent-v2.json', metadataHash(METADATA_V2))
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: agent.publicKey,
+        agentAddress: agent.publicKey,
+      })
+      .signers([agent])
+      .rpc();
+
+    const identity = await fetchAccount(program, 'agentIdentity', identityPda(agent.publicKey));
+    expect(identity.metadataUri).toBe('https://example.com/agent-v2.json');
+    expect(identity.metadataHash).toEqual(metadataHash(METADATA_V2));
+    expect(identity.metadataVersion).toBe(2);
+  });
+
+  test('verify_metadata reports matches and mismatches', async () => {
+    const agent = await register();
+
+    const ok = await verify(agent, METADATA_V1);
+    expect(ok.matches).toBe(true);
+
+    const tampered = await verify(agent, '{"name":"impostor","version":1}');
+    expect(tampered.matches).toBe(false);
+    expect(tampered.metadataHash).toEqual(metadataHash(METADATA_V1));
+    expect(tampered.metadataVersion).toBe(1);
+  });
+});
@@ -0,0 +1,278 @@
+/**
+ * Multisig Slashing Tests
+ * Tests slashing through reputation_registry's multisig instead of a single key
+ *
+ * Multisig slashing ensures:
+ * 1. A slash proposal only executes once it has quorum and the execution delay has passed
+ * 2. slash_agent_multisig can only be reached by CPI signed with the multisig PDA
+ * 3. Once legacy slashing is disabled, the staking authority cannot slash directly
+ * 4. Both paths apply the same quadratic severity curve
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, metadataHash, mockCoreAsset } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const EXECUTION_DELAY_SECONDS = 6 * 60 * 60;
+
+describe('Multisig Slashing', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let authority: Keypair;
+  let signers: Keypair[];
+  let stakingPoolPda: PublicKey;
+  let multisigPda: PublicKey;
+
+  const reasonHash = Array.from(createHash('sha256').update('Fabricated results').digest());
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function slashRecordPda(agent: PublicKey, index: number): PublicKey {
+    const indexBytes = Buffer.alloc(4);
+    indexBytes.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('slash'), agent.toBuffer(), indexBytes],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function proposalPda(id: number): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('proposal'), new BN(id).toArrayLike(Buffer, 'le', 8)],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  /** Register a fresh agent and stake 1 SOL */
+  async function stakedAgent(): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    await identityProgram.methods
+      .stakeCollateral(new BN(LAMPORTS_PER_SOL))
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        stakingPool: stakingPoolPda,
+        agent: agent.publicKey,
+        agentAddress: agent.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    return agent;
+  }
+
+  function legacySlash(agent: PublicKey, severityBps: number) {
+    return identityProgram.methods
+      .slashAgent(severityBps, 'Fabricated results')
+      .accounts({
+        agentIdentity: identityPda(agent),
+        stakingPool: stakingPoolPda,
+        slashRecord: slashRecordPda(agent, 0),
+        agentAddress: agent,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  async function proposeSlash(agent: PublicKey, severityBps: number): Promise<number> {
+    const multisig = await fetchAccount(reputationProgram, 'multisigAuthority', multisigPda);
+    const id = multisig.proposalCount.toNumber();
+    await reputationProgram.methods
+      .proposeSlash(severityBps, reasonHash)
+      .accounts({
+        multisig: multisigPda,
+        proposal: proposalPda(id),
+        targetAgent: agent,
+        proposer: signers[0].publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signers[0]])
+      .rpc();
+    return id;
+  }
+
+  function approve(id: number, signer: Keypair) {
+    return reputationProgram.methods
+      .approveProposal(new BN(id))
+      .accounts({ multisig: multisigPda, proposal: proposalPda(id), signer: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function executeSlash(id: number, agent: PublicKey) {
+    return reputationProgram.methods
+      .executeSlashProposal(new BN(id))
+      .accounts({
+        multisig: multisigPda,
+        proposal: proposalPda(id),
+        agentIdentity: identityPda(agent),
+        stakingPool: stakingPoolPda,
+        slashRecord: slashRecordPda(agent, 0),
+        targetAgent: agent,
+        executor: signers[0].publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signers[0]])
+      .rpc();
+  }
+
+  /** Propose, reach quorum, wait out the delay and execute */
+  async function multisigSlash(agent: PublicKey, severityBps: number) {
+    const id = await proposeSlash(agent, severityBps);
+    await approve(id, signers[1]);
+    await advanceTime(context, EXECUTION_DELAY_SECONDS);
+    await executeSlash(id, agent);
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+
+    authority = Keypair.generate();
+    signers = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    for (const kp of [authority, ...signers]) {
+      await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    }
+
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    [multisigPda] = PublicKey.findProgramAddressSync([Buffer.from('multisig_authority')], REPUTATION_PROGRAM_ID);
+
+    await identityProgram.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeMultisig(signers.map((s) => s.publicKey), 2)
+      .accounts({
+        multisig: multisigPda,
+        admin: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('severity math is the same on both paths', async () => {
+    const legacyAgent = await stakedAgent();
+    const multisigAgent = await stakedAgent();
+
+    await legacySlash(legacyAgent.publicKey, 7000);
+    await multisigSlash(multisigAgent.publicKey, 7000);
+
+    const legacy = await fetchAccount(identityProgram, 'slashRecord', slashRecordPda(legacyAgent.publicKey, 0));
+    const multisig = await fetchAccount(identityProgram, 'slashRecord', slashRecordPda(multisigAgent.publicKey, 0));
+    expect(multisig.amount.toString()).toBe(legacy.amount.toString());
+    expect(multisig.severityBps).toBe(7000);
+    expect(Array.from(multisig.reasonHash)).toEqual(reasonHash);
+    expect(multisig.status).toEqual({ escrowed: {} });
+  });
+
+  test('a slash proposal executes only with quorum', async () => {
+    const agent = await stakedAgent();
+    const id = await proposeSlash(agent.publicKey, 5000);
+
+    // Proposer's auto-approval is 1 of 2
+    await expect(executeSlash(id, agent.publicKey)).rejects.toThrow(/InsufficientApprovals/);
+
+    await approve(id, signers[1]);
+    await expect(executeSlash(id, agent.publicKey)).rejects.toThrow(/TimelockActive/);
+
+    await advanceTime(context, EXECUTION_DELAY_SECONDS);
+    await executeSlash(id, agent.publicKey);
+
+    const identity = await fetchAccount(identityProgram, 'agentIdentity', identityPda(agent.publicKey));
+    expect(identity.slashCount).toBe(1);
+    const proposal = await fetchAccount(reputationProgram, 'multisigProposal', proposalPda(id));
+    expect(proposal.status).toEqual({ executed: {} });
+  });
+
+  test('slash_agent_multisig rejects callers other than the multisig PDA', async () => {
+    const agent = await stakedAgent();
+    const impostor = Keypair.generate();
+    await airdrop(context, impostor.publicKey, LAMPORTS_PER_SOL);
+
+    await expect(
+      identityProgram.methods
+        .slashAgentMultisig(5000, reasonHash)
+        .accounts({
+          agentIdentity: identityPda(agent.publicKey),
+          stakingPool: stakingPoolPda,
+          slashRecord: slashRecordPda(agent.publicKey, 0),
+          agentAddress: agent.publicKey,
+          multisigAuthority: impostor.publicKey,
+          payer: impostor.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([impostor])
+        .rpc()
+    ).rejects.toThrow(/ConstraintSeeds/);
+  });
+
+  test('direct slashes fail once multisig mode is enabled', async () => {
+    const agent = await stakedAgent();
+
+    await identityProgram.methods
+      .disableLegacySlashing()
+      .accounts({ stakingPool: stakingPoolPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+    await expect(legacySlash(agent.publicKey, 5000)).rejects.toThrow(/LegacySlashingDisabled/);
+
+    await multisigSlash(agent.publicKey, 5000);
+    const identity = await fetchAccount(identityProgram, 'agentIdentity', identityPda(agent.publicKey));
+    expect(identity.slashCount).toBe(1);
+  });
+});
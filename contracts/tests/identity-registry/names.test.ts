@@ -0,0 +1,160 @@
+/**
+ * Name Record Tests
+ * Tests unique, human-readable agent names
+ *
+ * Names ensure:
+ * 1. A name maps to exactly one agent and an agent holds at most one name
+ * 2. Only normalized names (3-32 chars of a-z, 0-9, inner dashes) can be claimed
+ * 3. Releasing a name frees it for anyone to claim
+ * 4. A name resolves to its agent by deriving the ["name", name] PDA
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+
+describe('Name Records', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function namePda(name: string): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('name'), Buffer.from(name)], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  async function register(): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, LAMPORTS_PER_SOL);
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    return agent;
+  }
+
+  function claim(agent: Keypair, name: string) {
+    return program.methods
+      .claimName(name)
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        nameRecord: namePda(name),
+        agent: agent.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  function release(agent: Keypair, name: string) {
+    return program.methods
+      .releaseName()
+      .accounts({ agentIdentity: identityPda(agent.publicKey), nameRecord: namePda(name), agent: agent.publicKey })
+      .signers([agent])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+  });
+
+  test('a name resolves to its agent by PDA', async () => {
+    const agent = await register();
+    await claim(agent, 'alice-gpt');
+
+    const record = await fetchAccount(program, 'nameRecord', namePda('alice-gpt'));
+    expect(record.name).toBe('alice-gpt');
+    expect(record.agent.toBase58()).toBe(agent.publicKey.toBase58());
+
+    const identity = await fetchAccount(program, 'agentIdentity', identityPda(agent.publicKey));
+    expect(identity.nameRecord.toBase58()).toBe(namePda('alice-gpt').toBase58());
+  });
+
+  test('a duplicate claim fails', async () => {
+    const first = await register();
+    const second = await register();
+    await claim(first, 'bob-agent');
+
+    await expect(claim(second, 'bob-agent')).rejects.toThrow();
+    // One name per agent
+    await expect(claim(first, 'bob-agent-2')).rejects.toThrow(/NameAlreadyAssigned/);
+  });
+
+  test('invalid names are rejected', async () => {
+    const agent = await register();
+
+    for (const name of ['Alice', 'al', 'under_score', '-lead', 'trail-', 'dot.name']) {
+      await expect(claim(agent, name)).rejects.toThrow(/InvalidName/);
+    }
+  });
+
+  test('release frees the name for re-claim', async () => {
+    const first = await register();
+    const second = await register();
+    await claim(first, 'carol');
+
+    await release(first, 'carol');
+    expect(await context.banksClient.getAccount(namePda('carol'))).toBeNull();
+    const identity = await fetchAccount(program, 'agentIdentity', identityPda(first.publicKey));
+    expect(identity.nameRecord.toBase58()).toBe(PublicKey.default.toBase58());
+
+    await claim(second, 'carol');
+    const record = await fetchAccount(program, 'nameRecord', namePda('carol'));
+    expect(record.agent.toBase58()).toBe(second.publicKey.toBase58());
+  });
+
+  test('transfer moves the name to an agent without one', async () => {
+    const from = await register();
+    const to = await register();
+    await claim(from, 'dave-bot');
+
+    await program.methods
+      .transferName()
+      .accounts({
+        agentIdentity: identityPda(from.publicKey),
+        nameRecord: namePda('dave-bot'),
+        newAgentIdentity: identityPda(to.publicKey),
+        agent: from.publicKey,
+      })
+      .signers([from])
+      .rpc();
+
+    const record = await fetchAccount(program, 'nameRecord', namePda('dave-bot'));
+    expect(record.agent.toBase58()).toBe(to.publicKey.toBase58());
+    expect((await fetchAccount(program, 'agentIdentity', identityPda(to.publicKey))).nameRecord.toBase58()).toBe(
+      namePda('dave-bot').toBase58()
+    );
+    await expect(release(from, 'dave-bot')).rejects.toThrow(/NotNameOwner/);
+  });
+});
@@ -0,0 +1,135 @@
+/**
+ * Pause Expiry Tests
+ * Tests the max pause duration on ProgramConfig
+ *
+ * Pause expiry ensures:
+ * 1. A pause lapses on its own after max_pause_duration_seconds
+ * 2. Anyone can clear an expired pause with unpause_expired
+ * 3. Gated instructions run after expiry without waiting for that crank
+ * 4. The admin can extend a pause only by re-pausing with a new reason
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, metadataHash, mockCoreAsset } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const MAX_PAUSE_SECONDS = 24 * 60 * 60;
+
+describe('Pause Expiry', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let admin: Keypair;
+  let configPda: PublicKey;
+
+  function pause(reason: string) {
+    return program.methods
+      .pauseProgram(reason)
+      .accounts({ config: configPda, admin: admin.publicKey })
+      .signers([admin])
+      .rpc();
+  }
+
+  /** Permissionless; only the provider wallet pays the fee */
+  function unpauseExpired() {
+    return program.methods.unpauseExpired().accounts({ config: configPda }).rpc();
+  }
+
+  async function register() {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, LAMPORTS_PER_SOL);
+    const asset = mockCoreAsset(context, agent.publicKey);
+    return program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: PublicKey.findProgramAddressSync(
+          [Buffer.from('agent'), agent.publicKey.toBuffer()],
+          IDENTITY_PROGRAM_ID
+        )[0],
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  async function config() {
+    return fetchAccount(program, 'programConfig', configPda);
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    admin = Keypair.generate();
+    await airdrop(context, admin.publicKey, 10 * LAMPORTS_PER_SOL);
+    [configPda] = PublicKey.findProgramAddressSync([Buffer.from('program_config')], IDENTITY_PROGRAM_ID);
+
+    await program.methods
+      .initializeProgramConfig(60, new BN(MAX_PAUSE_SECONDS))
+      .accounts({ config: configPda, admin: admin.publicKey, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+  });
+
+  test('a pause auto-expires and can be cleared by anyone', async () => {
+    await pause('Incident 1');
+    await expect(unpauseExpired()).rejects.toThrow(/PauseNotExpired/);
+
+    await advanceTime(context, MAX_PAUSE_SECONDS);
+    await unpauseExpired();
+
+    const cleared = await config();
+    expect(cleared.isPaused).toBe(false);
+    expect(cleared.pauseReason).toBe('');
+    await expect(unpauseExpired()).rejects.toThrow(/ProgramNotPaused/);
+  });
+
+  test('gated instructions succeed after expiry without the crank', async () => {
+    await pause('Incident 2');
+    await expect(register()).rejects.toThrow(/ProgramPaused/);
+
+    await advanceTime(context, MAX_PAUSE_SECONDS);
+    await register();
+
+    // The flag is still set until someone cranks it
+    expect((await config()).isPaused).toBe(true);
+    await unpauseExpired();
+  });
+
+  test('re-pausing with a new reason resets the clock', async () => {
+    await pause('Incident 3');
+    await expect(pause('Incident 3')).rejects.toThrow(/PauseReasonReused/);
+
+    await advanceTime(context, MAX_PAUSE_SECONDS - 60);
+    await pause('Incident 3: extended pending audit');
+
+    await advanceTime(context, 120);
+    await expect(register()).rejects.toThrow(/ProgramPaused/);
+    await expect(unpauseExpired()).rejects.toThrow(/PauseNotExpired/);
+
+    await advanceTime(context, MAX_PAUSE_SECONDS);
+    await unpauseExpired();
+    await register();
+  });
+});
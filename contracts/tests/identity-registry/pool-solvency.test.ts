@@ -0,0 +1,199 @@
+/**
+ * Staking Pool Solvency Tests
+ * Tests lamport underflow protection on the StakingPool PDA
+ *
+ * Solvency checks ensure:
+ * 1. Pool lamports always cover total_staked plus the rent-exempt minimum
+ * 2. Withdrawals that would leave the pool below rent exemption fail
+ * 3. Slashes larger than the pool's real balance fail, whatever the bookkeeping says
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, metadataHash, mockCoreAsset } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const UNLOCK_PERIOD = 7 * 24 * 60 * 60;
+
+describe('Staking Pool Solvency', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let stakingPoolPda: PublicKey;
+  let agents: Keypair[];
+
+  function identityPda(agent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('agent'), agent.publicKey.toBuffer()],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function stakeAccounts(agent: Keypair) {
+    return {
+      agentIdentity: identityPda(agent),
+      stakingPool: stakingPoolPda,
+      agent: agent.publicKey,
+      agentAddress: agent.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  function slashRecordPda(agent: Keypair, index: number): PublicKey {
+    const indexBytes = Buffer.alloc(4);
+    indexBytes.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('slash'), agent.publicKey.toBuffer(), indexBytes],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  async function slash(agent: Keypair, severityBps: number) {
+    const identity = await fetchAccount(program, 'agentIdentity', identityPda(agent));
+    return program.methods
+      .slashAgent(severityBps, 'Solvency test')
+      .accounts({
+        agentIdentity: identityPda(agent),
+        stakingPool: stakingPoolPda,
+        slashRecord: slashRecordPda(agent, identity.slashCount),
+        agentAddress: agent.publicKey,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  async function poolBalances() {
+    const account = (await context.banksClient.getAccount(stakingPoolPda))!;
+    const pool = await fetchAccount(program, 'stakingPool', stakingPoolPda);
+    const rent = await context.banksClient.getRent();
+    return {
+      lamports: BigInt(account.lamports),
+      totalStaked: BigInt(pool.totalStaked.toString()),
+      rentExempt: rent.minimumBalance(BigInt(account.data.length)),
+      account,
+    };
+  }
+
+  /** Overwrite the pool's lamports to simulate a drained account */
+  function setPoolLamports(account: Awaited<ReturnType<typeof poolBalances>>['account'], lamports: bigint) {
+    context.setAccount(stakingPoolPda, { ...account, lamports: Number(lamports) });
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    authority = Keypair.generate();
+    agents = [Keypair.generate(), Keypair.generate()];
+    for (const kp of [authority, ...agents]) {
+      await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    }
+
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    await program.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    for (const agent of agents) {
+      const asset = mockCoreAsset(context, agent.publicKey);
+      await program.methods
+        .registerAgent(
+          asset,
+          'https://example.com/agent.json',
+          metadataHash('https://example.com/agent.json'),
+          0,
+          null,
+          null,
+          false
+        )
+        .accounts({
+          agentIdentity: identityPda(agent),
+          agent: agent.publicKey,
+          asset,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([agent])
+        .rpc();
+
+      await program.methods
+        .stakeCollateral(new BN(LAMPORTS_PER_SOL))
+        .accounts(stakeAccounts(agent))
+        .signers([agent])
+        .rpc();
+    }
+  });
+
+  test('pool lamports cover total_staked plus rent through stake, slash and withdraw', async () => {
+    const check = async () => {
+      const { lamports, totalStaked, rentExempt } = await poolBalances();
+      expect(lamports >= totalStaked + rentExempt).toBe(true);
+    };
+
+    await check();
+    await slash(agents[0], 5000);
+    await check();
+
+    await program.methods
+      .requestUnstake(new BN(LAMPORTS_PER_SOL / 2))
+      .accounts(stakeAccounts(agents[1]))
+      .signers([agents[1]])
+      .rpc();
+    await advanceTime(context, UNLOCK_PERIOD);
+    await program.methods
+      .withdrawUnstaked()
+      .accounts(stakeAccounts(agents[1]))
+      .signers([agents[1]])
+      .rpc();
+    await check();
+  });
+
+  test('a withdrawal that would dip below rent exemption fails', async () => {
+    await program.methods
+      .requestUnstake(new BN(LAMPORTS_PER_SOL / 2))
+      .accounts(stakeAccounts(agents[1]))
+      .signers([agents[1]])
+      .rpc();
+    await advanceTime(context, UNLOCK_PERIOD);
+
+    // Leave the pool holding exactly the pending amount and nothing for rent
+    const { account } = await poolBalances();
+    const original = BigInt(account.lamports);
+    setPoolLamports(account, BigInt(LAMPORTS_PER_SOL / 2));
+
+    await expect(
+      program.methods
+        .withdrawUnstaked()
+        .accounts(stakeAccounts(agents[1]))
+        .signers([agents[1]])
+        .rpc()
+    ).rejects.toThrow(/PoolBelowRentExempt/);
+
+    setPoolLamports((await poolBalances()).account, original);
+  });
+
+  test('a slash larger than the real pool balance fails', async () => {
+    const { account } = await poolBalances();
+    setPoolLamports(account, 1_000n);
+
+    await expect(slash(agents[0], 5000)).rejects.toThrow(/InsufficientPoolLamports/);
+  });
+});
@@ -0,0 +1,298 @@
+/**
+ * Program Guard Tests
+ * Tests that ProgramConfig pause and UserRateLimit are enforced inline
+ *
+ * Program guards ensure:
+ * 1. register_agent, update_identity, update_asset, stake_collateral,
+ *    request_unstake and withdraw_unstaked all fail while the program is paused
+ * 2. When the caller's rate limit PDA is supplied, each gated instruction
+ *    counts against the per-minute budget and fails once it is spent
+ * 3. Gated instructions succeed again after unpausing or once the window resets
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { airdrop, advanceTime, loadProgram, metadataHash, mockCoreAsset } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const UNLOCK_PERIOD = 7 * 24 * 60 * 60;
+const RATE_LIMIT_WINDOW = 60;
+
+type GatedCall = () => Promise<string>;
+
+describe('Program Guards', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let admin: Keypair;
+  let stakingPoolPda: PublicKey;
+  let configPda: PublicKey;
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function rateLimitPda(user: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('rate_limit'), user.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  /**
+   * The six gated instructions for `agent`, in an order that succeeds when run
+   * back to back on an agent with a cooled-down pending unstake.
+   * `fresh` is an unregistered wallet used for register_agent.
+   */
+  function gatedCalls(agent: Keypair, fresh: Keypair, withRateLimit: boolean): [string, GatedCall][] {
+    const rateLimit = (user: Keypair) => (withRateLimit ? rateLimitPda(user.publicKey) : null);
+    const stakeAccounts = {
+      agentIdentity: identityPda(agent.publicKey),
+      stakingPool: stakingPoolPda,
+      agent: agent.publicKey,
+      agentAddress: agent.publicKey,
+      systemProgram: SystemProgram.programId,
+      rateLimit: rateLimit(agent),
+    };
+
+    return [
+      [
+        'register_agent',
+        () => {
+          const asset = mockCoreAsset(context, fresh.publicKey);
+          return program.methods
+            .registerAgent(
+              asset,
+              'https://example.com/agent.json',
+              metadataHash('https://example.com/agent.json'),
+              0,
+              null,
+              null,
+              false
+            )
+            .accounts({
+              agentIdentity: identityPda(fresh.publicKey),
+              agent: fresh.publicKey,
+              asset,
+              systemProgram: SystemProgram.programId,
+              rateLimit: rateLimit(fresh),
+            })
+            .signers([fresh])
+            .rpc();
+        },
+      ],
+      [
+        'update_identity',
+        () =>
+          program.methods
+            .updateIdentity(
+              'https://example.com/agent-v2.json',
+              metadataHash('https://example.com/agent-v2.json')
+            )
+            .accounts({
+              agentIdentity: identityPda(agent.publicKey),
+              agent: agent.publicKey,
+              agentAddress: agent.publicKey,
+              rateLimit: rateLimit(agent),
+            })
+            .signers([agent])
+            .rpc(),
+      ],
+      [
+        'update_asset',
+        () =>
+          program.methods
+            .updateAsset()
+            .accounts({
+              agentIdentity: identityPda(agent.publicKey),
+              agent: agent.publicKey,
+              agentAddress: agent.publicKey,
+              asset: mockCoreAsset(context, agent.publicKey),
+              rateLimit: rateLimit(agent),
+            })
+            .signers([agent])
+            .rpc(),
+      ],
+      [
+        'withdraw_unstaked',
+        () => program.methods.withdrawUnstaked().accounts(stakeAccounts).signers([agent]).rpc(),
+      ],
+      [
+        'stake_collateral',
+        () =>
+          program.methods.stakeCollateral(new BN(LAMPORTS_PER_SOL)).accounts(stakeAccounts).signers([agent]).rpc(),
+      ],
+      [
+        'request_unstake',
+        () =>
+          program.methods
+            .requestUnstake(new BN(LAMPORTS_PER_SOL / 4))
+            .accounts(stakeAccounts)
+            .signers([agent])
+            .rpc(),
+      ],
+    ];
+  }
+
+  /** Registered agent with 1 SOL staked and a pending unstake that is ready to withdraw */
+  async function readyAgent(): Promise<Keypair> {
+    const agent = await fundedKeypair();
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    const stakeAccounts = {
+      agentIdentity: identityPda(agent.publicKey),
+      stakingPool: stakingPoolPda,
+      agent: agent.publicKey,
+      agentAddress: agent.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+    await program.methods.stakeCollateral(new BN(LAMPORTS_PER_SOL)).accounts(stakeAccounts).signers([agent]).rpc();
+    await program.methods
+      .requestUnstake(new BN(LAMPORTS_PER_SOL / 4))
+      .accounts(stakeAccounts)
+      .signers([agent])
+      .rpc();
+    await advanceTime(context, UNLOCK_PERIOD);
+
+    return agent;
+  }
+
+  async function initRateLimit(user: Keypair) {
+    await program.methods
+      .initializeUserRateLimit()
+      .accounts({
+        rateLimit: rateLimitPda(user.publicKey),
+        user: user.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  /** Spend the user's whole per-minute budget through check_rate_limit */
+  async function exhaustRateLimit(user: Keypair, budget: number) {
+    for (let i = 0; i < budget; i++) {
+      await program.methods
+        .checkRateLimit()
+        .accounts({ config: configPda, rateLimit: rateLimitPda(user.publicKey), user: user.publicKey })
+        .signers([user])
+        .rpc();
+    }
+  }
+
+  function setPaused(paused: boolean) {
+    const builder = paused ? program.methods.pauseProgram('Incident response') : program.methods.unpauseProgram();
+    return builder.accounts({ config: configPda, admin: admin.publicKey }).signers([admin]).rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    admin = await fundedKeypair();
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    [configPda] = PublicKey.findProgramAddressSync([Buffer.from('program_config')], IDENTITY_PROGRAM_ID);
+
+    await program.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  test('gated instructions run before a program config exists', async () => {
+    const agent = await readyAgent();
+    const fresh = await fundedKeypair();
+
+    for (const [, call] of gatedCalls(agent, fresh, false)) {
+      await call();
+    }
+  });
+
+  test('every gated instruction fails while paused', async () => {
+    await program.methods
+      .initializeProgramConfig(1, new BN(UNLOCK_PERIOD))
+      .accounts({ config: configPda, admin: admin.publicKey, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+
+    const agent = await readyAgent();
+    const fresh = await fundedKeypair();
+    const calls = gatedCalls(agent, fresh, false);
+
+    await setPaused(true);
+    for (const [name, call] of calls) {
+      await expect(call()).rejects.toThrow(/ProgramPaused/, `${name} should be paused`);
+    }
+
+    await setPaused(false);
+    for (const [, call] of calls) {
+      await call();
+    }
+  });
+
+  test('every gated instruction fails once the per-minute budget is spent', async () => {
+    const agent = await readyAgent();
+    const fresh = await fundedKeypair();
+    await initRateLimit(agent);
+    await initRateLimit(fresh);
+
+    // The config allows 1 instruction per minute
+    await exhaustRateLimit(agent, 1);
+    await exhaustRateLimit(fresh, 1);
+
+    const calls = gatedCalls(agent, fresh, true);
+    for (const [name, call] of calls) {
+      await expect(call()).rejects.toThrow(/RateLimitExceeded/, `${name} should be rate limited`);
+    }
+
+    // Each call opens a new window and uses its single slot
+    for (const [, call] of calls) {
+      await advanceTime(context, RATE_LIMIT_WINDOW);
+      await call();
+    }
+  });
+
+  test('omitting the rate limit account skips the budget check', async () => {
+    const agent = await readyAgent();
+    await initRateLimit(agent);
+    await exhaustRateLimit(agent, 1);
+
+    const [, updateIdentity] = gatedCalls(agent, agent, false)[1];
+    await updateIdentity();
+  });
+});
@@ -0,0 +1,152 @@
+/**
+ * Rate Limit Exemption Tests
+ * Tests the admin-managed ExemptAccount allowlist
+ *
+ * Rate limit exemptions ensure:
+ * 1. A fully exempt relayer is never counted against the program limit
+ * 2. A capped exemption replaces the program limit with its own cap
+ * 3. Revoking an exemption re-applies the program limit immediately
+ * 4. Only the admin can grant exemptions
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { ComputeBudgetProgram, Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const PROGRAM_LIMIT = 3;
+
+describe('Rate Limit Exemptions', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let admin: Keypair;
+  let configPda: PublicKey;
+  let sent = 0;
+
+  function exemptionPda(account: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('rate_exempt'), account.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function rateLimitPda(user: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('rate_limit'), user.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  /** Funded wallet with an initialized UserRateLimit */
+  async function relayer(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, LAMPORTS_PER_SOL);
+    await program.methods
+      .initializeUserRateLimit()
+      .accounts({ rateLimit: rateLimitPda(kp.publicKey), user: kp.publicKey, systemProgram: SystemProgram.programId })
+      .signers([kp])
+      .rpc();
+    return kp;
+  }
+
+  function grant(account: PublicKey, cap: number | null, signer: Keypair = admin) {
+    return program.methods
+      .grantRateExemption(cap)
+      .accounts({
+        config: configPda,
+        exemption: exemptionPda(account),
+        account,
+        admin: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  function revoke(account: PublicKey) {
+    return program.methods
+      .revokeRateExemption()
+      .accounts({ config: configPda, exemption: exemptionPda(account), admin: admin.publicKey })
+      .signers([admin])
+      .rpc();
+  }
+
+  /** Send `count` check_rate_limit calls within the same window */
+  async function burst(user: Keypair, count: number) {
+    for (let i = 0; i < count; i++) {
+      sent += 1;
+      await program.methods
+        .checkRateLimit()
+        .accounts({
+          config: configPda,
+          rateLimit: rateLimitPda(user.publicKey),
+          user: user.publicKey,
+          rateExemption: exemptionPda(user.publicKey),
+        })
+        .signers([user])
+        // A distinct compute limit keeps otherwise identical transactions unique
+        .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 200_000 + sent })])
+        .rpc();
+    }
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    admin = Keypair.generate();
+    await airdrop(context, admin.publicKey, 10 * LAMPORTS_PER_SOL);
+    [configPda] = PublicKey.findProgramAddressSync([Buffer.from('program_config')], IDENTITY_PROGRAM_ID);
+
+    await program.methods
+      .initializeProgramConfig(PROGRAM_LIMIT, new BN(7 * 24 * 60 * 60))
+      .accounts({ config: configPda, admin: admin.publicKey, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+  });
+
+  test('a fully exempt account blows past the limit', async () => {
+    const user = await relayer();
+    await grant(user.publicKey, null);
+
+    await burst(user, PROGRAM_LIMIT * 3);
+
+    // Exempt calls are not counted
+    const rateLimit = await fetchAccount(program, 'userRateLimit', rateLimitPda(user.publicKey));
+    expect(rateLimit.instructionCount).toBe(0);
+  });
+
+  test('a capped exemption enforces its own cap', async () => {
+    const user = await relayer();
+    await grant(user.publicKey, PROGRAM_LIMIT * 2);
+
+    await burst(user, PROGRAM_LIMIT * 2);
+    await expect(burst(user, 1)).rejects.toThrow(/RateLimitExceeded/);
+
+    const exemption = await fetchAccount(program, 'exemptAccount', exemptionPda(user.publicKey));
+    expect(exemption.maxPerMinute).toBe(PROGRAM_LIMIT * 2);
+  });
+
+  test('revocation immediately re-applies the limit', async () => {
+    const user = await relayer();
+    await grant(user.publicKey, null);
+    await burst(user, PROGRAM_LIMIT * 2);
+
+    await revoke(user.publicKey);
+    expect(await context.banksClient.getAccount(exemptionPda(user.publicKey))).toBeNull();
+
+    await burst(user, PROGRAM_LIMIT);
+    await expect(burst(user, 1)).rejects.toThrow(/RateLimitExceeded/);
+  });
+
+  test('non-admins cannot create exemptions', async () => {
+    const user = await relayer();
+    await expect(grant(user.publicKey, null, user)).rejects.toThrow(/UnauthorizedAdmin/);
+    await expect(grant(user.publicKey, 0)).rejects.toThrow(/InvalidExemptionCap/);
+  });
+});
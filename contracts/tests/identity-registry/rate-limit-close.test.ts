@@ -0,0 +1,113 @@
+/**
+ * Rate Limit Close Tests
+ * Tests closing UserRateLimit accounts to reclaim rent
+ *
+ * Closing ensures:
+ * 1. The user can close their rate limit account at any time
+ * 2. Anyone can close it once it has been idle for 30 days
+ * 3. Rent always goes back to the recorded payer
+ * 4. A closed account can be initialized again
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const IDLE_CLOSE_PERIOD = 30 * 24 * 60 * 60;
+
+describe('Rate Limit Close', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let cranker: Keypair;
+
+  function rateLimitPda(user: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('rate_limit'), user.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  function init(user: Keypair) {
+    return program.methods
+      .initializeUserRateLimit()
+      .accounts({ rateLimit: rateLimitPda(user.publicKey), user: user.publicKey, systemProgram: SystemProgram.programId })
+      .signers([user])
+      .rpc();
+  }
+
+  function close(user: Keypair, closer: Keypair) {
+    return program.methods
+      .closeUserRateLimit()
+      .accounts({ rateLimit: rateLimitPda(user.publicKey), payer: user.publicKey, closer: closer.publicKey })
+      .signers([closer])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+    cranker = await fundedKeypair();
+  });
+
+  test('the user can close at any time', async () => {
+    const user = await fundedKeypair();
+    await init(user);
+    const rateLimit = await fetchAccount(program, 'userRateLimit', rateLimitPda(user.publicKey));
+    expect(rateLimit.payer.toBase58()).toBe(user.publicKey.toBase58());
+
+    const rent = (await context.banksClient.getAccount(rateLimitPda(user.publicKey)))!.lamports;
+    const before = await context.banksClient.getBalance(user.publicKey);
+    await close(user, user);
+    const after = await context.banksClient.getBalance(user.publicKey);
+
+    expect(await context.banksClient.getAccount(rateLimitPda(user.publicKey))).toBeNull();
+    // The user also paid the transaction fee
+    expect(after - before).toBe(BigInt(rent) - 5000n);
+  });
+
+  test('anyone can close an idle account', async () => {
+    const user = await fundedKeypair();
+    await init(user);
+    await advanceTime(context, IDLE_CLOSE_PERIOD + 1);
+
+    const before = await context.banksClient.getBalance(user.publicKey);
+    await close(user, cranker);
+
+    expect(await context.banksClient.getAccount(rateLimitPda(user.publicKey))).toBeNull();
+    expect(await context.banksClient.getBalance(user.publicKey)).toBeGreaterThan(before);
+  });
+
+  test('a permissionless close before the idle period fails', async () => {
+    const user = await fundedKeypair();
+    await init(user);
+    await advanceTime(context, IDLE_CLOSE_PERIOD - 60);
+
+    await expect(close(user, cranker)).rejects.toThrow(/RateLimitNotIdle/);
+  });
+
+  test('a closed account can be re-initialized', async () => {
+    const user = await fundedKeypair();
+    await init(user);
+    await close(user, user);
+
+    await init(user);
+    const rateLimit = await fetchAccount(program, 'userRateLimit', rateLimitPda(user.publicKey));
+    expect(rateLimit.user.toBase58()).toBe(user.publicKey.toBase58());
+    expect(rateLimit.instructionCount).toBe(0);
+  });
+});
@@ -0,0 +1,287 @@
+/**
+ * Agent Reactivation Tests
+ * Tests restoring a deactivated identity
+ *
+ * Reactivation ensures:
+ * 1. The owner can reactivate only after the 72-hour cooldown
+ * 2. The ProgramConfig admin can reactivate immediately
+ * 3. Downstream checks (peer votes) work again once reactivated
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, metadataHash, mockCoreAsset, now } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+const COOLDOWN_SECONDS = 72 * 60 * 60;
+
+describe('Agent Reactivation', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let admin: Keypair;
+  let voter: Keypair;
+  let agent: Keypair;
+  let configPda: PublicKey;
+  let receiptPda: PublicKey;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  async function register(wallet: Keypair) {
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        agent: wallet.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda(wallet.publicKey),
+        agentAddress: wallet.publicKey,
+        authorityAccount: null,
+        initializer: wallet.publicKey,
+        payer: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+  }
+
+  function deactivate() {
+    return identityProgram.methods
+      .deactivateAgent(new Array(32).fill(0))
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: agent.publicKey,
+        agentAddress: agent.publicKey,
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  function reactivate(signer: Keypair, config: PublicKey | null) {
+    return identityProgram.methods
+      .reactivateAgent()
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agentAddress: agent.publicKey,
+        config,
+        signer: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  function vote() {
+    const [votePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('peer_vote'), receiptPda.toBuffer(), voter.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    );
+    return voteProgram.methods
+      .castPeerVote(
+        agent.publicKey,
+        { upvote: {} },
+        { responseQuality: 80, responseSpeed: 80, accuracy: 80, professionalism: 80 },
+        new Array(32).fill(0)
+      )
+      .accounts({
+        peerVote: votePda,
+        transactionReceipt: receiptPda,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(agent.publicKey),
+        votedAgentReputation: reputationPda(agent.publicKey),
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  async function isActive(): Promise<boolean> {
+    const identity = await fetchAccount(identityProgram, 'agentIdentity', identityPda(agent.publicKey));
+    return identity.isActive;
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    admin = Keypair.generate();
+    voter = Keypair.generate();
+    agent = Keypair.generate();
+    for (const kp of [admin, voter, agent]) {
+      await airdrop(context, kp.publicKey, 10_000_000_000);
+    }
+
+    [configPda] = PublicKey.findProgramAddressSync([Buffer.from('program_config')], IDENTITY_PROGRAM_ID);
+    await identityProgram.methods
+      .initializeProgramConfig(60, new BN(7 * 24 * 60 * 60))
+      .accounts({ config: configPda, admin: admin.publicKey, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+
+    const [authorityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('authority')],
+      REPUTATION_PROGRAM_ID
+    );
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: admin.publicKey,
+        initializer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await register(voter);
+    await register(agent);
+
+    // Voter needs a score >= 100 to vote
+    await advanceTime(context, 10);
+    await reputationProgram.methods
+      .updateReputation(
+        500,
+        { trust: 50, quality: 50, reliability: 50, economic: 50, social: 50 },
+        { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+        new Array(32).fill(0),
+        new BN(0),
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda(voter.publicKey),
+        authorityAccount: authorityPda,
+        agentAddress: voter.publicKey,
+        authority: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    const signature = 'reactivation_receipt';
+    const signatureHash = Array.from(createHash('sha256').update(signature).digest());
+    [receiptPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('tx_receipt'),
+        voter.publicKey.toBuffer(),
+        agent.publicKey.toBuffer(),
+        Buffer.from(signatureHash),
+      ],
+      VOTE_PROGRAM_ID
+    );
+    await voteProgram.methods
+      .createTransactionReceipt(signature, signatureHash, new BN(1_000_000), { apiResponse: {} }, PublicKey.default)
+      .accounts({
+        receipt: receiptPda,
+        payerPubkey: voter.publicKey,
+        recipientPubkey: agent.publicKey,
+        creator: voter.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+  });
+
+  test('votes against a deactivated agent fail', async () => {
+    await deactivate();
+
+    const identity = await fetchAccount(identityProgram, 'agentIdentity', identityPda(agent.publicKey));
+    expect(identity.deactivatedAt.toNumber()).toBeGreaterThan(0);
+    await expect(vote()).rejects.toThrow(/VotedAgentNotActive/);
+  });
+
+  test('owner cannot reactivate before the cooldown', async () => {
+    await advanceTime(context, COOLDOWN_SECONDS - 60);
+
+    await expect(reactivate(agent, null)).rejects.toThrow(/ReactivationCooldown/);
+    expect(await isActive()).toBe(false);
+  });
+
+  test('owner can reactivate after the cooldown', async () => {
+    const before = await fetchAccount(identityProgram, 'agentIdentity', identityPda(agent.publicKey));
+    await advanceTime(context, 60);
+
+    await reactivate(agent, null);
+
+    const identity = await fetchAccount(identityProgram, 'agentIdentity', identityPda(agent.publicKey));
+    expect(identity.isActive).toBe(true);
+    expect(identity.deactivatedAt.toNumber()).toBe(0);
+    expect(identity.activityCount.toNumber()).toBe(before.activityCount.toNumber() + 1);
+  });
+
+  test('votes work again after reactivation', async () => {
+    await vote();
+
+    const reputation = await fetchAccount(reputationProgram, 'agentReputation', reputationPda(agent.publicKey));
+    expect(reputation.stats.positiveVotes).toBe(1);
+  });
+
+  test('admin can reactivate immediately', async () => {
+    await deactivate();
+
+    await reactivate(admin, configPda);
+    expect(await isActive()).toBe(true);
+  });
+
+  test('strangers cannot reactivate', async () => {
+    await deactivate();
+    const stranger = Keypair.generate();
+    await airdrop(context, stranger.publicKey, 1_000_000_000);
+
+    await expect(reactivate(stranger, configPda)).rejects.toThrow(/UnauthorizedReactivation/);
+    await expect(reactivate(agent, null)).rejects.toThrow(/ReactivationCooldown/);
+  });
+
+  test('reactivating an active identity fails', async () => {
+    await reactivate(admin, configPda);
+
+    await expect(reactivate(admin, configPda)).rejects.toThrow(/AlreadyActive/);
+  });
+});
@@ -0,0 +1,131 @@
+/**
+ * Referral Tests
+ * Tests referrer tracking at registration
+ *
+ * Referrals ensure:
+ * 1. A valid referrer is stored on the new identity and its referral_count increments
+ * 2. An agent cannot refer itself
+ * 3. Only active identities can refer
+ * 4. Registering without a referrer still works
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset, simulateReturnData } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+
+describe('Referrals', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  async function register(agent: Keypair, referrer: PublicKey | null, referrerIdentity = referrer) {
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        referrer,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+        referrerIdentity: referrerIdentity ? identityPda(referrerIdentity) : null,
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  async function referrals(agent: PublicKey) {
+    const ix = await program.methods
+      .getReferrals()
+      .accounts({ agentIdentity: identityPda(agent), agentAddress: agent })
+      .instruction();
+    return program.coder.types.decode('ReferralSummary', await simulateReturnData(context, ix));
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+  });
+
+  test('a valid referral is recorded and counted', async () => {
+    const referrer = await fundedKeypair();
+    await register(referrer, null);
+
+    for (let i = 0; i < 2; i++) {
+      const referred = await fundedKeypair();
+      await register(referred, referrer.publicKey);
+      const identity = await fetchAccount(program, 'agentIdentity', identityPda(referred.publicKey));
+      expect(identity.referrer.toBase58()).toBe(referrer.publicKey.toBase58());
+    }
+
+    const summary = await referrals(referrer.publicKey);
+    expect(summary.referralCount).toBe(2);
+    expect(summary.referrer.toBase58()).toBe(PublicKey.default.toBase58());
+  });
+
+  test('self-referral fails', async () => {
+    const other = await fundedKeypair();
+    await register(other, null);
+    const agent = await fundedKeypair();
+
+    // Naming itself while passing another agent's identity is rejected outright
+    await expect(register(agent, agent.publicKey, other.publicKey)).rejects.toThrow(/InvalidReferrer/);
+    await expect(register(agent, agent.publicKey)).rejects.toThrow();
+  });
+
+  test('an inactive referrer fails', async () => {
+    const referrer = await fundedKeypair();
+    await register(referrer, null);
+    await program.methods
+      .deactivateAgent(new Array(32).fill(0))
+      .accounts({
+        agentIdentity: identityPda(referrer.publicKey),
+        agent: referrer.publicKey,
+        agentAddress: referrer.publicKey,
+      })
+      .signers([referrer])
+      .rpc();
+
+    const agent = await fundedKeypair();
+    await expect(register(agent, referrer.publicKey)).rejects.toThrow(/ReferrerNotActive/);
+    expect((await referrals(referrer.publicKey)).referralCount).toBe(0);
+  });
+
+  test('registration without a referrer is allowed', async () => {
+    const agent = await fundedKeypair();
+    await register(agent, null);
+
+    const summary = await referrals(agent.publicKey);
+    expect(summary.referrer.toBase58()).toBe(PublicKey.default.toBase58());
+    expect(summary.referralCount).toBe(0);
+  });
+});
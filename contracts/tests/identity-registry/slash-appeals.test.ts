@@ -0,0 +1,331 @@
+/**
+ * Slash Appeal Tests
+ * Tests escrowed slashes and the appeal window
+ *
+ * Slash escrow ensures:
+ * 1. Slashed lamports are held in the SlashRecord, not sent straight to a treasury
+ * 2. An upheld appeal returns the funds to the agent's stake
+ * 3. A denied appeal, or an unappealed slash after the window, releases
+ *    the funds to the pool's configured treasury and nowhere else
+ * 4. A denial settles the escrow without the agent's identity, so an appeal
+ *    is never stranded by a closed or transferred identity
+ * 5. The backers' part of an upheld slash goes to the agent once every backer
+ *    has exited, so a later backer cannot claim it
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, metadataHash, mockCoreAsset } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const APPEAL_WINDOW_SECONDS = 7 * 24 * 60 * 60;
+const UNLOCK_PERIOD = 7 * 24 * 60 * 60;
+
+describe('Slash Appeals', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let treasury: Keypair;
+  let stakingPoolPda: PublicKey;
+
+  function identityPda(agent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('agent'), agent.publicKey.toBuffer()],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function slashRecordPda(agent: Keypair, index = 0): PublicKey {
+    const indexBytes = Buffer.alloc(4);
+    indexBytes.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('slash'), agent.publicKey.toBuffer(), indexBytes],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function delegatedStakePda(agent: Keypair, backer: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('delegated_stake'), agent.publicKey.toBuffer(), backer.publicKey.toBuffer()],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function delegatedAccounts(agent: Keypair, backer: Keypair) {
+    return {
+      agentIdentity: identityPda(agent),
+      stakingPool: stakingPoolPda,
+      delegatedStake: delegatedStakePda(agent, backer),
+      agentAddress: agent.publicKey,
+      backer: backer.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  function stakeFor(agent: Keypair, backer: Keypair, lamports: number) {
+    return program.methods
+      .stakeForAgent(new BN(lamports))
+      .accounts(delegatedAccounts(agent, backer))
+      .signers([backer])
+      .rpc();
+  }
+
+  /**
+   * Register and stake 1 SOL, then slash half of it into escrow. With a
+   * backer, the backer stakes 1 SOL too and the slash takes half of the 2 SOL.
+   */
+  async function slashedAgent(backer?: Keypair): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    await program.methods
+      .stakeCollateral(new BN(LAMPORTS_PER_SOL))
+      .accounts({
+        agentIdentity: identityPda(agent),
+        stakingPool: stakingPoolPda,
+        agent: agent.publicKey,
+        agentAddress: agent.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    if (backer) {
+      await stakeFor(agent, backer, LAMPORTS_PER_SOL);
+    }
+
+    await program.methods
+      .slashAgent(10000, 'Disputed violation')
+      .accounts({
+        agentIdentity: identityPda(agent),
+        stakingPool: stakingPoolPda,
+        slashRecord: slashRecordPda(agent),
+        agentAddress: agent.publicKey,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    return agent;
+  }
+
+  function appeal(agent: Keypair, signer: Keypair = agent) {
+    return program.methods
+      .appealSlash()
+      .accounts({ slashRecord: slashRecordPda(agent), agent: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function resolve(agent: Keypair, uphold: boolean, withIdentity = true) {
+    return program.methods
+      .resolveAppeal(uphold)
+      .accounts({
+        slashRecord: slashRecordPda(agent),
+        agentIdentity: withIdentity ? identityPda(agent) : null,
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        treasury: treasury.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  function finalize(agent: Keypair, treasuryKey: PublicKey = treasury.publicKey) {
+    return program.methods
+      .finalizeSlash()
+      .accounts({ slashRecord: slashRecordPda(agent), stakingPool: stakingPoolPda, treasury: treasuryKey })
+      .rpc();
+  }
+
+  async function record(agent: Keypair) {
+    return fetchAccount(program, 'slashRecord', slashRecordPda(agent));
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    authority = Keypair.generate();
+    treasury = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    await program.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .updateSlashConfig(treasury.publicKey, new BN(APPEAL_WINDOW_SECONDS))
+      .accounts({ stakingPool: stakingPoolPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('slashed funds are escrowed in the record', async () => {
+    const agent = await slashedAgent();
+
+    const slash = await record(agent);
+    expect(slash.status).toEqual({ escrowed: {} });
+    expect(slash.amount.toNumber()).toBe(LAMPORTS_PER_SOL / 2);
+
+    const account = (await context.banksClient.getAccount(slashRecordPda(agent)))!;
+    const rent = await context.banksClient.getRent();
+    const rentExempt = rent.minimumBalance(BigInt(account.data.length));
+    expect(BigInt(account.lamports) - rentExempt).toBe(BigInt(LAMPORTS_PER_SOL / 2));
+    expect(await context.banksClient.getAccount(treasury.publicKey)).toBeNull();
+  });
+
+  test('appeal upheld returns funds to the stake', async () => {
+    const agent = await slashedAgent();
+    await appeal(agent);
+    expect((await record(agent)).status).toEqual({ appealed: {} });
+
+    await resolve(agent, true);
+
+    expect((await record(agent)).status).toEqual({ returned: {} });
+    const identity = await fetchAccount(program, 'agentIdentity', identityPda(agent));
+    expect(identity.stakedAmount.toNumber()).toBe(LAMPORTS_PER_SOL);
+    expect(identity.totalSlashed.toNumber()).toBe(0);
+  });
+
+  test('appeal denied releases funds to the treasury', async () => {
+    const agent = await slashedAgent();
+    await appeal(agent);
+    const before = await context.banksClient.getBalance(treasury.publicKey);
+
+    await resolve(agent, false);
+
+    expect((await record(agent)).status).toEqual({ released: {} });
+    const after = await context.banksClient.getBalance(treasury.publicKey);
+    expect(after - before).toBe(BigInt(LAMPORTS_PER_SOL / 2));
+    const identity = await fetchAccount(program, 'agentIdentity', identityPda(agent));
+    expect(identity.stakedAmount.toNumber()).toBe(LAMPORTS_PER_SOL / 2);
+  });
+
+  test('an appeal can be denied without the identity but not upheld', async () => {
+    const agent = await slashedAgent();
+    await appeal(agent);
+
+    await expect(resolve(agent, true, false)).rejects.toThrow(/AppealIdentityRequired/);
+
+    const before = await context.banksClient.getBalance(treasury.publicKey);
+    await resolve(agent, false, false);
+    const after = await context.banksClient.getBalance(treasury.publicKey);
+
+    expect(after - before).toBe(BigInt(LAMPORTS_PER_SOL / 2));
+    expect((await record(agent)).status).toEqual({ released: {} });
+  });
+
+  test('unappealed slashes auto-finalize after the window', async () => {
+    const agent = await slashedAgent();
+
+    await expect(finalize(agent)).rejects.toThrow(/AppealWindowActive/);
+
+    await advanceTime(context, APPEAL_WINDOW_SECONDS + 1);
+    await expect(appeal(agent)).rejects.toThrow(/AppealWindowClosed/);
+
+    const before = await context.banksClient.getBalance(treasury.publicKey);
+    await finalize(agent);
+    const after = await context.banksClient.getBalance(treasury.publicKey);
+
+    expect(after - before).toBe(BigInt(LAMPORTS_PER_SOL / 2));
+    expect((await record(agent)).status).toEqual({ released: {} });
+    await expect(finalize(agent)).rejects.toThrow(/SlashNotAppealable/);
+  });
+
+  test('the treasury must match the configured address', async () => {
+    const agent = await slashedAgent();
+    await advanceTime(context, APPEAL_WINDOW_SECONDS + 1);
+
+    await expect(finalize(agent, Keypair.generate().publicKey)).rejects.toThrow(/InvalidTreasury/);
+  });
+
+  test('only the slashed agent can appeal', async () => {
+    const agent = await slashedAgent();
+    const stranger = Keypair.generate();
+    await airdrop(context, stranger.publicKey, LAMPORTS_PER_SOL);
+
+    await expect(appeal(agent, stranger)).rejects.toThrow();
+    await expect(resolve(agent, true)).rejects.toThrow(/SlashNotAppealed/);
+  });
+
+  test('an upheld slash goes to the agent once every backer has exited', async () => {
+    const backer = Keypair.generate();
+    const latecomer = Keypair.generate();
+    for (const kp of [backer, latecomer]) {
+      await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    }
+    const agent = await slashedAgent(backer);
+    await appeal(agent);
+
+    const slash = await record(agent);
+    expect(slash.amount.toNumber()).toBe(LAMPORTS_PER_SOL);
+    expect(slash.delegatedAmount.toNumber()).toBe(LAMPORTS_PER_SOL / 2);
+
+    // The backer withdraws everything left before the appeal is resolved
+    await program.methods
+      .requestDelegatedUnstake(new BN(LAMPORTS_PER_SOL / 2))
+      .accounts(delegatedAccounts(agent, backer))
+      .signers([backer])
+      .rpc();
+    await advanceTime(context, UNLOCK_PERIOD);
+    await program.methods
+      .withdrawDelegatedStake()
+      .accounts(delegatedAccounts(agent, backer))
+      .signers([backer])
+      .rpc();
+    expect((await fetchAccount(program, 'agentIdentity', identityPda(agent))).delegatedShares.toNumber()).toBe(0);
+
+    await resolve(agent, true);
+
+    let identity = await fetchAccount(program, 'agentIdentity', identityPda(agent));
+    expect(identity.stakedAmount.toNumber()).toBe((3 * LAMPORTS_PER_SOL) / 2);
+    expect(identity.delegatedStake.toNumber()).toBe(0);
+
+    // A new backer's shares are worth exactly what they deposited
+    await stakeFor(agent, latecomer, LAMPORTS_PER_SOL);
+    identity = await fetchAccount(program, 'agentIdentity', identityPda(agent));
+    expect(identity.delegatedStake.toNumber()).toBe(LAMPORTS_PER_SOL);
+    const position = await fetchAccount(program, 'delegatedStake', delegatedStakePda(agent, latecomer));
+    expect(position.shares.toNumber()).toBe(LAMPORTS_PER_SOL);
+  });
+});
@@ -0,0 +1,199 @@
+/**
+ * Slash Record Tests
+ * Tests the on-chain SlashRecord PDA written by slash_agent
+ *
+ * Slash records ensure:
+ * 1. Every slash leaves a distinct, queryable record
+ * 2. Record contents match the slash (severity, amount, reason hash, authority)
+ * 3. Slash reasons are length-bounded
+ * 4. Records can only be closed once finalized and past the retention window
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  simulateReturnData,
+} from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const RETENTION_SECONDS = 365 * 24 * 60 * 60;
+const APPEAL_WINDOW_SECONDS = 7 * 24 * 60 * 60;
+
+describe('Slash Records', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let agent: Keypair;
+  let identityPda: PublicKey;
+  let stakingPoolPda: PublicKey;
+
+  function slashRecordPda(index: number): PublicKey {
+    const indexBytes = Buffer.alloc(4);
+    indexBytes.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('slash'), agent.publicKey.toBuffer(), indexBytes],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function slash(index: number, severityBps: number, reason: string) {
+    return program.methods
+      .slashAgent(severityBps, reason)
+      .accounts({
+        agentIdentity: identityPda,
+        stakingPool: stakingPoolPda,
+        slashRecord: slashRecordPda(index),
+        agentAddress: agent.publicKey,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  function sha256(text: string): number[] {
+    return Array.from(createHash('sha256').update(text).digest());
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    authority = Keypair.generate();
+    agent = Keypair.generate();
+    for (const kp of [authority, agent]) {
+      await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    }
+
+    [identityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('agent'), agent.publicKey.toBuffer()],
+      IDENTITY_PROGRAM_ID
+    );
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+
+    await program.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda,
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    await program.methods
+      .stakeCollateral(new BN(2 * LAMPORTS_PER_SOL))
+      .accounts({
+        agentIdentity: identityPda,
+        stakingPool: stakingPoolPda,
+        agent: agent.publicKey,
+        agentAddress: agent.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+  });
+
+  test('sequential slashes create distinct records', async () => {
+    await slash(0, 5000, 'Served stale data');
+    await slash(1, 10000, 'Repeated downtime');
+
+    const first = await fetchAccount(program, 'slashRecord', slashRecordPda(0));
+    const second = await fetchAccount(program, 'slashRecord', slashRecordPda(1));
+    expect(first.slashIndex).toBe(0);
+    expect(second.slashIndex).toBe(1);
+
+    const identity = await fetchAccount(program, 'agentIdentity', identityPda);
+    expect(identity.slashCount).toBe(2);
+  });
+
+  test('record contents match the slash', async () => {
+    const record = await fetchAccount(program, 'slashRecord', slashRecordPda(0));
+
+    // 5000 bps severity -> 25% of 2 SOL
+    expect(record.agent.toBase58()).toBe(agent.publicKey.toBase58());
+    expect(record.severityBps).toBe(5000);
+    expect(record.amount.toNumber()).toBe(LAMPORTS_PER_SOL / 2);
+    expect(Array.from(record.reasonHash)).toEqual(sha256('Served stale data'));
+    expect(record.authority.toBase58()).toBe(authority.publicKey.toBase58());
+    expect(record.timestamp.toNumber()).toBeGreaterThan(0);
+  });
+
+  test('slash reasons are length-bounded', async () => {
+    await expect(slash(2, 5000, 'x'.repeat(201))).rejects.toThrow(/SlashReasonTooLong/);
+  });
+
+  test('get_slash_history returns the passed records', async () => {
+    const ix = await program.methods
+      .getSlashHistory()
+      .accounts({ agentIdentity: identityPda, agentAddress: agent.publicKey })
+      .remainingAccounts(
+        [0, 1].map((i) => ({ pubkey: slashRecordPda(i), isSigner: false, isWritable: false }))
+      )
+      .instruction();
+
+    const view = program.coder.types.decode('SlashHistoryView', await simulateReturnData(context, ix));
+    expect(view.slashCount).toBe(2);
+    expect(view.records.map((r: { slashIndex: number }) => r.slashIndex)).toEqual([0, 1]);
+    expect(view.records[1].severityBps).toBe(10000);
+  });
+
+  test('records close only once finalized and past the retention window', async () => {
+    const close = () =>
+      program.methods
+        .closeSlashRecord()
+        .accounts({ slashRecord: slashRecordPda(0), authority: authority.publicKey })
+        .rpc();
+
+    await expect(close()).rejects.toThrow(/SlashNotFinalized/);
+
+    // Release the escrow after the appeal window (the pool authority is the default treasury)
+    await advanceTime(context, APPEAL_WINDOW_SECONDS + 1);
+    await program.methods
+      .finalizeSlash()
+      .accounts({ slashRecord: slashRecordPda(0), stakingPool: stakingPoolPda, treasury: authority.publicKey })
+      .rpc();
+    await expect(close()).rejects.toThrow(/SlashRecordRetentionActive/);
+
+    await advanceTime(context, RETENTION_SECONDS);
+    await close();
+    expect(await context.banksClient.getAccount(slashRecordPda(0))).toBeNull();
+  });
+});
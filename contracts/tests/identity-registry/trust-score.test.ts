@@ -0,0 +1,195 @@
+/**
+ * Trust Score Tests
+ * Tests the get_trust_score view
+ *
+ * The trust score ensures:
+ * 1. Agents without the minimum stake score 0
+ * 2. Stake size is log-scaled and stake age grows linearly up to a year
+ * 3. Every slash subtracts a fixed penalty
+ * 4. The pool authority can retune the weights
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  simulateReturnData,
+} from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const MIN_STAKE = 100_000_000;
+const ONE_YEAR = 365 * 24 * 60 * 60;
+
+describe('Trust Score', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let stakingPoolPda: PublicKey;
+
+  function identityPda(agent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('agent'), agent.publicKey.toBuffer()],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function slashRecordPda(agent: Keypair, index: number): PublicKey {
+    const indexBytes = Buffer.alloc(4);
+    indexBytes.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('slash'), agent.publicKey.toBuffer(), indexBytes],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  /** Register a fresh agent and stake `lamports` (0 to skip staking) */
+  async function agentWithStake(lamports: number): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, lamports + 10 * LAMPORTS_PER_SOL);
+
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    if (lamports > 0) {
+      await program.methods
+        .stakeCollateral(new BN(lamports))
+        .accounts({
+          agentIdentity: identityPda(agent),
+          stakingPool: stakingPoolPda,
+          agent: agent.publicKey,
+          agentAddress: agent.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([agent])
+        .rpc();
+    }
+
+    return agent;
+  }
+
+  async function trustScore(agent: Keypair) {
+    const ix = await program.methods
+      .getTrustScore()
+      .accounts({
+        agentIdentity: identityPda(agent),
+        stakingPool: stakingPoolPda,
+        agentAddress: agent.publicKey,
+      })
+      .instruction();
+    return program.coder.types.decode('TrustScoreBreakdown', await simulateReturnData(context, ix));
+  }
+
+  function setWeights(stake: number, age: number, penalty: number) {
+    return program.methods
+      .updateTrustWeights(stake, age, penalty)
+      .accounts({ stakingPool: stakingPoolPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    await program.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('zero stake scores 0', async () => {
+    const agent = await agentWithStake(0);
+
+    const score = await trustScore(agent);
+    expect(score.trustScore).toBe(0);
+    expect(score.stakePoints).toBe(0);
+  });
+
+  test('exactly the minimum stake earns one log step', async () => {
+    const agent = await agentWithStake(MIN_STAKE);
+
+    // Default stake weight 600 over 10 steps; no stake age yet
+    const score = await trustScore(agent);
+    expect(score.stakePoints).toBe(60);
+    expect(score.agePoints).toBe(0);
+    expect(score.trustScore).toBe(60);
+  });
+
+  test('a large, year-old stake with two slashes', async () => {
+    const agent = await agentWithStake(60 * LAMPORTS_PER_SOL);
+
+    // 1000 bps severity slashes 1%, leaving the stake above the top log step
+    for (const index of [0, 1]) {
+      await program.methods
+        .slashAgent(1000, 'Missed SLA')
+        .accounts({
+          agentIdentity: identityPda(agent),
+          stakingPool: stakingPoolPda,
+          slashRecord: slashRecordPda(agent, index),
+          agentAddress: agent.publicKey,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    }
+    await advanceTime(context, ONE_YEAR);
+
+    const score = await trustScore(agent);
+    expect(score.stakePoints).toBe(600);
+    expect(score.agePoints).toBe(400);
+    expect(score.slashPenalty).toBe(300);
+    expect(score.trustScore).toBe(700);
+
+    await setWeights(800, 200, 50);
+    const retuned = await trustScore(agent);
+    expect(retuned.stakePoints).toBe(800);
+    expect(retuned.agePoints).toBe(200);
+    expect(retuned.slashPenalty).toBe(100);
+    expect(retuned.trustScore).toBe(900);
+  });
+
+  test('weights cannot exceed the maximum score', async () => {
+    await expect(setWeights(700, 400, 0)).rejects.toThrow(/InvalidTrustWeights/);
+  });
+});
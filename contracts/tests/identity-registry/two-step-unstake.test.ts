@@ -0,0 +1,217 @@
+/**
+ * Two-Step Unstake Tests
+ * Tests request_unstake / withdraw_unstaked for SOL collateral
+ *
+ * Two-step unstaking ensures:
+ * 1. Staking no longer starts an unlock clock; exits must be requested
+ * 2. Withdrawals only succeed after the cooldown from the latest request
+ * 3. Pending unstakes remain slashable until withdrawn
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, metadataHash, mockCoreAsset } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const UNLOCK_PERIOD = 7 * 24 * 60 * 60;
+
+describe('Two-Step Unstake', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let stakingPoolPda: PublicKey;
+
+  function identityPda(agent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('agent'), agent.publicKey.toBuffer()],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function slashRecordPda(agent: Keypair, index: number): PublicKey {
+    const indexBytes = Buffer.alloc(4);
+    indexBytes.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('slash'), agent.publicKey.toBuffer(), indexBytes],
+      IDENTITY_PROGRAM_ID
+    )[0];
+  }
+
+  function stakeAccounts(agent: Keypair) {
+    return {
+      agentIdentity: identityPda(agent),
+      stakingPool: stakingPoolPda,
+      agent: agent.publicKey,
+      agentAddress: agent.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  /** Register a fresh agent and stake `lamports` */
+  async function stakedAgent(lamports: number): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await program.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    await program.methods
+      .stakeCollateral(new BN(lamports))
+      .accounts(stakeAccounts(agent))
+      .signers([agent])
+      .rpc();
+
+    return agent;
+  }
+
+  function requestUnstake(agent: Keypair, lamports: number) {
+    return program.methods
+      .requestUnstake(new BN(lamports))
+      .accounts(stakeAccounts(agent))
+      .signers([agent])
+      .rpc();
+  }
+
+  function withdraw(agent: Keypair) {
+    return program.methods
+      .withdrawUnstaked()
+      .accounts(stakeAccounts(agent))
+      .signers([agent])
+      .rpc();
+  }
+
+  function identity(agent: Keypair) {
+    return fetchAccount(program, 'agentIdentity', identityPda(agent));
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'identity_registry', programId: IDENTITY_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('identity_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    await program.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('old stakes cannot exit without a request', async () => {
+    const agent = await stakedAgent(LAMPORTS_PER_SOL);
+    await advanceTime(context, 180 * 24 * 60 * 60);
+
+    await expect(withdraw(agent)).rejects.toThrow(/NoPendingUnstake/);
+    expect((await identity(agent)).stakeUnlockTimestamp.toNumber()).toBe(0);
+  });
+
+  test('withdraw before the cooldown fails', async () => {
+    const agent = await stakedAgent(LAMPORTS_PER_SOL);
+    await requestUnstake(agent, LAMPORTS_PER_SOL / 2);
+
+    await advanceTime(context, UNLOCK_PERIOD - 60);
+    await expect(withdraw(agent)).rejects.toThrow(/StakeLocked/);
+
+    await advanceTime(context, 60);
+    const before = await context.banksClient.getBalance(agent.publicKey);
+    await withdraw(agent);
+    const after = await context.banksClient.getBalance(agent.publicKey);
+
+    expect(after - before).toBe(BigInt(LAMPORTS_PER_SOL / 2));
+    const state = await identity(agent);
+    expect(state.stakedAmount.toNumber()).toBe(LAMPORTS_PER_SOL / 2);
+    expect(state.pendingUnstakeAmount.toNumber()).toBe(0);
+  });
+
+  test('partial requests stack and restart the cooldown', async () => {
+    const agent = await stakedAgent(LAMPORTS_PER_SOL);
+    await requestUnstake(agent, LAMPORTS_PER_SOL / 4);
+
+    await advanceTime(context, UNLOCK_PERIOD - 60);
+    await requestUnstake(agent, LAMPORTS_PER_SOL / 4);
+
+    const state = await identity(agent);
+    expect(state.pendingUnstakeAmount.toNumber()).toBe(LAMPORTS_PER_SOL / 2);
+
+    // The first request's deadline has passed, but the second restarted it
+    await advanceTime(context, 120);
+    await expect(withdraw(agent)).rejects.toThrow(/StakeLocked/);
+
+    await advanceTime(context, UNLOCK_PERIOD);
+    await withdraw(agent);
+    expect((await identity(agent)).stakedAmount.toNumber()).toBe(LAMPORTS_PER_SOL / 2);
+  });
+
+  test('requests cannot exceed the unrequested stake', async () => {
+    const agent = await stakedAgent(LAMPORTS_PER_SOL);
+    await requestUnstake(agent, LAMPORTS_PER_SOL);
+
+    await expect(requestUnstake(agent, 1)).rejects.toThrow(/InsufficientStake/);
+    await expect(requestUnstake(agent, 0)).rejects.toThrow(/InvalidUnstakeAmount/);
+  });
+
+  test('slashing during the cooldown hits pending funds', async () => {
+    const agent = await stakedAgent(LAMPORTS_PER_SOL);
+    await requestUnstake(agent, LAMPORTS_PER_SOL);
+
+    // 10000 bps severity slashes the 50% cap
+    await program.methods
+      .slashAgent(10000, 'Exit after misbehaviour')
+      .accounts({
+        agentIdentity: identityPda(agent),
+        stakingPool: stakingPoolPda,
+        slashRecord: slashRecordPda(agent, 0),
+        agentAddress: agent.publicKey,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    const state = await identity(agent);
+    expect(state.stakedAmount.toNumber()).toBe(LAMPORTS_PER_SOL / 2);
+    expect(state.pendingUnstakeAmount.toNumber()).toBe(LAMPORTS_PER_SOL / 2);
+
+    await advanceTime(context, UNLOCK_PERIOD);
+    const before = await context.banksClient.getBalance(agent.publicKey);
+    await withdraw(agent);
+    const after = await context.banksClient.getBalance(agent.publicKey);
+
+    expect(after - before).toBe(BigInt(LAMPORTS_PER_SOL / 2));
+    expect((await identity(agent)).stakedAmount.toNumber()).toBe(0);
+  });
+});
@@ -0,0 +1,396 @@
+/**
+ * Agent Profile Tests
+ * Tests the agent_profile program's get_agent_profile view across all five registries
+ *
+ * get_agent_profile ensures:
+ * 1. One simulation returns identity, reputation, votes, endorsements, stake and stamps together
+ * 2. Accounts that are not passed come back as None instead of failing the view
+ * 3. Only endpoints with a valid stamp count toward stamped_endpoint_count
+ * 4. An account with the wrong seeds, owner or provider fails the view instead of being reported
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  now,
+  simulateReturnData,
+} from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const PROFILE_PROGRAM_ID = new PublicKey('Bz1DuYWipM1Y1buubMm5TdSXFn9PQnNPGAyPVMnuf89a');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const STAKE = LAMPORTS_PER_SOL;
+
+type ProfileAccounts = {
+  identity?: boolean;
+  reputation?: boolean;
+  voteTally?: PublicKey | null;
+  endorsementStats?: PublicKey | null;
+  endorsementIndex?: PublicKey | null;
+  endpointIndex?: PublicKey | null;
+  endpoints?: PublicKey[];
+};
+
+describe('Agent Profile', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let validationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let stakingProgram: Program<Idl>;
+  let profileProgram: Program<Idl>;
+  let authority: Keypair;
+  let authorityPda: PublicKey;
+  let stakingPoolPda: PublicKey;
+  let decayConfigPda: PublicKey;
+
+  function pda(seed: string, wallet: PublicKey, programId: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync([Buffer.from(seed), wallet.toBuffer()], programId);
+  }
+
+  /** Write an account encoded with its owning program's coder */
+  async function mockAccount(program: Program<Idl>, name: string, address: PublicKey, fields: object) {
+    context.setAccount(address, {
+      lamports: 1_000_000_000,
+      data: await program.coder.accounts.encode(name, fields),
+      owner: program.programId,
+      executable: false,
+    });
+  }
+
+  /** Register an identity, stake collateral and set a reputation score */
+  async function registerAgent(score: number): Promise<Keypair> {
+    const wallet = Keypair.generate();
+    await airdrop(context, wallet.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: pda('agent', wallet.publicKey, IDENTITY_PROGRAM_ID)[0],
+        agent: wallet.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await identityProgram.methods
+      .stakeCollateral(new BN(STAKE))
+      .accounts({
+        agentIdentity: pda('agent', wallet.publicKey, IDENTITY_PROGRAM_ID)[0],
+        stakingPool: stakingPoolPda,
+        agent: wallet.publicKey,
+        agentAddress: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: pda('reputation', wallet.publicKey, REPUTATION_PROGRAM_ID)[0],
+        agentAddress: wallet.publicKey,
+        authorityAccount: authorityPda,
+        initializer: authority.publicKey,
+        payer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await advanceTime(context, 10);
+    await reputationProgram.methods
+      .updateReputation(
+        score,
+        { trust: 70, quality: 65, reliability: 80, economic: 55, social: 60 },
+        { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+        new Array(32).fill(0),
+        new BN(0),
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: pda('reputation', wallet.publicKey, REPUTATION_PROGRAM_ID)[0],
+        authorityAccount: authorityPda,
+        agentAddress: wallet.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+    return wallet;
+  }
+
+  async function mockVoteTally(agent: PublicKey, upvotes: number, downvotes: number): Promise<PublicKey> {
+    const [address, bump] = pda('vote_tally', agent, VOTE_PROGRAM_ID);
+    await mockAccount(voteProgram, 'VoteTally', address, {
+      agent,
+      upvotes,
+      downvotes,
+      neutrals: 1,
+      qualitySums: {
+        responseQuality: new BN(0),
+        responseSpeed: new BN(0),
+        accuracy: new BN(0),
+        professionalism: new BN(0),
+      },
+      weightSum: new BN(100 * (upvotes + downvotes + 1)),
+      lastVoteAt: new BN(await now(context)),
+      bump,
+      closedVotes: 0,
+      disputedVotes: 0,
+      removedVotes: 0,
+    });
+    return address;
+  }
+
+  async function mockEndorsementStats(agent: PublicKey, received: number): Promise<PublicKey> {
+    const [address, bump] = pda('endorsement_stats', agent, VOTE_PROGRAM_ID);
+    await mockAccount(voteProgram, 'EndorsementStats', address, {
+      agent,
+      endorsementsGiven: 1,
+      endorsementsReceived: received,
+      bump,
+    });
+    return address;
+  }
+
+  async function mockEndorsementIndex(agent: PublicKey, owner = stakingProgram): Promise<PublicKey> {
+    const [address, bump] = pda('endorsement_index', agent, STAKING_PROGRAM_ID);
+    const data = await stakingProgram.coder.accounts.encode('AgentEndorsementIndex', {
+      targetAgent: agent,
+      vaultCount: 2,
+      totalTrustWeight: new BN(5_000),
+      totalStakers: 7,
+      updatedAt: new BN(await now(context)),
+      bump,
+    });
+    context.setAccount(address, { lamports: 1_000_000_000, data, owner: owner.programId, executable: false });
+    return address;
+  }
+
+  /** An endpoint validation of `provider` with a stamp expiring at `expiresAt` (0 = never) */
+  async function mockEndpoint(provider: PublicKey, url: string, expiresAt: number): Promise<PublicKey> {
+    const endpointHash = createHash('sha256').update(url).digest();
+    const [address, bump] = PublicKey.findProgramAddressSync(
+      [Buffer.from('validation'), endpointHash],
+      VALIDATION_PROGRAM_ID
+    );
+    await mockAccount(validationProgram, 'EndpointValidation', address, {
+      endpointHash: [...endpointHash],
+      endpointUrl: url,
+      providerAgent: provider,
+      testResults: [],
+      consensusScore: 900,
+      stampTier: { gold: {} },
+      timestamp: new BN(await now(context)),
+      bump,
+      validationRound: 1,
+      stampIssuedAt: new BN(await now(context)),
+      stampExpiresAt: new BN(expiresAt),
+      stampRevokedAt: new BN(0),
+      stampRevocationReason: new Array(32).fill(0),
+      disputedResults: 0,
+      disputeCount: 0,
+      payer: provider,
+    });
+    return address;
+  }
+
+  async function mockEndpointIndex(provider: PublicKey, endpointCount: number): Promise<PublicKey> {
+    const [address, bump] = pda('endpoints', provider, VALIDATION_PROGRAM_ID);
+    await mockAccount(validationProgram, 'ProviderEndpointIndex', address, {
+      provider,
+      endpointHashes: Array.from({ length: endpointCount }, (_, i) => new Array(32).fill(i + 1)),
+      latestEndpointHash: new Array(32).fill(endpointCount),
+      stampsIssued: 3,
+      latestStampedHash: new Array(32).fill(1),
+      bump,
+    });
+    return address;
+  }
+
+  async function getProfile(agent: PublicKey, accounts: ProfileAccounts) {
+    const ix = await profileProgram.methods
+      .getAgentProfile()
+      .accounts({
+        agent,
+        agentIdentity: accounts.identity ? pda('agent', agent, IDENTITY_PROGRAM_ID)[0] : null,
+        agentReputation: accounts.reputation ? pda('reputation', agent, REPUTATION_PROGRAM_ID)[0] : null,
+        decayConfig: decayConfigPda,
+        voteTally: accounts.voteTally ?? null,
+        endorsementStats: accounts.endorsementStats ?? null,
+        endorsementIndex: accounts.endorsementIndex ?? null,
+        endpointIndex: accounts.endpointIndex ?? null,
+      })
+      .remainingAccounts(
+        (accounts.endpoints ?? []).map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }))
+      )
+      .instruction();
+    return profileProgram.coder.types.decode('AgentProfile', await simulateReturnData(context, ix));
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+        { name: 'token_staking', programId: STAKING_PROGRAM_ID },
+        { name: 'agent_profile', programId: PROFILE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    validationProgram = loadProgram('validation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+    stakingProgram = loadProgram('token_staking', provider);
+    profileProgram = loadProgram('agent_profile', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    [decayConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('decay_config')], REPUTATION_PROGRAM_ID);
+
+    await identityProgram.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('one simulation returns the full profile', async () => {
+    const agent = (await registerAgent(640)).publicKey;
+    const expired = (await now(context)) - 1;
+    const endpoints = [
+      await mockEndpoint(agent, 'https://agent.example.com/a', 0),
+      await mockEndpoint(agent, 'https://agent.example.com/b', expired),
+    ];
+
+    const profile = await getProfile(agent, {
+      identity: true,
+      reputation: true,
+      voteTally: await mockVoteTally(agent, 5, 2),
+      endorsementStats: await mockEndorsementStats(agent, 4),
+      endorsementIndex: await mockEndorsementIndex(agent),
+      endpointIndex: await mockEndpointIndex(agent, 2),
+      endpoints,
+    });
+
+    expect(profile.agent.equals(agent)).toBe(true);
+    expect(profile.identity.isActive).toBe(true);
+    expect(profile.identity.isExpired).toBe(false);
+    expect(profile.identity.stakedAmount.toNumber()).toBe(STAKE);
+    expect(profile.reputation.overallScore).toBe(640);
+    expect(profile.reputation.effectiveScore).toBe(640);
+    expect(profile.reputation.isFrozen).toBe(false);
+    expect(profile.votes.upvotes).toBe(5);
+    expect(profile.votes.downvotes).toBe(2);
+    expect(profile.votes.neutrals).toBe(1);
+    expect(profile.endorsements.received).toBe(4);
+    expect(profile.endorsements.given).toBe(1);
+    expect(profile.staking.vaultCount).toBe(2);
+    expect(profile.staking.totalStakers).toBe(7);
+    expect(profile.staking.totalTrustWeight.toNumber()).toBe(5_000);
+    expect(profile.endpoints.endpointCount).toBe(2);
+    expect(profile.endpoints.stampsIssued).toBe(3);
+    // The expired stamp is not counted
+    expect(profile.stampedEndpointCount).toBe(1);
+  });
+
+  test('accounts that are not passed come back as None', async () => {
+    const agent = (await registerAgent(300)).publicKey;
+    const profile = await getProfile(agent, { identity: true, voteTally: await mockVoteTally(agent, 1, 0) });
+
+    expect(profile.identity.stakedAmount.toNumber()).toBe(STAKE);
+    expect(profile.votes.upvotes).toBe(1);
+    expect(profile.reputation).toBeNull();
+    expect(profile.endorsements).toBeNull();
+    expect(profile.staking).toBeNull();
+    expect(profile.endpoints).toBeNull();
+    expect(profile.stampedEndpointCount).toBe(0);
+
+    const bare = await getProfile(Keypair.generate().publicKey, {});
+    expect(bare.identity).toBeNull();
+    expect(bare.votes).toBeNull();
+  });
+
+  test("another agent's account fails the seeds check", async () => {
+    const agent = (await registerAgent(500)).publicKey;
+    const other = (await registerAgent(200)).publicKey;
+
+    await expect(getProfile(agent, { voteTally: await mockVoteTally(other, 9, 0) })).rejects.toThrow(
+      /ConstraintSeeds/
+    );
+    await expect(getProfile(agent, { endorsementIndex: await mockEndorsementIndex(other) })).rejects.toThrow(
+      /ConstraintSeeds/
+    );
+  });
+
+  test('an account at the right address but owned by another program is rejected', async () => {
+    const agent = (await registerAgent(500)).publicKey;
+
+    const tally = await mockVoteTally(agent, 9, 0);
+    const account = (await context.banksClient.getAccount(tally))!;
+    context.setAccount(tally, { ...account, owner: PROFILE_PROGRAM_ID });
+    await expect(getProfile(agent, { voteTally: tally })).rejects.toThrow(/AccountOwnedByWrongProgram/);
+
+    const index = await mockEndorsementIndex(agent, voteProgram);
+    await expect(getProfile(agent, { endorsementIndex: index })).rejects.toThrow(/InvalidEndorsementIndex/);
+  });
+
+  test("another provider's endpoint fails the view", async () => {
+    const agent = (await registerAgent(500)).publicKey;
+    const other = (await registerAgent(200)).publicKey;
+    const theirs = await mockEndpoint(other, 'https://other.example.com/a', 0);
+
+    await expect(getProfile(agent, { endpoints: [theirs] })).rejects.toThrow(/InvalidEndpointAccount/);
+
+    const ours = await mockEndpoint(agent, 'https://agent.example.com/c', 0);
+    await expect(getProfile(agent, { endpoints: [ours, ours] })).rejects.toThrow(/InvalidEndpointAccount/);
+    expect((await getProfile(agent, { endpoints: [ours] })).stampedEndpointCount).toBe(1);
+  });
+});
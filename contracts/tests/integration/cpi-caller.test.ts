@@ -0,0 +1,210 @@
+/**
+ * CPI Caller Tests
+ * Tests an external program (tests/cpi-caller) that reads the GhostSpeak programs through gs2-cpi
+ *
+ * The gs2-cpi helpers ensure:
+ * 1. verify_identity, get_reputation, get_effective_score and get_tier return typed results to the caller
+ * 2. The callee still validates the accounts the caller passes through
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  now,
+  simulateReturnData,
+} from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const CALLER_PROGRAM_ID = new PublicKey('2V7PiKSoLC4G1Ytpea3b4FVeyGsyMLbuC54GppQZco8S');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const STAKE = LAMPORTS_PER_SOL;
+
+describe('CPI Caller', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let callerProgram: Program<Idl>;
+  let authority: Keypair;
+  let agent: Keypair;
+  let other: Keypair;
+  let authorityPda: PublicKey;
+  let stakingPoolPda: PublicKey;
+  let decayConfigPda: PublicKey;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  /** Register an identity, stake collateral and set a reputation score */
+  async function registerAgent(wallet: Keypair, score: number) {
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        agent: wallet.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await identityProgram.methods
+      .stakeCollateral(new BN(STAKE))
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        stakingPool: stakingPoolPda,
+        agent: wallet.publicKey,
+        agentAddress: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda(wallet.publicKey),
+        agentAddress: wallet.publicKey,
+        authorityAccount: authorityPda,
+        initializer: authority.publicKey,
+        payer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await advanceTime(context, 10);
+    await reputationProgram.methods
+      .updateReputation(
+        score,
+        { trust: 70, quality: 65, reliability: 80, economic: 55, social: 60 },
+        { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+        new Array(32).fill(0),
+        new BN(0),
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda(wallet.publicKey),
+        authorityAccount: authorityPda,
+        agentAddress: wallet.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  function checkAgentIx(wallet: PublicKey, reputation: PublicKey) {
+    return callerProgram.methods
+      .checkAgent()
+      .accounts({
+        agentIdentity: identityPda(wallet),
+        agentReputation: reputation,
+        agentAddress: wallet,
+        decayConfig: decayConfigPda,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+      })
+      .instruction();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'cpi_caller', programId: CALLER_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    callerProgram = loadProgram('cpi_caller', provider);
+
+    authority = Keypair.generate();
+    agent = Keypair.generate();
+    other = Keypair.generate();
+    for (const kp of [authority, agent, other]) {
+      await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    }
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    [decayConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('decay_config')], REPUTATION_PROGRAM_ID);
+
+    await identityProgram.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await registerAgent(agent, 640);
+    await registerAgent(other, 300);
+  });
+
+  test('check_agent returns the identity and reputation it read by CPI', async () => {
+    const ix = await checkAgentIx(agent.publicKey, reputationPda(agent.publicKey));
+    const check = callerProgram.coder.types.decode('AgentCheck', await simulateReturnData(context, ix));
+
+    const viewIx = await reputationProgram.methods
+      .getReputation()
+      .accounts({ agentReputation: reputationPda(agent.publicKey), agentAddress: agent.publicKey })
+      .instruction();
+    const view = reputationProgram.coder.types.decode('ReputationView', await simulateReturnData(context, viewIx));
+
+    expect(check.agent.equals(agent.publicKey)).toBe(true);
+    expect(check.isActive).toBe(true);
+    expect(check.hasMinimumStake).toBe(true);
+    expect(check.stakedAmount.toNumber()).toBe(STAKE);
+    expect(check.overallScore).toBe(640);
+    expect(check.effectiveScore).toBe(640);
+    expect(check.tier).toBe(view.tier);
+  });
+
+  test("the callee rejects another agent's reputation account", async () => {
+    const ix = await checkAgentIx(agent.publicKey, reputationPda(other.publicKey));
+    await expect(simulateReturnData(context, ix)).rejects.toThrow(/ConstraintSeeds/);
+  });
+});
@@ -0,0 +1,479 @@
+/**
+ * Agent Lifecycle Integration Test
+ * Runs one scenario across identity, reputation, vote and validation registries
+ *
+ * The lifecycle ensures:
+ * 1. Agents register, open reputation and stake collateral
+ * 2. An x402 receipt gates a peer vote that is recorded in the provider's reputation stats
+ * 3. Updated reputation lets an agent endorse, while a low-reputation agent cannot
+ * 4. A validator's results earn the provider's endpoint a stamp
+ * 5. Votes outside the voting window are rejected
+ * 6. A slash after a violation lets the vote authority seize endorsements of the slashed agent
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  now,
+  testResult,
+} from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const STAKE = LAMPORTS_PER_SOL;
+const MIN_VALIDATOR_BOND = 100_000_000;
+const VOTING_WINDOW_SECONDS = 30 * 24 * 60 * 60;
+const ENDPOINT_URL = 'https://api.example.com/x402/lifecycle';
+
+const QUALITY = { responseQuality: 90, responseSpeed: 85, accuracy: 90, professionalism: 80 };
+const COMPONENTS = { trust: 60, quality: 60, reliability: 60, economic: 60, social: 60 };
+
+describe('Agent Lifecycle', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let validationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  // Provider of the paid endpoint
+  let alice: Keypair;
+  // Client that pays alice, then votes on and endorses her
+  let bob: Keypair;
+  // Agent that never earns enough reputation to endorse
+  let carol: Keypair;
+  let validator: Keypair;
+  let stakingPoolPda: PublicKey;
+  let reputationAuthorityPda: PublicKey;
+  let validationAuthorityPda: PublicKey;
+  let validationConfigPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+  let cpiAuthorityPda: PublicKey;
+  let receiptCount = 0;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function validationPda(seeds: Buffer[]): PublicKey {
+    return PublicKey.findProgramAddressSync(seeds, VALIDATION_PROGRAM_ID)[0];
+  }
+
+  function votePda(seeds: Buffer[]): PublicKey {
+    return PublicKey.findProgramAddressSync(seeds, VOTE_PROGRAM_ID)[0];
+  }
+
+  function endorsementPda(endorser: PublicKey, endorsed: PublicKey): PublicKey {
+    return votePda([Buffer.from('endorsement'), endorser.toBuffer(), endorsed.toBuffer()]);
+  }
+
+  function endorsementStatsPda(wallet: PublicKey): PublicKey {
+    return votePda([Buffer.from('endorsement_stats'), wallet.toBuffer()]);
+  }
+
+  async function register(wallet: Keypair) {
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        agent: wallet.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda(wallet.publicKey),
+        agentAddress: wallet.publicKey,
+        authorityAccount: null,
+        initializer: wallet.publicKey,
+        payer: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await identityProgram.methods
+      .stakeCollateral(new BN(STAKE))
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        stakingPool: stakingPoolPda,
+        agent: wallet.publicKey,
+        agentAddress: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+  }
+
+  /** Publish a new score for `wallet`, carrying over the stats recorded by CPI */
+  async function setScore(wallet: Keypair, score: number) {
+    const current = await fetchAccount(reputationProgram, 'agentReputation', reputationPda(wallet.publicKey));
+    await advanceTime(context, 10);
+    await reputationProgram.methods
+      .updateReputation(
+        score,
+        COMPONENTS,
+        current.stats,
+        new Array(32).fill(0),
+        current.updateNonce,
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda(wallet.publicKey),
+        authorityAccount: reputationAuthorityPda,
+        agentAddress: wallet.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  /** Receipt of a payment from `payer` to `recipient` */
+  async function receipt(payer: Keypair, recipient: Keypair): Promise<PublicKey> {
+    const signature = `lifecycle_sig_${receiptCount++}`;
+    const signatureHash = Array.from(createHash('sha256').update(signature).digest());
+    const receiptPda = votePda([
+      Buffer.from('tx_receipt'),
+      payer.publicKey.toBuffer(),
+      recipient.publicKey.toBuffer(),
+      Buffer.from(signatureHash),
+    ]);
+
+    await voteProgram.methods
+      .createTransactionReceipt(signature, signatureHash, new BN(1_000_000), { apiResponse: {} }, PublicKey.default)
+      .accounts({
+        receipt: receiptPda,
+        payerPubkey: payer.publicKey,
+        recipientPubkey: recipient.publicKey,
+        creator: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+    return receiptPda;
+  }
+
+  function vote(receiptPda: PublicKey, voter: Keypair, votedAgent: Keypair) {
+    return voteProgram.methods
+      .castPeerVote(votedAgent.publicKey, { upvote: {} }, QUALITY, new Array(32).fill(0))
+      .accounts({
+        peerVote: votePda([Buffer.from('peer_vote'), receiptPda.toBuffer(), voter.publicKey.toBuffer()]),
+        transactionReceipt: receiptPda,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(votedAgent.publicKey),
+        votedAgentReputation: reputationPda(votedAgent.publicKey),
+        reputationCpiAuthority: cpiAuthorityPda,
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  function endorse(endorser: Keypair, endorsed: Keypair) {
+    return voteProgram.methods
+      .endorseAgent(endorsed.publicKey, 80, { technical: {} })
+      .accounts({
+        endorsement: endorsementPda(endorser.publicKey, endorsed.publicKey),
+        endorserStats: endorsementStatsPda(endorser.publicKey),
+        endorsedStats: endorsementStatsPda(endorsed.publicKey),
+        endorserIdentity: identityPda(endorser.publicKey),
+        endorserReputation: reputationPda(endorser.publicKey),
+        endorsedAgentIdentity: identityPda(endorsed.publicKey),
+        endorser: endorser.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([endorser])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    validationProgram = loadProgram('validation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    alice = Keypair.generate();
+    bob = Keypair.generate();
+    carol = Keypair.generate();
+    validator = Keypair.generate();
+    for (const kp of [authority, alice, bob, carol, validator]) {
+      await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    }
+
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    validationAuthorityPda = validationPda([Buffer.from('authority')]);
+    validationConfigPda = validationPda([Buffer.from('config')]);
+    voteAuthorityPda = votePda([Buffer.from('authority')]);
+    cpiAuthorityPda = votePda([Buffer.from('reputation_cpi')]);
+
+    await identityProgram.methods
+      .initializeStakingPool()
+      .accounts({ stakingPool: stakingPoolPda, authority: authority.publicKey, systemProgram: SystemProgram.programId })
+      .signers([authority])
+      .rpc();
+
+    for (const [program, authorityAccount] of [
+      [reputationProgram, reputationAuthorityPda],
+      [validationProgram, validationAuthorityPda],
+      [voteProgram, voteAuthorityPda],
+    ] as const) {
+      await program.methods
+        .initializeAuthority()
+        .accounts({
+          authorityAccount,
+          authority: authority.publicKey,
+          initializer: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    }
+
+    await validationProgram.methods
+      .initializeValidationConfig()
+      .accounts({
+        validationConfig: validationConfigPda,
+        authorityAccount: validationAuthorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('agents register, open reputation and stake collateral', async () => {
+    for (const wallet of [alice, bob, carol, validator]) {
+      await register(wallet);
+
+      const identity = await fetchAccount(identityProgram, 'agentIdentity', identityPda(wallet.publicKey));
+      expect(identity.isActive).toBe(true);
+      expect(identity.stakedAmount.toNumber()).toBe(STAKE);
+
+      const reputation = await fetchAccount(reputationProgram, 'agentReputation', reputationPda(wallet.publicKey));
+      expect(reputation.agentAddress.equals(wallet.publicKey)).toBe(true);
+      expect(reputation.overallScore).toBe(0);
+    }
+
+    // Enough to vote, not yet to endorse
+    await setScore(bob, 300);
+    await setScore(carol, 200);
+  });
+
+  test('a paid call lets the payer vote on the provider', async () => {
+    const receiptPda = await receipt(bob, alice);
+    const stored = await fetchAccount(voteProgram, 'transactionReceipt', receiptPda);
+    expect(stored.payer.equals(bob.publicKey)).toBe(true);
+    expect(stored.recipient.equals(alice.publicKey)).toBe(true);
+    expect(stored.payerVoted).toBe(false);
+
+    await vote(receiptPda, bob, alice);
+
+    expect((await fetchAccount(voteProgram, 'transactionReceipt', receiptPda)).payerVoted).toBe(true);
+    const tallyPda = votePda([Buffer.from('vote_tally'), alice.publicKey.toBuffer()]);
+    const tally = await fetchAccount(voteProgram, 'voteTally', tallyPda);
+    expect(tally.upvotes).toBe(1);
+
+    // vote_registry recorded the vote in alice's reputation stats by CPI
+    const reputation = await fetchAccount(reputationProgram, 'agentReputation', reputationPda(alice.publicKey));
+    expect(reputation.stats.totalVotes).toBe(1);
+    expect(reputation.stats.positiveVotes).toBe(1);
+  });
+
+  test('updated reputation gates endorsements', async () => {
+    await setScore(alice, 650);
+    await setScore(bob, 600);
+
+    const reputation = await fetchAccount(reputationProgram, 'agentReputation', reputationPda(alice.publicKey));
+    expect(reputation.overallScore).toBe(650);
+    // The authority's update kept the vote the CPI recorded
+    expect(reputation.stats.totalVotes).toBe(1);
+
+    await expect(endorse(carol, alice)).rejects.toThrow(/InsufficientEndorserReputation/);
+
+    await endorse(bob, alice);
+    const endorsement = await fetchAccount(
+      voteProgram,
+      'agentEndorsement',
+      endorsementPda(bob.publicKey, alice.publicKey)
+    );
+    expect(endorsement.isActive).toBe(true);
+    expect(endorsement.endorserReputationSnapshot).toBe(600);
+    const stats = await fetchAccount(voteProgram, 'endorsementStats', endorsementStatsPda(alice.publicKey));
+    expect(stats.endorsementsReceived).toBe(1);
+  });
+
+  test("a validator's results earn the provider's endpoint a stamp", async () => {
+    const validatorRecord = validationPda([Buffer.from('validator'), validator.publicKey.toBuffer()]);
+    await validationProgram.methods
+      .registerValidator(new BN(MIN_VALIDATOR_BOND))
+      .accounts({
+        validatorRecord,
+        validatorIdentity: identityPda(validator.publicKey),
+        validator: validator.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([validator])
+      .rpc();
+
+    const endpointHash = metadataHash(ENDPOINT_URL);
+    const validation = validationPda([Buffer.from('validation'), Buffer.from(endpointHash)]);
+    const endpointIndex = validationPda([Buffer.from('endpoints'), alice.publicKey.toBuffer()]);
+    const result = (success: boolean) => testResult({ success, score: success ? 60 : 0 });
+
+    await validationProgram.methods
+      .submitValidation(ENDPOINT_URL, endpointHash, [result(true), result(true), result(true), result(false)])
+      .accounts({
+        endpointValidation: validation,
+        providerAgent: alice.publicKey,
+        providerIdentity: identityPda(alice.publicKey),
+        endpointIndex,
+        payer: validator.publicKey,
+        systemProgram: SystemProgram.programId,
+        validatorRecord,
+        validationConfig: validationConfigPda,
+        rewardPool: null,
+      })
+      .signers([validator])
+      .rpc();
+
+    await validationProgram.methods
+      .calculateConsensus()
+      .accounts({
+        endpointValidation: validation,
+        authorityAccount: validationAuthorityPda,
+        authority: authority.publicKey,
+        validationConfig: null,
+      })
+      .signers([authority])
+      .rpc();
+
+    await validationProgram.methods
+      .issueValidationStamp()
+      .accounts({
+        endpointValidation: validation,
+        authorityAccount: validationAuthorityPda,
+        authority: authority.publicKey,
+        validationConfig: validationConfigPda,
+        endpointIndex,
+        stampProposal: null,
+      })
+      .signers([authority])
+      .rpc();
+
+    // 3/4 passed: 75% * 9 + an average score of 60
+    const stamped = await fetchAccount(validationProgram, 'endpointValidation', validation);
+    expect(stamped.providerAgent.equals(alice.publicKey)).toBe(true);
+    expect(stamped.consensusScore).toBe(735);
+    expect(stamped.stampIssuedAt.toNumber()).toBeGreaterThan(0);
+    expect(stamped.stampTier).toEqual({ bronze: {} });
+  });
+
+  test('votes outside the voting window are rejected', async () => {
+    const receiptPda = await receipt(bob, alice);
+    await advanceTime(context, VOTING_WINDOW_SECONDS + 1);
+
+    await expect(vote(receiptPda, bob, alice)).rejects.toThrow(/VotingWindowExpired/);
+    expect((await fetchAccount(voteProgram, 'transactionReceipt', receiptPda)).payerVoted).toBe(false);
+  });
+
+  test("a slash after a violation lets the vote authority seize the slashed agent's endorsements", async () => {
+    const slashEndorsement = () =>
+      voteProgram.methods
+        .slashEndorsement()
+        .accounts({
+          endorsement: endorsementPda(bob.publicKey, alice.publicKey),
+          endorserStats: endorsementStatsPda(bob.publicKey),
+          endorsedStats: endorsementStatsPda(alice.publicKey),
+          endorsedAgentIdentity: identityPda(alice.publicKey),
+          authorityAccount: voteAuthorityPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+    await expect(slashEndorsement()).rejects.toThrow(/EndorsedAgentNotSlashed/);
+
+    await identityProgram.methods
+      .slashAgent(5000, 'Served fabricated results')
+      .accounts({
+        agentIdentity: identityPda(alice.publicKey),
+        stakingPool: stakingPoolPda,
+        slashRecord: PublicKey.findProgramAddressSync(
+          [Buffer.from('slash'), alice.publicKey.toBuffer(), Buffer.alloc(4)],
+          IDENTITY_PROGRAM_ID
+        )[0],
+        agentAddress: alice.publicKey,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    const identity = await fetchAccount(identityProgram, 'agentIdentity', identityPda(alice.publicKey));
+    expect(identity.slashCount).toBe(1);
+    // 5000 bps severity -> 25% of the stake
+    expect(identity.stakedAmount.toNumber()).toBe((STAKE * 3) / 4);
+
+    await slashEndorsement();
+    const endorsement = await fetchAccount(
+      voteProgram,
+      'agentEndorsement',
+      endorsementPda(bob.publicKey, alice.publicKey)
+    );
+    expect(endorsement.isActive).toBe(false);
+    expect(endorsement.isSlashed).toBe(true);
+    expect(endorsement.stakeAmount.toNumber()).toBe(0);
+  });
+});
@@ -23,7 +23,8 @@ import {
   isValidSolanaSignature,
   MockX402Payment,
 } from '../helpers/mock-x402-payment';
-import { startAnchor } from 'solana-bankrun';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { metadataHash, mockCoreAsset } from '../helpers/bankrun';
 import { createHash } from 'crypto';
 
 const VOTE_REGISTRY_PROGRAM_ID = 'EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6';
@@ -31,6 +32,7 @@ const IDENTITY_REGISTRY_PROGRAM_ID = '2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc
 const REPUTATION_REGISTRY_PROGRAM_ID = 'A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp';
 
 describe('x402 Payment Flow Integration', () => {
+  let context: ProgramTestContext;
   let provider: BankrunProvider;
   let voteRegistryProgram: Program;
   let identityRegistryProgram: Program;
@@ -43,7 +45,7 @@ describe('x402 Payment Flow Integration', () => {
 
   beforeAll(async () => {
     // Start bankrun with all programs
-    const context = await startAnchor(
+    context = await startAnchor(
       './',
       [
         {
@@ -160,7 +162,8 @@ describe('x402 Payment Flow Integration', () => {
         payment.signature,
         payment.signatureHash,
         amount,
-        contentType
+        contentType,
+        PublicKey.default
       )
       .accounts({
         receipt: receiptPda,
@@ -181,7 +184,8 @@ describe('x402 Payment Flow Integration', () => {
     expect(receiptAccount.payer.toString()).toBe(payer.publicKey.toString());
     expect(receiptAccount.recipient.toString()).toBe(recipient.publicKey.toString());
     expect(receiptAccount.amount.toNumber()).toBe(amount);
-    expect(receiptAccount.voteCast).toBe(false);
+    expect(receiptAccount.payerVoted).toBe(false);
+    expect(receiptAccount.recipientVoted).toBe(false);
   });
 
   test('END-TO-END: payment → receipt → vote flow', async () => {
@@ -210,7 +214,8 @@ describe('x402 Payment Flow Integration', () => {
         payment.signature,
         payment.signatureHash,
         amount,
-        { chat: {} }
+        { chat: {} },
+        PublicKey.default
       )
       .accounts({
         receipt: receiptPda,
@@ -241,28 +246,42 @@ describe('x402 Payment Flow Integration', () => {
     );
 
     // Register voter identity
+    const voterAsset = mockCoreAsset(context, voter.publicKey);
     await identityRegistryProgram.methods
       .registerAgent(
-        voter.publicKey,
-        'https://example.com/voter-metadata.json'
+        voterAsset,
+        'https://example.com/voter-metadata.json',
+        metadataHash('https://example.com/voter-metadata.json'),
+        0,
+        null,
+        null,
+        false
       )
       .accounts({
         agentIdentity: voterIdentityPda,
         agent: voter.publicKey,
+        asset: voterAsset,
         systemProgram: PublicKey.default,
       })
       .signers([voter])
       .rpc();
 
     // Register voted agent identity
+    const votedAgentAsset = mockCoreAsset(context, votedAgent.publicKey);
     await identityRegistryProgram.methods
       .registerAgent(
-        votedAgent.publicKey,
-        'https://example.com/agent-metadata.json'
+        votedAgentAsset,
+        'https://example.com/agent-metadata.json',
+        metadataHash('https://example.com/agent-metadata.json'),
+        0,
+        null,
+        null,
+        false
       )
       .accounts({
         agentIdentity: votedAgentIdentityPda,
         agent: votedAgent.publicKey,
+        asset: votedAgentAsset,
         systemProgram: PublicKey.default,
       })
       .signers([votedAgent])
@@ -301,7 +320,7 @@ describe('x402 Payment Flow Integration', () => {
 
     // Step 4: Cast vote
     const [votePda] = PublicKey.findProgramAddressSync(
-      [Buffer.from('peer_vote'), receiptPda.toBuffer()],
+      [Buffer.from('peer_vote'), receiptPda.toBuffer(), voter.publicKey.toBuffer()],
       voteRegistryProgram.programId
     );
 
@@ -354,7 +373,7 @@ describe('x402 Payment Flow Integration', () => {
     const updatedReceipt = await (voteRegistryProgram.account as any)['transactionReceipt'].fetch(
       receiptPda
     );
-    expect(updatedReceipt.voteCast).toBe(true);
+    expect(updatedReceipt.payerVoted).toBe(true);
 
     console.log('\n✅ END-TO-END TEST PASSED');
     console.log('Payment → Receipt → Vote flow working correctly!');
@@ -380,7 +399,8 @@ describe('x402 Payment Flow Integration', () => {
         payment.signature,
         payment.signatureHash,
         78_000,
-        { chat: {} }
+        { chat: {} },
+        PublicKey.default
       )
       .accounts({
         receipt: receiptPda,
@@ -410,7 +430,7 @@ describe('x402 Payment Flow Integration', () => {
 
     // Cast first vote (should succeed)
     const [votePda] = PublicKey.findProgramAddressSync(
-      [Buffer.from('peer_vote'), receiptPda.toBuffer()],
+      [Buffer.from('peer_vote'), receiptPda.toBuffer(), payer.publicKey.toBuffer()],
       voteRegistryProgram.programId
     );
 
@@ -488,7 +508,8 @@ describe('x402 Payment Flow Integration', () => {
           payment.signature,
           payment.signatureHash,
           amount,
-          { chat: {} }
+          { chat: {} },
+          PublicKey.default
         )
         .accounts({
           receipt: receiptPda,
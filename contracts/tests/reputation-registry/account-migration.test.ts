@@ -0,0 +1,250 @@
+/**
+ * Account Migration Tests
+ * Tests AgentReputation layout versioning and the migrate_account instruction
+ *
+ * Account migration ensures:
+ * 1. A reputation in the pre-version layout is rejected until it is migrated
+ * 2. migrate_account grows it to the current size, sets the current version and keeps every field
+ * 3. A reputation below the minimum supported version fails with UnsupportedAccountVersion
+ * 4. A version 1 reputation gains a zeroed last_authority_update when migrated
+ * 5. Score updates, decay, freezing and tier reads work on a migrated reputation
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, now, simulateReturnData, truncateAccount } from '../helpers/bankrun';
+
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const CURRENT_VERSION = 2;
+// version (u8) is followed by last_authority_update (i64)
+const VERSION_OFFSET_FROM_END = 9;
+
+describe('Account Migration', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let authorityPda: PublicKey;
+  let tierConfigPda: PublicKey;
+
+  function reputationPda(agent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), agent.publicKey.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  async function accountData(address: PublicKey): Promise<Buffer> {
+    return Buffer.from((await context.banksClient.getAccount(address))!.data);
+  }
+
+  async function setScore(agent: Keypair, score: number) {
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda(agent));
+    await advanceTime(context, 10);
+    await program.methods
+      .updateReputation(
+        score,
+        { trust: 50, quality: 50, reliability: 50, economic: 50, social: 50 },
+        { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+        new Array(32).fill(0),
+        reputation.updateNonce,
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda(agent),
+        authorityAccount: authorityPda,
+        agentAddress: agent.publicKey,
+        tierConfig: tierConfigPda,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  /** Initialize a reputation for a fresh agent and give it a score */
+  async function scoredAgent(score: number): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await program.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda(agent),
+        agentAddress: agent.publicKey,
+        authorityAccount: authorityPda,
+        initializer: authority.publicKey,
+        payer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    await setScore(agent, score);
+    return agent;
+  }
+
+  /** Cut the reputation back to the layout written before the version field existed */
+  async function preVersionAgent(score: number): Promise<Keypair> {
+    const agent = await scoredAgent(score);
+    const data = await accountData(reputationPda(agent));
+    await truncateAccount(context, reputationPda(agent), data.length - VERSION_OFFSET_FROM_END);
+    return agent;
+  }
+
+  function migrate(agent: Keypair) {
+    return program.methods
+      .migrateAccount()
+      .accounts({
+        agentReputation: reputationPda(agent),
+        agentAddress: agent.publicKey,
+        tierConfig: tierConfigPda,
+        payer: context.payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  function getReputation(agent: Keypair) {
+    return program.methods
+      .getReputation()
+      .accounts({ agentReputation: reputationPda(agent), agentAddress: agent.publicKey })
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('reputation_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], program.programId);
+    [tierConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('tier_config')], program.programId);
+
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('new reputations start at the current version', async () => {
+    const agent = await scoredAgent(400);
+    expect((await fetchAccount(program, 'agentReputation', reputationPda(agent))).version).toBe(CURRENT_VERSION);
+  });
+
+  test('a pre-version reputation is rejected until it is migrated', async () => {
+    const agent = await preVersionAgent(400);
+    await expect(getReputation(agent)).rejects.toThrow(/AccountDidNotDeserialize/);
+
+    await migrate(agent);
+    await getReputation(agent);
+  });
+
+  test('migrate_account grows the reputation and keeps every field', async () => {
+    const agent = await scoredAgent(600);
+    const before = await fetchAccount(program, 'agentReputation', reputationPda(agent));
+    const fullLength = (await accountData(reputationPda(agent))).length;
+    await truncateAccount(context, reputationPda(agent), fullLength - VERSION_OFFSET_FROM_END);
+
+    await migrate(agent);
+
+    expect((await accountData(reputationPda(agent))).length).toBe(fullLength);
+    const after = await fetchAccount(program, 'agentReputation', reputationPda(agent));
+    expect(after.version).toBe(CURRENT_VERSION);
+    expect(after.overallScore).toBe(600);
+    expect(after.tier).toBe(before.tier);
+    expect(after.updateNonce.toNumber()).toBe(before.updateNonce.toNumber());
+    expect(after.agentAddress.equals(agent.publicKey)).toBe(true);
+
+    // A second migration is a no-op
+    const migrated = await accountData(reputationPda(agent));
+    await migrate(agent);
+    expect((await accountData(reputationPda(agent))).equals(migrated)).toBe(true);
+  });
+
+  test('a reputation below the minimum version fails with UnsupportedAccountVersion', async () => {
+    const agent = await scoredAgent(400);
+    const account = (await context.banksClient.getAccount(reputationPda(agent)))!;
+    const data = Buffer.from(account.data);
+    data[data.length - VERSION_OFFSET_FROM_END] = 0;
+    context.setAccount(reputationPda(agent), { ...account, data });
+
+    await expect(getReputation(agent)).rejects.toThrow(/UnsupportedAccountVersion/);
+    await expect(setScore(agent, 500)).rejects.toThrow(/UnsupportedAccountVersion/);
+
+    await migrate(agent);
+    expect((await fetchAccount(program, 'agentReputation', reputationPda(agent))).version).toBe(CURRENT_VERSION);
+    await setScore(agent, 500);
+  });
+
+  test('a version 1 reputation gains a zeroed last_authority_update', async () => {
+    const agent = await scoredAgent(500);
+    const fullLength = (await accountData(reputationPda(agent))).length;
+    const account = (await context.banksClient.getAccount(reputationPda(agent)))!;
+    const data = Buffer.from(account.data.subarray(0, fullLength - 8));
+    data[data.length - 1] = 1;
+    context.setAccount(reputationPda(agent), { ...account, data });
+    await expect(getReputation(agent)).rejects.toThrow(/AccountDidNotDeserialize/);
+
+    await migrate(agent);
+
+    expect((await accountData(reputationPda(agent))).length).toBe(fullLength);
+    const after = await fetchAccount(program, 'agentReputation', reputationPda(agent));
+    expect(after.version).toBe(CURRENT_VERSION);
+    expect(after.lastAuthorityUpdate.toNumber()).toBe(0);
+    expect(after.overallScore).toBe(500);
+    await setScore(agent, 550);
+  });
+
+  test('score updates, decay, freezing and tier reads work on a migrated reputation', async () => {
+    const agent = await preVersionAgent(400);
+    await airdrop(context, agent.publicKey, 1_000_000_000);
+    await migrate(agent);
+
+    await setScore(agent, 800);
+    await program.methods
+      .enableDecay(10000)
+      .accounts({ agentReputation: reputationPda(agent), owner: agent.publicKey })
+      .signers([agent])
+      .rpc();
+    await program.methods
+      .applyDecay()
+      .accounts({ agentReputation: reputationPda(agent), caller: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+    const freezeAccounts = {
+      agentReputation: reputationPda(agent),
+      authorityAccount: authorityPda,
+      multisig: null,
+      authority: authority.publicKey,
+    };
+    await program.methods.freezeReputation(new Array(32).fill(7)).accounts(freezeAccounts).signers([authority]).rpc();
+    expect((await fetchAccount(program, 'agentReputation', reputationPda(agent))).isFrozen).toBe(true);
+    await program.methods.unfreezeReputation().accounts(freezeAccounts).signers([authority]).rpc();
+
+    const tierIx = await program.methods
+      .getTier()
+      .accounts({ agentReputation: reputationPda(agent) })
+      .instruction();
+    const tier = (await simulateReturnData(context, tierIx)).readUInt8(0);
+
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda(agent));
+    expect(reputation.overallScore).toBeLessThanOrEqual(800);
+    expect(reputation.tier).toBe(tier);
+    expect(reputation.isFrozen).toBe(false);
+    expect(reputation.version).toBe(CURRENT_VERSION);
+  });
+});
@@ -0,0 +1,151 @@
+/**
+ * Authority Rotation Tests
+ * Tests two-step handover of the ReputationAuthority
+ *
+ * The handover ensures:
+ * 1. Only the current authority can nominate or cancel
+ * 2. Only the nominee can accept
+ * 3. The previous authority loses update access after handover
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, now } from '../helpers/bankrun';
+
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+
+describe('Authority Rotation', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let original: Keypair;
+  let successor: Keypair;
+  let agent: Keypair;
+  let authorityPda: PublicKey;
+  let reputationPda: PublicKey;
+
+  function propose(signer: Keypair, nominee: PublicKey) {
+    return program.methods
+      .proposeAuthorityTransfer(nominee)
+      .accounts({ authorityAccount: authorityPda, authority: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function accept(signer: Keypair) {
+    return program.methods
+      .acceptAuthorityTransfer()
+      .accounts({ authorityAccount: authorityPda, newAuthority: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function updateAs(signer: Keypair, score: number) {
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+    await advanceTime(context, 10);
+    return program.methods
+      .updateReputation(
+        score,
+        { trust: 50, quality: 50, reliability: 50, economic: 50, social: 50 },
+        { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+        new Array(32).fill(0),
+        reputation.updateNonce,
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda,
+        authorityAccount: authorityPda,
+        agentAddress: agent.publicKey,
+        authority: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('reputation_registry', provider);
+
+    original = Keypair.generate();
+    successor = Keypair.generate();
+    agent = Keypair.generate();
+    await airdrop(context, original.publicKey, 10_000_000_000);
+    await airdrop(context, successor.publicKey, 1_000_000_000);
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], program.programId);
+    [reputationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), agent.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: original.publicKey,
+        initializer: original.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([original])
+      .rpc();
+
+    await program.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda,
+        agentAddress: agent.publicKey,
+        authorityAccount: authorityPda,
+        initializer: original.publicKey,
+        payer: original.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([original])
+      .rpc();
+  });
+
+  test('cancel clears a pending nomination', async () => {
+    await propose(original, successor.publicKey);
+
+    await program.methods
+      .cancelAuthorityTransfer()
+      .accounts({ authorityAccount: authorityPda, authority: original.publicKey })
+      .signers([original])
+      .rpc();
+
+    const account = await fetchAccount(program, 'reputationAuthority', authorityPda);
+    expect(account.pendingAuthority.toBase58()).toBe(PublicKey.default.toBase58());
+    await expect(accept(successor)).rejects.toThrow(/NoPendingAuthority/);
+  });
+
+  test('only the current authority can nominate', async () => {
+    await expect(propose(successor, successor.publicKey)).rejects.toThrow(/UnauthorizedAuthority/);
+  });
+
+  test('nominee must sign to accept', async () => {
+    await propose(original, successor.publicKey);
+
+    await expect(accept(original)).rejects.toThrow(/NotPendingAuthority/);
+    await accept(successor);
+
+    const account = await fetchAccount(program, 'reputationAuthority', authorityPda);
+    expect(account.authority.toBase58()).toBe(successor.publicKey.toBase58());
+    expect(account.pendingAuthority.toBase58()).toBe(PublicKey.default.toBase58());
+  });
+
+  test('previous authority loses update access after handover', async () => {
+    await expect(updateAs(original, 700)).rejects.toThrow(/UnauthorizedAuthority/);
+
+    await updateAs(successor, 700);
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+    expect(reputation.overallScore).toBe(700);
+  });
+});
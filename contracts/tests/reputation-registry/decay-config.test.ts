@@ -0,0 +1,205 @@
+/**
+ * Decay Config Tests
+ * Tests the registry-wide DecayConfig PDA
+ *
+ * The decay config ensures:
+ * 1. Compile-time defaults apply until the config is initialized
+ * 2. Authority updates change decay behavior without a program upgrade
+ * 3. The global kill-switch blocks apply_decay
+ * 4. Out-of-range parameters are rejected
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, now } from '../helpers/bankrun';
+
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const SECONDS_PER_DAY = 86400;
+
+describe('Decay Config', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let agent: Keypair;
+  let authorityPda: PublicKey;
+  let reputationPda: PublicKey;
+  let decayConfigPda: PublicKey;
+
+  async function applyDecay() {
+    return program.methods
+      .applyDecay()
+      .accounts({
+        agentReputation: reputationPda,
+        decayConfig: decayConfigPda,
+        caller: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  async function currentScore(): Promise<number> {
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+    return reputation.overallScore;
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('reputation_registry', provider);
+
+    authority = Keypair.generate();
+    agent = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+    await airdrop(context, agent.publicKey, 1_000_000_000);
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], program.programId);
+    [reputationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), agent.publicKey.toBuffer()],
+      program.programId
+    );
+    [decayConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('decay_config')],
+      program.programId
+    );
+
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda,
+        agentAddress: agent.publicKey,
+        authorityAccount: authorityPda,
+        initializer: authority.publicKey,
+        payer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await advanceTime(context, 10);
+    await program.methods
+      .updateReputation(
+        800,
+        { trust: 80, quality: 80, reliability: 80, economic: 80, social: 80 },
+        { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+        new Array(32).fill(0),
+        new BN(0),
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda,
+        authorityAccount: authorityPda,
+        agentAddress: agent.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .enableDecay(10000)
+      .accounts({ agentReputation: reputationPda, owner: agent.publicKey })
+      .signers([agent])
+      .rpc();
+
+    await advanceTime(context, 45 * SECONDS_PER_DAY);
+  });
+
+  test('uses default constants before the config exists', async () => {
+    // 45 days inactive: 15 days past the 30-day grace, well under the 90-day half-life
+    await applyDecay();
+    expect(await currentScore()).toBe(800);
+  });
+
+  test('applies new parameters after the authority initializes the config', async () => {
+    await program.methods
+      .initializeDecayConfig(7, 20, 7)
+      .accounts({
+        decayConfig: decayConfigPda,
+        authorityAccount: authorityPda,
+        multisig: null,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    // 38 days past a 7-day grace with a 7-day half-life: five halvings
+    await applyDecay();
+    expect(await currentScore()).toBe(25);
+  });
+
+  test('kill-switch blocks apply_decay', async () => {
+    await program.methods
+      .updateDecayConfig(7, 20, 7, true)
+      .accounts({
+        decayConfig: decayConfigPda,
+        authorityAccount: authorityPda,
+        multisig: null,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    await expect(applyDecay()).rejects.toThrow(/DecayPaused/);
+  });
+
+  test('rejects out-of-range parameters and non-authority signers', async () => {
+    await expect(
+      program.methods
+        .updateDecayConfig(5, 20, 7, false)
+        .accounts({
+          decayConfig: decayConfigPda,
+          authorityAccount: authorityPda,
+          multisig: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc()
+    ).rejects.toThrow(/InvalidHalfLife/);
+
+    await expect(
+      program.methods
+        .updateDecayConfig(30, 1001, 7, false)
+        .accounts({
+          decayConfig: decayConfigPda,
+          authorityAccount: authorityPda,
+          multisig: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc()
+    ).rejects.toThrow(/InvalidMinScore/);
+
+    await expect(
+      program.methods
+        .updateDecayConfig(30, 100, 7, false)
+        .accounts({
+          decayConfig: decayConfigPda,
+          authorityAccount: authorityPda,
+          multisig: null,
+          authority: agent.publicKey,
+        })
+        .signers([agent])
+        .rpc()
+    ).rejects.toThrow(/UnauthorizedAuthority/);
+  });
+});
@@ -0,0 +1,289 @@
+/**
+ * Emergency Pause Tests
+ * Tests the multisig-controlled registry-wide pause
+ *
+ * The pause ensures:
+ * 1. Pausing and unpausing both require multisig quorum
+ * 2. update_reputation, record_payment_proof, apply_decay and
+ *    execute_reputation_proposal are rejected while paused
+ * 3. Executing an unpause proposal restores normal operation
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, now } from '../helpers/bankrun';
+
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+
+const COMPONENTS = { trust: 50, quality: 50, reliability: 50, economic: 50, social: 50 };
+const STATS = { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 };
+
+describe('Emergency Pause', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let agent: Keypair;
+  let signers: Keypair[];
+  let authorityPda: PublicKey;
+  let reputationPda: PublicKey;
+  let multisigPda: PublicKey;
+  let pauseStatePda: PublicKey;
+
+  function proposalPda(id: number): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('proposal'), new BN(id).toArrayLike(Buffer, 'le', 8)],
+      program.programId
+    )[0];
+  }
+
+  async function nextProposalId(): Promise<number> {
+    const multisig = await fetchAccount(program, 'multisigAuthority', multisigPda);
+    return multisig.proposalCount.toNumber();
+  }
+
+  async function approve(id: number, signer: Keypair) {
+    await program.methods
+      .approveProposal(new BN(id))
+      .accounts({ multisig: multisigPda, proposal: proposalPda(id), signer: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function setPaused(pause: boolean) {
+    const id = await nextProposalId();
+    await program.methods
+      .proposeEmergencyPause(pause)
+      .accounts({
+        multisig: multisigPda,
+        proposal: proposalPda(id),
+        proposer: signers[0].publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signers[0]])
+      .rpc();
+    await approve(id, signers[1]);
+    await program.methods
+      .executeEmergencyPause(new BN(id))
+      .accounts({
+        multisig: multisigPda,
+        proposal: proposalPda(id),
+        pauseState: pauseStatePda,
+        executor: signers[0].publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signers[0]])
+      .rpc();
+  }
+
+  async function updateReputation(score: number) {
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+    await advanceTime(context, 10);
+    await program.methods
+      .updateReputation(
+        score,
+        COMPONENTS,
+        STATS,
+        new Array(32).fill(0),
+        reputation.updateNonce,
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda,
+        authorityAccount: authorityPda,
+        agentAddress: agent.publicKey,
+        pauseState: pauseStatePda,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('reputation_registry', provider);
+
+    authority = Keypair.generate();
+    agent = Keypair.generate();
+    signers = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    for (const kp of [authority, agent, ...signers]) {
+      await airdrop(context, kp.publicKey, 10_000_000_000);
+    }
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], program.programId);
+    [reputationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), agent.publicKey.toBuffer()],
+      program.programId
+    );
+    [multisigPda] = PublicKey.findProgramAddressSync([Buffer.from('multisig_authority')], program.programId);
+    [pauseStatePda] = PublicKey.findProgramAddressSync([Buffer.from('pause_state')], program.programId);
+
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda,
+        agentAddress: agent.publicKey,
+        authorityAccount: authorityPda,
+        initializer: authority.publicKey,
+        payer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .enableDecay(10000)
+      .accounts({ agentReputation: reputationPda, owner: agent.publicKey })
+      .signers([agent])
+      .rpc();
+
+    await program.methods
+      .initializeMultisig(signers.map((s) => s.publicKey), 2)
+      .accounts({
+        multisig: multisigPda,
+        admin: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('a single signer cannot pause without quorum', async () => {
+    const id = await nextProposalId();
+    await program.methods
+      .proposeEmergencyPause(true)
+      .accounts({
+        multisig: multisigPda,
+        proposal: proposalPda(id),
+        proposer: signers[0].publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signers[0]])
+      .rpc();
+
+    await expect(
+      program.methods
+        .executeEmergencyPause(new BN(id))
+        .accounts({
+          multisig: multisigPda,
+          proposal: proposalPda(id),
+          pauseState: pauseStatePda,
+          executor: signers[0].publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([signers[0]])
+        .rpc()
+    ).rejects.toThrow(/InsufficientApprovals/);
+  });
+
+  test('pause via proposal sets the flag', async () => {
+    await setPaused(true);
+
+    const pauseState = await fetchAccount(program, 'pauseState', pauseStatePda);
+    expect(pauseState.paused).toBe(true);
+  });
+
+  test('update_reputation is rejected while paused', async () => {
+    await expect(updateReputation(500)).rejects.toThrow(/RegistryPaused/);
+  });
+
+  test('record_payment_proof is rejected while paused', async () => {
+    const signature = 'paused_payment_sig';
+    const hash = Array.from(createHash('sha256').update(signature).digest());
+    await expect(
+      program.methods
+        .recordPaymentProof(signature, hash, null, new BN(await now(context)))
+        .accounts({
+          agentReputation: reputationPda,
+          paymentProof: PublicKey.findProgramAddressSync(
+            [Buffer.from('payment_proof'), agent.publicKey.toBuffer(), Buffer.from(hash)],
+            program.programId
+          )[0],
+          authorityAccount: authorityPda,
+          agentAddress: agent.publicKey,
+          pauseState: pauseStatePda,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc()
+    ).rejects.toThrow(/RegistryPaused/);
+  });
+
+  test('apply_decay is rejected while paused', async () => {
+    await expect(
+      program.methods
+        .applyDecay()
+        .accounts({
+          agentReputation: reputationPda,
+          pauseState: pauseStatePda,
+          caller: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc()
+    ).rejects.toThrow(/RegistryPaused/);
+  });
+
+  test('execute_reputation_proposal is rejected while paused', async () => {
+    const id = await nextProposalId();
+    await program.methods
+      .proposeReputationUpdate(700, COMPONENTS, STATS, new Array(32).fill(0))
+      .accounts({
+        multisig: multisigPda,
+        proposal: proposalPda(id),
+        agentReputation: reputationPda,
+        targetAgent: agent.publicKey,
+        proposer: signers[0].publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signers[0]])
+      .rpc();
+    await approve(id, signers[1]);
+
+    await expect(
+      program.methods
+        .executeReputationProposal(new BN(id))
+        .accounts({
+          multisig: multisigPda,
+          proposal: proposalPda(id),
+          agentReputation: reputationPda,
+          pauseState: pauseStatePda,
+          executor: signers[0].publicKey,
+        })
+        .signers([signers[0]])
+        .rpc()
+    ).rejects.toThrow(/RegistryPaused/);
+  });
+
+  test('unpause via proposal restores operation', async () => {
+    await setPaused(false);
+
+    const pauseState = await fetchAccount(program, 'pauseState', pauseStatePda);
+    expect(pauseState.paused).toBe(false);
+
+    await updateReputation(500);
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+    expect(reputation.overallScore).toBe(500);
+  });
+});
@@ -0,0 +1,275 @@
+/**
+ * Reputation Freeze Tests
+ * Tests freezing an agent's reputation during an investigation
+ *
+ * The freeze ensures:
+ * 1. Score-changing instructions fail with ReputationFrozen
+ * 2. Read-only views keep working
+ * 3. Unfreezing restores normal operation without back-dated decay
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, now } from '../helpers/bankrun';
+
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const SECONDS_PER_DAY = 86400;
+
+const COMPONENTS = { trust: 60, quality: 60, reliability: 60, economic: 60, social: 60 };
+const STATS = { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 };
+
+describe('Reputation Freeze', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let cosigner: Keypair;
+  let agent: Keypair;
+  let authorityPda: PublicKey;
+  let reputationPda: PublicKey;
+  let multisigPda: PublicKey;
+  let proposalPda: PublicKey;
+
+  async function updateReputation(score: number) {
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+    await advanceTime(context, 10);
+    return program.methods
+      .updateReputation(
+        score,
+        COMPONENTS,
+        STATS,
+        new Array(32).fill(0),
+        reputation.updateNonce,
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda,
+        authorityAccount: authorityPda,
+        agentAddress: agent.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  function executeProposal() {
+    return program.methods
+      .executeReputationProposal(new BN(0))
+      .accounts({
+        multisig: multisigPda,
+        proposal: proposalPda,
+        agentReputation: reputationPda,
+        executor: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  function applyDecay() {
+    return program.methods
+      .applyDecay()
+      .accounts({ agentReputation: reputationPda, caller: authority.publicKey })
+      .signers([authority])
+      .rpc();
+  }
+
+  function recordActivity() {
+    return program.methods
+      .recordActivity()
+      .accounts({ agentReputation: reputationPda, caller: agent.publicKey })
+      .signers([agent])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('reputation_registry', provider);
+
+    authority = Keypair.generate();
+    cosigner = Keypair.generate();
+    agent = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+    await airdrop(context, cosigner.publicKey, 1_000_000_000);
+    await airdrop(context, agent.publicKey, 1_000_000_000);
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], program.programId);
+    [reputationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), agent.publicKey.toBuffer()],
+      program.programId
+    );
+    [multisigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('multisig_authority')],
+      program.programId
+    );
+    [proposalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('proposal'), new BN(0).toArrayLike(Buffer, 'le', 8)],
+      program.programId
+    );
+
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda,
+        agentAddress: agent.publicKey,
+        authorityAccount: authorityPda,
+        initializer: authority.publicKey,
+        payer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await updateReputation(600);
+
+    await program.methods
+      .enableDecay(10000)
+      .accounts({ agentReputation: reputationPda, owner: agent.publicKey })
+      .signers([agent])
+      .rpc();
+
+    // Approved proposal waiting to be executed
+    await program.methods
+      .initializeMultisig([authority.publicKey, cosigner.publicKey], 2)
+      .accounts({
+        multisig: multisigPda,
+        admin: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .proposeReputationUpdate(650, COMPONENTS, STATS, new Array(32).fill(0))
+      .accounts({
+        multisig: multisigPda,
+        proposal: proposalPda,
+        agentReputation: reputationPda,
+        targetAgent: agent.publicKey,
+        proposer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .approveProposal(new BN(0))
+      .accounts({ multisig: multisigPda, proposal: proposalPda, signer: cosigner.publicKey })
+      .signers([cosigner])
+      .rpc();
+
+    const reasonHash = Array.from(createHash('sha256').update('suspected vote ring').digest());
+    await program.methods
+      .freezeReputation(reasonHash)
+      .accounts({
+        agentReputation: reputationPda,
+        authorityAccount: authorityPda,
+        multisig: null,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  describe('While frozen', () => {
+    test('update_reputation is blocked', async () => {
+      await expect(updateReputation(900)).rejects.toThrow(/ReputationFrozen/);
+    });
+
+    test('execute_reputation_proposal is blocked', async () => {
+      await expect(executeProposal()).rejects.toThrow(/ReputationFrozen/);
+    });
+
+    test('apply_decay is blocked', async () => {
+      await expect(applyDecay()).rejects.toThrow(/ReputationFrozen/);
+    });
+
+    test('record_activity is blocked', async () => {
+      await expect(recordActivity()).rejects.toThrow(/ReputationFrozen/);
+    });
+
+    test('read-only views keep working', async () => {
+      await program.methods
+        .getReputation()
+        .accounts({ agentReputation: reputationPda, agentAddress: agent.publicKey })
+        .rpc();
+
+      await program.methods
+        .getEffectiveScore()
+        .accounts({ agentReputation: reputationPda })
+        .rpc();
+    });
+
+    test('only the authority can freeze or unfreeze', async () => {
+      await expect(
+        program.methods
+          .unfreezeReputation()
+          .accounts({
+            agentReputation: reputationPda,
+            authorityAccount: authorityPda,
+            multisig: null,
+            authority: agent.publicKey,
+          })
+          .signers([agent])
+          .rpc()
+      ).rejects.toThrow(/UnauthorizedAuthority/);
+    });
+  });
+
+  describe('After unfreeze', () => {
+    beforeAll(async () => {
+      // Long enough that decay would have halved the score several times
+      await advanceTime(context, 400 * SECONDS_PER_DAY);
+
+      await program.methods
+        .unfreezeReputation()
+        .accounts({
+          agentReputation: reputationPda,
+          authorityAccount: authorityPda,
+          multisig: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+    });
+
+    test('missed decay is not applied retroactively', async () => {
+      await applyDecay();
+
+      const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+      expect(reputation.isFrozen).toBe(false);
+      expect(reputation.overallScore).toBe(600);
+    });
+
+    test('blocked instructions work again', async () => {
+      await executeProposal();
+      let reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+      expect(reputation.overallScore).toBe(650);
+
+      await recordActivity();
+      await updateReputation(700);
+      reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+      expect(reputation.overallScore).toBe(700);
+    });
+  });
+});
@@ -0,0 +1,128 @@
+/**
+ * Initialize Reputation Access Tests
+ * Tests who may create an agent's reputation account
+ *
+ * Initialization ensures:
+ * 1. Third parties cannot pre-create an agent's reputation PDA
+ * 2. The agent or the reputation authority can initialize it
+ * 3. The initializer is recorded for auditing
+ * 4. A second initialization fails
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram } from '../helpers/bankrun';
+
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+
+describe('Initialize Reputation Access', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let griefer: Keypair;
+  let authorityPda: PublicKey;
+
+  function reputationPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), agent.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  function initialize(agent: PublicKey, initializer: Keypair, payer: Keypair) {
+    const signers = initializer === payer ? [payer] : [initializer, payer];
+    return program.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda(agent),
+        agentAddress: agent,
+        authorityAccount: authorityPda,
+        initializer: initializer.publicKey,
+        payer: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers(signers)
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('reputation_registry', provider);
+
+    authority = Keypair.generate();
+    griefer = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+    await airdrop(context, griefer.publicKey, 1_000_000_000);
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], program.programId);
+
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('third-party initialization fails', async () => {
+    const victim = Keypair.generate();
+
+    await expect(initialize(victim.publicKey, griefer, griefer)).rejects.toThrow(
+      /UnauthorizedInitializer/
+    );
+  });
+
+  test('agent can initialize its own reputation', async () => {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 1_000_000_000);
+
+    await initialize(agent.publicKey, agent, agent);
+
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda(agent.publicKey));
+    expect(reputation.overallScore).toBe(0);
+    expect(reputation.decayEnabled).toBe(false);
+    expect(reputation.lastActivity.toNumber()).toBeGreaterThan(0);
+    expect(reputation.initializedBy).toEqual({ agent: {} });
+  });
+
+  test('authority can initialize on behalf of an agent', async () => {
+    const agent = Keypair.generate();
+
+    await initialize(agent.publicKey, authority, authority);
+
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda(agent.publicKey));
+    expect(reputation.initializedBy).toEqual({ authority: {} });
+  });
+
+  test('agent may authorize while someone else pays rent', async () => {
+    const agent = Keypair.generate();
+
+    await initialize(agent.publicKey, agent, griefer);
+
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda(agent.publicKey));
+    expect(reputation.initializedBy).toEqual({ agent: {} });
+  });
+
+  test('double initialization fails', async () => {
+    const agent = Keypair.generate();
+    await initialize(agent.publicKey, authority, authority);
+
+    await expect(initialize(agent.publicKey, authority, authority)).rejects.toThrow(
+      /already in use/
+    );
+  });
+});
@@ -0,0 +1,222 @@
+/**
+ * Multisig Timelock Tests
+ * Tests the delay between a reputation proposal reaching quorum and execution
+ *
+ * The timelock ensures:
+ * 1. Approved proposals cannot be executed before the delay elapses
+ * 2. They execute normally once it has
+ * 3. Signers and the targeted agent can cancel during the window
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram } from '../helpers/bankrun';
+
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const DEFAULT_DELAY_SECONDS = 6 * 60 * 60;
+
+const COMPONENTS = { trust: 50, quality: 50, reliability: 50, economic: 50, social: 50 };
+const STATS = { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 };
+
+describe('Multisig Timelock', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let admin: Keypair;
+  let agent: Keypair;
+  let signers: Keypair[];
+  let authorityPda: PublicKey;
+  let reputationPda: PublicKey;
+  let multisigPda: PublicKey;
+
+  function proposalPda(id: number): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('proposal'), new BN(id).toArrayLike(Buffer, 'le', 8)],
+      program.programId
+    )[0];
+  }
+
+  /** Propose and approve a score update; returns the proposal id */
+  async function approvedProposal(score: number): Promise<number> {
+    const multisig = await fetchAccount(program, 'multisigAuthority', multisigPda);
+    const id = multisig.proposalCount.toNumber();
+
+    await program.methods
+      .proposeReputationUpdate(score, COMPONENTS, STATS, new Array(32).fill(0))
+      .accounts({
+        multisig: multisigPda,
+        proposal: proposalPda(id),
+        agentReputation: reputationPda,
+        targetAgent: agent.publicKey,
+        proposer: signers[0].publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signers[0]])
+      .rpc();
+
+    await program.methods
+      .approveProposal(new BN(id))
+      .accounts({ multisig: multisigPda, proposal: proposalPda(id), signer: signers[1].publicKey })
+      .signers([signers[1]])
+      .rpc();
+
+    return id;
+  }
+
+  function execute(id: number) {
+    return program.methods
+      .executeReputationProposal(new BN(id))
+      .accounts({
+        multisig: multisigPda,
+        proposal: proposalPda(id),
+        agentReputation: reputationPda,
+        executor: signers[0].publicKey,
+      })
+      .signers([signers[0]])
+      .rpc();
+  }
+
+  function cancel(id: number, canceller: Keypair) {
+    return program.methods
+      .cancelProposal(new BN(id))
+      .accounts({ multisig: multisigPda, proposal: proposalPda(id), canceller: canceller.publicKey })
+      .signers([canceller])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('reputation_registry', provider);
+
+    admin = Keypair.generate();
+    agent = Keypair.generate();
+    signers = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    for (const kp of [admin, agent, ...signers]) {
+      await airdrop(context, kp.publicKey, 10_000_000_000);
+    }
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], program.programId);
+    [reputationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), agent.publicKey.toBuffer()],
+      program.programId
+    );
+    [multisigPda] = PublicKey.findProgramAddressSync([Buffer.from('multisig_authority')], program.programId);
+
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: admin.publicKey,
+        initializer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda,
+        agentAddress: agent.publicKey,
+        authorityAccount: authorityPda,
+        initializer: admin.publicKey,
+        payer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .initializeMultisig(signers.map((s) => s.publicKey), 2)
+      .accounts({
+        multisig: multisigPda,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  test('multisig starts with the default delay', async () => {
+    const multisig = await fetchAccount(program, 'multisigAuthority', multisigPda);
+    expect(multisig.executionDelaySeconds.toNumber()).toBe(DEFAULT_DELAY_SECONDS);
+  });
+
+  test('execution before the delay fails', async () => {
+    const id = await approvedProposal(600);
+
+    const proposal = await fetchAccount(program, 'multisigProposal', proposalPda(id));
+    expect(proposal.approvalReachedAt.toNumber()).toBeGreaterThan(0);
+
+    await expect(execute(id)).rejects.toThrow(/TimelockActive/);
+    await advanceTime(context, DEFAULT_DELAY_SECONDS - 60);
+    await expect(execute(id)).rejects.toThrow(/TimelockActive/);
+  });
+
+  test('execution after the delay succeeds', async () => {
+    await advanceTime(context, 60);
+    await execute(0);
+
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+    expect(reputation.overallScore).toBe(600);
+  });
+
+  test('a signer can cancel during the window', async () => {
+    const id = await approvedProposal(100);
+    await cancel(id, signers[2]);
+
+    const proposal = await fetchAccount(program, 'multisigProposal', proposalPda(id));
+    expect(proposal.status).toEqual({ rejected: {} });
+
+    await advanceTime(context, DEFAULT_DELAY_SECONDS);
+    await expect(execute(id)).rejects.toThrow(/InsufficientApprovals/);
+  });
+
+  test('the targeted agent can cancel during the window', async () => {
+    const id = await approvedProposal(150);
+    await cancel(id, agent);
+
+    const proposal = await fetchAccount(program, 'multisigProposal', proposalPda(id));
+    expect(proposal.status).toEqual({ rejected: {} });
+  });
+
+  test('outsiders cannot cancel', async () => {
+    const id = await approvedProposal(200);
+    const outsider = Keypair.generate();
+    await airdrop(context, outsider.publicKey, 1_000_000_000);
+
+    await expect(cancel(id, outsider)).rejects.toThrow(/UnauthorizedCanceller/);
+  });
+
+  test('executed proposals cannot be cancelled', async () => {
+    await expect(cancel(0, signers[0])).rejects.toThrow(/ProposalNotCancellable/);
+  });
+
+  test('admin can shorten the delay within bounds', async () => {
+    await expect(
+      program.methods
+        .updateExecutionDelay(new BN(8 * 86400))
+        .accounts({ multisig: multisigPda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc()
+    ).rejects.toThrow(/InvalidExecutionDelay/);
+
+    await program.methods
+      .updateExecutionDelay(new BN(0))
+      .accounts({ multisig: multisigPda, admin: admin.publicKey })
+      .signers([admin])
+      .rpc();
+
+    const multisig = await fetchAccount(program, 'multisigAuthority', multisigPda);
+    expect(multisig.executionDelaySeconds.toNumber()).toBe(0);
+  });
+});
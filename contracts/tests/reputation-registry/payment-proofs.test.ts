@@ -0,0 +1,207 @@
+/**
+ * Payment Proof Tests
+ * Tests per-signature PaymentProof PDAs and the payment_proof_count counter
+ *
+ * Payment proofs ensure:
+ * 1. Each recorded signature creates a queryable PaymentProof account
+ * 2. Recording the same signature twice fails
+ * 3. payment_proof_count increments once per distinct signature
+ * 4. Proofs can only be closed after the retention period
+ * 5. Only the reputation authority can record proofs
+ * 6. Payments older than the retention period are rejected, so a closed
+ *    proof cannot be recorded and counted again
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, now } from '../helpers/bankrun';
+
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const RETENTION_SECONDS = 180 * 86400;
+
+describe('Payment Proofs', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let agent: Keypair;
+  let authorityPda: PublicKey;
+  let reputationPda: PublicKey;
+
+  function signatureHash(signature: string): number[] {
+    return Array.from(createHash('sha256').update(signature).digest());
+  }
+
+  function proofPda(signature: string): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('payment_proof'), agent.publicKey.toBuffer(), Buffer.from(signatureHash(signature))],
+      program.programId
+    )[0];
+  }
+
+  /** Record a proof for a payment made at `paidAt`, by default the current time */
+  async function record(
+    signature: string,
+    amount: BN | null,
+    hash = signatureHash(signature),
+    signer = authority,
+    paidAt?: number
+  ) {
+    return program.methods
+      .recordPaymentProof(signature, hash, amount, new BN(paidAt ?? (await now(context))))
+      .accounts({
+        agentReputation: reputationPda,
+        paymentProof: PublicKey.findProgramAddressSync(
+          [Buffer.from('payment_proof'), agent.publicKey.toBuffer(), Buffer.from(hash)],
+          program.programId
+        )[0],
+        authorityAccount: authorityPda,
+        agentAddress: agent.publicKey,
+        authority: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  function close(signature: string, recorder: Keypair) {
+    return program.methods
+      .closePaymentProof()
+      .accounts({ paymentProof: proofPda(signature), recorder: recorder.publicKey })
+      .signers([recorder])
+      .rpc();
+  }
+
+  async function proofCount(): Promise<number> {
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+    return reputation.paymentProofCount;
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('reputation_registry', provider);
+
+    authority = Keypair.generate();
+    agent = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], program.programId);
+    [reputationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), agent.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda,
+        agentAddress: agent.publicKey,
+        authorityAccount: authorityPda,
+        initializer: authority.publicKey,
+        payer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('recording a proof creates the PDA and increments the count', async () => {
+    expect(await proofCount()).toBe(0);
+
+    await record('payment_sig_1', new BN(250_000));
+
+    const proof = await fetchAccount(program, 'paymentProof', proofPda('payment_sig_1'));
+    expect(proof.agent.toBase58()).toBe(agent.publicKey.toBase58());
+    expect(proof.amount.toNumber()).toBe(250_000);
+    expect(proof.recorder.toBase58()).toBe(authority.publicKey.toBase58());
+    expect(await proofCount()).toBe(1);
+
+    await record('payment_sig_2', null);
+    expect(await proofCount()).toBe(2);
+  });
+
+  test('recording the same signature twice fails', async () => {
+    await expect(record('payment_sig_1', new BN(250_000))).rejects.toThrow(/already in use/);
+    expect(await proofCount()).toBe(2);
+  });
+
+  test('a hash that does not match the signature is rejected', async () => {
+    await expect(
+      record('payment_sig_3', null, signatureHash('something_else'))
+    ).rejects.toThrow(/PaymentSignatureHashMismatch/);
+  });
+
+  test('a signer other than the reputation authority cannot record proofs', async () => {
+    const stranger = Keypair.generate();
+    await airdrop(context, stranger.publicKey, 1_000_000_000);
+
+    await expect(
+      record('payment_sig_forged', null, signatureHash('payment_sig_forged'), stranger)
+    ).rejects.toThrow(/UnauthorizedAuthority/);
+    expect(await context.banksClient.getAccount(proofPda('payment_sig_forged'))).toBeNull();
+    expect(await proofCount()).toBe(2);
+  });
+
+  test('payments in the future or older than the retention period are rejected', async () => {
+    const current = await now(context);
+
+    await expect(
+      record('payment_sig_future', null, signatureHash('payment_sig_future'), authority, current + 60)
+    ).rejects.toThrow(/PaymentOutsideRetention/);
+    await expect(
+      record('payment_sig_old', null, signatureHash('payment_sig_old'), authority, current - RETENTION_SECONDS)
+    ).rejects.toThrow(/PaymentOutsideRetention/);
+    expect(await proofCount()).toBe(2);
+  });
+
+  test('proofs cannot be closed before the retention period', async () => {
+    await expect(close('payment_sig_1', authority)).rejects.toThrow(/PaymentProofRetentionActive/);
+  });
+
+  test('only the recorder can close a proof', async () => {
+    await advanceTime(context, RETENTION_SECONDS + 1);
+    const stranger = Keypair.generate();
+    await airdrop(context, stranger.publicKey, 1_000_000_000);
+
+    await expect(close('payment_sig_1', stranger)).rejects.toThrow(/UnauthorizedAuthority/);
+  });
+
+  test('recorder reclaims rent after retention; the count is kept', async () => {
+    await close('payment_sig_1', authority);
+
+    expect(await context.banksClient.getAccount(proofPda('payment_sig_1'))).toBeNull();
+    expect(await proofCount()).toBe(2);
+  });
+
+  test('a closed proof cannot be recorded again', async () => {
+    const paidAt = (await fetchAccount(program, 'paymentProof', proofPda('payment_sig_2'))).recordedAt.toNumber();
+    await close('payment_sig_2', authority);
+
+    await expect(
+      record('payment_sig_2', null, signatureHash('payment_sig_2'), authority, paidAt)
+    ).rejects.toThrow(/PaymentOutsideRetention/);
+    expect(await context.banksClient.getAccount(proofPda('payment_sig_2'))).toBeNull();
+    expect(await proofCount()).toBe(2);
+  });
+});
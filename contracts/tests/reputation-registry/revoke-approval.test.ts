@@ -0,0 +1,176 @@
+/**
+ * Revoke Approval Tests
+ * Tests withdrawing a multisig approval before quorum
+ *
+ * Revocation ensures:
+ * 1. A signer can revoke and later re-approve while below quorum
+ * 2. Approvals are locked in once the proposal is Approved
+ * 3. Only signers who approved can revoke
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram } from '../helpers/bankrun';
+
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+
+const COMPONENTS = { trust: 50, quality: 50, reliability: 50, economic: 50, social: 50 };
+const STATS = { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 };
+
+describe('Revoke Approval', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let admin: Keypair;
+  let agent: Keypair;
+  let signers: Keypair[];
+  let authorityPda: PublicKey;
+  let reputationPda: PublicKey;
+  let multisigPda: PublicKey;
+
+  function proposalPda(id: number): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('proposal'), new BN(id).toArrayLike(Buffer, 'le', 8)],
+      program.programId
+    )[0];
+  }
+
+  async function propose(): Promise<number> {
+    const multisig = await fetchAccount(program, 'multisigAuthority', multisigPda);
+    const id = multisig.proposalCount.toNumber();
+
+    await program.methods
+      .proposeReputationUpdate(500, COMPONENTS, STATS, new Array(32).fill(0))
+      .accounts({
+        multisig: multisigPda,
+        proposal: proposalPda(id),
+        agentReputation: reputationPda,
+        targetAgent: agent.publicKey,
+        proposer: signers[0].publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signers[0]])
+      .rpc();
+
+    return id;
+  }
+
+  function approve(id: number, signer: Keypair) {
+    return program.methods
+      .approveProposal(new BN(id))
+      .accounts({ multisig: multisigPda, proposal: proposalPda(id), signer: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function revoke(id: number, signer: Keypair) {
+    return program.methods
+      .revokeApproval(new BN(id))
+      .accounts({ multisig: multisigPda, proposal: proposalPda(id), signer: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function approvals(id: number): Promise<number> {
+    const proposal = await fetchAccount(program, 'multisigProposal', proposalPda(id));
+    return proposal.approvalCount;
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('reputation_registry', provider);
+
+    admin = Keypair.generate();
+    agent = Keypair.generate();
+    signers = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    for (const kp of [admin, ...signers]) {
+      await airdrop(context, kp.publicKey, 10_000_000_000);
+    }
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], program.programId);
+    [reputationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), agent.publicKey.toBuffer()],
+      program.programId
+    );
+    [multisigPda] = PublicKey.findProgramAddressSync([Buffer.from('multisig_authority')], program.programId);
+
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: admin.publicKey,
+        initializer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda,
+        agentAddress: agent.publicKey,
+        authorityAccount: authorityPda,
+        initializer: admin.publicKey,
+        payer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    // 3-of-3 so two approvals stay below quorum
+    await program.methods
+      .initializeMultisig(signers.map((s) => s.publicKey), 3)
+      .accounts({
+        multisig: multisigPda,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  test('approve, revoke, then re-approve', async () => {
+    const id = await propose();
+    await approve(id, signers[1]);
+    expect(await approvals(id)).toBe(2);
+
+    await revoke(id, signers[1]);
+    expect(await approvals(id)).toBe(1);
+
+    await approve(id, signers[1]);
+    expect(await approvals(id)).toBe(2);
+  });
+
+  test('revoking after quorum fails', async () => {
+    await approve(0, signers[2]);
+    const proposal = await fetchAccount(program, 'multisigProposal', proposalPda(0));
+    expect(proposal.status).toEqual({ approved: {} });
+
+    await expect(revoke(0, signers[1])).rejects.toThrow(/QuorumAlreadyReached/);
+    expect(await approvals(0)).toBe(3);
+  });
+
+  test('a signer who has not approved cannot revoke', async () => {
+    const id = await propose();
+
+    await expect(revoke(id, signers[2])).rejects.toThrow(/NotApproved/);
+    expect(await approvals(id)).toBe(1);
+  });
+
+  test('non-signers cannot revoke', async () => {
+    const outsider = Keypair.generate();
+    await airdrop(context, outsider.publicKey, 1_000_000_000);
+
+    await expect(revoke(1, outsider)).rejects.toThrow(/UnauthorizedSigner/);
+  });
+});
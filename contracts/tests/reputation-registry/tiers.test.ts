@@ -0,0 +1,186 @@
+/**
+ * Reputation Tier Tests
+ * Tests on-chain tier derivation from overall_score
+ *
+ * Tier derivation ensures:
+ * 1. Promotion requires clearing a threshold by the configured margin
+ * 2. Scores hovering just above a threshold don't flap between tiers
+ * 3. Demotion is immediate, including when decay crosses a boundary
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, now } from '../helpers/bankrun';
+
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const SECONDS_PER_DAY = 86400;
+
+const BRONZE = 0;
+const SILVER = 1;
+const GOLD = 2;
+
+describe('Reputation Tiers', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let agent: Keypair;
+  let authorityPda: PublicKey;
+  let reputationPda: PublicKey;
+  let tierConfigPda: PublicKey;
+
+  async function setScore(score: number) {
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+    await advanceTime(context, 10);
+    await program.methods
+      .updateReputation(
+        score,
+        { trust: 50, quality: 50, reliability: 50, economic: 50, social: 50 },
+        { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+        new Array(32).fill(0),
+        reputation.updateNonce,
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda,
+        authorityAccount: authorityPda,
+        agentAddress: agent.publicKey,
+        tierConfig: tierConfigPda,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  async function currentTier(): Promise<number> {
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+    return reputation.tier;
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('reputation_registry', provider);
+
+    authority = Keypair.generate();
+    agent = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+    await airdrop(context, agent.publicKey, 1_000_000_000);
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], program.programId);
+    [reputationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), agent.publicKey.toBuffer()],
+      program.programId
+    );
+    [tierConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('tier_config')],
+      program.programId
+    );
+
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda,
+        agentAddress: agent.publicKey,
+        authorityAccount: authorityPda,
+        initializer: authority.publicKey,
+        payer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .initializeTierConfig(250, 500, 750, 20)
+      .accounts({
+        tierConfig: tierConfigPda,
+        authorityAccount: authorityPda,
+        multisig: null,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('new agents start at Bronze', async () => {
+    expect(await currentTier()).toBe(BRONZE);
+  });
+
+  test('promotion stops short of a tier until the margin is cleared', async () => {
+    await setScore(505);
+    expect(await currentTier()).toBe(SILVER);
+
+    await setScore(525);
+    expect(await currentTier()).toBe(GOLD);
+  });
+
+  test('hovering above the threshold does not demote', async () => {
+    await setScore(510);
+    expect(await currentTier()).toBe(GOLD);
+  });
+
+  test('dropping below a threshold demotes immediately', async () => {
+    await setScore(490);
+    expect(await currentTier()).toBe(SILVER);
+  });
+
+  test('decay crossing a boundary demotes the tier', async () => {
+    await program.methods
+      .enableDecay(10000)
+      .accounts({ agentReputation: reputationPda, owner: agent.publicKey })
+      .signers([agent])
+      .rpc();
+
+    // 30-day grace + 90-day half-life: 490 -> 245 after 120 days
+    await advanceTime(context, 121 * SECONDS_PER_DAY);
+
+    await program.methods
+      .applyDecay()
+      .accounts({
+        agentReputation: reputationPda,
+        tierConfig: tierConfigPda,
+        caller: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+    expect(reputation.overallScore).toBe(245);
+    expect(reputation.tier).toBe(BRONZE);
+  });
+
+  test('rejects non-increasing thresholds', async () => {
+    await expect(
+      program.methods
+        .updateTierConfig(500, 500, 750, 20)
+        .accounts({
+          tierConfig: tierConfigPda,
+          authorityAccount: authorityPda,
+          multisig: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc()
+    ).rejects.toThrow(/InvalidTierThresholds/);
+  });
+});
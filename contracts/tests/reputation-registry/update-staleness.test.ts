@@ -0,0 +1,169 @@
+/**
+ * Reputation Update Staleness Tests
+ * Tests ordering protection on update_reputation
+ *
+ * The staleness guard ensures:
+ * 1. Updates carrying the current nonce and a newer computed_at succeed
+ * 2. Replaying an already-consumed nonce is rejected
+ * 3. Scores computed before the last authority update are rejected
+ * 4. Permissionless decay between computing and submitting a score does not
+ *    make the update stale
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, now } from '../helpers/bankrun';
+
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+
+const COMPONENTS = { trust: 80, quality: 75, reliability: 90, economic: 60, social: 70 };
+const STATS = {
+  totalVotes: 10,
+  positiveVotes: 8,
+  negativeVotes: 2,
+  totalReviews: 4,
+  avgReviewRating: 42,
+};
+
+describe('Reputation Update Staleness', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let authority: Keypair;
+  let agent: Keypair;
+  let authorityPda: PublicKey;
+  let reputationPda: PublicKey;
+
+  async function submitUpdate(score: number, expectedNonce: number, computedAt: number) {
+    return program.methods
+      .updateReputation(
+        score,
+        COMPONENTS,
+        STATS,
+        new Array(32).fill(0),
+        new BN(expectedNonce),
+        new BN(computedAt)
+      )
+      .accounts({
+        agentReputation: reputationPda,
+        authorityAccount: authorityPda,
+        agentAddress: agent.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [{ name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID }],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('reputation_registry', provider);
+
+    authority = Keypair.generate();
+    agent = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+
+    [authorityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('authority')],
+      program.programId
+    );
+    [reputationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), agent.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda,
+        agentAddress: agent.publicKey,
+        authorityAccount: authorityPda,
+        initializer: authority.publicKey,
+        payer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('applies in-order updates and bumps the nonce', async () => {
+    await advanceTime(context, 10);
+    await submitUpdate(500, 0, await now(context));
+
+    await advanceTime(context, 10);
+    await submitUpdate(550, 1, await now(context));
+
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+    expect(reputation.overallScore).toBe(550);
+    expect(reputation.updateNonce.toNumber()).toBe(2);
+  });
+
+  test('rejects a replayed nonce', async () => {
+    await advanceTime(context, 10);
+
+    await expect(submitUpdate(600, 1, await now(context))).rejects.toThrow(/StaleUpdate/);
+
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+    expect(reputation.overallScore).toBe(550);
+  });
+
+  test('rejects a score computed before the last update', async () => {
+    const before = await fetchAccount(program, 'agentReputation', reputationPda);
+    await advanceTime(context, 10);
+
+    await expect(
+      submitUpdate(400, 2, before.lastAuthorityUpdate.toNumber() - 5)
+    ).rejects.toThrow(/StaleUpdate/);
+
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+    expect(reputation.overallScore).toBe(550);
+    expect(reputation.updateNonce.toNumber()).toBe(2);
+  });
+
+  test('decay landing before a computed score does not make it stale', async () => {
+    await program.methods
+      .enableDecay(10000)
+      .accounts({ agentReputation: reputationPda, owner: agent.publicKey })
+      .signers([agent])
+      .rpc();
+
+    await advanceTime(context, 10);
+    const computedAt = await now(context);
+
+    // Anyone can apply decay while the score is in flight
+    await advanceTime(context, 5);
+    await program.methods
+      .applyDecay()
+      .accounts({ agentReputation: reputationPda, caller: authority.publicKey })
+      .signers([authority])
+      .rpc();
+    const decayed = await fetchAccount(program, 'agentReputation', reputationPda);
+    expect(decayed.lastUpdated.toNumber()).toBeGreaterThan(computedAt);
+
+    await submitUpdate(600, 2, computedAt);
+
+    const reputation = await fetchAccount(program, 'agentReputation', reputationPda);
+    expect(reputation.overallScore).toBe(600);
+    expect(reputation.updateNonce.toNumber()).toBe(3);
+    expect(reputation.lastAuthorityUpdate.toNumber()).toBe(reputation.lastUpdated.toNumber());
+  });
+});
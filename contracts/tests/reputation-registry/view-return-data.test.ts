@@ -0,0 +1,159 @@
+/**
+ * Reputation View Return Data Tests
+ * Tests that read-only instructions return structured data via set_return_data
+ *
+ * The views ensure:
+ * 1. get_reputation returns a borsh-encoded ReputationView
+ * 2. get_reputation's return data reaches a program calling it by CPI
+ * 3. get_effective_score returns the decayed score as a u16
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { airdrop, advanceTime, loadProgram, now, simulateReturnData } from '../helpers/bankrun';
+
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const CALLER_PROGRAM_ID = new PublicKey('2V7PiKSoLC4G1Ytpea3b4FVeyGsyMLbuC54GppQZco8S');
+const SECONDS_PER_DAY = 86400;
+
+describe('Reputation View Return Data', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let callerProgram: Program<Idl>;
+  let authority: Keypair;
+  let agent: Keypair;
+  let authorityPda: PublicKey;
+  let reputationPda: PublicKey;
+  let decayConfigPda: PublicKey;
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'cpi_caller', programId: CALLER_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('reputation_registry', provider);
+    callerProgram = loadProgram('cpi_caller', provider);
+
+    authority = Keypair.generate();
+    agent = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+    await airdrop(context, agent.publicKey, 1_000_000_000);
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], program.programId);
+    [reputationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), agent.publicKey.toBuffer()],
+      program.programId
+    );
+    [decayConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('decay_config')], program.programId);
+
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda,
+        agentAddress: agent.publicKey,
+        authorityAccount: authorityPda,
+        initializer: authority.publicKey,
+        payer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await advanceTime(context, 10);
+    await program.methods
+      .updateReputation(
+        640,
+        { trust: 70, quality: 65, reliability: 80, economic: 55, social: 60 },
+        { totalVotes: 12, positiveVotes: 9, negativeVotes: 3, totalReviews: 5, avgReviewRating: 41 },
+        new Array(32).fill(0),
+        new BN(0),
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda,
+        authorityAccount: authorityPda,
+        agentAddress: agent.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('get_reputation returns a ReputationView', async () => {
+    const ix = await program.methods
+      .getReputation()
+      .accounts({ agentReputation: reputationPda, agentAddress: agent.publicKey, decayConfig: decayConfigPda })
+      .instruction();
+
+    const view = program.coder.types.decode('ReputationView', await simulateReturnData(context, ix));
+
+    expect(view.agent.toBase58()).toBe(agent.publicKey.toBase58());
+    expect(view.overallScore).toBe(640);
+    expect(view.effectiveScore).toBe(640);
+    expect(view.componentScores.reliability).toBe(80);
+    expect(view.stats.totalVotes).toBe(12);
+    expect(view.stats.avgReviewRating).toBe(41);
+    expect(view.updateNonce.toNumber()).toBe(1);
+    expect(view.isFrozen).toBe(false);
+  });
+
+  test('a program calling get_reputation by CPI decodes the same view', async () => {
+    const ix = await callerProgram.methods
+      .readReputation()
+      .accounts({
+        agentReputation: reputationPda,
+        agentAddress: agent.publicKey,
+        decayConfig: decayConfigPda,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+      })
+      .instruction();
+
+    const read = callerProgram.coder.types.decode('ReputationRead', await simulateReturnData(context, ix));
+
+    expect(read.agent.toBase58()).toBe(agent.publicKey.toBase58());
+    expect(read.overallScore).toBe(640);
+    expect(read.effectiveScore).toBe(640);
+    expect(read.updateNonce.toNumber()).toBe(1);
+    expect(read.isFrozen).toBe(false);
+  });
+
+  test('get_effective_score round-trips the decayed u16', async () => {
+    await program.methods
+      .enableDecay(10000)
+      .accounts({ agentReputation: reputationPda, owner: agent.publicKey })
+      .signers([agent])
+      .rpc();
+
+    // 30-day grace + one 90-day half-life
+    await advanceTime(context, 121 * SECONDS_PER_DAY);
+
+    const ix = await program.methods
+      .getEffectiveScore()
+      .accounts({ agentReputation: reputationPda, decayConfig: decayConfigPda })
+      .instruction();
+
+    const data = await simulateReturnData(context, ix);
+    expect(data.length).toBe(2);
+    expect(data.readUInt16LE(0)).toBe(320);
+  });
+});
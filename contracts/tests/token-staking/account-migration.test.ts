@@ -0,0 +1,196 @@
+/**
+ * Account Migration Tests
+ * Tests StakingVault layout versioning and the migrate_account instruction
+ *
+ * Account migration ensures:
+ * 1. A vault in the pre-version layout is rejected until it is migrated
+ * 2. migrate_account grows it to the current size, sets the current version and keeps every field
+ * 3. A vault below the minimum supported version fails with UnsupportedAccountVersion
+ * 4. Staking and vault configuration work on a migrated vault
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createMint, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { loadProgram, truncateAccount, tokenStaker, vaultIdentityPda, vaultOwner } from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const MIN_STAKE = 1_000_000;
+const CURRENT_VERSION = 1;
+
+describe('Account Migration', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mint: PublicKey;
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), agent.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** The endorsement index of the agent a vault belongs to */
+  async function vaultIndexPda(vault: PublicKey): Promise<PublicKey> {
+    const { targetAgent } = await fetchAccount(program, 'stakingVault', vault);
+    return endorsementIndexPda(targetAgent);
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  async function stake(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .stakeTokens(new BN(amount), { general: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: await vaultIndexPda(vault),
+        priceFeed: null,
+        agentIdentity: await vaultIdentityPda(program, vault),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  async function accountData(address: PublicKey): Promise<Buffer> {
+    return Buffer.from((await context.banksClient.getAccount(address))!.data);
+  }
+
+  /** Cut the vault back to the layout written before the version field existed */
+  async function truncateVault(vault: PublicKey) {
+    const data = await accountData(vault);
+    await truncateAccount(context, vault, data.length - 1);
+  }
+
+  function migrate(vault: PublicKey) {
+    return program.methods
+      .migrateAccount()
+      .accounts({ vault, payer: context.payer.publicKey, systemProgram: SystemProgram.programId })
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mint = await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6);
+  });
+
+  test('new vaults start at the current version', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    expect((await fetchAccount(program, 'stakingVault', vault)).version).toBe(CURRENT_VERSION);
+  });
+
+  test('a pre-version vault is rejected until it is migrated', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, MIN_STAKE);
+    await truncateVault(vault);
+
+    await expect(stake(vault, from, MIN_STAKE)).rejects.toThrow(/AccountDidNotDeserialize/);
+
+    await migrate(vault);
+    await stake(vault, from, MIN_STAKE);
+  });
+
+  test('migrate_account grows the vault and keeps every field', async () => {
+    const { vault } = await vaultOwner(context, program, mint, {
+      maxTotalStake: 50 * MIN_STAKE,
+      maxStakePerStaker: 20 * MIN_STAKE,
+    });
+    await stake(vault, await tokenStaker(context, mint), 3 * MIN_STAKE);
+    const before = await fetchAccount(program, 'stakingVault', vault);
+    const fullLength = (await accountData(vault)).length;
+    await truncateVault(vault);
+
+    await migrate(vault);
+
+    expect((await accountData(vault)).length).toBe(fullLength);
+    const after = await fetchAccount(program, 'stakingVault', vault);
+    expect(after.version).toBe(CURRENT_VERSION);
+    expect(after.totalStaked.toNumber()).toBe(3 * MIN_STAKE);
+    expect(after.maxTotalStaked.toNumber()).toBe(50 * MIN_STAKE);
+    expect(after.maxPerStaker.toNumber()).toBe(20 * MIN_STAKE);
+    expect(after.targetAgent.equals(before.targetAgent)).toBe(true);
+
+    // A second migration is a no-op
+    const migrated = await accountData(vault);
+    await migrate(vault);
+    expect((await accountData(vault)).equals(migrated)).toBe(true);
+  });
+
+  test('a vault below the minimum version fails with UnsupportedAccountVersion', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const account = (await context.banksClient.getAccount(vault))!;
+    const data = Buffer.from(account.data);
+    data[data.length - 1] = 0;
+    context.setAccount(vault, { ...account, data });
+
+    const from = await tokenStaker(context, mint);
+    await expect(stake(vault, from, MIN_STAKE)).rejects.toThrow(/UnsupportedAccountVersion/);
+
+    await migrate(vault);
+    expect((await fetchAccount(program, 'stakingVault', vault)).version).toBe(CURRENT_VERSION);
+    await stake(vault, from, MIN_STAKE);
+  });
+
+  test('staking and vault configuration work on a migrated vault', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint, { maxTotalStake: 5 * MIN_STAKE });
+    await truncateVault(vault);
+    await migrate(vault);
+
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 5 * MIN_STAKE);
+    await expect(stake(vault, from, MIN_STAKE)).rejects.toThrow(/VaultAtCapacity/);
+
+    await program.methods
+      .updateVaultConfig(null, null, null, new BN(0), null, null)
+      .accounts({ vault, authority: agent.publicKey })
+      .signers([agent])
+      .rpc();
+    await stake(vault, from, MIN_STAKE);
+
+    const position = await fetchAccount(program, 'stakePosition', positionPda(vault, from.wallet.publicKey));
+    expect(position.amount.toNumber()).toBe(6 * MIN_STAKE);
+    expect((await fetchAccount(program, 'stakingVault', vault)).version).toBe(CURRENT_VERSION);
+  });
+});
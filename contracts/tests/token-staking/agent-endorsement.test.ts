@@ -0,0 +1,291 @@
+/**
+ * Agent Endorsement Tests
+ * Tests the per-agent endorsement index aggregated across all of an agent's vaults
+ *
+ * The endorsement index ensures:
+ * 1. Every vault of an agent adds its trust weight and stakers to one index
+ * 2. Unstaking takes the position's weight and staker back out of the index
+ * 3. A vault's contribution is scaled by the normalization factor the verifier sets
+ * 4. get_agent_endorsement returns the aggregate without reading every vault
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+import { createAccount, createMint, mintTo, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  simulateReturnData,
+  IDENTITY_REGISTRY_PROGRAM_ID,
+  mockAgentIdentity,
+} from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const LOCK_PERIOD = 7 * 24 * 60 * 60;
+const MIN_STAKE = 1_000_000;
+
+describe('Agent Endorsement', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mints: PublicKey[];
+  let admin: Keypair;
+  let verifier: Keypair;
+
+  function vaultPda(agent: PublicKey, mint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('vault'), agent.toBuffer(), mint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), agent.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function agentIdentityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_REGISTRY_PROGRAM_ID)[0];
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** The test mint held by one of `agent`'s vaults */
+  function vaultMint(agent: Keypair, vault: PublicKey): PublicKey {
+    return mints.find((mint) => vaultPda(agent.publicKey, mint).equals(vault))!;
+  }
+
+  /** A fresh agent (also the vault authority) with one vault per test mint */
+  async function agentWithVaults(): Promise<{ agent: Keypair; vaults: PublicKey[] }> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    const vaults: PublicKey[] = [];
+    for (const mint of mints) {
+      const vault = vaultPda(agent.publicKey, mint);
+      await program.methods
+        .initializeVault(new BN(MIN_STAKE), new BN(LOCK_PERIOD), 100, new BN(0), new BN(0), new BN(0))
+        .accounts({
+          vault,
+          vaultTokenAccount: vaultTokenPda(vault),
+          tokenMint: mint,
+          targetAgent: agent.publicKey,
+          agentIdentity: mockAgentIdentity(context, agent.publicKey),
+          protocolConfig: protocolConfigPda(),
+          endorsementIndex: endorsementIndexPda(agent.publicKey),
+          authority: agent.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([agent])
+        .rpc();
+      vaults.push(vault);
+    }
+    return { agent, vaults };
+  }
+
+  /** A funded staker holding 100 tokens of the given mint */
+  async function staker(mint: PublicKey): Promise<{ wallet: Keypair; tokens: PublicKey }> {
+    const wallet = Keypair.generate();
+    await airdrop(context, wallet.publicKey, 10 * LAMPORTS_PER_SOL);
+    const tokens = await createAccount(context.banksClient, context.payer, mint, wallet.publicKey);
+    await mintTo(context.banksClient, context.payer, mint, tokens, context.payer, 100 * MIN_STAKE);
+    return { wallet, tokens };
+  }
+
+  function stake(agent: Keypair, vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .stakeTokens(new BN(amount), { general: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: endorsementIndexPda(agent.publicKey),
+        priceFeed: null,
+        agentIdentity: agentIdentityPda(agent.publicKey),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(vaultMint(agent, vault)),
+        tokenMint: vaultMint(agent, vault),
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  function unstake(agent: Keypair, vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .unstakeTokens(new BN(amount))
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        endorsementIndex: endorsementIndexPda(agent.publicKey),
+        staker: from.wallet.publicKey,
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(vaultMint(agent, vault)),
+        tokenMint: vaultMint(agent, vault),
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  function setNormalization(agent: Keypair, vault: PublicKey, bps: number, signer: Keypair = verifier) {
+    return program.methods
+      .setVaultNormalization(bps)
+      .accounts({
+        registryConfig: registryConfigPda(),
+        vault,
+        endorsementIndex: endorsementIndexPda(agent.publicKey),
+        verifier: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function index(agent: Keypair) {
+    return fetchAccount(program, 'agentEndorsementIndex', endorsementIndexPda(agent.publicKey));
+  }
+
+  async function vaultWeight(vault: PublicKey): Promise<number> {
+    const { totalTrustWeight } = await fetchAccount(program, 'stakingVault', vault);
+    return totalTrustWeight.toNumber();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mints = [
+      await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6),
+      await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6),
+    ];
+    [admin, verifier] = [Keypair.generate(), Keypair.generate()];
+    await airdrop(context, admin.publicKey, LAMPORTS_PER_SOL);
+    await airdrop(context, verifier.publicKey, LAMPORTS_PER_SOL);
+
+    await program.methods
+      .initializeRegistryConfig(verifier.publicKey, false)
+      .accounts({
+        registryConfig: registryConfigPda(),
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  test('stakes in two vaults of one agent aggregate into its index', async () => {
+    const { agent, vaults } = await agentWithVaults();
+    await stake(agent, vaults[0], await staker(mints[0]), 5 * MIN_STAKE);
+    await stake(agent, vaults[1], await staker(mints[1]), 3 * MIN_STAKE);
+    await stake(agent, vaults[1], await staker(mints[1]), 2 * MIN_STAKE);
+
+    const totals = await index(agent);
+    expect(totals.targetAgent.toBase58()).toBe(agent.publicKey.toBase58());
+    expect(totals.vaultCount).toBe(2);
+    expect(totals.totalStakers).toBe(3);
+    expect(totals.totalTrustWeight.toNumber()).toBe((await vaultWeight(vaults[0])) + (await vaultWeight(vaults[1])));
+    expect(totals.totalTrustWeight.toNumber()).toBeGreaterThan(0);
+  });
+
+  test('unstaking takes the position back out of the index', async () => {
+    const { agent, vaults } = await agentWithVaults();
+    const leaving = await staker(mints[0]);
+    await stake(agent, vaults[0], leaving, 5 * MIN_STAKE);
+    await stake(agent, vaults[1], await staker(mints[1]), 3 * MIN_STAKE);
+    const before = await index(agent);
+
+    await advanceTime(context, LOCK_PERIOD);
+    await unstake(agent, vaults[0], leaving, 5 * MIN_STAKE);
+
+    const after = await index(agent);
+    expect(after.vaultCount).toBe(2);
+    expect(after.totalStakers).toBe(before.totalStakers - 1);
+    expect(after.totalTrustWeight.toNumber()).toBe(await vaultWeight(vaults[1]));
+    expect(after.totalTrustWeight.toNumber()).toBeLessThan(before.totalTrustWeight.toNumber());
+  });
+
+  test('the normalization factor scales a vault\'s contribution', async () => {
+    const { agent, vaults } = await agentWithVaults();
+    await stake(agent, vaults[0], await staker(mints[0]), 4 * MIN_STAKE);
+    await stake(agent, vaults[1], await staker(mints[1]), 4 * MIN_STAKE);
+    const [weightA, weightB] = [await vaultWeight(vaults[0]), await vaultWeight(vaults[1])];
+
+    await setNormalization(agent, vaults[1], 5_000);
+
+    expect((await index(agent)).totalTrustWeight.toNumber()).toBe(weightA + Math.floor(weightB / 2));
+    const vault = await fetchAccount(program, 'stakingVault', vaults[1]);
+    expect(vault.normalizationBps).toBe(5_000);
+    // The raw vault weight is untouched; only the index contribution is scaled
+    expect(vault.totalTrustWeight.toNumber()).toBe(weightB);
+  });
+
+  test('normalization above 100% or from a non-verifier is rejected', async () => {
+    const { agent, vaults } = await agentWithVaults();
+
+    await expect(setNormalization(agent, vaults[0], 10_001)).rejects.toThrow(/InvalidNormalization/);
+    const outsider = Keypair.generate();
+    await airdrop(context, outsider.publicKey, LAMPORTS_PER_SOL);
+    await expect(setNormalization(agent, vaults[0], 5_000, outsider)).rejects.toThrow();
+  });
+
+  test('get_agent_endorsement returns the aggregate index', async () => {
+    const { agent, vaults } = await agentWithVaults();
+    await stake(agent, vaults[0], await staker(mints[0]), 5 * MIN_STAKE);
+    await stake(agent, vaults[1], await staker(mints[1]), 5 * MIN_STAKE);
+
+    const ix = await program.methods
+      .getAgentEndorsement()
+      .accounts({ endorsementIndex: endorsementIndexPda(agent.publicKey), targetAgent: agent.publicKey })
+      .instruction();
+    const data = await simulateReturnData(context, ix);
+
+    // target_agent (32) + vault_count (4) + total_trust_weight (8) + total_stakers (4)
+    const totals = await index(agent);
+    expect(new PublicKey(data.subarray(0, 32)).toBase58()).toBe(agent.publicKey.toBase58());
+    expect(data.readUInt32LE(32)).toBe(2);
+    expect(Number(data.readBigUInt64LE(36))).toBe(totals.totalTrustWeight.toNumber());
+    expect(data.readUInt32LE(44)).toBe(2);
+  });
+});
@@ -0,0 +1,231 @@
+/**
+ * Agent Identity Guard Tests
+ * Tests that vaults and new stakes require the target agent's identity_registry AgentIdentity
+ *
+ * The identity guard ensures:
+ * 1. Vaults cannot be created for pubkeys that were never registered as agents
+ * 2. Deactivated agents, and agents slashed max_agent_slash_count times or more, get no new vaults or stakes
+ * 3. Identity accounts not owned by identity_registry are treated as unregistered
+ * 4. Existing stakers can still exit after the agent is deactivated
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+import { createAccount, createMint, getAccount, mintTo, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, IDENTITY_REGISTRY_PROGRAM_ID, mockAgentIdentity } from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const LOCK_PERIOD = 7 * 24 * 60 * 60;
+const MIN_STAKE = 1_000_000;
+const DEFAULT_MAX_SLASH_COUNT = 3;
+
+type Wallet = { wallet: Keypair; tokens: PublicKey };
+
+describe('Agent Identity Guard', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mint: PublicKey;
+
+  function vaultPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('vault'), agent.toBuffer(), mint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), agent.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function agentIdentityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_REGISTRY_PROGRAM_ID)[0];
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** A funded agent keypair with no identity account yet */
+  async function newAgent(): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10 * LAMPORTS_PER_SOL);
+    return agent;
+  }
+
+  /** Create the agent's vault against whatever identity account currently sits at its PDA */
+  function initializeVault(agent: Keypair) {
+    const vault = vaultPda(agent.publicKey);
+    return program.methods
+      .initializeVault(new BN(MIN_STAKE), new BN(LOCK_PERIOD), 100, new BN(0), new BN(0), new BN(0))
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        tokenMint: mint,
+        targetAgent: agent.publicKey,
+        agentIdentity: agentIdentityPda(agent.publicKey),
+        protocolConfig: protocolConfigPda(),
+        endorsementIndex: endorsementIndexPda(agent.publicKey),
+        authority: agent.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  /** A registered agent in good standing and its vault */
+  async function registeredVault(): Promise<{ agent: Keypair; vault: PublicKey }> {
+    const agent = await newAgent();
+    mockAgentIdentity(context, agent.publicKey);
+    await initializeVault(agent);
+    return { agent, vault: vaultPda(agent.publicKey) };
+  }
+
+  /** A funded staker with a token account holding 100 tokens */
+  async function staker(): Promise<Wallet> {
+    const wallet = Keypair.generate();
+    await airdrop(context, wallet.publicKey, 10 * LAMPORTS_PER_SOL);
+    const tokens = await createAccount(context.banksClient, context.payer, mint, wallet.publicKey);
+    await mintTo(context.banksClient, context.payer, mint, tokens, context.payer, 100 * MIN_STAKE);
+    return { wallet, tokens };
+  }
+
+  function stake(agent: Keypair, vault: PublicKey, from: Wallet, amount: number) {
+    return program.methods
+      .stakeTokens(new BN(amount), { general: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: endorsementIndexPda(agent.publicKey),
+        priceFeed: null,
+        agentIdentity: agentIdentityPda(agent.publicKey),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  function unstake(agent: Keypair, vault: PublicKey, from: Wallet, amount: number) {
+    return program.methods
+      .unstakeTokens(new BN(amount))
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        endorsementIndex: endorsementIndexPda(agent.publicKey),
+        staker: from.wallet.publicKey,
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mint = await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6);
+  });
+
+  test('vault creation for an unregistered pubkey fails', async () => {
+    const agent = await newAgent();
+    await expect(initializeVault(agent)).rejects.toThrow(/AgentNotRegistered/);
+  });
+
+  test('identity accounts not owned by identity_registry are rejected', async () => {
+    const agent = await newAgent();
+    mockAgentIdentity(context, agent.publicKey, {}, SystemProgram.programId);
+    await expect(initializeVault(agent)).rejects.toThrow(/AgentNotRegistered/);
+  });
+
+  test('vault creation for a deactivated agent fails', async () => {
+    const agent = await newAgent();
+    mockAgentIdentity(context, agent.publicKey, { isActive: false });
+    await expect(initializeVault(agent)).rejects.toThrow(/AgentInactive/);
+  });
+
+  test('agents at the slash threshold are rejected', async () => {
+    const agent = await newAgent();
+    mockAgentIdentity(context, agent.publicKey, { slashCount: DEFAULT_MAX_SLASH_COUNT });
+    await expect(initializeVault(agent)).rejects.toThrow(/AgentSlashLimitExceeded/);
+
+    mockAgentIdentity(context, agent.publicKey, { slashCount: DEFAULT_MAX_SLASH_COUNT - 1 });
+    await initializeVault(agent);
+    const vault = await fetchAccount(program, 'stakingVault', vaultPda(agent.publicKey));
+    expect(vault.targetAgent.toBase58()).toBe(agent.publicKey.toBase58());
+  });
+
+  test('a slashed agent gets no new stakes on an existing vault', async () => {
+    const { agent, vault } = await registeredVault();
+    const from = await staker();
+    await stake(agent, vault, from, MIN_STAKE);
+
+    mockAgentIdentity(context, agent.publicKey, { slashCount: DEFAULT_MAX_SLASH_COUNT });
+    await expect(stake(agent, vault, from, MIN_STAKE)).rejects.toThrow(/AgentSlashLimitExceeded/);
+  });
+
+  test('existing stakers can still exit after the agent is deactivated', async () => {
+    const { agent, vault } = await registeredVault();
+    const from = await staker();
+    await stake(agent, vault, from, 5 * MIN_STAKE);
+
+    mockAgentIdentity(context, agent.publicKey, { isActive: false });
+    const newcomer = await staker();
+    await expect(stake(agent, vault, newcomer, MIN_STAKE)).rejects.toThrow(/AgentInactive/);
+    await expect(stake(agent, vault, from, MIN_STAKE)).rejects.toThrow(/AgentInactive/);
+
+    await advanceTime(context, LOCK_PERIOD + 1);
+    await unstake(agent, vault, from, 5 * MIN_STAKE);
+
+    const { amount } = await getAccount(context.banksClient, from.tokens);
+    expect(Number(amount)).toBe(100 * MIN_STAKE);
+    const { totalStaked } = await fetchAccount(program, 'stakingVault', vault);
+    expect(totalStaked.toNumber()).toBe(0);
+  });
+});
@@ -0,0 +1,224 @@
+/**
+ * Category Breakdown Tests
+ * Tests the per-category stake buckets on StakingVault and get_category_breakdown
+ *
+ * Category buckets ensure:
+ * 1. Stakes and unstakes move tokens and staker counts in their position's category bucket
+ * 2. Topping up with a different category moves the whole position to the new bucket
+ * 3. The buckets always sum to the vault's total_staked and total_stakers
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createMint, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  advanceTime,
+  loadProgram,
+  simulateReturnData,
+  tokenStaker,
+  vaultIdentityPda,
+  vaultOwner,
+} from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const LOCK_PERIOD = 7 * 24 * 60 * 60;
+const MIN_STAKE = 1_000_000;
+
+describe('Category Breakdown', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mint: PublicKey;
+
+  type Category = 'general' | 'quality' | 'reliability' | 'capability' | 'security';
+  const CATEGORIES: Category[] = ['general', 'quality', 'reliability', 'capability', 'security'];
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), agent.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** The endorsement index of the agent a vault belongs to */
+  async function vaultIndexPda(vault: PublicKey): Promise<PublicKey> {
+    const { targetAgent } = await fetchAccount(program, 'stakingVault', vault);
+    return endorsementIndexPda(targetAgent);
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  async function stake(
+    vault: PublicKey,
+    from: { wallet: Keypair; tokens: PublicKey },
+    amount: number,
+    category: Category = 'general'
+  ) {
+    return program.methods
+      .stakeTokens(new BN(amount), { [category]: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: await vaultIndexPda(vault),
+        priceFeed: null,
+        agentIdentity: await vaultIdentityPda(program, vault),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  async function unstake(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .unstakeTokens(new BN(amount))
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        endorsementIndex: await vaultIndexPda(vault),
+        staker: from.wallet.publicKey,
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  /** Decode get_category_breakdown's return data into per-category maps */
+  async function breakdown(vault: PublicKey) {
+    const ix = await program.methods.getCategoryBreakdown().accounts({ vault }).instruction();
+    const data = await simulateReturnData(context, ix);
+    // vault (32) + target_agent (32) + total_staked (8) + total_stakers (4), then the buckets
+    let offset = 64;
+    const totalStaked = Number(data.readBigUInt64LE(offset));
+    const totalStakers = data.readUInt32LE(offset + 8);
+    offset += 12;
+    const staked = {} as Record<Category, number>;
+    const stakers = {} as Record<Category, number>;
+    CATEGORIES.forEach((category, i) => {
+      staked[category] = Number(data.readBigUInt64LE(offset + 8 * i));
+      stakers[category] = data.readUInt32LE(offset + 8 * CATEGORIES.length + 4 * i);
+    });
+
+    const sum = (values: Record<Category, number>) => CATEGORIES.reduce((acc, c) => acc + values[c], 0);
+    expect(sum(staked)).toBe(totalStaked);
+    expect(sum(stakers)).toBe(totalStakers);
+    const vaultState = await fetchAccount(program, 'stakingVault', vault);
+    expect(totalStaked).toBe(vaultState.totalStaked.toNumber());
+    expect(totalStakers).toBe(vaultState.totalStakers);
+    return { staked, stakers };
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mint = await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6);
+  });
+
+  test('stakes land in the bucket of their category', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const [a, b, c] = [
+      await tokenStaker(context, mint),
+      await tokenStaker(context, mint),
+      await tokenStaker(context, mint),
+    ];
+    await stake(vault, a, 5 * MIN_STAKE, 'security');
+    await stake(vault, b, 3 * MIN_STAKE, 'security');
+    await stake(vault, c, 2 * MIN_STAKE, 'reliability');
+
+    const { staked, stakers } = await breakdown(vault);
+    expect(staked.security).toBe(8 * MIN_STAKE);
+    expect(stakers.security).toBe(2);
+    expect(staked.reliability).toBe(2 * MIN_STAKE);
+    expect(stakers.reliability).toBe(1);
+    expect(staked.general).toBe(0);
+  });
+
+  test('unstaking drains the bucket and drops the staker on a full exit', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const [a, b] = [await tokenStaker(context, mint), await tokenStaker(context, mint)];
+    await stake(vault, a, 5 * MIN_STAKE, 'quality');
+    await stake(vault, b, 4 * MIN_STAKE, 'quality');
+    await advanceTime(context, LOCK_PERIOD);
+
+    await unstake(vault, a, 2 * MIN_STAKE);
+    let { staked, stakers } = await breakdown(vault);
+    expect(staked.quality).toBe(7 * MIN_STAKE);
+    expect(stakers.quality).toBe(2);
+
+    await unstake(vault, b, 4 * MIN_STAKE);
+    ({ staked, stakers } = await breakdown(vault));
+    expect(staked.quality).toBe(3 * MIN_STAKE);
+    expect(stakers.quality).toBe(1);
+  });
+
+  test('a top-up under a new category moves the whole position', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const [mover, stayer] = [await tokenStaker(context, mint), await tokenStaker(context, mint)];
+    await stake(vault, mover, 5 * MIN_STAKE, 'general');
+    await stake(vault, stayer, 1 * MIN_STAKE, 'general');
+
+    await stake(vault, mover, 2 * MIN_STAKE, 'capability');
+    const { staked, stakers } = await breakdown(vault);
+    expect(staked.general).toBe(MIN_STAKE);
+    expect(stakers.general).toBe(1);
+    expect(staked.capability).toBe(7 * MIN_STAKE);
+    expect(stakers.capability).toBe(1);
+
+    const position = await fetchAccount(program, 'stakePosition', positionPda(vault, mover.wallet.publicKey));
+    expect(position.category).toEqual({ capability: {} });
+  });
+
+  test('a top-up in the same category only adds to its bucket', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 5 * MIN_STAKE, 'security');
+    await stake(vault, from, 2 * MIN_STAKE, 'security');
+
+    const { staked, stakers } = await breakdown(vault);
+    expect(staked.security).toBe(7 * MIN_STAKE);
+    expect(stakers.security).toBe(1);
+  });
+});
@@ -0,0 +1,228 @@
+/**
+ * Close Account Tests
+ * Tests close_stake_position / close_vault for token staking accounts
+ *
+ * Closing ensures:
+ * 1. Only inactive, empty positions can be closed, and their rent returns to the staker
+ * 2. Re-staking after a position is closed re-initializes it from scratch
+ * 3. Vaults close only with no stake and an empty token account, returning all rent to the authority
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createMint, mintTo, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, tokenStaker, vaultIdentityPda, vaultOwner } from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const LOCK_PERIOD = 7 * 24 * 60 * 60;
+const MIN_STAKE = 1_000_000;
+
+describe('Close Accounts', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mint: PublicKey;
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), agent.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** The endorsement index of the agent a vault belongs to */
+  async function vaultIndexPda(vault: PublicKey): Promise<PublicKey> {
+    const { targetAgent } = await fetchAccount(program, 'stakingVault', vault);
+    return endorsementIndexPda(targetAgent);
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  async function stake(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .stakeTokens(new BN(amount), { general: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: await vaultIndexPda(vault),
+        priceFeed: null,
+        agentIdentity: await vaultIdentityPda(program, vault),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  async function unstake(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .unstakeTokens(new BN(amount))
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        endorsementIndex: await vaultIndexPda(vault),
+        staker: from.wallet.publicKey,
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  function closePosition(vault: PublicKey, from: { wallet: Keypair }) {
+    return program.methods
+      .closeStakePosition()
+      .accounts({ stakePosition: positionPda(vault, from.wallet.publicKey), vault, staker: from.wallet.publicKey })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  async function closeVault(agent: Keypair, vault: PublicKey) {
+    return program.methods
+      .closeVault()
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        rewardTokenAccount: null,
+        authorityTokenAccount: null,
+        endorsementIndex: await vaultIndexPda(vault),
+        authority: agent.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  async function lamports(account: PublicKey): Promise<bigint> {
+    return context.banksClient.getBalance(account);
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mint = await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6);
+  });
+
+  test('a fully unstaked position closes and refunds its rent to the staker', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 5 * MIN_STAKE);
+    await advanceTime(context, LOCK_PERIOD);
+    await unstake(vault, from, 5 * MIN_STAKE);
+
+    const position = positionPda(vault, from.wallet.publicKey);
+    const rent = await lamports(position);
+    const before = await lamports(from.wallet.publicKey);
+    await closePosition(vault, from);
+
+    expect(await context.banksClient.getAccount(position)).toBeNull();
+    expect((await lamports(from.wallet.publicKey)) - before).toBe(rent);
+  });
+
+  test('a position still holding tokens cannot be closed', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 5 * MIN_STAKE);
+
+    await expect(closePosition(vault, from)).rejects.toThrow(/PositionStillActive/);
+  });
+
+  test('re-staking after closing a position starts a fresh one', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 5 * MIN_STAKE);
+    await advanceTime(context, LOCK_PERIOD);
+    await unstake(vault, from, 5 * MIN_STAKE);
+    await closePosition(vault, from);
+
+    await stake(vault, from, 2 * MIN_STAKE);
+    const position = await fetchAccount(program, 'stakePosition', positionPda(vault, from.wallet.publicKey));
+    expect(position.amount.toNumber()).toBe(2 * MIN_STAKE);
+    expect(position.isActive).toBe(true);
+    expect(position.isSlashed).toBe(false);
+    expect(position.unstakedAt.toNumber()).toBe(0);
+    const vaultState = await fetchAccount(program, 'stakingVault', vault);
+    expect(vaultState.totalStaked.toNumber()).toBe(2 * MIN_STAKE);
+    expect(vaultState.totalStakers).toBe(1);
+  });
+
+  test('an empty vault closes along with its token account', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 5 * MIN_STAKE);
+    await advanceTime(context, LOCK_PERIOD);
+    await unstake(vault, from, 5 * MIN_STAKE);
+
+    const rent = (await lamports(vault)) + (await lamports(vaultTokenPda(vault)));
+    const before = await lamports(agent.publicKey);
+    await closeVault(agent, vault);
+
+    expect(await context.banksClient.getAccount(vault)).toBeNull();
+    expect(await context.banksClient.getAccount(vaultTokenPda(vault))).toBeNull();
+    expect((await lamports(agent.publicKey)) - before).toBe(rent);
+  });
+
+  test('a vault with stake or a residual token balance cannot be closed', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 5 * MIN_STAKE);
+    await expect(closeVault(agent, vault)).rejects.toThrow(/VaultNotEmpty/);
+
+    await advanceTime(context, LOCK_PERIOD);
+    await unstake(vault, from, 5 * MIN_STAKE);
+    // Tokens sent straight to the vault token account are not tracked as stake
+    await mintTo(context.banksClient, context.payer, mint, vaultTokenPda(vault), context.payer, 1);
+    await expect(closeVault(agent, vault)).rejects.toThrow(/VaultNotEmpty/);
+  });
+
+  test('only the vault authority can close the vault', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const intruder = Keypair.generate();
+    await airdrop(context, intruder.publicKey, LAMPORTS_PER_SOL);
+
+    await expect(closeVault(intruder, vault)).rejects.toThrow(/UnauthorizedAuthority/);
+  });
+});
@@ -0,0 +1,263 @@
+/**
+ * Delegated Stake Tests
+ * Tests stake_tokens_for and set_stake_operator for positions staked on a beneficiary's behalf
+ *
+ * Delegated positions ensure:
+ * 1. A funder can open a position whose staker of record is the beneficiary
+ * 2. Only the beneficiary can unstake, and only to the withdrawal account recorded at funding
+ * 3. The funder's operator can top the position up but cannot withdraw from it
+ * 4. The beneficiary cannot redirect the position through a self-stake top-up
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createAccount, createMint, getAccount, mintTo, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, vaultIdentityPda, vaultOwner } from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const LOCK_PERIOD = 7 * 24 * 60 * 60;
+const MIN_STAKE = 1_000_000;
+
+type Wallet = { wallet: Keypair; tokens: PublicKey };
+
+describe('Delegated Stake', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mint: PublicKey;
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), agent.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** The endorsement index of the agent a vault belongs to */
+  async function vaultIndexPda(vault: PublicKey): Promise<PublicKey> {
+    const { targetAgent } = await fetchAccount(program, 'stakingVault', vault);
+    return endorsementIndexPda(targetAgent);
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** A funded wallet with a token account holding `balance` tokens */
+  async function wallet(balance = 100 * MIN_STAKE): Promise<Wallet> {
+    const keypair = Keypair.generate();
+    await airdrop(context, keypair.publicKey, 10 * LAMPORTS_PER_SOL);
+    const tokens = await createAccount(context.banksClient, context.payer, mint, keypair.publicKey);
+    if (balance > 0) {
+      await mintTo(context.banksClient, context.payer, mint, tokens, context.payer, balance);
+    }
+    return { wallet: keypair, tokens };
+  }
+
+  async function stakeFor(
+    vault: PublicKey,
+    funder: Wallet,
+    beneficiary: PublicKey,
+    withdrawalTokenAccount: PublicKey,
+    amount: number
+  ) {
+    return program.methods
+      .stakeTokensFor(new BN(amount), { general: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, beneficiary),
+        funderTokenAccount: funder.tokens,
+        withdrawalTokenAccount,
+        beneficiary,
+        funder: funder.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: await vaultIndexPda(vault),
+        priceFeed: null,
+        agentIdentity: await vaultIdentityPda(program, vault),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([funder.wallet])
+      .rpc();
+  }
+
+  async function stake(vault: PublicKey, from: Wallet, amount: number) {
+    return program.methods
+      .stakeTokens(new BN(amount), { general: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: await vaultIndexPda(vault),
+        priceFeed: null,
+        agentIdentity: await vaultIdentityPda(program, vault),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  /** Unstake the position of `staker` (its PDA wallet) to `to`, signed by `signer` */
+  async function unstake(vault: PublicKey, staker: PublicKey, signer: Keypair, to: PublicKey, amount: number) {
+    return program.methods
+      .unstakeTokens(new BN(amount))
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, staker),
+        stakerTokenAccount: to,
+        endorsementIndex: await vaultIndexPda(vault),
+        staker: signer.publicKey,
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  function setOperator(vault: PublicKey, beneficiary: PublicKey, funder: Keypair, operator: PublicKey) {
+    return program.methods
+      .setStakeOperator(operator)
+      .accounts({ stakePosition: positionPda(vault, beneficiary), funder: funder.publicKey })
+      .signers([funder])
+      .rpc();
+  }
+
+  async function position(vault: PublicKey, staker: PublicKey) {
+    return fetchAccount(program, 'stakePosition', positionPda(vault, staker));
+  }
+
+  async function balance(tokens: PublicKey): Promise<number> {
+    return Number((await getAccount(context.banksClient, tokens)).amount);
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mint = await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6);
+  });
+
+  test('a funder opens a position whose staker of record is the beneficiary', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const [funder, beneficiary] = [await wallet(), await wallet(0)];
+    await stakeFor(vault, funder, beneficiary.wallet.publicKey, beneficiary.tokens, 10 * MIN_STAKE);
+
+    const after = await position(vault, beneficiary.wallet.publicKey);
+    expect(after.isActive).toBe(true);
+    expect(after.amount.toNumber()).toBe(10 * MIN_STAKE);
+    expect(after.staker.toBase58()).toBe(beneficiary.wallet.publicKey.toBase58());
+    expect(after.funder.toBase58()).toBe(funder.wallet.publicKey.toBase58());
+    expect(after.withdrawalAddress.toBase58()).toBe(beneficiary.tokens.toBase58());
+    expect(after.operator.toBase58()).toBe(PublicKey.default.toBase58());
+    expect(await balance(funder.tokens)).toBe(90 * MIN_STAKE);
+  });
+
+  test('the beneficiary unstakes to the recorded withdrawal account only', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const [funder, beneficiary, treasury] = [await wallet(), await wallet(0), await wallet(0)];
+    await stakeFor(vault, funder, beneficiary.wallet.publicKey, treasury.tokens, 10 * MIN_STAKE);
+    await advanceTime(context, LOCK_PERIOD);
+
+    const staker = beneficiary.wallet.publicKey;
+    await expect(unstake(vault, staker, beneficiary.wallet, beneficiary.tokens, 10 * MIN_STAKE)).rejects.toThrow(
+      /InvalidTokenOwner/
+    );
+
+    await unstake(vault, staker, beneficiary.wallet, treasury.tokens, 10 * MIN_STAKE);
+    expect(await balance(treasury.tokens)).toBe(10 * MIN_STAKE);
+    expect((await position(vault, staker)).isActive).toBe(false);
+  });
+
+  test('the funder cannot unstake the beneficiary\'s position', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const [funder, beneficiary] = [await wallet(), await wallet(0)];
+    await stakeFor(vault, funder, beneficiary.wallet.publicKey, funder.tokens, 10 * MIN_STAKE);
+    await advanceTime(context, LOCK_PERIOD);
+
+    // The position PDA is seeded by the beneficiary, so the funder's signature does not match it
+    await expect(
+      unstake(vault, beneficiary.wallet.publicKey, funder.wallet, funder.tokens, 10 * MIN_STAKE)
+    ).rejects.toThrow();
+    expect((await position(vault, beneficiary.wallet.publicKey)).amount.toNumber()).toBe(10 * MIN_STAKE);
+  });
+
+  test('an operator can top up but not withdraw', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const [funder, beneficiary, operator] = [await wallet(), await wallet(0), await wallet()];
+    const staker = beneficiary.wallet.publicKey;
+    await stakeFor(vault, funder, staker, beneficiary.tokens, 10 * MIN_STAKE);
+
+    // Until the funder sets it, the operator cannot top up
+    await expect(stakeFor(vault, operator, staker, beneficiary.tokens, MIN_STAKE)).rejects.toThrow(
+      /UnauthorizedOperator/
+    );
+    await expect(setOperator(vault, staker, operator.wallet, operator.wallet.publicKey)).rejects.toThrow(
+      /UnauthorizedOperator/
+    );
+
+    await setOperator(vault, staker, funder.wallet, operator.wallet.publicKey);
+    await stakeFor(vault, operator, staker, beneficiary.tokens, 5 * MIN_STAKE);
+    expect((await position(vault, staker)).amount.toNumber()).toBe(15 * MIN_STAKE);
+
+    // Top-ups cannot redirect withdrawals
+    await expect(stakeFor(vault, operator, staker, operator.tokens, MIN_STAKE)).rejects.toThrow(
+      /WithdrawalAddressMismatch/
+    );
+
+    await advanceTime(context, LOCK_PERIOD);
+    await expect(unstake(vault, staker, operator.wallet, operator.tokens, 15 * MIN_STAKE)).rejects.toThrow();
+    await expect(unstake(vault, staker, operator.wallet, beneficiary.tokens, 15 * MIN_STAKE)).rejects.toThrow();
+    expect((await position(vault, staker)).amount.toNumber()).toBe(15 * MIN_STAKE);
+  });
+
+  test('the beneficiary cannot top up a delegated position with stake_tokens', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const [funder, beneficiary] = [await wallet(), await wallet()];
+    await stakeFor(vault, funder, beneficiary.wallet.publicKey, funder.tokens, 10 * MIN_STAKE);
+
+    await expect(stake(vault, beneficiary, MIN_STAKE)).rejects.toThrow(/DelegatedPosition/);
+  });
+});
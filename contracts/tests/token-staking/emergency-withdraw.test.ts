@@ -0,0 +1,200 @@
+/**
+ * Emergency Withdrawal Tests
+ * Tests configure_emergency_exit / emergency_withdraw for token stake positions
+ *
+ * Emergency withdrawal ensures:
+ * 1. Locked stakers can leave a vault that has been paused for longer than its grace period
+ * 2. The vault authority can open early exit without pausing
+ * 3. The early-exit penalty goes to the penalty destination and vault totals stay consistent
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createAccount, createMint, getAccount, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { advanceTime, loadProgram, tokenStaker, vaultIdentityPda, vaultOwner } from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const MIN_STAKE = 1_000_000;
+
+describe('Emergency Withdrawal', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mint: PublicKey;
+  let penaltyAccount: PublicKey;
+
+  const GRACE_PERIOD = 24 * 60 * 60;
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), agent.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** The endorsement index of the agent a vault belongs to */
+  async function vaultIndexPda(vault: PublicKey): Promise<PublicKey> {
+    const { targetAgent } = await fetchAccount(program, 'stakingVault', vault);
+    return endorsementIndexPda(targetAgent);
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  async function tokenBalance(account: PublicKey): Promise<number> {
+    return Number((await getAccount(context.banksClient, account)).amount);
+  }
+
+  async function stake(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .stakeTokens(new BN(amount), { general: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: await vaultIndexPda(vault),
+        priceFeed: null,
+        agentIdentity: await vaultIdentityPda(program, vault),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  function configureEmergencyExit(agent: Keypair, vault: PublicKey, earlyExit: boolean, penaltyBps: number) {
+    return program.methods
+      .configureEmergencyExit(new BN(GRACE_PERIOD), earlyExit, penaltyBps)
+      .accounts({ vault, penaltyDestination: penaltyAccount, authority: agent.publicKey })
+      .signers([agent])
+      .rpc();
+  }
+
+  function setPaused(agent: Keypair, vault: PublicKey, paused: boolean) {
+    const method = paused ? program.methods.pauseVault() : program.methods.unpauseVault();
+    return method.accounts({ vault, authority: agent.publicKey }).signers([agent]).rpc();
+  }
+
+  async function emergencyWithdraw(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }) {
+    return program.methods
+      .emergencyWithdraw()
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        penaltyDestination: penaltyAccount,
+        endorsementIndex: await vaultIndexPda(vault),
+        staker: from.wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mint = await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6);
+    penaltyAccount = await createAccount(context.banksClient, context.payer, mint, Keypair.generate().publicKey);
+  });
+
+  test('a long pause lets locked stakers exit with the early-exit penalty', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    await configureEmergencyExit(agent, vault, false, 1_000);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 10 * MIN_STAKE);
+
+    await setPaused(agent, vault, true);
+    await advanceTime(context, GRACE_PERIOD + 1);
+
+    const [balanceBefore, penaltyBefore] = [await tokenBalance(from.tokens), await tokenBalance(penaltyAccount)];
+    await emergencyWithdraw(vault, from);
+
+    expect((await tokenBalance(from.tokens)) - balanceBefore).toBe(9 * MIN_STAKE);
+    expect((await tokenBalance(penaltyAccount)) - penaltyBefore).toBe(MIN_STAKE);
+    expect(await tokenBalance(vaultTokenPda(vault))).toBe(0);
+
+    const position = await fetchAccount(program, 'stakePosition', positionPda(vault, from.wallet.publicKey));
+    expect(position.isActive).toBe(false);
+    expect(position.amount.toNumber()).toBe(0);
+    const vaultState = await fetchAccount(program, 'stakingVault', vault);
+    expect(vaultState.totalStaked.toNumber()).toBe(0);
+    expect(vaultState.totalStakers).toBe(0);
+    expect(vaultState.categoryStaked.every((bucket: BN) => bucket.isZero())).toBe(true);
+  });
+
+  test('a pause shorter than the grace period keeps stakes locked', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    await configureEmergencyExit(agent, vault, false, 0);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 10 * MIN_STAKE);
+
+    await expect(emergencyWithdraw(vault, from)).rejects.toThrow(/EmergencyExitUnavailable/);
+    await setPaused(agent, vault, true);
+    await advanceTime(context, GRACE_PERIOD / 2);
+    await expect(emergencyWithdraw(vault, from)).rejects.toThrow(/EmergencyExitUnavailable/);
+
+    // Pausing again does not restart the grace period
+    await setPaused(agent, vault, true);
+    await advanceTime(context, GRACE_PERIOD / 2 + 1);
+    await emergencyWithdraw(vault, from);
+  });
+
+  test('the early-exit flag opens emergency withdrawal on an active vault', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    const [leaver, stayer] = [await tokenStaker(context, mint), await tokenStaker(context, mint)];
+    await stake(vault, leaver, 4 * MIN_STAKE);
+    await stake(vault, stayer, 6 * MIN_STAKE);
+    await configureEmergencyExit(agent, vault, true, 0);
+
+    const balanceBefore = await tokenBalance(leaver.tokens);
+    await emergencyWithdraw(vault, leaver);
+    expect((await tokenBalance(leaver.tokens)) - balanceBefore).toBe(4 * MIN_STAKE);
+
+    const vaultState = await fetchAccount(program, 'stakingVault', vault);
+    expect(vaultState.totalStaked.toNumber()).toBe(6 * MIN_STAKE);
+    expect(vaultState.totalStakers).toBe(1);
+    expect(await tokenBalance(vaultTokenPda(vault))).toBe(6 * MIN_STAKE);
+  });
+
+  test('the early-exit penalty is capped', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    await expect(configureEmergencyExit(agent, vault, true, 2_001)).rejects.toThrow(/InvalidEarlyExitPenalty/);
+  });
+});
@@ -0,0 +1,253 @@
+/**
+ * Lock Tier Tests
+ * Tests stake_tokens' optional lock_duration and the vault's lock-tier boosts
+ *
+ * Lock tiers ensure:
+ * 1. Committing to a longer lock boosts trust weight by the highest tier it reaches
+ * 2. Requested locks must be at least the vault's lock period and at most 365 days
+ * 3. Top-ups may extend but never shorten the remaining lock
+ * 4. Unstaking waits for the longer lock the staker chose
+ * 5. The authority can only set an ascending, bounded tier table
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createMint, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { advanceTime, loadProgram, tokenStaker, vaultIdentityPda, vaultOwner } from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const DAY = 24 * 60 * 60;
+const LOCK_PERIOD = 7 * DAY;
+const MIN_STAKE = 1_000_000;
+
+// 30d = 1.0x, 90d = 1.5x, 365d = 2.5x
+const TIERS = [
+  { lockSeconds: 30 * DAY, boostBps: 10_000 },
+  { lockSeconds: 90 * DAY, boostBps: 15_000 },
+  { lockSeconds: 365 * DAY, boostBps: 25_000 },
+];
+
+describe('Lock Tiers', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mint: PublicKey;
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), agent.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** The endorsement index of the agent a vault belongs to */
+  async function vaultIndexPda(vault: PublicKey): Promise<PublicKey> {
+    const { targetAgent } = await fetchAccount(program, 'stakingVault', vault);
+    return endorsementIndexPda(targetAgent);
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function tierArgs(tiers: { lockSeconds: number; boostBps: number }[]) {
+    return tiers.map((tier) => ({ lockSeconds: new BN(tier.lockSeconds), boostBps: tier.boostBps }));
+  }
+
+  function setTiers(agent: Keypair, vault: PublicKey, tiers: { lockSeconds: number; boostBps: number }[]) {
+    return program.methods
+      .updateVaultConfig(null, null, null, null, null, tierArgs(tiers))
+      .accounts({ vault, authority: agent.publicKey })
+      .signers([agent])
+      .rpc();
+  }
+
+  /** A vault for a fresh agent (also the vault authority) with the TIERS table */
+  async function tieredVault(): Promise<{ agent: Keypair; vault: PublicKey }> {
+    const owner = await vaultOwner(context, program, mint);
+    await setTiers(owner.agent, owner.vault, TIERS);
+    return owner;
+  }
+
+  async function stake(
+    vault: PublicKey,
+    from: { wallet: Keypair; tokens: PublicKey },
+    amount: number,
+    lockDuration: number | null = null
+  ) {
+    return program.methods
+      .stakeTokens(new BN(amount), { general: {} }, lockDuration === null ? null : new BN(lockDuration))
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: await vaultIndexPda(vault),
+        priceFeed: null,
+        agentIdentity: await vaultIdentityPda(program, vault),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  async function unstake(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .unstakeTokens(new BN(amount))
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        endorsementIndex: await vaultIndexPda(vault),
+        staker: from.wallet.publicKey,
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  async function position(vault: PublicKey, from: { wallet: Keypair }) {
+    return fetchAccount(program, 'stakePosition', positionPda(vault, from.wallet.publicKey));
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mint = await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6);
+  });
+
+  test('each tier boosts trust weight by its multiplier', async () => {
+    const { vault } = await tieredVault();
+    const base = await tokenStaker(context, mint);
+    await stake(vault, base, 10 * MIN_STAKE);
+    const baseWeight = (await position(vault, base)).trustWeight.toNumber();
+
+    // Locks between tiers earn the lower tier's boost
+    const cases: [number, number][] = [[30 * DAY, 10_000], [89 * DAY, 10_000], [90 * DAY, 15_000], [365 * DAY, 25_000]];
+    for (const [lockDuration, boostBps] of cases) {
+      const from = await tokenStaker(context, mint);
+      await stake(vault, from, 10 * MIN_STAKE, lockDuration);
+
+      const after = await position(vault, from);
+      expect(after.lockDuration.toNumber()).toBe(lockDuration);
+      expect(after.lockBoostBps).toBe(boostBps);
+      expect(after.trustWeight.toNumber()).toBe(Math.floor((baseWeight * boostBps) / 10_000));
+    }
+    expect((await position(vault, base)).lockBoostBps).toBe(10_000);
+  });
+
+  test('locks shorter than the vault lock period or longer than 365 days are rejected', async () => {
+    const { vault } = await tieredVault();
+    const from = await tokenStaker(context, mint);
+
+    await expect(stake(vault, from, MIN_STAKE, LOCK_PERIOD - 1)).rejects.toThrow(/InvalidLockDuration/);
+    await expect(stake(vault, from, MIN_STAKE, 365 * DAY + 1)).rejects.toThrow(/InvalidLockDuration/);
+  });
+
+  test('a top-up cannot shorten the remaining lock but may extend it', async () => {
+    const { vault } = await tieredVault();
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 5 * MIN_STAKE, 90 * DAY);
+    await advanceTime(context, 10 * DAY);
+
+    await expect(stake(vault, from, MIN_STAKE, 30 * DAY)).rejects.toThrow(/LockShorterThanRemaining/);
+
+    // Without a requested lock the top-up keeps the position's 90-day commitment
+    const before = await position(vault, from);
+    await stake(vault, from, MIN_STAKE);
+    const kept = await position(vault, from);
+    expect(kept.lockDuration.toNumber()).toBe(90 * DAY);
+    expect(kept.lockBoostBps).toBe(15_000);
+    expect(kept.lockedUntil.toNumber()).toBeGreaterThan(before.lockedUntil.toNumber());
+
+    await stake(vault, from, MIN_STAKE, 365 * DAY);
+    const extended = await position(vault, from);
+    expect(extended.lockDuration.toNumber()).toBe(365 * DAY);
+    expect(extended.lockBoostBps).toBe(25_000);
+    expect(extended.trustWeight.toNumber()).toBeGreaterThan(kept.trustWeight.toNumber());
+  });
+
+  test('unstaking waits for the longer lock the staker chose', async () => {
+    const { vault } = await tieredVault();
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 5 * MIN_STAKE, 90 * DAY);
+
+    await advanceTime(context, LOCK_PERIOD);
+    await expect(unstake(vault, from, 5 * MIN_STAKE)).rejects.toThrow(/StakeLocked/);
+
+    await advanceTime(context, 90 * DAY - LOCK_PERIOD);
+    await unstake(vault, from, 5 * MIN_STAKE);
+    expect((await position(vault, from)).isActive).toBe(false);
+  });
+
+  test('changing the tier table keeps existing positions at their staked boost', async () => {
+    const { agent, vault } = await tieredVault();
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 5 * MIN_STAKE, 90 * DAY);
+
+    await setTiers(agent, vault, [{ lockSeconds: 90 * DAY, boostBps: 20_000 }]);
+    expect((await position(vault, from)).lockBoostBps).toBe(15_000);
+
+    const vaultState = await fetchAccount(program, 'stakingVault', vault);
+    expect(vaultState.lockTierCount).toBe(1);
+    expect(vaultState.lockTiers[0].boostBps).toBe(20_000);
+  });
+
+  test('the tier table must ascend and stay within bounds', async () => {
+    const { agent, vault } = await tieredVault();
+
+    const invalid = [
+      [TIERS[1], TIERS[0]],
+      [{ lockSeconds: 30 * DAY, boostBps: 15_000 }, { lockSeconds: 90 * DAY, boostBps: 10_000 }],
+      [{ lockSeconds: 30 * DAY, boostBps: 9_999 }],
+      [{ lockSeconds: 30 * DAY, boostBps: 30_001 }],
+      [{ lockSeconds: 366 * DAY, boostBps: 20_000 }],
+      [1, 2, 3, 4, 5].map((days) => ({ lockSeconds: days * DAY, boostBps: 10_000 })),
+    ];
+    for (const tiers of invalid) {
+      await expect(setTiers(agent, vault, tiers)).rejects.toThrow(/InvalidLockTiers/);
+    }
+
+    await setTiers(agent, vault, []);
+    expect((await fetchAccount(program, 'stakingVault', vault)).lockTierCount).toBe(0);
+  });
+});
@@ -0,0 +1,220 @@
+/**
+ * Price Feed Tests
+ * Tests configure_price_feed and USD-normalized trust weights for token staking vaults
+ *
+ * USD normalization ensures:
+ * 1. Trust weight is computed from a stake's micro-USD value at the feed's price
+ * 2. Stale or non-positive prices reject the stake, or fall back to raw token units when configured
+ * 3. Only fully verified Pyth price updates are accepted, and only the vault's own feed
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createMint, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  advanceTime,
+  loadProgram,
+  mockPriceFeed,
+  now,
+  tokenStaker,
+  vaultIdentityPda,
+  vaultOwner,
+} from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const MIN_STAKE = 1_000_000;
+const MAX_PRICE_AGE = 300;
+
+// $2.50 per token
+const PRICE = 250_000_000;
+const EXPONENT = -8;
+
+describe('Price Feed', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mint: PublicKey;
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), agent.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** The endorsement index of the agent a vault belongs to */
+  async function vaultIndexPda(vault: PublicKey): Promise<PublicKey> {
+    const { targetAgent } = await fetchAccount(program, 'stakingVault', vault);
+    return endorsementIndexPda(targetAgent);
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** A price feed reporting `price` as of the current bank time */
+  async function feed(price = PRICE, address?: PublicKey): Promise<PublicKey> {
+    return mockPriceFeed(context, { price, exponent: EXPONENT, publishTime: await now(context) }, address);
+  }
+
+  function configure(
+    agent: Keypair,
+    vault: PublicKey,
+    priceFeed: PublicKey | null,
+    usdNormalized = true,
+    fallbackToRaw = false
+  ) {
+    return program.methods
+      .configurePriceFeed(usdNormalized, new BN(MAX_PRICE_AGE), fallbackToRaw)
+      .accounts({ vault, tokenMint: mint, priceFeed, authority: agent.publicKey })
+      .signers([agent])
+      .rpc();
+  }
+
+  async function stake(
+    vault: PublicKey,
+    from: { wallet: Keypair; tokens: PublicKey },
+    amount: number,
+    priceFeed: PublicKey | null
+  ) {
+    return program.methods
+      .stakeTokens(new BN(amount), { general: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: await vaultIndexPda(vault),
+        priceFeed,
+        agentIdentity: await vaultIdentityPda(program, vault),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  /** Trust weight of a raw-unit stake of `amount` in a fresh, unpriced vault */
+  async function rawWeight(amount: number): Promise<number> {
+    const { vault } = await vaultOwner(context, program, mint);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, amount, null);
+    return (await position(vault, from)).trustWeight.toNumber();
+  }
+
+  async function position(vault: PublicKey, from: { wallet: Keypair }) {
+    return fetchAccount(program, 'stakePosition', positionPda(vault, from.wallet.publicKey));
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mint = await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6);
+  });
+
+  test('trust weight is computed from the micro-USD value of the stake', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    const priceFeed = await feed();
+    await configure(agent, vault, priceFeed);
+
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 10 * MIN_STAKE, priceFeed);
+
+    // 10 tokens at $2.50 are 25_000_000 micro-USD, weighed like 25 raw tokens
+    expect((await position(vault, from)).trustWeight.toNumber()).toBe(await rawWeight(25 * MIN_STAKE));
+    const vaultState = await fetchAccount(program, 'stakingVault', vault);
+    expect(vaultState.usdNormalized).toBe(true);
+    expect(vaultState.tokenDecimals).toBe(6);
+    expect(vaultState.lastPrice.toNumber()).toBe(PRICE);
+    expect(vaultState.lastPriceExponent).toBe(EXPONENT);
+  });
+
+  test('a stale price rejects the stake until the feed is updated', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    const priceFeed = await feed();
+    await configure(agent, vault, priceFeed);
+    await advanceTime(context, MAX_PRICE_AGE + 1);
+
+    const from = await tokenStaker(context, mint);
+    await expect(stake(vault, from, 10 * MIN_STAKE, priceFeed)).rejects.toThrow(/StalePrice/);
+
+    await feed(PRICE, priceFeed);
+    await stake(vault, from, 10 * MIN_STAKE, priceFeed);
+    expect((await position(vault, from)).isActive).toBe(true);
+  });
+
+  test('a zero price rejects the stake', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    const priceFeed = await feed();
+    await configure(agent, vault, priceFeed);
+    await feed(0, priceFeed);
+
+    const from = await tokenStaker(context, mint);
+    await expect(stake(vault, from, 10 * MIN_STAKE, priceFeed)).rejects.toThrow(/InvalidPrice/);
+  });
+
+  test('with fallback enabled, an unusable price weighs raw token units', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    const priceFeed = await feed();
+    await configure(agent, vault, priceFeed, true, true);
+    await advanceTime(context, MAX_PRICE_AGE + 1);
+
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 10 * MIN_STAKE, priceFeed);
+
+    expect((await position(vault, from)).trustWeight.toNumber()).toBe(await rawWeight(10 * MIN_STAKE));
+    expect((await fetchAccount(program, 'stakingVault', vault)).lastPrice.toNumber()).toBe(0);
+  });
+
+  test('only a verified Pyth update can be configured, and stakes must pass the vault feed', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    const foreign = mockPriceFeed(
+      context,
+      { price: PRICE, exponent: EXPONENT, publishTime: await now(context) },
+      undefined,
+      Keypair.generate().publicKey
+    );
+
+    await expect(configure(agent, vault, foreign)).rejects.toThrow(/InvalidPriceFeed/);
+    await expect(configure(agent, vault, null)).rejects.toThrow(/MissingPriceFeed/);
+
+    const priceFeed = await feed();
+    await configure(agent, vault, priceFeed);
+    const from = await tokenStaker(context, mint);
+    await expect(stake(vault, from, MIN_STAKE, null)).rejects.toThrow(/MissingPriceFeed/);
+    await expect(stake(vault, from, MIN_STAKE, await feed())).rejects.toThrow(/InvalidPriceFeed/);
+  });
+});
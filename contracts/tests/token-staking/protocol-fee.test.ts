@@ -0,0 +1,328 @@
+/**
+ * Protocol Fee Tests
+ * Tests the protocol fee config and the fee charged on stake_tokens and unstake_tokens
+ *
+ * Protocol fees ensure:
+ * 1. A stake credits its position with the amount net of the fee, rounded in the staker's favor
+ * 2. Fees land in the protocol's per-mint fee account, so the vault balance always equals total_staked
+ * 3. Unstake fees are withheld from the payout only when enabled
+ * 4. Fees are bounded to 100 bps, changed only by the admin, and swept only to the treasury
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+import { createAccount, createMint, getAccount, mintTo, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, mockAgentIdentity, vaultIdentityPda } from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const LOCK_PERIOD = 7 * 24 * 60 * 60;
+const TOKEN = 1_000_000;
+const FEE_BPS = 100;
+
+type Wallet = { wallet: Keypair; tokens: PublicKey };
+
+describe('Protocol Fee', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mint: PublicKey;
+  let admin: Keypair;
+  let treasury: Wallet;
+
+  function vaultPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('vault'), agent.toBuffer(), mint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), agent.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** The endorsement index of the agent a vault belongs to */
+  async function vaultIndexPda(vault: PublicKey): Promise<PublicKey> {
+    const { targetAgent } = await fetchAccount(program, 'stakingVault', vault);
+    return endorsementIndexPda(targetAgent);
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** A vault for a fresh agent accepting stakes of at least `minStake` base units */
+  async function newVault(minStake = TOKEN): Promise<PublicKey> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10 * LAMPORTS_PER_SOL);
+    const vault = vaultPda(agent.publicKey);
+
+    await program.methods
+      .initializeVault(new BN(minStake), new BN(LOCK_PERIOD), 100, new BN(0), new BN(0), new BN(0))
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        tokenMint: mint,
+        targetAgent: agent.publicKey,
+        agentIdentity: mockAgentIdentity(context, agent.publicKey),
+        protocolConfig: protocolConfigPda(),
+        endorsementIndex: endorsementIndexPda(agent.publicKey),
+        authority: agent.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([agent])
+      .rpc();
+    return vault;
+  }
+
+  /** A funded wallet with a token account holding `balance` base units */
+  async function wallet(balance = 100 * TOKEN): Promise<Wallet> {
+    const keypair = Keypair.generate();
+    await airdrop(context, keypair.publicKey, 10 * LAMPORTS_PER_SOL);
+    const tokens = await createAccount(context.banksClient, context.payer, mint, keypair.publicKey);
+    if (balance > 0) {
+      await mintTo(context.banksClient, context.payer, mint, tokens, context.payer, balance);
+    }
+    return { wallet: keypair, tokens };
+  }
+
+  async function stake(vault: PublicKey, from: Wallet, amount: number) {
+    return program.methods
+      .stakeTokens(new BN(amount), { general: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: await vaultIndexPda(vault),
+        priceFeed: null,
+        agentIdentity: await vaultIdentityPda(program, vault),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  async function unstake(vault: PublicKey, from: Wallet, amount: number) {
+    return program.methods
+      .unstakeTokens(new BN(amount))
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        endorsementIndex: await vaultIndexPda(vault),
+        staker: from.wallet.publicKey,
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  function updateConfig(feeBps: number | null, chargeUnstakeFee: boolean | null, signer: Keypair = admin) {
+    return program.methods
+      .updateProtocolConfig(null, feeBps, chargeUnstakeFee, null)
+      .accounts({ protocolConfig: protocolConfigPda(), admin: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function withdrawFees(to: PublicKey, amount: number | null) {
+    return program.methods
+      .withdrawProtocolFees(amount === null ? null : new BN(amount))
+      .accounts({
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        treasuryTokenAccount: to,
+        admin: admin.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function balance(tokens: PublicKey): Promise<number> {
+    return Number((await getAccount(context.banksClient, tokens)).amount);
+  }
+
+  /** Assert the vault holds exactly the tokens its positions are credited with */
+  async function expectVaultBacked(vault: PublicKey) {
+    const { totalStaked } = await fetchAccount(program, 'stakingVault', vault);
+    expect(await balance(vaultTokenPda(vault))).toBe(totalStaked.toNumber());
+  }
+
+  async function position(vault: PublicKey, from: Wallet) {
+    return fetchAccount(program, 'stakePosition', positionPda(vault, from.wallet.publicKey));
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mint = await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6);
+    admin = Keypair.generate();
+    await airdrop(context, admin.publicKey, LAMPORTS_PER_SOL);
+    treasury = await wallet(0);
+  });
+
+  test('without a protocol config no fee is charged', async () => {
+    const vault = await newVault();
+    const from = await wallet();
+    await stake(vault, from, 10 * TOKEN);
+
+    expect((await position(vault, from)).amount.toNumber()).toBe(10 * TOKEN);
+    expect(await balance(feeAccountPda(mint))).toBe(0);
+    await expectVaultBacked(vault);
+  });
+
+  test('the fee is capped at 100 bps', async () => {
+    const initialize = (feeBps: number) =>
+      program.methods
+        .initializeProtocolConfig(treasury.wallet.publicKey, feeBps, false)
+        .accounts({
+          protocolConfig: protocolConfigPda(),
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+    await expect(initialize(FEE_BPS + 1)).rejects.toThrow(/InvalidProtocolFee/);
+    await initialize(FEE_BPS);
+
+    await expect(updateConfig(FEE_BPS + 1, null)).rejects.toThrow(/InvalidProtocolFee/);
+    const outsider = Keypair.generate();
+    await airdrop(context, outsider.publicKey, LAMPORTS_PER_SOL);
+    await expect(updateConfig(0, null, outsider)).rejects.toThrow(/UnauthorizedProtocolAdmin/);
+
+    const config = await fetchAccount(program, 'protocolConfig', protocolConfigPda());
+    expect(config.protocolFeeBps).toBe(FEE_BPS);
+    expect(config.treasury.toBase58()).toBe(treasury.wallet.publicKey.toBase58());
+  });
+
+  test('the fee is deducted before the position is credited', async () => {
+    const vault = await newVault();
+    const from = await wallet();
+    const feesBefore = await balance(feeAccountPda(mint));
+    await stake(vault, from, 10 * TOKEN);
+
+    // 1% of 10 tokens
+    const fee = (10 * TOKEN * FEE_BPS) / 10_000;
+    expect((await position(vault, from)).amount.toNumber()).toBe(10 * TOKEN - fee);
+    expect(await balance(from.tokens)).toBe(90 * TOKEN);
+    expect(await balance(feeAccountPda(mint))).toBe(feesBefore + fee);
+    await expectVaultBacked(vault);
+  });
+
+  test('fees round down on tiny stakes', async () => {
+    const vault = await newVault(1);
+    const feesBefore = await balance(feeAccountPda(mint));
+
+    // 1% of 99 base units is 0.99, which rounds to no fee; 1% of 250 is 2.5, which rounds to 2
+    const tiny = await wallet();
+    await stake(vault, tiny, 99);
+    expect((await position(vault, tiny)).amount.toNumber()).toBe(99);
+    expect(await balance(feeAccountPda(mint))).toBe(feesBefore);
+
+    const small = await wallet();
+    await stake(vault, small, 250);
+    expect((await position(vault, small)).amount.toNumber()).toBe(248);
+    expect(await balance(feeAccountPda(mint))).toBe(feesBefore + 2);
+    await expectVaultBacked(vault);
+  });
+
+  test('unstake fees are withheld from the payout only when enabled', async () => {
+    const vault = await newVault();
+    const from = await wallet();
+    await stake(vault, from, 10 * TOKEN);
+    const staked = (await position(vault, from)).amount.toNumber();
+    await advanceTime(context, LOCK_PERIOD);
+
+    // Unstake fees are off by default
+    await unstake(vault, from, TOKEN);
+    expect(await balance(from.tokens)).toBe(90 * TOKEN + TOKEN);
+
+    await updateConfig(null, true);
+    const feesBefore = await balance(feeAccountPda(mint));
+    const rest = staked - TOKEN;
+    await unstake(vault, from, rest);
+
+    const fee = Math.floor((rest * FEE_BPS) / 10_000);
+    expect(await balance(from.tokens)).toBe(91 * TOKEN + rest - fee);
+    expect(await balance(feeAccountPda(mint))).toBe(feesBefore + fee);
+    expect((await position(vault, from)).isActive).toBe(false);
+    await expectVaultBacked(vault);
+
+    await updateConfig(null, false);
+  });
+
+  test('zero-fee mode credits the full stake', async () => {
+    await updateConfig(0, null);
+    const vault = await newVault();
+    const from = await wallet();
+    const feesBefore = await balance(feeAccountPda(mint));
+    await stake(vault, from, 10 * TOKEN);
+
+    expect((await position(vault, from)).amount.toNumber()).toBe(10 * TOKEN);
+    expect(await balance(feeAccountPda(mint))).toBe(feesBefore);
+    await expectVaultBacked(vault);
+
+    await updateConfig(FEE_BPS, null);
+  });
+
+  test('collected fees are swept only to the treasury', async () => {
+    const collected = await balance(feeAccountPda(mint));
+    expect(collected).toBeGreaterThan(0);
+
+    const stranger = await wallet(0);
+    await expect(withdrawFees(stranger.tokens, null)).rejects.toThrow(/InvalidTreasuryAccount/);
+    await expect(withdrawFees(treasury.tokens, collected + 1)).rejects.toThrow(/InvalidFeeWithdrawal/);
+
+    await withdrawFees(treasury.tokens, null);
+    expect(await balance(treasury.tokens)).toBe(collected);
+    expect(await balance(feeAccountPda(mint))).toBe(0);
+  });
+});
@@ -0,0 +1,298 @@
+/**
+ * Staker Rewards Tests
+ * Tests fund_rewards / claim_rewards for token stake positions
+ *
+ * Rewards ensure:
+ * 1. Funded rewards stream to stakers pro rata by amount and time staked
+ * 2. Funding during a period rolls the undistributed remainder into the new rate
+ * 3. Stake changes settle rewards first, so nothing accrued is lost
+ * 4. Rewards nobody can claim are swept to the authority when the vault closes,
+ *    after a grace period for former stakers
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createAccount, createMint, getAccount, mintTo, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, tokenStaker, vaultIdentityPda, vaultOwner } from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const LOCK_PERIOD = 7 * 24 * 60 * 60;
+const MIN_STAKE = 1_000_000;
+const REWARD_CLAIM_GRACE_PERIOD = 30 * 24 * 60 * 60;
+
+describe('Staker Rewards', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mint: PublicKey;
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function rewardTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('reward_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), agent.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** The endorsement index of the agent a vault belongs to */
+  async function vaultIndexPda(vault: PublicKey): Promise<PublicKey> {
+    const { targetAgent } = await fetchAccount(program, 'stakingVault', vault);
+    return endorsementIndexPda(targetAgent);
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  async function tokenBalance(account: PublicKey): Promise<number> {
+    return Number((await getAccount(context.banksClient, account)).amount);
+  }
+
+  async function stake(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .stakeTokens(new BN(amount), { general: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: await vaultIndexPda(vault),
+        priceFeed: null,
+        agentIdentity: await vaultIdentityPda(program, vault),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  async function unstake(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .unstakeTokens(new BN(amount))
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        endorsementIndex: await vaultIndexPda(vault),
+        staker: from.wallet.publicKey,
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  /** Fund `amount` reward tokens over `periodSeconds` from the vault agent's own tokens */
+  async function fundRewards(agent: Keypair, vault: PublicKey, amount: number, periodSeconds: number) {
+    const funder = await createAccount(context.banksClient, context.payer, mint, agent.publicKey);
+    await mintTo(context.banksClient, context.payer, mint, funder, context.payer, amount);
+    await program.methods
+      .fundRewards(new BN(amount), new BN(periodSeconds))
+      .accounts({
+        vault,
+        rewardTokenAccount: rewardTokenPda(vault),
+        tokenMint: mint,
+        funderTokenAccount: funder,
+        authority: agent.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  /** Claim `from`'s rewards and return how many tokens arrived */
+  async function claim(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }): Promise<number> {
+    const before = await tokenBalance(from.tokens);
+    await program.methods
+      .claimRewards()
+      .accounts({
+        vault,
+        rewardTokenAccount: rewardTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+    return (await tokenBalance(from.tokens)) - before;
+  }
+
+  async function closeVault(agent: Keypair, vault: PublicKey, authorityTokenAccount: PublicKey) {
+    return program.methods
+      .closeVault()
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        rewardTokenAccount: rewardTokenPda(vault),
+        authorityTokenAccount,
+        endorsementIndex: await vaultIndexPda(vault),
+        authority: agent.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mint = await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6);
+  });
+
+  test('stakers with different amounts and entry times claim their pro-rata share', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    const [early, late] = [await tokenStaker(context, mint), await tokenStaker(context, mint)];
+    await stake(vault, early, 20 * MIN_STAKE);
+    // 1 reward token per second for 1000 seconds
+    await fundRewards(agent, vault, 1000 * MIN_STAKE, 1000);
+
+    await advanceTime(context, 100);
+    await stake(vault, late, 5 * MIN_STAKE);
+    await advanceTime(context, 100);
+
+    // early: 100s alone, then 100s at 20/25 of the rate; late: 100s at 5/25
+    expect(await claim(vault, early)).toBe(100 * MIN_STAKE + 80 * MIN_STAKE);
+    expect(await claim(vault, late)).toBe(20 * MIN_STAKE);
+  });
+
+  test('claiming twice in the same second pays nothing the second time', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 10 * MIN_STAKE);
+    await fundRewards(agent, vault, 1000 * MIN_STAKE, 1000);
+    await advanceTime(context, 10);
+
+    expect(await claim(vault, from)).toBe(10 * MIN_STAKE);
+    expect(await claim(vault, from)).toBe(0);
+
+    const position = await fetchAccount(program, 'stakePosition', positionPda(vault, from.wallet.publicKey));
+    expect(position.rewardsAccrued.toNumber()).toBe(0);
+    expect(position.rewardsClaimed.toNumber()).toBe(10 * MIN_STAKE);
+  });
+
+  test('funding mid-stream rolls the undistributed rewards into the new rate', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 10 * MIN_STAKE);
+    // 0.1 token per second
+    await fundRewards(agent, vault, 100 * MIN_STAKE, 1000);
+    await advanceTime(context, 500);
+
+    // 50 tokens left over plus 150 new ones over 1000 seconds: 0.2 token per second
+    await fundRewards(agent, vault, 150 * MIN_STAKE, 1000);
+    await advanceTime(context, 250);
+    expect(await claim(vault, from)).toBe(50 * MIN_STAKE + 50 * MIN_STAKE);
+
+    // Nothing accrues past the end of the period
+    await advanceTime(context, 2000);
+    expect(await claim(vault, from)).toBe(150 * MIN_STAKE);
+    expect(await tokenBalance(rewardTokenPda(vault))).toBe(0);
+  });
+
+  test('unstaking settles accrued rewards first', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 10 * MIN_STAKE);
+    // 1000 base units per second for two lock periods
+    await fundRewards(agent, vault, 2 * LOCK_PERIOD * 1000, 2 * LOCK_PERIOD);
+
+    await advanceTime(context, LOCK_PERIOD);
+    await unstake(vault, from, 10 * MIN_STAKE);
+    const position = await fetchAccount(program, 'stakePosition', positionPda(vault, from.wallet.publicKey));
+    expect(position.rewardsAccrued.toNumber()).toBe(LOCK_PERIOD * 1000);
+
+    await advanceTime(context, LOCK_PERIOD);
+    expect(await claim(vault, from)).toBe(LOCK_PERIOD * 1000);
+  });
+
+  test('only the vault authority can fund rewards, over a valid period', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    const outsider = Keypair.generate();
+    await airdrop(context, outsider.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    await expect(fundRewards(outsider, vault, MIN_STAKE, 1000)).rejects.toThrow(/UnauthorizedAuthority/);
+    await expect(fundRewards(agent, vault, MIN_STAKE, 0)).rejects.toThrow(/InvalidRewardPeriod/);
+    await expect(fundRewards(agent, vault, 0, 1000)).rejects.toThrow(/InvalidRewardAmount/);
+  });
+
+  test('rewards streamed while nothing is staked are swept to the authority on close', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    await fundRewards(agent, vault, 1000 * MIN_STAKE, 1000);
+    const authorityTokens = await createAccount(context.banksClient, context.payer, mint, agent.publicKey);
+
+    await advanceTime(context, 1000);
+    await expect(closeVault(agent, vault, authorityTokens)).rejects.toThrow(/RewardClaimWindowOpen/);
+
+    await advanceTime(context, REWARD_CLAIM_GRACE_PERIOD);
+    await closeVault(agent, vault, authorityTokens);
+
+    expect(await tokenBalance(authorityTokens)).toBe(1000 * MIN_STAKE);
+    expect(await context.banksClient.getAccount(vault)).toBeNull();
+    expect(await context.banksClient.getAccount(rewardTokenPda(vault))).toBeNull();
+  });
+
+  test('a former staker can close its position once the vault is gone', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 10 * MIN_STAKE);
+    await fundRewards(agent, vault, 1000 * MIN_STAKE, 1000);
+    await advanceTime(context, LOCK_PERIOD);
+    await unstake(vault, from, 10 * MIN_STAKE);
+
+    const closePosition = () =>
+      program.methods
+        .closeStakePosition()
+        .accounts({ stakePosition: positionPda(vault, from.wallet.publicKey), vault, staker: from.wallet.publicKey })
+        .signers([from.wallet])
+        .rpc();
+    await expect(closePosition()).rejects.toThrow(/UnclaimedRewards/);
+
+    await advanceTime(context, REWARD_CLAIM_GRACE_PERIOD);
+    await closeVault(agent, vault, await createAccount(context.banksClient, context.payer, mint, agent.publicKey));
+    await closePosition();
+    expect(await context.banksClient.getAccount(positionPda(vault, from.wallet.publicKey))).toBeNull();
+  });
+});
@@ -0,0 +1,236 @@
+/**
+ * Stake Slashing Tests
+ * Tests configure_slashing / slash_stake_position for token stake positions
+ *
+ * Slashing ensures:
+ * 1. Only the vault's configured slash authority can slash, and only once configured
+ * 2. A slash moves severity_bps of the position to the penalty destination and recomputes its trust weight
+ * 3. Slashed positions cannot be topped up, but the remainder can still be unstaked
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createAccount, createMint, getAccount, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, tokenStaker, vaultIdentityPda, vaultOwner } from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const LOCK_PERIOD = 7 * 24 * 60 * 60;
+const MIN_STAKE = 1_000_000;
+
+describe('Stake Slashing', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mint: PublicKey;
+  let slashAuthority: Keypair;
+  let penaltyAccount: PublicKey;
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), agent.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** The endorsement index of the agent a vault belongs to */
+  async function vaultIndexPda(vault: PublicKey): Promise<PublicKey> {
+    const { targetAgent } = await fetchAccount(program, 'stakingVault', vault);
+    return endorsementIndexPda(targetAgent);
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  async function tokenBalance(account: PublicKey): Promise<number> {
+    return Number((await getAccount(context.banksClient, account)).amount);
+  }
+
+  function configureSlashing(agent: Keypair, vault: PublicKey, slasher = slashAuthority.publicKey) {
+    return program.methods
+      .configureSlashing(slasher)
+      .accounts({ vault, penaltyDestination: penaltyAccount, authority: agent.publicKey })
+      .signers([agent])
+      .rpc();
+  }
+
+  async function stake(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .stakeTokens(new BN(amount), { general: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: await vaultIndexPda(vault),
+        priceFeed: null,
+        agentIdentity: await vaultIdentityPda(program, vault),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  async function unstake(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .unstakeTokens(new BN(amount))
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        endorsementIndex: await vaultIndexPda(vault),
+        staker: from.wallet.publicKey,
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  async function slash(vault: PublicKey, position: PublicKey, severityBps: number, signer = slashAuthority) {
+    return program.methods
+      .slashStakePosition(severityBps)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: position,
+        penaltyDestination: penaltyAccount,
+        endorsementIndex: await vaultIndexPda(vault),
+        slashAuthority: signer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mint = await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6);
+    slashAuthority = Keypair.generate();
+    await airdrop(context, slashAuthority.publicKey, LAMPORTS_PER_SOL);
+    penaltyAccount = await createAccount(context.banksClient, context.payer, mint, Keypair.generate().publicKey);
+  });
+
+  test('a 50% slash halves the position and moves the tokens to the penalty destination', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    await configureSlashing(agent, vault);
+    const [slashed, reference] = [await tokenStaker(context, mint), await tokenStaker(context, mint)];
+    await stake(vault, slashed, 10 * MIN_STAKE);
+    await stake(vault, reference, 5 * MIN_STAKE);
+
+    const position = positionPda(vault, slashed.wallet.publicKey);
+    const penaltyBefore = await tokenBalance(penaltyAccount);
+    await slash(vault, position, 5_000);
+
+    const after = await fetchAccount(program, 'stakePosition', position);
+    expect(after.amount.toNumber()).toBe(5 * MIN_STAKE);
+    expect(after.isSlashed).toBe(true);
+    expect(after.isActive).toBe(true);
+    // Trust weight matches an unslashed position of the same size
+    const unslashed = await fetchAccount(program, 'stakePosition', positionPda(vault, reference.wallet.publicKey));
+    expect(after.trustWeight.toNumber()).toBe(unslashed.trustWeight.toNumber());
+
+    expect((await tokenBalance(penaltyAccount)) - penaltyBefore).toBe(5 * MIN_STAKE);
+    expect(await tokenBalance(vaultTokenPda(vault))).toBe(10 * MIN_STAKE);
+    const vaultState = await fetchAccount(program, 'stakingVault', vault);
+    expect(vaultState.totalStaked.toNumber()).toBe(10 * MIN_STAKE);
+    expect(vaultState.totalSlashed.toNumber()).toBe(5 * MIN_STAKE);
+  });
+
+  test('a slashed position cannot be topped up, but its remainder can be unstaked', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    await configureSlashing(agent, vault);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 10 * MIN_STAKE);
+    await slash(vault, positionPda(vault, from.wallet.publicKey), 2_500);
+
+    await expect(stake(vault, from, MIN_STAKE)).rejects.toThrow(/PositionSlashed/);
+
+    await advanceTime(context, LOCK_PERIOD);
+    const balanceBefore = await tokenBalance(from.tokens);
+    await unstake(vault, from, 7_500_000);
+    expect((await tokenBalance(from.tokens)) - balanceBefore).toBe(7_500_000);
+
+    const position = await fetchAccount(program, 'stakePosition', positionPda(vault, from.wallet.publicKey));
+    expect(position.isActive).toBe(false);
+    expect(position.isSlashed).toBe(true);
+  });
+
+  test('a full slash closes out the position', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    await configureSlashing(agent, vault);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 4 * MIN_STAKE);
+    const position = positionPda(vault, from.wallet.publicKey);
+    await slash(vault, position, 10_000);
+
+    const after = await fetchAccount(program, 'stakePosition', position);
+    expect(after.amount.toNumber()).toBe(0);
+    expect(after.isActive).toBe(false);
+    expect(after.trustWeight.toNumber()).toBe(0);
+    expect((await fetchAccount(program, 'stakingVault', vault)).totalStakers).toBe(0);
+    await expect(slash(vault, position, 10_000)).rejects.toThrow(/StakeNotActive/);
+  });
+
+  test('only the configured slash authority can slash, with a valid severity', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 10 * MIN_STAKE);
+    const position = positionPda(vault, from.wallet.publicKey);
+
+    await expect(slash(vault, position, 5_000)).rejects.toThrow(/SlashingNotConfigured/);
+
+    const outsider = Keypair.generate();
+    await airdrop(context, outsider.publicKey, LAMPORTS_PER_SOL);
+    await expect(configureSlashing(outsider, vault)).rejects.toThrow(/UnauthorizedAuthority/);
+
+    await configureSlashing(agent, vault);
+    await expect(slash(vault, position, 5_000, outsider)).rejects.toThrow(/UnauthorizedSlashAuthority/);
+    await expect(slash(vault, position, 0)).rejects.toThrow(/InvalidSlashSeverity/);
+    await expect(slash(vault, position, 10_001)).rejects.toThrow(/InvalidSlashSeverity/);
+
+    const untouched = await fetchAccount(program, 'stakePosition', position);
+    expect(untouched.amount.toNumber()).toBe(10 * MIN_STAKE);
+    expect(untouched.isSlashed).toBe(false);
+  });
+});
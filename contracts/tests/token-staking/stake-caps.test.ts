@@ -0,0 +1,165 @@
+/**
+ * Stake Cap Tests
+ * Tests the max_total_staked / max_per_staker caps on StakingVault
+ *
+ * Stake caps ensure:
+ * 1. Stakes up to a cap succeed and one unit over fails (VaultAtCapacity / StakerCapExceeded)
+ * 2. The per-staker cap applies to a position's running total across top-ups
+ * 3. Raising a cap through update_vault_config unblocks further stakes; 0 means unlimited
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createMint, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { loadProgram, tokenStaker, vaultIdentityPda, vaultOwner } from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const MIN_STAKE = 1_000_000;
+
+describe('Stake Caps', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mint: PublicKey;
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), agent.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** The endorsement index of the agent a vault belongs to */
+  async function vaultIndexPda(vault: PublicKey): Promise<PublicKey> {
+    const { targetAgent } = await fetchAccount(program, 'stakingVault', vault);
+    return endorsementIndexPda(targetAgent);
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  async function stake(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .stakeTokens(new BN(amount), { general: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: await vaultIndexPda(vault),
+        priceFeed: null,
+        agentIdentity: await vaultIdentityPda(program, vault),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  function setCaps(agent: Keypair, vault: PublicKey, maxTotal: number | null, maxPerStaker: number | null) {
+    const cap = (value: number | null) => (value === null ? null : new BN(value));
+    return program.methods
+      .updateVaultConfig(null, null, null, cap(maxTotal), cap(maxPerStaker), null)
+      .accounts({ vault, authority: agent.publicKey })
+      .signers([agent])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mint = await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6);
+  });
+
+  test('the vault fills up to its capacity and rejects one unit more', async () => {
+    const { vault } = await vaultOwner(context, program, mint, { maxTotalStake: 10 * MIN_STAKE });
+    const [a, b] = [await tokenStaker(context, mint), await tokenStaker(context, mint)];
+    await stake(vault, a, 6 * MIN_STAKE);
+    await stake(vault, b, 4 * MIN_STAKE);
+
+    const vaultState = await fetchAccount(program, 'stakingVault', vault);
+    expect(vaultState.totalStaked.toNumber()).toBe(10 * MIN_STAKE);
+    expect(vaultState.maxTotalStaked.toNumber()).toBe(10 * MIN_STAKE);
+
+    const c = await tokenStaker(context, mint);
+    await expect(stake(vault, c, MIN_STAKE)).rejects.toThrow(/VaultAtCapacity/);
+  });
+
+  test('the per-staker cap applies across top-ups', async () => {
+    const { vault } = await vaultOwner(context, program, mint, { maxStakePerStaker: 5 * MIN_STAKE });
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 3 * MIN_STAKE);
+    await stake(vault, from, 2 * MIN_STAKE);
+
+    await expect(stake(vault, from, 1)).rejects.toThrow(/StakerCapExceeded/);
+    // Other stakers get their own allowance
+    await stake(vault, await tokenStaker(context, mint), 5 * MIN_STAKE);
+  });
+
+  test('a single stake over the per-staker cap fails', async () => {
+    const { vault } = await vaultOwner(context, program, mint, { maxStakePerStaker: 5 * MIN_STAKE });
+    const from = await tokenStaker(context, mint);
+    await expect(stake(vault, from, 5 * MIN_STAKE + 1)).rejects.toThrow(/StakerCapExceeded/);
+  });
+
+  test('raising the caps later unblocks staking', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint, {
+      maxTotalStake: 5 * MIN_STAKE,
+      maxStakePerStaker: 5 * MIN_STAKE,
+    });
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 5 * MIN_STAKE);
+    await expect(stake(vault, from, MIN_STAKE)).rejects.toThrow(/VaultAtCapacity/);
+
+    await setCaps(agent, vault, 20 * MIN_STAKE, null);
+    await expect(stake(vault, from, MIN_STAKE)).rejects.toThrow(/StakerCapExceeded/);
+
+    await setCaps(agent, vault, null, 0);
+    await stake(vault, from, MIN_STAKE);
+    const position = await fetchAccount(program, 'stakePosition', positionPda(vault, from.wallet.publicKey));
+    expect(position.amount.toNumber()).toBe(6 * MIN_STAKE);
+  });
+
+  test('caps are unlimited by default', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    await stake(vault, await tokenStaker(context, mint), 100 * MIN_STAKE);
+
+    const vaultState = await fetchAccount(program, 'stakingVault', vault);
+    expect(vaultState.maxTotalStaked.toNumber()).toBe(0);
+    expect(vaultState.maxPerStaker.toNumber()).toBe(0);
+  });
+});
@@ -0,0 +1,326 @@
+/**
+ * Staking Events Tests
+ * Tests the events token_staking emits for vault creation, stakes and unstakes
+ *
+ * Staking events ensure:
+ * 1. initialize_vault emits VaultInitialized with the vault's terms
+ * 2. stake_tokens and stake_tokens_for emit TokensStaked with the net amount, fee and funder
+ * 3. unstake_tokens emits TokensUnstaked with the payout destination
+ * 4. Every event carries the position, vault and agent totals after the change
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+} from '@solana/web3.js';
+import { createAccount, createMint, mintTo, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  IDENTITY_REGISTRY_PROGRAM_ID,
+  mockAgentIdentity,
+  now,
+  simulateEvents,
+} from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const LOCK_PERIOD = 7 * 24 * 60 * 60;
+const TOKEN = 1_000_000;
+const FEE_BPS = 100;
+
+type Wallet = { wallet: Keypair; tokens: PublicKey };
+
+describe('Staking Events', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mint: PublicKey;
+  let agent: Keypair;
+  let vault: PublicKey;
+
+  function vaultPda(target: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('vault'), target.toBuffer(), mint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(target: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), target.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function identityPda(target: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), target.toBuffer()], IDENTITY_REGISTRY_PROGRAM_ID)[0];
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /**
+   * Simulate `ix` to decode the events it emits, then send it for real.
+   * Returns the single event, which must be named `name`.
+   */
+  async function emitted(ix: TransactionInstruction, signers: Keypair[], name: string) {
+    const events = await simulateEvents(context, program, ix, signers);
+    expect(events.map((event) => event.name)).toEqual([name]);
+
+    const tx = new Transaction().add(ix);
+    tx.recentBlockhash = context.lastBlockhash;
+    tx.feePayer = context.payer.publicKey;
+    tx.sign(context.payer, ...signers);
+    await context.banksClient.processTransaction(tx);
+    return events[0].data;
+  }
+
+  /** A funded wallet with a token account holding `balance` base units */
+  async function wallet(balance = 100 * TOKEN): Promise<Wallet> {
+    const keypair = Keypair.generate();
+    await airdrop(context, keypair.publicKey, 10 * LAMPORTS_PER_SOL);
+    const tokens = await createAccount(context.banksClient, context.payer, mint, keypair.publicKey);
+    if (balance > 0) {
+      await mintTo(context.banksClient, context.payer, mint, tokens, context.payer, balance);
+    }
+    return { wallet: keypair, tokens };
+  }
+
+  function stakeIx(from: Wallet, amount: number) {
+    return program.methods
+      .stakeTokens(new BN(amount), { security: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: endorsementIndexPda(agent.publicKey),
+        priceFeed: null,
+        agentIdentity: identityPda(agent.publicKey),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+  }
+
+  /** Expect an event's position, vault and agent totals to match the accounts */
+  async function expectTotals(event: Awaited<ReturnType<typeof emitted>>, position: PublicKey) {
+    const stakePosition = await fetchAccount(program, 'stakePosition', position);
+    const stakingVault = await fetchAccount(program, 'stakingVault', vault);
+    const index = await fetchAccount(program, 'agentEndorsementIndex', endorsementIndexPda(agent.publicKey));
+    expect(event.positionAmount.toNumber()).toBe(stakePosition.amount.toNumber());
+    expect(event.trustWeight.toNumber()).toBe(stakePosition.trustWeight.toNumber());
+    expect(event.vaultTotalStaked.toNumber()).toBe(stakingVault.totalStaked.toNumber());
+    expect(event.vaultTotalStakers).toBe(stakingVault.totalStakers);
+    expect(event.vaultTrustWeight.toNumber()).toBe(stakingVault.totalTrustWeight.toNumber());
+    expect(event.agentTrustWeight.toNumber()).toBe(index.totalTrustWeight.toNumber());
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mint = await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6);
+    agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10 * LAMPORTS_PER_SOL);
+    vault = vaultPda(agent.publicKey);
+
+    const admin = Keypair.generate();
+    await airdrop(context, admin.publicKey, LAMPORTS_PER_SOL);
+    await program.methods
+      .initializeProtocolConfig(admin.publicKey, FEE_BPS, false)
+      .accounts({
+        protocolConfig: protocolConfigPda(),
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  test('initialize_vault emits VaultInitialized', async () => {
+    const ix = await program.methods
+      .initializeVault(new BN(TOKEN), new BN(LOCK_PERIOD), 150, new BN(0), new BN(500 * TOKEN), new BN(0))
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        tokenMint: mint,
+        targetAgent: agent.publicKey,
+        agentIdentity: mockAgentIdentity(context, agent.publicKey),
+        protocolConfig: protocolConfigPda(),
+        endorsementIndex: endorsementIndexPda(agent.publicKey),
+        authority: agent.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .instruction();
+    const event = await emitted(ix, [agent], 'vaultInitialized');
+
+    expect(event.vault.equals(vault)).toBe(true);
+    expect(event.vaultTokenAccount.equals(vaultTokenPda(vault))).toBe(true);
+    expect(event.endorsementIndex.equals(endorsementIndexPda(agent.publicKey))).toBe(true);
+    expect(event.targetAgent.equals(agent.publicKey)).toBe(true);
+    expect(event.tokenMint.equals(mint)).toBe(true);
+    expect(event.authority.equals(agent.publicKey)).toBe(true);
+    expect(event.minStakeAmount.toNumber()).toBe(TOKEN);
+    expect(event.lockPeriodSeconds.toNumber()).toBe(LOCK_PERIOD);
+    expect(event.weightMultiplier).toBe(150);
+    expect(event.unstakeCooldownSeconds.toNumber()).toBe(0);
+    expect(event.maxTotalStaked.toNumber()).toBe(500 * TOKEN);
+    expect(event.maxPerStaker.toNumber()).toBe(0);
+    expect(event.timestamp.toNumber()).toBe(await now(context));
+  });
+
+  test('stake_tokens emits TokensStaked with the net amount and fee', async () => {
+    const from = await wallet();
+    const position = positionPda(vault, from.wallet.publicKey);
+    const event = await emitted(await stakeIx(from, 10 * TOKEN), [from.wallet], 'tokensStaked');
+
+    // 1% of 10 tokens
+    const fee = (10 * TOKEN * FEE_BPS) / 10_000;
+    expect(event.vault.equals(vault)).toBe(true);
+    expect(event.stakePosition.equals(position)).toBe(true);
+    expect(event.endorsementIndex.equals(endorsementIndexPda(agent.publicKey))).toBe(true);
+    expect(event.staker.equals(from.wallet.publicKey)).toBe(true);
+    expect(event.funder.equals(from.wallet.publicKey)).toBe(true);
+    expect(event.targetAgent.equals(agent.publicKey)).toBe(true);
+    expect(event.tokenMint.equals(mint)).toBe(true);
+    expect(event.category).toEqual({ security: {} });
+    expect(event.amount.toNumber()).toBe(10 * TOKEN - fee);
+    expect(event.protocolFee.toNumber()).toBe(fee);
+    expect(event.isNewPosition).toBe(true);
+    // No lock tiers, so the 1x boost
+    expect(event.lockBoostBps).toBe(10_000);
+    expect(event.lockedUntil.toNumber()).toBe((await now(context)) + LOCK_PERIOD);
+    await expectTotals(event, position);
+
+    // A top-up reports the same position as not new
+    const topUp = await emitted(await stakeIx(from, 5 * TOKEN), [from.wallet], 'tokensStaked');
+    expect(topUp.isNewPosition).toBe(false);
+    await expectTotals(topUp, position);
+  });
+
+  test('stake_tokens_for names the funder separately from the staker', async () => {
+    const funder = await wallet();
+    const beneficiary = await wallet(0);
+    const position = positionPda(vault, beneficiary.wallet.publicKey);
+
+    const ix = await program.methods
+      .stakeTokensFor(new BN(4 * TOKEN), { general: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: position,
+        funderTokenAccount: funder.tokens,
+        withdrawalTokenAccount: beneficiary.tokens,
+        beneficiary: beneficiary.wallet.publicKey,
+        funder: funder.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: endorsementIndexPda(agent.publicKey),
+        priceFeed: null,
+        agentIdentity: identityPda(agent.publicKey),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+    const event = await emitted(ix, [funder.wallet], 'tokensStaked');
+
+    expect(event.staker.equals(beneficiary.wallet.publicKey)).toBe(true);
+    expect(event.funder.equals(funder.wallet.publicKey)).toBe(true);
+    expect(event.protocolFee.toNumber()).toBe((4 * TOKEN * FEE_BPS) / 10_000);
+    expect(event.isNewPosition).toBe(true);
+    await expectTotals(event, position);
+  });
+
+  test('unstake_tokens emits TokensUnstaked with the payout destination', async () => {
+    const from = await wallet();
+    const position = positionPda(vault, from.wallet.publicKey);
+    await emitted(await stakeIx(from, 10 * TOKEN), [from.wallet], 'tokensStaked');
+    await advanceTime(context, LOCK_PERIOD);
+
+    const unstakeIx = (amount: number) =>
+      program.methods
+        .unstakeTokens(new BN(amount))
+        .accounts({
+          vault,
+          vaultTokenAccount: vaultTokenPda(vault),
+          stakePosition: position,
+          stakerTokenAccount: from.tokens,
+          endorsementIndex: endorsementIndexPda(agent.publicKey),
+          staker: from.wallet.publicKey,
+          protocolConfig: protocolConfigPda(),
+          feeTokenAccount: feeAccountPda(mint),
+          tokenMint: mint,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .instruction();
+
+    const partial = await emitted(await unstakeIx(TOKEN), [from.wallet], 'tokensUnstaked');
+    expect(partial.vault.equals(vault)).toBe(true);
+    expect(partial.stakePosition.equals(position)).toBe(true);
+    expect(partial.endorsementIndex.equals(endorsementIndexPda(agent.publicKey))).toBe(true);
+    expect(partial.staker.equals(from.wallet.publicKey)).toBe(true);
+    expect(partial.destination.equals(from.tokens)).toBe(true);
+    expect(partial.targetAgent.equals(agent.publicKey)).toBe(true);
+    expect(partial.tokenMint.equals(mint)).toBe(true);
+    expect(partial.category).toEqual({ security: {} });
+    expect(partial.amount.toNumber()).toBe(TOKEN);
+    // Unstake fees are off in this config
+    expect(partial.protocolFee.toNumber()).toBe(0);
+    expect(partial.isFullUnstake).toBe(false);
+    await expectTotals(partial, position);
+
+    const rest = (await fetchAccount(program, 'stakePosition', position)).amount.toNumber();
+    const full = await emitted(await unstakeIx(rest), [from.wallet], 'tokensUnstaked');
+    expect(full.amount.toNumber()).toBe(rest);
+    expect(full.isFullUnstake).toBe(true);
+    expect(full.positionAmount.toNumber()).toBe(0);
+    expect(full.trustWeight.toNumber()).toBe(0);
+    await expectTotals(full, position);
+  });
+});
@@ -0,0 +1,261 @@
+/**
+ * Unstake Cooldown Tests
+ * Tests request_unstake / withdraw_unstaked for cooldown-mode token vaults
+ *
+ * Cooldown mode ensures:
+ * 1. Stakers exit by requesting an unstake and waiting out the vault's cooldown
+ * 2. Requested amounts stop counting toward trust weight immediately
+ * 3. Pending unstakes remain slashable until withdrawn
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createAccount, createMint, getAccount, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, tokenStaker, vaultIdentityPda, vaultOwner } from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const LOCK_PERIOD = 7 * 24 * 60 * 60;
+const MIN_STAKE = 1_000_000;
+const COOLDOWN = 3 * 24 * 60 * 60;
+
+describe('Unstake Cooldown', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mint: PublicKey;
+  let slashAuthority: Keypair;
+  let penaltyAccount: PublicKey;
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), agent.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** The endorsement index of the agent a vault belongs to */
+  async function vaultIndexPda(vault: PublicKey): Promise<PublicKey> {
+    const { targetAgent } = await fetchAccount(program, 'stakingVault', vault);
+    return endorsementIndexPda(targetAgent);
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  async function tokenBalance(account: PublicKey): Promise<number> {
+    return Number((await getAccount(context.banksClient, account)).amount);
+  }
+
+  function configureSlashing(agent: Keypair, vault: PublicKey, slasher = slashAuthority.publicKey) {
+    return program.methods
+      .configureSlashing(slasher)
+      .accounts({ vault, penaltyDestination: penaltyAccount, authority: agent.publicKey })
+      .signers([agent])
+      .rpc();
+  }
+
+  async function stake(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .stakeTokens(new BN(amount), { general: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: await vaultIndexPda(vault),
+        priceFeed: null,
+        agentIdentity: await vaultIdentityPda(program, vault),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  async function unstake(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .unstakeTokens(new BN(amount))
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        endorsementIndex: await vaultIndexPda(vault),
+        staker: from.wallet.publicKey,
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  async function requestUnstake(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .requestUnstake(new BN(amount))
+      .accounts({
+        vault,
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        endorsementIndex: await vaultIndexPda(vault),
+        staker: from.wallet.publicKey,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  async function withdrawUnstaked(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }) {
+    return program.methods
+      .withdrawUnstaked()
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        endorsementIndex: await vaultIndexPda(vault),
+        staker: from.wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  async function positionOf(vault: PublicKey, from: { wallet: Keypair }) {
+    return fetchAccount(program, 'stakePosition', positionPda(vault, from.wallet.publicKey));
+  }
+
+  async function slash(vault: PublicKey, position: PublicKey, severityBps: number, signer = slashAuthority) {
+    return program.methods
+      .slashStakePosition(severityBps)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: position,
+        penaltyDestination: penaltyAccount,
+        endorsementIndex: await vaultIndexPda(vault),
+        slashAuthority: signer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mint = await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6);
+    slashAuthority = Keypair.generate();
+    await airdrop(context, slashAuthority.publicKey, LAMPORTS_PER_SOL);
+    penaltyAccount = await createAccount(context.banksClient, context.payer, mint, Keypair.generate().publicKey);
+  });
+
+  test('each vault mode only accepts its own exit path', async () => {
+    const cooldownVault = (await vaultOwner(context, program, mint, { cooldownSeconds: COOLDOWN })).vault;
+    const lockVault = (await vaultOwner(context, program, mint)).vault;
+    const from = await tokenStaker(context, mint);
+    await stake(cooldownVault, from, 10 * MIN_STAKE);
+    await stake(lockVault, from, 10 * MIN_STAKE);
+
+    await advanceTime(context, LOCK_PERIOD);
+    await expect(unstake(cooldownVault, from, MIN_STAKE)).rejects.toThrow(/CooldownModeVault/);
+    await expect(requestUnstake(lockVault, from, MIN_STAKE)).rejects.toThrow(/LockModeVault/);
+    const vaultState = await fetchAccount(program, 'stakingVault', cooldownVault);
+    expect(vaultState.cooldownMode).toBe(true);
+    expect(vaultState.unstakeCooldownSeconds.toNumber()).toBe(COOLDOWN);
+  });
+
+  test('trust weight drops as soon as an unstake is requested', async () => {
+    const { vault } = await vaultOwner(context, program, mint, { cooldownSeconds: COOLDOWN });
+    const [leaving, reference] = [await tokenStaker(context, mint), await tokenStaker(context, mint)];
+    await stake(vault, leaving, 10 * MIN_STAKE);
+    await stake(vault, reference, 6 * MIN_STAKE);
+
+    // No lock at stake time: the request can follow straight away
+    await requestUnstake(vault, leaving, 4 * MIN_STAKE);
+    const position = await positionOf(vault, leaving);
+    expect(position.amount.toNumber()).toBe(10 * MIN_STAKE);
+    expect(position.pendingUnstakeAmount.toNumber()).toBe(4 * MIN_STAKE);
+    expect(position.trustWeight.toNumber()).toBe((await positionOf(vault, reference)).trustWeight.toNumber());
+
+    await requestUnstake(vault, leaving, 6 * MIN_STAKE);
+    expect((await positionOf(vault, leaving)).trustWeight.toNumber()).toBe(0);
+    await expect(requestUnstake(vault, leaving, 1)).rejects.toThrow(/InvalidUnstakeAmount/);
+  });
+
+  test('a slash during the cooldown reaches the pending amount', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint, { cooldownSeconds: COOLDOWN });
+    await configureSlashing(agent, vault);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 10 * MIN_STAKE);
+    await requestUnstake(vault, from, 10 * MIN_STAKE);
+
+    await slash(vault, positionPda(vault, from.wallet.publicKey), 5_000);
+    const slashed = await positionOf(vault, from);
+    expect(slashed.amount.toNumber()).toBe(5 * MIN_STAKE);
+    expect(slashed.pendingUnstakeAmount.toNumber()).toBe(5 * MIN_STAKE);
+
+    await advanceTime(context, COOLDOWN);
+    const before = await tokenBalance(from.tokens);
+    await withdrawUnstaked(vault, from);
+    expect((await tokenBalance(from.tokens)) - before).toBe(5 * MIN_STAKE);
+    expect((await positionOf(vault, from)).isActive).toBe(false);
+  });
+
+  test('the pending amount can be withdrawn once the cooldown has elapsed', async () => {
+    const { vault } = await vaultOwner(context, program, mint, { cooldownSeconds: COOLDOWN });
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, 10 * MIN_STAKE);
+    await requestUnstake(vault, from, 4 * MIN_STAKE);
+
+    await advanceTime(context, COOLDOWN - 1);
+    await expect(withdrawUnstaked(vault, from)).rejects.toThrow(/StakeLocked/);
+
+    await advanceTime(context, 1);
+    const before = await tokenBalance(from.tokens);
+    await withdrawUnstaked(vault, from);
+    expect((await tokenBalance(from.tokens)) - before).toBe(4 * MIN_STAKE);
+
+    const position = await positionOf(vault, from);
+    expect(position.amount.toNumber()).toBe(6 * MIN_STAKE);
+    expect(position.pendingUnstakeAmount.toNumber()).toBe(0);
+    expect(position.isActive).toBe(true);
+    expect((await fetchAccount(program, 'stakingVault', vault)).totalStaked.toNumber()).toBe(6 * MIN_STAKE);
+    await expect(withdrawUnstaked(vault, from)).rejects.toThrow(/NoPendingUnstake/);
+  });
+});
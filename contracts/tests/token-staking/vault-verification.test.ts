@@ -0,0 +1,196 @@
+/**
+ * Vault Verification Tests
+ * Tests the vault registry config, verify_vault / unverify_vault and verified-only staking
+ *
+ * Verification ensures:
+ * 1. Only the platform verifier can verify or unverify a vault, recording a reason hash
+ * 2. Verification changes are emitted as events
+ * 3. In verified-only mode stake_tokens rejects unverified vaults
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createMint, TOKEN_PROGRAM_ID } from 'spl-token-bankrun';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  loadProgram,
+  now,
+  simulateEvents,
+  tokenStaker,
+  vaultIdentityPda,
+  vaultOwner,
+} from '../helpers/bankrun';
+
+const STAKING_PROGRAM_ID = new PublicKey('4JNxNBFEH3BD6VRjQoi2pNDpbEa8L46LKbHnUTrdAWeL');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const MIN_STAKE = 1_000_000;
+
+describe('Vault Verification', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let mint: PublicKey;
+  let admin: Keypair;
+  let verifier: Keypair;
+
+  function vaultTokenPda(vault: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vault_token'), vault.toBuffer()], STAKING_PROGRAM_ID)[0];
+  }
+
+  function registryConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('registry_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function protocolConfigPda(): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('protocol_config')], STAKING_PROGRAM_ID)[0];
+  }
+
+  function feeAccountPda(tokenMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_fees'), tokenMint.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementIndexPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_index'), agent.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  /** The endorsement index of the agent a vault belongs to */
+  async function vaultIndexPda(vault: PublicKey): Promise<PublicKey> {
+    const { targetAgent } = await fetchAccount(program, 'stakingVault', vault);
+    return endorsementIndexPda(targetAgent);
+  }
+
+  function positionPda(vault: PublicKey, staker: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stake'), vault.toBuffer(), staker.toBuffer()],
+      STAKING_PROGRAM_ID
+    )[0];
+  }
+
+  async function stake(vault: PublicKey, from: { wallet: Keypair; tokens: PublicKey }, amount: number) {
+    return program.methods
+      .stakeTokens(new BN(amount), { general: {} }, null)
+      .accounts({
+        vault,
+        vaultTokenAccount: vaultTokenPda(vault),
+        stakePosition: positionPda(vault, from.wallet.publicKey),
+        stakerTokenAccount: from.tokens,
+        staker: from.wallet.publicKey,
+        registryConfig: registryConfigPda(),
+        endorsementIndex: await vaultIndexPda(vault),
+        priceFeed: null,
+        agentIdentity: await vaultIdentityPda(program, vault),
+        protocolConfig: protocolConfigPda(),
+        feeTokenAccount: feeAccountPda(mint),
+        tokenMint: mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([from.wallet])
+      .rpc();
+  }
+
+  function reasonHash(reason: string): number[] {
+    return Array.from(createHash('sha256').update(reason).digest());
+  }
+
+  type Verification = 'verifyVault' | 'unverifyVault';
+
+  function verificationIx(method: Verification, vault: PublicKey, reason: string, signer = verifier) {
+    return program.methods[method](reasonHash(reason))
+      .accounts({ registryConfig: registryConfigPda(), vault, verifier: signer.publicKey })
+      .signers([signer]);
+  }
+
+  function requireVerifiedVaults(required: boolean, signer = admin) {
+    return program.methods
+      .updateRegistryConfig(null, required)
+      .accounts({ registryConfig: registryConfigPda(), admin: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor('', [{ name: 'token_staking', programId: STAKING_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = loadProgram('token_staking', provider);
+
+    mint = await createMint(context.banksClient, context.payer, context.payer.publicKey, null, 6);
+    [admin, verifier] = [Keypair.generate(), Keypair.generate()];
+    await airdrop(context, admin.publicKey, LAMPORTS_PER_SOL);
+    await airdrop(context, verifier.publicKey, LAMPORTS_PER_SOL);
+
+    await program.methods
+      .initializeRegistryConfig(verifier.publicKey, false)
+      .accounts({
+        registryConfig: registryConfigPda(),
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  test('the verifier flips the flag and emits VaultVerified', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+
+    const ix = await verificationIx('verifyVault', vault, 'audited token').instruction();
+    const events = await simulateEvents(context, program, ix, [verifier]);
+    expect(events.map((event) => event.name)).toEqual(['vaultVerified']);
+    const event = events[0].data;
+    expect(event.vault.equals(vault)).toBe(true);
+    expect(event.targetAgent.equals(agent.publicKey)).toBe(true);
+    expect(event.tokenMint.equals(mint)).toBe(true);
+    expect(event.verifier.equals(verifier.publicKey)).toBe(true);
+    expect(Array.from(event.reasonHash as number[])).toEqual(reasonHash('audited token'));
+
+    await verificationIx('verifyVault', vault, 'audited token').rpc();
+    const verified = await fetchAccount(program, 'stakingVault', vault);
+    expect(verified.isVerified).toBe(true);
+    expect(Array.from(verified.verificationReasonHash as number[])).toEqual(reasonHash('audited token'));
+    expect(verified.verificationUpdatedAt.toNumber()).toBe(await now(context));
+
+    await verificationIx('unverifyVault', vault, 'mint authority changed').rpc();
+    expect((await fetchAccount(program, 'stakingVault', vault)).isVerified).toBe(false);
+  });
+
+  test('only the verifier can verify, and only the admin can change the config', async () => {
+    const { agent, vault } = await vaultOwner(context, program, mint);
+
+    await expect(verificationIx('verifyVault', vault, 'self-verified', agent).rpc()).rejects.toThrow(
+      /UnauthorizedVerifier/
+    );
+    await expect(requireVerifiedVaults(true, verifier)).rejects.toThrow(/UnauthorizedRegistryAdmin/);
+    expect((await fetchAccount(program, 'stakingVault', vault)).isVerified).toBe(false);
+  });
+
+  test('verified-only mode rejects stakes into unverified vaults until they are verified', async () => {
+    const { vault } = await vaultOwner(context, program, mint);
+    const from = await tokenStaker(context, mint);
+    await stake(vault, from, MIN_STAKE);
+
+    await requireVerifiedVaults(true);
+    await expect(stake(vault, from, MIN_STAKE)).rejects.toThrow(/VaultNotVerified/);
+
+    await verificationIx('verifyVault', vault, 'audited token').rpc();
+    await stake(vault, from, MIN_STAKE);
+    const position = await fetchAccount(program, 'stakePosition', positionPda(vault, from.wallet.publicKey));
+    expect(position.amount.toNumber()).toBe(2 * MIN_STAKE);
+
+    await verificationIx('unverifyVault', vault, 'mint authority changed').rpc();
+    await expect(stake(vault, from, MIN_STAKE)).rejects.toThrow(/VaultNotVerified/);
+
+    await requireVerifiedVaults(false);
+    await stake(vault, from, MIN_STAKE);
+  });
+});
@@ -0,0 +1,314 @@
+/**
+ * Validation Authority Tests
+ * Tests authority rotation and the stamp multisig
+ *
+ * Authority ensures:
+ * 1. Authority transfers take two steps: nomination, then acceptance by the nominee
+ * 2. Until the multisig is enabled, the single authority key issues and revokes stamps
+ * 3. Once enabled, issuing or revoking a stamp needs a StampProposal approved by quorum
+ * 4. A proposal authorizes exactly one action on one endpoint, once
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, passingResults, bondedValidator } from '../helpers/bankrun';
+
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const [CONFIG_PDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], VALIDATION_PROGRAM_ID);
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+
+describe('Validation Authority', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let identityProgram: Program<Idl>;
+  let authority: Keypair;
+  let authorityPda: PublicKey;
+  let multisigPda: PublicKey;
+  let signers: Keypair[];
+
+  function validationPda(endpointHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('validation'), Buffer.from(endpointHash)],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function endpointIndexPda(provider: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('endpoints'), provider.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  function validatorRecordPda(validator: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('validator'), validator.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  /** A wallet with an agent identity and a bonded ValidatorRecord */
+  function registeredValidator(): Promise<Keypair> {
+    return bondedValidator(context, identityProgram, program);
+  }
+
+  /** Open an endpoint for `providerAgent`, paid by `payer`; results are submitted by the provider */
+  async function submit(providerAgent: Keypair, url: string, payer = providerAgent, withResults = false) {
+    const endpointHash = metadataHash(url);
+    await program.methods
+      .submitValidation(url, endpointHash, withResults ? passingResults() : [])
+      .accounts({
+        endpointValidation: validationPda(endpointHash),
+        providerAgent: providerAgent.publicKey,
+        providerIdentity: identityPda(providerAgent.publicKey),
+        endpointIndex: endpointIndexPda(providerAgent.publicKey),
+        payer: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+        validatorRecord: withResults ? validatorRecordPda(payer.publicKey) : null,
+        validationConfig: CONFIG_PDA,
+        rewardPool: null,
+      })
+      .signers([payer])
+      .rpc();
+    return { endpointHash, validation: validationPda(endpointHash) };
+  }
+
+  function proposalPda(proposalId: number): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('stamp_proposal'), new BN(proposalId).toArrayLike(Buffer, 'le', 8)],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  /** A fresh endpoint with passing results and computed consensus, ready to stamp */
+  async function readyEndpoint(url: string) {
+    const providerAgent = await registeredValidator();
+    const { endpointHash, validation } = await submit(providerAgent, url, providerAgent, true);
+    await program.methods
+      .calculateConsensus()
+      .accounts({
+        endpointValidation: validation,
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        validationConfig: null,
+      })
+      .signers([authority])
+      .rpc();
+    return { endpointHash, validation, providerAgent };
+  }
+
+  function issue(validation: PublicKey, providerAgent: Keypair, proposalId: number | null = null) {
+    return program.methods
+      .issueValidationStamp()
+      .accounts({
+        endpointValidation: validation,
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        validationConfig: null,
+        endpointIndex: endpointIndexPda(providerAgent.publicKey),
+        stampProposal: proposalId === null ? null : proposalPda(proposalId),
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  function revoke(validation: PublicKey, reason: number[], proposalId: number | null = null) {
+    return program.methods
+      .revokeStamp(reason)
+      .accounts({
+        endpointValidation: validation,
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        stampProposal: proposalId === null ? null : proposalPda(proposalId),
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  /** Propose a stamp action as `proposer`, returning the new proposal's id */
+  async function propose(
+    proposer: Keypair,
+    action: { issue: Record<string, never> } | { revoke: Record<string, never> },
+    endpointHash: number[],
+    reason: number[] = Array(32).fill(0)
+  ): Promise<number> {
+    const proposalId = (await fetchAccount(program, 'stampMultisig', multisigPda)).proposalCount.toNumber();
+    await program.methods
+      .proposeStampAction(action, endpointHash, reason)
+      .accounts({
+        stampMultisig: multisigPda,
+        stampProposal: proposalPda(proposalId),
+        proposer: proposer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([proposer])
+      .rpc();
+    return proposalId;
+  }
+
+  function approve(signer: Keypair, proposalId: number) {
+    return program.methods
+      .approveStampProposal(new BN(proposalId))
+      .accounts({ stampMultisig: multisigPda, stampProposal: proposalPda(proposalId), signer: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function enableMultisig() {
+    return program.methods
+      .enableStampMultisig()
+      .accounts({ authorityAccount: authorityPda, stampMultisig: multisigPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('validation_registry', provider);
+    identityProgram = loadProgram('identity_registry', provider);
+
+    authority = await fundedKeypair();
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VALIDATION_PROGRAM_ID);
+    [multisigPda] = PublicKey.findProgramAddressSync([Buffer.from('stamp_multisig')], VALIDATION_PROGRAM_ID);
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    signers = [await fundedKeypair(), await fundedKeypair(), await fundedKeypair()];
+  });
+
+  test('authority transfer needs the nominee to accept', async () => {
+    const nominee = await fundedKeypair();
+    const stranger = await fundedKeypair();
+
+    function accept(signer: Keypair) {
+      return program.methods
+        .acceptValidationAuthority()
+        .accounts({ authorityAccount: authorityPda, newAuthority: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    }
+    function transfer(from: Keypair, to: PublicKey) {
+      return program.methods
+        .transferValidationAuthority(to)
+        .accounts({ authorityAccount: authorityPda, authority: from.publicKey })
+        .signers([from])
+        .rpc();
+    }
+
+    await expect(accept(nominee)).rejects.toThrow(/NoPendingAuthority/);
+    await expect(transfer(stranger, nominee.publicKey)).rejects.toThrow(/UnauthorizedAuthority/);
+
+    await transfer(authority, nominee.publicKey);
+    let account = await fetchAccount(program, 'validationAuthority', authorityPda);
+    expect(account.authority.toBase58()).toBe(authority.publicKey.toBase58());
+    expect(account.pendingAuthority.toBase58()).toBe(nominee.publicKey.toBase58());
+
+    await expect(accept(stranger)).rejects.toThrow(/NotPendingAuthority/);
+    await accept(nominee);
+    account = await fetchAccount(program, 'validationAuthority', authorityPda);
+    expect(account.authority.toBase58()).toBe(nominee.publicKey.toBase58());
+    expect(account.pendingAuthority.toBase58()).toBe(PublicKey.default.toBase58());
+
+    // The previous key has lost its powers; hand authority back for the remaining tests
+    await expect(transfer(authority, stranger.publicKey)).rejects.toThrow(/UnauthorizedAuthority/);
+    await transfer(nominee, authority.publicKey);
+    await accept(authority);
+  });
+
+  test('the single key stamps until the multisig is enabled', async () => {
+    await program.methods
+      .initializeStampMultisig(signers.map((s) => s.publicKey), 2)
+      .accounts({
+        authorityAccount: authorityPda,
+        stampMultisig: multisigPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    // Creating the multisig alone changes nothing
+    const { validation, providerAgent } = await readyEndpoint('https://api.example.com/x402/single-key');
+    await issue(validation, providerAgent);
+    const account = await fetchAccount(program, 'endpointValidation', validation);
+    expect(account.stampTier).toEqual({ gold: {} });
+  });
+
+  test('after opt-in, stamps are issued through multisig quorum', async () => {
+    await enableMultisig();
+    await expect(enableMultisig()).rejects.toThrow(/MultisigAlreadyEnabled/);
+    expect((await fetchAccount(program, 'validationAuthority', authorityPda)).stampMultisig.toBase58()).toBe(
+      multisigPda.toBase58()
+    );
+
+    const { endpointHash, validation, providerAgent } = await readyEndpoint('https://api.example.com/x402/quorum');
+
+    // The authority alone is rejected
+    await expect(issue(validation, providerAgent)).rejects.toThrow(/MultisigApprovalRequired/);
+
+    // One of two approvals is not enough
+    const proposalId = await propose(signers[0], { issue: {} }, endpointHash);
+    await expect(issue(validation, providerAgent, proposalId)).rejects.toThrow(/MultisigApprovalRequired/);
+    await expect(approve(signers[0], proposalId)).rejects.toThrow(/AlreadyApproved/);
+    await expect(approve(await fundedKeypair(), proposalId)).rejects.toThrow(/UnauthorizedSigner/);
+
+    await approve(signers[1], proposalId);
+    await issue(validation, providerAgent, proposalId);
+    expect((await fetchAccount(program, 'endpointValidation', validation)).stampTier).toEqual({ gold: {} });
+
+    // Executed proposals cannot be replayed
+    const proposal = await fetchAccount(program, 'stampProposal', proposalPda(proposalId));
+    expect(proposal.status).toEqual({ executed: {} });
+    await expect(approve(signers[2], proposalId)).rejects.toThrow(/ProposalNotPending/);
+  });
+
+  test('a proposal only authorizes the action and endpoint it names', async () => {
+    const first = await readyEndpoint('https://api.example.com/x402/mismatch-a');
+    const second = await readyEndpoint('https://api.example.com/x402/mismatch-b');
+
+    const issueFirst = await propose(signers[1], { issue: {} }, first.endpointHash);
+    await approve(signers[2], issueFirst);
+    await expect(issue(second.validation, second.providerAgent, issueFirst)).rejects.toThrow(/ProposalMismatch/);
+    await issue(first.validation, first.providerAgent, issueFirst);
+
+    // Revocation is gated the same way, and the reason must match the approved one
+    const reason = metadataHash('endpoint returned fabricated data');
+    await expect(revoke(first.validation, reason)).rejects.toThrow(/MultisigApprovalRequired/);
+    const revokeFirst = await propose(signers[0], { revoke: {} }, first.endpointHash, reason);
+    await approve(signers[2], revokeFirst);
+    await expect(revoke(first.validation, metadataHash('something else'), revokeFirst)).rejects.toThrow(
+      /ProposalMismatch/
+    );
+    await revoke(first.validation, reason, revokeFirst);
+
+    const account = await fetchAccount(program, 'endpointValidation', first.validation);
+    expect(account.stampTier).toEqual({ none: {} });
+    expect(Array.from(account.stampRevocationReason)).toEqual(reason);
+  });
+});
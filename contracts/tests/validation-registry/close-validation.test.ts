@@ -0,0 +1,211 @@
+/**
+ * Close Validation Tests
+ * Tests close_validation for reclaiming rent from stale endpoint validations
+ *
+ * Closing ensures:
+ * 1. A validation can only be closed after the retention period (180 days by default)
+ * 2. A validation holding a valid stamp cannot be closed until the stamp expires
+ * 3. A validation with an open dispute cannot be closed
+ * 4. Rent returns to the recorded payer, and the endpoint leaves the provider's index
+ * 5. Only the recorded payer or the provider agent can close
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, metadataHash, passingResults, bondedValidator } from '../helpers/bankrun';
+
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const [CONFIG_PDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], VALIDATION_PROGRAM_ID);
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const DAY = 24 * 60 * 60;
+const DEFAULT_RETENTION = 180 * DAY;
+
+describe('Close Validation', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let identityProgram: Program<Idl>;
+  let authority: Keypair;
+  let authorityPda: PublicKey;
+
+  function validationPda(endpointHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('validation'), Buffer.from(endpointHash)],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function endpointIndexPda(provider: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('endpoints'), provider.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  function validatorRecordPda(validator: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('validator'), validator.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  /** A wallet with an agent identity and a bonded ValidatorRecord */
+  function registeredValidator(): Promise<Keypair> {
+    return bondedValidator(context, identityProgram, program);
+  }
+
+  /** Open an endpoint for `providerAgent`, paid by `payer`; results are submitted by the provider */
+  async function submit(providerAgent: Keypair, url: string, payer = providerAgent, withResults = false) {
+    const endpointHash = metadataHash(url);
+    await program.methods
+      .submitValidation(url, endpointHash, withResults ? passingResults() : [])
+      .accounts({
+        endpointValidation: validationPda(endpointHash),
+        providerAgent: providerAgent.publicKey,
+        providerIdentity: identityPda(providerAgent.publicKey),
+        endpointIndex: endpointIndexPda(providerAgent.publicKey),
+        payer: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+        validatorRecord: withResults ? validatorRecordPda(payer.publicKey) : null,
+        validationConfig: CONFIG_PDA,
+        rewardPool: null,
+      })
+      .signers([payer])
+      .rpc();
+    return { endpointHash, validation: validationPda(endpointHash) };
+  }
+
+  async function close(validation: PublicKey, signer: Keypair) {
+    const account = await fetchAccount(program, 'endpointValidation', validation);
+    return program.methods
+      .closeValidation()
+      .accounts({
+        endpointValidation: validation,
+        endpointIndex: endpointIndexPda(account.providerAgent),
+        payer: account.payer,
+        signer: signer.publicKey,
+        validationConfig: null,
+        rewardPool: null,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('validation_registry', provider);
+    identityProgram = loadProgram('identity_registry', provider);
+
+    authority = await fundedKeypair();
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VALIDATION_PROGRAM_ID);
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('after the retention period, rent returns to the recorded payer', async () => {
+    const providerAgent = await registeredValidator();
+    const payer = await fundedKeypair();
+    const stranger = await fundedKeypair();
+    const { endpointHash, validation } = await submit(providerAgent, 'https://api.example.com/x402/stale', payer);
+    expect((await fetchAccount(program, 'endpointValidation', validation)).payer.toBase58()).toBe(
+      payer.publicKey.toBase58()
+    );
+
+    await expect(close(validation, providerAgent)).rejects.toThrow(/RetentionPeriodActive/);
+    await advanceTime(context, DEFAULT_RETENTION);
+    await expect(close(validation, stranger)).rejects.toThrow(/UnauthorizedClose/);
+
+    const rent = (await context.banksClient.getAccount(validation))!.lamports;
+    const before = await context.banksClient.getBalance(payer.publicKey);
+    await close(validation, providerAgent);
+
+    expect(await context.banksClient.getAccount(validation)).toBeNull();
+    expect((await context.banksClient.getBalance(payer.publicKey)) - before).toBe(BigInt(rent));
+    const index = await fetchAccount(program, 'providerEndpointIndex', endpointIndexPda(providerAgent.publicKey));
+    expect(index.endpointHashes.map((h: number[]) => Array.from(h))).not.toContainEqual(endpointHash);
+  });
+
+  test('a validation with a valid stamp cannot be closed until it expires', async () => {
+    const providerAgent = await registeredValidator();
+    const { validation } = await submit(providerAgent, 'https://api.example.com/x402/stamped', providerAgent, true);
+
+    // Stamped 100 days after submission, so the 90-day stamp outlives the 180-day retention
+    await advanceTime(context, 100 * DAY);
+    const accounts = {
+      endpointValidation: validation,
+      authorityAccount: authorityPda,
+      authority: authority.publicKey,
+      validationConfig: null,
+    };
+    await program.methods.calculateConsensus().accounts(accounts).signers([authority]).rpc();
+    await program.methods
+      .issueValidationStamp()
+      .accounts({
+        ...accounts,
+        validationConfig: null,
+        endpointIndex: endpointIndexPda(providerAgent.publicKey),
+        stampProposal: null,
+      })
+      .signers([authority])
+      .rpc();
+
+    await advanceTime(context, 81 * DAY);
+    await expect(close(validation, providerAgent)).rejects.toThrow(/StampStillValid/);
+
+    await advanceTime(context, 10 * DAY);
+    await close(validation, providerAgent);
+    expect(await context.banksClient.getAccount(validation)).toBeNull();
+  });
+
+  test('a validation with an open dispute cannot be closed', async () => {
+    const providerAgent = await registeredValidator();
+    const { endpointHash, validation } = await submit(
+      providerAgent,
+      'https://api.example.com/x402/disputed',
+      providerAgent,
+      true
+    );
+    const [dispute] = PublicKey.findProgramAddressSync(
+      [Buffer.from('dispute'), Buffer.from(endpointHash), Buffer.alloc(4)],
+      VALIDATION_PROGRAM_ID
+    );
+    await program.methods
+      .disputeValidation(endpointHash, 0, metadataHash('evidence'))
+      .accounts({
+        endpointValidation: validation,
+        dispute,
+        providerAgent: providerAgent.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([providerAgent])
+      .rpc();
+
+    await advanceTime(context, DEFAULT_RETENTION);
+    await expect(close(validation, providerAgent)).rejects.toThrow(/DisputesOpen/);
+  });
+});
@@ -0,0 +1,187 @@
+/**
+ * Consensus Calculation Tests
+ * Tests the outlier-resistant, response-time weighted consensus formula
+ *
+ * Consensus ensures:
+ * 1. With 5+ successful results the highest and lowest scores are trimmed
+ * 2. Responses faster than 1s earn up to a 10% score bonus; timeouts count as failures
+ * 3. Fewer than 5 results fall back to a plain average
+ * 4. All-failed validations score 0
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset, testResult } from '../helpers/bankrun';
+
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const [CONFIG_PDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], VALIDATION_PROGRAM_ID);
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const MIN_VALIDATOR_BOND = 100_000_000;
+
+/** No speed bonus at or above 1s */
+const SLOW_MS = 2_000;
+
+const result = (score: number, responseTime = SLOW_MS, success = true) => testResult({ score, responseTime, success });
+
+describe('Consensus Calculation', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let identityProgram: Program<Idl>;
+  let authority: Keypair;
+  let authorityPda: PublicKey;
+  let validator: Keypair;
+  let endpointCount = 0;
+
+  function validationPda(endpointHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('validation'), Buffer.from(endpointHash)],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function endpointIndexPda(provider: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('endpoints'), provider.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  function validatorRecordPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('validator'), wallet.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  async function registeredValidator(): Promise<Keypair> {
+    const wallet = await fundedKeypair();
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    const identity = identityPda(wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/validator.json',
+        metadataHash('https://example.com/validator.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({ agentIdentity: identity, agent: wallet.publicKey, asset, systemProgram: SystemProgram.programId })
+      .signers([wallet])
+      .rpc();
+    await program.methods
+      .registerValidator(new BN(MIN_VALIDATOR_BOND))
+      .accounts({
+        validatorRecord: validatorRecordPda(wallet.publicKey),
+        validatorIdentity: identity,
+        validator: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+    return wallet;
+  }
+
+  /** Submit results for a fresh endpoint and return the computed consensus */
+  async function consensusOf(testResults: ReturnType<typeof result>[]): Promise<number> {
+    const url = `https://api.example.com/x402/consensus-${endpointCount++}`;
+    const endpointHash = metadataHash(url);
+    const validation = validationPda(endpointHash);
+    await program.methods
+      .submitValidation(url, endpointHash, testResults)
+      .accounts({
+        endpointValidation: validation,
+        providerAgent: validator.publicKey,
+        providerIdentity: identityPda(validator.publicKey),
+        endpointIndex: endpointIndexPda(validator.publicKey),
+        payer: validator.publicKey,
+        systemProgram: SystemProgram.programId,
+        validatorRecord: validatorRecordPda(validator.publicKey),
+        validationConfig: CONFIG_PDA,
+        rewardPool: null,
+      })
+      .signers([validator])
+      .rpc();
+    await program.methods
+      .calculateConsensus()
+      .accounts({
+        endpointValidation: validation,
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        validationConfig: null,
+      })
+      .signers([authority])
+      .rpc();
+    return (await fetchAccount(program, 'endpointValidation', validation)).consensusScore;
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('validation_registry', provider);
+    identityProgram = loadProgram('identity_registry', provider);
+
+    authority = await fundedKeypair();
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VALIDATION_PROGRAM_ID);
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    validator = await registeredValidator();
+  });
+
+  test('five or more results drop the highest and lowest score', async () => {
+    // One colluding 100 among honest 40s: trimmed mean stays 40
+    expect(await consensusOf([result(40), result(40), result(40), result(40), result(100)])).toBe(940);
+    // Trimming removes one outlier at each end
+    expect(await consensusOf([result(0), result(50), result(60), result(70), result(100)])).toBe(960);
+  });
+
+  test('fewer than five results use a plain average', async () => {
+    expect(await consensusOf([result(40), result(40), result(40), result(100)])).toBe(955);
+    expect(await consensusOf([result(40), result(60), result(80)])).toBe(960);
+  });
+
+  test('fast responses earn a mild bonus and timeouts count as failures', async () => {
+    // At the 100ms floor: +10% (80 -> 88)
+    expect(await consensusOf([result(80, 100), result(80, 100), result(80, 100)])).toBe(988);
+    // Claims below the floor gain nothing extra
+    expect(await consensusOf([result(80, 1), result(80, 1), result(80, 1)])).toBe(988);
+    // 550ms is halfway to the 1s cutoff: +5% (80 -> 84)
+    expect(await consensusOf([result(80, 550), result(80, 550), result(80, 550)])).toBe(984);
+    // Bonus is capped at 100
+    expect(await consensusOf([result(100, 100), result(100, 100), result(100, 100)])).toBe(1000);
+    // A 30s timeout is a failure even if reported as a success: 66% * 9 = 594
+    expect(await consensusOf([result(80), result(80), result(80, 30_000)])).toBe(674);
+  });
+
+  test('all-failed validations score 0', async () => {
+    const failed = result(90, SLOW_MS, false);
+    expect(await consensusOf([failed, failed, failed])).toBe(0);
+  });
+});
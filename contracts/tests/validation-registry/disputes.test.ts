@@ -0,0 +1,313 @@
+/**
+ * Validation Dispute Tests
+ * Tests dispute_validation and resolve_dispute
+ *
+ * Disputes ensure:
+ * 1. Only the provider agent can dispute its endpoint's results, escrowing a bond
+ * 2. Stamp issuance is frozen while a dispute is open
+ * 3. An upheld dispute removes the result, recomputes consensus and returns the bond
+ * 4. A rejected (frivolous) dispute keeps the result and forfeits the bond
+ * 5. Open disputes per endpoint are limited
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset, testResult } from '../helpers/bankrun';
+
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const [CONFIG_PDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], VALIDATION_PROGRAM_ID);
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const MIN_VALIDATOR_BOND = 100_000_000;
+const DISPUTE_BOND = 10_000_000n;
+const MAX_OPEN_DISPUTES = 3;
+
+describe('Validation Disputes', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let identityProgram: Program<Idl>;
+  let authority: Keypair;
+  let authorityPda: PublicKey;
+
+  function validationPda(endpointHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('validation'), Buffer.from(endpointHash)],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  function disputePda(endpointHash: number[], disputeIndex: number): PublicKey {
+    const index = Buffer.alloc(4);
+    index.writeUInt32LE(disputeIndex, 0);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('dispute'), Buffer.from(endpointHash), index],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function endpointIndexPda(provider: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('endpoints'), provider.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  function validatorRecordPda(validator: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('validator'), validator.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  async function withIdentity(): Promise<Keypair> {
+    const agent = await fundedKeypair();
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    return agent;
+  }
+
+  async function registeredValidator(): Promise<Keypair> {
+    const validator = await withIdentity();
+    await program.methods
+      .registerValidator(new BN(MIN_VALIDATOR_BOND))
+      .accounts({
+        validatorRecord: validatorRecordPda(validator.publicKey),
+        validatorIdentity: identityPda(validator.publicKey),
+        validator: validator.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([validator])
+      .rpc();
+    return validator;
+  }
+
+  /**
+   * Three honest 95-point passes followed by one bogus failure (index 3).
+   * Consensus is 95 + 75% * 9 = 770 (Bronze); without the failure it is 995 (Gold).
+   */
+  async function validatedEndpoint(url: string) {
+    const providerAgent = await withIdentity();
+    const endpointHash = metadataHash(url);
+    const validation = validationPda(endpointHash);
+    await program.methods
+      .submitValidation(url, endpointHash, [])
+      .accounts({
+        endpointValidation: validation,
+        providerAgent: providerAgent.publicKey,
+        providerIdentity: identityPda(providerAgent.publicKey),
+        endpointIndex: endpointIndexPda(providerAgent.publicKey),
+        payer: providerAgent.publicKey,
+        systemProgram: SystemProgram.programId,
+        validatorRecord: null,
+        validationConfig: CONFIG_PDA,
+        rewardPool: null,
+      })
+      .signers([providerAgent])
+      .rpc();
+
+    const entries = [testResult(), testResult(), testResult(), testResult({ success: false, score: 0 })];
+    for (const entry of entries) {
+      const validator = await registeredValidator();
+      await program.methods
+        .appendTestResult(entry)
+        .accounts({
+          endpointValidation: validation,
+          validator: validator.publicKey,
+          validatorRecord: validatorRecordPda(validator.publicKey),
+        })
+        .signers([validator])
+        .rpc();
+    }
+    await program.methods
+      .calculateConsensus()
+      .accounts({
+        endpointValidation: validation,
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        validationConfig: null,
+      })
+      .signers([authority])
+      .rpc();
+
+    return { providerAgent, endpointHash, validation };
+  }
+
+  function dispute(endpointHash: number[], disputeIndex: number, signer: Keypair, resultIndex = 3) {
+    return program.methods
+      .disputeValidation(endpointHash, resultIndex, metadataHash(`evidence-${disputeIndex}-${resultIndex}`))
+      .accounts({
+        endpointValidation: validationPda(endpointHash),
+        dispute: disputePda(endpointHash, disputeIndex),
+        providerAgent: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  function resolve(endpointHash: number[], disputeIndex: number, disputer: Keypair, uphold: boolean) {
+    return program.methods
+      .resolveDispute(uphold)
+      .accounts({
+        dispute: disputePda(endpointHash, disputeIndex),
+        endpointValidation: validationPda(endpointHash),
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        disputer: disputer.publicKey,
+        validationConfig: null,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  function stamp(validation: PublicKey, providerAgent: Keypair) {
+    return program.methods
+      .issueValidationStamp()
+      .accounts({
+        endpointValidation: validation,
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        validationConfig: null,
+        endpointIndex: endpointIndexPda(providerAgent.publicKey),
+        stampProposal: null,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('validation_registry', provider);
+    identityProgram = loadProgram('identity_registry', provider);
+
+    authority = await fundedKeypair();
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VALIDATION_PROGRAM_ID);
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('only the provider can dispute, and an open dispute blocks stamping', async () => {
+    const { providerAgent, endpointHash, validation } = await validatedEndpoint('https://api.example.com/x402/frozen');
+    const stranger = await withIdentity();
+
+    await expect(dispute(endpointHash, 0, stranger)).rejects.toThrow(/UnauthorizedDispute/);
+    await dispute(endpointHash, 0, providerAgent);
+
+    const account = await fetchAccount(program, 'endpointValidation', validation);
+    expect(account.disputedResults).toBe(1 << 3);
+    expect(account.disputeCount).toBe(1);
+    const record = await fetchAccount(program, 'validationDispute', disputePda(endpointHash, 0));
+    expect(record.status).toEqual({ open: {} });
+    expect(BigInt(record.bondLamports.toString())).toBe(DISPUTE_BOND);
+
+    await expect(stamp(validation, providerAgent)).rejects.toThrow(/DisputesOpen/);
+    await expect(dispute(endpointHash, 1, providerAgent)).rejects.toThrow(/ResultAlreadyDisputed/);
+  });
+
+  test('an upheld dispute removes the result, recomputes consensus and returns the bond', async () => {
+    const { providerAgent, endpointHash, validation } = await validatedEndpoint('https://api.example.com/x402/upheld');
+    expect((await fetchAccount(program, 'endpointValidation', validation)).consensusScore).toBe(770);
+
+    await dispute(endpointHash, 0, providerAgent);
+    const before = await context.banksClient.getBalance(providerAgent.publicKey);
+    await resolve(endpointHash, 0, providerAgent, true);
+
+    expect((await context.banksClient.getBalance(providerAgent.publicKey)) - before).toBe(DISPUTE_BOND);
+    const account = await fetchAccount(program, 'endpointValidation', validation);
+    expect(account.testResults).toHaveLength(3);
+    expect(account.testResults.every((r: { success: boolean }) => r.success)).toBe(true);
+    expect(account.consensusScore).toBe(995);
+    expect(account.disputedResults).toBe(0);
+    expect((await fetchAccount(program, 'validationDispute', disputePda(endpointHash, 0))).status).toEqual({
+      upheld: {},
+    });
+
+    await stamp(validation, providerAgent);
+    expect((await fetchAccount(program, 'endpointValidation', validation)).stampTier).toEqual({ gold: {} });
+  });
+
+  test('a frivolous dispute keeps the result and forfeits the bond', async () => {
+    const url = 'https://api.example.com/x402/rejected';
+    const { providerAgent, endpointHash, validation } = await validatedEndpoint(url);
+
+    await dispute(endpointHash, 0, providerAgent, 0);
+    const providerBefore = await context.banksClient.getBalance(providerAgent.publicKey);
+    const authorityBefore = await context.banksClient.getBalance(authority.publicKey);
+    await resolve(endpointHash, 0, providerAgent, false);
+
+    expect(await context.banksClient.getBalance(providerAgent.publicKey)).toBe(providerBefore);
+    expect((await context.banksClient.getBalance(authority.publicKey)) - authorityBefore).toBe(DISPUTE_BOND);
+    const account = await fetchAccount(program, 'endpointValidation', validation);
+    expect(account.testResults).toHaveLength(4);
+    expect(account.consensusScore).toBe(770);
+    expect(account.disputedResults).toBe(0);
+    expect((await fetchAccount(program, 'validationDispute', disputePda(endpointHash, 0))).status).toEqual({
+      rejected: {},
+    });
+
+    await expect(resolve(endpointHash, 0, providerAgent, true)).rejects.toThrow(/DisputeNotOpen/);
+    await stamp(validation, providerAgent);
+    expect((await fetchAccount(program, 'endpointValidation', validation)).stampTier).toEqual({ bronze: {} });
+  });
+
+  test('open disputes per endpoint are limited', async () => {
+    const { providerAgent, endpointHash } = await validatedEndpoint('https://api.example.com/x402/limited');
+
+    for (let i = 0; i < MAX_OPEN_DISPUTES; i++) {
+      await dispute(endpointHash, i, providerAgent, i);
+    }
+    await expect(dispute(endpointHash, MAX_OPEN_DISPUTES, providerAgent, MAX_OPEN_DISPUTES)).rejects.toThrow(
+      /TooManyOpenDisputes/
+    );
+
+    // Upholding the first shifts the others down; they still resolve against the right results
+    await resolve(endpointHash, 0, providerAgent, true);
+    await resolve(endpointHash, 2, providerAgent, true);
+    const account = await fetchAccount(program, 'endpointValidation', validationPda(endpointHash));
+    expect(account.testResults).toHaveLength(2);
+    expect(account.disputedResults).toBe(1 << 0);
+    expect(account.consensusScore).toBe(0);
+  });
+});
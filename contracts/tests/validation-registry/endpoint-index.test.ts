@@ -0,0 +1,206 @@
+/**
+ * Provider Endpoint Index Tests
+ * Tests provider identity checks and the ProviderEndpointIndex PDA
+ *
+ * Provider linking ensures:
+ * 1. Validations can only be submitted for providers with an active agent identity
+ * 2. Each new endpoint is appended to the provider's ["endpoints", provider] index
+ * 3. The index is bounded at 16 endpoints
+ * 4. Stamp issuance is recorded on the index
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset, passingResults } from '../helpers/bankrun';
+
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const [CONFIG_PDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], VALIDATION_PROGRAM_ID);
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const MIN_VALIDATOR_BOND = 100_000_000;
+const MAX_INDEXED_ENDPOINTS = 16;
+
+describe('Provider Endpoint Index', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let identityProgram: Program<Idl>;
+  let authority: Keypair;
+  let authorityPda: PublicKey;
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function endpointIndexPda(providerAgent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endpoints'), providerAgent.toBuffer()],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  function validatorRecordPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('validator'), wallet.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  function validationPda(endpointHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('validation'), Buffer.from(endpointHash)],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  async function withIdentity(): Promise<Keypair> {
+    const agent = await fundedKeypair();
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/provider.json',
+        metadataHash('https://example.com/provider.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    return agent;
+  }
+
+  /** Open an endpoint for `providerAgent`; results require it to be a registered validator */
+  function submit(providerAgent: Keypair, url: string, testResults: ReturnType<typeof passingResults> = []) {
+    const endpointHash = metadataHash(url);
+    return program.methods
+      .submitValidation(url, endpointHash, testResults)
+      .accounts({
+        endpointValidation: validationPda(endpointHash),
+        providerAgent: providerAgent.publicKey,
+        providerIdentity: identityPda(providerAgent.publicKey),
+        endpointIndex: endpointIndexPda(providerAgent.publicKey),
+        payer: providerAgent.publicKey,
+        systemProgram: SystemProgram.programId,
+        validatorRecord: testResults.length > 0 ? validatorRecordPda(providerAgent.publicKey) : null,
+        validationConfig: CONFIG_PDA,
+        rewardPool: null,
+      })
+      .signers([providerAgent])
+      .rpc();
+  }
+
+  function index(providerAgent: Keypair) {
+    return fetchAccount(program, 'providerEndpointIndex', endpointIndexPda(providerAgent.publicKey));
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('validation_registry', provider);
+    identityProgram = loadProgram('identity_registry', provider);
+
+    authority = await fundedKeypair();
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VALIDATION_PROGRAM_ID);
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('submitting for an unregistered provider fails', async () => {
+    const unregistered = await fundedKeypair();
+
+    await expect(submit(unregistered, 'https://api.example.com/x402/ghost')).rejects.toThrow();
+    expect(await context.banksClient.getAccount(endpointIndexPda(unregistered.publicKey))).toBeNull();
+  });
+
+  test('the index grows with each new endpoint', async () => {
+    const providerAgent = await withIdentity();
+    const urls = ['https://api.example.com/x402/a', 'https://api.example.com/x402/b'];
+
+    await submit(providerAgent, urls[0]);
+    expect((await index(providerAgent)).endpointHashes).toHaveLength(1);
+
+    await submit(providerAgent, urls[1]);
+    const after = await index(providerAgent);
+    expect(after.provider.toBase58()).toBe(providerAgent.publicKey.toBase58());
+    expect(after.endpointHashes.map((h: number[]) => Array.from(h))).toEqual(urls.map(metadataHash));
+    expect(Array.from(after.latestEndpointHash)).toEqual(metadataHash(urls[1]));
+  });
+
+  test('the index is bounded', async () => {
+    const providerAgent = await withIdentity();
+
+    for (let i = 0; i < MAX_INDEXED_ENDPOINTS; i++) {
+      await submit(providerAgent, `https://api.example.com/x402/bounded-${i}`);
+    }
+    await expect(submit(providerAgent, 'https://api.example.com/x402/one-too-many')).rejects.toThrow(
+      /EndpointIndexFull/
+    );
+  });
+
+  test('stamp issuance is recorded on the index', async () => {
+    const providerAgent = await withIdentity();
+    await program.methods
+      .registerValidator(new BN(MIN_VALIDATOR_BOND))
+      .accounts({
+        validatorRecord: validatorRecordPda(providerAgent.publicKey),
+        validatorIdentity: identityPda(providerAgent.publicKey),
+        validator: providerAgent.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([providerAgent])
+      .rpc();
+
+    const url = 'https://api.example.com/x402/stamped';
+    await submit(providerAgent, url, passingResults({ responseTime: 120 }));
+    const accounts = {
+      endpointValidation: validationPda(metadataHash(url)),
+      authorityAccount: authorityPda,
+      authority: authority.publicKey,
+    };
+    await program.methods.calculateConsensus().accounts(accounts).signers([authority]).rpc();
+    await program.methods
+      .issueValidationStamp()
+      .accounts({
+        ...accounts,
+        validationConfig: null,
+        endpointIndex: endpointIndexPda(providerAgent.publicKey),
+        stampProposal: null,
+      })
+      .signers([authority])
+      .rpc();
+
+    const after = await index(providerAgent);
+    expect(after.stampsIssued).toBe(1);
+    expect(Array.from(after.latestStampedHash)).toEqual(metadataHash(url));
+  });
+});
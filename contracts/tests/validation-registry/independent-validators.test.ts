@@ -0,0 +1,172 @@
+/**
+ * Independent Validator Tests
+ * Tests append_test_result submissions from separate validator wallets
+ *
+ * Appending ensures:
+ * 1. Each validator wallet contributes its own attributed result
+ * 2. A validator cannot submit twice for the same endpoint
+ * 3. The 10-result cap still applies
+ * 4. calculate_consensus aggregates the independent submissions
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, testResult, bondedValidator, registeredWallet } from '../helpers/bankrun';
+
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const [CONFIG_PDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], VALIDATION_PROGRAM_ID);
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+
+describe('Independent Validators', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let identityProgram: Program<Idl>;
+  let authority: Keypair;
+  let authorityPda: PublicKey;
+
+  function validationPda(endpointHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('validation'), Buffer.from(endpointHash)],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function endpointIndexPda(provider: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('endpoints'), provider.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  function validatorRecordPda(validator: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('validator'), validator.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  /** A wallet with a registered agent identity */
+  function withIdentity(): Promise<Keypair> {
+    return registeredWallet(context, identityProgram);
+  }
+
+  /** A wallet with an agent identity and a bonded ValidatorRecord */
+  function registeredValidator(): Promise<Keypair> {
+    return bondedValidator(context, identityProgram, program);
+  }
+
+  /** Open an endpoint validation with no results so validators can append */
+  async function openValidation(url: string): Promise<PublicKey> {
+    const providerAgent = await withIdentity();
+    const endpointHash = metadataHash(url);
+    await program.methods
+      .submitValidation(url, endpointHash, [])
+      .accounts({
+        endpointValidation: validationPda(endpointHash),
+        providerAgent: providerAgent.publicKey,
+        providerIdentity: identityPda(providerAgent.publicKey),
+        endpointIndex: endpointIndexPda(providerAgent.publicKey),
+        payer: providerAgent.publicKey,
+        systemProgram: SystemProgram.programId,
+        validatorRecord: null,
+        validationConfig: CONFIG_PDA,
+        rewardPool: null,
+      })
+      .signers([providerAgent])
+      .rpc();
+    return validationPda(endpointHash);
+  }
+
+  function append(validation: PublicKey, validator: Keypair, score = 90) {
+    return program.methods
+      .appendTestResult(testResult({ score }))
+      .accounts({
+        endpointValidation: validation,
+        validator: validator.publicKey,
+        validatorRecord: validatorRecordPda(validator.publicKey),
+      })
+      .signers([validator])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('validation_registry', provider);
+    identityProgram = loadProgram('identity_registry', provider);
+
+    authority = await fundedKeypair();
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VALIDATION_PROGRAM_ID);
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('three validators append and consensus aggregates them', async () => {
+    const validation = await openValidation('https://api.example.com/x402/quotes');
+    const validators = [await registeredValidator(), await registeredValidator(), await registeredValidator()];
+
+    for (const [i, validator] of validators.entries()) {
+      await append(validation, validator, 80 + i * 10);
+    }
+
+    const account = await fetchAccount(program, 'endpointValidation', validation);
+    expect(account.testResults.map((r: { validator: PublicKey }) => r.validator.toBase58())).toEqual(
+      validators.map((v) => v.publicKey.toBase58())
+    );
+
+    await program.methods
+      .calculateConsensus()
+      .accounts({
+        endpointValidation: validation,
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        validationConfig: null,
+      })
+      .signers([authority])
+      .rpc();
+    // avg 90 + full success bonus 900
+    expect((await fetchAccount(program, 'endpointValidation', validation)).consensusScore).toBe(990);
+  });
+
+  test('the same validator cannot append twice', async () => {
+    const validation = await openValidation('https://api.example.com/x402/translate');
+    const validator = await registeredValidator();
+
+    await append(validation, validator);
+    await expect(append(validation, validator, 100)).rejects.toThrow(/DuplicateValidator/);
+  });
+
+  test('the eleventh result is rejected', async () => {
+    const validation = await openValidation('https://api.example.com/x402/images');
+
+    for (let i = 0; i < 10; i++) {
+      await append(validation, await registeredValidator());
+    }
+    await expect(append(validation, await registeredValidator())).rejects.toThrow(/TooManyTestResults/);
+  });
+});
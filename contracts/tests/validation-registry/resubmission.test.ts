@@ -0,0 +1,178 @@
+/**
+ * Re-validation Tests
+ * Tests resubmit_validation for continuous endpoint monitoring
+ *
+ * Re-validation ensures:
+ * 1. An already validated endpoint can be validated again with fresh results
+ * 2. A new round clears the consensus score and any issued stamp
+ * 3. Only the provider agent or the validation authority can start a round
+ * 4. validation_round counts every submission
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, passingResults, bondedValidator } from '../helpers/bankrun';
+
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const [CONFIG_PDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], VALIDATION_PROGRAM_ID);
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+
+describe('Re-validation', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let identityProgram: Program<Idl>;
+  let authority: Keypair;
+  let authorityPda: PublicKey;
+
+  function validationPda(endpointHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('validation'), Buffer.from(endpointHash)],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function endpointIndexPda(provider: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('endpoints'), provider.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  function validatorRecordPda(validator: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('validator'), validator.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  /** A wallet with an agent identity and a bonded ValidatorRecord */
+  function registeredValidator(): Promise<Keypair> {
+    return bondedValidator(context, identityProgram, program);
+  }
+
+  async function submit(providerAgent: Keypair, url: string): Promise<PublicKey> {
+    const endpointHash = metadataHash(url);
+    await program.methods
+      .submitValidation(url, endpointHash, passingResults({ responseTime: 120 }))
+      .accounts({
+        endpointValidation: validationPda(endpointHash),
+        providerAgent: providerAgent.publicKey,
+        providerIdentity: identityPda(providerAgent.publicKey),
+        endpointIndex: endpointIndexPda(providerAgent.publicKey),
+        payer: providerAgent.publicKey,
+        systemProgram: SystemProgram.programId,
+        validatorRecord: validatorRecordPda(providerAgent.publicKey),
+        validationConfig: CONFIG_PDA,
+        rewardPool: null,
+      })
+      .signers([providerAgent])
+      .rpc();
+    return validationPda(endpointHash);
+  }
+
+  function resubmit(validation: PublicKey, signer: Keypair, score = 90) {
+    // The authority is trusted and resubmits without a validator record
+    const validatorRecord = signer === authority ? null : validatorRecordPda(signer.publicKey);
+    return program.methods
+      .resubmitValidation(passingResults({ score, responseTime: 120 }))
+      .accounts({
+        endpointValidation: validation,
+        authorityAccount: authorityPda,
+        signer: signer.publicKey,
+        validatorRecord,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function stamp(validation: PublicKey, providerAgent: Keypair) {
+    const accounts = {
+      endpointValidation: validation,
+      authorityAccount: authorityPda,
+      authority: authority.publicKey,
+      validationConfig: null,
+    };
+    await program.methods.calculateConsensus().accounts(accounts).signers([authority]).rpc();
+    await program.methods
+      .issueValidationStamp()
+      .accounts({
+        ...accounts,
+        validationConfig: null,
+        endpointIndex: endpointIndexPda(providerAgent.publicKey),
+        stampProposal: null,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('validation_registry', provider);
+    identityProgram = loadProgram('identity_registry', provider);
+
+    authority = await fundedKeypair();
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VALIDATION_PROGRAM_ID);
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('a second submission replaces results and clears the stamp', async () => {
+    const providerAgent = await registeredValidator();
+    const validation = await submit(providerAgent, 'https://api.example.com/x402/weather');
+    await stamp(validation, providerAgent);
+    expect((await fetchAccount(program, 'endpointValidation', validation)).stampTier).toEqual({ gold: {} });
+
+    await resubmit(validation, providerAgent, 40);
+
+    const after = await fetchAccount(program, 'endpointValidation', validation);
+    expect(after.stampTier).toEqual({ none: {} });
+    expect(after.consensusScore).toBe(0);
+    expect(after.testResults.map((r: { score: number }) => r.score)).toEqual([40, 40, 40]);
+    expect(after.validationRound).toBe(2);
+  });
+
+  test('third parties cannot re-validate an endpoint', async () => {
+    const providerAgent = await registeredValidator();
+    const stranger = await registeredValidator();
+    const validation = await submit(providerAgent, 'https://api.example.com/x402/news');
+
+    await expect(resubmit(validation, stranger)).rejects.toThrow(/UnauthorizedResubmission/);
+    expect((await fetchAccount(program, 'endpointValidation', validation)).validationRound).toBe(1);
+  });
+
+  test('the authority can re-validate and each round increments the counter', async () => {
+    const providerAgent = await registeredValidator();
+    const validation = await submit(providerAgent, 'https://api.example.com/x402/search');
+
+    await resubmit(validation, authority);
+    await resubmit(validation, providerAgent);
+
+    expect((await fetchAccount(program, 'endpointValidation', validation)).validationRound).toBe(3);
+  });
+});
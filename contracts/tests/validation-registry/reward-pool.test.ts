@@ -0,0 +1,331 @@
+/**
+ * Validator Reward Pool Tests
+ * Tests the provider submission fee and claim_validator_reward
+ *
+ * Rewards ensure:
+ * 1. Opening an endpoint pays the configured fee into its RewardPool, whoever the payer is
+ * 2. The pool splits pro-rata among validators whose results counted in the final consensus
+ * 3. A validator can claim only once, and only for results it contributed
+ * 4. A single counted validator receives the whole pool
+ * 5. With no validators, the fee returns to the provider when the validation is closed
+ * 6. A validation can't be closed while validators have unclaimed shares
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  metadataHash,
+  testResult,
+  bondedValidator,
+  registeredWallet,
+} from '../helpers/bankrun';
+
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const SUBMISSION_FEE = 100_000_000;
+const DEFAULT_RETENTION = 180 * 24 * 60 * 60;
+
+describe('Validator Reward Pool', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let identityProgram: Program<Idl>;
+  let authority: Keypair;
+  let authorityPda: PublicKey;
+  let configPda: PublicKey;
+
+  function validationPda(endpointHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('validation'), Buffer.from(endpointHash)],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  function rewardPoolPda(endpointHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reward_pool'), Buffer.from(endpointHash)],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function endpointIndexPda(provider: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('endpoints'), provider.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  function validatorRecordPda(validator: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('validator'), validator.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  /** A wallet with a registered agent identity */
+  function withIdentity(): Promise<Keypair> {
+    return registeredWallet(context, identityProgram);
+  }
+
+  /** A wallet with an agent identity and a bonded ValidatorRecord */
+  function registeredValidator(): Promise<Keypair> {
+    return bondedValidator(context, identityProgram, program);
+  }
+
+  /** Open an endpoint with no results, paid by `payer` */
+  function submit(providerAgent: Keypair, url: string, payer = providerAgent, withPool = true) {
+    const endpointHash = metadataHash(url);
+    return program.methods
+      .submitValidation(url, endpointHash, [])
+      .accounts({
+        endpointValidation: validationPda(endpointHash),
+        providerAgent: providerAgent.publicKey,
+        providerIdentity: identityPda(providerAgent.publicKey),
+        endpointIndex: endpointIndexPda(providerAgent.publicKey),
+        payer: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+        validatorRecord: null,
+        validationConfig: configPda,
+        rewardPool: withPool ? rewardPoolPda(endpointHash) : null,
+      })
+      .signers([payer])
+      .rpc();
+  }
+
+  /** Submit a fee-paying endpoint and have each validator append one result */
+  async function validatedEndpoint(url: string, results: [Keypair, boolean][]) {
+    const providerAgent = await withIdentity();
+    await submit(providerAgent, url);
+    const endpointHash = metadataHash(url);
+    const validation = validationPda(endpointHash);
+    for (const [validator, success] of results) {
+      await program.methods
+        .appendTestResult(testResult({ success, score: success ? 95 : 0 }))
+        .accounts({
+          endpointValidation: validation,
+          validator: validator.publicKey,
+          validatorRecord: validatorRecordPda(validator.publicKey),
+        })
+        .signers([validator])
+        .rpc();
+    }
+    await program.methods
+      .calculateConsensus()
+      .accounts({
+        endpointValidation: validation,
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        validationConfig: configPda,
+      })
+      .signers([authority])
+      .rpc();
+    return { endpointHash, providerAgent };
+  }
+
+  function close(endpointHash: number[], providerAgent: Keypair) {
+    return program.methods
+      .closeValidation()
+      .accounts({
+        endpointValidation: validationPda(endpointHash),
+        endpointIndex: endpointIndexPda(providerAgent.publicKey),
+        payer: providerAgent.publicKey,
+        signer: providerAgent.publicKey,
+        validationConfig: configPda,
+        rewardPool: rewardPoolPda(endpointHash),
+      })
+      .signers([providerAgent])
+      .rpc();
+  }
+
+  function claim(endpointHash: number[], validator: Keypair) {
+    return program.methods
+      .claimValidatorReward()
+      .accounts({
+        endpointValidation: validationPda(endpointHash),
+        rewardPool: rewardPoolPda(endpointHash),
+        validator: validator.publicKey,
+      })
+      .signers([validator])
+      .rpc();
+  }
+
+  /** Claim and return the lamports the validator received */
+  async function claimedAmount(endpointHash: number[], validator: Keypair): Promise<bigint> {
+    const before = await context.banksClient.getBalance(validator.publicKey);
+    await claim(endpointHash, validator);
+    return (await context.banksClient.getBalance(validator.publicKey)) - before;
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('validation_registry', provider);
+    identityProgram = loadProgram('identity_registry', provider);
+
+    authority = await fundedKeypair();
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VALIDATION_PROGRAM_ID);
+    [configPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VALIDATION_PROGRAM_ID);
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    await program.methods
+      .initializeValidationConfig()
+      .accounts({
+        validationConfig: configPda,
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    await program.methods
+      .updateRewardConfig(new BN(SUBMISSION_FEE), true)
+      .accounts({ validationConfig: configPda, authorityAccount: authorityPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('the fee is capped', async () => {
+    await expect(
+      program.methods
+        .updateRewardConfig(new BN(2 * LAMPORTS_PER_SOL), true)
+        .accounts({ validationConfig: configPda, authorityAccount: authorityPda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc()
+    ).rejects.toThrow(/InvalidSubmissionFee/);
+  });
+
+  test('a provider submission pays the fee into the reward pool', async () => {
+    const providerAgent = await withIdentity();
+    const url = 'https://api.example.com/x402/fee';
+    const endpointHash = metadataHash(url);
+
+    await expect(submit(providerAgent, url, providerAgent, false)).rejects.toThrow(/RewardPoolRequired/);
+
+    await submit(providerAgent, url);
+    const pool = await fetchAccount(program, 'rewardPool', rewardPoolPda(endpointHash));
+    expect(pool.totalLamports.toNumber()).toBe(SUBMISSION_FEE);
+    expect(pool.distributedLamports.toNumber()).toBe(0);
+    expect(pool.countedResultsOnly).toBe(true);
+    expect(pool.settled).toBe(false);
+
+    const rent = await context.banksClient.getRent();
+    const account = (await context.banksClient.getAccount(rewardPoolPda(endpointHash)))!;
+    expect(BigInt(account.lamports)).toBe(rent.minimumBalance(BigInt(account.data.length)) + BigInt(SUBMISSION_FEE));
+  });
+
+  test('paying from a second wallet still pays the fee', async () => {
+    const providerAgent = await withIdentity();
+    const sponsor = await fundedKeypair();
+    const url = 'https://api.example.com/x402/sponsored';
+    const endpointHash = metadataHash(url);
+
+    await expect(submit(providerAgent, url, sponsor, false)).rejects.toThrow(/RewardPoolRequired/);
+
+    await submit(providerAgent, url, sponsor);
+    const pool = await fetchAccount(program, 'rewardPool', rewardPoolPda(endpointHash));
+    expect(pool.totalLamports.toNumber()).toBe(SUBMISSION_FEE);
+  });
+
+  test('the pool splits pro-rata among three validators and each claims once', async () => {
+    const validators = [await registeredValidator(), await registeredValidator(), await registeredValidator()];
+    const { endpointHash } = await validatedEndpoint(
+      'https://api.example.com/x402/three',
+      validators.map((v) => [v, true])
+    );
+
+    const amounts = [];
+    for (const validator of validators) {
+      amounts.push(await claimedAmount(endpointHash, validator));
+    }
+    // 100_000_000 / 3, with the last claim taking the rounding remainder
+    expect(amounts).toEqual([33_333_333n, 33_333_333n, 33_333_334n]);
+
+    const pool = await fetchAccount(program, 'rewardPool', rewardPoolPda(endpointHash));
+    expect(pool.settled).toBe(true);
+    expect(pool.eligibleResults).toBe(3);
+    expect(pool.distributedLamports.toNumber()).toBe(SUBMISSION_FEE);
+    expect(pool.shares.every((share: { claimed: boolean }) => share.claimed)).toBe(true);
+
+    await expect(claim(endpointHash, validators[0])).rejects.toThrow(/RewardAlreadyClaimed/);
+    const outsider = await registeredValidator();
+    await expect(claim(endpointHash, outsider)).rejects.toThrow(/NotEligibleForReward/);
+  });
+
+  test('a single counted validator receives the whole pool', async () => {
+    const counted = await registeredValidator();
+    const failed = [await registeredValidator(), await registeredValidator()];
+    const { endpointHash } = await validatedEndpoint('https://api.example.com/x402/single', [
+      [counted, true],
+      [failed[0], false],
+      [failed[1], false],
+    ]);
+
+    // Failed results don't count toward consensus, so they earn no share
+    await expect(claim(endpointHash, failed[0])).rejects.toThrow(/NotEligibleForReward/);
+    expect(await claimedAmount(endpointHash, counted)).toBe(BigInt(SUBMISSION_FEE));
+  });
+
+  test('with no validators the fee returns to the provider on close', async () => {
+    const providerAgent = await withIdentity();
+    const url = 'https://api.example.com/x402/unvalidated';
+    const endpointHash = metadataHash(url);
+    await submit(providerAgent, url);
+
+    // No consensus can be calculated without results
+    await expect(claim(endpointHash, await registeredValidator())).rejects.toThrow(/ConsensusNotFinal/);
+
+    await advanceTime(context, DEFAULT_RETENTION);
+    const validationRent = (await context.banksClient.getAccount(validationPda(endpointHash)))!.lamports;
+    const poolLamports = (await context.banksClient.getAccount(rewardPoolPda(endpointHash)))!.lamports;
+    const before = await context.banksClient.getBalance(providerAgent.publicKey);
+    await close(endpointHash, providerAgent);
+
+    expect(await context.banksClient.getAccount(rewardPoolPda(endpointHash))).toBeNull();
+    expect((await context.banksClient.getBalance(providerAgent.publicKey)) - before).toBe(
+      BigInt(validationRent + poolLamports)
+    );
+  });
+
+  test('the provider cannot close the validation while shares are unclaimed', async () => {
+    const validators = [await registeredValidator(), await registeredValidator()];
+    const { endpointHash, providerAgent } = await validatedEndpoint(
+      'https://api.example.com/x402/unclaimed',
+      validators.map((v) => [v, true])
+    );
+    await claim(endpointHash, validators[0]);
+
+    await advanceTime(context, DEFAULT_RETENTION);
+    await expect(close(endpointHash, providerAgent)).rejects.toThrow(/RewardsUnclaimed/);
+
+    expect(await claimedAmount(endpointHash, validators[1])).toBe(BigInt(SUBMISSION_FEE / 2));
+    await close(endpointHash, providerAgent);
+    expect(await context.banksClient.getAccount(rewardPoolPda(endpointHash))).toBeNull();
+  });
+});
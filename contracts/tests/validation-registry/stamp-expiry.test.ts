@@ -0,0 +1,264 @@
+/**
+ * Stamp Expiry Tests
+ * Tests validation stamp expiry, revocation and the validity window config
+ *
+ * Stamp lifetimes ensure:
+ * 1. An issued stamp records when it was issued and when it expires
+ * 2. Past expiry anyone can mark the stamp expired, but not before
+ * 3. The authority can revoke a stamp, and revocation is distinguishable from expiry
+ * 4. The validity window is configurable and applies to future stamps
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  now,
+  passingResults,
+} from '../helpers/bankrun';
+
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const MIN_VALIDATOR_BOND = 100_000_000;
+const DAY = 24 * 60 * 60;
+const DEFAULT_VALIDITY = 90 * DAY;
+
+describe('Stamp Expiry', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let identityProgram: Program<Idl>;
+  let authority: Keypair;
+  let authorityPda: PublicKey;
+  let configPda: PublicKey;
+  let validator: Keypair;
+
+  function validationPda(endpointHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('validation'), Buffer.from(endpointHash)],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function endpointIndexPda(provider: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('endpoints'), provider.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  function validatorRecordPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('validator'), wallet.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  async function registeredValidator(): Promise<Keypair> {
+    const wallet = await fundedKeypair();
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    const identity = identityPda(wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/validator.json',
+        metadataHash('https://example.com/validator.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({ agentIdentity: identity, agent: wallet.publicKey, asset, systemProgram: SystemProgram.programId })
+      .signers([wallet])
+      .rpc();
+    await program.methods
+      .registerValidator(new BN(MIN_VALIDATOR_BOND))
+      .accounts({
+        validatorRecord: validatorRecordPda(wallet.publicKey),
+        validatorIdentity: identity,
+        validator: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+    return wallet;
+  }
+
+  /** Submit passing results, compute consensus and issue a stamp */
+  async function stampedEndpoint(url: string, validationConfig: PublicKey | null = null): Promise<PublicKey> {
+    const endpointHash = metadataHash(url);
+    const validation = validationPda(endpointHash);
+    await program.methods
+      .submitValidation(url, endpointHash, passingResults({ responseTime: 120 }))
+      .accounts({
+        endpointValidation: validation,
+        providerAgent: validator.publicKey,
+        providerIdentity: identityPda(validator.publicKey),
+        endpointIndex: endpointIndexPda(validator.publicKey),
+        payer: validator.publicKey,
+        systemProgram: SystemProgram.programId,
+        validatorRecord: validatorRecordPda(validator.publicKey),
+        validationConfig: configPda,
+        rewardPool: null,
+      })
+      .signers([validator])
+      .rpc();
+
+    const accounts = {
+      endpointValidation: validation,
+      authorityAccount: authorityPda,
+      authority: authority.publicKey,
+      validationConfig: null,
+    };
+    await program.methods.calculateConsensus().accounts(accounts).signers([authority]).rpc();
+    await program.methods
+      .issueValidationStamp()
+      .accounts({
+        ...accounts,
+        validationConfig,
+        endpointIndex: endpointIndexPda(validator.publicKey),
+        stampProposal: null,
+      })
+      .signers([authority])
+      .rpc();
+    return validation;
+  }
+
+  function markExpired(validation: PublicKey) {
+    return program.methods.markStampExpired().accounts({ endpointValidation: validation }).rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('validation_registry', provider);
+    identityProgram = loadProgram('identity_registry', provider);
+
+    authority = await fundedKeypair();
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VALIDATION_PROGRAM_ID);
+    [configPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VALIDATION_PROGRAM_ID);
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    validator = await registeredValidator();
+  });
+
+  test('a stamp is valid inside its window and expires after', async () => {
+    const validation = await stampedEndpoint('https://api.example.com/x402/expiry');
+
+    const issued = await fetchAccount(program, 'endpointValidation', validation);
+    expect(issued.stampIssuedAt.toNumber()).toBe(await now(context));
+    expect(issued.stampExpiresAt.toNumber()).toBe(issued.stampIssuedAt.toNumber() + DEFAULT_VALIDITY);
+
+    await advanceTime(context, DEFAULT_VALIDITY - 60);
+    await expect(markExpired(validation)).rejects.toThrow(/StampNotExpired/);
+
+    await advanceTime(context, 60);
+    await markExpired(validation);
+
+    const expired = await fetchAccount(program, 'endpointValidation', validation);
+    expect(expired.stampTier).toEqual({ none: {} });
+    expect(expired.stampRevokedAt.toNumber()).toBe(0);
+  });
+
+  test('revocation is distinguishable from expiry', async () => {
+    const validation = await stampedEndpoint('https://api.example.com/x402/revoked');
+    const reason = metadataHash('endpoint returned fabricated data');
+
+    await program.methods
+      .revokeStamp(reason)
+      .accounts({
+        endpointValidation: validation,
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        stampProposal: null,
+      })
+      .signers([authority])
+      .rpc();
+
+    const revoked = await fetchAccount(program, 'endpointValidation', validation);
+    expect(revoked.stampTier).toEqual({ none: {} });
+    expect(revoked.stampRevokedAt.toNumber()).toBe(await now(context));
+    expect(Array.from(revoked.stampRevocationReason)).toEqual(reason);
+    // Still inside the window, so the stamp ended by revocation rather than expiry
+    expect(revoked.stampExpiresAt.toNumber()).toBeGreaterThan(await now(context));
+
+    await expect(markExpired(validation)).rejects.toThrow(/StampNotIssued/);
+  });
+
+  test('only the authority can revoke', async () => {
+    const validation = await stampedEndpoint('https://api.example.com/x402/not-yours');
+    const stranger = await fundedKeypair();
+
+    await expect(
+      program.methods
+        .revokeStamp(metadataHash('griefing'))
+        .accounts({
+          endpointValidation: validation,
+          authorityAccount: authorityPda,
+          authority: stranger.publicKey,
+          stampProposal: null,
+        })
+        .signers([stranger])
+        .rpc()
+    ).rejects.toThrow(/UnauthorizedAuthority/);
+  });
+
+  test('the configured validity applies to newly issued stamps', async () => {
+    await program.methods
+      .initializeValidationConfig()
+      .accounts({
+        validationConfig: configPda,
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    await program.methods
+      .updateStampValidity(new BN(7 * DAY))
+      .accounts({ validationConfig: configPda, authorityAccount: authorityPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+    const validation = await stampedEndpoint('https://api.example.com/x402/weekly', configPda);
+    const account = await fetchAccount(program, 'endpointValidation', validation);
+    expect(account.stampExpiresAt.toNumber() - account.stampIssuedAt.toNumber()).toBe(7 * DAY);
+
+    await expect(
+      program.methods
+        .updateStampValidity(new BN(0))
+        .accounts({ validationConfig: configPda, authorityAccount: authorityPda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc()
+    ).rejects.toThrow(/InvalidStampValidity/);
+  });
+});
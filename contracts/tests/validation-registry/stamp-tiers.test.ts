@@ -0,0 +1,256 @@
+/**
+ * Stamp Tier Tests
+ * Tests Bronze/Silver/Gold stamps derived from consensus thresholds
+ *
+ * Tiers ensure:
+ * 1. Each threshold boundary lands on the expected tier
+ * 2. Gold additionally requires every test to have passed
+ * 3. Threshold changes in ValidationConfig only affect future stamps
+ * 4. The issued tier is reported in the ValidationStampIssued event
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  simulateEvents,
+  testResult,
+} from '../helpers/bankrun';
+
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const MIN_VALIDATOR_BOND = 100_000_000;
+
+/**
+ * `passed` successful results scoring `score` plus `failed` failures.
+ * Consensus = average successful score + success rate (%) * 9.
+ */
+function results(passed: number, failed: number, score: number) {
+  return [...Array(passed).fill(true), ...Array(failed).fill(false)].map((success: boolean) =>
+    testResult({ success, score: success ? score : 0 })
+  );
+}
+
+describe('Stamp Tiers', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let identityProgram: Program<Idl>;
+  let authority: Keypair;
+  let authorityPda: PublicKey;
+  let configPda: PublicKey;
+  let validator: Keypair;
+  let endpointCount = 0;
+
+  function validationPda(endpointHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('validation'), Buffer.from(endpointHash)],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function endpointIndexPda(provider: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('endpoints'), provider.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  function validatorRecordPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('validator'), wallet.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  async function registeredValidator(): Promise<Keypair> {
+    const wallet = await fundedKeypair();
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    const identity = identityPda(wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/validator.json',
+        metadataHash('https://example.com/validator.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({ agentIdentity: identity, agent: wallet.publicKey, asset, systemProgram: SystemProgram.programId })
+      .signers([wallet])
+      .rpc();
+    await program.methods
+      .registerValidator(new BN(MIN_VALIDATOR_BOND))
+      .accounts({
+        validatorRecord: validatorRecordPda(wallet.publicKey),
+        validatorIdentity: identity,
+        validator: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+    return wallet;
+  }
+
+  /** Submit results for a fresh endpoint and compute its consensus */
+  async function scoredEndpoint(testResults: ReturnType<typeof results>): Promise<PublicKey> {
+    const url = `https://api.example.com/x402/tier-${endpointCount++}`;
+    const endpointHash = metadataHash(url);
+    const validation = validationPda(endpointHash);
+    await program.methods
+      .submitValidation(url, endpointHash, testResults)
+      .accounts({
+        endpointValidation: validation,
+        providerAgent: validator.publicKey,
+        providerIdentity: identityPda(validator.publicKey),
+        endpointIndex: endpointIndexPda(validator.publicKey),
+        payer: validator.publicKey,
+        systemProgram: SystemProgram.programId,
+        validatorRecord: validatorRecordPda(validator.publicKey),
+        validationConfig: configPda,
+        rewardPool: null,
+      })
+      .signers([validator])
+      .rpc();
+    await program.methods
+      .calculateConsensus()
+      .accounts({
+        endpointValidation: validation,
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        validationConfig: null,
+      })
+      .signers([authority])
+      .rpc();
+    return validation;
+  }
+
+  function issueStamp(validation: PublicKey) {
+    return program.methods.issueValidationStamp().accounts({
+      endpointValidation: validation,
+      authorityAccount: authorityPda,
+      authority: authority.publicKey,
+      validationConfig: configPda,
+      endpointIndex: endpointIndexPda(validator.publicKey),
+      stampProposal: null,
+    });
+  }
+
+  async function stampTier(testResults: ReturnType<typeof results>) {
+    const validation = await scoredEndpoint(testResults);
+    await issueStamp(validation).signers([authority]).rpc();
+    return (await fetchAccount(program, 'endpointValidation', validation)).stampTier;
+  }
+
+  function updateThresholds(bronze: number, silver: number, gold: number) {
+    return program.methods
+      .updateStampThresholds(bronze, silver, gold)
+      .accounts({ validationConfig: configPda, authorityAccount: authorityPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('validation_registry', provider);
+    identityProgram = loadProgram('identity_registry', provider);
+
+    authority = await fundedKeypair();
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VALIDATION_PROGRAM_ID);
+    [configPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VALIDATION_PROGRAM_ID);
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    await program.methods
+      .initializeValidationConfig()
+      .accounts({
+        validationConfig: configPda,
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    validator = await registeredValidator();
+  });
+
+  test('each default threshold boundary lands on its tier', async () => {
+    // 3/4 passed: 75% * 9 = 675, plus the average score
+    const belowBronze = await scoredEndpoint(results(3, 1, 24)); // 699
+    await expect(issueStamp(belowBronze).signers([authority]).rpc()).rejects.toThrow(/InvalidConsensusScore/);
+    expect(await stampTier(results(3, 1, 25))).toEqual({ bronze: {} }); // 700
+
+    // 9/10 passed: 810 plus the average score
+    expect(await stampTier(results(9, 1, 39))).toEqual({ bronze: {} }); // 849
+    expect(await stampTier(results(9, 1, 40))).toEqual({ silver: {} }); // 850
+
+    // All passed: 900 plus the average score
+    expect(await stampTier(results(3, 0, 49))).toEqual({ silver: {} }); // 949
+    expect(await stampTier(results(3, 0, 50))).toEqual({ gold: {} }); // 950
+  });
+
+  test('gold requires every test to pass', async () => {
+    await updateThresholds(700, 850, 900);
+
+    // 910 clears the gold threshold but one test failed
+    expect(await stampTier(results(9, 1, 100))).toEqual({ silver: {} });
+    expect(await stampTier(results(3, 0, 10))).toEqual({ gold: {} }); // 910
+
+    await updateThresholds(700, 850, 950);
+  });
+
+  test('threshold changes only affect future stamps', async () => {
+    const earlier = await scoredEndpoint(results(3, 1, 60)); // 735
+    await issueStamp(earlier).signers([authority]).rpc();
+
+    await updateThresholds(800, 850, 950);
+
+    expect((await fetchAccount(program, 'endpointValidation', earlier)).stampTier).toEqual({ bronze: {} });
+    const later = await scoredEndpoint(results(3, 1, 60));
+    await expect(issueStamp(later).signers([authority]).rpc()).rejects.toThrow(/InvalidConsensusScore/);
+
+    await expect(updateThresholds(900, 850, 950)).rejects.toThrow(/InvalidStampThresholds/);
+    await updateThresholds(700, 850, 950);
+  });
+
+  test('the tier is emitted in ValidationStampIssued', async () => {
+    const validation = await scoredEndpoint(results(9, 1, 50)); // 860
+
+    const events = await simulateEvents(context, program, await issueStamp(validation).instruction(), [authority]);
+
+    const issued = events.find((e) => e.name === 'validationStampIssued');
+    expect(issued).toBeDefined();
+    expect(issued!.data.tier).toEqual({ silver: {} });
+    expect(issued!.data.consensusScore).toBe(860);
+    expect((issued!.data.providerAgent as PublicKey).toBase58()).toBe(validator.publicKey.toBase58());
+  });
+});
@@ -0,0 +1,231 @@
+/**
+ * Test Result Metrics Tests
+ * Tests the structured latency, cost and error metrics on TestResult
+ *
+ * Metrics ensure:
+ * 1. p50/p95 latency, cost, HTTP status class and error code hash round-trip through submission
+ * 2. Out-of-range status classes and over-long model names are rejected
+ * 3. With a configured threshold, results whose p95 exceeds it are penalized in consensus
+ * 4. Results without metrics (and consensus without the config) aggregate as before
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset, testResult, type TestResultOverrides } from '../helpers/bankrun';
+
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const MIN_VALIDATOR_BOND = 100_000_000;
+const P95_THRESHOLD_MS = 500;
+
+/** No speed bonus at or above 1s */
+const SLOW_MS = 2_000;
+
+const result = (score: number, metrics: TestResultOverrides = {}, llmModel = 'gpt-4') =>
+  testResult({ score, responseTime: SLOW_MS, llmModel, ...metrics });
+
+describe('Test Result Metrics', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let identityProgram: Program<Idl>;
+  let authority: Keypair;
+  let authorityPda: PublicKey;
+  let configPda: PublicKey;
+  let validator: Keypair;
+  let endpointCount = 0;
+
+  function validationPda(endpointHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('validation'), Buffer.from(endpointHash)],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function endpointIndexPda(provider: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('endpoints'), provider.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  function validatorRecordPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('validator'), wallet.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  async function registeredValidator(): Promise<Keypair> {
+    const wallet = await fundedKeypair();
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    const identity = identityPda(wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/validator.json',
+        metadataHash('https://example.com/validator.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({ agentIdentity: identity, agent: wallet.publicKey, asset, systemProgram: SystemProgram.programId })
+      .signers([wallet])
+      .rpc();
+    await program.methods
+      .registerValidator(new BN(MIN_VALIDATOR_BOND))
+      .accounts({
+        validatorRecord: validatorRecordPda(wallet.publicKey),
+        validatorIdentity: identity,
+        validator: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+    return wallet;
+  }
+
+  function submit(testResults: ReturnType<typeof result>[]) {
+    const url = `https://api.example.com/x402/metrics-${endpointCount++}`;
+    const endpointHash = metadataHash(url);
+    const validation = validationPda(endpointHash);
+    const tx = program.methods
+      .submitValidation(url, endpointHash, testResults)
+      .accounts({
+        endpointValidation: validation,
+        providerAgent: validator.publicKey,
+        providerIdentity: identityPda(validator.publicKey),
+        endpointIndex: endpointIndexPda(validator.publicKey),
+        payer: validator.publicKey,
+        systemProgram: SystemProgram.programId,
+        validatorRecord: validatorRecordPda(validator.publicKey),
+        validationConfig: configPda,
+        rewardPool: null,
+      })
+      .signers([validator])
+      .rpc();
+    return { validation, tx };
+  }
+
+  /** Submit results for a fresh endpoint and return the computed consensus */
+  async function consensusOf(testResults: ReturnType<typeof result>[], withConfig = true): Promise<number> {
+    const { validation, tx } = submit(testResults);
+    await tx;
+    await program.methods
+      .calculateConsensus()
+      .accounts({
+        endpointValidation: validation,
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        validationConfig: withConfig ? configPda : null,
+      })
+      .signers([authority])
+      .rpc();
+    return (await fetchAccount(program, 'endpointValidation', validation)).consensusScore;
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('validation_registry', provider);
+    identityProgram = loadProgram('identity_registry', provider);
+
+    authority = await fundedKeypair();
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VALIDATION_PROGRAM_ID);
+    [configPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VALIDATION_PROGRAM_ID);
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    await program.methods
+      .initializeValidationConfig()
+      .accounts({
+        validationConfig: configPda,
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    await program.methods
+      .updateLatencyThreshold(P95_THRESHOLD_MS)
+      .accounts({ validationConfig: configPda, authorityAccount: authorityPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+    validator = await registeredValidator();
+  });
+
+  test('metrics round-trip through submission', async () => {
+    const errorCodeHash = metadataHash('rate_limit_exceeded');
+    const metrics = {
+      p50Ms: 180,
+      p95Ms: 420,
+      costMicroUsd: new BN(1_250),
+      httpStatusClass: 4,
+      errorCodeHash,
+    };
+    const { validation, tx } = submit([result(70, metrics), result(90)]);
+    await tx;
+
+    const [reported, unreported] = (await fetchAccount(program, 'endpointValidation', validation)).testResults;
+    expect(reported.p50Ms).toBe(180);
+    expect(reported.p95Ms).toBe(420);
+    expect(reported.costMicroUsd.toString()).toBe('1250');
+    expect(reported.httpStatusClass).toBe(4);
+    expect(Array.from(reported.errorCodeHash)).toEqual(errorCodeHash);
+
+    // Unreported metrics keep their sentinels
+    expect(unreported.p95Ms).toBe(0);
+    expect(unreported.costMicroUsd.toString()).toBe('18446744073709551615');
+    expect(unreported.httpStatusClass).toBe(0);
+  });
+
+  test('out-of-range status classes and long model names are rejected', async () => {
+    await expect(submit([result(90, { httpStatusClass: 6 })]).tx).rejects.toThrow(/InvalidHttpStatusClass/);
+    await expect(submit([result(90, {}, 'm'.repeat(33))]).tx).rejects.toThrow(/LlmModelNameTooLong/);
+    // 32 bytes is the limit
+    await expect(submit([result(90, {}, 'm'.repeat(32))]).tx).resolves.toBeDefined();
+  });
+
+  test('p95 above the configured threshold scales the score down', async () => {
+    // Twice the threshold halves each score: 80 -> 40, plus 900 for a 100% success rate
+    const slow = [result(80, { p95Ms: 1_000 }), result(80, { p95Ms: 1_000 }), result(80, { p95Ms: 1_000 })];
+    expect(await consensusOf(slow)).toBe(940);
+
+    // At or under the threshold there is no penalty
+    const fast = [result(80, { p95Ms: 500 }), result(80, { p95Ms: 200 }), result(80, { p95Ms: 500 })];
+    expect(await consensusOf(fast)).toBe(980);
+  });
+
+  test('results without metrics and consensus without the config aggregate as before', async () => {
+    expect(await consensusOf([result(80), result(80), result(80)])).toBe(980);
+
+    // Omitting the config disables the penalty
+    const slow = [result(80, { p95Ms: 1_000 }), result(80, { p95Ms: 1_000 }), result(80, { p95Ms: 1_000 })];
+    expect(await consensusOf(slow, false)).toBe(980);
+  });
+});
@@ -0,0 +1,127 @@
+/**
+ * Endpoint URL Canonicalization Tests
+ * Tests that submit_validation verifies endpoint_hash against the canonical URL
+ *
+ * Canonicalization ensures:
+ * 1. endpoint_hash must be the sha256 of the canonical URL
+ * 2. Scheme and host case, the default port and a trailing slash don't fork the PDA
+ * 3. Only https endpoints are accepted
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset } from '../helpers/bankrun';
+
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const [CONFIG_PDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], VALIDATION_PROGRAM_ID);
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+
+describe('Endpoint URL Canonicalization', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let identityProgram: Program<Idl>;
+  let providerAgent: Keypair;
+
+  function validationPda(endpointHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('validation'), Buffer.from(endpointHash)],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function endpointIndexPda(provider: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('endpoints'), provider.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  function submit(url: string, endpointHash: number[]) {
+    return program.methods
+      .submitValidation(url, endpointHash, [])
+      .accounts({
+        endpointValidation: validationPda(endpointHash),
+        providerAgent: providerAgent.publicKey,
+        providerIdentity: identityPda(providerAgent.publicKey),
+        endpointIndex: endpointIndexPda(providerAgent.publicKey),
+        payer: providerAgent.publicKey,
+        systemProgram: SystemProgram.programId,
+        validatorRecord: null,
+        validationConfig: CONFIG_PDA,
+        rewardPool: null,
+      })
+      .signers([providerAgent])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('validation_registry', provider);
+    identityProgram = loadProgram('identity_registry', provider);
+
+    providerAgent = Keypair.generate();
+    await airdrop(context, providerAgent.publicKey, 10 * LAMPORTS_PER_SOL);
+    const asset = mockCoreAsset(context, providerAgent.publicKey);
+    await identityProgram.methods
+      .registerAgent(asset, 'https://example.com/provider.json', metadataHash('provider'), 0, null, null, false)
+      .accounts({
+        agentIdentity: identityPda(providerAgent.publicKey),
+        agent: providerAgent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([providerAgent])
+      .rpc();
+  });
+
+  test('a hash of the canonical URL is accepted', async () => {
+    const url = 'https://api.example.com/x402/canonical';
+    await submit(url, metadataHash(url));
+
+    const account = await fetchAccount(program, 'endpointValidation', validationPda(metadataHash(url)));
+    expect(account.endpointUrl).toBe(url);
+  });
+
+  test('a hash that does not match the URL is rejected', async () => {
+    await expect(
+      submit('https://api.example.com/x402/real', metadataHash('https://api.example.com/x402/claimed'))
+    ).rejects.toThrow(/HashMismatch/);
+
+    // Hashing the raw, non-canonical spelling doesn't match either
+    const raw = 'https://API.example.com/x402/raw/';
+    await expect(submit(raw, metadataHash(raw))).rejects.toThrow(/HashMismatch/);
+  });
+
+  test('case, default port and trailing slash variants share one PDA', async () => {
+    const canonical = 'https://example.com';
+    const endpointHash = metadataHash(canonical);
+
+    await submit('HTTPS://Example.com/', endpointHash);
+    const account = await fetchAccount(program, 'endpointValidation', validationPda(endpointHash));
+    expect(account.endpointUrl).toBe(canonical);
+
+    // Other spellings of the same endpoint resolve to the existing account
+    await expect(submit('https://example.com', endpointHash)).rejects.toThrow(/already in use/);
+    await expect(submit('https://EXAMPLE.com:443', endpointHash)).rejects.toThrow(/already in use/);
+  });
+
+  test('non-https endpoints are rejected', async () => {
+    const url = 'http://api.example.com/x402/plain';
+    await expect(submit(url, metadataHash(url))).rejects.toThrow(/InvalidUrlScheme/);
+  });
+});
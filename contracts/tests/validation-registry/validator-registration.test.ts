@@ -0,0 +1,199 @@
+/**
+ * Validator Registration Tests
+ * Tests ValidatorRecord registration, bonding and slashing
+ *
+ * Validator records ensure:
+ * 1. Only wallets with an active agent identity can register as validators
+ * 2. Registration bonds at least MIN_VALIDATOR_BOND in the record
+ * 3. Test results can only be submitted by registered validators
+ * 4. Slashing seizes the bond and blocks further submissions
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset, testResult } from '../helpers/bankrun';
+
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const [CONFIG_PDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], VALIDATION_PROGRAM_ID);
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const MIN_VALIDATOR_BOND = 100_000_000;
+
+describe('Validator Registration', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let identityProgram: Program<Idl>;
+  let authority: Keypair;
+  let authorityPda: PublicKey;
+
+  function identityPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), agent.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function endpointIndexPda(provider: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('endpoints'), provider.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  function validatorRecordPda(validator: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('validator'), validator.toBuffer()], VALIDATION_PROGRAM_ID)[0];
+  }
+
+  function validationPda(endpointHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('validation'), Buffer.from(endpointHash)],
+      VALIDATION_PROGRAM_ID
+    )[0];
+  }
+
+  async function fundedKeypair(): Promise<Keypair> {
+    const kp = Keypair.generate();
+    await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    return kp;
+  }
+
+  async function withIdentity(): Promise<Keypair> {
+    const agent = await fundedKeypair();
+    const asset = mockCoreAsset(context, agent.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/validator.json',
+        metadataHash('https://example.com/validator.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: agent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    return agent;
+  }
+
+  function register(validator: Keypair, bond = MIN_VALIDATOR_BOND) {
+    return program.methods
+      .registerValidator(new BN(bond))
+      .accounts({
+        validatorRecord: validatorRecordPda(validator.publicKey),
+        validatorIdentity: identityPda(validator.publicKey),
+        validator: validator.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([validator])
+      .rpc();
+  }
+
+  function submit(submitter: Keypair, url: string, withRecord = true) {
+    const endpointHash = metadataHash(url);
+    return program.methods
+      .submitValidation(url, endpointHash, [90, 95, 85].map((score) => testResult({ score, responseTime: 150 })))
+      .accounts({
+        endpointValidation: validationPda(endpointHash),
+        providerAgent: submitter.publicKey,
+        providerIdentity: identityPda(submitter.publicKey),
+        endpointIndex: endpointIndexPda(submitter.publicKey),
+        payer: submitter.publicKey,
+        systemProgram: SystemProgram.programId,
+        validatorRecord: withRecord ? validatorRecordPda(submitter.publicKey) : null,
+        validationConfig: CONFIG_PDA,
+        rewardPool: null,
+      })
+      .signers([submitter])
+      .rpc();
+  }
+
+  function slash(validator: PublicKey) {
+    return program.methods
+      .slashValidator(metadataHash('fabricated latency numbers'))
+      .accounts({
+        validatorRecord: validatorRecordPda(validator),
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('validation_registry', provider);
+    identityProgram = loadProgram('identity_registry', provider);
+
+    authority = await fundedKeypair();
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VALIDATION_PROGRAM_ID);
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('unregistered submitters are rejected', async () => {
+    const submitter = await withIdentity();
+
+    await expect(submit(submitter, 'https://api.example.com/x402/unregistered', false)).rejects.toThrow(
+      /ValidatorNotRegistered/
+    );
+    // Registration itself needs an agent identity
+    await expect(register(await fundedKeypair())).rejects.toThrow();
+  });
+
+  test('a bonded validator can submit results', async () => {
+    const validator = await withIdentity();
+    await expect(register(validator, MIN_VALIDATOR_BOND - 1)).rejects.toThrow(/InsufficientValidatorBond/);
+
+    await register(validator, 2 * MIN_VALIDATOR_BOND);
+    const record = await fetchAccount(program, 'validatorRecord', validatorRecordPda(validator.publicKey));
+    expect(record.bondLamports.toNumber()).toBe(2 * MIN_VALIDATOR_BOND);
+    expect(record.isSlashed).toBe(false);
+
+    await submit(validator, 'https://api.example.com/x402/bonded');
+    const validation = await fetchAccount(
+      program,
+      'endpointValidation',
+      validationPda(metadataHash('https://api.example.com/x402/bonded'))
+    );
+    expect(validation.testResults[0].validator.toBase58()).toBe(validator.publicKey.toBase58());
+  });
+
+  test('a slashed validator loses its bond and cannot submit', async () => {
+    const validator = await withIdentity();
+    await register(validator);
+    const authorityBefore = (await context.banksClient.getAccount(authority.publicKey))!.lamports;
+
+    await slash(validator.publicKey);
+
+    const authorityAfter = (await context.banksClient.getAccount(authority.publicKey))!.lamports;
+    // The slash fee is paid by the authority itself
+    expect(authorityAfter).toBeGreaterThan(authorityBefore + MIN_VALIDATOR_BOND - 10_000);
+    const record = await fetchAccount(program, 'validatorRecord', validatorRecordPda(validator.publicKey));
+    expect(record.isSlashed).toBe(true);
+    expect(record.bondLamports.toNumber()).toBe(0);
+
+    await expect(submit(validator, 'https://api.example.com/x402/slashed')).rejects.toThrow(/ValidatorSlashed/);
+    await expect(slash(validator.publicKey)).rejects.toThrow(/ValidatorSlashed/);
+  });
+});
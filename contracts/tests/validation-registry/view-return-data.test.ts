@@ -0,0 +1,166 @@
+/**
+ * Validation View Return Data Tests
+ * Tests that query_validations returns structured data via set_return_data
+ *
+ * The view ensures:
+ * 1. query_validations returns a borsh-encoded ValidationView
+ * 2. min_score makes it fail with BelowThreshold, so it can guard CPI callers
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import {
+  airdrop,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  simulateReturnData,
+  passingResults,
+} from '../helpers/bankrun';
+
+const VALIDATION_PROGRAM_ID = new PublicKey('9wwukuFjurWGDXREvnyBLPyePP4wssP5HCuRd1FJsaKc');
+const [CONFIG_PDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], VALIDATION_PROGRAM_ID);
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const MIN_VALIDATOR_BOND = 100_000_000;
+const ENDPOINT_URL = 'https://api.example.com/x402/view';
+
+describe('Validation View Return Data', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Idl>;
+  let identityProgram: Program<Idl>;
+  let authority: Keypair;
+  let providerAgent: Keypair;
+  let validationPda: PublicKey;
+
+  function queryIx(minScore: number) {
+    return program.methods.queryValidations(minScore).accounts({ endpointValidation: validationPda }).instruction();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'validation_registry', programId: VALIDATION_PROGRAM_ID },
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    program = loadProgram('validation_registry', provider);
+    identityProgram = loadProgram('identity_registry', provider);
+
+    authority = Keypair.generate();
+    providerAgent = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(context, providerAgent.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    const [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VALIDATION_PROGRAM_ID);
+    const [identityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('agent'), providerAgent.publicKey.toBuffer()],
+      IDENTITY_PROGRAM_ID
+    );
+    const [validatorRecordPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('validator'), providerAgent.publicKey.toBuffer()],
+      VALIDATION_PROGRAM_ID
+    );
+    const [endpointIndexPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('endpoints'), providerAgent.publicKey.toBuffer()],
+      VALIDATION_PROGRAM_ID
+    );
+    const endpointHash = metadataHash(ENDPOINT_URL);
+    [validationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('validation'), Buffer.from(endpointHash)],
+      VALIDATION_PROGRAM_ID
+    );
+
+    await program.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    const asset = mockCoreAsset(context, providerAgent.publicKey);
+    await identityProgram.methods
+      .registerAgent(asset, 'https://example.com/provider.json', metadataHash('provider'), 0, null, null, false)
+      .accounts({
+        agentIdentity: identityPda,
+        agent: providerAgent.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([providerAgent])
+      .rpc();
+    await program.methods
+      .registerValidator(new BN(MIN_VALIDATOR_BOND))
+      .accounts({
+        validatorRecord: validatorRecordPda,
+        validatorIdentity: identityPda,
+        validator: providerAgent.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([providerAgent])
+      .rpc();
+
+    // Three 95-point passes with no speed bonus: consensus 95 + 900 = 995 (Gold)
+    await program.methods
+      .submitValidation(ENDPOINT_URL, endpointHash, passingResults())
+      .accounts({
+        endpointValidation: validationPda,
+        providerAgent: providerAgent.publicKey,
+        providerIdentity: identityPda,
+        endpointIndex: endpointIndexPda,
+        payer: providerAgent.publicKey,
+        systemProgram: SystemProgram.programId,
+        validatorRecord: validatorRecordPda,
+        validationConfig: CONFIG_PDA,
+        rewardPool: null,
+      })
+      .signers([providerAgent])
+      .rpc();
+
+    const accounts = {
+      endpointValidation: validationPda,
+      authorityAccount: authorityPda,
+      authority: authority.publicKey,
+    };
+    await program.methods.calculateConsensus().accounts(accounts).signers([authority]).rpc();
+    await program.methods
+      .issueValidationStamp()
+      .accounts({
+        ...accounts,
+        validationConfig: null,
+        endpointIndex: endpointIndexPda,
+        stampProposal: null,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('query_validations returns a ValidationView', async () => {
+    const view = program.coder.types.decode('ValidationView', await simulateReturnData(context, await queryIx(0)));
+
+    expect(view.endpointUrl).toBe(ENDPOINT_URL);
+    expect(view.providerAgent.toBase58()).toBe(providerAgent.publicKey.toBase58());
+    expect(view.consensusScore).toBe(995);
+    expect(view.stampIssued).toBe(true);
+    expect(view.stampTier).toEqual({ gold: {} });
+    expect(view.testCount).toBe(3);
+    expect(view.timestamp.toNumber()).toBeGreaterThan(0);
+  });
+
+  test('min_score guards on the stored consensus', async () => {
+    await expect(simulateReturnData(context, await queryIx(800))).resolves.toBeDefined();
+    await expect(simulateReturnData(context, await queryIx(995))).resolves.toBeDefined();
+    await expect(simulateReturnData(context, await queryIx(996))).rejects.toThrow(/BelowThreshold/);
+  });
+});
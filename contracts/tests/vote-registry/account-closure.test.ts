@@ -0,0 +1,241 @@
+/**
+ * Account Closure Tests
+ * Tests reclaiming rent from consumed TransactionReceipt and PeerVote accounts
+ *
+ * Closure ensures:
+ * 1. Receipts close only after the voting window, only by their creator, with rent back to the creator
+ * 2. Peer votes close only after the retention period, only by their voter, with rent back to the voter
+ * 3. The voted agent's tally is unchanged by closure and records how many of its votes were closed
+ * 4. A closed vote cannot be cast again on the same receipt
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, transactionReceipt, reputableAgent } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+const DAY = 24 * 60 * 60;
+const QUALITY = { responseQuality: 80, responseSpeed: 80, accuracy: 80, professionalism: 80 };
+
+describe('Account Closure', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let reputationAuthorityPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+  let voteConfigPda: PublicKey;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  /** A registered agent whose reputation score (500) allows it to vote */
+  function votingAgent(): Promise<Keypair> {
+    return reputableAgent(context, identityProgram, reputationProgram, authority);
+  }
+
+  function vote(receipt: PublicKey, voter: Keypair, votedAgent: Keypair) {
+    return voteProgram.methods
+      .castPeerVote(votedAgent.publicKey, { upvote: {} }, QUALITY, new Array(32).fill(0))
+      .accounts({
+        peerVote: PublicKey.findProgramAddressSync(
+          [Buffer.from('peer_vote'), receipt.toBuffer(), voter.publicKey.toBuffer()],
+          VOTE_PROGRAM_ID
+        )[0],
+        transactionReceipt: receipt,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(votedAgent.publicKey),
+        votedAgentReputation: reputationPda(votedAgent.publicKey),
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        voteConfig: voteConfigPda,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  function peerVotePda(receiptAddress: PublicKey, voter: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('peer_vote'), receiptAddress.toBuffer(), voter.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function voteTallyPda(agent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('vote_tally'), agent.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function closeReceipt(receiptAddress: PublicKey, creator: Keypair) {
+    return voteProgram.methods
+      .closeReceipt()
+      .accounts({ receipt: receiptAddress, creator: creator.publicKey })
+      .signers([creator])
+      .rpc();
+  }
+
+  function closePeerVote(receiptAddress: PublicKey, voter: Keypair, votedAgent: Keypair) {
+    return voteProgram.methods
+      .closePeerVote()
+      .accounts({
+        peerVote: peerVotePda(receiptAddress, voter),
+        voteTally: voteTallyPda(votedAgent),
+        voter: voter.publicKey,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: reputationAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeVoteConfig()
+      .accounts({
+        voteConfig: voteConfigPda,
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('closing before the voting window or retention period fails', async () => {
+    const [voter, agent] = [await votingAgent(), await votingAgent()];
+    const receiptAddress = await transactionReceipt(voteProgram, voter, agent);
+    await vote(receiptAddress, voter, agent);
+
+    await expect(closeReceipt(receiptAddress, voter)).rejects.toThrow(/ReceiptStillActive/);
+    await expect(closePeerVote(receiptAddress, voter, agent)).rejects.toThrow(/VoteRetentionActive/);
+
+    // Not even one second early
+    await advanceTime(context, 30 * DAY - 1);
+    await expect(closePeerVote(receiptAddress, voter, agent)).rejects.toThrow(/VoteRetentionActive/);
+    await expect(closeReceipt(receiptAddress, voter)).rejects.toThrow(/ReceiptStillActive/);
+  });
+
+  test('only the creator and the voter can close', async () => {
+    const [voter, agent] = [await votingAgent(), await votingAgent()];
+    const receiptAddress = await transactionReceipt(voteProgram, voter, agent);
+    await vote(receiptAddress, voter, agent);
+    await advanceTime(context, 30 * DAY + 1);
+
+    // The agent is a party to the receipt but did not create it or cast this vote
+    await expect(closeReceipt(receiptAddress, agent)).rejects.toThrow(/NotReceiptCreator/);
+    await expect(
+      voteProgram.methods
+        .closePeerVote()
+        .accounts({
+          peerVote: peerVotePda(receiptAddress, voter),
+          voteTally: voteTallyPda(agent),
+          voter: agent.publicKey,
+        })
+        .signers([agent])
+        .rpc()
+    ).rejects.toThrow(/NotOriginalVoter/);
+  });
+
+  test('after the window both accounts close and refund their rent', async () => {
+    const [voter, agent] = [await votingAgent(), await votingAgent()];
+    const receiptAddress = await transactionReceipt(voteProgram, voter, agent);
+    await vote(receiptAddress, voter, agent);
+    await advanceTime(context, 30 * DAY + 1);
+
+    const receiptRent = (await context.banksClient.getAccount(receiptAddress))!.lamports;
+    const voteRent = (await context.banksClient.getAccount(peerVotePda(receiptAddress, voter)))!.lamports;
+    const before = await context.banksClient.getBalance(voter.publicKey);
+
+    // The voter created the receipt and cast the vote, so both refunds go to it
+    await closePeerVote(receiptAddress, voter, agent);
+    await closeReceipt(receiptAddress, voter);
+
+    expect(await context.banksClient.getAccount(receiptAddress)).toBeNull();
+    expect(await context.banksClient.getAccount(peerVotePda(receiptAddress, voter))).toBeNull();
+    const after = await context.banksClient.getBalance(voter.publicKey);
+    expect(after - before).toBe(BigInt(receiptRent + voteRent));
+  });
+
+  test('the tally is unchanged by closure and the vote cannot be recast', async () => {
+    const [voter, agent] = [await votingAgent(), await votingAgent()];
+    const receiptAddress = await transactionReceipt(voteProgram, voter, agent);
+    await vote(receiptAddress, voter, agent);
+    const before = await fetchAccount(voteProgram, 'voteTally', voteTallyPda(agent));
+
+    await advanceTime(context, 30 * DAY + 1);
+    await closePeerVote(receiptAddress, voter, agent);
+
+    const after = await fetchAccount(voteProgram, 'voteTally', voteTallyPda(agent));
+    expect(after.upvotes).toBe(before.upvotes);
+    expect(after.downvotes).toBe(before.downvotes);
+    expect(after.neutrals).toBe(before.neutrals);
+    expect(after.weightSum.toString()).toBe(before.weightSum.toString());
+    expect(after.qualitySums.responseQuality.toString()).toBe(before.qualitySums.responseQuality.toString());
+    expect(after.closedVotes).toBe(before.closedVotes + 1);
+
+    // The receipt still records the vote, so the closed vote cannot be cast again
+    await expect(vote(receiptAddress, voter, agent)).rejects.toThrow(/VoteAlreadyCast/);
+  });
+});
@@ -0,0 +1,158 @@
+/**
+ * Bidirectional Vote Tests
+ * Tests that the payer and the recipient of a receipt can each cast one vote
+ *
+ * Per-party voting ensures:
+ * 1. The payer and the recipient each get their own PeerVote on the same receipt
+ * 2. Each party's flag on the receipt is set independently
+ * 3. Neither party can vote twice with the same receipt
+ * 4. A wallet that is not a party to the receipt cannot vote with it
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, transactionReceipt, reputableAgent } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+const QUALITY = { responseQuality: 80, responseSpeed: 80, accuracy: 80, professionalism: 80 };
+
+describe('Bidirectional Votes', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let authorityPda: PublicKey;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function peerVotePda(receiptPda: PublicKey, voter: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('peer_vote'), receiptPda.toBuffer(), voter.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function voteTallyPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vote_tally'), agent.toBuffer()], VOTE_PROGRAM_ID)[0];
+  }
+
+  /** A registered agent whose reputation score (500) allows it to vote */
+  function votingAgent(): Promise<Keypair> {
+    return reputableAgent(context, identityProgram, reputationProgram, authority);
+  }
+
+  function vote(receiptPda: PublicKey, voter: Keypair, votedAgent: Keypair) {
+    return voteProgram.methods
+      .castPeerVote(votedAgent.publicKey, { upvote: {} }, QUALITY, new Array(32).fill(0))
+      .accounts({
+        peerVote: peerVotePda(receiptPda, voter.publicKey),
+        voteTally: voteTallyPda(votedAgent.publicKey),
+        transactionReceipt: receiptPda,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(votedAgent.publicKey),
+        votedAgentReputation: reputationPda(votedAgent.publicKey),
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('payer and recipient each vote once on the same receipt', async () => {
+    const [payer, recipient] = [await votingAgent(), await votingAgent()];
+    const receiptPda = await transactionReceipt(voteProgram, payer, recipient);
+
+    await vote(receiptPda, payer, recipient);
+    let account = await fetchAccount(voteProgram, 'transactionReceipt', receiptPda);
+    expect(account.payerVoted).toBe(true);
+    expect(account.recipientVoted).toBe(false);
+
+    await vote(receiptPda, recipient, payer);
+    account = await fetchAccount(voteProgram, 'transactionReceipt', receiptPda);
+    expect(account.payerVoted).toBe(true);
+    expect(account.recipientVoted).toBe(true);
+
+    const payerVote = await fetchAccount(voteProgram, 'peerVote', peerVotePda(receiptPda, payer.publicKey));
+    expect(payerVote.voter.toBase58()).toBe(payer.publicKey.toBase58());
+    expect(payerVote.votedAgent.toBase58()).toBe(recipient.publicKey.toBase58());
+
+    const recipientVote = await fetchAccount(voteProgram, 'peerVote', peerVotePda(receiptPda, recipient.publicKey));
+    expect(recipientVote.voter.toBase58()).toBe(recipient.publicKey.toBase58());
+    expect(recipientVote.votedAgent.toBase58()).toBe(payer.publicKey.toBase58());
+  });
+
+  test('neither party can vote twice with the same receipt', async () => {
+    const [payer, recipient] = [await votingAgent(), await votingAgent()];
+    const receiptPda = await transactionReceipt(voteProgram, payer, recipient);
+
+    await vote(receiptPda, payer, recipient);
+    await vote(receiptPda, recipient, payer);
+
+    // The PeerVote PDA already exists, so init may fail before the receipt flag check
+    await expect(vote(receiptPda, payer, recipient)).rejects.toThrow(/VoteAlreadyCast|already in use/);
+    await expect(vote(receiptPda, recipient, payer)).rejects.toThrow(/VoteAlreadyCast|already in use/);
+  });
+
+  test('a third party cannot vote with the receipt', async () => {
+    const [payer, recipient, outsider] = [await votingAgent(), await votingAgent(), await votingAgent()];
+    const receiptPda = await transactionReceipt(voteProgram, payer, recipient);
+
+    await expect(vote(receiptPda, outsider, recipient)).rejects.toThrow(/VoterNotPartyToTransaction/);
+
+    const account = await fetchAccount(voteProgram, 'transactionReceipt', receiptPda);
+    expect(account.payerVoted).toBe(false);
+    expect(account.recipientVoted).toBe(false);
+  });
+});
@@ -0,0 +1,217 @@
+/**
+ * Content Rating Tests
+ * Tests that rate_content is backed by a TransactionReceipt between the rater and the rated agent
+ *
+ * Receipt-backed ratings ensure:
+ * 1. Only the receipt's payer can rate, and only the receipt's recipient can be rated
+ * 2. Signature, amount and content type are copied from the receipt, not supplied by the rater
+ * 3. Each receipt backs at most one rating
+ * 4. The rating PDA is keyed by the receipt, so full-length (88 char) x402 signatures work
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+describe('Content Rating', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let cpiAuthorityPda: PublicKey;
+  let receiptCount = 0;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function contentRatingPda(receiptPda: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('content_rating'), receiptPda.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  /** A registered agent with a reputation account */
+  async function agent(): Promise<Keypair> {
+    const wallet = Keypair.generate();
+    await airdrop(context, wallet.publicKey, 10_000_000_000);
+
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        agent: wallet.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda(wallet.publicKey),
+        agentAddress: wallet.publicKey,
+        authorityAccount: null,
+        initializer: wallet.publicKey,
+        payer: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+    return wallet;
+  }
+
+  /** Create a receipt for a payment of `amount` lamports from `payer` to `recipient` */
+  async function receipt(
+    payer: Keypair,
+    recipient: Keypair,
+    amount = 1_000_000,
+    contentType: object = { apiResponse: {} },
+    signature = `rating_sig_${receiptCount++}`
+  ): Promise<{ receiptPda: PublicKey; signature: string }> {
+    const signatureHash = Array.from(createHash('sha256').update(signature).digest());
+    const [receiptPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('tx_receipt'),
+        payer.publicKey.toBuffer(),
+        recipient.publicKey.toBuffer(),
+        Buffer.from(signatureHash),
+      ],
+      VOTE_PROGRAM_ID
+    );
+
+    await voteProgram.methods
+      .createTransactionReceipt(signature, signatureHash, new BN(amount), contentType, PublicKey.default)
+      .accounts({
+        receipt: receiptPda,
+        payerPubkey: payer.publicKey,
+        recipientPubkey: recipient.publicKey,
+        creator: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+    return { receiptPda, signature };
+  }
+
+  function rate(receiptPda: PublicKey, rater: Keypair, ratedAgent: Keypair, qualityRating = 90) {
+    return voteProgram.methods
+      .rateContent(qualityRating)
+      .accounts({
+        contentRating: contentRatingPda(receiptPda),
+        transactionReceipt: receiptPda,
+        raterIdentity: identityPda(rater.publicKey),
+        raterReputation: reputationPda(rater.publicKey),
+        ratedAgentIdentity: identityPda(ratedAgent.publicKey),
+        ratedAgent: ratedAgent.publicKey,
+        ratedAgentReputation: reputationPda(ratedAgent.publicKey),
+        reputationCpiAuthority: cpiAuthorityPda,
+        rater: rater.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([rater])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    [cpiAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('reputation_cpi')], VOTE_PROGRAM_ID);
+  });
+
+  test('rating without a receipt the rater paid on fails', async () => {
+    const [payer, seller, outsider] = [await agent(), await agent(), await agent()];
+    const { receiptPda } = await receipt(payer, seller);
+
+    // The recipient cannot rate its own delivery, and a third party cannot borrow the receipt
+    await expect(rate(receiptPda, seller, payer)).rejects.toThrow(/RaterNotPayer/);
+    await expect(rate(receiptPda, outsider, seller)).rejects.toThrow(/RaterNotPayer/);
+    expect(await context.banksClient.getAccount(contentRatingPda(receiptPda))).toBeNull();
+  });
+
+  test('rating with a receipt for a different agent fails', async () => {
+    const [payer, seller, otherAgent] = [await agent(), await agent(), await agent()];
+    const { receiptPda } = await receipt(payer, seller);
+
+    await expect(rate(receiptPda, payer, otherAgent)).rejects.toThrow(/ReceiptRecipientMismatch/);
+  });
+
+  test('signature, amount and content type come from the receipt', async () => {
+    const [payer, seller] = [await agent(), await agent()];
+    const { receiptPda, signature } = await receipt(payer, seller, 42_000_000, { generatedCode: {} });
+
+    await rate(receiptPda, payer, seller, 75);
+
+    const rating = await fetchAccount(voteProgram, 'contentRating', contentRatingPda(receiptPda));
+    expect(rating.agent.toBase58()).toBe(seller.publicKey.toBase58());
+    expect(rating.rater.toBase58()).toBe(payer.publicKey.toBase58());
+    expect(rating.transactionReceipt.toBase58()).toBe(receiptPda.toBase58());
+    expect(rating.x402Signature).toBe(signature);
+    expect(rating.amountPaid.toNumber()).toBe(42_000_000);
+    expect(rating.contentType).toEqual({ generatedCode: {} });
+    expect(rating.qualityRating).toBe(75);
+  });
+
+  test('a receipt backs only one rating', async () => {
+    const [payer, seller] = [await agent(), await agent()];
+    const { receiptPda } = await receipt(payer, seller);
+
+    await rate(receiptPda, payer, seller);
+    await expect(rate(receiptPda, payer, seller, 10)).rejects.toThrow(/already in use/);
+  });
+
+  test('a full-length base58 signature rates through the hashed receipt seed', async () => {
+    const [payer, seller] = [await agent(), await agent()];
+    // 64 bytes of base58 is 87-88 chars, well past the 32-byte PDA seed limit
+    const signature = '5'.repeat(88);
+    const { receiptPda } = await receipt(payer, seller, 1_000_000, { apiResponse: {} }, signature);
+
+    await rate(receiptPda, payer, seller);
+
+    const rating = await fetchAccount(voteProgram, 'contentRating', contentRatingPda(receiptPda));
+    expect(rating.x402Signature).toBe(signature);
+    expect(rating.x402Signature.length).toBe(88);
+  });
+});
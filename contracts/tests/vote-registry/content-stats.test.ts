@@ -0,0 +1,257 @@
+/**
+ * Content Rating Stats Tests
+ * Tests the per-agent ContentRatingStats aggregate maintained by rate_content
+ *
+ * Content stats ensure:
+ * 1. Ratings are bucketed by the receipt's content type, with overall totals across all types
+ * 2. Weighted sums use the rater's reputation snapshot (floored at 1), so weighted averages match hand computation
+ * 3. get_content_stats returns the aggregate via return data
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, metadataHash, mockCoreAsset } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+describe('Content Rating Stats', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let cpiAuthorityPda: PublicKey;
+  let receiptCount = 0;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function contentRatingPda(receiptPda: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('content_rating'), receiptPda.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  /** A registered agent with a reputation account */
+  async function agent(): Promise<Keypair> {
+    const wallet = Keypair.generate();
+    await airdrop(context, wallet.publicKey, 10_000_000_000);
+
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        agent: wallet.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda(wallet.publicKey),
+        agentAddress: wallet.publicKey,
+        authorityAccount: null,
+        initializer: wallet.publicKey,
+        payer: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+    return wallet;
+  }
+
+  /** Create a receipt for a payment of `amount` lamports from `payer` to `recipient` */
+  async function receipt(
+    payer: Keypair,
+    recipient: Keypair,
+    amount = 1_000_000,
+    contentType: object = { apiResponse: {} },
+    signature = `rating_sig_${receiptCount++}`
+  ): Promise<{ receiptPda: PublicKey; signature: string }> {
+    const signatureHash = Array.from(createHash('sha256').update(signature).digest());
+    const [receiptPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('tx_receipt'),
+        payer.publicKey.toBuffer(),
+        recipient.publicKey.toBuffer(),
+        Buffer.from(signatureHash),
+      ],
+      VOTE_PROGRAM_ID
+    );
+
+    await voteProgram.methods
+      .createTransactionReceipt(signature, signatureHash, new BN(amount), contentType, PublicKey.default)
+      .accounts({
+        receipt: receiptPda,
+        payerPubkey: payer.publicKey,
+        recipientPubkey: recipient.publicKey,
+        creator: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+    return { receiptPda, signature };
+  }
+
+  function contentStatsPda(agentKey: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('content_stats'), agentKey.toBuffer()], VOTE_PROGRAM_ID)[0];
+  }
+
+  function rate(receiptPda: PublicKey, rater: Keypair, ratedAgent: Keypair, qualityRating = 90) {
+    return voteProgram.methods
+      .rateContent(qualityRating)
+      .accounts({
+        contentRating: contentRatingPda(receiptPda),
+        contentStats: contentStatsPda(ratedAgent.publicKey),
+        transactionReceipt: receiptPda,
+        raterIdentity: identityPda(rater.publicKey),
+        raterReputation: reputationPda(rater.publicKey),
+        ratedAgentIdentity: identityPda(ratedAgent.publicKey),
+        ratedAgent: ratedAgent.publicKey,
+        ratedAgentReputation: reputationPda(ratedAgent.publicKey),
+        reputationCpiAuthority: cpiAuthorityPda,
+        rater: rater.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([rater])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    [cpiAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('reputation_cpi')], VOTE_PROGRAM_ID);
+  });
+
+  /** Index of each ContentType variant in the per-type arrays */
+  const API_RESPONSE = 0;
+  const GENERATED_CODE = 3;
+
+  test('ratings are bucketed by content type with overall totals', async () => {
+    const seller = await agent();
+    const [buyerA, buyerB, buyerC] = [await agent(), await agent(), await agent()];
+
+    const api1 = await receipt(buyerA, seller, 1_000_000, { apiResponse: {} });
+    const api2 = await receipt(buyerB, seller, 1_000_000, { apiResponse: {} });
+    const code = await receipt(buyerC, seller, 1_000_000, { generatedCode: {} });
+    await rate(api1.receiptPda, buyerA, seller, 80);
+    await rate(api2.receiptPda, buyerB, seller, 60);
+    await rate(code.receiptPda, buyerC, seller, 95);
+
+    const stats = await fetchAccount(voteProgram, 'contentRatingStats', contentStatsPda(seller.publicKey));
+    expect(stats.agent.toBase58()).toBe(seller.publicKey.toBase58());
+    expect(stats.ratingsCount[API_RESPONSE]).toBe(2);
+    expect(stats.ratingSum[API_RESPONSE].toNumber()).toBe(140);
+    expect(stats.ratingsCount[GENERATED_CODE]).toBe(1);
+    expect(stats.ratingSum[GENERATED_CODE].toNumber()).toBe(95);
+    for (const i of [1, 2, 4, 5]) {
+      expect(stats.ratingsCount[i]).toBe(0);
+      expect(stats.ratingSum[i].toNumber()).toBe(0);
+    }
+    expect(stats.totalRatings).toBe(3);
+    expect(stats.totalRatingSum.toNumber()).toBe(235);
+  });
+
+  test('weighted sums match hand computation from rater reputation snapshots', async () => {
+    const seller = await agent();
+    const buyers = [await agent(), await agent(), await agent()];
+    const qualities = [100, 40, 70];
+
+    const ratingPdas: PublicKey[] = [];
+    for (let i = 0; i < buyers.length; i++) {
+      const { receiptPda } = await receipt(buyers[i], seller, 1_000_000, { apiResponse: {} });
+      await rate(receiptPda, buyers[i], seller, qualities[i]);
+      ratingPdas.push(contentRatingPda(receiptPda));
+    }
+
+    let weightedSum = 0;
+    let weightSum = 0;
+    for (let i = 0; i < ratingPdas.length; i++) {
+      const rating = await fetchAccount(voteProgram, 'contentRating', ratingPdas[i]);
+      const weight = Math.max(rating.raterReputationSnapshot, 1);
+      weightedSum += qualities[i] * weight;
+      weightSum += weight;
+    }
+
+    const stats = await fetchAccount(voteProgram, 'contentRatingStats', contentStatsPda(seller.publicKey));
+    expect(stats.weightedRatingSum[API_RESPONSE].toNumber()).toBe(weightedSum);
+    expect(stats.weightSum[API_RESPONSE].toNumber()).toBe(weightSum);
+    expect(stats.totalWeightedRatingSum.toNumber()).toBe(weightedSum);
+    expect(stats.totalWeightSum.toNumber()).toBe(weightSum);
+
+    const weightedAverage = stats.weightedRatingSum[API_RESPONSE].toNumber() / stats.weightSum[API_RESPONSE].toNumber();
+    expect(weightedAverage).toBeCloseTo(weightedSum / weightSum);
+  });
+
+  test('get_content_stats returns the aggregate via return data', async () => {
+    const [buyer, seller] = [await agent(), await agent()];
+    const { receiptPda } = await receipt(buyer, seller, 1_000_000, { generatedCode: {} });
+    await rate(receiptPda, buyer, seller, 88);
+
+    const tx = await voteProgram.methods
+      .getContentStats()
+      .accounts({ contentStats: contentStatsPda(seller.publicKey), agent: seller.publicKey })
+      .transaction();
+    tx.recentBlockhash = context.lastBlockhash;
+    tx.feePayer = context.payer.publicKey;
+    tx.sign(context.payer);
+    const meta = await context.banksClient.processTransaction(tx);
+
+    // Return data is the account's borsh body without the 8-byte discriminator
+    const account = await context.banksClient.getAccount(contentStatsPda(seller.publicKey));
+    expect(Buffer.from(meta.returnData!.data)).toEqual(Buffer.from(account!.data).subarray(8));
+  });
+
+  test('stats cannot be read before an agent has been rated', async () => {
+    const unrated = await agent();
+
+    await expect(
+      voteProgram.methods
+        .getContentStats()
+        .accounts({ contentStats: contentStatsPda(unrated.publicKey), agent: unrated.publicKey })
+        .rpc()
+    ).rejects.toThrow(/AccountNotInitialized/);
+  });
+});
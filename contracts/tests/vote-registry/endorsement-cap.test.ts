@@ -0,0 +1,238 @@
+/**
+ * Endorsement Cap Tests
+ * Tests the EndorsementStats counters and the cap on active endorsements given
+ *
+ * The counters ensure:
+ * 1. An agent cannot hold more active endorsements than VoteConfig allows (default 10)
+ * 2. Revoking an endorsement frees a slot
+ * 3. endorsements_received tracks the active endorsements an agent holds
+ * 4. The vote authority can lower or raise the cap
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, registeredWallet, reputableAgent } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const DEFAULT_MAX_ACTIVE_ENDORSEMENTS = 10;
+const MIN_ENDORSEMENT_DURATION = 30 * 24 * 60 * 60;
+
+describe('Endorsement Cap', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let reputationAuthorityPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+  let voteConfigPda: PublicKey;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementPda(endorser: PublicKey, endorsed: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement'), endorser.toBuffer(), endorsed.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementStatsPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_stats'), agent.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function registeredAgent(): Promise<Keypair> {
+    return registeredWallet(context, identityProgram);
+  }
+
+  /** A registered agent with the 500 reputation an endorser needs */
+  function endorser(): Promise<Keypair> {
+    return reputableAgent(context, identityProgram, reputationProgram, authority);
+  }
+
+  async function endorse(from: Keypair, agent: Keypair, useConfig = true): Promise<PublicKey> {
+    const endorsement = endorsementPda(from.publicKey, agent.publicKey);
+    await voteProgram.methods
+      .endorseAgent(agent.publicKey, 50, { technical: {} })
+      .accounts({
+        endorsement,
+        endorserStats: endorsementStatsPda(from.publicKey),
+        endorsedStats: endorsementStatsPda(agent.publicKey),
+        endorserIdentity: identityPda(from.publicKey),
+        endorserReputation: reputationPda(from.publicKey),
+        endorsedAgentIdentity: identityPda(agent.publicKey),
+        endorser: from.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        voteConfig: useConfig ? voteConfigPda : null,
+      })
+      .signers([from])
+      .rpc();
+    return endorsement;
+  }
+
+  function revoke(from: Keypair, agent: Keypair) {
+    return voteProgram.methods
+      .revokeEndorsement()
+      .accounts({
+        endorsement: endorsementPda(from.publicKey, agent.publicKey),
+        endorserStats: endorsementStatsPda(from.publicKey),
+        endorsedStats: endorsementStatsPda(agent.publicKey),
+        endorser: from.publicKey,
+      })
+      .signers([from])
+      .rpc();
+  }
+
+  function setMaxEndorsements(max: number) {
+    return voteProgram.methods
+      .updateMaxActiveEndorsements(max)
+      .accounts({ voteConfig: voteConfigPda, authorityAccount: voteAuthorityPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+  }
+
+  function stats(agent: Keypair) {
+    return fetchAccount(voteProgram, 'endorsementStats', endorsementStatsPda(agent.publicKey));
+  }
+
+  /** Endorse `count` fresh agents and return them */
+  async function endorseMany(from: Keypair, count: number, useConfig = true): Promise<Keypair[]> {
+    const agents: Keypair[] = [];
+    for (let i = 0; i < count; i++) {
+      const agent = await registeredAgent();
+      await endorse(from, agent, useConfig);
+      agents.push(agent);
+    }
+    return agents;
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: reputationAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeVoteConfig()
+      .accounts({
+        voteConfig: voteConfigPda,
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('the eleventh active endorsement fails', async () => {
+    const from = await endorser();
+    await endorseMany(from, DEFAULT_MAX_ACTIVE_ENDORSEMENTS);
+    expect((await stats(from)).endorsementsGiven).toBe(DEFAULT_MAX_ACTIVE_ENDORSEMENTS);
+
+    await expect(endorse(from, await registeredAgent())).rejects.toThrow(/MaxEndorsementsReached/);
+    // The default cap also applies when no VoteConfig is supplied
+    await expect(endorse(from, await registeredAgent(), false)).rejects.toThrow(/MaxEndorsementsReached/);
+  });
+
+  test('revoking an endorsement frees a slot', async () => {
+    const from = await endorser();
+    const [first] = await endorseMany(from, DEFAULT_MAX_ACTIVE_ENDORSEMENTS);
+
+    await advanceTime(context, MIN_ENDORSEMENT_DURATION);
+    await revoke(from, first);
+    expect((await stats(from)).endorsementsGiven).toBe(DEFAULT_MAX_ACTIVE_ENDORSEMENTS - 1);
+    expect((await stats(first)).endorsementsReceived).toBe(0);
+
+    await endorse(from, await registeredAgent());
+    expect((await stats(from)).endorsementsGiven).toBe(DEFAULT_MAX_ACTIVE_ENDORSEMENTS);
+  });
+
+  test('endorsements_received tracks the endorsements an agent holds', async () => {
+    const agent = await registeredAgent();
+    const endorsers = [await endorser(), await endorser(), await endorser()];
+    for (const from of endorsers) {
+      await endorse(from, agent);
+    }
+
+    const received = await stats(agent);
+    expect(received.agent.toBase58()).toBe(agent.publicKey.toBase58());
+    expect(received.endorsementsReceived).toBe(3);
+    expect(received.endorsementsGiven).toBe(0);
+
+    await advanceTime(context, MIN_ENDORSEMENT_DURATION);
+    await revoke(endorsers[1], agent);
+    expect((await stats(agent)).endorsementsReceived).toBe(2);
+    expect((await stats(endorsers[1])).endorsementsGiven).toBe(0);
+  });
+
+  test('the authority can change the cap', async () => {
+    const from = await endorser();
+    await setMaxEndorsements(2);
+    await endorseMany(from, 2);
+    await expect(endorse(from, await registeredAgent())).rejects.toThrow(/MaxEndorsementsReached/);
+
+    await setMaxEndorsements(3);
+    await endorse(from, await registeredAgent());
+    expect((await stats(from)).endorsementsGiven).toBe(3);
+
+    await expect(setMaxEndorsements(0)).rejects.toThrow(/InvalidMaxEndorsements/);
+  });
+});
@@ -0,0 +1,283 @@
+/**
+ * Endorsement Expiry Tests
+ * Tests endorsement validity periods, re-affirmation and permissionless expiry
+ *
+ * Expiry ensures:
+ * 1. An endorsement expires a validity period (180 days by default) after it is made
+ * 2. It cannot be marked expired before expires_at, and can be from expires_at on
+ * 3. Re-affirming pushes expires_at out again and can lock extra stake
+ * 4. Anyone can mark a lapsed endorsement expired, and the stake goes back to the endorser
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, now, registeredWallet, reputableAgent } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const DAY = 24 * 60 * 60;
+const VALIDITY_SECONDS = 180 * DAY;
+
+describe('Endorsement Expiry', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let reputationAuthorityPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+  let voteConfigPda: PublicKey;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementPda(endorser: PublicKey, endorsed: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement'), endorser.toBuffer(), endorsed.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementStatsPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_stats'), agent.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  async function balance(address: PublicKey): Promise<number> {
+    return Number(await context.banksClient.getBalance(address));
+  }
+
+  function registeredAgent(): Promise<Keypair> {
+    return registeredWallet(context, identityProgram);
+  }
+
+  /** A registered agent with the 500 reputation an endorser needs */
+  function endorser(): Promise<Keypair> {
+    return reputableAgent(context, identityProgram, reputationProgram, authority);
+  }
+
+  async function endorse(from: Keypair, agent: Keypair, strength = 50): Promise<PublicKey> {
+    const endorsement = endorsementPda(from.publicKey, agent.publicKey);
+    await voteProgram.methods
+      .endorseAgent(agent.publicKey, strength, { technical: {} })
+      .accounts({
+        endorsement,
+        endorserStats: endorsementStatsPda(from.publicKey),
+        endorsedStats: endorsementStatsPda(agent.publicKey),
+        endorserIdentity: identityPda(from.publicKey),
+        endorserReputation: reputationPda(from.publicKey),
+        endorsedAgentIdentity: identityPda(agent.publicKey),
+        endorser: from.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        voteConfig: voteConfigPda,
+      })
+      .signers([from])
+      .rpc();
+    return endorsement;
+  }
+
+  function reaffirm(from: Keypair, agent: Keypair, extraStake = 0) {
+    return voteProgram.methods
+      .reaffirmEndorsement(new BN(extraStake))
+      .accounts({
+        endorsement: endorsementPda(from.publicKey, agent.publicKey),
+        endorser: from.publicKey,
+        systemProgram: SystemProgram.programId,
+        voteConfig: voteConfigPda,
+      })
+      .signers([from])
+      .rpc();
+  }
+
+  /** Expire `from`'s endorsement of `agent`, signed by `caller`; `endorserWallet` receives the stake */
+  function markExpired(from: Keypair, agent: Keypair, caller: Keypair, endorserWallet = from.publicKey) {
+    return voteProgram.methods
+      .markEndorsementExpired()
+      .accounts({
+        endorsement: endorsementPda(from.publicKey, agent.publicKey),
+        endorserStats: endorsementStatsPda(from.publicKey),
+        endorsedStats: endorsementStatsPda(agent.publicKey),
+        endorser: endorserWallet,
+        caller: caller.publicKey,
+      })
+      .signers([caller])
+      .rpc();
+  }
+
+  async function endorsementOf(endorsement: PublicKey) {
+    return fetchAccount(voteProgram, 'agentEndorsement', endorsement);
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: reputationAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeVoteConfig()
+      .accounts({
+        voteConfig: voteConfigPda,
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('expires_at is set a validity period out and is enforced at the boundary', async () => {
+    const [from, agent] = [await endorser(), await registeredAgent()];
+    const endorsement = await endorse(from, agent);
+
+    const account = await endorsementOf(endorsement);
+    expect(account.expiresAt.toNumber()).toBe(account.timestamp.toNumber() + VALIDITY_SECONDS);
+
+    // One second before expires_at the endorsement is still current
+    await advanceTime(context, account.expiresAt.toNumber() - (await now(context)) - 1);
+    await expect(markExpired(from, agent, from)).rejects.toThrow(/EndorsementNotExpired/);
+
+    // At expires_at it is not
+    await advanceTime(context, 1);
+    await expect(reaffirm(from, agent)).rejects.toThrow(/EndorsementExpired/);
+    await markExpired(from, agent, from);
+    expect(await context.banksClient.getAccount(endorsement)).toBeNull();
+  });
+
+  test('re-affirming extends the expiry and can add stake', async () => {
+    const [from, agent] = [await endorser(), await registeredAgent()];
+    const endorsement = await endorse(from, agent);
+    const original = await endorsementOf(endorsement);
+
+    await advanceTime(context, 100 * DAY);
+    await reaffirm(from, agent, 5_000_000);
+
+    const reaffirmed = await endorsementOf(endorsement);
+    expect(reaffirmed.expiresAt.toNumber()).toBe((await now(context)) + VALIDITY_SECONDS);
+    expect(reaffirmed.expiresAt.toNumber()).toBeGreaterThan(original.expiresAt.toNumber());
+    expect(reaffirmed.stakeAmount.toNumber()).toBe(original.stakeAmount.toNumber() + 5_000_000);
+    expect(reaffirmed.isActive).toBe(true);
+
+    // The original expiry no longer applies
+    await advanceTime(context, original.expiresAt.toNumber() - (await now(context)));
+    await expect(markExpired(from, agent, from)).rejects.toThrow(/EndorsementNotExpired/);
+  });
+
+  test('only the endorser can re-affirm', async () => {
+    const [from, agent, outsider] = [await endorser(), await registeredAgent(), await registeredAgent()];
+    await endorse(from, agent);
+
+    await expect(
+      voteProgram.methods
+        .reaffirmEndorsement(new BN(0))
+        .accounts({
+          endorsement: endorsementPda(from.publicKey, agent.publicKey),
+          endorser: outsider.publicKey,
+          systemProgram: SystemProgram.programId,
+          voteConfig: voteConfigPda,
+        })
+        .signers([outsider])
+        .rpc()
+    ).rejects.toThrow(/NotEndorser/);
+  });
+
+  test('anyone can expire a lapsed endorsement and the stake returns to the endorser', async () => {
+    const [from, agent, caller] = [await endorser(), await registeredAgent(), await registeredAgent()];
+    const endorsement = await endorse(from, agent);
+    const { stakeAmount } = await endorsementOf(endorsement);
+    const held = await balance(endorsement);
+
+    await advanceTime(context, VALIDITY_SECONDS);
+
+    // The stake cannot be redirected to the caller
+    await expect(markExpired(from, agent, caller, caller.publicKey)).rejects.toThrow(/NotEndorser/);
+
+    const endorserBefore = await balance(from.publicKey);
+    const callerBefore = await balance(caller.publicKey);
+    await markExpired(from, agent, caller);
+
+    // Fees are paid by the provider wallet, so the endorser receives exactly stake + rent
+    expect(held).toBeGreaterThan(stakeAmount.toNumber());
+    expect(await balance(from.publicKey)).toBe(endorserBefore + held);
+    expect(await balance(caller.publicKey)).toBe(callerBefore);
+
+    const endorserStats = await fetchAccount(voteProgram, 'endorsementStats', endorsementStatsPda(from.publicKey));
+    const endorsedStats = await fetchAccount(voteProgram, 'endorsementStats', endorsementStatsPda(agent.publicKey));
+    expect(endorserStats.endorsementsGiven).toBe(0);
+    expect(endorsedStats.endorsementsReceived).toBe(0);
+  });
+
+  test('only the vote authority can change the validity period, and it must be positive', async () => {
+    const outsider = await registeredAgent();
+    const update = (seconds: number, signer: Keypair) =>
+      voteProgram.methods
+        .updateEndorsementValidity(new BN(seconds))
+        .accounts({ voteConfig: voteConfigPda, authorityAccount: voteAuthorityPda, authority: signer.publicKey })
+        .signers([signer])
+        .rpc();
+
+    await expect(update(30 * DAY, outsider)).rejects.toThrow(/UnauthorizedAuthority/);
+    await expect(update(0, authority)).rejects.toThrow(/InvalidEndorsementValidity/);
+
+    await update(30 * DAY, authority);
+    const [from, agent] = [await endorser(), await registeredAgent()];
+    const account = await endorsementOf(await endorse(from, agent));
+    expect(account.expiresAt.toNumber()).toBe(account.timestamp.toNumber() + 30 * DAY);
+  });
+});
@@ -0,0 +1,280 @@
+/**
+ * Endorsement Lifecycle Tests
+ * Tests revoke_endorsement and slash_endorsement
+ *
+ * The lifecycle ensures:
+ * 1. An endorsement cannot be revoked before the 30-day minimum duration
+ * 2. Revoking afterwards closes the endorsement and refunds stake and rent to the endorser
+ * 3. The vote authority can seize the stake once the endorsed agent is slashed in identity_registry
+ * 4. A slashed endorsement cannot be revoked for a refund
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, now, registeredWallet, reputableAgent } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const ENDORSEMENT_STAKE = 10_000_000;
+const IDENTITY_MIN_STAKE = 100_000_000;
+const MIN_ENDORSEMENT_DURATION = 30 * 24 * 60 * 60;
+
+describe('Endorsement Lifecycle', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let reputationAuthorityPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+  let stakingPoolPda: PublicKey;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementPda(endorser: PublicKey, endorsed: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement'), endorser.toBuffer(), endorsed.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementStatsPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_stats'), agent.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  async function balance(address: PublicKey): Promise<number> {
+    return Number(await context.banksClient.getBalance(address));
+  }
+
+  function registeredAgent(): Promise<Keypair> {
+    return registeredWallet(context, identityProgram);
+  }
+
+  /** A registered agent with the 500 reputation an endorser needs */
+  function endorser(): Promise<Keypair> {
+    return reputableAgent(context, identityProgram, reputationProgram, authority);
+  }
+
+  /** A registered agent with the minimum identity stake, so it can be slashed */
+  async function stakedAgent(): Promise<Keypair> {
+    const agent = await registeredAgent();
+    await identityProgram.methods
+      .stakeCollateral(new BN(IDENTITY_MIN_STAKE))
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        stakingPool: stakingPoolPda,
+        agent: agent.publicKey,
+        agentAddress: agent.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    return agent;
+  }
+
+  async function slashInIdentityRegistry(agent: Keypair) {
+    await identityProgram.methods
+      .slashAgent(10000, 'Fabricated results')
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        stakingPool: stakingPoolPda,
+        slashRecord: PublicKey.findProgramAddressSync(
+          [Buffer.from('slash'), agent.publicKey.toBuffer(), Buffer.alloc(4)],
+          IDENTITY_PROGRAM_ID
+        )[0],
+        agentAddress: agent.publicKey,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  async function endorse(from: Keypair, agent: Keypair): Promise<PublicKey> {
+    const endorsement = endorsementPda(from.publicKey, agent.publicKey);
+    await voteProgram.methods
+      .endorseAgent(agent.publicKey, 80, { technical: {} })
+      .accounts({
+        endorsement,
+        endorserStats: endorsementStatsPda(from.publicKey),
+        endorsedStats: endorsementStatsPda(agent.publicKey),
+        endorserIdentity: identityPda(from.publicKey),
+        endorserReputation: reputationPda(from.publicKey),
+        endorsedAgentIdentity: identityPda(agent.publicKey),
+        endorser: from.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([from])
+      .rpc();
+    return endorsement;
+  }
+
+  function revoke(from: Keypair, agent: Keypair, signer = from) {
+    return voteProgram.methods
+      .revokeEndorsement()
+      .accounts({
+        endorsement: endorsementPda(from.publicKey, agent.publicKey),
+        endorserStats: endorsementStatsPda(from.publicKey),
+        endorsedStats: endorsementStatsPda(agent.publicKey),
+        endorser: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  function slash(from: Keypair, agent: Keypair, signer = authority) {
+    return voteProgram.methods
+      .slashEndorsement()
+      .accounts({
+        endorsement: endorsementPda(from.publicKey, agent.publicKey),
+        endorserStats: endorsementStatsPda(from.publicKey),
+        endorsedStats: endorsementStatsPda(agent.publicKey),
+        endorsedAgentIdentity: identityPda(agent.publicKey),
+        authorityAccount: voteAuthorityPda,
+        authority: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: reputationAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    await identityProgram.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('revoking before the minimum duration fails', async () => {
+    const from = await endorser();
+    const agent = await registeredAgent();
+    await endorse(from, agent);
+
+    await advanceTime(context, MIN_ENDORSEMENT_DURATION - 60);
+    await expect(revoke(from, agent)).rejects.toThrow(/EndorsementLocked/);
+  });
+
+  test('revoking after the minimum duration closes the endorsement and refunds the endorser', async () => {
+    const from = await endorser();
+    const agent = await registeredAgent();
+    const endorsement = await endorse(from, agent);
+    await advanceTime(context, MIN_ENDORSEMENT_DURATION);
+
+    const held = await balance(endorsement);
+    expect(held).toBeGreaterThan(ENDORSEMENT_STAKE);
+    const before = await balance(from.publicKey);
+
+    await revoke(from, agent);
+
+    expect(await context.banksClient.getAccount(endorsement)).toBeNull();
+    expect(await balance(from.publicKey)).toBe(before + held);
+  });
+
+  test('only the endorser can revoke', async () => {
+    const from = await endorser();
+    const agent = await registeredAgent();
+    await endorse(from, agent);
+    await advanceTime(context, MIN_ENDORSEMENT_DURATION);
+
+    await expect(revoke(from, agent, agent)).rejects.toThrow(/NotEndorser/);
+  });
+
+  test('a slashed endorsement loses its stake and cannot be revoked', async () => {
+    const from = await endorser();
+    const agent = await stakedAgent();
+    const endorsement = await endorse(from, agent);
+    await slashInIdentityRegistry(agent);
+
+    const authorityBefore = await balance(authority.publicKey);
+    await slash(from, agent);
+    // The slash transaction's fee is paid by the provider wallet
+    expect(await balance(authority.publicKey)).toBe(authorityBefore + ENDORSEMENT_STAKE);
+
+    const account = await fetchAccount(voteProgram, 'agentEndorsement', endorsement);
+    expect(account.isActive).toBe(false);
+    expect(account.isSlashed).toBe(true);
+    expect(account.stakeAmount.toNumber()).toBe(0);
+    expect(account.slashedAt.toNumber()).toBe(await now(context));
+
+    await advanceTime(context, MIN_ENDORSEMENT_DURATION);
+    await expect(revoke(from, agent)).rejects.toThrow(/EndorsementNotActive/);
+  });
+
+  test('slashing requires a slashed agent and the vote authority', async () => {
+    const from = await endorser();
+    const agent = await stakedAgent();
+    await endorse(from, agent);
+
+    await expect(slash(from, agent)).rejects.toThrow(/EndorsedAgentNotSlashed/);
+
+    await slashInIdentityRegistry(agent);
+    await expect(slash(from, agent, from)).rejects.toThrow(/UnauthorizedAuthority/);
+  });
+});
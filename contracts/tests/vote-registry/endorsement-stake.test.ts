@@ -0,0 +1,224 @@
+/**
+ * Endorsement Stake Tests
+ * Tests strength-proportional endorsement stakes driven by the VoteConfig PDA
+ *
+ * The stake ensures:
+ * 1. Without a VoteConfig the flat 0.01 SOL minimum is locked
+ * 2. With a VoteConfig the stake is base + strength * per-point, and the parameters are recorded
+ * 3. Config updates change future endorsements only
+ * 4. An endorser who cannot cover the stake is rejected cleanly
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, registeredWallet, reputableAgent } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const ENDORSEMENT_STAKE = 10_000_000;
+const DEFAULT_STAKE_PER_POINT = 100_000;
+
+describe('Endorsement Stake', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let reputationAuthorityPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+  let voteConfigPda: PublicKey;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementPda(endorser: PublicKey, endorsed: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement'), endorser.toBuffer(), endorsed.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementStatsPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_stats'), agent.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  async function balance(address: PublicKey): Promise<number> {
+    return Number(await context.banksClient.getBalance(address));
+  }
+
+  function registeredAgent(): Promise<Keypair> {
+    return registeredWallet(context, identityProgram);
+  }
+
+  /** A registered agent with the 500 reputation an endorser needs */
+  function endorser(): Promise<Keypair> {
+    return reputableAgent(context, identityProgram, reputationProgram, authority);
+  }
+
+  async function endorse(from: Keypair, agent: Keypair, strength: number, useConfig = true): Promise<PublicKey> {
+    const endorsement = endorsementPda(from.publicKey, agent.publicKey);
+    await voteProgram.methods
+      .endorseAgent(agent.publicKey, strength, { technical: {} })
+      .accounts({
+        endorsement,
+        endorserStats: endorsementStatsPda(from.publicKey),
+        endorsedStats: endorsementStatsPda(agent.publicKey),
+        endorserIdentity: identityPda(from.publicKey),
+        endorserReputation: reputationPda(from.publicKey),
+        endorsedAgentIdentity: identityPda(agent.publicKey),
+        endorser: from.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        voteConfig: useConfig ? voteConfigPda : null,
+      })
+      .signers([from])
+      .rpc();
+    return endorsement;
+  }
+
+  function updateStake(baseStake: number, stakePerPoint: number, signer = authority) {
+    return voteProgram.methods
+      .updateEndorsementStake(new BN(baseStake), new BN(stakePerPoint))
+      .accounts({ voteConfig: voteConfigPda, authorityAccount: voteAuthorityPda, authority: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function stakeOf(endorsement: PublicKey) {
+    return fetchAccount(voteProgram, 'agentEndorsement', endorsement);
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: reputationAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeVoteConfig()
+      .accounts({
+        voteConfig: voteConfigPda,
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('without the config a flat minimum stake is locked', async () => {
+    const from = await endorser();
+    const endorsement = await endorse(from, await registeredAgent(), 100, false);
+
+    const account = await stakeOf(endorsement);
+    expect(account.stakeAmount.toNumber()).toBe(ENDORSEMENT_STAKE);
+    expect(account.stakeBase.toNumber()).toBe(ENDORSEMENT_STAKE);
+    expect(account.stakePerPoint.toNumber()).toBe(0);
+  });
+
+  test('strength 10 and strength 100 lock different amounts', async () => {
+    const from = await endorser();
+    const weak = await endorse(from, await registeredAgent(), 10);
+    const strong = await endorse(from, await registeredAgent(), 100);
+
+    const weakAccount = await stakeOf(weak);
+    const strongAccount = await stakeOf(strong);
+    expect(weakAccount.stakeAmount.toNumber()).toBe(ENDORSEMENT_STAKE + 10 * DEFAULT_STAKE_PER_POINT);
+    expect(strongAccount.stakeAmount.toNumber()).toBe(ENDORSEMENT_STAKE + 100 * DEFAULT_STAKE_PER_POINT);
+    expect(strongAccount.stakePerPoint.toNumber()).toBe(DEFAULT_STAKE_PER_POINT);
+
+    // The stake sits in the endorsement PDA on top of its rent
+    const rent = await context.banksClient.getRent();
+    const rentExempt = Number(rent.minimumBalance(BigInt((await context.banksClient.getAccount(strong))!.data.length)));
+    expect(await balance(strong)).toBe(rentExempt + strongAccount.stakeAmount.toNumber());
+  });
+
+  test('a config update changes future endorsements only', async () => {
+    const from = await endorser();
+    const before = await endorse(from, await registeredAgent(), 50);
+
+    await updateStake(2 * ENDORSEMENT_STAKE, 1_000_000);
+    const after = await endorse(from, await registeredAgent(), 50);
+
+    const beforeAccount = await stakeOf(before);
+    expect(beforeAccount.stakeAmount.toNumber()).toBe(ENDORSEMENT_STAKE + 50 * DEFAULT_STAKE_PER_POINT);
+    expect(beforeAccount.stakeBase.toNumber()).toBe(ENDORSEMENT_STAKE);
+
+    const afterAccount = await stakeOf(after);
+    expect(afterAccount.stakeAmount.toNumber()).toBe(2 * ENDORSEMENT_STAKE + 50 * 1_000_000);
+    expect(afterAccount.stakeBase.toNumber()).toBe(2 * ENDORSEMENT_STAKE);
+    expect(afterAccount.stakePerPoint.toNumber()).toBe(1_000_000);
+  });
+
+  test('only the vote authority can update the stake, and not below the minimum', async () => {
+    const outsider = await registeredAgent();
+    await expect(updateStake(ENDORSEMENT_STAKE, 0, outsider)).rejects.toThrow(/UnauthorizedAuthority/);
+    await expect(updateStake(ENDORSEMENT_STAKE - 1, 0)).rejects.toThrow(/InvalidEndorsementStakeConfig/);
+  });
+
+  test('an endorser who cannot cover the stake fails cleanly', async () => {
+    const from = await endorser();
+    // 100 SOL per point puts a strength-100 endorsement far beyond the endorser's 10 SOL
+    await updateStake(ENDORSEMENT_STAKE, 100 * LAMPORTS_PER_SOL);
+
+    const agent = await registeredAgent();
+    await expect(endorse(from, agent, 100)).rejects.toThrow(/InsufficientBalanceForStake/);
+    expect(await context.banksClient.getAccount(endorsementPda(from.publicKey, agent.publicKey))).toBeNull();
+  });
+});
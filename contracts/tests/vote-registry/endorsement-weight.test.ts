@@ -0,0 +1,265 @@
+/**
+ * Endorsement Weight Tests
+ * Tests the static weight stored on endorsements and the live get_endorsement_weight view
+ *
+ * Endorsement weight ensures:
+ * 1. Each endorsement stores strength * endorser reputation as its static weight
+ * 2. The live weight decays linearly with age (to zero after 360 days by default), and re-affirming restarts it
+ * 3. Expired endorsements contribute zero
+ * 4. Only distinct endorsement PDAs of the agent are accepted as remaining accounts
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  simulateReturnData,
+  registeredWallet,
+  reputableAgent,
+} from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const DAY = 24 * 60 * 60;
+const DECAY_SECONDS = 360 * DAY;
+
+describe('Endorsement Weight', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let reputationAuthorityPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+  let voteConfigPda: PublicKey;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementPda(endorser: PublicKey, endorsed: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement'), endorser.toBuffer(), endorsed.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function endorsementStatsPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement_stats'), agent.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function registeredAgent(): Promise<Keypair> {
+    return registeredWallet(context, identityProgram);
+  }
+
+  /** A registered agent with the 500 reputation an endorser needs */
+  function endorser(): Promise<Keypair> {
+    return reputableAgent(context, identityProgram, reputationProgram, authority);
+  }
+
+  async function endorse(from: Keypair, agent: Keypair, strength = 50): Promise<PublicKey> {
+    const endorsement = endorsementPda(from.publicKey, agent.publicKey);
+    await voteProgram.methods
+      .endorseAgent(agent.publicKey, strength, { technical: {} })
+      .accounts({
+        endorsement,
+        endorserStats: endorsementStatsPda(from.publicKey),
+        endorsedStats: endorsementStatsPda(agent.publicKey),
+        endorserIdentity: identityPda(from.publicKey),
+        endorserReputation: reputationPda(from.publicKey),
+        endorsedAgentIdentity: identityPda(agent.publicKey),
+        endorser: from.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        voteConfig: voteConfigPda,
+      })
+      .signers([from])
+      .rpc();
+    return endorsement;
+  }
+
+  function reaffirm(from: Keypair, agent: Keypair, extraStake = 0) {
+    return voteProgram.methods
+      .reaffirmEndorsement(new BN(extraStake))
+      .accounts({
+        endorsement: endorsementPda(from.publicKey, agent.publicKey),
+        endorser: from.publicKey,
+        systemProgram: SystemProgram.programId,
+        voteConfig: voteConfigPda,
+      })
+      .signers([from])
+      .rpc();
+  }
+
+  function updateDecay(decaySeconds: number, signer = authority) {
+    return voteProgram.methods
+      .updateEndorsementDecay(new BN(decaySeconds))
+      .accounts({ voteConfig: voteConfigPda, authorityAccount: voteAuthorityPda, authority: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function endorsementOf(endorsement: PublicKey) {
+    return fetchAccount(voteProgram, 'agentEndorsement', endorsement);
+  }
+
+  /** get_endorsement_weight for `agent` over the given remaining accounts */
+  function weightIx(agent: Keypair, accounts: PublicKey[]) {
+    return voteProgram.methods
+      .getEndorsementWeight()
+      .accounts({ agent: agent.publicKey, voteConfig: voteConfigPda })
+      .remainingAccounts(accounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+      .instruction();
+  }
+
+  /** Current endorsement weight of `agent` from the given endorsers */
+  async function endorsementWeight(agent: Keypair, endorsers: Keypair[]): Promise<number> {
+    const accounts = endorsers.map((from) => endorsementPda(from.publicKey, agent.publicKey));
+    const returnData = await simulateReturnData(context, await weightIx(agent, accounts));
+    return Number(returnData.readBigUInt64LE(0));
+  }
+
+  /** `weight` decayed linearly over DECAY_SECONDS after `ageSeconds` */
+  function decayed(weight: number, ageSeconds: number): number {
+    return Math.floor((weight * (DECAY_SECONDS - ageSeconds)) / DECAY_SECONDS);
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: reputationAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeVoteConfig()
+      .accounts({
+        voteConfig: voteConfigPda,
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('an endorsement stores its static weight and when it was affirmed', async () => {
+    const [from, agent] = [await endorser(), await registeredAgent()];
+    const endorsement = await endorsementOf(await endorse(from, agent, 50));
+
+    expect(endorsement.weight).toBe(50 * 500);
+    expect(endorsement.affirmedAt.toNumber()).toBe(endorsement.timestamp.toNumber());
+  });
+
+  test('a 150-day-old endorsement weighs less than a fresh one', async () => {
+    const [old, fresh, agent] = [await endorser(), await endorser(), await registeredAgent()];
+    await endorse(old, agent, 50);
+    expect(await endorsementWeight(agent, [old])).toBe(25_000);
+
+    await advanceTime(context, 150 * DAY);
+    await endorse(fresh, agent, 50);
+    expect(await endorsementWeight(agent, [fresh])).toBe(25_000);
+    expect(await endorsementWeight(agent, [old])).toBe(decayed(25_000, 150 * DAY));
+    expect(await endorsementWeight(agent, [old, fresh])).toBe(decayed(25_000, 150 * DAY) + 25_000);
+  });
+
+  test('expired endorsements contribute zero, and re-affirming restarts the decay', async () => {
+    const [lapsing, renewed, agent] = [await endorser(), await endorser(), await registeredAgent()];
+    await endorse(lapsing, agent, 40);
+    await endorse(renewed, agent, 80);
+
+    await advanceTime(context, 100 * DAY);
+    await reaffirm(renewed, agent);
+    expect(await endorsementWeight(agent, [renewed])).toBe(80 * 500);
+
+    await advanceTime(context, 80 * DAY);
+    expect(await endorsementWeight(agent, [lapsing])).toBe(0);
+    expect(await endorsementWeight(agent, [lapsing, renewed])).toBe(decayed(80 * 500, 80 * DAY));
+  });
+
+  test('only endorsement PDAs of the agent are accepted, each once', async () => {
+    const [from, agent, other] = [await endorser(), await registeredAgent(), await registeredAgent()];
+    await endorse(from, agent, 50);
+    await endorse(from, other, 50);
+
+    const weight = async (accounts: PublicKey[]) => simulateReturnData(context, await weightIx(agent, accounts));
+    // Endorsement of another agent: valid PDA, wrong seeds for this agent
+    await expect(weight([endorsementPda(from.publicKey, other.publicKey)])).rejects.toThrow(
+      /InvalidEndorsementAccount/
+    );
+    // Not owned by the vote registry
+    await expect(weight([from.publicKey])).rejects.toThrow(/InvalidEndorsementAccount/);
+    const endorsement = endorsementPda(from.publicKey, agent.publicKey);
+    await expect(weight([endorsement, endorsement])).rejects.toThrow(/InvalidEndorsementAccount/);
+  });
+
+  test('the authority can turn decay off, and only to a non-negative period', async () => {
+    const [from, agent] = [await endorser(), await registeredAgent()];
+    await endorse(from, agent, 50);
+    await advanceTime(context, 90 * DAY);
+    expect(await endorsementWeight(agent, [from])).toBe(decayed(25_000, 90 * DAY));
+
+    await updateDecay(0);
+    expect(await endorsementWeight(agent, [from])).toBe(25_000);
+
+    await expect(updateDecay(-1)).rejects.toThrow(/InvalidEndorsementDecay/);
+    await expect(updateDecay(DAY, from)).rejects.toThrow(/UnauthorizedAuthority/);
+    await updateDecay(DECAY_SECONDS);
+  });
+});
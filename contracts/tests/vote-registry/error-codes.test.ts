@@ -0,0 +1,249 @@
+/**
+ * Vote Error Code Tests
+ * Tests that every VoteError failure mode has one variant with a stable code
+ *
+ * Error codes ensure:
+ * 1. Codes of removed duplicates (6007, 6015-6017) stay reserved, so no other variant shifts into them
+ * 2. Every remaining variant is raised by at least one require! or constraint in the program
+ * 3. The canonical variants of the former duplicate pairs, and the receipt and vote checks around them,
+ *    surface under their expected codes (the remaining variants are triggered by their feature suites)
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import * as fs from 'fs';
+import * as path from 'path';
+import { airdrop, advanceTime, loadProgram, transactionReceipt, reputableAgent } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+const DAY = 24 * 60 * 60;
+const QUALITY = { responseQuality: 80, responseSpeed: 80, accuracy: 80, professionalism: 80 };
+
+describe('Vote Error Codes', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let reputationAuthorityPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+  let voteConfigPda: PublicKey;
+  let receiptCount = 0;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function receiptPda(payer: PublicKey, recipient: PublicKey, signatureHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('tx_receipt'), payer.toBuffer(), recipient.toBuffer(), Buffer.from(signatureHash)],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function nextSignature(): { signature: string; signatureHash: number[] } {
+    const signature = `error_sig_${receiptCount++}`;
+    return { signature, signatureHash: Array.from(createHash('sha256').update(signature).digest()) };
+  }
+
+  /** A registered agent with the given reputation score */
+  function votingAgent(score?: number): Promise<Keypair> {
+    return reputableAgent(context, identityProgram, reputationProgram, authority, score);
+  }
+
+  function createReceipt(payer: Keypair, recipient: Keypair, creator = payer, signature = nextSignature().signature) {
+    const signatureHash = Array.from(createHash('sha256').update(signature).digest());
+    const receiptAddress = receiptPda(payer.publicKey, recipient.publicKey, signatureHash);
+    const rpc = voteProgram.methods
+      .createTransactionReceipt(signature, signatureHash, new BN(1_000_000), { apiResponse: {} }, PublicKey.default)
+      .accounts({
+        receipt: receiptAddress,
+        payerPubkey: payer.publicKey,
+        recipientPubkey: recipient.publicKey,
+        creator: creator.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+    return { receiptAddress, rpc };
+  }
+
+  function vote(receipt: PublicKey, voter: Keypair, votedAgent: Keypair, quality = QUALITY) {
+    return voteProgram.methods
+      .castPeerVote(votedAgent.publicKey, { upvote: {} }, quality, new Array(32).fill(0))
+      .accounts({
+        peerVote: PublicKey.findProgramAddressSync(
+          [Buffer.from('peer_vote'), receipt.toBuffer(), voter.publicKey.toBuffer()],
+          VOTE_PROGRAM_ID
+        )[0],
+        transactionReceipt: receipt,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(votedAgent.publicKey),
+        votedAgentReputation: reputationPda(votedAgent.publicKey),
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        voteConfig: voteConfigPda,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  function setPairLimits(cooldownSeconds: number, windowSeconds: number, maxVotes: number, signer = authority) {
+    return voteProgram.methods
+      .updatePairVoteLimits(new BN(cooldownSeconds), new BN(windowSeconds), maxVotes)
+      .accounts({ voteConfig: voteConfigPda, authorityAccount: voteAuthorityPda, authority: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function votePairPda(voter: Keypair, votedAgent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('vote_pair'), voter.publicKey.toBuffer(), votedAgent.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  /** The IDL's numeric code for a VoteError variant */
+  function errorCode(name: string): number {
+    const error = (voteProgram.idl.errors ?? []).find((e) => e.name === name);
+    if (!error) throw new Error(`VoteError::${name} is not in the IDL`);
+    return error.code;
+  }
+
+  /** Assert that `promise` fails with VoteError::`name` under its expected numeric code */
+  async function expectError(promise: Promise<unknown>, name: string, code: number) {
+    expect(errorCode(name)).toBe(code);
+    await expect(promise).rejects.toThrow(new RegExp(`Error Code: ${name}\\. Error Number: ${code}\\.`));
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: reputationAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VOTE_PROGRAM_ID);
+  });
+
+  test('reserved codes stay unused and the rest keep their numbers', () => {
+    const errors = voteProgram.idl.errors ?? [];
+    const codes = errors.map((e) => e.code);
+    const names = errors.map((e) => e.name);
+    expect(new Set(codes).size).toBe(codes.length);
+    for (const reserved of [6007, 6015, 6016, 6017]) expect(codes).not.toContain(reserved);
+    const removed = ['InsufficientEndorsementStake', 'AlreadyVoted', 'NotPartyToTransaction', 'VotedAgentMismatch'];
+    for (const name of removed) expect(names).not.toContain(name);
+
+    expect(errorCode('InactiveVoter')).toBe(6000);
+    expect(errorCode('InsufficientEndorserReputation')).toBe(6006);
+    expect(errorCode('MaxEndorsementsReached')).toBe(6008);
+    expect(errorCode('SelfTransactionNotAllowed')).toBe(6014);
+    expect(errorCode('VotingWindowExpired')).toBe(6018);
+    expect(errorCode('VoteAlreadyCast')).toBe(6019);
+    expect(errorCode('VoterNotPartyToTransaction')).toBe(6020);
+    expect(errorCode('VotedAgentNotCounterparty')).toBe(6021);
+  });
+
+  test('every variant is raised somewhere in the program', () => {
+    const sources: string[] = [];
+    const walk = (dir: string) => {
+      for (const entry of fs.readdirSync(dir, { withFileTypes: true })) {
+        const full = path.join(dir, entry.name);
+        if (entry.isDirectory()) walk(full);
+        else if (entry.name.endsWith('.rs') && entry.name !== 'error.rs') sources.push(fs.readFileSync(full, 'utf-8'));
+      }
+    };
+    walk('./programs/vote_registry/src');
+    const program = sources.join('\n');
+
+    const unraised = (voteProgram.idl.errors ?? [])
+      .map((e) => e.name)
+      .filter((name) => !new RegExp(`VoteError::${name}\\b`).test(program));
+    expect(unraised).toEqual([]);
+  });
+
+  test('receipt creation errors', async () => {
+    const [payer, seller, outsider] = [await votingAgent(), await votingAgent(), await votingAgent()];
+
+    await expectError(createReceipt(payer, seller, outsider).rpc, 'UnauthorizedReceiptCreation', 6013);
+    await expectError(createReceipt(payer, payer).rpc, 'SelfTransactionNotAllowed', 6014);
+    await expectError(createReceipt(payer, seller, payer, '1'.repeat(89)).rpc, 'InvalidX402Signature', 6004);
+  });
+
+  test('peer vote errors, including the canonical duplicates', async () => {
+    const [payer, seller, outsider] = [await votingAgent(), await votingAgent(), await votingAgent()];
+    const lowVoter = await votingAgent(50);
+
+    const receiptAddress = await transactionReceipt(voteProgram, payer, seller);
+    await expectError(vote(receiptAddress, outsider, seller), 'VoterNotPartyToTransaction', 6020);
+    await expectError(vote(receiptAddress, payer, outsider), 'VotedAgentNotCounterparty', 6021);
+    await expectError(
+      vote(receiptAddress, payer, seller, { ...QUALITY, accuracy: 101 }),
+      'InvalidQualityScore',
+      6002
+    );
+    await vote(receiptAddress, payer, seller);
+    await expectError(vote(receiptAddress, payer, seller), 'VoteAlreadyCast', 6019);
+
+    const receipt = await transactionReceipt(voteProgram, lowVoter, seller);
+    await expectError(vote(receipt, lowVoter, seller), 'InsufficientReputation', 6001);
+
+    const stale = await transactionReceipt(voteProgram, seller, payer);
+    await advanceTime(context, 30 * DAY + 1);
+    await expectError(vote(stale, seller, payer), 'VotingWindowExpired', 6018);
+  });
+});
@@ -170,7 +170,8 @@ describe('End-to-End: x402 Payment to Vote', () => {
         signature,
         signatureHash,
         new BN(transferAmount),
-        { chat: {} }
+        { chat: {} },
+        PublicKey.default
       )
       .accounts({
         receipt: receiptPda,
@@ -191,13 +192,14 @@ describe('End-to-End: x402 Payment to Vote', () => {
     expect((receipt.payer as PublicKey).toBase58()).toBe(buyer.publicKey.toBase58());
     expect((receipt.recipient as PublicKey).toBase58()).toBe(seller.publicKey.toBase58());
     expect((receipt.amount as BN).toNumber()).toBe(transferAmount);
-    expect(receipt.voteCast).toBe(false);
+    expect(receipt.payerVoted).toBe(false);
+    expect(receipt.recipientVoted).toBe(false);
 
     console.log('\n=== STEP 3: Buyer Votes on Seller ===');
 
     // Buyer votes on seller's service quality
     const [votePda] = PublicKey.findProgramAddressSync(
-      [Buffer.from('peer_vote'), receiptPda.toBuffer()],
+      [Buffer.from('peer_vote'), receiptPda.toBuffer(), buyer.publicKey.toBuffer()],
       program.programId
     );
 
@@ -266,7 +268,7 @@ describe('End-to-End: x402 Payment to Vote', () => {
     const updatedReceipt = await (program.account as any)['transactionReceipt'].fetch(
       receiptPda
     );
-    expect(updatedReceipt.voteCast).toBe(true);
+    expect(updatedReceipt.payerVoted).toBe(true);
 
     console.log('\n=== SUCCESS: Complete Flow Verified ===');
     console.log('Payment Amount:', transferAmount, 'lamports');
@@ -277,7 +279,7 @@ describe('End-to-End: x402 Payment to Vote', () => {
         qualityScores.accuracy +
         qualityScores.professionalism) / 4
     );
-    console.log('Receipt can no longer be used for voting:', updatedReceipt.voteCast);
+    console.log('Payer can no longer use this receipt to vote:', updatedReceipt.payerVoted);
   });
 
   test('seller can also vote on buyer (bidirectional)', async () => {
@@ -348,7 +350,8 @@ describe('End-to-End: x402 Payment to Vote', () => {
         signature,
         signatureHash,
         new BN(transferAmount),
-        { data: {} }
+        { data: {} },
+        PublicKey.default
       )
       .accounts({
         receipt: receiptPda,
@@ -362,7 +365,7 @@ describe('End-to-End: x402 Payment to Vote', () => {
 
     // Seller votes on buyer
     const [votePda] = PublicKey.findProgramAddressSync(
-      [Buffer.from('peer_vote'), receiptPda.toBuffer()],
+      [Buffer.from('peer_vote'), receiptPda.toBuffer(), seller.publicKey.toBuffer()],
       program.programId
     );
 
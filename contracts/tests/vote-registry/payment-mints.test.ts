@@ -0,0 +1,307 @@
+/**
+ * Payment Mint Tests
+ * Tests transaction receipts denominated in SPL tokens (e.g. USDC) as well as native SOL
+ *
+ * Mint-aware receipts ensure:
+ * 1. Receipts record the payment mint, with Pubkey::default() meaning native SOL
+ * 2. cast_peer_vote accepts receipts in native SOL and in SPL mints
+ * 3. Only the vote authority registers mint decimals in the VoteConfig, within bounds
+ * 4. A verified receipt cannot claim a different mint than the payer signed
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Ed25519Program, Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, reputableAgent } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+const PAYMENT_MESSAGE_DOMAIN = Buffer.from('ghostspeak:x402-receipt:v2');
+const USDC_MINT = new PublicKey('EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v');
+const QUALITY = { responseQuality: 80, responseSpeed: 80, accuracy: 80, professionalism: 80 };
+
+describe('Payment Mints', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let reputationAuthorityPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+  let voteConfigPda: PublicKey;
+  let receiptCount = 0;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function receiptPda(payer: PublicKey, recipient: PublicKey, signatureHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('tx_receipt'), payer.toBuffer(), recipient.toBuffer(), Buffer.from(signatureHash)],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  /** domain || payer || recipient || paymentMint || amount (u64 LE) || signatureHash */
+  function paymentMessage(
+    payer: PublicKey,
+    recipient: PublicKey,
+    paymentMint: PublicKey,
+    amount: number,
+    signatureHash: number[]
+  ): Buffer {
+    const amountBuffer = Buffer.alloc(8);
+    amountBuffer.writeBigUInt64LE(BigInt(amount));
+    return Buffer.concat([
+      PAYMENT_MESSAGE_DOMAIN,
+      payer.toBuffer(),
+      recipient.toBuffer(),
+      paymentMint.toBuffer(),
+      amountBuffer,
+      Buffer.from(signatureHash),
+    ]);
+  }
+
+  function nextSignature(): { signature: string; signatureHash: number[] } {
+    const signature = `mint_sig_${receiptCount++}`;
+    return { signature, signatureHash: Array.from(createHash('sha256').update(signature).digest()) };
+  }
+
+  /** A registered agent whose reputation score (500) allows it to vote */
+  function votingAgent(): Promise<Keypair> {
+    return reputableAgent(context, identityProgram, reputationProgram, authority);
+  }
+
+  /** Create a receipt for a payment of `amount` base units of `paymentMint` from `payer` to `recipient` */
+  async function receipt(
+    payer: Keypair,
+    recipient: Keypair,
+    amount: number,
+    paymentMint: PublicKey
+  ): Promise<PublicKey> {
+    const { signature, signatureHash } = nextSignature();
+    const receiptAddress = receiptPda(payer.publicKey, recipient.publicKey, signatureHash);
+    await voteProgram.methods
+      .createTransactionReceipt(signature, signatureHash, new BN(amount), { apiResponse: {} }, paymentMint)
+      .accounts({
+        receipt: receiptAddress,
+        payerPubkey: payer.publicKey,
+        recipientPubkey: recipient.publicKey,
+        creator: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+    return receiptAddress;
+  }
+
+  /** Create a verified receipt claiming `paymentMint`, with the payer's signature over `signedMint` */
+  async function verifiedReceipt(
+    payer: Keypair,
+    recipient: Keypair,
+    paymentMint: PublicKey,
+    signedMint = paymentMint
+  ): Promise<PublicKey> {
+    const { signature, signatureHash } = nextSignature();
+    const receiptAddress = receiptPda(payer.publicKey, recipient.publicKey, signatureHash);
+
+    await voteProgram.methods
+      .createVerifiedTransactionReceipt(
+        signature,
+        signatureHash,
+        new BN(1_000_000),
+        { apiResponse: {} },
+        paymentMint
+      )
+      .accounts({
+        receipt: receiptAddress,
+        payerPubkey: payer.publicKey,
+        recipientPubkey: recipient.publicKey,
+        creator: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: payer.secretKey,
+          message: paymentMessage(payer.publicKey, recipient.publicKey, signedMint, 1_000_000, signatureHash),
+        }),
+      ])
+      .signers([payer])
+      .rpc();
+    return receiptAddress;
+  }
+
+  function vote(receipt: PublicKey, voter: Keypair, votedAgent: Keypair) {
+    return voteProgram.methods
+      .castPeerVote(votedAgent.publicKey, { upvote: {} }, QUALITY, new Array(32).fill(0))
+      .accounts({
+        peerVote: PublicKey.findProgramAddressSync(
+          [Buffer.from('peer_vote'), receipt.toBuffer(), voter.publicKey.toBuffer()],
+          VOTE_PROGRAM_ID
+        )[0],
+        transactionReceipt: receipt,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(votedAgent.publicKey),
+        votedAgentReputation: reputationPda(votedAgent.publicKey),
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        voteConfig: voteConfigPda,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  function setMintDecimals(mint: PublicKey, decimals: number, signer = authority) {
+    return voteProgram.methods
+      .setPaymentMintDecimals(mint, decimals)
+      .accounts({ voteConfig: voteConfigPda, authorityAccount: voteAuthorityPda, authority: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: reputationAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeVoteConfig()
+      .accounts({
+        voteConfig: voteConfigPda,
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('a SOL receipt records the native mint', async () => {
+    const [payer, recipient] = [await votingAgent(), await votingAgent()];
+    const receiptAddress = await receipt(payer, recipient, 5_000_000, PublicKey.default);
+
+    const account = await fetchAccount(voteProgram, 'transactionReceipt', receiptAddress);
+    expect(account.paymentMint.toBase58()).toBe(PublicKey.default.toBase58());
+    expect(account.amount.toNumber()).toBe(5_000_000);
+
+    await vote(receiptAddress, payer, recipient);
+  });
+
+  test('a USDC receipt records the mint and amount in base units', async () => {
+    const [payer, recipient] = [await votingAgent(), await votingAgent()];
+    await setMintDecimals(USDC_MINT, 6);
+
+    // 0.25 USDC
+    const receiptAddress = await receipt(payer, recipient, 250_000, USDC_MINT);
+
+    const account = await fetchAccount(voteProgram, 'transactionReceipt', receiptAddress);
+    expect(account.paymentMint.toBase58()).toBe(USDC_MINT.toBase58());
+    expect(account.amount.toNumber()).toBe(250_000);
+
+    await vote(receiptAddress, payer, recipient);
+    await vote(receiptAddress, recipient, payer);
+  });
+
+  test('votes are accepted for mints without registered decimals', async () => {
+    const [payer, recipient] = [await votingAgent(), await votingAgent()];
+    const receiptAddress = await receipt(payer, recipient, 1_000, Keypair.generate().publicKey);
+
+    await vote(receiptAddress, payer, recipient);
+  });
+
+  test('only the authority registers mint decimals, within bounds', async () => {
+    const outsider = await votingAgent();
+    const mint = Keypair.generate().publicKey;
+
+    await expect(setMintDecimals(mint, 6, outsider)).rejects.toThrow(/UnauthorizedAuthority/);
+    await expect(setMintDecimals(PublicKey.default, 9)).rejects.toThrow(/NativePaymentMint/);
+    await expect(setMintDecimals(mint, 19)).rejects.toThrow(/InvalidMintDecimals/);
+
+    await setMintDecimals(mint, 6);
+    await setMintDecimals(mint, 8);
+    const config = await fetchAccount(voteProgram, 'voteConfig', voteConfigPda);
+    const scales = config.paymentMints.filter((scale: { mint: PublicKey }) => scale.mint.equals(mint));
+    expect(scales).toHaveLength(1);
+    expect(scales[0].decimals).toBe(8);
+  });
+
+  test('at most eight payment mints can be registered', async () => {
+    const config = await fetchAccount(voteProgram, 'voteConfig', voteConfigPda);
+    for (let i = config.paymentMints.length; i < 8; i++) {
+      await setMintDecimals(Keypair.generate().publicKey, 6);
+    }
+
+    await expect(setMintDecimals(Keypair.generate().publicKey, 6)).rejects.toThrow(/TooManyPaymentMints/);
+    // Updating an already registered mint still works
+    await setMintDecimals(USDC_MINT, 6);
+  });
+
+  test('a verified receipt must claim the mint the payer signed', async () => {
+    const [payer, recipient] = [await votingAgent(), await votingAgent()];
+
+    // Signed for a SOL payment, claimed as USDC
+    await expect(verifiedReceipt(payer, recipient, USDC_MINT, PublicKey.default)).rejects.toThrow(
+      /PaymentSignatureMismatch/
+    );
+
+    const receiptAddress = await verifiedReceipt(payer, recipient, USDC_MINT);
+    const account = await fetchAccount(voteProgram, 'transactionReceipt', receiptAddress);
+    expect(account.verified).toBe(true);
+    expect(account.paymentMint.toBase58()).toBe(USDC_MINT.toBase58());
+  });
+});
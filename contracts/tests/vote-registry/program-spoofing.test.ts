@@ -0,0 +1,313 @@
+/**
+ * External Program Spoofing Tests
+ * Tests that cast_peer_vote, rate_content and endorse_agent only trust the real
+ * identity_registry and reputation_registry programs
+ *
+ * Pinning ensures:
+ * 1. Look-alike identity/reputation PDAs derived under an attacker program are rejected
+ * 2. Attacker program IDs passed as identity_registry_program / reputation_registry_program are rejected
+ * 3. Accounts at the expected addresses but owned by another program are rejected
+ * 4. Legitimate votes, ratings and endorsements still pass
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { airdrop, advanceTime, loadProgram, metadataHash, mockCoreAsset, now } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+const ATTACKER_PROGRAM_ID = Keypair.generate().publicKey;
+
+const QUALITY = { responseQuality: 80, responseSpeed: 80, accuracy: 80, professionalism: 80 };
+
+describe('External Program Spoofing', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let voter: Keypair;
+  let agent: Keypair;
+  let authorityPda: PublicKey;
+  let receiptCount = 0;
+
+  function identityPda(wallet: PublicKey, programId = IDENTITY_PROGRAM_ID): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], programId)[0];
+  }
+
+  function reputationPda(wallet: PublicKey, programId = REPUTATION_PROGRAM_ID): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('reputation'), wallet.toBuffer()], programId)[0];
+  }
+
+  async function registerWithReputation(wallet: Keypair) {
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        agent: wallet.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda(wallet.publicKey),
+        agentAddress: wallet.publicKey,
+        authorityAccount: null,
+        initializer: wallet.publicKey,
+        payer: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+  }
+
+  /** Copy a real account's data to `address` under `owner`, like a look-alike account the attacker controls */
+  async function cloneAccount(source: PublicKey, address: PublicKey, owner: PublicKey) {
+    const account = (await context.banksClient.getAccount(source))!;
+    context.setAccount(address, { lamports: account.lamports, data: account.data, owner, executable: false });
+  }
+
+  async function receipt(): Promise<PublicKey> {
+    const signature = `spoof_sig_${receiptCount++}`;
+    const signatureHash = Array.from(createHash('sha256').update(signature).digest());
+    const [receiptPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('tx_receipt'),
+        voter.publicKey.toBuffer(),
+        agent.publicKey.toBuffer(),
+        Buffer.from(signatureHash),
+      ],
+      VOTE_PROGRAM_ID
+    );
+    await voteProgram.methods
+      .createTransactionReceipt(signature, signatureHash, new BN(1_000_000), { apiResponse: {} }, PublicKey.default)
+      .accounts({
+        receipt: receiptPda,
+        payerPubkey: voter.publicKey,
+        recipientPubkey: agent.publicKey,
+        creator: voter.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+    return receiptPda;
+  }
+
+  async function vote(overrides: Record<string, PublicKey> = {}) {
+    const receiptPda = await receipt();
+    const [votePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('peer_vote'), receiptPda.toBuffer(), voter.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    );
+    return voteProgram.methods
+      .castPeerVote(agent.publicKey, { upvote: {} }, QUALITY, new Array(32).fill(0))
+      .accounts({
+        peerVote: votePda,
+        transactionReceipt: receiptPda,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(agent.publicKey),
+        votedAgentReputation: reputationPda(agent.publicKey),
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        ...overrides,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  async function rate(overrides: Record<string, PublicKey> = {}) {
+    const receiptPda = await receipt();
+    const [ratingPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('content_rating'), receiptPda.toBuffer()],
+      VOTE_PROGRAM_ID
+    );
+    return voteProgram.methods
+      .rateContent(90)
+      .accounts({
+        contentRating: ratingPda,
+        transactionReceipt: receiptPda,
+        raterIdentity: identityPda(voter.publicKey),
+        raterReputation: reputationPda(voter.publicKey),
+        ratedAgentIdentity: identityPda(agent.publicKey),
+        ratedAgent: agent.publicKey,
+        ratedAgentReputation: reputationPda(agent.publicKey),
+        rater: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        ...overrides,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  function endorse(endorser: Keypair, overrides: Record<string, PublicKey> = {}) {
+    const [endorsementPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement'), endorser.publicKey.toBuffer(), agent.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    );
+    return voteProgram.methods
+      .endorseAgent(agent.publicKey, 80, { technical: {} })
+      .accounts({
+        endorserStats: PublicKey.findProgramAddressSync(
+          [Buffer.from('endorsement_stats'), endorser.publicKey.toBuffer()],
+          VOTE_PROGRAM_ID
+        )[0],
+        endorsedStats: PublicKey.findProgramAddressSync(
+          [Buffer.from('endorsement_stats'), agent.publicKey.toBuffer()],
+          VOTE_PROGRAM_ID
+        )[0],
+        endorsement: endorsementPda,
+        endorserIdentity: identityPda(endorser.publicKey),
+        endorserReputation: reputationPda(endorser.publicKey),
+        endorsedAgentIdentity: identityPda(agent.publicKey),
+        endorser: endorser.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        ...overrides,
+      })
+      .signers([endorser])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    voter = Keypair.generate();
+    agent = Keypair.generate();
+    for (const kp of [authority, voter, agent]) {
+      await airdrop(context, kp.publicKey, 10_000_000_000);
+    }
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await registerWithReputation(voter);
+    await registerWithReputation(agent);
+
+    // Voter needs 100 to vote and 500 to endorse
+    await advanceTime(context, 10);
+    await reputationProgram.methods
+      .updateReputation(
+        900,
+        { trust: 90, quality: 90, reliability: 90, economic: 90, social: 90 },
+        { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+        new Array(32).fill(0),
+        new BN(0),
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda(voter.publicKey),
+        authorityAccount: authorityPda,
+        agentAddress: voter.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('look-alike accounts derived under an attacker program are rejected', async () => {
+    // The attacker holds no real identity; it fabricates one under its own program
+    const attacker = Keypair.generate();
+    await airdrop(context, attacker.publicKey, 10_000_000_000);
+    const fakeIdentity = identityPda(attacker.publicKey, ATTACKER_PROGRAM_ID);
+    const fakeReputation = reputationPda(attacker.publicKey, ATTACKER_PROGRAM_ID);
+    await cloneAccount(identityPda(voter.publicKey), fakeIdentity, ATTACKER_PROGRAM_ID);
+    await cloneAccount(reputationPda(voter.publicKey), fakeReputation, ATTACKER_PROGRAM_ID);
+
+    await expect(
+      endorse(attacker, {
+        endorserIdentity: fakeIdentity,
+        endorserReputation: fakeReputation,
+        identityRegistryProgram: ATTACKER_PROGRAM_ID,
+        reputationRegistryProgram: ATTACKER_PROGRAM_ID,
+      })
+    ).rejects.toThrow(/ConstraintSeeds/);
+  });
+
+  test('attacker program IDs are rejected by every instruction', async () => {
+    await expect(vote({ identityRegistryProgram: ATTACKER_PROGRAM_ID })).rejects.toThrow(/InvalidIdentityProgram/);
+    await expect(vote({ reputationRegistryProgram: ATTACKER_PROGRAM_ID })).rejects.toThrow(
+      /InvalidReputationProgram/
+    );
+    await expect(rate({ identityRegistryProgram: ATTACKER_PROGRAM_ID })).rejects.toThrow(
+      /InvalidIdentityProgram/
+    );
+    await expect(rate({ reputationRegistryProgram: ATTACKER_PROGRAM_ID })).rejects.toThrow(
+      /InvalidReputationProgram/
+    );
+    await expect(endorse(voter, { identityRegistryProgram: ATTACKER_PROGRAM_ID })).rejects.toThrow(
+      /InvalidIdentityProgram/
+    );
+    await expect(endorse(voter, { reputationRegistryProgram: ATTACKER_PROGRAM_ID })).rejects.toThrow(
+      /InvalidReputationProgram/
+    );
+  });
+
+  test('accounts at the expected address but owned by another program are rejected', async () => {
+    // Simulates fabricated is_active / overall_score data sitting at the real PDA addresses
+    const impostor = Keypair.generate();
+    await airdrop(context, impostor.publicKey, 10_000_000_000);
+    await cloneAccount(identityPda(voter.publicKey), identityPda(impostor.publicKey), ATTACKER_PROGRAM_ID);
+    await cloneAccount(reputationPda(voter.publicKey), reputationPda(impostor.publicKey), ATTACKER_PROGRAM_ID);
+
+    await expect(endorse(impostor)).rejects.toThrow(/InvalidAccountOwner/);
+  });
+
+  test('legitimate votes, ratings and endorsements still pass', async () => {
+    await vote();
+    await rate();
+    await endorse(voter);
+
+    const [endorsementPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('endorsement'), voter.publicKey.toBuffer(), agent.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    );
+    expect(await context.banksClient.getAccount(endorsementPda)).not.toBeNull();
+  });
+});
@@ -0,0 +1,304 @@
+/**
+ * Receipt Confirmation Tests
+ * Tests counterparty confirmation and rejection of transaction receipts
+ *
+ * Dual attestation ensures:
+ * 1. With require_confirmed_receipts on, unconfirmed receipts cannot be voted on until the counterparty confirms
+ * 2. Receipts verified by the payer's Ed25519 signature count as confirmed
+ * 3. A counterparty rejection blocks votes on the receipt permanently
+ * 4. Only the party that did not create the receipt can confirm or reject it
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Ed25519Program, Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, reputableAgent } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+const PAYMENT_MESSAGE_DOMAIN = Buffer.from('ghostspeak:x402-receipt:v2');
+const QUALITY = { responseQuality: 80, responseSpeed: 80, accuracy: 80, professionalism: 80 };
+
+describe('Receipt Confirmation', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let reputationAuthorityPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+  let voteConfigPda: PublicKey;
+  let receiptCount = 0;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function receiptPda(payer: PublicKey, recipient: PublicKey, signatureHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('tx_receipt'), payer.toBuffer(), recipient.toBuffer(), Buffer.from(signatureHash)],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  /** domain || payer || recipient || paymentMint || amount (u64 LE) || signatureHash */
+  function paymentMessage(
+    payer: PublicKey,
+    recipient: PublicKey,
+    paymentMint: PublicKey,
+    amount: number,
+    signatureHash: number[]
+  ): Buffer {
+    const amountBuffer = Buffer.alloc(8);
+    amountBuffer.writeBigUInt64LE(BigInt(amount));
+    return Buffer.concat([
+      PAYMENT_MESSAGE_DOMAIN,
+      payer.toBuffer(),
+      recipient.toBuffer(),
+      paymentMint.toBuffer(),
+      amountBuffer,
+      Buffer.from(signatureHash),
+    ]);
+  }
+
+  function nextSignature(): { signature: string; signatureHash: number[] } {
+    const signature = `confirmation_sig_${receiptCount++}`;
+    return { signature, signatureHash: Array.from(createHash('sha256').update(signature).digest()) };
+  }
+
+  /** A registered agent whose reputation score (500) allows it to vote */
+  function votingAgent(): Promise<Keypair> {
+    return reputableAgent(context, identityProgram, reputationProgram, authority);
+  }
+
+  /** Create a receipt backed by the payer's Ed25519 signature over the payment message */
+  async function verifiedReceipt(payer: Keypair, recipient: Keypair): Promise<PublicKey> {
+    const { signature, signatureHash } = nextSignature();
+    const receipt = receiptPda(payer.publicKey, recipient.publicKey, signatureHash);
+
+    await voteProgram.methods
+      .createVerifiedTransactionReceipt(
+        signature,
+        signatureHash,
+        new BN(1_000_000),
+        { apiResponse: {} },
+        PublicKey.default
+      )
+      .accounts({
+        receipt,
+        payerPubkey: payer.publicKey,
+        recipientPubkey: recipient.publicKey,
+        creator: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: payer.secretKey,
+          message: paymentMessage(payer.publicKey, recipient.publicKey, PublicKey.default, 1_000_000, signatureHash),
+        }),
+      ])
+      .signers([payer])
+      .rpc();
+    return receipt;
+  }
+
+  /** Create an unverified receipt for a payment from `payer` to `recipient`, created by `creator` */
+  async function unverifiedReceipt(payer: Keypair, recipient: Keypair, creator = payer): Promise<PublicKey> {
+    const { signature, signatureHash } = nextSignature();
+    const receipt = receiptPda(payer.publicKey, recipient.publicKey, signatureHash);
+    await voteProgram.methods
+      .createTransactionReceipt(signature, signatureHash, new BN(1_000_000), { apiResponse: {} }, PublicKey.default)
+      .accounts({
+        receipt,
+        payerPubkey: payer.publicKey,
+        recipientPubkey: recipient.publicKey,
+        creator: creator.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+    return receipt;
+  }
+
+  function confirm(receipt: PublicKey, signer: Keypair) {
+    return voteProgram.methods
+      .confirmReceipt()
+      .accounts({ receipt, counterparty: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function reject(receipt: PublicKey, signer: Keypair) {
+    return voteProgram.methods
+      .rejectReceipt()
+      .accounts({ receipt, counterparty: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function vote(receipt: PublicKey, voter: Keypair, votedAgent: Keypair) {
+    return voteProgram.methods
+      .castPeerVote(votedAgent.publicKey, { upvote: {} }, QUALITY, new Array(32).fill(0))
+      .accounts({
+        peerVote: PublicKey.findProgramAddressSync(
+          [Buffer.from('peer_vote'), receipt.toBuffer(), voter.publicKey.toBuffer()],
+          VOTE_PROGRAM_ID
+        )[0],
+        transactionReceipt: receipt,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(votedAgent.publicKey),
+        votedAgentReputation: reputationPda(votedAgent.publicKey),
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        voteConfig: voteConfigPda,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  function setStrictMode(required: boolean) {
+    return voteProgram.methods
+      .updateRequireConfirmedReceipts(required)
+      .accounts({ voteConfig: voteConfigPda, authorityAccount: voteAuthorityPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: reputationAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeVoteConfig()
+      .accounts({
+        voteConfig: voteConfigPda,
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('an unconfirmed receipt blocks voting in strict mode until the counterparty confirms', async () => {
+    const [payer, recipient] = [await votingAgent(), await votingAgent()];
+    const receipt = await unverifiedReceipt(payer, recipient);
+
+    let account = await fetchAccount(voteProgram, 'transactionReceipt', receipt);
+    expect(account.creator.toBase58()).toBe(payer.publicKey.toBase58());
+    expect(account.counterpartyConfirmed).toBe(false);
+
+    await setStrictMode(true);
+    try {
+      await expect(vote(receipt, payer, recipient)).rejects.toThrow(/ReceiptNotConfirmed/);
+
+      await confirm(receipt, recipient);
+      account = await fetchAccount(voteProgram, 'transactionReceipt', receipt);
+      expect(account.counterpartyConfirmed).toBe(true);
+
+      await vote(receipt, payer, recipient);
+      await expect(confirm(receipt, recipient)).rejects.toThrow(/ReceiptAlreadyConfirmed/);
+    } finally {
+      await setStrictMode(false);
+    }
+  });
+
+  test('a verified receipt is confirmed without a counterparty signature', async () => {
+    const [payer, recipient] = [await votingAgent(), await votingAgent()];
+    const receipt = await verifiedReceipt(payer, recipient);
+
+    const account = await fetchAccount(voteProgram, 'transactionReceipt', receipt);
+    expect(account.counterpartyConfirmed).toBe(true);
+
+    await setStrictMode(true);
+    try {
+      await vote(receipt, recipient, payer);
+    } finally {
+      await setStrictMode(false);
+    }
+  });
+
+  test('a rejected receipt is permanently blocked', async () => {
+    const [payer, recipient] = [await votingAgent(), await votingAgent()];
+    // Created by the recipient, so the payer is the counterparty
+    const receipt = await unverifiedReceipt(payer, recipient, recipient);
+
+    await reject(receipt, payer);
+    const account = await fetchAccount(voteProgram, 'transactionReceipt', receipt);
+    expect(account.isDisputed).toBe(true);
+
+    // Blocked even with strict mode off, and cannot be confirmed afterwards
+    await expect(vote(receipt, recipient, payer)).rejects.toThrow(/ReceiptDisputed/);
+    await expect(vote(receipt, payer, recipient)).rejects.toThrow(/ReceiptDisputed/);
+    await expect(confirm(receipt, payer)).rejects.toThrow(/ReceiptDisputed/);
+  });
+
+  test('the creator cannot confirm or reject its own receipt', async () => {
+    const [payer, recipient, outsider] = [await votingAgent(), await votingAgent(), await votingAgent()];
+    const receipt = await unverifiedReceipt(payer, recipient);
+
+    await expect(confirm(receipt, payer)).rejects.toThrow(/CreatorCannotConfirm/);
+    await expect(reject(receipt, payer)).rejects.toThrow(/CreatorCannotConfirm/);
+    await expect(confirm(receipt, outsider)).rejects.toThrow(/NotReceiptCounterparty/);
+
+    const account = await fetchAccount(voteProgram, 'transactionReceipt', receipt);
+    expect(account.counterpartyConfirmed).toBe(false);
+    expect(account.isDisputed).toBe(false);
+  });
+});
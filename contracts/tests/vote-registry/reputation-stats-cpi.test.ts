@@ -0,0 +1,286 @@
+/**
+ * Reputation Stats CPI Tests
+ * Tests that votes and content ratings update reputation stats atomically
+ *
+ * The CPI path ensures:
+ * 1. cast_peer_vote increments the voted agent's vote counters
+ * 2. rate_content records a review and recomputes the average rating
+ * 3. record_vote_result / record_review reject direct (non-CPI) calls
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, metadataHash, mockCoreAsset, now } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+const QUALITY = { responseQuality: 80, responseSpeed: 80, accuracy: 80, professionalism: 80 };
+
+describe('Reputation Stats CPI', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let voter: Keypair;
+  let agent: Keypair;
+  let authorityPda: PublicKey;
+  let cpiAuthorityPda: PublicKey;
+  let receiptCount = 0;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  async function registerWithReputation(wallet: Keypair) {
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        agent: wallet.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda(wallet.publicKey),
+        agentAddress: wallet.publicKey,
+        authorityAccount: null,
+        initializer: wallet.publicKey,
+        payer: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+  }
+
+  async function receipt(): Promise<PublicKey> {
+    const signature = `sig_${receiptCount++}`;
+    const signatureHash = Array.from(createHash('sha256').update(signature).digest());
+    const [receiptPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('tx_receipt'),
+        voter.publicKey.toBuffer(),
+        agent.publicKey.toBuffer(),
+        Buffer.from(signatureHash),
+      ],
+      VOTE_PROGRAM_ID
+    );
+
+    await voteProgram.methods
+      .createTransactionReceipt(signature, signatureHash, new BN(1_000_000), { apiResponse: {} }, PublicKey.default)
+      .accounts({
+        receipt: receiptPda,
+        payerPubkey: voter.publicKey,
+        recipientPubkey: agent.publicKey,
+        creator: voter.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+    return receiptPda;
+  }
+
+  async function vote(voteType: object) {
+    const receiptPda = await receipt();
+    const [votePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('peer_vote'), receiptPda.toBuffer(), voter.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    );
+
+    await voteProgram.methods
+      .castPeerVote(agent.publicKey, voteType, QUALITY, new Array(32).fill(0))
+      .accounts({
+        peerVote: votePda,
+        transactionReceipt: receiptPda,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(agent.publicKey),
+        votedAgentReputation: reputationPda(agent.publicKey),
+        reputationCpiAuthority: cpiAuthorityPda,
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  async function rate(qualityRating: number) {
+    const receiptPda = await receipt();
+    const [ratingPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('content_rating'), receiptPda.toBuffer()],
+      VOTE_PROGRAM_ID
+    );
+
+    await voteProgram.methods
+      .rateContent(qualityRating)
+      .accounts({
+        contentRating: ratingPda,
+        transactionReceipt: receiptPda,
+        raterIdentity: identityPda(voter.publicKey),
+        raterReputation: reputationPda(voter.publicKey),
+        ratedAgentIdentity: identityPda(agent.publicKey),
+        ratedAgent: agent.publicKey,
+        ratedAgentReputation: reputationPda(agent.publicKey),
+        reputationCpiAuthority: cpiAuthorityPda,
+        rater: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  async function agentStats() {
+    const reputation = await fetchAccount(reputationProgram, 'agentReputation', reputationPda(agent.publicKey));
+    return reputation.stats;
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    voter = Keypair.generate();
+    agent = Keypair.generate();
+    for (const kp of [authority, voter, agent]) {
+      await airdrop(context, kp.publicKey, 10_000_000_000);
+    }
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    [cpiAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('reputation_cpi')], VOTE_PROGRAM_ID);
+
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await registerWithReputation(voter);
+    await registerWithReputation(agent);
+
+    // Voter needs a score >= 100 to vote
+    await advanceTime(context, 10);
+    await reputationProgram.methods
+      .updateReputation(
+        500,
+        { trust: 50, quality: 50, reliability: 50, economic: 50, social: 50 },
+        { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+        new Array(32).fill(0),
+        new BN(0),
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda(voter.publicKey),
+        authorityAccount: authorityPda,
+        agentAddress: voter.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('each vote increments the matching counters', async () => {
+    await vote({ upvote: {} });
+    await vote({ downvote: {} });
+    await vote({ neutral: {} });
+
+    const stats = await agentStats();
+    expect(stats.totalVotes).toBe(3);
+    expect(stats.positiveVotes).toBe(1);
+    expect(stats.negativeVotes).toBe(1);
+  });
+
+  test('content ratings recompute the average review rating', async () => {
+    await rate(100); // 50
+    let stats = await agentStats();
+    expect(stats.totalReviews).toBe(1);
+    expect(stats.avgReviewRating).toBe(50);
+
+    await rate(80); // 40 -> avg 45
+    stats = await agentStats();
+    expect(stats.avgReviewRating).toBe(45);
+
+    await rate(61); // 31 -> (50 + 40 + 31) / 3 = 40.33
+    stats = await agentStats();
+    expect(stats.totalReviews).toBe(3);
+    expect(stats.avgReviewRating).toBe(40);
+  });
+
+  test('direct calls without the vote_registry PDA signature are rejected', async () => {
+    const impostor = Keypair.generate();
+
+    await expect(
+      reputationProgram.methods
+        .recordVoteResult({ positive: {} })
+        .accounts({
+          agentReputation: reputationPda(agent.publicKey),
+          cpiAuthority: impostor.publicKey,
+        })
+        .signers([impostor])
+        .rpc()
+    ).rejects.toThrow(/ConstraintSeeds/);
+
+    await expect(
+      reputationProgram.methods
+        .recordReview(50)
+        .accounts({
+          agentReputation: reputationPda(agent.publicKey),
+          cpiAuthority: impostor.publicKey,
+        })
+        .signers([impostor])
+        .rpc()
+    ).rejects.toThrow(/ConstraintSeeds/);
+
+    const stats = await agentStats();
+    expect(stats.totalVotes).toBe(3);
+    expect(stats.totalReviews).toBe(3);
+  });
+});
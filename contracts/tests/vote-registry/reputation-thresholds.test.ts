@@ -0,0 +1,259 @@
+/**
+ * Reputation Threshold and Voting Window Tests
+ * Tests the VoteConfig parameters that replace the hard-coded anti-sybil thresholds
+ *
+ * Configurable thresholds ensure:
+ * 1. Without a vote config the legacy thresholds apply (vote >= 100, any reputation rates)
+ * 2. min_voter_reputation and min_rater_reputation gate cast_peer_vote and rate_content
+ * 3. voting_window_seconds shortens the 30-day window, so older receipts are rejected
+ * 4. Only the vote authority can change them, and only to values within range
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, transactionReceipt, reputableAgent } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+const DAY = 24 * 60 * 60;
+const QUALITY = { responseQuality: 80, responseSpeed: 80, accuracy: 80, professionalism: 80 };
+
+describe('Reputation Thresholds and Voting Window', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let reputationAuthorityPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+  let voteConfigPda: PublicKey;
+  let cpiAuthorityPda: PublicKey;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  /** A registered agent with the given reputation score */
+  function votingAgent(score?: number): Promise<Keypair> {
+    return reputableAgent(context, identityProgram, reputationProgram, authority, score);
+  }
+
+  function vote(receipt: PublicKey, voter: Keypair, votedAgent: Keypair) {
+    return voteProgram.methods
+      .castPeerVote(votedAgent.publicKey, { upvote: {} }, QUALITY, new Array(32).fill(0))
+      .accounts({
+        peerVote: PublicKey.findProgramAddressSync(
+          [Buffer.from('peer_vote'), receipt.toBuffer(), voter.publicKey.toBuffer()],
+          VOTE_PROGRAM_ID
+        )[0],
+        transactionReceipt: receipt,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(votedAgent.publicKey),
+        votedAgentReputation: reputationPda(votedAgent.publicKey),
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        voteConfig: voteConfigPda,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  function setPairLimits(cooldownSeconds: number, windowSeconds: number, maxVotes: number, signer = authority) {
+    return voteProgram.methods
+      .updatePairVoteLimits(new BN(cooldownSeconds), new BN(windowSeconds), maxVotes)
+      .accounts({ voteConfig: voteConfigPda, authorityAccount: voteAuthorityPda, authority: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function votePairPda(voter: Keypair, votedAgent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('vote_pair'), voter.publicKey.toBuffer(), votedAgent.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function rate(receipt: PublicKey, rater: Keypair, ratedAgent: Keypair) {
+    return voteProgram.methods
+      .rateContent(90)
+      .accounts({
+        contentRating: PublicKey.findProgramAddressSync(
+          [Buffer.from('content_rating'), receipt.toBuffer()],
+          VOTE_PROGRAM_ID
+        )[0],
+        transactionReceipt: receipt,
+        raterIdentity: identityPda(rater.publicKey),
+        raterReputation: reputationPda(rater.publicKey),
+        ratedAgentIdentity: identityPda(ratedAgent.publicKey),
+        ratedAgent: ratedAgent.publicKey,
+        ratedAgentReputation: reputationPda(ratedAgent.publicKey),
+        reputationCpiAuthority: cpiAuthorityPda,
+        rater: rater.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        voteConfig: voteConfigPda,
+      })
+      .signers([rater])
+      .rpc();
+  }
+
+  function updateThresholds(minVoter: number, minEndorser: number, minRater: number, signer = authority) {
+    return voteProgram.methods
+      .updateReputationThresholds(minVoter, minEndorser, minRater)
+      .accounts({ voteConfig: voteConfigPda, authorityAccount: voteAuthorityPda, authority: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function updateVotingWindow(seconds: number, signer = authority) {
+    return voteProgram.methods
+      .updateVotingWindow(new BN(seconds))
+      .accounts({ voteConfig: voteConfigPda, authorityAccount: voteAuthorityPda, authority: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: reputationAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VOTE_PROGRAM_ID);
+    [cpiAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('reputation_cpi')], VOTE_PROGRAM_ID);
+  });
+
+  test('without a vote config the legacy thresholds apply', async () => {
+    const seller = await votingAgent();
+    const lowVoter = await votingAgent(99);
+    const voter = await votingAgent(100);
+    const newcomer = await votingAgent(0);
+
+    const receipt = await transactionReceipt(voteProgram, lowVoter, seller);
+    await expect(vote(receipt, lowVoter, seller)).rejects.toThrow(/InsufficientReputation/);
+    await vote(await transactionReceipt(voteProgram, voter, seller), voter, seller);
+
+    // Rating has no reputation floor by default
+    await rate(await transactionReceipt(voteProgram, newcomer, seller), newcomer, seller);
+  });
+
+  test('raising min_voter_reputation to 200 rejects a 150-rep voter', async () => {
+    await voteProgram.methods
+      .initializeVoteConfig()
+      .accounts({
+        voteConfig: voteConfigPda,
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    const config = await fetchAccount(voteProgram, 'voteConfig', voteConfigPda);
+    expect(config.minVoterReputation).toBe(100);
+    expect(config.minEndorserReputation).toBe(500);
+    expect(config.minRaterReputation).toBe(0);
+    expect(config.votingWindowSeconds.toNumber()).toBe(30 * DAY);
+
+    await updateThresholds(200, 500, 0);
+
+    const seller = await votingAgent();
+    const voter = await votingAgent(150);
+    const trustedVoter = await votingAgent(250);
+    const receipt = await transactionReceipt(voteProgram, voter, seller);
+    await expect(vote(receipt, voter, seller)).rejects.toThrow(/InsufficientReputation/);
+    await vote(await transactionReceipt(voteProgram, trustedVoter, seller), trustedVoter, seller);
+  });
+
+  test('min_rater_reputation gates rate_content', async () => {
+    await updateThresholds(200, 500, 300);
+
+    const seller = await votingAgent();
+    const rater = await votingAgent(250);
+    const trustedRater = await votingAgent(300);
+    const receipt = await transactionReceipt(voteProgram, rater, seller);
+    await expect(rate(receipt, rater, seller)).rejects.toThrow(/InsufficientReputation/);
+    await rate(await transactionReceipt(voteProgram, trustedRater, seller), trustedRater, seller);
+
+    await updateThresholds(100, 500, 0);
+  });
+
+  test('lowering the voting window rejects older receipts', async () => {
+    await updateVotingWindow(DAY);
+
+    const seller = await votingAgent();
+    const [voter, lateVoter] = [await votingAgent(), await votingAgent()];
+    const fresh = await transactionReceipt(voteProgram, voter, seller);
+    const old = await transactionReceipt(voteProgram, lateVoter, seller);
+
+    await advanceTime(context, DAY - 60);
+    await vote(fresh, voter, seller);
+
+    await advanceTime(context, 120);
+    await expect(vote(old, lateVoter, seller)).rejects.toThrow(/VotingWindowExpired/);
+  });
+
+  test('only the authority can change thresholds, and only within range', async () => {
+    const outsider = await votingAgent();
+
+    await expect(updateThresholds(150, 500, 0, outsider)).rejects.toThrow(/UnauthorizedAuthority/);
+    await expect(updateVotingWindow(2 * DAY, outsider)).rejects.toThrow(/UnauthorizedAuthority/);
+    await expect(updateThresholds(1001, 500, 0)).rejects.toThrow(/InvalidReputationThreshold/);
+    await expect(updateVotingWindow(0)).rejects.toThrow(/InvalidVotingWindow/);
+    await expect(updateVotingWindow(30 * DAY + 1)).rejects.toThrow(/InvalidVotingWindow/);
+  });
+});
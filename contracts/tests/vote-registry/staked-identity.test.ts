@@ -0,0 +1,289 @@
+/**
+ * Staked Identity Tests
+ * Tests that vote_registry reads identities that hold identity_registry collateral
+ *
+ * Staked identities ensure:
+ * 1. A voter and voted agent that staked collateral can cast a peer vote
+ * 2. Staked raters and endorsers pass the same identity checks
+ * 3. vote_registry reads only the identity fields it needs, so later staking fields do not break it
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, metadataHash, mockCoreAsset, now } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const STAKE = LAMPORTS_PER_SOL;
+
+const QUALITY = { responseQuality: 80, responseSpeed: 80, accuracy: 80, professionalism: 80 };
+
+describe('Staked Identity', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let voter: Keypair;
+  let agent: Keypair;
+  let authorityPda: PublicKey;
+  let stakingPoolPda: PublicKey;
+  let cpiAuthorityPda: PublicKey;
+  let receiptCount = 0;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function votePda(seeds: Buffer[]): PublicKey {
+    return PublicKey.findProgramAddressSync(seeds, VOTE_PROGRAM_ID)[0];
+  }
+
+  /** Register through identity_registry, stake collateral and open a reputation account */
+  async function registerStaked(wallet: Keypair) {
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        agent: wallet.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await identityProgram.methods
+      .stakeCollateral(new BN(STAKE))
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        stakingPool: stakingPoolPda,
+        agent: wallet.publicKey,
+        agentAddress: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda(wallet.publicKey),
+        agentAddress: wallet.publicKey,
+        authorityAccount: null,
+        initializer: wallet.publicKey,
+        payer: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+  }
+
+  async function receipt(): Promise<PublicKey> {
+    const signature = `staked_sig_${receiptCount++}`;
+    const signatureHash = Array.from(createHash('sha256').update(signature).digest());
+    const receiptPda = votePda([
+      Buffer.from('tx_receipt'),
+      voter.publicKey.toBuffer(),
+      agent.publicKey.toBuffer(),
+      Buffer.from(signatureHash),
+    ]);
+
+    await voteProgram.methods
+      .createTransactionReceipt(signature, signatureHash, new BN(1_000_000), { apiResponse: {} }, PublicKey.default)
+      .accounts({
+        receipt: receiptPda,
+        payerPubkey: voter.publicKey,
+        recipientPubkey: agent.publicKey,
+        creator: voter.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+    return receiptPda;
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    voter = Keypair.generate();
+    agent = Keypair.generate();
+    for (const kp of [authority, voter, agent]) {
+      await airdrop(context, kp.publicKey, 10 * LAMPORTS_PER_SOL);
+    }
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from('staking_pool')], IDENTITY_PROGRAM_ID);
+    [cpiAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('reputation_cpi')], VOTE_PROGRAM_ID);
+
+    await identityProgram.methods
+      .initializeStakingPool()
+      .accounts({
+        stakingPool: stakingPoolPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await registerStaked(voter);
+    await registerStaked(agent);
+
+    // The voter needs enough reputation to vote, rate and endorse
+    await advanceTime(context, 10);
+    await reputationProgram.methods
+      .updateReputation(
+        500,
+        { trust: 50, quality: 50, reliability: 50, economic: 50, social: 50 },
+        { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+        new Array(32).fill(0),
+        new BN(0),
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda(voter.publicKey),
+        authorityAccount: authorityPda,
+        agentAddress: voter.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('both identities hold collateral', async () => {
+    for (const wallet of [voter, agent]) {
+      const identity = await fetchAccount(identityProgram, 'agentIdentity', identityPda(wallet.publicKey));
+      expect(identity.stakedAmount.toNumber()).toBe(STAKE);
+      expect(identity.firstStakedAt.toNumber()).toBeGreaterThan(0);
+    }
+  });
+
+  test('a staked voter can vote on a staked agent', async () => {
+    const receiptPda = await receipt();
+    const peerVote = votePda([Buffer.from('peer_vote'), receiptPda.toBuffer(), voter.publicKey.toBuffer()]);
+
+    await voteProgram.methods
+      .castPeerVote(agent.publicKey, { upvote: {} }, QUALITY, new Array(32).fill(0))
+      .accounts({
+        peerVote,
+        transactionReceipt: receiptPda,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(agent.publicKey),
+        votedAgentReputation: reputationPda(agent.publicKey),
+        reputationCpiAuthority: cpiAuthorityPda,
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+    const vote = await fetchAccount(voteProgram, 'peerVote', peerVote);
+    expect(vote.voter.equals(voter.publicKey)).toBe(true);
+    expect(vote.votedAgent.equals(agent.publicKey)).toBe(true);
+    expect(vote.voterReputationSnapshot).toBe(500);
+
+    const tallyPda = votePda([Buffer.from('vote_tally'), agent.publicKey.toBuffer()]);
+    expect((await fetchAccount(voteProgram, 'voteTally', tallyPda)).upvotes).toBe(1);
+  });
+
+  test('a staked rater can rate a staked agent', async () => {
+    const receiptPda = await receipt();
+    const contentRating = votePda([Buffer.from('content_rating'), receiptPda.toBuffer()]);
+
+    await voteProgram.methods
+      .rateContent(90)
+      .accounts({
+        contentRating,
+        transactionReceipt: receiptPda,
+        raterIdentity: identityPda(voter.publicKey),
+        raterReputation: reputationPda(voter.publicKey),
+        ratedAgentIdentity: identityPda(agent.publicKey),
+        ratedAgent: agent.publicKey,
+        ratedAgentReputation: reputationPda(agent.publicKey),
+        reputationCpiAuthority: cpiAuthorityPda,
+        rater: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+    expect((await fetchAccount(voteProgram, 'contentRating', contentRating)).qualityRating).toBe(90);
+  });
+
+  test('a staked endorser can endorse a staked agent', async () => {
+    const endorsement = votePda([Buffer.from('endorsement'), voter.publicKey.toBuffer(), agent.publicKey.toBuffer()]);
+
+    await voteProgram.methods
+      .endorseAgent(agent.publicKey, 60, { technical: {} })
+      .accounts({
+        endorsement,
+        endorserStats: votePda([Buffer.from('endorsement_stats'), voter.publicKey.toBuffer()]),
+        endorsedStats: votePda([Buffer.from('endorsement_stats'), agent.publicKey.toBuffer()]),
+        endorserIdentity: identityPda(voter.publicKey),
+        endorserReputation: reputationPda(voter.publicKey),
+        endorsedAgentIdentity: identityPda(agent.publicKey),
+        endorser: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+    const account = await fetchAccount(voteProgram, 'agentEndorsement', endorsement);
+    expect(account.endorsed.equals(agent.publicKey)).toBe(true);
+    expect(account.endorserReputationSnapshot).toBe(500);
+  });
+});
@@ -0,0 +1,368 @@
+/**
+ * Sybil Guard Tests
+ * Tests the identity relationship checks cast_peer_vote applies before counting a vote
+ *
+ * Sybil guards ensure:
+ * 1. A voter and voted agent linked to the same Core asset cannot vote on each other
+ * 2. Neither can identities that share a delegate, or where one is the other's delegate
+ * 3. A voter whose identity is younger than new_voter_age_seconds votes at new_voter_weight_bps,
+ *    and the vote records it in new_voter
+ * 4. Each guard is a VoteConfig toggle only the vote authority can change
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, reputableAgent } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+const DAY = 24 * 60 * 60;
+const QUALITY = { responseQuality: 80, responseSpeed: 80, accuracy: 80, professionalism: 80 };
+
+describe('Sybil Guards', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let reputationAuthorityPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+  let voteConfigPda: PublicKey;
+  let receiptCount = 0;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function receiptPda(payer: PublicKey, recipient: PublicKey, signatureHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('tx_receipt'), payer.toBuffer(), recipient.toBuffer(), Buffer.from(signatureHash)],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function nextSignature(): { signature: string; signatureHash: number[] } {
+    const signature = `sybil_sig_${receiptCount++}`;
+    return { signature, signatureHash: Array.from(createHash('sha256').update(signature).digest()) };
+  }
+
+  /** A registered agent whose reputation score (500) allows it to vote */
+  function votingAgent(): Promise<Keypair> {
+    return reputableAgent(context, identityProgram, reputationProgram, authority);
+  }
+
+  /** Create a receipt for a payment of `amount` base units of `paymentMint` from `payer` to `recipient` */
+  async function receipt(
+    payer: Keypair,
+    recipient: Keypair,
+    amount: number,
+    paymentMint = PublicKey.default
+  ): Promise<PublicKey> {
+    const { signature, signatureHash } = nextSignature();
+    const receiptAddress = receiptPda(payer.publicKey, recipient.publicKey, signatureHash);
+    await voteProgram.methods
+      .createTransactionReceipt(signature, signatureHash, new BN(amount), { apiResponse: {} }, paymentMint)
+      .accounts({
+        receipt: receiptAddress,
+        payerPubkey: payer.publicKey,
+        recipientPubkey: recipient.publicKey,
+        creator: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+    return receiptAddress;
+  }
+
+  function peerVotePda(receipt: PublicKey, voter: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('peer_vote'), receipt.toBuffer(), voter.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function voteTallyPda(agent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('vote_tally'), agent.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function vote(receipt: PublicKey, voter: Keypair, votedAgent: Keypair) {
+    return voteProgram.methods
+      .castPeerVote(votedAgent.publicKey, { upvote: {} }, QUALITY, new Array(32).fill(0))
+      .accounts({
+        peerVote: peerVotePda(receipt, voter),
+        transactionReceipt: receipt,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(votedAgent.publicKey),
+        votedAgentReputation: reputationPda(votedAgent.publicKey),
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        voteConfig: voteConfigPda,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  function updateSybilGuards(
+    rejectSharedAsset: boolean,
+    rejectSharedDelegate: boolean,
+    newVoterAgeSeconds: number,
+    newVoterWeightBps: number,
+    signer = authority
+  ) {
+    return voteProgram.methods
+      .updateSybilGuards(rejectSharedAsset, rejectSharedDelegate, new BN(newVoterAgeSeconds), newVoterWeightBps)
+      .accounts({ voteConfig: voteConfigPda, authorityAccount: voteAuthorityPda, authority: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function setDelegate(agent: Keypair, delegate: PublicKey) {
+    return identityProgram.methods
+      .setDelegate(delegate, 1)
+      .accounts({ agentIdentity: identityPda(agent.publicKey), agent: agent.publicKey, agentAddress: agent.publicKey })
+      .signers([agent])
+      .rpc();
+  }
+
+  /** Hand the mock Core asset at `asset` to `agent` and link it to the agent's identity */
+  async function relinkAsset(agent: Keypair, asset: PublicKey) {
+    const account = await context.banksClient.getAccount(asset);
+    const data = Buffer.from(account!.data);
+    agent.publicKey.toBuffer().copy(data, 1);
+    context.setAccount(asset, { ...account!, data });
+
+    await identityProgram.methods
+      .updateAsset()
+      .accounts({
+        agentIdentity: identityPda(agent.publicKey),
+        agent: agent.publicKey,
+        agentAddress: agent.publicKey,
+        asset,
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: reputationAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeVoteConfig()
+      .accounts({
+        voteConfig: voteConfigPda,
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: reputationAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeVoteConfig()
+      .accounts({
+        voteConfig: voteConfigPda,
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('a new config rejects linked identities and has no new voter rule', async () => {
+    const config = await fetchAccount(voteProgram, 'voteConfig', voteConfigPda);
+    expect(config.rejectSharedAssetVotes).toBe(true);
+    expect(config.rejectSharedDelegateVotes).toBe(true);
+    expect(config.newVoterAgeSeconds.toNumber()).toBe(0);
+    expect(config.newVoterWeightBps).toBe(5_000);
+  });
+
+  test('identities linked to the same Core asset cannot vote on each other', async () => {
+    const [seller, buyer] = [await votingAgent(), await votingAgent()];
+    const sellerIdentity = await fetchAccount(identityProgram, 'agentIdentity', identityPda(seller.publicKey));
+    await relinkAsset(buyer, sellerIdentity.assetAddress);
+
+    const receiptAddress = await receipt(buyer, seller, 1_000_000);
+    await expect(vote(receiptAddress, buyer, seller)).rejects.toThrow(/SharedAssetVote/);
+
+    await updateSybilGuards(false, true, 0, 5_000);
+    await vote(receiptAddress, buyer, seller);
+    await updateSybilGuards(true, true, 0, 5_000);
+  });
+
+  test('identities sharing a delegate, or delegating to each other, cannot vote on each other', async () => {
+    const [seller, buyer, otherSeller, otherBuyer] = [
+      await votingAgent(),
+      await votingAgent(),
+      await votingAgent(),
+      await votingAgent(),
+    ];
+    const operator = Keypair.generate().publicKey;
+    await setDelegate(seller, operator);
+    await setDelegate(buyer, operator);
+    // otherSeller is otherBuyer's operator
+    await setDelegate(otherBuyer, otherSeller.publicKey);
+
+    const shared = await receipt(buyer, seller, 1_000_000);
+    await expect(vote(shared, buyer, seller)).rejects.toThrow(/SharedDelegateVote/);
+    const circular = await receipt(otherBuyer, otherSeller, 1_000_000);
+    await expect(vote(circular, otherBuyer, otherSeller)).rejects.toThrow(/SharedDelegateVote/);
+    await expect(vote(circular, otherSeller, otherBuyer)).rejects.toThrow(/SharedDelegateVote/);
+
+    await updateSybilGuards(true, false, 0, 5_000);
+    await vote(shared, buyer, seller);
+    await vote(circular, otherBuyer, otherSeller);
+    await updateSybilGuards(true, true, 0, 5_000);
+  });
+
+  test('a new voter carries reduced weight until its identity ages', async () => {
+    await updateSybilGuards(true, true, 7 * DAY, 5_000);
+    const seller = await votingAgent();
+    const buyer = await votingAgent();
+
+    const first = await receipt(buyer, seller, 1_000_000);
+    await vote(first, buyer, seller);
+    let peerVote = await fetchAccount(voteProgram, 'peerVote', peerVotePda(first, buyer));
+    expect(peerVote.newVoter).toBe(true);
+    expect(peerVote.voteWeight).toBe(50);
+
+    await advanceTime(context, 7 * DAY);
+    const second = await receipt(buyer, seller, 10_000_000);
+    await vote(second, buyer, seller);
+    peerVote = await fetchAccount(voteProgram, 'peerVote', peerVotePda(second, buyer));
+    expect(peerVote.newVoter).toBe(false);
+    expect(peerVote.voteWeight).toBe(125);
+
+    const tally = await fetchAccount(voteProgram, 'voteTally', voteTallyPda(seller));
+    expect(tally.weightSum.toNumber()).toBe(50 + 125);
+
+    await updateSybilGuards(true, true, 0, 5_000);
+  });
+
+  test('a new voter on a higher tier is scaled from that tier', async () => {
+    await updateSybilGuards(true, true, 7 * DAY, 2_500);
+    const [seller, buyer] = [await votingAgent(), await votingAgent()];
+
+    const receiptAddress = await receipt(buyer, seller, 100_000_000);
+    await vote(receiptAddress, buyer, seller);
+    const peerVote = await fetchAccount(voteProgram, 'peerVote', peerVotePda(receiptAddress, buyer));
+    expect(peerVote.newVoter).toBe(true);
+    expect(peerVote.voteWeight).toBe(37);
+
+    await updateSybilGuards(true, true, 0, 5_000);
+  });
+
+  test('the new voter rule is validated, and only the authority sets the guards', async () => {
+    const outsider = await votingAgent();
+
+    await expect(updateSybilGuards(true, true, -1, 5_000)).rejects.toThrow(/InvalidNewVoterRule/);
+    await expect(updateSybilGuards(true, true, DAY, 0)).rejects.toThrow(/InvalidNewVoterRule/);
+    await expect(updateSybilGuards(true, true, DAY, 10_001)).rejects.toThrow(/InvalidNewVoterRule/);
+    await expect(updateSybilGuards(false, false, 0, 5_000, outsider)).rejects.toThrow(/UnauthorizedAuthority/);
+  });
+});
@@ -90,7 +90,7 @@ describe('Transaction Receipt Creation', () => {
 
     // Create receipt
     const tx = await program.methods
-      .createTransactionReceipt(signature, signatureHash, amount, contentType)
+      .createTransactionReceipt(signature, signatureHash, amount, contentType, PublicKey.default)
       .accounts({
         receipt: receiptPda,
         payerPubkey: payer.publicKey,
@@ -110,7 +110,8 @@ describe('Transaction Receipt Creation', () => {
     expect(receipt.payer.toBase58()).toBe(payer.publicKey.toBase58());
     expect(receipt.recipient.toBase58()).toBe(recipient.publicKey.toBase58());
     expect(receipt.amount.toNumber()).toBe(amount.toNumber());
-    expect(receipt.voteCast).toBe(false);
+    expect(receipt.payerVoted).toBe(false);
+    expect(receipt.recipientVoted).toBe(false);
     expect(receipt.bump).toBe(bump);
   });
 
@@ -141,7 +142,8 @@ describe('Transaction Receipt Creation', () => {
           signature,
           signatureHash,
           new BN(100_000_000),
-          { chat: {} }
+          { chat: {} },
+          PublicKey.default
         )
         .accounts({
           receipt: receiptPda,
@@ -177,7 +179,8 @@ describe('Transaction Receipt Creation', () => {
           signature,
           signatureHash,
           new BN(100_000_000),
-          { chat: {} }
+          { chat: {} },
+          PublicKey.default
         )
         .accounts({
           receipt: receiptPda,
@@ -213,7 +216,8 @@ describe('Transaction Receipt Creation', () => {
           longSignature,
           signatureHash,
           new BN(100_000_000),
-          { chat: {} }
+          { chat: {} },
+          PublicKey.default
         )
         .accounts({
           receipt: receiptPda,
@@ -252,7 +256,8 @@ describe('Transaction Receipt Creation', () => {
         signature,
         signatureHash,
         new BN(100_000_000),
-        { audio: {} }
+        { audio: {} },
+        PublicKey.default
       )
       .accounts({
         receipt: receiptPda,
@@ -301,7 +306,8 @@ describe('Transaction Receipt Creation', () => {
           signature,
           signatureHash,
           new BN(100_000_000),
-          contentTypes[i]
+          contentTypes[i],
+          PublicKey.default
         )
         .accounts({
           receipt: receiptPda,
@@ -0,0 +1,300 @@
+/**
+ * Verified Receipt Tests
+ * Tests receipts backed by the payer's Ed25519 signature, checked through the instructions sysvar
+ *
+ * Verification ensures:
+ * 1. A receipt preceded by a valid Ed25519 verify instruction over the payment message is marked verified
+ * 2. A payment message that does not match the receipt (tampered amount, wrong signer) is rejected
+ * 3. The verified variant cannot be used without the Ed25519 instruction
+ * 4. With require_verified_receipts on, votes on unverified receipts fail while verified ones pass
+ * 5. Verified receipts are never closed, so the payer's signature cannot be replayed into a fresh receipt
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Ed25519Program, Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, now, reputableAgent } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+const PAYMENT_MESSAGE_DOMAIN = Buffer.from('ghostspeak:x402-receipt:v2');
+const VOTING_WINDOW_SECONDS = 30 * 24 * 60 * 60;
+const QUALITY = { responseQuality: 80, responseSpeed: 80, accuracy: 80, professionalism: 80 };
+
+describe('Verified Receipts', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let reputationAuthorityPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+  let voteConfigPda: PublicKey;
+  let receiptCount = 0;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function receiptPda(payer: PublicKey, recipient: PublicKey, signatureHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('tx_receipt'), payer.toBuffer(), recipient.toBuffer(), Buffer.from(signatureHash)],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  /** domain || payer || recipient || paymentMint || amount (u64 LE) || signatureHash */
+  function paymentMessage(
+    payer: PublicKey,
+    recipient: PublicKey,
+    paymentMint: PublicKey,
+    amount: number,
+    signatureHash: number[]
+  ): Buffer {
+    const amountBuffer = Buffer.alloc(8);
+    amountBuffer.writeBigUInt64LE(BigInt(amount));
+    return Buffer.concat([
+      PAYMENT_MESSAGE_DOMAIN,
+      payer.toBuffer(),
+      recipient.toBuffer(),
+      paymentMint.toBuffer(),
+      amountBuffer,
+      Buffer.from(signatureHash),
+    ]);
+  }
+
+  function nextSignature(): { signature: string; signatureHash: number[] } {
+    const signature = `verified_sig_${receiptCount++}`;
+    return { signature, signatureHash: Array.from(createHash('sha256').update(signature).digest()) };
+  }
+
+  /** A registered agent whose reputation score (500) allows it to vote */
+  function votingAgent(): Promise<Keypair> {
+    return reputableAgent(context, identityProgram, reputationProgram, authority);
+  }
+
+  /**
+   * Create a verified receipt for `amount` lamports. `signer` signs the payment message for
+   * `signedAmount`; both default to the honest values. Pass `signer: null` to omit the Ed25519 instruction.
+   */
+  async function verifiedReceipt(
+    payer: Keypair,
+    recipient: Keypair,
+    amount: number,
+    { signer = payer as Keypair | null, signedAmount = amount } = {}
+  ): Promise<PublicKey> {
+    const { signature, signatureHash } = nextSignature();
+    const receipt = receiptPda(payer.publicKey, recipient.publicKey, signatureHash);
+    const preInstructions = signer
+      ? [
+          Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: signer.secretKey,
+            message: paymentMessage(
+              payer.publicKey,
+              recipient.publicKey,
+              PublicKey.default,
+              signedAmount,
+              signatureHash
+            ),
+          }),
+        ]
+      : [];
+
+    await voteProgram.methods
+      .createVerifiedTransactionReceipt(
+        signature,
+        signatureHash,
+        new BN(amount),
+        { apiResponse: {} },
+        PublicKey.default
+      )
+      .accounts({
+        receipt,
+        payerPubkey: payer.publicKey,
+        recipientPubkey: recipient.publicKey,
+        creator: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions(preInstructions)
+      .signers([payer])
+      .rpc();
+    return receipt;
+  }
+
+  async function unverifiedReceipt(payer: Keypair, recipient: Keypair): Promise<PublicKey> {
+    const { signature, signatureHash } = nextSignature();
+    const receipt = receiptPda(payer.publicKey, recipient.publicKey, signatureHash);
+    await voteProgram.methods
+      .createTransactionReceipt(signature, signatureHash, new BN(1_000_000), { apiResponse: {} }, PublicKey.default)
+      .accounts({
+        receipt,
+        payerPubkey: payer.publicKey,
+        recipientPubkey: recipient.publicKey,
+        creator: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+    return receipt;
+  }
+
+  function vote(receipt: PublicKey, voter: Keypair, votedAgent: Keypair) {
+    return voteProgram.methods
+      .castPeerVote(votedAgent.publicKey, { upvote: {} }, QUALITY, new Array(32).fill(0))
+      .accounts({
+        peerVote: PublicKey.findProgramAddressSync(
+          [Buffer.from('peer_vote'), receipt.toBuffer(), voter.publicKey.toBuffer()],
+          VOTE_PROGRAM_ID
+        )[0],
+        transactionReceipt: receipt,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(votedAgent.publicKey),
+        votedAgentReputation: reputationPda(votedAgent.publicKey),
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        voteConfig: voteConfigPda,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  function setStrictMode(required: boolean) {
+    return voteProgram.methods
+      .updateRequireVerifiedReceipts(required)
+      .accounts({ voteConfig: voteConfigPda, authorityAccount: voteAuthorityPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: reputationAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeVoteConfig()
+      .accounts({
+        voteConfig: voteConfigPda,
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('a receipt with a valid sysvar-verified payer signature is marked verified', async () => {
+    const [payer, recipient] = [await votingAgent(), await votingAgent()];
+    const receipt = await verifiedReceipt(payer, recipient, 2_500_000);
+
+    const account = await fetchAccount(voteProgram, 'transactionReceipt', receipt);
+    expect(account.verified).toBe(true);
+    expect(account.amount.toNumber()).toBe(2_500_000);
+
+    const unverified = await fetchAccount(voteProgram, 'transactionReceipt', await unverifiedReceipt(payer, recipient));
+    expect(unverified.verified).toBe(false);
+  });
+
+  test('a tampered amount fails', async () => {
+    const [payer, recipient] = [await votingAgent(), await votingAgent()];
+
+    // The payer signed for 1_000_000 lamports; the receipt claims 50 SOL
+    await expect(
+      verifiedReceipt(payer, recipient, 50_000_000_000, { signedAmount: 1_000_000 })
+    ).rejects.toThrow(/PaymentSignatureMismatch/);
+  });
+
+  test('the payment message must be signed by the payer', async () => {
+    const [payer, recipient] = [await votingAgent(), await votingAgent()];
+
+    await expect(verifiedReceipt(payer, recipient, 1_000_000, { signer: recipient })).rejects.toThrow(
+      /PaymentSignatureMismatch/
+    );
+    await expect(verifiedReceipt(payer, recipient, 1_000_000, { signer: null })).rejects.toThrow(
+      /MissingPaymentVerification/
+    );
+  });
+
+  test('strict mode rejects votes on unverified receipts', async () => {
+    const [payer, recipient] = [await votingAgent(), await votingAgent()];
+    const unverified = await unverifiedReceipt(payer, recipient);
+    const verified = await verifiedReceipt(payer, recipient, 1_000_000);
+
+    await setStrictMode(true);
+    try {
+      await expect(vote(unverified, payer, recipient)).rejects.toThrow(/UnverifiedReceipt/);
+      await vote(verified, payer, recipient);
+    } finally {
+      await setStrictMode(false);
+    }
+
+    // With strict mode off the unverified receipt is usable again (by the recipient,
+    // since the payer is now within its pair vote cooldown)
+    await vote(unverified, recipient, payer);
+  });
+
+  test('a verified receipt cannot be closed, so its signed message cannot be replayed', async () => {
+    const [payer, recipient] = [await votingAgent(), await votingAgent()];
+    const receipt = await verifiedReceipt(payer, recipient, 1_000_000);
+
+    await advanceTime(context, VOTING_WINDOW_SECONDS + 1);
+    await expect(
+      voteProgram.methods.closeReceipt().accounts({ receipt, creator: payer.publicKey }).signers([payer]).rpc()
+    ).rejects.toThrow(/VerifiedReceiptNotClosable/);
+  });
+});
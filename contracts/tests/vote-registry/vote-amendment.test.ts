@@ -0,0 +1,276 @@
+/**
+ * Vote Amendment Tests
+ * Tests amend_peer_vote within and after the 48-hour grace period
+ *
+ * Amendment ensures:
+ * 1. The voter can replace vote type and quality scores inside the window
+ * 2. The agent's tally moves the vote between counters and adjusts quality sums
+ * 3. amended_count and amended_at record the change
+ * 4. Amendments after the window, or by anyone but the voter, are rejected
+ * 5. The voted agent's reputation vote counters follow a changed vote type
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  now,
+  transactionReceipt,
+} from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+const AMENDMENT_WINDOW_SECONDS = 48 * 60 * 60;
+
+type Quality = { responseQuality: number; responseSpeed: number; accuracy: number; professionalism: number };
+
+function quality(value: number): Quality {
+  return { responseQuality: value, responseSpeed: value, accuracy: value, professionalism: value };
+}
+
+describe('Vote Amendment', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let voter: Keypair;
+  let authorityPda: PublicKey;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function voteTallyPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vote_tally'), agent.toBuffer()], VOTE_PROGRAM_ID)[0];
+  }
+
+  async function registerWithReputation(wallet: Keypair) {
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        agent: wallet.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda(wallet.publicKey),
+        agentAddress: wallet.publicKey,
+        authorityAccount: null,
+        initializer: wallet.publicKey,
+        payer: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+  }
+
+  async function votedAgent(): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10_000_000_000);
+    await registerWithReputation(agent);
+    return agent;
+  }
+
+  function peerVotePda(receiptPda: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('peer_vote'), receiptPda.toBuffer(), voter.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function vote(agent: Keypair, receiptPda: PublicKey, voteType: object, scores: Quality) {
+    return voteProgram.methods
+      .castPeerVote(agent.publicKey, voteType, scores, new Array(32).fill(0))
+      .accounts({
+        peerVote: peerVotePda(receiptPda),
+        voteTally: voteTallyPda(agent.publicKey),
+        transactionReceipt: receiptPda,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(agent.publicKey),
+        votedAgentReputation: reputationPda(agent.publicKey),
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  function amend(agent: Keypair, receiptPda: PublicKey, voteType: object, scores: Quality, signer = voter) {
+    return voteProgram.methods
+      .amendPeerVote(voteType, scores)
+      .accounts({
+        peerVote: peerVotePda(receiptPda),
+        voteTally: voteTallyPda(agent.publicKey),
+        votedAgentReputation: reputationPda(agent.publicKey),
+        voter: signer.publicKey,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    voter = Keypair.generate();
+    for (const kp of [authority, voter]) {
+      await airdrop(context, kp.publicKey, 10_000_000_000);
+    }
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await registerWithReputation(voter);
+
+    // Voter needs a score >= 100 to vote
+    await advanceTime(context, 10);
+    await reputationProgram.methods
+      .updateReputation(
+        500,
+        { trust: 50, quality: 50, reliability: 50, economic: 50, social: 50 },
+        { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+        new Array(32).fill(0),
+        new BN(0),
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda(voter.publicKey),
+        authorityAccount: authorityPda,
+        agentAddress: voter.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('amending inside the window moves the vote in the tally', async () => {
+    const agent = await votedAgent();
+    const receiptPda = await transactionReceipt(voteProgram, voter, agent);
+    await vote(agent, receiptPda, { upvote: {} }, quality(80));
+    await vote(agent, await transactionReceipt(voteProgram, voter, agent), { upvote: {} }, quality(60));
+
+    await advanceTime(context, AMENDMENT_WINDOW_SECONDS - 60);
+    await amend(agent, receiptPda, { downvote: {} }, quality(20));
+
+    const tally = await fetchAccount(voteProgram, 'voteTally', voteTallyPda(agent.publicKey));
+    expect(tally.upvotes).toBe(1);
+    expect(tally.downvotes).toBe(1);
+    expect(tally.neutrals).toBe(0);
+    expect(tally.qualitySums.accuracy.toNumber()).toBe(80);
+    expect(tally.weightSum.toNumber()).toBe(200);
+
+    const peerVote = await fetchAccount(voteProgram, 'peerVote', peerVotePda(receiptPda));
+    expect(peerVote.voteType).toEqual({ downvote: {} });
+    expect(peerVote.qualityScores.accuracy).toBe(20);
+    expect(peerVote.amendedCount).toBe(1);
+    expect(peerVote.amendedAt.toNumber()).toBe(await now(context));
+
+    const reputation = await fetchAccount(reputationProgram, 'agentReputation', reputationPda(agent.publicKey));
+    expect(reputation.stats.totalVotes).toBe(2);
+    expect(reputation.stats.positiveVotes).toBe(1);
+    expect(reputation.stats.negativeVotes).toBe(1);
+  });
+
+  test('repeated amendments keep the tally consistent', async () => {
+    const agent = await votedAgent();
+    const receiptPda = await transactionReceipt(voteProgram, voter, agent);
+    await vote(agent, receiptPda, { neutral: {} }, quality(50));
+
+    await amend(agent, receiptPda, { upvote: {} }, quality(90));
+    await advanceTime(context, 1);
+    await amend(agent, receiptPda, { downvote: {} }, quality(10));
+
+    const tally = await fetchAccount(voteProgram, 'voteTally', voteTallyPda(agent.publicKey));
+    expect([tally.upvotes, tally.downvotes, tally.neutrals]).toEqual([0, 1, 0]);
+    expect(tally.qualitySums.responseQuality.toNumber()).toBe(10);
+    expect((await fetchAccount(voteProgram, 'peerVote', peerVotePda(receiptPda))).amendedCount).toBe(2);
+
+    const { stats } = await fetchAccount(reputationProgram, 'agentReputation', reputationPda(agent.publicKey));
+    expect([stats.totalVotes, stats.positiveVotes, stats.negativeVotes]).toEqual([1, 0, 1]);
+  });
+
+  test('amending after the window fails', async () => {
+    const agent = await votedAgent();
+    const receiptPda = await transactionReceipt(voteProgram, voter, agent);
+    await vote(agent, receiptPda, { upvote: {} }, quality(80));
+
+    await advanceTime(context, AMENDMENT_WINDOW_SECONDS + 1);
+    await expect(amend(agent, receiptPda, { downvote: {} }, quality(20))).rejects.toThrow(
+      /AmendmentWindowExpired/
+    );
+  });
+
+  test('only the original voter can amend', async () => {
+    const agent = await votedAgent();
+    const receiptPda = await transactionReceipt(voteProgram, voter, agent);
+    await vote(agent, receiptPda, { upvote: {} }, quality(80));
+
+    const outsider = Keypair.generate();
+    await airdrop(context, outsider.publicKey, 1_000_000_000);
+    await expect(amend(agent, receiptPda, { downvote: {} }, quality(0), outsider)).rejects.toThrow(
+      /NotOriginalVoter/
+    );
+    // The agent cannot rewrite a vote about itself either
+    await expect(amend(agent, receiptPda, { upvote: {} }, quality(100), agent)).rejects.toThrow(
+      /NotOriginalVoter/
+    );
+  });
+});
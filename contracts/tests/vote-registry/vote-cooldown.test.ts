@@ -0,0 +1,221 @@
+/**
+ * Pair Vote Cooldown Tests
+ * Tests the per voter/agent pair limits that stop two agents wash-trading votes
+ *
+ * Pair limits ensure:
+ * 1. A voter cannot vote on the same agent again within the cooldown (24h by default)
+ * 2. Votes per pair are capped per window (10 per 30 days by default), independently of the cooldown
+ * 3. Limits are per pair: voting on a different counterparty is unaffected
+ * 4. Only the vote authority can change the limits, and only to sane values
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, advanceTime, loadProgram, now, transactionReceipt, reputableAgent } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+const DAY = 24 * 60 * 60;
+const QUALITY = { responseQuality: 80, responseSpeed: 80, accuracy: 80, professionalism: 80 };
+
+describe('Pair Vote Cooldown', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let reputationAuthorityPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+  let voteConfigPda: PublicKey;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  /** A registered agent whose reputation score (500) allows it to vote */
+  function votingAgent(): Promise<Keypair> {
+    return reputableAgent(context, identityProgram, reputationProgram, authority);
+  }
+
+  function vote(receipt: PublicKey, voter: Keypair, votedAgent: Keypair) {
+    return voteProgram.methods
+      .castPeerVote(votedAgent.publicKey, { upvote: {} }, QUALITY, new Array(32).fill(0))
+      .accounts({
+        peerVote: PublicKey.findProgramAddressSync(
+          [Buffer.from('peer_vote'), receipt.toBuffer(), voter.publicKey.toBuffer()],
+          VOTE_PROGRAM_ID
+        )[0],
+        transactionReceipt: receipt,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(votedAgent.publicKey),
+        votedAgentReputation: reputationPda(votedAgent.publicKey),
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        voteConfig: voteConfigPda,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  function setPairLimits(cooldownSeconds: number, windowSeconds: number, maxVotes: number, signer = authority) {
+    return voteProgram.methods
+      .updatePairVoteLimits(new BN(cooldownSeconds), new BN(windowSeconds), maxVotes)
+      .accounts({ voteConfig: voteConfigPda, authorityAccount: voteAuthorityPda, authority: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function votePairPda(voter: Keypair, votedAgent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('vote_pair'), voter.publicKey.toBuffer(), votedAgent.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: reputationAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeVoteConfig()
+      .accounts({
+        voteConfig: voteConfigPda,
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('a second vote on the same agent within 24h fails', async () => {
+    const [voter, agent] = [await votingAgent(), await votingAgent()];
+
+    await vote(await transactionReceipt(voteProgram, voter, agent), voter, agent);
+    await advanceTime(context, DAY - 60);
+    const receipt = await transactionReceipt(voteProgram, voter, agent);
+    await expect(vote(receipt, voter, agent)).rejects.toThrow(/VoteCooldownActive/);
+
+    const pair = await fetchAccount(voteProgram, 'votePairState', votePairPda(voter, agent));
+    expect(pair.votesInWindow).toBe(1);
+  });
+
+  test('a vote after the cooldown succeeds', async () => {
+    const [voter, agent] = [await votingAgent(), await votingAgent()];
+
+    await vote(await transactionReceipt(voteProgram, voter, agent), voter, agent);
+    await advanceTime(context, DAY);
+    await vote(await transactionReceipt(voteProgram, voter, agent), voter, agent);
+
+    const pair = await fetchAccount(voteProgram, 'votePairState', votePairPda(voter, agent));
+    expect(pair.voter.toBase58()).toBe(voter.publicKey.toBase58());
+    expect(pair.votedAgent.toBase58()).toBe(agent.publicKey.toBase58());
+    expect(pair.votesInWindow).toBe(2);
+    expect(pair.lastVoteAt.toNumber()).toBe(await now(context));
+  });
+
+  test('the window cap triggers independently of the cooldown', async () => {
+    const [voter, agent] = [await votingAgent(), await votingAgent()];
+    await setPairLimits(60, 30 * DAY, 2);
+    try {
+      await vote(await transactionReceipt(voteProgram, voter, agent), voter, agent);
+      await advanceTime(context, 61);
+      await vote(await transactionReceipt(voteProgram, voter, agent), voter, agent);
+
+      // Cooldown has passed, but the pair used up its votes for this window
+      await advanceTime(context, 61);
+      const receipt = await transactionReceipt(voteProgram, voter, agent);
+      await expect(vote(receipt, voter, agent)).rejects.toThrow(/PairVoteLimitReached/);
+
+      // A new window starts once 30 days have passed since the first vote
+      await advanceTime(context, 30 * DAY);
+      await vote(await transactionReceipt(voteProgram, voter, agent), voter, agent);
+      const pair = await fetchAccount(voteProgram, 'votePairState', votePairPda(voter, agent));
+      expect(pair.votesInWindow).toBe(1);
+    } finally {
+      await setPairLimits(DAY, 30 * DAY, 10);
+    }
+  });
+
+  test('voting on a different counterparty is unaffected', async () => {
+    const [voter, first, second] = [await votingAgent(), await votingAgent(), await votingAgent()];
+
+    await vote(await transactionReceipt(voteProgram, voter, first), voter, first);
+    await vote(await transactionReceipt(voteProgram, voter, second), voter, second);
+    // The reverse direction is its own pair
+    await vote(await transactionReceipt(voteProgram, voter, first), first, voter);
+
+    const receipt = await transactionReceipt(voteProgram, voter, first);
+    await expect(vote(receipt, voter, first)).rejects.toThrow(/VoteCooldownActive/);
+  });
+
+  test('only the authority can change the limits, and only to sane values', async () => {
+    const outsider = await votingAgent();
+
+    await expect(setPairLimits(DAY, 30 * DAY, 10, outsider)).rejects.toThrow(/UnauthorizedAuthority/);
+    await expect(setPairLimits(-1, 30 * DAY, 10)).rejects.toThrow(/InvalidPairVoteLimits/);
+    await expect(setPairLimits(DAY, 0, 10)).rejects.toThrow(/InvalidPairVoteLimits/);
+    await expect(setPairLimits(DAY, 30 * DAY, 0)).rejects.toThrow(/InvalidPairVoteLimits/);
+
+    const config = await fetchAccount(voteProgram, 'voteConfig', voteConfigPda);
+    expect(config.pairVoteCooldownSeconds.toNumber()).toBe(DAY);
+    expect(config.pairVoteWindowSeconds.toNumber()).toBe(30 * DAY);
+    expect(config.maxPairVotesPerWindow).toBe(10);
+  });
+});
@@ -0,0 +1,376 @@
+/**
+ * Vote Dispute Tests
+ * Tests reveal_comment, dispute_vote and resolve_vote_dispute
+ *
+ * Comment reveal and disputes ensure:
+ * 1. A comment is revealed only with the salt that produced comment_hash = sha256(comment || salt)
+ * 2. Only the voted agent can dispute a vote, and a disputed vote leaves the tally while pending
+ * 3. Only the vote authority resolves disputes, either reinstating the vote or removing it for good
+ * 4. Pending and removed votes cannot be amended
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash, randomBytes } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  now,
+  transactionReceipt,
+} from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+type Quality = { responseQuality: number; responseSpeed: number; accuracy: number; professionalism: number };
+
+function quality(value: number): Quality {
+  return { responseQuality: value, responseSpeed: value, accuracy: value, professionalism: value };
+}
+
+describe('Vote Disputes', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let voter: Keypair;
+  let authorityPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function voteTallyPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vote_tally'), agent.toBuffer()], VOTE_PROGRAM_ID)[0];
+  }
+
+  async function registerWithReputation(wallet: Keypair) {
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        agent: wallet.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda(wallet.publicKey),
+        agentAddress: wallet.publicKey,
+        authorityAccount: null,
+        initializer: wallet.publicKey,
+        payer: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+  }
+
+  async function votedAgent(): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10_000_000_000);
+    await registerWithReputation(agent);
+    return agent;
+  }
+
+  function peerVotePda(receiptPda: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('peer_vote'), receiptPda.toBuffer(), voter.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function vote(
+    agent: Keypair,
+    receiptPda: PublicKey,
+    voteType: object,
+    scores: Quality,
+    commentHash: number[] = new Array(32).fill(0)
+  ) {
+    return voteProgram.methods
+      .castPeerVote(agent.publicKey, voteType, scores, commentHash)
+      .accounts({
+        peerVote: peerVotePda(receiptPda),
+        voteTally: voteTallyPda(agent.publicKey),
+        transactionReceipt: receiptPda,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(agent.publicKey),
+        votedAgentReputation: reputationPda(agent.publicKey),
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  function commentRevealPda(peerVote: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('comment_reveal'), peerVote.toBuffer()], VOTE_PROGRAM_ID)[0];
+  }
+
+  function commentHash(comment: string, salt: Buffer): number[] {
+    return Array.from(createHash('sha256').update(Buffer.concat([Buffer.from(comment), salt])).digest());
+  }
+
+  function reveal(receiptPda: PublicKey, comment: string, salt: Buffer) {
+    return voteProgram.methods
+      .revealComment(comment, Array.from(salt))
+      .accounts({
+        peerVote: peerVotePda(receiptPda),
+        commentReveal: commentRevealPda(peerVotePda(receiptPda)),
+        revealer: voter.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  function dispute(agent: Keypair, receiptPda: PublicKey, signer = agent) {
+    return voteProgram.methods
+      .disputeVote()
+      .accounts({
+        peerVote: peerVotePda(receiptPda),
+        voteTally: voteTallyPda(agent.publicKey),
+        votedAgent: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  function resolve(agent: Keypair, receiptPda: PublicKey, removeVote: boolean, signer = authority) {
+    return voteProgram.methods
+      .resolveVoteDispute(removeVote)
+      .accounts({
+        peerVote: peerVotePda(receiptPda),
+        voteTally: voteTallyPda(agent.publicKey),
+        authorityAccount: voteAuthorityPda,
+        authority: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    voter = Keypair.generate();
+    for (const kp of [authority, voter]) {
+      await airdrop(context, kp.publicKey, 10_000_000_000);
+    }
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await registerWithReputation(voter);
+
+    // Voter needs a score >= 100 to vote
+    await advanceTime(context, 10);
+    await reputationProgram.methods
+      .updateReputation(
+        500,
+        { trust: 50, quality: 50, reliability: 50, economic: 50, social: 50 },
+        { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+        new Array(32).fill(0),
+        new BN(0),
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda(voter.publicKey),
+        authorityAccount: authorityPda,
+        agentAddress: voter.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('revealing with the right salt stores the comment', async () => {
+    const agent = await votedAgent();
+    const receiptPda = await transactionReceipt(voteProgram, voter, agent);
+    const comment = 'Responses were slow and two of five answers were wrong';
+    const salt = randomBytes(32);
+    await vote(agent, receiptPda, { downvote: {} }, quality(30), commentHash(comment, salt));
+
+    await reveal(receiptPda, comment, salt);
+
+    const revealed = await fetchAccount(voteProgram, 'commentReveal', commentRevealPda(peerVotePda(receiptPda)));
+    expect(revealed.comment).toBe(comment);
+    expect(revealed.peerVote.toBase58()).toBe(peerVotePda(receiptPda).toBase58());
+    expect(revealed.revealer.toBase58()).toBe(voter.publicKey.toBase58());
+    expect(revealed.revealedAt.toNumber()).toBe(await now(context));
+    expect((await fetchAccount(voteProgram, 'peerVote', peerVotePda(receiptPda))).commentRevealed).toBe(true);
+  });
+
+  test('revealing with the wrong salt or comment fails', async () => {
+    const agent = await votedAgent();
+    const receiptPda = await transactionReceipt(voteProgram, voter, agent);
+    const comment = 'Great service';
+    const salt = randomBytes(32);
+    await vote(agent, receiptPda, { upvote: {} }, quality(90), commentHash(comment, salt));
+
+    await expect(reveal(receiptPda, comment, randomBytes(32))).rejects.toThrow(/CommentHashMismatch/);
+    await expect(reveal(receiptPda, 'Terrible service', salt)).rejects.toThrow(/CommentHashMismatch/);
+    expect(await context.banksClient.getAccount(commentRevealPda(peerVotePda(receiptPda)))).toBeNull();
+  });
+
+  test('votes without a comment have nothing to reveal', async () => {
+    const agent = await votedAgent();
+    const receiptPda = await transactionReceipt(voteProgram, voter, agent);
+    await vote(agent, receiptPda, { upvote: {} }, quality(90));
+
+    await expect(reveal(receiptPda, '', randomBytes(32))).rejects.toThrow(/NoCommentToReveal/);
+  });
+
+  test('only the voted agent can dispute, once', async () => {
+    const agent = await votedAgent();
+    const outsider = await votedAgent();
+    const receiptPda = await transactionReceipt(voteProgram, voter, agent);
+    await vote(agent, receiptPda, { downvote: {} }, quality(10));
+
+    await expect(dispute(agent, receiptPda, outsider)).rejects.toThrow(/NotVotedAgent/);
+    await dispute(agent, receiptPda);
+    await expect(dispute(agent, receiptPda)).rejects.toThrow(/VoteAlreadyDisputed/);
+  });
+
+  test('a disputed vote is excluded from the tally and cannot be amended', async () => {
+    const agent = await votedAgent();
+    const disputed = await transactionReceipt(voteProgram, voter, agent);
+    await vote(agent, disputed, { downvote: {} }, quality(10));
+    await vote(agent, await transactionReceipt(voteProgram, voter, agent), { upvote: {} }, quality(80));
+
+    await dispute(agent, disputed);
+
+    const tally = await fetchAccount(voteProgram, 'voteTally', voteTallyPda(agent.publicKey));
+    expect([tally.upvotes, tally.downvotes, tally.neutrals]).toEqual([1, 0, 0]);
+    expect(tally.qualitySums.accuracy.toNumber()).toBe(80);
+    expect(tally.weightSum.toNumber()).toBe(100);
+    expect(tally.disputedVotes).toBe(1);
+    expect((await fetchAccount(voteProgram, 'peerVote', peerVotePda(disputed))).disputeStatus).toEqual({
+      pending: {},
+    });
+
+    await expect(
+      voteProgram.methods
+        .amendPeerVote({ upvote: {} }, quality(90))
+        .accounts({
+          peerVote: peerVotePda(disputed),
+          voteTally: voteTallyPda(agent.publicKey),
+          votedAgentReputation: reputationPda(agent.publicKey),
+          voter: voter.publicKey,
+          reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        })
+        .signers([voter])
+        .rpc()
+    ).rejects.toThrow(/VoteDisputed/);
+  });
+
+  test('rejecting a dispute reinstates the vote in the tally', async () => {
+    const agent = await votedAgent();
+    const receiptPda = await transactionReceipt(voteProgram, voter, agent);
+    await vote(agent, receiptPda, { downvote: {} }, quality(20));
+    await dispute(agent, receiptPda);
+
+    await expect(resolve(agent, receiptPda, false, agent)).rejects.toThrow(/UnauthorizedAuthority/);
+    await resolve(agent, receiptPda, false);
+
+    const tally = await fetchAccount(voteProgram, 'voteTally', voteTallyPda(agent.publicKey));
+    expect([tally.upvotes, tally.downvotes, tally.neutrals]).toEqual([0, 1, 0]);
+    expect(tally.qualitySums.accuracy.toNumber()).toBe(20);
+    expect(tally.weightSum.toNumber()).toBe(100);
+    expect(tally.disputedVotes).toBe(0);
+    expect(tally.removedVotes).toBe(0);
+    expect((await fetchAccount(voteProgram, 'peerVote', peerVotePda(receiptPda))).disputeStatus).toEqual({
+      reinstated: {},
+    });
+
+    // A reinstated vote cannot be disputed again
+    await expect(dispute(agent, receiptPda)).rejects.toThrow(/VoteAlreadyDisputed/);
+  });
+
+  test('upholding a dispute removes the vote for good', async () => {
+    const agent = await votedAgent();
+    const receiptPda = await transactionReceipt(voteProgram, voter, agent);
+    await vote(agent, receiptPda, { downvote: {} }, quality(20));
+    await dispute(agent, receiptPda);
+
+    await resolve(agent, receiptPda, true);
+    await expect(resolve(agent, receiptPda, false)).rejects.toThrow(/VoteNotDisputed/);
+
+    const tally = await fetchAccount(voteProgram, 'voteTally', voteTallyPda(agent.publicKey));
+    expect([tally.upvotes, tally.downvotes, tally.neutrals]).toEqual([0, 0, 0]);
+    expect(tally.qualitySums.accuracy.toNumber()).toBe(0);
+    expect(tally.weightSum.toNumber()).toBe(0);
+    expect(tally.disputedVotes).toBe(0);
+    expect(tally.removedVotes).toBe(1);
+    expect((await fetchAccount(voteProgram, 'peerVote', peerVotePda(receiptPda))).disputeStatus).toEqual({
+      removed: {},
+    });
+  });
+});
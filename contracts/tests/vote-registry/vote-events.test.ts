@@ -0,0 +1,340 @@
+/**
+ * Vote Events Tests
+ * Tests the events vote_registry emits for indexers and dashboards
+ *
+ * Vote events ensure:
+ * 1. Receipts, peer votes, content ratings and endorsements each emit one typed event
+ * 2. Every event names the PDAs it wrote, so indexers can back-fill accounts cheaply
+ * 3. Vote, rating and endorsement events carry the running totals they updated
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram, Transaction, TransactionInstruction } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  now,
+  simulateEvents,
+} from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+const VOTER_REPUTATION = 500;
+const AMOUNT = 1_000_000;
+
+const QUALITY = { responseQuality: 90, responseSpeed: 80, accuracy: 71, professionalism: 60 };
+
+describe('Vote Events', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let voter: Keypair;
+  let agent: Keypair;
+  let authorityPda: PublicKey;
+  let cpiAuthorityPda: PublicKey;
+  let receiptCount = 0;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function votePda(seeds: Buffer[]): PublicKey {
+    return PublicKey.findProgramAddressSync(seeds, VOTE_PROGRAM_ID)[0];
+  }
+
+  /**
+   * Simulate `ix` to decode the events it emits, then send it for real.
+   * Returns the single event, which must be named `name`.
+   */
+  async function emitted(ix: TransactionInstruction, signers: Keypair[], name: string) {
+    const events = await simulateEvents(context, voteProgram, ix, signers);
+    expect(events.map((event) => event.name)).toEqual([name]);
+
+    const tx = new Transaction().add(ix);
+    tx.recentBlockhash = context.lastBlockhash;
+    tx.feePayer = context.payer.publicKey;
+    tx.sign(context.payer, ...signers);
+    await context.banksClient.processTransaction(tx);
+    return events[0].data;
+  }
+
+  async function registerWithReputation(wallet: Keypair) {
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        agent: wallet.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda(wallet.publicKey),
+        agentAddress: wallet.publicKey,
+        authorityAccount: null,
+        initializer: wallet.publicKey,
+        payer: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+  }
+
+  /** Create a voter -> agent receipt and return its address and ReceiptCreated event */
+  async function receipt() {
+    const signature = `sig_${receiptCount++}`;
+    const signatureHash = Array.from(createHash('sha256').update(signature).digest());
+    const receiptPda = votePda([
+      Buffer.from('tx_receipt'),
+      voter.publicKey.toBuffer(),
+      agent.publicKey.toBuffer(),
+      Buffer.from(signatureHash),
+    ]);
+
+    const ix = await voteProgram.methods
+      .createTransactionReceipt(signature, signatureHash, new BN(AMOUNT), { apiResponse: {} }, PublicKey.default)
+      .accounts({
+        receipt: receiptPda,
+        payerPubkey: voter.publicKey,
+        recipientPubkey: agent.publicKey,
+        creator: voter.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+    const event = await emitted(ix, [voter], 'receiptCreated');
+    return { receiptPda, signature, event };
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    voter = Keypair.generate();
+    agent = Keypair.generate();
+    for (const kp of [authority, voter, agent]) {
+      await airdrop(context, kp.publicKey, 10_000_000_000);
+    }
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    [cpiAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('reputation_cpi')], VOTE_PROGRAM_ID);
+
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await registerWithReputation(voter);
+    await registerWithReputation(agent);
+
+    // The voter needs enough reputation to vote, rate and endorse
+    await advanceTime(context, 10);
+    await reputationProgram.methods
+      .updateReputation(
+        VOTER_REPUTATION,
+        { trust: 50, quality: 50, reliability: 50, economic: 50, social: 50 },
+        { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+        new Array(32).fill(0),
+        new BN(0),
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda(voter.publicKey),
+        authorityAccount: authorityPda,
+        agentAddress: voter.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('create_transaction_receipt emits ReceiptCreated', async () => {
+    const { receiptPda, signature, event } = await receipt();
+
+    expect(event.receipt.equals(receiptPda)).toBe(true);
+    expect(event.creator.equals(voter.publicKey)).toBe(true);
+    expect(event.payer.equals(voter.publicKey)).toBe(true);
+    expect(event.recipient.equals(agent.publicKey)).toBe(true);
+    expect(event.signature).toBe(signature);
+    expect(event.amount.toNumber()).toBe(AMOUNT);
+    expect(event.paymentMint.equals(PublicKey.default)).toBe(true);
+    expect(event.contentType).toEqual({ apiResponse: {} });
+    expect(event.verified).toBe(false);
+    expect(event.timestamp.toNumber()).toBe(await now(context));
+  });
+
+  test('cast_peer_vote emits PeerVoteCast with the weight, quality average and tally', async () => {
+    const { receiptPda } = await receipt();
+    const peerVote = votePda([Buffer.from('peer_vote'), receiptPda.toBuffer(), voter.publicKey.toBuffer()]);
+
+    const ix = await voteProgram.methods
+      .castPeerVote(agent.publicKey, { upvote: {} }, QUALITY, new Array(32).fill(0))
+      .accounts({
+        peerVote,
+        transactionReceipt: receiptPda,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(agent.publicKey),
+        votedAgentReputation: reputationPda(agent.publicKey),
+        reputationCpiAuthority: cpiAuthorityPda,
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+    const event = await emitted(ix, [voter], 'peerVoteCast');
+
+    const vote = await fetchAccount(voteProgram, 'peerVote', peerVote);
+    const tallyPda = votePda([Buffer.from('vote_tally'), agent.publicKey.toBuffer()]);
+    const tally = await fetchAccount(voteProgram, 'voteTally', tallyPda);
+    expect(event.peerVote.equals(peerVote)).toBe(true);
+    expect(event.transactionReceipt.equals(receiptPda)).toBe(true);
+    expect(event.voteTally.equals(tallyPda)).toBe(true);
+    const pairPda = votePda([Buffer.from('vote_pair'), voter.publicKey.toBuffer(), agent.publicKey.toBuffer()]);
+    expect(event.votePair.equals(pairPda)).toBe(true);
+    expect(event.voter.equals(voter.publicKey)).toBe(true);
+    expect(event.votedAgent.equals(agent.publicKey)).toBe(true);
+    expect(event.voteType).toEqual({ upvote: {} });
+    expect(event.voteWeight).toBe(vote.voteWeight);
+    expect(event.qualityScores).toEqual(QUALITY);
+    // (90 + 80 + 71 + 60) / 4 = 75.25
+    expect(event.qualityAverage).toBe(75);
+    expect(event.voterReputation).toBe(VOTER_REPUTATION);
+    expect(event.newVoter).toBe(vote.newVoter);
+    expect(event.upvotes).toBe(tally.upvotes);
+    expect(event.downvotes).toBe(0);
+    expect(event.neutrals).toBe(0);
+    expect(event.weightSum.toNumber()).toBe(tally.weightSum.toNumber());
+  });
+
+  test('rate_content emits ContentRated with the agent rating totals', async () => {
+    const { receiptPda, event: created } = await receipt();
+    const contentRating = votePda([Buffer.from('content_rating'), receiptPda.toBuffer()]);
+
+    const ix = await voteProgram.methods
+      .rateContent(84)
+      .accounts({
+        contentRating,
+        transactionReceipt: receiptPda,
+        raterIdentity: identityPda(voter.publicKey),
+        raterReputation: reputationPda(voter.publicKey),
+        ratedAgentIdentity: identityPda(agent.publicKey),
+        ratedAgent: agent.publicKey,
+        ratedAgentReputation: reputationPda(agent.publicKey),
+        reputationCpiAuthority: cpiAuthorityPda,
+        rater: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+    const event = await emitted(ix, [voter], 'contentRated');
+
+    const statsPda = votePda([Buffer.from('content_stats'), agent.publicKey.toBuffer()]);
+    const stats = await fetchAccount(voteProgram, 'contentRatingStats', statsPda);
+    expect(event.contentRating.equals(contentRating)).toBe(true);
+    expect(event.contentStats.equals(statsPda)).toBe(true);
+    expect(event.transactionReceipt.equals(receiptPda)).toBe(true);
+    expect(event.agent.equals(agent.publicKey)).toBe(true);
+    expect(event.rater.equals(voter.publicKey)).toBe(true);
+    expect(event.qualityRating).toBe(84);
+    expect(event.contentType).toEqual(created.contentType);
+    expect(event.amountPaid.toNumber()).toBe(AMOUNT);
+    expect(event.paymentMint.equals(PublicKey.default)).toBe(true);
+    expect(event.raterReputation).toBe(VOTER_REPUTATION);
+    expect(event.totalRatings).toBe(1);
+    expect(event.totalRatingSum.toNumber()).toBe(84);
+    expect(event.totalWeightedRatingSum.toNumber()).toBe(stats.totalWeightedRatingSum.toNumber());
+    expect(event.totalWeightSum.toNumber()).toBe(stats.totalWeightSum.toNumber());
+  });
+
+  test('endorse_agent emits AgentEndorsed with the stake, weight and counters', async () => {
+    const endorsement = votePda([Buffer.from('endorsement'), voter.publicKey.toBuffer(), agent.publicKey.toBuffer()]);
+    const endorserStats = votePda([Buffer.from('endorsement_stats'), voter.publicKey.toBuffer()]);
+    const endorsedStats = votePda([Buffer.from('endorsement_stats'), agent.publicKey.toBuffer()]);
+
+    const ix = await voteProgram.methods
+      .endorseAgent(agent.publicKey, 70, { technical: {} })
+      .accounts({
+        endorsement,
+        endorserStats,
+        endorsedStats,
+        endorserIdentity: identityPda(voter.publicKey),
+        endorserReputation: reputationPda(voter.publicKey),
+        endorsedAgentIdentity: identityPda(agent.publicKey),
+        endorser: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        voteConfig: null,
+      })
+      .instruction();
+    const event = await emitted(ix, [voter], 'agentEndorsed');
+
+    const account = await fetchAccount(voteProgram, 'agentEndorsement', endorsement);
+    expect(event.endorsement.equals(endorsement)).toBe(true);
+    expect(event.endorserStats.equals(endorserStats)).toBe(true);
+    expect(event.endorsedStats.equals(endorsedStats)).toBe(true);
+    expect(event.endorser.equals(voter.publicKey)).toBe(true);
+    expect(event.endorsed.equals(agent.publicKey)).toBe(true);
+    expect(event.strength).toBe(70);
+    expect(event.category).toEqual({ technical: {} });
+    // Without a vote config the stake is the flat 0.01 SOL minimum
+    expect(event.stakeAmount.toNumber()).toBe(10_000_000);
+    expect(event.weight).toBe(70 * VOTER_REPUTATION);
+    expect(event.endorserReputation).toBe(VOTER_REPUTATION);
+    expect(event.expiresAt.toNumber()).toBe(account.expiresAt.toNumber());
+    expect(event.endorsementsGiven).toBe(1);
+    expect(event.endorsementsReceived).toBe(1);
+  });
+});
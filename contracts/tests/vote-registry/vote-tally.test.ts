@@ -0,0 +1,245 @@
+/**
+ * Vote Tally Tests
+ * Tests the per-agent VoteTally maintained by cast_peer_vote and the get_vote_tally view
+ *
+ * The tally ensures:
+ * 1. Each vote increments the counter for its type, the weight sum and last_vote_at
+ * 2. Quality sums divided by the vote count give each dimension's average
+ * 3. Votes on different receipts submitted together both land
+ * 4. get_vote_tally returns the account via return data
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import {
+  airdrop,
+  advanceTime,
+  loadProgram,
+  metadataHash,
+  mockCoreAsset,
+  now,
+  simulateReturnData,
+  transactionReceipt,
+} from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+type Quality = { responseQuality: number; responseSpeed: number; accuracy: number; professionalism: number };
+
+function quality(value: number): Quality {
+  return { responseQuality: value, responseSpeed: value, accuracy: value, professionalism: value };
+}
+
+describe('Vote Tally', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let voter: Keypair;
+  let authorityPda: PublicKey;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function voteTallyPda(agent: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('vote_tally'), agent.toBuffer()], VOTE_PROGRAM_ID)[0];
+  }
+
+  async function registerWithReputation(wallet: Keypair) {
+    const asset = mockCoreAsset(context, wallet.publicKey);
+    await identityProgram.methods
+      .registerAgent(
+        asset,
+        'https://example.com/agent.json',
+        metadataHash('https://example.com/agent.json'),
+        0,
+        null,
+        null,
+        false
+      )
+      .accounts({
+        agentIdentity: identityPda(wallet.publicKey),
+        agent: wallet.publicKey,
+        asset,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+    await reputationProgram.methods
+      .initializeReputation()
+      .accounts({
+        agentReputation: reputationPda(wallet.publicKey),
+        agentAddress: wallet.publicKey,
+        authorityAccount: null,
+        initializer: wallet.publicKey,
+        payer: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+  }
+
+  async function votedAgent(): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdrop(context, agent.publicKey, 10_000_000_000);
+    await registerWithReputation(agent);
+    return agent;
+  }
+
+  function vote(agent: Keypair, receiptPda: PublicKey, voteType: object, scores: Quality) {
+    const [votePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('peer_vote'), receiptPda.toBuffer()],
+      VOTE_PROGRAM_ID
+    );
+
+    return voteProgram.methods
+      .castPeerVote(agent.publicKey, voteType, scores, new Array(32).fill(0))
+      .accounts({
+        peerVote: votePda,
+        voteTally: voteTallyPda(agent.publicKey),
+        transactionReceipt: receiptPda,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(agent.publicKey),
+        votedAgentReputation: reputationPda(agent.publicKey),
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    voter = Keypair.generate();
+    for (const kp of [authority, voter]) {
+      await airdrop(context, kp.publicKey, 10_000_000_000);
+    }
+
+    [authorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: authorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await registerWithReputation(voter);
+
+    // Voter needs a score >= 100 to vote
+    await advanceTime(context, 10);
+    await reputationProgram.methods
+      .updateReputation(
+        500,
+        { trust: 50, quality: 50, reliability: 50, economic: 50, social: 50 },
+        { totalVotes: 0, positiveVotes: 0, negativeVotes: 0, totalReviews: 0, avgReviewRating: 0 },
+        new Array(32).fill(0),
+        new BN(0),
+        new BN(await now(context))
+      )
+      .accounts({
+        agentReputation: reputationPda(voter.publicKey),
+        authorityAccount: authorityPda,
+        agentAddress: voter.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('three votes of different types each increment their counter', async () => {
+    const agent = await votedAgent();
+    await vote(agent, await transactionReceipt(voteProgram, voter, agent), { upvote: {} }, quality(80));
+    await vote(agent, await transactionReceipt(voteProgram, voter, agent), { downvote: {} }, quality(80));
+    await vote(agent, await transactionReceipt(voteProgram, voter, agent), { neutral: {} }, quality(80));
+
+    const tally = await fetchAccount(voteProgram, 'voteTally', voteTallyPda(agent.publicKey));
+    expect(tally.agent.toBase58()).toBe(agent.publicKey.toBase58());
+    expect(tally.upvotes).toBe(1);
+    expect(tally.downvotes).toBe(1);
+    expect(tally.neutrals).toBe(1);
+    // Every transaction weighs 100 (1.0x)
+    expect(tally.weightSum.toNumber()).toBe(300);
+    expect(tally.lastVoteAt.toNumber()).toBe(await now(context));
+  });
+
+  test('quality sums give per-dimension averages', async () => {
+    const agent = await votedAgent();
+    const votes: Quality[] = [
+      { responseQuality: 90, responseSpeed: 60, accuracy: 100, professionalism: 70 },
+      { responseQuality: 70, responseSpeed: 80, accuracy: 40, professionalism: 70 },
+      { responseQuality: 50, responseSpeed: 100, accuracy: 70, professionalism: 70 },
+    ];
+    for (const scores of votes) {
+      await vote(agent, await transactionReceipt(voteProgram, voter, agent), { upvote: {} }, scores);
+    }
+
+    const ix = await voteProgram.methods
+      .getVoteTally()
+      .accounts({ voteTally: voteTallyPda(agent.publicKey), agent: agent.publicKey })
+      .instruction();
+    const tally = voteProgram.coder.types.decode('VoteTally', await simulateReturnData(context, ix));
+    const count = tally.upvotes + tally.downvotes + tally.neutrals;
+    expect(count).toBe(3);
+
+    const sums = tally.qualitySums;
+    expect(sums.responseQuality.toNumber() / count).toBe(70);
+    expect(sums.responseSpeed.toNumber() / count).toBe(80);
+    expect(sums.accuracy.toNumber() / count).toBe(70);
+    expect(sums.professionalism.toNumber() / count).toBe(70);
+  });
+
+  test('votes on different receipts submitted together both land', async () => {
+    const agent = await votedAgent();
+    const first = await transactionReceipt(voteProgram, voter, agent);
+    const second = await transactionReceipt(voteProgram, voter, agent);
+
+    await Promise.all([
+      vote(agent, first, { upvote: {} }, quality(90)),
+      vote(agent, second, { downvote: {} }, quality(30)),
+    ]);
+
+    const tally = await fetchAccount(voteProgram, 'voteTally', voteTallyPda(agent.publicKey));
+    expect(tally.upvotes).toBe(1);
+    expect(tally.downvotes).toBe(1);
+    expect(tally.qualitySums.accuracy.toNumber()).toBe(120);
+    expect(tally.weightSum.toNumber()).toBe(200);
+  });
+});
@@ -0,0 +1,286 @@
+/**
+ * Vote Weighting Tests
+ * Tests the transaction amount tiers that set a peer vote's weight
+ *
+ * Tiered weighting ensures:
+ * 1. By default votes weigh 1.0x below 0.01 SOL, 1.25x up to 0.1 SOL and 1.5x from 0.1 SOL
+ * 2. SPL amounts are normalized with the registered mint decimals; unregistered mints get the first tier
+ * 3. The weight is stored in PeerVote.vote_weight and summed into VoteTally.weight_sum
+ * 4. The authority can change the tiers, but no tier may exceed the 3.0x cap
+ * 5. flat_vote_weight makes every vote 1.0x, and existing votes keep their weight
+ */
+
+import { describe, test, beforeAll, expect } from '@jest/globals';
+import { startAnchor, ProgramTestContext } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, type Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { fetchAccount } from '../helpers/anchor-accounts';
+import { airdrop, loadProgram, reputableAgent } from '../helpers/bankrun';
+
+const IDENTITY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e');
+const REPUTATION_PROGRAM_ID = new PublicKey('A99rMj3Nu975ShFzyhPyae9raBPxDYQiwi8g6RPC73Mp');
+const VOTE_PROGRAM_ID = new PublicKey('EKqkjsLHK8rFr7pdySSFKZjhQfnEWeVqPRdZekw1t1j6');
+
+const USDC_MINT = new PublicKey('EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v');
+const QUALITY = { responseQuality: 80, responseSpeed: 80, accuracy: 80, professionalism: 80 };
+
+describe('Vote Weighting', () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let identityProgram: Program<Idl>;
+  let reputationProgram: Program<Idl>;
+  let voteProgram: Program<Idl>;
+  let authority: Keypair;
+  let reputationAuthorityPda: PublicKey;
+  let voteAuthorityPda: PublicKey;
+  let voteConfigPda: PublicKey;
+  let receiptCount = 0;
+
+  function identityPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from('agent'), wallet.toBuffer()], IDENTITY_PROGRAM_ID)[0];
+  }
+
+  function reputationPda(wallet: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), wallet.toBuffer()],
+      REPUTATION_PROGRAM_ID
+    )[0];
+  }
+
+  function receiptPda(payer: PublicKey, recipient: PublicKey, signatureHash: number[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('tx_receipt'), payer.toBuffer(), recipient.toBuffer(), Buffer.from(signatureHash)],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function nextSignature(): { signature: string; signatureHash: number[] } {
+    const signature = `weight_sig_${receiptCount++}`;
+    return { signature, signatureHash: Array.from(createHash('sha256').update(signature).digest()) };
+  }
+
+  /** A registered agent whose reputation score (500) allows it to vote */
+  function votingAgent(): Promise<Keypair> {
+    return reputableAgent(context, identityProgram, reputationProgram, authority);
+  }
+
+  /** Create a receipt for a payment of `amount` base units of `paymentMint` from `payer` to `recipient` */
+  async function receipt(
+    payer: Keypair,
+    recipient: Keypair,
+    amount: number,
+    paymentMint = PublicKey.default
+  ): Promise<PublicKey> {
+    const { signature, signatureHash } = nextSignature();
+    const receiptAddress = receiptPda(payer.publicKey, recipient.publicKey, signatureHash);
+    await voteProgram.methods
+      .createTransactionReceipt(signature, signatureHash, new BN(amount), { apiResponse: {} }, paymentMint)
+      .accounts({
+        receipt: receiptAddress,
+        payerPubkey: payer.publicKey,
+        recipientPubkey: recipient.publicKey,
+        creator: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+    return receiptAddress;
+  }
+
+  function peerVotePda(receipt: PublicKey, voter: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('peer_vote'), receipt.toBuffer(), voter.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function voteTallyPda(agent: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('vote_tally'), agent.publicKey.toBuffer()],
+      VOTE_PROGRAM_ID
+    )[0];
+  }
+
+  function vote(receipt: PublicKey, voter: Keypair, votedAgent: Keypair) {
+    return voteProgram.methods
+      .castPeerVote(votedAgent.publicKey, { upvote: {} }, QUALITY, new Array(32).fill(0))
+      .accounts({
+        peerVote: peerVotePda(receipt, voter),
+        transactionReceipt: receipt,
+        voterIdentity: identityPda(voter.publicKey),
+        voterReputation: reputationPda(voter.publicKey),
+        votedAgentIdentity: identityPda(votedAgent.publicKey),
+        votedAgentReputation: reputationPda(votedAgent.publicKey),
+        voter: voter.publicKey,
+        identityRegistryProgram: IDENTITY_PROGRAM_ID,
+        reputationRegistryProgram: REPUTATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        voteConfig: voteConfigPda,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  function setMintDecimals(mint: PublicKey, decimals: number, signer = authority) {
+    return voteProgram.methods
+      .setPaymentMintDecimals(mint, decimals)
+      .accounts({ voteConfig: voteConfigPda, authorityAccount: voteAuthorityPda, authority: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  function updateWeighting(flat: boolean, thresholds: number[], weightsBps: number[], signer = authority) {
+    return voteProgram.methods
+      .updateVoteWeighting(flat, { thresholds: thresholds.map((t) => new BN(t)), weightsBps })
+      .accounts({ voteConfig: voteConfigPda, authorityAccount: voteAuthorityPda, authority: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  /** Vote on `seller` from a fresh buyer paying `amount` base units of `paymentMint`; returns the vote's weight */
+  async function weightOf(seller: Keypair, amount: number, paymentMint = PublicKey.default): Promise<number> {
+    const buyer = await votingAgent();
+    const receiptAddress = await receipt(buyer, seller, amount, paymentMint);
+    await vote(receiptAddress, buyer, seller);
+    const peerVote = await fetchAccount(voteProgram, 'peerVote', peerVotePda(receiptAddress, buyer));
+    return peerVote.voteWeight;
+  }
+
+  beforeAll(async () => {
+    context = await startAnchor(
+      '',
+      [
+        { name: 'identity_registry', programId: IDENTITY_PROGRAM_ID },
+        { name: 'reputation_registry', programId: REPUTATION_PROGRAM_ID },
+        { name: 'vote_registry', programId: VOTE_PROGRAM_ID },
+      ],
+      []
+    );
+    provider = new BankrunProvider(context);
+    identityProgram = loadProgram('identity_registry', provider);
+    reputationProgram = loadProgram('reputation_registry', provider);
+    voteProgram = loadProgram('vote_registry', provider);
+
+    authority = Keypair.generate();
+    await airdrop(context, authority.publicKey, 10_000_000_000);
+
+    [reputationAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], REPUTATION_PROGRAM_ID);
+    await reputationProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: reputationAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('authority')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeAuthority()
+      .accounts({
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        initializer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    [voteConfigPda] = PublicKey.findProgramAddressSync([Buffer.from('config')], VOTE_PROGRAM_ID);
+    await voteProgram.methods
+      .initializeVoteConfig()
+      .accounts({
+        voteConfig: voteConfigPda,
+        authorityAccount: voteAuthorityPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test('a new config uses the default tiers', async () => {
+    const config = await fetchAccount(voteProgram, 'voteConfig', voteConfigPda);
+    expect(config.flatVoteWeight).toBe(false);
+    expect(config.voteWeightTiers.thresholds.map((t: BN) => t.toNumber())).toEqual([10_000_000, 100_000_000]);
+    expect(config.voteWeightTiers.weightsBps).toEqual([10_000, 12_500, 15_000]);
+  });
+
+  test('each tier boundary belongs to the tier it starts, and the tally sums the weights', async () => {
+    const seller = await votingAgent();
+
+    expect(await weightOf(seller, 1)).toBe(100);
+    expect(await weightOf(seller, 9_999_999)).toBe(100);
+    expect(await weightOf(seller, 10_000_000)).toBe(125);
+    expect(await weightOf(seller, 99_999_999)).toBe(125);
+    expect(await weightOf(seller, 100_000_000)).toBe(150);
+    expect(await weightOf(seller, 1_000_000_000_000)).toBe(150);
+
+    const tally = await fetchAccount(voteProgram, 'voteTally', voteTallyPda(seller));
+    expect(tally.upvotes).toBe(6);
+    expect(tally.weightSum.toNumber()).toBe(100 + 100 + 125 + 125 + 150 + 150);
+  });
+
+  test('SPL amounts are normalized with the registered decimals', async () => {
+    const seller = await votingAgent();
+    await setMintDecimals(USDC_MINT, 6);
+
+    expect(await weightOf(seller, 9_999, USDC_MINT)).toBe(100);
+    expect(await weightOf(seller, 10_000, USDC_MINT)).toBe(125);
+    expect(await weightOf(seller, 100_000, USDC_MINT)).toBe(150);
+
+    // Without registered decimals the amount cannot be normalized: first tier
+    expect(await weightOf(seller, 1_000_000_000, Keypair.generate().publicKey)).toBe(100);
+  });
+
+  test('custom tiers apply up to the 3.0x cap', async () => {
+    const seller = await votingAgent();
+    await updateWeighting(false, [1_000, 2_000], [5_000, 20_000, 30_000]);
+
+    expect(await weightOf(seller, 999)).toBe(50);
+    expect(await weightOf(seller, 1_000)).toBe(200);
+    expect(await weightOf(seller, 2_000)).toBe(300);
+
+    await expect(updateWeighting(false, [1_000, 2_000], [10_000, 20_000, 30_100])).rejects.toThrow(
+      /InvalidVoteWeightTiers/
+    );
+    await updateWeighting(false, [10_000_000, 100_000_000], [10_000, 12_500, 15_000]);
+  });
+
+  test('tiers must ascend and use whole hundredths, and only the authority can set them', async () => {
+    const outsider = await votingAgent();
+    const invalid = [
+      [[0, 100_000_000], [10_000, 12_500, 15_000]],
+      [[100_000_000, 100_000_000], [10_000, 12_500, 15_000]],
+      [[100_000_000, 10_000_000], [10_000, 12_500, 15_000]],
+      [[10_000_000, 100_000_000], [0, 12_500, 15_000]],
+      [[10_000_000, 100_000_000], [10_000, 12_550, 15_000]],
+    ];
+    for (const [thresholds, weightsBps] of invalid) {
+      await expect(updateWeighting(false, thresholds, weightsBps)).rejects.toThrow(/InvalidVoteWeightTiers/);
+    }
+
+    await expect(
+      updateWeighting(true, [10_000_000, 100_000_000], [10_000, 12_500, 15_000], outsider)
+    ).rejects.toThrow(/UnauthorizedAuthority/);
+  });
+
+  test('flat mode weighs every vote 1.0x and leaves existing weights alone', async () => {
+    const seller = await votingAgent();
+    expect(await weightOf(seller, 500_000_000)).toBe(150);
+
+    await updateWeighting(true, [10_000_000, 100_000_000], [10_000, 12_500, 15_000]);
+    expect(await weightOf(seller, 500_000_000)).toBe(100);
+    expect(await weightOf(seller, 50_000_000)).toBe(100);
+
+    const tally = await fetchAccount(voteProgram, 'voteTally', voteTallyPda(seller));
+    expect(tally.weightSum.toNumber()).toBe(150 + 100 + 100);
+
+    await updateWeighting(false, [10_000_000, 100_000_000], [10_000, 12_500, 15_000]);
+    expect(await weightOf(seller, 500_000_000)).toBe(150);
+  });
+});
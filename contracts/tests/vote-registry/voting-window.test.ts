@@ -76,7 +76,8 @@ describe('30-Day Voting Window', () => {
         signature,
         signatureHash,
         new BN(100_000_000), // 0.1 SOL
-        { chat: {} }
+        { chat: {} },
+        PublicKey.default
       )
       .accounts({
         receipt: receiptPda,
@@ -107,7 +108,7 @@ describe('30-Day Voting Window', () => {
   // Helper to derive vote accounts
   function deriveVoteAccounts(receiptPda: PublicKey) {
     const [votePda] = PublicKey.findProgramAddressSync(
-      [Buffer.from('peer_vote'), receiptPda.toBuffer()],
+      [Buffer.from('peer_vote'), receiptPda.toBuffer(), voter.publicKey.toBuffer()],
       voteProgram.programId
     );
 
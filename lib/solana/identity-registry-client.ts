@@ -28,12 +28,38 @@ const AGENT_SEED = Buffer.from('agent')
 const STAKING_POOL_SEED = Buffer.from('staking_pool')
 const STAKE_ACCOUNT_SEED = Buffer.from('stake_account')
 const PROGRAM_CONFIG_SEED = Buffer.from('program_config')
-const USER_RATE_LIMIT_SEED = Buffer.from('user_rate_limit')
+const USER_RATE_LIMIT_SEED = Buffer.from('rate_limit')
+const RATE_EXEMPT_SEED = Buffer.from('rate_exempt')
+const NAME_SEED = Buffer.from('name')
+const DELEGATED_STAKE_SEED = Buffer.from('delegated_stake')
 
 // ============================================================================
 // TYPES
 // ============================================================================
 
+/** Capability bits on AgentIdentity.capabilities */
+export const CAPABILITIES = {
+  inference: 1 << 0,
+  dataFeeds: 1 << 1,
+  codeGeneration: 1 << 2,
+  trading: 1 << 3,
+} as const
+
+/** Mirrors the on-chain ServiceCategory enum (Borsh variant index) */
+export enum ServiceCategory {
+  Inference = 0,
+  DataFeed = 1,
+  CodeGeneration = 2,
+  Trading = 3,
+  Other = 4,
+}
+
+/** Mirrors the on-chain HealthStatus enum (Borsh variant index) */
+export enum HealthStatus {
+  Healthy = 0,
+  Degraded = 1,
+}
+
 export interface AgentIdentity {
   agentAddress: PublicKey
   assetAddress: PublicKey
@@ -43,6 +69,53 @@ export interface AgentIdentity {
   activityCount: bigint
   isActive: boolean
   bump: number
+  capabilities: number
+  serviceCategory: ServiceCategory | null
+  /** sha256 of the metadata JSON (all zeros if never set) */
+  metadataHash: Uint8Array
+  metadataVersion: number
+  /** NameRecord PDA of the claimed name (default public key if none) */
+  nameRecord: PublicKey
+  /** Unix timestamp after which the identity lapses until renewed (0 = never) */
+  expiresAt: bigint
+  /** Referring agent (default public key if none) */
+  referrer: PublicKey
+  referralCount: number
+  /** Unix timestamp of the most recent deactivation (0 while active) */
+  deactivatedAt: bigint
+  /** Signer of the most recent deactivation (default public key while active) */
+  deactivatedBy: PublicKey
+  /** sha256 of the deactivation reason (all zeros while active) */
+  deactivationReasonHash: Uint8Array
+  /** Lamports staked for the agent by backers, net of slashes */
+  delegatedStake: bigint
+  delegatedShares: bigint
+  /** Shares backers have requested to withdraw (still slashable) */
+  pendingDelegatedShares: bigint
+  /** Unix timestamp of the most recent heartbeat (0 if none) */
+  lastHeartbeatAt: bigint
+  healthStatus: HealthStatus
+  /** False for soulbound identities */
+  transferable: boolean
+  /** Layout version; 0 until migrate_account upgrades the account */
+  version: number
+}
+
+export interface NameRecord {
+  name: string
+  agent: PublicKey
+  claimedAt: bigint
+  bump: number
+}
+
+/** One agent in a register_agents_batch call; `agent` must sign the transaction */
+export interface BatchRegistration {
+  agent: PublicKey
+  assetAddress: PublicKey
+  metadataUri: string
+  metadataHash: Uint8Array
+  /** Whether the identity can later be moved with transfer_identity (default false) */
+  transferable?: boolean
 }
 
 export interface StakingPool {
@@ -66,6 +139,16 @@ export interface StakeAccount {
   bump: number
 }
 
+/** A backer's position in an agent's delegated stake */
+export interface DelegatedStake {
+  agent: PublicKey
+  backer: PublicKey
+  shares: bigint
+  pendingShares: bigint
+  unlockTimestamp: bigint
+  bump: number
+}
+
 export interface ProgramConfig {
   admin: PublicKey
   isPaused: boolean
@@ -73,6 +156,17 @@ export interface ProgramConfig {
   rateLimitPerMinute: number
   pausedAt: bigint
   bump: number
+  pendingAdmin: PublicKey
+  maxPauseDurationSeconds: bigint
+  /** Identity lifetime at registration and renewal (0 = identities never expire) */
+  defaultValiditySeconds: bigint
+}
+
+export interface ExemptAccount {
+  account: PublicKey
+  maxPerMinute: number | null
+  grantedAt: bigint
+  bump: number
 }
 
 export interface UserRateLimit {
@@ -80,6 +174,7 @@ export interface UserRateLimit {
   requestCount: number
   windowStart: bigint
   bump: number
+  payer: PublicKey
 }
 
 // ============================================================================
@@ -128,28 +223,80 @@ export function getUserRateLimitPDA(
   )
 }
 
+/** `name` must already be normalized: 3-32 chars of a-z, 0-9 and inner dashes */
+export function getNameRecordPDA(
+  name: string,
+  programId: PublicKey = IDENTITY_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([NAME_SEED, Buffer.from(name)], programId)
+}
+
+export function getDelegatedStakePDA(
+  agentAddress: PublicKey,
+  backer: PublicKey,
+  programId: PublicKey = IDENTITY_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [DELEGATED_STAKE_SEED, agentAddress.toBuffer(), backer.toBuffer()],
+    programId
+  )
+}
+
+export function getRateExemptionPDA(
+  account: PublicKey,
+  programId: PublicKey = IDENTITY_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [RATE_EXEMPT_SEED, account.toBuffer()],
+    programId
+  )
+}
+
 // ============================================================================
 // INSTRUCTION DISCRIMINATORS (from Anchor IDL)
 // ============================================================================
 
 const DISCRIMINATORS = {
   registerAgent: Buffer.from([135, 157, 66, 195, 2, 113, 175, 30]),
+  registerAgentsBatch: Buffer.from([166, 43, 136, 74, 251, 208, 78, 63]),
   updateIdentity: Buffer.from([130, 54, 88, 104, 222, 124, 238, 252]),
+  updateCapabilities: Buffer.from([102, 104, 235, 240, 127, 163, 100, 149]),
+  verifyMetadata: Buffer.from([154, 243, 227, 221, 136, 178, 119, 217]),
   verifyIdentity: Buffer.from([177, 162, 9, 111, 44, 84, 80, 21]),
   deactivateAgent: Buffer.from([205, 171, 239, 225, 82, 126, 96, 166]),
+  setDelegate: Buffer.from([242, 30, 46, 76, 108, 235, 128, 181]),
+  revokeDelegate: Buffer.from([142, 66, 98, 126, 102, 60, 92, 163]),
+  claimName: Buffer.from([198, 183, 177, 101, 183, 218, 69, 237]),
+  releaseName: Buffer.from([3, 68, 17, 217, 57, 53, 123, 164]),
+  transferName: Buffer.from([32, 215, 182, 130, 12, 106, 157, 75]),
+  renewIdentity: Buffer.from([197, 64, 180, 28, 143, 21, 192, 184]),
+  transferIdentity: Buffer.from([182, 143, 44, 176, 187, 28, 115, 57]),
+  recordHeartbeat: Buffer.from([109, 43, 126, 223, 32, 70, 78, 82]),
   initializeStakingPool: Buffer.from([63, 144, 152, 249, 210, 189, 59, 155]),
   stakeCollateral: Buffer.from([88, 42, 51, 179, 124, 181, 254, 97]),
-  unstakeCollateral: Buffer.from([72, 244, 105, 31, 143, 57, 206, 59]),
+  requestUnstake: Buffer.from([44, 154, 110, 253, 160, 202, 54, 34]),
+  withdrawUnstaked: Buffer.from([19, 202, 68, 255, 216, 40, 205, 61]),
+  stakeForAgent: Buffer.from([178, 52, 21, 97, 185, 55, 103, 166]),
+  requestDelegatedUnstake: Buffer.from([26, 6, 141, 8, 13, 44, 195, 39]),
+  withdrawDelegatedStake: Buffer.from([203, 159, 183, 190, 144, 29, 114, 230]),
   slashAgent: Buffer.from([186, 35, 159, 224, 128, 46, 176, 95]),
   pauseStaking: Buffer.from([67, 210, 243, 112, 159, 34, 18, 100]),
   unpauseStaking: Buffer.from([214, 25, 146, 89, 178, 194, 186, 39]),
   initializeProgramConfig: Buffer.from([125, 156, 41, 246, 158, 139, 192, 57]),
   pauseProgram: Buffer.from([63, 94, 213, 139, 88, 209, 52, 57]),
   unpauseProgram: Buffer.from([127, 58, 115, 129, 29, 173, 162, 44]),
+  unpauseExpired: Buffer.from([161, 124, 24, 179, 155, 234, 150, 212]),
   updateRateLimit: Buffer.from([57, 245, 88, 189, 213, 218, 245, 124]),
+  updateDefaultValidity: Buffer.from([187, 84, 40, 226, 54, 80, 48, 5]),
+  updateHeartbeatInterval: Buffer.from([171, 209, 39, 93, 109, 250, 90, 134]),
   initializeUserRateLimit: Buffer.from([38, 233, 159, 122, 255, 176, 118, 219]),
+  closeUserRateLimit: Buffer.from([214, 77, 212, 194, 12, 48, 241, 108]),
   checkRateLimit: Buffer.from([198, 144, 50, 237, 163, 145, 241, 25]),
-  transferAdmin: Buffer.from([42, 242, 66, 106, 228, 124, 83, 203]),
+  grantRateExemption: Buffer.from([54, 92, 24, 170, 102, 23, 9, 163]),
+  revokeRateExemption: Buffer.from([92, 84, 85, 39, 230, 19, 183, 201]),
+  proposeAdmin: Buffer.from([121, 214, 199, 212, 87, 39, 117, 234]),
+  acceptAdmin: Buffer.from([112, 42, 45, 90, 116, 181, 13, 170]),
+  cancelAdminTransfer: Buffer.from([38, 131, 157, 31, 240, 137, 44, 215]),
 }
 
 // ============================================================================
@@ -172,18 +319,49 @@ export class IdentityRegistryClient {
   // INSTRUCTION BUILDERS
   // ==========================================================================
 
+  /**
+   * Trailing program_config, rate_limit and rate_exemption accounts of gated
+   * instructions. The program ID stands in for the optional rate limit when it
+   * is not used.
+   */
+  private programGuardKeys(user: PublicKey, withRateLimit: boolean) {
+    const [programConfig] = getProgramConfigPDA(this.programId)
+    const [rateLimit] = getUserRateLimitPDA(user, this.programId)
+    const [rateExemption] = getRateExemptionPDA(user, this.programId)
+    return [
+      { pubkey: programConfig, isSigner: false, isWritable: false },
+      withRateLimit
+        ? { pubkey: rateLimit, isSigner: false, isWritable: true }
+        : { pubkey: this.programId, isSigner: false, isWritable: false },
+      { pubkey: rateExemption, isSigner: false, isWritable: false },
+    ]
+  }
+
   /**
    * Build register agent instruction
+   *
+   * The asset must be a Metaplex Core asset owned by `agent`. Only
+   * `transferable` identities can later be moved to another wallet.
    */
   buildRegisterAgentInstruction(
     agent: PublicKey,
     assetAddress: PublicKey,
-    metadataUri: string
+    metadataUri: string,
+    withRateLimit = false,
+    capabilities = 0,
+    serviceCategory: ServiceCategory | null = null,
+    metadataHash: Uint8Array = new Uint8Array(32),
+    referrer: PublicKey | null = null,
+    transferable = false
   ): TransactionInstruction {
     const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
 
     const metadataBuffer = Buffer.from(metadataUri)
-    const data = Buffer.alloc(8 + 32 + 4 + metadataBuffer.length)
+    const categoryLength = serviceCategory === null ? 1 : 2
+    const referrerLength = referrer === null ? 1 : 33
+    const data = Buffer.alloc(
+      8 + 32 + 4 + metadataBuffer.length + 32 + 4 + categoryLength + referrerLength + 1
+    )
     let offset = 0
     DISCRIMINATORS.registerAgent.copy(data, offset)
     offset += 8
@@ -192,12 +370,38 @@ export class IdentityRegistryClient {
     data.writeUInt32LE(metadataBuffer.length, offset)
     offset += 4
     metadataBuffer.copy(data, offset)
+    offset += metadataBuffer.length
+    Buffer.from(metadataHash).copy(data, offset)
+    offset += 32
+    data.writeUInt32LE(capabilities, offset)
+    offset += 4
+    if (serviceCategory === null) {
+      data.writeUInt8(0, offset)
+    } else {
+      data.writeUInt8(1, offset)
+      data.writeUInt8(serviceCategory, offset + 1)
+    }
+    offset += categoryLength
+    if (referrer === null) {
+      data.writeUInt8(0, offset)
+    } else {
+      data.writeUInt8(1, offset)
+      referrer.toBuffer().copy(data, offset + 1)
+    }
+    offset += referrerLength
+    data.writeUInt8(transferable ? 1 : 0, offset)
+
+    // The program ID stands in for the optional referrer identity
+    const referrerIdentity = referrer ? getAgentIdentityPDA(referrer, this.programId)[0] : this.programId
 
     return new TransactionInstruction({
       keys: [
         { pubkey: agentIdentity, isSigner: false, isWritable: true },
         { pubkey: agent, isSigner: true, isWritable: true },
+        { pubkey: assetAddress, isSigner: false, isWritable: false },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: referrerIdentity, isSigner: false, isWritable: referrer !== null },
+        ...this.programGuardKeys(agent, withRateLimit),
       ],
       programId: this.programId,
       data,
@@ -206,27 +410,285 @@ export class IdentityRegistryClient {
 
   /**
    * Build update identity instruction
+   *
+   * `signer` may be the identity's delegate when it holds the metadata scope.
    */
   buildUpdateIdentityInstruction(
     agent: PublicKey,
-    metadataUri: string
+    metadataUri: string,
+    withRateLimit = false,
+    signer: PublicKey = agent,
+    metadataHash: Uint8Array = new Uint8Array(32)
   ): TransactionInstruction {
     const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
 
     const metadataBuffer = Buffer.from(metadataUri)
-    const data = Buffer.alloc(8 + 4 + metadataBuffer.length)
+    const data = Buffer.alloc(8 + 4 + metadataBuffer.length + 32)
     let offset = 0
     DISCRIMINATORS.updateIdentity.copy(data, offset)
     offset += 8
     data.writeUInt32LE(metadataBuffer.length, offset)
     offset += 4
     metadataBuffer.copy(data, offset)
+    offset += metadataBuffer.length
+    Buffer.from(metadataHash).copy(data, offset)
 
     return new TransactionInstruction({
       keys: [
         { pubkey: agentIdentity, isSigner: false, isWritable: true },
+        { pubkey: signer, isSigner: true, isWritable: true },
+        { pubkey: agent, isSigner: false, isWritable: false },
+        ...this.programGuardKeys(signer, withRateLimit),
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build register agents batch instruction
+   *
+   * `payer` funds every identity; each entry's agent must also sign.
+   */
+  buildRegisterAgentsBatchInstruction(
+    payer: PublicKey,
+    registrations: BatchRegistration[],
+    withRateLimit = false
+  ): TransactionInstruction {
+    const encoded = registrations.map((entry) => Buffer.from(entry.metadataUri))
+    const data = Buffer.alloc(
+      8 + 4 + encoded.reduce((total, uri) => total + 32 + 4 + uri.length + 32 + 1, 0)
+    )
+    let offset = 0
+    DISCRIMINATORS.registerAgentsBatch.copy(data, offset)
+    offset += 8
+    data.writeUInt32LE(registrations.length, offset)
+    offset += 4
+    registrations.forEach((entry, i) => {
+      entry.assetAddress.toBuffer().copy(data, offset)
+      offset += 32
+      data.writeUInt32LE(encoded[i].length, offset)
+      offset += 4
+      encoded[i].copy(data, offset)
+      offset += encoded[i].length
+      Buffer.from(entry.metadataHash).copy(data, offset)
+      offset += 32
+      data.writeUInt8(entry.transferable ? 1 : 0, offset)
+      offset += 1
+    })
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: payer, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ...this.programGuardKeys(payer, withRateLimit),
+        ...registrations.flatMap((entry) => [
+          { pubkey: entry.agent, isSigner: true, isWritable: false },
+          {
+            pubkey: getAgentIdentityPDA(entry.agent, this.programId)[0],
+            isSigner: false,
+            isWritable: true,
+          },
+          { pubkey: entry.assetAddress, isSigner: false, isWritable: false },
+        ]),
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build verify metadata instruction
+   *
+   * Read-only; simulate it and decode the MetadataCheck return data.
+   */
+  buildVerifyMetadataInstruction(agent: PublicKey, metadataHash: Uint8Array): TransactionInstruction {
+    const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
+
+    const data = Buffer.alloc(8 + 32)
+    DISCRIMINATORS.verifyMetadata.copy(data, 0)
+    Buffer.from(metadataHash).copy(data, 8)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: agentIdentity, isSigner: false, isWritable: false },
+        { pubkey: agent, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build verify identity instruction
+   *
+   * Simulate it and decode the IdentityStatus return data. With `strict`
+   * the instruction fails unless the agent is active, unexpired and staked.
+   */
+  buildVerifyIdentityInstruction(agent: PublicKey, strict = false): TransactionInstruction {
+    const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
+    const [stakingPool] = getStakingPoolPDA(this.programId)
+
+    const data = Buffer.alloc(8 + 1)
+    DISCRIMINATORS.verifyIdentity.copy(data, 0)
+    data.writeUInt8(strict ? 1 : 0, 8)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: agentIdentity, isSigner: false, isWritable: false },
+        { pubkey: agent, isSigner: false, isWritable: false },
+        // Supplies the agent's category minimum stake
+        { pubkey: stakingPool, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build claim name instruction
+   *
+   * `name` must already be normalized; the program rejects anything else.
+   */
+  buildClaimNameInstruction(agent: PublicKey, name: string, withRateLimit = false): TransactionInstruction {
+    const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
+    const [nameRecord] = getNameRecordPDA(name, this.programId)
+
+    const nameBuffer = Buffer.from(name)
+    const data = Buffer.alloc(8 + 4 + nameBuffer.length)
+    DISCRIMINATORS.claimName.copy(data, 0)
+    data.writeUInt32LE(nameBuffer.length, 8)
+    nameBuffer.copy(data, 12)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: agentIdentity, isSigner: false, isWritable: true },
+        { pubkey: nameRecord, isSigner: false, isWritable: true },
+        { pubkey: agent, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ...this.programGuardKeys(agent, withRateLimit),
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build release name instruction
+   */
+  buildReleaseNameInstruction(agent: PublicKey, name: string): TransactionInstruction {
+    const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
+    const [nameRecord] = getNameRecordPDA(name, this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: agentIdentity, isSigner: false, isWritable: true },
+        { pubkey: nameRecord, isSigner: false, isWritable: true },
         { pubkey: agent, isSigner: true, isWritable: true },
+      ],
+      programId: this.programId,
+      data: Buffer.from(DISCRIMINATORS.releaseName),
+    })
+  }
+
+  /**
+   * Build transfer name instruction
+   */
+  buildTransferNameInstruction(agent: PublicKey, name: string, newAgent: PublicKey): TransactionInstruction {
+    const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
+    const [nameRecord] = getNameRecordPDA(name, this.programId)
+    const [newAgentIdentity] = getAgentIdentityPDA(newAgent, this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: agentIdentity, isSigner: false, isWritable: true },
+        { pubkey: nameRecord, isSigner: false, isWritable: true },
+        { pubkey: newAgentIdentity, isSigner: false, isWritable: true },
+        { pubkey: agent, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data: Buffer.from(DISCRIMINATORS.transferName),
+    })
+  }
+
+  /**
+   * Build renew identity instruction
+   */
+  buildRenewIdentityInstruction(agent: PublicKey, withRateLimit = false): TransactionInstruction {
+    const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: agentIdentity, isSigner: false, isWritable: true },
+        { pubkey: agent, isSigner: true, isWritable: true },
+        ...this.programGuardKeys(agent, withRateLimit),
+      ],
+      programId: this.programId,
+      data: Buffer.from(DISCRIMINATORS.renewIdentity),
+    })
+  }
+
+  /**
+   * Build transfer identity instruction
+   *
+   * Only transferable identities with no stake can move. Pass the identity's
+   * name record when it holds a name; the name moves with it. Both `agent`
+   * and `newOwner` must sign.
+   */
+  buildTransferIdentityInstruction(
+    agent: PublicKey,
+    newOwner: PublicKey,
+    nameRecord: PublicKey | null = null,
+    withRateLimit = false
+  ): TransactionInstruction {
+    const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
+    const [newIdentity] = getAgentIdentityPDA(newOwner, this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: agentIdentity, isSigner: false, isWritable: true },
+        { pubkey: newIdentity, isSigner: false, isWritable: true },
+        // The program ID stands in for the optional name record
+        nameRecord
+          ? { pubkey: nameRecord, isSigner: false, isWritable: true }
+          : { pubkey: this.programId, isSigner: false, isWritable: false },
+        { pubkey: agent, isSigner: true, isWritable: true },
+        { pubkey: newOwner, isSigner: true, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ...this.programGuardKeys(agent, withRateLimit),
+      ],
+      programId: this.programId,
+      data: Buffer.from(DISCRIMINATORS.transferIdentity),
+    })
+  }
+
+  /**
+   * Build record heartbeat instruction
+   *
+   * `signer` may be the owner or a delegate with the activity scope. The
+   * stored health status is left unchanged when `status` is null.
+   */
+  buildRecordHeartbeatInstruction(
+    agent: PublicKey,
+    status: HealthStatus | null = null,
+    signer: PublicKey = agent,
+    withRateLimit = false
+  ): TransactionInstruction {
+    const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
+
+    const data = Buffer.alloc(8 + (status === null ? 1 : 2))
+    DISCRIMINATORS.recordHeartbeat.copy(data, 0)
+    if (status !== null) {
+      data.writeUInt8(1, 8)
+      data.writeUInt8(status, 9)
+    }
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: agentIdentity, isSigner: false, isWritable: true },
+        { pubkey: signer, isSigner: true, isWritable: false },
         { pubkey: agent, isSigner: false, isWritable: false },
+        ...this.programGuardKeys(signer, withRateLimit),
       ],
       programId: this.programId,
       data,
@@ -235,10 +697,19 @@ export class IdentityRegistryClient {
 
   /**
    * Build deactivate agent instruction
+   *
+   * `reasonHash` is the sha256 of the deactivation reason.
    */
-  buildDeactivateAgentInstruction(agent: PublicKey): TransactionInstruction {
+  buildDeactivateAgentInstruction(
+    agent: PublicKey,
+    reasonHash: Uint8Array = new Uint8Array(32)
+  ): TransactionInstruction {
     const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
 
+    const data = Buffer.alloc(8 + 32)
+    DISCRIMINATORS.deactivateAgent.copy(data, 0)
+    Buffer.from(reasonHash).copy(data, 8)
+
     return new TransactionInstruction({
       keys: [
         { pubkey: agentIdentity, isSigner: false, isWritable: true },
@@ -246,7 +717,7 @@ export class IdentityRegistryClient {
         { pubkey: agent, isSigner: false, isWritable: false },
       ],
       programId: this.programId,
-      data: DISCRIMINATORS.deactivateAgent,
+      data,
     })
   }
 
@@ -280,33 +751,146 @@ export class IdentityRegistryClient {
   }
 
   /**
-   * Build unstake collateral instruction
+   * Build request unstake instruction (starts the unlock cooldown)
    */
-  buildUnstakeCollateralInstruction(
+  buildRequestUnstakeInstruction(
     agent: PublicKey,
-    amount: bigint
+    amount: bigint,
+    withRateLimit = false
   ): TransactionInstruction {
     const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
     const [stakingPool] = getStakingPoolPDA(this.programId)
-    const [stakeAccount] = getStakeAccountPDA(agent, this.programId)
 
     const data = Buffer.alloc(8 + 8)
-    DISCRIMINATORS.unstakeCollateral.copy(data, 0)
+    DISCRIMINATORS.requestUnstake.copy(data, 0)
     data.writeBigUInt64LE(amount, 8)
 
     return new TransactionInstruction({
       keys: [
+        { pubkey: agentIdentity, isSigner: false, isWritable: true },
+        { pubkey: stakingPool, isSigner: false, isWritable: false },
+        { pubkey: agent, isSigner: true, isWritable: false },
+        { pubkey: agent, isSigner: false, isWritable: false },
+        ...this.programGuardKeys(agent, withRateLimit),
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build withdraw unstaked instruction (after the cooldown)
+   */
+  buildWithdrawUnstakedInstruction(agent: PublicKey, withRateLimit = false): TransactionInstruction {
+    const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
+    const [stakingPool] = getStakingPoolPDA(this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: agentIdentity, isSigner: false, isWritable: true },
+        { pubkey: stakingPool, isSigner: false, isWritable: true },
         { pubkey: agent, isSigner: true, isWritable: true },
+        { pubkey: agent, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ...this.programGuardKeys(agent, withRateLimit),
+      ],
+      programId: this.programId,
+      data: Buffer.from(DISCRIMINATORS.withdrawUnstaked),
+    })
+  }
+
+  /**
+   * Build stake for agent instruction
+   *
+   * `backer` pays and alone can withdraw; the agent does not sign.
+   */
+  buildStakeForAgentInstruction(
+    backer: PublicKey,
+    agent: PublicKey,
+    amount: bigint,
+    withRateLimit = false
+  ): TransactionInstruction {
+    const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
+    const [stakingPool] = getStakingPoolPDA(this.programId)
+    const [delegatedStake] = getDelegatedStakePDA(agent, backer, this.programId)
+
+    const data = Buffer.alloc(8 + 8)
+    DISCRIMINATORS.stakeForAgent.copy(data, 0)
+    data.writeBigUInt64LE(amount, 8)
+
+    return new TransactionInstruction({
+      keys: [
         { pubkey: agentIdentity, isSigner: false, isWritable: true },
         { pubkey: stakingPool, isSigner: false, isWritable: true },
-        { pubkey: stakeAccount, isSigner: false, isWritable: true },
-        { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
+        { pubkey: delegatedStake, isSigner: false, isWritable: true },
+        { pubkey: agent, isSigner: false, isWritable: false },
+        { pubkey: backer, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ...this.programGuardKeys(backer, withRateLimit),
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build request delegated unstake instruction (starts the backer's cooldown)
+   */
+  buildRequestDelegatedUnstakeInstruction(
+    backer: PublicKey,
+    agent: PublicKey,
+    amount: bigint,
+    withRateLimit = false
+  ): TransactionInstruction {
+    const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
+    const [stakingPool] = getStakingPoolPDA(this.programId)
+    const [delegatedStake] = getDelegatedStakePDA(agent, backer, this.programId)
+
+    const data = Buffer.alloc(8 + 8)
+    DISCRIMINATORS.requestDelegatedUnstake.copy(data, 0)
+    data.writeBigUInt64LE(amount, 8)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: agentIdentity, isSigner: false, isWritable: true },
+        { pubkey: stakingPool, isSigner: false, isWritable: false },
+        { pubkey: delegatedStake, isSigner: false, isWritable: true },
+        { pubkey: agent, isSigner: false, isWritable: false },
+        { pubkey: backer, isSigner: true, isWritable: false },
+        ...this.programGuardKeys(backer, withRateLimit),
       ],
       programId: this.programId,
       data,
     })
   }
 
+  /**
+   * Build withdraw delegated stake instruction (after the cooldown)
+   */
+  buildWithdrawDelegatedStakeInstruction(
+    backer: PublicKey,
+    agent: PublicKey,
+    withRateLimit = false
+  ): TransactionInstruction {
+    const [agentIdentity] = getAgentIdentityPDA(agent, this.programId)
+    const [stakingPool] = getStakingPoolPDA(this.programId)
+    const [delegatedStake] = getDelegatedStakePDA(agent, backer, this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: agentIdentity, isSigner: false, isWritable: true },
+        { pubkey: stakingPool, isSigner: false, isWritable: true },
+        { pubkey: delegatedStake, isSigner: false, isWritable: true },
+        { pubkey: agent, isSigner: false, isWritable: false },
+        { pubkey: backer, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ...this.programGuardKeys(backer, withRateLimit),
+      ],
+      programId: this.programId,
+      data: Buffer.from(DISCRIMINATORS.withdrawDelegatedStake),
+    })
+  }
+
   // ==========================================================================
   // ACCOUNT FETCHERS
   // ==========================================================================
@@ -333,6 +917,21 @@ export class IdentityRegistryClient {
     }
   }
 
+  /**
+   * Resolve a claimed name to its NameRecord (null if unclaimed)
+   */
+  async resolveName(name: string): Promise<NameRecord | null> {
+    const [pda] = getNameRecordPDA(name, this.programId)
+    try {
+      const accountInfo = await this.connection.getAccountInfo(pda)
+      if (!accountInfo?.data) return null
+      return parseNameRecord(accountInfo.data)
+    } catch (error) {
+      console.error('Failed to resolve name:', error)
+      return null
+    }
+  }
+
   /**
    * Fetch staking pool
    */
@@ -363,6 +962,21 @@ export class IdentityRegistryClient {
     }
   }
 
+  /**
+   * Fetch a backer's delegated stake in an agent
+   */
+  async getDelegatedStake(agentAddress: PublicKey, backer: PublicKey): Promise<DelegatedStake | null> {
+    const [pda] = getDelegatedStakePDA(agentAddress, backer, this.programId)
+    try {
+      const accountInfo = await this.connection.getAccountInfo(pda)
+      if (!accountInfo?.data) return null
+      return parseDelegatedStake(accountInfo.data)
+    } catch (error) {
+      console.error('Failed to fetch delegated stake:', error)
+      return null
+    }
+  }
+
   /**
    * Get all registered agents
    */
@@ -443,7 +1057,70 @@ function parseAgentIdentity(data: Buffer): AgentIdentity | null {
     const isActive = data.readUInt8(offset) === 1
     offset += 1
 
+    // staked_amount, stake_unlock_timestamp, slash_count, total_slashed
+    offset += 8 + 8 + 4 + 8
+
     const bump = data.readUInt8(offset)
+    offset += 1
+
+    const deactivatedAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    // pending_unstake_amount, first_staked_at, delegate, delegate_scope
+    offset += 8 + 8 + 32 + 1
+
+    const capabilities = data.readUInt32LE(offset)
+    offset += 4
+
+    const hasCategory = data.readUInt8(offset) === 1
+    offset += 1
+    const serviceCategory = hasCategory ? (data.readUInt8(offset) as ServiceCategory) : null
+    if (hasCategory) offset += 1
+
+    const metadataHash = new Uint8Array(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const metadataVersion = data.readUInt32LE(offset)
+    offset += 4
+
+    const nameRecord = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const expiresAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const referrer = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const referralCount = data.readUInt32LE(offset)
+    offset += 4
+
+    const deactivatedBy = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const deactivationReasonHash = new Uint8Array(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const delegatedStake = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const delegatedShares = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const pendingDelegatedShares = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const lastHeartbeatAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const healthStatus = data.readUInt8(offset) as HealthStatus
+    offset += 1
+
+    const transferable = data.readUInt8(offset) === 1
+    offset += 1
+
+    // Accounts created before the version field end here
+    const version = data.length > offset ? data.readUInt8(offset) : 0
 
     return {
       agentAddress,
@@ -454,6 +1131,53 @@ function parseAgentIdentity(data: Buffer): AgentIdentity | null {
       activityCount,
       isActive,
       bump,
+      capabilities,
+      serviceCategory,
+      metadataHash,
+      metadataVersion,
+      nameRecord,
+      expiresAt,
+      referrer,
+      referralCount,
+      deactivatedAt,
+      deactivatedBy,
+      deactivationReasonHash,
+      delegatedStake,
+      delegatedShares,
+      pendingDelegatedShares,
+      lastHeartbeatAt,
+      healthStatus,
+      transferable,
+      version,
+    }
+  } catch {
+    return null
+  }
+}
+
+function parseNameRecord(data: Buffer): NameRecord | null {
+  try {
+    let offset = 8
+
+    const nameLen = data.readUInt32LE(offset)
+    offset += 4
+
+    const name = data.subarray(offset, offset + nameLen).toString('utf-8')
+    offset += nameLen
+
+    const agent = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const claimedAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const bump = data.readUInt8(offset)
+
+    return {
+      name,
+      agent,
+      claimedAt,
+      bump,
     }
   } catch {
     return null
@@ -540,4 +1264,31 @@ function parseStakeAccount(data: Buffer): StakeAccount | null {
   }
 }
 
+function parseDelegatedStake(data: Buffer): DelegatedStake | null {
+  try {
+    let offset = 8
+
+    const agent = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const backer = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const shares = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const pendingShares = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const unlockTimestamp = data.readBigInt64LE(offset)
+    offset += 8
+
+    const bump = data.readUInt8(offset)
+
+    return { agent, backer, shares, pendingShares, unlockTimestamp, bump }
+  } catch {
+    return null
+  }
+}
+
 // Note: lamportsToSol and solToLamports are exported from './utils'
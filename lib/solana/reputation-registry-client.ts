@@ -27,6 +27,7 @@ export { REPUTATION_REGISTRY_PROGRAM_ID }
 // PDA Seeds
 const REPUTATION_SEED = Buffer.from('reputation')
 const AUTHORITY_SEED = Buffer.from('authority')
+const DECAY_CONFIG_SEED = Buffer.from('decay_config')
 
 // ============================================================================
 // TYPES
@@ -83,6 +84,12 @@ export function getReputationAuthorityPDA(
   return PublicKey.findProgramAddressSync([AUTHORITY_SEED], programId)
 }
 
+export function getDecayConfigPDA(
+  programId: PublicKey = REPUTATION_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([DECAY_CONFIG_SEED], programId)
+}
+
 // ============================================================================
 // INSTRUCTION DISCRIMINATORS (from Anchor IDL)
 // ============================================================================
@@ -249,6 +256,26 @@ export class ReputationRegistryClient {
     })
   }
 
+  /**
+   * Build get_reputation view instruction; simulate it and decode the
+   * ReputationView from its return data. The decay config PDA must be passed
+   * even before it is initialized, when the default decay parameters apply.
+   */
+  buildGetReputationInstruction(agentAddress: PublicKey): TransactionInstruction {
+    const [agentReputation] = getReputationPDA(agentAddress, this.programId)
+    const [decayConfig] = getDecayConfigPDA(this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: agentReputation, isSigner: false, isWritable: false },
+        { pubkey: agentAddress, isSigner: false, isWritable: false },
+        { pubkey: decayConfig, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data: DISCRIMINATORS.getReputation,
+    })
+  }
+
   // ==========================================================================
   // ACCOUNT FETCHERS
   // ==========================================================================
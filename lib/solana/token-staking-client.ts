@@ -17,7 +17,7 @@ import {
   TOKEN_PROGRAM_ID,
   getAssociatedTokenAddress,
 } from '@solana/spl-token'
-import { TOKEN_STAKING_PROGRAM_ID } from './programs'
+import { IDENTITY_REGISTRY_PROGRAM_ID, TOKEN_STAKING_PROGRAM_ID } from './programs'
 
 // Re-export for backward compatibility
 export { TOKEN_STAKING_PROGRAM_ID }
@@ -26,6 +26,11 @@ export { TOKEN_STAKING_PROGRAM_ID }
 const VAULT_SEED = Buffer.from('vault')
 const VAULT_TOKEN_SEED = Buffer.from('vault_token')
 const STAKE_SEED = Buffer.from('stake')
+const REWARD_TOKEN_SEED = Buffer.from('reward_token')
+const REGISTRY_CONFIG_SEED = Buffer.from('registry_config')
+const ENDORSEMENT_INDEX_SEED = Buffer.from('endorsement_index')
+const PROTOCOL_CONFIG_SEED = Buffer.from('protocol_config')
+const PROTOCOL_FEES_SEED = Buffer.from('protocol_fees')
 
 // Stake categories matching Rust enum
 export type StakeCategory =
@@ -44,6 +49,11 @@ export const StakeCategoryIndex: Record<StakeCategory, number> = {
 }
 
 // Account types
+export interface LockTier {
+  lockSeconds: bigint
+  boostBps: number
+}
+
 export interface StakingVault {
   targetAgent: PublicKey
   tokenMint: PublicKey
@@ -60,6 +70,66 @@ export interface StakingVault {
   updatedAt: bigint
   bump: number
   vaultBump: number
+  slashAuthority: PublicKey
+  penaltyDestination: PublicKey
+  totalSlashed: bigint
+  rewardTokenAccount: PublicKey
+  rewardRate: bigint
+  rewardPeriodEnd: bigint
+  rewardUpdatedAt: bigint
+  rewardPerTokenStored: bigint
+  totalRewardsFunded: bigint
+  rewardBump: number
+  verificationReasonHash: Buffer
+  verificationUpdatedAt: bigint
+  cooldownMode: boolean
+  unstakeCooldownSeconds: bigint
+  // Indexed by StakeCategoryIndex
+  categoryStaked: bigint[]
+  categoryStakers: number[]
+  maxTotalStaked: bigint
+  maxPerStaker: bigint
+  pausedAt: bigint
+  emergencyGracePeriod: bigint
+  earlyExitEnabled: boolean
+  earlyExitPenaltyBps: number
+  totalTrustWeight: bigint
+  normalizationBps: number
+  isIndexed: boolean
+  indexedTrustWeight: bigint
+  indexedStakers: number
+  // Only the first lockTierCount tiers are in use
+  lockTiers: LockTier[]
+  lockTierCount: number
+  priceFeed: PublicKey
+  usdNormalized: boolean
+  maxPriceAge: bigint
+  priceFallbackToRaw: boolean
+  tokenDecimals: number
+  lastPrice: bigint
+  lastPriceExponent: number
+  lastPricePublishedAt: bigint
+  // Layout version; 0 until migrate_account upgrades the vault
+  version: number
+}
+
+export interface AgentEndorsementIndex {
+  targetAgent: PublicKey
+  vaultCount: number
+  totalTrustWeight: bigint
+  totalStakers: number
+  updatedAt: bigint
+  bump: number
+}
+
+export interface ProtocolConfig {
+  admin: PublicKey
+  treasury: PublicKey
+  protocolFeeBps: number
+  chargeUnstakeFee: boolean
+  /** Agents slashed this many times or more cannot get new vaults or stakes */
+  maxAgentSlashCount: number
+  bump: number
 }
 
 export interface StakePosition {
@@ -76,6 +146,18 @@ export interface StakePosition {
   isActive: boolean
   isSlashed: boolean
   bump: number
+  rewardPerTokenPaid: bigint
+  rewardsAccrued: bigint
+  rewardsClaimed: bigint
+  pendingUnstakeAmount: bigint
+  unstakeAvailableAt: bigint
+  lockDuration: bigint
+  lockBoostBps: number
+  /** Wallet that staked on the staker's behalf (PublicKey.default when self-staked) */
+  funder: PublicKey
+  operator: PublicKey
+  /** Token account a delegated position unstakes to */
+  withdrawalAddress: PublicKey
 }
 
 /**
@@ -105,6 +187,75 @@ export function getVaultTokenAccountPDA(
   )
 }
 
+/**
+ * Derive the platform registry config PDA
+ */
+export function getRegistryConfigPDA(
+  programId: PublicKey = TOKEN_STAKING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([REGISTRY_CONFIG_SEED], programId)
+}
+
+/**
+ * Derive an agent's cross-vault endorsement index PDA
+ */
+export function getEndorsementIndexPDA(
+  targetAgent: PublicKey,
+  programId: PublicKey = TOKEN_STAKING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [ENDORSEMENT_INDEX_SEED, targetAgent.toBuffer()],
+    programId
+  )
+}
+
+/**
+ * Derive the protocol fee config PDA
+ */
+export function getProtocolConfigPDA(
+  programId: PublicKey = TOKEN_STAKING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([PROTOCOL_CONFIG_SEED], programId)
+}
+
+/**
+ * Derive the protocol's fee token account PDA for a mint
+ */
+export function getProtocolFeeAccountPDA(
+  tokenMint: PublicKey,
+  programId: PublicKey = TOKEN_STAKING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [PROTOCOL_FEES_SEED, tokenMint.toBuffer()],
+    programId
+  )
+}
+
+// Helper to derive identity PDA (vaults and stakes require the target agent's identity)
+// Note: Not exported to avoid conflict with identity-registry-client
+function deriveAgentIdentityPDA(
+  agentAddress: PublicKey,
+  programId: PublicKey = IDENTITY_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('agent'), agentAddress.toBuffer()],
+    programId
+  )
+}
+
+/**
+ * Derive vault reward token account PDA
+ */
+export function getRewardTokenAccountPDA(
+  vault: PublicKey,
+  programId: PublicKey = TOKEN_STAKING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [REWARD_TOKEN_SEED, vault.toBuffer()],
+    programId
+  )
+}
+
 /**
  * Derive stake position PDA
  */
@@ -119,59 +270,611 @@ export function getStakePositionPDA(
   )
 }
 
-/**
- * Token Staking Program Client
- */
-export class TokenStakingClient {
-  private connection: Connection
-  private programId: PublicKey
+/**
+ * Token Staking Program Client
+ */
+export class TokenStakingClient {
+  private connection: Connection
+  private programId: PublicKey
+
+  constructor(
+    connection: Connection,
+    programId: PublicKey = TOKEN_STAKING_PROGRAM_ID
+  ) {
+    this.connection = connection
+    this.programId = programId
+  }
+
+  /**
+   * Build initialize vault instruction
+   * A non-zero unstakeCooldownSeconds puts the vault in cooldown mode
+   * maxTotalStaked and maxPerStaker cap stakes (0 = unlimited)
+   */
+  buildInitializeVaultInstruction(
+    authority: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey,
+    minStakeAmount: bigint,
+    lockPeriodSeconds: bigint,
+    weightMultiplier: number,
+    unstakeCooldownSeconds: bigint = 0n,
+    maxTotalStaked: bigint = 0n,
+    maxPerStaker: bigint = 0n
+  ): TransactionInstruction {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+    const [vaultTokenAccount] = getVaultTokenAccountPDA(vault, this.programId)
+
+    // Anchor discriminator for initialize_vault
+    const discriminator = Buffer.from([48, 191, 163, 44, 71, 129, 63, 164])
+
+    // Serialize instruction data
+    const data = Buffer.alloc(8 + 8 + 8 + 2 + 8 + 8 + 8)
+    let offset = 0
+    discriminator.copy(data, offset)
+    offset += 8
+    data.writeBigUInt64LE(minStakeAmount, offset)
+    offset += 8
+    data.writeBigInt64LE(lockPeriodSeconds, offset)
+    offset += 8
+    data.writeUInt16LE(weightMultiplier, offset)
+    offset += 2
+    data.writeBigInt64LE(unstakeCooldownSeconds, offset)
+    offset += 8
+    data.writeBigUInt64LE(maxTotalStaked, offset)
+    offset += 8
+    data.writeBigUInt64LE(maxPerStaker, offset)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: vault, isSigner: false, isWritable: true },
+        { pubkey: vaultTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: targetAgent, isSigner: false, isWritable: false },
+        { pubkey: tokenMint, isSigner: false, isWritable: false },
+        { pubkey: deriveAgentIdentityPDA(targetAgent)[0], isSigner: false, isWritable: false },
+        { pubkey: getProtocolConfigPDA(this.programId)[0], isSigner: false, isWritable: false },
+        { pubkey: getEndorsementIndexPDA(targetAgent, this.programId)[0], isSigner: false, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build stake tokens instruction
+   * Fails once the target agent's identity is deactivated or over the slash threshold
+   * lockDuration (seconds) commits the stake beyond the vault's lock period for a lock-tier boost;
+   * priceFeed is the vault's price feed, required when the vault is USD-normalized
+   */
+  async buildStakeTokensInstruction(
+    staker: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey,
+    amount: bigint,
+    category: StakeCategory,
+    lockDuration?: bigint,
+    priceFeed?: PublicKey
+  ): Promise<TransactionInstruction> {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+    const [vaultTokenAccount] = getVaultTokenAccountPDA(vault, this.programId)
+    const [stakePosition] = getStakePositionPDA(vault, staker, this.programId)
+    const stakerTokenAccount = await getAssociatedTokenAddress(tokenMint, staker)
+
+    // Anchor discriminator for stake_tokens
+    const discriminator = Buffer.from([136, 126, 91, 63, 35, 255, 226, 251])
+
+    // Serialize instruction data
+    const data = Buffer.alloc(8 + 8 + 1 + 1 + (lockDuration === undefined ? 0 : 8))
+    let offset = 0
+    discriminator.copy(data, offset)
+    offset += 8
+    data.writeBigUInt64LE(amount, offset)
+    offset += 8
+    data.writeUInt8(StakeCategoryIndex[category], offset)
+    offset += 1
+    data.writeUInt8(lockDuration === undefined ? 0 : 1, offset)
+    offset += 1
+    if (lockDuration !== undefined) {
+      data.writeBigInt64LE(lockDuration, offset)
+    }
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: staker, isSigner: true, isWritable: true },
+        { pubkey: vault, isSigner: false, isWritable: true },
+        { pubkey: stakePosition, isSigner: false, isWritable: true },
+        { pubkey: stakerTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: vaultTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: tokenMint, isSigner: false, isWritable: false },
+        { pubkey: targetAgent, isSigner: false, isWritable: false },
+        { pubkey: getRegistryConfigPDA(this.programId)[0], isSigner: false, isWritable: false },
+        { pubkey: getEndorsementIndexPDA(targetAgent, this.programId)[0], isSigner: false, isWritable: true },
+        { pubkey: priceFeed ?? this.programId, isSigner: false, isWritable: false },
+        { pubkey: deriveAgentIdentityPDA(targetAgent)[0], isSigner: false, isWritable: false },
+        { pubkey: getProtocolConfigPDA(this.programId)[0], isSigner: false, isWritable: false },
+        { pubkey: getProtocolFeeAccountPDA(tokenMint, this.programId)[0], isSigner: false, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build stake tokens for instruction (stake on a beneficiary's behalf)
+   * withdrawalTokenAccount receives the tokens when the beneficiary unstakes; top-ups of an
+   * existing delegated position must pass the recorded one and be signed by its funder or operator
+   */
+  async buildStakeTokensForInstruction(
+    funder: PublicKey,
+    beneficiary: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey,
+    withdrawalTokenAccount: PublicKey,
+    amount: bigint,
+    category: StakeCategory,
+    lockDuration?: bigint,
+    priceFeed?: PublicKey
+  ): Promise<TransactionInstruction> {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+    const [vaultTokenAccount] = getVaultTokenAccountPDA(vault, this.programId)
+    const [stakePosition] = getStakePositionPDA(vault, beneficiary, this.programId)
+    const funderTokenAccount = await getAssociatedTokenAddress(tokenMint, funder)
+
+    // Anchor discriminator for stake_tokens_for
+    const discriminator = Buffer.from([116, 105, 174, 46, 190, 73, 190, 17])
+
+    const data = Buffer.alloc(8 + 8 + 1 + 1 + (lockDuration === undefined ? 0 : 8))
+    let offset = 0
+    discriminator.copy(data, offset)
+    offset += 8
+    data.writeBigUInt64LE(amount, offset)
+    offset += 8
+    data.writeUInt8(StakeCategoryIndex[category], offset)
+    offset += 1
+    data.writeUInt8(lockDuration === undefined ? 0 : 1, offset)
+    offset += 1
+    if (lockDuration !== undefined) {
+      data.writeBigInt64LE(lockDuration, offset)
+    }
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: vault, isSigner: false, isWritable: true },
+        { pubkey: vaultTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: stakePosition, isSigner: false, isWritable: true },
+        { pubkey: funderTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: withdrawalTokenAccount, isSigner: false, isWritable: false },
+        { pubkey: beneficiary, isSigner: false, isWritable: false },
+        { pubkey: funder, isSigner: true, isWritable: true },
+        { pubkey: getRegistryConfigPDA(this.programId)[0], isSigner: false, isWritable: false },
+        { pubkey: getEndorsementIndexPDA(targetAgent, this.programId)[0], isSigner: false, isWritable: true },
+        { pubkey: priceFeed ?? this.programId, isSigner: false, isWritable: false },
+        { pubkey: deriveAgentIdentityPDA(targetAgent)[0], isSigner: false, isWritable: false },
+        { pubkey: getProtocolConfigPDA(this.programId)[0], isSigner: false, isWritable: false },
+        { pubkey: getProtocolFeeAccountPDA(tokenMint, this.programId)[0], isSigner: false, isWritable: true },
+        { pubkey: tokenMint, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build set stake operator instruction (funder of a delegated position only)
+   * Passing PublicKey.default removes the operator
+   */
+  buildSetStakeOperatorInstruction(
+    funder: PublicKey,
+    beneficiary: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey,
+    operator: PublicKey
+  ): TransactionInstruction {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+    const [stakePosition] = getStakePositionPDA(vault, beneficiary, this.programId)
+
+    // Anchor discriminator for set_stake_operator
+    const discriminator = Buffer.from([133, 214, 247, 129, 181, 26, 174, 1])
+
+    const data = Buffer.alloc(8 + 32)
+    discriminator.copy(data, 0)
+    operator.toBuffer().copy(data, 8)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: stakePosition, isSigner: false, isWritable: true },
+        { pubkey: funder, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build unstake tokens instruction
+   * With unstake fees enabled, the protocol fee is withheld from the tokens paid out
+   */
+  async buildUnstakeTokensInstruction(
+    staker: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey,
+    amount: bigint
+  ): Promise<TransactionInstruction> {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+    const [vaultTokenAccount] = getVaultTokenAccountPDA(vault, this.programId)
+    const [stakePosition] = getStakePositionPDA(vault, staker, this.programId)
+    const stakerTokenAccount = await getAssociatedTokenAddress(tokenMint, staker)
+
+    // Anchor discriminator for unstake_tokens
+    const discriminator = Buffer.from([58, 119, 215, 143, 203, 223, 32, 86])
+
+    // Serialize instruction data
+    const data = Buffer.alloc(8 + 8)
+    let offset = 0
+    discriminator.copy(data, offset)
+    offset += 8
+    data.writeBigUInt64LE(amount, offset)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: staker, isSigner: true, isWritable: true },
+        { pubkey: vault, isSigner: false, isWritable: true },
+        { pubkey: stakePosition, isSigner: false, isWritable: true },
+        { pubkey: stakerTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: vaultTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: tokenMint, isSigner: false, isWritable: false },
+        { pubkey: targetAgent, isSigner: false, isWritable: false },
+        { pubkey: getEndorsementIndexPDA(targetAgent, this.programId)[0], isSigner: false, isWritable: true },
+        { pubkey: getProtocolConfigPDA(this.programId)[0], isSigner: false, isWritable: false },
+        { pubkey: getProtocolFeeAccountPDA(tokenMint, this.programId)[0], isSigner: false, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build request unstake instruction (cooldown-mode vaults)
+   */
+  buildRequestUnstakeInstruction(
+    staker: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey,
+    amount: bigint
+  ): TransactionInstruction {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+    const [stakePosition] = getStakePositionPDA(vault, staker, this.programId)
+
+    // Anchor discriminator for request_unstake
+    const discriminator = Buffer.from([44, 154, 110, 253, 160, 202, 54, 34])
+
+    const data = Buffer.alloc(8 + 8)
+    discriminator.copy(data, 0)
+    data.writeBigUInt64LE(amount, 8)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: vault, isSigner: false, isWritable: true },
+        { pubkey: stakePosition, isSigner: false, isWritable: true },
+        { pubkey: getEndorsementIndexPDA(targetAgent, this.programId)[0], isSigner: false, isWritable: true },
+        { pubkey: staker, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build withdraw unstaked instruction (cooldown-mode vaults)
+   */
+  async buildWithdrawUnstakedInstruction(
+    staker: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey
+  ): Promise<TransactionInstruction> {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+    const [vaultTokenAccount] = getVaultTokenAccountPDA(vault, this.programId)
+    const [stakePosition] = getStakePositionPDA(vault, staker, this.programId)
+    const stakerTokenAccount = await getAssociatedTokenAddress(tokenMint, staker)
+
+    // Anchor discriminator for withdraw_unstaked
+    const discriminator = Buffer.from([19, 202, 68, 255, 216, 40, 205, 61])
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: vault, isSigner: false, isWritable: true },
+        { pubkey: vaultTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: stakePosition, isSigner: false, isWritable: true },
+        { pubkey: stakerTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: getEndorsementIndexPDA(targetAgent, this.programId)[0], isSigner: false, isWritable: true },
+        { pubkey: staker, isSigner: true, isWritable: true },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data: discriminator,
+    })
+  }
+
+  /**
+   * Build verify or unverify vault instruction (platform verifier only)
+   */
+  buildSetVaultVerificationInstruction(
+    verifier: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey,
+    verified: boolean,
+    reasonHash: Buffer
+  ): TransactionInstruction {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+    const [registryConfig] = getRegistryConfigPDA(this.programId)
+
+    // Anchor discriminators for verify_vault / unverify_vault
+    const discriminator = verified
+      ? Buffer.from([149, 172, 202, 242, 33, 114, 182, 196])
+      : Buffer.from([166, 163, 39, 7, 63, 182, 33, 97])
+
+    const data = Buffer.alloc(8 + 32)
+    discriminator.copy(data, 0)
+    reasonHash.copy(data, 8)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: registryConfig, isSigner: false, isWritable: false },
+        { pubkey: vault, isSigner: false, isWritable: true },
+        { pubkey: verifier, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build set vault normalization instruction (platform verifier only)
+   * normalizationBps scales the vault's contribution to the agent's endorsement index (10000 = 1x)
+   */
+  buildSetVaultNormalizationInstruction(
+    verifier: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey,
+    normalizationBps: number
+  ): TransactionInstruction {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+    const [registryConfig] = getRegistryConfigPDA(this.programId)
+
+    // Anchor discriminator for set_vault_normalization
+    const discriminator = Buffer.from([231, 235, 102, 127, 104, 127, 229, 43])
+
+    const data = Buffer.alloc(8 + 2)
+    discriminator.copy(data, 0)
+    data.writeUInt16LE(normalizationBps, 8)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: registryConfig, isSigner: false, isWritable: false },
+        { pubkey: vault, isSigner: false, isWritable: true },
+        { pubkey: getEndorsementIndexPDA(targetAgent, this.programId)[0], isSigner: false, isWritable: true },
+        { pubkey: verifier, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build fund rewards instruction (vault authority only)
+   * Streams amount reward tokens (the vault's mint) to stakers over periodSeconds
+   */
+  async buildFundRewardsInstruction(
+    authority: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey,
+    amount: bigint,
+    periodSeconds: bigint
+  ): Promise<TransactionInstruction> {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+    const [rewardTokenAccount] = getRewardTokenAccountPDA(vault, this.programId)
+    const funderTokenAccount = await getAssociatedTokenAddress(tokenMint, authority)
+
+    // Anchor discriminator for fund_rewards
+    const discriminator = Buffer.from([114, 64, 163, 112, 175, 167, 19, 121])
+
+    const data = Buffer.alloc(8 + 8 + 8)
+    discriminator.copy(data, 0)
+    data.writeBigUInt64LE(amount, 8)
+    data.writeBigInt64LE(periodSeconds, 16)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: vault, isSigner: false, isWritable: true },
+        { pubkey: rewardTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: tokenMint, isSigner: false, isWritable: false },
+        { pubkey: funderTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build claim rewards instruction
+   */
+  async buildClaimRewardsInstruction(
+    staker: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey
+  ): Promise<TransactionInstruction> {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+    const [rewardTokenAccount] = getRewardTokenAccountPDA(vault, this.programId)
+    const [stakePosition] = getStakePositionPDA(vault, staker, this.programId)
+    const stakerTokenAccount = await getAssociatedTokenAddress(tokenMint, staker)
+
+    // Anchor discriminator for claim_rewards
+    const discriminator = Buffer.from([4, 144, 132, 71, 116, 23, 151, 80])
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: vault, isSigner: false, isWritable: true },
+        { pubkey: rewardTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: stakePosition, isSigner: false, isWritable: true },
+        { pubkey: stakerTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: staker, isSigner: true, isWritable: false },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data: discriminator,
+    })
+  }
+
+  /**
+   * Build emergency withdraw instruction
+   * Pass the vault's penalty destination when it charges an early-exit penalty
+   */
+  async buildEmergencyWithdrawInstruction(
+    staker: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey,
+    penaltyDestination: PublicKey | null
+  ): Promise<TransactionInstruction> {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+    const [vaultTokenAccount] = getVaultTokenAccountPDA(vault, this.programId)
+    const [stakePosition] = getStakePositionPDA(vault, staker, this.programId)
+    const stakerTokenAccount = await getAssociatedTokenAddress(tokenMint, staker)
+
+    // Anchor discriminator for emergency_withdraw
+    const discriminator = Buffer.from([239, 45, 203, 64, 150, 73, 218, 92])
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: vault, isSigner: false, isWritable: true },
+        { pubkey: vaultTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: stakePosition, isSigner: false, isWritable: true },
+        { pubkey: stakerTokenAccount, isSigner: false, isWritable: true },
+        // Anchor treats the program ID as "no account" for optional accounts
+        penaltyDestination
+          ? { pubkey: penaltyDestination, isSigner: false, isWritable: true }
+          : { pubkey: this.programId, isSigner: false, isWritable: false },
+        { pubkey: getEndorsementIndexPDA(targetAgent, this.programId)[0], isSigner: false, isWritable: true },
+        { pubkey: staker, isSigner: true, isWritable: true },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data: discriminator,
+    })
+  }
+
+  /**
+   * Build configure emergency exit instruction (vault authority only)
+   * A gracePeriodSeconds of 0 uses the program default (30 days)
+   */
+  buildConfigureEmergencyExitInstruction(
+    authority: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey,
+    gracePeriodSeconds: bigint,
+    earlyExitEnabled: boolean,
+    earlyExitPenaltyBps: number,
+    penaltyDestination: PublicKey | null
+  ): TransactionInstruction {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+
+    // Anchor discriminator for configure_emergency_exit
+    const discriminator = Buffer.from([79, 134, 57, 180, 110, 42, 107, 130])
+
+    const data = Buffer.alloc(8 + 8 + 1 + 2)
+    discriminator.copy(data, 0)
+    data.writeBigInt64LE(gracePeriodSeconds, 8)
+    data.writeUInt8(earlyExitEnabled ? 1 : 0, 16)
+    data.writeUInt16LE(earlyExitPenaltyBps, 17)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: vault, isSigner: false, isWritable: true },
+        penaltyDestination
+          ? { pubkey: penaltyDestination, isSigner: false, isWritable: false }
+          : { pubkey: this.programId, isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build configure price feed instruction (vault authority only)
+   * priceFeed is a Pyth PriceUpdateV2 account; null keeps the current feed.
+   * maxPriceAge 0 uses the program default (60 seconds)
+   */
+  buildConfigurePriceFeedInstruction(
+    authority: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey,
+    priceFeed: PublicKey | null,
+    usdNormalized: boolean,
+    maxPriceAge: bigint,
+    priceFallbackToRaw: boolean
+  ): TransactionInstruction {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+
+    // Anchor discriminator for configure_price_feed
+    const discriminator = Buffer.from([60, 4, 253, 253, 119, 57, 106, 98])
 
-  constructor(
-    connection: Connection,
-    programId: PublicKey = TOKEN_STAKING_PROGRAM_ID
-  ) {
-    this.connection = connection
-    this.programId = programId
+    const data = Buffer.alloc(8 + 1 + 8 + 1)
+    discriminator.copy(data, 0)
+    data.writeUInt8(usdNormalized ? 1 : 0, 8)
+    data.writeBigInt64LE(maxPriceAge, 9)
+    data.writeUInt8(priceFallbackToRaw ? 1 : 0, 17)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: vault, isSigner: false, isWritable: true },
+        { pubkey: tokenMint, isSigner: false, isWritable: false },
+        { pubkey: priceFeed ?? this.programId, isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
   }
 
   /**
-   * Build initialize vault instruction
+   * Build configure slashing instruction (vault authority only)
+   * Passing PublicKey.default as the slash authority disables slashing
    */
-  buildInitializeVaultInstruction(
+  buildConfigureSlashingInstruction(
     authority: PublicKey,
     targetAgent: PublicKey,
     tokenMint: PublicKey,
-    minStakeAmount: bigint,
-    lockPeriodSeconds: bigint,
-    weightMultiplier: number
+    slashAuthority: PublicKey,
+    penaltyDestination: PublicKey
   ): TransactionInstruction {
     const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
-    const [vaultTokenAccount] = getVaultTokenAccountPDA(vault, this.programId)
 
-    // Anchor discriminator for initialize_vault
-    const discriminator = Buffer.from([48, 191, 163, 44, 71, 129, 63, 164])
+    // Anchor discriminator for configure_slashing
+    const discriminator = Buffer.from([205, 7, 0, 148, 116, 42, 124, 145])
 
-    // Serialize instruction data
-    const data = Buffer.alloc(8 + 8 + 8 + 2)
-    let offset = 0
-    discriminator.copy(data, offset)
-    offset += 8
-    data.writeBigUInt64LE(minStakeAmount, offset)
-    offset += 8
-    data.writeBigInt64LE(lockPeriodSeconds, offset)
-    offset += 8
-    data.writeUInt16LE(weightMultiplier, offset)
+    const data = Buffer.alloc(8 + 32)
+    discriminator.copy(data, 0)
+    slashAuthority.toBuffer().copy(data, 8)
 
     return new TransactionInstruction({
       keys: [
-        { pubkey: authority, isSigner: true, isWritable: true },
         { pubkey: vault, isSigner: false, isWritable: true },
-        { pubkey: vaultTokenAccount, isSigner: false, isWritable: true },
-        { pubkey: targetAgent, isSigner: false, isWritable: false },
-        { pubkey: tokenMint, isSigner: false, isWritable: false },
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-        { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+        { pubkey: penaltyDestination, isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: false },
       ],
       programId: this.programId,
       data,
@@ -179,44 +882,36 @@ export class TokenStakingClient {
   }
 
   /**
-   * Build stake tokens instruction
+   * Build slash stake position instruction (vault slash authority only)
    */
-  async buildStakeTokensInstruction(
-    staker: PublicKey,
+  buildSlashStakePositionInstruction(
+    slashAuthority: PublicKey,
     targetAgent: PublicKey,
     tokenMint: PublicKey,
-    amount: bigint,
-    category: StakeCategory
-  ): Promise<TransactionInstruction> {
+    staker: PublicKey,
+    penaltyDestination: PublicKey,
+    severityBps: number
+  ): TransactionInstruction {
     const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
     const [vaultTokenAccount] = getVaultTokenAccountPDA(vault, this.programId)
     const [stakePosition] = getStakePositionPDA(vault, staker, this.programId)
-    const stakerTokenAccount = await getAssociatedTokenAddress(tokenMint, staker)
 
-    // Anchor discriminator for stake_tokens
-    const discriminator = Buffer.from([136, 126, 91, 63, 35, 255, 226, 251])
+    // Anchor discriminator for slash_stake_position
+    const discriminator = Buffer.from([173, 113, 5, 160, 108, 56, 14, 18])
 
-    // Serialize instruction data
-    const data = Buffer.alloc(8 + 8 + 1)
-    let offset = 0
-    discriminator.copy(data, offset)
-    offset += 8
-    data.writeBigUInt64LE(amount, offset)
-    offset += 8
-    data.writeUInt8(StakeCategoryIndex[category], offset)
+    const data = Buffer.alloc(8 + 2)
+    discriminator.copy(data, 0)
+    data.writeUInt16LE(severityBps, 8)
 
     return new TransactionInstruction({
       keys: [
-        { pubkey: staker, isSigner: true, isWritable: true },
         { pubkey: vault, isSigner: false, isWritable: true },
-        { pubkey: stakePosition, isSigner: false, isWritable: true },
-        { pubkey: stakerTokenAccount, isSigner: false, isWritable: true },
         { pubkey: vaultTokenAccount, isSigner: false, isWritable: true },
-        { pubkey: tokenMint, isSigner: false, isWritable: false },
-        { pubkey: targetAgent, isSigner: false, isWritable: false },
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: stakePosition, isSigner: false, isWritable: true },
+        { pubkey: penaltyDestination, isSigner: false, isWritable: true },
+        { pubkey: getEndorsementIndexPDA(targetAgent, this.programId)[0], isSigner: false, isWritable: true },
+        { pubkey: slashAuthority, isSigner: true, isWritable: false },
         { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-        { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
       ],
       programId: this.programId,
       data,
@@ -224,43 +919,96 @@ export class TokenStakingClient {
   }
 
   /**
-   * Build unstake tokens instruction
+   * Build close stake position instruction (inactive, empty positions only)
    */
-  async buildUnstakeTokensInstruction(
+  buildCloseStakePositionInstruction(
     staker: PublicKey,
     targetAgent: PublicKey,
-    tokenMint: PublicKey,
-    amount: bigint
-  ): Promise<TransactionInstruction> {
+    tokenMint: PublicKey
+  ): TransactionInstruction {
     const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
-    const [vaultTokenAccount] = getVaultTokenAccountPDA(vault, this.programId)
     const [stakePosition] = getStakePositionPDA(vault, staker, this.programId)
-    const stakerTokenAccount = await getAssociatedTokenAddress(tokenMint, staker)
-
-    // Anchor discriminator for unstake_tokens
-    const discriminator = Buffer.from([58, 119, 215, 143, 203, 223, 32, 86])
 
-    // Serialize instruction data
-    const data = Buffer.alloc(8 + 8)
-    let offset = 0
-    discriminator.copy(data, offset)
-    offset += 8
-    data.writeBigUInt64LE(amount, offset)
+    // Anchor discriminator for close_stake_position
+    const discriminator = Buffer.from([8, 63, 74, 143, 105, 42, 28, 64])
 
     return new TransactionInstruction({
       keys: [
+        { pubkey: stakePosition, isSigner: false, isWritable: true },
+        { pubkey: vault, isSigner: false, isWritable: false },
         { pubkey: staker, isSigner: true, isWritable: true },
+      ],
+      programId: this.programId,
+      data: discriminator,
+    })
+  }
+
+  /**
+   * Build close vault instruction (vault authority only)
+   * Set hasRewardAccount once the vault has been funded with rewards, and pass
+   * authorityTokenAccount to receive any rewards left in it
+   */
+  buildCloseVaultInstruction(
+    authority: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey,
+    hasRewardAccount: boolean,
+    authorityTokenAccount?: PublicKey
+  ): TransactionInstruction {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+    const [vaultTokenAccount] = getVaultTokenAccountPDA(vault, this.programId)
+    const [rewardTokenAccount] = getRewardTokenAccountPDA(vault, this.programId)
+
+    // Anchor discriminator for close_vault
+    const discriminator = Buffer.from([141, 103, 17, 126, 72, 75, 29, 29])
+
+    return new TransactionInstruction({
+      keys: [
         { pubkey: vault, isSigner: false, isWritable: true },
-        { pubkey: stakePosition, isSigner: false, isWritable: true },
-        { pubkey: stakerTokenAccount, isSigner: false, isWritable: true },
         { pubkey: vaultTokenAccount, isSigner: false, isWritable: true },
-        { pubkey: tokenMint, isSigner: false, isWritable: false },
-        { pubkey: targetAgent, isSigner: false, isWritable: false },
+        // Anchor treats the program ID as "no account" for optional accounts
+        hasRewardAccount
+          ? { pubkey: rewardTokenAccount, isSigner: false, isWritable: true }
+          : { pubkey: this.programId, isSigner: false, isWritable: false },
+        authorityTokenAccount
+          ? { pubkey: authorityTokenAccount, isSigner: false, isWritable: true }
+          : { pubkey: this.programId, isSigner: false, isWritable: false },
+        { pubkey: getEndorsementIndexPDA(targetAgent, this.programId)[0], isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: true },
         { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-        { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
       ],
       programId: this.programId,
-      data,
+      data: discriminator,
+    })
+  }
+
+  /**
+   * Build migrate account instruction (permissionless)
+   * Upgrades the vault to the current layout version. Vaults created before
+   * per-category totals need every active stake position in activePositions;
+   * later vaults take none.
+   */
+  buildMigrateAccountInstruction(
+    payer: PublicKey,
+    targetAgent: PublicKey,
+    tokenMint: PublicKey,
+    activePositions: PublicKey[] = []
+  ): TransactionInstruction {
+    const [vault] = getVaultPDA(targetAgent, tokenMint, this.programId)
+    const sorted = [...activePositions].sort((a, b) => a.toBuffer().compare(b.toBuffer()))
+
+    // Anchor discriminator for migrate_account
+    const discriminator = Buffer.from([177, 228, 60, 125, 13, 116, 44, 84])
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: vault, isSigner: false, isWritable: true },
+        { pubkey: payer, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ...sorted.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })),
+      ],
+      programId: this.programId,
+      data: discriminator,
     })
   }
 
@@ -300,6 +1048,44 @@ export class TokenStakingClient {
     }
   }
 
+  /**
+   * Fetch an agent's cross-vault endorsement index
+   */
+  async getAgentEndorsement(targetAgent: PublicKey): Promise<AgentEndorsementIndex | null> {
+    try {
+      const [indexAddress] = getEndorsementIndexPDA(targetAgent, this.programId)
+      const accountInfo = await this.connection.getAccountInfo(indexAddress)
+
+      if (!accountInfo || !accountInfo.data) {
+        return null
+      }
+
+      return parseAgentEndorsementIndex(accountInfo.data)
+    } catch (error) {
+      console.error('Failed to fetch agent endorsement index:', error)
+      return null
+    }
+  }
+
+  /**
+   * Fetch the protocol fee config (null until it is initialized, meaning no fee)
+   */
+  async getProtocolConfig(): Promise<ProtocolConfig | null> {
+    try {
+      const [configAddress] = getProtocolConfigPDA(this.programId)
+      const accountInfo = await this.connection.getAccountInfo(configAddress)
+
+      if (!accountInfo || !accountInfo.data) {
+        return null
+      }
+
+      return parseProtocolConfig(accountInfo.data)
+    } catch (error) {
+      console.error('Failed to fetch protocol config:', error)
+      return null
+    }
+  }
+
   /**
    * Get all stake positions for a vault
    */
@@ -405,9 +1191,20 @@ export class TokenStakingClient {
   }
 }
 
+// Lock tier slots on a vault (StakingVault::MAX_LOCK_TIERS)
+const MAX_LOCK_TIERS = 4
+
 // Account sizes (8-byte discriminator + fields)
-const STAKING_VAULT_SIZE = 8 + 32 * 4 + 8 * 2 + 2 + 8 + 4 + 32 + 1 + 1 + 8 * 2 + 1 + 1
-const STAKE_POSITION_SIZE = 8 + 32 * 4 + 8 + 1 + 8 * 4 + 1 + 1 + 1
+const STAKING_VAULT_SIZE =
+  8 + 32 * 4 + 8 * 2 + 2 + 8 + 4 + 32 + 1 + 1 + 8 * 2 + 1 + 1 + 32 * 2 + 8 + 32 + 16 + 8 * 2 + 16 + 8 + 1 +
+  32 + 8 + 1 + 8 + 8 * 5 + 4 * 5 + 8 * 2 + 8 * 2 + 1 + 2 + 8 + 2 + 1 + 8 + 4 +
+  (8 + 2) * MAX_LOCK_TIERS + 1 + 32 + 1 + 8 + 1 + 1 + 8 + 4 + 8 + 1
+const STAKE_POSITION_SIZE = 8 + 32 * 4 + 8 + 1 + 8 * 4 + 1 + 1 + 1 + 16 + 8 + 8 + 8 + 8 + 8 + 2 + 32 * 3
+
+// Little-endian u128 as stored by Anchor
+function readU128LE(data: Buffer, offset: number): bigint {
+  return data.readBigUInt64LE(offset) + (data.readBigUInt64LE(offset + 8) << 64n)
+}
 
 // Helper functions for parsing account data
 function parseStakingVault(data: Buffer): StakingVault | null {
@@ -444,6 +1241,92 @@ function parseStakingVault(data: Buffer): StakingVault | null {
     const bump = data.readUInt8(offset)
     offset += 1
     const vaultBump = data.readUInt8(offset)
+    offset += 1
+    const slashAuthority = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+    const penaltyDestination = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+    const totalSlashed = data.readBigUInt64LE(offset)
+    offset += 8
+    const rewardTokenAccount = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+    const rewardRate = readU128LE(data, offset)
+    offset += 16
+    const rewardPeriodEnd = data.readBigInt64LE(offset)
+    offset += 8
+    const rewardUpdatedAt = data.readBigInt64LE(offset)
+    offset += 8
+    const rewardPerTokenStored = readU128LE(data, offset)
+    offset += 16
+    const totalRewardsFunded = data.readBigUInt64LE(offset)
+    offset += 8
+    const rewardBump = data.readUInt8(offset)
+    offset += 1
+    const verificationReasonHash = Buffer.from(data.subarray(offset, offset + 32))
+    offset += 32
+    const verificationUpdatedAt = data.readBigInt64LE(offset)
+    offset += 8
+    const cooldownMode = data.readUInt8(offset) === 1
+    offset += 1
+    const unstakeCooldownSeconds = data.readBigInt64LE(offset)
+    offset += 8
+    const categoryStaked: bigint[] = []
+    for (let i = 0; i < 5; i++) {
+      categoryStaked.push(data.readBigUInt64LE(offset))
+      offset += 8
+    }
+    const categoryStakers: number[] = []
+    for (let i = 0; i < 5; i++) {
+      categoryStakers.push(data.readUInt32LE(offset))
+      offset += 4
+    }
+    const maxTotalStaked = data.readBigUInt64LE(offset)
+    offset += 8
+    const maxPerStaker = data.readBigUInt64LE(offset)
+    offset += 8
+    const pausedAt = data.readBigInt64LE(offset)
+    offset += 8
+    const emergencyGracePeriod = data.readBigInt64LE(offset)
+    offset += 8
+    const earlyExitEnabled = data.readUInt8(offset) === 1
+    offset += 1
+    const earlyExitPenaltyBps = data.readUInt16LE(offset)
+    offset += 2
+    const totalTrustWeight = data.readBigUInt64LE(offset)
+    offset += 8
+    const normalizationBps = data.readUInt16LE(offset)
+    offset += 2
+    const isIndexed = data.readUInt8(offset) === 1
+    offset += 1
+    const indexedTrustWeight = data.readBigUInt64LE(offset)
+    offset += 8
+    const indexedStakers = data.readUInt32LE(offset)
+    offset += 4
+    const lockTiers: LockTier[] = []
+    for (let i = 0; i < MAX_LOCK_TIERS; i++) {
+      lockTiers.push({ lockSeconds: data.readBigInt64LE(offset), boostBps: data.readUInt16LE(offset + 8) })
+      offset += 10
+    }
+    const lockTierCount = data.readUInt8(offset)
+    offset += 1
+    const priceFeed = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+    const usdNormalized = data.readUInt8(offset) === 1
+    offset += 1
+    const maxPriceAge = data.readBigInt64LE(offset)
+    offset += 8
+    const priceFallbackToRaw = data.readUInt8(offset) === 1
+    offset += 1
+    const tokenDecimals = data.readUInt8(offset)
+    offset += 1
+    const lastPrice = data.readBigInt64LE(offset)
+    offset += 8
+    const lastPriceExponent = data.readInt32LE(offset)
+    offset += 4
+    const lastPricePublishedAt = data.readBigInt64LE(offset)
+    offset += 8
+    // Vaults created before the version field end here
+    const version = data.length > offset ? data.readUInt8(offset) : 0
 
     return {
       targetAgent,
@@ -461,12 +1344,96 @@ function parseStakingVault(data: Buffer): StakingVault | null {
       updatedAt,
       bump,
       vaultBump,
+      slashAuthority,
+      penaltyDestination,
+      totalSlashed,
+      rewardTokenAccount,
+      rewardRate,
+      rewardPeriodEnd,
+      rewardUpdatedAt,
+      rewardPerTokenStored,
+      totalRewardsFunded,
+      rewardBump,
+      verificationReasonHash,
+      verificationUpdatedAt,
+      cooldownMode,
+      unstakeCooldownSeconds,
+      categoryStaked,
+      categoryStakers,
+      maxTotalStaked,
+      maxPerStaker,
+      pausedAt,
+      emergencyGracePeriod,
+      earlyExitEnabled,
+      earlyExitPenaltyBps,
+      totalTrustWeight,
+      normalizationBps,
+      isIndexed,
+      indexedTrustWeight,
+      indexedStakers,
+      lockTiers,
+      lockTierCount,
+      priceFeed,
+      usdNormalized,
+      maxPriceAge,
+      priceFallbackToRaw,
+      tokenDecimals,
+      lastPrice,
+      lastPriceExponent,
+      lastPricePublishedAt,
+      version,
     }
   } catch {
     return null
   }
 }
 
+function parseAgentEndorsementIndex(data: Buffer): AgentEndorsementIndex | null {
+  try {
+    // Skip 8-byte discriminator
+    let offset = 8
+
+    const targetAgent = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+    const vaultCount = data.readUInt32LE(offset)
+    offset += 4
+    const totalTrustWeight = data.readBigUInt64LE(offset)
+    offset += 8
+    const totalStakers = data.readUInt32LE(offset)
+    offset += 4
+    const updatedAt = data.readBigInt64LE(offset)
+    offset += 8
+    const bump = data.readUInt8(offset)
+
+    return { targetAgent, vaultCount, totalTrustWeight, totalStakers, updatedAt, bump }
+  } catch {
+    return null
+  }
+}
+
+function parseProtocolConfig(data: Buffer): ProtocolConfig | null {
+  try {
+    // Skip 8-byte discriminator
+    let offset = 8
+
+    const admin = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+    const treasury = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+    const protocolFeeBps = data.readUInt16LE(offset)
+    offset += 2
+    const chargeUnstakeFee = data.readUInt8(offset) === 1
+    offset += 1
+    const maxAgentSlashCount = data.readUInt32LE(offset)
+    offset += 4
+    const bump = data.readUInt8(offset)
+
+    return { admin, treasury, protocolFeeBps, chargeUnstakeFee, maxAgentSlashCount, bump }
+  } catch {
+    return null
+  }
+}
+
 function parseStakePosition(data: Buffer): StakePosition | null {
   try {
     const categories: StakeCategory[] = [
@@ -505,6 +1472,26 @@ function parseStakePosition(data: Buffer): StakePosition | null {
     const isSlashed = data.readUInt8(offset) === 1
     offset += 1
     const bump = data.readUInt8(offset)
+    offset += 1
+    const rewardPerTokenPaid = readU128LE(data, offset)
+    offset += 16
+    const rewardsAccrued = data.readBigUInt64LE(offset)
+    offset += 8
+    const rewardsClaimed = data.readBigUInt64LE(offset)
+    offset += 8
+    const pendingUnstakeAmount = data.readBigUInt64LE(offset)
+    offset += 8
+    const unstakeAvailableAt = data.readBigInt64LE(offset)
+    offset += 8
+    const lockDuration = data.readBigInt64LE(offset)
+    offset += 8
+    const lockBoostBps = data.readUInt16LE(offset)
+    offset += 2
+    const funder = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+    const operator = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+    const withdrawalAddress = new PublicKey(data.subarray(offset, offset + 32))
 
     return {
       vault,
@@ -520,22 +1507,72 @@ function parseStakePosition(data: Buffer): StakePosition | null {
       isActive,
       isSlashed,
       bump,
+      rewardPerTokenPaid,
+      rewardsAccrued,
+      rewardsClaimed,
+      pendingUnstakeAmount,
+      unstakeAvailableAt,
+      lockDuration,
+      lockBoostBps,
+      funder,
+      operator,
+      withdrawalAddress,
     }
   } catch {
     return null
   }
 }
 
+// Fractional bits of the fixed-point log2 behind trust weights
+const LOG2_FRACTION_BITS = 8n
+
+/**
+ * log2(x) rounded down to LOG2_FRACTION_BITS fractional bits (x >= 1)
+ * Matches StakingVault::log2_fixed: highest set bit, then one mantissa squaring per fractional bit
+ */
+function log2Fixed(x: bigint): bigint {
+  const integer = BigInt(x.toString(2).length - 1)
+  let mantissa = integer >= 63n ? x >> (integer - 63n) : x << (63n - integer)
+  let log2 = integer << LOG2_FRACTION_BITS
+  for (let bit = LOG2_FRACTION_BITS - 1n; bit >= 0n; bit--) {
+    mantissa = (mantissa * mantissa) >> 63n
+    if (mantissa >= 1n << 64n) {
+      log2 |= 1n << bit
+      mantissa >>= 1n
+    }
+  }
+  return log2
+}
+
 /**
  * Calculate trust weight for a stake amount
- * Matches the Rust implementation: log2(amount + 1) * multiplier / 100
+ * Matches the Rust implementation: floor(log2(amount + 1) * 100) * multiplier / 100,
+ * with log2 in integer fixed point
  */
 export function calculateTrustWeight(
   amount: bigint,
   weightMultiplier: number
 ): bigint {
-  const baseWeight = Math.log2(Number(amount) + 1) * 100
-  return BigInt(Math.floor((baseWeight * weightMultiplier) / 100))
+  const baseWeight = (log2Fixed(amount + 1n) * 100n) >> LOG2_FRACTION_BITS
+  return (baseWeight * BigInt(weightMultiplier)) / 100n
+}
+
+/**
+ * Lock-tier boost (bps, 10000 = 1x) a lock of lockDuration seconds earns in a vault
+ * Matches StakingVault::lock_boost_bps: the highest tier reached, 1x below the first
+ */
+export function lockBoostBps(vault: StakingVault, lockDuration: bigint): number {
+  const reached = vault.lockTiers
+    .slice(0, vault.lockTierCount)
+    .filter((tier) => lockDuration >= tier.lockSeconds)
+  return reached.length > 0 ? reached[reached.length - 1].boostBps : 10000
+}
+
+/**
+ * Protocol fee on amount, rounded down like ProtocolConfig::fee_on (0 without a config)
+ */
+export function protocolFee(config: ProtocolConfig | null, amount: bigint): bigint {
+  return config ? (amount * BigInt(config.protocolFeeBps)) / 10000n : 0n
 }
 
 // Legacy exports for backward compatibility
@@ -25,16 +25,54 @@ export { VALIDATION_REGISTRY_PROGRAM_ID }
 // PDA Seeds
 const VALIDATION_SEED = Buffer.from('validation')
 const AUTHORITY_SEED = Buffer.from('authority')
+const VALIDATOR_SEED = Buffer.from('validator')
+const CONFIG_SEED = Buffer.from('config')
+const ENDPOINTS_SEED = Buffer.from('endpoints')
+const DISPUTE_SEED = Buffer.from('dispute')
+const STAMP_MULTISIG_SEED = Buffer.from('stamp_multisig')
+const STAMP_PROPOSAL_SEED = Buffer.from('stamp_proposal')
+const REWARD_POOL_SEED = Buffer.from('reward_pool')
+const IDENTITY_AGENT_SEED = Buffer.from('agent')
+const IDENTITY_REGISTRY_PROGRAM_ID = new PublicKey('2pELseyWXsBRXWBEPZAMqXsyBsRKADAz6LhSgV8Szc2e')
+
+/** Minimum validator bond in lamports (0.1 SOL), matching MIN_VALIDATOR_BOND */
+export const MIN_VALIDATOR_BOND = 100_000_000n
+
+/** Bond in lamports (0.01 SOL) escrowed to open a dispute, matching DISPUTE_BOND */
+export const DISPUTE_BOND = 10_000_000n
+
+/** Sentinel for a TestResult cost that wasn't reported, matching COST_NOT_REPORTED */
+export const COST_NOT_REPORTED = 0xffff_ffff_ffff_ffffn
 
 // ============================================================================
 // TYPES
 // ============================================================================
 
+/** Validation stamp tier (u8 on-chain) */
+export enum StampTier {
+  None = 0,
+  Bronze = 1,
+  Silver = 2,
+  Gold = 3,
+}
+
 export interface TestResult {
   llmModel: string
   success: boolean
   responseTime: bigint
   score: number
+  /** Submitting wallet; assigned on-chain, so it may be omitted when building instructions */
+  validator?: PublicKey
+  /** Median latency in ms; 0 (or omitted) when not reported */
+  p50Ms?: number
+  /** 95th percentile latency in ms; 0 (or omitted) when not reported */
+  p95Ms?: number
+  /** Cost in micro-USD; COST_NOT_REPORTED (or omitted) when not reported */
+  costMicroUsd?: bigint
+  /** HTTP status class (1-5 for 1xx-5xx); 0 (or omitted) when not reported */
+  httpStatusClass?: number
+  /** Hash of the provider error code; zeroed (or omitted) when there was none */
+  errorCodeHash?: Uint8Array
 }
 
 export interface EndpointValidation {
@@ -43,14 +81,142 @@ export interface EndpointValidation {
   providerAgent: PublicKey
   testResults: TestResult[]
   consensusScore: number
+  stampTier: StampTier
+  /** Compatibility view: stamped at any tier from Bronze up */
+  stampIssued: boolean
+  timestamp: bigint
+  bump: number
+  validationRound: number
+  stampIssuedAt: bigint
+  stampExpiresAt: bigint
+  stampRevokedAt: bigint
+  stampRevocationReason: Uint8Array
+  /** Bitmask of testResults indices under an open dispute */
+  disputedResults: number
+  disputeCount: number
+  /** Wallet that paid the rent; receives it back on close */
+  payer: PublicKey
+}
+
+/** Snapshot returned by query_validations via return data */
+export interface ValidationView {
+  endpointUrl: string
+  providerAgent: PublicKey
+  consensusScore: number
   stampIssued: boolean
+  stampTier: StampTier
+  testCount: number
   timestamp: bigint
+}
+
+/** Dispute lifecycle (u8 on-chain) */
+export enum DisputeStatus {
+  Open = 0,
+  Upheld = 1,
+  Rejected = 2,
+}
+
+export interface ValidationDispute {
+  endpointHash: Uint8Array
+  disputeIndex: number
+  disputer: PublicKey
+  resultIndex: number
+  disputedResult: TestResult
+  evidenceHash: Uint8Array
+  bondLamports: bigint
+  validationRound: number
+  status: DisputeStatus
+  openedAt: bigint
+  resolvedAt: bigint
   bump: number
 }
 
 export interface ValidationAuthority {
   authority: PublicKey
   bump: number
+  /** Nominated successor awaiting acceptance (PublicKey.default when none) */
+  pendingAuthority: PublicKey
+  /** StampMultisig gating stamp issuance and revocation (PublicKey.default in single-key mode) */
+  stampMultisig: PublicKey
+}
+
+/** Stamp action a multisig proposal authorizes (u8 on-chain) */
+export enum StampAction {
+  Issue = 0,
+  Revoke = 1,
+}
+
+/** Stamp proposal lifecycle (u8 on-chain) */
+export enum ProposalStatus {
+  Pending = 0,
+  Approved = 1,
+  Executed = 2,
+}
+
+export interface StampProposal {
+  proposalId: bigint
+  action: StampAction
+  endpointHash: Uint8Array
+  reasonHash: Uint8Array
+  proposer: PublicKey
+  approvalBitmap: number
+  approvalCount: number
+  status: ProposalStatus
+  createdAt: bigint
+  executedAt: bigint
+  bump: number
+}
+
+export interface ValidationConfig {
+  stampValiditySeconds: bigint
+  bump: number
+  bronzeThreshold: number
+  silverThreshold: number
+  goldThreshold: number
+  retentionSeconds: bigint
+  /** p95 latency (ms) above which results are penalized in consensus; 0 disables the penalty */
+  p95LatencyThresholdMs: number
+  /** Lamports a provider pays into the endpoint's reward pool when it submits; 0 = free */
+  submissionFeeLamports: bigint
+  /** Whether only results counted in consensus earn reward shares */
+  rewardCountedResultsOnly: boolean
+}
+
+export interface RewardShare {
+  validator: PublicKey
+  results: number
+  claimed: boolean
+}
+
+export interface RewardPool {
+  endpointHash: Uint8Array
+  totalLamports: bigint
+  distributedLamports: bigint
+  countedResultsOnly: boolean
+  /** Whether shares have been fixed from the endpoint's results (on the first claim) */
+  settled: boolean
+  eligibleResults: number
+  shares: RewardShare[]
+  bump: number
+}
+
+export interface ProviderEndpointIndex {
+  provider: PublicKey
+  endpointHashes: Uint8Array[]
+  latestEndpointHash: Uint8Array
+  stampsIssued: number
+  latestStampedHash: Uint8Array
+  bump: number
+}
+
+export interface ValidatorRecord {
+  validator: PublicKey
+  bondLamports: bigint
+  registeredAt: bigint
+  isSlashed: boolean
+  slashedAt: bigint
+  slashReasonHash: Uint8Array
+  bump: number
 }
 
 // ============================================================================
@@ -73,6 +239,65 @@ export function getAuthorityPDA(
   return PublicKey.findProgramAddressSync([AUTHORITY_SEED], programId)
 }
 
+export function getValidationConfigPDA(
+  programId: PublicKey = VALIDATION_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([CONFIG_SEED], programId)
+}
+
+export function getProviderEndpointIndexPDA(
+  provider: PublicKey,
+  programId: PublicKey = VALIDATION_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([ENDPOINTS_SEED, provider.toBuffer()], programId)
+}
+
+function getIdentityPDA(agent: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [IDENTITY_AGENT_SEED, agent.toBuffer()],
+    IDENTITY_REGISTRY_PROGRAM_ID
+  )[0]
+}
+
+export function getStampMultisigPDA(
+  programId: PublicKey = VALIDATION_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([STAMP_MULTISIG_SEED], programId)
+}
+
+export function getStampProposalPDA(
+  proposalId: bigint,
+  programId: PublicKey = VALIDATION_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  const id = Buffer.alloc(8)
+  id.writeBigUInt64LE(proposalId, 0)
+  return PublicKey.findProgramAddressSync([STAMP_PROPOSAL_SEED, id], programId)
+}
+
+export function getRewardPoolPDA(
+  endpointHash: Uint8Array,
+  programId: PublicKey = VALIDATION_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([REWARD_POOL_SEED, Buffer.from(endpointHash)], programId)
+}
+
+export function getDisputePDA(
+  endpointHash: Uint8Array,
+  disputeIndex: number,
+  programId: PublicKey = VALIDATION_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  const index = Buffer.alloc(4)
+  index.writeUInt32LE(disputeIndex, 0)
+  return PublicKey.findProgramAddressSync([DISPUTE_SEED, Buffer.from(endpointHash), index], programId)
+}
+
+export function getValidatorRecordPDA(
+  validator: PublicKey,
+  programId: PublicKey = VALIDATION_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([VALIDATOR_SEED, validator.toBuffer()], programId)
+}
+
 // ============================================================================
 // INSTRUCTION DISCRIMINATORS (from Anchor IDL)
 // ============================================================================
@@ -80,11 +305,41 @@ export function getAuthorityPDA(
 const DISCRIMINATORS = {
   initializeAuthority: Buffer.from([13, 186, 25, 16, 218, 31, 90, 1]),
   submitValidation: Buffer.from([224, 75, 32, 63, 177, 137, 242, 221]),
+  appendTestResult: Buffer.from([138, 233, 16, 180, 63, 154, 160, 229]),
+  resubmitValidation: Buffer.from([181, 157, 206, 153, 33, 231, 13, 45]),
+  registerValidator: Buffer.from([118, 98, 251, 58, 81, 30, 13, 240]),
+  slashValidator: Buffer.from([238, 57, 244, 40, 132, 82, 78, 5]),
   queryValidations: Buffer.from([163, 117, 85, 0, 163, 254, 58, 54]),
   calculateConsensus: Buffer.from([87, 74, 198, 240, 8, 148, 101, 185]),
   issueValidationStamp: Buffer.from([157, 211, 53, 131, 210, 78, 253, 176]),
+  revokeStamp: Buffer.from([43, 97, 245, 168, 118, 53, 141, 23]),
+  markStampExpired: Buffer.from([135, 141, 250, 235, 245, 101, 82, 65]),
+  disputeValidation: Buffer.from([224, 104, 157, 254, 105, 94, 10, 235]),
+  resolveDispute: Buffer.from([231, 6, 202, 6, 96, 103, 12, 230]),
+  closeValidation: Buffer.from([107, 119, 249, 35, 5, 54, 9, 15]),
+  claimValidatorReward: Buffer.from([255, 194, 143, 228, 188, 239, 126, 109]),
+  // Authority rotation / stamp multisig
+  transferValidationAuthority: Buffer.from([20, 136, 54, 196, 218, 212, 228, 209]),
+  cancelValidationAuthorityTransfer: Buffer.from([64, 252, 113, 38, 65, 46, 143, 26]),
+  acceptValidationAuthority: Buffer.from([213, 135, 108, 93, 176, 213, 242, 236]),
+  initializeStampMultisig: Buffer.from([12, 223, 250, 74, 104, 250, 202, 244]),
+  enableStampMultisig: Buffer.from([47, 7, 205, 31, 28, 215, 170, 131]),
+  proposeStampAction: Buffer.from([42, 121, 22, 140, 41, 204, 176, 138]),
+  approveStampProposal: Buffer.from([202, 189, 70, 51, 147, 51, 37, 63]),
+  // Admin / maintenance
+  initializeValidationConfig: Buffer.from([138, 209, 223, 183, 48, 227, 146, 152]),
+  updateStampThresholds: Buffer.from([147, 48, 153, 19, 217, 38, 194, 207]),
+  updateStampValidity: Buffer.from([49, 77, 61, 213, 23, 199, 6, 120]),
+  updateRetentionPeriod: Buffer.from([53, 230, 250, 110, 84, 34, 122, 76]),
+  updateLatencyThreshold: Buffer.from([236, 102, 78, 227, 66, 62, 173, 133]),
+  updateRewardConfig: Buffer.from([35, 111, 215, 56, 135, 228, 232, 50]),
+  migrateEndpointValidation: Buffer.from([166, 192, 168, 241, 24, 36, 166, 160]),
+  migrateAuthority: Buffer.from([208, 84, 72, 24, 205, 144, 51, 86]),
 }
 
+/** Account discriminator of EndpointValidation (sha256("account:EndpointValidation")[..8]) */
+const ENDPOINT_VALIDATION_DISCRIMINATOR = Buffer.from([31, 57, 134, 51, 12, 222, 76, 203])
+
 // ============================================================================
 // CLIENT CLASS
 // ============================================================================
@@ -128,44 +383,31 @@ export class ValidationRegistryClient {
 
   /**
    * Build submit validation instruction
+   *
+   * Pass `withRewardPool` while a submission fee is configured; the payer funds it whoever the provider is.
    */
   buildSubmitValidationInstruction(
     payer: PublicKey,
     providerAgent: PublicKey,
     endpointUrl: string,
     endpointHash: Uint8Array,
-    testResults: TestResult[]
+    testResults: TestResult[],
+    withValidatorRecord = testResults.length > 0,
+    withRewardPool = false
   ): TransactionInstruction {
     const [endpointValidation] = getValidationPDA(endpointHash, this.programId)
+    const [endpointIndex] = getProviderEndpointIndexPDA(providerAgent, this.programId)
+    const validatorRecord = withValidatorRecord
+      ? getValidatorRecordPDA(payer, this.programId)[0]
+      : this.programId
+    const [validationConfig] = getValidationConfigPDA(this.programId)
+    const rewardPool = withRewardPool ? getRewardPoolPDA(endpointHash, this.programId)[0] : this.programId
 
     // Serialize endpoint URL
     const urlBuffer = Buffer.from(endpointUrl)
 
     // Serialize test results
-    const testResultsBuffers: Buffer[] = []
-    for (const result of testResults) {
-      const modelBuffer = Buffer.from(result.llmModel)
-      const resultBuffer = Buffer.alloc(4 + modelBuffer.length + 1 + 8 + 1)
-      let offset = 0
-
-      resultBuffer.writeUInt32LE(modelBuffer.length, offset)
-      offset += 4
-      modelBuffer.copy(resultBuffer, offset)
-      offset += modelBuffer.length
-      resultBuffer.writeUInt8(result.success ? 1 : 0, offset)
-      offset += 1
-      resultBuffer.writeBigUInt64LE(result.responseTime, offset)
-      offset += 8
-      resultBuffer.writeUInt8(result.score, offset)
-
-      testResultsBuffers.push(resultBuffer)
-    }
-
-    const testResultsBuffer = Buffer.concat([
-      Buffer.alloc(4), // vec length
-      ...testResultsBuffers,
-    ])
-    testResultsBuffer.writeUInt32LE(testResults.length, 0)
+    const testResultsBuffer = serializeTestResults(testResults)
 
     // Build instruction data
     const data = Buffer.concat([
@@ -183,28 +425,165 @@ export class ValidationRegistryClient {
       keys: [
         { pubkey: endpointValidation, isSigner: false, isWritable: true },
         { pubkey: providerAgent, isSigner: false, isWritable: false },
+        { pubkey: getIdentityPDA(providerAgent), isSigner: false, isWritable: false },
+        { pubkey: endpointIndex, isSigner: false, isWritable: true },
         { pubkey: payer, isSigner: true, isWritable: true },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: validatorRecord, isSigner: false, isWritable: false },
+        { pubkey: validationConfig, isSigner: false, isWritable: false },
+        { pubkey: rewardPool, isSigner: false, isWritable: withRewardPool },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build append test result instruction (one result per validator)
+   */
+  buildAppendTestResultInstruction(
+    validator: PublicKey,
+    endpointHash: Uint8Array,
+    testResult: TestResult
+  ): TransactionInstruction {
+    const [endpointValidation] = getValidationPDA(endpointHash, this.programId)
+    const [validatorRecord] = getValidatorRecordPDA(validator, this.programId)
+
+    const data = Buffer.concat([
+      DISCRIMINATORS.appendTestResult,
+      serializeTestResult(testResult),
+    ])
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: endpointValidation, isSigner: false, isWritable: true },
+        { pubkey: validator, isSigner: true, isWritable: false },
+        { pubkey: validatorRecord, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build resubmit validation instruction (provider agent or authority).
+   * Providers resubmitting results must pass withValidatorRecord.
+   */
+  buildResubmitValidationInstruction(
+    signer: PublicKey,
+    endpointHash: Uint8Array,
+    testResults: TestResult[],
+    withValidatorRecord = false
+  ): TransactionInstruction {
+    const [endpointValidation] = getValidationPDA(endpointHash, this.programId)
+    const [authorityAccount] = getAuthorityPDA(this.programId)
+    const validatorRecord = withValidatorRecord
+      ? getValidatorRecordPDA(signer, this.programId)[0]
+      : this.programId
+
+    const data = Buffer.concat([
+      DISCRIMINATORS.resubmitValidation,
+      serializeTestResults(testResults),
+    ])
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: endpointValidation, isSigner: false, isWritable: true },
+        { pubkey: authorityAccount, isSigner: false, isWritable: false },
+        { pubkey: signer, isSigner: true, isWritable: false },
+        { pubkey: validatorRecord, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build register validator instruction (requires an active agent identity)
+   */
+  buildRegisterValidatorInstruction(
+    validator: PublicKey,
+    bondLamports: bigint = MIN_VALIDATOR_BOND
+  ): TransactionInstruction {
+    const [validatorRecord] = getValidatorRecordPDA(validator, this.programId)
+    const validatorIdentity = getIdentityPDA(validator)
+
+    const data = Buffer.alloc(8 + 8)
+    DISCRIMINATORS.registerValidator.copy(data, 0)
+    data.writeBigUInt64LE(bondLamports, 8)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: validatorRecord, isSigner: false, isWritable: true },
+        { pubkey: validatorIdentity, isSigner: false, isWritable: false },
+        { pubkey: validator, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build slash validator instruction (authority only)
+   */
+  buildSlashValidatorInstruction(
+    authority: PublicKey,
+    validator: PublicKey,
+    reasonHash: Uint8Array
+  ): TransactionInstruction {
+    const [validatorRecord] = getValidatorRecordPDA(validator, this.programId)
+    const [authorityAccount] = getAuthorityPDA(this.programId)
+
+    const data = Buffer.concat([DISCRIMINATORS.slashValidator, Buffer.from(reasonHash)])
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: validatorRecord, isSigner: false, isWritable: true },
+        { pubkey: authorityAccount, isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: true },
       ],
       programId: this.programId,
       data,
     })
   }
 
+  /**
+   * Build query validations instruction; simulate it and decode the return
+   * data with decodeValidationView. Fails when consensus is below `minScore`.
+   */
+  buildQueryValidationsInstruction(
+    endpointValidation: PublicKey,
+    minScore = 0
+  ): TransactionInstruction {
+    const data = Buffer.alloc(8 + 2)
+    DISCRIMINATORS.queryValidations.copy(data, 0)
+    data.writeUInt16LE(minScore, 8)
+
+    return new TransactionInstruction({
+      keys: [{ pubkey: endpointValidation, isSigner: false, isWritable: false }],
+      programId: this.programId,
+      data,
+    })
+  }
+
   /**
    * Build calculate consensus instruction
    */
   buildCalculateConsensusInstruction(
     authority: PublicKey,
-    endpointValidation: PublicKey
+    endpointValidation: PublicKey,
+    withConfig = false
   ): TransactionInstruction {
     const [authorityAccount] = getAuthorityPDA(this.programId)
+    const validationConfig = withConfig ? getValidationConfigPDA(this.programId)[0] : this.programId
 
     return new TransactionInstruction({
       keys: [
         { pubkey: endpointValidation, isSigner: false, isWritable: true },
         { pubkey: authorityAccount, isSigner: false, isWritable: false },
         { pubkey: authority, isSigner: true, isWritable: false },
+        { pubkey: validationConfig, isSigner: false, isWritable: false },
       ],
       programId: this.programId,
       data: DISCRIMINATORS.calculateConsensus,
@@ -216,58 +595,470 @@ export class ValidationRegistryClient {
    */
   buildIssueValidationStampInstruction(
     authority: PublicKey,
-    endpointValidation: PublicKey
+    endpointValidation: PublicKey,
+    providerAgent: PublicKey,
+    withConfig = false,
+    proposalId?: bigint
   ): TransactionInstruction {
     const [authorityAccount] = getAuthorityPDA(this.programId)
+    const validationConfig = withConfig ? getValidationConfigPDA(this.programId)[0] : this.programId
+    const [endpointIndex] = getProviderEndpointIndexPDA(providerAgent, this.programId)
+    const stampProposal =
+      proposalId === undefined ? this.programId : getStampProposalPDA(proposalId, this.programId)[0]
 
     return new TransactionInstruction({
       keys: [
         { pubkey: endpointValidation, isSigner: false, isWritable: true },
         { pubkey: authorityAccount, isSigner: false, isWritable: false },
         { pubkey: authority, isSigner: true, isWritable: false },
+        { pubkey: validationConfig, isSigner: false, isWritable: false },
+        { pubkey: endpointIndex, isSigner: false, isWritable: true },
+        { pubkey: stampProposal, isSigner: false, isWritable: proposalId !== undefined },
       ],
       programId: this.programId,
       data: DISCRIMINATORS.issueValidationStamp,
     })
   }
 
-  // ==========================================================================
-  // ACCOUNT FETCHERS
-  // ==========================================================================
-
   /**
-   * Fetch validation by endpoint hash
+   * Build revoke stamp instruction (authority only; pass the approved proposal
+   * once the stamp multisig is enabled)
+   */
+  buildRevokeStampInstruction(
+    authority: PublicKey,
+    endpointValidation: PublicKey,
+    reasonHash: Uint8Array,
+    proposalId?: bigint
+  ): TransactionInstruction {
+    const [authorityAccount] = getAuthorityPDA(this.programId)
+    const stampProposal =
+      proposalId === undefined ? this.programId : getStampProposalPDA(proposalId, this.programId)[0]
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: endpointValidation, isSigner: false, isWritable: true },
+        { pubkey: authorityAccount, isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: false },
+        { pubkey: stampProposal, isSigner: false, isWritable: proposalId !== undefined },
+      ],
+      programId: this.programId,
+      data: Buffer.concat([DISCRIMINATORS.revokeStamp, Buffer.from(reasonHash)]),
+    })
+  }
+
+  /**
+   * Build mark stamp expired instruction (permissionless once past expiry)
+   */
+  buildMarkStampExpiredInstruction(endpointValidation: PublicKey): TransactionInstruction {
+    return new TransactionInstruction({
+      keys: [{ pubkey: endpointValidation, isSigner: false, isWritable: true }],
+      programId: this.programId,
+      data: DISCRIMINATORS.markStampExpired,
+    })
+  }
+
+  /**
+   * Build dispute validation instruction (provider agent only)
+   *
+   * @param disputeIndex - The endpoint's current disputeCount
+   */
+  buildDisputeValidationInstruction(
+    providerAgent: PublicKey,
+    endpointHash: Uint8Array,
+    disputeIndex: number,
+    disputedResultIndex: number,
+    evidenceHash: Uint8Array
+  ): TransactionInstruction {
+    const [endpointValidation] = getValidationPDA(endpointHash, this.programId)
+    const [dispute] = getDisputePDA(endpointHash, disputeIndex, this.programId)
+
+    const data = Buffer.concat([
+      DISCRIMINATORS.disputeValidation,
+      Buffer.from(endpointHash),
+      Buffer.from([disputedResultIndex]),
+      Buffer.from(evidenceHash),
+    ])
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: endpointValidation, isSigner: false, isWritable: true },
+        { pubkey: dispute, isSigner: false, isWritable: true },
+        { pubkey: providerAgent, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build resolve dispute instruction (authority only)
+   */
+  buildResolveDisputeInstruction(
+    authority: PublicKey,
+    endpointHash: Uint8Array,
+    disputeIndex: number,
+    disputer: PublicKey,
+    uphold: boolean,
+    withConfig = false
+  ): TransactionInstruction {
+    const [dispute] = getDisputePDA(endpointHash, disputeIndex, this.programId)
+    const [endpointValidation] = getValidationPDA(endpointHash, this.programId)
+    const [authorityAccount] = getAuthorityPDA(this.programId)
+    const validationConfig = withConfig ? getValidationConfigPDA(this.programId)[0] : this.programId
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: dispute, isSigner: false, isWritable: true },
+        { pubkey: endpointValidation, isSigner: false, isWritable: true },
+        { pubkey: authorityAccount, isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: disputer, isSigner: false, isWritable: true },
+        { pubkey: validationConfig, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data: Buffer.concat([DISCRIMINATORS.resolveDispute, Buffer.from([uphold ? 1 : 0])]),
+    })
+  }
+
+  /**
+   * Build transfer validation authority instruction (current authority only);
+   * the nominee completes it with buildAcceptValidationAuthorityInstruction
+   */
+  buildTransferValidationAuthorityInstruction(
+    authority: PublicKey,
+    newAuthority: PublicKey
+  ): TransactionInstruction {
+    const [authorityAccount] = getAuthorityPDA(this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: authorityAccount, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data: Buffer.concat([DISCRIMINATORS.transferValidationAuthority, newAuthority.toBuffer()]),
+    })
+  }
+
+  /**
+   * Build cancel validation authority transfer instruction (current authority only)
+   */
+  buildCancelValidationAuthorityTransferInstruction(authority: PublicKey): TransactionInstruction {
+    const [authorityAccount] = getAuthorityPDA(this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: authorityAccount, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data: DISCRIMINATORS.cancelValidationAuthorityTransfer,
+    })
+  }
+
+  /**
+   * Build accept validation authority instruction (nominee only)
+   */
+  buildAcceptValidationAuthorityInstruction(newAuthority: PublicKey): TransactionInstruction {
+    const [authorityAccount] = getAuthorityPDA(this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: authorityAccount, isSigner: false, isWritable: true },
+        { pubkey: newAuthority, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data: DISCRIMINATORS.acceptValidationAuthority,
+    })
+  }
+
+  /**
+   * Build initialize stamp multisig instruction (authority only)
+   */
+  buildInitializeStampMultisigInstruction(
+    authority: PublicKey,
+    signers: PublicKey[],
+    threshold: number
+  ): TransactionInstruction {
+    const [authorityAccount] = getAuthorityPDA(this.programId)
+    const [stampMultisig] = getStampMultisigPDA(this.programId)
+
+    const count = Buffer.alloc(4)
+    count.writeUInt32LE(signers.length, 0)
+    const data = Buffer.concat([
+      DISCRIMINATORS.initializeStampMultisig,
+      count,
+      ...signers.map((s) => s.toBuffer()),
+      Buffer.from([threshold]),
+    ])
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: authorityAccount, isSigner: false, isWritable: false },
+        { pubkey: stampMultisig, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build enable stamp multisig instruction (authority only, irreversible)
+   */
+  buildEnableStampMultisigInstruction(authority: PublicKey): TransactionInstruction {
+    const [authorityAccount] = getAuthorityPDA(this.programId)
+    const [stampMultisig] = getStampMultisigPDA(this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: authorityAccount, isSigner: false, isWritable: true },
+        { pubkey: stampMultisig, isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data: DISCRIMINATORS.enableStampMultisig,
+    })
+  }
+
+  /**
+   * Build propose stamp action instruction (multisig signers only)
+   *
+   * @param proposalId - The multisig's current proposalCount
+   */
+  buildProposeStampActionInstruction(
+    proposer: PublicKey,
+    proposalId: bigint,
+    action: StampAction,
+    endpointHash: Uint8Array,
+    reasonHash: Uint8Array = new Uint8Array(32)
+  ): TransactionInstruction {
+    const [stampMultisig] = getStampMultisigPDA(this.programId)
+    const [stampProposal] = getStampProposalPDA(proposalId, this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: stampMultisig, isSigner: false, isWritable: true },
+        { pubkey: stampProposal, isSigner: false, isWritable: true },
+        { pubkey: proposer, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data: Buffer.concat([
+        DISCRIMINATORS.proposeStampAction,
+        Buffer.from([action]),
+        Buffer.from(endpointHash),
+        Buffer.from(reasonHash),
+      ]),
+    })
+  }
+
+  /**
+   * Build approve stamp proposal instruction (multisig signers only)
+   */
+  buildApproveStampProposalInstruction(signer: PublicKey, proposalId: bigint): TransactionInstruction {
+    const [stampMultisig] = getStampMultisigPDA(this.programId)
+    const [stampProposal] = getStampProposalPDA(proposalId, this.programId)
+
+    const data = Buffer.alloc(16)
+    DISCRIMINATORS.approveStampProposal.copy(data, 0)
+    data.writeBigUInt64LE(proposalId, 8)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: stampMultisig, isSigner: false, isWritable: false },
+        { pubkey: stampProposal, isSigner: false, isWritable: true },
+        { pubkey: signer, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build migrate authority instruction (permissionless; resizes a pre-rotation authority account)
+   */
+  buildMigrateAuthorityInstruction(payer: PublicKey): TransactionInstruction {
+    const [authorityAccount] = getAuthorityPDA(this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: authorityAccount, isSigner: false, isWritable: true },
+        { pubkey: payer, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data: DISCRIMINATORS.migrateAuthority,
+    })
+  }
+
+  /**
+   * Build close validation instruction (recorded payer or provider agent, after
+   * the retention period and with no valid stamp or open dispute)
+   */
+  buildCloseValidationInstruction(
+    signer: PublicKey,
+    validation: EndpointValidation,
+    withConfig = false,
+    withRewardPool = false
+  ): TransactionInstruction {
+    const [endpointValidation] = getValidationPDA(validation.endpointHash, this.programId)
+    const [endpointIndex] = getProviderEndpointIndexPDA(validation.providerAgent, this.programId)
+    const validationConfig = withConfig ? getValidationConfigPDA(this.programId)[0] : this.programId
+    const rewardPool = withRewardPool
+      ? getRewardPoolPDA(validation.endpointHash, this.programId)[0]
+      : this.programId
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: endpointValidation, isSigner: false, isWritable: true },
+        { pubkey: endpointIndex, isSigner: false, isWritable: true },
+        { pubkey: validation.payer, isSigner: false, isWritable: true },
+        { pubkey: signer, isSigner: true, isWritable: false },
+        { pubkey: validationConfig, isSigner: false, isWritable: false },
+        { pubkey: rewardPool, isSigner: false, isWritable: withRewardPool },
+      ],
+      programId: this.programId,
+      data: DISCRIMINATORS.closeValidation,
+    })
+  }
+
+  /**
+   * Build claim validator reward instruction (validators with eligible results, once
+   * consensus is calculated and no disputes are open)
+   */
+  buildClaimValidatorRewardInstruction(
+    validator: PublicKey,
+    endpointHash: Uint8Array
+  ): TransactionInstruction {
+    const [endpointValidation] = getValidationPDA(endpointHash, this.programId)
+    const [rewardPool] = getRewardPoolPDA(endpointHash, this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: endpointValidation, isSigner: false, isWritable: false },
+        { pubkey: rewardPool, isSigner: false, isWritable: true },
+        { pubkey: validator, isSigner: true, isWritable: true },
+      ],
+      programId: this.programId,
+      data: DISCRIMINATORS.claimValidatorReward,
+    })
+  }
+
+  // ==========================================================================
+  // ACCOUNT FETCHERS
+  // ==========================================================================
+
+  /**
+   * Fetch validation by endpoint hash
+   */
+  async getValidation(endpointHash: Uint8Array): Promise<EndpointValidation | null> {
+    const [pda] = getValidationPDA(endpointHash, this.programId)
+    return this.fetchValidation(pda)
+  }
+
+  /**
+   * Fetch validation by PDA
+   */
+  async fetchValidation(pda: PublicKey): Promise<EndpointValidation | null> {
+    try {
+      const accountInfo = await this.connection.getAccountInfo(pda)
+      if (!accountInfo?.data) return null
+      return parseEndpointValidation(accountInfo.data)
+    } catch (error) {
+      console.error('Failed to fetch endpoint validation:', error)
+      return null
+    }
+  }
+
+  /**
+   * Fetch authority configuration
+   */
+  async getAuthority(): Promise<ValidationAuthority | null> {
+    const [pda] = getAuthorityPDA(this.programId)
+    try {
+      const accountInfo = await this.connection.getAccountInfo(pda)
+      if (!accountInfo?.data) return null
+      return parseValidationAuthority(accountInfo.data)
+    } catch (error) {
+      console.error('Failed to fetch validation authority:', error)
+      return null
+    }
+  }
+
+  /**
+   * Fetch a stamp multisig proposal
+   */
+  async getStampProposal(proposalId: bigint): Promise<StampProposal | null> {
+    const [pda] = getStampProposalPDA(proposalId, this.programId)
+    try {
+      const accountInfo = await this.connection.getAccountInfo(pda)
+      if (!accountInfo?.data) return null
+      return parseStampProposal(accountInfo.data)
+    } catch (error) {
+      console.error('Failed to fetch stamp proposal:', error)
+      return null
+    }
+  }
+
+  /**
+   * Fetch an endpoint's validator reward pool
+   */
+  async getRewardPool(endpointHash: Uint8Array): Promise<RewardPool | null> {
+    const [pda] = getRewardPoolPDA(endpointHash, this.programId)
+    try {
+      const accountInfo = await this.connection.getAccountInfo(pda)
+      if (!accountInfo?.data) return null
+      return parseRewardPool(accountInfo.data)
+    } catch (error) {
+      console.error('Failed to fetch reward pool:', error)
+      return null
+    }
+  }
+
+  /**
+   * Fetch a validator's registration record
    */
-  async getValidation(endpointHash: Uint8Array): Promise<EndpointValidation | null> {
-    const [pda] = getValidationPDA(endpointHash, this.programId)
-    return this.fetchValidation(pda)
+  async getValidatorRecord(validator: PublicKey): Promise<ValidatorRecord | null> {
+    const [pda] = getValidatorRecordPDA(validator, this.programId)
+    try {
+      const accountInfo = await this.connection.getAccountInfo(pda)
+      if (!accountInfo?.data) return null
+      return parseValidatorRecord(accountInfo.data)
+    } catch (error) {
+      console.error('Failed to fetch validator record:', error)
+      return null
+    }
   }
 
   /**
-   * Fetch validation by PDA
+   * Fetch a dispute by endpoint hash and dispute index
    */
-  async fetchValidation(pda: PublicKey): Promise<EndpointValidation | null> {
+  async getDispute(endpointHash: Uint8Array, disputeIndex: number): Promise<ValidationDispute | null> {
+    const [pda] = getDisputePDA(endpointHash, disputeIndex, this.programId)
     try {
       const accountInfo = await this.connection.getAccountInfo(pda)
       if (!accountInfo?.data) return null
-      return parseEndpointValidation(accountInfo.data)
+      return parseValidationDispute(accountInfo.data)
     } catch (error) {
-      console.error('Failed to fetch endpoint validation:', error)
+      console.error('Failed to fetch validation dispute:', error)
       return null
     }
   }
 
   /**
-   * Fetch authority configuration
+   * Fetch a provider's on-chain endpoint index
    */
-  async getAuthority(): Promise<ValidationAuthority | null> {
-    const [pda] = getAuthorityPDA(this.programId)
+  async getProviderEndpointIndex(provider: PublicKey): Promise<ProviderEndpointIndex | null> {
+    const [pda] = getProviderEndpointIndexPDA(provider, this.programId)
     try {
       const accountInfo = await this.connection.getAccountInfo(pda)
       if (!accountInfo?.data) return null
-      return parseValidationAuthority(accountInfo.data)
+      return parseProviderEndpointIndex(accountInfo.data)
     } catch (error) {
-      console.error('Failed to fetch validation authority:', error)
+      console.error('Failed to fetch provider endpoint index:', error)
       return null
     }
   }
@@ -317,8 +1108,8 @@ export class ValidationRegistryClient {
 
       const validations: { address: PublicKey; validation: EndpointValidation }[] = []
       for (const { pubkey, account } of accounts) {
-        // Skip authority account (different discriminator)
-        if (account.data.length < 50) continue
+        // Skip authority, config, validator, index and dispute accounts
+        if (!account.data.subarray(0, 8).equals(ENDPOINT_VALIDATION_DISCRIMINATOR)) continue
 
         const validation = parseEndpointValidation(account.data)
         if (validation && validation.stampIssued) {
@@ -338,7 +1129,7 @@ export class ValidationRegistryClient {
    */
   async isEndpointValidated(endpointHash: Uint8Array): Promise<boolean> {
     const validation = await this.getValidation(endpointHash)
-    return validation !== null && validation.stampIssued
+    return validation !== null && isStampValid(validation)
   }
 
   /**
@@ -350,6 +1141,52 @@ export class ValidationRegistryClient {
   }
 }
 
+// ============================================================================
+// SERIALIZERS
+// ============================================================================
+
+function serializeTestResult(result: TestResult): Buffer {
+  const modelBuffer = Buffer.from(result.llmModel)
+  const resultBuffer = Buffer.alloc(4 + modelBuffer.length + 1 + 8 + 1 + 32 + 4 + 4 + 8 + 1 + 32)
+  let offset = 0
+
+  resultBuffer.writeUInt32LE(modelBuffer.length, offset)
+  offset += 4
+  modelBuffer.copy(resultBuffer, offset)
+  offset += modelBuffer.length
+  resultBuffer.writeUInt8(result.success ? 1 : 0, offset)
+  offset += 1
+  resultBuffer.writeBigUInt64LE(result.responseTime, offset)
+  offset += 8
+  resultBuffer.writeUInt8(result.score, offset)
+  offset += 1
+  const validator = result.validator ?? PublicKey.default
+  validator.toBuffer().copy(resultBuffer, offset)
+  offset += 32
+  resultBuffer.writeUInt32LE(result.p50Ms ?? 0, offset)
+  offset += 4
+  resultBuffer.writeUInt32LE(result.p95Ms ?? 0, offset)
+  offset += 4
+  resultBuffer.writeBigUInt64LE(result.costMicroUsd ?? COST_NOT_REPORTED, offset)
+  offset += 8
+  resultBuffer.writeUInt8(result.httpStatusClass ?? 0, offset)
+  offset += 1
+  Buffer.from(result.errorCodeHash ?? new Uint8Array(32)).copy(resultBuffer, offset)
+
+  return resultBuffer
+}
+
+function serializeTestResults(testResults: TestResult[]): Buffer {
+  const testResultsBuffers = testResults.map(serializeTestResult)
+
+  const testResultsBuffer = Buffer.concat([
+    Buffer.alloc(4), // vec length
+    ...testResultsBuffers,
+  ])
+  testResultsBuffer.writeUInt32LE(testResults.length, 0)
+  return testResultsBuffer
+}
+
 // ============================================================================
 // ACCOUNT PARSERS
 // ============================================================================
@@ -375,33 +1212,46 @@ function parseEndpointValidation(data: Buffer): EndpointValidation | null {
 
     const testResults: TestResult[] = []
     for (let i = 0; i < numResults; i++) {
-      const modelLen = data.readUInt32LE(offset)
-      offset += 4
-      const llmModel = data.subarray(offset, offset + modelLen).toString('utf-8')
-      offset += modelLen
-
-      const success = data.readUInt8(offset) === 1
-      offset += 1
-
-      const responseTime = data.readBigUInt64LE(offset)
-      offset += 8
-
-      const score = data.readUInt8(offset)
-      offset += 1
-
-      testResults.push({ llmModel, success, responseTime, score })
+      const [result, next] = parseTestResult(data, offset)
+      testResults.push(result)
+      offset = next
     }
 
     const consensusScore = data.readUInt16LE(offset)
     offset += 2
 
-    const stampIssued = data.readUInt8(offset) === 1
+    const stampTier = data.readUInt8(offset) as StampTier
+    const stampIssued = stampTier >= StampTier.Bronze
     offset += 1
 
     const timestamp = data.readBigInt64LE(offset)
     offset += 8
 
     const bump = data.readUInt8(offset)
+    offset += 1
+
+    const validationRound = data.readUInt32LE(offset)
+    offset += 4
+
+    const stampIssuedAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const stampExpiresAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const stampRevokedAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const stampRevocationReason = new Uint8Array(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const disputedResults = data.readUInt16LE(offset)
+    offset += 2
+
+    const disputeCount = data.readUInt32LE(offset)
+    offset += 4
+
+    const payer = new PublicKey(data.subarray(offset, offset + 32))
 
     return {
       endpointHash,
@@ -409,9 +1259,145 @@ function parseEndpointValidation(data: Buffer): EndpointValidation | null {
       providerAgent,
       testResults,
       consensusScore,
+      stampTier,
       stampIssued,
       timestamp,
       bump,
+      validationRound,
+      stampIssuedAt,
+      stampExpiresAt,
+      stampRevokedAt,
+      stampRevocationReason,
+      disputedResults,
+      disputeCount,
+      payer,
+    }
+  } catch {
+    return null
+  }
+}
+
+/**
+ * Decode the ValidationView that query_validations sets as return data
+ */
+export function decodeValidationView(data: Buffer): ValidationView {
+  let offset = 0
+
+  const urlLength = data.readUInt32LE(offset)
+  offset += 4
+  const endpointUrl = data.subarray(offset, offset + urlLength).toString('utf-8')
+  offset += urlLength
+
+  const providerAgent = new PublicKey(data.subarray(offset, offset + 32))
+  offset += 32
+
+  const consensusScore = data.readUInt16LE(offset)
+  offset += 2
+
+  const stampIssued = data.readUInt8(offset) === 1
+  offset += 1
+
+  const stampTier = data.readUInt8(offset) as StampTier
+  offset += 1
+
+  const testCount = data.readUInt8(offset)
+  offset += 1
+
+  const timestamp = data.readBigInt64LE(offset)
+
+  return { endpointUrl, providerAgent, consensusScore, stampIssued, stampTier, testCount, timestamp }
+}
+
+/** Decode one TestResult at `offset`, returning it with the offset just past it */
+function parseTestResult(data: Buffer, offset: number): [TestResult, number] {
+  const modelLen = data.readUInt32LE(offset)
+  offset += 4
+  const llmModel = data.subarray(offset, offset + modelLen).toString('utf-8')
+  offset += modelLen
+
+  const success = data.readUInt8(offset) === 1
+  offset += 1
+
+  const responseTime = data.readBigUInt64LE(offset)
+  offset += 8
+
+  const score = data.readUInt8(offset)
+  offset += 1
+
+  const validator = new PublicKey(data.subarray(offset, offset + 32))
+  offset += 32
+
+  const p50Ms = data.readUInt32LE(offset)
+  offset += 4
+
+  const p95Ms = data.readUInt32LE(offset)
+  offset += 4
+
+  const costMicroUsd = data.readBigUInt64LE(offset)
+  offset += 8
+
+  const httpStatusClass = data.readUInt8(offset)
+  offset += 1
+
+  const errorCodeHash = new Uint8Array(data.subarray(offset, offset + 32))
+  offset += 32
+
+  const result = { llmModel, success, responseTime, score, validator }
+  return [{ ...result, p50Ms, p95Ms, costMicroUsd, httpStatusClass, errorCodeHash }, offset]
+}
+
+function parseValidationDispute(data: Buffer): ValidationDispute | null {
+  try {
+    let offset = 8 // Skip discriminator
+
+    const endpointHash = new Uint8Array(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const disputeIndex = data.readUInt32LE(offset)
+    offset += 4
+
+    const disputer = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const resultIndex = data.readUInt8(offset)
+    offset += 1
+
+    const [disputedResult, next] = parseTestResult(data, offset)
+    offset = next
+
+    const evidenceHash = new Uint8Array(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const bondLamports = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const validationRound = data.readUInt32LE(offset)
+    offset += 4
+
+    const status = data.readUInt8(offset) as DisputeStatus
+    offset += 1
+
+    const openedAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const resolvedAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const bump = data.readUInt8(offset)
+
+    return {
+      endpointHash,
+      disputeIndex,
+      disputer,
+      resultIndex,
+      disputedResult,
+      evidenceHash,
+      bondLamports,
+      validationRound,
+      status,
+      openedAt,
+      resolvedAt,
+      bump,
     }
   } catch {
     return null
@@ -425,9 +1411,188 @@ function parseValidationAuthority(data: Buffer): ValidationAuthority | null {
     const authority = new PublicKey(data.subarray(offset, offset + 32))
     offset += 32
 
+    const bump = data.readUInt8(offset)
+    offset += 1
+
+    // Accounts not yet migrated end here: no pending transfer, single-key stamps
+    if (data.length < offset + 64) {
+      return { authority, bump, pendingAuthority: PublicKey.default, stampMultisig: PublicKey.default }
+    }
+
+    const pendingAuthority = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const stampMultisig = new PublicKey(data.subarray(offset, offset + 32))
+
+    return { authority, bump, pendingAuthority, stampMultisig }
+  } catch {
+    return null
+  }
+}
+
+function parseStampProposal(data: Buffer): StampProposal | null {
+  try {
+    let offset = 8 // Skip discriminator
+
+    const proposalId = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const action = data.readUInt8(offset) as StampAction
+    offset += 1
+
+    const endpointHash = new Uint8Array(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const reasonHash = new Uint8Array(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const proposer = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const approvalBitmap = data.readUInt8(offset)
+    offset += 1
+
+    const approvalCount = data.readUInt8(offset)
+    offset += 1
+
+    const status = data.readUInt8(offset) as ProposalStatus
+    offset += 1
+
+    const createdAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const executedAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const bump = data.readUInt8(offset)
+
+    return {
+      proposalId,
+      action,
+      endpointHash,
+      reasonHash,
+      proposer,
+      approvalBitmap,
+      approvalCount,
+      status,
+      createdAt,
+      executedAt,
+      bump,
+    }
+  } catch {
+    return null
+  }
+}
+
+function parseRewardPool(data: Buffer): RewardPool | null {
+  try {
+    let offset = 8 // Skip discriminator
+
+    const endpointHash = new Uint8Array(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const totalLamports = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const distributedLamports = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const countedResultsOnly = data.readUInt8(offset) === 1
+    offset += 1
+
+    const settled = data.readUInt8(offset) === 1
+    offset += 1
+
+    const eligibleResults = data.readUInt8(offset)
+    offset += 1
+
+    const count = data.readUInt32LE(offset)
+    offset += 4
+    const shares: RewardShare[] = []
+    for (let i = 0; i < count; i++) {
+      const validator = new PublicKey(data.subarray(offset, offset + 32))
+      offset += 32
+      const results = data.readUInt8(offset)
+      offset += 1
+      const claimed = data.readUInt8(offset) === 1
+      offset += 1
+      shares.push({ validator, results, claimed })
+    }
+
+    const bump = data.readUInt8(offset)
+
+    return {
+      endpointHash,
+      totalLamports,
+      distributedLamports,
+      countedResultsOnly,
+      settled,
+      eligibleResults,
+      shares,
+      bump,
+    }
+  } catch {
+    return null
+  }
+}
+
+function parseProviderEndpointIndex(data: Buffer): ProviderEndpointIndex | null {
+  try {
+    let offset = 8 // Skip discriminator
+
+    const provider = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const count = data.readUInt32LE(offset)
+    offset += 4
+    const endpointHashes: Uint8Array[] = []
+    for (let i = 0; i < count; i++) {
+      endpointHashes.push(new Uint8Array(data.subarray(offset, offset + 32)))
+      offset += 32
+    }
+
+    const latestEndpointHash = new Uint8Array(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const stampsIssued = data.readUInt32LE(offset)
+    offset += 4
+
+    const latestStampedHash = new Uint8Array(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const bump = data.readUInt8(offset)
+
+    return { provider, endpointHashes, latestEndpointHash, stampsIssued, latestStampedHash, bump }
+  } catch {
+    return null
+  }
+}
+
+function parseValidatorRecord(data: Buffer): ValidatorRecord | null {
+  try {
+    let offset = 8 // Skip discriminator
+
+    const validator = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const bondLamports = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const registeredAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const isSlashed = data.readUInt8(offset) === 1
+    offset += 1
+
+    const slashedAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const slashReasonHash = new Uint8Array(data.subarray(offset, offset + 32))
+    offset += 32
+
     const bump = data.readUInt8(offset)
 
-    return { authority, bump }
+    return { validator, bondLamports, registeredAt, isSlashed, slashedAt, slashReasonHash, bump }
   } catch {
     return null
   }
@@ -438,53 +1603,130 @@ function parseValidationAuthority(data: Buffer): ValidationAuthority | null {
 // ============================================================================
 
 /**
- * Hash an endpoint URL for PDA derivation
+ * Canonicalize an https endpoint URL the way submit_validation does:
+ * lowercase scheme and host, drop the default :443 port and one trailing slash.
+ * Path and query are kept as given.
+ *
+ * @throws if the URL does not use https or has no host
+ */
+export function canonicalizeEndpointUrl(url: string): string {
+  const separator = url.indexOf('://')
+  if (separator < 0 || url.slice(0, separator).toLowerCase() !== 'https') {
+    throw new Error('Endpoint URL must use the https scheme')
+  }
+  const rest = url.slice(separator + 3)
+
+  const hostEnd = rest.search(/[/?#]/)
+  let host = (hostEnd < 0 ? rest : rest.slice(0, hostEnd)).toLowerCase()
+  let path = hostEnd < 0 ? '' : rest.slice(hostEnd)
+  if (host.endsWith(':443')) host = host.slice(0, -4)
+  if (!host) throw new Error('Endpoint URL has no host')
+  if (path.endsWith('/')) path = path.slice(0, -1)
+
+  return `https://${host}${path}`
+}
+
+/**
+ * Hash an endpoint URL for PDA derivation (sha256 of its canonical form)
  */
 export async function hashEndpointUrl(url: string): Promise<Uint8Array> {
   const encoder = new TextEncoder()
-  const data = encoder.encode(url)
+  const data = encoder.encode(canonicalizeEndpointUrl(url))
   const hashBuffer = await crypto.subtle.digest('SHA-256', data.buffer as ArrayBuffer)
   return new Uint8Array(hashBuffer)
 }
 
+/** Consensus tuning, matching the program constants */
+const RESPONSE_TIMEOUT_MS = 30_000n
+const FAST_RESPONSE_MS = 1_000n
+const RESPONSE_TIME_FLOOR_MS = 100n
+const MAX_SPEED_BONUS_BPS = 1_000n
+const TRIM_MIN_RESULTS = 5
+
 /**
  * Calculate consensus score from test results
- * Matches the on-chain calculation
+ * Matches the on-chain calculation (compute_consensus):
+ * - Timeouts (>= 30s) count as failures
+ * - Successful scores get up to a 10% bonus for responses faster than 1s
+ * - With a non-zero p95 threshold (ValidationConfig.p95LatencyThresholdMs), a score
+ *   whose reported p95 exceeds it is scaled by threshold / p95
+ * - With 5+ successful results the highest and lowest scores are trimmed
+ * - Success rate adds up to 900 points; total is capped at 1000
  */
-export function calculateConsensusScore(testResults: TestResult[]): number {
+export function calculateConsensusScore(testResults: TestResult[], p95ThresholdMs = 0): number {
   if (testResults.length === 0) return 0
 
-  let totalScore = 0
-  let successCount = 0
+  const scores = testResults
+    .filter((r) => r.success && r.responseTime < RESPONSE_TIMEOUT_MS)
+    .map((r) => {
+      const responseTime = r.responseTime > RESPONSE_TIME_FLOOR_MS ? r.responseTime : RESPONSE_TIME_FLOOR_MS
+      const fasterBy = FAST_RESPONSE_MS > responseTime ? FAST_RESPONSE_MS - responseTime : 0n
+      const bonusBps = (fasterBy * MAX_SPEED_BONUS_BPS) / (FAST_RESPONSE_MS - RESPONSE_TIME_FLOOR_MS)
+      const adjusted = Math.min(100, Number((BigInt(r.score) * (10_000n + bonusBps)) / 10_000n))
+      const p95Ms = r.p95Ms ?? 0
+      if (p95ThresholdMs === 0 || p95Ms <= p95ThresholdMs) return adjusted
+      return Math.floor((adjusted * p95ThresholdMs) / p95Ms)
+    })
+  const successCount = scores.length
 
-  for (const result of testResults) {
-    if (result.success) {
-      successCount++
-      totalScore += result.score
-    }
+  if (scores.length >= TRIM_MIN_RESULTS) {
+    scores.sort((a, b) => a - b)
+    scores.pop()
+    scores.shift()
   }
 
-  if (successCount === 0) return 0
+  const avgScore = scores.length > 0 ? Math.floor(scores.reduce((a, b) => a + b, 0) / scores.length) : 0
 
-  // Base score: average of successful test scores
-  const avgScore = totalScore / successCount
-
-  // Success rate bonus (0-200 points)
-  const successRate = successCount / testResults.length
-  const successBonus = Math.floor(successRate * 200)
-
-  // Minimum tests bonus (up to 100 points for 5+ tests)
-  const testCountBonus = Math.min(100, testResults.length * 20)
+  // Success rate bonus (0-900 points)
+  const successRate = Math.floor((successCount * 100) / testResults.length)
+  const successBonus = successRate * 9
 
   // Final score (0-1000)
-  return Math.min(1000, Math.floor(avgScore * 7) + successBonus + testCountBonus)
+  return Math.min(1000, avgScore + successBonus)
 }
 
 /**
  * Check if validation meets stamp requirements
  * - At least 3 test results
- * - Consensus score >= 600
+ * - Consensus score at or above the Bronze threshold (700 by default)
+ * - No open disputes
+ */
+export function meetsStampRequirements(
+  validation: EndpointValidation,
+  bronzeThreshold = 700
+): boolean {
+  return (
+    validation.testResults.length >= 3 &&
+    validation.consensusScore >= bronzeThreshold &&
+    validation.disputedResults === 0
+  )
+}
+
+/**
+ * Tier a consensus score earns; Gold also requires every test to have passed.
+ * Matches stamp_tier_for on-chain (defaults are the program's default thresholds).
+ */
+export function stampTierFor(
+  consensusScore: number,
+  allPassed: boolean,
+  thresholds: { bronze: number; silver: number; gold: number } = { bronze: 700, silver: 850, gold: 950 }
+): StampTier {
+  if (consensusScore >= thresholds.gold && allPassed) return StampTier.Gold
+  if (consensusScore >= thresholds.silver) return StampTier.Silver
+  if (consensusScore >= thresholds.bronze) return StampTier.Bronze
+  return StampTier.None
+}
+
+/**
+ * Check whether a validation's stamp is issued and not past its expiry.
+ * Matches EndpointValidation::is_stamp_valid on-chain.
  */
-export function meetsStampRequirements(validation: EndpointValidation): boolean {
-  return validation.testResults.length >= 3 && validation.consensusScore >= 600
+export function isStampValid(
+  validation: EndpointValidation,
+  now: bigint = BigInt(Math.floor(Date.now() / 1000))
+): boolean {
+  return (
+    validation.stampIssued &&
+    (validation.stampExpiresAt === 0n || now < validation.stampExpiresAt)
+  )
 }
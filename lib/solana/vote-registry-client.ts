@@ -13,9 +13,11 @@
 
 import {
   Connection,
+  Ed25519Program,
   PublicKey,
   TransactionInstruction,
   SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
 } from '@solana/web3.js'
 import {
   VOTE_REGISTRY_PROGRAM_ID,
@@ -31,6 +33,14 @@ const TX_RECEIPT_SEED = Buffer.from('tx_receipt')
 const PEER_VOTE_SEED = Buffer.from('peer_vote')
 const CONTENT_RATING_SEED = Buffer.from('content_rating')
 const ENDORSEMENT_SEED = Buffer.from('endorsement')
+const VOTE_TALLY_SEED = Buffer.from('vote_tally')
+const REPUTATION_CPI_SEED = Buffer.from('reputation_cpi')
+const VOTE_AUTHORITY_SEED = Buffer.from('authority')
+const VOTE_CONFIG_SEED = Buffer.from('config')
+const ENDORSEMENT_STATS_SEED = Buffer.from('endorsement_stats')
+const VOTE_PAIR_SEED = Buffer.from('vote_pair')
+const CONTENT_STATS_SEED = Buffer.from('content_stats')
+const COMMENT_REVEAL_SEED = Buffer.from('comment_reveal')
 
 // ============================================================================
 // TYPES
@@ -43,6 +53,8 @@ export const VoteTypeIndex: Record<VoteType, number> = {
   Neutral: 2,
 }
 
+export type VoteDisputeStatus = 'None' | 'Pending' | 'Removed' | 'Reinstated'
+
 export type ContentType =
   | 'ApiResponse'
   | 'GeneratedText'
@@ -87,8 +99,17 @@ export interface TransactionReceipt {
   amount: bigint
   timestamp: bigint
   contentType: ContentType
-  voteCast: boolean
+  payerVoted: boolean
+  recipientVoted: boolean
   bump: number
+  /** Whether the payer signed the payment message (create_verified_transaction_receipt) */
+  verified: boolean
+  /** Party that created the receipt; the other party confirms or rejects it */
+  creator: PublicKey
+  counterpartyConfirmed: boolean
+  isDisputed: boolean
+  /** Mint the amount is denominated in; NATIVE_SOL_MINT for lamports */
+  paymentMint: PublicKey
 }
 
 export interface PeerVote {
@@ -102,6 +123,25 @@ export interface PeerVote {
   transactionReceipt: PublicKey
   voteWeight: number
   bump: number
+  amendedCount: number
+  amendedAt: bigint
+  /** Whether the vote was folded into the VoteTally (required to close it) */
+  tallied: boolean
+  /** Whether the comment behind commentHash has been revealed */
+  commentRevealed: boolean
+  /** Pending and Removed votes are excluded from the VoteTally */
+  disputeStatus: VoteDisputeStatus
+  /** Whether the voter's identity was new, so voteWeight was reduced */
+  newVoter: boolean
+}
+
+/** Plaintext of a peer vote's comment, proven against its commentHash */
+export interface CommentReveal {
+  peerVote: PublicKey
+  revealer: PublicKey
+  comment: string
+  revealedAt: bigint
+  bump: number
 }
 
 export interface ContentRating {
@@ -114,6 +154,10 @@ export interface ContentRating {
   timestamp: bigint
   raterReputationSnapshot: number
   bump: number
+  /** Receipt the rating was made against (source of signature, amount and content type) */
+  transactionReceipt: PublicKey
+  /** Mint amountPaid is denominated in; NATIVE_SOL_MINT for lamports */
+  paymentMint: PublicKey
 }
 
 export interface AgentEndorsement {
@@ -126,6 +170,128 @@ export interface AgentEndorsement {
   stakeAmount: bigint
   isActive: boolean
   bump: number
+  isSlashed: boolean
+  slashedAt: bigint
+  /** VoteConfig stake parameters in effect when the endorsement was made */
+  stakeBase: bigint
+  stakePerPoint: bigint
+  /** After this timestamp the endorsement no longer counts unless re-affirmed */
+  expiresAt: bigint
+  /** strength * endorserReputationSnapshot, before age decay */
+  weight: number
+  /** Endorsement or latest re-affirmation; age decay starts here */
+  affirmedAt: bigint
+}
+
+/** Tunable vote registry parameters, managed by the vote authority */
+export interface VoteConfig {
+  endorsementBaseStake: bigint
+  endorsementStakePerPoint: bigint
+  bump: number
+  maxActiveEndorsements: number
+  endorsementValiditySeconds: bigint
+  requireVerifiedReceipts: boolean
+  requireConfirmedReceipts: boolean
+  /** Decimals of the SPL mints receipts may be denominated in */
+  paymentMints: PaymentMintScale[]
+  /** Minimum seconds between two votes by one voter on one agent */
+  pairVoteCooldownSeconds: bigint
+  pairVoteWindowSeconds: bigint
+  maxPairVotesPerWindow: number
+  /** Reputation (0-1000) needed to vote, endorse and rate content */
+  minVoterReputation: number
+  minEndorserReputation: number
+  minRaterReputation: number
+  /** Seconds after a transaction its receipt can be voted on (at most 30 days) */
+  votingWindowSeconds: bigint
+  /** Whether every peer vote weighs 1.0x regardless of the transaction amount */
+  flatVoteWeight: boolean
+  voteWeightTiers: VoteWeightTiers
+  /** Whether votes between identities sharing a Core asset or a delegate are rejected */
+  rejectSharedAssetVotes: boolean
+  rejectSharedDelegateVotes: boolean
+  /** Identity age below which a voter's weight is scaled to newVoterWeightBps (0 = off) */
+  newVoterAgeSeconds: bigint
+  newVoterWeightBps: number
+  /** Age at which endorsement weight has decayed linearly to zero (0 = no decay) */
+  endorsementDecaySeconds: bigint
+}
+
+/**
+ * Transaction amount tiers a peer vote's weight is taken from. Thresholds are
+ * amounts normalized to 9 decimals at which the second and third tier start;
+ * weights are in basis points (10_000 = 1.0x)
+ */
+export interface VoteWeightTiers {
+  thresholds: [bigint, bigint]
+  weightsBps: [number, number, number]
+}
+
+/** Registered SPL payment mint and its decimals */
+export interface PaymentMintScale {
+  mint: PublicKey
+  decimals: number
+}
+
+/** Active endorsements an agent has given and received */
+export interface EndorsementStats {
+  agent: PublicKey
+  endorsementsGiven: number
+  endorsementsReceived: number
+  bump: number
+}
+
+/** Running vote totals for an agent, maintained on-chain by cast_peer_vote */
+export interface VoteTally {
+  agent: PublicKey
+  upvotes: number
+  downvotes: number
+  neutrals: number
+  /** Sum of each quality dimension across all votes; divide by the vote count for averages */
+  qualitySums: {
+    responseQuality: bigint
+    responseSpeed: bigint
+    accuracy: bigint
+    professionalism: bigint
+  }
+  weightSum: bigint
+  lastVoteAt: bigint
+  bump: number
+  /** Votes still counted here whose PeerVote accounts have been closed */
+  closedVotes: number
+  /** Votes excluded while their dispute awaits review */
+  disputedVotes: number
+  /** Votes excluded for good after an upheld dispute */
+  removedVotes: number
+}
+
+/**
+ * Content rating totals for an agent, maintained on-chain by rate_content.
+ * Per-type arrays are indexed by ContentTypeIndex; weights are the rater's
+ * reputation snapshot floored at 1.
+ */
+export interface ContentRatingStats {
+  agent: PublicKey
+  ratingsCount: number[]
+  ratingSum: bigint[]
+  weightedRatingSum: bigint[]
+  weightSum: bigint[]
+  totalRatings: number
+  totalRatingSum: bigint
+  totalWeightedRatingSum: bigint
+  totalWeightSum: bigint
+  lastRatedAt: bigint
+  bump: number
+}
+
+/** One voter's vote history on one agent, used for the pair cooldown and window cap */
+export interface VotePairState {
+  voter: PublicKey
+  votedAgent: PublicKey
+  lastVoteAt: bigint
+  windowStart: bigint
+  votesInWindow: number
+  bump: number
 }
 
 // ============================================================================
@@ -146,24 +312,46 @@ export function getTransactionReceiptPDA(
 
 export function getPeerVotePDA(
   transactionReceipt: PublicKey,
+  voter: PublicKey,
   programId: PublicKey = VOTE_REGISTRY_PROGRAM_ID
 ): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
-    [PEER_VOTE_SEED, transactionReceipt.toBuffer()],
+    [PEER_VOTE_SEED, transactionReceipt.toBuffer(), voter.toBuffer()],
     programId
   )
 }
 
 export function getContentRatingPDA(
-  x402Signature: string,
+  transactionReceipt: PublicKey,
   programId: PublicKey = VOTE_REGISTRY_PROGRAM_ID
 ): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
-    [CONTENT_RATING_SEED, Buffer.from(x402Signature)],
+    [CONTENT_RATING_SEED, transactionReceipt.toBuffer()],
     programId
   )
 }
 
+/**
+ * Derive the content rating PDA straight from the x402 payment, going through
+ * the receipt PDA (keyed by the signature hash, never the raw signature bytes)
+ */
+export function getContentRatingPDAForPayment(
+  payer: PublicKey,
+  recipient: PublicKey,
+  signatureHash: Uint8Array,
+  programId: PublicKey = VOTE_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  const [transactionReceipt] = getTransactionReceiptPDA(payer, recipient, signatureHash, programId)
+  return getContentRatingPDA(transactionReceipt, programId)
+}
+
+export function getCommentRevealPDA(
+  peerVote: PublicKey,
+  programId: PublicKey = VOTE_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([COMMENT_REVEAL_SEED, peerVote.toBuffer()], programId)
+}
+
 export function getEndorsementPDA(
   endorser: PublicKey,
   endorsed: PublicKey,
@@ -175,6 +363,59 @@ export function getEndorsementPDA(
   )
 }
 
+export function getVoteTallyPDA(
+  agent: PublicKey,
+  programId: PublicKey = VOTE_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([VOTE_TALLY_SEED, agent.toBuffer()], programId)
+}
+
+export function getContentStatsPDA(
+  agent: PublicKey,
+  programId: PublicKey = VOTE_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([CONTENT_STATS_SEED, agent.toBuffer()], programId)
+}
+
+export function getVotePairPDA(
+  voter: PublicKey,
+  votedAgent: PublicKey,
+  programId: PublicKey = VOTE_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [VOTE_PAIR_SEED, voter.toBuffer(), votedAgent.toBuffer()],
+    programId
+  )
+}
+
+/** PDA that signs vote_registry's stat-update CPIs into reputation_registry */
+export function getReputationCpiAuthorityPDA(
+  programId: PublicKey = VOTE_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([REPUTATION_CPI_SEED], programId)
+}
+
+/** Vote registry authority PDA (gates slash_endorsement and resolve_vote_dispute) */
+export function getVoteAuthorityPDA(
+  programId: PublicKey = VOTE_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([VOTE_AUTHORITY_SEED], programId)
+}
+
+export function getEndorsementStatsPDA(
+  agent: PublicKey,
+  programId: PublicKey = VOTE_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([ENDORSEMENT_STATS_SEED, agent.toBuffer()], programId)
+}
+
+/** Vote config PDA (endorsement stake parameters) */
+export function getVoteConfigPDA(
+  programId: PublicKey = VOTE_REGISTRY_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([VOTE_CONFIG_SEED], programId)
+}
+
 // Helper to derive identity PDA (for cross-program invocation)
 // Note: Not exported to avoid conflict with identity-registry-client
 function deriveAgentIdentityPDA(
@@ -205,9 +446,128 @@ function deriveReputationPDA(
 
 const DISCRIMINATORS = {
   createTransactionReceipt: Buffer.from([67, 122, 43, 192, 180, 76, 15, 151]),
+  confirmReceipt: Buffer.from([203, 36, 80, 115, 249, 12, 141, 170]),
+  rejectReceipt: Buffer.from([36, 27, 203, 184, 173, 218, 148, 35]),
+  closeReceipt: Buffer.from([126, 254, 244, 203, 124, 164, 134, 89]),
+  closePeerVote: Buffer.from([209, 39, 158, 229, 165, 16, 132, 54]),
+  createVerifiedTransactionReceipt: Buffer.from([249, 130, 119, 172, 98, 239, 0, 4]),
   castPeerVote: Buffer.from([134, 128, 196, 183, 241, 250, 33, 45]),
   rateContent: Buffer.from([237, 161, 216, 135, 145, 73, 46, 59]),
   endorseAgent: Buffer.from([150, 194, 86, 132, 94, 161, 156, 198]),
+  getVoteTally: Buffer.from([114, 86, 118, 47, 95, 95, 32, 161]),
+  getContentStats: Buffer.from([12, 206, 11, 180, 110, 50, 236, 151]),
+  getEndorsementWeight: Buffer.from([7, 50, 19, 230, 38, 250, 83, 2]),
+  amendPeerVote: Buffer.from([96, 110, 79, 135, 62, 241, 29, 220]),
+  revealComment: Buffer.from([255, 47, 94, 106, 228, 82, 245, 91]),
+  disputeVote: Buffer.from([238, 241, 19, 141, 112, 220, 44, 92]),
+  resolveVoteDispute: Buffer.from([59, 150, 189, 117, 71, 82, 16, 8]),
+  revokeEndorsement: Buffer.from([21, 248, 241, 84, 48, 12, 232, 58]),
+  slashEndorsement: Buffer.from([14, 230, 226, 29, 15, 244, 91, 183]),
+  reaffirmEndorsement: Buffer.from([169, 49, 98, 193, 192, 133, 184, 108]),
+  markEndorsementExpired: Buffer.from([230, 128, 218, 33, 102, 160, 40, 166]),
+}
+
+// ============================================================================
+// ERROR CODES (VoteError)
+// ============================================================================
+
+export const VoteErrorCode = {
+  InactiveVoter: 6000,
+  InsufficientReputation: 6001,
+  InvalidQualityScore: 6002,
+  InvalidContentRating: 6003,
+  InvalidX402Signature: 6004,
+  InvalidEndorsementStrength: 6005,
+  InsufficientEndorserReputation: 6006,
+  MaxEndorsementsReached: 6008,
+  SelfEndorsementNotAllowed: 6009,
+  VotedAgentNotActive: 6010,
+  RatedAgentNotActive: 6011,
+  EndorsedAgentNotActive: 6012,
+  UnauthorizedReceiptCreation: 6013,
+  SelfTransactionNotAllowed: 6014,
+  VotingWindowExpired: 6018,
+  VoteAlreadyCast: 6019,
+  VoterNotPartyToTransaction: 6020,
+  VotedAgentNotCounterparty: 6021,
+  InvalidIdentityProgram: 6022,
+  InvalidReputationProgram: 6023,
+  InvalidAccountOwner: 6024,
+  NotOriginalVoter: 6025,
+  AmendmentWindowExpired: 6026,
+  UnauthorizedAuthority: 6027,
+  NotEndorser: 6028,
+  EndorsementNotActive: 6029,
+  EndorsementLocked: 6030,
+  EndorsedAgentNotSlashed: 6031,
+  InvalidEndorsementStakeConfig: 6032,
+  InsufficientBalanceForStake: 6033,
+  InvalidMaxEndorsements: 6034,
+  InvalidEndorsementValidity: 6035,
+  EndorsementExpired: 6036,
+  EndorsementNotExpired: 6037,
+  RaterNotPayer: 6038,
+  ReceiptRecipientMismatch: 6039,
+  MissingPaymentVerification: 6040,
+  InvalidPaymentVerification: 6041,
+  PaymentSignatureMismatch: 6042,
+  UnverifiedReceipt: 6043,
+  CreatorCannotConfirm: 6044,
+  NotReceiptCounterparty: 6045,
+  ReceiptAlreadyConfirmed: 6046,
+  ReceiptDisputed: 6047,
+  ReceiptNotConfirmed: 6048,
+  NativePaymentMint: 6049,
+  InvalidMintDecimals: 6050,
+  TooManyPaymentMints: 6051,
+  VoteCooldownActive: 6052,
+  PairVoteLimitReached: 6053,
+  InvalidPairVoteLimits: 6054,
+  NotReceiptCreator: 6055,
+  ReceiptStillActive: 6056,
+  VerifiedReceiptNotClosable: 6057,
+  VoteNotTallied: 6058,
+  VoteRetentionActive: 6059,
+  NoCommentToReveal: 6060,
+  CommentTooLong: 6061,
+  CommentHashMismatch: 6062,
+  NotVotedAgent: 6063,
+  VoteAlreadyDisputed: 6064,
+  VoteNotDisputed: 6065,
+  VoteDisputed: 6066,
+  InvalidReputationThreshold: 6067,
+  InvalidVotingWindow: 6068,
+  InvalidVoteWeightTiers: 6069,
+  SharedAssetVote: 6070,
+  SharedDelegateVote: 6071,
+  InvalidNewVoterRule: 6072,
+  InvalidEndorsementDecay: 6073,
+  InvalidEndorsementAccount: 6074,
+} as const
+
+export type VoteErrorName = keyof typeof VoteErrorCode
+
+/**
+ * Codes of removed duplicate variants. They are reserved and never raised; each
+ * maps to the variant raised instead (null when the check no longer exists)
+ */
+export const RESERVED_VOTE_ERROR_CODES: Record<number, VoteErrorName | null> = {
+  6007: null, // InsufficientEndorsementStake
+  6015: 'VoteAlreadyCast', // AlreadyVoted
+  6016: 'VoterNotPartyToTransaction', // NotPartyToTransaction
+  6017: 'VotedAgentNotCounterparty', // VotedAgentMismatch
+}
+
+const VoteErrorNames = new Map<number, VoteErrorName>(
+  Object.entries(VoteErrorCode).map(([name, code]) => [code, name as VoteErrorName])
+)
+
+/**
+ * Name of the VoteError variant behind an error code, following reserved codes
+ * to the variant that replaced them
+ */
+export function voteErrorName(code: number): VoteErrorName | undefined {
+  return VoteErrorNames.get(code) ?? RESERVED_VOTE_ERROR_CODES[code] ?? undefined
 }
 
 // ============================================================================
@@ -240,7 +600,8 @@ export class VoteRegistryClient {
     signature: string,
     signatureHash: Uint8Array,
     amount: bigint,
-    contentType: ContentType
+    contentType: ContentType,
+    paymentMint: PublicKey = NATIVE_SOL_MINT
   ): TransactionInstruction {
     const [receipt] = getTransactionReceiptPDA(
       payer,
@@ -249,32 +610,169 @@ export class VoteRegistryClient {
       this.programId
     )
 
-    const signatureBuffer = Buffer.from(signature)
-    const data = Buffer.alloc(8 + 4 + signatureBuffer.length + 32 + 8 + 1)
-    let offset = 0
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: receipt, isSigner: false, isWritable: true },
+        { pubkey: payer, isSigner: false, isWritable: false },
+        { pubkey: recipient, isSigner: false, isWritable: false },
+        { pubkey: creator, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data: encodeReceiptArgs(
+        DISCRIMINATORS.createTransactionReceipt,
+        signature,
+        signatureHash,
+        amount,
+        contentType,
+        paymentMint
+      ),
+    })
+  }
 
-    DISCRIMINATORS.createTransactionReceipt.copy(data, offset)
-    offset += 8
+  /**
+   * Build the instructions for a verified receipt: an Ed25519 verify instruction over the
+   * payment message (see buildPaymentMessage) signed by the payer, followed by the receipt
+   * instruction. Both must be sent in this order in one transaction.
+   */
+  buildCreateVerifiedTransactionReceiptInstructions(
+    creator: PublicKey,
+    payer: PublicKey,
+    recipient: PublicKey,
+    signature: string,
+    signatureHash: Uint8Array,
+    amount: bigint,
+    contentType: ContentType,
+    paymentSignature: Uint8Array,
+    paymentMint: PublicKey = NATIVE_SOL_MINT
+  ): TransactionInstruction[] {
+    const [receipt] = getTransactionReceiptPDA(
+      payer,
+      recipient,
+      signatureHash,
+      this.programId
+    )
 
-    data.writeUInt32LE(signatureBuffer.length, offset)
-    offset += 4
-    signatureBuffer.copy(data, offset)
-    offset += signatureBuffer.length
+    const verifyInstruction = Ed25519Program.createInstructionWithPublicKey({
+      publicKey: payer.toBytes(),
+      message: buildPaymentMessage(payer, recipient, paymentMint, amount, signatureHash),
+      signature: paymentSignature,
+    })
 
-    Buffer.from(signatureHash).copy(data, offset)
-    offset += 32
+    const receiptInstruction = new TransactionInstruction({
+      keys: [
+        { pubkey: receipt, isSigner: false, isWritable: true },
+        { pubkey: payer, isSigner: false, isWritable: false },
+        { pubkey: recipient, isSigner: false, isWritable: false },
+        { pubkey: creator, isSigner: true, isWritable: true },
+        { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data: encodeReceiptArgs(
+        DISCRIMINATORS.createVerifiedTransactionReceipt,
+        signature,
+        signatureHash,
+        amount,
+        contentType,
+        paymentMint
+      ),
+    })
 
-    data.writeBigUInt64LE(amount, offset)
-    offset += 8
+    return [verifyInstruction, receiptInstruction]
+  }
+
+  /**
+   * Build confirm receipt instruction (signed by the party that did not create the receipt)
+   */
+  buildConfirmReceiptInstruction(counterparty: PublicKey, receipt: PublicKey): TransactionInstruction {
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: receipt, isSigner: false, isWritable: true },
+        { pubkey: counterparty, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data: Buffer.from(DISCRIMINATORS.confirmReceipt),
+    })
+  }
 
-    data.writeUInt8(ContentTypeIndex[contentType], offset)
+  /**
+   * Build reject receipt instruction (permanently disputes the receipt; blocks votes on it)
+   */
+  buildRejectReceiptInstruction(counterparty: PublicKey, receipt: PublicKey): TransactionInstruction {
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: receipt, isSigner: false, isWritable: true },
+        { pubkey: counterparty, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data: Buffer.from(DISCRIMINATORS.rejectReceipt),
+    })
+  }
 
+  /**
+   * Build close receipt instruction (creator only, after the voting window; not for verified receipts)
+   */
+  buildCloseReceiptInstruction(creator: PublicKey, receipt: PublicKey): TransactionInstruction {
     return new TransactionInstruction({
       keys: [
         { pubkey: receipt, isSigner: false, isWritable: true },
-        { pubkey: payer, isSigner: false, isWritable: false },
-        { pubkey: recipient, isSigner: false, isWritable: false },
         { pubkey: creator, isSigner: true, isWritable: true },
+      ],
+      programId: this.programId,
+      data: Buffer.from(DISCRIMINATORS.closeReceipt),
+    })
+  }
+
+  /**
+   * Build close peer vote instruction (voter only, after the retention period)
+   */
+  buildClosePeerVoteInstruction(
+    voter: PublicKey,
+    transactionReceipt: PublicKey,
+    votedAgent: PublicKey
+  ): TransactionInstruction {
+    const [peerVote] = getPeerVotePDA(transactionReceipt, voter, this.programId)
+    const [voteTally] = getVoteTallyPDA(votedAgent, this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: peerVote, isSigner: false, isWritable: true },
+        { pubkey: voteTally, isSigner: false, isWritable: true },
+        { pubkey: voter, isSigner: true, isWritable: true },
+      ],
+      programId: this.programId,
+      data: Buffer.from(DISCRIMINATORS.closePeerVote),
+    })
+  }
+
+  /**
+   * Build reveal comment instruction (anyone holding the comment and salt)
+   */
+  buildRevealCommentInstruction(
+    revealer: PublicKey,
+    peerVote: PublicKey,
+    comment: string,
+    salt: Uint8Array
+  ): TransactionInstruction {
+    const [commentReveal] = getCommentRevealPDA(peerVote, this.programId)
+    const commentBuffer = Buffer.from(comment, 'utf8')
+
+    const data = Buffer.alloc(8 + 4 + commentBuffer.length + 32)
+    let offset = 0
+    DISCRIMINATORS.revealComment.copy(data, offset)
+    offset += 8
+    data.writeUInt32LE(commentBuffer.length, offset)
+    offset += 4
+    commentBuffer.copy(data, offset)
+    offset += commentBuffer.length
+    Buffer.from(salt).copy(data, offset)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: peerVote, isSigner: false, isWritable: true },
+        { pubkey: commentReveal, isSigner: false, isWritable: true },
+        { pubkey: revealer, isSigner: true, isWritable: true },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
       ],
       programId: this.programId,
@@ -282,6 +780,51 @@ export class VoteRegistryClient {
     })
   }
 
+  /**
+   * Build dispute vote instruction (voted agent only; excludes the vote from the tally)
+   */
+  buildDisputeVoteInstruction(votedAgent: PublicKey, peerVote: PublicKey): TransactionInstruction {
+    const [voteTally] = getVoteTallyPDA(votedAgent, this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: peerVote, isSigner: false, isWritable: true },
+        { pubkey: voteTally, isSigner: false, isWritable: true },
+        { pubkey: votedAgent, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data: Buffer.from(DISCRIMINATORS.disputeVote),
+    })
+  }
+
+  /**
+   * Build resolve vote dispute instruction (authority only; removes or reinstates the vote)
+   */
+  buildResolveVoteDisputeInstruction(
+    authority: PublicKey,
+    peerVote: PublicKey,
+    votedAgent: PublicKey,
+    removeVote: boolean
+  ): TransactionInstruction {
+    const [voteTally] = getVoteTallyPDA(votedAgent, this.programId)
+    const [authorityAccount] = getVoteAuthorityPDA(this.programId)
+
+    const data = Buffer.alloc(8 + 1)
+    DISCRIMINATORS.resolveVoteDispute.copy(data, 0)
+    data.writeUInt8(removeVote ? 1 : 0, 8)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: peerVote, isSigner: false, isWritable: true },
+        { pubkey: voteTally, isSigner: false, isWritable: true },
+        { pubkey: authorityAccount, isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
   /**
    * Build cast peer vote instruction
    */
@@ -293,10 +836,14 @@ export class VoteRegistryClient {
     qualityScores: QualityScores,
     commentHash: Uint8Array
   ): TransactionInstruction {
-    const [peerVote] = getPeerVotePDA(transactionReceipt, this.programId)
+    const [peerVote] = getPeerVotePDA(transactionReceipt, voter, this.programId)
     const [voterIdentity] = deriveAgentIdentityPDA(voter)
     const [voterReputation] = deriveReputationPDA(voter)
     const [votedAgentIdentity] = deriveAgentIdentityPDA(votedAgent)
+    const [votedAgentReputation] = deriveReputationPDA(votedAgent)
+    const [voteTally] = getVoteTallyPDA(votedAgent, this.programId)
+    const [votePair] = getVotePairPDA(voter, votedAgent, this.programId)
+    const [reputationCpiAuthority] = getReputationCpiAuthorityPDA(this.programId)
 
     const data = Buffer.alloc(8 + 32 + 1 + 4 + 32)
     let offset = 0
@@ -324,14 +871,19 @@ export class VoteRegistryClient {
     return new TransactionInstruction({
       keys: [
         { pubkey: peerVote, isSigner: false, isWritable: true },
+        { pubkey: voteTally, isSigner: false, isWritable: true },
+        { pubkey: votePair, isSigner: false, isWritable: true },
         { pubkey: transactionReceipt, isSigner: false, isWritable: true },
         { pubkey: voterIdentity, isSigner: false, isWritable: false },
         { pubkey: voterReputation, isSigner: false, isWritable: false },
         { pubkey: votedAgentIdentity, isSigner: false, isWritable: false },
+        { pubkey: votedAgentReputation, isSigner: false, isWritable: true },
+        { pubkey: reputationCpiAuthority, isSigner: false, isWritable: false },
         { pubkey: voter, isSigner: true, isWritable: true },
         { pubkey: IDENTITY_REGISTRY_PROGRAM_ID, isSigner: false, isWritable: false },
         { pubkey: REPUTATION_REGISTRY_PROGRAM_ID, isSigner: false, isWritable: false },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: getVoteConfigPDA(this.programId)[0], isSigner: false, isWritable: false },
       ],
       programId: this.programId,
       data,
@@ -339,52 +891,88 @@ export class VoteRegistryClient {
   }
 
   /**
-   * Build rate content instruction
+   * Build amend peer vote instruction (within 48 hours of the vote)
    */
-  buildRateContentInstruction(
-    rater: PublicKey,
-    ratedAgent: PublicKey,
-    x402Signature: string,
-    qualityRating: number,
-    contentType: ContentType,
-    amountPaid: bigint
+  buildAmendPeerVoteInstruction(
+    voter: PublicKey,
+    votedAgent: PublicKey,
+    transactionReceipt: PublicKey,
+    voteType: VoteType,
+    qualityScores: QualityScores
   ): TransactionInstruction {
-    const [contentRating] = getContentRatingPDA(x402Signature, this.programId)
-    const [raterIdentity] = deriveAgentIdentityPDA(rater)
-    const [raterReputation] = deriveReputationPDA(rater)
-    const [ratedAgentIdentity] = deriveAgentIdentityPDA(ratedAgent)
+    const [peerVote] = getPeerVotePDA(transactionReceipt, voter, this.programId)
+    const [voteTally] = getVoteTallyPDA(votedAgent, this.programId)
+    const [votedAgentReputation] = deriveReputationPDA(votedAgent)
+    const [reputationCpiAuthority] = getReputationCpiAuthorityPDA(this.programId)
 
-    const signatureBuffer = Buffer.from(x402Signature)
-    const data = Buffer.alloc(8 + 4 + signatureBuffer.length + 1 + 1 + 8)
+    const data = Buffer.alloc(8 + 1 + 4)
     let offset = 0
 
-    DISCRIMINATORS.rateContent.copy(data, offset)
+    DISCRIMINATORS.amendPeerVote.copy(data, offset)
     offset += 8
 
-    data.writeUInt32LE(signatureBuffer.length, offset)
-    offset += 4
-    signatureBuffer.copy(data, offset)
-    offset += signatureBuffer.length
-
-    data.writeUInt8(qualityRating, offset)
+    data.writeUInt8(VoteTypeIndex[voteType], offset)
     offset += 1
 
-    data.writeUInt8(ContentTypeIndex[contentType], offset)
+    data.writeUInt8(qualityScores.responseQuality, offset)
+    offset += 1
+    data.writeUInt8(qualityScores.responseSpeed, offset)
     offset += 1
+    data.writeUInt8(qualityScores.accuracy, offset)
+    offset += 1
+    data.writeUInt8(qualityScores.professionalism, offset)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: peerVote, isSigner: false, isWritable: true },
+        { pubkey: voteTally, isSigner: false, isWritable: true },
+        { pubkey: votedAgentReputation, isSigner: false, isWritable: true },
+        { pubkey: reputationCpiAuthority, isSigner: false, isWritable: false },
+        { pubkey: voter, isSigner: true, isWritable: false },
+        { pubkey: REPUTATION_REGISTRY_PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build rate content instruction (rater must be the receipt's payer, ratedAgent its recipient)
+   */
+  buildRateContentInstruction(
+    rater: PublicKey,
+    ratedAgent: PublicKey,
+    transactionReceipt: PublicKey,
+    qualityRating: number
+  ): TransactionInstruction {
+    const [contentRating] = getContentRatingPDA(transactionReceipt, this.programId)
+    const [contentStats] = getContentStatsPDA(ratedAgent, this.programId)
+    const [raterIdentity] = deriveAgentIdentityPDA(rater)
+    const [raterReputation] = deriveReputationPDA(rater)
+    const [ratedAgentIdentity] = deriveAgentIdentityPDA(ratedAgent)
+    const [ratedAgentReputation] = deriveReputationPDA(ratedAgent)
+    const [reputationCpiAuthority] = getReputationCpiAuthorityPDA(this.programId)
 
-    data.writeBigUInt64LE(amountPaid, offset)
+    const data = Buffer.alloc(8 + 1)
+    DISCRIMINATORS.rateContent.copy(data, 0)
+    data.writeUInt8(qualityRating, 8)
 
     return new TransactionInstruction({
       keys: [
         { pubkey: contentRating, isSigner: false, isWritable: true },
+        { pubkey: contentStats, isSigner: false, isWritable: true },
+        { pubkey: transactionReceipt, isSigner: false, isWritable: false },
         { pubkey: raterIdentity, isSigner: false, isWritable: false },
         { pubkey: raterReputation, isSigner: false, isWritable: false },
         { pubkey: ratedAgentIdentity, isSigner: false, isWritable: false },
         { pubkey: ratedAgent, isSigner: false, isWritable: false },
+        { pubkey: ratedAgentReputation, isSigner: false, isWritable: true },
+        { pubkey: reputationCpiAuthority, isSigner: false, isWritable: false },
         { pubkey: rater, isSigner: true, isWritable: true },
         { pubkey: IDENTITY_REGISTRY_PROGRAM_ID, isSigner: false, isWritable: false },
         { pubkey: REPUTATION_REGISTRY_PROGRAM_ID, isSigner: false, isWritable: false },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: getVoteConfigPDA(this.programId)[0], isSigner: false, isWritable: false },
       ],
       programId: this.programId,
       data,
@@ -393,14 +981,19 @@ export class VoteRegistryClient {
 
   /**
    * Build endorse agent instruction
+   * With `withVoteConfig` the stake scales with strength per the on-chain VoteConfig;
+   * otherwise a flat 0.01 SOL is locked
    */
   buildEndorseAgentInstruction(
     endorser: PublicKey,
     endorsedAgent: PublicKey,
     strength: number,
-    category: EndorsementCategory
+    category: EndorsementCategory,
+    withVoteConfig = false
   ): TransactionInstruction {
     const [endorsement] = getEndorsementPDA(endorser, endorsedAgent, this.programId)
+    const [endorserStats] = getEndorsementStatsPDA(endorser, this.programId)
+    const [endorsedStats] = getEndorsementStatsPDA(endorsedAgent, this.programId)
     const [endorserIdentity] = deriveAgentIdentityPDA(endorser)
     const [endorserReputation] = deriveReputationPDA(endorser)
     const [endorsedAgentIdentity] = deriveAgentIdentityPDA(endorsedAgent)
@@ -422,6 +1015,8 @@ export class VoteRegistryClient {
     return new TransactionInstruction({
       keys: [
         { pubkey: endorsement, isSigner: false, isWritable: true },
+        { pubkey: endorserStats, isSigner: false, isWritable: true },
+        { pubkey: endorsedStats, isSigner: false, isWritable: true },
         { pubkey: endorserIdentity, isSigner: false, isWritable: false },
         { pubkey: endorserReputation, isSigner: false, isWritable: false },
         { pubkey: endorsedAgentIdentity, isSigner: false, isWritable: false },
@@ -429,12 +1024,137 @@ export class VoteRegistryClient {
         { pubkey: IDENTITY_REGISTRY_PROGRAM_ID, isSigner: false, isWritable: false },
         { pubkey: REPUTATION_REGISTRY_PROGRAM_ID, isSigner: false, isWritable: false },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ...(withVoteConfig
+          ? [{ pubkey: getVoteConfigPDA(this.programId)[0], isSigner: false, isWritable: false }]
+          : []),
       ],
       programId: this.programId,
       data,
     })
   }
 
+  /**
+   * Build revoke endorsement instruction (closes the endorsement and refunds its stake)
+   */
+  buildRevokeEndorsementInstruction(endorser: PublicKey, endorsedAgent: PublicKey): TransactionInstruction {
+    const [endorsement] = getEndorsementPDA(endorser, endorsedAgent, this.programId)
+    const [endorserStats] = getEndorsementStatsPDA(endorser, this.programId)
+    const [endorsedStats] = getEndorsementStatsPDA(endorsedAgent, this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: endorsement, isSigner: false, isWritable: true },
+        { pubkey: endorserStats, isSigner: false, isWritable: true },
+        { pubkey: endorsedStats, isSigner: false, isWritable: true },
+        { pubkey: endorser, isSigner: true, isWritable: true },
+      ],
+      programId: this.programId,
+      data: Buffer.from(DISCRIMINATORS.revokeEndorsement),
+    })
+  }
+
+  /**
+   * Build slash endorsement instruction (authority only; endorsed agent must be slashed)
+   */
+  buildSlashEndorsementInstruction(
+    authority: PublicKey,
+    endorser: PublicKey,
+    endorsedAgent: PublicKey
+  ): TransactionInstruction {
+    const [endorsement] = getEndorsementPDA(endorser, endorsedAgent, this.programId)
+    const [endorserStats] = getEndorsementStatsPDA(endorser, this.programId)
+    const [endorsedStats] = getEndorsementStatsPDA(endorsedAgent, this.programId)
+    const [endorsedAgentIdentity] = deriveAgentIdentityPDA(endorsedAgent)
+    const [authorityAccount] = getVoteAuthorityPDA(this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: endorsement, isSigner: false, isWritable: true },
+        { pubkey: endorserStats, isSigner: false, isWritable: true },
+        { pubkey: endorsedStats, isSigner: false, isWritable: true },
+        { pubkey: endorsedAgentIdentity, isSigner: false, isWritable: false },
+        { pubkey: authorityAccount, isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: true },
+      ],
+      programId: this.programId,
+      data: Buffer.from(DISCRIMINATORS.slashEndorsement),
+    })
+  }
+
+  /**
+   * Build reaffirm endorsement instruction (extends expiry, optionally locking extra stake)
+   */
+  buildReaffirmEndorsementInstruction(
+    endorser: PublicKey,
+    endorsedAgent: PublicKey,
+    extraStake: bigint = 0n,
+    withVoteConfig = false
+  ): TransactionInstruction {
+    const [endorsement] = getEndorsementPDA(endorser, endorsedAgent, this.programId)
+
+    const data = Buffer.alloc(8 + 8)
+    DISCRIMINATORS.reaffirmEndorsement.copy(data, 0)
+    data.writeBigUInt64LE(extraStake, 8)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: endorsement, isSigner: false, isWritable: true },
+        { pubkey: endorser, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ...(withVoteConfig
+          ? [{ pubkey: getVoteConfigPDA(this.programId)[0], isSigner: false, isWritable: false }]
+          : []),
+      ],
+      programId: this.programId,
+      data,
+    })
+  }
+
+  /**
+   * Build mark endorsement expired instruction (anyone can call; stake goes back to the endorser)
+   */
+  buildMarkEndorsementExpiredInstruction(
+    caller: PublicKey,
+    endorser: PublicKey,
+    endorsedAgent: PublicKey
+  ): TransactionInstruction {
+    const [endorsement] = getEndorsementPDA(endorser, endorsedAgent, this.programId)
+    const [endorserStats] = getEndorsementStatsPDA(endorser, this.programId)
+    const [endorsedStats] = getEndorsementStatsPDA(endorsedAgent, this.programId)
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: endorsement, isSigner: false, isWritable: true },
+        { pubkey: endorserStats, isSigner: false, isWritable: true },
+        { pubkey: endorsedStats, isSigner: false, isWritable: true },
+        { pubkey: endorser, isSigner: false, isWritable: true },
+        { pubkey: caller, isSigner: true, isWritable: false },
+      ],
+      programId: this.programId,
+      data: Buffer.from(DISCRIMINATORS.markEndorsementExpired),
+    })
+  }
+
+  /**
+   * Build get endorsement weight instruction; simulate it and read the u64 return data.
+   * Pass the endorsers whose endorsements of the agent should be counted
+   */
+  buildGetEndorsementWeightInstruction(agent: PublicKey, endorsers: PublicKey[]): TransactionInstruction {
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: agent, isSigner: false, isWritable: false },
+        { pubkey: getVoteConfigPDA(this.programId)[0], isSigner: false, isWritable: false },
+        ...endorsers.map((endorser) => ({
+          pubkey: getEndorsementPDA(endorser, agent, this.programId)[0],
+          isSigner: false,
+          isWritable: false,
+        })),
+      ],
+      programId: this.programId,
+      data: Buffer.from(DISCRIMINATORS.getEndorsementWeight),
+    })
+  }
+
   // ==========================================================================
   // ACCOUNT FETCHERS
   // ==========================================================================
@@ -459,10 +1179,10 @@ export class VoteRegistryClient {
   }
 
   /**
-   * Fetch peer vote
+   * Fetch the peer vote cast by `voter` using a transaction receipt
    */
-  async getPeerVote(transactionReceipt: PublicKey): Promise<PeerVote | null> {
-    const [pda] = getPeerVotePDA(transactionReceipt, this.programId)
+  async getPeerVote(transactionReceipt: PublicKey, voter: PublicKey): Promise<PeerVote | null> {
+    const [pda] = getPeerVotePDA(transactionReceipt, voter, this.programId)
     try {
       const accountInfo = await this.connection.getAccountInfo(pda)
       if (!accountInfo?.data) return null
@@ -473,6 +1193,21 @@ export class VoteRegistryClient {
     }
   }
 
+  /**
+   * Fetch the revealed comment of a peer vote (null if not revealed)
+   */
+  async getCommentReveal(peerVote: PublicKey): Promise<CommentReveal | null> {
+    const [pda] = getCommentRevealPDA(peerVote, this.programId)
+    try {
+      const accountInfo = await this.connection.getAccountInfo(pda)
+      if (!accountInfo?.data) return null
+      return parseCommentReveal(accountInfo.data)
+    } catch (error) {
+      console.error('Failed to fetch comment reveal:', error)
+      return null
+    }
+  }
+
   /**
    * Fetch all votes for an agent
    */
@@ -502,6 +1237,81 @@ export class VoteRegistryClient {
     }
   }
 
+  /**
+   * Fetch an agent's aggregated vote totals
+   */
+  async getVoteTally(agentAddress: PublicKey): Promise<VoteTally | null> {
+    const [pda] = getVoteTallyPDA(agentAddress, this.programId)
+    try {
+      const accountInfo = await this.connection.getAccountInfo(pda)
+      if (!accountInfo?.data) return null
+      return parseVoteTally(accountInfo.data)
+    } catch (error) {
+      console.error('Failed to fetch vote tally:', error)
+      return null
+    }
+  }
+
+  /**
+   * Fetch an agent's aggregated content rating stats (null before its first rating)
+   */
+  async getContentStats(agentAddress: PublicKey): Promise<ContentRatingStats | null> {
+    const [pda] = getContentStatsPDA(agentAddress, this.programId)
+    try {
+      const accountInfo = await this.connection.getAccountInfo(pda)
+      if (!accountInfo?.data) return null
+      return parseContentRatingStats(accountInfo.data)
+    } catch (error) {
+      console.error('Failed to fetch content stats:', error)
+      return null
+    }
+  }
+
+  /**
+   * Fetch a voter's vote history on an agent (null before their first vote on it)
+   */
+  async getVotePairState(voter: PublicKey, votedAgent: PublicKey): Promise<VotePairState | null> {
+    const [pda] = getVotePairPDA(voter, votedAgent, this.programId)
+    try {
+      const accountInfo = await this.connection.getAccountInfo(pda)
+      if (!accountInfo?.data) return null
+      return parseVotePairState(accountInfo.data)
+    } catch (error) {
+      console.error('Failed to fetch vote pair state:', error)
+      return null
+    }
+  }
+
+  /**
+   * Fetch the vote config
+   */
+  async getVoteConfig(): Promise<VoteConfig | null> {
+    const [pda] = getVoteConfigPDA(this.programId)
+    try {
+      const accountInfo = await this.connection.getAccountInfo(pda)
+      if (!accountInfo?.data) return null
+      return parseVoteConfig(accountInfo.data)
+    } catch (error) {
+      console.error('Failed to fetch vote config:', error)
+      return null
+    }
+  }
+
+  /**
+   * Fetch an agent's endorsement counters
+   */
+  async getEndorsementStats(agent: PublicKey): Promise<EndorsementStats | null> {
+    const [pda] = getEndorsementStatsPDA(agent, this.programId)
+    try {
+      const accountInfo = await this.connection.getAccountInfo(pda)
+      if (!accountInfo?.data) return null
+      return parseEndorsementStats(accountInfo.data)
+    } catch (error) {
+      console.error('Failed to fetch endorsement stats:', error)
+      return null
+    }
+  }
+
   /**
    * Fetch endorsement
    */
@@ -521,7 +1331,7 @@ export class VoteRegistryClient {
   }
 
   /**
-   * Fetch all endorsements for an agent
+   * Fetch all current (active and unexpired) endorsements for an agent
    */
   async getEndorsementsForAgent(agentAddress: PublicKey): Promise<AgentEndorsement[]> {
     try {
@@ -537,10 +1347,11 @@ export class VoteRegistryClient {
         ],
       })
 
+      const nowSeconds = BigInt(Math.floor(Date.now() / 1000))
       const endorsements: AgentEndorsement[] = []
       for (const { account } of accounts) {
         const endorsement = parseAgentEndorsement(account.data)
-        if (endorsement && endorsement.isActive) endorsements.push(endorsement)
+        if (endorsement && isEndorsementCurrent(endorsement, nowSeconds)) endorsements.push(endorsement)
       }
       return endorsements
     } catch (error) {
@@ -550,7 +1361,7 @@ export class VoteRegistryClient {
   }
 
   /**
-   * Get vote statistics for an agent
+   * Get vote statistics for an agent from its VoteTally (no votes yet = all zeros)
    */
   async getVoteStatistics(agentAddress: PublicKey): Promise<{
     totalVotes: number
@@ -560,34 +1371,22 @@ export class VoteRegistryClient {
     averageQuality: number
     upvoteRatio: number
   }> {
-    const votes = await this.getVotesForAgent(agentAddress)
-
-    let upvotes = 0
-    let downvotes = 0
-    let neutral = 0
-    let totalQuality = 0
-
-    for (const vote of votes) {
-      if (vote.voteType === 'Upvote') upvotes++
-      else if (vote.voteType === 'Downvote') downvotes++
-      else neutral++
-
-      const avgScore =
-        (vote.qualityScores.responseQuality +
-          vote.qualityScores.responseSpeed +
-          vote.qualityScores.accuracy +
-          vote.qualityScores.professionalism) /
-        4
-      totalQuality += avgScore
+    const tally = await this.getVoteTally(agentAddress)
+    if (!tally) {
+      return { totalVotes: 0, upvotes: 0, downvotes: 0, neutral: 0, averageQuality: 0, upvoteRatio: 0 }
     }
 
+    const totalVotes = tally.upvotes + tally.downvotes + tally.neutrals
+    const { responseQuality, responseSpeed, accuracy, professionalism } = tally.qualitySums
+    const qualitySum = Number(responseQuality + responseSpeed + accuracy + professionalism)
+
     return {
-      totalVotes: votes.length,
-      upvotes,
-      downvotes,
-      neutral,
-      averageQuality: votes.length > 0 ? totalQuality / votes.length : 0,
-      upvoteRatio: votes.length > 0 ? upvotes / votes.length : 0,
+      totalVotes,
+      upvotes: tally.upvotes,
+      downvotes: tally.downvotes,
+      neutral: tally.neutrals,
+      averageQuality: totalVotes > 0 ? qualitySum / 4 / totalVotes : 0,
+      upvoteRatio: totalVotes > 0 ? tally.upvotes / totalVotes : 0,
     }
   }
 }
@@ -596,8 +1395,8 @@ export class VoteRegistryClient {
 // ACCOUNT SIZES
 // ============================================================================
 
-const PEER_VOTE_SIZE = 8 + 32 + 32 + 1 + 4 + 32 + 8 + 2 + 32 + 2 + 1 // ~160 bytes
-const ENDORSEMENT_SIZE = 8 + 32 + 32 + 1 + 1 + 8 + 2 + 8 + 1 + 1 // ~96 bytes
+const PEER_VOTE_SIZE = 8 + 32 + 32 + 1 + 4 + 32 + 8 + 2 + 32 + 2 + 1 + 2 + 8 + 1 + 1 + 1 + 1 // ~174 bytes
+const ENDORSEMENT_SIZE = 8 + 32 + 32 + 1 + 1 + 8 + 2 + 8 + 1 + 1 + 1 + 8 + 8 + 8 + 8 + 4 + 8 // ~139 bytes
 
 // ============================================================================
 // ACCOUNT PARSERS
@@ -614,6 +1413,8 @@ const ContentTypes: ContentType[] = [
 
 const VoteTypes: VoteType[] = ['Upvote', 'Downvote', 'Neutral']
 
+const VoteDisputeStatuses: VoteDisputeStatus[] = ['None', 'Pending', 'Removed', 'Reinstated']
+
 const EndorsementCategories: EndorsementCategory[] = [
   'Technical',
   'Reliability',
@@ -646,10 +1447,28 @@ function parseTransactionReceipt(data: Buffer): TransactionReceipt | null {
     const contentTypeIndex = data.readUInt8(offset)
     offset += 1
 
-    const voteCast = data.readUInt8(offset) === 1
+    const payerVoted = data.readUInt8(offset) === 1
+    offset += 1
+
+    const recipientVoted = data.readUInt8(offset) === 1
     offset += 1
 
     const bump = data.readUInt8(offset)
+    offset += 1
+
+    const verified = data.readUInt8(offset) === 1
+    offset += 1
+
+    const creator = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const counterpartyConfirmed = data.readUInt8(offset) === 1
+    offset += 1
+
+    const isDisputed = data.readUInt8(offset) === 1
+    offset += 1
+
+    const paymentMint = new PublicKey(data.subarray(offset, offset + 32))
 
     return {
       signature,
@@ -658,8 +1477,14 @@ function parseTransactionReceipt(data: Buffer): TransactionReceipt | null {
       amount,
       timestamp,
       contentType: ContentTypes[contentTypeIndex] || 'Other',
-      voteCast,
+      payerVoted,
+      recipientVoted,
       bump,
+      verified,
+      creator,
+      counterpartyConfirmed,
+      isDisputed,
+      paymentMint,
     }
   } catch {
     return null
@@ -703,6 +1528,24 @@ function parsePeerVote(data: Buffer): PeerVote | null {
     offset += 2
 
     const bump = data.readUInt8(offset)
+    offset += 1
+
+    const amendedCount = data.readUInt16LE(offset)
+    offset += 2
+
+    const amendedAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const tallied = data.readUInt8(offset) === 1
+    offset += 1
+
+    const commentRevealed = data.readUInt8(offset) === 1
+    offset += 1
+
+    const disputeStatus = VoteDisputeStatuses[data.readUInt8(offset)] || 'None'
+    offset += 1
+
+    const newVoter = data.readUInt8(offset) === 1
 
     return {
       voter,
@@ -715,12 +1558,184 @@ function parsePeerVote(data: Buffer): PeerVote | null {
       transactionReceipt,
       voteWeight,
       bump,
+      amendedCount,
+      amendedAt,
+      tallied,
+      commentRevealed,
+      disputeStatus,
+      newVoter,
+    }
+  } catch {
+    return null
+  }
+}
+
+function parseCommentReveal(data: Buffer): CommentReveal | null {
+  try {
+    let offset = 8 // Skip discriminator
+
+    const peerVote = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const revealer = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const commentLen = data.readUInt32LE(offset)
+    offset += 4
+    const comment = data.subarray(offset, offset + commentLen).toString('utf8')
+    offset += commentLen
+
+    const revealedAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const bump = data.readUInt8(offset)
+
+    return { peerVote, revealer, comment, revealedAt, bump }
+  } catch {
+    return null
+  }
+}
+
+function parseVoteTally(data: Buffer): VoteTally | null {
+  try {
+    let offset = 8 // Skip discriminator
+
+    const agent = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const upvotes = data.readUInt32LE(offset)
+    offset += 4
+
+    const downvotes = data.readUInt32LE(offset)
+    offset += 4
+
+    const neutrals = data.readUInt32LE(offset)
+    offset += 4
+
+    const qualitySums = {
+      responseQuality: data.readBigUInt64LE(offset),
+      responseSpeed: data.readBigUInt64LE(offset + 8),
+      accuracy: data.readBigUInt64LE(offset + 16),
+      professionalism: data.readBigUInt64LE(offset + 24),
+    }
+    offset += 32
+
+    const weightSum = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const lastVoteAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const bump = data.readUInt8(offset)
+    offset += 1
+
+    const closedVotes = data.readUInt32LE(offset)
+    offset += 4
+
+    const disputedVotes = data.readUInt32LE(offset)
+    offset += 4
+
+    const removedVotes = data.readUInt32LE(offset)
+
+    return {
+      agent,
+      upvotes,
+      downvotes,
+      neutrals,
+      qualitySums,
+      weightSum,
+      lastVoteAt,
+      bump,
+      closedVotes,
+      disputedVotes,
+      removedVotes,
     }
   } catch {
     return null
   }
 }
 
+function parseContentRatingStats(data: Buffer): ContentRatingStats | null {
+  try {
+    let offset = 8 // Skip discriminator
+
+    const agent = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const typeCount = ContentTypes.length
+    const ratingsCount: number[] = []
+    for (let i = 0; i < typeCount; i++, offset += 4) ratingsCount.push(data.readUInt32LE(offset))
+    const readU64s = (): bigint[] => {
+      const values: bigint[] = []
+      for (let i = 0; i < typeCount; i++, offset += 8) values.push(data.readBigUInt64LE(offset))
+      return values
+    }
+    const ratingSum = readU64s()
+    const weightedRatingSum = readU64s()
+    const weightSum = readU64s()
+
+    const totalRatings = data.readUInt32LE(offset)
+    offset += 4
+
+    const totalRatingSum = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const totalWeightedRatingSum = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const totalWeightSum = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const lastRatedAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const bump = data.readUInt8(offset)
+
+    return {
+      agent,
+      ratingsCount,
+      ratingSum,
+      weightedRatingSum,
+      weightSum,
+      totalRatings,
+      totalRatingSum,
+      totalWeightedRatingSum,
+      totalWeightSum,
+      lastRatedAt,
+      bump,
+    }
+  } catch {
+    return null
+  }
+}
+
+function parseVotePairState(data: Buffer): VotePairState | null {
+  try {
+    let offset = 8 // Skip discriminator
+
+    const voter = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const votedAgent = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const lastVoteAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const windowStart = data.readBigInt64LE(offset)
+    offset += 8
+
+    const votesInWindow = data.readUInt32LE(offset)
+    offset += 4
+
+    const bump = data.readUInt8(offset)
+
+    return { voter, votedAgent, lastVoteAt, windowStart, votesInWindow, bump }
+  } catch {
+    return null
+  }
+}
+
 function parseAgentEndorsement(data: Buffer): AgentEndorsement | null {
   try {
     let offset = 8
@@ -750,6 +1765,27 @@ function parseAgentEndorsement(data: Buffer): AgentEndorsement | null {
     offset += 1
 
     const bump = data.readUInt8(offset)
+    offset += 1
+
+    const isSlashed = data.readUInt8(offset) === 1
+    offset += 1
+
+    const slashedAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const stakeBase = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const stakePerPoint = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const expiresAt = data.readBigInt64LE(offset)
+    offset += 8
+
+    const weight = data.readUInt32LE(offset)
+    offset += 4
+
+    const affirmedAt = data.readBigInt64LE(offset)
 
     return {
       endorser,
@@ -761,16 +1797,220 @@ function parseAgentEndorsement(data: Buffer): AgentEndorsement | null {
       stakeAmount,
       isActive,
       bump,
+      isSlashed,
+      slashedAt,
+      stakeBase,
+      stakePerPoint,
+      expiresAt,
+      weight,
+      affirmedAt,
     }
   } catch {
     return null
   }
 }
 
+function parseVoteConfig(data: Buffer): VoteConfig | null {
+  try {
+    let offset = 8 // Skip discriminator
+
+    const endorsementBaseStake = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const endorsementStakePerPoint = data.readBigUInt64LE(offset)
+    offset += 8
+
+    const bump = data.readUInt8(offset)
+    offset += 1
+
+    const maxActiveEndorsements = data.readUInt32LE(offset)
+    offset += 4
+
+    const endorsementValiditySeconds = data.readBigInt64LE(offset)
+    offset += 8
+
+    const requireVerifiedReceipts = data.readUInt8(offset) === 1
+    offset += 1
+
+    const requireConfirmedReceipts = data.readUInt8(offset) === 1
+    offset += 1
+
+    const paymentMintCount = data.readUInt32LE(offset)
+    offset += 4
+    const paymentMints: PaymentMintScale[] = []
+    for (let i = 0; i < paymentMintCount; i++) {
+      paymentMints.push({
+        mint: new PublicKey(data.subarray(offset, offset + 32)),
+        decimals: data.readUInt8(offset + 32),
+      })
+      offset += 33
+    }
+
+    const pairVoteCooldownSeconds = data.readBigInt64LE(offset)
+    offset += 8
+
+    const pairVoteWindowSeconds = data.readBigInt64LE(offset)
+    offset += 8
+
+    const maxPairVotesPerWindow = data.readUInt32LE(offset)
+    offset += 4
+
+    const minVoterReputation = data.readUInt16LE(offset)
+    offset += 2
+
+    const minEndorserReputation = data.readUInt16LE(offset)
+    offset += 2
+
+    const minRaterReputation = data.readUInt16LE(offset)
+    offset += 2
+
+    const votingWindowSeconds = data.readBigInt64LE(offset)
+    offset += 8
+
+    const flatVoteWeight = data.readUInt8(offset) === 1
+    offset += 1
+
+    const thresholds: [bigint, bigint] = [data.readBigUInt64LE(offset), data.readBigUInt64LE(offset + 8)]
+    offset += 16
+
+    const weightsBps: [number, number, number] = [
+      data.readUInt16LE(offset),
+      data.readUInt16LE(offset + 2),
+      data.readUInt16LE(offset + 4),
+    ]
+    offset += 6
+
+    const rejectSharedAssetVotes = data.readUInt8(offset) === 1
+    offset += 1
+
+    const rejectSharedDelegateVotes = data.readUInt8(offset) === 1
+    offset += 1
+
+    const newVoterAgeSeconds = data.readBigInt64LE(offset)
+    offset += 8
+
+    const newVoterWeightBps = data.readUInt16LE(offset)
+    offset += 2
+
+    const endorsementDecaySeconds = data.readBigInt64LE(offset)
+
+    return {
+      endorsementBaseStake,
+      endorsementStakePerPoint,
+      bump,
+      maxActiveEndorsements,
+      endorsementValiditySeconds,
+      requireVerifiedReceipts,
+      requireConfirmedReceipts,
+      paymentMints,
+      pairVoteCooldownSeconds,
+      pairVoteWindowSeconds,
+      maxPairVotesPerWindow,
+      minVoterReputation,
+      minEndorserReputation,
+      minRaterReputation,
+      votingWindowSeconds,
+      flatVoteWeight,
+      voteWeightTiers: { thresholds, weightsBps },
+      rejectSharedAssetVotes,
+      rejectSharedDelegateVotes,
+      newVoterAgeSeconds,
+      newVoterWeightBps,
+      endorsementDecaySeconds,
+    }
+  } catch {
+    return null
+  }
+}
+
+function parseEndorsementStats(data: Buffer): EndorsementStats | null {
+  try {
+    let offset = 8 // Skip discriminator
+
+    const agent = new PublicKey(data.subarray(offset, offset + 32))
+    offset += 32
+
+    const endorsementsGiven = data.readUInt32LE(offset)
+    offset += 4
+
+    const endorsementsReceived = data.readUInt32LE(offset)
+    offset += 4
+
+    const bump = data.readUInt8(offset)
+
+    return { agent, endorsementsGiven, endorsementsReceived, bump }
+  } catch {
+    return null
+  }
+}
+
 // ============================================================================
 // UTILITY FUNCTIONS
 // ============================================================================
 
+/** Payment mint of native SOL receipts (TransactionReceipt::NATIVE_MINT) */
+export const NATIVE_SOL_MINT = PublicKey.default
+
+/** Domain prefix of the payment message (TransactionReceipt::PAYMENT_MESSAGE_DOMAIN) */
+export const PAYMENT_MESSAGE_DOMAIN = Buffer.from('ghostspeak:x402-receipt:v2')
+
+/**
+ * Canonical payment message the payer signs for a verified receipt:
+ * domain || payer || recipient || paymentMint || amount (u64 LE) || signatureHash (the nonce)
+ */
+export function buildPaymentMessage(
+  payer: PublicKey,
+  recipient: PublicKey,
+  paymentMint: PublicKey,
+  amount: bigint,
+  signatureHash: Uint8Array
+): Buffer {
+  const amountBuffer = Buffer.alloc(8)
+  amountBuffer.writeBigUInt64LE(amount)
+  return Buffer.concat([
+    PAYMENT_MESSAGE_DOMAIN,
+    payer.toBuffer(),
+    recipient.toBuffer(),
+    paymentMint.toBuffer(),
+    amountBuffer,
+    Buffer.from(signatureHash),
+  ])
+}
+
+/** Encode the shared create_transaction_receipt / create_verified_transaction_receipt args */
+function encodeReceiptArgs(
+  discriminator: Buffer,
+  signature: string,
+  signatureHash: Uint8Array,
+  amount: bigint,
+  contentType: ContentType,
+  paymentMint: PublicKey
+): Buffer {
+  const signatureBuffer = Buffer.from(signature)
+  const data = Buffer.alloc(8 + 4 + signatureBuffer.length + 32 + 8 + 1 + 32)
+  let offset = 0
+
+  discriminator.copy(data, offset)
+  offset += 8
+
+  data.writeUInt32LE(signatureBuffer.length, offset)
+  offset += 4
+  signatureBuffer.copy(data, offset)
+  offset += signatureBuffer.length
+
+  Buffer.from(signatureHash).copy(data, offset)
+  offset += 32
+
+  data.writeBigUInt64LE(amount, offset)
+  offset += 8
+
+  data.writeUInt8(ContentTypeIndex[contentType], offset)
+  offset += 1
+
+  paymentMint.toBuffer().copy(data, offset)
+  return data
+}
+
 /**
  * Hash a signature for PDA derivation
  */
@@ -783,10 +2023,15 @@ export async function hashSignature(signature: string): Promise<Uint8Array> {
 
 /**
  * Hash a comment for storage
+ * Pass a random 32-byte salt to make the comment revealable later with
+ * reveal_comment, which checks sha256(comment || salt)
  */
-export async function hashComment(comment: string): Promise<Uint8Array> {
+export async function hashComment(comment: string, salt?: Uint8Array): Promise<Uint8Array> {
   const encoder = new TextEncoder()
-  const data = encoder.encode(comment)
+  const commentBytes = encoder.encode(comment)
+  const data = new Uint8Array(commentBytes.length + (salt?.length ?? 0))
+  data.set(commentBytes)
+  if (salt) data.set(salt, commentBytes.length)
   const hashBuffer = await crypto.subtle.digest('SHA-256', data.buffer as ArrayBuffer)
   return new Uint8Array(hashBuffer)
 }
@@ -802,3 +2047,11 @@ export function calculateVoteWeight(amountLamports: bigint): number {
   const bonus = Math.min(100, Math.floor(solAmount * 100))
   return 100 + bonus
 }
+
+/**
+ * Whether an endorsement still counts: active and not past expiresAt
+ * (mirrors AgentEndorsement::is_current on-chain)
+ */
+export function isEndorsementCurrent(endorsement: AgentEndorsement, nowSeconds: bigint): boolean {
+  return endorsement.isActive && nowSeconds < endorsement.expiresAt
+}
@@ -149,10 +149,10 @@ async function testPDADerivations() {
     const sigHash = await hashSignature(testSignature);
     const txReceipt = Keypair.generate().publicKey;
 
-    const [votePDA] = getPeerVotePDA(txReceipt);
+    const [votePDA] = getPeerVotePDA(txReceipt, testAgent);
     addResult('PDA', 'Vote Registry Peer Vote PDA', votePDA instanceof PublicKey ? 'PASS' : 'FAIL', `Address: ${votePDA.toBase58()}`);
 
-    const [ratingPDA] = getContentRatingPDA(testSignature);
+    const [ratingPDA] = getContentRatingPDA(txReceipt);
     addResult('PDA', 'Vote Registry Content Rating PDA', ratingPDA instanceof PublicKey ? 'PASS' : 'FAIL', `Address: ${ratingPDA.toBase58()}`);
 
     const [endorsePDA] = getEndorsementPDA(testAgent, Keypair.generate().publicKey);